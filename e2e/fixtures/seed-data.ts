@@ -286,14 +286,14 @@ const sampleHunk: DiffHunk = {
   newStart: 1,
   newCount: 8,
   lines: [
-    { type: 'context', content: 'import React from "react";', oldLineNumber: 1, newLineNumber: 1 },
-    { type: 'context', content: '', oldLineNumber: 2, newLineNumber: 2 },
-    { type: 'remove', content: 'function App() {', oldLineNumber: 3, newLineNumber: null },
-    { type: 'add', content: 'import { ThemeProvider } from "./ThemeProvider";', oldLineNumber: null, newLineNumber: 3 },
-    { type: 'add', content: '', oldLineNumber: null, newLineNumber: 4 },
-    { type: 'add', content: 'function App() {', oldLineNumber: null, newLineNumber: 5 },
-    { type: 'context', content: '  return (', oldLineNumber: 4, newLineNumber: 6 },
-    { type: 'context', content: '    <div className="app">', oldLineNumber: 5, newLineNumber: 7 },
+    { type: 'context', content: 'import React from "react";', oldLineNumber: 1, newLineNumber: 1, highlights: [], wordChanges: [] },
+    { type: 'context', content: '', oldLineNumber: 2, newLineNumber: 2, highlights: [], wordChanges: [] },
+    { type: 'remove', content: 'function App() {', oldLineNumber: 3, newLineNumber: null, highlights: [], wordChanges: [] },
+    { type: 'add', content: 'import { ThemeProvider } from "./ThemeProvider";', oldLineNumber: null, newLineNumber: 3, highlights: [], wordChanges: [] },
+    { type: 'add', content: '', oldLineNumber: null, newLineNumber: 4, highlights: [], wordChanges: [] },
+    { type: 'add', content: 'function App() {', oldLineNumber: null, newLineNumber: 5, highlights: [], wordChanges: [] },
+    { type: 'context', content: '  return (', oldLineNumber: 4, newLineNumber: 6, highlights: [], wordChanges: [] },
+    { type: 'context', content: '    <div className="app">', oldLineNumber: 5, newLineNumber: 7, highlights: [], wordChanges: [] },
   ],
 };
 
@@ -304,6 +304,7 @@ export const diffHunksMap: Record<string, DiffHunksResponse> = {
     startIndex: 0,
     totalHunks: 1,
     hasMore: false,
+    whitespaceIgnored: false,
   },
 };
 
@@ -717,6 +718,10 @@ export const companionSettings: CompanionServerSettings = {
   port: 8080,
   pin: '1234',
   authorizedDevices: [],
+  bindMode: 'lan',
+  socketPath: null,
+  basePath: '',
+  publicUrl: null,
 };
 
 // ============================================================================
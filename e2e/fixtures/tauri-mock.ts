@@ -67,6 +67,25 @@ export async function mockTauriIPC(page: Page) {
     }
     const autoRunClaims: MockAutoRunClaim[] = [];
 
+    // -- Pending reviews (per-page) --
+    interface MockDraftComment {
+      id: number;
+      body: string;
+      filePath: string | null;
+      oldLine: number | null;
+      newLine: number | null;
+      createdAt: number;
+    }
+    const pendingReviews = new Map<number, { id: number; mrId: number; startedAt: number; comments: MockDraftComment[] }>();
+    const ensureReview = (mrId: number) => {
+      let review = pendingReviews.get(mrId);
+      if (!review) {
+        review = { id: pendingReviews.size + 1, mrId, startedAt: Math.floor(Date.now() / 1000), comments: [] };
+        pendingReviews.set(mrId, review);
+      }
+      return review;
+    };
+
     // Command handlers — return data matching the Rust backend shape
     const handlers: Record<string, (args: Record<string, unknown>) => unknown> = {
       // -- Instances --
@@ -82,6 +101,11 @@ export async function mockTauriIPC(page: Page) {
         scopes: ['api', 'read_user'],
         active: true,
       }),
+      get_instance_quota: (args) => ({
+        instanceId: args.instanceId as number,
+        rateLimit: { limit: 2000, remaining: 1990, resetAt: null, observedAt: 0 },
+        namespaces: [],
+      }),
       update_instance_token: () => 'testuser',
 
       // -- Merge Requests --
@@ -110,6 +134,17 @@ export async function mockTauriIPC(page: Page) {
       },
       merge_mr: () => undefined,
       check_merge_status: () => 'mergeable',
+      get_merge_blockers: (args) => ({
+        mrId: args.mrId as number,
+        blockers: [],
+        draft: false,
+        hasConflicts: false,
+        pipelineStatus: 'success',
+        unresolvedDiscussionIds: [],
+        approvals: { required: 1, given: 1, approved: true, rules: [] },
+        detailedMergeStatus: 'mergeable',
+        refreshed: Boolean(args.refresh),
+      }),
       rebase_mr: () => undefined,
 
       // -- Diff --
@@ -119,6 +154,7 @@ export async function mockTauriIPC(page: Page) {
         oldContent: 'old content',
         newContent: 'new content',
         diffHunks: [],
+        whitespaceIgnored: false,
       }),
       get_diff_file_metadata: () => ({
         filePath: 'src/App.tsx',
@@ -136,6 +172,7 @@ export async function mockTauriIPC(page: Page) {
           startIndex: 0,
           totalHunks: 0,
           hasMore: false,
+          whitespaceIgnored: false,
         };
       },
       get_diff_refs: (args) => data.diffRefsMap[args.mrId as number] || {
@@ -143,6 +180,48 @@ export async function mockTauriIPC(page: Page) {
         headSha: 'def',
         startSha: 'abc',
       },
+      get_mr_commits: () => [
+        {
+          sha: 'abc1234def',
+          shortSha: 'abc1234',
+          title: 'Add component',
+          message: 'Add component\n',
+          authorName: 'Alice',
+          authorEmail: 'alice@example.com',
+          authoredAt: 1700000000,
+          webUrl: '',
+        },
+      ],
+      get_commit_diff: (args) => ({
+        commit: {
+          sha: args.sha as string,
+          shortSha: (args.sha as string).slice(0, 7),
+          title: 'Add component',
+          message: 'Add component\n',
+          authorName: 'Alice',
+          authorEmail: 'alice@example.com',
+          authoredAt: 1700000000,
+          webUrl: '',
+        },
+        files: [
+          {
+            oldPath: 'src/App.tsx',
+            newPath: 'src/App.tsx',
+            changeType: 'modified',
+            additions: 1,
+            deletions: 0,
+            diff: '@@ -1 +1,2 @@\n import React from "react";\n+import { useState } from "react";\n',
+          },
+        ],
+      }),
+      mark_mr_reviewed: () => 'abc1234def',
+      get_file_review_states: () => [],
+      mark_file_viewed: () => undefined,
+      get_changes_since_review: () => ({
+        reviewedSha: 'abc1234def',
+        headSha: 'abc1234def',
+        files: [],
+      }),
       get_file_content: () => '// file content mock',
       get_file_content_base64: () => '',
       get_cached_file_pair: (args) => {
@@ -204,21 +283,25 @@ export async function mockTauriIPC(page: Page) {
         return allComments.filter((c: { filePath: string | null }) => c.filePath === args.filePath);
       },
       add_comment: (_args) => ({
-        id: Date.now(),
-        mrId: 101,
-        discussionId: `disc-${Date.now()}`,
-        parentId: null,
-        authorUsername: 'testuser',
-        body: 'mock comment',
-        filePath: null,
-        oldLine: null,
-        newLine: null,
-        resolved: false,
-        system: false,
-        createdAt: Math.floor(Date.now() / 1000),
-        updatedAt: Math.floor(Date.now() / 1000),
-        isLocal: true,
-        syncStatus: 'pending',
+        comment: {
+          id: Date.now(),
+          mrId: 101,
+          discussionId: `disc-${Date.now()}`,
+          parentId: null,
+          authorUsername: 'testuser',
+          body: 'mock comment',
+          filePath: null,
+          oldLine: null,
+          newLine: null,
+          resolved: false,
+          system: false,
+          createdAt: Math.floor(Date.now() / 1000),
+          updatedAt: Math.floor(Date.now() / 1000),
+          isLocal: true,
+          syncStatus: 'pending',
+        },
+        unmet: [],
+        blockers: null,
       }),
       reply_to_comment: () => ({
         id: Date.now(),
@@ -238,6 +321,85 @@ export async function mockTauriIPC(page: Page) {
         syncStatus: 'pending',
       }),
       resolve_discussion: () => undefined,
+      apply_suggestion: (args) => ({
+        id: args.suggestionId,
+        commentId: 5001,
+        fromLine: 1,
+        toLine: 1,
+        toContent: '',
+        appliable: false,
+        applied: true,
+      }),
+      submit_review_bundle: (args) => {
+        const input = args.input as { mrId: number; comments?: { body: string }[] };
+        return {
+          submitted: true,
+          batchId: 'batch-mock',
+          comments: (input.comments ?? []).map((c, i) => ({
+            id: -(Date.now() + i),
+            mrId: input.mrId,
+            discussionId: null,
+            parentId: null,
+            authorUsername: 'testuser',
+            body: c.body,
+            filePath: null,
+            oldLine: null,
+            newLine: null,
+            resolved: false,
+            system: false,
+            createdAt: Math.floor(Date.now() / 1000),
+            updatedAt: Math.floor(Date.now() / 1000),
+            isLocal: true,
+            syncStatus: 'pending',
+          })),
+          unmet: [],
+        };
+      },
+      get_review_bundle_status: (args) => ({
+        batchId: args.batchId,
+        mrId: 101,
+        state: 'synced',
+        total: 2,
+        synced: 2,
+        steps: [],
+      }),
+      get_file_assignments: () => [],
+      assign_files_to_reviewer: (args) =>
+        (args.paths as string[]).map((filePath) => ({
+          filePath,
+          username: args.username,
+          assignedBy: 'testuser',
+          assignedAt: Math.floor(Date.now() / 1000),
+        })),
+      start_review: (args) => ensureReview(args.mrId as number),
+      get_pending_review: (args) => pendingReviews.get(args.mrId as number) ?? null,
+      add_draft_comment: (args) => {
+        const input = args.input as { mrId: number; body: string; filePath?: string; oldLine?: number; newLine?: number };
+        const draft = {
+          id: Date.now(),
+          body: input.body,
+          filePath: input.filePath ?? null,
+          oldLine: input.oldLine ?? null,
+          newLine: input.newLine ?? null,
+          createdAt: Math.floor(Date.now() / 1000),
+        };
+        ensureReview(input.mrId).comments.push(draft);
+        return draft;
+      },
+      delete_draft_comment: (args) => {
+        const review = pendingReviews.get(args.mrId as number);
+        if (review) review.comments = review.comments.filter((c) => c.id !== args.draftId);
+        return undefined;
+      },
+      discard_review: (args) => {
+        pendingReviews.delete(args.mrId as number);
+        return undefined;
+      },
+      submit_review: (args) => {
+        const input = args.input as { mrId: number };
+        pendingReviews.delete(input.mrId);
+        return { submitted: true, batchId: 'batch-mock', comments: [], unmet: [] };
+      },
       delete_comment: (args) => {
         // Remove the comment from the in-memory data
         const input = args.input as { mrId: number; commentId: number };
@@ -255,19 +417,60 @@ export async function mockTauriIPC(page: Page) {
       unapprove_mr: () => undefined,
       get_approval_status: () => ({ status: 'pending', count: 1, required: 2 }),
       get_mr_reviewers: (args) => data.reviewers[args.mrId as number] || [],
+      get_mr_participants: () => [],
+      get_project_labels: () => [],
+      get_project_milestones: () => [],
+      get_mr_language_breakdown: (args) => ({ mrId: args.mrId, languages: [], totalLines: 0 }),
+      get_review_sla_status: () => [],
 
       // -- Sync --
       trigger_sync: () => undefined,
       get_sync_status: () => data.syncStatus,
-      retry_failed_actions: () => undefined,
+      retry_failed_actions: () => ({ retried_count: 0, success_count: 0, failed_count: 0 }),
+      list_failed_actions: () => [],
       discard_failed_action: () => undefined,
-      get_sync_config: () => ({ intervalMinutes: 5 }),
-      update_sync_config: () => undefined,
+      get_background_jobs: () => [],
+      report_user_activity: () => undefined,
+      get_sync_config: (args) => ({
+        instanceId: args.instanceId,
+        syncAuthored: true,
+        syncReviewing: true,
+        syncAssigned: true,
+        intervalSecs: null,
+        maxMrsPerSync: null,
+      }),
+      update_sync_config: (args) => ({
+        ...(args.config as Record<string, unknown>),
+        instanceId: args.instanceId,
+      }),
+      run_sync_selftest: (args) => ({
+        instanceId: args.instanceId,
+        instanceUrl: 'https://gitlab.example.com',
+        passed: true,
+        steps: ['validate_token', 'list_mrs', 'fetch_diff', 'queue'].map((name) => ({
+          name,
+          status: 'passed',
+          durationMs: 12,
+          detail: 'OK',
+          errorKind: null,
+          httpStatus: null,
+          endpoint: null,
+        })),
+        startedAt: Math.floor(Date.now() / 1000),
+        totalMs: 48,
+      }),
       get_sync_settings: () => ({ syncAuthored: true, syncReviewing: true }),
       update_sync_settings: () => undefined,
 
       // -- Settings --
       get_settings: () => data.settings,
+      register_link_handler: (args) => ({
+        enabled: true,
+        hosts: args.hosts,
+        fallbackBrowser: args.fallbackBrowser,
+      }),
+      unregister_link_handler: () => undefined,
+      open_link: () => null,
       update_settings: () => data.settings,
       get_collapse_patterns: () => data.settings.collapsePatterns,
       update_collapse_patterns: () => undefined,
@@ -276,6 +479,21 @@ export async function mockTauriIPC(page: Page) {
       get_gitattributes: () => [],
       refresh_gitattributes: () => [],
 
+      // -- Raw API passthrough --
+      gitlab_api_request: () => null,
+      update_allow_api_writes: () => undefined,
+
+      // -- Markdown --
+      // Escaped paragraphs: enough for assertions on comment and description text.
+      render_gfm: (args) =>
+        String(args.text ?? '')
+          .split(/\n{2,}/)
+          .map((p) => `<p>${p.replace(/&/g, '&amp;').replace(/</g, '&lt;').replace(/>/g, '&gt;')}</p>`)
+          .join('\n'),
+
+      // -- Project README --
+      get_project_readme: () => ({ refName: 'main', filePath: null, html: '', fetchedAt: 0 }),
+
       // -- Diagnostics --
       get_memory_stats: () => ({
         processMemoryBytes: 100_000_000,
@@ -292,6 +510,11 @@ export async function mockTauriIPC(page: Page) {
         dbSizeBytes: 10_485_760,
         dbSizeMb: 10,
       }),
+      get_instance_mode: () => 'primary',
+      list_automation_rules: () => [],
+      list_automation_runs: () => [],
+      list_badge_rules: () => [],
+      update_badge_rules: () => [],
       get_diagnostics_report: () => ({
         memory: { processMemoryBytes: 100_000_000, processMemoryMb: 100 },
         cache: { mrCount: 4, dbSizeMb: 10 },
@@ -305,14 +528,27 @@ export async function mockTauriIPC(page: Page) {
       visit_pipeline_project: () => undefined,
       toggle_pin_pipeline_project: () => undefined,
       remove_pipeline_project: () => undefined,
+      list_muted_projects: () => [],
+      mute_project: () => undefined,
+      unmute_project: () => undefined,
+      get_pending_manual_jobs: () => [],
       search_projects: () => [],
       get_pipeline_statuses: () => data.pipelineStatuses,
       get_project_pipelines: () => data.pipelineStatuses,
       get_mr_pipelines: () => data.pipelineStatuses,
+      compare_mrs: (args) => ({
+        mrA: { id: args.mrIdA, iid: 0, title: '', sourceBranch: '', targetBranch: 'main', baseSha: 'abc' },
+        mrB: { id: args.mrIdB, iid: 0, title: '', sourceBranch: '', targetBranch: 'main', baseSha: 'abc' },
+        sameBase: true,
+        overlappingFiles: [],
+      }),
       // Pipeline 3002 is the downstream pipeline triggered by the Docs bridge job.
       get_pipeline_jobs: (args) =>
         args.pipelineId === 3002 ? data.downstreamPipelineJobs : data.pipelineJobs,
-      get_job_trace: () => 'Job log output mock\nLine 2\nLine 3',
+      get_job_trace: () => {
+        const content = 'Job log output mock\nLine 2\nLine 3';
+        return { content, offset: 0, totalBytes: content.length };
+      },
       play_pipeline_job: () => data.pipelineJobs[0],
       retry_pipeline_job: () => data.pipelineJobs[0],
       cancel_pipeline_job: () => data.pipelineJobs[0],
@@ -351,6 +587,7 @@ export async function mockTauriIPC(page: Page) {
       get_companion_settings: () => data.companionSettings,
       update_companion_settings: () => undefined,
       get_companion_qr_svg: () => '<svg></svg>',
+      get_companion_url: () => 'http://192.168.1.10:8080',
       get_companion_status: () => data.companionStatus,
       regenerate_companion_pin: () => '5678',
       revoke_companion_device: () => undefined,
@@ -30,7 +30,7 @@ async function mockMassiveTrace(page: import('@playwright/test').Page, lineCount
     }).__TAURI_INTERNALS__;
     const originalInvoke = internals.invoke;
     internals.invoke = async (cmd, args) => {
-      if (cmd === 'get_job_trace') return trace;
+      if (cmd === 'get_job_trace') return { content: trace, offset: 0, totalBytes: trace.length };
       return originalInvoke(cmd, args);
     };
   }, lineCount);
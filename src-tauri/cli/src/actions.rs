@@ -1,6 +1,7 @@
 //! Action dispatch on the detail screen.
 //!
-//! Review tab: `a` approve / unapprove (toggles on the row's current state).
+//! Review tab: `a` approve / unapprove (toggles on the row's current state),
+//!             `A` approve despite review blockers (confirmed).
 //! Mine tab:   `R` rebase, `M` merge (confirmed), `U` undraft,
 //!             `A` toggle the auto-merge claim.
 //! All actions run as background tasks; results arrive via AppEvent::ActionDone.
@@ -29,6 +30,20 @@ pub fn handle_action_key(app: &mut App, code: KeyCode) {
                 approve_optimistic(app, mr_id);
             }
         }
+        (Tab::Review, KeyCode::Char('A')) => {
+            // Review blockers hold a plain `a` back; the approval gate still
+            // applies here.
+            let prompt = app
+                .detail
+                .as_ref()
+                .map(|d| format!("Approve !{} despite review blockers?", d.row.iid))
+                .unwrap_or_else(|| "Approve despite review blockers?".into());
+            app.confirm = Some(Confirm {
+                verb: "approve anyway".into(),
+                mr_id,
+                prompt,
+            });
+        }
         (Tab::Mine, KeyCode::Char('R')) => dispatch(app, "rebase", mr_id),
         (Tab::Mine, KeyCode::Char('U')) => dispatch(app, "undraft", mr_id),
         (Tab::Mine, KeyCode::Char('A')) => {
@@ -90,22 +105,40 @@ pub fn dispatch(app: &mut App, verb: &str, mr_id: i64) {
 async fn run(pool: &Arc<DbPool>, verb: &str, mr_id: i64) -> Result<String, String> {
     let pool = pool.as_ref();
     match verb {
-        "approve" => mr_actions::approve(pool, mr_id).await.map(|_| "approved".to_string()).map_err(|e| e.to_string()),
-        "unapprove" => mr_actions::unapprove(pool, mr_id).await.map(|_| "unapproved".to_string()).map_err(|e| e.to_string()),
-        "rebase" => mr_actions::rebase(pool, mr_id).await.map(|_| "rebase requested".to_string()).map_err(|e| e.to_string()),
-        "merge" => mr_actions::merge(pool, mr_id).await.map(|_| "merged".to_string()).map_err(|e| e.to_string()),
-        "undraft" => mr_actions::undraft(pool, mr_id).await.map(|t| format!("ready: {t}")).map_err(|e| e.to_string()),
+        "approve" => mr_actions::approve(pool, mr_id, false)
+            .await
+            .map(|_| "approved".to_string())
+            .map_err(|e| e.to_string()),
+        "approve anyway" => mr_actions::approve(pool, mr_id, true)
+            .await
+            .map(|_| "approved".to_string())
+            .map_err(|e| e.to_string()),
+        "unapprove" => mr_actions::unapprove(pool, mr_id)
+            .await
+            .map(|_| "unapproved".to_string())
+            .map_err(|e| e.to_string()),
+        "rebase" => mr_actions::rebase(pool, mr_id)
+            .await
+            .map(|_| "rebase requested".to_string())
+            .map_err(|e| e.to_string()),
+        "merge" => mr_actions::merge(pool, mr_id)
+            .await
+            .map(|_| "merged".to_string())
+            .map_err(|e| e.to_string()),
+        "undraft" => mr_actions::undraft(pool, mr_id)
+            .await
+            .map(|t| format!("ready: {t}"))
+            .map_err(|e| e.to_string()),
         "auto-merge" => {
             let now = chrono::Utc::now().timestamp();
             auto_merge::upsert_claim(pool, mr_id, now).await
                 .map(|_| "auto-merge claimed (desktop will process)".to_string())
                 .map_err(|e| e.to_string())
         }
-        "cancel auto-merge" => {
-            auto_merge::delete_claim(pool, mr_id).await
-                .map(|_| "auto-merge claim removed".to_string())
-                .map_err(|e| e.to_string())
-        }
+        "cancel auto-merge" => auto_merge::delete_claim(pool, mr_id)
+            .await
+            .map(|_| "auto-merge claim removed".to_string())
+            .map_err(|e| e.to_string()),
         other => Err(format!("unknown action {other}")),
     }
 }
@@ -90,9 +90,13 @@ const DETAIL_COMMON: &[Section] = &[
     ]),
 ];
 
-const REVIEW_ACTIONS: Section = ("Review actions", &[
-    ("a", "approve / unapprove"),
-]);
+const REVIEW_ACTIONS: Section = (
+    "Review actions",
+    &[
+        ("a", "approve / unapprove"),
+        ("A", "approve despite review blockers (confirm with y)"),
+    ],
+);
 
 const MINE_ACTIONS: Section = ("Mine actions", &[
     ("R", "rebase"),
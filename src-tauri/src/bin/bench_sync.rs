@@ -10,6 +10,7 @@ use std::sync::Arc;
 use ultra_gitlab_lib::db;
 use ultra_gitlab_lib::services::sync_engine::{SyncConfig, SyncEngine};
 use ultra_gitlab_lib::services::sync_events::NoopEmitter;
+use ultra_gitlab_lib::services::{instance_headers, instance_tls};
 
 #[tokio::main]
 async fn main() {
@@ -80,6 +81,13 @@ async fn main() {
         .await
         .expect("Failed to insert instance");
     }
+    // Clients pick up custom instance headers (SSO proxies) from the registry
+    instance_headers::load_all(&app_pool)
+        .await
+        .expect("Failed to load custom instance headers");
+    instance_tls::load_all(&app_pool)
+        .await
+        .expect("Failed to load instance TLS settings");
     eprintln!();
 
     // Create engine with no-op emitter
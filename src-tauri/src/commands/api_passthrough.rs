@@ -0,0 +1,33 @@
+//! Raw GitLab API passthrough command.
+//!
+//! Lets power users run an API call with the instance's stored credentials
+//! from the app, e.g. to debug sync or try an endpoint the UI doesn't cover.
+//! Deliberately not routed through the companion server.
+
+use crate::commands::settings::load_settings;
+use crate::core::api_passthrough;
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use tauri::{AppHandle, State};
+
+/// Execute an arbitrary GitLab REST API call and return its JSON response.
+///
+/// Only `GET` is allowed unless "Allow API writes" is enabled in settings.
+///
+/// # Arguments
+/// * `instance_id` - The GitLab instance ID whose credentials are used
+/// * `method` - HTTP method (`GET`, `POST`, `PUT`, `PATCH` or `DELETE`)
+/// * `path` - Endpoint relative to `/api/v4`, optionally with a query string
+/// * `body` - Optional JSON request body (not allowed for `GET`)
+#[tauri::command]
+pub async fn gitlab_api_request(
+    app: AppHandle,
+    pool: State<'_, DbPool>,
+    instance_id: i64,
+    method: String,
+    path: String,
+    body: Option<serde_json::Value>,
+) -> Result<serde_json::Value, AppError> {
+    let allow_writes = load_settings(&app).await?.allow_api_writes;
+    api_passthrough::request(&pool, instance_id, &method, &path, body, allow_writes).await
+}
@@ -3,11 +3,16 @@
 //! These commands provide optimistic updates for MR approval status
 //! and queue actions for synchronization to GitLab.
 
+use crate::core::approval_gate::{self, ApprovalGate, ChecklistItem};
+use crate::core::mr_actions::{self, ApproveResult};
+use crate::core::review_blockers::{self, ReviewBlockers};
+use crate::core::token_scopes;
+use crate::db::approval_gates;
 use crate::db::pool::DbPool;
 use crate::error::AppError;
 use crate::models::sync_action::ActionType;
 use crate::services::sync_engine::SyncHandle;
-use crate::services::sync_queue::{self, ApprovalPayload, EnqueueInput};
+use crate::services::sync_queue::{self, EnqueueInput};
 use sqlx::Row;
 use tauri::State;
 
@@ -24,46 +29,37 @@ async fn get_mr_ids(pool: &DbPool, mr_id: i64) -> Result<(i64, i64), AppError> {
 
 /// Approve a merge request.
 ///
-/// The approval is applied optimistically to the local database
-/// and queued for synchronization to GitLab.
+/// If the project has an approval gate enabled, it is checked first against
+/// the files marked viewed; when any condition is unmet nothing is changed or
+/// queued and the unmet conditions are returned. Unless `confirm` is set, the
+/// MR's review blockers (open threads, a failing pipeline, conflicts) hold
+/// the approval back the same way. Otherwise the approval is applied
+/// optimistically to the local database and queued for synchronization to
+/// GitLab.
 ///
 /// # Arguments
 /// * `mr_id` - Merge request ID (local database ID)
+/// * `confirm` - Approve even if there are review blockers
 ///
 /// # Returns
-/// Success or error
+/// Whether the MR was approved, plus any unmet gate conditions or blockers
 #[tauri::command]
 pub async fn approve_mr(
     pool: State<'_, DbPool>,
     sync_handle: State<'_, SyncHandle>,
     mr_id: i64,
-) -> Result<(), AppError> {
-    let (project_id, mr_iid) = get_mr_ids(pool.inner(), mr_id).await?;
-
-    // Update approval status optimistically
-    crate::core::mr_actions::apply_local_approval(pool.inner(), mr_id, true).await?;
-
-    // Build payload for sync queue
-    let payload = serde_json::to_string(&ApprovalPayload { project_id, mr_iid })?;
-
-    // Queue for sync
-    sync_queue::enqueue_action(
-        pool.inner(),
-        EnqueueInput {
-            mr_id,
-            action_type: ActionType::Approve,
-            payload,
-            local_reference_id: None,
-        },
-    )
-    .await?;
+    confirm: Option<bool>,
+) -> Result<ApproveResult, AppError> {
+    let result = mr_actions::queue_approval(pool.inner(), mr_id, confirm.unwrap_or(false)).await?;
 
     // Fire-and-forget: flush approval actions immediately
-    if let Err(e) = sync_handle.flush_approvals().await {
-        eprintln!("[approval] Failed to send flush signal: {}", e);
+    if result.approved {
+        if let Err(e) = sync_handle.flush_approvals().await {
+            eprintln!("[approval] Failed to send flush signal: {}", e);
+        }
     }
 
-    Ok(())
+    Ok(result)
 }
 
 /// Unapprove a merge request (remove your approval).
@@ -85,10 +81,11 @@ pub async fn unapprove_mr(
     sync_handle: State<'_, SyncHandle>,
     mr_id: i64,
 ) -> Result<(), AppError> {
+    token_scopes::ensure_can_write(pool.inner(), mr_id).await?;
     let (project_id, mr_iid) = get_mr_ids(pool.inner(), mr_id).await?;
 
     // Update approval status optimistically
-    crate::core::mr_actions::apply_local_approval(pool.inner(), mr_id, false).await?;
+    mr_actions::apply_local_approval(pool.inner(), mr_id, false).await?;
 
     // Build payload for sync queue
     // For unapprove, we'll use a special action type marker in the payload
@@ -152,6 +149,57 @@ pub async fn get_approval_status(
     })
 }
 
+/// Get the approval gate configured for a project (disabled if none).
+#[tauri::command]
+pub async fn get_approval_gate(
+    pool: State<'_, DbPool>,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<ApprovalGate, AppError> {
+    approval_gate::load_gate(pool.inner(), instance_id, project_id).await
+}
+
+/// Create or update a project's approval gate.
+#[tauri::command]
+pub async fn update_approval_gate(
+    pool: State<'_, DbPool>,
+    gate: ApprovalGate,
+) -> Result<(), AppError> {
+    approval_gate::save_gate(pool.inner(), &gate).await
+}
+
+/// Get the reviewer checklist for an MR's project with this MR's ticks.
+#[tauri::command]
+pub async fn get_review_checklist(
+    pool: State<'_, DbPool>,
+    mr_id: i64,
+) -> Result<Vec<ChecklistItem>, AppError> {
+    approval_gate::review_checklist(pool.inner(), mr_id).await
+}
+
+/// Get what stands against approving an MR: unresolved threads, a failing
+/// pipeline and merge conflicts, from cached data.
+#[tauri::command]
+pub async fn get_review_blockers(
+    pool: State<'_, DbPool>,
+    mr_id: i64,
+) -> Result<ReviewBlockers, AppError> {
+    review_blockers::get_review_blockers(pool.inner(), mr_id).await
+}
+
+/// Tick or untick a reviewer checklist item on an MR.
+#[tauri::command]
+pub async fn set_review_checklist_item(
+    pool: State<'_, DbPool>,
+    mr_id: i64,
+    item: String,
+    checked: bool,
+) -> Result<(), AppError> {
+    let now = chrono::Utc::now().timestamp();
+    approval_gates::set_item_checked(pool.inner(), mr_id, &item, checked, now).await?;
+    Ok(())
+}
+
 /// Approval status response.
 #[derive(Debug, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -3,21 +3,36 @@
 //! These commands handle setting up, retrieving, and deleting GitLab instances
 //! with their credentials stored in the local SQLite database.
 
+use crate::core::instance_quota::{self, InstanceQuota};
+use crate::core::token_scopes::{self, ScopeCheck};
 use crate::db::pool::DbPool;
 use crate::error::AppError;
 use crate::models::GitLabInstance;
+use crate::services::config_import::{self, ConfigSource};
 use crate::services::gitlab_client::{GitLabClient, GitLabClientConfig, PersonalAccessTokenInfo};
+use crate::services::instance_headers::{self, CustomHeader};
+use crate::services::instance_tls::{self, InstanceTls};
+use crate::services::quick_switch;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use tauri::State;
 
 /// Response for setup_gitlab_instance command.
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SetupInstanceResponse {
     /// The created GitLab instance.
     pub instance: GitLabInstance,
 
     /// The authenticated user's username.
     pub username: String,
+
+    /// The token's verified scopes and any warnings about them.
+    pub scope_check: ScopeCheck,
+
+    /// TLS version the connection was negotiated on (e.g. "TLS 1.3"), or
+    /// `None` for plain HTTP.
+    pub tls_protocol: Option<&'static str>,
 }
 
 /// Input for setup_gitlab_instance command.
@@ -35,33 +50,66 @@ pub struct SetupInstanceInput {
 
     /// Optional session cookie for avatar downloads.
     pub session_cookie: Option<String>,
+
+    /// Extra headers required on every request (e.g. an SSO proxy token).
+    /// `None` keeps the headers already configured for this URL.
+    pub custom_headers: Option<Vec<CustomHeader>>,
+
+    /// Minimum TLS version and client certificate. `None` keeps the settings
+    /// already configured for this URL.
+    pub tls: Option<InstanceTls>,
 }
 
 /// Set up a new GitLab instance.
 ///
 /// This command:
-/// 1. Validates the token by calling GitLab API
-/// 2. Creates the instance record in the database with credentials
+/// 1. Validates the token (and any custom headers and TLS settings) by
+///    calling GitLab API
+/// 2. Checks the token's scopes and that it can list merge requests
+/// 3. Creates the instance record in the database with credentials and the
+///    verified scopes
+/// 4. Reports the TLS version the connection was negotiated on
 ///
 /// # Errors
 /// - Authentication error if token is invalid
+/// - Invalid input error if the token lacks the read_api/api scope
 /// - Database error if instance already exists or insert fails
 #[tauri::command]
 pub async fn setup_gitlab_instance(
     pool: State<'_, DbPool>,
     input: SetupInstanceInput,
+) -> Result<SetupInstanceResponse, AppError> {
+    save_instance(pool.inner(), input).await
+}
+
+async fn save_instance(
+    pool: &DbPool,
+    input: SetupInstanceInput,
 ) -> Result<SetupInstanceResponse, AppError> {
     // Normalize the URL
     let url = GitLabInstance::normalize_url(&input.url);
 
-    // Validate the token by fetching user info
-    let client = GitLabClient::new(GitLabClientConfig {
-        base_url: url.clone(),
-        token: input.token.clone(),
-        timeout_secs: 30,
-    })?;
+    let custom_headers = input
+        .custom_headers
+        .unwrap_or_else(|| instance_headers::headers_for(&url));
+    instance_headers::validate(&custom_headers)?;
+    let tls = input.tls.unwrap_or_else(|| instance_tls::tls_for(&url));
+    instance_tls::validate(&tls)?;
+
+    // Validate the token by fetching user info, through the proxy headers
+    // and with the instance's TLS settings
+    let client = GitLabClient::with_connection_settings(
+        GitLabClientConfig {
+            base_url: url.clone(),
+            token: input.token.clone(),
+            timeout_secs: 30,
+        },
+        &custom_headers,
+        &tls,
+    )?;
 
     let user = client.validate_token().await?;
+    let scope_check = token_scopes::verify(&client).await?;
 
     // Trim empty session cookie to None
     let session_cookie = input.session_cookie.filter(|s| !s.trim().is_empty());
@@ -81,15 +129,132 @@ pub async fn setup_gitlab_instance(
     .bind(now)
     .bind(&user.username)
     .bind(&session_cookie)
-    .fetch_one(pool.inner())
+    .fetch_one(pool)
     .await?;
 
+    instance_headers::save(pool, result.id, &custom_headers).await?;
+    instance_tls::save(pool, result.id, &tls).await?;
+    token_scopes::store(pool, result.id, &scope_check).await?;
+    let tls_protocol = instance_tls::negotiated_protocol(&url, &tls).await;
+
     Ok(SetupInstanceResponse {
         instance: result,
         username: user.username,
+        scope_check,
+        tls_protocol,
     })
 }
 
+/// Outcome for one instance found by import_instances_from_config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConfigImportStatus {
+    /// Found and awaiting confirmation.
+    Pending,
+    /// The file has no token for this instance, so it can't be imported.
+    MissingToken,
+    /// Not confirmed by the user.
+    Skipped,
+    /// Token validated and instance saved.
+    Imported,
+    /// Confirmed but validation or saving failed; see `error`.
+    Failed,
+}
+
+/// One instance found in a glab or python-gitlab config file.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigImportEntry {
+    pub url: String,
+    pub name: String,
+    pub source: ConfigSource,
+    /// Whether an instance with this URL is already set up here.
+    pub already_configured: bool,
+    pub status: ConfigImportStatus,
+    /// The authenticated username, once imported.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Import GitLab instances from a `glab` or python-gitlab config file.
+///
+/// Called twice: first without `confirmed_urls` to list what the file
+/// contains (nothing is saved), then with the URLs the user ticked. Each
+/// confirmed instance goes through the same validation as
+/// `setup_gitlab_instance`; the rest are reported as skipped. Tokens are
+/// never returned to the frontend.
+///
+/// # Arguments
+/// * `path` - Config file path; a leading `~/` is expanded
+/// * `confirmed_urls` - URLs (as returned by the first call) to import
+#[tauri::command]
+pub async fn import_instances_from_config(
+    pool: State<'_, DbPool>,
+    path: String,
+    confirmed_urls: Option<Vec<String>>,
+) -> Result<Vec<ConfigImportEntry>, AppError> {
+    let path = crate::core::expand_home(&path)?;
+    let content = tokio::fs::read_to_string(&path).await.map_err(|e| {
+        AppError::invalid_input_field(format!("Cannot read {}: {}", path.display(), e), "path")
+    })?;
+    let (source, configured) = config_import::parse_config(&content).ok_or_else(|| {
+        AppError::invalid_input_field("Not a glab or python-gitlab config file", "path")
+    })?;
+
+    let existing: Vec<String> = sqlx::query_scalar("SELECT url FROM gitlab_instances")
+        .fetch_all(pool.inner())
+        .await?;
+
+    let mut entries = Vec::with_capacity(configured.len());
+    for instance in configured {
+        let url = GitLabInstance::normalize_url(&instance.url);
+        let mut entry = ConfigImportEntry {
+            already_configured: existing.contains(&url),
+            url,
+            name: instance.name,
+            source,
+            status: ConfigImportStatus::Pending,
+            username: None,
+            error: None,
+        };
+        let Some(token) = instance.token else {
+            entry.status = ConfigImportStatus::MissingToken;
+            entries.push(entry);
+            continue;
+        };
+        match &confirmed_urls {
+            None => {}
+            Some(confirmed) if !confirmed.contains(&entry.url) => {
+                entry.status = ConfigImportStatus::Skipped;
+            }
+            Some(_) => {
+                let input = SetupInstanceInput {
+                    url: entry.url.clone(),
+                    token,
+                    name: Some(entry.name.clone()),
+                    session_cookie: None,
+                    custom_headers: None,
+                    tls: None,
+                };
+                match save_instance(pool.inner(), input).await {
+                    Ok(saved) => {
+                        entry.status = ConfigImportStatus::Imported;
+                        entry.username = Some(saved.username);
+                    }
+                    Err(e) => {
+                        entry.status = ConfigImportStatus::Failed;
+                        entry.error = Some(e.to_string());
+                    }
+                }
+            }
+        }
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GitLabInstanceWithStatus {
@@ -102,6 +267,19 @@ pub struct GitLabInstanceWithStatus {
     /// Error message if token check failed.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub token_error: Option<String>,
+
+    /// Names of the custom headers sent to this instance (values are not
+    /// exposed).
+    pub custom_header_names: Vec<String>,
+
+    /// Minimum TLS version and client certificate used for this instance.
+    pub tls: InstanceTls,
+
+    /// Scopes verified when the token was saved, if GitLab reported them.
+    pub token_scopes: Option<Vec<String>>,
+
+    /// Whether the token may comment, approve and make other changes.
+    pub can_write: bool,
 }
 
 /// Get all configured GitLab instances.
@@ -116,15 +294,33 @@ pub async fn get_gitlab_instances(
         sqlx::query_as("SELECT id, url, name, token, created_at, authenticated_username, session_cookie, is_default FROM gitlab_instances ORDER BY is_default DESC, created_at DESC")
             .fetch_all(pool.inner())
             .await?;
+    let scopes: HashMap<i64, Option<String>> =
+        sqlx::query_as("SELECT id, token_scopes FROM gitlab_instances")
+            .fetch_all(pool.inner())
+            .await?
+            .into_iter()
+            .collect();
 
     Ok(instances
         .into_iter()
         .map(|instance| {
             let has_token = instance.token.is_some();
+            let stored_scopes = scopes.get(&instance.id).cloned().flatten();
+            let can_write = token_scopes::allows_write(stored_scopes.as_deref());
+            let token_scopes = stored_scopes.and_then(|s| serde_json::from_str(&s).ok());
+            let custom_header_names = instance_headers::headers_for(&instance.url)
+                .into_iter()
+                .map(|h| h.name)
+                .collect();
+            let tls = instance_tls::tls_for(&instance.url);
             GitLabInstanceWithStatus {
                 instance,
                 has_token,
                 token_error: None,
+                custom_header_names,
+                tls,
+                token_scopes,
+                can_write,
             }
         })
         .collect())
@@ -189,16 +385,37 @@ pub async fn get_token_info(
     Ok(info.into())
 }
 
+/// Get an instance's remaining CI minutes, storage usage and API rate-limit
+/// budget, for the namespaces of the projects with synced MRs.
+#[tauri::command]
+pub async fn get_instance_quota(
+    pool: State<'_, DbPool>,
+    instance_id: i64,
+) -> Result<InstanceQuota, AppError> {
+    instance_quota::get_instance_quota(pool.inner(), instance_id).await
+}
+
+/// Response for update_instance_token command.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTokenResponse {
+    /// The authenticated user's username.
+    pub username: String,
+
+    /// The new token's verified scopes and any warnings about them.
+    pub scope_check: ScopeCheck,
+}
+
 /// Update the personal access token for a GitLab instance.
 ///
-/// Validates the new token by calling GitLab's /user endpoint, then updates
-/// the token in the database.
+/// Validates the new token by calling GitLab's /user endpoint and checks its
+/// scopes, then updates the token and scopes in the database.
 #[tauri::command]
 pub async fn update_instance_token(
     pool: State<'_, DbPool>,
     instance_id: i64,
     token: String,
-) -> Result<String, AppError> {
+) -> Result<UpdateTokenResponse, AppError> {
     let instance: GitLabInstance = sqlx::query_as(
         "SELECT id, url, name, token, created_at, authenticated_username, session_cookie, is_default FROM gitlab_instances WHERE id = $1",
     )
@@ -215,6 +432,7 @@ pub async fn update_instance_token(
     })?;
 
     let user = client.validate_token().await?;
+    let scope_check = token_scopes::verify(&client).await?;
 
     // Update the token and authenticated username in the database
     sqlx::query(
@@ -225,8 +443,82 @@ pub async fn update_instance_token(
     .bind(instance_id)
     .execute(pool.inner())
     .await?;
+    token_scopes::store(pool.inner(), instance_id, &scope_check).await?;
+
+    Ok(UpdateTokenResponse {
+        username: user.username,
+        scope_check,
+    })
+}
 
-    Ok(user.username)
+/// Replace the custom headers sent to a GitLab instance.
+///
+/// The headers are validated against GitLab with the stored token before
+/// being saved; an empty list removes them.
+#[tauri::command]
+pub async fn update_instance_headers(
+    pool: State<'_, DbPool>,
+    instance_id: i64,
+    headers: Vec<CustomHeader>,
+) -> Result<(), AppError> {
+    instance_headers::validate(&headers)?;
+
+    let (url, token): (String, Option<String>) =
+        sqlx::query_as("SELECT url, token FROM gitlab_instances WHERE id = $1")
+            .bind(instance_id)
+            .fetch_optional(pool.inner())
+            .await?
+            .ok_or_else(|| AppError::not_found("GitLab instance not found"))?;
+    let token =
+        token.ok_or_else(|| AppError::authentication("No token configured for this instance"))?;
+
+    let client = GitLabClient::with_custom_headers(
+        GitLabClientConfig {
+            base_url: url,
+            token,
+            timeout_secs: 30,
+        },
+        &headers,
+    )?;
+    client.validate_token().await?;
+
+    instance_headers::save(pool.inner(), instance_id, &headers).await
+}
+
+/// Replace the TLS settings for a GitLab instance.
+///
+/// The settings are validated against GitLab with the stored token before
+/// being saved. Returns the TLS version the connection was negotiated on.
+#[tauri::command]
+pub async fn update_instance_tls(
+    pool: State<'_, DbPool>,
+    instance_id: i64,
+    tls: InstanceTls,
+) -> Result<Option<&'static str>, AppError> {
+    instance_tls::validate(&tls)?;
+
+    let (url, token): (String, Option<String>) =
+        sqlx::query_as("SELECT url, token FROM gitlab_instances WHERE id = $1")
+            .bind(instance_id)
+            .fetch_optional(pool.inner())
+            .await?
+            .ok_or_else(|| AppError::not_found("GitLab instance not found"))?;
+    let token =
+        token.ok_or_else(|| AppError::authentication("No token configured for this instance"))?;
+
+    let client = GitLabClient::with_connection_settings(
+        GitLabClientConfig {
+            base_url: url.clone(),
+            token,
+            timeout_secs: 30,
+        },
+        &instance_headers::headers_for(&url),
+        &tls,
+    )?;
+    client.validate_token().await?;
+
+    instance_tls::save(pool.inner(), instance_id, &tls).await?;
+    Ok(instance_tls::negotiated_protocol(&url, &tls).await)
 }
 
 /// Rename a GitLab instance.
@@ -269,10 +561,16 @@ pub async fn delete_gitlab_instance(
     instance_id: i64,
 ) -> Result<(), AppError> {
     // Delete the instance from database (cascades to related records)
-    sqlx::query("DELETE FROM gitlab_instances WHERE id = $1")
-        .bind(instance_id)
-        .execute(pool.inner())
-        .await?;
+    let url: Option<String> =
+        sqlx::query_scalar("DELETE FROM gitlab_instances WHERE id = $1 RETURNING url")
+            .bind(instance_id)
+            .fetch_optional(pool.inner())
+            .await?;
+    if let Some(url) = url {
+        instance_headers::unregister(&url);
+        instance_tls::unregister(&url);
+    }
+    quick_switch::invalidate().await;
 
     Ok(())
 }
@@ -3,7 +3,11 @@
 //! Users mark their own MR for auto-merge from the UI. The actual merging
 //! happens in the background sync engine (`services::auto_merge_processor`).
 //! These commands just read/write the `auto_merge_claims` table.
+//!
+//! "Merge when ready" is a claim with extra local conditions; see
+//! `core::merge_when_ready`.
 
+use crate::core::merge_when_ready::{self, MergeCondition, MergeConditions};
 use crate::db::auto_merge;
 use crate::db::pool::DbPool;
 use crate::error::AppError;
@@ -29,6 +33,10 @@ pub struct AutoMergeClaim {
     pub last_error: Option<String>,
     pub last_attempt_at: Option<i64>,
     pub attempts: i64,
+    /// Conditions of a merge-when-ready claim; null for plain auto-merge.
+    pub conditions: Option<MergeConditions>,
+    /// Conditions that weren't met at the last check.
+    pub unmet_conditions: Vec<MergeCondition>,
 }
 
 impl From<auto_merge::AutoMergeClaimRow> for AutoMergeClaim {
@@ -40,6 +48,12 @@ impl From<auto_merge::AutoMergeClaimRow> for AutoMergeClaim {
             last_error: row.last_error,
             last_attempt_at: row.last_attempt_at,
             attempts: row.attempts,
+            conditions: MergeConditions::from_column(row.conditions.as_deref()),
+            unmet_conditions: row
+                .unmet_conditions
+                .as_deref()
+                .and_then(|s| serde_json::from_str(s).ok())
+                .unwrap_or_default(),
         }
     }
 }
@@ -63,6 +77,32 @@ pub async fn claim_auto_merge(
     Ok(claim.into())
 }
 
+/// Queue an MR to merge once the given conditions hold.
+///
+/// Replaces the conditions of an existing claim. Like `claim_auto_merge`, the
+/// sync engine is kicked so the first check happens right away.
+#[tauri::command]
+pub async fn merge_when_ready(
+    pool: State<'_, DbPool>,
+    sync_handle: State<'_, SyncHandle>,
+    mr_id: i64,
+    conditions: MergeConditions,
+) -> Result<AutoMergeClaim, AppError> {
+    merge_when_ready::queue(pool.inner(), mr_id, &conditions, now()).await?;
+    let claim = auto_merge::get_claim(pool.inner(), mr_id)
+        .await?
+        .ok_or_else(|| AppError::internal("Failed to read back merge-when-ready claim"))?;
+    let _ = sync_handle.process_auto_merge_now().await;
+    Ok(claim.into())
+}
+
+/// Cancel a pending merge-when-ready intent for an MR.
+#[tauri::command]
+pub async fn cancel_merge_when_ready(pool: State<'_, DbPool>, mr_id: i64) -> Result<(), AppError> {
+    auto_merge::delete_claim(pool.inner(), mr_id).await?;
+    Ok(())
+}
+
 /// Force the sync engine to process all auto-merge claims right now.
 #[tauri::command]
 pub async fn process_auto_merge_now(
@@ -0,0 +1,50 @@
+//! Automation commands: manage user-defined rules, test them and read the
+//! run log. Deliberately not exposed through the companion server, since a
+//! rule can run arbitrary shell commands.
+
+use crate::core::automations::{self, AutomationRule, AutomationRuleInput, AutomationRun};
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use chrono::Utc;
+use tauri::State;
+
+/// All automation rules, oldest first.
+#[tauri::command]
+pub async fn list_automation_rules(
+    pool: State<'_, DbPool>,
+) -> Result<Vec<AutomationRule>, AppError> {
+    automations::list_rules(pool.inner()).await
+}
+
+/// Create a rule, or update it when `rule.id` is set.
+#[tauri::command]
+pub async fn save_automation_rule(
+    pool: State<'_, DbPool>,
+    rule: AutomationRuleInput,
+) -> Result<AutomationRule, AppError> {
+    automations::save_rule(pool.inner(), rule, Utc::now().timestamp()).await
+}
+
+/// Delete a rule along with its run log.
+#[tauri::command]
+pub async fn delete_automation_rule(pool: State<'_, DbPool>, rule_id: i64) -> Result<(), AppError> {
+    automations::delete_rule(pool.inner(), rule_id).await
+}
+
+/// Run a rule once against a sample MR and return the logged run.
+#[tauri::command]
+pub async fn test_automation(
+    pool: State<'_, DbPool>,
+    rule_id: i64,
+) -> Result<AutomationRun, AppError> {
+    automations::test(pool.inner(), rule_id).await
+}
+
+/// Recent runs, newest first; all rules when `rule_id` is omitted.
+#[tauri::command]
+pub async fn list_automation_runs(
+    pool: State<'_, DbPool>,
+    rule_id: Option<i64>,
+) -> Result<Vec<AutomationRun>, AppError> {
+    automations::list_runs(pool.inner(), rule_id).await
+}
@@ -3,7 +3,9 @@
 use crate::db::pool::DbPool;
 use crate::error::AppError;
 use crate::models::gitlab_instance::GitLabInstance;
-use crate::services::avatar;
+use crate::services::avatar::{self, AvatarAuth};
+use crate::services::sync_engine::SyncHandle;
+use futures::future::join_all;
 use std::collections::HashMap;
 use tauri::State;
 
@@ -46,21 +48,56 @@ pub async fn update_session_cookie(
     Ok(())
 }
 
-/// Manually refresh all avatars for an instance (ignores TTL).
-/// Returns the number of avatars downloaded.
+/// Manually revalidate all avatars (ignores TTL), for one instance or, when
+/// `instance_id` is omitted, every instance with credentials at once.
+/// Uses the instance token, plus the session cookie when one is set.
+/// Returns the number of avatars whose image changed.
 #[tauri::command]
-pub async fn refresh_avatars(pool: State<'_, DbPool>, instance_id: i64) -> Result<u32, AppError> {
-    let instance: GitLabInstance = sqlx::query_as(
-        "SELECT id, url, name, token, created_at, authenticated_username, session_cookie, is_default FROM gitlab_instances WHERE id = ?",
+pub async fn refresh_avatars(
+    pool: State<'_, DbPool>,
+    sync_handle: State<'_, SyncHandle>,
+    instance_id: Option<i64>,
+) -> Result<u32, AppError> {
+    let instances: Vec<GitLabInstance> = sqlx::query_as(
+        "SELECT id, url, name, token, created_at, authenticated_username, session_cookie, is_default FROM gitlab_instances WHERE ?1 IS NULL OR id = ?1",
     )
     .bind(instance_id)
-    .fetch_optional(pool.inner())
-    .await?
-    .ok_or_else(|| AppError::not_found("GitLab instance not found"))?;
+    .fetch_all(pool.inner())
+    .await?;
+
+    let has_credentials =
+        |instance: &GitLabInstance| instance.token.is_some() || instance.session_cookie.is_some();
+    match (instance_id, instances.first()) {
+        (Some(_), None) => return Err(AppError::not_found("GitLab instance not found")),
+        (Some(_), Some(instance)) if !has_credentials(instance) => {
+            return Err(AppError::invalid_input(
+                "No token or session cookie configured for this instance",
+            ));
+        }
+        _ => {}
+    }
 
-    let cookie = instance
-        .session_cookie
-        .ok_or_else(|| AppError::invalid_input("No session cookie configured for this instance"))?;
+    let allow_gravatar = sync_handle.get_config().await.gravatar_fallback;
+    let refreshes = instances
+        .iter()
+        .filter(|instance| has_credentials(instance))
+        .map(|instance| {
+            let auth = AvatarAuth {
+                token: instance.token.as_deref(),
+                cookie: instance.session_cookie.as_deref(),
+            };
+            avatar::refresh_all_avatars(
+                pool.inner(),
+                instance.id,
+                &instance.url,
+                auth,
+                allow_gravatar,
+            )
+        });
 
-    avatar::refresh_all_avatars(pool.inner(), instance_id, &instance.url, &cookie).await
+    let mut updated = 0;
+    for result in join_all(refreshes).await {
+        updated += result?;
+    }
+    Ok(updated)
 }
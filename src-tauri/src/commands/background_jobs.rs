@@ -0,0 +1,23 @@
+//! Idle-time background job commands.
+//!
+//! The frontend reports user activity so jobs stay out of the way while the
+//! app is in use, and the settings page shows what each job is doing.
+
+use crate::error::AppError;
+use crate::services::background_jobs::{self, BackgroundJobStatus};
+
+/// Get the state of every background job and the result of its last batch.
+#[tauri::command]
+pub async fn get_background_jobs() -> Result<Vec<BackgroundJobStatus>, AppError> {
+    Ok(background_jobs::statuses())
+}
+
+/// Tell the scheduler the user is interacting with the app.
+///
+/// Cancels the running batch, if any; jobs resume once the app has been
+/// idle for a minute.
+#[tauri::command]
+pub async fn report_user_activity() -> Result<(), AppError> {
+    background_jobs::record_activity();
+    Ok(())
+}
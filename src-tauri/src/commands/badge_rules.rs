@@ -0,0 +1,29 @@
+//! Commands for the user's MR list badge rules.
+
+use crate::db::pool::{DbPool, ReadPool};
+use crate::error::AppError;
+use crate::services::badge_scripts::{self, BadgeRule, BadgeRuleInput};
+use tauri::State;
+
+/// List the badge rules in display order, with the last error each one hit
+/// while evaluating.
+#[tauri::command]
+pub async fn list_badge_rules(pool: State<'_, ReadPool>) -> Result<Vec<BadgeRule>, AppError> {
+    badge_scripts::list_rules(pool.inner()).await
+}
+
+/// Replace the badge rules.
+///
+/// Every script must compile, or nothing is saved and the error names the
+/// offending rule (`rules[i].script`). The new rules apply from the next
+/// MR list query.
+///
+/// # Arguments
+/// * `rules` - The complete list of rules, in display order
+#[tauri::command]
+pub async fn update_badge_rules(
+    pool: State<'_, DbPool>,
+    rules: Vec<BadgeRuleInput>,
+) -> Result<Vec<BadgeRule>, AppError> {
+    badge_scripts::update_rules(pool.inner(), rules, chrono::Utc::now().timestamp()).await
+}
@@ -0,0 +1,42 @@
+//! Cache archive commands: export the local cache to a file and warm-start
+//! another machine's cache from it.
+
+use crate::core::cache_archive::{self, CacheArchiveSummary};
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use chrono::Utc;
+use std::path::PathBuf;
+use tauri::State;
+
+fn archive_path(path: &str) -> Result<PathBuf, AppError> {
+    let path = PathBuf::from(path.trim());
+    if path.as_os_str().is_empty() || !path.is_absolute() {
+        return Err(AppError::invalid_input_field(
+            "Archive path must be absolute",
+            "path",
+        ));
+    }
+    Ok(path)
+}
+
+/// Write the cached MRs, diffs and file content of all instances to `path`.
+/// Credentials are not included.
+#[tauri::command]
+pub async fn export_cache_archive(
+    pool: State<'_, DbPool>,
+    path: String,
+) -> Result<CacheArchiveSummary, AppError> {
+    let path = archive_path(&path)?;
+    cache_archive::export(pool.inner(), &path, Utc::now().timestamp()).await
+}
+
+/// Fill the local cache from an archive written by `export_cache_archive`.
+/// Only instances already configured here are imported.
+#[tauri::command]
+pub async fn import_cache_archive(
+    pool: State<'_, DbPool>,
+    path: String,
+) -> Result<CacheArchiveSummary, AppError> {
+    let path = archive_path(&path)?;
+    cache_archive::import(pool.inner(), &path).await
+}
@@ -61,6 +61,7 @@ pub async fn cli_status() -> Result<CliStatus, AppError> {
 /// Download the latest released `ultra` CLI and install it to ~/.local/bin.
 #[tauri::command]
 pub async fn download_and_install_cli() -> Result<CliInstallResult, AppError> {
+    crate::services::offline_mode::ensure_online()?;
     let client = reqwest::Client::builder()
         .user_agent("ultra-gitlab-desktop")
         .timeout(std::time::Duration::from_secs(60))
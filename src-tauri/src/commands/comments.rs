@@ -1,18 +1,42 @@
 //! Comment commands for reading and creating comments.
 //!
 //! These commands handle both cached comments and local (pending sync) comments.
-//! New comments are inserted optimistically into the local database and queued for sync.
-
+//! New comments are inserted optimistically into the local database and queued for sync,
+//! unless the cache already knows GitLab would refuse them (see `core::discussion_access`)
+//! or the body is over GitLab's size limit (see `core::comment_size`).
+
+use crate::core::approval_gate::UnmetCondition;
+use crate::core::author_ping::{self, AuthorPing, PingTemplate};
+use crate::core::comment_context::{self, CommentSnippet};
+use crate::core::comment_history::{self, CommentRange, MyComment};
+use crate::core::comment_order::{self, CommentCursor, CommentSort};
+use crate::core::comment_size::{self, CommentAttachment};
+use crate::core::comments::{self, ExportFormat};
+use crate::core::discussion_access;
+use crate::core::file_assignments;
+use crate::core::mr_actions;
+use crate::core::quick_actions::{self, QuickActionPreview, QuickActionSpec};
+use crate::core::review_blockers::ReviewBlockers;
+use crate::core::review_import;
+use crate::core::suggestions::{self, CommentSuggestion};
+use crate::core::token_scopes;
+use crate::db::file_assignments::FileAssignment;
 use crate::db::pool::DbPool;
+use crate::db::review_drafts::{self, DraftComment, NewDraftComment, PendingReview};
 use crate::error::AppError;
 use crate::models::sync_action::ActionType;
 use crate::models::Comment;
+use crate::services::ipc_compression;
 use crate::services::sync_engine::SyncHandle;
-use crate::services::sync_queue::{self, DeleteCommentPayload, EnqueueInput, ReplyPayload, ResolvePayload};
+use crate::services::sync_queue::{
+    self, ApprovalPayload, BatchStatus, DeleteCommentPayload, EnqueueInput, ReplyPayload,
+    ResolvePayload,
+};
 use serde::{Deserialize, Serialize};
 use sqlx::Row;
 use std::sync::atomic::{AtomicI64, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::ipc::Response;
 use tauri::State;
 
 /// Response for get_comments command.
@@ -37,6 +61,10 @@ pub struct CommentResponse {
     pub is_local: bool,
     /// Sync status: 'synced', 'pending', or 'failed'
     pub sync_status: String,
+    /// Suggestions GitLab found in the note (synced diff notes only).
+    pub suggestions: Vec<CommentSuggestion>,
+    /// Code around the commented line, when asked for and the file is cached.
+    pub snippet: Option<CommentSnippet>,
 }
 
 /// Get the current Unix timestamp.
@@ -48,10 +76,17 @@ fn now() -> i64 {
 }
 
 /// Convert a list of Comments to CommentResponses, resolving sync status for local comments.
+/// With `context`, inline comments get a snippet of that many lines either side.
 async fn to_comment_responses(
     pool: &DbPool,
     comments: Vec<Comment>,
+    context: Option<u32>,
 ) -> Result<Vec<CommentResponse>, AppError> {
+    let mut by_comment = suggestions::for_comments(pool, &comments).await?;
+    let mut snippets = match context {
+        Some(lines) => comment_context::for_comments(pool, &comments, lines).await?,
+        None => Default::default(),
+    };
     let mut responses = Vec::with_capacity(comments.len());
     for comment in comments {
         let sync_status = if comment.is_local {
@@ -78,6 +113,8 @@ async fn to_comment_responses(
             updated_at: comment.updated_at,
             is_local: comment.is_local,
             sync_status,
+            suggestions: by_comment.remove(&comment.id).unwrap_or_default(),
+            snippet: snippets.remove(&comment.id),
         });
     }
     Ok(responses)
@@ -104,34 +141,44 @@ fn generate_local_id() -> i64 {
 
 /// Get all comments for a merge request.
 ///
-/// Returns both cached GitLab comments and local (pending sync) comments.
-/// Comments are ordered by creation time.
+/// Returns both cached GitLab comments and local (pending sync) comments,
+/// ordered by `sort` (oldest first when omitted).
 ///
 /// # Arguments
 /// * `mr_id` - Merge request ID
+/// * `sort` - Comment order, see `CommentSort`
+/// * `context_lines` - Include this many lines of cached code either side of
+///   each inline thread (at most `comment_context::MAX_CONTEXT_LINES`)
 ///
 /// # Returns
-/// Array of comments with sync status
+/// Array of comments with sync status, compressed when large (see
+/// `ipc_compression`).
 #[tauri::command]
 pub async fn get_comments(
     pool: State<'_, DbPool>,
     mr_id: i64,
-) -> Result<Vec<CommentResponse>, AppError> {
-    let comments = sqlx::query_as::<_, Comment>(
-        r#"
-        SELECT id, mr_id, discussion_id, parent_id, author_username, body,
-               file_path, old_line, new_line, line_type, resolved, resolvable,
-               system, created_at, updated_at, cached_at, is_local
-        FROM comments
-        WHERE mr_id = ?
-        ORDER BY created_at ASC
-        "#,
-    )
-    .bind(mr_id)
-    .fetch_all(pool.inner())
-    .await?;
+    sort: Option<CommentSort>,
+    context_lines: Option<u32>,
+) -> Result<Response, AppError> {
+    let comments = comment_order::list(pool.inner(), mr_id, sort.unwrap_or_default()).await?;
+    let responses = to_comment_responses(pool.inner(), comments, context_lines).await?;
+    ipc_compression::respond("get_comments", &responses)
+}
 
-    to_comment_responses(pool.inner(), comments).await
+/// Where the unread comments of an MR start, relative to the last time the
+/// MR was opened. Call before `mark_mr_viewed` to jump to the first unread.
+#[tauri::command]
+pub async fn get_latest_comment_cursor(
+    pool: State<'_, DbPool>,
+    mr_id: i64,
+) -> Result<CommentCursor, AppError> {
+    comment_order::cursor(pool.inner(), mr_id).await
+}
+
+/// Record that the MR was opened now.
+#[tauri::command]
+pub async fn mark_mr_viewed(pool: State<'_, DbPool>, mr_id: i64) -> Result<(), AppError> {
+    comment_order::mark_viewed(pool.inner(), mr_id, now()).await
 }
 
 /// Get sync status for a local comment from the sync queue.
@@ -202,6 +249,29 @@ async fn resolve_context_line_numbers(
     Ok((known_line, known_line))
 }
 
+/// Line numbers to post a comment at. For context lines, both old and new
+/// line numbers are resolved from the diff.
+async fn comment_lines(
+    pool: &DbPool,
+    mr_id: i64,
+    file_path: Option<&str>,
+    old_line: Option<i64>,
+    new_line: Option<i64>,
+    is_context_line: bool,
+) -> Result<(Option<i64>, Option<i64>), AppError> {
+    match file_path {
+        Some(file_path) if is_context_line => {
+            let known_line = new_line.or(old_line).unwrap_or(1);
+            let is_old_side = old_line.is_some() && new_line.is_none();
+            let (old, new) =
+                resolve_context_line_numbers(pool, mr_id, file_path, known_line, is_old_side)
+                    .await?;
+            Ok((Some(old), Some(new)))
+        }
+        _ => Ok((old_line, new_line)),
+    }
+}
+
 /// Look up diff refs (base_sha, head_sha, start_sha) for a merge request.
 async fn get_diff_shas(pool: &DbPool, mr_id: i64) -> Result<(String, String, String), AppError> {
     let row = sqlx::query("SELECT base_sha, head_sha, start_sha FROM diffs WHERE mr_id = ?")
@@ -229,6 +299,36 @@ async fn get_authenticated_username(pool: &DbPool, instance_id: i64) -> Result<S
     Ok(username.unwrap_or_else(|| "You".to_string()))
 }
 
+/// A comment's body with its suggestion, if any, checked against GitLab's
+/// size limit. Suggestions need a line in the new version of a file.
+fn comment_body(
+    body: String,
+    suggestion_content: Option<&str>,
+    on_new_line: bool,
+) -> Result<String, AppError> {
+    let body = match suggestion_content {
+        Some(_) if !on_new_line => {
+            return Err(AppError::invalid_input_field(
+                "Suggestions need a line in the new version of a file",
+                "suggestionContent",
+            ));
+        }
+        Some(content) => suggestions::with_suggestion(&body, content),
+        None => body,
+    };
+    comment_size::check_body(&body)?;
+    Ok(body)
+}
+
+/// Whether `body` carries an `/approve` quick action, which GitLab runs when
+/// the note is posted.
+fn approves(body: &str) -> bool {
+    quick_actions::parse_quick_actions(body)
+        .actions
+        .iter()
+        .any(|action| action.name == "approve")
+}
+
 /// Input for add_comment command.
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -247,64 +347,121 @@ pub struct AddCommentInput {
     /// line number from the stored diff.
     #[serde(default)]
     pub is_context_line: bool,
+    /// Replacement for the commented line, posted as a GitLab suggestion
+    /// after the body. Needs `new_line`.
+    #[serde(default)]
+    pub suggestion_content: Option<String>,
+    /// Post an `/approve` in the body even if there are review blockers.
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+/// Result of add_comment.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddCommentResult {
+    /// The comment as inserted locally. `None` when the approval gate or
+    /// review blockers held back an `/approve` in it; nothing was queued.
+    pub comment: Option<CommentResponse>,
+    /// Gate conditions that still need attention.
+    pub unmet: Vec<UnmetCondition>,
+    /// Set when review blockers held the approval back; post again with
+    /// `confirm` to approve anyway.
+    pub blockers: Option<ReviewBlockers>,
+}
+
+impl AddCommentResult {
+    /// The posted comment, or the hold as an error.
+    fn into_comment(self) -> Result<CommentResponse, AppError> {
+        match (self.comment, self.blockers) {
+            (Some(comment), _) => Ok(comment),
+            (None, Some(blockers)) => Err(AppError::invalid_input(
+                mr_actions::ApprovalHold::Blockers(blockers).to_string(),
+            )),
+            (None, None) => Err(AppError::invalid_input(
+                mr_actions::ApprovalHold::Gate(self.unmet).to_string(),
+            )),
+        }
+    }
 }
 
 /// Add a new comment to a merge request.
 ///
 /// The comment is inserted immediately into the local database (optimistic update)
 /// and queued for synchronization to GitLab. MR details (project_id, iid, SHAs)
-/// are looked up from the database automatically.
+/// are looked up from the database automatically. GitLab runs quick actions in
+/// the body, so an `/approve` is checked against the approval gate and (unless
+/// `confirm` is set) review blockers first, like `approve_mr`.
 ///
 /// # Arguments
 /// * `input` - Comment details (mr_id, body, optional position)
 ///
 /// # Returns
-/// The created comment with pending sync status
+/// The created comment with pending sync status, or what held its `/approve`
+/// back
 #[tauri::command]
 pub async fn add_comment(
     pool: State<'_, DbPool>,
     sync_handle: State<'_, SyncHandle>,
     input: AddCommentInput,
-) -> Result<CommentResponse, AppError> {
+) -> Result<AddCommentResult, AppError> {
+    let result = queue_comment(pool.inner(), input).await?;
+    if result.comment.is_some() {
+        // Fire-and-forget: flush comment actions immediately
+        if let Err(e) = sync_handle.flush_comments().await {
+            eprintln!("[comment] Failed to send flush signal: {}", e);
+        }
+    }
+    Ok(result)
+}
+
+/// Check a comment and queue it, without flushing.
+async fn queue_comment(db: &DbPool, input: AddCommentInput) -> Result<AddCommentResult, AppError> {
     // Look up MR info from database
-    let mr_info = get_mr_info(pool.inner(), input.mr_id).await?;
+    let mr_info = get_mr_info(db, input.mr_id).await?;
+    discussion_access::ensure_can_discuss(db, input.mr_id).await?;
+    if approves(&input.body) {
+        if let Some(hold) = mr_actions::approval_hold(db, input.mr_id, input.confirm).await? {
+            let held = mr_actions::ApproveResult::from(hold);
+            return Ok(AddCommentResult {
+                comment: None,
+                unmet: held.unmet,
+                blockers: held.blockers,
+            });
+        }
+    }
+    let body = comment_body(
+        input.body,
+        input.suggestion_content.as_deref(),
+        input.file_path.is_some() && input.new_line.is_some(),
+    )?;
 
     // Look up diff SHAs for inline comments
     let (base_sha, head_sha, start_sha) = if input.file_path.is_some() {
-        let shas = get_diff_shas(pool.inner(), input.mr_id).await?;
+        let shas = get_diff_shas(db, input.mr_id).await?;
         (Some(shas.0), Some(shas.1), Some(shas.2))
     } else {
         (None, None, None)
     };
 
     // Get the authenticated username for optimistic display
-    let author_username = get_authenticated_username(pool.inner(), mr_info.instance_id).await?;
-
-    // For context lines, resolve both old and new line numbers from the diff
-    let (old_line, new_line) = if input.is_context_line {
-        if let Some(file_path) = &input.file_path {
-            let known_line = input.new_line.or(input.old_line).unwrap_or(1);
-            let is_old_side = input.old_line.is_some() && input.new_line.is_none();
-            let (old, new) = resolve_context_line_numbers(
-                pool.inner(),
-                input.mr_id,
-                file_path,
-                known_line,
-                is_old_side,
-            )
-            .await?;
-            (Some(old), Some(new))
-        } else {
-            (input.old_line, input.new_line)
-        }
-    } else {
-        (input.old_line, input.new_line)
-    };
+    let author_username = get_authenticated_username(db, mr_info.instance_id).await?;
+
+    let (old_line, new_line) = comment_lines(
+        db,
+        input.mr_id,
+        input.file_path.as_deref(),
+        input.old_line,
+        input.new_line,
+        input.is_context_line,
+    )
+    .await?;
 
     let timestamp = now();
     let local_id = generate_local_id();
+    let body_chunks = comment_size::chunks(&body, comment_size::INSERT_CHUNK_BYTES);
 
-    // Insert comment optimistically
+    // Insert comment optimistically (large bodies in slices)
     sqlx::query(
         r#"
         INSERT INTO comments (id, mr_id, discussion_id, parent_id, author_username, body,
@@ -316,21 +473,22 @@ pub async fn add_comment(
     .bind(local_id)
     .bind(input.mr_id)
     .bind(&author_username)
-    .bind(&input.body)
+    .bind(body_chunks[0])
     .bind(&input.file_path)
     .bind(old_line)
     .bind(new_line)
     .bind(timestamp)
     .bind(timestamp)
     .bind(timestamp)
-    .execute(pool.inner())
+    .execute(db)
     .await?;
+    comment_size::append_body_chunks(db, local_id, &body_chunks[1..]).await?;
 
     // Build payload for sync queue (includes SHA info for inline comments)
     let payload = serde_json::to_string(&serde_json::json!({
         "project_id": mr_info.project_id,
         "mr_iid": mr_info.iid,
-        "body": input.body,
+        "body": body,
         "file_path": input.file_path,
         "old_line": old_line,
         "new_line": new_line,
@@ -341,7 +499,7 @@ pub async fn add_comment(
 
     // Queue for sync
     sync_queue::enqueue_action(
-        pool.inner(),
+        db,
         EnqueueInput {
             mr_id: input.mr_id,
             action_type: ActionType::Comment,
@@ -351,30 +509,83 @@ pub async fn add_comment(
     )
     .await?;
 
-    // Fire-and-forget: flush comment actions immediately
-    if let Err(e) = sync_handle.flush_comments().await {
-        eprintln!("[comment] Failed to send flush signal: {}", e);
+    Ok(AddCommentResult {
+        comment: Some(CommentResponse {
+            id: local_id,
+            mr_id: input.mr_id,
+            discussion_id: None,
+            parent_id: None,
+            author_username,
+            body,
+            file_path: input.file_path,
+            old_line,
+            new_line,
+            line_type: None,
+            resolved: false,
+            resolvable: true,
+            system: false,
+            created_at: timestamp,
+            updated_at: timestamp,
+            is_local: true,
+            sync_status: "pending".to_string(),
+            suggestions: Vec::new(),
+            snippet: None,
+        }),
+        unmet: Vec::new(),
+        blockers: None,
+    })
+}
+
+/// Apply a suggestion from a diff note.
+///
+/// GitLab commits the change to the MR's source branch. The cached diff of
+/// the file is patched right away and a sync is started to pick up the new
+/// commit.
+///
+/// # Arguments
+/// * `mr_id` - Merge request ID
+/// * `suggestion_id` - GitLab suggestion ID, from the comment's `suggestions`
+/// * `commit_message` - Optional message for the commit GitLab creates
+#[tauri::command]
+pub async fn apply_suggestion(
+    pool: State<'_, DbPool>,
+    sync_handle: State<'_, SyncHandle>,
+    mr_id: i64,
+    suggestion_id: i64,
+    commit_message: Option<String>,
+) -> Result<CommentSuggestion, AppError> {
+    let suggestion = suggestions::apply(
+        pool.inner(),
+        mr_id,
+        suggestion_id,
+        commit_message.as_deref(),
+    )
+    .await?;
+
+    if let Err(e) = sync_handle.trigger_sync().await {
+        eprintln!("[comment] Failed to trigger sync: {}", e);
     }
 
-    Ok(CommentResponse {
-        id: local_id,
-        mr_id: input.mr_id,
-        discussion_id: None,
-        parent_id: None,
-        author_username,
-        body: input.body,
-        file_path: input.file_path,
-        old_line,
-        new_line,
-        line_type: None,
-        resolved: false,
-        resolvable: true,
-        system: false,
-        created_at: timestamp,
-        updated_at: timestamp,
-        is_local: true,
-        sync_status: "pending".to_string(),
-    })
+    Ok(suggestion)
+}
+
+/// Upload a large pasted text to the MR's project as a file.
+///
+/// Used by the comment composer for pastes too large to post inline; the
+/// returned Markdown link goes into the comment instead.
+///
+/// # Arguments
+/// * `mr_id` - Merge request ID
+/// * `filename` - File name for the upload (e.g. "paste.log")
+/// * `content` - The pasted text
+#[tauri::command]
+pub async fn upload_comment_attachment(
+    pool: State<'_, DbPool>,
+    mr_id: i64,
+    filename: String,
+    content: String,
+) -> Result<CommentAttachment, AppError> {
+    comment_size::upload_as_attachment(pool.inner(), mr_id, &filename, &content).await
 }
 
 /// Input for reply_to_comment command.
@@ -408,10 +619,13 @@ pub async fn reply_to_comment(
     input: ReplyInput,
 ) -> Result<CommentResponse, AppError> {
     let mr_info = get_mr_info(pool.inner(), input.mr_id).await?;
+    discussion_access::ensure_can_discuss(pool.inner(), input.mr_id).await?;
+    comment_size::check_body(&input.body)?;
     let author_username = get_authenticated_username(pool.inner(), mr_info.instance_id).await?;
 
     let timestamp = now();
     let local_id = generate_local_id();
+    let body_chunks = comment_size::chunks(&input.body, comment_size::INSERT_CHUNK_BYTES);
 
     // Get the parent comment to inherit file_path and line info
     let parent = sqlx::query_as::<_, Comment>(
@@ -428,7 +642,7 @@ pub async fn reply_to_comment(
     .await?
     .ok_or_else(|| AppError::not_found_with_id("Comment", input.parent_id.to_string()))?;
 
-    // Insert reply optimistically
+    // Insert reply optimistically (large bodies in slices)
     sqlx::query(
         r#"
         INSERT INTO comments (id, mr_id, discussion_id, parent_id, author_username, body,
@@ -442,7 +656,7 @@ pub async fn reply_to_comment(
     .bind(&input.discussion_id)
     .bind(input.parent_id)
     .bind(&author_username)
-    .bind(&input.body)
+    .bind(body_chunks[0])
     .bind(&parent.file_path)
     .bind(parent.old_line)
     .bind(parent.new_line)
@@ -452,6 +666,7 @@ pub async fn reply_to_comment(
     .bind(timestamp)
     .execute(pool.inner())
     .await?;
+    comment_size::append_body_chunks(pool.inner(), local_id, &body_chunks[1..]).await?;
 
     // Build payload for sync queue
     let payload = serde_json::to_string(&ReplyPayload {
@@ -496,6 +711,8 @@ pub async fn reply_to_comment(
         updated_at: timestamp,
         is_local: true,
         sync_status: "pending".to_string(),
+        suggestions: Vec::new(),
+        snippet: None,
     })
 }
 
@@ -528,6 +745,7 @@ pub async fn resolve_discussion(
     input: ResolveInput,
 ) -> Result<(), AppError> {
     let mr_info = get_mr_info(pool.inner(), input.mr_id).await?;
+    discussion_access::ensure_can_discuss(pool.inner(), input.mr_id).await?;
 
     // Update all comments in the discussion optimistically
     sqlx::query("UPDATE comments SET resolved = ? WHERE discussion_id = ?")
@@ -569,6 +787,599 @@ pub async fn resolve_discussion(
     Ok(())
 }
 
+/// A comment in a review bundle; inline when `file_path` is set.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewBundleComment {
+    pub body: String,
+    pub file_path: Option<String>,
+    pub old_line: Option<i64>,
+    pub new_line: Option<i64>,
+    #[serde(default)]
+    pub is_context_line: bool,
+}
+
+/// Input for submit_review_bundle command.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewBundleInput {
+    /// Merge request ID (local database ID).
+    pub mr_id: i64,
+    /// Comments to post, in order.
+    #[serde(default)]
+    pub comments: Vec<ReviewBundleComment>,
+    /// Discussion IDs to resolve after the comments are posted.
+    #[serde(default)]
+    pub resolve_discussions: Vec<String>,
+    /// Approve the MR once everything else has been posted.
+    #[serde(default)]
+    pub approve: bool,
+    /// Approve even if there are review blockers.
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+/// Result of submit_review_bundle.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewBundleResult {
+    /// False when the approval gate or review blockers held the approval
+    /// back; nothing was queued.
+    pub submitted: bool,
+    /// Batch to poll with `get_review_bundle_status`.
+    pub batch_id: Option<String>,
+    /// The comments as inserted locally, in order.
+    pub comments: Vec<CommentResponse>,
+    /// Gate conditions that still need attention (empty when submitted).
+    pub unmet: Vec<UnmetCondition>,
+    /// Set when review blockers held the approval back; submit again with
+    /// `confirm` to approve anyway.
+    pub blockers: Option<ReviewBlockers>,
+}
+
+/// A bundle comment with its position worked out.
+struct PreparedComment {
+    body: String,
+    file_path: Option<String>,
+    old_line: Option<i64>,
+    new_line: Option<i64>,
+}
+
+/// Submit a review: comments, resolved threads and an optional approval.
+///
+/// Everything is checked before anything is written, then the steps are
+/// saved locally and queued as one batch in a single transaction. The sync
+/// engine pushes a batch in order and holds later steps back while an
+/// earlier one is unsynced, so the approval never lands before the comments
+/// it goes with. If the approval gate or (unless `confirm` is set) review
+/// blockers hold the approval back, nothing is queued. An `/approve` quick
+/// action in a comment is checked the same way.
+///
+/// # Arguments
+/// * `input` - The review (mr_id, comments, discussions to resolve, approve)
+///
+/// # Returns
+/// The batch ID and the locally inserted comments
+#[tauri::command]
+pub async fn submit_review_bundle(
+    pool: State<'_, DbPool>,
+    sync_handle: State<'_, SyncHandle>,
+    input: ReviewBundleInput,
+) -> Result<ReviewBundleResult, AppError> {
+    let result = queue_review_bundle(pool.inner(), input, false).await?;
+    if result.batch_id.is_some() {
+        flush_review(&sync_handle).await;
+    }
+    Ok(result)
+}
+
+/// Push a queued review batch right away.
+async fn flush_review(sync_handle: &SyncHandle) {
+    // Fire-and-forget: push the batch immediately
+    if let Err(e) = sync_handle
+        .flush_actions(vec![
+            ActionType::Comment,
+            ActionType::Resolve,
+            ActionType::Approve,
+        ])
+        .await
+    {
+        eprintln!("[comment] Failed to send flush signal: {}", e);
+    }
+}
+
+/// Check a review and queue it as one batch, without flushing. With
+/// `from_pending_review`, the MR's pending review is deleted in the same
+/// transaction, so its drafts can't be submitted twice.
+async fn queue_review_bundle(
+    db: &DbPool,
+    input: ReviewBundleInput,
+    from_pending_review: bool,
+) -> Result<ReviewBundleResult, AppError> {
+    let mr_id = input.mr_id;
+    if input.comments.is_empty() && input.resolve_discussions.is_empty() && !input.approve {
+        return Err(AppError::invalid_input(
+            "A review needs a comment, a resolved thread or an approval",
+        ));
+    }
+
+    let mr_info = get_mr_info(db, mr_id).await?;
+    if !input.comments.is_empty() || !input.resolve_discussions.is_empty() {
+        discussion_access::ensure_can_discuss(db, mr_id).await?;
+    }
+    for comment in &input.comments {
+        comment_size::check_body(&comment.body)?;
+    }
+    for discussion_id in &input.resolve_discussions {
+        let exists: bool = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM comments WHERE mr_id = ? AND discussion_id = ?)",
+        )
+        .bind(mr_id)
+        .bind(discussion_id)
+        .fetch_one(db)
+        .await?;
+        if !exists {
+            return Err(AppError::not_found_with_id(
+                "Discussion",
+                discussion_id.clone(),
+            ));
+        }
+    }
+
+    let mut approve = false;
+    if input.approve || input.comments.iter().any(|c| approves(&c.body)) {
+        token_scopes::ensure_can_write(db, mr_id).await?;
+        if let Some(hold) = mr_actions::approval_hold(db, mr_id, input.confirm).await? {
+            let held = mr_actions::ApproveResult::from(hold);
+            return Ok(ReviewBundleResult {
+                submitted: false,
+                batch_id: None,
+                comments: Vec::new(),
+                unmet: held.unmet,
+                blockers: held.blockers,
+            });
+        }
+    }
+    if input.approve {
+        let already: bool =
+            sqlx::query_scalar("SELECT user_has_approved FROM merge_requests WHERE id = ?")
+                .bind(mr_id)
+                .fetch_one(db)
+                .await?;
+        approve = !already;
+    }
+
+    let (base_sha, head_sha, start_sha) = if input.comments.iter().any(|c| c.file_path.is_some()) {
+        let shas = get_diff_shas(db, mr_id).await?;
+        (Some(shas.0), Some(shas.1), Some(shas.2))
+    } else {
+        (None, None, None)
+    };
+    let author_username = get_authenticated_username(db, mr_info.instance_id).await?;
+    let mut prepared = Vec::with_capacity(input.comments.len());
+    for comment in input.comments {
+        let (old_line, new_line) = comment_lines(
+            db,
+            mr_id,
+            comment.file_path.as_deref(),
+            comment.old_line,
+            comment.new_line,
+            comment.is_context_line,
+        )
+        .await?;
+        prepared.push(PreparedComment {
+            body: comment.body,
+            file_path: comment.file_path,
+            old_line,
+            new_line,
+        });
+    }
+
+    let action_count = prepared.len() + input.resolve_discussions.len() + usize::from(approve);
+    if action_count == 0 {
+        // Only an approval was asked for, and the MR is already approved.
+        if from_pending_review {
+            review_drafts::delete_review(db, mr_id).await?;
+        }
+        return Ok(ReviewBundleResult {
+            submitted: true,
+            batch_id: None,
+            comments: Vec::new(),
+            unmet: Vec::new(),
+            blockers: None,
+        });
+    }
+
+    let timestamp = now();
+    let mut tx = db.begin().await?;
+    let batch_id = sync_queue::create_batch(&mut tx, mr_id, action_count as i64).await?;
+
+    let mut comments = Vec::with_capacity(prepared.len());
+    for comment in prepared {
+        let local_id = generate_local_id();
+        sqlx::query(
+            r#"
+            INSERT INTO comments (id, mr_id, discussion_id, parent_id, author_username, body,
+                                  file_path, old_line, new_line, line_type, resolved, resolvable,
+                                  system, created_at, updated_at, cached_at, is_local)
+            VALUES (?, ?, NULL, NULL, ?, ?, ?, ?, ?, NULL, 0, 1, 0, ?, ?, ?, 1)
+            "#,
+        )
+        .bind(local_id)
+        .bind(mr_id)
+        .bind(&author_username)
+        .bind(&comment.body)
+        .bind(&comment.file_path)
+        .bind(comment.old_line)
+        .bind(comment.new_line)
+        .bind(timestamp)
+        .bind(timestamp)
+        .bind(timestamp)
+        .execute(&mut *tx)
+        .await?;
+
+        let payload = serde_json::to_string(&serde_json::json!({
+            "project_id": mr_info.project_id,
+            "mr_iid": mr_info.iid,
+            "body": comment.body,
+            "file_path": comment.file_path,
+            "old_line": comment.old_line,
+            "new_line": comment.new_line,
+            "base_sha": base_sha,
+            "head_sha": head_sha,
+            "start_sha": start_sha,
+        }))?;
+        sync_queue::enqueue_batch_action(
+            &mut tx,
+            &batch_id,
+            EnqueueInput {
+                mr_id,
+                action_type: ActionType::Comment,
+                payload,
+                local_reference_id: Some(local_id),
+            },
+        )
+        .await?;
+
+        comments.push(CommentResponse {
+            id: local_id,
+            mr_id,
+            discussion_id: None,
+            parent_id: None,
+            author_username: author_username.clone(),
+            body: comment.body,
+            file_path: comment.file_path,
+            old_line: comment.old_line,
+            new_line: comment.new_line,
+            line_type: None,
+            resolved: false,
+            resolvable: true,
+            system: false,
+            created_at: timestamp,
+            updated_at: timestamp,
+            is_local: true,
+            sync_status: "pending".to_string(),
+            suggestions: Vec::new(),
+            snippet: None,
+        });
+    }
+
+    for discussion_id in input.resolve_discussions {
+        sqlx::query("UPDATE comments SET resolved = 1 WHERE discussion_id = ?")
+            .bind(&discussion_id)
+            .execute(&mut *tx)
+            .await?;
+        let payload = serde_json::to_string(&ResolvePayload {
+            project_id: mr_info.project_id,
+            mr_iid: mr_info.iid,
+            discussion_id,
+        })?;
+        sync_queue::enqueue_batch_action(
+            &mut tx,
+            &batch_id,
+            EnqueueInput {
+                mr_id,
+                action_type: ActionType::Resolve,
+                payload,
+                local_reference_id: None,
+            },
+        )
+        .await?;
+    }
+
+    if approve {
+        mr_actions::apply_local_approval(&mut *tx, mr_id, true).await?;
+        let payload = serde_json::to_string(&ApprovalPayload {
+            project_id: mr_info.project_id,
+            mr_iid: mr_info.iid,
+        })?;
+        sync_queue::enqueue_batch_action(
+            &mut tx,
+            &batch_id,
+            EnqueueInput {
+                mr_id,
+                action_type: ActionType::Approve,
+                payload,
+                local_reference_id: None,
+            },
+        )
+        .await?;
+    }
+    if from_pending_review {
+        review_drafts::delete_review(&mut *tx, mr_id).await?;
+    }
+    tx.commit().await?;
+
+    Ok(ReviewBundleResult {
+        submitted: true,
+        batch_id: Some(batch_id),
+        comments,
+        unmet: Vec::new(),
+        blockers: None,
+    })
+}
+
+/// Progress of a batch queued by `submit_review_bundle`.
+#[tauri::command]
+pub async fn get_review_bundle_status(
+    pool: State<'_, DbPool>,
+    batch_id: String,
+) -> Result<BatchStatus, AppError> {
+    sync_queue::get_batch_status(pool.inner(), &batch_id).await
+}
+
+/// Start a pending review of an MR, or return the one already started.
+///
+/// Comments added to a pending review stay local, and nothing reaches
+/// GitLab (or anyone's notifications) until `submit_review`.
+#[tauri::command]
+pub async fn start_review(pool: State<'_, DbPool>, mr_id: i64) -> Result<PendingReview, AppError> {
+    let db = pool.inner();
+    get_mr_info(db, mr_id).await?;
+    discussion_access::ensure_can_discuss(db, mr_id).await?;
+    review_drafts::start_review(db, mr_id, now()).await?;
+    review_drafts::get_review(db, mr_id)
+        .await?
+        .ok_or_else(|| AppError::internal("Pending review vanished after it was started"))
+}
+
+/// The MR's pending review, if one has been started.
+#[tauri::command]
+pub async fn get_pending_review(
+    pool: State<'_, DbPool>,
+    mr_id: i64,
+) -> Result<Option<PendingReview>, AppError> {
+    Ok(review_drafts::get_review(pool.inner(), mr_id).await?)
+}
+
+/// Input for add_draft_comment command; positioned like `AddCommentInput`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddDraftCommentInput {
+    pub mr_id: i64,
+    pub body: String,
+    pub file_path: Option<String>,
+    pub old_line: Option<i64>,
+    pub new_line: Option<i64>,
+    #[serde(default)]
+    pub is_context_line: bool,
+    #[serde(default)]
+    pub suggestion_content: Option<String>,
+}
+
+/// Add a draft comment to the MR's pending review, starting one if needed.
+///
+/// The draft is checked like a regular comment but only saved locally.
+#[tauri::command]
+pub async fn add_draft_comment(
+    pool: State<'_, DbPool>,
+    input: AddDraftCommentInput,
+) -> Result<DraftComment, AppError> {
+    save_draft_comment(pool.inner(), input).await
+}
+
+async fn save_draft_comment(
+    db: &DbPool,
+    input: AddDraftCommentInput,
+) -> Result<DraftComment, AppError> {
+    get_mr_info(db, input.mr_id).await?;
+    discussion_access::ensure_can_discuss(db, input.mr_id).await?;
+    let body = comment_body(
+        input.body,
+        input.suggestion_content.as_deref(),
+        input.file_path.is_some() && input.new_line.is_some(),
+    )?;
+    let (old_line, new_line) = comment_lines(
+        db,
+        input.mr_id,
+        input.file_path.as_deref(),
+        input.old_line,
+        input.new_line,
+        input.is_context_line,
+    )
+    .await?;
+
+    let timestamp = now();
+    let review_id = review_drafts::start_review(db, input.mr_id, timestamp).await?;
+    let draft = NewDraftComment {
+        body: &body,
+        file_path: input.file_path.as_deref(),
+        old_line,
+        new_line,
+    };
+    Ok(review_drafts::add_comment(db, review_id, &draft, timestamp).await?)
+}
+
+/// Remove one draft from the MR's pending review.
+#[tauri::command]
+pub async fn delete_draft_comment(
+    pool: State<'_, DbPool>,
+    mr_id: i64,
+    draft_id: i64,
+) -> Result<(), AppError> {
+    if !review_drafts::delete_comment(pool.inner(), mr_id, draft_id).await? {
+        return Err(AppError::not_found_with_id(
+            "DraftComment",
+            draft_id.to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Throw away the MR's pending review and all its drafts.
+#[tauri::command]
+pub async fn discard_review(pool: State<'_, DbPool>, mr_id: i64) -> Result<(), AppError> {
+    review_drafts::delete_review(pool.inner(), mr_id).await?;
+    Ok(())
+}
+
+/// Input for submit_review command.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitReviewInput {
+    pub mr_id: i64,
+    /// Overall review note, posted after the drafts.
+    pub summary: Option<String>,
+    /// Discussion IDs to resolve after the comments are posted.
+    #[serde(default)]
+    pub resolve_discussions: Vec<String>,
+    /// Approve the MR once everything else has been posted.
+    #[serde(default)]
+    pub approve: bool,
+    /// Approve even if there are review blockers.
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+/// Submit the MR's pending review.
+///
+/// The drafts, the summary note and the approval are queued as one batch
+/// and flushed together, as with `submit_review_bundle`, and the pending
+/// review is cleared in the same transaction. When the approval is held
+/// back, nothing is queued and the drafts are kept.
+#[tauri::command]
+pub async fn submit_review(
+    pool: State<'_, DbPool>,
+    sync_handle: State<'_, SyncHandle>,
+    input: SubmitReviewInput,
+) -> Result<ReviewBundleResult, AppError> {
+    let result = queue_pending_review(pool.inner(), input).await?;
+    if result.batch_id.is_some() {
+        flush_review(&sync_handle).await;
+    }
+    Ok(result)
+}
+
+/// Queue the MR's pending review as a review bundle, without flushing.
+async fn queue_pending_review(
+    db: &DbPool,
+    input: SubmitReviewInput,
+) -> Result<ReviewBundleResult, AppError> {
+    let review = review_drafts::get_review(db, input.mr_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::invalid_input("No review has been started for this merge request")
+        })?;
+
+    let mut comments: Vec<ReviewBundleComment> = review
+        .comments
+        .into_iter()
+        .map(|draft| ReviewBundleComment {
+            body: draft.body,
+            file_path: draft.file_path,
+            old_line: draft.old_line,
+            new_line: draft.new_line,
+            is_context_line: false,
+        })
+        .collect();
+    if let Some(summary) = input.summary.filter(|s| !s.trim().is_empty()) {
+        comments.push(ReviewBundleComment {
+            body: summary,
+            file_path: None,
+            old_line: None,
+            new_line: None,
+            is_context_line: false,
+        });
+    }
+
+    let bundle = ReviewBundleInput {
+        mr_id: input.mr_id,
+        comments,
+        resolve_discussions: input.resolve_discussions,
+        approve: input.approve,
+        confirm: input.confirm,
+    };
+    queue_review_bundle(db, bundle, true).await
+}
+
+/// Import review annotations from a file as inline comments.
+///
+/// The file uses the `file:line: comment` format of CLI review tools (see
+/// `core::review_import`). Every annotation must be on a line shown in the
+/// MR's diff; the comments are then queued as one review batch, so either
+/// all of them are posted or none.
+///
+/// # Arguments
+/// * `mr_id` - Merge request ID
+/// * `path` - Annotation file path; a leading `~/` is expanded
+///
+/// # Returns
+/// The batch ID and the locally inserted comments
+#[tauri::command]
+pub async fn import_review_annotations(
+    pool: State<'_, DbPool>,
+    sync_handle: State<'_, SyncHandle>,
+    mr_id: i64,
+    path: String,
+) -> Result<ReviewBundleResult, AppError> {
+    let path = crate::core::expand_home(&path)?;
+    let content = tokio::fs::read_to_string(&path).await.map_err(|e| {
+        AppError::invalid_input_field(format!("Cannot read {}: {}", path.display(), e), "path")
+    })?;
+    let annotations = review_import::parse(&content)?;
+    let placed = review_import::place(pool.inner(), mr_id, annotations).await?;
+
+    let bundle = ReviewBundleInput {
+        mr_id,
+        comments: placed
+            .into_iter()
+            .map(|annotation| ReviewBundleComment {
+                body: annotation.body,
+                file_path: Some(annotation.file_path),
+                old_line: None,
+                new_line: Some(annotation.new_line),
+                is_context_line: annotation.is_context_line,
+            })
+            .collect(),
+        resolve_discussions: Vec::new(),
+        approve: false,
+        confirm: false,
+    };
+    let result = queue_review_bundle(pool.inner(), bundle, false).await?;
+    if result.batch_id.is_some() {
+        flush_review(&sync_handle).await;
+    }
+    Ok(result)
+}
+
+/// Export a discussion thread as a markdown or plain-text transcript.
+///
+/// Each note carries its author, timestamp and a permalink to the note on
+/// GitLab, so the thread can be pasted into an issue or document. Reads
+/// from the local cache only.
+///
+/// # Arguments
+/// * `discussion_id` - GitLab discussion ID
+/// * `format` - `"markdown"` or `"text"`
+#[tauri::command]
+pub async fn export_discussion(
+    pool: State<'_, DbPool>,
+    discussion_id: String,
+    format: ExportFormat,
+) -> Result<String, AppError> {
+    comments::export_discussion(pool.inner(), &discussion_id, format).await
+}
+
 /// Get comments for a specific file and line.
 ///
 /// Useful for showing inline comments in the diff viewer.
@@ -600,7 +1411,7 @@ pub async fn get_file_comments(
     .fetch_all(pool.inner())
     .await?;
 
-    to_comment_responses(pool.inner(), comments).await
+    to_comment_responses(pool.inner(), comments, None).await
 }
 
 /// Input for delete_comment command.
@@ -677,6 +1488,146 @@ pub async fn delete_comment(
     Ok(())
 }
 
+/// Ping the author of an MR I'm reviewing.
+///
+/// Posts a general comment mentioning the author, worded from `template` and
+/// linking the unresolved threads it is about. The comment goes through the
+/// sync queue like any other. Refused if the same ping was already sent and
+/// the author hasn't commented or pushed since.
+///
+/// # Arguments
+/// * `mr_id` - Merge request ID
+/// * `template` - `"awaiting_reply"`, `"open_threads"` or `"rebase"`
+///
+/// # Returns
+/// The created comment with pending sync status
+#[tauri::command]
+pub async fn ping_author(
+    pool: State<'_, DbPool>,
+    sync_handle: State<'_, SyncHandle>,
+    mr_id: i64,
+    template: PingTemplate,
+) -> Result<CommentResponse, AppError> {
+    let db = pool.inner();
+    let ping = author_ping::prepare_ping(db, mr_id, template).await?;
+    let comment = add_comment(
+        pool,
+        sync_handle,
+        AddCommentInput {
+            mr_id,
+            body: ping.body.clone(),
+            file_path: None,
+            old_line: None,
+            new_line: None,
+            is_context_line: false,
+            suggestion_content: None,
+            confirm: false,
+        },
+    )
+    .await?
+    .into_comment()?;
+    author_ping::record_ping(db, mr_id, template, &ping, now()).await?;
+    Ok(comment)
+}
+
+/// Pings sent to the author of an MR, newest first.
+#[tauri::command]
+pub async fn get_author_pings(
+    pool: State<'_, DbPool>,
+    mr_id: i64,
+) -> Result<Vec<AuthorPing>, AppError> {
+    author_ping::list_pings(pool.inner(), mr_id).await
+}
+
+/// Assign files of an MR to one reviewer, for splitting a large MR.
+///
+/// Posts a comment asking the reviewer to look at the files; the mapping
+/// rides along in the comment so other clients pick it up after syncing.
+/// Locally the assignment applies right away.
+///
+/// # Returns
+/// All file assignments of the MR
+#[tauri::command]
+pub async fn assign_files_to_reviewer(
+    pool: State<'_, DbPool>,
+    sync_handle: State<'_, SyncHandle>,
+    mr_id: i64,
+    paths: Vec<String>,
+    username: String,
+) -> Result<Vec<FileAssignment>, AppError> {
+    let db = pool.inner();
+    let note = file_assignments::normalize_request(&username, &paths)?;
+    file_assignments::ensure_paths_in_diff(db, mr_id, &note.paths).await?;
+    let comment = add_comment(
+        pool,
+        sync_handle,
+        AddCommentInput {
+            mr_id,
+            body: file_assignments::comment_body(&note),
+            file_path: None,
+            old_line: None,
+            new_line: None,
+            is_context_line: false,
+            suggestion_content: None,
+            confirm: false,
+        },
+    )
+    .await?
+    .into_comment()?;
+    crate::db::file_assignments::record_assignment(
+        db,
+        mr_id,
+        &note.paths,
+        &note.username,
+        &comment.author_username,
+        comment.created_at,
+    )
+    .await?;
+    file_assignments::get_file_assignments(db, mr_id).await
+}
+
+/// Which reviewer each file of an MR is assigned to. Unassigned files are
+/// left out.
+#[tauri::command]
+pub async fn get_file_assignments(
+    pool: State<'_, DbPool>,
+    mr_id: i64,
+) -> Result<Vec<FileAssignment>, AppError> {
+    file_assignments::get_file_assignments(pool.inner(), mr_id).await
+}
+
+/// Search my own comments across all cached MRs and instances.
+///
+/// # Arguments
+/// * `range` - Optional `since`/`until` bounds on the creation time
+/// * `search` - Words that must all appear in the comment body
+///
+/// # Returns
+/// Up to 200 comments, newest first
+#[tauri::command]
+pub async fn get_my_comment_history(
+    pool: State<'_, DbPool>,
+    range: CommentRange,
+    search: Option<String>,
+) -> Result<Vec<MyComment>, AppError> {
+    comment_history::get_my_comments(pool.inner(), range, search.as_deref()).await
+}
+
+/// Quick actions GitLab accepts in MR comments, for composer autocomplete.
+#[tauri::command]
+pub async fn list_quick_actions() -> Result<Vec<QuickActionSpec>, AppError> {
+    Ok(quick_actions::QUICK_ACTIONS.to_vec())
+}
+
+/// Preview the quick actions GitLab will run when `body` is posted.
+///
+/// Purely local: the comment itself is still sent verbatim and the server
+/// executes the commands.
+#[tauri::command]
+pub async fn preview_quick_actions(body: String) -> Result<QuickActionPreview, AppError> {
+    Ok(quick_actions::parse_quick_actions(&body))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -725,4 +1676,363 @@ mod tests {
         }
         assert_eq!(all_ids.len(), 20);
     }
+
+    #[tokio::test]
+    async fn review_bundle_with_blockers_is_refused_until_confirmed() {
+        let (pool, inst) = crate::core::tests::seed_instance(true).await;
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, title, author_username, source_branch,
+              target_branch, state, web_url, created_at, updated_at, has_conflicts)
+             VALUES (1, ?, 7, 100, 'Add cache', 'alice', 'f', 'main', 'opened', 'w', 0, 0, 1)",
+        )
+        .bind(inst)
+        .execute(&pool)
+        .await
+        .unwrap();
+        let input = |confirm| ReviewBundleInput {
+            mr_id: 1,
+            comments: vec![ReviewBundleComment {
+                body: "LGTM".into(),
+                file_path: None,
+                old_line: None,
+                new_line: None,
+                is_context_line: false,
+            }],
+            resolve_discussions: Vec::new(),
+            approve: true,
+            confirm,
+        };
+
+        let held = queue_review_bundle(&pool, input(false), false)
+            .await
+            .unwrap();
+        assert!(!held.submitted);
+        assert!(held.blockers.is_some_and(|b| b.has_conflicts));
+        assert!(sync_queue::get_actions_for_mr(&pool, 1)
+            .await
+            .unwrap()
+            .is_empty());
+        let comments: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM comments WHERE mr_id = 1")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(comments, 0);
+
+        let submitted = queue_review_bundle(&pool, input(true), false)
+            .await
+            .unwrap();
+        assert!(submitted.submitted);
+        assert_eq!(submitted.comments.len(), 1);
+        assert_eq!(
+            sync_queue::get_actions_for_mr(&pool, 1)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    /// Seed an MR (id 1) by alice; `has_conflicts` makes it a review blocker.
+    async fn seed_mr(has_conflicts: bool) -> DbPool {
+        let (pool, inst) = crate::core::tests::seed_instance(true).await;
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, title, author_username, source_branch,
+              target_branch, state, web_url, created_at, updated_at, has_conflicts)
+             VALUES (1, ?, 7, 100, 'Add cache', 'alice', 'f', 'main', 'opened', 'w', 0, 0, ?)",
+        )
+        .bind(inst)
+        .bind(has_conflicts)
+        .execute(&pool)
+        .await
+        .unwrap();
+        pool
+    }
+
+    fn general_comment(body: &str, confirm: bool) -> AddCommentInput {
+        AddCommentInput {
+            mr_id: 1,
+            body: body.into(),
+            file_path: None,
+            old_line: None,
+            new_line: None,
+            is_context_line: false,
+            suggestion_content: None,
+            confirm,
+        }
+    }
+
+    #[tokio::test]
+    async fn approve_quick_action_in_comment_goes_through_approval_hold() {
+        let pool = seed_mr(true).await;
+
+        let held = queue_comment(&pool, general_comment("LGTM\n/approve", false))
+            .await
+            .unwrap();
+        assert!(held.comment.is_none());
+        assert!(held.blockers.is_some_and(|b| b.has_conflicts));
+        let bundle = ReviewBundleInput {
+            mr_id: 1,
+            comments: vec![ReviewBundleComment {
+                body: "/approve".into(),
+                file_path: None,
+                old_line: None,
+                new_line: None,
+                is_context_line: false,
+            }],
+            resolve_discussions: Vec::new(),
+            approve: false,
+            confirm: false,
+        };
+        let held = queue_review_bundle(&pool, bundle, false).await.unwrap();
+        assert!(!held.submitted);
+        assert!(sync_queue::get_actions_for_mr(&pool, 1)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let plain = queue_comment(&pool, general_comment("LGTM", false))
+            .await
+            .unwrap();
+        assert!(plain.comment.is_some());
+        let confirmed = queue_comment(&pool, general_comment("/approve", true))
+            .await
+            .unwrap();
+        assert!(confirmed.comment.is_some());
+        assert_eq!(
+            sync_queue::get_actions_for_mr(&pool, 1)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    fn draft_input(body: &str) -> AddDraftCommentInput {
+        AddDraftCommentInput {
+            mr_id: 1,
+            body: body.into(),
+            file_path: None,
+            old_line: None,
+            new_line: None,
+            is_context_line: false,
+            suggestion_content: None,
+        }
+    }
+
+    fn review_input(approve: bool, confirm: bool) -> SubmitReviewInput {
+        SubmitReviewInput {
+            mr_id: 1,
+            summary: Some("Summary".into()),
+            resolve_discussions: Vec::new(),
+            approve,
+            confirm,
+        }
+    }
+
+    /// (action type, batch, comment body) of MR 1's queued actions in order.
+    async fn queued(pool: &DbPool) -> Vec<(String, Option<String>, Option<String>)> {
+        let rows: Vec<(String, Option<String>, String)> = sqlx::query_as(
+            "SELECT action_type, batch_id, payload FROM sync_queue WHERE mr_id = 1 ORDER BY id",
+        )
+        .fetch_all(pool)
+        .await
+        .unwrap();
+        rows.into_iter()
+            .map(|(action_type, batch_id, payload)| {
+                let payload: serde_json::Value = serde_json::from_str(&payload).unwrap();
+                let body = payload["body"].as_str().map(str::to_string);
+                (action_type, batch_id, body)
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn pending_review_is_queued_as_one_ordered_batch() {
+        let pool = seed_mr(false).await;
+        for body in ["first", "second", "third"] {
+            save_draft_comment(&pool, draft_input(body)).await.unwrap();
+        }
+
+        let result = queue_pending_review(&pool, review_input(true, false))
+            .await
+            .unwrap();
+        assert!(result.submitted);
+        let batch_id = result.batch_id.unwrap();
+        let queued = queued(&pool).await;
+        let steps: Vec<(&str, Option<&str>)> = queued
+            .iter()
+            .map(|(action_type, _, body)| (action_type.as_str(), body.as_deref()))
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                ("comment", Some("first")),
+                ("comment", Some("second")),
+                ("comment", Some("third")),
+                ("comment", Some("Summary")),
+                ("approve", None),
+            ]
+        );
+        assert!(queued
+            .iter()
+            .all(|(_, batch, _)| batch.as_deref() == Some(batch_id.as_str())));
+        assert!(review_drafts::get_review(&pool, 1).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn discarded_pending_review_queues_nothing() {
+        let pool = seed_mr(false).await;
+        save_draft_comment(&pool, draft_input("first"))
+            .await
+            .unwrap();
+        save_draft_comment(&pool, draft_input("second"))
+            .await
+            .unwrap();
+
+        assert!(review_drafts::delete_review(&pool, 1).await.unwrap());
+        assert!(review_drafts::get_review(&pool, 1).await.unwrap().is_none());
+        let drafts: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM review_draft_comments")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(drafts, 0);
+
+        let err = queue_pending_review(&pool, review_input(false, false))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("No review has been started"));
+        assert!(queued(&pool).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn pending_review_approval_goes_through_approval_hold() {
+        let pool = seed_mr(true).await;
+        save_draft_comment(&pool, draft_input("first"))
+            .await
+            .unwrap();
+        save_draft_comment(&pool, draft_input("/approve"))
+            .await
+            .unwrap();
+
+        // Both an `/approve` draft and `approve` are held by the blocker.
+        for approve in [false, true] {
+            let held = queue_pending_review(&pool, review_input(approve, false))
+                .await
+                .unwrap();
+            assert!(!held.submitted);
+            assert!(held.blockers.is_some_and(|b| b.has_conflicts));
+        }
+        assert!(queued(&pool).await.is_empty());
+        let review = review_drafts::get_review(&pool, 1).await.unwrap().unwrap();
+        assert_eq!(review.comments.len(), 2, "held drafts are kept");
+
+        let submitted = queue_pending_review(&pool, review_input(true, true))
+            .await
+            .unwrap();
+        assert!(submitted.submitted);
+        assert_eq!(queued(&pool).await.len(), 4);
+    }
+
+    fn bundle_input(bodies: &[&str], resolve: &[&str], approve: bool) -> ReviewBundleInput {
+        ReviewBundleInput {
+            mr_id: 1,
+            comments: bodies
+                .iter()
+                .map(|body| ReviewBundleComment {
+                    body: body.to_string(),
+                    file_path: None,
+                    old_line: None,
+                    new_line: None,
+                    is_context_line: false,
+                })
+                .collect(),
+            resolve_discussions: resolve.iter().map(|id| id.to_string()).collect(),
+            approve,
+            confirm: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn review_bundle_approving_an_approved_mr_queues_nothing() {
+        let pool = seed_mr(false).await;
+        sqlx::query("UPDATE merge_requests SET user_has_approved = 1 WHERE id = 1")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let result = queue_review_bundle(&pool, bundle_input(&[], &[], true), false)
+            .await
+            .unwrap();
+        assert!(result.submitted);
+        assert!(result.batch_id.is_none());
+        assert!(queued(&pool).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn review_bundle_held_by_the_gate_returns_unmet_conditions() {
+        use crate::core::approval_gate::{save_gate, ApprovalGate};
+
+        let pool = seed_mr(false).await;
+        let instance_id: i64 = sqlx::query_scalar("SELECT instance_id FROM merge_requests")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        let gate = ApprovalGate {
+            instance_id,
+            project_id: 100,
+            enabled: true,
+            require_files_viewed: false,
+            require_own_threads_resolved: false,
+            checklist: vec!["Tests cover the change".into()],
+        };
+        save_gate(&pool, &gate).await.unwrap();
+
+        let held = queue_review_bundle(&pool, bundle_input(&["LGTM"], &[], true), false)
+            .await
+            .unwrap();
+        assert!(!held.submitted);
+        assert_eq!(
+            held.unmet,
+            vec![UnmetCondition::ChecklistIncomplete {
+                items: vec!["Tests cover the change".into()],
+            }]
+        );
+        assert!(held.blockers.is_none());
+        assert!(queued(&pool).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn review_bundle_queues_resolves_and_approval_after_comments() {
+        let pool = seed_mr(false).await;
+        sqlx::query(
+            "INSERT INTO comments (id, mr_id, discussion_id, author_username, body, resolvable, resolved, created_at, updated_at)
+             VALUES (100, 1, 'd1', 'alice', 'Why?', 1, 0, 5, 5)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let mut input = bundle_input(&["first", "second"], &["d1"], true);
+        // The open thread is a review blocker until the bundle resolves it.
+        input.confirm = true;
+        let result = queue_review_bundle(&pool, input, false).await.unwrap();
+        assert!(result.submitted);
+        let queued = queued(&pool).await;
+        let steps: Vec<(&str, Option<&str>)> = queued
+            .iter()
+            .map(|(action_type, _, body)| (action_type.as_str(), body.as_deref()))
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                ("comment", Some("first")),
+                ("comment", Some("second")),
+                ("resolve", None),
+                ("approve", None),
+            ]
+        );
+        assert!(queued.iter().all(|(_, batch, _)| *batch == result.batch_id));
+    }
 }
@@ -5,7 +5,7 @@
 
 use crate::db::pool::DbPool;
 use crate::error::AppError;
-use crate::services::companion_server;
+use crate::services::companion_server::{self, CompanionListenConfig};
 use crate::services::sync_engine::SyncHandle;
 use std::path::PathBuf;
 use tauri::{AppHandle, Manager, State};
@@ -43,7 +43,7 @@ fn resolve_frontend_dist(app: &AppHandle) -> Result<PathBuf, AppError> {
 
 /// Start the companion HTTP server.
 ///
-/// Reads the companion server port from settings and starts serving.
+/// Reads the listen address and base path from settings and starts serving.
 #[tauri::command]
 pub async fn start_companion_server_cmd(
     app: AppHandle,
@@ -51,17 +51,17 @@ pub async fn start_companion_server_cmd(
     sync_handle: State<'_, SyncHandle>,
 ) -> Result<(), AppError> {
     let settings = load_settings(&app).await?;
-    let port = settings.companion_server.port;
+    let config = CompanionListenConfig::from(&settings.companion_server);
     let frontend_dist = resolve_frontend_dist(&app)?;
 
     log::info!(
-        "[companion] Starting server on port {} serving {:?}",
-        port,
+        "[companion] Starting server ({:?}) serving {:?}",
+        config.bind_mode,
         frontend_dist
     );
 
     companion_server::start_companion_server(
-        port,
+        config,
         frontend_dist,
         pool.inner().clone(),
         sync_handle.inner().clone(),
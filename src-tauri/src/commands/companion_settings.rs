@@ -1,7 +1,7 @@
 //! Companion server settings commands.
 //!
 //! These commands manage the companion server configuration: enable/disable,
-//! port, PIN, and authorized device management. Settings are persisted via
+//! listen address, PIN, and authorized device management. Settings are persisted via
 //! the existing tauri-plugin-store system alongside other AppSettings.
 
 use crate::error::AppError;
@@ -30,18 +30,52 @@ pub struct AuthorizedDevice {
     pub created_at: DateTime<Utc>,
 }
 
+/// Where the companion server listens.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CompanionBindMode {
+    /// All interfaces, so phones on the same network can connect directly.
+    #[default]
+    Lan,
+    /// 127.0.0.1 only, for a reverse proxy running on this machine.
+    Loopback,
+    /// A Unix domain socket at `socket_path`, for a reverse proxy.
+    UnixSocket,
+}
+
+impl CompanionBindMode {
+    /// Whether clients reach the server through a local reverse proxy.
+    pub fn is_proxied(self) -> bool {
+        self != CompanionBindMode::Lan
+    }
+}
+
 /// Companion server configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CompanionServerSettings {
     /// Whether the companion server is enabled.
     pub enabled: bool,
-    /// Port to bind the HTTP server on.
+    /// Port to bind the HTTP server on (ignored for Unix sockets).
     pub port: u16,
     /// 6-digit PIN for device authentication.
     pub pin: String,
     /// List of authorized devices.
     pub authorized_devices: Vec<AuthorizedDevice>,
+    /// Listen on the LAN, loopback only, or a Unix socket.
+    #[serde(default)]
+    pub bind_mode: CompanionBindMode,
+    /// Socket file path when `bind_mode` is `unixSocket`.
+    #[serde(default)]
+    pub socket_path: Option<String>,
+    /// Path prefix the UI and API are served under, e.g. `/gitlab`.
+    /// Empty when served from the root.
+    #[serde(default)]
+    pub base_path: String,
+    /// Externally reachable URL (including any base path) used in the
+    /// pairing QR code, e.g. `https://review.example.com/gitlab`.
+    #[serde(default)]
+    pub public_url: Option<String>,
 }
 
 impl Default for CompanionServerSettings {
@@ -51,6 +85,24 @@ impl Default for CompanionServerSettings {
             port: 6767,
             pin: generate_pin(),
             authorized_devices: Vec::new(),
+            bind_mode: CompanionBindMode::default(),
+            socket_path: None,
+            base_path: String::new(),
+            public_url: None,
+        }
+    }
+}
+
+impl CompanionServerSettings {
+    /// Root URL a phone should open, without a trailing slash.
+    ///
+    /// Uses the configured public URL when set. Without one, a LAN server is
+    /// addressed by `lan_ip`; a proxied server has no known public address.
+    pub fn public_base_url(&self, lan_ip: &str) -> Option<String> {
+        match &self.public_url {
+            Some(url) => Some(url.clone()),
+            None if self.bind_mode.is_proxied() => None,
+            None => Some(format!("http://{}:{}{}", lan_ip, self.port, self.base_path)),
         }
     }
 }
@@ -73,6 +125,81 @@ fn validate_port(port: u16) -> Result<(), AppError> {
     Ok(())
 }
 
+/// Normalize a base path to `""` or `/segment[/segment...]` without a
+/// trailing slash.
+fn normalize_base_path(path: &str) -> Result<String, AppError> {
+    let trimmed = path.trim().trim_matches('/');
+    if trimmed.is_empty() {
+        return Ok(String::new());
+    }
+    let valid = trimmed.split('/').all(|segment| {
+        !segment.is_empty()
+            && segment != "."
+            && segment != ".."
+            && segment
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '~'))
+    });
+    if !valid {
+        return Err(AppError::invalid_input_field(
+            "Base path may only contain letters, digits, '-', '_', '.' and '/'",
+            "basePath",
+        ));
+    }
+    Ok(format!("/{}", trimmed))
+}
+
+/// Normalize a public URL to an absolute http(s) URL without a trailing slash.
+fn normalize_public_url(url: Option<&str>) -> Result<Option<String>, AppError> {
+    let Some(url) = url.map(str::trim).filter(|u| !u.is_empty()) else {
+        return Ok(None);
+    };
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"));
+    match rest {
+        Some(host) if !host.is_empty() && !host.contains(['?', '#', ' ']) => {
+            Ok(Some(url.trim_end_matches('/').to_string()))
+        }
+        _ => Err(AppError::invalid_input_field(
+            "Public URL must be an absolute http:// or https:// URL",
+            "publicUrl",
+        )),
+    }
+}
+
+/// Validate the listen settings and normalize paths and URLs in place.
+fn validate_companion_settings(companion: &mut CompanionServerSettings) -> Result<(), AppError> {
+    validate_port(companion.port)?;
+    companion.base_path = normalize_base_path(&companion.base_path)?;
+    companion.public_url = normalize_public_url(companion.public_url.as_deref())?;
+    companion.socket_path = companion
+        .socket_path
+        .as_deref()
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(str::to_string);
+
+    if companion.bind_mode == CompanionBindMode::UnixSocket {
+        if !cfg!(unix) {
+            return Err(AppError::invalid_input_field(
+                "Unix sockets are not supported on this platform",
+                "bindMode",
+            ));
+        }
+        match &companion.socket_path {
+            Some(path) if std::path::Path::new(path).is_absolute() => {}
+            _ => {
+                return Err(AppError::invalid_input_field(
+                    "Socket path must be an absolute path",
+                    "socketPath",
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Get the current companion server settings.
 #[tauri::command]
 pub async fn get_companion_settings(app: AppHandle) -> Result<CompanionServerSettings, AppError> {
@@ -82,13 +209,14 @@ pub async fn get_companion_settings(app: AppHandle) -> Result<CompanionServerSet
 
 /// Update the companion server settings.
 ///
-/// Validates port range before saving.
+/// Validates the port, socket path, base path and public URL before saving.
+/// Takes effect the next time the server is started.
 #[tauri::command]
 pub async fn update_companion_settings(
     app: AppHandle,
-    companion: CompanionServerSettings,
+    mut companion: CompanionServerSettings,
 ) -> Result<(), AppError> {
-    validate_port(companion.port)?;
+    validate_companion_settings(&mut companion)?;
 
     let mut settings = load_settings(&app).await?;
     settings.companion_server = companion;
@@ -160,24 +288,45 @@ pub async fn get_companion_status() -> Result<CompanionStatus, AppError> {
     })
 }
 
+/// Root URL of the companion UI as a phone would open it.
+fn companion_base_url(companion: &CompanionServerSettings) -> Result<String, AppError> {
+    let local_ip = match local_ip_address::local_ip() {
+        Ok(ip) => ip.to_string(),
+        Err(_) => "127.0.0.1".to_string(),
+    };
+
+    companion.public_base_url(&local_ip).ok_or_else(|| {
+        AppError::invalid_input_field(
+            "Set a public URL to pair devices through a reverse proxy",
+            "publicUrl",
+        )
+    })
+}
+
+/// Get the URL phones should open to reach the companion UI.
+#[tauri::command]
+pub async fn get_companion_url(app: AppHandle) -> Result<String, AppError> {
+    let settings = load_settings(&app).await?;
+    companion_base_url(&settings.companion_server)
+}
+
 /// Generate a QR code SVG for the companion server.
 ///
-/// Encodes `http://{local_ip}:{port}/auth?pin={pin}` so mobile users
-/// can scan it to auto-authenticate. Available as a Tauri command so
-/// the desktop settings UI can display the QR without the companion
-/// HTTP server needing to be running.
+/// Encodes `{base_url}/auth?pin={pin}` so mobile users can scan it to
+/// auto-authenticate, where the base URL is the configured public URL or
+/// the LAN address. Available as a Tauri command so the desktop settings
+/// UI can display the QR without the companion HTTP server needing to be
+/// running.
 #[tauri::command]
 pub async fn get_companion_qr_svg(app: AppHandle) -> Result<String, AppError> {
     let settings = load_settings(&app).await?;
     let pin = &settings.companion_server.pin;
-    let port = settings.companion_server.port;
-
-    let local_ip = match local_ip_address::local_ip() {
-        Ok(ip) => ip.to_string(),
-        Err(_) => "127.0.0.1".to_string(),
-    };
 
-    let url = format!("http://{}:{}/auth?pin={}", local_ip, port, pin);
+    let url = format!(
+        "{}/auth?pin={}",
+        companion_base_url(&settings.companion_server)?,
+        pin
+    );
 
     let qr = qrcode::QrCode::new(url.as_bytes())
         .map_err(|e| AppError::internal(format!("Failed to generate QR code: {}", e)))?;
@@ -211,3 +360,75 @@ pub async fn revoke_companion_device(app: AppHandle, device_id: String) -> Resul
     *settings_cache().write().await = settings;
     Ok(())
 }
+
+/// Confirm a code-based pairing request shown in the desktop dialog.
+///
+/// Authorizes the requesting device and persists it like a PIN login; the
+/// companion UI picks up its session on its next status poll.
+#[tauri::command]
+pub async fn confirm_pairing(app: AppHandle, code: String) -> Result<AuthorizedDevice, AppError> {
+    let device = companion_auth::confirm_pairing_code(code.trim())
+        .await
+        .ok_or_else(|| AppError::not_found_with_id("PairingRequest", code.trim()))?;
+
+    let mut settings = load_settings(&app).await?;
+    settings
+        .companion_server
+        .authorized_devices
+        .push(device.clone());
+    save_settings(&app, &settings).await?;
+    *settings_cache().write().await = settings;
+    Ok(device)
+}
+
+/// Reject a code-based pairing request.
+#[tauri::command]
+pub async fn reject_pairing(code: String) -> Result<(), AppError> {
+    if companion_auth::reject_pairing_code(code.trim()).await {
+        Ok(())
+    } else {
+        Err(AppError::not_found_with_id("PairingRequest", code.trim()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base_path_is_normalized() {
+        assert_eq!(normalize_base_path("").unwrap(), "");
+        assert_eq!(normalize_base_path(" / ").unwrap(), "");
+        assert_eq!(normalize_base_path("gitlab/").unwrap(), "/gitlab");
+        assert_eq!(
+            normalize_base_path("/tools/review").unwrap(),
+            "/tools/review"
+        );
+        assert!(normalize_base_path("/a//b").is_err());
+        assert!(normalize_base_path("/../etc").is_err());
+        assert!(normalize_base_path("/a?b").is_err());
+    }
+
+    #[test]
+    fn public_base_url_prefers_configured_url() {
+        let mut companion = CompanionServerSettings {
+            base_path: "/gitlab".into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            companion.public_base_url("192.168.1.20").as_deref(),
+            Some("http://192.168.1.20:6767/gitlab")
+        );
+
+        companion.bind_mode = CompanionBindMode::UnixSocket;
+        assert_eq!(companion.public_base_url("192.168.1.20"), None);
+
+        companion.public_url =
+            normalize_public_url(Some("https://review.example.com/gitlab/")).unwrap();
+        assert_eq!(
+            companion.public_base_url("192.168.1.20").as_deref(),
+            Some("https://review.example.com/gitlab")
+        );
+        assert!(normalize_public_url(Some("review.example.com")).is_err());
+    }
+}
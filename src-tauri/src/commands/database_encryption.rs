@@ -0,0 +1,111 @@
+//! Database encryption commands: toggle SQLCipher encryption (applied on the
+//! next start), and export or restore the keychain key for recovery.
+
+use super::settings::{load_settings, save_settings, settings_cache};
+use crate::db::{self, encryption};
+use crate::error::AppError;
+use serde::Serialize;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+
+/// Encryption state of the database file and its key.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseEncryptionStatus {
+    /// Whether this build links SQLCipher.
+    pub supported: bool,
+    /// The setting; takes effect on the next start.
+    pub enabled: bool,
+    /// Whether the database file is currently encrypted.
+    pub encrypted: bool,
+    /// Whether a key is stored in the OS keychain.
+    pub key_stored: bool,
+    /// Whether an encrypted database was moved aside because its key was
+    /// missing. Restoring the key brings it back on the next start.
+    pub locked_database: bool,
+}
+
+fn db_path(app: &AppHandle) -> Result<PathBuf, AppError> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::internal(format!("Failed to get app data directory: {}", e)))?;
+    Ok(db::get_db_path(&dir))
+}
+
+async fn status(app: &AppHandle) -> Result<DatabaseEncryptionStatus, AppError> {
+    let path = db_path(app)?;
+    Ok(DatabaseEncryptionStatus {
+        supported: encryption::supported(),
+        enabled: load_settings(app).await?.encrypt_database,
+        encrypted: encryption::is_encrypted(&path),
+        key_stored: matches!(encryption::load_key(), Ok(Some(_))),
+        locked_database: encryption::locked_path(&path).exists(),
+    })
+}
+
+/// Current encryption setting and state of the database file.
+#[tauri::command]
+pub async fn get_database_encryption_status(
+    app: AppHandle,
+) -> Result<DatabaseEncryptionStatus, AppError> {
+    status(&app).await
+}
+
+/// Turn database encryption on or off. The file is rewritten on the next
+/// start, before the database is opened.
+///
+/// # Arguments
+/// * `enabled` - When true, the database is encrypted with a key kept in the OS keychain.
+#[tauri::command]
+pub async fn update_database_encryption(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<DatabaseEncryptionStatus, AppError> {
+    if enabled && !encryption::supported() {
+        return Err(AppError::invalid_input(
+            "This build does not include database encryption support",
+        ));
+    }
+    let mut settings = load_settings(&app).await?;
+    settings.encrypt_database = enabled;
+    save_settings(&app, &settings).await?;
+    *settings_cache().write().await = settings;
+    status(&app).await
+}
+
+/// The database key as 64 hex characters, to be kept somewhere safe. It is
+/// the only way to open the database if the keychain entry is lost.
+#[tauri::command]
+pub async fn export_database_key() -> Result<String, AppError> {
+    encryption::load_key()?.ok_or_else(|| AppError::not_found("Database key"))
+}
+
+/// Put a previously exported key back into the OS keychain. A database that
+/// was moved aside for lack of a key is restored on the next start.
+///
+/// # Arguments
+/// * `key` - The exported key (64 hex characters)
+#[tauri::command]
+pub async fn restore_database_key(
+    app: AppHandle,
+    key: String,
+) -> Result<DatabaseEncryptionStatus, AppError> {
+    let key = encryption::normalize_key(&key)?;
+    let path = db_path(&app)?;
+    let target = if encryption::is_encrypted(&path) {
+        Some(path)
+    } else {
+        Some(encryption::locked_path(&path)).filter(|p| p.exists())
+    };
+    if let Some(target) = target {
+        if !encryption::key_opens(&target, &key).await {
+            return Err(AppError::invalid_input_field(
+                "This key does not open the database",
+                "key",
+            ));
+        }
+    }
+    encryption::store_key(&key)?;
+    status(&app).await
+}
@@ -0,0 +1,22 @@
+//! Command for linting my MR descriptions against the project's template.
+
+use crate::core::description_lint::{self, DescriptionLint};
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use tauri::State;
+
+/// Check the description of one of my MRs against the project's MR template
+/// and report the sections that are missing or still empty.
+///
+/// Templates are read live from `.gitlab/merge_request_templates` on the
+/// MR's target branch. Projects without templates get no sections.
+///
+/// # Arguments
+/// * `mr_id` - Merge request ID
+#[tauri::command]
+pub async fn lint_mr_description(
+    pool: State<'_, DbPool>,
+    mr_id: i64,
+) -> Result<DescriptionLint, AppError> {
+    description_lint::lint_description(pool.inner(), mr_id).await
+}
@@ -3,8 +3,12 @@
 //! These commands are used for testing and validating success criteria:
 //! - SC-008: Application uses less than 500MB RAM with 100 cached MRs
 
-use crate::db::pool::DbPool;
+use crate::db::pool::{pool_stats, DbPool, PoolStats, ReadPool};
 use crate::error::AppError;
+use crate::services::command_metrics::{self, CommandMetric, CommandTiming};
+use crate::services::instance_lock::InstanceMode;
+use crate::services::memory_profile::{self, SubsystemUsage};
+use crate::services::quick_switch;
 use serde::Serialize;
 use sqlx::Row;
 use sysinfo::{Pid, System};
@@ -28,6 +32,21 @@ pub struct MemoryStats {
     pub target_bytes: u64,
 }
 
+/// Process memory broken down by subsystem
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryBreakdown {
+    /// Resident set size of the whole process in bytes
+    pub process_memory_bytes: u64,
+    /// False unless the app was built with the `memory-profiling` feature;
+    /// the per-subsystem counters are all zero in that case
+    pub tracking_enabled: bool,
+    /// Heap usage charged to each subsystem
+    pub subsystems: Vec<SubsystemUsage>,
+    /// Live heap bytes across all subsystems
+    pub tracked_heap_bytes: i64,
+}
+
 /// Database cache statistics
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -44,12 +63,23 @@ pub struct CacheStats {
     pub db_size_mb: f64,
 }
 
+/// Connection usage of the writer and read-only SQLite pools
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DbPoolStats {
+    /// Single-connection pool all writes go through
+    pub writer: PoolStats,
+    /// Read-only pool serving UI list/detail queries
+    pub reader: PoolStats,
+}
+
 /// Combined diagnostics report
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DiagnosticsReport {
     pub memory: MemoryStats,
     pub cache: CacheStats,
+    pub pools: DbPoolStats,
     /// Timestamp of the report
     pub timestamp: i64,
 }
@@ -91,6 +121,61 @@ pub async fn get_memory_stats() -> Result<MemoryStats, AppError> {
     })
 }
 
+/// Get heap usage attributed to each subsystem (sync engine, diff parsing,
+/// file cache), alongside total process RSS for comparison.
+///
+/// RSS far above `trackedHeapBytes` points at memory outside the Rust heap
+/// (SQLite page cache, webview, allocator fragmentation).
+#[tauri::command]
+pub async fn get_memory_breakdown() -> Result<MemoryBreakdown, AppError> {
+    let process_memory_bytes = get_memory_stats().await?.process_memory_bytes;
+    let subsystems = memory_profile::snapshot();
+    let tracked_heap_bytes = subsystems.iter().map(|s| s.live_bytes).sum();
+
+    Ok(MemoryBreakdown {
+        process_memory_bytes,
+        tracking_enabled: memory_profile::tracking_enabled(),
+        subsystems,
+        tracked_heap_bytes,
+    })
+}
+
+/// Get connection counts for the writer and read-only database pools.
+///
+/// A writer with its one connection permanently in use while the reader sits
+/// idle means UI reads are correctly bypassing a busy sync.
+#[tauri::command]
+pub async fn get_pool_stats(
+    pool: State<'_, DbPool>,
+    read_pool: State<'_, ReadPool>,
+) -> Result<DbPoolStats, AppError> {
+    Ok(DbPoolStats {
+        writer: pool_stats(pool.inner()),
+        reader: pool_stats(read_pool.inner()),
+    })
+}
+
+/// Whether this process owns the database or is a read-only secondary
+/// viewer (started with `--secondary`, or while another process held it).
+#[tauri::command]
+pub async fn get_instance_mode(mode: State<'_, InstanceMode>) -> Result<InstanceMode, AppError> {
+    Ok(*mode.inner())
+}
+
+/// Store invoke timings measured by the frontend (batched, fire-and-forget).
+#[tauri::command]
+pub async fn record_command_timings(timings: Vec<CommandTiming>) -> Result<(), AppError> {
+    command_metrics::record(timings);
+    Ok(())
+}
+
+/// Per-command call counts and P50/P95/max latency over the most recent
+/// invokes, slowest first.
+#[tauri::command]
+pub async fn get_command_metrics() -> Result<Vec<CommandMetric>, AppError> {
+    Ok(command_metrics::summarize())
+}
+
 /// Get database cache statistics.
 #[tauri::command]
 pub async fn get_cache_stats(pool: State<'_, DbPool>) -> Result<CacheStats, AppError> {
@@ -128,7 +213,9 @@ pub async fn get_cache_stats(pool: State<'_, DbPool>) -> Result<CacheStats, AppE
 #[tauri::command]
 pub async fn get_diagnostics_report(
     pool: State<'_, DbPool>,
+    read_pool: State<'_, ReadPool>,
 ) -> Result<DiagnosticsReport, AppError> {
+    let pools = get_pool_stats(pool.clone(), read_pool).await?;
     let memory = get_memory_stats().await?;
     let cache = get_cache_stats(pool).await?;
 
@@ -140,6 +227,7 @@ pub async fn get_diagnostics_report(
     Ok(DiagnosticsReport {
         memory,
         cache,
+        pools,
         timestamp,
     })
 }
@@ -312,6 +400,7 @@ pub async fn generate_test_data(
             comments_generated += 1;
         }
     }
+    quick_switch::invalidate().await;
 
     let duration = start.elapsed();
 
@@ -329,6 +418,7 @@ pub async fn clear_test_data(pool: State<'_, DbPool>) -> Result<i64, AppError> {
     let result = sqlx::query("DELETE FROM merge_requests WHERE id >= 1000000")
         .execute(pool.inner())
         .await?;
+    quick_switch::invalidate().await;
 
     Ok(result.rows_affected() as i64)
 }
@@ -446,6 +536,16 @@ mod tests {
         assert!(stats.system_total_bytes > 0);
     }
 
+    #[tokio::test]
+    async fn test_get_memory_breakdown_reports_all_subsystems() {
+        let breakdown = get_memory_breakdown().await.unwrap();
+        assert!(breakdown.process_memory_bytes > 0);
+        assert_eq!(
+            breakdown.subsystems.len(),
+            memory_profile::Subsystem::ALL.len()
+        );
+    }
+
     #[test]
     fn test_generate_diff_content() {
         let content = generate_diff_content(3);
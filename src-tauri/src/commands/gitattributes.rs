@@ -1,7 +1,8 @@
 //! Gitattributes cache commands.
 //!
 //! These commands handle fetching and caching `.gitattributes` patterns
-//! for identifying linguist-generated files in merge requests.
+//! for identifying linguist-generated files in merge requests, along with
+//! the `linguist-language` overrides used for syntax highlighting.
 //!
 //! Uses stale-while-revalidate: cached data is returned immediately, and
 //! a background refresh is spawned when the cache is older than 24 hours.
@@ -9,7 +10,7 @@
 use crate::db::pool::DbPool;
 use crate::error::AppError;
 use crate::models::GitLabInstance;
-use crate::services::gitattributes::parse_gitattributes;
+use crate::services::gitattributes::{parse_gitattributes, parse_language_overrides};
 use crate::services::gitlab_client::{GitLabClient, GitLabClientConfig};
 use tauri::State;
 
@@ -68,8 +69,9 @@ pub async fn get_gitattributes(
 /// Fetch .gitattributes from GitLab and update the local cache.
 ///
 /// Fetches the `.gitattributes` file from the project's default branch via
-/// the GitLab Repository Files API, parses it for `linguist-generated` patterns,
-/// and upserts the result into the `gitattributes_cache` table.
+/// the GitLab Repository Files API, parses it for `linguist-generated` patterns
+/// and `linguist-language` overrides, and upserts the result into the
+/// `gitattributes_cache` table.
 ///
 /// Handles 404 gracefully — if the project has no `.gitattributes`, the cache
 /// is updated with an empty patterns array.
@@ -133,21 +135,24 @@ pub async fn refresh_gitattributes_inner(
 
     // Parse the content — empty string (404) gives empty patterns
     let patterns = parse_gitattributes(&content);
+    let language_overrides = parse_language_overrides(&content);
 
     // Upsert into cache
     let now = chrono::Utc::now().timestamp();
     let patterns_json = serde_json::to_string(&patterns)?;
+    let overrides_json = serde_json::to_string(&language_overrides)?;
 
     sqlx::query(
         r#"
-        INSERT INTO gitattributes_cache (instance_id, project_id, patterns, fetched_at)
-        VALUES (?, ?, ?, ?)
-        ON CONFLICT(instance_id, project_id) DO UPDATE SET patterns = excluded.patterns, fetched_at = excluded.fetched_at
+        INSERT INTO gitattributes_cache (instance_id, project_id, patterns, language_overrides, fetched_at)
+        VALUES (?, ?, ?, ?, ?)
+        ON CONFLICT(instance_id, project_id) DO UPDATE SET patterns = excluded.patterns, language_overrides = excluded.language_overrides, fetched_at = excluded.fetched_at
         "#,
     )
     .bind(instance_id)
     .bind(project_id)
     .bind(&patterns_json)
+    .bind(&overrides_json)
     .bind(now)
     .execute(pool)
     .await?;
@@ -0,0 +1,61 @@
+//! Commands for MR labels.
+
+use crate::core::mr_labels::{self, ProjectLabel};
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use crate::services::sync_engine::SyncHandle;
+use tauri::State;
+
+/// Get the cached labels of a project, by name.
+///
+/// Sync refreshes the cache for every project with open MRs, so the list is
+/// available offline.
+#[tauri::command]
+pub async fn get_project_labels(
+    pool: State<'_, DbPool>,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<Vec<ProjectLabel>, AppError> {
+    mr_labels::project_labels(pool.inner(), instance_id, project_id).await
+}
+
+/// Add a label to an MR.
+///
+/// The cached MR is updated immediately; the change is queued and pushed to
+/// GitLab in the background.
+///
+/// # Returns
+/// The MR's labels afterwards
+#[tauri::command]
+pub async fn add_mr_label(
+    pool: State<'_, DbPool>,
+    sync_handle: State<'_, SyncHandle>,
+    mr_id: i64,
+    label: String,
+) -> Result<Vec<String>, AppError> {
+    let labels = mr_labels::set_mr_label(pool.inner(), mr_id, &label, true).await?;
+    flush(&sync_handle).await;
+    Ok(labels)
+}
+
+/// Remove a label from an MR, queued like [`add_mr_label`].
+///
+/// # Returns
+/// The MR's labels afterwards
+#[tauri::command]
+pub async fn remove_mr_label(
+    pool: State<'_, DbPool>,
+    sync_handle: State<'_, SyncHandle>,
+    mr_id: i64,
+    label: String,
+) -> Result<Vec<String>, AppError> {
+    let labels = mr_labels::set_mr_label(pool.inner(), mr_id, &label, false).await?;
+    flush(&sync_handle).await;
+    Ok(labels)
+}
+
+async fn flush(sync_handle: &SyncHandle) {
+    if let Err(e) = sync_handle.flush_comments().await {
+        log::warn!("[labels] Failed to send flush signal: {}", e);
+    }
+}
@@ -0,0 +1,92 @@
+//! Register the app as the OS handler for GitLab links.
+//!
+//! Registration claims http and https through the deep-link plugin, which
+//! only supports this on Windows and Linux; macOS has no API for an app to
+//! become the browser for a single domain. Incoming links are routed by
+//! `services::link_handler`.
+
+use crate::commands::settings::{load_settings, save_settings, settings_cache};
+use crate::error::AppError;
+use crate::services::link_handler::{self, LinkHandlerSettings, LinkRoute};
+use tauri::AppHandle;
+use tauri_plugin_deep_link::DeepLinkExt;
+use tauri_plugin_opener::OpenerExt;
+
+fn set_registered(app: &AppHandle, registered: bool) -> Result<(), AppError> {
+    for scheme in link_handler::SCHEMES {
+        let result = if registered {
+            app.deep_link().register(scheme)
+        } else {
+            app.deep_link().unregister(scheme)
+        };
+        result.map_err(|e| match e {
+            tauri_plugin_deep_link::Error::UnsupportedPlatform => AppError::invalid_input(
+                "Opening GitLab links in the app isn't supported on this platform",
+            ),
+            e => AppError::internal(format!("Failed to update {} handler: {}", scheme, e)),
+        })?;
+    }
+    Ok(())
+}
+
+/// Make the app the OS handler for links, opening MR links on `hosts` in
+/// the app and passing everything else to `fallback_browser`.
+///
+/// # Arguments
+/// * `hosts` - Hosts (or instance URLs) to open MR links for
+/// * `fallback_browser` - Program that receives all other links
+#[tauri::command]
+pub async fn register_link_handler(
+    app: AppHandle,
+    hosts: Vec<String>,
+    fallback_browser: String,
+) -> Result<LinkHandlerSettings, AppError> {
+    let mut link_handler = LinkHandlerSettings {
+        enabled: true,
+        hosts,
+        fallback_browser,
+    };
+    link_handler::validate_settings(&mut link_handler)?;
+    set_registered(&app, true)?;
+
+    let mut settings = load_settings(&app).await?;
+    settings.link_handler = link_handler.clone();
+    save_settings(&app, &settings).await?;
+    *settings_cache().write().await = settings;
+    log::info!("Registered as link handler for {:?}", link_handler.hosts);
+    Ok(link_handler)
+}
+
+/// Give links back to the browser. The opted-in hosts are kept for the next
+/// registration.
+#[tauri::command]
+pub async fn unregister_link_handler(app: AppHandle) -> Result<(), AppError> {
+    let mut settings = load_settings(&app).await?;
+    if settings.link_handler.enabled {
+        set_registered(&app, false)?;
+    }
+    settings.link_handler.enabled = false;
+    save_settings(&app, &settings).await?;
+    *settings_cache().write().await = settings;
+    Ok(())
+}
+
+/// Route a link the OS handed to the app.
+///
+/// Returns the MR URL to open when the link belongs in the app. Any other
+/// link is forwarded to the fallback browser (or the system default while
+/// unregistered) and `None` is returned.
+#[tauri::command]
+pub async fn open_link(app: AppHandle, url: String) -> Result<Option<String>, AppError> {
+    let settings = load_settings(&app).await?.link_handler;
+    match link_handler::route(&url, &settings) {
+        LinkRoute::App(mr_url) => Ok(Some(mr_url)),
+        LinkRoute::Browser => {
+            let browser = Some(settings.fallback_browser).filter(|b| !b.is_empty());
+            app.opener().open_url(url, browser).map_err(|e| {
+                AppError::internal(format!("Failed to open link in browser: {}", e))
+            })?;
+            Ok(None)
+        }
+    }
+}
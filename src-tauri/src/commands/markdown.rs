@@ -0,0 +1,22 @@
+//! Markdown rendering command.
+
+use crate::core::markdown::{self, ProjectContext};
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use chrono::Utc;
+use tauri::State;
+
+/// Render GitLab-flavored markdown to sanitized HTML.
+///
+/// # Arguments
+/// * `text` - Markdown source (a description or comment body)
+/// * `project_context` - Project references like `#12` resolve against; when
+///   unset only bare URLs are linked
+#[tauri::command]
+pub async fn render_gfm(
+    pool: State<'_, DbPool>,
+    text: String,
+    project_context: Option<ProjectContext>,
+) -> Result<String, AppError> {
+    markdown::render(pool.inner(), &text, project_context, Utc::now().timestamp()).await
+}
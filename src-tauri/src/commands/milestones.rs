@@ -0,0 +1,41 @@
+//! Commands for MR milestones.
+
+use crate::core::mr_milestones::{self, ProjectMilestone};
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use crate::services::sync_engine::SyncHandle;
+use tauri::State;
+
+/// Get the cached active milestones of a project, soonest due first.
+///
+/// Sync refreshes the cache for every project with open MRs, so the list is
+/// available offline.
+#[tauri::command]
+pub async fn get_project_milestones(
+    pool: State<'_, DbPool>,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<Vec<ProjectMilestone>, AppError> {
+    mr_milestones::project_milestones(pool.inner(), instance_id, project_id).await
+}
+
+/// Put an MR in a milestone, or take it out of its milestone with `None`.
+///
+/// The cached MR is updated immediately; the change is queued and pushed to
+/// GitLab in the background.
+///
+/// # Returns
+/// The MR's milestone afterwards
+#[tauri::command]
+pub async fn set_mr_milestone(
+    pool: State<'_, DbPool>,
+    sync_handle: State<'_, SyncHandle>,
+    mr_id: i64,
+    milestone_id: Option<i64>,
+) -> Result<Option<ProjectMilestone>, AppError> {
+    let milestone = mr_milestones::set_mr_milestone(pool.inner(), mr_id, milestone_id).await?;
+    if let Err(e) = sync_handle.flush_comments().await {
+        log::warn!("[milestones] Failed to send flush signal: {}", e);
+    }
+    Ok(milestone)
+}
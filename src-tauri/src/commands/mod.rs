@@ -10,53 +10,90 @@
 //! - `settings`: Application settings management
 //! - `diagnostics`: Memory and performance verification
 
+pub mod api_passthrough;
 pub mod approval;
 pub mod auth;
 pub mod auto_merge;
 pub mod auto_run;
+pub mod automations;
+pub mod badge_rules;
+pub mod cache_archive;
 pub mod cli;
 pub mod avatar;
+pub mod background_jobs;
 pub mod comments;
 pub mod companion_server;
 pub mod companion_settings;
+pub mod database_encryption;
+pub mod description_lint;
 pub mod diagnostics;
 pub mod fonts;
 pub mod gitattributes;
 pub mod issues;
+pub mod labels;
+pub mod link_handler;
+pub mod markdown;
+pub mod milestones;
 pub mod mr;
+pub mod mr_compare;
+pub mod mr_tasks;
 pub mod notification_settings;
 pub mod notifications;
 pub mod pipeline;
+pub mod project_readme;
+pub mod retention;
+pub mod review_assistant;
+pub mod review_stats;
 pub mod reviewers;
 pub mod settings;
+pub mod statistics_export;
 pub mod sync;
+pub mod translation;
+pub mod watch;
+pub mod working_copy;
 
 // Re-export commands for registration in lib.rs
 pub use cli::{cli_status, download_and_install_cli};
-pub use approval::{approve_mr, get_approval_status, unapprove_mr};
+pub use link_handler::{open_link, register_link_handler, unregister_link_handler};
+pub use api_passthrough::gitlab_api_request;
+pub use approval::{
+    approve_mr, get_approval_gate, get_approval_status, get_review_blockers,
+    get_review_checklist, set_review_checklist_item, unapprove_mr, update_approval_gate,
+};
 pub use auto_merge::{
-    claim_auto_merge, get_auto_merge_claim, process_auto_merge_now, unclaim_auto_merge,
+    cancel_merge_when_ready, claim_auto_merge, get_auto_merge_claim, merge_when_ready,
+    process_auto_merge_now, unclaim_auto_merge,
 };
 pub use auto_run::{claim_auto_run, list_auto_run_claims, unclaim_auto_run};
 pub use auth::{
-    delete_gitlab_instance, get_gitlab_instances, get_token_info, rename_instance,
-    set_default_instance, setup_gitlab_instance, update_instance_token,
+    delete_gitlab_instance, get_gitlab_instances, get_instance_quota, get_token_info, import_instances_from_config, rename_instance,
+    set_default_instance, setup_gitlab_instance, update_instance_headers, update_instance_tls, update_instance_token,
 };
 pub use avatar::{get_avatar, get_avatars, refresh_avatars, update_session_cookie};
+pub use background_jobs::{get_background_jobs, report_user_activity};
 pub use comments::{
-    add_comment, delete_comment, get_comments, get_file_comments, reply_to_comment,
-    resolve_discussion,
+    add_comment, delete_comment, get_author_pings, get_comments, get_file_comments, get_latest_comment_cursor, mark_mr_viewed, get_my_comment_history,
+    list_quick_actions, ping_author, preview_quick_actions, reply_to_comment, resolve_discussion,
+    export_discussion, import_review_annotations, upload_comment_attachment, submit_review_bundle, get_review_bundle_status,
+    start_review, get_pending_review, add_draft_comment, delete_draft_comment, discard_review,
+    submit_review,
+    assign_files_to_reviewer, get_file_assignments,
+    apply_suggestion,
 };
 pub use companion_server::{start_companion_server_cmd, stop_companion_server_cmd};
 pub use companion_settings::{
-    get_companion_qr_svg, get_companion_settings, get_companion_status, regenerate_companion_pin,
-    revoke_companion_device, set_companion_pin, update_companion_settings,
+    confirm_pairing, get_companion_qr_svg, get_companion_settings, get_companion_status,
+    get_companion_url, regenerate_companion_pin, reject_pairing, revoke_companion_device, set_companion_pin,
+    update_companion_settings,
 };
 pub use fonts::list_system_fonts;
 pub use diagnostics::{
-    clear_test_data, generate_test_data, get_cache_stats, get_diagnostics_report, get_memory_stats,
+    clear_test_data, generate_test_data, get_cache_stats, get_command_metrics,
+    get_diagnostics_report, get_instance_mode, get_memory_breakdown, get_memory_stats,
+    get_pool_stats, record_command_timings,
 };
 pub use gitattributes::{get_gitattributes, refresh_gitattributes};
+pub use project_readme::get_project_readme;
 pub use issues::{
     add_issue_note, get_cached_issue_detail, list_cached_issue_notes,
     list_cached_issues, list_issue_assignee_candidates,
@@ -65,28 +102,71 @@ pub use issues::{
     sync_my_issues, sync_project_issues, toggle_issue_star, toggle_project_star,
 };
 pub use mr::{
-    check_merge_status, get_cached_file_pair, get_diff_content, get_diff_file,
-    get_diff_file_metadata, get_diff_files, get_diff_hunks, get_diff_refs, get_file_content,
+    check_merge_status, get_merge_blockers, get_accessible_diff, get_cached_file_pair, get_diff_content, get_diff_file,
+    get_diff_file_metadata, get_diff_files, get_diff_hunks, get_diff_refs, get_file_blame, get_file_content,
+    get_commit_diff, get_mr_activity, get_mr_language_breakdown, get_mr_commits, mark_mr_reviewed, get_changes_since_review,
+    mark_file_viewed, get_file_review_states,
     get_file_content_base64, get_merge_request_detail, get_merge_requests, get_mr_pipelines,
     list_my_merge_requests, merge_mr, rebase_mr, undraft_mr, resolve_mr_by_web_url, fetch_mr_by_web_url,
+    run_post_merge_tasks, get_semantic_summary, get_side_by_side_diff, get_security_findings, get_quick_switch_index,
+};
+pub use notification_settings::{
+    get_notification_settings, list_muted_projects, mute_project, unmute_project,
+    update_notification_settings,
 };
-pub use notification_settings::{get_notification_settings, update_notification_settings};
 pub use notifications::send_native_notification;
 pub use pipeline::{
     cancel_pipeline, cancel_pipeline_job, get_cached_pipeline_statuses, get_job_trace,
-    get_pipeline_jobs, get_pipeline_statuses, get_project_pipelines, list_pipeline_projects,
-    play_pipeline_job, remove_pipeline_project, reorder_pinned_pipeline_projects,
+    get_job_trace_range,
+    get_pending_manual_jobs, get_pipeline_graph, get_pipeline_jobs, get_pipeline_statuses,
+    get_project_pipelines, list_pipeline_projects,
+    play_jobs, play_pipeline_job, remove_pipeline_project, reorder_pinned_pipeline_projects,
     resolve_project_by_path, retry_pipeline_job,
-    search_projects, toggle_pin_pipeline_project, visit_pipeline_project,
+    search_projects, set_pipeline_manual_gate_notify, set_pipeline_notify_rule,
+    toggle_pin_pipeline_project, visit_pipeline_project,
+    get_cached_pipeline_schedules, list_pipeline_schedules, run_schedule_now,
+    toggle_schedule_active, get_cached_project_releases, get_cached_project_tags,
+    list_project_releases, list_project_tags,
+};
+pub use retention::{audit_cached_data, get_retention_policy, update_retention_policy};
+pub use cache_archive::{export_cache_archive, import_cache_archive};
+pub use automations::{
+    delete_automation_rule, list_automation_rules, list_automation_runs, save_automation_rule,
+    test_automation,
+};
+pub use badge_rules::{list_badge_rules, update_badge_rules};
+pub use review_stats::{
+    export_review_stats, get_review_sla_status, get_review_streaks, set_review_stats_enabled,
+};
+pub use statistics_export::export_statistics;
+pub use mr_compare::compare_mrs;
+pub use description_lint::lint_mr_description;
+pub use mr_tasks::{get_mr_tasks, toggle_mr_task};
+pub use labels::{add_mr_label, get_project_labels, remove_mr_label};
+pub use milestones::{get_project_milestones, set_mr_milestone};
+pub use reviewers::{
+    get_mr_participants, get_mr_reviewers, request_rereview, search_project_members,
+    set_mr_assignees, set_mr_reviewers,
 };
-pub use reviewers::get_mr_reviewers;
 pub use settings::{
     get_collapse_patterns, get_settings, get_sync_settings, update_collapse_patterns,
     update_custom_theme_colors, update_diffs_font, update_display_font,
-    update_keyboard_shortcuts, update_mr_list_condensed, update_settings,
+    set_offline_mode, update_allow_api_writes, update_keyboard_shortcuts, update_mr_list_condensed, update_settings,
     update_show_draft_mrs, update_show_recently_merged_mrs, update_sync_settings, update_theme, update_ui_font,
+    update_translation_settings, update_review_assistant_settings, list_highlight_themes,
+    set_highlight_theme, get_list_view_state, update_list_view_state, update_editor_command,
 };
 pub use sync::{
-    discard_failed_action, get_action_counts, get_sync_config, get_sync_status,
-    retry_failed_actions, trigger_sync, update_sync_config,
+    discard_failed_action, get_action_counts, get_changes_since, get_mr_delta, get_orphaned_actions,
+    get_sync_config, get_sync_status, list_failed_actions, retry_failed_actions,
+    run_sync_selftest, trigger_full_resync, trigger_sync, update_sync_config,
+};
+pub use review_assistant::{suggest_comment, summarize_mr};
+pub use translation::translate_text;
+pub use markdown::render_gfm;
+pub use database_encryption::{
+    export_database_key, get_database_encryption_status, restore_database_key,
+    update_database_encryption,
 };
+pub use watch::{unwatch_mr, watch_mr};
+pub use working_copy::{checkout_mr_branch, get_mr_repo_path, open_in_editor};
@@ -3,15 +3,42 @@
 //! These commands read from local SQLite storage for instant access.
 //! No network requests are made - all data comes from the sync cache.
 
-use crate::db::pool::DbPool;
+use crate::core::file_blame::{self, FileBlame};
+use crate::core::file_review::{self, FileReviewState};
+use crate::core::language_stats::{self, LanguageBreakdown};
+use crate::core::merge_blockers::{self, MergeBlockers};
+use crate::core::mr_activity;
+use crate::core::mr_commits;
+use crate::core::post_merge::{PostMergeTask, PostMergeTaskResult};
+use crate::core::review_progress;
+use crate::core::semantic_summaries;
+use crate::db::mr_commits::MrCommit;
+use crate::db::mr_events::MrEvent;
+use crate::db::pool::{DbPool, ReadPool};
 use crate::error::AppError;
-use crate::models::{Diff, DiffFile, GitLabInstance, MergeRequest};
+use crate::models::{
+    description_preview, Diff, DiffFile, GitLabInstance, MergeRequest,
+    DEFAULT_DESCRIPTION_PREVIEW_CHARS,
+};
+use crate::services::accessible_diff::{self, AccessibleDiff};
+use crate::services::badge_scripts::{self, CustomBadge};
 use crate::services::gitlab_client::{GitLabClient, GitLabClientConfig};
+use crate::services::highlighter::{self, HighlightSpan, SyntaxHighlighter};
+use crate::services::ipc_compression;
+use crate::services::memory_profile::{self, Subsystem};
+use crate::services::quick_switch::{self, QuickSwitchEntry};
+use crate::services::secret_scan::{self, SecurityFinding};
+use crate::services::semantic_diff::{Lang, SemanticSummary};
+use crate::services::side_by_side;
+use crate::services::whitespace_diff;
+use crate::services::word_diff::{self, ChangeRange};
 use serde::{Deserialize, Serialize};
+use tauri::ipc::Response;
 use tauri::State;
 
 /// Filter options for get_merge_requests command.
 #[derive(Debug, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
 pub struct MergeRequestFilter {
     /// Filter by state: opened, merged, closed, or all.
     pub state: Option<String>,
@@ -21,6 +48,9 @@ pub struct MergeRequestFilter {
 
     /// Search in title and description.
     pub search: Option<String>,
+
+    /// Include bot-authored MRs (hidden by default).
+    pub include_bots: Option<bool>,
 }
 
 /// Response item for get_merge_requests command.
@@ -52,6 +82,18 @@ pub struct MergeRequestListItem {
     pub cached_at: i64,
     pub user_has_approved: bool,
     pub head_pipeline_status: Option<String>,
+    pub watched: bool,
+    pub is_bot_author: bool,
+    pub review_rerequested_at: Option<i64>,
+    pub task_count: i64,
+    pub task_completed_count: i64,
+    pub discussion_locked: bool,
+    pub changed_since_review: bool,
+    pub milestone_id: Option<i64>,
+    pub milestone_title: Option<String>,
+    pub milestone_due_date: Option<String>,
+    /// Badges from the user's badge rules; only set by the list queries.
+    pub custom_badges: Vec<CustomBadge>,
 }
 
 impl From<MergeRequest> for MergeRequestListItem {
@@ -84,24 +126,52 @@ impl From<MergeRequest> for MergeRequestListItem {
             cached_at: mr.cached_at,
             user_has_approved: mr.user_has_approved,
             head_pipeline_status: mr.head_pipeline_status,
+            watched: mr.watched,
+            is_bot_author: mr.is_bot_author,
+            review_rerequested_at: mr.review_rerequested_at,
+            task_count: mr.task_count,
+            task_completed_count: mr.task_completed_count,
+            discussion_locked: mr.discussion_locked,
+            changed_since_review: mr.changed_since_review,
+            milestone_id: mr.milestone_id,
+            milestone_title: mr.milestone_title,
+            milestone_due_date: mr.milestone_due_date,
+            custom_badges: Vec::new(),
         }
     }
 }
 
+/// List items for `rows`, with the user's badge rules applied.
+pub(crate) async fn list_items(
+    pool: &DbPool,
+    rows: Vec<MergeRequest>,
+) -> Result<Vec<MergeRequestListItem>, AppError> {
+    let badges = badge_scripts::evaluate(pool, &rows, chrono::Utc::now().timestamp()).await?;
+    Ok(rows
+        .into_iter()
+        .zip(badges)
+        .map(|(mr, custom_badges)| MergeRequestListItem {
+            custom_badges,
+            ..MergeRequestListItem::from(mr)
+        })
+        .collect())
+}
+
 /// Get cached merge requests from local storage.
 ///
-/// Returns instantly from the local SQLite cache.
+/// Returns instantly from the local SQLite cache. Reads go through the
+/// read-only pool so a running sync never delays them.
 /// No network requests are made.
 ///
 /// # Arguments
 /// * `instance_id` - GitLab instance to query
-/// * `filter` - Optional filter for state, scope, and search
+/// * `filter` - Optional filter for state, scope, search, and bot MRs
 ///
 /// # Returns
 /// Array of merge requests, empty if not yet synced.
 #[tauri::command]
 pub async fn get_merge_requests(
-    pool: State<'_, DbPool>,
+    pool: State<'_, ReadPool>,
     instance_id: i64,
     filter: Option<MergeRequestFilter>,
 ) -> Result<Vec<MergeRequestListItem>, AppError> {
@@ -112,10 +182,23 @@ pub async fn get_merge_requests(
         crate::core::mr_query::ReviewFilter {
             state: filter.state,
             search: filter.search,
+            include_bots: filter.include_bots.unwrap_or(false),
         },
     )
     .await?;
-    Ok(rows.into_iter().map(MergeRequestListItem::from).collect())
+    list_items(pool.inner(), rows).await
+}
+
+/// Get the quick switcher's ranked list of open MRs across all instances.
+///
+/// Served from an in-memory index the sync engine keeps current, so the
+/// switcher can filter it on every keystroke. Entries are ordered most
+/// recently updated first; `score` decays with the time since the update.
+#[tauri::command]
+pub async fn get_quick_switch_index(
+    pool: State<'_, ReadPool>,
+) -> Result<Vec<QuickSwitchEntry>, AppError> {
+    quick_switch::entries(pool.inner(), chrono::Utc::now().timestamp()).await
 }
 
 /// Get merge requests authored by the authenticated user.
@@ -133,7 +216,7 @@ pub async fn get_merge_requests(
 /// Array of authored merge requests, opened first then recently merged.
 #[tauri::command]
 pub async fn list_my_merge_requests(
-    pool: State<'_, DbPool>,
+    pool: State<'_, ReadPool>,
     instance_id: i64,
     include_recently_merged: Option<bool>,
     include_drafts: Option<bool>,
@@ -145,7 +228,7 @@ pub async fn list_my_merge_requests(
         include_drafts.unwrap_or(true),
     )
     .await?;
-    Ok(rows.into_iter().map(MergeRequestListItem::from).collect())
+    list_items(pool.inner(), rows).await
 }
 
 /// Diff summary information for an MR.
@@ -199,7 +282,7 @@ pub struct MergeRequestDetail {
 /// MR details with diff summary and pending action count.
 #[tauri::command]
 pub async fn get_merge_request_detail(
-    pool: State<'_, DbPool>,
+    pool: State<'_, ReadPool>,
     mr_id: i64,
 ) -> Result<MergeRequestDetail, AppError> {
     let detail = crate::core::mr_query::get_detail(pool.inner(), mr_id).await?;
@@ -352,6 +435,12 @@ pub struct DiffLine {
     pub content: String,
     pub old_line_number: Option<i64>,
     pub new_line_number: Option<i64>,
+    /// Syntax highlighting of `content`; empty when the language isn't
+    /// supported.
+    pub highlights: Vec<HighlightSpan>,
+    /// Byte ranges of `content` that differ from the line it replaced or
+    /// was replaced by; empty for context lines and unpaired changes.
+    pub word_changes: Vec<ChangeRange>,
 }
 
 /// A hunk in a diff.
@@ -373,6 +462,8 @@ pub struct DiffFileResponse {
     pub old_content: Option<String>,
     pub new_content: Option<String>,
     pub diff_hunks: Vec<DiffHunk>,
+    /// Whether whitespace-only changes were left out of the hunks.
+    pub whitespace_ignored: bool,
 }
 
 /// Metadata about a diff file for progressive loading.
@@ -399,6 +490,8 @@ pub struct DiffHunksResponse {
     pub start_index: usize,
     pub total_hunks: usize,
     pub has_more: bool,
+    /// Whether whitespace-only changes were left out of the hunks.
+    pub whitespace_ignored: bool,
 }
 
 /// Get diff files for a merge request.
@@ -407,13 +500,11 @@ pub struct DiffHunksResponse {
 /// * `mr_id` - The MR ID
 ///
 /// # Returns
-/// List of diff files.
+/// List of diff files, compressed when large (see `ipc_compression`).
 #[tauri::command]
-pub async fn get_diff_files(
-    pool: State<'_, DbPool>,
-    mr_id: i64,
-) -> Result<Vec<DiffFile>, AppError> {
-    crate::core::mr_query::get_diff_files(pool.inner(), mr_id).await
+pub async fn get_diff_files(pool: State<'_, DbPool>, mr_id: i64) -> Result<Response, AppError> {
+    let files = crate::core::mr_query::get_diff_files(pool.inner(), mr_id).await?;
+    ipc_compression::respond("get_diff_files", &files)
 }
 
 /// Get diff content for a specific file with syntax highlighting.
@@ -421,15 +512,18 @@ pub async fn get_diff_files(
 /// # Arguments
 /// * `mr_id` - The MR ID
 /// * `file_path` - The file path to get diff for
+/// * `ignore_whitespace` - Leave out whitespace-only changes (see `file_hunks`)
 ///
 /// # Returns
-/// Parsed diff with hunks and syntax-highlighted lines.
+/// Parsed diff with hunks and syntax-highlighted lines, compressed when
+/// large (see `ipc_compression`).
 #[tauri::command]
 pub async fn get_diff_file(
     pool: State<'_, DbPool>,
     mr_id: i64,
     file_path: String,
-) -> Result<DiffFileResponse, AppError> {
+    ignore_whitespace: Option<bool>,
+) -> Result<Response, AppError> {
     // Get the diff file
     let file: Option<DiffFile> = sqlx::query_as(
         r#"
@@ -447,19 +541,18 @@ pub async fn get_diff_file(
     let file =
         file.ok_or_else(|| AppError::not_found(format!("DiffFile for path: {}", file_path)))?;
 
-    // Parse the diff content into hunks
-    let diff_content = file.diff_content.unwrap_or_default();
-    let hunks = parse_unified_diff(&diff_content);
-
-    // Note: Syntax highlighting will be added in T037-T039.
-    // For now, we return the parsed hunks without highlighting tokens.
+    let (mut hunks, whitespace_ignored) =
+        file_hunks(pool.inner(), &file, ignore_whitespace.unwrap_or(false)).await?;
+    highlight_hunks(pool.inner(), mr_id, &file.new_path, &mut hunks).await?;
 
-    Ok(DiffFileResponse {
+    let response = DiffFileResponse {
         file_path: file.new_path,
         old_content: None, // Would require fetching from git or storing separately
         new_content: None,
         diff_hunks: hunks,
-    })
+        whitespace_ignored,
+    };
+    ipc_compression::respond("get_diff_file", &response)
 }
 
 /// Get metadata about a diff file without parsing all content.
@@ -523,6 +616,7 @@ pub async fn get_diff_file_metadata(
 /// * `file_path` - The file path
 /// * `start` - Starting hunk index (0-based)
 /// * `count` - Number of hunks to fetch
+/// * `ignore_whitespace` - Leave out whitespace-only changes (see `file_hunks`)
 ///
 /// # Returns
 /// The requested hunks with pagination info.
@@ -533,6 +627,7 @@ pub async fn get_diff_hunks(
     file_path: String,
     start: usize,
     count: usize,
+    ignore_whitespace: Option<bool>,
 ) -> Result<DiffHunksResponse, AppError> {
     // Get the diff file
     let file: Option<DiffFile> = sqlx::query_as(
@@ -552,18 +647,19 @@ pub async fn get_diff_hunks(
         file.ok_or_else(|| AppError::not_found(format!("DiffFile for path: {}", file_path)))?;
 
     // Parse all hunks (we need to parse the full diff to extract a range)
-    let diff_content = file.diff_content.unwrap_or_default();
-    let all_hunks = parse_unified_diff(&diff_content);
+    let (all_hunks, whitespace_ignored) =
+        file_hunks(pool.inner(), &file, ignore_whitespace.unwrap_or(false)).await?;
     let total_hunks = all_hunks.len();
 
     // Extract the requested range
     let end = (start + count).min(total_hunks);
-    let hunks: Vec<DiffHunk> = all_hunks
+    let mut hunks: Vec<DiffHunk> = all_hunks
         .into_iter()
         .skip(start)
         .take(end - start)
         .collect();
     let has_more = end < total_hunks;
+    highlight_hunks(pool.inner(), mr_id, &file.new_path, &mut hunks).await?;
 
     Ok(DiffHunksResponse {
         file_path: file.new_path,
@@ -571,14 +667,167 @@ pub async fn get_diff_hunks(
         start_index: start,
         total_hunks,
         has_more,
+        whitespace_ignored,
     })
 }
 
+/// Hunks of a diff file. With `ignore_whitespace`, they're recomputed from
+/// the cached base and head versions without whitespace-only changes;
+/// otherwise, and when that isn't possible (added or deleted file, a
+/// version not cached, too many changes), GitLab's diff is used. The flag
+/// says which one the hunks came from.
+pub async fn file_hunks(
+    pool: &DbPool,
+    file: &DiffFile,
+    ignore_whitespace: bool,
+) -> Result<(Vec<DiffHunk>, bool), AppError> {
+    if ignore_whitespace && file.change_type != "added" && file.change_type != "deleted" {
+        let old_path = file.old_path.as_deref().unwrap_or(&file.new_path);
+        let base =
+            crate::db::file_cache::get_cached_file_content(pool, file.mr_id, old_path, "base")
+                .await?;
+        let head = crate::db::file_cache::get_cached_file_content(
+            pool,
+            file.mr_id,
+            &file.new_path,
+            "head",
+        )
+        .await?;
+        if let (Some(base), Some(head)) = (base, head) {
+            // Diffing whole files is CPU-bound; keep it off the async workers.
+            let diff = tokio::task::spawn_blocking(move || {
+                let _mem = memory_profile::enter(Subsystem::DiffParsing);
+                whitespace_diff::unified_diff(&base, &head)
+            })
+            .await
+            .map_err(|e| AppError::internal(format!("Whitespace diff failed: {}", e)))?;
+            if let Some(diff) = diff {
+                return Ok((parse_unified_diff(&diff), true));
+            }
+        }
+    }
+    let diff_content = file.diff_content.as_deref().unwrap_or_default();
+    Ok((parse_unified_diff(diff_content), false))
+}
+
 /// Parse a unified diff into hunks (public alias for companion API).
 pub fn parse_unified_diff_public(diff: &str) -> Vec<DiffHunk> {
     parse_unified_diff(diff)
 }
 
+/// Add syntax highlighting to hunks of a file in the MR's cached diff.
+///
+/// The language comes from the file extension unless the project's
+/// `.gitattributes` sets `linguist-language` for the path. Files without a
+/// grammar are left as plain lines.
+pub async fn highlight_hunks(
+    pool: &DbPool,
+    mr_id: i64,
+    file_path: &str,
+    hunks: &mut [DiffHunk],
+) -> Result<(), AppError> {
+    let Some(lang) = highlight_language(pool, mr_id, file_path).await? else {
+        return Ok(());
+    };
+    let (base, head) = crate::db::file_cache::get_cached_file_pair(pool, mr_id, file_path).await?;
+    apply_highlights(hunks, lang, base.as_deref(), head.as_deref());
+    Ok(())
+}
+
+/// Grammar for a file of the MR: from the project's `.gitattributes`
+/// `linguist-language` when set for the path, else from the extension.
+async fn highlight_language(
+    pool: &DbPool,
+    mr_id: i64,
+    file_path: &str,
+) -> Result<Option<Lang>, AppError> {
+    let project: Option<(i64, i64)> =
+        sqlx::query_as("SELECT instance_id, project_id FROM merge_requests WHERE id = ?")
+            .bind(mr_id)
+            .fetch_optional(pool)
+            .await?;
+    let overrides = match project {
+        Some((instance_id, project_id)) => {
+            crate::core::cached_language_overrides(pool, instance_id, project_id).await?
+        }
+        None => Vec::new(),
+    };
+    Ok(highlighter::language_for(file_path, &overrides))
+}
+
+/// Highlight removed lines from the old side of the diff and the others
+/// from the new side.
+///
+/// Each side is highlighted from the whole cached file version when it
+/// agrees with the diff, so a hunk starting inside a block comment or string
+/// still comes out right. Otherwise the side's lines from the hunks are
+/// highlighted on their own.
+fn apply_highlights(hunks: &mut [DiffHunk], lang: Lang, base: Option<&str>, head: Option<&str>) {
+    let mut highlighter = SyntaxHighlighter::new();
+    for (old_side, file) in [(true, base), (false, head)] {
+        // (hunk index, line index, line number) of the side's lines. Context
+        // lines are on both sides but take the new side's highlighting.
+        let lines: Vec<(usize, usize, i64)> = hunks
+            .iter()
+            .enumerate()
+            .flat_map(|(h, hunk)| {
+                hunk.lines.iter().enumerate().filter_map(move |(l, line)| {
+                    let number = if old_side {
+                        line.old_line_number
+                    } else {
+                        line.new_line_number
+                    };
+                    number.map(|n| (h, l, n))
+                })
+            })
+            .collect();
+        if lines.is_empty() {
+            continue;
+        }
+        let content = |&(h, l, _): &(usize, usize, i64)| hunks[h].lines[l].content.as_str();
+
+        let file_lines: Vec<&str> = file.map(|f| f.lines().collect()).unwrap_or_default();
+        let in_file = |number: i64| {
+            usize::try_from(number - 1)
+                .ok()
+                .and_then(|i| file_lines.get(i).copied())
+        };
+        let matches_file = file.is_some()
+            && lines
+                .iter()
+                .all(|line| in_file(line.2) == Some(content(line)));
+
+        let highlighted: Vec<Vec<HighlightSpan>> = if matches_file {
+            let Some(spans) = highlighter.highlight(lang, file.unwrap_or_default()) else {
+                continue;
+            };
+            lines
+                .iter()
+                .map(|&(_, _, n)| {
+                    usize::try_from(n - 1)
+                        .ok()
+                        .and_then(|i| spans.get(i).cloned())
+                        .unwrap_or_default()
+                })
+                .collect()
+        } else {
+            let source = lines.iter().map(content).collect::<Vec<_>>().join("\n");
+            let Some(mut spans) = highlighter.highlight(lang, &source) else {
+                continue;
+            };
+            spans.resize(lines.len(), Vec::new());
+            spans
+        };
+
+        for ((h, l, _), spans) in lines.into_iter().zip(highlighted) {
+            let line = &mut hunks[h].lines[l];
+            if old_side == (line.line_type == "remove") {
+                line.highlights = spans;
+            }
+        }
+    }
+}
+
 /// Parse a unified diff into hunks.
 ///
 /// This parses the standard unified diff format:
@@ -589,6 +838,7 @@ pub fn parse_unified_diff_public(diff: &str) -> Vec<DiffHunk> {
 /// +added line
 /// ```
 fn parse_unified_diff(diff: &str) -> Vec<DiffHunk> {
+    let _mem = memory_profile::enter(Subsystem::DiffParsing);
     let mut hunks = Vec::new();
     let mut current_hunk: Option<DiffHunk> = None;
     let mut old_line = 0i64;
@@ -638,6 +888,8 @@ fn parse_unified_diff(diff: &str) -> Vec<DiffHunk> {
                 content,
                 old_line_number: old_ln,
                 new_line_number: new_ln,
+                highlights: Vec::new(),
+                word_changes: Vec::new(),
             });
         }
     }
@@ -647,9 +899,42 @@ fn parse_unified_diff(diff: &str) -> Vec<DiffHunk> {
         hunks.push(hunk);
     }
 
+    for hunk in &mut hunks {
+        refine_hunk(hunk);
+    }
     hunks
 }
 
+/// Pair each run of removed lines with the added lines right after it, in
+/// order, and mark the words that changed within each pair.
+fn refine_hunk(hunk: &mut DiffHunk) {
+    let lines = &mut hunk.lines;
+    let mut i = 0;
+    while i < lines.len() {
+        if lines[i].line_type != "remove" {
+            i += 1;
+            continue;
+        }
+        let removed_start = i;
+        while i < lines.len() && lines[i].line_type == "remove" {
+            i += 1;
+        }
+        let added_start = i;
+        while i < lines.len() && lines[i].line_type == "add" {
+            i += 1;
+        }
+
+        let pairs = (added_start - removed_start).min(i - added_start);
+        for offset in 0..pairs {
+            let (old, new) = (removed_start + offset, added_start + offset);
+            if let Some(changes) = word_diff::diff_words(&lines[old].content, &lines[new].content) {
+                lines[old].word_changes = changes.old;
+                lines[new].word_changes = changes.new;
+            }
+        }
+    }
+}
+
 /// Parse a hunk header line.
 ///
 /// Format: `@@ -old_start,old_count +new_start,new_count @@`
@@ -770,6 +1055,135 @@ pub async fn get_file_content_base64(
     Ok(STANDARD.encode(&bytes))
 }
 
+/// Get the blame of a file at an MR's head SHA.
+///
+/// Served from the `file_blame` cache when any MR at the same head commit
+/// already fetched it; otherwise fetched from GitLab and cached.
+///
+/// # Arguments
+/// * `mr_id` - Merge request ID
+/// * `file_path` - Path of the file at the head SHA
+#[tauri::command]
+pub async fn get_file_blame(
+    pool: State<'_, DbPool>,
+    mr_id: i64,
+    file_path: String,
+) -> Result<FileBlame, AppError> {
+    file_blame::mr_file_blame(&pool, mr_id, &file_path, chrono::Utc::now().timestamp()).await
+}
+
+/// Get the commits of an MR, oldest first, as of the last sync.
+///
+/// # Arguments
+/// * `mr_id` - Merge request ID
+#[tauri::command]
+pub async fn get_mr_commits(
+    pool: State<'_, DbPool>,
+    mr_id: i64,
+) -> Result<Vec<MrCommit>, AppError> {
+    Ok(crate::db::mr_commits::list_commits(pool.inner(), mr_id).await?)
+}
+
+/// Get the activity timeline of an MR (pushes, label, reviewer and state
+/// changes, ...), oldest first, as of the last sync.
+///
+/// # Arguments
+/// * `mr_id` - Merge request ID
+#[tauri::command]
+pub async fn get_mr_activity(
+    pool: State<'_, DbPool>,
+    mr_id: i64,
+) -> Result<Vec<MrEvent>, AppError> {
+    mr_activity::timeline(pool.inner(), mr_id).await
+}
+
+/// Get the lines changed per language in an MR, most changed first, for
+/// the "70% Rust, 20% SQL" summary in the MR header.
+///
+/// # Arguments
+/// * `mr_id` - Merge request ID
+#[tauri::command]
+pub async fn get_mr_language_breakdown(
+    pool: State<'_, DbPool>,
+    mr_id: i64,
+) -> Result<LanguageBreakdown, AppError> {
+    language_stats::get_language_breakdown(pool.inner(), mr_id).await
+}
+
+/// Get the files one commit of an MR changed, compared with its parent.
+///
+/// Served from the `commit_diffs` cache once the commit has been opened;
+/// otherwise fetched from GitLab and cached.
+///
+/// # Arguments
+/// * `mr_id` - Merge request ID
+/// * `sha` - SHA of one of the MR's commits
+///
+/// # Returns
+/// The commit with its file diffs, compressed when large (see `ipc_compression`).
+#[tauri::command]
+pub async fn get_commit_diff(
+    pool: State<'_, DbPool>,
+    mr_id: i64,
+    sha: String,
+) -> Result<Response, AppError> {
+    let diff =
+        mr_commits::commit_diff(pool.inner(), mr_id, &sha, chrono::Utc::now().timestamp()).await?;
+    ipc_compression::respond("get_commit_diff", &diff)
+}
+
+/// Mark a merge request reviewed at the head of its cached diff.
+///
+/// # Returns
+/// The head SHA recorded as reviewed.
+#[tauri::command]
+pub async fn mark_mr_reviewed(pool: State<'_, DbPool>, mr_id: i64) -> Result<String, AppError> {
+    review_progress::mark_reviewed(pool.inner(), mr_id).await
+}
+
+/// Get what changed in a merge request's files since it was last marked
+/// reviewed.
+///
+/// # Returns
+/// The file diffs between the reviewed and current head, compressed when
+/// large (see `ipc_compression`).
+#[tauri::command]
+pub async fn get_changes_since_review(
+    pool: State<'_, DbPool>,
+    mr_id: i64,
+) -> Result<Response, AppError> {
+    let changes = review_progress::changes_since_review(pool.inner(), mr_id).await?;
+    ipc_compression::respond("get_changes_since_review", &changes)
+}
+
+/// Mark a file of a merge request's diff viewed, or unmark it. The mark is
+/// kept across syncs until the file's diff changes.
+#[tauri::command]
+pub async fn mark_file_viewed(
+    pool: State<'_, DbPool>,
+    mr_id: i64,
+    file_path: String,
+    viewed: bool,
+) -> Result<(), AppError> {
+    file_review::mark_viewed(
+        pool.inner(),
+        mr_id,
+        &file_path,
+        viewed,
+        chrono::Utc::now().timestamp(),
+    )
+    .await
+}
+
+/// Get the files of a merge request marked viewed at its current head.
+#[tauri::command]
+pub async fn get_file_review_states(
+    pool: State<'_, DbPool>,
+    mr_id: i64,
+) -> Result<Vec<FileReviewState>, AppError> {
+    file_review::states(pool.inner(), mr_id).await
+}
+
 /// Response struct for cached file pair content.
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -797,6 +1211,164 @@ pub async fn get_cached_file_pair(
     })
 }
 
+/// Get a file's diff as aligned side-by-side rows.
+///
+/// Built from the cached base and head versions rather than GitLab's
+/// hunks, so every line of both files is present: unchanged lines face
+/// each other and changed runs are padded with placeholder rows. Lines are
+/// syntax highlighted and paired changes carry word-level ranges.
+///
+/// # Arguments
+/// * `mr_id` - The local MR database ID
+/// * `file_path` - Path of the file within the diff
+///
+/// # Returns
+/// Equally long left and right line arrays, compressed when large (see
+/// `ipc_compression`).
+#[tauri::command]
+pub async fn get_side_by_side_diff(
+    pool: State<'_, DbPool>,
+    mr_id: i64,
+    file_path: String,
+) -> Result<Response, AppError> {
+    let pool = pool.inner();
+    let (old_path, change_type): (Option<String>, String) = sqlx::query_as(
+        "SELECT old_path, change_type FROM diff_files WHERE mr_id = ? AND new_path = ?",
+    )
+    .bind(mr_id)
+    .bind(&file_path)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::not_found(format!("DiffFile for path: {}", file_path)))?;
+
+    // Added files have no base version and deleted files no head version.
+    let base = if change_type == "added" {
+        Some(String::new())
+    } else {
+        let old_path = old_path.as_deref().unwrap_or(&file_path);
+        crate::db::file_cache::get_cached_file_content(pool, mr_id, old_path, "base").await?
+    };
+    let head = if change_type == "deleted" {
+        Some(String::new())
+    } else {
+        crate::db::file_cache::get_cached_file_content(pool, mr_id, &file_path, "head").await?
+    };
+    let (Some(base), Some(head)) = (base, head) else {
+        return Err(AppError::not_found(format!(
+            "Cached file versions for {}",
+            file_path
+        )));
+    };
+
+    let lang = highlight_language(pool, mr_id, &file_path).await?;
+    let diff = tokio::task::spawn_blocking(move || {
+        let _mem = memory_profile::enter(Subsystem::DiffParsing);
+        side_by_side::align(&base, &head, lang)
+    })
+    .await
+    .map_err(|e| AppError::internal(format!("Side-by-side diff failed: {}", e)))?;
+    ipc_compression::respond("get_side_by_side_diff", &diff)
+}
+
+/// Summarise which functions, classes and types a file's change touches.
+///
+/// Parses the cached base and head versions with tree-sitter, so it works
+/// offline but only once the file cache has both sides (a missing side is
+/// treated as an added or deleted file). Summaries are cached per head
+/// commit and usually precomputed by the idle background job. Unsupported
+/// languages return an empty summary with `language: null`.
+///
+/// # Arguments
+/// * `mr_id` - The local MR database ID
+/// * `file_path` - Path of the file within the diff
+#[tauri::command]
+pub async fn get_semantic_summary(
+    pool: State<'_, DbPool>,
+    mr_id: i64,
+    file_path: String,
+) -> Result<SemanticSummary, AppError> {
+    semantic_summaries::summary_for(&pool, mr_id, &file_path, chrono::Utc::now().timestamp())
+        .await?
+        .ok_or_else(|| AppError::not_found_with_id("CachedFile", file_path))
+}
+
+/// Flag added lines in an MR's diff that look like leaked credentials.
+///
+/// Scans the cached per-file diffs, so it works offline once the diff has
+/// synced. Findings are ordered by file (diff order) and line; secret values
+/// in the excerpts are masked.
+///
+/// # Arguments
+/// * `mr_id` - The local MR database ID
+#[tauri::command]
+pub async fn get_security_findings(
+    pool: State<'_, ReadPool>,
+    mr_id: i64,
+) -> Result<Vec<SecurityFinding>, AppError> {
+    let files: Vec<(String, Option<String>)> = sqlx::query_as(
+        r#"
+        SELECT new_path, diff_content
+        FROM diff_files
+        WHERE mr_id = $1
+        ORDER BY file_position
+        "#,
+    )
+    .bind(mr_id)
+    .fetch_all(&**pool)
+    .await?;
+
+    tokio::task::spawn_blocking(move || {
+        let _mem = memory_profile::enter(Subsystem::DiffParsing);
+        files
+            .iter()
+            .flat_map(|(path, diff)| {
+                secret_scan::scan_diff(path, diff.as_deref().unwrap_or_default())
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| AppError::internal(format!("Security scan failed: {}", e)))
+}
+
+/// Linearized, labeled form of a file's diff for screen readers.
+///
+/// Each hunk starts with a summary sentence and every line is prefixed with
+/// its role and number ("added line 12"), so the frontend can render it as a
+/// plain list instead of a color-coded grid.
+///
+/// # Arguments
+/// * `mr_id` - The local MR database ID
+/// * `file_path` - Path of the file within the diff
+#[tauri::command]
+pub async fn get_accessible_diff(
+    pool: State<'_, ReadPool>,
+    mr_id: i64,
+    file_path: String,
+) -> Result<AccessibleDiff, AppError> {
+    let file: Option<DiffFile> = sqlx::query_as(
+        r#"
+        SELECT id, mr_id, old_path, new_path, change_type,
+               additions, deletions, file_position, diff_content
+        FROM diff_files
+        WHERE mr_id = $1 AND new_path = $2
+        "#,
+    )
+    .bind(mr_id)
+    .bind(&file_path)
+    .fetch_optional(&**pool)
+    .await?;
+
+    let file =
+        file.ok_or_else(|| AppError::not_found(format!("DiffFile for path: {}", file_path)))?;
+
+    Ok(accessible_diff::build(
+        &file.new_path,
+        file.old_path.as_deref(),
+        &file.change_type,
+        file.diff_content.as_deref().unwrap_or_default(),
+    ))
+}
+
 /// Response for resolve_mr_by_web_url command.
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -905,6 +1477,15 @@ pub async fn fetch_mr_by_web_url(
                 .unwrap_or_else(|_| "[]".to_string())
         })
         .unwrap_or_else(|| "[]".to_string());
+    let assignees_json = serde_json::to_string(
+        &gitlab_mr
+            .assignees
+            .iter()
+            .flatten()
+            .map(|u| &u.username)
+            .collect::<Vec<_>>(),
+    )
+    .unwrap_or_else(|_| "[]".to_string());
     let head_pipeline_status = gitlab_mr.head_pipeline.as_ref().map(|p| p.status.clone());
     let now = chrono::Utc::now().timestamp();
 
@@ -914,16 +1495,67 @@ pub async fn fetch_mr_by_web_url(
             id, instance_id, iid, project_id, title, description,
             author_username, source_branch, target_branch, state, web_url,
             created_at, updated_at, merged_at, labels, reviewers, cached_at,
-            project_name, head_pipeline_status
-        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            project_name, head_pipeline_status, description_preview, assignees,
+            milestone_id, milestone_title, milestone_due_date
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         ON CONFLICT(instance_id, project_id, iid) DO UPDATE SET
             title = excluded.title,
             description = excluded.description,
+            description_preview = excluded.description_preview,
             state = excluded.state,
             updated_at = excluded.updated_at,
             merged_at = excluded.merged_at,
-            labels = excluded.labels,
-            reviewers = excluded.reviewers,
+            -- Keep label changes that haven't been pushed yet.
+            labels = CASE
+                WHEN EXISTS (
+                    SELECT 1 FROM sync_queue
+                    WHERE mr_id = merge_requests.id AND action_type = 'updatelabels'
+                      AND status IN ('pending', 'syncing')
+                ) THEN merge_requests.labels
+                ELSE excluded.labels
+            END,
+            -- Same for reviewers and assignees.
+            reviewers = CASE
+                WHEN EXISTS (
+                    SELECT 1 FROM sync_queue
+                    WHERE mr_id = merge_requests.id AND action_type = 'setreviewers'
+                      AND status IN ('pending', 'syncing')
+                ) THEN merge_requests.reviewers
+                ELSE excluded.reviewers
+            END,
+            assignees = CASE
+                WHEN EXISTS (
+                    SELECT 1 FROM sync_queue
+                    WHERE mr_id = merge_requests.id AND action_type = 'setassignees'
+                      AND status IN ('pending', 'syncing')
+                ) THEN merge_requests.assignees
+                ELSE excluded.assignees
+            END,
+            -- And the milestone.
+            milestone_id = CASE
+                WHEN EXISTS (
+                    SELECT 1 FROM sync_queue
+                    WHERE mr_id = merge_requests.id AND action_type = 'setmilestone'
+                      AND status IN ('pending', 'syncing')
+                ) THEN merge_requests.milestone_id
+                ELSE excluded.milestone_id
+            END,
+            milestone_title = CASE
+                WHEN EXISTS (
+                    SELECT 1 FROM sync_queue
+                    WHERE mr_id = merge_requests.id AND action_type = 'setmilestone'
+                      AND status IN ('pending', 'syncing')
+                ) THEN merge_requests.milestone_title
+                ELSE excluded.milestone_title
+            END,
+            milestone_due_date = CASE
+                WHEN EXISTS (
+                    SELECT 1 FROM sync_queue
+                    WHERE mr_id = merge_requests.id AND action_type = 'setmilestone'
+                      AND status IN ('pending', 'syncing')
+                ) THEN merge_requests.milestone_due_date
+                ELSE excluded.milestone_due_date
+            END,
             cached_at = excluded.cached_at,
             project_name = excluded.project_name,
             head_pipeline_status = COALESCE(excluded.head_pipeline_status, merge_requests.head_pipeline_status)
@@ -948,6 +1580,14 @@ pub async fn fetch_mr_by_web_url(
     .bind(now)
     .bind(&project_path)
     .bind(&head_pipeline_status)
+    .bind(description_preview(
+        gitlab_mr.description.as_deref(),
+        DEFAULT_DESCRIPTION_PREVIEW_CHARS,
+    ))
+    .bind(&assignees_json)
+    .bind(gitlab_mr.milestone.as_ref().map(|m| m.id))
+    .bind(gitlab_mr.milestone.as_ref().map(|m| m.title.as_str()))
+    .bind(gitlab_mr.milestone.as_ref().and_then(|m| m.due_date.as_deref()))
     .execute(pool.inner())
     .await?;
 
@@ -1036,6 +1676,20 @@ pub async fn check_merge_status(pool: State<'_, DbPool>, mr_id: i64) -> Result<S
         .unwrap_or_else(|| "unknown".into()))
 }
 
+/// Everything blocking an MR's merge: unresolved threads, pipeline,
+/// approvals per rule, draft status and conflicts.
+///
+/// Computed from the cache unless `refresh` is set, in which case the state
+/// is read from GitLab and saved.
+#[tauri::command]
+pub async fn get_merge_blockers(
+    pool: State<'_, DbPool>,
+    mr_id: i64,
+    refresh: Option<bool>,
+) -> Result<MergeBlockers, AppError> {
+    merge_blockers::get_merge_blockers(pool.inner(), mr_id, refresh.unwrap_or(false)).await
+}
+
 /// Fetch pipelines associated with a merge request.
 ///
 /// Returns up to ~20 pipelines newest-first (MR pipelines + branch pipelines
@@ -1095,6 +1749,24 @@ pub async fn undraft_mr(pool: State<'_, DbPool>, mr_id: i64) -> Result<String, A
     crate::core::mr_actions::undraft(pool.inner(), mr_id).await
 }
 
+/// Run optional cleanup tasks after one of the user's MRs has merged.
+///
+/// # Arguments
+/// * `mr_id` - The local MR database ID
+/// * `tasks` - Tasks to run, in order (`delete_source_branch`,
+///   `close_linked_issues`, `trigger_pipeline`)
+///
+/// # Returns
+/// One result per task; a failed task doesn't stop the rest.
+#[tauri::command]
+pub async fn run_post_merge_tasks(
+    pool: State<'_, DbPool>,
+    mr_id: i64,
+    tasks: Vec<PostMergeTask>,
+) -> Result<Vec<PostMergeTaskResult>, AppError> {
+    crate::core::post_merge::run_post_merge_tasks(pool.inner(), mr_id, &tasks).await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1134,4 +1806,126 @@ mod tests {
         assert_eq!(hunks[0].lines[1].line_type, "remove");
         assert_eq!(hunks[0].lines[2].line_type, "add");
     }
+
+    #[test]
+    fn test_parse_unified_diff_marks_changed_words() {
+        let diff = "@@ -1,4 +1,4 @@\n-let a = 1;\n-let b = 2;\n+let a = 10;\n+fn unrelated() {}\n+let c = 3;\n same";
+        let hunks = parse_unified_diff(diff);
+        let changed = |i: usize| -> Vec<&str> {
+            let line = &hunks[0].lines[i];
+            line.word_changes
+                .iter()
+                .map(|r| &line.content[r.start..r.end])
+                .collect()
+        };
+
+        // First removed line pairs with the first added line.
+        assert_eq!(changed(0), vec!["1"]);
+        assert_eq!(changed(2), vec!["10"]);
+        // The second pair has nothing in common; the extra added line and
+        // context lines are unpaired.
+        assert!(changed(1).is_empty());
+        assert!(changed(3).is_empty());
+        assert!(changed(4).is_empty());
+        assert!(changed(5).is_empty());
+    }
+
+    #[test]
+    fn test_apply_highlights_per_side() {
+        let diff = "@@ -1,3 +1,3 @@\n /* start\n-let a = 1;\n+let b = \"x\";\n end */";
+        let tokens = |hunks: &[DiffHunk], i: usize| -> Vec<&'static str> {
+            hunks[0].lines[i]
+                .highlights
+                .iter()
+                .map(|s| s.token_type)
+                .collect()
+        };
+
+        // Without cached files each side's lines are highlighted together.
+        let mut hunks = parse_unified_diff(diff);
+        apply_highlights(&mut hunks, Lang::Rust, None, None);
+        assert_eq!(tokens(&hunks, 1), vec!["comment"]);
+        assert_eq!(tokens(&hunks, 2), vec!["comment"]);
+
+        // Cached files that match are used, so a comment opened above the
+        // hunk is seen.
+        let base = "fn f() {\n/* start\nlet a = 1;\nend */";
+        let head = "fn f() {\nlet b = \"x\";\nend */";
+        let diff = "@@ -3,1 +2,1 @@\n-let a = 1;\n+let b = \"x\";";
+        let mut hunks = parse_unified_diff(diff);
+        apply_highlights(&mut hunks, Lang::Rust, Some(base), Some(head));
+        assert_eq!(tokens(&hunks, 0), vec!["comment"]);
+        assert!(tokens(&hunks, 1).contains(&"keyword"));
+        assert!(tokens(&hunks, 1).contains(&"string"));
+
+        // A stale cached file falls back to the hunk lines.
+        let mut hunks = parse_unified_diff(diff);
+        apply_highlights(&mut hunks, Lang::Rust, Some("other"), None);
+        assert!(tokens(&hunks, 0).contains(&"keyword"));
+    }
+
+    #[tokio::test]
+    async fn test_file_hunks_ignores_whitespace_from_cached_versions() {
+        let (pool, inst) = crate::core::tests::seed_instance(true).await;
+        let base = "fn f() {\nlet a = 1;\nlet b = 2;\n}\n";
+        let head = "fn f() {\n    let a = 1;\n    let b = 3;\n}\n";
+        for (version, path, content) in [("base", "old.rs", base), ("head", "new.rs", head)] {
+            crate::db::file_cache::upsert_file_blob(&pool, version, content, 0)
+                .await
+                .unwrap();
+            crate::db::file_cache::upsert_file_version(
+                &pool,
+                1,
+                path,
+                version,
+                version,
+                &inst.to_string(),
+                10,
+            )
+            .await
+            .unwrap();
+        }
+        let mut file = DiffFile {
+            id: 1,
+            mr_id: 1,
+            old_path: Some("old.rs".to_string()),
+            new_path: "new.rs".to_string(),
+            change_type: "renamed".to_string(),
+            additions: 2,
+            deletions: 2,
+            file_position: 0,
+            diff_content: Some(
+                "@@ -2,2 +2,2 @@\n-let a = 1;\n-let b = 2;\n+    let a = 1;\n+    let b = 3;"
+                    .to_string(),
+            ),
+        };
+
+        let (hunks, ignored) = file_hunks(&pool, &file, false).await.unwrap();
+        assert!(!ignored);
+        assert_eq!(hunks[0].lines.len(), 4);
+
+        // The re-indented `a` line becomes context; only `b` changed.
+        let (hunks, ignored) = file_hunks(&pool, &file, true).await.unwrap();
+        assert!(ignored);
+        let lines: Vec<(&str, &str)> = hunks[0]
+            .lines
+            .iter()
+            .map(|l| (l.line_type.as_str(), l.content.as_str()))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                ("context", "fn f() {"),
+                ("context", "    let a = 1;"),
+                ("remove", "let b = 2;"),
+                ("add", "    let b = 3;"),
+                ("context", "}"),
+            ]
+        );
+
+        // Added files have nothing to compare against.
+        file.change_type = "added".to_string();
+        let (_, ignored) = file_hunks(&pool, &file, true).await.unwrap();
+        assert!(!ignored);
+    }
 }
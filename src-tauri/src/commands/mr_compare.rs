@@ -0,0 +1,26 @@
+//! MR comparison command.
+//!
+//! Shows whether two open MRs of the same project touch the same files and
+//! lines, i.e. whether merging one is likely to conflict with the other.
+
+use crate::core::mr_compare::{self, MrComparison};
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use tauri::State;
+
+/// Compare two open MRs of the same project using their cached diffs.
+///
+/// # Arguments
+/// * `mr_id_a` - The first MR ID
+/// * `mr_id_b` - The second MR ID
+///
+/// # Returns
+/// Files both MRs change, with the hunk ranges that collide on the target branch.
+#[tauri::command]
+pub async fn compare_mrs(
+    pool: State<'_, DbPool>,
+    mr_id_a: i64,
+    mr_id_b: i64,
+) -> Result<MrComparison, AppError> {
+    mr_compare::compare_mrs(&pool, mr_id_a, mr_id_b).await
+}
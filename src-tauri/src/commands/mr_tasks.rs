@@ -0,0 +1,39 @@
+//! Commands for task lists in MR descriptions.
+
+use crate::core::mr_tasks::{self, MrTaskList};
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use crate::services::sync_engine::SyncHandle;
+use tauri::State;
+
+/// Get the task list items of an MR description with progress counts.
+#[tauri::command]
+pub async fn get_mr_tasks(pool: State<'_, DbPool>, mr_id: i64) -> Result<MrTaskList, AppError> {
+    mr_tasks::get_mr_tasks(pool.inner(), mr_id).await
+}
+
+/// Check or uncheck a task in an MR description.
+///
+/// The cached description is updated immediately; the edit is queued and
+/// pushed to GitLab in the background.
+///
+/// # Arguments
+/// * `mr_id` - Merge request ID
+/// * `index` - Task index, as returned by `get_mr_tasks`
+/// * `checked` - New state of the checkbox
+#[tauri::command]
+pub async fn toggle_mr_task(
+    pool: State<'_, DbPool>,
+    sync_handle: State<'_, SyncHandle>,
+    mr_id: i64,
+    index: i64,
+    checked: bool,
+) -> Result<MrTaskList, AppError> {
+    let tasks = mr_tasks::toggle_mr_task(pool.inner(), mr_id, index, checked).await?;
+
+    if let Err(e) = sync_handle.flush_comments().await {
+        log::warn!("[tasks] Failed to send flush signal: {}", e);
+    }
+
+    Ok(tasks)
+}
@@ -1,11 +1,21 @@
-//! Notification settings commands.
+//! Notification settings commands, including per-project muting.
 
+use crate::db::muted_projects::{self, MutedProject};
 use crate::db::notification_settings as db;
 use crate::db::pool::DbPool;
 use crate::error::AppError;
 use crate::models::NotificationSettings;
+use crate::services::sync_engine::SyncHandle;
+use std::time::{SystemTime, UNIX_EPOCH};
 use tauri::State;
 
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
 /// Get the current notification settings.
 #[tauri::command]
 pub async fn get_notification_settings(
@@ -24,3 +34,51 @@ pub async fn update_notification_settings(
     db::update_notification_settings(pool.inner(), &settings).await?;
     Ok(())
 }
+
+/// Mute a project until `until` (Unix seconds), or until unmuted when
+/// `until` is omitted. Its MRs and pipelines keep syncing, but raise no
+/// notifications and don't count toward the dock badge.
+#[tauri::command]
+pub async fn mute_project(
+    pool: State<'_, DbPool>,
+    sync_handle: State<'_, SyncHandle>,
+    instance_id: i64,
+    project_id: i64,
+    until: Option<i64>,
+) -> Result<(), AppError> {
+    let now = now();
+    if until.is_some_and(|t| t <= now) {
+        return Err(AppError::invalid_input_field(
+            "Mute end must be in the future",
+            "until",
+        ));
+    }
+    muted_projects::mute(pool.inner(), instance_id, project_id, until, now).await?;
+    // Best-effort: the badge drops the project right away.
+    let _ = sync_handle.refresh_badge().await;
+    Ok(())
+}
+
+/// Unmute a project.
+#[tauri::command]
+pub async fn unmute_project(
+    pool: State<'_, DbPool>,
+    sync_handle: State<'_, SyncHandle>,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<(), AppError> {
+    if !muted_projects::unmute(pool.inner(), instance_id, project_id).await? {
+        return Err(AppError::not_found_with_id(
+            "MutedProject",
+            project_id.to_string(),
+        ));
+    }
+    let _ = sync_handle.refresh_badge().await;
+    Ok(())
+}
+
+/// Projects currently muted, across all instances.
+#[tauri::command]
+pub async fn list_muted_projects(pool: State<'_, DbPool>) -> Result<Vec<MutedProject>, AppError> {
+    Ok(muted_projects::list_muted(pool.inner(), now()).await?)
+}
@@ -1,6 +1,13 @@
 //! Pipeline dashboard commands for managing tracked projects and their pipeline statuses.
 
+use crate::core::manual_jobs::{JobRef, PendingManualJob, PlayResult};
+use crate::core::pipeline_graph::{GraphEdge, GraphStage};
+use crate::core::pipeline_notify::NotifyRule;
+use crate::core::pipelines::TraceChunk;
+use crate::core::releases::{self, ReleaseLink};
+use crate::db::pipeline_cache::CachedPipelineSchedule;
 use crate::db::pool::DbPool;
+use crate::db::release_cache::{CachedRelease, CachedTag};
 use crate::error::AppError;
 use crate::models::pipeline_project::PipelineProject;
 use crate::models::project::{self, Project};
@@ -67,6 +74,126 @@ pub struct DownstreamPipeline {
     pub web_url: String,
 }
 
+/// Pipeline dependency graph returned to the frontend.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PipelineGraphResponse {
+    pub stages: Vec<GraphStage>,
+    pub jobs: Vec<PipelineJob>,
+    pub edges: Vec<GraphEdge>,
+    /// False when `needs:` couldn't be loaded and edges follow stages only.
+    pub needs_known: bool,
+}
+
+/// Pipeline schedule DTO returned to the frontend.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PipelineSchedule {
+    pub id: i64,
+    pub project_id: i64,
+    pub description: String,
+    pub ref_name: String,
+    pub cron: String,
+    pub cron_timezone: String,
+    pub next_run_at: Option<String>,
+    pub active: bool,
+    pub owner_username: Option<String>,
+}
+
+fn to_schedule_dto(s: CachedPipelineSchedule) -> PipelineSchedule {
+    PipelineSchedule {
+        id: s.schedule_id,
+        project_id: s.project_id,
+        description: s.description,
+        ref_name: s.ref_name,
+        cron: s.cron,
+        cron_timezone: s.cron_timezone,
+        next_run_at: s.next_run_at,
+        active: s.active,
+        owner_username: s.owner_username,
+    }
+}
+
+/// Project release DTO returned to the frontend.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectRelease {
+    pub project_id: i64,
+    pub tag_name: String,
+    pub name: Option<String>,
+    /// Changelog / release notes (Markdown).
+    pub description: Option<String>,
+    pub created_at: String,
+    pub released_at: Option<String>,
+    pub upcoming_release: bool,
+    pub author_username: Option<String>,
+    pub commit_sha: Option<String>,
+    pub web_url: Option<String>,
+    pub links: Vec<ReleaseLink>,
+}
+
+fn to_release_dto(r: CachedRelease) -> ProjectRelease {
+    let links = releases::release_links(&r);
+    ProjectRelease {
+        project_id: r.project_id,
+        tag_name: r.tag_name,
+        name: r.name,
+        description: r.description,
+        created_at: r.created_at,
+        released_at: r.released_at,
+        upcoming_release: r.upcoming_release,
+        author_username: r.author_username,
+        commit_sha: r.commit_sha,
+        web_url: r.web_url,
+        links,
+    }
+}
+
+/// Project tag DTO returned to the frontend.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectTag {
+    pub project_id: i64,
+    pub name: String,
+    pub message: Option<String>,
+    pub commit_sha: Option<String>,
+    pub commit_title: Option<String>,
+    pub committed_at: Option<String>,
+    pub protected: bool,
+    pub has_release: bool,
+}
+
+fn to_tag_dto(t: CachedTag) -> ProjectTag {
+    ProjectTag {
+        project_id: t.project_id,
+        name: t.name,
+        message: t.message,
+        commit_sha: t.commit_sha,
+        commit_title: t.commit_title,
+        committed_at: t.committed_at,
+        protected: t.protected,
+        has_release: t.has_release,
+    }
+}
+
+/// Part of a job log DTO returned to the frontend. `offset` and `totalBytes`
+/// let the viewer fetch earlier output with `get_job_trace_range`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobTrace {
+    pub content: String,
+    pub offset: u64,
+    pub total_bytes: u64,
+}
+
+fn to_trace_dto(c: TraceChunk) -> JobTrace {
+    JobTrace {
+        content: c.content,
+        offset: c.offset,
+        total_bytes: c.total_bytes,
+    }
+}
+
 fn to_status_dto(p: GitLabPipeline) -> PipelineStatus {
     PipelineStatus {
         id: p.id,
@@ -136,6 +263,30 @@ pub async fn toggle_pin_pipeline_project(
     crate::core::pipelines::toggle_pin(pool.inner(), instance_id, project_id).await
 }
 
+/// Set when a pinned pipeline project's pipelines notify.
+#[tauri::command]
+pub async fn set_pipeline_notify_rule(
+    pool: State<'_, DbPool>,
+    instance_id: i64,
+    project_id: i64,
+    rule: NotifyRule,
+) -> Result<(), AppError> {
+    crate::core::pipeline_notify::set_rule(pool.inner(), instance_id, project_id, rule).await
+}
+
+/// Turn notifications about new manual jobs on a pinned project's default
+/// branch on or off.
+#[tauri::command]
+pub async fn set_pipeline_manual_gate_notify(
+    pool: State<'_, DbPool>,
+    instance_id: i64,
+    project_id: i64,
+    enabled: bool,
+) -> Result<(), AppError> {
+    crate::core::pipeline_notify::set_manual_gates(pool.inner(), instance_id, project_id, enabled)
+        .await
+}
+
 /// Persist a new ordering for pinned pipeline projects.
 #[tauri::command]
 pub async fn reorder_pinned_pipeline_projects(
@@ -211,6 +362,29 @@ pub async fn get_pipeline_jobs(
     Ok(jobs.into_iter().map(to_job_dto).collect())
 }
 
+/// Fetch a pipeline's jobs with their stage and `needs:` dependencies.
+#[tauri::command]
+pub async fn get_pipeline_graph(
+    pool: State<'_, DbPool>,
+    instance_id: i64,
+    project_id: i64,
+    pipeline_id: i64,
+) -> Result<PipelineGraphResponse, AppError> {
+    let graph = crate::core::pipeline_graph::pipeline_graph(
+        pool.inner(),
+        instance_id,
+        project_id,
+        pipeline_id,
+    )
+    .await?;
+    Ok(PipelineGraphResponse {
+        stages: graph.stages,
+        jobs: graph.jobs.into_iter().map(to_job_dto).collect(),
+        edges: graph.edges,
+        needs_known: graph.needs_known,
+    })
+}
+
 /// Fetch recent pipelines for a project.
 #[tauri::command]
 pub async fn get_project_pipelines(
@@ -235,6 +409,25 @@ pub async fn play_pipeline_job(
     Ok(to_job_dto(j))
 }
 
+/// Manual jobs waiting in the latest pipelines of the dashboard projects of
+/// every instance, pinned projects first.
+#[tauri::command]
+pub async fn get_pending_manual_jobs(
+    pool: State<'_, DbPool>,
+) -> Result<Vec<PendingManualJob>, AppError> {
+    crate::core::manual_jobs::pending_manual_jobs(pool.inner()).await
+}
+
+/// Play several manual jobs at once. Each job reports its own outcome, so
+/// one failure doesn't hide the others.
+#[tauri::command]
+pub async fn play_jobs(
+    pool: State<'_, DbPool>,
+    job_refs: Vec<JobRef>,
+) -> Result<Vec<PlayResult>, AppError> {
+    Ok(crate::core::manual_jobs::play_jobs(pool.inner(), &job_refs).await)
+}
+
 /// Retry a failed or canceled job. Returns the new job.
 #[tauri::command]
 pub async fn retry_pipeline_job(
@@ -271,16 +464,145 @@ pub async fn cancel_pipeline(
     Ok(to_status_dto(p))
 }
 
-/// Fetch the raw log trace for a specific job.
+/// Fetch a project's pipeline schedules and refresh the local cache.
+#[tauri::command]
+pub async fn list_pipeline_schedules(
+    pool: State<'_, DbPool>,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<Vec<PipelineSchedule>, AppError> {
+    let schedules =
+        crate::core::pipelines::list_schedules(pool.inner(), instance_id, project_id).await?;
+    Ok(schedules.into_iter().map(to_schedule_dto).collect())
+}
+
+/// Load cached pipeline schedules from the local DB for instant display.
+#[tauri::command]
+pub async fn get_cached_pipeline_schedules(
+    pool: State<'_, DbPool>,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<Vec<PipelineSchedule>, AppError> {
+    let schedules =
+        crate::core::pipelines::cached_schedules(pool.inner(), instance_id, project_id).await?;
+    Ok(schedules.into_iter().map(to_schedule_dto).collect())
+}
+
+/// Run a pipeline schedule immediately, outside its cron timing.
+#[tauri::command]
+pub async fn run_schedule_now(
+    pool: State<'_, DbPool>,
+    instance_id: i64,
+    project_id: i64,
+    schedule_id: i64,
+) -> Result<(), AppError> {
+    crate::core::pipelines::run_schedule(pool.inner(), instance_id, project_id, schedule_id).await
+}
+
+/// Activate or deactivate a pipeline schedule. Returns the updated schedule.
+#[tauri::command]
+pub async fn toggle_schedule_active(
+    pool: State<'_, DbPool>,
+    instance_id: i64,
+    project_id: i64,
+    schedule_id: i64,
+    active: bool,
+) -> Result<PipelineSchedule, AppError> {
+    let s = crate::core::pipelines::set_schedule_active(
+        pool.inner(),
+        instance_id,
+        project_id,
+        schedule_id,
+        active,
+    )
+    .await?;
+    Ok(to_schedule_dto(s))
+}
+
+/// Fetch a project's recent releases and refresh the local cache.
+#[tauri::command]
+pub async fn list_project_releases(
+    pool: State<'_, DbPool>,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<Vec<ProjectRelease>, AppError> {
+    let releases = releases::list_releases(pool.inner(), instance_id, project_id).await?;
+    Ok(releases.into_iter().map(to_release_dto).collect())
+}
+
+/// Load cached releases from the local DB for instant display.
+#[tauri::command]
+pub async fn get_cached_project_releases(
+    pool: State<'_, DbPool>,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<Vec<ProjectRelease>, AppError> {
+    let releases = releases::cached_releases(pool.inner(), instance_id, project_id).await?;
+    Ok(releases.into_iter().map(to_release_dto).collect())
+}
+
+/// Fetch a project's recent tags and refresh the local cache.
+#[tauri::command]
+pub async fn list_project_tags(
+    pool: State<'_, DbPool>,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<Vec<ProjectTag>, AppError> {
+    let tags = releases::list_tags(pool.inner(), instance_id, project_id).await?;
+    Ok(tags.into_iter().map(to_tag_dto).collect())
+}
+
+/// Load cached tags from the local DB for instant display.
+#[tauri::command]
+pub async fn get_cached_project_tags(
+    pool: State<'_, DbPool>,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<Vec<ProjectTag>, AppError> {
+    let tags = releases::cached_tags(pool.inner(), instance_id, project_id).await?;
+    Ok(tags.into_iter().map(to_tag_dto).collect())
+}
+
+/// Fetch the end of the log trace for a specific job.
+///
+/// Returns the last few hundred KB; earlier output is loaded on demand with
+/// `get_job_trace_range`. Failed jobs on the user's MRs are served from the
+/// sync prefetch cache; everything else is fetched live.
 #[tauri::command]
 pub async fn get_job_trace(
     pool: State<'_, DbPool>,
     instance_id: i64,
     project_id: i64,
     job_id: i64,
-) -> Result<String, AppError> {
-    let client = create_gitlab_client(&pool, instance_id).await?;
-    client.get_job_trace(project_id, job_id).await
+) -> Result<JobTrace, AppError> {
+    crate::core::pipelines::job_trace(pool.inner(), instance_id, project_id, job_id)
+        .await
+        .map(to_trace_dto)
+}
+
+/// Fetch `length` bytes of a job's log trace starting at `offset`.
+///
+/// The returned chunk starts at a line boundary, so its `offset` can be past
+/// the requested one.
+#[tauri::command]
+pub async fn get_job_trace_range(
+    pool: State<'_, DbPool>,
+    instance_id: i64,
+    project_id: i64,
+    job_id: i64,
+    offset: i64,
+    length: i64,
+) -> Result<JobTrace, AppError> {
+    crate::core::pipelines::job_trace_range(
+        pool.inner(),
+        instance_id,
+        project_id,
+        job_id,
+        offset,
+        length,
+    )
+    .await
+    .map(to_trace_dto)
 }
 
 /// Resolve a project by its path (e.g. "group/subgroup/project") and return its numeric ID and name.
@@ -0,0 +1,81 @@
+//! Project README commands.
+//!
+//! Serves the README shown in the MR detail context panel. Like the
+//! gitattributes cache, this is stale-while-revalidate: cached HTML is
+//! returned immediately and refreshed in the background once it is a day
+//! old; only a cache miss waits on the network.
+
+use crate::core::project_readme::{self, is_stale};
+use crate::db::pool::DbPool;
+use crate::db::project_readme::CachedReadme;
+use crate::error::AppError;
+use serde::Serialize;
+use tauri::State;
+
+/// A project's README rendered to HTML.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectReadme {
+    /// Ref the README was read at (the MR's target branch).
+    pub ref_name: String,
+    /// Path of the README in the repository, or null when the project has none.
+    pub file_path: Option<String>,
+    /// HTML rendered by GitLab; empty when there is no README.
+    pub html: String,
+    /// Unix timestamp of when the README was fetched.
+    pub fetched_at: i64,
+}
+
+impl From<CachedReadme> for ProjectReadme {
+    fn from(r: CachedReadme) -> Self {
+        Self {
+            ref_name: r.ref_name,
+            file_path: r.file_path,
+            html: r.html,
+            fetched_at: r.fetched_at,
+        }
+    }
+}
+
+/// Get the README of a project at a ref, typically the MR's target branch.
+///
+/// # Arguments
+/// * `instance_id` - The GitLab instance ID
+/// * `project_id` - The GitLab project ID
+/// * `ref_name` - Branch, tag or SHA to read the README at
+#[tauri::command]
+pub async fn get_project_readme(
+    pool: State<'_, DbPool>,
+    instance_id: i64,
+    project_id: i64,
+    ref_name: String,
+) -> Result<ProjectReadme, AppError> {
+    let cached = project_readme::cached_readme(&pool, instance_id, project_id, &ref_name).await?;
+
+    match cached {
+        Some(readme) => {
+            if is_stale(&readme, chrono::Utc::now().timestamp()) {
+                let bg_pool = pool.inner().clone();
+                tokio::spawn(async move {
+                    if let Err(e) =
+                        project_readme::refresh_readme(&bg_pool, instance_id, project_id, &ref_name)
+                            .await
+                    {
+                        log::warn!(
+                            "[readme] Background refresh failed for instance={} project={}: {}",
+                            instance_id,
+                            project_id,
+                            e
+                        );
+                    }
+                });
+            }
+            Ok(readme.into())
+        }
+        None => Ok(
+            project_readme::refresh_readme(&pool, instance_id, project_id, &ref_name)
+                .await?
+                .into(),
+        ),
+    }
+}
@@ -0,0 +1,38 @@
+//! Data retention policy commands.
+//!
+//! Per-instance retention settings plus an audit of what the local cache
+//! currently holds, for security reviews.
+
+use crate::core::retention::{self, InstanceDataAudit, RetentionPolicy, RetentionSweep};
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use chrono::Utc;
+use tauri::State;
+
+/// Get an instance's retention policy (the defaults if none is set).
+#[tauri::command]
+pub async fn get_retention_policy(
+    pool: State<'_, DbPool>,
+    instance_id: i64,
+) -> Result<RetentionPolicy, AppError> {
+    retention::load_policy(pool.inner(), instance_id).await
+}
+
+/// Save an instance's retention policy and apply it to the existing cache
+/// right away, returning what was removed.
+#[tauri::command]
+pub async fn update_retention_policy(
+    pool: State<'_, DbPool>,
+    policy: RetentionPolicy,
+) -> Result<RetentionSweep, AppError> {
+    retention::save_policy(pool.inner(), &policy).await?;
+    retention::enforce(pool.inner(), &policy, Utc::now().timestamp()).await
+}
+
+/// List the categories of cached data held for each instance.
+#[tauri::command]
+pub async fn audit_cached_data(
+    pool: State<'_, DbPool>,
+) -> Result<Vec<InstanceDataAudit>, AppError> {
+    retention::audit(pool.inner()).await
+}
@@ -0,0 +1,45 @@
+//! Review assistant commands. Only contact the configured model provider
+//! once the assistant has been enabled in settings.
+
+use super::settings::load_settings;
+use crate::core::review_assistant::{self, AssistantReply};
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use tauri::{AppHandle, State};
+
+/// Draft a review comment for a line range of a file in the MR diff.
+///
+/// # Arguments
+/// * `file_path` - New path of the file
+/// * `start_line`, `end_line` - Inclusive range of new-file line numbers
+#[tauri::command]
+pub async fn suggest_comment(
+    app: AppHandle,
+    pool: State<'_, DbPool>,
+    mr_id: i64,
+    file_path: String,
+    start_line: i64,
+    end_line: i64,
+) -> Result<AssistantReply, AppError> {
+    let settings = load_settings(&app).await?.review_assistant;
+    review_assistant::suggest_comment(
+        pool.inner(),
+        &settings,
+        mr_id,
+        &file_path,
+        start_line,
+        end_line,
+    )
+    .await
+}
+
+/// Summarize an MR from its cached description and diffs.
+#[tauri::command]
+pub async fn summarize_mr(
+    app: AppHandle,
+    pool: State<'_, DbPool>,
+    mr_id: i64,
+) -> Result<AssistantReply, AppError> {
+    let settings = load_settings(&app).await?.review_assistant;
+    review_assistant::summarize_mr(pool.inner(), &settings, mr_id).await
+}
@@ -0,0 +1,52 @@
+//! Review stats and review SLA commands.
+//!
+//! Streaks, weekly comment counts and median response time are opt-in and
+//! computed from a local activity log that is only written while the user
+//! has opted in. SLA status comes from the cached reviewers.
+
+use super::settings::load_settings;
+use crate::core::review_sla::{self, ReviewSlaStatus};
+use crate::core::review_stats::{self, ReviewStatsExport, ReviewStreaks};
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use chrono::{FixedOffset, Local, Offset, Utc};
+use tauri::{AppHandle, State};
+
+fn local_offset() -> FixedOffset {
+    Local::now().offset().fix()
+}
+
+/// Get the user's review stats. `enabled` is false until they opt in.
+#[tauri::command]
+pub async fn get_review_streaks(pool: State<'_, DbPool>) -> Result<ReviewStreaks, AppError> {
+    review_stats::streaks(pool.inner(), Utc::now().timestamp(), local_offset()).await
+}
+
+/// Opt in to or out of review stats. Opting out deletes the recorded
+/// activity.
+#[tauri::command]
+pub async fn set_review_stats_enabled(
+    pool: State<'_, DbPool>,
+    enabled: bool,
+) -> Result<ReviewStreaks, AppError> {
+    let now = Utc::now().timestamp();
+    review_stats::set_enabled(pool.inner(), enabled, now).await?;
+    review_stats::streaks(pool.inner(), now, local_offset()).await
+}
+
+/// Export the stats together with the raw activity log.
+#[tauri::command]
+pub async fn export_review_stats(pool: State<'_, DbPool>) -> Result<ReviewStatsExport, AppError> {
+    review_stats::export(pool.inner(), Utc::now().timestamp(), local_offset()).await
+}
+
+/// SLA status of every MR awaiting my review, in working hours of the local
+/// time zone (DST included). Empty when the review SLA is turned off.
+#[tauri::command]
+pub async fn get_review_sla_status(
+    app: AppHandle,
+    pool: State<'_, DbPool>,
+) -> Result<Vec<ReviewSlaStatus>, AppError> {
+    let config = load_settings(&app).await?.sync.review_sla;
+    review_sla::statuses(pool.inner(), &config, Utc::now().timestamp(), Local).await
+}
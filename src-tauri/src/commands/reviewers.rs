@@ -1,8 +1,12 @@
-//! Reviewer commands for fetching per-reviewer approval status.
+//! Reviewer commands for fetching per-reviewer approval status, and for
+//! changing an MR's reviewers and assignees.
 
+use crate::core::mr_people::{self, MrRole, ProjectMember};
+use crate::core::participants::{self, Participant};
 use crate::db::pool::DbPool;
 use crate::error::AppError;
 use crate::models::MrReviewer;
+use crate::services::sync_engine::SyncHandle;
 use tauri::State;
 
 /// Get per-reviewer approval statuses for a merge request.
@@ -19,7 +23,7 @@ pub async fn get_mr_reviewers(
 ) -> Result<Vec<MrReviewer>, AppError> {
     let reviewers: Vec<MrReviewer> = sqlx::query_as(
         r#"
-        SELECT mr_id, username, status, cached_at
+        SELECT mr_id, username, status, cached_at, review_state, rerequested_at
         FROM mr_reviewers
         WHERE mr_id = ?
         ORDER BY username
@@ -31,3 +35,104 @@ pub async fn get_mr_reviewers(
 
     Ok(reviewers)
 }
+
+/// Ask reviewers who already reviewed one of my MRs to review it again,
+/// e.g. after pushing fixes. Needs GitLab 16 or later.
+///
+/// # Returns
+/// Usernames of the reviewers that were asked.
+#[tauri::command]
+pub async fn request_rereview(
+    pool: State<'_, DbPool>,
+    mr_id: i64,
+) -> Result<Vec<String>, AppError> {
+    crate::core::rereview::request_rereview(pool.inner(), mr_id, chrono::Utc::now().timestamp())
+        .await
+}
+
+/// Get everyone involved in a merge request: author, assignees, reviewers,
+/// approvers and commenters, with their cached avatars.
+///
+/// # Arguments
+/// * `mr_id` - The MR ID
+///
+/// # Returns
+/// One entry per person with all their roles and comment counts.
+#[tauri::command]
+pub async fn get_mr_participants(
+    pool: State<'_, DbPool>,
+    mr_id: i64,
+) -> Result<Vec<Participant>, AppError> {
+    participants::participants(pool.inner(), mr_id).await
+}
+
+/// Search a project's members for the reviewer/assignee picker.
+///
+/// Members are fetched the first time a project is searched and again once
+/// a day; offline (or when the fetch fails) the cached list is searched.
+#[tauri::command]
+pub async fn search_project_members(
+    pool: State<'_, DbPool>,
+    instance_id: i64,
+    project_id: i64,
+    query: String,
+) -> Result<Vec<ProjectMember>, AppError> {
+    let now = chrono::Utc::now().timestamp();
+    let refreshed = async {
+        let client = crate::core::create_client(pool.inner(), instance_id).await?;
+        mr_people::refresh_members_if_stale(pool.inner(), &client, instance_id, project_id, now)
+            .await
+    };
+    if let Err(e) = refreshed.await {
+        log::warn!(
+            "[members] Using cached members of project {}: {}",
+            project_id,
+            e
+        );
+    }
+    mr_people::search_members(pool.inner(), instance_id, project_id, &query).await
+}
+
+/// Replace an MR's reviewers.
+///
+/// The cached MR is updated immediately; the change is queued and pushed to
+/// GitLab in the background. Usernames must come from
+/// [`search_project_members`].
+///
+/// # Returns
+/// The MR's reviewers afterwards
+#[tauri::command]
+pub async fn set_mr_reviewers(
+    pool: State<'_, DbPool>,
+    sync_handle: State<'_, SyncHandle>,
+    mr_id: i64,
+    usernames: Vec<String>,
+) -> Result<Vec<String>, AppError> {
+    let reviewers =
+        mr_people::set_mr_users(pool.inner(), mr_id, MrRole::Reviewer, &usernames).await?;
+    flush(&sync_handle).await;
+    Ok(reviewers)
+}
+
+/// Replace an MR's assignees, queued like [`set_mr_reviewers`].
+///
+/// # Returns
+/// The MR's assignees afterwards
+#[tauri::command]
+pub async fn set_mr_assignees(
+    pool: State<'_, DbPool>,
+    sync_handle: State<'_, SyncHandle>,
+    mr_id: i64,
+    usernames: Vec<String>,
+) -> Result<Vec<String>, AppError> {
+    let assignees =
+        mr_people::set_mr_users(pool.inner(), mr_id, MrRole::Assignee, &usernames).await?;
+    flush(&sync_handle).await;
+    Ok(assignees)
+}
+
+async fn flush(sync_handle: &SyncHandle) {
+    if let Err(e) = sync_handle.flush_comments().await {
+        log::warn!("[members] Failed to send flush signal: {}", e);
+    }
+}
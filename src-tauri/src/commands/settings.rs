@@ -4,7 +4,14 @@
 //! Settings are persisted using the tauri-plugin-store.
 
 use crate::commands::companion_settings::CompanionServerSettings;
+use crate::core::review_assistant::{self, AssistantSettings};
+use crate::core::translation::{self, TranslationSettings};
+use crate::db::pool::DbPool;
 use crate::error::AppError;
+use crate::services::editor;
+use crate::services::highlight_themes::{self, HighlightTheme};
+use crate::services::link_handler::LinkHandlerSettings;
+use crate::services::offline_mode;
 use crate::services::sync_engine::{SyncConfig, SyncHandle};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -35,6 +42,12 @@ const DISPLAY_FONT_KEY: &str = "display_font";
 /// Key for diffs font in the store.
 const DIFFS_FONT_KEY: &str = "diffs_font";
 
+/// Key for the diff syntax highlight theme ID in the store.
+const HIGHLIGHT_THEME_KEY: &str = "highlight_theme";
+
+/// Key for the MR list view state (instance, project filters, grouping).
+const LIST_VIEW_STATE_KEY: &str = "list_view_state";
+
 /// Key for custom theme colors in the store.
 const CUSTOM_THEME_COLORS_KEY: &str = "custom_theme_colors";
 
@@ -56,6 +69,29 @@ const SHOW_RECENTLY_MERGED_MRS_KEY: &str = "show_recently_merged_mrs";
 /// Key for the "show draft MRs" toggle on the My MRs page.
 const SHOW_DRAFT_MRS_KEY: &str = "show_draft_mrs";
 
+/// Key for the raw API passthrough write toggle in the store.
+const ALLOW_API_WRITES_KEY: &str = "allow_api_writes";
+
+/// Key for comment translation settings in the store.
+const TRANSLATION_KEY: &str = "translation";
+
+/// Key for review assistant settings in the store.
+const REVIEW_ASSISTANT_KEY: &str = "review_assistant";
+
+/// Key for the OS link handler settings in the store.
+const LINK_HANDLER_KEY: &str = "link_handler";
+
+/// Key for the external editor command template in the store.
+const EDITOR_COMMAND_KEY: &str = "editor_command";
+
+/// Key for the database encryption toggle in the store. Also read directly
+/// at startup, before the database is opened.
+const ENCRYPT_DATABASE_KEY: &str = "encrypt_database";
+
+/// Key for the offline mode toggle in the store. Also read directly at
+/// startup, before the sync engine starts.
+const OFFLINE_MODE_KEY: &str = "offline_mode";
+
 /// Default number of files to jump with arrow-left/right.
 const DEFAULT_FILE_JUMP_COUNT: u32 = 5;
 
@@ -97,6 +133,46 @@ pub struct CustomThemeColors {
     pub accent: String,
 }
 
+/// MR list view state for one instance.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct InstanceListView {
+    /// Projects the list is limited to; empty shows all.
+    pub project_filter: Vec<i64>,
+    /// Order of project groups; unlisted projects follow by name.
+    pub project_order: Vec<i64>,
+}
+
+/// MR list view state, shared by the desktop app and the companion.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ListViewState {
+    /// Instance selected when the list was last used.
+    pub last_instance_id: Option<i64>,
+    /// Whether MRs are grouped under project headers.
+    pub group_by_project: bool,
+    /// Per-instance project filters and group order.
+    pub instances: HashMap<i64, InstanceListView>,
+}
+
+impl ListViewState {
+    /// Drop duplicate project IDs and instances without any state.
+    fn normalized(mut self) -> Self {
+        for view in self.instances.values_mut() {
+            dedup_in_order(&mut view.project_filter);
+            dedup_in_order(&mut view.project_order);
+        }
+        self.instances
+            .retain(|_, v| *v != InstanceListView::default());
+        self
+    }
+}
+
+fn dedup_in_order(ids: &mut Vec<i64>) {
+    let mut seen = std::collections::HashSet::new();
+    ids.retain(|id| seen.insert(*id));
+}
+
 /// Application settings.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -115,6 +191,8 @@ pub struct AppSettings {
     pub diffs_font: String,
     /// Custom theme input colors (bg, text, accent hex strings). None if no custom theme saved.
     pub custom_theme_colors: Option<CustomThemeColors>,
+    /// Syntax highlight theme for diff tokens ("app" follows the active theme).
+    pub highlight_theme: String,
     /// Companion server settings (mobile web access).
     pub companion_server: CompanionServerSettings,
     /// Number of files to jump when pressing arrow-left/right in file navigation.
@@ -127,6 +205,24 @@ pub struct AppSettings {
     pub show_recently_merged_mrs: bool,
     /// Whether the My MRs page includes the user's draft MRs. Defaults to true.
     pub show_draft_mrs: bool,
+    /// Whether the raw API passthrough may send non-GET requests. Defaults to false.
+    pub allow_api_writes: bool,
+    /// Comment translation provider. Disabled by default.
+    pub translation: TranslationSettings,
+    /// Review assistant model provider. Disabled by default.
+    pub review_assistant: AssistantSettings,
+    /// Whether the database should be encrypted with SQLCipher. Applied on
+    /// the next start.
+    pub encrypt_database: bool,
+    /// Whether offline mode is on: no network requests until turned off.
+    pub offline_mode: bool,
+    /// MR list instance, project filters and grouping.
+    pub list_view_state: ListViewState,
+    /// OS link handler registration. Off by default.
+    pub link_handler: LinkHandlerSettings,
+    /// Command template for opening a file in an external editor, e.g.
+    /// `code -g {file}:{line}`. Empty until the user sets one.
+    pub editor_command: String,
 }
 
 impl Default for AppSettings {
@@ -139,12 +235,21 @@ impl Default for AppSettings {
             display_font: DEFAULT_DISPLAY_FONT.to_string(),
             diffs_font: DEFAULT_DIFFS_FONT.to_string(),
             custom_theme_colors: None,
+            highlight_theme: highlight_themes::APP_THEME_ID.to_string(),
             companion_server: CompanionServerSettings::default(),
             file_jump_count: DEFAULT_FILE_JUMP_COUNT,
             keyboard_shortcuts: HashMap::new(),
             mr_list_condensed: false,
             show_recently_merged_mrs: false,
             show_draft_mrs: true,
+            allow_api_writes: false,
+            translation: TranslationSettings::default(),
+            review_assistant: AssistantSettings::default(),
+            encrypt_database: false,
+            offline_mode: false,
+            list_view_state: ListViewState::default(),
+            link_handler: LinkHandlerSettings::default(),
+            editor_command: String::new(),
         }
     }
 }
@@ -213,6 +318,13 @@ pub(crate) async fn load_settings(app: &AppHandle) -> Result<AppSettings, AppErr
         None => None,
     };
 
+    // Try to load highlight theme
+    let highlight_theme = match store.get(HIGHLIGHT_THEME_KEY) {
+        Some(value) => serde_json::from_value(value.clone())
+            .unwrap_or_else(|_| highlight_themes::APP_THEME_ID.to_string()),
+        None => highlight_themes::APP_THEME_ID.to_string(),
+    };
+
     // Try to load companion server settings
     let companion_server = match store.get(COMPANION_SERVER_KEY) {
         Some(value) => serde_json::from_value(value.clone()).unwrap_or_default(),
@@ -249,6 +361,54 @@ pub(crate) async fn load_settings(app: &AppHandle) -> Result<AppSettings, AppErr
         None => true,
     };
 
+    // Try to load the API passthrough write toggle (defaults to false)
+    let allow_api_writes = match store.get(ALLOW_API_WRITES_KEY) {
+        Some(value) => serde_json::from_value(value.clone()).unwrap_or(false),
+        None => false,
+    };
+
+    // Try to load translation settings (disabled by default)
+    let translation = match store.get(TRANSLATION_KEY) {
+        Some(value) => serde_json::from_value(value.clone()).unwrap_or_default(),
+        None => TranslationSettings::default(),
+    };
+
+    // Try to load review assistant settings (disabled by default)
+    let review_assistant = match store.get(REVIEW_ASSISTANT_KEY) {
+        Some(value) => serde_json::from_value(value.clone()).unwrap_or_default(),
+        None => AssistantSettings::default(),
+    };
+
+    // Try to load the database encryption toggle (defaults to false)
+    let encrypt_database = match store.get(ENCRYPT_DATABASE_KEY) {
+        Some(value) => serde_json::from_value(value.clone()).unwrap_or(false),
+        None => false,
+    };
+
+    // Try to load the offline mode toggle (defaults to false)
+    let offline_mode = match store.get(OFFLINE_MODE_KEY) {
+        Some(value) => serde_json::from_value(value.clone()).unwrap_or(false),
+        None => false,
+    };
+
+    // Try to load the MR list view state
+    let list_view_state = match store.get(LIST_VIEW_STATE_KEY) {
+        Some(value) => serde_json::from_value(value.clone()).unwrap_or_default(),
+        None => ListViewState::default(),
+    };
+
+    // Try to load link handler settings (off by default)
+    let link_handler = match store.get(LINK_HANDLER_KEY) {
+        Some(value) => serde_json::from_value(value.clone()).unwrap_or_default(),
+        None => LinkHandlerSettings::default(),
+    };
+
+    // Try to load the external editor command (unset by default)
+    let editor_command = match store.get(EDITOR_COMMAND_KEY) {
+        Some(value) => serde_json::from_value(value.clone()).unwrap_or_default(),
+        None => String::new(),
+    };
+
     Ok(AppSettings {
         sync,
         collapse_patterns,
@@ -257,12 +417,21 @@ pub(crate) async fn load_settings(app: &AppHandle) -> Result<AppSettings, AppErr
         display_font,
         diffs_font,
         custom_theme_colors,
+        highlight_theme,
         companion_server,
         file_jump_count,
         keyboard_shortcuts,
         mr_list_condensed,
         show_recently_merged_mrs,
         show_draft_mrs,
+        allow_api_writes,
+        translation,
+        review_assistant,
+        encrypt_database,
+        offline_mode,
+        list_view_state,
+        link_handler,
+        editor_command,
     })
 }
 
@@ -300,6 +469,10 @@ pub(crate) async fn save_settings(app: &AppHandle, settings: &AppSettings) -> Re
     let custom_theme_value = serde_json::to_value(&settings.custom_theme_colors)?;
     store.set(CUSTOM_THEME_COLORS_KEY, custom_theme_value);
 
+    // Save highlight theme
+    let highlight_theme_value = serde_json::to_value(&settings.highlight_theme)?;
+    store.set(HIGHLIGHT_THEME_KEY, highlight_theme_value);
+
     // Save companion server settings
     let companion_value = serde_json::to_value(&settings.companion_server)?;
     store.set(COMPANION_SERVER_KEY, companion_value);
@@ -324,6 +497,38 @@ pub(crate) async fn save_settings(app: &AppHandle, settings: &AppSettings) -> Re
     let show_draft_value = serde_json::to_value(settings.show_draft_mrs)?;
     store.set(SHOW_DRAFT_MRS_KEY, show_draft_value);
 
+    // Save API passthrough write toggle
+    let allow_api_writes_value = serde_json::to_value(settings.allow_api_writes)?;
+    store.set(ALLOW_API_WRITES_KEY, allow_api_writes_value);
+
+    // Save translation settings
+    let translation_value = serde_json::to_value(&settings.translation)?;
+    store.set(TRANSLATION_KEY, translation_value);
+
+    // Save review assistant settings
+    let review_assistant_value = serde_json::to_value(&settings.review_assistant)?;
+    store.set(REVIEW_ASSISTANT_KEY, review_assistant_value);
+
+    // Save database encryption toggle
+    let encrypt_database_value = serde_json::to_value(settings.encrypt_database)?;
+    store.set(ENCRYPT_DATABASE_KEY, encrypt_database_value);
+
+    // Save offline mode toggle
+    let offline_mode_value = serde_json::to_value(settings.offline_mode)?;
+    store.set(OFFLINE_MODE_KEY, offline_mode_value);
+
+    // Save MR list view state
+    let list_view_state_value = serde_json::to_value(&settings.list_view_state)?;
+    store.set(LIST_VIEW_STATE_KEY, list_view_state_value);
+
+    // Save link handler settings
+    let link_handler_value = serde_json::to_value(&settings.link_handler)?;
+    store.set(LINK_HANDLER_KEY, link_handler_value);
+
+    // Save external editor command
+    let editor_command_value = serde_json::to_value(&settings.editor_command)?;
+    store.set(EDITOR_COMMAND_KEY, editor_command_value);
+
     // Persist to disk
     store
         .save()
@@ -355,6 +560,7 @@ pub async fn get_settings(app: AppHandle) -> Result<AppSettings, AppError> {
 pub async fn update_settings(app: AppHandle, settings: AppSettings) -> Result<(), AppError> {
     // Save to store
     save_settings(&app, &settings).await?;
+    offline_mode::set_offline(settings.offline_mode);
 
     // Update cache
     *settings_cache().write().await = settings;
@@ -386,6 +592,11 @@ pub async fn update_sync_settings(
     sync_handle: State<'_, SyncHandle>,
     sync_config: SyncConfig,
 ) -> Result<(), AppError> {
+    sync_config.vacation.validate()?;
+    sync_config.review_sla.validate()?;
+    sync_config.validate_disabled_stages()?;
+    sync_config.validate_action_max_attempts()?;
+
     // Get current settings
     let mut settings = load_settings(&app).await?;
 
@@ -513,6 +724,66 @@ pub async fn update_custom_theme_colors(
     Ok(())
 }
 
+/// List the syntax highlight themes for diff tokens, each with its stylesheet.
+#[tauri::command]
+pub async fn list_highlight_themes() -> Result<Vec<HighlightTheme>, AppError> {
+    Ok(highlight_themes::list_themes())
+}
+
+/// Select the syntax highlight theme for diff tokens.
+///
+/// # Arguments
+/// * `theme_id` - A theme ID from `list_highlight_themes`, or "app" to follow the app theme
+///
+/// # Returns
+/// The stylesheet to apply (empty for "app").
+#[tauri::command]
+pub async fn set_highlight_theme(app: AppHandle, theme_id: String) -> Result<String, AppError> {
+    if !highlight_themes::is_known(&theme_id) {
+        return Err(AppError::invalid_input_field(
+            format!("Unknown highlight theme: {}", theme_id),
+            "themeId",
+        ));
+    }
+    let mut settings = load_settings(&app).await?;
+    settings.highlight_theme = theme_id;
+    save_settings(&app, &settings).await?;
+    let css = highlight_themes::css_for(&settings.highlight_theme);
+    *settings_cache().write().await = settings;
+    Ok(css)
+}
+
+/// Get the MR list view state (last instance, project filters, grouping).
+#[tauri::command]
+pub async fn get_list_view_state(app: AppHandle) -> Result<ListViewState, AppError> {
+    Ok(load_settings(&app).await?.list_view_state)
+}
+
+/// Replace the MR list view state.
+///
+/// # Returns
+/// The stored state, with duplicate project IDs removed.
+#[tauri::command]
+pub async fn update_list_view_state(
+    app: AppHandle,
+    state: ListViewState,
+) -> Result<ListViewState, AppError> {
+    set_list_view_state(&app, state).await
+}
+
+/// Store the MR list view state; shared with the companion API.
+pub(crate) async fn set_list_view_state(
+    app: &AppHandle,
+    state: ListViewState,
+) -> Result<ListViewState, AppError> {
+    let mut settings = load_settings(app).await?;
+    settings.list_view_state = state.normalized();
+    save_settings(app, &settings).await?;
+    let stored = settings.list_view_state.clone();
+    *settings_cache().write().await = settings;
+    Ok(stored)
+}
+
 /// Update custom keyboard shortcuts.
 ///
 /// Convenience method that updates just the keyboard shortcut bindings.
@@ -575,6 +846,103 @@ pub async fn update_show_draft_mrs(app: AppHandle, show: bool) -> Result<(), App
     Ok(())
 }
 
+/// Allow or forbid write requests through the raw API passthrough.
+///
+/// # Arguments
+/// * `allow` - When true, `gitlab_api_request` also sends POST/PUT/PATCH/DELETE.
+#[tauri::command]
+pub async fn update_allow_api_writes(app: AppHandle, allow: bool) -> Result<(), AppError> {
+    let mut settings = load_settings(&app).await?;
+    settings.allow_api_writes = allow;
+    save_settings(&app, &settings).await?;
+    *settings_cache().write().await = settings;
+    Ok(())
+}
+
+/// Set the command template used to open files in an external editor.
+///
+/// # Arguments
+/// * `command` - Template with `{file}` and optionally `{line}`; empty clears it.
+#[tauri::command]
+pub async fn update_editor_command(app: AppHandle, command: String) -> Result<(), AppError> {
+    let command = command.trim().to_string();
+    if !command.is_empty() {
+        editor::validate_template(&command)?;
+    }
+    let mut settings = load_settings(&app).await?;
+    settings.editor_command = command;
+    save_settings(&app, &settings).await?;
+    *settings_cache().write().await = settings;
+    Ok(())
+}
+
+/// Turn offline mode on or off.
+///
+/// While on, sync, file fetches, avatars and other outgoing requests are
+/// skipped; cached data stays readable and new actions stay queued. Turning
+/// it off starts a sync right away so the queue drains.
+///
+/// # Arguments
+/// * `offline` - When true, no network requests are made.
+#[tauri::command]
+pub async fn set_offline_mode(
+    app: AppHandle,
+    sync_handle: State<'_, SyncHandle>,
+    offline: bool,
+) -> Result<(), AppError> {
+    let mut settings = load_settings(&app).await?;
+    settings.offline_mode = offline;
+    save_settings(&app, &settings).await?;
+    *settings_cache().write().await = settings;
+
+    let was_offline = offline_mode::set_offline(offline);
+    log::info!("Offline mode {}", if offline { "on" } else { "off" });
+    if was_offline && !offline {
+        sync_handle.trigger_sync().await?;
+    }
+    Ok(())
+}
+
+/// Update the comment translation settings.
+///
+/// Turning translation off also drops cached translations.
+///
+/// # Arguments
+/// * `translation` - Provider, endpoint, API key and default target language.
+#[tauri::command]
+pub async fn update_translation_settings(
+    app: AppHandle,
+    pool: State<'_, DbPool>,
+    translation: TranslationSettings,
+) -> Result<(), AppError> {
+    translation::validate_settings(&translation)?;
+    let mut settings = load_settings(&app).await?;
+    if !translation.enabled {
+        crate::db::translations::clear_translations(pool.inner()).await?;
+    }
+    settings.translation = translation;
+    save_settings(&app, &settings).await?;
+    *settings_cache().write().await = settings;
+    Ok(())
+}
+
+/// Update the review assistant settings.
+///
+/// # Arguments
+/// * `review_assistant` - Provider, endpoint, API key, model and redaction terms.
+#[tauri::command]
+pub async fn update_review_assistant_settings(
+    app: AppHandle,
+    review_assistant: AssistantSettings,
+) -> Result<(), AppError> {
+    review_assistant::validate_settings(&review_assistant)?;
+    let mut settings = load_settings(&app).await?;
+    settings.review_assistant = review_assistant;
+    save_settings(&app, &settings).await?;
+    *settings_cache().write().await = settings;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -597,4 +965,22 @@ mod tests {
             .collapse_patterns
             .contains(&"Cargo.lock".to_string()));
     }
+
+    #[test]
+    fn test_list_view_state_normalized() {
+        let state: ListViewState = serde_json::from_value(serde_json::json!({
+            "lastInstanceId": 1,
+            "instances": {
+                "1": { "projectFilter": [3, 4, 3], "projectOrder": [4, 4] },
+                "2": { "projectFilter": [] }
+            }
+        }))
+        .unwrap();
+        let state = state.normalized();
+        assert_eq!(state.last_instance_id, Some(1));
+        assert!(!state.group_by_project);
+        assert_eq!(state.instances.len(), 1);
+        assert_eq!(state.instances[&1].project_filter, vec![3, 4]);
+        assert_eq!(state.instances[&1].project_order, vec![4]);
+    }
 }
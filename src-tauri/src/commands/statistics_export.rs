@@ -0,0 +1,35 @@
+//! Statistics export command: review activity, sync performance and
+//! pipeline outcomes as CSV or JSON, for dashboards built elsewhere.
+
+use crate::core::statistics_export::{self, ExportFormat, StatisticsExportSummary, StatsRange};
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use chrono::Utc;
+use std::path::PathBuf;
+use tauri::State;
+
+/// Write the statistics recorded in `range` to `path` (absolute) as `format`.
+#[tauri::command]
+pub async fn export_statistics(
+    pool: State<'_, DbPool>,
+    range: StatsRange,
+    format: ExportFormat,
+    path: String,
+) -> Result<StatisticsExportSummary, AppError> {
+    let path = PathBuf::from(path.trim());
+    if path.as_os_str().is_empty() || !path.is_absolute() {
+        return Err(AppError::invalid_input_field(
+            "Export path must be absolute",
+            "path",
+        ));
+    }
+    if let (Some(from), Some(to)) = (range.from, range.to) {
+        if from >= to {
+            return Err(AppError::invalid_input_field(
+                "Range start must be before its end",
+                "range",
+            ));
+        }
+    }
+    statistics_export::export(pool.inner(), range, format, &path, Utc::now().timestamp()).await
+}
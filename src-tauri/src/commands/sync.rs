@@ -2,25 +2,37 @@
 //!
 //! These commands provide access to sync status and control.
 
-use crate::db::pool::DbPool;
+use crate::core::change_log::{self, ChangesSince, MrDelta};
+use crate::db::pool::{DbPool, ReadPool};
+use crate::db::sync_configs::{self, InstanceSyncConfig};
+use crate::db::sync_cursors;
 use crate::error::AppError;
 use crate::services::gitlab_client::{GitLabClient, GitLabClientConfig};
-use crate::services::sync_engine::{SyncConfig, SyncEngine, SyncHandle, SyncLogEntry};
+use crate::services::offline_mode;
+use crate::services::sync_engine::{
+    validate_instance_config, SyncEngine, SyncHandle, SyncLogEntry,
+};
 use crate::services::sync_events::{
     ActionSyncedPayload, AuthExpiredPayload, SyncPhase, SyncProgressPayload, ACTION_SYNCED_EVENT,
     AUTH_EXPIRED_EVENT, SYNC_PROGRESS_EVENT,
 };
 use crate::services::sync_processor;
 use crate::services::sync_queue;
+use crate::services::sync_selftest::{self, SelfTestReport};
 
 use serde::Serialize;
 use tauri::{AppHandle, Emitter, State};
 
 /// Response for get_action_counts command.
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ActionCountsResponse {
     pub pending: i64,
     pub failed: i64,
+    /// Failed actions a plain retry can be expected to fix.
+    pub retryable: i64,
+    /// Failed actions that need re-authentication or should be discarded.
+    pub needs_attention: i64,
 }
 
 /// Response for get_sync_status command.
@@ -40,6 +52,8 @@ pub struct GetSyncStatusResponse {
     pub last_sync_mr_count: i64,
     /// Recent sync log entries.
     pub recent_logs: Vec<SyncLogEntry>,
+    /// Whether offline mode is on, so no sync runs.
+    pub offline: bool,
 }
 
 /// Response for retry_failed_actions command.
@@ -60,7 +74,36 @@ pub struct RetryActionsResponse {
 #[tauri::command]
 pub async fn get_action_counts(pool: State<'_, DbPool>) -> Result<ActionCountsResponse, AppError> {
     let (pending, failed) = sync_queue::get_action_counts(pool.inner()).await?;
-    Ok(ActionCountsResponse { pending, failed })
+    let retryable = sync_queue::get_failed_actions(pool.inner())
+        .await?
+        .iter()
+        .filter(|a| a.status == "failed" && a.advice.is_retryable())
+        .count() as i64;
+    Ok(ActionCountsResponse {
+        pending,
+        failed,
+        retryable,
+        needs_attention: failed - retryable,
+    })
+}
+
+/// List failed sync actions with why they failed (HTTP status, GitLab
+/// message, endpoint, attempt times) and whether retrying can help. Actions
+/// waiting for an automatic retry are included with their `next_retry_at`.
+#[tauri::command]
+pub async fn list_failed_actions(
+    pool: State<'_, DbPool>,
+) -> Result<Vec<sync_queue::FailedAction>, AppError> {
+    sync_queue::get_failed_actions(pool.inner()).await
+}
+
+/// List queued actions that were dropped because their MR left the cache
+/// (purged after merge/close, deleted on GitLab), newest first.
+#[tauri::command]
+pub async fn get_orphaned_actions(
+    pool: State<'_, DbPool>,
+) -> Result<Vec<sync_queue::OrphanedAction>, AppError> {
+    sync_queue::get_orphaned_actions(pool.inner()).await
 }
 
 /// Trigger an immediate sync operation.
@@ -75,6 +118,16 @@ pub async fn trigger_sync(
     sync_handle.trigger_sync_force(force.unwrap_or(false)).await
 }
 
+/// Trigger a deep reconciliation sync.
+///
+/// Re-fetches every MR regardless of `updated_at`, re-checks cached MRs
+/// the fetch didn't return and removes orphaned cache rows. The drift
+/// summary shows up in the sync log as a `reconcile` entry.
+#[tauri::command]
+pub async fn trigger_full_resync(sync_handle: State<'_, SyncHandle>) -> Result<(), AppError> {
+    sync_handle.trigger_full_resync().await
+}
+
 /// Get the current sync status.
 ///
 /// Returns information about the last sync, pending actions,
@@ -128,6 +181,7 @@ pub async fn get_sync_status(
         failed_actions: failed,
         last_sync_mr_count,
         recent_logs,
+        offline: offline_mode::is_offline(),
     })
 }
 
@@ -144,6 +198,12 @@ pub async fn retry_failed_actions(
     app: AppHandle,
     pool: State<'_, DbPool>,
 ) -> Result<RetryActionsResponse, AppError> {
+    // Resetting them now would only fail them again.
+    offline_mode::ensure_online()?;
+
+    // Actions whose MR row was replaced are re-homed before picking them up
+    sync_queue::resolve_orphaned_actions(pool.inner()).await?;
+
     // Query failed actions joined with their instance credentials
     let action_instances = sqlx::query_as::<_, ActionWithInstance>(
         r#"
@@ -206,6 +266,11 @@ pub async fn retry_failed_actions(
         },
     );
 
+    let policy = crate::commands::settings::load_settings(&app)
+        .await
+        .map(|s| s.sync.retry_policy())
+        .unwrap_or_default();
+
     // Group actions by instance_id
     let mut groups: std::collections::HashMap<i64, (String, Option<String>, Vec<crate::models::sync_action::SyncAction>)> =
         std::collections::HashMap::new();
@@ -230,13 +295,10 @@ pub async fn retry_failed_actions(
                     message: "GitLab token missing. Please re-authenticate.".to_string(),
                 },
             );
+            let err =
+                AppError::authentication(format!("Token missing for instance {}", instance_id));
             for action in actions {
-                let _ = sync_queue::mark_failed(
-                    pool.inner(),
-                    action.id,
-                    &format!("Token missing for instance {}", instance_id),
-                )
-                .await;
+                let _ = sync_queue::mark_failed_with_error(pool.inner(), action.id, &err).await;
             }
             continue;
         };
@@ -268,7 +330,8 @@ pub async fn retry_failed_actions(
                 continue;
             }
 
-            let result = sync_processor::process_action(&client, pool.inner(), action).await;
+            let result =
+                sync_processor::process_action(&client, pool.inner(), action, &policy).await;
 
             // Check for auth expiry
             if let Some(ref error) = result.error {
@@ -343,6 +406,7 @@ impl ActionWithInstance {
             last_error: self.last_error,
             created_at: self.created_at,
             synced_at: self.synced_at,
+            batch_id: None,
         }
     }
 }
@@ -362,17 +426,101 @@ pub async fn discard_failed_action(
     sync_queue::delete_action(pool.inner(), action_id).await
 }
 
-/// Get the current sync configuration.
+/// Run the sync self-test against an instance: validate the token, list
+/// one MR, fetch its diff and dry-run the action queue, each timed.
+///
+/// Failing steps are reported in the result rather than as an error, so
+/// the whole report can be attached to a bug report.
+///
+/// # Arguments
+/// * `instance_id` - The GitLab instance to test
+#[tauri::command]
+pub async fn run_sync_selftest(
+    pool: State<'_, DbPool>,
+    instance_id: i64,
+) -> Result<SelfTestReport, AppError> {
+    sync_selftest::run(pool.inner(), instance_id).await
+}
+
+/// Get an instance's sync configuration: which MR scopes it syncs, and
+/// its own interval and MR cap when it overrides the global ones.
+///
+/// # Arguments
+/// * `instance_id` - The GitLab instance
 #[tauri::command]
-pub async fn get_sync_config(sync_handle: State<'_, SyncHandle>) -> Result<SyncConfig, AppError> {
-    Ok(sync_handle.get_config().await)
+pub async fn get_sync_config(
+    pool: State<'_, DbPool>,
+    instance_id: i64,
+) -> Result<InstanceSyncConfig, AppError> {
+    Ok(sync_configs::get_config(pool.inner(), instance_id).await?)
 }
 
-/// Update the sync configuration.
+/// Update an instance's sync configuration. Takes effect from the next
+/// sync of that instance.
+///
+/// # Arguments
+/// * `instance_id` - The GitLab instance
+/// * `config` - New configuration; its `instance_id` is ignored
 #[tauri::command]
 pub async fn update_sync_config(
+    pool: State<'_, DbPool>,
     sync_handle: State<'_, SyncHandle>,
-    config: SyncConfig,
-) -> Result<(), AppError> {
-    sync_handle.update_config(config).await
+    instance_id: i64,
+    config: InstanceSyncConfig,
+) -> Result<InstanceSyncConfig, AppError> {
+    let config = InstanceSyncConfig {
+        instance_id,
+        ..config
+    };
+    validate_instance_config(&config)?;
+    let exists: bool =
+        sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM gitlab_instances WHERE id = ?)")
+            .bind(instance_id)
+            .fetch_one(pool.inner())
+            .await?;
+    if !exists {
+        return Err(AppError::not_found_with_id(
+            "GitLabInstance",
+            instance_id.to_string(),
+        ));
+    }
+    let previous = sync_configs::get_config(pool.inner(), instance_id).await?;
+    sync_configs::upsert_config(pool.inner(), &config).await?;
+
+    // A newly enabled scope has MRs the incremental cursor would skip.
+    let scopes = |c: &InstanceSyncConfig| (c.sync_authored, c.sync_reviewing, c.sync_assigned);
+    if scopes(&previous) != scopes(&config) {
+        sync_cursors::reset(pool.inner(), instance_id).await?;
+    }
+
+    // Resending the global config restarts the background timer, which
+    // picks up a changed per-instance interval.
+    sync_handle
+        .update_config(sync_handle.get_config().await)
+        .await?;
+    Ok(config)
+}
+
+/// Get cache changes recorded after `cursor`, for clients that missed
+/// `db-changes` events (e.g. after a reload or reconnect).
+///
+/// Pass `None` to just obtain the current cursor before loading data. When
+/// `reset` is set in the response the cursor could not be caught up from
+/// and the client should reload everything.
+#[tauri::command]
+pub async fn get_changes_since(
+    pool: State<'_, ReadPool>,
+    cursor: Option<i64>,
+) -> Result<ChangesSince, AppError> {
+    change_log::changes_since(pool.inner(), cursor).await
+}
+
+/// Get the net MR changes after `since`: which MRs were created, updated
+/// (with the changed fields) or removed, one entry per MR.
+#[tauri::command]
+pub async fn get_mr_delta(
+    pool: State<'_, ReadPool>,
+    since: Option<i64>,
+) -> Result<MrDelta, AppError> {
+    change_log::mr_delta(pool.inner(), since).await
 }
@@ -0,0 +1,35 @@
+//! Comment translation command. Only contacts the configured provider once
+//! translation has been enabled in settings.
+
+use super::settings::load_settings;
+use crate::core::translation::{self, Translation};
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use chrono::Utc;
+use tauri::{AppHandle, State};
+
+/// Translate text into `target_lang` with the configured provider.
+///
+/// # Arguments
+/// * `text` - Text to translate (a comment body, as markdown source)
+/// * `target_lang` - Language code, e.g. "en"
+/// * `comment_id` - When set, the translation is cached for this comment
+#[tauri::command]
+pub async fn translate_text(
+    app: AppHandle,
+    pool: State<'_, DbPool>,
+    text: String,
+    target_lang: String,
+    comment_id: Option<i64>,
+) -> Result<Translation, AppError> {
+    let settings = load_settings(&app).await?.translation;
+    translation::translate(
+        pool.inner(),
+        &settings,
+        &text,
+        &target_lang,
+        comment_id,
+        Utc::now().timestamp(),
+    )
+    .await
+}
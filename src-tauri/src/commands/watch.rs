@@ -0,0 +1,77 @@
+//! MR watch list commands.
+//!
+//! Watching lets the user follow an MR they are not involved in (e.g. one
+//! that will conflict with their own). The flag lives on the cached
+//! `merge_requests` row; the sync engine keeps watched MRs fresh and includes
+//! them in ready-to-merge notifications.
+
+use crate::commands::mr::{fetch_mr_by_web_url, ResolvedMr};
+use crate::db::pool::DbPool;
+use crate::db::watched;
+use crate::error::AppError;
+use crate::services::sync_engine::SyncHandle;
+use tauri::State;
+
+/// Watch an MR, identified either by its local id or its GitLab web URL.
+///
+/// A URL for an MR that isn't cached yet is fetched from GitLab first, so
+/// any MR on a configured instance can be watched. Kicks a sync so the
+/// watched MR's diff and discussions are pulled in right away.
+#[tauri::command]
+pub async fn watch_mr(
+    pool: State<'_, DbPool>,
+    sync_handle: State<'_, SyncHandle>,
+    web_url: Option<String>,
+    mr_id: Option<i64>,
+) -> Result<ResolvedMr, AppError> {
+    let resolved = match (mr_id, web_url) {
+        (Some(id), _) => {
+            let state: Option<String> =
+                sqlx::query_scalar("SELECT state FROM merge_requests WHERE id = ?")
+                    .bind(id)
+                    .fetch_optional(pool.inner())
+                    .await?;
+            let state =
+                state.ok_or_else(|| AppError::not_found_with_id("MergeRequest", id.to_string()))?;
+            ResolvedMr {
+                local_id: id,
+                state,
+            }
+        }
+        (None, Some(url)) => {
+            let normalized = url.trim_end_matches('/');
+            let cached: Option<(i64, String)> =
+                sqlx::query_as("SELECT id, state FROM merge_requests WHERE web_url = ?")
+                    .bind(normalized)
+                    .fetch_optional(pool.inner())
+                    .await?;
+            match cached {
+                Some((local_id, state)) => ResolvedMr { local_id, state },
+                None => fetch_mr_by_web_url(pool.clone(), normalized.to_string()).await?,
+            }
+        }
+        (None, None) => {
+            return Err(AppError::invalid_input(
+                "Either webUrl or mrId is required to watch an MR",
+            ))
+        }
+    };
+
+    watched::set_watched(pool.inner(), resolved.local_id, true).await?;
+    // Best-effort: pull the watched MR's diff and discussions now.
+    let _ = sync_handle.trigger_sync().await;
+    Ok(resolved)
+}
+
+/// Stop watching an MR. If it matches none of the regular sync scopes, the
+/// next sync purges it like any other MR that dropped out of the fetch.
+#[tauri::command]
+pub async fn unwatch_mr(pool: State<'_, DbPool>, mr_id: i64) -> Result<(), AppError> {
+    if !watched::set_watched(pool.inner(), mr_id, false).await? {
+        return Err(AppError::not_found_with_id(
+            "MergeRequest",
+            mr_id.to_string(),
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,118 @@
+//! Local working copy commands.
+//!
+//! Each project can be mapped to a clone on disk; checking out an MR fetches
+//! its head there and switches to its branch, so the code can be run right
+//! after reviewing it. Files of the clone can also be opened in the user's
+//! editor.
+
+use crate::commands::settings::load_settings;
+use crate::db::pool::DbPool;
+use crate::db::project_repos;
+use crate::error::AppError;
+use crate::services::editor;
+use crate::services::git::{self, Checkout};
+use chrono::Utc;
+use std::path::PathBuf;
+use tauri::{AppHandle, State};
+
+async fn mr_project(
+    pool: &DbPool,
+    mr_id: i64,
+) -> Result<(i64, i64, i64, String, String), AppError> {
+    sqlx::query_as(
+        "SELECT instance_id, project_id, iid, source_branch, web_url FROM merge_requests WHERE id = ?",
+    )
+    .bind(mr_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::not_found_with_id("MergeRequest", mr_id.to_string()))
+}
+
+/// The working copy mapped to a project; an error when none is set.
+async fn mapped_repo(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<PathBuf, AppError> {
+    let path = project_repos::get(pool, instance_id, project_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::invalid_input_field(
+                "No local working copy is set for this project",
+                "repo_path",
+            )
+        })?;
+    git::working_copy(&path).await
+}
+
+/// Get the working copy mapped to the MR's project, if one is set.
+#[tauri::command]
+pub async fn get_mr_repo_path(
+    pool: State<'_, DbPool>,
+    mr_id: i64,
+) -> Result<Option<String>, AppError> {
+    let (instance_id, project_id, ..) = mr_project(pool.inner(), mr_id).await?;
+    Ok(project_repos::get(pool.inner(), instance_id, project_id).await?)
+}
+
+/// Check out an MR's source branch in its project's working copy.
+///
+/// # Arguments
+/// * `mr_id` - Merge request ID
+/// * `repo_path` - Working copy to use; when given it is remembered for the
+///   project. Without it the remembered one is used.
+///
+/// # Returns
+/// The branch and commit checked out
+#[tauri::command]
+pub async fn checkout_mr_branch(
+    pool: State<'_, DbPool>,
+    mr_id: i64,
+    repo_path: Option<String>,
+) -> Result<Checkout, AppError> {
+    let pool = pool.inner();
+    let (instance_id, project_id, iid, source_branch, web_url) = mr_project(pool, mr_id).await?;
+    let project_url = web_url
+        .split_once("/-/merge_requests/")
+        .map_or(web_url.as_str(), |(project, _)| project);
+
+    let repo = match repo_path.filter(|p| !p.trim().is_empty()) {
+        Some(path) => {
+            let repo = git::working_copy(&path).await?;
+            project_repos::set(
+                pool,
+                instance_id,
+                project_id,
+                &repo.display().to_string(),
+                Utc::now().timestamp(),
+            )
+            .await?;
+            repo
+        }
+        None => mapped_repo(pool, instance_id, project_id).await?,
+    };
+
+    git::checkout_mr(&repo, project_url, iid, &source_branch).await
+}
+
+/// Open a file of a project's working copy in the configured editor.
+///
+/// # Arguments
+/// * `instance_id` - GitLab instance of the project
+/// * `project_id` - Project whose working copy is used
+/// * `file_path` - Path relative to the repository root, as in the diff
+/// * `line` - 1-based line to open at; the first line when omitted
+#[tauri::command]
+pub async fn open_in_editor(
+    app: AppHandle,
+    pool: State<'_, DbPool>,
+    instance_id: i64,
+    project_id: i64,
+    file_path: String,
+    line: Option<i64>,
+) -> Result<(), AppError> {
+    let template = load_settings(&app).await?.editor_command;
+    let repo = mapped_repo(pool.inner(), instance_id, project_id).await?;
+    let file = editor::resolve_file(&repo, &file_path)?;
+    editor::open(&template, &repo, &file, line.unwrap_or(1))
+}
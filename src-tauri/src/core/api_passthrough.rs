@@ -0,0 +1,148 @@
+//! Raw GitLab API passthrough for power users and debugging.
+//!
+//! Runs an arbitrary REST call against an instance with its stored token, so
+//! users can poke at the API without copying the token out of the app. Only
+//! `GET` is allowed unless writes are enabled in the settings.
+
+use crate::core::create_client;
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use reqwest::Method;
+
+/// Parse an HTTP method name, accepting the verbs GitLab's REST API uses.
+pub fn parse_method(method: &str) -> Result<Method, AppError> {
+    match method.trim().to_ascii_uppercase().as_str() {
+        "GET" => Ok(Method::GET),
+        "POST" => Ok(Method::POST),
+        "PUT" => Ok(Method::PUT),
+        "PATCH" => Ok(Method::PATCH),
+        "DELETE" => Ok(Method::DELETE),
+        other => Err(AppError::invalid_input_field(
+            format!("Unsupported HTTP method: {other}"),
+            "method",
+        )),
+    }
+}
+
+/// Normalize a user-supplied API path to an endpoint relative to `/api/v4`.
+///
+/// Accepts `/projects/1`, `projects/1` and `/api/v4/projects/1`. Anything
+/// that could leave the API root — full URLs, `..` segments, fragments or
+/// whitespace — is rejected so the token is only ever sent to the instance.
+pub fn normalize_path(path: &str) -> Result<String, AppError> {
+    let invalid = |msg: &str| AppError::invalid_input_field(msg, "path");
+
+    let path = path.trim();
+    if path.is_empty() {
+        return Err(invalid("Path is required"));
+    }
+    if path.contains("://") || path.starts_with("//") {
+        return Err(invalid("Path must be relative to the API root, not a URL"));
+    }
+    if path.chars().any(|c| c.is_whitespace() || c.is_control()) || path.contains('#') {
+        return Err(invalid("Path must not contain whitespace or fragments"));
+    }
+
+    let path = if path.starts_with('/') {
+        path.to_string()
+    } else {
+        format!("/{path}")
+    };
+    let path = match path.strip_prefix("/api/v4") {
+        Some(rest) if rest.is_empty() || rest.starts_with(['/', '?']) => rest.to_string(),
+        _ => path,
+    };
+    let route = path.split('?').next().unwrap_or_default();
+    let is_dot_segment = |seg: &str| {
+        let seg = seg.replace("%2e", ".").replace("%2E", ".");
+        seg == ".." || seg == "."
+    };
+    if route.split('/').any(is_dot_segment) {
+        return Err(invalid("Path must not contain '.' or '..' segments"));
+    }
+    if route.is_empty() || route == "/" {
+        return Err(invalid("Path must name an API endpoint"));
+    }
+    Ok(path)
+}
+
+/// Execute `method path` against the instance and return the JSON response.
+///
+/// `allow_writes` comes from the settings; without it only `GET` runs.
+pub async fn request(
+    pool: &DbPool,
+    instance_id: i64,
+    method: &str,
+    path: &str,
+    body: Option<serde_json::Value>,
+    allow_writes: bool,
+) -> Result<serde_json::Value, AppError> {
+    let method = parse_method(method)?;
+    let endpoint = normalize_path(path)?;
+
+    if method != Method::GET && !allow_writes {
+        return Err(AppError::invalid_input_field(
+            "Write requests are disabled. Enable API writes in Settings to send them.",
+            "method",
+        ));
+    }
+    if method == Method::GET && body.is_some() {
+        return Err(AppError::invalid_input_field(
+            "GET requests can't have a body",
+            "body",
+        ));
+    }
+
+    log::info!(
+        "[api-passthrough] instance={} {} {}",
+        instance_id,
+        method,
+        endpoint
+    );
+    let client = create_client(pool, instance_id).await?;
+    client.raw_request(method, &endpoint, body.as_ref()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths_are_normalized_to_the_api_root() {
+        assert_eq!(normalize_path("/projects/1").unwrap(), "/projects/1");
+        assert_eq!(normalize_path(" projects/1 ").unwrap(), "/projects/1");
+        assert_eq!(
+            normalize_path("/api/v4/projects/1/merge_requests?state=opened").unwrap(),
+            "/projects/1/merge_requests?state=opened"
+        );
+        assert_eq!(
+            normalize_path("/projects/group%2Fapp").unwrap(),
+            "/projects/group%2Fapp"
+        );
+    }
+
+    #[test]
+    fn paths_leaving_the_api_root_are_rejected() {
+        for path in [
+            "",
+            "/",
+            "/api/v4",
+            "https://evil.example/api/v4/user",
+            "//evil.example/user",
+            "/projects/../../admin",
+            "/projects/%2e%2E/admin",
+            "/projects/1#frag",
+            "/projects/1 HTTP/1.1",
+            "/api/v4?private_token=x",
+        ] {
+            assert!(normalize_path(path).is_err(), "accepted {path:?}");
+        }
+    }
+
+    #[test]
+    fn methods_are_case_insensitive() {
+        assert_eq!(parse_method("get").unwrap(), Method::GET);
+        assert_eq!(parse_method("Delete").unwrap(), Method::DELETE);
+        assert!(parse_method("TRACE").is_err());
+    }
+}
@@ -0,0 +1,308 @@
+//! Optional reviewer checklist gate checked before approving an MR.
+//!
+//! A project can require that every changed file was viewed, that threads the
+//! user started are resolved, and that a fixed checklist is ticked. With the
+//! gate disabled (the default) nothing is checked.
+
+use crate::core::authenticated_username;
+use crate::core::file_review;
+use crate::core::mr_actions::mr_api_ids;
+use crate::db::approval_gates::{self, ApprovalGateRow};
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Gate configuration for one project.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalGate {
+    pub instance_id: i64,
+    pub project_id: i64,
+    pub enabled: bool,
+    pub require_files_viewed: bool,
+    pub require_own_threads_resolved: bool,
+    /// Items the reviewer must tick before approving.
+    pub checklist: Vec<String>,
+}
+
+impl ApprovalGate {
+    fn disabled(instance_id: i64, project_id: i64) -> Self {
+        Self {
+            instance_id,
+            project_id,
+            enabled: false,
+            require_files_viewed: true,
+            require_own_threads_resolved: true,
+            checklist: Vec::new(),
+        }
+    }
+}
+
+impl From<ApprovalGateRow> for ApprovalGate {
+    fn from(row: ApprovalGateRow) -> Self {
+        Self {
+            instance_id: row.instance_id,
+            project_id: row.project_id,
+            enabled: row.enabled,
+            require_files_viewed: row.require_files_viewed,
+            require_own_threads_resolved: row.require_own_threads_resolved,
+            checklist: serde_json::from_str(&row.checklist).unwrap_or_default(),
+        }
+    }
+}
+
+/// A gate condition that isn't met yet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum UnmetCondition {
+    /// Changed files the reviewer hasn't marked as viewed.
+    UnviewedFiles { files: Vec<String> },
+    /// Resolvable threads started by the user that are still open.
+    UnresolvedOwnThreads { discussion_ids: Vec<String> },
+    /// Checklist items not ticked on this MR.
+    ChecklistIncomplete { items: Vec<String> },
+}
+
+/// A checklist item with its state on one MR.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecklistItem {
+    pub label: String,
+    pub checked: bool,
+}
+
+/// Load a project's gate, falling back to a disabled one.
+pub async fn load_gate(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<ApprovalGate, AppError> {
+    Ok(approval_gates::get_gate(pool, instance_id, project_id)
+        .await?
+        .map(ApprovalGate::from)
+        .unwrap_or_else(|| ApprovalGate::disabled(instance_id, project_id)))
+}
+
+/// Persist a project's gate configuration.
+pub async fn save_gate(pool: &DbPool, gate: &ApprovalGate) -> Result<(), AppError> {
+    let row = ApprovalGateRow {
+        instance_id: gate.instance_id,
+        project_id: gate.project_id,
+        enabled: gate.enabled,
+        require_files_viewed: gate.require_files_viewed,
+        require_own_threads_resolved: gate.require_own_threads_resolved,
+        checklist: serde_json::to_string(&gate.checklist)?,
+    };
+    approval_gates::upsert_gate(pool, &row).await?;
+    Ok(())
+}
+
+/// The MR's project checklist with each item's ticked state.
+pub async fn review_checklist(pool: &DbPool, mr_id: i64) -> Result<Vec<ChecklistItem>, AppError> {
+    let (instance_id, project_id, _) = mr_api_ids(pool, mr_id).await?;
+    let gate = load_gate(pool, instance_id, project_id).await?;
+    let checked: HashSet<String> = approval_gates::checked_items(pool, mr_id)
+        .await?
+        .into_iter()
+        .collect();
+    Ok(gate
+        .checklist
+        .into_iter()
+        .map(|label| ChecklistItem {
+            checked: checked.contains(&label),
+            label,
+        })
+        .collect())
+}
+
+/// Discussion ids of the open resolvable threads `username` started on an
+/// MR. A thread belongs to whoever wrote its first note.
+pub async fn own_open_threads(
+    pool: &DbPool,
+    mr_id: i64,
+    username: &str,
+) -> Result<Vec<String>, AppError> {
+    Ok(sqlx::query_scalar(
+        r#"
+        SELECT DISTINCT c.discussion_id
+        FROM comments c
+        WHERE c.mr_id = ? AND c.discussion_id IS NOT NULL
+          AND c.resolvable = 1 AND c.resolved = 0 AND c.system = 0
+          AND c.author_username = ?
+          AND c.created_at = (
+              SELECT MIN(created_at) FROM comments
+              WHERE mr_id = c.mr_id AND discussion_id = c.discussion_id
+          )
+        "#,
+    )
+    .bind(mr_id)
+    .bind(username)
+    .fetch_all(pool)
+    .await?)
+}
+
+/// Check the gate for an MR. Returns the unmet conditions, empty when the
+/// MR may be approved (always empty if the gate is disabled).
+///
+/// Viewed files are the ones marked viewed at the head of the cached diff,
+/// never a list from the caller.
+pub async fn evaluate(pool: &DbPool, mr_id: i64) -> Result<Vec<UnmetCondition>, AppError> {
+    let (instance_id, project_id, _) = mr_api_ids(pool, mr_id).await?;
+    let gate = load_gate(pool, instance_id, project_id).await?;
+    if !gate.enabled {
+        return Ok(Vec::new());
+    }
+
+    let mut unmet = Vec::new();
+
+    if gate.require_files_viewed {
+        let viewed: HashSet<String> = file_review::states(pool, mr_id)
+            .await?
+            .into_iter()
+            .map(|state| state.file_path)
+            .collect();
+        let files: Vec<String> = sqlx::query_scalar(
+            "SELECT new_path FROM diff_files WHERE mr_id = ? ORDER BY file_position",
+        )
+        .bind(mr_id)
+        .fetch_all(pool)
+        .await?;
+        let unviewed: Vec<String> = files.into_iter().filter(|f| !viewed.contains(f)).collect();
+        if !unviewed.is_empty() {
+            unmet.push(UnmetCondition::UnviewedFiles { files: unviewed });
+        }
+    }
+
+    if gate.require_own_threads_resolved {
+        if let Some(me) = authenticated_username(pool, instance_id).await? {
+            let discussion_ids = own_open_threads(pool, mr_id, &me).await?;
+            if !discussion_ids.is_empty() {
+                unmet.push(UnmetCondition::UnresolvedOwnThreads { discussion_ids });
+            }
+        }
+    }
+
+    let missing: Vec<String> = review_checklist(pool, mr_id)
+        .await?
+        .into_iter()
+        .filter(|item| !item.checked)
+        .map(|item| item.label)
+        .collect();
+    if !missing.is_empty() {
+        unmet.push(UnmetCondition::ChecklistIncomplete { items: missing });
+    }
+
+    Ok(unmet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db;
+    use tempfile::{tempdir, TempDir};
+
+    /// One instance (user "me"), MR 1 in project 10 with a cached diff of
+    /// a.rs and b.rs, and an open thread started by "me".
+    async fn setup() -> (TempDir, DbPool) {
+        let dir = tempdir().unwrap();
+        let pool = db::initialize(&dir.path().join("t.db")).await.unwrap();
+        sqlx::query(
+            "INSERT INTO gitlab_instances (id, url, token, created_at, authenticated_username)
+             VALUES (1, 'u', 't', 0, 'me')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, project_name, title, author_username,
+              source_branch, target_branch, state, web_url, created_at, updated_at,
+              labels, reviewers, cached_at)
+             VALUES (1, 1, 1, 10, 'g/p', 't', 'alice', 's', 'main', 'opened', 'http://x', 0, 0, '[]', '[]', 0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO diffs (mr_id, content, base_sha, head_sha, start_sha, file_count, additions, deletions)
+             VALUES (1, '', 'base', 'head', 'start', 2, 2, 2)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        for (pos, path) in ["a.rs", "b.rs"].iter().enumerate() {
+            sqlx::query(
+                "INSERT INTO diff_files (mr_id, new_path, change_type, additions, deletions, file_position)
+                 VALUES (1, ?, 'modified', 1, 1, ?)",
+            )
+            .bind(path)
+            .bind(pos as i64)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        sqlx::query(
+            "INSERT INTO comments (id, mr_id, discussion_id, author_username, body, resolvable, resolved, created_at, updated_at)
+             VALUES (100, 1, 'd1', 'me', 'q', 1, 0, 5, 5), (101, 1, 'd1', 'alice', 'a', 1, 0, 6, 6),
+                    (102, 1, 'd2', 'alice', 'x', 1, 0, 5, 5), (103, 1, 'd2', 'me', 'y', 1, 0, 6, 6)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        (dir, pool)
+    }
+
+    #[tokio::test]
+    async fn disabled_gate_allows_approval() {
+        let (_dir, pool) = setup().await;
+        assert!(evaluate(&pool, 1).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn enabled_gate_lists_unmet_conditions() {
+        let (_dir, pool) = setup().await;
+        let gate = ApprovalGate {
+            enabled: true,
+            checklist: vec!["Tests cover the change".into()],
+            ..ApprovalGate::disabled(1, 10)
+        };
+        save_gate(&pool, &gate).await.unwrap();
+
+        file_review::mark_viewed(&pool, 1, "a.rs", true, 0)
+            .await
+            .unwrap();
+        let unmet = evaluate(&pool, 1).await.unwrap();
+        assert_eq!(
+            unmet,
+            vec![
+                UnmetCondition::UnviewedFiles {
+                    files: vec!["b.rs".into()]
+                },
+                // d2 was started by alice, so only d1 counts.
+                UnmetCondition::UnresolvedOwnThreads {
+                    discussion_ids: vec!["d1".into()]
+                },
+                UnmetCondition::ChecklistIncomplete {
+                    items: vec!["Tests cover the change".into()]
+                },
+            ]
+        );
+
+        approval_gates::set_item_checked(&pool, 1, "Tests cover the change", true, 0)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE comments SET resolved = 1 WHERE discussion_id = 'd1'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        file_review::mark_viewed(&pool, 1, "b.rs", true, 0)
+            .await
+            .unwrap();
+        assert!(evaluate(&pool, 1).await.unwrap().is_empty());
+    }
+}
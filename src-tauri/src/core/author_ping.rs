@@ -0,0 +1,474 @@
+//! Reviewer-to-author pings.
+//!
+//! A ping is an ordinary MR comment that mentions the author, worded from a
+//! template and linking the unresolved threads it is about. Every ping is
+//! recorded in `author_pings`, and the same template is refused until the
+//! author has reacted (commented or pushed) or new threads came up.
+
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::collections::HashMap;
+
+/// What the author is being pinged about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PingTemplate {
+    /// Threads where the last word is mine.
+    AwaitingReply,
+    /// All unresolved threads raised by reviewers.
+    OpenThreads,
+    /// The source branch needs a rebase.
+    Rebase,
+}
+
+impl PingTemplate {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::AwaitingReply => "awaiting_reply",
+            Self::OpenThreads => "open_threads",
+            Self::Rebase => "rebase",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "awaiting_reply" => Some(Self::AwaitingReply),
+            "open_threads" => Some(Self::OpenThreads),
+            "rebase" => Some(Self::Rebase),
+            _ => None,
+        }
+    }
+}
+
+/// An unresolved discussion, reduced to what a ping needs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpenThread {
+    pub discussion_id: String,
+    /// GitLab ID of the first note; the thread's link anchor.
+    pub first_note_id: i64,
+    pub started_by: String,
+    pub last_author: String,
+}
+
+/// The MR as a ping sees it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PingContext {
+    pub author: String,
+    pub web_url: String,
+    pub target_branch: String,
+}
+
+/// Threads a ping with `template` should link.
+pub fn relevant_threads<'a>(
+    template: PingTemplate,
+    threads: &'a [OpenThread],
+    me: &str,
+    author: &str,
+) -> Vec<&'a OpenThread> {
+    threads
+        .iter()
+        .filter(|t| match template {
+            PingTemplate::AwaitingReply => t.last_author == me,
+            PingTemplate::OpenThreads => t.started_by != author,
+            PingTemplate::Rebase => false,
+        })
+        .collect()
+}
+
+/// Comment body for a ping.
+pub fn render_body(template: PingTemplate, mr: &PingContext, threads: &[&OpenThread]) -> String {
+    let links: Vec<String> = threads
+        .iter()
+        .map(|t| format!("{}#note_{}", mr.web_url, t.first_note_id))
+        .collect();
+    let mention = format!("@{}", mr.author);
+    match (template, links.as_slice()) {
+        (PingTemplate::Rebase, _) => format!(
+            "{mention} could you rebase this onto `{}`? It needs to be up to date before it can be merged.",
+            mr.target_branch
+        ),
+        (PingTemplate::AwaitingReply, [link]) => {
+            format!("{mention} I'm waiting on your reply in this thread: {link}")
+        }
+        (PingTemplate::AwaitingReply, _) => format!(
+            "{mention} I'm waiting on your reply in these threads:\n\n{}",
+            bullet_list(&links)
+        ),
+        (PingTemplate::OpenThreads, [link]) => {
+            format!("{mention} there is still an unresolved thread here: {link}")
+        }
+        (PingTemplate::OpenThreads, _) => format!(
+            "{mention} there are still {} unresolved threads here:\n\n{}",
+            links.len(),
+            bullet_list(&links)
+        ),
+    }
+}
+
+fn bullet_list(items: &[String]) -> String {
+    items
+        .iter()
+        .map(|i| format!("- {i}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A ping sent earlier.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PingRecord {
+    pub discussion_ids: Vec<String>,
+    pub head_sha: Option<String>,
+    pub pinged_at: i64,
+}
+
+/// Whether a new ping would only repeat `previous`: the author hasn't
+/// commented or pushed since, and it links no thread the previous one didn't.
+pub fn is_repeat(
+    previous: &PingRecord,
+    discussion_ids: &[String],
+    head_sha: Option<&str>,
+    author_commented_since: bool,
+) -> bool {
+    !author_commented_since
+        && previous.head_sha.as_deref() == head_sha
+        && discussion_ids
+            .iter()
+            .all(|id| previous.discussion_ids.contains(id))
+}
+
+/// A ping ready to be posted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreparedPing {
+    pub body: String,
+    pub discussion_ids: Vec<String>,
+    pub head_sha: Option<String>,
+}
+
+/// A recorded ping, as listed for an MR.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorPing {
+    pub template: PingTemplate,
+    pub thread_count: usize,
+    pub pinged_at: i64,
+}
+
+#[derive(FromRow)]
+struct MrRow {
+    author_username: String,
+    web_url: String,
+    target_branch: String,
+    me: Option<String>,
+    head_sha: Option<String>,
+}
+
+#[derive(FromRow)]
+struct NoteRow {
+    discussion_id: String,
+    id: i64,
+    author_username: String,
+}
+
+#[derive(FromRow)]
+struct PingRow {
+    template: String,
+    discussion_ids: String,
+    head_sha: Option<String>,
+    pinged_at: i64,
+}
+
+/// Unresolved discussions of an MR that exist on GitLab, oldest first.
+async fn open_threads(pool: &DbPool, mr_id: i64) -> Result<Vec<OpenThread>, AppError> {
+    let notes: Vec<NoteRow> = sqlx::query_as(
+        "SELECT discussion_id, id, author_username FROM comments
+         WHERE mr_id = ? AND system = 0 AND discussion_id IN (
+             SELECT discussion_id FROM comments
+             WHERE mr_id = ? AND resolvable = 1 AND resolved = 0 AND system = 0
+         )
+         ORDER BY created_at, id",
+    )
+    .bind(mr_id)
+    .bind(mr_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut threads: Vec<OpenThread> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    for note in notes {
+        match index.get(&note.discussion_id) {
+            Some(&i) => threads[i].last_author = note.author_username,
+            None => {
+                index.insert(note.discussion_id.clone(), threads.len());
+                threads.push(OpenThread {
+                    discussion_id: note.discussion_id,
+                    first_note_id: note.id,
+                    started_by: note.author_username.clone(),
+                    last_author: note.author_username,
+                });
+            }
+        }
+    }
+    // Threads started locally have no GitLab note to link yet.
+    threads.retain(|t| t.first_note_id > 0);
+    Ok(threads)
+}
+
+async fn last_ping(
+    pool: &DbPool,
+    mr_id: i64,
+    template: PingTemplate,
+) -> Result<Option<PingRecord>, AppError> {
+    let row: Option<PingRow> = sqlx::query_as(
+        "SELECT template, discussion_ids, head_sha, pinged_at FROM author_pings
+         WHERE mr_id = ? AND template = ? ORDER BY pinged_at DESC, id DESC LIMIT 1",
+    )
+    .bind(mr_id)
+    .bind(template.as_str())
+    .fetch_optional(pool)
+    .await?;
+    Ok(row.map(|r| PingRecord {
+        discussion_ids: serde_json::from_str(&r.discussion_ids).unwrap_or_default(),
+        head_sha: r.head_sha,
+        pinged_at: r.pinged_at,
+    }))
+}
+
+/// Build the ping comment for an MR I'm reviewing.
+///
+/// Fails when I'm the author, when the template has no thread to point at,
+/// or when the same ping was already sent and the author hasn't reacted.
+pub async fn prepare_ping(
+    pool: &DbPool,
+    mr_id: i64,
+    template: PingTemplate,
+) -> Result<PreparedPing, AppError> {
+    let mr: MrRow = sqlx::query_as(
+        "SELECT mr.author_username, mr.web_url, mr.target_branch,
+                i.authenticated_username AS me, d.head_sha
+         FROM merge_requests mr
+         JOIN gitlab_instances i ON i.id = mr.instance_id
+         LEFT JOIN diffs d ON d.mr_id = mr.id
+         WHERE mr.id = ?",
+    )
+    .bind(mr_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::not_found_with_id("MergeRequest", mr_id.to_string()))?;
+    let me = mr.me.unwrap_or_default();
+    if mr.author_username == me {
+        return Err(AppError::invalid_input(
+            "You are the author of this merge request",
+        ));
+    }
+
+    let threads = open_threads(pool, mr_id).await?;
+    let relevant = relevant_threads(template, &threads, &me, &mr.author_username);
+    if relevant.is_empty() {
+        match template {
+            PingTemplate::AwaitingReply => {
+                return Err(AppError::invalid_input(
+                    "No unresolved thread is waiting on the author",
+                ))
+            }
+            PingTemplate::OpenThreads => {
+                return Err(AppError::invalid_input("No unresolved threads to point at"))
+            }
+            PingTemplate::Rebase => {}
+        }
+    }
+    let discussion_ids: Vec<String> = relevant.iter().map(|t| t.discussion_id.clone()).collect();
+
+    if let Some(previous) = last_ping(pool, mr_id, template).await? {
+        let author_commented_since: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM comments
+             WHERE mr_id = ? AND author_username = ? AND system = 0 AND created_at > ?)",
+        )
+        .bind(mr_id)
+        .bind(&mr.author_username)
+        .bind(previous.pinged_at)
+        .fetch_one(pool)
+        .await?;
+        if is_repeat(
+            &previous,
+            &discussion_ids,
+            mr.head_sha.as_deref(),
+            author_commented_since,
+        ) {
+            return Err(AppError::invalid_input(format!(
+                "@{} was already pinged about this and hasn't responded yet",
+                mr.author_username
+            )));
+        }
+    }
+
+    let context = PingContext {
+        author: mr.author_username,
+        web_url: mr.web_url,
+        target_branch: mr.target_branch,
+    };
+    Ok(PreparedPing {
+        body: render_body(template, &context, &relevant),
+        discussion_ids,
+        head_sha: mr.head_sha,
+    })
+}
+
+/// Remember a posted ping.
+pub async fn record_ping(
+    pool: &DbPool,
+    mr_id: i64,
+    template: PingTemplate,
+    ping: &PreparedPing,
+    now: i64,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO author_pings (mr_id, template, discussion_ids, head_sha, pinged_at)
+         VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(mr_id)
+    .bind(template.as_str())
+    .bind(serde_json::to_string(&ping.discussion_ids)?)
+    .bind(&ping.head_sha)
+    .bind(now)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Pings sent for an MR, newest first.
+pub async fn list_pings(pool: &DbPool, mr_id: i64) -> Result<Vec<AuthorPing>, AppError> {
+    let rows: Vec<PingRow> = sqlx::query_as(
+        "SELECT template, discussion_ids, head_sha, pinged_at FROM author_pings
+         WHERE mr_id = ? ORDER BY pinged_at DESC, id DESC",
+    )
+    .bind(mr_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|r| {
+            let ids: Vec<String> = serde_json::from_str(&r.discussion_ids).unwrap_or_default();
+            Some(AuthorPing {
+                template: PingTemplate::parse(&r.template)?,
+                thread_count: ids.len(),
+                pinged_at: r.pinged_at,
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+
+    fn thread(id: &str, note: i64, started_by: &str, last_author: &str) -> OpenThread {
+        OpenThread {
+            discussion_id: id.to_string(),
+            first_note_id: note,
+            started_by: started_by.to_string(),
+            last_author: last_author.to_string(),
+        }
+    }
+
+    #[test]
+    fn renders_links_to_the_relevant_threads() {
+        let threads = vec![
+            thread("d1", 11, "me", "me"),
+            thread("d2", 12, "me", "alice"),
+            thread("d3", 13, "alice", "bob"),
+        ];
+        let mr = PingContext {
+            author: "alice".into(),
+            web_url: "https://gl/g/p/-/merge_requests/3".into(),
+            target_branch: "main".into(),
+        };
+
+        let waiting = relevant_threads(PingTemplate::AwaitingReply, &threads, "me", "alice");
+        assert_eq!(
+            render_body(PingTemplate::AwaitingReply, &mr, &waiting),
+            "@alice I'm waiting on your reply in this thread: https://gl/g/p/-/merge_requests/3#note_11"
+        );
+
+        let open = relevant_threads(PingTemplate::OpenThreads, &threads, "me", "alice");
+        let body = render_body(PingTemplate::OpenThreads, &mr, &open);
+        assert!(body.starts_with("@alice there are still 2 unresolved threads"));
+        assert!(body.contains("- https://gl/g/p/-/merge_requests/3#note_12"));
+        assert!(!body.contains("note_13"));
+
+        assert!(relevant_threads(PingTemplate::Rebase, &threads, "me", "alice").is_empty());
+        assert!(render_body(PingTemplate::Rebase, &mr, &[]).contains("onto `main`"));
+    }
+
+    #[test]
+    fn repeat_until_the_author_reacts() {
+        let previous = PingRecord {
+            discussion_ids: vec!["d1".into(), "d2".into()],
+            head_sha: Some("abc".into()),
+            pinged_at: 100,
+        };
+        let same = vec!["d1".to_string()];
+        assert!(is_repeat(&previous, &same, Some("abc"), false));
+        assert!(!is_repeat(&previous, &same, Some("abc"), true));
+        assert!(!is_repeat(&previous, &same, Some("def"), false));
+        assert!(!is_repeat(
+            &previous,
+            &["d3".to_string()],
+            Some("abc"),
+            false
+        ));
+    }
+
+    #[tokio::test]
+    async fn refuses_a_second_ping_without_a_reaction() {
+        let (pool, inst) = seed_instance(true).await;
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, project_name, title, author_username,
+              source_branch, target_branch, state, web_url, created_at, updated_at,
+              labels, reviewers, cached_at)
+             VALUES (1, ?, 3, 10, 'g/p', 't', 'alice', 's', 'main', 'opened', 'http://x', 0, 0, '[]', '[]', 0)",
+        )
+        .bind(inst)
+        .execute(&pool)
+        .await
+        .unwrap();
+        for (id, author, at) in [(11, "me", 10), (12, "alice", 20), (13, "me", 30)] {
+            sqlx::query(
+                "INSERT INTO comments (id, mr_id, discussion_id, author_username, body,
+                                       resolvable, resolved, created_at, updated_at)
+                 VALUES (?, 1, 'd1', ?, 'x', 1, 0, ?, ?)",
+            )
+            .bind(id)
+            .bind(author)
+            .bind(at)
+            .bind(at)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let ping = prepare_ping(&pool, 1, PingTemplate::AwaitingReply)
+            .await
+            .unwrap();
+        assert_eq!(ping.discussion_ids, vec!["d1".to_string()]);
+        assert!(ping.body.contains("http://x#note_11"));
+        record_ping(&pool, 1, PingTemplate::AwaitingReply, &ping, 40)
+            .await
+            .unwrap();
+
+        let err = prepare_ping(&pool, 1, PingTemplate::AwaitingReply)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("already pinged"));
+        // A different nudge is still allowed.
+        assert!(prepare_ping(&pool, 1, PingTemplate::Rebase).await.is_ok());
+
+        let pings = list_pings(&pool, 1).await.unwrap();
+        assert_eq!(pings.len(), 1);
+        assert_eq!(pings[0].template, PingTemplate::AwaitingReply);
+        assert_eq!(pings[0].thread_count, 1);
+    }
+}
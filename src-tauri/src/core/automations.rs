@@ -0,0 +1,676 @@
+//! User-defined automations.
+//!
+//! A rule pairs a trigger ("an MR I authored was approved") with an action:
+//! POST a JSON payload to a webhook (Slack-compatible `text` field included)
+//! or run a shell command. Triggers are derived locally from the before/after
+//! MR snapshots the sync engine already takes for the change log, so nothing
+//! here needs GitLab-side webhooks. Every execution is written to a run log.
+
+use crate::db::automations::{self, AutomationRuleRow, AutomationRunRow};
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::time::{Duration, Instant};
+
+/// How long a webhook request or shell command may take.
+const ACTION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Longest output kept in the run log.
+const MAX_DETAIL_LEN: usize = 2000;
+
+/// Runs returned by the log view.
+pub const RUN_LOG_LIMIT: i64 = 100;
+
+/// Local event a rule reacts to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutomationTrigger {
+    /// An MR authored by the user became approved.
+    MyMrApproved,
+    /// The head pipeline of an MR authored by the user failed.
+    MyMrPipelineFailed,
+    /// An MR authored by the user was merged.
+    MyMrMerged,
+    /// The user was added as a reviewer on someone else's open MR.
+    ReviewRequested,
+}
+
+impl AutomationTrigger {
+    pub const ALL: [AutomationTrigger; 4] = [
+        Self::MyMrApproved,
+        Self::MyMrPipelineFailed,
+        Self::MyMrMerged,
+        Self::ReviewRequested,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::MyMrApproved => "my_mr_approved",
+            Self::MyMrPipelineFailed => "my_mr_pipeline_failed",
+            Self::MyMrMerged => "my_mr_merged",
+            Self::ReviewRequested => "review_requested",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.as_str() == s)
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            Self::MyMrApproved => "was approved",
+            Self::MyMrPipelineFailed => "has a failed pipeline",
+            Self::MyMrMerged => "was merged",
+            Self::ReviewRequested => "needs your review",
+        }
+    }
+}
+
+/// What a rule does when its trigger fires.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "target", rename_all = "snake_case")]
+pub enum AutomationAction {
+    /// POST the event as JSON to this URL.
+    Webhook(String),
+    /// Run this command through the platform shell.
+    Command(String),
+}
+
+impl AutomationAction {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Webhook(_) => "webhook",
+            Self::Command(_) => "command",
+        }
+    }
+
+    fn target(&self) -> &str {
+        match self {
+            Self::Webhook(t) | Self::Command(t) => t,
+        }
+    }
+
+    fn from_row(kind: &str, target: String) -> Result<Self, AppError> {
+        match kind {
+            "webhook" => Ok(Self::Webhook(target)),
+            "command" => Ok(Self::Command(target)),
+            other => Err(AppError::internal(format!(
+                "Unknown automation action '{other}'"
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationRule {
+    pub id: i64,
+    pub name: String,
+    pub trigger: AutomationTrigger,
+    pub action: AutomationAction,
+    pub enabled: bool,
+    pub created_at: i64,
+}
+
+impl TryFrom<AutomationRuleRow> for AutomationRule {
+    type Error = AppError;
+
+    fn try_from(row: AutomationRuleRow) -> Result<Self, AppError> {
+        let trigger = AutomationTrigger::parse(&row.trigger).ok_or_else(|| {
+            AppError::internal(format!("Unknown automation trigger '{}'", row.trigger))
+        })?;
+        Ok(Self {
+            id: row.id,
+            name: row.name,
+            trigger,
+            action: AutomationAction::from_row(&row.action_kind, row.target)?,
+            enabled: row.enabled,
+            created_at: row.created_at,
+        })
+    }
+}
+
+/// A rule as submitted from the settings form; `id` is set when editing.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationRuleInput {
+    pub id: Option<i64>,
+    pub name: String,
+    pub trigger: AutomationTrigger,
+    pub action: AutomationAction,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationRun {
+    pub id: i64,
+    pub rule_id: i64,
+    pub trigger: String,
+    pub mr_id: Option<i64>,
+    pub is_test: bool,
+    pub success: bool,
+    pub detail: Option<String>,
+    pub duration_ms: i64,
+    pub ran_at: i64,
+}
+
+impl From<AutomationRunRow> for AutomationRun {
+    fn from(row: AutomationRunRow) -> Self {
+        Self {
+            id: row.id,
+            rule_id: row.rule_id,
+            trigger: row.trigger,
+            mr_id: row.mr_id,
+            is_test: row.is_test,
+            success: row.success,
+            detail: row.detail,
+            duration_ms: row.duration_ms,
+            ran_at: row.ran_at,
+        }
+    }
+}
+
+/// The MR an automation fired for, as handed to the action.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationEvent {
+    pub trigger: AutomationTrigger,
+    pub mr_id: Option<i64>,
+    pub iid: i64,
+    pub title: String,
+    pub project: String,
+    pub author: String,
+    pub web_url: String,
+}
+
+impl AutomationEvent {
+    fn from_snapshot(trigger: AutomationTrigger, snapshot: &Map<String, Value>) -> Self {
+        let text = |key: &str| {
+            snapshot
+                .get(key)
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string()
+        };
+        Self {
+            trigger,
+            mr_id: snapshot.get("id").and_then(Value::as_i64),
+            iid: snapshot
+                .get("iid")
+                .and_then(Value::as_i64)
+                .unwrap_or_default(),
+            title: text("title"),
+            project: text("projectName"),
+            author: text("authorUsername"),
+            web_url: text("webUrl"),
+        }
+    }
+
+    /// Placeholder event used by `test_automation`.
+    fn sample(trigger: AutomationTrigger) -> Self {
+        Self {
+            trigger,
+            mr_id: None,
+            iid: 1,
+            title: "Test automation".to_string(),
+            project: "group/project".to_string(),
+            author: "ultra-gitlab".to_string(),
+            web_url: "https://gitlab.example.com/group/project/-/merge_requests/1".to_string(),
+        }
+    }
+
+    /// One-line summary, used as the webhook `text`.
+    pub fn summary(&self) -> String {
+        format!(
+            "{} !{} \"{}\" {}: {}",
+            self.project,
+            self.iid,
+            self.title,
+            self.trigger.describe(),
+            self.web_url
+        )
+    }
+}
+
+fn field<'a>(snapshot: &'a Map<String, Value>, key: &str) -> Option<&'a str> {
+    snapshot.get(key).and_then(Value::as_str)
+}
+
+fn has_reviewer(snapshot: &Map<String, Value>, username: &str) -> bool {
+    snapshot
+        .get("reviewers")
+        .and_then(Value::as_array)
+        .is_some_and(|r| r.iter().any(|v| v.as_str() == Some(username)))
+}
+
+/// Triggers fired by an MR going from `before` to `after`, from the point
+/// of view of `me`. Transitions need a `before`; a newly cached MR only
+/// fires `ReviewRequested`, and only when `include_new` is set (false on an
+/// instance's first sync, where every MR is new).
+pub fn fired_triggers(
+    before: Option<&Map<String, Value>>,
+    after: &Map<String, Value>,
+    me: &str,
+    include_new: bool,
+) -> Vec<AutomationTrigger> {
+    let mut fired = Vec::new();
+    if me.is_empty() {
+        return fired;
+    }
+    let authored = field(after, "authorUsername") == Some(me);
+
+    if let Some(before) = before {
+        let became = |key: &str, value: &str| {
+            field(before, key) != Some(value) && field(after, key) == Some(value)
+        };
+        if authored && became("approvalStatus", "approved") {
+            fired.push(AutomationTrigger::MyMrApproved);
+        }
+        if authored && became("headPipelineStatus", "failed") {
+            fired.push(AutomationTrigger::MyMrPipelineFailed);
+        }
+        if authored && became("state", "merged") {
+            fired.push(AutomationTrigger::MyMrMerged);
+        }
+    }
+
+    let newly_reviewing = match before {
+        Some(before) => !has_reviewer(before, me),
+        None => include_new,
+    };
+    if !authored
+        && newly_reviewing
+        && has_reviewer(after, me)
+        && field(after, "state") == Some("opened")
+    {
+        fired.push(AutomationTrigger::ReviewRequested);
+    }
+    fired
+}
+
+pub async fn list_rules(pool: &DbPool) -> Result<Vec<AutomationRule>, AppError> {
+    automations::list_rules(pool)
+        .await?
+        .into_iter()
+        .map(AutomationRule::try_from)
+        .collect()
+}
+
+fn validate(input: &AutomationRuleInput) -> Result<(), AppError> {
+    if input.name.trim().is_empty() {
+        return Err(AppError::invalid_input_field("Name is required", "name"));
+    }
+    match &input.action {
+        AutomationAction::Webhook(url) => {
+            let parsed = reqwest::Url::parse(url.trim()).map_err(|_| {
+                AppError::invalid_input_field("Webhook URL is not a valid URL", "target")
+            })?;
+            if !matches!(parsed.scheme(), "http" | "https") {
+                return Err(AppError::invalid_input_field(
+                    "Webhook URL must use http or https",
+                    "target",
+                ));
+            }
+        }
+        AutomationAction::Command(cmd) => {
+            if cmd.trim().is_empty() {
+                return Err(AppError::invalid_input_field(
+                    "Command is required",
+                    "target",
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Create or update a rule and return it.
+pub async fn save_rule(
+    pool: &DbPool,
+    input: AutomationRuleInput,
+    now: i64,
+) -> Result<AutomationRule, AppError> {
+    validate(&input)?;
+    let name = input.name.trim();
+    let target = input.action.target().trim();
+    let id = match input.id {
+        Some(id) => {
+            let updated = automations::update_rule(
+                pool,
+                id,
+                name,
+                input.trigger.as_str(),
+                input.action.kind(),
+                target,
+                input.enabled,
+            )
+            .await?;
+            if !updated {
+                return Err(AppError::not_found_with_id(
+                    "Automation rule",
+                    id.to_string(),
+                ));
+            }
+            id
+        }
+        None => {
+            automations::insert_rule(
+                pool,
+                name,
+                input.trigger.as_str(),
+                input.action.kind(),
+                target,
+                input.enabled,
+                now,
+            )
+            .await?
+        }
+    };
+    get_rule(pool, id).await
+}
+
+async fn get_rule(pool: &DbPool, rule_id: i64) -> Result<AutomationRule, AppError> {
+    automations::get_rule(pool, rule_id)
+        .await?
+        .ok_or_else(|| AppError::not_found_with_id("Automation rule", rule_id.to_string()))?
+        .try_into()
+}
+
+pub async fn delete_rule(pool: &DbPool, rule_id: i64) -> Result<(), AppError> {
+    automations::delete_rule(pool, rule_id).await?;
+    Ok(())
+}
+
+pub async fn list_runs(
+    pool: &DbPool,
+    rule_id: Option<i64>,
+) -> Result<Vec<AutomationRun>, AppError> {
+    Ok(automations::list_runs(pool, rule_id, RUN_LOG_LIMIT)
+        .await?
+        .into_iter()
+        .map(AutomationRun::from)
+        .collect())
+}
+
+fn truncate(mut s: String) -> String {
+    if s.len() > MAX_DETAIL_LEN {
+        let mut end = MAX_DETAIL_LEN;
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        s.truncate(end);
+        s.push('…');
+    }
+    s
+}
+
+async fn post_webhook(url: &str, event: &AutomationEvent) -> Result<String, String> {
+    crate::services::offline_mode::ensure_online().map_err(|e| e.to_string())?;
+    let client = reqwest::Client::builder()
+        .timeout(ACTION_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {e}"))?;
+    let mut payload = serde_json::to_value(event).map_err(|e| e.to_string())?;
+    payload["text"] = json!(event.summary());
+
+    let response = client
+        .post(url)
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {e}"))?;
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    if status.is_success() {
+        Ok(format!("HTTP {status}"))
+    } else {
+        Err(format!("HTTP {status}: {}", body.trim()))
+    }
+}
+
+async fn run_command(command: &str, event: &AutomationEvent) -> Result<String, String> {
+    let mut cmd = if cfg!(windows) {
+        let mut c = tokio::process::Command::new("cmd");
+        c.arg("/C").arg(command);
+        c
+    } else {
+        let mut c = tokio::process::Command::new("sh");
+        c.arg("-c").arg(command);
+        c
+    };
+    cmd.env("ULTRA_TRIGGER", event.trigger.as_str())
+        .env("ULTRA_MR_IID", event.iid.to_string())
+        .env("ULTRA_MR_TITLE", &event.title)
+        .env("ULTRA_MR_PROJECT", &event.project)
+        .env("ULTRA_MR_AUTHOR", &event.author)
+        .env("ULTRA_MR_URL", &event.web_url)
+        .env("ULTRA_SUMMARY", event.summary())
+        .stdin(std::process::Stdio::null())
+        .kill_on_drop(true);
+    if let Some(id) = event.mr_id {
+        cmd.env("ULTRA_MR_ID", id.to_string());
+    }
+
+    let output = tokio::time::timeout(ACTION_TIMEOUT, cmd.output())
+        .await
+        .map_err(|_| format!("Timed out after {}s", ACTION_TIMEOUT.as_secs()))?
+        .map_err(|e| format!("Failed to start command: {e}"))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let combined = [stdout.trim(), stderr.trim()]
+        .into_iter()
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    match output.status.code() {
+        Some(0) => Ok(combined),
+        Some(code) => Err(format!("Exited with code {code}\n{combined}")
+            .trim_end()
+            .to_string()),
+        None => Err("Terminated by a signal".to_string()),
+    }
+}
+
+/// Run `rule` for `event` and record the outcome.
+pub async fn run_and_log(
+    pool: &DbPool,
+    rule: &AutomationRule,
+    event: &AutomationEvent,
+    is_test: bool,
+) -> Result<AutomationRun, AppError> {
+    let start = Instant::now();
+    let outcome = match &rule.action {
+        AutomationAction::Webhook(url) => post_webhook(url, event).await,
+        AutomationAction::Command(command) => run_command(command, event).await,
+    };
+    let duration_ms = start.elapsed().as_millis() as i64;
+    let ran_at = chrono::Utc::now().timestamp();
+    let (success, detail) = match outcome {
+        Ok(detail) => (true, detail),
+        Err(detail) => (false, detail),
+    };
+    if !success {
+        log::warn!("Automation '{}' failed: {}", rule.name, detail);
+    }
+    let detail = (!detail.is_empty()).then(|| truncate(detail));
+
+    let id = automations::insert_run(
+        pool,
+        rule.id,
+        event.trigger.as_str(),
+        event.mr_id,
+        is_test,
+        success,
+        detail.as_deref(),
+        duration_ms,
+        ran_at,
+    )
+    .await?;
+    Ok(AutomationRun {
+        id,
+        rule_id: rule.id,
+        trigger: event.trigger.as_str().to_string(),
+        mr_id: event.mr_id,
+        is_test,
+        success,
+        detail,
+        duration_ms,
+        ran_at,
+    })
+}
+
+/// Run a rule once against a sample MR, regardless of whether it's enabled.
+pub async fn test(pool: &DbPool, rule_id: i64) -> Result<AutomationRun, AppError> {
+    let rule = get_rule(pool, rule_id).await?;
+    let event = AutomationEvent::sample(rule.trigger);
+    run_and_log(pool, &rule, &event, true).await
+}
+
+/// Fire the enabled rules matching an MR change. Called by the sync engine
+/// after it snapshots the MR; rules run in the background so a slow webhook
+/// never holds up sync.
+pub async fn on_mr_changed(
+    pool: &DbPool,
+    instance_id: i64,
+    before: Option<&Map<String, Value>>,
+    after: &Map<String, Value>,
+    include_new: bool,
+) -> Result<(), AppError> {
+    let me: Option<String> =
+        sqlx::query_scalar("SELECT authenticated_username FROM gitlab_instances WHERE id = ?")
+            .bind(instance_id)
+            .fetch_optional(pool)
+            .await?
+            .flatten();
+    let fired = fired_triggers(
+        before,
+        after,
+        me.as_deref().unwrap_or_default(),
+        include_new,
+    );
+    if fired.is_empty() {
+        return Ok(());
+    }
+
+    let rules = automations::list_enabled_rules(pool).await?;
+    for trigger in fired {
+        for row in rules.iter().filter(|r| r.trigger == trigger.as_str()) {
+            let rule = AutomationRule::try_from(row.clone())?;
+            let event = AutomationEvent::from_snapshot(trigger, after);
+            let pool = pool.clone();
+            tokio::spawn(async move {
+                if let Err(e) = run_and_log(&pool, &rule, &event, false).await {
+                    log::warn!("Failed to record automation run for '{}': {}", rule.name, e);
+                }
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+
+    fn snapshot(
+        author: &str,
+        state: &str,
+        approval: &str,
+        reviewers: &[&str],
+    ) -> Map<String, Value> {
+        let Value::Object(map) = json!({
+            "id": 42,
+            "iid": 7,
+            "title": "Add feature",
+            "projectName": "group/app",
+            "authorUsername": author,
+            "webUrl": "https://gitlab.example.com/group/app/-/merge_requests/7",
+            "state": state,
+            "approvalStatus": approval,
+            "headPipelineStatus": "success",
+            "reviewers": reviewers,
+        }) else {
+            unreachable!()
+        };
+        map
+    }
+
+    #[test]
+    fn transitions_of_my_mrs_fire_triggers() {
+        let before = snapshot("me", "opened", "pending", &[]);
+        let mut after = snapshot("me", "merged", "approved", &[]);
+        after.insert("headPipelineStatus".into(), json!("failed"));
+        assert_eq!(
+            fired_triggers(Some(&before), &after, "me", true),
+            vec![
+                AutomationTrigger::MyMrApproved,
+                AutomationTrigger::MyMrPipelineFailed,
+                AutomationTrigger::MyMrMerged,
+            ]
+        );
+        // Unchanged state fires nothing, and someone else's MR never fires
+        // the my_mr triggers.
+        assert!(fired_triggers(Some(&after), &after, "me", true).is_empty());
+        let theirs_before = snapshot("alice", "opened", "pending", &[]);
+        let theirs_after = snapshot("alice", "merged", "approved", &[]);
+        assert!(fired_triggers(Some(&theirs_before), &theirs_after, "me", true).is_empty());
+        // A first sighting is not a transition.
+        assert!(fired_triggers(None, &after, "me", true).is_empty());
+    }
+
+    #[test]
+    fn review_requests_fire_once_and_respect_first_sync() {
+        let before = snapshot("alice", "opened", "pending", &["bob"]);
+        let after = snapshot("alice", "opened", "pending", &["bob", "me"]);
+        assert_eq!(
+            fired_triggers(Some(&before), &after, "me", false),
+            vec![AutomationTrigger::ReviewRequested]
+        );
+        assert!(fired_triggers(Some(&after), &after, "me", true).is_empty());
+        assert_eq!(
+            fired_triggers(None, &after, "me", true),
+            vec![AutomationTrigger::ReviewRequested]
+        );
+        assert!(fired_triggers(None, &after, "me", false).is_empty());
+        assert!(fired_triggers(None, &after, "", true).is_empty());
+    }
+
+    #[tokio::test]
+    async fn rules_are_validated_and_test_runs_are_logged() {
+        let (pool, _) = seed_instance(true).await;
+        let input = |action| AutomationRuleInput {
+            id: None,
+            name: "Notify".into(),
+            trigger: AutomationTrigger::MyMrApproved,
+            action,
+            enabled: true,
+        };
+        assert!(
+            save_rule(&pool, input(AutomationAction::Webhook("ftp://x".into())), 0)
+                .await
+                .is_err()
+        );
+        assert!(
+            save_rule(&pool, input(AutomationAction::Command("  ".into())), 0)
+                .await
+                .is_err()
+        );
+
+        let rule = save_rule(&pool, input(AutomationAction::Command("exit 3".into())), 5)
+            .await
+            .unwrap();
+        assert_eq!(list_rules(&pool).await.unwrap(), vec![rule.clone()]);
+
+        let run = test(&pool, rule.id).await.unwrap();
+        assert!(run.is_test);
+        assert!(!run.success);
+        assert!(run.detail.unwrap().contains("code 3"));
+        assert_eq!(list_runs(&pool, Some(rule.id)).await.unwrap().len(), 1);
+
+        delete_rule(&pool, rule.id).await.unwrap();
+        assert!(list_runs(&pool, None).await.unwrap().is_empty());
+    }
+}
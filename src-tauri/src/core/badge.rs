@@ -0,0 +1,168 @@
+//! Dock / taskbar badge count.
+//!
+//! After each sync the engine counts what the user asked to be badged for
+//! and hands the number to the emitter, which applies it to the app icon.
+//! Muted projects (see `db::muted_projects`) never count.
+
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+
+/// What counts toward the badge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BadgeSources {
+    /// Open MRs from others that the user hasn't approved yet.
+    pub awaiting_review: bool,
+    /// Queued actions (comments, approvals, ...) that failed to sync.
+    pub failed_actions: bool,
+}
+
+impl Default for BadgeSources {
+    fn default() -> Self {
+        Self {
+            awaiting_review: true,
+            failed_actions: true,
+        }
+    }
+}
+
+/// Open MRs across all instances that are waiting on the user's review.
+/// Uses the same notion of "for review" as the review list, minus bot MRs,
+/// MRs the user already approved and MRs of projects muted at `now`.
+pub async fn count_awaiting_review(pool: &DbPool, now: i64) -> Result<i64, AppError> {
+    let count: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*)
+        FROM merge_requests mr
+        WHERE mr.state = 'opened'
+          AND mr.author_username != COALESCE(
+              (SELECT authenticated_username FROM gitlab_instances WHERE id = mr.instance_id),
+              ''
+          )
+          AND mr.assigned_to_me = 0
+          AND mr.is_bot_author = 0
+          AND COALESCE(mr.user_has_approved, 0) = 0
+          AND NOT EXISTS (
+              SELECT 1 FROM muted_projects mp
+              WHERE mp.instance_id = mr.instance_id AND mp.project_id = mr.project_id
+                AND (mp.muted_until IS NULL OR mp.muted_until > ?)
+          )
+        "#,
+    )
+    .bind(now)
+    .fetch_one(pool)
+    .await?;
+    Ok(count)
+}
+
+/// Badge value for `sources` at `now`; `None` when there is nothing to show.
+pub async fn badge_count(
+    pool: &DbPool,
+    sources: BadgeSources,
+    now: i64,
+) -> Result<Option<i64>, AppError> {
+    let mut count = 0;
+    if sources.awaiting_review {
+        count += count_awaiting_review(pool, now).await?;
+    }
+    if sources.failed_actions {
+        count += sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*)
+            FROM sync_queue q
+            LEFT JOIN merge_requests mr ON mr.id = q.mr_id
+            WHERE q.status = 'failed'
+              AND NOT EXISTS (
+                  SELECT 1 FROM muted_projects mp
+                  WHERE mp.instance_id = mr.instance_id AND mp.project_id = mr.project_id
+                    AND (mp.muted_until IS NULL OR mp.muted_until > ?)
+              )
+            "#,
+        )
+        .bind(now)
+        .fetch_one(pool)
+        .await?;
+    }
+    Ok((count > 0).then_some(count))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+    use crate::db::muted_projects;
+
+    async fn insert_mr(pool: &DbPool, instance_id: i64, id: i64, author: &str, approved: bool) {
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, title, author_username, source_branch,
+              target_branch, state, web_url, created_at, updated_at, user_has_approved)
+             VALUES (?, ?, ?, 1, 't', ?, 'f', 'main', 'opened', 'https://x', 0, 0, ?)",
+        )
+        .bind(id)
+        .bind(instance_id)
+        .bind(id)
+        .bind(author)
+        .bind(approved)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn badge_counts_only_the_selected_sources() {
+        let (pool, instance_id) = seed_instance(true).await;
+        // Seeded instance authenticates as "me".
+        insert_mr(&pool, instance_id, 1, "alice", false).await;
+        insert_mr(&pool, instance_id, 2, "bob", true).await;
+        insert_mr(&pool, instance_id, 3, "me", false).await;
+        sqlx::query(
+            "INSERT INTO sync_queue (mr_id, action_type, payload, status, created_at)
+             VALUES (1, 'comment', '{}', 'failed', 0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        assert_eq!(count_awaiting_review(&pool, 0).await.unwrap(), 1);
+        assert_eq!(
+            badge_count(&pool, BadgeSources::default(), 0)
+                .await
+                .unwrap(),
+            Some(2)
+        );
+        let reviews_only = BadgeSources {
+            failed_actions: false,
+            ..Default::default()
+        };
+        assert_eq!(badge_count(&pool, reviews_only, 0).await.unwrap(), Some(1));
+        let nothing = BadgeSources {
+            awaiting_review: false,
+            failed_actions: false,
+        };
+        assert_eq!(badge_count(&pool, nothing, 0).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn muted_projects_do_not_count() {
+        let (pool, instance_id) = seed_instance(true).await;
+        insert_mr(&pool, instance_id, 1, "alice", false).await;
+        sqlx::query(
+            "INSERT INTO sync_queue (mr_id, action_type, payload, status, created_at)
+             VALUES (1, 'comment', '{}', 'failed', 0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // All test MRs are in project 1.
+        muted_projects::mute(&pool, instance_id, 1, Some(500), 100)
+            .await
+            .unwrap();
+        let sources = BadgeSources::default();
+        assert_eq!(badge_count(&pool, sources, 200).await.unwrap(), None);
+        // The mute expired.
+        assert_eq!(badge_count(&pool, sources, 500).await.unwrap(), Some(2));
+    }
+}
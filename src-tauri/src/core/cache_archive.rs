@@ -0,0 +1,316 @@
+//! Warm-start cache archives.
+//!
+//! An archive is a gzipped JSON snapshot of the open MRs, diffs and cached
+//! file content of every configured instance, keyed by instance URL. Importing
+//! it on another machine fills the local cache so the first sync only has to
+//! refresh comments and approvals instead of downloading every file. Tokens,
+//! session cookies and local-only state (drafts, queued actions, settings)
+//! are never included.
+
+use crate::core::retention;
+use crate::db::cache_archive::{self, ArchivedInstanceData};
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Bumped whenever the archive layout changes incompatibly.
+pub const ARCHIVE_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheArchive {
+    pub format_version: u32,
+    pub exported_at: i64,
+    pub instances: Vec<ArchivedInstance>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedInstance {
+    /// Instance URL; matched against the importing machine's instances.
+    pub url: String,
+    #[serde(flatten)]
+    pub data: ArchivedInstanceData,
+}
+
+/// What an export or import covered.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheArchiveSummary {
+    pub instances: i64,
+    pub merge_requests: i64,
+    pub diffs: i64,
+    pub files: i64,
+    /// Archived instance URLs with no matching instance here (import only).
+    pub skipped_instances: Vec<String>,
+}
+
+fn same_instance_url(a: &str, b: &str) -> bool {
+    a.trim_end_matches('/')
+        .eq_ignore_ascii_case(b.trim_end_matches('/'))
+}
+
+/// Snapshot the cache of every configured instance.
+pub async fn build_archive(pool: &DbPool, now: i64) -> Result<CacheArchive, AppError> {
+    let instances: Vec<(i64, String)> =
+        sqlx::query_as("SELECT id, url FROM gitlab_instances ORDER BY id")
+            .fetch_all(pool)
+            .await?;
+
+    let mut archived = Vec::with_capacity(instances.len());
+    for (id, url) in instances {
+        let data = cache_archive::read_instance_data(pool, id).await?;
+        archived.push(ArchivedInstance { url, data });
+    }
+    Ok(CacheArchive {
+        format_version: ARCHIVE_FORMAT_VERSION,
+        exported_at: now,
+        instances: archived,
+    })
+}
+
+/// Load `archive` into the cache of the matching local instances.
+pub async fn apply_archive(
+    pool: &DbPool,
+    archive: &CacheArchive,
+) -> Result<CacheArchiveSummary, AppError> {
+    if archive.format_version != ARCHIVE_FORMAT_VERSION {
+        return Err(AppError::invalid_input(format!(
+            "Unsupported cache archive version {} (expected {})",
+            archive.format_version, ARCHIVE_FORMAT_VERSION
+        )));
+    }
+
+    let local: Vec<(i64, String)> = sqlx::query_as("SELECT id, url FROM gitlab_instances")
+        .fetch_all(pool)
+        .await?;
+
+    let mut summary = CacheArchiveSummary::default();
+    for instance in &archive.instances {
+        let Some((instance_id, _)) = local
+            .iter()
+            .find(|(_, url)| same_instance_url(url, &instance.url))
+        else {
+            summary.skipped_instances.push(instance.url.clone());
+            continue;
+        };
+        let policy = retention::load_policy(pool, *instance_id).await?;
+        let counts = cache_archive::import_instance_data(
+            pool,
+            *instance_id,
+            &instance.data,
+            policy.cache_file_content,
+        )
+        .await?;
+        summary.instances += 1;
+        summary.merge_requests += counts.merge_requests;
+        summary.diffs += counts.diffs;
+        summary.files += counts.files;
+    }
+    Ok(summary)
+}
+
+fn archive_error(action: &str, path: &Path, e: impl std::fmt::Display) -> AppError {
+    AppError::internal(format!("Failed to {action} {}: {e}", path.display()))
+}
+
+/// Write `archive` to `path` as gzipped JSON, replacing the file atomically.
+pub fn write_archive(path: &Path, archive: &CacheArchive) -> Result<(), AppError> {
+    let mut tmp = PathBuf::from(path);
+    tmp.as_mut_os_string().push(".partial");
+
+    let file = File::create(&tmp).map_err(|e| archive_error("create", &tmp, e))?;
+    let mut encoder = GzEncoder::new(BufWriter::new(file), Compression::default());
+    serde_json::to_writer(&mut encoder, archive).map_err(|e| archive_error("write", &tmp, e))?;
+    encoder
+        .finish()
+        .and_then(|mut w| w.flush())
+        .map_err(|e| archive_error("write", &tmp, e))?;
+    std::fs::rename(&tmp, path).map_err(|e| archive_error("write", path, e))
+}
+
+/// Read a gzipped JSON archive from `path`.
+pub fn read_archive(path: &Path) -> Result<CacheArchive, AppError> {
+    let file = File::open(path).map_err(|e| archive_error("open", path, e))?;
+    serde_json::from_reader(GzDecoder::new(BufReader::new(file))).map_err(|e| {
+        AppError::invalid_input(format!("{} is not a cache archive: {e}", path.display()))
+    })
+}
+
+fn counts_of(archive: &CacheArchive) -> CacheArchiveSummary {
+    let mut summary = CacheArchiveSummary {
+        instances: archive.instances.len() as i64,
+        ..Default::default()
+    };
+    for instance in &archive.instances {
+        summary.merge_requests += instance.data.merge_requests.len() as i64;
+        summary.diffs += instance.data.diffs.len() as i64;
+        summary.files += instance.data.file_versions.len() as i64;
+    }
+    summary
+}
+
+/// Export the cache of all instances to `path`.
+pub async fn export(pool: &DbPool, path: &Path, now: i64) -> Result<CacheArchiveSummary, AppError> {
+    let archive = build_archive(pool, now).await?;
+    let summary = counts_of(&archive);
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || write_archive(&path, &archive))
+        .await
+        .map_err(|e| AppError::internal(format!("Archive writer panicked: {e}")))??;
+    Ok(summary)
+}
+
+/// Import the archive at `path` into the local cache.
+pub async fn import(pool: &DbPool, path: &Path) -> Result<CacheArchiveSummary, AppError> {
+    let path = path.to_path_buf();
+    let archive = tokio::task::spawn_blocking(move || read_archive(&path))
+        .await
+        .map_err(|e| AppError::internal(format!("Archive reader panicked: {e}")))??;
+    apply_archive(pool, &archive).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+
+    async fn seed_cached_mr(pool: &DbPool, instance_id: i64, mr_id: i64, state: &str) {
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, title, author_username, source_branch,
+              target_branch, state, web_url, created_at, updated_at, user_has_approved)
+             VALUES (?, ?, ?, 7, 'Cached MR', 'alice', 'feature', 'main', ?, 'https://x', 0, 500, 1)",
+        )
+        .bind(mr_id)
+        .bind(instance_id)
+        .bind(mr_id)
+        .bind(state)
+        .execute(pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO diffs (mr_id, content, base_sha, head_sha, start_sha, file_count, additions, deletions)
+             VALUES (?, '', 'base', 'head', 'start', 1, 1, 0)",
+        )
+        .bind(mr_id)
+        .execute(pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO diff_files (mr_id, new_path, change_type, additions, deletions, file_position, diff_content)
+             VALUES (?, 'src/lib.rs', 'modified', 1, 0, 0, '@@ -1 +1 @@')",
+        )
+        .bind(mr_id)
+        .execute(pool)
+        .await
+        .unwrap();
+        crate::db::file_cache::upsert_file_blob(pool, "blob-sha", "fn main() {}", 12)
+            .await
+            .unwrap();
+        crate::db::file_cache::upsert_file_version(
+            pool,
+            mr_id,
+            "src/lib.rs",
+            "head",
+            "blob-sha",
+            &instance_id.to_string(),
+            7,
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn archive_round_trips_into_another_cache() {
+        let (source, source_instance) = seed_instance(true).await;
+        seed_cached_mr(&source, source_instance, 101, "opened").await;
+        seed_cached_mr(&source, source_instance, 102, "merged").await;
+
+        let archive = build_archive(&source, 1_000).await.unwrap();
+        assert_eq!(archive.instances.len(), 1);
+        let data = &archive.instances[0].data;
+        assert_eq!(data.merge_requests.len(), 1, "only open MRs are archived");
+        assert_eq!(data.blobs.len(), 1);
+        let json = serde_json::to_string(&archive).unwrap();
+        assert!(!json.contains("\"tok\""), "tokens must not be archived");
+
+        let (target, target_instance) = seed_instance(true).await;
+        // Make the local id differ from the archived one.
+        sqlx::query("UPDATE gitlab_instances SET id = id + 10 WHERE id = ?")
+            .bind(target_instance)
+            .execute(&target)
+            .await
+            .unwrap();
+        let target_instance = target_instance + 10;
+
+        let summary = apply_archive(&target, &archive).await.unwrap();
+        assert_eq!(summary.instances, 1);
+        assert_eq!(summary.merge_requests, 1);
+        assert_eq!(summary.diffs, 1);
+        assert_eq!(summary.files, 1);
+
+        let (instance_id, updated_at, approved): (i64, i64, bool) = sqlx::query_as(
+            "SELECT instance_id, updated_at, user_has_approved FROM merge_requests WHERE id = 101",
+        )
+        .fetch_one(&target)
+        .await
+        .unwrap();
+        assert_eq!(instance_id, target_instance);
+        assert_eq!(
+            updated_at, 499,
+            "imported MRs are marked stale for the next sync"
+        );
+        assert!(!approved, "per-user state isn't carried over");
+        let content =
+            crate::db::file_cache::get_cached_file_content(&target, 101, "src/lib.rs", "head")
+                .await
+                .unwrap();
+        assert_eq!(content.as_deref(), Some("fn main() {}"));
+
+        // A second import only fills gaps, so nothing is written again.
+        let again = apply_archive(&target, &archive).await.unwrap();
+        assert_eq!((again.merge_requests, again.diffs, again.files), (0, 0, 0));
+    }
+
+    #[tokio::test]
+    async fn unknown_instances_are_skipped_and_versions_checked() {
+        let (pool, _) = seed_instance(true).await;
+        let mut archive = CacheArchive {
+            format_version: ARCHIVE_FORMAT_VERSION,
+            exported_at: 0,
+            instances: vec![ArchivedInstance {
+                url: "https://other.example.com".into(),
+                data: ArchivedInstanceData::default(),
+            }],
+        };
+        let summary = apply_archive(&pool, &archive).await.unwrap();
+        assert_eq!(summary.instances, 0);
+        assert_eq!(summary.skipped_instances, vec!["https://other.example.com"]);
+
+        archive.format_version = ARCHIVE_FORMAT_VERSION + 1;
+        assert!(apply_archive(&pool, &archive).await.is_err());
+    }
+
+    #[test]
+    fn archives_survive_a_trip_through_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.ugz");
+        let archive = CacheArchive {
+            format_version: ARCHIVE_FORMAT_VERSION,
+            exported_at: 42,
+            instances: vec![],
+        };
+        write_archive(&path, &archive).unwrap();
+        assert_eq!(read_archive(&path).unwrap(), archive);
+
+        std::fs::write(&path, b"not gzip").unwrap();
+        assert!(read_archive(&path).is_err());
+    }
+}
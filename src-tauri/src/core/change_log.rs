@@ -0,0 +1,603 @@
+//! Fine-grained change events for the local cache.
+//!
+//! The sync engine appends a [`ChangeEvent`] whenever it writes an MR, its
+//! diff or its discussions, and emits the same event to the desktop UI. MR
+//! events carry only the fields that changed (camelCase, with the values the
+//! list DTO uses) so clients can patch cached rows in place. Clients that
+//! were disconnected catch up with [`changes_since`]; when their cursor is
+//! older than the retained log they are told to reload instead.
+
+use crate::core::mr_query;
+use crate::db::change_log as db;
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// Number of log rows kept; older rows are pruned after each sync.
+pub const MAX_RETAINED_CHANGES: i64 = 10_000;
+
+/// Largest batch returned by one [`changes_since`] call.
+pub const MAX_CHANGES_PER_PAGE: i64 = 500;
+
+/// Columns left out of MR snapshots: bookkeeping that changes on every sync
+/// without meaning anything to a client.
+const IGNORED_MR_FIELDS: &[&str] = &["cached_at", "state_changed_at"];
+
+/// What a change applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeEntity {
+    /// The MR row itself; `fields` holds the changed values.
+    MergeRequest,
+    /// The MR's diff and file list (`entity_id` is the MR id).
+    Diff,
+    /// The MR's discussions (`entity_id` is the MR id).
+    Comments,
+}
+
+impl ChangeEntity {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::MergeRequest => "merge_request",
+            Self::Diff => "diff",
+            Self::Comments => "comments",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "merge_request" => Some(Self::MergeRequest),
+            "diff" => Some(Self::Diff),
+            "comments" => Some(Self::Comments),
+            _ => None,
+        }
+    }
+}
+
+/// Whether the entity was written or removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeOp {
+    Upsert,
+    Delete,
+}
+
+impl ChangeOp {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Upsert => "upsert",
+            Self::Delete => "delete",
+        }
+    }
+}
+
+/// One entry of the change log.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeEvent {
+    pub seq: i64,
+    pub entity: ChangeEntity,
+    pub entity_id: i64,
+    pub instance_id: i64,
+    pub op: ChangeOp,
+    /// Changed fields and their new values. For a newly cached MR this is
+    /// the whole row; empty for diff, comment and delete events.
+    pub fields: Map<String, Value>,
+    pub changed_at: i64,
+}
+
+/// Result of [`changes_since`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangesSince {
+    pub changes: Vec<ChangeEvent>,
+    /// Cursor to pass on the next call.
+    pub cursor: i64,
+    /// More changes are waiting; call again with `cursor`.
+    pub has_more: bool,
+    /// The given cursor can't be caught up from (too old, or from a
+    /// different database). Reload everything and continue from `cursor`.
+    pub reset: bool,
+}
+
+fn to_camel_case(key: &str) -> String {
+    let mut out = String::with_capacity(key.len());
+    let mut upper = false;
+    for c in key.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            out.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Snapshot of an MR as the list views see it, keyed like the list DTO.
+/// `None` when the MR isn't cached.
+pub async fn mr_snapshot(
+    pool: &DbPool,
+    mr_id: i64,
+) -> Result<Option<Map<String, Value>>, AppError> {
+    let Some(mr) = mr_query::get_list_row(pool, mr_id).await? else {
+        return Ok(None);
+    };
+    let labels = mr.labels_vec();
+    let reviewers = mr.reviewers_vec();
+    let Value::Object(row) = serde_json::to_value(mr)? else {
+        return Ok(None);
+    };
+
+    let mut snapshot: Map<String, Value> = row
+        .into_iter()
+        .filter(|(k, _)| !IGNORED_MR_FIELDS.contains(&k.as_str()))
+        .map(|(k, v)| (to_camel_case(&k), v))
+        .collect();
+    snapshot.insert("labels".to_string(), labels.into());
+    snapshot.insert("reviewers".to_string(), reviewers.into());
+    Ok(Some(snapshot))
+}
+
+/// Fields of `after` that differ from `before` (all of them when there is
+/// no `before`).
+pub fn changed_fields(
+    before: Option<&Map<String, Value>>,
+    after: &Map<String, Value>,
+) -> Map<String, Value> {
+    after
+        .iter()
+        .filter(|(k, v)| before.and_then(|b| b.get(*k)) != Some(*v))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect()
+}
+
+/// Append a change to the log and return it.
+pub async fn record(
+    pool: &DbPool,
+    entity: ChangeEntity,
+    entity_id: i64,
+    instance_id: i64,
+    op: ChangeOp,
+    fields: Map<String, Value>,
+    now: i64,
+) -> Result<ChangeEvent, AppError> {
+    let json = serde_json::to_string(&fields)?;
+    let seq = db::insert(
+        pool,
+        entity.as_str(),
+        entity_id,
+        instance_id,
+        op.as_str(),
+        &json,
+        now,
+    )
+    .await?;
+    Ok(ChangeEvent {
+        seq,
+        entity,
+        entity_id,
+        instance_id,
+        op,
+        fields,
+        changed_at: now,
+    })
+}
+
+/// Changes after `cursor`, oldest first. `None` asks only for the current
+/// cursor (with `reset` set), for clients that are about to load everything.
+pub async fn changes_since(pool: &DbPool, cursor: Option<i64>) -> Result<ChangesSince, AppError> {
+    let last = db::last_seq(pool).await?;
+    let Some(cursor) = cursor else {
+        return Ok(ChangesSince {
+            changes: Vec::new(),
+            cursor: last,
+            has_more: false,
+            reset: true,
+        });
+    };
+
+    let first = db::first_seq(pool).await?;
+    let gap = match first {
+        Some(first) => cursor + 1 < first,
+        None => cursor < last,
+    };
+    if cursor > last || gap {
+        return Ok(ChangesSince {
+            changes: Vec::new(),
+            cursor: last,
+            has_more: false,
+            reset: true,
+        });
+    }
+
+    let rows = db::list_after(pool, cursor, MAX_CHANGES_PER_PAGE).await?;
+    let next = rows.last().map_or(cursor, |r| r.seq);
+    let changes = rows
+        .into_iter()
+        .filter_map(|row| {
+            Some(ChangeEvent {
+                seq: row.seq,
+                entity: ChangeEntity::parse(&row.entity)?,
+                entity_id: row.entity_id,
+                instance_id: row.instance_id,
+                op: if row.op == "delete" {
+                    ChangeOp::Delete
+                } else {
+                    ChangeOp::Upsert
+                },
+                fields: serde_json::from_str(&row.fields).unwrap_or_default(),
+                changed_at: row.changed_at,
+            })
+        })
+        .collect();
+
+    Ok(ChangesSince {
+        changes,
+        cursor: next,
+        has_more: next < last,
+        reset: false,
+    })
+}
+
+/// An MR that was cached or changed since the cursor.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MrFieldChanges {
+    pub id: i64,
+    pub instance_id: i64,
+    /// The whole row for created MRs, only the changed fields otherwise.
+    pub fields: Map<String, Value>,
+}
+
+/// Result of [`mr_delta`]: the MR log folded into one change per MR.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MrDelta {
+    pub created: Vec<MrFieldChanges>,
+    pub updated: Vec<MrFieldChanges>,
+    /// Ids of MRs dropped from the cache.
+    pub removed: Vec<i64>,
+    /// Cursor to pass on the next call.
+    pub cursor: i64,
+    /// Same as [`ChangesSince::reset`]: reload everything.
+    pub reset: bool,
+}
+
+/// Net MR changes after `cursor`, for clients that only keep MR lists.
+/// Unlike [`changes_since`] this reads the whole log up to now and merges
+/// repeated updates, so an MR shows up at most once. MRs that were both
+/// cached and dropped within the window are left out.
+pub async fn mr_delta(pool: &DbPool, cursor: Option<i64>) -> Result<MrDelta, AppError> {
+    enum Net {
+        Created(i64, Map<String, Value>),
+        Updated(i64, Map<String, Value>),
+        Removed,
+    }
+
+    let mut page = changes_since(pool, cursor).await?;
+    if page.reset {
+        return Ok(MrDelta {
+            cursor: page.cursor,
+            reset: true,
+            ..MrDelta::default()
+        });
+    }
+
+    let mut net: BTreeMap<i64, Net> = BTreeMap::new();
+    // Whether the client already had each MR, i.e. its first event in the
+    // window isn't a full snapshot.
+    let mut known: BTreeMap<i64, bool> = BTreeMap::new();
+    loop {
+        for change in page.changes {
+            if change.entity != ChangeEntity::MergeRequest {
+                continue;
+            }
+            let id = change.entity_id;
+            let known_before = *known
+                .entry(id)
+                .or_insert(change.op == ChangeOp::Delete || !change.fields.contains_key("id"));
+            match (change.op, net.remove(&id)) {
+                (ChangeOp::Delete, Some(Net::Created(..))) if !known_before => {}
+                (ChangeOp::Delete, _) => {
+                    net.insert(id, Net::Removed);
+                }
+                (
+                    ChangeOp::Upsert,
+                    Some(
+                        Net::Created(instance_id, mut fields)
+                        | Net::Updated(instance_id, mut fields),
+                    ),
+                ) => {
+                    let created = fields.contains_key("id") || change.fields.contains_key("id");
+                    fields.extend(change.fields);
+                    net.insert(
+                        id,
+                        if created {
+                            Net::Created(instance_id, fields)
+                        } else {
+                            Net::Updated(instance_id, fields)
+                        },
+                    );
+                }
+                (ChangeOp::Upsert, _) => {
+                    let entry = if change.fields.contains_key("id") {
+                        Net::Created(change.instance_id, change.fields)
+                    } else {
+                        Net::Updated(change.instance_id, change.fields)
+                    };
+                    net.insert(id, entry);
+                }
+            }
+        }
+        if !page.has_more {
+            break;
+        }
+        page = changes_since(pool, Some(page.cursor)).await?;
+    }
+
+    let mut delta = MrDelta {
+        cursor: page.cursor,
+        ..MrDelta::default()
+    };
+    for (id, entry) in net {
+        match entry {
+            Net::Created(instance_id, fields) => delta.created.push(MrFieldChanges {
+                id,
+                instance_id,
+                fields,
+            }),
+            Net::Updated(instance_id, fields) => delta.updated.push(MrFieldChanges {
+                id,
+                instance_id,
+                fields,
+            }),
+            Net::Removed => delta.removed.push(id),
+        }
+    }
+    Ok(delta)
+}
+
+/// Drop all but the newest [`MAX_RETAINED_CHANGES`] entries.
+pub async fn prune(pool: &DbPool) -> Result<u64, AppError> {
+    Ok(db::prune(pool, MAX_RETAINED_CHANGES).await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db;
+    use tempfile::tempdir;
+
+    async fn seeded_pool() -> (tempfile::TempDir, DbPool) {
+        let dir = tempdir().unwrap();
+        let pool = db::initialize(&dir.path().join("t.db")).await.unwrap();
+        sqlx::query(
+            "INSERT INTO gitlab_instances (id, url, token, created_at) VALUES (1, 'u', 't', 0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, project_name, title, author_username,
+              source_branch, target_branch, state, web_url, created_at, updated_at,
+              labels, reviewers, cached_at)
+             VALUES (7, 1, 3, 10, 'g/p', 'Fix parser', 'alice', 's', 'main', 'opened',
+                     'http://x', 0, 0, '[\"bug\"]', '[]', 0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        (dir, pool)
+    }
+
+    #[tokio::test]
+    async fn snapshot_diff_reports_only_changed_fields() {
+        let (_dir, pool) = seeded_pool().await;
+        let before = mr_snapshot(&pool, 7).await.unwrap().unwrap();
+        assert_eq!(before["labels"], serde_json::json!(["bug"]));
+        assert_eq!(before["authorUsername"], "alice");
+
+        sqlx::query(
+            "UPDATE merge_requests SET title = 'Fix lexer', cached_at = 99, head_pipeline_status = 'success' WHERE id = 7",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let after = mr_snapshot(&pool, 7).await.unwrap().unwrap();
+        let changed = changed_fields(Some(&before), &after);
+        assert_eq!(
+            changed.keys().collect::<Vec<_>>(),
+            vec!["headPipelineStatus", "title"]
+        );
+        assert_eq!(changed_fields(None, &after).len(), after.len());
+    }
+
+    #[tokio::test]
+    async fn changes_since_pages_and_resets_after_pruning() {
+        let (_dir, pool) = seeded_pool().await;
+        let start = changes_since(&pool, None).await.unwrap();
+        assert!(start.reset);
+        assert_eq!(start.cursor, 0);
+
+        for _ in 0..3 {
+            record(
+                &pool,
+                ChangeEntity::Diff,
+                7,
+                1,
+                ChangeOp::Upsert,
+                Map::new(),
+                5,
+            )
+            .await
+            .unwrap();
+        }
+        let caught_up = changes_since(&pool, Some(1)).await.unwrap();
+        assert!(!caught_up.reset);
+        assert_eq!(
+            caught_up.changes.iter().map(|c| c.seq).collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        assert_eq!(caught_up.cursor, 3);
+        assert!(!caught_up.has_more);
+
+        db::change_log::prune(&pool, 1).await.unwrap();
+        assert!(changes_since(&pool, Some(1)).await.unwrap().reset);
+        assert!(!changes_since(&pool, Some(2)).await.unwrap().reset);
+        // A cursor from the future belongs to another database.
+        assert!(changes_since(&pool, Some(42)).await.unwrap().reset);
+    }
+
+    #[tokio::test]
+    async fn mr_delta_folds_changes_per_mr() {
+        let (_dir, pool) = seeded_pool().await;
+        let fields = |pairs: &[(&str, Value)]| -> Map<String, Value> {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.clone()))
+                .collect()
+        };
+        let mr = |id: i64, op: ChangeOp, f: Map<String, Value>| {
+            let pool = pool.clone();
+            async move {
+                record(&pool, ChangeEntity::MergeRequest, id, 1, op, f, 5)
+                    .await
+                    .unwrap();
+            }
+        };
+
+        let start = mr_delta(&pool, None).await.unwrap();
+        assert!(start.reset);
+
+        // 7 is updated twice, 8 is new, 9 comes and goes, 10 goes away.
+        mr(7, ChangeOp::Upsert, fields(&[("title", "A".into())])).await;
+        mr(
+            8,
+            ChangeOp::Upsert,
+            fields(&[("id", 8.into()), ("title", "New".into())]),
+        )
+        .await;
+        mr(9, ChangeOp::Upsert, fields(&[("id", 9.into())])).await;
+        record(
+            &pool,
+            ChangeEntity::Diff,
+            7,
+            1,
+            ChangeOp::Upsert,
+            Map::new(),
+            5,
+        )
+        .await
+        .unwrap();
+        mr(
+            7,
+            ChangeOp::Upsert,
+            fields(&[("title", "B".into()), ("draft", true.into())]),
+        )
+        .await;
+        mr(8, ChangeOp::Upsert, fields(&[("title", "Newer".into())])).await;
+        mr(9, ChangeOp::Delete, Map::new()).await;
+        mr(10, ChangeOp::Delete, Map::new()).await;
+
+        let delta = mr_delta(&pool, Some(start.cursor)).await.unwrap();
+        assert!(!delta.reset);
+        assert_eq!(delta.cursor, 8);
+        assert_eq!(
+            delta.updated,
+            vec![MrFieldChanges {
+                id: 7,
+                instance_id: 1,
+                fields: fields(&[("title", "B".into()), ("draft", true.into())]),
+            }]
+        );
+        assert_eq!(delta.created.len(), 1);
+        assert_eq!(delta.created[0].fields["title"], "Newer");
+        assert_eq!(delta.removed, vec![10]);
+
+        // Nothing new since the returned cursor.
+        let empty = mr_delta(&pool, Some(delta.cursor)).await.unwrap();
+        assert!(empty.created.is_empty() && empty.updated.is_empty() && empty.removed.is_empty());
+        assert_eq!(empty.cursor, 8);
+    }
+
+    #[tokio::test]
+    async fn mr_delta_resets_a_cursor_older_than_the_log() {
+        let (_dir, pool) = seeded_pool().await;
+        let title = |t: &str| Map::from_iter([("title".to_string(), Value::from(t))]);
+        for t in ["A", "B", "C", "D"] {
+            record(
+                &pool,
+                ChangeEntity::MergeRequest,
+                7,
+                1,
+                ChangeOp::Upsert,
+                title(t),
+                5,
+            )
+            .await
+            .unwrap();
+        }
+        db::change_log::prune(&pool, 2).await.unwrap();
+
+        // Seq 2 is gone, so a client at 1 can't tell what it missed.
+        let stale = mr_delta(&pool, Some(1)).await.unwrap();
+        assert!(stale.reset);
+        assert!(stale.created.is_empty() && stale.updated.is_empty() && stale.removed.is_empty());
+        assert_eq!(stale.cursor, 4);
+
+        // After reloading, the returned cursor picks up from there.
+        let current = mr_delta(&pool, Some(stale.cursor)).await.unwrap();
+        assert!(!current.reset);
+        assert_eq!(current.cursor, 4);
+        let caught_up = mr_delta(&pool, Some(2)).await.unwrap();
+        assert!(!caught_up.reset);
+        assert_eq!(caught_up.updated[0].fields, title("D"));
+    }
+
+    #[tokio::test]
+    async fn mr_delta_resets_when_a_deletion_was_pruned() {
+        let (_dir, pool) = seeded_pool().await;
+        let change = |op: ChangeOp| {
+            let pool = pool.clone();
+            async move {
+                record(&pool, ChangeEntity::MergeRequest, 7, 1, op, Map::new(), 5)
+                    .await
+                    .unwrap();
+            }
+        };
+        change(ChangeOp::Upsert).await;
+        let cursor = mr_delta(&pool, None).await.unwrap().cursor;
+        change(ChangeOp::Delete).await;
+
+        let delta = mr_delta(&pool, Some(cursor)).await.unwrap();
+        assert!(!delta.reset);
+        assert_eq!(delta.removed, vec![7]);
+
+        // Once the deletion is pruned the client can only learn about it
+        // by reloading the list.
+        for _ in 0..2 {
+            record(
+                &pool,
+                ChangeEntity::Diff,
+                8,
+                1,
+                ChangeOp::Upsert,
+                Map::new(),
+                5,
+            )
+            .await
+            .unwrap();
+        }
+        db::change_log::prune(&pool, 1).await.unwrap();
+        let delta = mr_delta(&pool, Some(cursor)).await.unwrap();
+        assert!(delta.reset);
+        assert!(delta.removed.is_empty());
+        assert_eq!(delta.cursor, 4);
+    }
+}
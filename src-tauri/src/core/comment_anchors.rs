@@ -0,0 +1,291 @@
+//! Moving cached inline comments along when an MR's diff changes.
+//!
+//! Comment positions are cached once and never updated from GitLab, so
+//! after a rebase or a new push they point at whatever code now sits at
+//! their old line numbers. When the sync replaces an MR's file versions it
+//! keeps the previous base and head content of every file that has inline
+//! comments, then maps each comment's lines through a diff of the previous
+//! version against the new one: `new_line` through the head versions,
+//! `old_line` through the base versions.
+//!
+//! Unchanged lines map exactly. A line inside a changed block keeps its
+//! offset into the block, clamped to the block's new size; a line whose
+//! block was deleted moves to the line after it.
+
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use std::collections::HashMap;
+
+/// Above this many line pairs the changed middle of a file is not diffed
+/// line by line but treated as one changed block.
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// File content captured before the sync replaced it, keyed by
+/// `(file_path, version_type)`.
+pub type PreviousVersions = HashMap<(String, String), String>;
+
+/// Pairs of matching line indexes (0-based) in the middle sections `a` and
+/// `b`, in order, from their longest common subsequence.
+fn common_lines(a: &[&str], b: &[&str]) -> Vec<(usize, usize)> {
+    if a.is_empty() || b.is_empty() || a.len().saturating_mul(b.len()) > MAX_DIFF_CELLS {
+        return Vec::new();
+    }
+    // lengths[i][j]: LCS length of a[i..] and b[j..]
+    let width = b.len() + 1;
+    let mut lengths = vec![0u32; (a.len() + 1) * width];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lengths[i * width + j] = if a[i] == b[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+    let mut pairs = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            pairs.push((i, j));
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs
+}
+
+/// For every line of `old` (index `n` is line `n + 1`), its line number in
+/// `new`, or `None` when `new` is empty.
+pub fn line_map(old: &str, new: &str) -> Vec<Option<i64>> {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let prefix = old_lines
+        .iter()
+        .zip(&new_lines)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old_lines[prefix..]
+        .iter()
+        .rev()
+        .zip(new_lines[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old_lines[prefix..old_lines.len() - suffix];
+    let new_mid = &new_lines[prefix..new_lines.len() - suffix];
+
+    // Matching (old, new) index pairs across the whole file, in order.
+    let mut anchors: Vec<(usize, usize)> = (0..prefix).map(|i| (i, i)).collect();
+    anchors.extend(
+        common_lines(old_mid, new_mid)
+            .into_iter()
+            .map(|(i, j)| (i + prefix, j + prefix)),
+    );
+    let old_tail = old_lines.len() - suffix;
+    let new_tail = new_lines.len() - suffix;
+    anchors.extend((0..suffix).map(|k| (old_tail + k, new_tail + k)));
+
+    let mut map = vec![None; old_lines.len()];
+    // Gaps between consecutive anchors, with virtual anchors before the
+    // first line and after the last.
+    let mut prev: (isize, isize) = (-1, -1);
+    for next in anchors
+        .iter()
+        .map(|&(i, j)| (i as isize, j as isize))
+        .chain(std::iter::once((
+            old_lines.len() as isize,
+            new_lines.len() as isize,
+        )))
+    {
+        let gap_new = next.1 - prev.1 - 1;
+        for i in prev.0 + 1..next.0 {
+            let offset = i - prev.0 - 1;
+            let j = if gap_new > 0 {
+                prev.1 + 1 + offset.min(gap_new - 1)
+            } else {
+                next.1.min(new_lines.len() as isize - 1)
+            };
+            map[i as usize] = (j >= 0).then_some(j as i64 + 1);
+        }
+        if let Ok(i) = usize::try_from(next.0) {
+            if i < old_lines.len() {
+                map[i] = Some(next.1 as i64 + 1);
+            }
+        }
+        prev = next;
+    }
+    map
+}
+
+/// Look up a 1-based `line` in a map from [`line_map`].
+fn remap(map: &[Option<i64>], line: i64) -> Option<i64> {
+    usize::try_from(line - 1)
+        .ok()
+        .and_then(|i| map.get(i).copied().flatten())
+}
+
+/// Capture the cached base and head content of the MR's files that have
+/// inline comments, before the sync replaces them.
+pub async fn snapshot(pool: &DbPool, mr_id: i64) -> Result<PreviousVersions, AppError> {
+    let rows: Vec<(String, String, String)> = sqlx::query_as(
+        "SELECT fv.file_path, fv.version_type, fb.content
+         FROM file_versions fv
+         JOIN file_blobs fb ON fb.sha = fv.sha
+         WHERE fv.mr_id = ? AND fv.file_path IN (
+             SELECT file_path FROM comments
+             WHERE mr_id = ? AND file_path IS NOT NULL
+               AND (old_line IS NOT NULL OR new_line IS NOT NULL)
+         )",
+    )
+    .bind(mr_id)
+    .bind(mr_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(path, version, content)| ((path, version), content))
+        .collect())
+}
+
+/// Move the MR's inline comments from the `previous` file versions to the
+/// ones now cached. Files without new content cached keep their comments
+/// where they are. Returns the number of comments moved.
+pub async fn reanchor(
+    pool: &DbPool,
+    mr_id: i64,
+    previous: &PreviousVersions,
+) -> Result<u64, AppError> {
+    let mut moved = 0;
+    for ((path, version), old_content) in previous {
+        let Some(new_content) =
+            crate::db::file_cache::get_cached_file_content(pool, mr_id, path, version).await?
+        else {
+            continue;
+        };
+        if &new_content == old_content {
+            continue;
+        }
+        let map = line_map(old_content, &new_content);
+        // Head versions carry new-side lines, base versions old-side ones.
+        let column = if version == "head" {
+            "new_line"
+        } else {
+            "old_line"
+        };
+        let lines: Vec<(i64, i64)> = sqlx::query_as(&format!(
+            "SELECT id, {column} FROM comments
+             WHERE mr_id = ? AND file_path = ? AND {column} IS NOT NULL"
+        ))
+        .bind(mr_id)
+        .bind(path)
+        .fetch_all(pool)
+        .await?;
+        for (id, line) in lines {
+            let Some(target) = remap(&map, line).filter(|&target| target != line) else {
+                continue;
+            };
+            sqlx::query(&format!("UPDATE comments SET {column} = ? WHERE id = ?"))
+                .bind(target)
+                .bind(id)
+                .execute(pool)
+                .await?;
+            moved += 1;
+        }
+    }
+    Ok(moved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db;
+
+    fn lines(map: &[Option<i64>]) -> Vec<i64> {
+        map.iter().map(|l| l.unwrap()).collect()
+    }
+
+    #[test]
+    fn test_line_map_follows_inserted_and_removed_lines() {
+        let old = "a\nb\nc\nd\ne\n";
+        // Two lines inserted above `b`, `d` removed.
+        let new = "a\nx\ny\nb\nc\ne\n";
+        assert_eq!(lines(&line_map(old, new)), vec![1, 4, 5, 6, 6]);
+    }
+
+    #[test]
+    fn test_line_map_keeps_offset_in_changed_block() {
+        let old = "a\nb1\nb2\nb3\nc\n";
+        let new = "a\nB1\nB2\nc\n";
+        assert_eq!(lines(&line_map(old, new)), vec![1, 2, 3, 3, 4]);
+
+        assert_eq!(line_map("a\nb\n", ""), vec![None, None]);
+        assert_eq!(lines(&line_map("a\nb\n", "a\nb\n")), vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_reanchor_moves_comments_to_new_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = db::initialize(&dir.path().join("test.db")).await.unwrap();
+        sqlx::query("INSERT INTO gitlab_instances (id, url, name) VALUES (1, 'https://gitlab.com', 'GitLab')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO merge_requests (id, instance_id, iid, project_id, title, author_username,
+             source_branch, target_branch, state, web_url, created_at, updated_at)
+             VALUES (10, 1, 5, 100, 'MR', 'alice', 'feature', 'main', 'opened', 'https://gitlab.com/g/p/-/merge_requests/5', 0, 0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let cache = |content: &'static str, sha: &'static str| {
+            let pool = pool.clone();
+            async move {
+                crate::db::file_cache::upsert_file_blob(&pool, sha, content, 0)
+                    .await
+                    .unwrap();
+                crate::db::file_cache::upsert_file_version(
+                    &pool,
+                    10,
+                    "src/lib.rs",
+                    "head",
+                    sha,
+                    "1",
+                    100,
+                )
+                .await
+                .unwrap();
+            }
+        };
+        cache("fn a() {}\nfn b() {}\n", "old").await;
+        for (id, new_line, old_line) in [(1, Some(2), None), (2, None, Some(2))] {
+            sqlx::query(
+                "INSERT INTO comments (id, mr_id, author_username, body, file_path, old_line,
+                 new_line, created_at, updated_at)
+                 VALUES (?, 10, 'bob', 'nit', 'src/lib.rs', ?, ?, 0, 0)",
+            )
+            .bind(id)
+            .bind(old_line)
+            .bind(new_line)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let previous = snapshot(&pool, 10).await.unwrap();
+        assert_eq!(previous.len(), 1);
+        cache("use std::fmt;\n\nfn a() {}\nfn b() {}\n", "new").await;
+        assert_eq!(reanchor(&pool, 10, &previous).await.unwrap(), 1);
+
+        let moved: Vec<(Option<i64>, Option<i64>)> =
+            sqlx::query_as("SELECT new_line, old_line FROM comments ORDER BY id")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        // The base version wasn't cached, so the old-side comment stays.
+        assert_eq!(moved, vec![(Some(4), None), (None, Some(2))]);
+    }
+}
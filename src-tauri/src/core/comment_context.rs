@@ -0,0 +1,192 @@
+//! Code around inline comments, so the discussion overview can show what a
+//! thread is about without opening the file.
+//!
+//! Snippets are cut from the file versions cached at sync time: the head
+//! version for comments on a new line, the base version for comments on a
+//! removed line. Comments whose file isn't cached get no snippet.
+
+use crate::db::file_cache;
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use crate::models::Comment;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// Most lines of context accepted on each side of the commented line.
+pub const MAX_CONTEXT_LINES: u32 = 10;
+
+/// Lines around the line an inline comment is on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentSnippet {
+    /// `"new"` when cut from the head version, `"old"` from the base version.
+    pub side: &'static str,
+    /// Line number of the first line in `lines`.
+    pub start_line: i64,
+    /// Line number of the commented line.
+    pub line: i64,
+    pub lines: Vec<String>,
+}
+
+/// Cut `context` lines either side of 1-based `line` out of `content`.
+/// `None` when the line isn't in the file.
+fn extract(content: &str, line: i64, context: u32) -> Option<(i64, Vec<String>)> {
+    let lines: Vec<&str> = content.lines().collect();
+    if line < 1 || line as usize > lines.len() {
+        return None;
+    }
+    let index = line as usize - 1;
+    let start = index.saturating_sub(context as usize);
+    let end = (index + context as usize + 1).min(lines.len());
+    let snippet = lines[start..end].iter().map(|l| l.to_string()).collect();
+    Some((start as i64 + 1, snippet))
+}
+
+/// Snippets for the inline comments in `comments`, by comment id, with
+/// `context` lines either side (capped at `MAX_CONTEXT_LINES`).
+///
+/// Only the first comment of each discussion gets one: replies sit on the
+/// same line.
+pub async fn for_comments(
+    pool: &DbPool,
+    comments: &[Comment],
+    context: u32,
+) -> Result<HashMap<i64, CommentSnippet>, AppError> {
+    let context = context.min(MAX_CONTEXT_LINES);
+    let mut seen_discussions: HashSet<&str> = HashSet::new();
+    let mut files: HashMap<(i64, String, &'static str), Option<String>> = HashMap::new();
+    let mut snippets = HashMap::new();
+
+    for comment in comments {
+        let Some(path) = &comment.file_path else {
+            continue;
+        };
+        if let Some(discussion_id) = comment.discussion_id.as_deref() {
+            if !seen_discussions.insert(discussion_id) {
+                continue;
+            }
+        }
+        let (side, version, line) = match (comment.new_line, comment.old_line) {
+            (Some(line), _) => ("new", "head", line),
+            (None, Some(line)) => ("old", "base", line),
+            (None, None) => continue,
+        };
+
+        let key = (comment.mr_id, path.clone(), version);
+        let content = match files.get(&key) {
+            Some(content) => content,
+            None => {
+                let content =
+                    file_cache::get_cached_file_content(pool, comment.mr_id, path, version).await?;
+                files.entry(key).or_insert(content)
+            }
+        };
+        let Some((start_line, lines)) = content.as_deref().and_then(|c| extract(c, line, context))
+        else {
+            continue;
+        };
+        snippets.insert(
+            comment.id,
+            CommentSnippet {
+                side,
+                start_line,
+                line,
+                lines,
+            },
+        );
+    }
+    Ok(snippets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_clamps_to_the_file() {
+        let content = "one\ntwo\nthree\nfour\nfive";
+        assert_eq!(
+            extract(content, 3, 1),
+            Some((2, vec!["two".into(), "three".into(), "four".into()]))
+        );
+        assert_eq!(
+            extract(content, 1, 2),
+            Some((1, vec!["one".into(), "two".into(), "three".into()]))
+        );
+        assert_eq!(extract(content, 5, 0), Some((5, vec!["five".into()])));
+        assert_eq!(extract(content, 6, 2), None);
+        assert_eq!(extract(content, 0, 2), None);
+    }
+
+    fn comment(
+        id: i64,
+        discussion: Option<&str>,
+        old_line: Option<i64>,
+        new_line: Option<i64>,
+    ) -> Comment {
+        Comment {
+            id,
+            mr_id: 1,
+            discussion_id: discussion.map(String::from),
+            parent_id: None,
+            author_username: "alice".to_string(),
+            body: "note".to_string(),
+            file_path: discussion.map(|_| "src/lib.rs".to_string()),
+            old_line,
+            new_line,
+            line_type: None,
+            resolved: false,
+            resolvable: true,
+            system: false,
+            created_at: 0,
+            updated_at: 0,
+            cached_at: 0,
+            is_local: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn one_snippet_per_discussion_from_the_right_version() {
+        let (pool, inst) = crate::core::tests::seed_instance(true).await;
+        for (version, content) in [("head", "a\nb\nc\nd"), ("base", "x\ny\nz")] {
+            let sha = format!("sha-{version}");
+            file_cache::upsert_file_blob(&pool, &sha, content, content.len() as i64)
+                .await
+                .unwrap();
+            file_cache::upsert_file_version(
+                &pool,
+                1,
+                "src/lib.rs",
+                version,
+                &sha,
+                &inst.to_string(),
+                10,
+            )
+            .await
+            .unwrap();
+        }
+
+        let comments = vec![
+            comment(1, Some("d1"), None, Some(3)),
+            comment(2, Some("d1"), None, Some(3)),
+            comment(3, Some("d2"), Some(1), None),
+            comment(4, Some("d3"), None, Some(99)),
+            // A general comment.
+            comment(5, None, None, None),
+        ];
+
+        let snippets = for_comments(&pool, &comments, 1).await.unwrap();
+        assert_eq!(snippets.len(), 2);
+        assert_eq!(
+            snippets[&1],
+            CommentSnippet {
+                side: "new",
+                start_line: 2,
+                line: 3,
+                lines: vec!["b".into(), "c".into(), "d".into()],
+            }
+        );
+        assert_eq!(snippets[&3].side, "old");
+        assert_eq!(snippets[&3].lines, vec!["x".to_string(), "y".to_string()]);
+    }
+}
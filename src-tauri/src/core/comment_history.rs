@@ -0,0 +1,178 @@
+//! My own comments across all cached MRs.
+//!
+//! Lets the user find something they explained before and reuse it. Only
+//! MRs still in the local cache are covered: comments of purged MRs are gone
+//! with them.
+
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Most entries returned by one query.
+pub const HISTORY_LIMIT: i64 = 200;
+
+/// Creation time bounds, in Unix seconds. Both ends are optional.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentRange {
+    pub since: Option<i64>,
+    pub until: Option<i64>,
+}
+
+/// One of my comments, with enough MR context to recognise it.
+#[derive(Debug, Clone, PartialEq, Serialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct MyComment {
+    pub id: i64,
+    pub mr_id: i64,
+    pub instance_id: i64,
+    pub mr_iid: i64,
+    pub mr_title: String,
+    pub project_name: String,
+    /// Link to the note on GitLab; `None` while it is still queued.
+    #[sqlx(skip)]
+    pub web_url: Option<String>,
+    #[serde(skip)]
+    pub mr_web_url: String,
+    pub body: String,
+    pub file_path: Option<String>,
+    pub created_at: i64,
+}
+
+/// `LIKE` pattern matching `word` anywhere, with wildcards in it escaped.
+fn like_pattern(word: &str) -> String {
+    let mut pattern = String::with_capacity(word.len() + 2);
+    pattern.push('%');
+    for c in word.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern.push('%');
+    pattern
+}
+
+/// My comments in `range`, newest first. With a search, every word must
+/// appear in the body (case-insensitively for ASCII).
+pub async fn get_my_comments(
+    pool: &DbPool,
+    range: CommentRange,
+    search: Option<&str>,
+) -> Result<Vec<MyComment>, AppError> {
+    let words: Vec<&str> = search
+        .map(|s| s.split_whitespace().collect())
+        .unwrap_or_default();
+
+    let mut sql = String::from(
+        "SELECT c.id, c.mr_id, mr.instance_id, mr.iid AS mr_iid, mr.title AS mr_title,
+                mr.project_name, mr.web_url AS mr_web_url, c.body, c.file_path, c.created_at
+         FROM comments c
+         JOIN merge_requests mr ON mr.id = c.mr_id
+         JOIN gitlab_instances i ON i.id = mr.instance_id
+         WHERE c.system = 0 AND c.author_username = i.authenticated_username",
+    );
+    if range.since.is_some() {
+        sql.push_str(" AND c.created_at >= ?");
+    }
+    if range.until.is_some() {
+        sql.push_str(" AND c.created_at < ?");
+    }
+    for _ in &words {
+        sql.push_str(" AND c.body LIKE ? ESCAPE '\\'");
+    }
+    sql.push_str(" ORDER BY c.created_at DESC, c.id DESC LIMIT ?");
+
+    let mut query = sqlx::query_as::<_, MyComment>(&sql);
+    if let Some(since) = range.since {
+        query = query.bind(since);
+    }
+    if let Some(until) = range.until {
+        query = query.bind(until);
+    }
+    for word in &words {
+        query = query.bind(like_pattern(word));
+    }
+    let mut comments = query.bind(HISTORY_LIMIT).fetch_all(pool).await?;
+
+    for comment in &mut comments {
+        if comment.id > 0 {
+            comment.web_url = Some(format!("{}#note_{}", comment.mr_web_url, comment.id));
+        }
+    }
+    Ok(comments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+
+    #[test]
+    fn escapes_like_wildcards() {
+        assert_eq!(like_pattern("retry"), "%retry%");
+        assert_eq!(like_pattern("100%_a\\b"), "%100\\%\\_a\\\\b%");
+    }
+
+    #[tokio::test]
+    async fn finds_my_comments_by_words_and_range() {
+        let (pool, inst) = seed_instance(true).await;
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, project_name, title, author_username,
+              source_branch, target_branch, state, web_url, created_at, updated_at,
+              labels, reviewers, cached_at)
+             VALUES (1, ?, 3, 10, 'g/p', 'Retry client', 'alice', 's', 'main', 'opened',
+                     'http://x/3', 0, 0, '[]', '[]', 0)",
+        )
+        .bind(inst)
+        .execute(&pool)
+        .await
+        .unwrap();
+        for (id, author, body, at, system) in [
+            (11, "me", "Retry semantics: we back off on 429 only", 100, 0),
+            (12, "me", "Looks good", 200, 0),
+            (13, "alice", "About retry semantics...", 300, 0),
+            (14, "me", "retry semantics changed", 400, 1),
+            (-5, "me", "Pending: RETRY SEMANTICS again", 500, 0),
+        ] {
+            sqlx::query(
+                "INSERT INTO comments (id, mr_id, author_username, body, system, created_at, updated_at)
+                 VALUES (?, 1, ?, ?, ?, ?, ?)",
+            )
+            .bind(id)
+            .bind(author)
+            .bind(body)
+            .bind(system)
+            .bind(at)
+            .bind(at)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let all = get_my_comments(&pool, CommentRange::default(), None)
+            .await
+            .unwrap();
+        let ids: Vec<i64> = all.iter().map(|c| c.id).collect();
+        assert_eq!(ids, vec![-5, 12, 11]);
+        assert_eq!(all[0].web_url, None);
+        assert_eq!(all[2].web_url.as_deref(), Some("http://x/3#note_11"));
+        assert_eq!(all[2].mr_title, "Retry client");
+
+        let found = get_my_comments(&pool, CommentRange::default(), Some("semantics retry"))
+            .await
+            .unwrap();
+        let ids: Vec<i64> = found.iter().map(|c| c.id).collect();
+        assert_eq!(ids, vec![-5, 11]);
+
+        let range = CommentRange {
+            since: Some(100),
+            until: Some(500),
+        };
+        let found = get_my_comments(&pool, range, Some("retry")).await.unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, 11);
+    }
+}
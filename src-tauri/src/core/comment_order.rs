@@ -0,0 +1,279 @@
+//! Comment ordering and the unread cursor.
+//!
+//! Sorting is done in SQL. Apart from the plain chronological order, every
+//! sort keeps threads together: notes are ordered by a key of their thread
+//! (latest activity, open state, file position) and chronologically within
+//! it. Standalone notes are threads of one.
+//!
+//! "Unread" means created by someone else after the MR was last opened,
+//! which the MR view records with [`mark_viewed`].
+
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use crate::models::Comment;
+use serde::{Deserialize, Serialize};
+
+/// Order of the comments returned by `get_comments`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommentSort {
+    /// By creation time, oldest first.
+    #[default]
+    Oldest,
+    /// Threads with the latest activity first.
+    Newest,
+    /// Open threads first, then by when the thread started.
+    UnresolvedFirst,
+    /// Inline threads by file and line, general comments last.
+    ByFile,
+}
+
+impl CommentSort {
+    fn order_by(self) -> &'static str {
+        match self {
+            CommentSort::Oldest => "created_at ASC, id ASC",
+            CommentSort::Newest => "thread_active DESC, thread_key, created_at ASC, id ASC",
+            CommentSort::UnresolvedFirst => {
+                "thread_open DESC, thread_started ASC, thread_key, created_at ASC, id ASC"
+            }
+            CommentSort::ByFile => {
+                "thread_file IS NULL, thread_file, thread_line, thread_started ASC, thread_key, \
+                 created_at ASC, id ASC"
+            }
+        }
+    }
+}
+
+/// All comments of an MR, cached and local, in `sort` order.
+pub async fn list(pool: &DbPool, mr_id: i64, sort: CommentSort) -> Result<Vec<Comment>, AppError> {
+    let sql = format!(
+        r#"
+        SELECT id, mr_id, discussion_id, parent_id, author_username, body,
+               file_path, old_line, new_line, line_type, resolved, resolvable,
+               system, created_at, updated_at, cached_at, is_local
+        FROM (
+            SELECT c.*,
+                   COALESCE(discussion_id, 'note:' || id) AS thread_key,
+                   MIN(created_at) OVER thread AS thread_started,
+                   MAX(created_at) OVER thread AS thread_active,
+                   MAX(file_path) OVER thread AS thread_file,
+                   MIN(COALESCE(new_line, old_line)) OVER thread AS thread_line,
+                   (MAX(resolvable) OVER thread) = 1 AND (MAX(resolved) OVER thread) = 0
+                       AND (MAX(system) OVER thread) = 0 AS thread_open
+            FROM comments c
+            WHERE mr_id = ?
+            WINDOW thread AS (PARTITION BY COALESCE(discussion_id, 'note:' || id))
+        )
+        ORDER BY {}
+        "#,
+        sort.order_by()
+    );
+    let comments = sqlx::query_as::<_, Comment>(&sql)
+        .bind(mr_id)
+        .fetch_all(pool)
+        .await?;
+    Ok(comments)
+}
+
+/// Where the unread comments of an MR start.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentCursor {
+    /// When the MR was last opened; None if never.
+    pub last_viewed_at: Option<i64>,
+    /// Oldest unread comment.
+    pub first_unread_id: Option<i64>,
+    pub unread_count: i64,
+    /// Newest comment, read or not.
+    pub latest_id: Option<i64>,
+}
+
+/// The unread cursor of an MR. System notes, the user's own comments and
+/// unsynced local comments never count as unread.
+pub async fn cursor(pool: &DbPool, mr_id: i64) -> Result<CommentCursor, AppError> {
+    let mr: Option<(Option<i64>, Option<String>)> = sqlx::query_as(
+        "SELECT m.last_viewed_at, i.authenticated_username
+         FROM merge_requests m
+         LEFT JOIN gitlab_instances i ON i.id = m.instance_id
+         WHERE m.id = ?",
+    )
+    .bind(mr_id)
+    .fetch_optional(pool)
+    .await?;
+    let (last_viewed_at, username) =
+        mr.ok_or_else(|| AppError::not_found_with_id("MergeRequest", mr_id.to_string()))?;
+
+    let (unread_count, first_unread_id): (i64, Option<i64>) = sqlx::query_as(
+        "SELECT COUNT(*),
+                (SELECT id FROM comments
+                 WHERE mr_id = ?1 AND system = 0 AND is_local = 0
+                   AND author_username != COALESCE(?2, '') AND created_at > ?3
+                 ORDER BY created_at ASC, id ASC LIMIT 1)
+         FROM comments
+         WHERE mr_id = ?1 AND system = 0 AND is_local = 0
+           AND author_username != COALESCE(?2, '') AND created_at > ?3",
+    )
+    .bind(mr_id)
+    .bind(username.as_deref())
+    .bind(last_viewed_at.unwrap_or(0))
+    .fetch_one(pool)
+    .await?;
+
+    let latest_id: Option<i64> = sqlx::query_scalar(
+        "SELECT id FROM comments WHERE mr_id = ? AND system = 0
+         ORDER BY created_at DESC, id DESC LIMIT 1",
+    )
+    .bind(mr_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(CommentCursor {
+        last_viewed_at,
+        first_unread_id,
+        unread_count,
+        latest_id,
+    })
+}
+
+/// Record that the MR was opened at `at`.
+pub async fn mark_viewed(pool: &DbPool, mr_id: i64, at: i64) -> Result<(), AppError> {
+    let updated = sqlx::query("UPDATE merge_requests SET last_viewed_at = ? WHERE id = ?")
+        .bind(at)
+        .bind(mr_id)
+        .execute(pool)
+        .await?
+        .rows_affected();
+    if updated == 0 {
+        return Err(AppError::not_found_with_id(
+            "MergeRequest",
+            mr_id.to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+
+    async fn seed_mr(pool: &DbPool, inst: i64) {
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, project_name, title, author_username,
+              source_branch, target_branch, state, web_url, created_at, updated_at,
+              labels, reviewers, cached_at)
+             VALUES (1, ?, 3, 10, 'g/p', 'Sorting', 'alice', 's', 'main', 'opened',
+                     'http://x/3', 0, 0, '[]', '[]', 0)",
+        )
+        .bind(inst)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    /// (id, discussion, author, file, resolved, created_at)
+    type Row = (
+        i64,
+        Option<&'static str>,
+        &'static str,
+        Option<&'static str>,
+        bool,
+        i64,
+    );
+
+    async fn seed_comments(pool: &DbPool, rows: &[Row]) {
+        for (id, discussion, author, file, resolved, at) in rows {
+            sqlx::query(
+                "INSERT INTO comments (id, mr_id, discussion_id, author_username, body,
+                                       file_path, new_line, resolved, created_at, updated_at)
+                 VALUES (?, 1, ?, ?, 'x', ?, ?, ?, ?, ?)",
+            )
+            .bind(id)
+            .bind(discussion)
+            .bind(author)
+            .bind(file)
+            .bind(file.map(|_| *id))
+            .bind(resolved)
+            .bind(at)
+            .bind(at)
+            .execute(pool)
+            .await
+            .unwrap();
+        }
+    }
+
+    async fn ids(pool: &DbPool, sort: CommentSort) -> Vec<i64> {
+        list(pool, 1, sort)
+            .await
+            .unwrap()
+            .iter()
+            .map(|c| c.id)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn sorts_keep_threads_together() {
+        let (pool, inst) = seed_instance(true).await;
+        seed_mr(&pool, inst).await;
+        seed_comments(
+            &pool,
+            &[
+                // Resolved thread on b.rs, started first.
+                (1, Some("d1"), "bob", Some("b.rs"), true, 100),
+                // Open thread on a.rs with a late reply.
+                (2, Some("d2"), "bob", Some("a.rs"), false, 200),
+                (3, None, "carol", None, false, 300),
+                (4, Some("d1"), "me", Some("b.rs"), true, 400),
+                (5, Some("d2"), "me", Some("a.rs"), false, 500),
+            ],
+        )
+        .await;
+
+        assert_eq!(ids(&pool, CommentSort::Oldest).await, vec![1, 2, 3, 4, 5]);
+        assert_eq!(ids(&pool, CommentSort::Newest).await, vec![2, 5, 1, 4, 3]);
+        assert_eq!(
+            ids(&pool, CommentSort::UnresolvedFirst).await,
+            vec![2, 5, 3, 1, 4]
+        );
+        assert_eq!(ids(&pool, CommentSort::ByFile).await, vec![2, 5, 1, 4, 3]);
+    }
+
+    #[tokio::test]
+    async fn cursor_counts_others_comments_since_last_view() {
+        let (pool, inst) = seed_instance(true).await;
+        seed_mr(&pool, inst).await;
+        seed_comments(
+            &pool,
+            &[
+                (1, None, "bob", None, false, 100),
+                (2, None, "carol", None, false, 300),
+                (3, None, "me", None, false, 400),
+                (4, None, "bob", None, false, 500),
+            ],
+        )
+        .await;
+
+        // Never opened: everything from others is unread.
+        let never = cursor(&pool, 1).await.unwrap();
+        assert_eq!(never.last_viewed_at, None);
+        assert_eq!((never.first_unread_id, never.unread_count), (Some(1), 3));
+        assert_eq!(never.latest_id, Some(4));
+
+        mark_viewed(&pool, 1, 200).await.unwrap();
+        let since = cursor(&pool, 1).await.unwrap();
+        assert_eq!(since.last_viewed_at, Some(200));
+        assert_eq!((since.first_unread_id, since.unread_count), (Some(2), 2));
+
+        mark_viewed(&pool, 1, 600).await.unwrap();
+        let caught_up = cursor(&pool, 1).await.unwrap();
+        assert_eq!(
+            (caught_up.first_unread_id, caught_up.unread_count),
+            (None, 0)
+        );
+        assert_eq!(caught_up.latest_id, Some(4));
+
+        assert!(cursor(&pool, 2).await.is_err());
+        assert!(mark_viewed(&pool, 2, 600).await.is_err());
+    }
+}
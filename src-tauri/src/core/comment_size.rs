@@ -0,0 +1,165 @@
+//! Size limits for comment bodies.
+//!
+//! GitLab rejects notes over a million characters, but only when the sync
+//! queue pushes them, after the comment already shows as posted. Bodies are
+//! checked before the optimistic insert instead. The composer offers to turn
+//! large pastes into a project upload linked from the comment, and large
+//! bodies are written in slices so a single insert doesn't hold the writer
+//! connection for long.
+
+use crate::core::create_client;
+use crate::core::mr_actions::mr_api_ids;
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use serde::Serialize;
+
+/// GitLab's limit on note length, in characters.
+pub const MAX_BODY_CHARS: usize = 1_000_000;
+
+/// Bodies larger than this are written in slices of this size.
+pub const INSERT_CHUNK_BYTES: usize = 64 * 1024;
+
+/// A pasted text uploaded as a project file.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentAttachment {
+    /// Markdown link to insert into the comment.
+    pub markdown: String,
+    pub url: String,
+    pub size_bytes: usize,
+}
+
+/// Fail with a readable error when GitLab would reject the body's length.
+pub fn check_body(body: &str) -> Result<(), AppError> {
+    // Bytes bound chars from above, so most bodies skip the count.
+    if body.len() <= MAX_BODY_CHARS {
+        return Ok(());
+    }
+    let chars = body.chars().count();
+    if chars <= MAX_BODY_CHARS {
+        return Ok(());
+    }
+    Err(AppError::invalid_input_field(
+        format!(
+            "Comment is too long ({} characters, GitLab allows {}). Upload it as an attachment instead.",
+            chars, MAX_BODY_CHARS
+        ),
+        "body",
+    ))
+}
+
+/// Split `body` into slices of at most `max_bytes`, on character boundaries.
+pub fn chunks(body: &str, max_bytes: usize) -> Vec<&str> {
+    let mut out = Vec::new();
+    let mut rest = body;
+    while rest.len() > max_bytes {
+        let mut end = max_bytes;
+        while !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+        let (head, tail) = rest.split_at(end);
+        out.push(head);
+        rest = tail;
+    }
+    out.push(rest);
+    out
+}
+
+/// Append the remaining slices of a large body to a comment that was inserted
+/// with only its first slice, yielding between writes.
+pub async fn append_body_chunks(
+    pool: &DbPool,
+    comment_id: i64,
+    rest: &[&str],
+) -> Result<(), AppError> {
+    for chunk in rest {
+        sqlx::query("UPDATE comments SET body = body || ? WHERE id = ?")
+            .bind(chunk)
+            .bind(comment_id)
+            .execute(pool)
+            .await?;
+        tokio::task::yield_now().await;
+    }
+    Ok(())
+}
+
+/// Upload `content` to the MR's project and return the link to put in a
+/// comment instead of the text itself.
+pub async fn upload_as_attachment(
+    pool: &DbPool,
+    mr_id: i64,
+    filename: &str,
+    content: &str,
+) -> Result<CommentAttachment, AppError> {
+    if content.is_empty() {
+        return Err(AppError::invalid_input_field(
+            "Nothing to upload",
+            "content",
+        ));
+    }
+    let (instance_id, project_id, _) = mr_api_ids(pool, mr_id).await?;
+    let client = create_client(pool, instance_id).await?;
+    let upload = client
+        .upload_project_file(project_id, filename, content.as_bytes())
+        .await?;
+    Ok(CommentAttachment {
+        markdown: upload.markdown,
+        url: upload.url,
+        size_bytes: content.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+
+    #[test]
+    fn rejects_only_past_the_gitlab_limit() {
+        assert!(check_body("short").is_ok());
+        assert!(check_body(&"a".repeat(MAX_BODY_CHARS)).is_ok());
+        // Multi-byte characters count once.
+        assert!(check_body(&"é".repeat(MAX_BODY_CHARS)).is_ok());
+        let err = check_body(&"a".repeat(MAX_BODY_CHARS + 1)).unwrap_err();
+        assert!(err.to_string().contains("too long"));
+    }
+
+    #[test]
+    fn chunks_on_char_boundaries() {
+        assert_eq!(chunks("", 4), vec![""]);
+        assert_eq!(chunks("abcdefgh", 4), vec!["abcd", "efgh"]);
+        let body = "aéé";
+        let parts = chunks(body, 2);
+        assert_eq!(parts, vec!["a", "é", "é"]);
+        assert_eq!(parts.concat(), body);
+    }
+
+    #[tokio::test]
+    async fn appends_remaining_chunks() {
+        let (pool, inst) = seed_instance(true).await;
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, title, author_username, source_branch,
+              target_branch, state, web_url, created_at, updated_at, cached_at)
+             VALUES (1, ?, 1, 10, 't', 'alice', 's', 'main', 'opened', 'http://x', 0, 0, 0)",
+        )
+        .bind(inst)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO comments (id, mr_id, author_username, body, system, created_at, updated_at, is_local)
+             VALUES (-1, 1, 'alice', 'ab', 0, 0, 0, 1)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        append_body_chunks(&pool, -1, &["cd", "ef"]).await.unwrap();
+        let body: String = sqlx::query_scalar("SELECT body FROM comments WHERE id = -1")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(body, "abcdef");
+    }
+}
@@ -86,10 +86,13 @@ pub fn resolve_context_lines(
     None
 }
 
+use crate::core::comment_size::check_body;
 use crate::core::create_client;
+use crate::core::discussion_access::ensure_can_discuss;
 use crate::core::mr_actions::mr_api_ids;
 use crate::db::pool::DbPool;
 use crate::error::AppError;
+use serde::Deserialize;
 
 /// The three SHAs GitLab needs to position an inline note.
 #[derive(Debug, Clone)]
@@ -173,6 +176,8 @@ pub async fn list_discussions(pool: &DbPool, mr_id: i64) -> Result<Vec<Thread>,
 
 /// Post a general (MR-level) comment.
 pub async fn post_general_comment(pool: &DbPool, mr_id: i64, body: &str) -> Result<(), AppError> {
+    ensure_can_discuss(pool, mr_id).await?;
+    check_body(body)?;
     let (instance_id, project_id, iid) = mr_api_ids(pool, mr_id).await?;
     let client = create_client(pool, instance_id).await?;
     client.add_comment(project_id, iid, body).await?;
@@ -191,6 +196,8 @@ pub async fn post_inline_comment(
     new_line: Option<i64>,
     refs: &DiffRefs,
 ) -> Result<(), AppError> {
+    ensure_can_discuss(pool, mr_id).await?;
+    check_body(body)?;
     let (instance_id, project_id, iid) = mr_api_ids(pool, mr_id).await?;
     let client = create_client(pool, instance_id).await?;
     client
@@ -216,6 +223,8 @@ pub async fn reply(
     discussion_id: &str,
     body: &str,
 ) -> Result<(), AppError> {
+    ensure_can_discuss(pool, mr_id).await?;
+    check_body(body)?;
     let (instance_id, project_id, iid) = mr_api_ids(pool, mr_id).await?;
     let client = create_client(pool, instance_id).await?;
     client
@@ -231,6 +240,7 @@ pub async fn resolve(
     discussion_id: &str,
     resolved: bool,
 ) -> Result<(), AppError> {
+    ensure_can_discuss(pool, mr_id).await?;
     let (instance_id, project_id, iid) = mr_api_ids(pool, mr_id).await?;
     let client = create_client(pool, instance_id).await?;
     client
@@ -239,6 +249,185 @@ pub async fn resolve(
     Ok(())
 }
 
+/// Output format for [`export_discussion`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Markdown,
+    Text,
+}
+
+/// The MR and position a discussion belongs to.
+#[derive(Debug, Clone)]
+pub struct DiscussionContext {
+    pub mr_iid: i64,
+    pub mr_title: String,
+    pub project_name: String,
+    pub mr_web_url: String,
+    pub file_path: Option<String>,
+    pub line: Option<i64>,
+}
+
+/// One note in an exported discussion.
+#[derive(Debug, Clone)]
+pub struct ExportedNote {
+    pub id: i64,
+    pub author: String,
+    pub body: String,
+    pub created_at: i64,
+    /// Not yet synced, so there is no note on GitLab to link to.
+    pub is_local: bool,
+}
+
+fn note_permalink(ctx: &DiscussionContext, note: &ExportedNote) -> Option<String> {
+    (!note.is_local).then(|| format!("{}#note_{}", ctx.mr_web_url, note.id))
+}
+
+fn format_timestamp(ts: i64) -> String {
+    chrono::DateTime::from_timestamp(ts, 0)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M UTC").to_string())
+        .unwrap_or_default()
+}
+
+/// Render a discussion as a transcript: a heading naming the MR, the file
+/// position for inline threads, then every note with author, time and a
+/// link back to it on GitLab. Markdown quotes each note; text indents it.
+pub fn render_discussion(
+    ctx: &DiscussionContext,
+    notes: &[ExportedNote],
+    format: ExportFormat,
+) -> String {
+    let location = ctx.file_path.as_ref().map(|path| match ctx.line {
+        Some(line) => format!("{}:{}", path, line),
+        None => path.clone(),
+    });
+    let thread_link = notes
+        .first()
+        .and_then(|n| note_permalink(ctx, n))
+        .unwrap_or_else(|| ctx.mr_web_url.clone());
+
+    let mut out = String::new();
+    match format {
+        ExportFormat::Markdown => {
+            out.push_str(&format!(
+                "### Discussion on [{}!{}]({}): {}\n\n",
+                ctx.project_name, ctx.mr_iid, thread_link, ctx.mr_title
+            ));
+            if let Some(location) = &location {
+                out.push_str(&format!("On `{}`\n\n", location));
+            }
+            for note in notes {
+                let when = format_timestamp(note.created_at);
+                match note_permalink(ctx, note) {
+                    Some(link) => out.push_str(&format!(
+                        "> **@{}** wrote on [{}]({}):\n>\n",
+                        note.author, when, link
+                    )),
+                    None => {
+                        out.push_str(&format!("> **@{}** wrote on {}:\n>\n", note.author, when))
+                    }
+                }
+                for line in note.body.lines() {
+                    out.push_str(if line.is_empty() { ">" } else { "> " });
+                    out.push_str(line);
+                    out.push('\n');
+                }
+                out.push('\n');
+            }
+        }
+        ExportFormat::Text => {
+            out.push_str(&format!(
+                "Discussion on {}!{}: {}\n",
+                ctx.project_name, ctx.mr_iid, ctx.mr_title
+            ));
+            if let Some(location) = &location {
+                out.push_str(&format!("File: {}\n", location));
+            }
+            out.push_str(&format!("Link: {}\n\n", thread_link));
+            for note in notes {
+                out.push_str(&format!(
+                    "{} ({}):\n",
+                    note.author,
+                    format_timestamp(note.created_at)
+                ));
+                for line in note.body.lines() {
+                    if !line.is_empty() {
+                        out.push_str("    ");
+                    }
+                    out.push_str(line);
+                    out.push('\n');
+                }
+                out.push('\n');
+            }
+        }
+    }
+    out.truncate(out.trim_end().len());
+    out.push('\n');
+    out
+}
+
+#[derive(sqlx::FromRow)]
+struct DiscussionNoteRow {
+    id: i64,
+    author_username: String,
+    body: String,
+    created_at: i64,
+    is_local: bool,
+    file_path: Option<String>,
+    line: Option<i64>,
+    iid: i64,
+    title: String,
+    project_name: String,
+    web_url: String,
+}
+
+/// Export a cached discussion thread as a transcript. System notes are
+/// left out.
+pub async fn export_discussion(
+    pool: &DbPool,
+    discussion_id: &str,
+    format: ExportFormat,
+) -> Result<String, AppError> {
+    let rows: Vec<DiscussionNoteRow> = sqlx::query_as(
+        r#"
+        SELECT c.id, c.author_username, c.body, c.created_at, c.is_local,
+               c.file_path, COALESCE(c.new_line, c.old_line) AS line,
+               m.iid, m.title, m.project_name, m.web_url
+        FROM comments c
+        JOIN merge_requests m ON m.id = c.mr_id
+        WHERE c.discussion_id = ? AND c.system = 0
+        ORDER BY c.created_at ASC, c.id ASC
+        "#,
+    )
+    .bind(discussion_id)
+    .fetch_all(pool)
+    .await?;
+
+    let Some(first) = rows.first() else {
+        return Err(AppError::not_found_with_id("Discussion", discussion_id));
+    };
+    let ctx = DiscussionContext {
+        mr_iid: first.iid,
+        mr_title: first.title.clone(),
+        project_name: first.project_name.clone(),
+        mr_web_url: first.web_url.clone(),
+        file_path: first.file_path.clone(),
+        line: first.line,
+    };
+    let notes: Vec<ExportedNote> = rows
+        .into_iter()
+        .map(|r| ExportedNote {
+            id: r.id,
+            author: r.author_username,
+            body: r.body,
+            created_at: r.created_at,
+            is_local: r.is_local,
+        })
+        .collect();
+
+    Ok(render_discussion(&ctx, &notes, format))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -294,4 +483,59 @@ mod tests {
         let pool = db::initialize(&dir.path().join("t.db")).await.unwrap();
         assert!(diff_refs_from_cache(&pool, 1).await.unwrap().is_none());
     }
+
+    fn export_fixture() -> (DiscussionContext, Vec<ExportedNote>) {
+        let ctx = DiscussionContext {
+            mr_iid: 12,
+            mr_title: "Cache diffs".into(),
+            project_name: "g/p".into(),
+            mr_web_url: "https://gl/g/p/-/merge_requests/12".into(),
+            file_path: Some("src/lib.rs".into()),
+            line: Some(42),
+        };
+        let notes = vec![
+            ExportedNote {
+                id: 100,
+                author: "alice".into(),
+                body: "Why a map?\n\nA vec seems enough.".into(),
+                created_at: 1_700_000_000,
+                is_local: false,
+            },
+            ExportedNote {
+                id: -1,
+                author: "bob".into(),
+                body: "Lookups by id.".into(),
+                created_at: 1_700_000_600,
+                is_local: true,
+            },
+        ];
+        (ctx, notes)
+    }
+
+    #[test]
+    fn export_markdown_quotes_notes_with_permalinks() {
+        let (ctx, notes) = export_fixture();
+        assert_eq!(
+            render_discussion(&ctx, &notes, ExportFormat::Markdown),
+            "### Discussion on [g/p!12](https://gl/g/p/-/merge_requests/12#note_100): Cache diffs\n\n\
+             On `src/lib.rs:42`\n\n\
+             > **@alice** wrote on [2023-11-14 22:13 UTC](https://gl/g/p/-/merge_requests/12#note_100):\n>\n\
+             > Why a map?\n>\n> A vec seems enough.\n\n\
+             > **@bob** wrote on 2023-11-14 22:23 UTC:\n>\n\
+             > Lookups by id.\n"
+        );
+    }
+
+    #[test]
+    fn export_text_indents_notes() {
+        let (ctx, notes) = export_fixture();
+        assert_eq!(
+            render_discussion(&ctx, &notes, ExportFormat::Text),
+            "Discussion on g/p!12: Cache diffs\n\
+             File: src/lib.rs:42\n\
+             Link: https://gl/g/p/-/merge_requests/12#note_100\n\n\
+             alice (2023-11-14 22:13 UTC):\n    Why a map?\n\n    A vec seems enough.\n\n\
+             bob (2023-11-14 22:23 UTC):\n    Lookups by id.\n"
+        );
+    }
 }
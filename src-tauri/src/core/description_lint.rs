@@ -0,0 +1,365 @@
+//! Description lint for my own MRs.
+//!
+//! Projects can keep MR templates in `.gitlab/merge_request_templates`. The
+//! headings of the template a description was written from are the sections
+//! reviewers expect (testing notes, changelog, ...). The lint reports those
+//! the description lacks, or that still hold only the template's placeholder
+//! text, so they get filled in before review.
+
+use crate::core::create_client;
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+
+/// Where GitLab looks for MR description templates.
+pub const TEMPLATE_DIR: &str = ".gitlab/merge_request_templates";
+
+/// Templates read per lint; projects rarely have more than a handful.
+const MAX_TEMPLATES: usize = 20;
+
+/// State of a template section in the description.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SectionStatus {
+    Filled,
+    /// No heading with the section's title.
+    Missing,
+    /// The heading is there but holds nothing beyond the template's text.
+    Empty,
+}
+
+/// One section of the template.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SectionCheck {
+    /// Heading as written in the template.
+    pub heading: String,
+    pub status: SectionStatus,
+}
+
+/// Result of linting a description against the project's MR template.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DescriptionLint {
+    /// Template the description was checked against; None when the project
+    /// has no MR templates.
+    pub template_path: Option<String>,
+    pub sections: Vec<SectionCheck>,
+}
+
+/// A heading and the lines under it, up to the next heading of the same or
+/// a higher level.
+struct Section<'a> {
+    key: String,
+    title: &'a str,
+    body: Vec<&'a str>,
+}
+
+/// Remove `<!-- ... -->` comments, which templates use for instructions.
+fn strip_comments(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("<!--") {
+        out.push_str(&rest[..start]);
+        match rest[start + 4..].find("-->") {
+            Some(end) => rest = &rest[start + 4 + end + 3..],
+            None => return out,
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Parse an ATX heading line into (level, title).
+fn parse_heading(line: &str) -> Option<(usize, &str)> {
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    if indent > 3 {
+        return None;
+    }
+    let line = &line[indent..];
+    let level = line.len() - line.trim_start_matches('#').len();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let rest = &line[level..];
+    if !rest.is_empty() && !rest.starts_with([' ', '\t']) {
+        return None;
+    }
+    let title = rest.trim().trim_end_matches('#').trim_end();
+    Some((level, title))
+}
+
+/// Comparison key of a heading: case, punctuation and emoji are ignored, so
+/// `## Testing notes:` matches `### testing notes`.
+fn heading_key(title: &str) -> String {
+    title
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn sections(markdown: &str) -> Vec<Section<'_>> {
+    let mut headings = Vec::new();
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut in_fence = false;
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        if let Some((level, title)) = parse_heading(line) {
+            headings.push((i, level, title));
+        }
+    }
+
+    headings
+        .iter()
+        .enumerate()
+        .filter_map(|(n, &(line, level, title))| {
+            let key = heading_key(title);
+            if key.is_empty() {
+                return None;
+            }
+            let end = headings[n + 1..]
+                .iter()
+                .find(|&&(_, next_level, _)| next_level <= level)
+                .map_or(lines.len(), |&(next_line, _, _)| next_line);
+            Some(Section {
+                key,
+                title,
+                body: lines[line + 1..end].to_vec(),
+            })
+        })
+        .collect()
+}
+
+/// Check `description` against one template.
+fn check(template: &str, description: &str) -> Vec<SectionCheck> {
+    let template = strip_comments(template);
+    let description = strip_comments(description);
+    let written: HashMap<String, Section> = sections(&description)
+        .into_iter()
+        .rev()
+        .map(|s| (s.key.clone(), s))
+        .collect();
+
+    let mut seen = HashSet::new();
+    sections(&template)
+        .into_iter()
+        .filter(|s| seen.insert(s.key.clone()))
+        .map(|expected| {
+            let placeholder: HashSet<&str> = expected.body.iter().map(|l| l.trim()).collect();
+            let status = match written.get(&expected.key) {
+                None => SectionStatus::Missing,
+                Some(section)
+                    if section.body.iter().any(|l| {
+                        let l = l.trim();
+                        !l.is_empty() && !placeholder.contains(l)
+                    }) =>
+                {
+                    SectionStatus::Filled
+                }
+                Some(_) => SectionStatus::Empty,
+            };
+            SectionCheck {
+                heading: expected.title.to_string(),
+                status,
+            }
+        })
+        .collect()
+}
+
+fn is_default_template(path: &str) -> bool {
+    path.rsplit('/')
+        .next()
+        .and_then(|name| name.rsplit_once('.'))
+        .is_some_and(|(stem, _)| stem.eq_ignore_ascii_case("default"))
+}
+
+/// Lint a description against the template it most likely came from: the
+/// one whose headings it shares most, then `Default.md`, then by path.
+fn lint_against(templates: &[(String, String)], description: &str) -> DescriptionLint {
+    let best = templates
+        .iter()
+        .map(|(path, content)| {
+            let checks = check(content, description);
+            let shared = checks
+                .iter()
+                .filter(|c| c.status != SectionStatus::Missing)
+                .count();
+            (path, checks, shared)
+        })
+        .max_by_key(|(path, _, shared)| (*shared, is_default_template(path), Reverse(*path)));
+
+    match best {
+        Some((path, sections, _)) => DescriptionLint {
+            template_path: Some(path.clone()),
+            sections,
+        },
+        None => DescriptionLint {
+            template_path: None,
+            sections: Vec::new(),
+        },
+    }
+}
+
+/// Lint the description of one of my MRs against the project's MR
+/// templates, read from the MR's target branch.
+pub async fn lint_description(pool: &DbPool, mr_id: i64) -> Result<DescriptionLint, AppError> {
+    let (instance_id, project_id, target_branch, description, is_mine): (
+        i64,
+        i64,
+        String,
+        Option<String>,
+        bool,
+    ) = sqlx::query_as(
+        "SELECT mr.instance_id, mr.project_id, mr.target_branch, mr.description,
+                mr.author_username = COALESCE(i.authenticated_username, '')
+         FROM merge_requests mr
+         JOIN gitlab_instances i ON i.id = mr.instance_id
+         WHERE mr.id = ?",
+    )
+    .bind(mr_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::not_found_with_id("MergeRequest", mr_id.to_string()))?;
+    if !is_mine {
+        return Err(AppError::invalid_input(
+            "Only your own merge requests can be linted",
+        ));
+    }
+
+    let client = create_client(pool, instance_id).await?;
+    let entries = client
+        .get_repository_tree(project_id, TEMPLATE_DIR, &target_branch)
+        .await?;
+    let mut templates = Vec::new();
+    for entry in entries
+        .iter()
+        .filter(|e| e.entry_type == "blob" && e.name.to_ascii_lowercase().ends_with(".md"))
+        .take(MAX_TEMPLATES)
+    {
+        let content = client
+            .get_file_content(project_id, &entry.path, &target_branch)
+            .await?;
+        templates.push((entry.path.clone(), content));
+    }
+
+    Ok(lint_against(
+        &templates,
+        description.as_deref().unwrap_or_default(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE: &str = "\
+## What does this MR do?
+
+<!-- Describe the change. -->
+
+## Testing notes
+
+- [ ] Unit tests
+- [ ] Manual testing
+
+### Screenshots
+
+## Changelog
+";
+
+    fn statuses(checks: &[SectionCheck]) -> Vec<(&str, SectionStatus)> {
+        checks
+            .iter()
+            .map(|c| (c.heading.as_str(), c.status))
+            .collect()
+    }
+
+    #[test]
+    fn reports_missing_and_placeholder_sections() {
+        let description = "\
+## What does this MR do?
+
+Adds retries to the sync loop.
+
+```
+## Changelog
+```
+
+## Testing Notes:
+
+- [ ] Unit tests
+- [ ] Manual testing
+
+### Screenshots
+";
+        assert_eq!(
+            statuses(&check(TEMPLATE, description)),
+            vec![
+                ("What does this MR do?", SectionStatus::Filled),
+                ("Testing notes", SectionStatus::Empty),
+                ("Screenshots", SectionStatus::Empty),
+                ("Changelog", SectionStatus::Missing),
+            ]
+        );
+
+        // Content under a subsection fills its parent too.
+        let description =
+            description.replace("### Screenshots\n", "### Screenshots\n\n![ui](a.png)\n");
+        assert_eq!(
+            check(TEMPLATE, &description)[1].status,
+            SectionStatus::Filled
+        );
+        assert_eq!(
+            statuses(&check(TEMPLATE, ""))
+                .iter()
+                .filter(|(_, s)| *s == SectionStatus::Missing)
+                .count(),
+            4
+        );
+    }
+
+    #[test]
+    fn picks_the_template_the_description_follows() {
+        let templates = vec![
+            (
+                "t/Bug.md".to_string(),
+                "## Root cause\n\n## Fix\n".to_string(),
+            ),
+            ("t/Default.md".to_string(), "## Summary\n".to_string()),
+            ("t/Feature.md".to_string(), TEMPLATE.to_string()),
+        ];
+        let lint = lint_against(&templates, "## Root cause\n\nRace in the pool.\n");
+        assert_eq!(lint.template_path.as_deref(), Some("t/Bug.md"));
+        assert_eq!(
+            statuses(&lint.sections),
+            vec![
+                ("Root cause", SectionStatus::Filled),
+                ("Fix", SectionStatus::Missing)
+            ]
+        );
+
+        // No heading in common: Default.md wins.
+        let lint = lint_against(&templates, "Quick fix.");
+        assert_eq!(lint.template_path.as_deref(), Some("t/Default.md"));
+        assert_eq!(
+            lint_against(&[], "Quick fix."),
+            DescriptionLint {
+                template_path: None,
+                sections: Vec::new()
+            }
+        );
+    }
+}
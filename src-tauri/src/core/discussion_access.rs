@@ -0,0 +1,181 @@
+//! Whether the current user may still comment on an MR.
+//!
+//! GitLab only accepts comments on a locked discussion from project members,
+//! and refuses them outright once the user lost access to the project. The
+//! sync queue used to find out only when pushing, then retried. Sync now
+//! stores `discussion_locked` and the user's project access level; the
+//! comment commands check them before the optimistic insert, and a 403 from
+//! GitLab is remembered on the MR until the MR changes again.
+
+use crate::core::token_scopes;
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use crate::services::sync_queue;
+use sqlx::FromRow;
+
+/// Lowest membership access level (Guest).
+pub const GUEST_ACCESS: i64 = 10;
+
+/// Shown when a non-member tries to comment on a locked discussion.
+pub const LOCKED_MESSAGE: &str =
+    "The discussion on this merge request is locked. Only project members can comment.";
+
+/// Shown when GitLab refused a comment without saying why.
+pub const REFUSED_MESSAGE: &str =
+    "GitLab refused comments on this merge request for your account. Check your access to the project.";
+
+/// What the cache knows about the user's right to comment on an MR.
+#[derive(Debug, Clone, Default, PartialEq, FromRow)]
+pub struct DiscussionAccess {
+    pub discussion_locked: bool,
+    /// 0 when not a member, `None` when not fetched yet.
+    pub access_level: Option<i64>,
+    /// Why GitLab last refused a comment, until the MR changes.
+    pub discussion_refused: Option<String>,
+}
+
+impl DiscussionAccess {
+    /// Why commenting would fail, or `None` when it should go through.
+    /// An unknown access level never blocks; GitLab gets the final say.
+    pub fn denial(&self) -> Option<&str> {
+        if let Some(reason) = &self.discussion_refused {
+            return Some(reason);
+        }
+        let non_member = self.access_level.is_some_and(|level| level < GUEST_ACCESS);
+        (self.discussion_locked && non_member).then_some(LOCKED_MESSAGE)
+    }
+}
+
+/// Load the access hints for an MR.
+pub async fn load(pool: &DbPool, mr_id: i64) -> Result<DiscussionAccess, AppError> {
+    sqlx::query_as(
+        "SELECT mr.discussion_locked, p.access_level, mr.discussion_refused
+         FROM merge_requests mr
+         LEFT JOIN projects p ON p.id = mr.project_id AND p.instance_id = mr.instance_id
+         WHERE mr.id = ?",
+    )
+    .bind(mr_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::not_found_with_id("MergeRequest", mr_id.to_string()))
+}
+
+/// Fail with a readable error when a new comment, reply or resolve on the MR
+/// would be refused by GitLab.
+pub async fn ensure_can_discuss(pool: &DbPool, mr_id: i64) -> Result<(), AppError> {
+    token_scopes::ensure_can_write(pool, mr_id).await?;
+    match load(pool, mr_id).await?.denial() {
+        Some(reason) => Err(AppError::invalid_input(reason)),
+        None => Ok(()),
+    }
+}
+
+/// Remember that GitLab refused a discussion action on the MR and stop the
+/// MR's other queued ones. Returns the number of queued actions stopped.
+pub async fn record_refusal(pool: &DbPool, mr_id: i64, reason: &str) -> Result<u64, AppError> {
+    sqlx::query("UPDATE merge_requests SET discussion_refused = ? WHERE id = ?")
+        .bind(reason)
+        .bind(mr_id)
+        .execute(pool)
+        .await?;
+    sync_queue::fail_discussion_actions(pool, mr_id, reason).await
+}
+
+/// After an MR was synced: stop its queued discussion actions if the fresh
+/// data says GitLab won't take them.
+pub async fn stop_denied_actions(pool: &DbPool, mr_id: i64) -> Result<u64, AppError> {
+    match load(pool, mr_id).await?.denial() {
+        Some(reason) => sync_queue::fail_discussion_actions(pool, mr_id, reason).await,
+        None => Ok(0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+    use crate::models::sync_action::ActionType;
+    use crate::services::sync_queue::EnqueueInput;
+
+    async fn insert_mr(pool: &DbPool, inst: i64, locked: bool) {
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, title, author_username, source_branch,
+              target_branch, state, web_url, created_at, updated_at, cached_at, discussion_locked)
+             VALUES (1, ?, 1, 10, 't', 'alice', 's', 'main', 'opened', 'http://x', 0, 0, 0, ?)",
+        )
+        .bind(inst)
+        .bind(locked)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    async fn set_access(pool: &DbPool, inst: i64, level: Option<i64>) {
+        sqlx::query(
+            "INSERT INTO projects (id, instance_id, name, name_with_namespace, path_with_namespace, web_url, access_level)
+             VALUES (10, ?, 'p', 'g / p', 'g/p', 'http://x', ?)",
+        )
+        .bind(inst)
+        .bind(level)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    #[test]
+    fn locked_blocks_only_known_non_members() {
+        let locked = |access_level| DiscussionAccess {
+            discussion_locked: true,
+            access_level,
+            discussion_refused: None,
+        };
+        assert_eq!(locked(Some(0)).denial(), Some(LOCKED_MESSAGE));
+        assert_eq!(locked(Some(30)).denial(), None);
+        assert_eq!(locked(None).denial(), None);
+
+        let refused = DiscussionAccess {
+            discussion_refused: Some("nope".into()),
+            ..Default::default()
+        };
+        assert_eq!(refused.denial(), Some("nope"));
+    }
+
+    #[tokio::test]
+    async fn blocks_up_front_and_stops_queued_actions() {
+        let (pool, inst) = seed_instance(true).await;
+        insert_mr(&pool, inst, true).await;
+        ensure_can_discuss(&pool, 1).await.unwrap();
+
+        set_access(&pool, inst, Some(0)).await;
+        let err = ensure_can_discuss(&pool, 1).await.unwrap_err();
+        assert!(err.to_string().contains("locked"));
+
+        sync_queue::enqueue_action(
+            &pool,
+            EnqueueInput {
+                mr_id: 1,
+                action_type: ActionType::Reply,
+                payload: "{}".into(),
+                local_reference_id: None,
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(stop_denied_actions(&pool, 1).await.unwrap(), 1);
+        assert!(sync_queue::get_pending_actions(&pool)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn remembers_refusals() {
+        let (pool, inst) = seed_instance(true).await;
+        insert_mr(&pool, inst, false).await;
+        record_refusal(&pool, 1, REFUSED_MESSAGE).await.unwrap();
+        let access = load(&pool, 1).await.unwrap();
+        assert_eq!(access.denial(), Some(REFUSED_MESSAGE));
+        assert!(ensure_can_discuss(&pool, 1).await.is_err());
+    }
+}
@@ -0,0 +1,183 @@
+//! File-level review assignments within one MR.
+//!
+//! An assignment is posted as a regular comment that mentions the reviewer
+//! and lists the files, with the mapping repeated in a hidden HTML comment.
+//! Everyone running the app reads the mapping back from the synced comments,
+//! so assignments travel through GitLab without any extra storage there.
+
+use crate::db::file_assignments::{self, FileAssignment};
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+
+const MARKER_START: &str = "<!-- ultra-gitlab:file-assignment ";
+const MARKER_END: &str = " -->";
+
+/// The mapping carried by an assignment comment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssignmentNote {
+    pub username: String,
+    pub paths: Vec<String>,
+}
+
+/// Body of the comment announcing an assignment.
+pub fn comment_body(note: &AssignmentNote) -> String {
+    // `-->` in a path would end the HTML comment early; `\u003e` is the
+    // same JSON string without it.
+    let json = serde_json::to_string(note)
+        .unwrap_or_default()
+        .replace("-->", "--\\u003e");
+    let mut body = format!(
+        "{}{}{}\n@{}, please review these files:\n",
+        MARKER_START, json, MARKER_END, note.username
+    );
+    for path in &note.paths {
+        body.push_str(&format!("\n- `{}`", path));
+    }
+    body
+}
+
+/// The assignment in a comment body, if it is an assignment comment. Only a
+/// leading marker counts, so quoting an old assignment doesn't repeat it.
+pub fn parse_comment(body: &str) -> Option<AssignmentNote> {
+    let rest = body.trim_start().strip_prefix(MARKER_START)?;
+    let json = &rest[..rest.find(MARKER_END)?];
+    let note: AssignmentNote = serde_json::from_str(json).ok()?;
+    (!note.username.is_empty() && !note.paths.is_empty()).then_some(note)
+}
+
+/// Check an assignment request: the username without a leading `@`, and
+/// the paths deduplicated in order.
+pub fn normalize_request(username: &str, paths: &[String]) -> Result<AssignmentNote, AppError> {
+    let username = username.trim().trim_start_matches('@');
+    if username.is_empty() || username.contains(char::is_whitespace) {
+        return Err(AppError::invalid_input_field(
+            "A reviewer username is required",
+            "username",
+        ));
+    }
+    let mut unique: Vec<String> = Vec::with_capacity(paths.len());
+    for path in paths {
+        if !path.is_empty() && !unique.contains(path) {
+            unique.push(path.clone());
+        }
+    }
+    if unique.is_empty() {
+        return Err(AppError::invalid_input_field(
+            "Pick at least one file to assign",
+            "paths",
+        ));
+    }
+    Ok(AssignmentNote {
+        username: username.to_string(),
+        paths: unique,
+    })
+}
+
+/// Fail unless every path is a file of the MR's cached diff.
+pub async fn ensure_paths_in_diff(
+    pool: &DbPool,
+    mr_id: i64,
+    paths: &[String],
+) -> Result<(), AppError> {
+    for path in paths {
+        let known: bool = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM diff_files
+             WHERE mr_id = ? AND (new_path = ? OR old_path = ?))",
+        )
+        .bind(mr_id)
+        .bind(path)
+        .bind(path)
+        .fetch_one(pool)
+        .await?;
+        if !known {
+            return Err(AppError::not_found_with_id("DiffFile", path.clone()));
+        }
+    }
+    Ok(())
+}
+
+/// The MR's file assignments. Assignment comments in the cache are applied
+/// first, so assignments made by others show up once their comments sync.
+pub async fn get_file_assignments(
+    pool: &DbPool,
+    mr_id: i64,
+) -> Result<Vec<FileAssignment>, AppError> {
+    let notes: Vec<(String, String, i64)> = sqlx::query_as(
+        "SELECT body, author_username, created_at FROM comments
+         WHERE mr_id = ? AND body LIKE '%' || ? || '%'
+         ORDER BY created_at, id",
+    )
+    .bind(mr_id)
+    .bind(MARKER_START.trim_end())
+    .fetch_all(pool)
+    .await?;
+    for (body, author, created_at) in notes {
+        if let Some(note) = parse_comment(&body) {
+            file_assignments::record_assignment(
+                pool,
+                mr_id,
+                &note.paths,
+                &note.username,
+                &author,
+                created_at,
+            )
+            .await?;
+        }
+    }
+    Ok(file_assignments::list_assignments(pool, mr_id).await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(username: &str, paths: &[&str]) -> AssignmentNote {
+        AssignmentNote {
+            username: username.to_string(),
+            paths: paths.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_comment_round_trip() {
+        let original = note("bob", &["src/main.rs", "odd-->name.txt"]);
+        let body = comment_body(&original);
+        assert!(body.contains("@bob, please review these files:"));
+        assert!(body.contains("- `src/main.rs`"));
+        assert_eq!(parse_comment(&body), Some(original));
+
+        // Quoting an assignment in a reply doesn't assign again.
+        assert_eq!(parse_comment(&format!("> {}\n\nOn it!", body)), None);
+    }
+
+    #[test]
+    fn test_parse_comment_ignores_other_comments() {
+        assert_eq!(parse_comment("@bob please review src/main.rs"), None);
+        assert_eq!(
+            parse_comment("<!-- ultra-gitlab:file-assignment not json -->"),
+            None
+        );
+        assert_eq!(
+            parse_comment(r#"<!-- ultra-gitlab:file-assignment {"username":"bob","paths":[]} -->"#),
+            None
+        );
+    }
+
+    #[test]
+    fn test_normalize_request() {
+        let paths = vec![
+            "a.rs".to_string(),
+            "".to_string(),
+            "a.rs".to_string(),
+            "b.rs".to_string(),
+        ];
+        assert_eq!(
+            normalize_request(" @bob ", &paths).unwrap(),
+            note("bob", &["a.rs", "b.rs"])
+        );
+        assert!(normalize_request("@", &paths).is_err());
+        assert!(normalize_request("bob smith", &paths).is_err());
+        assert!(normalize_request("bob", &[]).is_err());
+    }
+}
@@ -0,0 +1,204 @@
+//! Blame for the files of an MR.
+//!
+//! Blame is read at the MR's head SHA, so the diff viewer can show who last
+//! touched each line of the new version. It is cached per `(project, sha,
+//! path)` and never refetched: blame at a commit can't change, and MRs whose
+//! head is the same commit share the row.
+
+use crate::core::create_client;
+use crate::db::file_blame;
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use crate::services::gitlab_client::GitLabBlameRange;
+use serde::{Deserialize, Serialize};
+
+/// Cached blame older than this is pruned once no cached MR diff is at its commit.
+pub const BLAME_TTL_SECS: i64 = 30 * 24 * 60 * 60;
+
+/// Consecutive lines last changed by the same commit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlameRange {
+    /// First line of the range, from 1.
+    pub start_line: i64,
+    pub line_count: i64,
+    pub commit_sha: String,
+    pub author_name: String,
+    pub author_email: String,
+    /// Unix seconds; 0 when GitLab sent an unparseable date.
+    pub authored_at: i64,
+    /// First line of the commit message.
+    pub summary: String,
+}
+
+/// Blame of one file of an MR at its head SHA.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileBlame {
+    pub sha: String,
+    pub file_path: String,
+    /// Ranges in line order; empty when the file doesn't exist at `sha`.
+    pub ranges: Vec<BlameRange>,
+}
+
+fn to_ranges(blame: Vec<GitLabBlameRange>) -> Vec<BlameRange> {
+    let mut next_line = 1;
+    blame
+        .into_iter()
+        .filter(|range| !range.lines.is_empty())
+        .map(|range| {
+            let start_line = next_line;
+            let line_count = range.lines.len() as i64;
+            next_line += line_count;
+            let commit = range.commit;
+            BlameRange {
+                start_line,
+                line_count,
+                authored_at: chrono::DateTime::parse_from_rfc3339(&commit.authored_date)
+                    .map(|d| d.timestamp())
+                    .unwrap_or(0),
+                summary: commit
+                    .message
+                    .lines()
+                    .next()
+                    .unwrap_or_default()
+                    .to_string(),
+                commit_sha: commit.id,
+                author_name: commit.author_name,
+                author_email: commit.author_email,
+            }
+        })
+        .collect()
+}
+
+/// Blame of `file_path` at the MR's head SHA, from the cache or GitLab.
+pub async fn mr_file_blame(
+    pool: &DbPool,
+    mr_id: i64,
+    file_path: &str,
+    now: i64,
+) -> Result<FileBlame, AppError> {
+    let (instance_id, project_id, head_sha): (i64, i64, Option<String>) = sqlx::query_as(
+        "SELECT mr.instance_id, mr.project_id, d.head_sha
+         FROM merge_requests mr
+         LEFT JOIN diffs d ON d.mr_id = mr.id
+         WHERE mr.id = ?",
+    )
+    .bind(mr_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::not_found_with_id("MergeRequest", mr_id.to_string()))?;
+    let sha = head_sha.ok_or_else(|| AppError::not_found_with_id("Diff", mr_id.to_string()))?;
+
+    let cached = file_blame::get_blame(pool, instance_id, project_id, &sha, file_path).await?;
+    let ranges = match cached {
+        Some(json) => serde_json::from_str(&json)
+            .map_err(|e| AppError::internal(format!("Corrupt cached blame: {}", e)))?,
+        None => {
+            let client = create_client(pool, instance_id).await?;
+            let ranges = to_ranges(client.get_file_blame(project_id, file_path, &sha).await?);
+            let json = serde_json::to_string(&ranges)
+                .map_err(|e| AppError::internal(format!("Failed to encode blame: {}", e)))?;
+            file_blame::save_blame(pool, instance_id, project_id, &sha, file_path, &json, now)
+                .await?;
+            ranges
+        }
+    };
+
+    Ok(FileBlame {
+        sha,
+        file_path: file_path.to_string(),
+        ranges,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+    use crate::services::gitlab_client::GitLabBlameCommit;
+
+    fn range(sha: &str, message: &str, lines: usize) -> GitLabBlameRange {
+        GitLabBlameRange {
+            commit: GitLabBlameCommit {
+                id: sha.to_string(),
+                message: message.to_string(),
+                author_name: "Alice".to_string(),
+                author_email: "alice@example.com".to_string(),
+                authored_date: "2024-05-01T12:00:00.000+02:00".to_string(),
+            },
+            lines: vec!["x".to_string(); lines],
+        }
+    }
+
+    #[test]
+    fn ranges_are_numbered_from_line_one() {
+        let ranges = to_ranges(vec![
+            range("a1", "Add parser\n\nLong body.", 3),
+            range("b2", "", 0),
+            range("c3", "Fix typo", 2),
+        ]);
+        let spans: Vec<_> = ranges
+            .iter()
+            .map(|r| (r.commit_sha.as_str(), r.start_line, r.line_count))
+            .collect();
+        assert_eq!(spans, vec![("a1", 1, 3), ("c3", 4, 2)]);
+        assert_eq!(ranges[0].summary, "Add parser");
+        assert_eq!(ranges[0].authored_at, 1_714_557_600);
+    }
+
+    #[tokio::test]
+    async fn cached_blame_is_shared_by_sha_and_pruned_once_unused() {
+        let (pool, inst) = seed_instance(true).await;
+        for (id, head) in [(1, "abc"), (2, "abc")] {
+            sqlx::query(
+                "INSERT INTO merge_requests
+                 (id, instance_id, iid, project_id, project_name, title, author_username,
+                  source_branch, target_branch, state, web_url, created_at, updated_at,
+                  labels, reviewers, cached_at)
+                 VALUES (?, ?, ?, 10, 'g/p', 'MR', 'alice', 's', 'main', 'opened',
+                         'http://x', 0, 0, '[]', '[]', 0)",
+            )
+            .bind(id)
+            .bind(inst)
+            .bind(id)
+            .execute(&pool)
+            .await
+            .unwrap();
+            sqlx::query(
+                "INSERT INTO diffs (mr_id, content, base_sha, head_sha, start_sha,
+                                    file_count, additions, deletions, cached_at)
+                 VALUES (?, '', 'base', ?, 'base', 1, 0, 0, 0)",
+            )
+            .bind(id)
+            .bind(head)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        let ranges = to_ranges(vec![range("a1", "Add parser", 2)]);
+        let json = serde_json::to_string(&ranges).unwrap();
+        file_blame::save_blame(&pool, inst, 10, "abc", "src/lib.rs", &json, 100)
+            .await
+            .unwrap();
+
+        // Both MRs are at `abc`, so neither goes to the network.
+        for mr_id in [1, 2] {
+            let blame = mr_file_blame(&pool, mr_id, "src/lib.rs", 200)
+                .await
+                .unwrap();
+            assert_eq!(blame.sha, "abc");
+            assert_eq!(blame.ranges, ranges);
+        }
+        assert!(mr_file_blame(&pool, 3, "src/lib.rs", 200).await.is_err());
+
+        // Kept while a diff is at the commit, however old.
+        assert_eq!(file_blame::prune_blame(&pool, 1_000).await.unwrap(), 0);
+        sqlx::query("UPDATE diffs SET head_sha = 'def'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert_eq!(file_blame::prune_blame(&pool, 100).await.unwrap(), 0);
+        assert_eq!(file_blame::prune_blame(&pool, 1_000).await.unwrap(), 1);
+    }
+}
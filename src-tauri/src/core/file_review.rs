@@ -0,0 +1,322 @@
+//! Per-file "viewed" state in an MR's diff.
+//!
+//! Marking a file viewed records the MR's head commit and a hash of the
+//! file's diff at that point. When sync caches a new diff, `carry_forward`
+//! moves viewed files whose diff didn't change along to the new head and
+//! unmarks the rest, so files touched by a new push come back for review.
+//! A file whose new diff wasn't loaded (GitLab leaves out very large ones)
+//! can't be compared, so it is unmarked too.
+
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::FromRow;
+use std::collections::HashMap;
+
+/// A file marked viewed at the MR's current head.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct FileReviewState {
+    pub file_path: String,
+    pub head_sha: String,
+    pub viewed_at: i64,
+}
+
+fn diff_hash(diff: Option<&str>) -> String {
+    format!("{:x}", Sha256::digest(diff.unwrap_or_default().as_bytes()))
+}
+
+/// Mark a file of the MR's cached diff viewed, or unmark it.
+pub async fn mark_viewed(
+    pool: &DbPool,
+    mr_id: i64,
+    file_path: &str,
+    viewed: bool,
+    now: i64,
+) -> Result<(), AppError> {
+    if !viewed {
+        sqlx::query("DELETE FROM file_review_state WHERE mr_id = ? AND file_path = ?")
+            .bind(mr_id)
+            .bind(file_path)
+            .execute(pool)
+            .await?;
+        return Ok(());
+    }
+
+    let (head_sha, diff): (String, Option<String>) = sqlx::query_as(
+        "SELECT d.head_sha, f.diff_content
+         FROM diffs d
+         JOIN diff_files f ON f.mr_id = d.mr_id
+         WHERE d.mr_id = ? AND f.new_path = ?",
+    )
+    .bind(mr_id)
+    .bind(file_path)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| {
+        AppError::invalid_input_field("The file isn't in the MR's cached diff", "file_path")
+    })?;
+
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM file_review_state WHERE mr_id = ? AND file_path = ?")
+        .bind(mr_id)
+        .bind(file_path)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query(
+        "INSERT INTO file_review_state (mr_id, file_path, head_sha, diff_hash, viewed_at)
+         VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(mr_id)
+    .bind(file_path)
+    .bind(&head_sha)
+    .bind(diff_hash(diff.as_deref()))
+    .bind(now)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Files of the MR marked viewed at the head of its cached diff.
+pub async fn states(pool: &DbPool, mr_id: i64) -> Result<Vec<FileReviewState>, AppError> {
+    let states = sqlx::query_as::<_, FileReviewState>(
+        "SELECT s.file_path, s.head_sha, s.viewed_at
+         FROM file_review_state s
+         JOIN diffs d ON d.mr_id = s.mr_id AND d.head_sha = s.head_sha
+         WHERE s.mr_id = ?
+         ORDER BY s.file_path",
+    )
+    .bind(mr_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(states)
+}
+
+/// Bring the MR's viewed files in line with its newly cached diff: files
+/// whose diff is unchanged stay viewed at the new head, the others (and
+/// those whose diff isn't loaded) are unmarked. Returns the paths that were
+/// unmarked.
+pub async fn carry_forward(pool: &DbPool, mr_id: i64) -> Result<Vec<String>, AppError> {
+    let Some(head_sha) =
+        sqlx::query_scalar::<_, String>("SELECT head_sha FROM diffs WHERE mr_id = ?")
+            .bind(mr_id)
+            .fetch_optional(pool)
+            .await?
+    else {
+        return Ok(Vec::new());
+    };
+
+    let stale: Vec<(String, String)> = sqlx::query_as(
+        "SELECT file_path, diff_hash FROM file_review_state
+         WHERE mr_id = ? AND head_sha != ?",
+    )
+    .bind(mr_id)
+    .bind(&head_sha)
+    .fetch_all(pool)
+    .await?;
+    if stale.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let current: HashMap<String, String> = sqlx::query_as::<_, (String, Option<String>)>(
+        "SELECT new_path, diff_content FROM diff_files WHERE mr_id = ?",
+    )
+    .bind(mr_id)
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    // Files without a loaded diff are left out, so they count as changed.
+    .filter_map(|(path, diff)| diff.map(|diff| (path, diff_hash(Some(&diff)))))
+    .collect();
+
+    let mut unmarked = Vec::new();
+    let mut tx = pool.begin().await?;
+    for (path, hash) in stale {
+        if current.get(&path) == Some(&hash) {
+            sqlx::query(
+                "UPDATE OR REPLACE file_review_state SET head_sha = ?
+                 WHERE mr_id = ? AND file_path = ? AND head_sha != ?",
+            )
+            .bind(&head_sha)
+            .bind(mr_id)
+            .bind(&path)
+            .bind(&head_sha)
+            .execute(&mut *tx)
+            .await?;
+        } else {
+            sqlx::query("DELETE FROM file_review_state WHERE mr_id = ? AND file_path = ?")
+                .bind(mr_id)
+                .bind(&path)
+                .execute(&mut *tx)
+                .await?;
+            unmarked.push(path);
+        }
+    }
+    tx.commit().await?;
+    Ok(unmarked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+
+    async fn seed_mr(pool: &DbPool, inst: i64) {
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, project_name, title, author_username,
+              source_branch, target_branch, state, web_url, created_at, updated_at,
+              labels, reviewers, cached_at)
+             VALUES (1, ?, 1, 10, 'g/p', 'MR', 'alice', 's', 'main', 'opened',
+                     'http://x', 0, 0, '[]', '[]', 0)",
+        )
+        .bind(inst)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    /// Cache a diff at `head` with the given files and their diffs.
+    async fn set_diff(pool: &DbPool, head: &str, files: &[(&str, &str)]) {
+        let files: Vec<_> = files
+            .iter()
+            .map(|&(path, diff)| (path, path, Some(diff)))
+            .collect();
+        set_diff_files(pool, head, &files).await;
+    }
+
+    /// Cache a diff at `head` with (old path, new path, diff) files.
+    async fn set_diff_files(pool: &DbPool, head: &str, files: &[(&str, &str, Option<&str>)]) {
+        sqlx::query(
+            "INSERT INTO diffs (mr_id, content, base_sha, head_sha, start_sha,
+                                file_count, additions, deletions)
+             VALUES (1, '', 'b', ?, 'b', 0, 0, 0)
+             ON CONFLICT (mr_id) DO UPDATE SET head_sha = excluded.head_sha",
+        )
+        .bind(head)
+        .execute(pool)
+        .await
+        .unwrap();
+        sqlx::query("DELETE FROM diff_files WHERE mr_id = 1")
+            .execute(pool)
+            .await
+            .unwrap();
+        for (i, (old_path, new_path, diff)) in files.iter().enumerate() {
+            sqlx::query(
+                "INSERT INTO diff_files (mr_id, old_path, new_path, change_type, additions,
+                                         deletions, file_position, diff_content)
+                 VALUES (1, ?, ?, 'modified', 1, 0, ?, ?)",
+            )
+            .bind(old_path)
+            .bind(new_path)
+            .bind(i as i64)
+            .bind(diff)
+            .execute(pool)
+            .await
+            .unwrap();
+        }
+    }
+
+    async fn viewed(pool: &DbPool) -> Vec<String> {
+        states(pool, 1)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|s| s.file_path)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn viewed_files_survive_pushes_that_leave_them_alone() {
+        let (pool, inst) = seed_instance(true).await;
+        seed_mr(&pool, inst).await;
+        set_diff(
+            &pool,
+            "aaa",
+            &[("a.rs", "+a"), ("b.rs", "+b"), ("c.rs", "+c")],
+        )
+        .await;
+        assert!(mark_viewed(&pool, 1, "missing.rs", true, 0).await.is_err());
+        for path in ["a.rs", "b.rs", "c.rs"] {
+            mark_viewed(&pool, 1, path, true, 100).await.unwrap();
+        }
+        mark_viewed(&pool, 1, "c.rs", false, 0).await.unwrap();
+        assert_eq!(viewed(&pool).await, vec!["a.rs", "b.rs"]);
+
+        // b.rs changes; c.rs left the MR.
+        set_diff(&pool, "bbb", &[("a.rs", "+a"), ("b.rs", "+b\n+more")]).await;
+        // Until sync carries them forward, nothing is viewed at the new head.
+        assert!(viewed(&pool).await.is_empty());
+        assert_eq!(carry_forward(&pool, 1).await.unwrap(), vec!["b.rs"]);
+        let states = states(&pool, 1).await.unwrap();
+        assert_eq!(states.len(), 1);
+        assert_eq!(states[0].file_path, "a.rs");
+        assert_eq!(states[0].head_sha, "bbb");
+        assert_eq!(states[0].viewed_at, 100);
+
+        assert!(carry_forward(&pool, 1).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn files_without_a_loaded_diff_are_unmarked() {
+        let (pool, inst) = seed_instance(true).await;
+        seed_mr(&pool, inst).await;
+        set_diff_files(
+            &pool,
+            "aaa",
+            &[("a.rs", "a.rs", Some("+a")), ("big.rs", "big.rs", None)],
+        )
+        .await;
+        mark_viewed(&pool, 1, "a.rs", true, 100).await.unwrap();
+        mark_viewed(&pool, 1, "big.rs", true, 100).await.unwrap();
+
+        // Neither diff is loaded at the new head; "no diff" both times
+        // doesn't mean unchanged.
+        set_diff_files(
+            &pool,
+            "bbb",
+            &[("a.rs", "a.rs", None), ("big.rs", "big.rs", None)],
+        )
+        .await;
+        let mut unmarked = carry_forward(&pool, 1).await.unwrap();
+        unmarked.sort();
+        assert_eq!(unmarked, vec!["a.rs", "big.rs"]);
+        assert!(viewed(&pool).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn renamed_files_are_tracked_by_their_new_path() {
+        let (pool, inst) = seed_instance(true).await;
+        seed_mr(&pool, inst).await;
+        set_diff_files(&pool, "aaa", &[("old.rs", "new.rs", Some("+a"))]).await;
+        mark_viewed(&pool, 1, "new.rs", true, 100).await.unwrap();
+
+        // An unrelated push keeps the renamed file viewed.
+        set_diff_files(
+            &pool,
+            "bbb",
+            &[
+                ("old.rs", "new.rs", Some("+a")),
+                ("b.rs", "b.rs", Some("+b")),
+            ],
+        )
+        .await;
+        assert!(carry_forward(&pool, 1).await.unwrap().is_empty());
+        assert_eq!(viewed(&pool).await, vec!["new.rs"]);
+
+        // Renaming it again brings it back for review under the new name.
+        set_diff_files(
+            &pool,
+            "ccc",
+            &[
+                ("old.rs", "newer.rs", Some("+a")),
+                ("b.rs", "b.rs", Some("+b")),
+            ],
+        )
+        .await;
+        assert_eq!(carry_forward(&pool, 1).await.unwrap(), vec!["new.rs"]);
+        assert!(viewed(&pool).await.is_empty());
+    }
+}
@@ -0,0 +1,193 @@
+//! CI minutes, storage and API budget of an instance.
+//!
+//! Explains why pipelines sit queued or syncs slow down. Minutes and storage
+//! are read for the root namespaces of the projects with cached MRs, since
+//! those are the quotas the user's pipelines run against. GitLab hides most
+//! of these numbers from non-owners and from instances without quotas, so
+//! every figure is optional and a namespace that can't be read is skipped.
+
+use crate::core::create_client;
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use crate::services::gitlab_client::{GitLabClient, GitLabNamespace};
+use crate::services::rate_limits::{self, RateLimitSnapshot};
+use chrono::Datelike;
+use serde::Serialize;
+
+/// Namespaces looked up per call, largest by cached MR count first.
+const MAX_NAMESPACES: usize = 10;
+
+/// Quota figures for one root namespace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NamespaceQuota {
+    pub full_path: String,
+    pub kind: String,
+    pub plan: Option<String>,
+    /// Monthly plus purchased shared-runner minutes; `None` when hidden or
+    /// unlimited (see `ci_minutes_unlimited`).
+    pub ci_minutes_limit: Option<i64>,
+    pub ci_minutes_unlimited: bool,
+    /// Minutes used this month.
+    pub ci_minutes_used: Option<i64>,
+    pub ci_minutes_remaining: Option<i64>,
+    pub storage_bytes: Option<i64>,
+}
+
+/// Everything known about an instance's limits.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceQuota {
+    pub instance_id: i64,
+    /// Budget from the most recent response carrying `RateLimit-*` headers;
+    /// `None` when the instance doesn't send them.
+    pub rate_limit: Option<RateLimitSnapshot>,
+    pub namespaces: Vec<NamespaceQuota>,
+}
+
+/// `(limit, unlimited, remaining)` from GitLab's minute fields. A monthly
+/// limit of 0 means unlimited; purchased minutes only count on top of a
+/// real limit.
+fn minutes_budget(
+    monthly: Option<i64>,
+    extra: Option<i64>,
+    used: Option<i64>,
+) -> (Option<i64>, bool, Option<i64>) {
+    match monthly {
+        Some(0) => (None, true, None),
+        Some(monthly) => {
+            let limit = monthly + extra.unwrap_or(0).max(0);
+            (Some(limit), false, used.map(|u| (limit - u).max(0)))
+        }
+        None => (None, false, None),
+    }
+}
+
+/// Root namespace of a project path (`group/sub/project` → `group`).
+fn root_namespace(project_path: &str) -> Option<&str> {
+    project_path
+        .split('/')
+        .next()
+        .filter(|root| !root.is_empty())
+}
+
+async fn namespace_quota(
+    client: &GitLabClient,
+    namespace: GitLabNamespace,
+    month_start: &str,
+) -> NamespaceQuota {
+    let ci_minutes_used = match client.get_ci_minutes_used(namespace.id, month_start).await {
+        Ok(used) => used,
+        Err(e) => {
+            log::debug!("No CI minutes usage for {}: {}", namespace.full_path, e);
+            None
+        }
+    };
+    let storage_bytes = match namespace.root_storage_statistics {
+        Some(stats) => Some(stats.storage_size),
+        None => client
+            .get_namespace_storage_size(&namespace.full_path)
+            .await
+            .unwrap_or_else(|e| {
+                log::debug!("No storage statistics for {}: {}", namespace.full_path, e);
+                None
+            }),
+    };
+    let (ci_minutes_limit, ci_minutes_unlimited, ci_minutes_remaining) = minutes_budget(
+        namespace.shared_runners_minutes_limit,
+        namespace.extra_shared_runners_minutes_limit,
+        ci_minutes_used,
+    );
+    NamespaceQuota {
+        full_path: namespace.full_path,
+        kind: namespace.kind,
+        plan: namespace.plan,
+        ci_minutes_limit,
+        ci_minutes_unlimited,
+        ci_minutes_used,
+        ci_minutes_remaining,
+        storage_bytes,
+    }
+}
+
+/// Read the quotas of an instance's namespaces and its current API budget.
+pub async fn get_instance_quota(
+    pool: &DbPool,
+    instance_id: i64,
+) -> Result<InstanceQuota, AppError> {
+    let client = create_client(pool, instance_id).await?;
+    let url: String = sqlx::query_scalar("SELECT url FROM gitlab_instances WHERE id = ?")
+        .bind(instance_id)
+        .fetch_one(pool)
+        .await?;
+
+    let projects: Vec<String> = sqlx::query_scalar(
+        "SELECT project_name FROM merge_requests
+         WHERE instance_id = ? AND project_name != ''
+         GROUP BY project_name ORDER BY COUNT(*) DESC, project_name",
+    )
+    .bind(instance_id)
+    .fetch_all(pool)
+    .await?;
+    let mut roots: Vec<&str> = Vec::new();
+    for root in projects.iter().filter_map(|p| root_namespace(p)) {
+        if !roots.contains(&root) && roots.len() < MAX_NAMESPACES {
+            roots.push(root);
+        }
+    }
+
+    let today = chrono::Utc::now().date_naive();
+    let month_start = format!("{:04}-{:02}-01", today.year(), today.month());
+    let mut namespaces = Vec::with_capacity(roots.len());
+    for root in roots {
+        match client.get_namespace(root).await {
+            Ok(namespace) => {
+                namespaces.push(namespace_quota(&client, namespace, &month_start).await)
+            }
+            Err(e) => log::debug!("Skipping namespace {} in quota: {}", root, e),
+        }
+    }
+
+    // Read last: the lookups above refresh the budget.
+    Ok(InstanceQuota {
+        instance_id,
+        rate_limit: rate_limits::latest(&url),
+        namespaces,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minutes_budget() {
+        assert_eq!(
+            minutes_budget(Some(400), Some(100), Some(120)),
+            (Some(500), false, Some(380))
+        );
+        assert_eq!(
+            minutes_budget(Some(400), None, Some(900)),
+            (Some(400), false, Some(0))
+        );
+        assert_eq!(
+            minutes_budget(Some(400), None, None),
+            (Some(400), false, None)
+        );
+        assert_eq!(
+            minutes_budget(Some(0), Some(100), Some(5)),
+            (None, true, None)
+        );
+        assert_eq!(
+            minutes_budget(None, Some(100), Some(5)),
+            (None, false, None)
+        );
+    }
+
+    #[test]
+    fn test_root_namespace() {
+        assert_eq!(root_namespace("group/sub/project"), Some("group"));
+        assert_eq!(root_namespace("user/project"), Some("user"));
+        assert_eq!(root_namespace(""), None);
+    }
+}
@@ -0,0 +1,279 @@
+//! Lines changed per language in an MR.
+//!
+//! Languages come from the file extension (or a few well-known file names),
+//! unless the project's `.gitattributes` sets `linguist-language` for the
+//! path. Files marked `linguist-generated` are left out, as GitLab does for
+//! its repository language bar. Names follow Linguist so they match the
+//! overrides.
+
+use crate::core::{cached_gitattributes, cached_language_overrides};
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use crate::services::gitattributes::{self, LanguageOverride};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Language of files none of the rules recognize.
+pub const OTHER_LANGUAGE: &str = "Other";
+
+/// Linguist names by lowercase extension.
+const EXTENSIONS: &[(&str, &str)] = &[
+    ("rs", "Rust"),
+    ("ts", "TypeScript"),
+    ("mts", "TypeScript"),
+    ("cts", "TypeScript"),
+    ("tsx", "TSX"),
+    ("js", "JavaScript"),
+    ("mjs", "JavaScript"),
+    ("cjs", "JavaScript"),
+    ("jsx", "JavaScript"),
+    ("py", "Python"),
+    ("go", "Go"),
+    ("java", "Java"),
+    ("kt", "Kotlin"),
+    ("kts", "Kotlin"),
+    ("scala", "Scala"),
+    ("swift", "Swift"),
+    ("c", "C"),
+    ("h", "C"),
+    ("cc", "C++"),
+    ("cpp", "C++"),
+    ("cxx", "C++"),
+    ("hpp", "C++"),
+    ("cs", "C#"),
+    ("rb", "Ruby"),
+    ("php", "PHP"),
+    ("ex", "Elixir"),
+    ("exs", "Elixir"),
+    ("erl", "Erlang"),
+    ("hs", "Haskell"),
+    ("dart", "Dart"),
+    ("lua", "Lua"),
+    ("r", "R"),
+    ("sql", "SQL"),
+    ("sh", "Shell"),
+    ("bash", "Shell"),
+    ("zsh", "Shell"),
+    ("ps1", "PowerShell"),
+    ("html", "HTML"),
+    ("htm", "HTML"),
+    ("css", "CSS"),
+    ("scss", "SCSS"),
+    ("sass", "Sass"),
+    ("less", "Less"),
+    ("vue", "Vue"),
+    ("svelte", "Svelte"),
+    ("json", "JSON"),
+    ("yml", "YAML"),
+    ("yaml", "YAML"),
+    ("toml", "TOML"),
+    ("xml", "XML"),
+    ("md", "Markdown"),
+    ("markdown", "Markdown"),
+    ("tf", "HCL"),
+    ("hcl", "HCL"),
+    ("proto", "Protocol Buffer"),
+    ("graphql", "GraphQL"),
+    ("gql", "GraphQL"),
+    ("nix", "Nix"),
+];
+
+/// Linguist names of files recognized by their name.
+const FILE_NAMES: &[(&str, &str)] = &[
+    ("Dockerfile", "Dockerfile"),
+    ("Containerfile", "Dockerfile"),
+    ("Makefile", "Makefile"),
+    ("GNUmakefile", "Makefile"),
+    ("Gemfile", "Ruby"),
+    ("Rakefile", "Ruby"),
+    ("Jenkinsfile", "Groovy"),
+    ("CMakeLists.txt", "CMake"),
+];
+
+/// Lines changed in one language.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LanguageLines {
+    pub language: String,
+    /// Added plus removed lines.
+    pub lines: i64,
+}
+
+/// Lines changed per language in an MR, most changed first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LanguageBreakdown {
+    pub mr_id: i64,
+    pub languages: Vec<LanguageLines>,
+    /// Sum over `languages`; generated files aren't counted.
+    pub total_lines: i64,
+}
+
+/// Language of a file from its name alone.
+pub fn language_from_path(path: &str) -> Option<&'static str> {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    if let Some((_, language)) = FILE_NAMES.iter().find(|(n, _)| *n == name) {
+        return Some(language);
+    }
+    let (stem, ext) = name.rsplit_once('.')?;
+    if stem.is_empty() {
+        return None;
+    }
+    let ext = ext.to_ascii_lowercase();
+    EXTENSIONS
+        .iter()
+        .find(|(e, _)| *e == ext)
+        .map(|(_, language)| *language)
+}
+
+/// Group `(path, lines)` pairs by language. The last matching override wins,
+/// as in git; paths matching a `generated` pattern are skipped.
+pub fn breakdown(
+    files: &[(String, i64)],
+    overrides: &[LanguageOverride],
+    generated: &[String],
+) -> Vec<LanguageLines> {
+    let mut lines: HashMap<String, i64> = HashMap::new();
+    for (path, count) in files {
+        if *count == 0
+            || generated
+                .iter()
+                .any(|p| gitattributes::pattern_matches(p, path))
+        {
+            continue;
+        }
+        let language = overrides
+            .iter()
+            .rev()
+            .find(|o| gitattributes::pattern_matches(&o.pattern, path))
+            .map(|o| o.language.as_str())
+            .or_else(|| language_from_path(path))
+            .unwrap_or(OTHER_LANGUAGE);
+        *lines.entry(language.to_string()).or_default() += count;
+    }
+    let mut languages: Vec<LanguageLines> = lines
+        .into_iter()
+        .map(|(language, lines)| LanguageLines { language, lines })
+        .collect();
+    languages.sort_by(|a, b| b.lines.cmp(&a.lines).then(a.language.cmp(&b.language)));
+    languages
+}
+
+/// Lines changed per language in an MR's cached diff.
+pub async fn get_language_breakdown(
+    pool: &DbPool,
+    mr_id: i64,
+) -> Result<LanguageBreakdown, AppError> {
+    let (instance_id, project_id): (i64, i64) =
+        sqlx::query_as("SELECT instance_id, project_id FROM merge_requests WHERE id = ?")
+            .bind(mr_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::not_found_with_id("MergeRequest", mr_id.to_string()))?;
+    let files: Vec<(String, i64)> =
+        sqlx::query_as("SELECT new_path, additions + deletions FROM diff_files WHERE mr_id = ?")
+            .bind(mr_id)
+            .fetch_all(pool)
+            .await?;
+
+    let overrides = cached_language_overrides(pool, instance_id, project_id).await?;
+    let generated = cached_gitattributes(pool, instance_id, project_id).await?;
+    let languages = breakdown(&files, &overrides, &generated);
+    Ok(LanguageBreakdown {
+        mr_id,
+        total_lines: languages.iter().map(|l| l.lines).sum(),
+        languages,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+
+    fn files(list: &[(&str, i64)]) -> Vec<(String, i64)> {
+        list.iter().map(|(p, n)| (p.to_string(), *n)).collect()
+    }
+
+    #[test]
+    fn recognizes_extensions_and_file_names() {
+        assert_eq!(language_from_path("src/lib.rs"), Some("Rust"));
+        assert_eq!(language_from_path("ci/deploy.YML"), Some("YAML"));
+        assert_eq!(language_from_path("docker/Dockerfile"), Some("Dockerfile"));
+        assert_eq!(language_from_path(".gitignore"), None);
+        assert_eq!(language_from_path("LICENSE"), None);
+    }
+
+    #[test]
+    fn groups_lines_with_overrides_and_skips_generated() {
+        let overrides = vec![
+            LanguageOverride {
+                pattern: "*.inc".into(),
+                language: "PHP".into(),
+            },
+            LanguageOverride {
+                pattern: "legacy/*.inc".into(),
+                language: "C".into(),
+            },
+        ];
+        let generated = vec!["*.lock".to_string()];
+        let result = breakdown(
+            &files(&[
+                ("src/main.rs", 50),
+                ("src/db.rs", 20),
+                ("migrations/1.sql", 20),
+                ("Cargo.lock", 900),
+                ("lib/util.inc", 5),
+                ("legacy/old.inc", 5),
+                ("LICENSE", 3),
+                ("README.md", 0),
+            ]),
+            &overrides,
+            &generated,
+        );
+        let pairs: Vec<(&str, i64)> = result
+            .iter()
+            .map(|l| (l.language.as_str(), l.lines))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("Rust", 70),
+                ("SQL", 20),
+                ("C", 5),
+                ("PHP", 5),
+                ("Other", 3)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn reads_the_cached_diff() {
+        let (pool, inst) = seed_instance(true).await;
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, title, author_username, source_branch,
+              target_branch, state, web_url, created_at, updated_at)
+             VALUES (1, ?, 7, 100, 'Add cache', 'alice', 'f', 'main', 'opened', 'w', 0, 0)",
+        )
+        .bind(inst)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO diff_files
+             (mr_id, old_path, new_path, change_type, additions, deletions, file_position)
+             VALUES (1, 'a.rs', 'a.rs', 'modified', 6, 1, 0),
+                    (1, 'ci.yml', 'ci.yml', 'added', 3, 0, 1)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let result = get_language_breakdown(&pool, 1).await.unwrap();
+        assert_eq!(result.total_lines, 10);
+        assert_eq!(result.languages[0].language, "Rust");
+        assert_eq!(result.languages[1].lines, 3);
+        assert!(get_language_breakdown(&pool, 2).await.is_err());
+    }
+}
@@ -0,0 +1,257 @@
+//! Manual-job approval queue.
+//!
+//! Manual jobs (deploy gates and the like) waiting in the pipelines of the
+//! dashboard projects, gathered across instances so they can be reviewed
+//! and played together. Only the newest pipeline of each ref counts: a
+//! manual job left behind in a superseded pipeline isn't waiting for anyone.
+//!
+//! Pinned projects can also notify when a new manual job appears on their
+//! default branch; the sync engine polls them with the pipeline statuses and
+//! asks [`new_gates`] what changed.
+
+use crate::core::create_client;
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use crate::services::gitlab_client::{GitLabClient, GitLabJob};
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+
+/// Manual jobs fetched per project. Enough for the latest pipeline of every
+/// active branch; older ones are superseded anyway.
+pub const MANUAL_JOBS_PER_PROJECT: u32 = 50;
+
+/// A manual job waiting to be played.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingManualJob {
+    pub instance_id: i64,
+    pub project_id: i64,
+    pub project_name: String,
+    pub pipeline_id: i64,
+    pub ref_name: Option<String>,
+    pub job_id: i64,
+    pub job_name: String,
+    pub stage: String,
+    pub web_url: String,
+    pub created_at: String,
+}
+
+/// A job to play, as picked from the queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobRef {
+    pub instance_id: i64,
+    pub project_id: i64,
+    pub job_id: i64,
+}
+
+/// Outcome of playing one job.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayResult {
+    pub instance_id: i64,
+    pub project_id: i64,
+    pub job_id: i64,
+    /// `None` when the job was started.
+    pub error: Option<String>,
+}
+
+/// The manual jobs of `jobs` still waiting in the newest pipeline of their
+/// ref. Order is kept.
+pub fn waiting_jobs(jobs: Vec<GitLabJob>) -> Vec<GitLabJob> {
+    let mut newest: HashMap<Option<String>, i64> = HashMap::new();
+    for job in &jobs {
+        if let Some(pipeline) = &job.pipeline {
+            let id = newest.entry(job.ref_name.clone()).or_insert(pipeline.id);
+            *id = (*id).max(pipeline.id);
+        }
+    }
+    jobs.into_iter()
+        .filter(|job| {
+            job.status == "manual"
+                && job
+                    .pipeline
+                    .as_ref()
+                    .is_some_and(|p| newest.get(&job.ref_name) == Some(&p.id))
+        })
+        .collect()
+}
+
+/// Waiting jobs on `default_branch` that weren't waiting at the previous
+/// poll. `seen` is `None` on the first poll, which only sets the baseline.
+pub fn new_gates<'a>(
+    waiting: &'a [GitLabJob],
+    default_branch: &str,
+    seen: Option<&HashSet<i64>>,
+) -> Vec<&'a GitLabJob> {
+    let Some(seen) = seen else {
+        return Vec::new();
+    };
+    waiting
+        .iter()
+        .filter(|job| job.ref_name.as_deref() == Some(default_branch) && !seen.contains(&job.id))
+        .collect()
+}
+
+#[derive(sqlx::FromRow)]
+struct DashboardProject {
+    instance_id: i64,
+    project_id: i64,
+    name_with_namespace: String,
+}
+
+/// One client per instance in `instance_ids`, or the error creating it.
+async fn clients(
+    pool: &DbPool,
+    instance_ids: impl IntoIterator<Item = i64>,
+) -> HashMap<i64, Result<GitLabClient, String>> {
+    let mut clients = HashMap::new();
+    for instance_id in instance_ids {
+        if let Entry::Vacant(entry) = clients.entry(instance_id) {
+            let client = create_client(pool, instance_id)
+                .await
+                .map_err(|e| e.to_string());
+            entry.insert(client);
+        }
+    }
+    clients
+}
+
+/// Manual jobs waiting in the dashboard projects of every instance with a
+/// token, pinned projects first. A project that can't be fetched is left
+/// out rather than failing the whole queue.
+pub async fn pending_manual_jobs(pool: &DbPool) -> Result<Vec<PendingManualJob>, AppError> {
+    let projects: Vec<DashboardProject> = sqlx::query_as(
+        r#"
+        SELECT pp.instance_id, pp.project_id, p.name_with_namespace
+        FROM pipeline_projects pp
+        JOIN projects p ON p.id = pp.project_id AND p.instance_id = pp.instance_id
+        JOIN gitlab_instances i ON i.id = pp.instance_id
+        WHERE i.token IS NOT NULL
+        ORDER BY pp.pinned DESC,
+                 CASE WHEN pp.sort_order IS NULL THEN 1 ELSE 0 END,
+                 pp.sort_order ASC,
+                 p.name_with_namespace ASC
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    let clients = clients(pool, projects.iter().map(|p| p.instance_id)).await;
+
+    let fetches = projects.iter().filter_map(|project| {
+        let client = clients.get(&project.instance_id)?.as_ref().ok()?;
+        Some(async move {
+            let jobs = client
+                .list_manual_jobs(project.project_id, MANUAL_JOBS_PER_PROJECT)
+                .await;
+            (project, jobs)
+        })
+    });
+
+    let mut pending = Vec::new();
+    for (project, jobs) in join_all(fetches).await {
+        let jobs = match jobs {
+            Ok(jobs) => jobs,
+            Err(e) => {
+                log::warn!(
+                    "Failed to fetch manual jobs of {}: {}",
+                    project.name_with_namespace,
+                    e
+                );
+                continue;
+            }
+        };
+        pending.extend(waiting_jobs(jobs).into_iter().map(|job| PendingManualJob {
+            instance_id: project.instance_id,
+            project_id: project.project_id,
+            project_name: project.name_with_namespace.clone(),
+            pipeline_id: job.pipeline.as_ref().map_or(0, |p| p.id),
+            ref_name: job.ref_name,
+            job_id: job.id,
+            job_name: job.name,
+            stage: job.stage,
+            web_url: job.web_url,
+            created_at: job.created_at,
+        }));
+    }
+    Ok(pending)
+}
+
+/// Play every job in `jobs` at once. One failing job doesn't stop the
+/// others; results come back in the order of `jobs`.
+pub async fn play_jobs(pool: &DbPool, jobs: &[JobRef]) -> Vec<PlayResult> {
+    let clients = clients(pool, jobs.iter().map(|j| j.instance_id)).await;
+    let plays = jobs.iter().map(|job| {
+        let client = &clients[&job.instance_id];
+        async move {
+            let error = match client {
+                Ok(client) => client
+                    .play_job(job.project_id, job.job_id)
+                    .await
+                    .err()
+                    .map(|e| e.to_string()),
+                Err(e) => Some(e.clone()),
+            };
+            PlayResult {
+                instance_id: job.instance_id,
+                project_id: job.project_id,
+                job_id: job.job_id,
+                error,
+            }
+        }
+    });
+    join_all(plays).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(id: i64, pipeline_id: i64, ref_name: &str, status: &str) -> GitLabJob {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": format!("deploy-{id}"),
+            "stage": "deploy",
+            "status": status,
+            "ref": ref_name,
+            "created_at": "2026-01-01T00:00:00Z",
+            "web_url": "http://x",
+            "allow_failure": false,
+            "pipeline": { "id": pipeline_id, "status": "manual" },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn only_the_newest_pipeline_of_a_ref_is_waiting() {
+        let jobs = vec![
+            job(5, 30, "main", "manual"),
+            job(4, 20, "feature", "manual"),
+            job(3, 20, "feature", "success"),
+            job(2, 10, "main", "manual"),
+        ];
+        let ids: Vec<i64> = waiting_jobs(jobs).iter().map(|j| j.id).collect();
+        assert_eq!(ids, vec![5, 4]);
+    }
+
+    #[test]
+    fn new_gates_are_default_branch_jobs_not_seen_before() {
+        let waiting = vec![
+            job(5, 30, "main", "manual"),
+            job(4, 20, "feature", "manual"),
+        ];
+        assert!(new_gates(&waiting, "main", None).is_empty());
+
+        let seen = HashSet::from([4]);
+        let ids: Vec<i64> = new_gates(&waiting, "main", Some(&seen))
+            .iter()
+            .map(|j| j.id)
+            .collect();
+        assert_eq!(ids, vec![5]);
+
+        let seen = HashSet::from([5]);
+        assert!(new_gates(&waiting, "main", Some(&seen)).is_empty());
+    }
+}
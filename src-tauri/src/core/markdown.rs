@@ -0,0 +1,539 @@
+//! GitLab-flavored markdown rendering.
+//!
+//! Descriptions and comments are rendered here rather than in the webview so
+//! the desktop app and the companion show the same HTML. Bodies are untrusted
+//! remote content: raw HTML passes through the parser and the result is
+//! sanitized with an allow-list before it leaves this module.
+//!
+//! References (`#123`, `!45`, `group/project#6`, `@user`) and bare URLs are
+//! turned into links against the instance the text came from. Rendered HTML is
+//! cached by a hash of everything that affects it.
+
+use crate::db::markdown_cache;
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use ammonia::{Builder, UrlRelative, UrlRelativeEvaluate};
+use pulldown_cmark::{CowStr, Event, LinkType, Options, Parser, Tag, TagEnd};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+
+/// Bumped whenever rendering changes, so cached HTML from older versions is
+/// never served.
+const RENDERER_VERSION: u32 = 1;
+
+/// Cached HTML unused for this long is pruned.
+pub const MARKDOWN_TTL_SECS: i64 = 30 * 24 * 60 * 60;
+
+/// The project a text belongs to, as sent by the frontend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectContext {
+    pub instance_id: i64,
+    pub project_id: i64,
+}
+
+/// Where references in a text link to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkBase {
+    /// Instance URL without a trailing slash.
+    pub instance_url: String,
+    /// `None` when the project isn't cached; local references stay text.
+    pub project_path: Option<String>,
+}
+
+impl LinkBase {
+    fn project_url(&self) -> Option<String> {
+        self.project_path
+            .as_ref()
+            .map(|path| format!("{}/{}", self.instance_url, path))
+    }
+}
+
+/// A link found in plain text: `text[start..end]` links to `url`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    pub start: usize,
+    pub end: usize,
+    pub url: String,
+}
+
+fn is_word(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+fn is_path(b: u8) -> bool {
+    is_word(b) || matches!(b, b'.' | b'-' | b'/')
+}
+
+/// `@username` at `at`, unless it's part of an email address or `@all`.
+fn user_reference(text: &str, at: usize, base: &LinkBase) -> Option<Reference> {
+    let bytes = text.as_bytes();
+    if at > 0 && is_path(bytes[at - 1]) {
+        return None;
+    }
+    let mut end = at + 1;
+    while end < bytes.len() && (is_path(bytes[end]) && bytes[end] != b'/') {
+        end += 1;
+    }
+    while end > at + 1 && matches!(bytes[end - 1], b'.' | b'-') {
+        end -= 1;
+    }
+    let username = &text[at + 1..end];
+    if username.is_empty() || !is_word(bytes[at + 1]) || username == "all" {
+        return None;
+    }
+    Some(Reference {
+        start: at,
+        end,
+        url: format!("{}/{}", base.instance_url, username),
+    })
+}
+
+/// `#iid` or `!iid` at `at`, optionally prefixed by a project path.
+fn issuable_reference(text: &str, at: usize, base: &LinkBase) -> Option<Reference> {
+    let bytes = text.as_bytes();
+    let mut end = at + 1;
+    while end < bytes.len() && bytes[end].is_ascii_digit() {
+        end += 1;
+    }
+    if end == at + 1 || (end < bytes.len() && is_word(bytes[end])) {
+        return None;
+    }
+    let mut start = at;
+    while start > 0 && is_path(bytes[start - 1]) {
+        start -= 1;
+    }
+    let prefix = &text[start..at];
+    let project_url = if prefix.is_empty() {
+        base.project_url()?
+    } else if prefix.contains('/')
+        && is_word(bytes[start])
+        && is_word(bytes[at - 1])
+        && (start == 0 || !matches!(bytes[start - 1], b'@' | b':'))
+    {
+        format!("{}/{}", base.instance_url, prefix)
+    } else {
+        return None;
+    };
+    let kind = if bytes[at] == b'#' {
+        "issues"
+    } else {
+        "merge_requests"
+    };
+    Some(Reference {
+        start,
+        end,
+        url: format!("{}/-/{}/{}", project_url, kind, &text[at + 1..end]),
+    })
+}
+
+/// A bare `http(s)://` URL at `at`, without trailing punctuation.
+fn url_reference(text: &str, at: usize) -> Option<Reference> {
+    let bytes = text.as_bytes();
+    if at > 0 && is_path(bytes[at - 1]) {
+        return None;
+    }
+    let rest = &text[at..];
+    let scheme = ["https://", "http://"]
+        .into_iter()
+        .find(|scheme| rest.starts_with(scheme))?;
+    let mut end = at
+        + rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '<' | '>' | '"'))
+            .unwrap_or(rest.len());
+    loop {
+        match bytes[end - 1] {
+            b'.' | b',' | b':' | b';' | b'!' | b'?' | b'\'' | b'*' | b'_' => end -= 1,
+            b')' if text[at..end].matches('(').count() < text[at..end].matches(')').count() => {
+                end -= 1
+            }
+            _ => break,
+        }
+    }
+    if end <= at + scheme.len() {
+        return None;
+    }
+    Some(Reference {
+        start: at,
+        end,
+        url: text[at..end].to_string(),
+    })
+}
+
+/// Links in a run of plain text, in order and not overlapping. Without a
+/// base only bare URLs are found.
+pub fn find_references(text: &str, base: Option<&LinkBase>) -> Vec<Reference> {
+    let bytes = text.as_bytes();
+    let mut refs: Vec<Reference> = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let found = match (bytes[i], base) {
+            (b'h', _) => url_reference(text, i),
+            (b'@', Some(base)) => user_reference(text, i, base),
+            (b'#' | b'!', Some(base)) => issuable_reference(text, i, base),
+            _ => None,
+        };
+        match found {
+            Some(found) if refs.last().is_none_or(|last| found.start >= last.end) => {
+                i = found.end;
+                refs.push(found);
+            }
+            _ => i += 1,
+        }
+    }
+    refs
+}
+
+/// Insert a blank line after `</summary>` when the author forgot one.
+///
+/// A raw HTML block runs until the next blank line, so without it the
+/// markdown inside `<details>` (typically a code fence) shows as literal
+/// text. Lines inside code fences are left alone.
+fn normalize_details_blocks(text: &str) -> Cow<'_, str> {
+    if !text.contains("</summary>") {
+        return Cow::Borrowed(text);
+    }
+    let lines: Vec<&str> = text.split('\n').collect();
+    let mut out = String::with_capacity(text.len() + 16);
+    let mut fence: Option<char> = None;
+    for (i, line) in lines.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        out.push_str(line);
+        let indent = line.len() - line.trim_start_matches(' ').len();
+        let marker = line.trim_start_matches(' ').chars().next();
+        if indent <= 3
+            && matches!(marker, Some('`' | '~'))
+            && line
+                .trim_start_matches(' ')
+                .starts_with(&marker.unwrap().to_string().repeat(3))
+        {
+            match fence {
+                None => fence = marker,
+                Some(open) if Some(open) == marker => fence = None,
+                Some(_) => {}
+            }
+            continue;
+        }
+        let closes_summary = line.trim_end().to_ascii_lowercase().ends_with("</summary>");
+        if fence.is_none()
+            && closes_summary
+            && lines.get(i + 1).is_some_and(|next| !next.trim().is_empty())
+        {
+            out.push('\n');
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// Split buffered text into text and link events.
+fn push_linked<'a>(events: &mut Vec<Event<'a>>, text: String, base: Option<&LinkBase>) {
+    let mut plain_start = 0;
+    for reference in find_references(&text, base) {
+        if reference.start > plain_start {
+            events.push(Event::Text(
+                text[plain_start..reference.start].to_string().into(),
+            ));
+        }
+        events.push(Event::Start(Tag::Link {
+            link_type: LinkType::Autolink,
+            dest_url: reference.url.into(),
+            title: CowStr::Borrowed(""),
+            id: CowStr::Borrowed(""),
+        }));
+        events.push(Event::Text(
+            text[reference.start..reference.end].to_string().into(),
+        ));
+        events.push(Event::End(TagEnd::Link));
+        plain_start = reference.end;
+    }
+    if plain_start < text.len() {
+        events.push(Event::Text(text[plain_start..].to_string().into()));
+    }
+}
+
+/// Link references in text outside code and existing links. The parser
+/// splits text at punctuation, so adjacent text events are joined first.
+fn linkify<'a>(parser: Parser<'a>, base: Option<&LinkBase>) -> Vec<Event<'a>> {
+    let mut events = Vec::new();
+    let mut pending = String::new();
+    let mut in_code = false;
+    let mut link_depth = 0usize;
+    for event in parser {
+        if let Event::Text(text) = &event {
+            if !in_code && link_depth == 0 {
+                pending.push_str(text);
+                continue;
+            }
+        }
+        if !pending.is_empty() {
+            push_linked(&mut events, std::mem::take(&mut pending), base);
+        }
+        match &event {
+            Event::Start(Tag::CodeBlock(_)) => in_code = true,
+            Event::End(TagEnd::CodeBlock) => in_code = false,
+            Event::Start(Tag::Link { .. } | Tag::Image { .. }) => link_depth += 1,
+            Event::End(TagEnd::Link | TagEnd::Image) => link_depth = link_depth.saturating_sub(1),
+            Event::InlineHtml(html) => {
+                let html = html.to_ascii_lowercase();
+                if html.starts_with("<a ") || html.starts_with("<a>") {
+                    link_depth += 1;
+                } else if html.starts_with("</a") {
+                    link_depth = link_depth.saturating_sub(1);
+                }
+            }
+            _ => {}
+        }
+        events.push(event);
+    }
+    if !pending.is_empty() {
+        push_linked(&mut events, pending, base);
+    }
+    events
+}
+
+/// Resolves relative URLs the way GitLab does: uploads against the project,
+/// other absolute paths against the instance and the rest against the
+/// project's repository.
+struct RelativeUrls {
+    instance_url: String,
+    project_url: Option<String>,
+}
+
+impl<'a> UrlRelativeEvaluate<'a> for RelativeUrls {
+    fn evaluate<'url>(&self, url: &'url str) -> Option<Cow<'url, str>> {
+        if url.starts_with('#') {
+            return Some(Cow::Borrowed(url));
+        }
+        let resolved = match (&self.project_url, url.strip_prefix('/')) {
+            (Some(project), Some(path)) if path.starts_with("uploads/") => {
+                format!("{}/{}", project, path)
+            }
+            (_, Some(path)) => format!("{}/{}", self.instance_url, path),
+            (Some(project), None) => format!("{}/-/blob/HEAD/{}", project, url),
+            (None, None) => return None,
+        };
+        Some(Cow::Owned(resolved))
+    }
+}
+
+fn sanitize(html: &str, base: Option<&LinkBase>) -> String {
+    let mut builder = Builder::default();
+    builder
+        // Task list checkboxes, display only.
+        .add_tags(&["input"])
+        .add_tag_attribute_values("input", "type", &["checkbox"])
+        .add_tag_attributes("input", &["checked"])
+        .set_tag_attribute_value("input", "disabled", "")
+        .set_tag_attribute_value("a", "target", "_blank");
+    builder.url_relative(match base {
+        Some(base) => UrlRelative::Custom(Box::new(RelativeUrls {
+            instance_url: base.instance_url.clone(),
+            project_url: base.project_url(),
+        })),
+        None => UrlRelative::Deny,
+    });
+    builder.clean(html).to_string()
+}
+
+/// Render `text` to sanitized HTML, linking references against `base`.
+pub fn render_gfm(text: &str, base: Option<&LinkBase>) -> String {
+    let normalized = normalize_details_blocks(text);
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_GFM;
+    let events = linkify(Parser::new_ext(&normalized, options), base);
+    let mut html = String::with_capacity(text.len() * 3 / 2);
+    pulldown_cmark::html::push_html(&mut html, events.into_iter());
+    sanitize(&html, base)
+}
+
+fn cache_key(text: &str, base: Option<&LinkBase>) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(RENDERER_VERSION.to_le_bytes());
+    if let Some(base) = base {
+        hasher.update(base.instance_url.as_bytes());
+        hasher.update([0]);
+        hasher.update(base.project_path.as_deref().unwrap_or_default().as_bytes());
+    }
+    hasher.update([0]);
+    hasher.update(text.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Instance URL and project path for `context`, if the instance exists.
+async fn link_base(pool: &DbPool, context: ProjectContext) -> Result<Option<LinkBase>, AppError> {
+    let row: Option<(String, Option<String>)> = sqlx::query_as(
+        "SELECT i.url, p.path_with_namespace
+         FROM gitlab_instances i
+         LEFT JOIN projects p ON p.instance_id = i.id AND p.id = ?
+         WHERE i.id = ?",
+    )
+    .bind(context.project_id)
+    .bind(context.instance_id)
+    .fetch_optional(pool)
+    .await?;
+    Ok(row.map(|(url, project_path)| LinkBase {
+        instance_url: url.trim_end_matches('/').to_string(),
+        project_path,
+    }))
+}
+
+/// Render `text` for the project in `context` without touching the cache,
+/// for callers whose text isn't a cached description or comment.
+pub async fn render_uncached(
+    pool: &DbPool,
+    text: &str,
+    context: Option<ProjectContext>,
+) -> Result<String, AppError> {
+    let base = match context {
+        Some(context) => link_base(pool, context).await?,
+        None => None,
+    };
+    Ok(render_gfm(text, base.as_ref()))
+}
+
+/// Render `text` for the project in `context`, from the cache when possible.
+pub async fn render(
+    pool: &DbPool,
+    text: &str,
+    context: Option<ProjectContext>,
+    now: i64,
+) -> Result<String, AppError> {
+    let base = match context {
+        Some(context) => link_base(pool, context).await?,
+        None => None,
+    };
+    let key = cache_key(text, base.as_ref());
+    if let Some(html) = markdown_cache::get_html(pool, &key, now).await? {
+        return Ok(html);
+    }
+    let html = render_gfm(text, base.as_ref());
+    // Attributed to the instance so retention can sweep it with the rest.
+    let instance_id = base.and(context).map(|c| c.instance_id);
+    markdown_cache::save_html(pool, &key, &html, instance_id, now).await?;
+    Ok(html)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+
+    fn base() -> LinkBase {
+        LinkBase {
+            instance_url: "https://gitlab.example.com".into(),
+            project_path: Some("group/app".into()),
+        }
+    }
+
+    #[test]
+    fn references_link_against_the_instance() {
+        let base = base();
+        let text = "Fixes #12 and other/lib!3, thanks @jane.doe. Not a@b.com or @all or #x1.";
+        let refs: Vec<(&str, String)> = find_references(text, Some(&base))
+            .into_iter()
+            .map(|r| (&text[r.start..r.end], r.url))
+            .collect();
+        assert_eq!(
+            refs,
+            vec![
+                (
+                    "#12",
+                    "https://gitlab.example.com/group/app/-/issues/12".into()
+                ),
+                (
+                    "other/lib!3",
+                    "https://gitlab.example.com/other/lib/-/merge_requests/3".into()
+                ),
+                ("@jane.doe", "https://gitlab.example.com/jane.doe".into()),
+            ]
+        );
+
+        let text = "See (https://example.com/a_(b)). and https://x.io/#12";
+        let urls: Vec<String> = find_references(text, None)
+            .into_iter()
+            .map(|r| r.url)
+            .collect();
+        assert_eq!(urls, vec!["https://example.com/a_(b)", "https://x.io/#12"]);
+    }
+
+    #[test]
+    fn renders_sanitized_html_without_linking_code() {
+        let base = base();
+        let html = render_gfm(
+            "<script>alert(1)</script><img src=x onerror=alert(1)>\n\n\
+             Closes #7 in `#8`\n\n- [x] done\n\n[doc](docs/a.md) ![](/uploads/ab/c.png)",
+            Some(&base),
+        );
+        assert!(!html.contains("script") && !html.contains("onerror"));
+        assert!(html.contains(
+            r#"<a href="https://gitlab.example.com/group/app/-/issues/7" target="_blank" rel="noopener noreferrer">#7</a>"#
+        ));
+        assert!(html.contains("<code>#8</code>"));
+        assert!(html.contains(r#"<input type="checkbox" checked="" disabled="">"#));
+        assert!(
+            html.contains(r#"href="https://gitlab.example.com/group/app/-/blob/HEAD/docs/a.md""#)
+        );
+        assert!(html.contains(r#"src="https://gitlab.example.com/group/app/uploads/ab/c.png""#));
+
+        let html = render_gfm(
+            "<details><summary>Logs</summary>\n```\nx\n```\n</details>",
+            None,
+        );
+        assert!(html.contains("<pre><code>x\n</code></pre>"), "{html}");
+        assert!(!render_gfm("[x](javascript:alert(1))", None).contains("javascript"));
+    }
+
+    #[tokio::test]
+    async fn render_caches_per_project() {
+        let (pool, inst) = seed_instance(true).await;
+        sqlx::query(
+            "INSERT INTO projects (id, instance_id, name, name_with_namespace, path_with_namespace, web_url)
+             VALUES (5, ?, 'app', 'Group / app', 'group/app', 'https://gitlab.example.com/group/app')",
+        )
+        .bind(inst)
+        .execute(&pool)
+        .await
+        .unwrap();
+        let context = ProjectContext {
+            instance_id: inst,
+            project_id: 5,
+        };
+
+        let html = render(&pool, "See #1", Some(context), 100).await.unwrap();
+        assert!(html.contains("/group/app/-/issues/1"));
+        assert_eq!(
+            render(&pool, "See #1", None, 100).await.unwrap(),
+            "<p>See #1</p>\n"
+        );
+
+        let rows: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM markdown_cache")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(rows, 2);
+        assert_eq!(
+            render(&pool, "See #1", Some(context), 200).await.unwrap(),
+            html
+        );
+        assert_eq!(markdown_cache::prune_html(&pool, 150).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn uncached_render_leaves_the_cache_alone() {
+        let (pool, _) = seed_instance(true).await;
+        assert_eq!(
+            render_uncached(&pool, "*hi*", None).await.unwrap(),
+            "<p><em>hi</em></p>\n"
+        );
+        let rows: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM markdown_cache")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(rows, 0);
+    }
+}
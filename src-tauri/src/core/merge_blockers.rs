@@ -0,0 +1,414 @@
+//! Everything that stands between an MR and the merge button.
+//!
+//! Normally computed from the cache: threads from `comments`, pipeline and
+//! approval counts from the MR row, and conflicts and GitLab's own verdict
+//! from the `detailed_merge_status` saved on each sync. Per-rule approvals
+//! take a request per MR, so they're only known once the blockers have been
+//! refreshed; a refresh reads everything from GitLab and saves what the
+//! sync doesn't.
+
+use crate::core::create_client;
+use crate::core::mr_actions::{mr_api_ids, strip_draft_prefix};
+use crate::db::approval_rules::{self, ApprovalRuleRow};
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use crate::services::gitlab_client::GitLabDiscussion;
+use serde::Serialize;
+
+/// One reason the MR can't be merged yet, in the order they're reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MergeBlocker {
+    Draft,
+    Conflicts,
+    NeedsRebase,
+    UnresolvedDiscussions,
+    PipelineFailed,
+    PipelineRunning,
+    /// The project requires a pipeline and the MR has none.
+    PipelineMissing,
+    ApprovalsMissing,
+}
+
+/// An approval rule and who satisfied it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalRuleStatus {
+    pub name: String,
+    pub rule_type: String,
+    pub approvals_required: i64,
+    pub approved: bool,
+    pub approved_by: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalProgress {
+    pub required: i64,
+    pub given: i64,
+    /// `None` until approvals have been synced.
+    pub approved: Option<bool>,
+    /// Empty until the blockers are refreshed, or when the instance has no
+    /// approval rules.
+    pub rules: Vec<ApprovalRuleStatus>,
+}
+
+/// What blocks an MR from merging, and the state each blocker comes from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeBlockers {
+    pub mr_id: i64,
+    /// Empty when nothing known blocks the merge.
+    pub blockers: Vec<MergeBlocker>,
+    pub draft: bool,
+    pub has_conflicts: bool,
+    pub pipeline_status: Option<String>,
+    /// Discussion ids of the unresolved threads, oldest first.
+    pub unresolved_discussion_ids: Vec<String>,
+    pub approvals: ApprovalProgress,
+    pub detailed_merge_status: Option<String>,
+    /// Whether this was read from GitLab rather than the cache.
+    pub refreshed: bool,
+}
+
+/// MR state the blockers are derived from, from the cache or from GitLab.
+#[derive(Debug, Clone, Default)]
+struct BlockerInputs {
+    title: String,
+    detailed_merge_status: Option<String>,
+    has_conflicts: bool,
+    pipeline_status: Option<String>,
+    unresolved_discussion_ids: Vec<String>,
+    approvals: ApprovalProgress,
+}
+
+fn blockers(inputs: &BlockerInputs) -> Vec<MergeBlocker> {
+    let status = inputs.detailed_merge_status.as_deref();
+    let mut blockers = Vec::new();
+    if strip_draft_prefix(&inputs.title) != inputs.title || status == Some("draft_status") {
+        blockers.push(MergeBlocker::Draft);
+    }
+    if inputs.has_conflicts || status == Some("conflict") {
+        blockers.push(MergeBlocker::Conflicts);
+    }
+    if status == Some("need_rebase") {
+        blockers.push(MergeBlocker::NeedsRebase);
+    }
+    if !inputs.unresolved_discussion_ids.is_empty() {
+        blockers.push(MergeBlocker::UnresolvedDiscussions);
+    }
+    match inputs.pipeline_status.as_deref() {
+        Some("failed" | "canceled") => blockers.push(MergeBlocker::PipelineFailed),
+        Some("success" | "skipped" | "manual") => {}
+        Some(_) => blockers.push(MergeBlocker::PipelineRunning),
+        None if matches!(status, Some("ci_must_pass" | "ci_still_running")) => {
+            blockers.push(MergeBlocker::PipelineMissing)
+        }
+        None => {}
+    }
+    if inputs.approvals.approved == Some(false) {
+        blockers.push(MergeBlocker::ApprovalsMissing);
+    }
+    blockers
+}
+
+fn rule_status(row: ApprovalRuleRow) -> ApprovalRuleStatus {
+    ApprovalRuleStatus {
+        name: row.name,
+        rule_type: row.rule_type,
+        approvals_required: row.approvals_required,
+        approved: row.approved,
+        approved_by: serde_json::from_str(&row.approved_by).unwrap_or_default(),
+    }
+}
+
+/// Ids of discussions with an open resolvable note, in GitLab's order.
+fn unresolved_ids(discussions: &[GitLabDiscussion]) -> Vec<String> {
+    discussions
+        .iter()
+        .filter(|d| {
+            d.notes
+                .iter()
+                .any(|n| n.resolvable && !n.resolved.unwrap_or(false))
+        })
+        .map(|d| d.id.clone())
+        .collect()
+}
+
+#[derive(sqlx::FromRow)]
+struct CachedMr {
+    title: String,
+    detailed_merge_status: Option<String>,
+    has_conflicts: bool,
+    head_pipeline_status: Option<String>,
+    approval_status: Option<String>,
+    approvals_required: Option<i64>,
+    approvals_count: Option<i64>,
+}
+
+async fn cached_inputs(pool: &DbPool, mr_id: i64) -> Result<BlockerInputs, AppError> {
+    let mr: CachedMr = sqlx::query_as(
+        "SELECT title, detailed_merge_status, has_conflicts, head_pipeline_status,
+                approval_status, approvals_required, approvals_count
+         FROM merge_requests WHERE id = ?",
+    )
+    .bind(mr_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::not_found_with_id("MergeRequest", mr_id.to_string()))?;
+
+    let unresolved_discussion_ids: Vec<String> = sqlx::query_scalar(
+        "SELECT discussion_id FROM comments
+         WHERE mr_id = ? AND discussion_id IS NOT NULL
+           AND resolvable = 1 AND resolved = 0 AND system = 0
+         GROUP BY discussion_id ORDER BY MIN(created_at), discussion_id",
+    )
+    .bind(mr_id)
+    .fetch_all(pool)
+    .await?;
+
+    let rules: Vec<ApprovalRuleStatus> = approval_rules::list_rules(pool, mr_id)
+        .await?
+        .into_iter()
+        .map(rule_status)
+        .collect();
+    let approved = if rules.is_empty() {
+        mr.approval_status.as_deref().map(|s| s == "approved")
+    } else {
+        Some(rules.iter().all(|r| r.approved))
+    };
+
+    Ok(BlockerInputs {
+        title: mr.title,
+        detailed_merge_status: mr.detailed_merge_status,
+        has_conflicts: mr.has_conflicts,
+        pipeline_status: mr.head_pipeline_status,
+        unresolved_discussion_ids,
+        approvals: ApprovalProgress {
+            required: mr.approvals_required.unwrap_or(0),
+            given: mr.approvals_count.unwrap_or(0),
+            approved,
+            rules,
+        },
+    })
+}
+
+/// Read the blocker state from GitLab and save the parts the sync doesn't
+/// keep up to date on its own.
+async fn remote_inputs(pool: &DbPool, mr_id: i64) -> Result<BlockerInputs, AppError> {
+    let (instance_id, project_id, iid) = mr_api_ids(pool, mr_id).await?;
+    let client = create_client(pool, instance_id).await?;
+    let (mr, approvals, approval_state, discussions) = tokio::try_join!(
+        client.get_merge_request(project_id, iid),
+        client.get_mr_approvals(project_id, iid),
+        client.get_mr_approval_state(project_id, iid),
+        client.list_discussions(project_id, iid),
+    )?;
+
+    let rules: Vec<ApprovalRuleRow> = approval_state
+        .map(|state| state.rules)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|rule| ApprovalRuleRow {
+            rule_id: rule.id,
+            name: rule.name,
+            rule_type: rule.rule_type,
+            approvals_required: rule.approvals_required,
+            approved: rule.approved,
+            approved_by: serde_json::to_string(
+                &rule
+                    .approved_by
+                    .iter()
+                    .map(|u| &u.username)
+                    .collect::<Vec<_>>(),
+            )
+            .unwrap_or_else(|_| "[]".to_string()),
+        })
+        .collect();
+    approval_rules::replace_rules(pool, mr_id, &rules).await?;
+
+    let pipeline_status = mr.head_pipeline.map(|p| p.status);
+    sqlx::query(
+        "UPDATE merge_requests SET
+            detailed_merge_status = ?,
+            has_conflicts = ?,
+            head_pipeline_status = COALESCE(?, head_pipeline_status)
+         WHERE id = ?",
+    )
+    .bind(&mr.detailed_merge_status)
+    .bind(mr.has_conflicts)
+    .bind(&pipeline_status)
+    .bind(mr_id)
+    .execute(pool)
+    .await?;
+
+    Ok(BlockerInputs {
+        title: mr.title,
+        detailed_merge_status: mr.detailed_merge_status,
+        has_conflicts: mr.has_conflicts,
+        pipeline_status,
+        unresolved_discussion_ids: unresolved_ids(&discussions),
+        approvals: ApprovalProgress {
+            required: approvals.approvals_required,
+            given: approvals.approvals_required - approvals.approvals_left,
+            approved: Some(approvals.approved),
+            rules: rules.into_iter().map(rule_status).collect(),
+        },
+    })
+}
+
+/// Everything blocking the merge of an MR, from the cache or, with
+/// `refresh`, from GitLab.
+pub async fn get_merge_blockers(
+    pool: &DbPool,
+    mr_id: i64,
+    refresh: bool,
+) -> Result<MergeBlockers, AppError> {
+    let inputs = if refresh {
+        remote_inputs(pool, mr_id).await?
+    } else {
+        cached_inputs(pool, mr_id).await?
+    };
+    let blockers = blockers(&inputs);
+    Ok(MergeBlockers {
+        mr_id,
+        draft: blockers.contains(&MergeBlocker::Draft),
+        blockers,
+        has_conflicts: inputs.has_conflicts,
+        pipeline_status: inputs.pipeline_status,
+        unresolved_discussion_ids: inputs.unresolved_discussion_ids,
+        approvals: inputs.approvals,
+        detailed_merge_status: inputs.detailed_merge_status,
+        refreshed: refresh,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db;
+
+    fn approvals(approved: Option<bool>) -> ApprovalProgress {
+        ApprovalProgress {
+            required: 2,
+            given: 1,
+            approved,
+            rules: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_blockers_in_report_order() {
+        let inputs = BlockerInputs {
+            title: "Draft: Add cache".into(),
+            detailed_merge_status: Some("conflict".into()),
+            pipeline_status: Some("failed".into()),
+            unresolved_discussion_ids: vec!["abc".into()],
+            approvals: approvals(Some(false)),
+            ..Default::default()
+        };
+        assert_eq!(
+            blockers(&inputs),
+            vec![
+                MergeBlocker::Draft,
+                MergeBlocker::Conflicts,
+                MergeBlocker::UnresolvedDiscussions,
+                MergeBlocker::PipelineFailed,
+                MergeBlocker::ApprovalsMissing,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pipeline_and_approval_blockers() {
+        let mut inputs = BlockerInputs {
+            title: "Add cache".into(),
+            pipeline_status: Some("running".into()),
+            approvals: approvals(None),
+            ..Default::default()
+        };
+        assert_eq!(blockers(&inputs), vec![MergeBlocker::PipelineRunning]);
+
+        inputs.pipeline_status = None;
+        assert!(blockers(&inputs).is_empty());
+        inputs.detailed_merge_status = Some("ci_must_pass".into());
+        assert_eq!(blockers(&inputs), vec![MergeBlocker::PipelineMissing]);
+
+        inputs.pipeline_status = Some("success".into());
+        inputs.detailed_merge_status = Some("need_rebase".into());
+        inputs.approvals = approvals(Some(true));
+        assert_eq!(blockers(&inputs), vec![MergeBlocker::NeedsRebase]);
+    }
+
+    #[tokio::test]
+    async fn test_cached_blockers_use_saved_rules_and_threads() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = db::initialize(&dir.path().join("test.db")).await.unwrap();
+        sqlx::query("INSERT INTO gitlab_instances (id, url, name) VALUES (1, 'https://gitlab.com', 'GitLab')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, title, author_username, source_branch,
+              target_branch, state, web_url, created_at, updated_at, approval_status,
+              approvals_required, approvals_count, has_conflicts, head_pipeline_status)
+             VALUES (1, 1, 7, 100, 'Add cache', 'alice', 'f', 'main', 'opened', 'w', 0, 0,
+                     'approved', 1, 1, 1, 'success')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        for (id, discussion, resolved, created_at) in [
+            (1, "d2", 0, 20),
+            (2, "d1", 1, 5),
+            (3, "d1", 0, 10),
+            (4, "d3", 1, 1),
+        ] {
+            sqlx::query(
+                "INSERT INTO comments
+                 (id, mr_id, discussion_id, author_username, body, resolved, created_at, updated_at)
+                 VALUES (?, 1, ?, 'bob', 'x', ?, ?, 0)",
+            )
+            .bind(id)
+            .bind(discussion)
+            .bind(resolved)
+            .bind(created_at)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let result = get_merge_blockers(&pool, 1, false).await.unwrap();
+        assert_eq!(result.unresolved_discussion_ids, vec!["d1", "d2"]);
+        assert_eq!(
+            result.blockers,
+            vec![MergeBlocker::Conflicts, MergeBlocker::UnresolvedDiscussions]
+        );
+
+        // Saved rules override the MR's overall approval status.
+        approval_rules::replace_rules(
+            &pool,
+            1,
+            &[ApprovalRuleRow {
+                rule_id: 9,
+                name: "Backend".into(),
+                rule_type: "code_owner".into(),
+                approvals_required: 1,
+                approved: false,
+                approved_by: "[]".into(),
+            }],
+        )
+        .await
+        .unwrap();
+        let result = get_merge_blockers(&pool, 1, false).await.unwrap();
+        assert_eq!(result.approvals.approved, Some(false));
+        assert_eq!(result.approvals.rules[0].name, "Backend");
+        assert!(result.blockers.contains(&MergeBlocker::ApprovalsMissing));
+
+        assert!(matches!(
+            get_merge_blockers(&pool, 2, false).await,
+            Err(AppError::NotFound { .. })
+        ));
+    }
+}
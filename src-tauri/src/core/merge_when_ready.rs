@@ -0,0 +1,216 @@
+//! "Merge when ready": auto-merge claims with the user's own conditions.
+//!
+//! GitLab's `detailed_merge_status` only blocks on what the project enforces.
+//! A merge-when-ready claim adds a local bar on top — pipeline succeeded,
+//! approvals met, no unresolved threads — which the sync engine checks each
+//! cycle once GitLab reports the MR as mergeable. The claim itself lives in
+//! `auto_merge_claims`, so rebasing and conflict handling are shared with
+//! plain auto-merge.
+
+use crate::db::auto_merge;
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use crate::services::gitlab_client::{GitLabClient, GitLabDiscussion};
+use serde::{Deserialize, Serialize};
+
+/// Status recorded on a claim while GitLab says `mergeable` but one of the
+/// local conditions isn't met yet.
+pub const CONDITIONS_UNMET_STATUS: &str = "conditions_unmet";
+
+/// Conditions a merge-when-ready claim waits for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MergeConditions {
+    /// The head pipeline finished with `success`.
+    pub pipeline_succeeded: bool,
+    /// GitLab reports the approval rules as satisfied.
+    pub approvals_met: bool,
+    /// No resolvable thread is left open.
+    pub threads_resolved: bool,
+}
+
+impl MergeConditions {
+    pub fn is_empty(&self) -> bool {
+        !(self.pipeline_succeeded || self.approvals_met || self.threads_resolved)
+    }
+
+    /// Parse the JSON stored on a claim; `None` for plain auto-merge claims.
+    pub fn from_column(json: Option<&str>) -> Option<Self> {
+        json.and_then(|s| serde_json::from_str(s).ok())
+    }
+}
+
+/// A single condition, as reported back when it blocks the merge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MergeCondition {
+    PipelineSucceeded,
+    ApprovalsMet,
+    ThreadsResolved,
+}
+
+impl MergeCondition {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::PipelineSucceeded => "pipeline success",
+            Self::ApprovalsMet => "approvals",
+            Self::ThreadsResolved => "resolved threads",
+        }
+    }
+}
+
+/// MR state the conditions are checked against. Fields for conditions that
+/// weren't requested are left at their defaults and ignored.
+#[derive(Debug, Clone, Default)]
+pub struct MergeReadiness {
+    pub pipeline_status: Option<String>,
+    pub approvals_met: bool,
+    pub unresolved_threads: usize,
+}
+
+/// Conditions in `conditions` that `readiness` doesn't satisfy.
+pub fn unmet_conditions(
+    conditions: &MergeConditions,
+    readiness: &MergeReadiness,
+) -> Vec<MergeCondition> {
+    let mut unmet = Vec::new();
+    if conditions.pipeline_succeeded && readiness.pipeline_status.as_deref() != Some("success") {
+        unmet.push(MergeCondition::PipelineSucceeded);
+    }
+    if conditions.approvals_met && !readiness.approvals_met {
+        unmet.push(MergeCondition::ApprovalsMet);
+    }
+    if conditions.threads_resolved && readiness.unresolved_threads > 0 {
+        unmet.push(MergeCondition::ThreadsResolved);
+    }
+    unmet
+}
+
+/// Number of discussions with at least one unresolved resolvable note.
+pub fn count_unresolved_threads(discussions: &[GitLabDiscussion]) -> usize {
+    discussions
+        .iter()
+        .filter(|d| {
+            d.notes
+                .iter()
+                .any(|n| n.resolvable && !n.resolved.unwrap_or(false))
+        })
+        .count()
+}
+
+/// Fetch what the requested conditions need from GitLab and return the
+/// unmet ones. `pipeline_status` comes from the MR the caller already has.
+pub async fn check_conditions(
+    client: &GitLabClient,
+    project_id: i64,
+    mr_iid: i64,
+    conditions: &MergeConditions,
+    pipeline_status: Option<String>,
+) -> Result<Vec<MergeCondition>, AppError> {
+    let mut readiness = MergeReadiness {
+        pipeline_status,
+        ..Default::default()
+    };
+    if conditions.approvals_met {
+        readiness.approvals_met = client.get_mr_approvals(project_id, mr_iid).await?.approved;
+    }
+    if conditions.threads_resolved {
+        let discussions = client.list_discussions(project_id, mr_iid).await?;
+        readiness.unresolved_threads = count_unresolved_threads(&discussions);
+    }
+    Ok(unmet_conditions(conditions, &readiness))
+}
+
+/// Store a merge-when-ready intent for an MR, replacing any previous
+/// conditions.
+pub async fn queue(
+    pool: &DbPool,
+    mr_id: i64,
+    conditions: &MergeConditions,
+    now: i64,
+) -> Result<(), AppError> {
+    if conditions.is_empty() {
+        return Err(AppError::invalid_input_field(
+            "Pick at least one condition to wait for",
+            "conditions",
+        ));
+    }
+    let state: Option<String> = sqlx::query_scalar("SELECT state FROM merge_requests WHERE id = ?")
+        .bind(mr_id)
+        .fetch_optional(pool)
+        .await?;
+    let state =
+        state.ok_or_else(|| AppError::not_found_with_id("MergeRequest", mr_id.to_string()))?;
+    if state != "opened" {
+        return Err(AppError::invalid_input(format!(
+            "Merge request is {state}, not open"
+        )));
+    }
+    let json = serde_json::to_string(conditions)
+        .map_err(|e| AppError::internal(format!("Failed to encode merge conditions: {e}")))?;
+    auto_merge::upsert_conditional_claim(pool, mr_id, now, &json).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_requested_conditions_are_checked() {
+        let readiness = MergeReadiness {
+            pipeline_status: Some("running".into()),
+            approvals_met: false,
+            unresolved_threads: 2,
+        };
+        let all = MergeConditions {
+            pipeline_succeeded: true,
+            approvals_met: true,
+            threads_resolved: true,
+        };
+        assert_eq!(
+            unmet_conditions(&all, &readiness),
+            vec![
+                MergeCondition::PipelineSucceeded,
+                MergeCondition::ApprovalsMet,
+                MergeCondition::ThreadsResolved,
+            ]
+        );
+
+        let pipeline_only = MergeConditions {
+            pipeline_succeeded: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            unmet_conditions(&pipeline_only, &readiness),
+            vec![MergeCondition::PipelineSucceeded]
+        );
+
+        let ready = MergeReadiness {
+            pipeline_status: Some("success".into()),
+            approvals_met: true,
+            unresolved_threads: 0,
+        };
+        assert!(unmet_conditions(&all, &ready).is_empty());
+        assert!(MergeConditions::default().is_empty());
+    }
+
+    #[test]
+    fn conditions_round_trip_through_the_claim_column() {
+        let conditions = MergeConditions {
+            pipeline_succeeded: true,
+            approvals_met: false,
+            threads_resolved: true,
+        };
+        let json = serde_json::to_string(&conditions).unwrap();
+        assert_eq!(MergeConditions::from_column(Some(&json)), Some(conditions));
+        assert_eq!(
+            MergeConditions::from_column(Some(r#"{"approvalsMet":true}"#)),
+            Some(MergeConditions {
+                approvals_met: true,
+                ..Default::default()
+            })
+        );
+        assert_eq!(MergeConditions::from_column(None), None);
+    }
+}
@@ -4,15 +4,68 @@
 //! process. The Tauri command handlers delegate to these; the CLI calls them
 //! directly against the same SQLite database.
 
+pub mod api_passthrough;
+pub mod approval_gate;
+pub mod author_ping;
+pub mod automations;
+pub mod badge;
+pub mod cache_archive;
+pub mod change_log;
+pub mod comment_anchors;
+pub mod comment_context;
+pub mod comment_history;
+pub mod comment_order;
+pub mod comment_size;
 pub mod comments;
+pub mod description_lint;
+pub mod discussion_access;
+pub mod file_assignments;
+pub mod file_blame;
+pub mod file_review;
+pub mod instance_quota;
+pub mod language_stats;
+pub mod manual_jobs;
+pub mod markdown;
+pub mod merge_blockers;
+pub mod merge_when_ready;
 pub mod mr_actions;
+pub mod mr_activity;
+pub mod mr_commits;
+pub mod mr_compare;
+pub mod mr_labels;
+pub mod mr_milestones;
+pub mod mr_people;
 pub mod mr_query;
+pub mod mr_tasks;
+pub mod participants;
+pub mod pipeline_graph;
+pub mod pipeline_notify;
 pub mod pipelines;
+pub mod post_merge;
+pub mod project_readme;
+pub mod quick_actions;
+pub mod releases;
+pub mod rereview;
+pub mod retention;
+pub mod review_assistant;
+pub mod review_blockers;
+pub mod review_import;
+pub mod review_progress;
+pub mod review_sla;
+pub mod review_stats;
+pub mod semantic_summaries;
+pub mod statistics_export;
+pub mod suggestions;
+pub mod token_scopes;
+pub mod translation;
+pub mod vacation;
 
 use crate::db::pool::DbPool;
 use crate::error::AppError;
 use crate::models::GitLabInstance;
+use crate::services::gitattributes::LanguageOverride;
 use crate::services::gitlab_client::{GitLabClient, GitLabClientConfig};
+use std::path::PathBuf;
 
 /// Build a GitLab API client for the given instance from its stored token.
 pub async fn create_client(pool: &DbPool, instance_id: i64) -> Result<GitLabClient, AppError> {
@@ -75,6 +128,39 @@ pub async fn cached_gitattributes(
     }
 }
 
+/// Read cached `.gitattributes` `linguist-language` overrides for a project.
+///
+/// Like `cached_gitattributes`, a pure read that returns an empty vec until
+/// the sync engine has fetched the file.
+pub async fn cached_language_overrides(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<Vec<LanguageOverride>, AppError> {
+    let json: Option<String> = sqlx::query_scalar(
+        "SELECT language_overrides FROM gitattributes_cache WHERE instance_id = ? AND project_id = ?",
+    )
+    .bind(instance_id)
+    .bind(project_id)
+    .fetch_optional(pool)
+    .await?;
+    Ok(json
+        .and_then(|j| serde_json::from_str(&j).ok())
+        .unwrap_or_default())
+}
+
+/// Expand a leading `~/` in a user-supplied path to the home directory.
+pub fn expand_home(path: &str) -> Result<PathBuf, AppError> {
+    match path.strip_prefix("~/") {
+        Some(rest) => {
+            let home = std::env::var("HOME")
+                .map_err(|_| AppError::internal("HOME environment variable not set"))?;
+            Ok(PathBuf::from(home).join(rest))
+        }
+        None => Ok(PathBuf::from(path)),
+    }
+}
+
 /// Return the authenticated username stored for an instance, if any.
 pub async fn authenticated_username(
     pool: &DbPool,
@@ -90,7 +176,7 @@ pub async fn authenticated_username(
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
     use crate::db;
     use tempfile::tempdir;
@@ -122,6 +208,17 @@ mod tests {
         (pool, id)
     }
 
+    #[test]
+    fn expand_home_only_touches_a_leading_tilde() {
+        let home = std::env::var("HOME").unwrap();
+        assert_eq!(
+            expand_home("~/notes/review.json").unwrap(),
+            PathBuf::from(home).join("notes/review.json")
+        );
+        assert_eq!(expand_home("/tmp/~/x").unwrap(), PathBuf::from("/tmp/~/x"));
+        assert_eq!(expand_home("~user/x").unwrap(), PathBuf::from("~user/x"));
+    }
+
     #[tokio::test]
     async fn default_instance_id_returns_seeded() {
         let (pool, id) = seed_instance(true).await;
@@ -167,5 +264,23 @@ mod tests {
             cached_gitattributes(&pool, id, 42).await.unwrap(),
             vec!["*.lock".to_string(), "dist/**/*".to_string()]
         );
+        // Rows cached before overrides were stored have none.
+        assert!(cached_language_overrides(&pool, id, 42)
+            .await
+            .unwrap()
+            .is_empty());
+
+        sqlx::query("UPDATE gitattributes_cache SET language_overrides = ? WHERE project_id = 42")
+            .bind(r#"[{"pattern":"*.inc","language":"Rust"}]"#)
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert_eq!(
+            cached_language_overrides(&pool, id, 42).await.unwrap(),
+            vec![LanguageOverride {
+                pattern: "*.inc".to_string(),
+                language: "Rust".to_string(),
+            }]
+        );
     }
 }
@@ -2,10 +2,20 @@
 //!
 //! merge/rebase/undraft call the GitLab API directly (not the sync queue) and
 //! write an optimistic local update, matching the desktop command handlers.
+//!
+//! Every way of approving — the desktop button, review bundles, the
+//! companion, the CLI and bot auto-approve — asks [`approval_hold`] first, so
+//! the approval gate and review blockers can't be sidestepped.
 
+use crate::core::approval_gate::{self, UnmetCondition};
 use crate::core::create_client;
+use crate::core::review_blockers::{self, ReviewBlockers};
+use crate::core::token_scopes::ensure_can_write;
 use crate::db::pool::DbPool;
 use crate::error::AppError;
+use crate::models::sync_action::ActionType;
+use crate::services::sync_queue::{self, ApprovalPayload, EnqueueInput};
+use serde::Serialize;
 
 /// Look up (instance_id, project_id, iid) for a local MR id.
 pub async fn mr_api_ids(pool: &DbPool, mr_id: i64) -> Result<(i64, i64, i64), AppError> {
@@ -30,6 +40,7 @@ pub fn strip_draft_prefix(title: &str) -> String {
 
 /// Merge an MR via the GitLab API, then mark it merged locally.
 pub async fn merge(pool: &DbPool, mr_id: i64) -> Result<(), AppError> {
+    ensure_can_write(pool, mr_id).await?;
     let (instance_id, project_id, iid) = mr_api_ids(pool, mr_id).await?;
     let client = create_client(pool, instance_id).await?;
     client.merge_merge_request(project_id, iid).await?;
@@ -49,6 +60,7 @@ pub async fn merge(pool: &DbPool, mr_id: i64) -> Result<(), AppError> {
 
 /// Rebase an MR's source branch via the GitLab API (async on GitLab's side).
 pub async fn rebase(pool: &DbPool, mr_id: i64) -> Result<(), AppError> {
+    ensure_can_write(pool, mr_id).await?;
     let (instance_id, project_id, iid) = mr_api_ids(pool, mr_id).await?;
     let client = create_client(pool, instance_id).await?;
     client.rebase_merge_request(project_id, iid).await
@@ -66,6 +78,7 @@ pub async fn undraft(pool: &DbPool, mr_id: i64) -> Result<String, AppError> {
     if new_title == title {
         return Ok(title);
     }
+    ensure_can_write(pool, mr_id).await?;
     let (instance_id, project_id, iid) = mr_api_ids(pool, mr_id).await?;
     let client = create_client(pool, instance_id).await?;
     client.mark_merge_request_ready(project_id, iid, &new_title).await?;
@@ -80,8 +93,10 @@ pub async fn undraft(pool: &DbPool, mr_id: i64) -> Result<String, AppError> {
 /// Apply the optimistic local approval-count update used by both the desktop
 /// (queue path) and the CLI (direct path). `approved=true` increments and sets
 /// `user_has_approved=1`; `false` decrements (floored at 0) and clears it.
-pub async fn apply_local_approval(
-    pool: &DbPool,
+///
+/// Takes any executor so it can join a caller's transaction.
+pub async fn apply_local_approval<'e>(
+    executor: impl sqlx::SqliteExecutor<'e>,
     mr_id: i64,
     approved: bool,
 ) -> Result<(), AppError> {
@@ -106,12 +121,153 @@ pub async fn apply_local_approval(
         WHERE id = ?
         "#
     };
-    sqlx::query(sql).bind(mr_id).execute(pool).await?;
+    sqlx::query(sql).bind(mr_id).execute(executor).await?;
     Ok(())
 }
 
+/// Why an approval was held back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApprovalHold {
+    /// The project's approval gate isn't met. Can't be overridden.
+    Gate(Vec<UnmetCondition>),
+    /// Review blockers; approving with `confirm` goes ahead anyway.
+    Blockers(ReviewBlockers),
+}
+
+impl std::fmt::Display for ApprovalHold {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Gate(unmet) => {
+                let reasons: Vec<String> = unmet
+                    .iter()
+                    .map(|condition| match condition {
+                        UnmetCondition::UnviewedFiles { files } => {
+                            format!("{} file(s) not viewed", files.len())
+                        }
+                        UnmetCondition::UnresolvedOwnThreads { discussion_ids } => {
+                            format!("{} of your thread(s) unresolved", discussion_ids.len())
+                        }
+                        UnmetCondition::ChecklistIncomplete { items } => {
+                            format!("{} checklist item(s) open", items.len())
+                        }
+                    })
+                    .collect();
+                write!(f, "Approval gate not met: {}", reasons.join(", "))
+            }
+            Self::Blockers(blockers) => {
+                let mut reasons = Vec::new();
+                if blockers.unresolved_discussions > 0 {
+                    reasons.push(format!(
+                        "{} unresolved thread(s)",
+                        blockers.unresolved_discussions
+                    ));
+                }
+                if blockers.pipeline_failed {
+                    reasons.push("pipeline failed".to_string());
+                }
+                if blockers.has_conflicts {
+                    reasons.push("merge conflicts".to_string());
+                }
+                write!(f, "Review blockers: {}", reasons.join(", "))
+            }
+        }
+    }
+}
+
+/// Result of an approval attempt.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApproveResult {
+    /// False when the approval gate or review blockers held the approval
+    /// back; nothing was changed or queued then.
+    pub approved: bool,
+    /// Gate conditions that still need attention (empty when approved).
+    pub unmet: Vec<UnmetCondition>,
+    /// Set when the approval was held back because of review blockers; the
+    /// caller may confirm and approve anyway.
+    pub blockers: Option<ReviewBlockers>,
+}
+
+impl ApproveResult {
+    fn approved() -> Self {
+        Self {
+            approved: true,
+            unmet: Vec::new(),
+            blockers: None,
+        }
+    }
+}
+
+impl From<ApprovalHold> for ApproveResult {
+    fn from(hold: ApprovalHold) -> Self {
+        let (unmet, blockers) = match hold {
+            ApprovalHold::Gate(unmet) => (unmet, None),
+            ApprovalHold::Blockers(blockers) => (Vec::new(), Some(blockers)),
+        };
+        Self {
+            approved: false,
+            unmet,
+            blockers,
+        }
+    }
+}
+
+/// Whether anything holds back approving an MR: first the project's approval
+/// gate, judged on the MR's stored file review state, then — unless
+/// `confirm` is set — its review blockers. `None` means go ahead.
+pub async fn approval_hold(
+    pool: &DbPool,
+    mr_id: i64,
+    confirm: bool,
+) -> Result<Option<ApprovalHold>, AppError> {
+    let unmet = approval_gate::evaluate(pool, mr_id).await?;
+    if !unmet.is_empty() {
+        return Ok(Some(ApprovalHold::Gate(unmet)));
+    }
+    if !confirm {
+        let blockers = review_blockers::get_review_blockers(pool, mr_id).await?;
+        if blockers.any() {
+            return Ok(Some(ApprovalHold::Blockers(blockers)));
+        }
+    }
+    Ok(None)
+}
+
+/// Approve an MR through the sync queue: checked with [`approval_hold`],
+/// then applied locally and queued. The caller flushes the queue.
+pub async fn queue_approval(
+    pool: &DbPool,
+    mr_id: i64,
+    confirm: bool,
+) -> Result<ApproveResult, AppError> {
+    ensure_can_write(pool, mr_id).await?;
+    if let Some(hold) = approval_hold(pool, mr_id, confirm).await? {
+        return Ok(hold.into());
+    }
+    let (_, project_id, mr_iid) = mr_api_ids(pool, mr_id).await?;
+    apply_local_approval(pool, mr_id, true).await?;
+    let payload = serde_json::to_string(&ApprovalPayload { project_id, mr_iid })?;
+    sync_queue::enqueue_action(
+        pool,
+        EnqueueInput {
+            mr_id,
+            action_type: ActionType::Approve,
+            payload,
+            local_reference_id: None,
+        },
+    )
+    .await?;
+    Ok(ApproveResult::approved())
+}
+
 /// Approve an MR via the GitLab API + optimistic local update (CLI path).
-pub async fn approve(pool: &DbPool, mr_id: i64) -> Result<(), AppError> {
+/// Checked with [`approval_hold`] like the queued path; a hold comes back as
+/// an error describing it.
+pub async fn approve(pool: &DbPool, mr_id: i64, confirm: bool) -> Result<(), AppError> {
+    ensure_can_write(pool, mr_id).await?;
+    if let Some(hold) = approval_hold(pool, mr_id, confirm).await? {
+        return Err(AppError::invalid_input(hold.to_string()));
+    }
     let (instance_id, project_id, iid) = mr_api_ids(pool, mr_id).await?;
     let client = create_client(pool, instance_id).await?;
     client.approve_merge_request(project_id, iid).await?;
@@ -120,6 +276,7 @@ pub async fn approve(pool: &DbPool, mr_id: i64) -> Result<(), AppError> {
 
 /// Unapprove an MR via the GitLab API + optimistic local update (CLI path).
 pub async fn unapprove(pool: &DbPool, mr_id: i64) -> Result<(), AppError> {
+    ensure_can_write(pool, mr_id).await?;
     let (instance_id, project_id, iid) = mr_api_ids(pool, mr_id).await?;
     let client = create_client(pool, instance_id).await?;
     client.unapprove_merge_request(project_id, iid).await?;
@@ -239,4 +396,35 @@ mod tests {
                 .unwrap();
         assert_eq!(status.as_deref(), Some("pending"));
     }
+
+    #[tokio::test]
+    async fn review_blockers_hold_queued_approval_until_confirmed() {
+        let (pool, inst) = crate::core::tests::seed_instance(true).await;
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, title, author_username, source_branch,
+              target_branch, state, web_url, created_at, updated_at, head_pipeline_status)
+             VALUES (1, ?, 7, 100, 'Add cache', 'alice', 'f', 'main', 'opened', 'w', 0, 0, 'failed')",
+        )
+        .bind(inst)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let held = queue_approval(&pool, 1, false).await.unwrap();
+        assert!(!held.approved);
+        assert!(held.blockers.is_some_and(|b| b.pipeline_failed));
+        assert!(sync_queue::get_actions_for_mr(&pool, 1)
+            .await
+            .unwrap()
+            .is_empty());
+
+        assert_eq!(
+            queue_approval(&pool, 1, true).await.unwrap(),
+            ApproveResult::approved()
+        );
+        let queued = sync_queue::get_actions_for_mr(&pool, 1).await.unwrap();
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].action_type, "approve");
+    }
 }
@@ -0,0 +1,446 @@
+//! MR activity timeline.
+//!
+//! GitLab reports what happened to an MR in two ways. Pushes, reviewer and
+//! assignee changes, approvals, draft toggles and title edits only exist as
+//! system notes, whose English text is parsed here. Label, milestone and
+//! state changes come from the resource event endpoints; older GitLab
+//! versions wrote those as system notes too, which are parsed the same way.
+//! The sync stores both in `mr_events`, one source per endpoint.
+
+use crate::db::mr_events::{self, EventKind, MrEvent};
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use crate::services::gitlab_client::{
+    GitLabLabelEvent, GitLabMilestoneEvent, GitLabStateEvent, GitLabUser,
+};
+
+/// `mr_events.source` of events parsed from system notes.
+pub const NOTE: &str = "note";
+/// `mr_events.source` of resource label events.
+pub const LABEL: &str = "label";
+/// `mr_events.source` of resource milestone events.
+pub const MILESTONE: &str = "milestone";
+/// `mr_events.source` of resource state events.
+pub const STATE: &str = "state";
+
+/// What a system note says happened.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedNote {
+    pub kind: EventKind,
+    pub subjects: Vec<String>,
+    pub detail: Option<String>,
+}
+
+impl ParsedNote {
+    fn new(kind: EventKind, subjects: Vec<String>) -> Self {
+        Self {
+            kind,
+            subjects,
+            detail: None,
+        }
+    }
+}
+
+/// Usernames at the start of `text`: `@a, @b and @c`. Stops at the first
+/// other word, so a following clause isn't read as more users.
+fn leading_usernames(text: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    for word in text.split_whitespace() {
+        if word == "and" {
+            continue;
+        }
+        let Some(name) = word.strip_prefix('@') else {
+            break;
+        };
+        let name = name.trim_end_matches([',', '.']);
+        if !name.is_empty() {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
+/// Label (`~`) or milestone (`%`) references in `text`, quoted (`~"needs
+/// review"`) or bare (`~bug`), as written in the note.
+fn references(text: &str, sigil: char) -> Vec<String> {
+    let mut refs = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(sigil) {
+        rest = &rest[start + sigil.len_utf8()..];
+        let (name, remaining) = match rest.strip_prefix('"') {
+            Some(quoted) => match quoted.split_once('"') {
+                Some((name, remaining)) => (name, remaining),
+                None => (quoted, ""),
+            },
+            None => {
+                let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                (rest[..end].trim_end_matches(','), &rest[end..])
+            }
+        };
+        if !name.is_empty() {
+            refs.push(name.to_string());
+        }
+        rest = remaining;
+    }
+    refs
+}
+
+/// The new title of a `changed title from **old** to **new**` note, without
+/// GitLab's inline diff markers (`{-removed-}`, `{+added+}`).
+fn new_title(line: &str) -> Option<String> {
+    let (_, new) = line.split_once("** to **")?;
+    let mut title = new.strip_suffix("**").unwrap_or(new).to_string();
+    while let Some(start) = title.find("{-") {
+        let end = title[start..]
+            .find("-}")
+            .map_or(title.len(), |end| start + end + 2);
+        title.replace_range(start..end, "");
+    }
+    Some(title.replace("{+", "").replace("+}", ""))
+}
+
+/// Parse the body of a system note. Only the first line is read: pushes
+/// list their commits below it. Notes that combine two changes ("assigned
+/// to @a and unassigned @b") are read as their first change; anything not
+/// recognised is `Other` with the line as detail.
+pub fn parse_system_note(body: &str) -> ParsedNote {
+    let line = body.lines().next().unwrap_or_default().trim();
+
+    if let Some((count, rest)) = line
+        .strip_prefix("added ")
+        .and_then(|rest| rest.split_once(' '))
+    {
+        if let (Ok(count), true) = (count.parse::<u32>(), rest.starts_with("commit")) {
+            return ParsedNote {
+                detail: Some(count.to_string()),
+                ..ParsedNote::new(EventKind::Pushed, Vec::new())
+            };
+        }
+    }
+    if line.starts_with("force-pushed") {
+        return ParsedNote::new(EventKind::ForcePushed, Vec::new());
+    }
+    let user_prefixes = [
+        ("requested review from ", EventKind::ReviewRequested),
+        (
+            "removed review request for ",
+            EventKind::ReviewRequestRemoved,
+        ),
+        ("assigned to ", EventKind::Assigned),
+        ("unassigned ", EventKind::Unassigned),
+    ];
+    for (prefix, kind) in user_prefixes {
+        if let Some(rest) = line.strip_prefix(prefix) {
+            return ParsedNote::new(kind, leading_usernames(rest));
+        }
+    }
+    if line.starts_with("approved this merge request") {
+        return ParsedNote::new(EventKind::Approved, Vec::new());
+    }
+    if line.starts_with("unapproved this merge request") {
+        return ParsedNote::new(EventKind::Unapproved, Vec::new());
+    }
+    if line.contains("as **draft**") || line.starts_with("marked as a **Work In Progress**") {
+        return ParsedNote::new(EventKind::MarkedDraft, Vec::new());
+    }
+    if line.contains("as **ready**") || line.starts_with("unmarked as a **Work In Progress**") {
+        return ParsedNote::new(EventKind::MarkedReady, Vec::new());
+    }
+    if line.starts_with("changed title from ") {
+        return ParsedNote {
+            detail: new_title(line),
+            ..ParsedNote::new(EventKind::TitleChanged, Vec::new())
+        };
+    }
+    if line.starts_with("changed the description") {
+        return ParsedNote::new(EventKind::DescriptionChanged, Vec::new());
+    }
+    if line.ends_with(" label") || line.ends_with(" labels") {
+        let clause = line.split(" and removed ").next().unwrap_or(line);
+        if clause.starts_with("added ") {
+            return ParsedNote::new(EventKind::LabelsAdded, references(clause, '~'));
+        }
+        if clause.starts_with("removed ") {
+            return ParsedNote::new(EventKind::LabelsRemoved, references(clause, '~'));
+        }
+    }
+    if let Some(rest) = line.strip_prefix("changed milestone to ") {
+        return ParsedNote::new(EventKind::MilestoneSet, references(rest, '%'));
+    }
+    if line.starts_with("removed milestone") {
+        return ParsedNote::new(EventKind::MilestoneRemoved, Vec::new());
+    }
+    match line {
+        "closed" => ParsedNote::new(EventKind::Closed, Vec::new()),
+        "reopened" => ParsedNote::new(EventKind::Reopened, Vec::new()),
+        _ if line.starts_with("merged") => ParsedNote::new(EventKind::Merged, Vec::new()),
+        _ => ParsedNote {
+            detail: Some(line.to_string()),
+            ..ParsedNote::new(EventKind::Other, Vec::new())
+        },
+    }
+}
+
+fn timestamp(iso: &str) -> i64 {
+    chrono::DateTime::parse_from_rfc3339(iso)
+        .map(|d| d.timestamp())
+        .unwrap_or(0)
+}
+
+fn username(user: &Option<GitLabUser>) -> Option<String> {
+    user.as_ref().map(|u| u.username.clone())
+}
+
+/// Events from label changes. Events of deleted labels are dropped.
+pub fn from_label_events(events: &[GitLabLabelEvent]) -> Vec<MrEvent> {
+    events
+        .iter()
+        .filter_map(|event| {
+            let kind = match event.action.as_str() {
+                "add" => EventKind::LabelsAdded,
+                "remove" => EventKind::LabelsRemoved,
+                _ => return None,
+            };
+            Some(MrEvent {
+                source: LABEL.to_string(),
+                source_id: event.id,
+                kind,
+                actor_username: username(&event.user),
+                subjects: vec![event.label.as_ref()?.name.clone()],
+                detail: None,
+                created_at: timestamp(&event.created_at),
+            })
+        })
+        .collect()
+}
+
+/// Events from milestone changes.
+pub fn from_milestone_events(events: &[GitLabMilestoneEvent]) -> Vec<MrEvent> {
+    events
+        .iter()
+        .filter_map(|event| {
+            let kind = match event.action.as_str() {
+                "add" => EventKind::MilestoneSet,
+                "remove" => EventKind::MilestoneRemoved,
+                _ => return None,
+            };
+            Some(MrEvent {
+                source: MILESTONE.to_string(),
+                source_id: event.id,
+                kind,
+                actor_username: username(&event.user),
+                subjects: event.milestone.iter().map(|m| m.title.clone()).collect(),
+                detail: None,
+                created_at: timestamp(&event.created_at),
+            })
+        })
+        .collect()
+}
+
+/// Events from state changes. Locking isn't shown.
+pub fn from_state_events(events: &[GitLabStateEvent]) -> Vec<MrEvent> {
+    events
+        .iter()
+        .filter_map(|event| {
+            let kind = match event.state.as_str() {
+                "closed" => EventKind::Closed,
+                "reopened" => EventKind::Reopened,
+                "merged" => EventKind::Merged,
+                _ => return None,
+            };
+            Some(MrEvent {
+                source: STATE.to_string(),
+                source_id: event.id,
+                kind,
+                actor_username: username(&event.user),
+                subjects: Vec::new(),
+                detail: None,
+                created_at: timestamp(&event.created_at),
+            })
+        })
+        .collect()
+}
+
+/// Events parsed from the MR's cached system notes.
+pub async fn note_events(pool: &DbPool, mr_id: i64) -> Result<Vec<MrEvent>, AppError> {
+    let notes: Vec<(i64, String, String, i64)> = sqlx::query_as(
+        "SELECT id, author_username, body, created_at FROM comments
+         WHERE mr_id = ? AND system = 1 AND is_local = 0",
+    )
+    .bind(mr_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(notes
+        .into_iter()
+        .map(|(id, author, body, created_at)| {
+            let parsed = parse_system_note(&body);
+            MrEvent {
+                source: NOTE.to_string(),
+                source_id: id,
+                kind: parsed.kind,
+                actor_username: Some(author),
+                subjects: parsed.subjects,
+                detail: parsed.detail,
+                created_at,
+            }
+        })
+        .collect())
+}
+
+/// The MR's timeline, oldest first. GitLab records one event per label, so
+/// labels changed together (same user, same second) are shown as one entry.
+pub async fn timeline(pool: &DbPool, mr_id: i64) -> Result<Vec<MrEvent>, AppError> {
+    let mut events = mr_events::list_events(pool, mr_id).await?;
+    events.dedup_by(|next, prev| {
+        let same_change = next.source == LABEL
+            && prev.source == LABEL
+            && next.kind == prev.kind
+            && next.actor_username == prev.actor_username
+            && next.created_at == prev.created_at;
+        if same_change {
+            prev.subjects.append(&mut next.subjects);
+        }
+        same_change
+    });
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+    use crate::services::gitlab_client::GitLabEventLabel;
+
+    fn parsed(body: &str) -> (EventKind, Vec<String>, Option<String>) {
+        let note = parse_system_note(body);
+        (note.kind, note.subjects, note.detail)
+    }
+
+    #[test]
+    fn system_notes_are_parsed() {
+        assert_eq!(
+            parsed("added 3 commits\n\n<ul><li>abc - Fix</li></ul>"),
+            (EventKind::Pushed, vec![], Some("3".to_string()))
+        );
+        assert_eq!(parsed("added 1 commit").0, EventKind::Pushed);
+        assert_eq!(
+            parsed("force-pushed the source branch").0,
+            EventKind::ForcePushed
+        );
+        assert_eq!(
+            parsed("requested review from @alice, @bob and @carol"),
+            (
+                EventKind::ReviewRequested,
+                vec!["alice".into(), "bob".into(), "carol".into()],
+                None
+            )
+        );
+        assert_eq!(
+            parsed("assigned to @alice and unassigned @bob"),
+            (EventKind::Assigned, vec!["alice".into()], None)
+        );
+        assert_eq!(
+            parsed("removed review request for @dave").1,
+            vec!["dave".to_string()]
+        );
+        assert_eq!(
+            parsed("marked this merge request as **draft**").0,
+            EventKind::MarkedDraft
+        );
+        assert_eq!(
+            parsed("marked this merge request as **ready**").0,
+            EventKind::MarkedReady
+        );
+        assert_eq!(
+            parsed("changed title from **{-Draft: -}Add parser** to **Add {+the +}parser**"),
+            (
+                EventKind::TitleChanged,
+                vec![],
+                Some("Add the parser".to_string())
+            )
+        );
+        assert_eq!(
+            parsed(r#"added ~bug ~"needs review" labels"#),
+            (
+                EventKind::LabelsAdded,
+                vec!["bug".into(), "needs review".into()],
+                None
+            )
+        );
+        assert_eq!(
+            parsed("changed milestone to %\"v1.2\"").1,
+            vec!["v1.2".to_string()]
+        );
+        assert_eq!(parsed("merged").0, EventKind::Merged);
+        assert_eq!(
+            parsed("mentioned in issue #12"),
+            (
+                EventKind::Other,
+                vec![],
+                Some("mentioned in issue #12".to_string())
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn labels_changed_together_are_one_entry() {
+        let (pool, inst) = seed_instance(true).await;
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, project_name, title, author_username,
+              source_branch, target_branch, state, web_url, created_at, updated_at,
+              labels, reviewers, cached_at)
+             VALUES (1, ?, 1, 10, 'g/p', 'MR', 'alice', 's', 'main', 'opened',
+                     'http://x', 0, 0, '[]', '[]', 0)",
+        )
+        .bind(inst)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO comments (id, mr_id, discussion_id, author_username, body,
+                                   resolved, resolvable, system, created_at, updated_at,
+                                   cached_at, is_local)
+             VALUES (7, 1, 'd', 'bob', 'added 2 commits', 0, 0, 1, 50, 50, 0, 0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let label = |id: i64, name: &str, at: &str| GitLabLabelEvent {
+            id,
+            user: None,
+            created_at: at.to_string(),
+            label: Some(GitLabEventLabel {
+                name: name.to_string(),
+            }),
+            action: "add".to_string(),
+        };
+        let labels = from_label_events(&[
+            label(1, "bug", "1970-01-01T00:01:40Z"),
+            label(2, "ui", "1970-01-01T00:01:40Z"),
+            label(3, "docs", "1970-01-01T00:03:20Z"),
+        ]);
+        mr_events::replace_events(&pool, 1, LABEL, &labels)
+            .await
+            .unwrap();
+        let notes = note_events(&pool, 1).await.unwrap();
+        mr_events::replace_events(&pool, 1, NOTE, &notes)
+            .await
+            .unwrap();
+
+        let events = timeline(&pool, 1).await.unwrap();
+        let entries: Vec<_> = events
+            .iter()
+            .map(|e| (e.created_at, e.kind, e.subjects.len()))
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                (50, EventKind::Pushed, 0),
+                (100, EventKind::LabelsAdded, 2),
+                (200, EventKind::LabelsAdded, 1),
+            ]
+        );
+    }
+}
@@ -0,0 +1,224 @@
+//! Commit-by-commit review of an MR.
+//!
+//! The sync keeps each MR's commit list in `mr_commits`. A commit's diff
+//! against its first parent is fetched the first time the commit is opened
+//! and cached per `(project, sha)`: it can't change, and MRs that share a
+//! commit share the row. Cached diffs are pruned once no cached MR contains
+//! the commit.
+
+use crate::core::create_client;
+use crate::db::mr_commits::{self, MrCommit};
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use crate::services::gitlab_client::{GitLabCommit, GitLabFileDiff};
+use serde::{Deserialize, Serialize};
+
+/// One file changed by a commit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitDiffFile {
+    pub old_path: String,
+    pub new_path: String,
+    /// `added`, `modified`, `deleted` or `renamed`, as for MR diff files.
+    pub change_type: String,
+    pub additions: i64,
+    pub deletions: i64,
+    /// Unified diff of the file.
+    pub diff: String,
+}
+
+/// The changes one commit of an MR made.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitDiff {
+    pub commit: MrCommit,
+    pub files: Vec<CommitDiffFile>,
+}
+
+/// Turn GitLab's commit list (newest first) into rows, oldest first.
+pub fn to_commits(commits: Vec<GitLabCommit>) -> Vec<MrCommit> {
+    commits
+        .into_iter()
+        .rev()
+        .map(|commit| MrCommit {
+            title: if commit.title.is_empty() {
+                commit
+                    .message
+                    .lines()
+                    .next()
+                    .unwrap_or_default()
+                    .to_string()
+            } else {
+                commit.title
+            },
+            authored_at: chrono::DateTime::parse_from_rfc3339(&commit.authored_date)
+                .map(|d| d.timestamp())
+                .unwrap_or(0),
+            sha: commit.id,
+            short_sha: commit.short_id,
+            message: commit.message,
+            author_name: commit.author_name,
+            author_email: commit.author_email,
+            web_url: commit.web_url,
+        })
+        .collect()
+}
+
+pub(crate) fn to_files(diffs: Vec<GitLabFileDiff>) -> Vec<CommitDiffFile> {
+    diffs
+        .into_iter()
+        .map(|file| {
+            let change_type = if file.new_file {
+                "added"
+            } else if file.deleted_file {
+                "deleted"
+            } else if file.renamed_file {
+                "renamed"
+            } else {
+                "modified"
+            };
+            let (mut additions, mut deletions) = (0, 0);
+            for line in file.diff.lines() {
+                if line.starts_with('+') && !line.starts_with("+++") {
+                    additions += 1;
+                } else if line.starts_with('-') && !line.starts_with("---") {
+                    deletions += 1;
+                }
+            }
+            CommitDiffFile {
+                old_path: file.old_path,
+                new_path: file.new_path,
+                change_type: change_type.to_string(),
+                additions,
+                deletions,
+                diff: file.diff,
+            }
+        })
+        .collect()
+}
+
+/// The diff of commit `sha` of the MR, from the cache or GitLab.
+pub async fn commit_diff(
+    pool: &DbPool,
+    mr_id: i64,
+    sha: &str,
+    now: i64,
+) -> Result<CommitDiff, AppError> {
+    let (instance_id, project_id): (i64, i64) =
+        sqlx::query_as("SELECT instance_id, project_id FROM merge_requests WHERE id = ?")
+            .bind(mr_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::not_found_with_id("MergeRequest", mr_id.to_string()))?;
+    let commit = mr_commits::list_commits(pool, mr_id)
+        .await?
+        .into_iter()
+        .find(|c| c.sha == sha)
+        .ok_or_else(|| AppError::not_found_with_id("Commit", sha.to_string()))?;
+
+    let cached = mr_commits::get_commit_diff(pool, instance_id, project_id, sha).await?;
+    let files = match cached {
+        Some(json) => serde_json::from_str(&json)
+            .map_err(|e| AppError::internal(format!("Corrupt cached commit diff: {}", e)))?,
+        None => {
+            let client = create_client(pool, instance_id).await?;
+            let files = to_files(client.get_commit_diff(project_id, sha).await?);
+            let json = serde_json::to_string(&files)
+                .map_err(|e| AppError::internal(format!("Failed to encode commit diff: {}", e)))?;
+            mr_commits::save_commit_diff(pool, instance_id, project_id, sha, &json, now).await?;
+            files
+        }
+    };
+
+    Ok(CommitDiff { commit, files })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+
+    fn commit(sha: &str, title: &str, message: &str) -> GitLabCommit {
+        GitLabCommit {
+            id: sha.to_string(),
+            short_id: sha[..2].to_string(),
+            title: title.to_string(),
+            message: message.to_string(),
+            author_name: "Alice".to_string(),
+            author_email: "alice@example.com".to_string(),
+            authored_date: "2024-05-01T12:00:00.000+02:00".to_string(),
+            web_url: String::new(),
+        }
+    }
+
+    #[test]
+    fn commits_are_stored_oldest_first() {
+        let commits = to_commits(vec![
+            commit("b2b2", "Fix typo", "Fix typo\n"),
+            commit("a1a1", "", "Add parser\n\nLong body."),
+        ]);
+        let titles: Vec<_> = commits
+            .iter()
+            .map(|c| (c.sha.as_str(), c.title.as_str()))
+            .collect();
+        assert_eq!(titles, vec![("a1a1", "Add parser"), ("b2b2", "Fix typo")]);
+        assert_eq!(commits[0].authored_at, 1_714_557_600);
+    }
+
+    #[test]
+    fn files_are_counted_like_mr_diff_files() {
+        let files = to_files(vec![GitLabFileDiff {
+            old_path: "a.rs".to_string(),
+            new_path: "b.rs".to_string(),
+            new_file: false,
+            renamed_file: true,
+            deleted_file: false,
+            diff: "@@ -1,2 +1,2 @@\n-old\n+new\n+more\n ctx\n".to_string(),
+        }]);
+        assert_eq!(files[0].change_type, "renamed");
+        assert_eq!((files[0].additions, files[0].deletions), (2, 1));
+    }
+
+    #[tokio::test]
+    async fn cached_commit_diff_is_served_for_commits_of_the_mr() {
+        let (pool, inst) = seed_instance(true).await;
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, project_name, title, author_username,
+              source_branch, target_branch, state, web_url, created_at, updated_at,
+              labels, reviewers, cached_at)
+             VALUES (1, ?, 1, 10, 'g/p', 'MR', 'alice', 's', 'main', 'opened',
+                     'http://x', 0, 0, '[]', '[]', 0)",
+        )
+        .bind(inst)
+        .execute(&pool)
+        .await
+        .unwrap();
+        let commits = to_commits(vec![commit("a1a1", "Add parser", "Add parser")]);
+        mr_commits::replace_commits(&pool, 1, &commits)
+            .await
+            .unwrap();
+        let files = vec![CommitDiffFile {
+            old_path: "src/lib.rs".to_string(),
+            new_path: "src/lib.rs".to_string(),
+            change_type: "modified".to_string(),
+            additions: 1,
+            deletions: 0,
+            diff: "@@ -1 +1,2 @@\n a\n+b\n".to_string(),
+        }];
+        let json = serde_json::to_string(&files).unwrap();
+        mr_commits::save_commit_diff(&pool, inst, 10, "a1a1", &json, 100)
+            .await
+            .unwrap();
+
+        let diff = commit_diff(&pool, 1, "a1a1", 200).await.unwrap();
+        assert_eq!(diff.commit, commits[0]);
+        assert_eq!(diff.files, files);
+        assert!(commit_diff(&pool, 1, "ffff", 200).await.is_err());
+
+        // Pruned once no MR contains the commit.
+        assert_eq!(mr_commits::prune_commit_diffs(&pool).await.unwrap(), 0);
+        mr_commits::replace_commits(&pool, 1, &[]).await.unwrap();
+        assert_eq!(mr_commits::prune_commit_diffs(&pool).await.unwrap(), 1);
+    }
+}
@@ -0,0 +1,385 @@
+//! Structured comparison of two open MRs in the same project.
+//!
+//! Works on the cached diffs: files both MRs touch are reported, and for each
+//! of them the hunks whose changed lines overlap (or sit directly next to each
+//! other) on the target branch. Those are the places git is likely to report
+//! a conflict once one of the MRs merges. When the MRs branch off different
+//! base commits the line numbers are only approximate; `same_base` says so.
+
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Inclusive range of target-branch (old side) line numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LineRange {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl LineRange {
+    /// Whether the ranges overlap or touch. Git also refuses to merge
+    /// changes to adjacent lines, so touching counts.
+    fn collides_with(&self, other: &LineRange) -> bool {
+        self.start <= other.end + 1 && other.start <= self.end + 1
+    }
+}
+
+/// A pair of hunks, one from each MR, that change the same region.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConflictingHunks {
+    pub range_a: LineRange,
+    pub range_b: LineRange,
+}
+
+/// A file changed by both MRs.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverlappingFile {
+    /// Path on the target branch (the old path for renames).
+    pub path: String,
+    pub change_type_a: String,
+    pub change_type_b: String,
+    /// False when either MR's diff for this file isn't cached, so hunks
+    /// couldn't be compared.
+    pub diff_available: bool,
+    pub conflicts: Vec<ConflictingHunks>,
+}
+
+/// The bits of each MR the comparison is shown with.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComparedMr {
+    pub id: i64,
+    pub iid: i64,
+    pub title: String,
+    pub source_branch: String,
+    pub target_branch: String,
+    pub base_sha: String,
+}
+
+/// Result of `compare_mrs`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MrComparison {
+    pub mr_a: ComparedMr,
+    pub mr_b: ComparedMr,
+    /// Both diffs were computed against the same base commit, so hunk line
+    /// numbers are directly comparable.
+    pub same_base: bool,
+    pub overlapping_files: Vec<OverlappingFile>,
+}
+
+impl MrComparison {
+    /// Whether any overlapping file has colliding hunks.
+    pub fn has_conflicts(&self) -> bool {
+        self.overlapping_files
+            .iter()
+            .any(|f| !f.conflicts.is_empty())
+    }
+}
+
+/// Old-side ranges of the lines each hunk in `diff` changes, ignoring
+/// context lines. A pure insertion is placed at the line it follows.
+pub fn changed_ranges(diff: &str) -> Vec<LineRange> {
+    let mut ranges = Vec::new();
+    let mut old_line = 0i64;
+    let mut current: Option<LineRange> = None;
+
+    fn extend(current: &mut Option<LineRange>, line: i64) {
+        *current = Some(match current.take() {
+            Some(r) => LineRange {
+                start: r.start.min(line),
+                end: r.end.max(line),
+            },
+            None => LineRange {
+                start: line,
+                end: line,
+            },
+        });
+    }
+
+    for line in diff.lines() {
+        if let Some(header) = line.strip_prefix("@@ -") {
+            ranges.extend(current.take());
+            let old = header.split_whitespace().next().unwrap_or_default();
+            old_line = old
+                .split(',')
+                .next()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(0);
+            // "-0,0" (new file) starts before line 1
+            if old.ends_with(",0") {
+                old_line += 1;
+            }
+        } else if line.starts_with('-') {
+            extend(&mut current, old_line);
+            old_line += 1;
+        } else if line.starts_with('+') {
+            extend(&mut current, (old_line - 1).max(0));
+        } else if line.starts_with(' ') {
+            // Context between two changes ends the changed region.
+            ranges.extend(current.take());
+            old_line += 1;
+        }
+    }
+    ranges.extend(current);
+    ranges
+}
+
+/// Pairs of changed ranges from the two diffs that collide.
+pub fn conflicting_hunks(diff_a: &str, diff_b: &str) -> Vec<ConflictingHunks> {
+    let ranges_b = changed_ranges(diff_b);
+    changed_ranges(diff_a)
+        .into_iter()
+        .flat_map(|a| {
+            ranges_b
+                .iter()
+                .filter(move |b| a.collides_with(b))
+                .map(move |b| ConflictingHunks {
+                    range_a: a,
+                    range_b: *b,
+                })
+        })
+        .collect()
+}
+
+#[derive(sqlx::FromRow)]
+struct MrRow {
+    id: i64,
+    iid: i64,
+    instance_id: i64,
+    project_id: i64,
+    title: String,
+    source_branch: String,
+    target_branch: String,
+    state: String,
+}
+
+#[derive(sqlx::FromRow)]
+struct FileRow {
+    old_path: Option<String>,
+    new_path: String,
+    change_type: String,
+    diff_content: Option<String>,
+}
+
+async fn load_mr(pool: &DbPool, mr_id: i64) -> Result<(MrRow, String), AppError> {
+    let mr: MrRow = sqlx::query_as(
+        "SELECT id, iid, instance_id, project_id, title, source_branch, target_branch, state
+         FROM merge_requests WHERE id = ?",
+    )
+    .bind(mr_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::not_found_with_id("MergeRequest", mr_id.to_string()))?;
+    if mr.state != "opened" {
+        return Err(AppError::invalid_input(format!(
+            "!{} is {}, only open MRs can be compared",
+            mr.iid, mr.state
+        )));
+    }
+    let base_sha: String = sqlx::query_scalar("SELECT base_sha FROM diffs WHERE mr_id = ?")
+        .bind(mr_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| {
+            AppError::invalid_input(format!(
+                "The diff of !{} isn't cached yet; open the MR first",
+                mr.iid
+            ))
+        })?;
+    Ok((mr, base_sha))
+}
+
+/// Files of an MR keyed by their path on the target branch.
+async fn load_files(pool: &DbPool, mr_id: i64) -> Result<HashMap<String, FileRow>, AppError> {
+    let rows: Vec<FileRow> = sqlx::query_as(
+        "SELECT old_path, new_path, change_type, diff_content
+         FROM diff_files WHERE mr_id = ? ORDER BY file_position",
+    )
+    .bind(mr_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|f| (f.old_path.clone().unwrap_or_else(|| f.new_path.clone()), f))
+        .collect())
+}
+
+/// Compare two open MRs of the same project.
+pub async fn compare_mrs(
+    pool: &DbPool,
+    mr_id_a: i64,
+    mr_id_b: i64,
+) -> Result<MrComparison, AppError> {
+    if mr_id_a == mr_id_b {
+        return Err(AppError::invalid_input("Pick two different merge requests"));
+    }
+    let (a, base_a) = load_mr(pool, mr_id_a).await?;
+    let (b, base_b) = load_mr(pool, mr_id_b).await?;
+    if a.instance_id != b.instance_id || a.project_id != b.project_id {
+        return Err(AppError::invalid_input(
+            "Only merge requests of the same project can be compared",
+        ));
+    }
+
+    let files_a = load_files(pool, a.id).await?;
+    let mut files_b = load_files(pool, b.id).await?;
+    let mut overlapping_files: Vec<OverlappingFile> = files_a
+        .into_iter()
+        .filter_map(|(path, fa)| {
+            let fb = files_b.remove(&path)?;
+            let (conflicts, diff_available) = match (&fa.diff_content, &fb.diff_content) {
+                (Some(da), Some(db)) => (conflicting_hunks(da, db), true),
+                _ => (Vec::new(), false),
+            };
+            Some(OverlappingFile {
+                path,
+                change_type_a: fa.change_type,
+                change_type_b: fb.change_type,
+                diff_available,
+                conflicts,
+            })
+        })
+        .collect();
+    // Files with colliding hunks first.
+    overlapping_files
+        .sort_by(|x, y| (x.conflicts.is_empty(), &x.path).cmp(&(y.conflicts.is_empty(), &y.path)));
+
+    let compared = |mr: MrRow, base_sha: String| ComparedMr {
+        id: mr.id,
+        iid: mr.iid,
+        title: mr.title,
+        source_branch: mr.source_branch,
+        target_branch: mr.target_branch,
+        base_sha,
+    };
+    Ok(MrComparison {
+        same_base: base_a == base_b,
+        mr_a: compared(a, base_a),
+        mr_b: compared(b, base_b),
+        overlapping_files,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+
+    fn range(start: i64, end: i64) -> LineRange {
+        LineRange { start, end }
+    }
+
+    #[test]
+    fn changed_ranges_skip_context() {
+        let diff = "@@ -10,6 +10,6 @@\n ctx\n-old\n+new\n ctx\n ctx\n-gone\n ctx\n";
+        assert_eq!(changed_ranges(diff), vec![range(11, 11), range(14, 14)]);
+
+        // Pure insertion after line 3; new file inserts at line 0.
+        let insert = "@@ -3,1 +3,3 @@\n ctx\n+a\n+b\n";
+        assert_eq!(changed_ranges(insert), vec![range(3, 3)]);
+        assert_eq!(
+            changed_ranges("@@ -0,0 +1,2 @@\n+a\n+b\n"),
+            vec![range(0, 0)]
+        );
+    }
+
+    #[test]
+    fn adjacent_and_overlapping_hunks_conflict() {
+        let a = "@@ -10,3 +10,3 @@\n ctx\n-x\n+y\n ctx\n";
+        let touching = "@@ -12,2 +12,2 @@\n-z\n+w\n ctx\n";
+        let far = "@@ -40,2 +40,2 @@\n-z\n+w\n ctx\n";
+        assert_eq!(
+            conflicting_hunks(a, touching),
+            vec![ConflictingHunks {
+                range_a: range(11, 11),
+                range_b: range(12, 12),
+            }]
+        );
+        assert!(conflicting_hunks(a, far).is_empty());
+    }
+
+    async fn seed_mr(pool: &DbPool, inst: i64, id: i64, project_id: i64, files: &[(&str, &str)]) {
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, project_name, title, author_username,
+              source_branch, target_branch, state, web_url, created_at, updated_at,
+              labels, reviewers, cached_at)
+             VALUES (?, ?, ?, ?, 'g/p', 't', 'me', 's', 'main', 'opened', 'http://x', 0, 0, '[]', '[]', 0)",
+        )
+        .bind(id)
+        .bind(inst)
+        .bind(id)
+        .bind(project_id)
+        .execute(pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO diffs (mr_id, content, base_sha, head_sha, start_sha, file_count, additions, deletions)
+             VALUES (?, '', 'base', 'head', 'base', 0, 0, 0)",
+        )
+        .bind(id)
+        .execute(pool)
+        .await
+        .unwrap();
+        for (pos, (path, diff)) in files.iter().enumerate() {
+            sqlx::query(
+                "INSERT INTO diff_files (mr_id, new_path, change_type, additions, deletions, file_position, diff_content)
+                 VALUES (?, ?, 'modified', 1, 1, ?, ?)",
+            )
+            .bind(id)
+            .bind(path)
+            .bind(pos as i64)
+            .bind(diff)
+            .execute(pool)
+            .await
+            .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn compare_reports_shared_files_and_conflicts() {
+        let (pool, inst) = seed_instance(true).await;
+        let hunk = "@@ -5,1 +5,1 @@\n-a\n+b\n";
+        seed_mr(
+            &pool,
+            inst,
+            1,
+            10,
+            &[("src/lib.rs", hunk), ("README.md", hunk)],
+        )
+        .await;
+        seed_mr(
+            &pool,
+            inst,
+            2,
+            10,
+            &[
+                ("README.md", "@@ -50,1 +50,1 @@\n-a\n+b\n"),
+                ("src/lib.rs", hunk),
+            ],
+        )
+        .await;
+        seed_mr(&pool, inst, 3, 11, &[("src/lib.rs", hunk)]).await;
+
+        let cmp = compare_mrs(&pool, 1, 2).await.unwrap();
+        assert!(cmp.same_base);
+        assert!(cmp.has_conflicts());
+        let paths: Vec<&str> = cmp
+            .overlapping_files
+            .iter()
+            .map(|f| f.path.as_str())
+            .collect();
+        assert_eq!(paths, vec!["src/lib.rs", "README.md"]);
+        assert_eq!(cmp.overlapping_files[0].conflicts.len(), 1);
+        assert!(cmp.overlapping_files[1].conflicts.is_empty());
+
+        assert!(compare_mrs(&pool, 1, 3).await.is_err());
+        assert!(compare_mrs(&pool, 1, 1).await.is_err());
+    }
+}
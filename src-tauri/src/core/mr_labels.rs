@@ -0,0 +1,239 @@
+//! MR labels.
+//!
+//! Sync caches the labels of every project with open MRs so they can be
+//! picked offline. Adding or removing a label updates the cached MR right
+//! away and queues the change; until it's pushed, sync keeps the local
+//! labels instead of GitLab's.
+
+use crate::core::token_scopes;
+use crate::db::pool::DbPool;
+use crate::db::project_labels::{self, ProjectLabelRow};
+use crate::error::AppError;
+use crate::models::sync_action::ActionType;
+use crate::services::gitlab_client::GitLabClient;
+use crate::services::sync_queue::{self, EnqueueInput, UpdateLabelsPayload};
+use serde::Serialize;
+
+/// How long a project's cached labels are used before sync fetches them
+/// again (24 hours).
+pub const LABELS_TTL_SECS: i64 = 24 * 60 * 60;
+
+/// A label that can be applied in a project.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectLabel {
+    pub name: String,
+    pub color: String,
+    pub text_color: Option<String>,
+    pub description: Option<String>,
+}
+
+impl From<ProjectLabelRow> for ProjectLabel {
+    fn from(row: ProjectLabelRow) -> Self {
+        Self {
+            name: row.name,
+            color: row.color,
+            text_color: row.text_color,
+            description: row.description,
+        }
+    }
+}
+
+/// Cached labels of a project, by name.
+pub async fn project_labels(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<Vec<ProjectLabel>, AppError> {
+    Ok(project_labels::list_labels(pool, instance_id, project_id)
+        .await?
+        .into_iter()
+        .map(ProjectLabel::from)
+        .collect())
+}
+
+/// Fetch a project's labels unless they were fetched within
+/// [`LABELS_TTL_SECS`]. Returns whether they were fetched.
+pub async fn refresh_labels_if_stale(
+    pool: &DbPool,
+    client: &GitLabClient,
+    instance_id: i64,
+    project_id: i64,
+    now: i64,
+) -> Result<bool, AppError> {
+    if let Some(fetched_at) = project_labels::fetched_at(pool, instance_id, project_id).await? {
+        if now - fetched_at < LABELS_TTL_SECS {
+            return Ok(false);
+        }
+    }
+    let labels: Vec<ProjectLabelRow> = client
+        .list_project_labels(project_id)
+        .await?
+        .into_iter()
+        .map(|label| ProjectLabelRow {
+            name: label.name,
+            color: label.color,
+            text_color: label.text_color,
+            description: label.description,
+        })
+        .collect();
+    project_labels::replace_labels(pool, instance_id, project_id, &labels, now).await?;
+    Ok(true)
+}
+
+/// Add (`add = true`) or remove a label on an MR locally and queue the
+/// change. Returns the MR's labels afterwards; nothing is queued when the
+/// label was already there (or already gone).
+pub async fn set_mr_label(
+    pool: &DbPool,
+    mr_id: i64,
+    label: &str,
+    add: bool,
+) -> Result<Vec<String>, AppError> {
+    let label = label.trim();
+    if label.is_empty() {
+        return Err(AppError::invalid_input_field(
+            "Label must not be empty",
+            "label",
+        ));
+    }
+    let (project_id, iid, labels_json): (i64, i64, String) =
+        sqlx::query_as("SELECT project_id, iid, labels FROM merge_requests WHERE id = ?")
+            .bind(mr_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::not_found_with_id("MergeRequest", mr_id.to_string()))?;
+    token_scopes::ensure_can_write(pool, mr_id).await?;
+
+    let mut labels: Vec<String> = serde_json::from_str(&labels_json).unwrap_or_default();
+    let present = labels.iter().any(|l| l == label);
+    if present == add {
+        return Ok(labels);
+    }
+    if add {
+        labels.push(label.to_string());
+    } else {
+        labels.retain(|l| l != label);
+    }
+
+    sqlx::query("UPDATE merge_requests SET labels = ? WHERE id = ?")
+        .bind(serde_json::to_string(&labels)?)
+        .bind(mr_id)
+        .execute(pool)
+        .await?;
+
+    let (added, removed) = if add {
+        (vec![label.to_string()], Vec::new())
+    } else {
+        (Vec::new(), vec![label.to_string()])
+    };
+    let payload = serde_json::to_string(&UpdateLabelsPayload {
+        project_id,
+        mr_iid: iid,
+        add: added,
+        remove: removed,
+    })?;
+    sync_queue::enqueue_action(
+        pool,
+        EnqueueInput {
+            mr_id,
+            action_type: ActionType::UpdateLabels,
+            payload,
+            local_reference_id: None,
+        },
+    )
+    .await?;
+
+    Ok(labels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+
+    async fn seed_mr(pool: &DbPool, inst: i64) {
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, title, author_username, source_branch,
+              target_branch, state, web_url, created_at, updated_at, labels)
+             VALUES (1, ?, 7, 100, 'Add cache', 'alice', 'f', 'main', 'opened', 'w', 0, 0, '[\"bug\"]')",
+        )
+        .bind(inst)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn label_changes_apply_locally_and_queue() {
+        let (pool, inst) = seed_instance(true).await;
+        seed_mr(&pool, inst).await;
+
+        let labels = set_mr_label(&pool, 1, " backend ", true).await.unwrap();
+        assert_eq!(labels, vec!["bug", "backend"]);
+        let labels = set_mr_label(&pool, 1, "bug", false).await.unwrap();
+        assert_eq!(labels, vec!["backend"]);
+        // No-ops queue nothing.
+        set_mr_label(&pool, 1, "backend", true).await.unwrap();
+        set_mr_label(&pool, 1, "bug", false).await.unwrap();
+
+        let stored: String = sqlx::query_scalar("SELECT labels FROM merge_requests WHERE id = 1")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(stored, r#"["backend"]"#);
+
+        let queued = sync_queue::get_actions_for_mr(&pool, 1).await.unwrap();
+        assert_eq!(queued.len(), 2);
+        assert!(queued.iter().all(|a| a.action_type == "updatelabels"));
+        let payloads: Vec<UpdateLabelsPayload> = queued
+            .iter()
+            .map(|a| serde_json::from_str(&a.payload).unwrap())
+            .collect();
+        assert!(payloads
+            .iter()
+            .any(|p| p.add == ["backend"] && p.remove.is_empty()));
+        assert!(payloads
+            .iter()
+            .any(|p| p.add.is_empty() && p.remove == ["bug"]));
+        assert!(payloads
+            .iter()
+            .all(|p| p.project_id == 100 && p.mr_iid == 7));
+
+        assert!(set_mr_label(&pool, 1, "  ", true).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn cached_labels_are_sorted_by_name() {
+        let (pool, inst) = seed_instance(true).await;
+        let row = |name: &str| ProjectLabelRow {
+            name: name.into(),
+            color: "#428BCA".into(),
+            text_color: Some("#FFFFFF".into()),
+            description: None,
+        };
+        project_labels::replace_labels(&pool, inst, 100, &[row("frontend"), row("Backend")], 10)
+            .await
+            .unwrap();
+        project_labels::replace_labels(&pool, inst, 100, &[row("frontend"), row("Backend")], 20)
+            .await
+            .unwrap();
+
+        let names: Vec<String> = project_labels(&pool, inst, 100)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|l| l.name)
+            .collect();
+        assert_eq!(names, vec!["Backend", "frontend"]);
+        assert_eq!(
+            project_labels::fetched_at(&pool, inst, 100).await.unwrap(),
+            Some(20)
+        );
+        assert_eq!(
+            project_labels::fetched_at(&pool, inst, 200).await.unwrap(),
+            None
+        );
+    }
+}
@@ -0,0 +1,249 @@
+//! MR milestones.
+//!
+//! Sync caches the active milestones of every project with open MRs, group
+//! milestones included, so MRs can be triaged offline. Setting an MR's
+//! milestone updates the cached MR right away and queues the change; until
+//! it's pushed, sync keeps the local milestone instead of GitLab's.
+
+use crate::core::token_scopes;
+use crate::db::pool::DbPool;
+use crate::db::project_milestones::{self, ProjectMilestoneRow};
+use crate::error::AppError;
+use crate::models::sync_action::ActionType;
+use crate::services::gitlab_client::GitLabClient;
+use crate::services::sync_queue::{self, EnqueueInput, SetMilestonePayload};
+use serde::Serialize;
+
+/// How long a project's cached milestones are used before sync fetches them
+/// again (6 hours; milestones come and go more often than labels).
+pub const MILESTONES_TTL_SECS: i64 = 6 * 60 * 60;
+
+/// A milestone an MR can be put in.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectMilestone {
+    pub id: i64,
+    pub title: String,
+    /// `YYYY-MM-DD`.
+    pub due_date: Option<String>,
+}
+
+impl From<ProjectMilestoneRow> for ProjectMilestone {
+    fn from(row: ProjectMilestoneRow) -> Self {
+        Self {
+            id: row.milestone_id,
+            title: row.title,
+            due_date: row.due_date,
+        }
+    }
+}
+
+/// Cached active milestones of a project, soonest due first.
+pub async fn project_milestones(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<Vec<ProjectMilestone>, AppError> {
+    Ok(
+        project_milestones::list_milestones(pool, instance_id, project_id)
+            .await?
+            .into_iter()
+            .map(ProjectMilestone::from)
+            .collect(),
+    )
+}
+
+/// Fetch a project's milestones unless they were fetched within
+/// [`MILESTONES_TTL_SECS`]. Returns whether they were fetched.
+pub async fn refresh_milestones_if_stale(
+    pool: &DbPool,
+    client: &GitLabClient,
+    instance_id: i64,
+    project_id: i64,
+    now: i64,
+) -> Result<bool, AppError> {
+    if let Some(fetched_at) = project_milestones::fetched_at(pool, instance_id, project_id).await? {
+        if now - fetched_at < MILESTONES_TTL_SECS {
+            return Ok(false);
+        }
+    }
+    let milestones: Vec<ProjectMilestoneRow> = client
+        .list_project_milestones(project_id)
+        .await?
+        .into_iter()
+        .map(|milestone| ProjectMilestoneRow {
+            milestone_id: milestone.id,
+            title: milestone.title,
+            due_date: milestone.due_date,
+        })
+        .collect();
+    project_milestones::replace_milestones(pool, instance_id, project_id, &milestones, now).await?;
+    Ok(true)
+}
+
+/// Put an MR in a milestone (`None` takes it out) locally and queue the
+/// change. Returns the MR's milestone afterwards; nothing is queued when it
+/// didn't change.
+///
+/// The milestone must be one of the project's cached milestones.
+pub async fn set_mr_milestone(
+    pool: &DbPool,
+    mr_id: i64,
+    milestone_id: Option<i64>,
+) -> Result<Option<ProjectMilestone>, AppError> {
+    let (instance_id, project_id, iid, current): (i64, i64, i64, Option<i64>) = sqlx::query_as(
+        "SELECT instance_id, project_id, iid, milestone_id FROM merge_requests WHERE id = ?",
+    )
+    .bind(mr_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::not_found_with_id("MergeRequest", mr_id.to_string()))?;
+    token_scopes::ensure_can_write(pool, mr_id).await?;
+
+    let milestone = match milestone_id {
+        Some(id) => Some(
+            project_milestones::get_milestone(pool, instance_id, project_id, id)
+                .await?
+                .map(ProjectMilestone::from)
+                .ok_or_else(|| {
+                    AppError::invalid_input_field(
+                        format!("Unknown milestone: {}", id),
+                        "milestoneId",
+                    )
+                })?,
+        ),
+        None => None,
+    };
+    if current == milestone_id {
+        return Ok(milestone);
+    }
+
+    sqlx::query(
+        "UPDATE merge_requests
+         SET milestone_id = ?, milestone_title = ?, milestone_due_date = ?
+         WHERE id = ?",
+    )
+    .bind(milestone_id)
+    .bind(milestone.as_ref().map(|m| m.title.as_str()))
+    .bind(milestone.as_ref().and_then(|m| m.due_date.as_deref()))
+    .bind(mr_id)
+    .execute(pool)
+    .await?;
+
+    let payload = serde_json::to_string(&SetMilestonePayload {
+        project_id,
+        mr_iid: iid,
+        milestone_id,
+    })?;
+    sync_queue::enqueue_action(
+        pool,
+        EnqueueInput {
+            mr_id,
+            action_type: ActionType::SetMilestone,
+            payload,
+            local_reference_id: None,
+        },
+    )
+    .await?;
+
+    Ok(milestone)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+
+    fn row(id: i64, title: &str, due_date: Option<&str>) -> ProjectMilestoneRow {
+        ProjectMilestoneRow {
+            milestone_id: id,
+            title: title.into(),
+            due_date: due_date.map(String::from),
+        }
+    }
+
+    #[tokio::test]
+    async fn cached_milestones_are_sorted_by_due_date() {
+        let (pool, inst) = seed_instance(true).await;
+        project_milestones::replace_milestones(
+            &pool,
+            inst,
+            100,
+            &[
+                row(1, "Backlog", None),
+                row(2, "v2.0", Some("2026-12-01")),
+                row(3, "v1.9", Some("2026-11-01")),
+            ],
+            10,
+        )
+        .await
+        .unwrap();
+
+        let titles: Vec<String> = project_milestones(&pool, inst, 100)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|m| m.title)
+            .collect();
+        assert_eq!(titles, vec!["v1.9", "v2.0", "Backlog"]);
+        assert!(project_milestones(&pool, inst, 200)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn setting_a_milestone_applies_locally_and_queues() {
+        let (pool, inst) = seed_instance(true).await;
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, title, author_username, source_branch,
+              target_branch, state, web_url, created_at, updated_at)
+             VALUES (1, ?, 7, 100, 'Add cache', 'alice', 'f', 'main', 'opened', 'w', 0, 0)",
+        )
+        .bind(inst)
+        .execute(&pool)
+        .await
+        .unwrap();
+        project_milestones::replace_milestones(
+            &pool,
+            inst,
+            100,
+            &[row(3, "v1.9", Some("2026-11-01"))],
+            10,
+        )
+        .await
+        .unwrap();
+
+        let milestone = set_mr_milestone(&pool, 1, Some(3)).await.unwrap().unwrap();
+        assert_eq!(milestone.title, "v1.9");
+        let stored: (Option<i64>, Option<String>, Option<String>) = sqlx::query_as(
+            "SELECT milestone_id, milestone_title, milestone_due_date FROM merge_requests WHERE id = 1",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            stored,
+            (Some(3), Some("v1.9".into()), Some("2026-11-01".into()))
+        );
+
+        // Unchanged milestones queue nothing; unknown ones are refused.
+        set_mr_milestone(&pool, 1, Some(3)).await.unwrap();
+        assert!(set_mr_milestone(&pool, 1, Some(9)).await.is_err());
+        assert_eq!(set_mr_milestone(&pool, 1, None).await.unwrap(), None);
+
+        let queued = sync_queue::get_actions_for_mr(&pool, 1).await.unwrap();
+        assert_eq!(queued.len(), 2);
+        assert!(queued.iter().all(|a| a.action_type == "setmilestone"));
+        let payloads: Vec<SetMilestonePayload> = queued
+            .iter()
+            .map(|a| serde_json::from_str(&a.payload).unwrap())
+            .collect();
+        assert!(payloads.iter().any(|p| p.milestone_id == Some(3)));
+        assert!(payloads.iter().any(|p| p.milestone_id.is_none()));
+        assert!(payloads
+            .iter()
+            .all(|p| p.project_id == 100 && p.mr_iid == 7));
+    }
+}
@@ -0,0 +1,351 @@
+//! MR reviewers and assignees.
+//!
+//! Project members are cached the first time the picker searches a project,
+//! so it keeps working offline. Setting reviewers or assignees updates the
+//! cached MR right away and queues the new list; GitLab wants user ids, which
+//! are resolved from the member cache. Until the change is pushed, sync keeps
+//! the local lists instead of GitLab's.
+
+use crate::core::{authenticated_username, token_scopes};
+use crate::db::pool::DbPool;
+use crate::db::project_members::{self, ProjectMemberRow};
+use crate::error::AppError;
+use crate::models::sync_action::ActionType;
+use crate::services::gitlab_client::GitLabClient;
+use crate::services::sync_queue::{self, EnqueueInput, SetMrUsersPayload};
+use serde::Serialize;
+
+/// How long a project's cached members are used before the picker fetches
+/// them again (24 hours).
+pub const MEMBERS_TTL_SECS: i64 = 24 * 60 * 60;
+
+/// Most members returned by one search.
+const MAX_SEARCH_RESULTS: i64 = 20;
+
+/// A user who can be picked as reviewer or assignee.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectMember {
+    pub id: i64,
+    pub username: String,
+    pub name: String,
+    pub avatar_url: Option<String>,
+}
+
+impl From<ProjectMemberRow> for ProjectMember {
+    fn from(row: ProjectMemberRow) -> Self {
+        Self {
+            id: row.user_id,
+            username: row.username,
+            name: row.name,
+            avatar_url: row.avatar_url,
+        }
+    }
+}
+
+/// Cached members of a project matching `query` by username or name. An
+/// empty query lists the first few members.
+pub async fn search_members(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+    query: &str,
+) -> Result<Vec<ProjectMember>, AppError> {
+    Ok(project_members::search_members(
+        pool,
+        instance_id,
+        project_id,
+        query.trim(),
+        MAX_SEARCH_RESULTS,
+    )
+    .await?
+    .into_iter()
+    .map(ProjectMember::from)
+    .collect())
+}
+
+/// Fetch a project's members unless they were fetched within
+/// [`MEMBERS_TTL_SECS`]. Returns whether they were fetched.
+pub async fn refresh_members_if_stale(
+    pool: &DbPool,
+    client: &GitLabClient,
+    instance_id: i64,
+    project_id: i64,
+    now: i64,
+) -> Result<bool, AppError> {
+    if let Some(fetched_at) = project_members::fetched_at(pool, instance_id, project_id).await? {
+        if now - fetched_at < MEMBERS_TTL_SECS {
+            return Ok(false);
+        }
+    }
+    let members: Vec<ProjectMemberRow> = client
+        .list_project_members(project_id)
+        .await?
+        .into_iter()
+        .map(|user| ProjectMemberRow {
+            user_id: user.id,
+            username: user.username,
+            name: user.name,
+            avatar_url: user.avatar_url,
+        })
+        .collect();
+    project_members::replace_members(pool, instance_id, project_id, &members, now).await?;
+    Ok(true)
+}
+
+/// Which of an MR's user lists to set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MrRole {
+    Reviewer,
+    Assignee,
+}
+
+impl MrRole {
+    fn column(self) -> &'static str {
+        match self {
+            Self::Reviewer => "reviewers",
+            Self::Assignee => "assignees",
+        }
+    }
+
+    fn action_type(self) -> ActionType {
+        match self {
+            Self::Reviewer => ActionType::SetReviewers,
+            Self::Assignee => ActionType::SetAssignees,
+        }
+    }
+}
+
+/// Replace an MR's reviewers or assignees locally and queue the change.
+/// Returns the list afterwards; nothing is queued when it didn't change.
+///
+/// Every username must be in a cached member list of the MR's instance.
+pub async fn set_mr_users(
+    pool: &DbPool,
+    mr_id: i64,
+    role: MrRole,
+    usernames: &[String],
+) -> Result<Vec<String>, AppError> {
+    let mut wanted: Vec<String> = Vec::new();
+    for name in usernames.iter().map(|n| n.trim().trim_start_matches('@')) {
+        if !name.is_empty() && !wanted.iter().any(|w| w == name) {
+            wanted.push(name.to_string());
+        }
+    }
+
+    let (instance_id, project_id, iid, current_json): (i64, i64, i64, String) =
+        sqlx::query_as(&format!(
+            "SELECT instance_id, project_id, iid, {} FROM merge_requests WHERE id = ?",
+            role.column()
+        ))
+        .bind(mr_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::not_found_with_id("MergeRequest", mr_id.to_string()))?;
+    token_scopes::ensure_can_write(pool, mr_id).await?;
+
+    let current: Vec<String> = serde_json::from_str(&current_json).unwrap_or_default();
+    if current.len() == wanted.len() && wanted.iter().all(|w| current.contains(w)) {
+        return Ok(current);
+    }
+
+    let mut user_ids = Vec::with_capacity(wanted.len());
+    for name in &wanted {
+        let id = project_members::user_id(pool, instance_id, name)
+            .await?
+            .ok_or_else(|| {
+                AppError::invalid_input_field(format!("Unknown user: {}", name), "usernames")
+            })?;
+        user_ids.push(id);
+    }
+
+    let me = authenticated_username(pool, instance_id).await?;
+    let wanted_json = serde_json::to_string(&wanted)?;
+    let mut tx = pool.begin().await?;
+    sqlx::query(&format!(
+        "UPDATE merge_requests SET {} = ? WHERE id = ?",
+        role.column()
+    ))
+    .bind(&wanted_json)
+    .bind(mr_id)
+    .execute(&mut *tx)
+    .await?;
+    match role {
+        MrRole::Reviewer => {
+            // Approvers who aren't reviewers keep their row.
+            sqlx::query(
+                "DELETE FROM mr_reviewers
+                 WHERE mr_id = ? AND status != 'approved'
+                   AND username NOT IN (SELECT value FROM json_each(?))",
+            )
+            .bind(mr_id)
+            .bind(&wanted_json)
+            .execute(&mut *tx)
+            .await?;
+            for name in &wanted {
+                sqlx::query(
+                    "INSERT OR IGNORE INTO mr_reviewers (mr_id, username, status, requested_at)
+                     VALUES (?, ?, 'pending', strftime('%s', 'now'))",
+                )
+                .bind(mr_id)
+                .bind(name)
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+        MrRole::Assignee => {
+            let assigned_to_me = me.is_some_and(|me| wanted.contains(&me));
+            sqlx::query("UPDATE merge_requests SET assigned_to_me = ? WHERE id = ?")
+                .bind(assigned_to_me)
+                .bind(mr_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+    }
+    tx.commit().await?;
+
+    let payload = serde_json::to_string(&SetMrUsersPayload {
+        project_id,
+        mr_iid: iid,
+        user_ids,
+        usernames: wanted.clone(),
+    })?;
+    sync_queue::enqueue_action(
+        pool,
+        EnqueueInput {
+            mr_id,
+            action_type: role.action_type(),
+            payload,
+            local_reference_id: None,
+        },
+    )
+    .await?;
+
+    Ok(wanted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+
+    async fn seed(pool: &DbPool, inst: i64) {
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, title, author_username, source_branch,
+              target_branch, state, web_url, created_at, updated_at, reviewers, assignees)
+             VALUES (1, ?, 7, 100, 'Add cache', 'alice', 'f', 'main', 'opened', 'w', 0, 0,
+                     '[\"bob\"]', '[]')",
+        )
+        .bind(inst)
+        .execute(pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO mr_reviewers (mr_id, username, status) VALUES (1, 'bob', 'pending'),
+                                                                        (1, 'dave', 'approved')",
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+        let member = |id: i64, username: &str, name: &str| ProjectMemberRow {
+            user_id: id,
+            username: username.into(),
+            name: name.into(),
+            avatar_url: None,
+        };
+        project_members::replace_members(
+            pool,
+            inst,
+            100,
+            &[
+                member(1, "me", "Me Myself"),
+                member(2, "bob", "Bob Builder"),
+                member(3, "carol", "Carol Bobson"),
+            ],
+            10,
+        )
+        .await
+        .unwrap();
+    }
+
+    async fn reviewer_rows(pool: &DbPool) -> Vec<String> {
+        sqlx::query_scalar("SELECT username FROM mr_reviewers WHERE mr_id = 1 ORDER BY username")
+            .fetch_all(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn search_matches_username_and_name() {
+        let (pool, inst) = seed_instance(true).await;
+        seed(&pool, inst).await;
+
+        let names = |members: Vec<ProjectMember>| -> Vec<String> {
+            members.into_iter().map(|m| m.username).collect()
+        };
+        let found = search_members(&pool, inst, 100, "bob").await.unwrap();
+        assert_eq!(names(found), vec!["bob", "carol"]);
+        let found = search_members(&pool, inst, 100, " CAR").await.unwrap();
+        assert_eq!(names(found), vec!["carol"]);
+        assert_eq!(search_members(&pool, inst, 100, "").await.unwrap().len(), 3);
+        assert!(search_members(&pool, inst, 100, "%")
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(search_members(&pool, inst, 200, "bob")
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn setting_users_applies_locally_and_queues_ids() {
+        let (pool, inst) = seed_instance(true).await;
+        seed(&pool, inst).await;
+
+        let reviewers = set_mr_users(
+            &pool,
+            1,
+            MrRole::Reviewer,
+            &["carol".into(), "@carol".into()],
+        )
+        .await
+        .unwrap();
+        assert_eq!(reviewers, vec!["carol"]);
+        // bob is gone, the approver stays, carol is new.
+        assert_eq!(reviewer_rows(&pool).await, vec!["carol", "dave"]);
+
+        let assignees = set_mr_users(&pool, 1, MrRole::Assignee, &["me".into()])
+            .await
+            .unwrap();
+        assert_eq!(assignees, vec!["me"]);
+        let (stored, assigned_to_me): (String, bool) =
+            sqlx::query_as("SELECT assignees, assigned_to_me FROM merge_requests WHERE id = 1")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(stored, r#"["me"]"#);
+        assert!(assigned_to_me);
+
+        // Unchanged lists queue nothing; unknown users are refused.
+        set_mr_users(&pool, 1, MrRole::Assignee, &["me".into()])
+            .await
+            .unwrap();
+        assert!(set_mr_users(&pool, 1, MrRole::Assignee, &["zed".into()])
+            .await
+            .is_err());
+
+        let queued = sync_queue::get_actions_for_mr(&pool, 1).await.unwrap();
+        let types: Vec<&str> = queued.iter().map(|a| a.action_type.as_str()).collect();
+        assert_eq!(types.len(), 2);
+        assert!(types.contains(&"setreviewers") && types.contains(&"setassignees"));
+        let payload: SetMrUsersPayload = queued
+            .iter()
+            .find(|a| a.action_type == "setreviewers")
+            .map(|a| serde_json::from_str(&a.payload).unwrap())
+            .unwrap();
+        assert_eq!(payload.user_ids, vec![3]);
+        assert_eq!((payload.project_id, payload.mr_iid), (100, 7));
+    }
+}
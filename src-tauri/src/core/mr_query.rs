@@ -14,8 +14,11 @@ pub struct ReviewFilter {
     pub state: Option<String>,
     /// Substring matched against title and description.
     pub search: Option<String>,
+    /// Include MRs opened by bot accounts. Watched bot MRs are always shown.
+    pub include_bots: bool,
 }
 
+/// Full row, including the complete description. Only for the detail view.
 const MR_COLUMNS: &str = r#"
     mr.id, mr.instance_id, mr.iid, mr.project_id,
     COALESCE(p.name_with_namespace, mr.project_name) AS project_name,
@@ -24,11 +27,37 @@ const MR_COLUMNS: &str = r#"
     mr.web_url, mr.created_at, mr.updated_at, mr.merged_at,
     mr.approval_status, mr.approvals_required, mr.approvals_count,
     mr.labels, mr.reviewers, mr.cached_at, mr.user_has_approved,
-    mr.head_pipeline_status, mr.state_changed_at
+    mr.head_pipeline_status, mr.state_changed_at, mr.watched, mr.is_bot_author,
+    mr.task_count, mr.task_completed_count, mr.discussion_locked, mr.changed_since_review,
+    mr.milestone_id, mr.milestone_title, mr.milestone_due_date,
+    (SELECT r.rerequested_at FROM mr_reviewers r
+     WHERE r.mr_id = mr.id
+       AND r.username = (SELECT authenticated_username FROM gitlab_instances WHERE id = mr.instance_id)
+    ) AS review_rerequested_at
 "#;
 
-/// MRs for review: excludes the authenticated user's own authored MRs and
-/// MRs assigned to the user. Mirrors `commands::mr::get_merge_requests`.
+/// Same columns as `MR_COLUMNS`, but `description` holds the truncated
+/// preview so list payloads stay small.
+const MR_LIST_COLUMNS: &str = r#"
+    mr.id, mr.instance_id, mr.iid, mr.project_id,
+    COALESCE(p.name_with_namespace, mr.project_name) AS project_name,
+    mr.title, mr.description_preview AS description,
+    mr.author_username, mr.source_branch, mr.target_branch, mr.state,
+    mr.web_url, mr.created_at, mr.updated_at, mr.merged_at,
+    mr.approval_status, mr.approvals_required, mr.approvals_count,
+    mr.labels, mr.reviewers, mr.cached_at, mr.user_has_approved,
+    mr.head_pipeline_status, mr.state_changed_at, mr.watched, mr.is_bot_author,
+    mr.task_count, mr.task_completed_count, mr.discussion_locked, mr.changed_since_review,
+    mr.milestone_id, mr.milestone_title, mr.milestone_due_date,
+    (SELECT r.rerequested_at FROM mr_reviewers r
+     WHERE r.mr_id = mr.id
+       AND r.username = (SELECT authenticated_username FROM gitlab_instances WHERE id = mr.instance_id)
+    ) AS review_rerequested_at
+"#;
+
+/// MRs for review: excludes the authenticated user's own authored MRs, and
+/// MRs assigned to the user unless the MR is watched. Mirrors
+/// `commands::mr::get_merge_requests`.
 pub async fn list_review_mrs(
     pool: &DbPool,
     instance_id: i64,
@@ -36,7 +65,7 @@ pub async fn list_review_mrs(
 ) -> Result<Vec<MergeRequest>, AppError> {
     let mut query = format!(
         r#"
-        SELECT {MR_COLUMNS}
+        SELECT {MR_LIST_COLUMNS}
         FROM merge_requests mr
         LEFT JOIN projects p ON p.id = mr.project_id AND p.instance_id = mr.instance_id
         WHERE mr.instance_id = $1
@@ -44,7 +73,7 @@ pub async fn list_review_mrs(
               (SELECT authenticated_username FROM gitlab_instances WHERE id = mr.instance_id),
               ''
           )
-          AND mr.assigned_to_me = 0
+          AND (mr.watched = 1 OR mr.assigned_to_me = 0)
         "#
     );
 
@@ -53,6 +82,9 @@ pub async fn list_review_mrs(
     if apply_state_filter {
         query.push_str(" AND mr.state = $2");
     }
+    if !filter.include_bots {
+        query.push_str(" AND (mr.is_bot_author = 0 OR mr.watched = 1)");
+    }
 
     let has_search = filter.search.is_some();
     let search_pattern = filter.search.map(|s| format!("%{}%", s));
@@ -62,7 +94,8 @@ pub async fn list_review_mrs(
             " AND (mr.title LIKE {param} OR mr.description LIKE {param})"
         ));
     }
-    query.push_str(" ORDER BY mr.updated_at DESC");
+    // Re-requested reviews jump back to the top of the queue.
+    query.push_str(" ORDER BY (review_rerequested_at IS NOT NULL) DESC, mr.updated_at DESC");
 
     let rows: Vec<MergeRequest> = match (apply_state_filter, search_pattern.as_ref()) {
         (true, Some(search)) => {
@@ -120,7 +153,7 @@ pub async fn list_my_mrs(
         let cutoff = chrono::Utc::now().timestamp() - 86_400;
         sqlx::query_as(&format!(
             r#"
-            SELECT {MR_COLUMNS}
+            SELECT {MR_LIST_COLUMNS}
             FROM merge_requests mr
             LEFT JOIN projects p ON p.id = mr.project_id AND p.instance_id = mr.instance_id
             WHERE mr.instance_id = ?
@@ -140,7 +173,7 @@ pub async fn list_my_mrs(
     } else {
         sqlx::query_as(&format!(
             r#"
-            SELECT {MR_COLUMNS}
+            SELECT {MR_LIST_COLUMNS}
             FROM merge_requests mr
             LEFT JOIN projects p ON p.id = mr.project_id AND p.instance_id = mr.instance_id
             WHERE mr.instance_id = ? AND mr.state = 'opened'
@@ -156,6 +189,22 @@ pub async fn list_my_mrs(
     Ok(rows)
 }
 
+/// One MR as list views see it (description holds the preview).
+pub async fn get_list_row(pool: &DbPool, mr_id: i64) -> Result<Option<MergeRequest>, AppError> {
+    let mr = sqlx::query_as(&format!(
+        r#"
+        SELECT {MR_LIST_COLUMNS}
+        FROM merge_requests mr
+        LEFT JOIN projects p ON p.id = mr.project_id AND p.instance_id = mr.instance_id
+        WHERE mr.id = $1
+        "#
+    ))
+    .bind(mr_id)
+    .fetch_optional(pool)
+    .await?;
+    Ok(mr)
+}
+
 /// Detail bundle for one MR: the row, its diff metadata, changed files, and a
 /// count of pending sync-queue actions.
 #[derive(Debug)]
@@ -306,6 +355,54 @@ mod tests {
         assert!(rows.is_empty(), "assigned-to-me MR must be excluded from review");
     }
 
+    #[tokio::test]
+    async fn review_includes_watched_mr_despite_scope() {
+        // Watching overrides the assigned-to-me exclusion.
+        let (_dir, pool, inst) = pool_with_mr("alice", 1, "opened", "watched").await;
+        sqlx::query("UPDATE merge_requests SET watched = 1 WHERE id = 1")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let rows = list_review_mrs(&pool, inst, ReviewFilter::default())
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        assert!(rows[0].watched);
+    }
+
+    #[tokio::test]
+    async fn review_excludes_my_own_watched_mr() {
+        let (_dir, pool, inst) = pool_with_mr("me", 0, "opened", "mine").await;
+        sqlx::query("UPDATE merge_requests SET watched = 1 WHERE id = 1")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let rows = list_review_mrs(&pool, inst, ReviewFilter::default())
+            .await
+            .unwrap();
+        assert!(
+            rows.is_empty(),
+            "my own MR belongs in My MRs even when watched"
+        );
+    }
+
+    #[tokio::test]
+    async fn list_returns_preview_and_detail_full_description() {
+        let (_dir, pool, inst) = pool_with_mr("alice", 0, "opened", "long").await;
+        sqlx::query(
+            "UPDATE merge_requests SET description = 'full release notes', description_preview = 'full…' WHERE id = 1",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let rows = list_review_mrs(&pool, inst, ReviewFilter::default())
+            .await
+            .unwrap();
+        assert_eq!(rows[0].description.as_deref(), Some("full…"));
+        let detail = get_detail(&pool, 1).await.unwrap();
+        assert_eq!(detail.mr.description.as_deref(), Some("full release notes"));
+    }
+
     #[tokio::test]
     async fn mine_hides_drafts_when_excluded() {
         let (_dir, pool, inst) = pool_with_mr("me", 0, "opened", "Draft: wip thing").await;
@@ -321,7 +418,10 @@ mod tests {
         let hit = list_review_mrs(
             &pool,
             inst,
-            ReviewFilter { state: None, search: Some("parser".into()) },
+            ReviewFilter {
+                search: Some("parser".into()),
+                ..Default::default()
+            },
         )
         .await
         .unwrap();
@@ -329,10 +429,74 @@ mod tests {
         let miss = list_review_mrs(
             &pool,
             inst,
-            ReviewFilter { state: None, search: Some("zzznomatch".into()) },
+            ReviewFilter {
+                search: Some("zzznomatch".into()),
+                ..Default::default()
+            },
         )
         .await
         .unwrap();
         assert!(miss.is_empty(), "non-matching search returns nothing");
     }
+
+    #[tokio::test]
+    async fn review_hides_bot_mrs_unless_included() {
+        let (_dir, pool, inst) = pool_with_mr("dependabot", 0, "opened", "Bump serde").await;
+        sqlx::query("UPDATE merge_requests SET is_bot_author = 1 WHERE id = 1")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let hidden = list_review_mrs(&pool, inst, ReviewFilter::default())
+            .await
+            .unwrap();
+        assert!(hidden.is_empty(), "bot MRs are hidden by default");
+        let shown = list_review_mrs(
+            &pool,
+            inst,
+            ReviewFilter {
+                include_bots: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(shown.len(), 1);
+        assert!(shown[0].is_bot_author);
+    }
+
+    #[tokio::test]
+    async fn review_lists_rerequested_mrs_first() {
+        let (_dir, pool, inst) = pool_with_mr("alice", 0, "opened", "older").await;
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, project_name, title, author_username,
+              source_branch, target_branch, state, web_url, created_at, updated_at,
+              labels, reviewers, cached_at)
+             VALUES (2, ?, 2, 10, 'g/p', 'newer', 'bob', 's', 'main', 'opened', 'http://y', 0, 50, '[]', '[]', 0)",
+        )
+        .bind(inst)
+        .execute(&pool)
+        .await
+        .unwrap();
+        let rows = list_review_mrs(&pool, inst, ReviewFilter::default())
+            .await
+            .unwrap();
+        assert_eq!(rows[0].id, 2);
+        assert!(rows[0].review_rerequested_at.is_none());
+
+        // Only my own reviewer row counts.
+        sqlx::query(
+            "INSERT INTO mr_reviewers (mr_id, username, status, review_state, rerequested_at)
+             VALUES (1, 'me', 'pending', 'unreviewed', 99), (2, 'carol', 'pending', 'unreviewed', 98)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let rows = list_review_mrs(&pool, inst, ReviewFilter::default())
+            .await
+            .unwrap();
+        assert_eq!(rows[0].id, 1);
+        assert_eq!(rows[0].review_rerequested_at, Some(99));
+        assert!(rows[1].review_rerequested_at.is_none());
+    }
 }
@@ -0,0 +1,335 @@
+//! Task lists in MR descriptions.
+//!
+//! GitLab renders `- [ ] item` lines as checkboxes. Sync parses them into
+//! `mr_tasks` and keeps per-MR counts on `merge_requests`, so progress shows
+//! without loading descriptions. Ticking a box rewrites the cached
+//! description right away and queues the edit for GitLab.
+
+use crate::core::token_scopes;
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use crate::models::sync_action::ActionType;
+use crate::services::sync_queue::{self, EnqueueInput, ToggleTaskPayload};
+use serde::Serialize;
+use sqlx::FromRow;
+
+/// One checkbox of a description task list.
+#[derive(Debug, Clone, PartialEq, Serialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct MrTask {
+    /// Position among the description's tasks, from 0.
+    pub position: i64,
+    pub text: String,
+    pub checked: bool,
+}
+
+/// Task list progress of an MR.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MrTaskList {
+    pub total: i64,
+    pub completed: i64,
+    pub items: Vec<MrTask>,
+}
+
+/// A task found in markdown, with the byte offset of its checkbox mark.
+struct TaskMark<'a> {
+    offset: usize,
+    checked: bool,
+    text: &'a str,
+}
+
+/// Parse `- [ ] text` style line; returns (offset of the mark in `line`, checked, text).
+fn parse_task_line(line: &str) -> Option<(usize, bool, &str)> {
+    // Tasks may be nested in blockquotes or indented lists.
+    let body = line.trim_start_matches([' ', '\t', '>']);
+    let after_marker = if let Some(rest) = body.strip_prefix(['-', '*', '+']) {
+        rest
+    } else {
+        let digits = body.len() - body.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        if digits == 0 {
+            return None;
+        }
+        body[digits..].strip_prefix(['.', ')'])?
+    };
+    let item = after_marker.trim_start_matches([' ', '\t']);
+    if item.len() == after_marker.len() {
+        return None;
+    }
+    let bytes = item.as_bytes();
+    if bytes.len() < 3 || bytes[0] != b'[' || bytes[2] != b']' {
+        return None;
+    }
+    let checked = match bytes[1] {
+        b' ' => false,
+        b'x' | b'X' => true,
+        // `[~]` (inapplicable) and anything else isn't counted by GitLab.
+        _ => return None,
+    };
+    let text = &item[3..];
+    if !text.is_empty() && !text.starts_with([' ', '\t']) {
+        return None;
+    }
+    Some((line.len() - item.len() + 1, checked, text.trim()))
+}
+
+fn scan_tasks(description: &str) -> Vec<TaskMark<'_>> {
+    let mut tasks = Vec::new();
+    let mut in_fence = false;
+    let mut offset = 0;
+    for raw in description.split_inclusive('\n') {
+        let line = raw.trim_end_matches(['\n', '\r']);
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        } else if !in_fence {
+            if let Some((at, checked, text)) = parse_task_line(line) {
+                tasks.push(TaskMark {
+                    offset: offset + at,
+                    checked,
+                    text,
+                });
+            }
+        }
+        offset += raw.len();
+    }
+    tasks
+}
+
+/// Parse the task list items of a description, in order.
+pub fn parse_tasks(description: &str) -> Vec<MrTask> {
+    scan_tasks(description)
+        .into_iter()
+        .enumerate()
+        .map(|(i, t)| MrTask {
+            position: i as i64,
+            text: t.text.to_string(),
+            checked: t.checked,
+        })
+        .collect()
+}
+
+/// Return `description` with the task at `position` (un)checked, or `None`
+/// if there is no such task.
+pub fn set_task_checked(description: &str, position: usize, checked: bool) -> Option<String> {
+    let task = scan_tasks(description).into_iter().nth(position)?;
+    let mark = if checked { "x" } else { " " };
+    let mut out = description.to_string();
+    out.replace_range(task.offset..task.offset + 1, mark);
+    Some(out)
+}
+
+/// Find the task to toggle in a description that may have changed since the
+/// toggle was queued: the same position if its text still matches,
+/// otherwise the first task with that text.
+pub fn locate_task(description: &str, position: usize, text: &str) -> Option<usize> {
+    let tasks = scan_tasks(description);
+    if tasks.get(position).is_some_and(|t| t.text == text) {
+        return Some(position);
+    }
+    tasks.iter().position(|t| t.text == text)
+}
+
+/// Replace the cached tasks and counts of an MR from its description.
+pub async fn store_tasks(
+    pool: &DbPool,
+    mr_id: i64,
+    description: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    let tasks = description.map(parse_tasks).unwrap_or_default();
+    let completed = tasks.iter().filter(|t| t.checked).count() as i64;
+
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM mr_tasks WHERE mr_id = ?")
+        .bind(mr_id)
+        .execute(&mut *tx)
+        .await?;
+    for task in &tasks {
+        sqlx::query("INSERT INTO mr_tasks (mr_id, position, text, checked) VALUES (?, ?, ?, ?)")
+            .bind(mr_id)
+            .bind(task.position)
+            .bind(&task.text)
+            .bind(task.checked)
+            .execute(&mut *tx)
+            .await?;
+    }
+    sqlx::query("UPDATE merge_requests SET task_count = ?, task_completed_count = ? WHERE id = ?")
+        .bind(tasks.len() as i64)
+        .bind(completed)
+        .bind(mr_id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await
+}
+
+/// Cached task list of an MR.
+pub async fn get_mr_tasks(pool: &DbPool, mr_id: i64) -> Result<MrTaskList, AppError> {
+    let items: Vec<MrTask> = sqlx::query_as(
+        "SELECT position, text, checked FROM mr_tasks WHERE mr_id = ? ORDER BY position",
+    )
+    .bind(mr_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(MrTaskList {
+        total: items.len() as i64,
+        completed: items.iter().filter(|t| t.checked).count() as i64,
+        items,
+    })
+}
+
+/// Check or uncheck a task locally and queue the description edit.
+pub async fn toggle_mr_task(
+    pool: &DbPool,
+    mr_id: i64,
+    index: i64,
+    checked: bool,
+) -> Result<MrTaskList, AppError> {
+    let (project_id, iid, description, preview): (i64, i64, Option<String>, Option<String>) =
+        sqlx::query_as(
+            "SELECT project_id, iid, description, description_preview
+             FROM merge_requests WHERE id = ?",
+        )
+        .bind(mr_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::not_found_with_id("MergeRequest", mr_id.to_string()))?;
+    token_scopes::ensure_can_write(pool, mr_id).await?;
+
+    let description = description.unwrap_or_default();
+    let position = usize::try_from(index)
+        .map_err(|_| AppError::invalid_input_field("Task index must not be negative", "index"))?;
+    let text = scan_tasks(&description)
+        .get(position)
+        .map(|t| t.text.to_string())
+        .ok_or_else(|| AppError::not_found_with_id("Task", index.to_string()))?;
+    let updated = set_task_checked(&description, position, checked)
+        .ok_or_else(|| AppError::not_found_with_id("Task", index.to_string()))?;
+    // The preview is a prefix of the description, so the same task (if it
+    // made the cut) sits at the same position.
+    let preview = preview.map(|p| set_task_checked(&p, position, checked).unwrap_or(p));
+
+    sqlx::query("UPDATE merge_requests SET description = ?, description_preview = ? WHERE id = ?")
+        .bind(&updated)
+        .bind(&preview)
+        .bind(mr_id)
+        .execute(pool)
+        .await?;
+    store_tasks(pool, mr_id, Some(&updated)).await?;
+
+    let payload = serde_json::to_string(&ToggleTaskPayload {
+        project_id,
+        mr_iid: iid,
+        index,
+        text,
+        checked,
+    })?;
+    sync_queue::enqueue_action(
+        pool,
+        EnqueueInput {
+            mr_id,
+            action_type: ActionType::ToggleTask,
+            payload,
+            local_reference_id: None,
+        },
+    )
+    .await?;
+
+    get_mr_tasks(pool, mr_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+
+    const DESCRIPTION: &str = "## Checklist\n\
+        - [x] Tests\n\
+        - [ ] Docs\n\
+        * [X] Changelog\n\
+        1. [ ] Migration\n\
+        > - [ ] Quoted\n\
+        - [~] Not applicable\n\
+        - [ ]no space\n\
+        ```\n\
+        - [ ] in a code block\n\
+        ```\n\
+        -  [ ]  Spaced  \n";
+
+    #[test]
+    fn parses_gitlab_task_items() {
+        let tasks = parse_tasks(DESCRIPTION);
+        let summary: Vec<(&str, bool)> =
+            tasks.iter().map(|t| (t.text.as_str(), t.checked)).collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Tests", true),
+                ("Docs", false),
+                ("Changelog", true),
+                ("Migration", false),
+                ("Quoted", false),
+                ("Spaced", false),
+            ]
+        );
+        assert_eq!(tasks[5].position, 5);
+    }
+
+    #[test]
+    fn toggles_only_the_requested_task() {
+        let checked = set_task_checked(DESCRIPTION, 1, true).unwrap();
+        assert!(checked.contains("- [x] Docs\n"));
+        assert_eq!(checked.len(), DESCRIPTION.len());
+        let unchecked = set_task_checked(&checked, 2, false).unwrap();
+        assert!(unchecked.contains("* [ ] Changelog\n"));
+        assert!(unchecked.contains("- [ ] in a code block"));
+        assert!(set_task_checked(DESCRIPTION, 6, true).is_none());
+
+        // A task inserted above moves the target; match it by text.
+        let edited = format!("- [ ] New\n{}", DESCRIPTION);
+        assert_eq!(locate_task(&edited, 1, "Docs"), Some(2));
+        assert_eq!(locate_task(DESCRIPTION, 1, "Docs"), Some(1));
+        assert_eq!(locate_task(DESCRIPTION, 1, "Gone"), None);
+    }
+
+    #[tokio::test]
+    async fn toggle_updates_cache_and_queues_edit() {
+        let (pool, inst) = seed_instance(true).await;
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, project_name, title, description, author_username,
+              source_branch, target_branch, state, web_url, created_at, updated_at,
+              labels, reviewers, cached_at)
+             VALUES (1, ?, 3, 10, 'g/p', 't', '- [ ] a\n- [ ] b', 'me', 's', 'main', 'opened',
+                     'http://x', 0, 0, '[]', '[]', 0)",
+        )
+        .bind(inst)
+        .execute(&pool)
+        .await
+        .unwrap();
+        store_tasks(&pool, 1, Some("- [ ] a\n- [ ] b"))
+            .await
+            .unwrap();
+
+        let list = toggle_mr_task(&pool, 1, 1, true).await.unwrap();
+        assert_eq!((list.total, list.completed), (2, 1));
+        assert!(list.items[1].checked);
+
+        let (description, done): (String, i64) = sqlx::query_as(
+            "SELECT description, task_completed_count FROM merge_requests WHERE id = 1",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(description, "- [ ] a\n- [x] b");
+        assert_eq!(done, 1);
+
+        let queued = sync_queue::get_pending_actions(&pool).await.unwrap();
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].action_type, "toggletask");
+        let payload: ToggleTaskPayload = serde_json::from_str(&queued[0].payload).unwrap();
+        assert_eq!((payload.mr_iid, payload.index), (3, 1));
+        assert_eq!(payload.text, "b");
+
+        assert!(toggle_mr_task(&pool, 1, 5, true).await.is_err());
+    }
+}
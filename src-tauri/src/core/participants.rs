@@ -0,0 +1,180 @@
+//! Everyone involved in an MR, from cached data.
+//!
+//! The author, assignees and requested reviewers come from the MR row,
+//! approvers from `mr_reviewers` (which also holds approvers who weren't
+//! asked to review) and commenters from the cached comments. A person with
+//! several roles is listed once.
+
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use crate::services::avatar;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+
+/// How someone is involved in an MR, in display order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ParticipantRole {
+    Author,
+    Assignee,
+    Reviewer,
+    Approver,
+    Commenter,
+}
+
+/// One person involved in an MR.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Participant {
+    pub username: String,
+    /// Every role the person has, in display order.
+    pub roles: Vec<ParticipantRole>,
+    /// Comments and replies written, system notes excluded.
+    pub comment_count: i64,
+    pub last_comment_at: Option<i64>,
+    /// Cached avatar as a data URI, if one was downloaded.
+    pub avatar: Option<String>,
+}
+
+fn usernames(json: Option<&str>) -> Vec<String> {
+    json.and_then(|j| serde_json::from_str(j).ok())
+        .unwrap_or_default()
+}
+
+/// Participants of an MR, ordered by their most prominent role and then
+/// by username.
+pub async fn participants(pool: &DbPool, mr_id: i64) -> Result<Vec<Participant>, AppError> {
+    let (instance_id, author, reviewers, assignees): (i64, String, Option<String>, String) =
+        sqlx::query_as(
+            "SELECT instance_id, author_username, reviewers, assignees
+             FROM merge_requests WHERE id = ?",
+        )
+        .bind(mr_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::not_found_with_id("MergeRequest", mr_id.to_string()))?;
+
+    let approvers: Vec<String> = sqlx::query_scalar(
+        "SELECT username FROM mr_reviewers WHERE mr_id = ? AND status = 'approved'",
+    )
+    .bind(mr_id)
+    .fetch_all(pool)
+    .await?;
+
+    let commenters: Vec<(String, i64, i64)> = sqlx::query_as(
+        "SELECT author_username, COUNT(*), MAX(created_at) FROM comments
+         WHERE mr_id = ? AND system = 0
+         GROUP BY author_username",
+    )
+    .bind(mr_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut roles: HashMap<String, BTreeSet<ParticipantRole>> = HashMap::new();
+    let mut add = |username: String, role: ParticipantRole| {
+        roles.entry(username).or_default().insert(role);
+    };
+    add(author, ParticipantRole::Author);
+    for username in usernames(Some(&assignees)) {
+        add(username, ParticipantRole::Assignee);
+    }
+    for username in usernames(reviewers.as_deref()) {
+        add(username, ParticipantRole::Reviewer);
+    }
+    for username in approvers {
+        add(username, ParticipantRole::Approver);
+    }
+    let mut comments = HashMap::new();
+    for (username, count, last_at) in commenters {
+        add(username.clone(), ParticipantRole::Commenter);
+        comments.insert(username, (count, last_at));
+    }
+
+    let names: Vec<String> = roles.keys().cloned().collect();
+    let mut avatars = avatar::get_avatar_data_uris_batch(pool, instance_id, &names).await?;
+
+    let mut participants: Vec<Participant> = roles
+        .into_iter()
+        .map(|(username, roles)| {
+            let (comment_count, last_comment_at) = comments
+                .get(&username)
+                .map_or((0, None), |&(count, last_at)| (count, Some(last_at)));
+            Participant {
+                avatar: avatars.remove(&username),
+                roles: roles.into_iter().collect(),
+                comment_count,
+                last_comment_at,
+                username,
+            }
+        })
+        .collect();
+    participants.sort_by(|a, b| {
+        a.roles
+            .first()
+            .cmp(&b.roles.first())
+            .then_with(|| a.username.cmp(&b.username))
+    });
+    Ok(participants)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+
+    #[tokio::test]
+    async fn participants_merge_roles_per_person() {
+        let (pool, inst) = seed_instance(true).await;
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, project_name, title, author_username,
+              source_branch, target_branch, state, web_url, created_at, updated_at,
+              labels, reviewers, assignees, cached_at)
+             VALUES (1, ?, 1, 10, 'g/p', 'MR', 'alice', 's', 'main', 'opened',
+                     'http://x', 0, 0, '[]', '[\"bob\",\"carol\"]', '[\"alice\"]', 0)",
+        )
+        .bind(inst)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO mr_reviewers (mr_id, username, status)
+             VALUES (1, 'bob', 'approved'), (1, 'carol', 'pending'), (1, 'dave', 'approved')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO comments (id, mr_id, discussion_id, author_username, body,
+                                   resolvable, resolved, system, created_at, updated_at)
+             VALUES (1, 1, 'd1', 'erin', 'q', 1, 0, 0, 5, 5),
+                    (2, 1, 'd1', 'alice', 'a', 1, 0, 0, 6, 6),
+                    (3, 1, 'd1', 'erin', 'b', 1, 0, 0, 9, 9),
+                    (4, 1, NULL, 'frank', 'approved this merge request', 0, 0, 1, 7, 7)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let people = participants(&pool, 1).await.unwrap();
+        let summary: Vec<(&str, &[ParticipantRole], i64)> = people
+            .iter()
+            .map(|p| (p.username.as_str(), p.roles.as_slice(), p.comment_count))
+            .collect();
+        use ParticipantRole::*;
+        assert_eq!(
+            summary,
+            vec![
+                ("alice", &[Author, Assignee, Commenter][..], 1),
+                ("bob", &[Reviewer, Approver][..], 0),
+                ("carol", &[Reviewer][..], 0),
+                ("dave", &[Approver][..], 0),
+                ("erin", &[Commenter][..], 2),
+            ]
+        );
+        assert_eq!(people[4].last_comment_at, Some(9));
+        assert!(people[0].avatar.is_none());
+
+        assert!(participants(&pool, 2).await.is_err());
+    }
+}
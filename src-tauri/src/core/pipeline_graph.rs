@@ -0,0 +1,320 @@
+//! Pipeline job dependency graph.
+//!
+//! Jobs come from the REST jobs and bridges endpoints, as for the flat job
+//! list. Stage order and `needs:` only exist in GraphQL; when that query
+//! fails (old instance, missing permission) the graph falls back to plain
+//! stage ordering, and `needs_known` tells the UI so.
+
+use crate::core::{create_client, pipelines};
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use crate::services::gitlab_client::{GitLabDagJob, GitLabJob, GitLabPipelineDag};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// A stage column of the graph.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphStage {
+    pub name: String,
+    /// Aggregate status of the stage's jobs.
+    pub status: String,
+    pub job_ids: Vec<i64>,
+}
+
+/// Why `to` waits for `from`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeKind {
+    /// `to` lists `from` in its `needs:`.
+    Needs,
+    /// `to` has no `needs:` and waits for the whole previous stage.
+    Stage,
+}
+
+/// A dependency between two jobs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphEdge {
+    pub from: i64,
+    pub to: i64,
+    pub kind: EdgeKind,
+}
+
+/// A pipeline as a DAG.
+#[derive(Debug, Clone)]
+pub struct PipelineGraph {
+    pub stages: Vec<GraphStage>,
+    pub jobs: Vec<GitLabJob>,
+    pub edges: Vec<GraphEdge>,
+    /// False when `needs:` couldn't be loaded and edges follow stages only.
+    pub needs_known: bool,
+}
+
+/// Name of a job without its `parallel:` suffix (`rspec 1/3`) or matrix
+/// values (`build: [linux, amd64]`), which is what `needs:` refers to.
+fn base_name(name: &str) -> &str {
+    if let Some((base, _)) = name.split_once(": [") {
+        return base;
+    }
+    if let Some((base, suffix)) = name.rsplit_once(' ') {
+        if let Some((i, n)) = suffix.split_once('/') {
+            if i.parse::<u32>().is_ok() && n.parse::<u32>().is_ok() {
+                return base;
+            }
+        }
+    }
+    name
+}
+
+/// Aggregate job statuses the way GitLab labels a stage.
+fn stage_status(jobs: &[&GitLabJob]) -> String {
+    let has = |status: &str| jobs.iter().any(|j| j.status == status);
+    let status = if has("running") {
+        "running"
+    } else if jobs
+        .iter()
+        .any(|j| j.status == "failed" && !j.allow_failure)
+    {
+        "failed"
+    } else if [
+        "pending",
+        "created",
+        "preparing",
+        "waiting_for_resource",
+        "scheduled",
+    ]
+    .iter()
+    .any(|s| has(s))
+    {
+        "pending"
+    } else if has("canceled") {
+        "canceled"
+    } else if has("manual") {
+        "manual"
+    } else if !jobs.is_empty() && jobs.iter().all(|j| j.status == "skipped") {
+        "skipped"
+    } else {
+        "success"
+    };
+    status.to_string()
+}
+
+/// Build the graph from the REST jobs and, if available, the GraphQL DAG.
+pub fn build_graph(jobs: Vec<GitLabJob>, dag: Option<&GitLabPipelineDag>) -> PipelineGraph {
+    let scheduling: HashMap<i64, &GitLabDagJob> = dag
+        .map(|d| d.jobs.iter().map(|j| (j.id, j)).collect())
+        .unwrap_or_default();
+
+    // Stage order: GraphQL's, else by the first job created in each stage.
+    let mut stage_names: Vec<String> = dag.map(|d| d.stages.clone()).unwrap_or_default();
+    let mut by_age: Vec<&GitLabJob> = jobs.iter().collect();
+    by_age.sort_by_key(|j| j.id);
+    for job in by_age {
+        if !stage_names.contains(&job.stage) {
+            stage_names.push(job.stage.clone());
+        }
+    }
+
+    let stages: Vec<GraphStage> = stage_names
+        .into_iter()
+        .filter_map(|name| {
+            let members: Vec<&GitLabJob> = jobs.iter().filter(|j| j.stage == name).collect();
+            if members.is_empty() {
+                return None;
+            }
+            let mut job_ids: Vec<i64> = members.iter().map(|j| j.id).collect();
+            job_ids.sort_unstable();
+            Some(GraphStage {
+                status: stage_status(&members),
+                name,
+                job_ids,
+            })
+        })
+        .collect();
+
+    let mut by_name: HashMap<&str, Vec<i64>> = HashMap::new();
+    for job in &jobs {
+        by_name
+            .entry(base_name(&job.name))
+            .or_default()
+            .push(job.id);
+    }
+
+    let mut edges = Vec::new();
+    for (index, stage) in stages.iter().enumerate() {
+        for &to in &stage.job_ids {
+            match scheduling.get(&to).filter(|s| s.uses_needs) {
+                Some(sched) => {
+                    for need in &sched.needs {
+                        for &from in by_name.get(base_name(need)).into_iter().flatten() {
+                            if from != to {
+                                edges.push(GraphEdge {
+                                    from,
+                                    to,
+                                    kind: EdgeKind::Needs,
+                                });
+                            }
+                        }
+                    }
+                }
+                None if index > 0 => {
+                    edges.extend(stages[index - 1].job_ids.iter().map(|&from| GraphEdge {
+                        from,
+                        to,
+                        kind: EdgeKind::Stage,
+                    }));
+                }
+                None => {}
+            }
+        }
+    }
+
+    PipelineGraph {
+        stages,
+        jobs,
+        edges,
+        needs_known: dag.is_some(),
+    }
+}
+
+/// Fetch a pipeline's jobs and dependencies.
+pub async fn pipeline_graph(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+    pipeline_id: i64,
+) -> Result<PipelineGraph, AppError> {
+    let jobs = pipelines::pipeline_jobs(pool, instance_id, project_id, pipeline_id).await?;
+
+    let mut project_path: Option<String> = sqlx::query_scalar(
+        "SELECT path_with_namespace FROM projects WHERE id = ? AND instance_id = ?",
+    )
+    .bind(project_id)
+    .bind(instance_id)
+    .fetch_optional(pool)
+    .await?;
+    let client = create_client(pool, instance_id).await?;
+    if project_path.is_none() {
+        project_path = client
+            .get_project(project_id)
+            .await
+            .ok()
+            .map(|p| p.path_with_namespace);
+    }
+
+    let dag = match project_path {
+        Some(path) => match client.get_pipeline_dag(&path, pipeline_id).await {
+            Ok(dag) => Some(dag),
+            Err(e) => {
+                log::warn!("[pipeline-graph] needs unavailable for pipeline {pipeline_id}: {e}");
+                None
+            }
+        },
+        None => None,
+    };
+    Ok(build_graph(jobs, dag.as_ref()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(id: i64, name: &str, stage: &str, status: &str) -> GitLabJob {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": name,
+            "stage": stage,
+            "status": status,
+            "created_at": "2024-01-01T00:00:00Z",
+            "web_url": "http://x",
+            "allow_failure": false,
+        }))
+        .unwrap()
+    }
+
+    fn dag_job(id: i64, name: &str, stage: &str, needs: Option<&[&str]>) -> GitLabDagJob {
+        GitLabDagJob {
+            id,
+            name: name.to_string(),
+            stage: stage.to_string(),
+            uses_needs: needs.is_some(),
+            needs: needs
+                .unwrap_or_default()
+                .iter()
+                .map(|n| n.to_string())
+                .collect(),
+        }
+    }
+
+    fn edges_of(graph: &PipelineGraph, kind: EdgeKind) -> Vec<(i64, i64)> {
+        let mut edges: Vec<(i64, i64)> = graph
+            .edges
+            .iter()
+            .filter(|e| e.kind == kind)
+            .map(|e| (e.from, e.to))
+            .collect();
+        edges.sort_unstable();
+        edges
+    }
+
+    #[test]
+    fn strips_parallel_and_matrix_suffixes() {
+        assert_eq!(base_name("rspec 1/3"), "rspec");
+        assert_eq!(base_name("build: [linux, amd64]"), "build");
+        assert_eq!(base_name("deploy prod"), "deploy prod");
+        assert_eq!(base_name("lint"), "lint");
+    }
+
+    #[test]
+    fn builds_needs_and_stage_edges() {
+        let jobs = vec![
+            job(1, "build", "build", "success"),
+            job(2, "rspec 1/2", "test", "failed"),
+            job(3, "rspec 2/2", "test", "success"),
+            job(4, "lint", "test", "running"),
+            job(5, "deploy", "deploy", "manual"),
+            job(6, "notify", "deploy", "created"),
+        ];
+        let dag = GitLabPipelineDag {
+            stages: vec!["build".into(), "test".into(), "deploy".into()],
+            jobs: vec![
+                dag_job(1, "build", "build", None),
+                dag_job(2, "rspec 1/2", "test", None),
+                dag_job(3, "rspec 2/2", "test", None),
+                // Starts right away.
+                dag_job(4, "lint", "test", Some(&[])),
+                dag_job(5, "deploy", "deploy", Some(&["rspec"])),
+                dag_job(6, "notify", "deploy", None),
+            ],
+        };
+
+        let graph = build_graph(jobs, Some(&dag));
+        assert!(graph.needs_known);
+        let names: Vec<&str> = graph.stages.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["build", "test", "deploy"]);
+        assert_eq!(graph.stages[1].status, "running");
+        assert_eq!(graph.stages[2].status, "pending");
+
+        assert_eq!(edges_of(&graph, EdgeKind::Needs), vec![(2, 5), (3, 5)]);
+        assert_eq!(
+            edges_of(&graph, EdgeKind::Stage),
+            vec![(1, 2), (1, 3), (2, 6), (3, 6), (4, 6)]
+        );
+    }
+
+    #[test]
+    fn falls_back_to_stage_order_without_graphql() {
+        let jobs = vec![
+            job(12, "deploy", "deploy", "success"),
+            job(11, "test", "test", "success"),
+            job(10, "build", "build", "skipped"),
+        ];
+        let graph = build_graph(jobs, None);
+        assert!(!graph.needs_known);
+        let names: Vec<&str> = graph.stages.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["build", "test", "deploy"]);
+        assert_eq!(graph.stages[0].status, "skipped");
+        assert_eq!(edges_of(&graph, EdgeKind::Stage), vec![(10, 11), (11, 12)]);
+    }
+}
@@ -0,0 +1,317 @@
+//! Notification rules for pinned pipeline projects.
+//!
+//! Each pinned project picks when its pipelines notify: on failures only, on
+//! any finished pipeline, or on finished pipelines of the default branch. The
+//! sync engine polls pinned projects on its own timer and asks
+//! [`should_notify`] about each project's latest pipeline. The first poll of a
+//! project only records what it saw, so existing pipelines don't notify on
+//! startup. Pinned projects can also opt in to notifications about new manual
+//! jobs on their default branch (see `core::manual_jobs`).
+
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use crate::models::pipeline_project;
+use crate::services::gitlab_client::{GitLabJob, GitLabPipeline};
+use serde::{Deserialize, Serialize};
+
+/// When a pinned project's pipelines notify.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotifyRule {
+    /// Only failed pipelines.
+    Failures,
+    /// Every finished pipeline, on any branch.
+    #[default]
+    Completion,
+    /// Every finished pipeline on the project's default branch.
+    DefaultBranch,
+}
+
+impl NotifyRule {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NotifyRule::Failures => "failures",
+            NotifyRule::Completion => "completion",
+            NotifyRule::DefaultBranch => "default_branch",
+        }
+    }
+
+    /// Parse a stored rule. Unknown values fall back to the default.
+    pub fn from_stored(value: &str) -> Self {
+        match value {
+            "failures" => NotifyRule::Failures,
+            "default_branch" => NotifyRule::DefaultBranch,
+            _ => NotifyRule::Completion,
+        }
+    }
+}
+
+/// The last pipeline seen for a pinned project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeenPipeline {
+    pub pipeline_id: i64,
+    pub status: String,
+}
+
+impl From<&GitLabPipeline> for SeenPipeline {
+    fn from(pipeline: &GitLabPipeline) -> Self {
+        Self {
+            pipeline_id: pipeline.id,
+            status: pipeline.status.clone(),
+        }
+    }
+}
+
+/// Whether a pipeline status is final.
+pub fn is_finished(status: &str) -> bool {
+    matches!(status, "success" | "failed" | "canceled")
+}
+
+/// Whether `pipeline` should notify under `rule`, given the pipeline seen on
+/// the previous poll. A pipeline notifies once, when it is first seen
+/// finished. `default_branch` is only consulted by the `DefaultBranch` rule;
+/// an unknown default branch never matches.
+pub fn should_notify(
+    rule: NotifyRule,
+    previous: Option<&SeenPipeline>,
+    pipeline: &GitLabPipeline,
+    default_branch: Option<&str>,
+) -> bool {
+    let Some(previous) = previous else {
+        return false;
+    };
+    if !is_finished(&pipeline.status) {
+        return false;
+    }
+    if previous.pipeline_id == pipeline.id && previous.status == pipeline.status {
+        return false;
+    }
+    match rule {
+        NotifyRule::Failures => pipeline.status == "failed",
+        NotifyRule::Completion => true,
+        NotifyRule::DefaultBranch => default_branch == Some(pipeline.ref_name.as_str()),
+    }
+}
+
+/// Names of the jobs that failed a pipeline, in pipeline order. Jobs allowed
+/// to fail are left out, and a retried job is listed once.
+pub fn failed_job_names(jobs: &[GitLabJob]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for job in jobs {
+        if job.status == "failed" && !job.allow_failure && !names.contains(&job.name) {
+            names.push(job.name.clone());
+        }
+    }
+    names
+}
+
+/// Set the notification rule of a project on the pipelines dashboard.
+pub async fn set_rule(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+    rule: NotifyRule,
+) -> Result<(), AppError> {
+    let updated =
+        pipeline_project::set_notify_rule(pool, project_id, instance_id, rule.as_str()).await?;
+    if updated == 0 {
+        return Err(AppError::not_found_with_id(
+            "PipelineProject",
+            project_id.to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Turn notifications about new manual jobs on the default branch on or off
+/// for a project on the pipelines dashboard.
+pub async fn set_manual_gates(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+    enabled: bool,
+) -> Result<(), AppError> {
+    let updated =
+        pipeline_project::set_notify_manual_gates(pool, project_id, instance_id, enabled).await?;
+    if updated == 0 {
+        return Err(AppError::not_found_with_id(
+            "PipelineProject",
+            project_id.to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+
+    fn pipeline(id: i64, status: &str, ref_name: &str) -> GitLabPipeline {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "project_id": 10,
+            "status": status,
+            "ref": ref_name,
+            "sha": "abc",
+            "web_url": "http://x/-/pipelines/1",
+            "created_at": "2026-01-01T00:00:00Z",
+        }))
+        .unwrap()
+    }
+
+    fn seen(pipeline_id: i64, status: &str) -> SeenPipeline {
+        SeenPipeline {
+            pipeline_id,
+            status: status.to_string(),
+        }
+    }
+
+    #[test]
+    fn first_poll_and_running_pipelines_do_not_notify() {
+        let done = pipeline(2, "success", "main");
+        assert!(!should_notify(NotifyRule::Completion, None, &done, None));
+
+        let running = pipeline(2, "running", "main");
+        assert!(!should_notify(
+            NotifyRule::Completion,
+            Some(&seen(1, "success")),
+            &running,
+            None
+        ));
+    }
+
+    #[test]
+    fn notifies_once_per_finished_pipeline() {
+        let done = pipeline(2, "success", "feature");
+        assert!(should_notify(
+            NotifyRule::Completion,
+            Some(&seen(2, "running")),
+            &done,
+            None
+        ));
+        // Already notified on the previous poll.
+        assert!(!should_notify(
+            NotifyRule::Completion,
+            Some(&seen(2, "success")),
+            &done,
+            None
+        ));
+        // A new pipeline that finished between polls still notifies.
+        assert!(should_notify(
+            NotifyRule::Completion,
+            Some(&seen(1, "success")),
+            &done,
+            None
+        ));
+    }
+
+    #[test]
+    fn rules_filter_by_status_and_branch() {
+        let prev = seen(1, "success");
+        let failed = pipeline(2, "failed", "feature");
+        let passed = pipeline(2, "success", "main");
+
+        assert!(should_notify(
+            NotifyRule::Failures,
+            Some(&prev),
+            &failed,
+            None
+        ));
+        assert!(!should_notify(
+            NotifyRule::Failures,
+            Some(&prev),
+            &passed,
+            None
+        ));
+
+        assert!(should_notify(
+            NotifyRule::DefaultBranch,
+            Some(&prev),
+            &passed,
+            Some("main")
+        ));
+        assert!(!should_notify(
+            NotifyRule::DefaultBranch,
+            Some(&prev),
+            &failed,
+            Some("main")
+        ));
+        assert!(!should_notify(
+            NotifyRule::DefaultBranch,
+            Some(&prev),
+            &passed,
+            None
+        ));
+    }
+
+    #[test]
+    fn failed_job_names_skip_allowed_failures_and_retries() {
+        let jobs: Vec<GitLabJob> = [
+            ("lint", "failed", true),
+            ("test", "failed", false),
+            ("test", "failed", false),
+            ("build", "success", false),
+            ("e2e", "failed", false),
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, (name, status, allow_failure))| {
+            serde_json::from_value(serde_json::json!({
+                "id": i,
+                "name": name,
+                "stage": "test",
+                "status": status,
+                "ref": "main",
+                "created_at": "2026-01-01T00:00:00Z",
+                "web_url": "http://x",
+                "allow_failure": allow_failure,
+            }))
+            .unwrap()
+        })
+        .collect();
+
+        assert_eq!(failed_job_names(&jobs), vec!["test", "e2e"]);
+    }
+
+    #[tokio::test]
+    async fn set_rule_updates_dashboard_projects_only() {
+        let (pool, inst) = seed_instance(true).await;
+        sqlx::query(
+            "INSERT INTO projects (id, instance_id, name, name_with_namespace, path_with_namespace, web_url)
+             VALUES (10, ?, 'proj', 'group/proj', 'group/proj', 'http://x/group/proj')",
+        )
+        .bind(inst)
+        .execute(&pool)
+        .await
+        .unwrap();
+        pipeline_project::upsert_pipeline_project(&pool, 10, inst)
+            .await
+            .unwrap();
+
+        let listed = pipeline_project::list_pipeline_projects(&pool, inst)
+            .await
+            .unwrap();
+        assert_eq!(listed[0].notify_rule, "completion");
+
+        set_rule(&pool, inst, 10, NotifyRule::Failures)
+            .await
+            .unwrap();
+        let listed = pipeline_project::list_pipeline_projects(&pool, inst)
+            .await
+            .unwrap();
+        assert_eq!(listed[0].notify_rule, "failures");
+
+        assert!(set_rule(&pool, inst, 11, NotifyRule::Failures)
+            .await
+            .is_err());
+
+        assert!(!listed[0].notify_manual_gates);
+        set_manual_gates(&pool, inst, 10, true).await.unwrap();
+        let listed = pipeline_project::list_pipeline_projects(&pool, inst)
+            .await
+            .unwrap();
+        assert!(listed[0].notify_manual_gates);
+        assert!(set_manual_gates(&pool, inst, 11, true).await.is_err());
+    }
+}
@@ -5,12 +5,15 @@
 //! maps these into camelCase DTOs; the CLI uses them directly.
 
 use crate::core::create_client;
-use crate::db::pipeline_cache;
+use crate::db::job_trace_cache::{self, CachedJobTrace, JobTraceSource};
+use crate::db::pipeline_cache::{self, CachedPipelineSchedule};
 use crate::db::pool::DbPool;
 use crate::error::AppError;
 use crate::models::pipeline_project::{self, PipelineProject};
 use crate::models::project::{self, Project};
-use crate::services::gitlab_client::{GitLabJob, GitLabPipeline};
+use crate::services::gitlab_client::{
+    GitLabClient, GitLabJob, GitLabPipeline, GitLabPipelineSchedule, JobTraceBytes, TraceRange,
+};
 use futures::future::join_all;
 use std::collections::HashSet;
 
@@ -258,6 +261,81 @@ pub async fn cancel_pipeline(
         .await
 }
 
+fn to_cached_schedule(project_id: i64, s: GitLabPipelineSchedule) -> CachedPipelineSchedule {
+    CachedPipelineSchedule {
+        schedule_id: s.id,
+        project_id,
+        description: s.description,
+        ref_name: s
+            .ref_name
+            .strip_prefix("refs/heads/")
+            .or_else(|| s.ref_name.strip_prefix("refs/tags/"))
+            .map(str::to_string)
+            .unwrap_or(s.ref_name),
+        cron: s.cron,
+        cron_timezone: s.cron_timezone,
+        next_run_at: s.next_run_at,
+        active: s.active,
+        owner_username: s.owner.map(|o| o.username),
+    }
+}
+
+/// Cached pipeline schedules for a project (instant display, offline).
+pub async fn cached_schedules(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<Vec<CachedPipelineSchedule>, AppError> {
+    Ok(pipeline_cache::get_cached_pipeline_schedules(pool, instance_id, project_id).await?)
+}
+
+/// Fetch a project's pipeline schedules and replace the cached list.
+pub async fn list_schedules(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<Vec<CachedPipelineSchedule>, AppError> {
+    let client = create_client(pool, instance_id).await?;
+    let schedules: Vec<CachedPipelineSchedule> = client
+        .get_pipeline_schedules(project_id)
+        .await?
+        .into_iter()
+        .map(|s| to_cached_schedule(project_id, s))
+        .collect();
+    pipeline_cache::replace_pipeline_schedules(pool, instance_id, project_id, &schedules).await?;
+    cached_schedules(pool, instance_id, project_id).await
+}
+
+/// Trigger a pipeline for a schedule right away.
+pub async fn run_schedule(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+    schedule_id: i64,
+) -> Result<(), AppError> {
+    create_client(pool, instance_id)
+        .await?
+        .play_pipeline_schedule(project_id, schedule_id)
+        .await
+}
+
+/// Activate or deactivate a schedule and update the cache.
+pub async fn set_schedule_active(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+    schedule_id: i64,
+    active: bool,
+) -> Result<CachedPipelineSchedule, AppError> {
+    let updated = create_client(pool, instance_id)
+        .await?
+        .set_pipeline_schedule_active(project_id, schedule_id, active)
+        .await?;
+    let schedule = to_cached_schedule(project_id, updated);
+    pipeline_cache::upsert_pipeline_schedule(pool, instance_id, &schedule).await?;
+    Ok(schedule)
+}
+
 /// Pipelines attached to a merge request (resolves the local `mr_id` to API ids).
 pub async fn mr_pipelines(pool: &DbPool, mr_id: i64) -> Result<Vec<GitLabPipeline>, AppError> {
     let (instance_id, project_id, mr_iid) =
@@ -266,6 +344,183 @@ pub async fn mr_pipelines(pool: &DbPool, mr_id: i64) -> Result<Vec<GitLabPipelin
     client.get_mr_pipelines(project_id, mr_iid).await
 }
 
+/// Bytes of each failed job's log kept by the trace prefetch. Errors are
+/// almost always at the end, and whole logs can run to tens of megabytes.
+pub const TRACE_CACHE_BYTES: usize = 64 * 1024;
+
+/// Failed jobs per pipeline whose traces are prefetched.
+const MAX_PREFETCHED_JOBS: usize = 10;
+
+/// The last `limit` bytes of `trace`, starting at a line boundary when one
+/// is available, plus the number of bytes cut from the front.
+pub fn trace_tail(trace: &str, limit: usize) -> (&str, usize) {
+    if trace.len() <= limit {
+        return (trace, 0);
+    }
+    let mut start = trace.len() - limit;
+    while !trace.is_char_boundary(start) {
+        start += 1;
+    }
+    if let Some(nl) = trace[start..].find('\n') {
+        if nl + 1 < trace.len() - start {
+            start += nl + 1;
+        }
+    }
+    (&trace[start..], start)
+}
+
+/// Cache the log tails of the failed jobs in an MR's head pipeline.
+///
+/// Skips the work when the head pipeline didn't fail or its traces were
+/// already cached, so repeated syncs cost one pipelines request per MR.
+/// Returns the number of traces stored.
+pub async fn prefetch_failed_traces(
+    pool: &DbPool,
+    client: &GitLabClient,
+    instance_id: i64,
+    mr_id: i64,
+    project_id: i64,
+    mr_iid: i64,
+    now: i64,
+) -> Result<usize, AppError> {
+    let pipelines = client.get_mr_pipelines(project_id, mr_iid).await?;
+    let Some(head) = pipelines.first() else {
+        return Ok(0);
+    };
+    if head.status != "failed"
+        || job_trace_cache::has_pipeline_traces(pool, instance_id, head.id).await?
+    {
+        return Ok(0);
+    }
+
+    let jobs = client.get_pipeline_jobs(project_id, head.id).await?;
+    let mut stored = 0;
+    for job in jobs
+        .iter()
+        .filter(|j| j.status == "failed")
+        .take(MAX_PREFETCHED_JOBS)
+    {
+        let trace = client.get_job_trace(project_id, job.id).await?;
+        let (tail, omitted) = trace_tail(&trace, TRACE_CACHE_BYTES);
+        let source = JobTraceSource {
+            instance_id,
+            project_id,
+            pipeline_id: head.id,
+            mr_id,
+            job_id: job.id,
+        };
+        let cached = CachedJobTrace {
+            trace: tail.to_string(),
+            omitted_bytes: omitted as i64,
+        };
+        job_trace_cache::save_job_trace(pool, &source, &cached, now).await?;
+        stored += 1;
+    }
+    Ok(stored)
+}
+
+/// Bytes of a job's log the viewer loads at first, from the end.
+pub const TRACE_WINDOW_BYTES: u64 = 256 * 1024;
+
+/// Largest range [`job_trace_range`] fetches at once.
+pub const MAX_TRACE_RANGE_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Part of a job's log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceChunk {
+    pub content: String,
+    /// Where `content` starts in the whole log; 0 when nothing is before it.
+    pub offset: u64,
+    /// Size of the whole log.
+    pub total_bytes: u64,
+}
+
+/// Decode a fetched log range so it starts at a line boundary.
+///
+/// A range that doesn't start the log probably starts mid-line, so its first
+/// line is dropped and `offset` moves past it; the next earlier range ends
+/// there and picks it up. Without a usable newline only a split character
+/// is dropped.
+pub fn trace_chunk(range: JobTraceBytes) -> TraceChunk {
+    let bytes = &range.bytes;
+    let mut skip = 0;
+    if range.offset > 0 {
+        match bytes.iter().position(|&b| b == b'\n') {
+            Some(nl) if nl + 1 < bytes.len() => skip = nl + 1,
+            _ => {
+                while skip < bytes.len() && bytes[skip] & 0xC0 == 0x80 {
+                    skip += 1;
+                }
+            }
+        }
+    }
+    TraceChunk {
+        content: String::from_utf8_lossy(&bytes[skip..]).into_owned(),
+        offset: range.offset + skip as u64,
+        total_bytes: range.total,
+    }
+}
+
+/// The end of a job's log: the prefetched tail when cached, otherwise the
+/// last [`TRACE_WINDOW_BYTES`] fetched live.
+pub async fn job_trace(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+    job_id: i64,
+) -> Result<TraceChunk, AppError> {
+    if let Some(cached) =
+        job_trace_cache::get_job_trace(pool, instance_id, project_id, job_id).await?
+    {
+        let offset = cached.omitted_bytes as u64;
+        return Ok(TraceChunk {
+            offset,
+            total_bytes: offset + cached.trace.len() as u64,
+            content: cached.trace,
+        });
+    }
+    let client = create_client(pool, instance_id).await?;
+    let range = client
+        .get_job_trace_range(project_id, job_id, TraceRange::Tail(TRACE_WINDOW_BYTES))
+        .await?;
+    Ok(trace_chunk(range))
+}
+
+/// `length` bytes of a job's log from `offset`, for loading output earlier
+/// than what [`job_trace`] returned. Always fetched live.
+pub async fn job_trace_range(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+    job_id: i64,
+    offset: i64,
+    length: i64,
+) -> Result<TraceChunk, AppError> {
+    let Ok(offset) = u64::try_from(offset) else {
+        return Err(AppError::invalid_input_field(
+            "Offset must not be negative",
+            "offset",
+        ));
+    };
+    let length = match u64::try_from(length) {
+        Ok(n) if (1..=MAX_TRACE_RANGE_BYTES).contains(&n) => n,
+        _ => {
+            return Err(AppError::invalid_input_field(
+                format!(
+                    "Length must be between 1 and {} bytes",
+                    MAX_TRACE_RANGE_BYTES
+                ),
+                "length",
+            ))
+        }
+    };
+    let client = create_client(pool, instance_id).await?;
+    let range = client
+        .get_job_trace_range(project_id, job_id, TraceRange::Span { offset, length })
+        .await?;
+    Ok(trace_chunk(range))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -358,6 +613,55 @@ mod tests {
         assert_eq!(got[0].duration, Some(42));
     }
 
+    #[tokio::test]
+    async fn schedule_cache_is_replaced_per_project() {
+        let (_dir, pool, inst) = seed(true).await;
+        let schedule = |id: i64, project_id: i64, description: &str| CachedPipelineSchedule {
+            schedule_id: id,
+            project_id,
+            description: description.to_string(),
+            ref_name: "main".to_string(),
+            cron: "0 2 * * *".to_string(),
+            cron_timezone: "UTC".to_string(),
+            next_run_at: None,
+            active: true,
+            owner_username: Some("me".to_string()),
+        };
+        pipeline_cache::replace_pipeline_schedules(
+            &pool,
+            inst,
+            10,
+            &[schedule(1, 10, "nightly"), schedule(2, 10, "Audit")],
+        )
+        .await
+        .unwrap();
+        pipeline_cache::replace_pipeline_schedules(&pool, inst, 11, &[schedule(3, 11, "weekly")])
+            .await
+            .unwrap();
+
+        let got = cached_schedules(&pool, inst, 10).await.unwrap();
+        assert_eq!(
+            got.iter()
+                .map(|s| s.description.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Audit", "nightly"]
+        );
+
+        // A refetch drops schedules deleted upstream without touching other projects.
+        pipeline_cache::replace_pipeline_schedules(&pool, inst, 10, &[schedule(1, 10, "nightly")])
+            .await
+            .unwrap();
+        assert_eq!(cached_schedules(&pool, inst, 10).await.unwrap().len(), 1);
+        assert_eq!(cached_schedules(&pool, inst, 11).await.unwrap().len(), 1);
+
+        let mut paused = schedule(1, 10, "nightly");
+        paused.active = false;
+        pipeline_cache::upsert_pipeline_schedule(&pool, inst, &paused)
+            .await
+            .unwrap();
+        assert!(!cached_schedules(&pool, inst, 10).await.unwrap()[0].active);
+    }
+
     #[tokio::test]
     async fn search_matches_local_by_namespace() {
         // seed(true) inserts project id=10 with name_with_namespace='group/proj'
@@ -381,4 +685,112 @@ mod tests {
             "did not expect project id=10 in results for 'zzznomatch'"
         );
     }
+
+    #[test]
+    fn trace_tail_keeps_whole_lines_at_the_end() {
+        assert_eq!(trace_tail("short", 64), ("short", 0));
+
+        let log = "first line\nsecond line\nerror: boom\n";
+        let (tail, omitted) = trace_tail(log, 18);
+        assert_eq!(tail, "error: boom\n");
+        assert_eq!(omitted, log.len() - tail.len());
+
+        // Never splits a multi-byte character, even without a newline.
+        let (tail, omitted) = trace_tail("ééééé", 3);
+        assert_eq!(tail, "é");
+        assert_eq!(omitted, 8);
+    }
+
+    #[test]
+    fn trace_chunks_start_at_line_boundaries() {
+        let bytes = |offset: u64, data: &[u8]| JobTraceBytes {
+            bytes: data.to_vec(),
+            offset,
+            total: 100,
+        };
+
+        // The start of the log is kept whole.
+        let chunk = trace_chunk(bytes(0, b"one\ntwo\n"));
+        assert_eq!((chunk.content.as_str(), chunk.offset), ("one\ntwo\n", 0));
+
+        // A partial first line is dropped and the offset moves past it.
+        let chunk = trace_chunk(bytes(40, b"ne\ntwo\n"));
+        assert_eq!((chunk.content.as_str(), chunk.offset), ("two\n", 43));
+        assert_eq!(chunk.total_bytes, 100);
+
+        // One long line: only the split character goes.
+        let chunk = trace_chunk(bytes(40, "\u{e9}\u{e9}x".as_bytes()[1..].as_ref()));
+        assert_eq!((chunk.content.as_str(), chunk.offset), ("\u{e9}x", 41));
+    }
+
+    #[tokio::test]
+    async fn job_trace_range_validates_bounds() {
+        let (_dir, pool, inst) = seed(true).await;
+        for (offset, length) in [(-1, 10), (0, 0), (0, MAX_TRACE_RANGE_BYTES as i64 + 1)] {
+            let err = job_trace_range(&pool, inst, 10, 7, offset, length)
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(err, AppError::InvalidInput { .. }),
+                "{offset}+{length}: {err:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn job_trace_serves_cached_tail_first() {
+        let (_dir, pool, inst) = seed(true).await;
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, project_name, title, author_username,
+              source_branch, target_branch, state, web_url, created_at, updated_at,
+              labels, reviewers, cached_at)
+             VALUES (1, ?, 1, 10, 'group/proj', 't', 'me', 's', 'main', 'opened', 'http://x', 0, 0, '[]', '[]', 0)",
+        )
+        .bind(inst)
+        .execute(&pool)
+        .await
+        .unwrap();
+        let source = JobTraceSource {
+            instance_id: inst,
+            project_id: 10,
+            pipeline_id: 500,
+            mr_id: 1,
+            job_id: 7,
+        };
+        let cached = CachedJobTrace {
+            trace: "error: boom\n".to_string(),
+            omitted_bytes: 4096,
+        };
+        job_trace_cache::save_job_trace(&pool, &source, &cached, 100)
+            .await
+            .unwrap();
+        assert!(job_trace_cache::has_pipeline_traces(&pool, inst, 500)
+            .await
+            .unwrap());
+
+        // The fake instance URL is unreachable, so this only passes from cache.
+        let trace = job_trace(&pool, inst, 10, 7).await.unwrap();
+        assert_eq!(
+            trace,
+            TraceChunk {
+                content: "error: boom\n".to_string(),
+                offset: 4096,
+                total_bytes: 4108,
+            }
+        );
+
+        assert_eq!(
+            job_trace_cache::prune_job_traces(&pool, 50).await.unwrap(),
+            0
+        );
+        assert_eq!(
+            job_trace_cache::prune_job_traces(&pool, 200).await.unwrap(),
+            1
+        );
+        assert!(job_trace_cache::get_job_trace(&pool, inst, 10, 7)
+            .await
+            .unwrap()
+            .is_none());
+    }
 }
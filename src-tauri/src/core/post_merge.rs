@@ -0,0 +1,339 @@
+//! Post-merge cleanup for the user's own merged MRs.
+//!
+//! Only MRs the user authored or merged qualify. Each task runs
+//! independently and reports its own outcome, so a failure (e.g. no
+//! permission to run pipelines) doesn't stop the remaining tasks.
+
+use crate::core::{authenticated_username, create_client};
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use crate::services::gitlab_client::{GitLabClient, IssueUpdate};
+use serde::{Deserialize, Serialize};
+
+/// A cleanup step the user can opt into after a merge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PostMergeTask {
+    /// Delete the source branch unless GitLab already removed it on merge.
+    DeleteSourceBranch,
+    /// Close issues referenced with `Closes #N` that are still open (GitLab
+    /// only auto-closes them when merging into the default branch).
+    CloseLinkedIssues,
+    /// Start a new pipeline on the target branch, e.g. for deployments.
+    TriggerPipeline,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PostMergeTaskStatus {
+    Done,
+    /// Nothing to do (branch already deleted, no open linked issues).
+    Skipped,
+    /// Some of the work was done; the message says what failed.
+    Partial,
+    Failed,
+}
+
+/// Outcome of a single post-merge task.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostMergeTaskResult {
+    pub task: PostMergeTask,
+    pub status: PostMergeTaskStatus,
+    pub message: String,
+}
+
+impl PostMergeTaskResult {
+    fn new(task: PostMergeTask, status: PostMergeTaskStatus, message: impl Into<String>) -> Self {
+        Self {
+            task,
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+/// Run the requested cleanup tasks for a merged MR, in the order given.
+///
+/// Errors only when the MR isn't cached, isn't merged, wasn't authored or
+/// merged by the user, or no client can be built; per-task failures are
+/// reported in the results instead.
+pub async fn run_post_merge_tasks(
+    pool: &DbPool,
+    mr_id: i64,
+    tasks: &[PostMergeTask],
+) -> Result<Vec<PostMergeTaskResult>, AppError> {
+    let (instance_id, project_id, iid, state, author, source_branch, target_branch): (
+        i64,
+        i64,
+        i64,
+        String,
+        String,
+        String,
+        String,
+    ) = sqlx::query_as(
+        "SELECT instance_id, project_id, iid, state, author_username, source_branch, \
+         target_branch FROM merge_requests WHERE id = ?",
+    )
+    .bind(mr_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::not_found_with_id("MergeRequest", mr_id.to_string()))?;
+
+    if state != "merged" {
+        return Err(AppError::invalid_input(format!(
+            "Post-merge tasks require a merged MR (state is '{}')",
+            state
+        )));
+    }
+
+    let client = create_client(pool, instance_id).await?;
+    let me = authenticated_username(pool, instance_id).await?;
+    if me.as_deref() != Some(author.as_str()) {
+        // The cache doesn't know who merged; ask GitLab.
+        let merged_by = client.get_merge_request(project_id, iid).await?.merged_by;
+        if me.is_none() || merged_by.map(|u| u.username) != me {
+            return Err(AppError::invalid_input(
+                "Post-merge tasks only run for MRs you authored or merged",
+            ));
+        }
+    }
+    let mut results = Vec::with_capacity(tasks.len());
+    for &task in tasks {
+        let outcome = match task {
+            PostMergeTask::DeleteSourceBranch => {
+                delete_source_branch(&client, project_id, &source_branch).await
+            }
+            PostMergeTask::CloseLinkedIssues => close_linked_issues(&client, project_id, iid).await,
+            PostMergeTask::TriggerPipeline => client
+                .create_pipeline(project_id, &target_branch)
+                .await
+                .map(|p| {
+                    (
+                        PostMergeTaskStatus::Done,
+                        format!("Started pipeline #{} on {}", p.id, target_branch),
+                    )
+                }),
+        };
+        results.push(match outcome {
+            Ok((status, message)) => PostMergeTaskResult::new(task, status, message),
+            Err(e) => PostMergeTaskResult::new(task, PostMergeTaskStatus::Failed, e.to_string()),
+        });
+    }
+    Ok(results)
+}
+
+async fn delete_source_branch(
+    client: &GitLabClient,
+    project_id: i64,
+    branch: &str,
+) -> Result<(PostMergeTaskStatus, String), AppError> {
+    if !client.branch_exists(project_id, branch).await? {
+        return Ok((
+            PostMergeTaskStatus::Skipped,
+            format!("Branch {} was already deleted", branch),
+        ));
+    }
+    client.delete_branch(project_id, branch).await?;
+    Ok((
+        PostMergeTaskStatus::Done,
+        format!("Deleted branch {}", branch),
+    ))
+}
+
+async fn close_linked_issues(
+    client: &GitLabClient,
+    project_id: i64,
+    mr_iid: i64,
+) -> Result<(PostMergeTaskStatus, String), AppError> {
+    let open: Vec<_> = client
+        .get_mr_closes_issues(project_id, mr_iid)
+        .await?
+        .into_iter()
+        .filter(|issue| issue.state == "opened")
+        .collect();
+    if open.is_empty() {
+        return Ok((
+            PostMergeTaskStatus::Skipped,
+            "No open linked issues".to_string(),
+        ));
+    }
+
+    let close = IssueUpdate {
+        state_event: Some("close".to_string()),
+        ..Default::default()
+    };
+    let mut closed = Vec::new();
+    let mut failed = Vec::new();
+    for issue in &open {
+        match client
+            .update_issue(issue.project_id, issue.iid, &close)
+            .await
+        {
+            Ok(_) => closed.push(issue.iid),
+            Err(e) => failed.push((issue.iid, e.to_string())),
+        }
+    }
+    Ok(close_summary(&closed, &failed))
+}
+
+/// Status and message for closing linked issues, naming each failure.
+fn close_summary(closed: &[i64], failed: &[(i64, String)]) -> (PostMergeTaskStatus, String) {
+    let list = |iids: &[i64]| {
+        iids.iter()
+            .map(|iid| format!("#{}", iid))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let failures = failed
+        .iter()
+        .map(|(iid, error)| format!("failed #{}: {}", iid, error))
+        .collect::<Vec<_>>()
+        .join("; ");
+    match (closed.is_empty(), failed.is_empty()) {
+        (_, true) => (
+            PostMergeTaskStatus::Done,
+            format!("Closed {}", list(closed)),
+        ),
+        (true, false) => (PostMergeTaskStatus::Failed, failures),
+        (false, false) => (
+            PostMergeTaskStatus::Partial,
+            format!("Closed {}; {}", list(closed), failures),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_task_names_are_snake_case() {
+        let tasks: Vec<PostMergeTask> = serde_json::from_str(
+            r#"["delete_source_branch","close_linked_issues","trigger_pipeline"]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            tasks,
+            vec![
+                PostMergeTask::DeleteSourceBranch,
+                PostMergeTask::CloseLinkedIssues,
+                PostMergeTask::TriggerPipeline
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unmerged_mr() {
+        let dir = tempdir().unwrap();
+        let pool = db::initialize(&dir.path().join("t.db")).await.unwrap();
+        sqlx::query(
+            "INSERT INTO gitlab_instances (id, url, token, created_at) VALUES (1, 'u', 't', 0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, project_name, title, author_username,
+              source_branch, target_branch, state, web_url, created_at, updated_at,
+              labels, reviewers, cached_at)
+             VALUES (1, 1, 1, 10, 'g/p', 't', 'me', 's', 'main', 'opened', 'http://x', 0, 0, '[]', '[]', 0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let err = run_post_merge_tasks(&pool, 1, &[PostMergeTask::DeleteSourceBranch])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::InvalidInput { .. }));
+    }
+
+    #[test]
+    fn test_close_summary_names_each_failure() {
+        let err = "GitLab API error: 403".to_string();
+        assert_eq!(
+            close_summary(&[1, 3], &[]),
+            (PostMergeTaskStatus::Done, "Closed #1, #3".to_string())
+        );
+        assert_eq!(
+            close_summary(&[1], &[(2, err.clone())]),
+            (
+                PostMergeTaskStatus::Partial,
+                "Closed #1; failed #2: GitLab API error: 403".to_string()
+            )
+        );
+        assert_eq!(
+            close_summary(&[], &[(2, err)]),
+            (
+                PostMergeTaskStatus::Failed,
+                "failed #2: GitLab API error: 403".to_string()
+            )
+        );
+    }
+
+    /// Answer one request with a JSON `body`; returns the base URL.
+    async fn serve_json_once(body: &'static str) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut head = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = socket.read(&mut buf).await.unwrap();
+                head.extend_from_slice(&buf[..n]);
+            }
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn test_rejects_mr_neither_authored_nor_merged_by_user() {
+        let url = serve_json_once(
+            r#"{"id":1,"iid":1,"project_id":10,"title":"t","description":null,
+                "state":"merged","web_url":"http://x","source_branch":"s",
+                "target_branch":"main","created_at":"2026-01-01T00:00:00Z",
+                "updated_at":"2026-01-01T00:00:00Z","merged_at":"2026-01-01T00:00:00Z",
+                "author":{"id":2,"username":"alice","name":"Alice","avatar_url":null},
+                "merged_by":{"id":3,"username":"bob","name":"Bob","avatar_url":null},
+                "labels":[]}"#,
+        )
+        .await;
+        let dir = tempdir().unwrap();
+        let pool = db::initialize(&dir.path().join("t.db")).await.unwrap();
+        sqlx::query(
+            "INSERT INTO gitlab_instances (id, url, token, created_at, authenticated_username)
+             VALUES (1, ?, 't', 0, 'me')",
+        )
+        .bind(&url)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, project_name, title, author_username,
+              source_branch, target_branch, state, web_url, created_at, updated_at,
+              labels, reviewers, cached_at)
+             VALUES (1, 1, 1, 10, 'g/p', 't', 'alice', 's', 'main', 'merged', 'http://x', 0, 0, '[]', '[]', 0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let err = run_post_merge_tasks(&pool, 1, &[PostMergeTask::DeleteSourceBranch])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::InvalidInput { .. }), "{err:?}");
+    }
+}
@@ -0,0 +1,167 @@
+//! Project README lookup for the MR detail context panel.
+//!
+//! The README is looked up at the MR's target branch, rendered to HTML by the
+//! instance's own Markdown renderer (so relative links and images resolve
+//! against the project) and cached per `(project, ref)`. Projects without a
+//! README are cached too, with no file path, so they aren't re-checked on
+//! every open.
+
+use crate::core::create_client;
+use crate::db::pool::DbPool;
+use crate::db::project_readme::{self, CachedReadme};
+use crate::error::AppError;
+use crate::services::gitlab_client::GitLabTreeEntry;
+
+/// Cached READMEs older than this are refreshed in the background.
+pub const STALE_THRESHOLD_SECS: i64 = 24 * 60 * 60;
+
+const MARKDOWN_EXTENSIONS: &[&str] = &["md", "markdown", "mdown", "mkd"];
+
+fn is_markdown(path: &str) -> bool {
+    path.rsplit_once('.')
+        .is_some_and(|(_, ext)| MARKDOWN_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+/// Pick the README among a repository's top-level entries, preferring
+/// Markdown over other formats and `README.md` over `readme.md`.
+fn pick_readme(entries: &[GitLabTreeEntry]) -> Option<&GitLabTreeEntry> {
+    entries
+        .iter()
+        .filter(|e| e.entry_type == "blob")
+        .filter(|e| {
+            let stem = e.name.split('.').next().unwrap_or_default();
+            stem.eq_ignore_ascii_case("readme")
+        })
+        .min_by_key(|e| (!is_markdown(&e.name), e.name != "README.md", e.name.len()))
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Whether a cached README should be refreshed.
+pub fn is_stale(readme: &CachedReadme, now: i64) -> bool {
+    now - readme.fetched_at >= STALE_THRESHOLD_SECS
+}
+
+/// Read the cached README without touching the network.
+pub async fn cached_readme(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+    ref_name: &str,
+) -> Result<Option<CachedReadme>, AppError> {
+    Ok(project_readme::get_cached_readme(pool, instance_id, project_id, ref_name).await?)
+}
+
+/// Fetch the README at `ref_name`, render it to HTML and update the cache.
+///
+/// Markdown files go through GitLab's renderer; other formats (`README`,
+/// `README.txt`, `README.rst`, ...) are shown as preformatted text.
+pub async fn refresh_readme(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+    ref_name: &str,
+) -> Result<CachedReadme, AppError> {
+    let client = create_client(pool, instance_id).await?;
+    let entries = client.get_repository_root(project_id, ref_name).await?;
+
+    let (file_path, html) = match pick_readme(&entries) {
+        Some(entry) => {
+            let content = client
+                .get_file_content(project_id, &entry.path, ref_name)
+                .await?;
+            let html = if is_markdown(&entry.path) {
+                let project_path: Option<String> = sqlx::query_scalar(
+                    "SELECT path_with_namespace FROM projects WHERE id = ? AND instance_id = ?",
+                )
+                .bind(project_id)
+                .bind(instance_id)
+                .fetch_optional(pool)
+                .await?;
+                client
+                    .render_markdown(&content, project_path.as_deref())
+                    .await?
+            } else {
+                format!("<pre>{}</pre>", escape_html(&content))
+            };
+            (Some(entry.path.clone()), html)
+        }
+        None => (None, String::new()),
+    };
+
+    let readme = CachedReadme {
+        ref_name: ref_name.to_string(),
+        file_path,
+        html,
+        fetched_at: chrono::Utc::now().timestamp(),
+    };
+    project_readme::upsert_readme(pool, instance_id, project_id, &readme).await?;
+    Ok(readme)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+
+    fn entry(name: &str, entry_type: &str) -> GitLabTreeEntry {
+        GitLabTreeEntry {
+            name: name.to_string(),
+            path: name.to_string(),
+            entry_type: entry_type.to_string(),
+        }
+    }
+
+    #[test]
+    fn pick_readme_prefers_markdown() {
+        let entries = vec![
+            entry("readme", "tree"),
+            entry("README.txt", "blob"),
+            entry("readme.md", "blob"),
+            entry("README.md", "blob"),
+            entry("src", "tree"),
+        ];
+        assert_eq!(pick_readme(&entries).unwrap().name, "README.md");
+        assert_eq!(pick_readme(&entries[..3]).unwrap().name, "readme.md");
+        assert_eq!(pick_readme(&entries[..2]).unwrap().name, "README.txt");
+        assert!(pick_readme(&entries[4..]).is_none());
+    }
+
+    #[tokio::test]
+    async fn readme_cache_is_kept_per_ref() {
+        let (pool, id) = seed_instance(true).await;
+        let main = CachedReadme {
+            ref_name: "main".into(),
+            file_path: Some("README.md".into()),
+            html: "<h1>Hello</h1>".into(),
+            fetched_at: 1_000,
+        };
+        project_readme::upsert_readme(&pool, id, 7, &main)
+            .await
+            .unwrap();
+        let develop = CachedReadme {
+            ref_name: "develop".into(),
+            file_path: None,
+            html: String::new(),
+            fetched_at: 2_000,
+        };
+        project_readme::upsert_readme(&pool, id, 7, &develop)
+            .await
+            .unwrap();
+
+        let cached = cached_readme(&pool, id, 7, "main").await.unwrap().unwrap();
+        assert_eq!(cached, main);
+        assert!(!is_stale(&cached, 1_000 + STALE_THRESHOLD_SECS - 1));
+        assert!(is_stale(&cached, 1_000 + STALE_THRESHOLD_SECS));
+        assert_eq!(
+            cached_readme(&pool, id, 7, "develop").await.unwrap(),
+            Some(develop)
+        );
+        assert_eq!(cached_readme(&pool, id, 8, "main").await.unwrap(), None);
+    }
+}
@@ -0,0 +1,187 @@
+//! GitLab quick actions (`/approve`, `/label ~bug`, `/assign @user`, ...).
+//!
+//! Quick actions are executed by GitLab itself when a note containing them is
+//! posted, so comments are always sent verbatim. This module only exists so
+//! the UI can autocomplete the commands and preview what a comment will do
+//! before it is queued.
+
+use serde::Serialize;
+
+/// Static description of one merge-request quick action.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickActionSpec {
+    /// Canonical command name, without the leading slash.
+    pub name: &'static str,
+    /// Alternative spellings GitLab accepts for the same command.
+    pub aliases: &'static [&'static str],
+    /// Argument hint shown in autocomplete (empty when the command takes none).
+    pub params: &'static str,
+    pub description: &'static str,
+}
+
+/// Quick actions GitLab supports on merge request notes.
+#[rustfmt::skip]
+pub const QUICK_ACTIONS: &[QuickActionSpec] = &[
+    QuickActionSpec { name: "approve", aliases: &[], params: "", description: "Approve the merge request" },
+    QuickActionSpec { name: "unapprove", aliases: &[], params: "", description: "Remove your approval" },
+    QuickActionSpec { name: "assign", aliases: &[], params: "@user1 @user2", description: "Add assignees" },
+    QuickActionSpec { name: "unassign", aliases: &["remove_assignee"], params: "@user1 @user2", description: "Remove assignees (all when no users are given)" },
+    QuickActionSpec { name: "assign_reviewer", aliases: &["reviewer", "request_review"], params: "@user1 @user2", description: "Request review from users" },
+    QuickActionSpec { name: "unassign_reviewer", aliases: &["remove_reviewer"], params: "@user1 @user2", description: "Remove reviewers (all when no users are given)" },
+    QuickActionSpec { name: "label", aliases: &["labels"], params: "~label1 ~label2", description: "Add labels" },
+    QuickActionSpec { name: "unlabel", aliases: &["remove_label"], params: "~label1 ~label2", description: "Remove labels (all when none are given)" },
+    QuickActionSpec { name: "relabel", aliases: &[], params: "~label1 ~label2", description: "Replace all labels" },
+    QuickActionSpec { name: "milestone", aliases: &[], params: "%milestone", description: "Set the milestone" },
+    QuickActionSpec { name: "remove_milestone", aliases: &[], params: "", description: "Remove the milestone" },
+    QuickActionSpec { name: "title", aliases: &[], params: "<title>", description: "Change the title" },
+    QuickActionSpec { name: "draft", aliases: &[], params: "", description: "Toggle draft status" },
+    QuickActionSpec { name: "ready", aliases: &[], params: "", description: "Mark as ready" },
+    QuickActionSpec { name: "rebase", aliases: &[], params: "", description: "Rebase the source branch onto the target branch" },
+    QuickActionSpec { name: "merge", aliases: &[], params: "", description: "Merge (when the pipeline succeeds, if one is running)" },
+    QuickActionSpec { name: "close", aliases: &[], params: "", description: "Close the merge request" },
+    QuickActionSpec { name: "reopen", aliases: &[], params: "", description: "Reopen the merge request" },
+    QuickActionSpec { name: "target_branch", aliases: &[], params: "<branch>", description: "Change the target branch" },
+    QuickActionSpec { name: "estimate", aliases: &["estimate_time"], params: "<1w 3d 2h 14m>", description: "Set the time estimate" },
+    QuickActionSpec { name: "spend", aliases: &["spent", "spend_time"], params: "<time> [<date>]", description: "Add or subtract time spent" },
+    QuickActionSpec { name: "remove_estimate", aliases: &["remove_time_estimate"], params: "", description: "Remove the time estimate" },
+    QuickActionSpec { name: "remove_time_spent", aliases: &[], params: "", description: "Remove all time spent" },
+    QuickActionSpec { name: "cc", aliases: &[], params: "@user", description: "Mention a user" },
+    QuickActionSpec { name: "award", aliases: &["react"], params: ":emoji:", description: "Add an emoji reaction" },
+    QuickActionSpec { name: "subscribe", aliases: &[], params: "", description: "Subscribe to notifications" },
+    QuickActionSpec { name: "unsubscribe", aliases: &[], params: "", description: "Unsubscribe from notifications" },
+    QuickActionSpec { name: "todo", aliases: &[], params: "", description: "Add a to-do item" },
+    QuickActionSpec { name: "done", aliases: &[], params: "", description: "Mark the to-do item as done" },
+    QuickActionSpec { name: "lock", aliases: &[], params: "", description: "Lock the discussion" },
+    QuickActionSpec { name: "unlock", aliases: &[], params: "", description: "Unlock the discussion" },
+    QuickActionSpec { name: "copy_metadata", aliases: &[], params: "<!merge_request>", description: "Copy labels and milestone from another merge request" },
+    QuickActionSpec { name: "submit_review", aliases: &[], params: "", description: "Submit pending review comments" },
+];
+
+/// A quick action found in a comment body.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParsedQuickAction {
+    /// Canonical command name (aliases are resolved).
+    pub name: String,
+    /// Everything after the command on its line, trimmed.
+    pub args: String,
+    /// Human-readable summary of what GitLab will do.
+    pub effect: String,
+}
+
+/// Result of scanning a comment body for quick actions.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickActionPreview {
+    pub actions: Vec<ParsedQuickAction>,
+    /// True when the body is nothing but quick actions. GitLab executes them
+    /// without creating a visible note in that case.
+    pub commands_only: bool,
+}
+
+fn find_spec(name: &str) -> Option<&'static QuickActionSpec> {
+    QUICK_ACTIONS
+        .iter()
+        .find(|spec| spec.name == name || spec.aliases.contains(&name))
+}
+
+/// Describe one action's effect, including its arguments where helpful.
+fn describe(spec: &QuickActionSpec, args: &str) -> String {
+    if args.is_empty() {
+        return spec.description.to_string();
+    }
+    match spec.name {
+        "title" => format!("Change the title to \"{}\"", args),
+        "target_branch" => format!("Change the target branch to {}", args),
+        "milestone" => format!("Set the milestone to {}", args),
+        "estimate" => format!("Set the time estimate to {}", args),
+        "spend" => format!("Log {} of time spent", args),
+        _ => format!("{}: {}", spec.description, args),
+    }
+}
+
+/// Find the quick actions GitLab will execute for `body`.
+///
+/// Mirrors GitLab's extraction rules closely enough for a preview: a command
+/// must start its own line, and lines inside fenced code blocks or quotes are
+/// ignored. Unknown `/words` are ordinary text and are not reported.
+pub fn parse_quick_actions(body: &str) -> QuickActionPreview {
+    let mut actions = Vec::new();
+    let mut has_text = false;
+    let mut in_fence = false;
+
+    for line in body.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            has_text = true;
+            continue;
+        }
+        if trimmed.is_empty() {
+            continue;
+        }
+        if in_fence || !line.starts_with('/') {
+            has_text = true;
+            continue;
+        }
+
+        let rest = &line[1..];
+        let (name, args) = match rest.split_once(char::is_whitespace) {
+            Some((name, args)) => (name, args.trim()),
+            None => (rest.trim_end(), ""),
+        };
+        match find_spec(&name.to_lowercase()) {
+            Some(spec) => actions.push(ParsedQuickAction {
+                name: spec.name.to_string(),
+                args: args.to_string(),
+                effect: describe(spec, args),
+            }),
+            None => has_text = true,
+        }
+    }
+
+    QuickActionPreview {
+        commands_only: !actions.is_empty() && !has_text,
+        actions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_commands_and_resolves_aliases() {
+        let preview =
+            parse_quick_actions("Looks good!\n/approve\n/labels ~bug ~ui\n/reviewer @alice");
+        let names: Vec<&str> = preview.actions.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["approve", "label", "assign_reviewer"]);
+        assert_eq!(preview.actions[1].args, "~bug ~ui");
+        assert!(!preview.commands_only);
+    }
+
+    #[test]
+    fn test_commands_only_body() {
+        let preview = parse_quick_actions("/approve\n\n/merge");
+        assert_eq!(preview.actions.len(), 2);
+        assert!(preview.commands_only);
+    }
+
+    #[test]
+    fn test_ignores_code_fences_quotes_and_unknown_commands() {
+        let body = "```\n/approve\n```\n> /merge\n/usr/bin/env is a path\n not /close";
+        let preview = parse_quick_actions(body);
+        assert!(preview.actions.is_empty());
+        assert!(!preview.commands_only);
+    }
+
+    #[test]
+    fn test_describe_includes_arguments() {
+        let preview = parse_quick_actions("/title Fix the parser");
+        assert_eq!(
+            preview.actions[0].effect,
+            "Change the title to \"Fix the parser\""
+        );
+    }
+}
@@ -0,0 +1,239 @@
+//! Releases and tags of pipeline dashboard projects.
+//!
+//! Fetches go to GitLab and refresh the cache; the `cached_*` variants serve
+//! the last fetched lists for instant (and offline) display.
+
+use super::create_client;
+use crate::db::pool::DbPool;
+use crate::db::release_cache::{self, CachedRelease, CachedTag};
+use crate::error::AppError;
+use crate::services::gitlab_client::{GitLabRelease, GitLabReleaseLink, GitLabTag};
+use serde::{Deserialize, Serialize};
+
+/// Releases fetched per project; older ones stay one click away in GitLab.
+const RELEASE_LIMIT: u32 = 50;
+
+/// Tags fetched per project.
+const TAG_LIMIT: u32 = 50;
+
+/// Asset link of a release as stored in the cache and sent to the frontend.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReleaseLink {
+    pub name: String,
+    pub url: String,
+    pub link_type: Option<String>,
+}
+
+impl From<GitLabReleaseLink> for ReleaseLink {
+    fn from(l: GitLabReleaseLink) -> Self {
+        Self {
+            name: l.name,
+            url: l.url,
+            link_type: l.link_type,
+        }
+    }
+}
+
+/// Decode the `links` column of a cached release.
+pub fn release_links(release: &CachedRelease) -> Vec<ReleaseLink> {
+    serde_json::from_str(&release.links).unwrap_or_default()
+}
+
+fn to_cached_release(project_id: i64, r: GitLabRelease) -> CachedRelease {
+    let links: Vec<ReleaseLink> = r.assets.links.into_iter().map(Into::into).collect();
+    CachedRelease {
+        project_id,
+        tag_name: r.tag_name,
+        name: r.name,
+        description: r.description,
+        created_at: r.created_at,
+        released_at: r.released_at,
+        upcoming_release: r.upcoming_release,
+        author_username: r.author.map(|a| a.username),
+        commit_sha: r.commit.map(|c| c.id),
+        web_url: r.links.and_then(|l| l.self_url),
+        links: serde_json::to_string(&links).unwrap_or_else(|_| "[]".to_string()),
+    }
+}
+
+fn to_cached_tag(project_id: i64, t: GitLabTag) -> CachedTag {
+    let (commit_sha, commit_title, committed_at) = match t.commit {
+        Some(c) => (Some(c.id), Some(c.title), c.committed_date),
+        None => (None, None, None),
+    };
+    CachedTag {
+        project_id,
+        name: t.name,
+        message: t.message.filter(|m| !m.trim().is_empty()),
+        commit_sha,
+        commit_title,
+        committed_at,
+        protected: t.protected,
+        has_release: t.release.is_some(),
+    }
+}
+
+/// Cached releases for a project (instant display, offline).
+pub async fn cached_releases(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<Vec<CachedRelease>, AppError> {
+    Ok(release_cache::get_cached_releases(pool, instance_id, project_id).await?)
+}
+
+/// Fetch a project's recent releases and replace the cached list.
+pub async fn list_releases(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<Vec<CachedRelease>, AppError> {
+    let client = create_client(pool, instance_id).await?;
+    let releases: Vec<CachedRelease> = client
+        .get_project_releases(project_id, RELEASE_LIMIT)
+        .await?
+        .into_iter()
+        .map(|r| to_cached_release(project_id, r))
+        .collect();
+    release_cache::replace_releases(pool, instance_id, project_id, &releases).await?;
+    cached_releases(pool, instance_id, project_id).await
+}
+
+/// Cached tags for a project (instant display, offline).
+pub async fn cached_tags(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<Vec<CachedTag>, AppError> {
+    Ok(release_cache::get_cached_tags(pool, instance_id, project_id).await?)
+}
+
+/// Fetch a project's recent tags and replace the cached list.
+pub async fn list_tags(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<Vec<CachedTag>, AppError> {
+    let client = create_client(pool, instance_id).await?;
+    let tags: Vec<CachedTag> = client
+        .get_project_tags(project_id, TAG_LIMIT)
+        .await?
+        .into_iter()
+        .map(|t| to_cached_tag(project_id, t))
+        .collect();
+    release_cache::replace_tags(pool, instance_id, project_id, &tags).await?;
+    cached_tags(pool, instance_id, project_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+
+    fn release_json(tag: &str, released_at: &str) -> GitLabRelease {
+        serde_json::from_value(serde_json::json!({
+            "tag_name": tag,
+            "name": format!("Release {tag}"),
+            "description": "## Changelog\n- Fixed things",
+            "created_at": released_at,
+            "released_at": released_at,
+            "author": { "id": 1, "username": "alice", "name": "Alice", "avatar_url": null },
+            "commit": { "id": "abc123", "title": "Bump version" },
+            "assets": {
+                "count": 1,
+                "links": [{ "id": 9, "name": "app.dmg", "url": "https://x/app.dmg", "link_type": "package" }],
+                "sources": []
+            },
+            "_links": { "self": format!("https://gitlab.example.com/g/p/-/releases/{tag}") }
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn releases_keep_links_and_changelog_per_project() {
+        let (pool, inst) = seed_instance(true).await;
+        let releases = vec![
+            to_cached_release(10, release_json("v2.4.0", "2026-05-01T10:00:00Z")),
+            to_cached_release(10, release_json("v2.4.1", "2026-06-01T10:00:00Z")),
+        ];
+        release_cache::replace_releases(&pool, inst, 10, &releases)
+            .await
+            .unwrap();
+        release_cache::replace_releases(
+            &pool,
+            inst,
+            11,
+            &[to_cached_release(
+                11,
+                release_json("v1.0.0", "2026-01-01T00:00:00Z"),
+            )],
+        )
+        .await
+        .unwrap();
+
+        let got = cached_releases(&pool, inst, 10).await.unwrap();
+        assert_eq!(
+            got.iter().map(|r| r.tag_name.as_str()).collect::<Vec<_>>(),
+            vec!["v2.4.1", "v2.4.0"]
+        );
+        assert_eq!(got[0].author_username.as_deref(), Some("alice"));
+        assert_eq!(
+            got[0].description.as_deref(),
+            Some("## Changelog\n- Fixed things")
+        );
+        assert_eq!(
+            got[0].web_url.as_deref(),
+            Some("https://gitlab.example.com/g/p/-/releases/v2.4.1")
+        );
+        assert_eq!(
+            release_links(&got[0]),
+            vec![ReleaseLink {
+                name: "app.dmg".into(),
+                url: "https://x/app.dmg".into(),
+                link_type: Some("package".into()),
+            }]
+        );
+
+        // A refetch drops releases deleted upstream, leaving other projects alone.
+        release_cache::replace_releases(&pool, inst, 10, &releases[..1])
+            .await
+            .unwrap();
+        assert_eq!(cached_releases(&pool, inst, 10).await.unwrap().len(), 1);
+        assert_eq!(cached_releases(&pool, inst, 11).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn tags_record_commit_and_release() {
+        let (pool, inst) = seed_instance(true).await;
+        let tag = |name: &str, date: &str, release: bool| -> GitLabTag {
+            serde_json::from_value(serde_json::json!({
+                "name": name,
+                "message": "",
+                "target": "abc",
+                "commit": { "id": "abc", "title": "Release", "committed_date": date },
+                "release": if release {
+                    serde_json::json!({ "tag_name": name, "description": "notes" })
+                } else {
+                    serde_json::Value::Null
+                },
+                "protected": true
+            }))
+            .unwrap()
+        };
+        let tags = vec![
+            to_cached_tag(10, tag("v1", "2026-01-01T00:00:00Z", false)),
+            to_cached_tag(10, tag("v2", "2026-02-01T00:00:00Z", true)),
+        ];
+        release_cache::replace_tags(&pool, inst, 10, &tags)
+            .await
+            .unwrap();
+
+        let got = cached_tags(&pool, inst, 10).await.unwrap();
+        assert_eq!(got[0].name, "v2");
+        assert!(got[0].has_release);
+        assert!(!got[1].has_release);
+        assert!(got[0].protected);
+        assert_eq!(got[0].message, None, "empty annotations are dropped");
+    }
+}
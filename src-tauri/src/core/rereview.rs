@@ -0,0 +1,181 @@
+//! Re-requested reviews.
+//!
+//! Since GitLab 16 an author can ask reviewers to look again after pushing
+//! fixes, which drops the reviewer's state back to `unreviewed`. Sync notices
+//! that transition and stamps `mr_reviewers.rerequested_at`, so the MR
+//! resurfaces at the top of the reviewer's queue.
+
+use crate::core::create_client;
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use sqlx::FromRow;
+
+/// Review states meaning the reviewer already finished a pass.
+const FINISHED_STATES: &[&str] = &["reviewed", "requested_changes", "approved", "unapproved"];
+
+fn is_finished(state: &str) -> bool {
+    FINISHED_STATES.contains(&state)
+}
+
+/// A reviewer's state as cached by the previous sync.
+#[derive(Debug, Clone, Default, PartialEq, FromRow)]
+pub struct ReviewerState {
+    pub review_state: Option<String>,
+    pub rerequested_at: Option<i64>,
+    /// When sync first saw the reviewer on the MR.
+    pub requested_at: Option<i64>,
+}
+
+/// The `rerequested_at` to store for a reviewer now in `state`.
+///
+/// A reviewer counts as re-requested from the sync that sees them go from a
+/// finished state back to `unreviewed` until they review again. An unknown
+/// `state` (older GitLab, failed fetch) keeps what was cached.
+pub fn next_rerequested_at(
+    previous: Option<&ReviewerState>,
+    state: Option<&str>,
+    now: i64,
+) -> Option<i64> {
+    let previous_at = previous.and_then(|p| p.rerequested_at);
+    match state {
+        None => previous_at,
+        Some("unreviewed") => previous_at.or_else(|| {
+            previous
+                .and_then(|p| p.review_state.as_deref())
+                .filter(|s| is_finished(s))
+                .map(|_| now)
+        }),
+        Some(_) => None,
+    }
+}
+
+/// Ask every reviewer who already reviewed one of my MRs to review it again.
+///
+/// Reviewers are read live from GitLab so the request goes to the current
+/// list. Returns the usernames that were asked.
+pub async fn request_rereview(
+    pool: &DbPool,
+    mr_id: i64,
+    now: i64,
+) -> Result<Vec<String>, AppError> {
+    let (instance_id, project_id, iid, project_path, is_mine): (i64, i64, i64, String, bool) =
+        sqlx::query_as(
+            "SELECT mr.instance_id, mr.project_id, mr.iid, mr.project_name,
+                    mr.author_username = COALESCE(i.authenticated_username, '')
+             FROM merge_requests mr
+             JOIN gitlab_instances i ON i.id = mr.instance_id
+             WHERE mr.id = ?",
+        )
+        .bind(mr_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::not_found_with_id("MergeRequest", mr_id.to_string()))?;
+    if !is_mine {
+        return Err(AppError::invalid_input(
+            "Reviews can only be re-requested on your own merge requests",
+        ));
+    }
+
+    let client = create_client(pool, instance_id).await?;
+    let reviewers = client.get_mr_reviewer_states(project_id, iid).await?;
+    let targets: Vec<_> = reviewers
+        .iter()
+        .filter(|r| r.state.as_deref().is_none_or(is_finished))
+        .collect();
+    if targets.is_empty() {
+        return Err(AppError::invalid_input(
+            "No reviewer has finished a review yet",
+        ));
+    }
+
+    let mut asked = Vec::with_capacity(targets.len());
+    for reviewer in targets {
+        client
+            .rerequest_review(&project_path, iid, reviewer.user.id)
+            .await?;
+        sqlx::query(
+            "UPDATE mr_reviewers SET review_state = 'unreviewed', rerequested_at = ?
+             WHERE mr_id = ? AND username = ?",
+        )
+        .bind(now)
+        .bind(mr_id)
+        .bind(&reviewer.user.username)
+        .execute(pool)
+        .await?;
+        asked.push(reviewer.user.username.clone());
+    }
+    Ok(asked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+
+    fn cached(state: &str, at: Option<i64>) -> ReviewerState {
+        ReviewerState {
+            review_state: Some(state.to_string()),
+            rerequested_at: at,
+            requested_at: None,
+        }
+    }
+
+    #[test]
+    fn rerequest_is_a_finished_to_unreviewed_transition() {
+        // First sight of a pending reviewer is a plain request.
+        assert_eq!(next_rerequested_at(None, Some("unreviewed"), 10), None);
+        assert_eq!(
+            next_rerequested_at(Some(&cached("unreviewed", None)), Some("unreviewed"), 10),
+            None
+        );
+        assert_eq!(
+            next_rerequested_at(Some(&cached("reviewed", None)), Some("unreviewed"), 10),
+            Some(10)
+        );
+        assert_eq!(
+            next_rerequested_at(Some(&cached("approved", None)), Some("unreviewed"), 10),
+            Some(10)
+        );
+        // Sticks until the reviewer acts, and survives unknown states.
+        assert_eq!(
+            next_rerequested_at(
+                Some(&cached("unreviewed", Some(10))),
+                Some("unreviewed"),
+                20
+            ),
+            Some(10)
+        );
+        assert_eq!(
+            next_rerequested_at(Some(&cached("unreviewed", Some(10))), None, 20),
+            Some(10)
+        );
+        assert_eq!(
+            next_rerequested_at(
+                Some(&cached("unreviewed", Some(10))),
+                Some("review_started"),
+                20
+            ),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn only_the_author_can_rerequest() {
+        let (pool, inst) = seed_instance(true).await;
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, project_name, title, author_username,
+              source_branch, target_branch, state, web_url, created_at, updated_at,
+              labels, reviewers, cached_at)
+             VALUES (1, ?, 1, 10, 'g/p', 't', 'alice', 's', 'main', 'opened', 'http://x', 0, 0, '[]', '[]', 0)",
+        )
+        .bind(inst)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let err = request_rereview(&pool, 1, 0).await.unwrap_err();
+        assert!(err.to_string().contains("your own"));
+        assert!(request_rereview(&pool, 99, 0).await.is_err());
+    }
+}
@@ -0,0 +1,327 @@
+//! Per-instance data retention.
+//!
+//! A policy limits what the local cache keeps for one GitLab instance. The
+//! sync engine honours it while syncing (no file content fetched, old notes
+//! skipped) and calls [`enforce`] after each instance sync to remove data
+//! cached before the policy was tightened.
+//!
+//! Turning file content off also clears the other repository and CI content
+//! kept for the instance (blame, commit diffs, READMEs, job logs); dropping
+//! old comments also clears the instance's rendered markdown, which may
+//! still hold their text.
+
+use crate::db::file_cache;
+use crate::db::pool::DbPool;
+use crate::db::retention::{self, DataCategoryRow, RetentionPolicyRow};
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+
+/// Retention settings for one instance.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionPolicy {
+    pub instance_id: i64,
+    /// Cache full base/head file content for instant file viewing.
+    pub cache_file_content: bool,
+    /// Drop MR comments and issue notes older than this many days.
+    pub comment_retention_days: Option<i64>,
+    /// How long merged/closed MRs stay cached before being deleted.
+    pub closed_mr_retention_hours: i64,
+}
+
+impl RetentionPolicy {
+    fn default_for(instance_id: i64) -> Self {
+        Self {
+            instance_id,
+            cache_file_content: true,
+            comment_retention_days: None,
+            closed_mr_retention_hours: 24,
+        }
+    }
+
+    /// Notes created before this timestamp must not be cached.
+    pub fn comment_cutoff(&self, now: i64) -> Option<i64> {
+        self.comment_retention_days
+            .map(|days| now - days * 24 * 60 * 60)
+    }
+}
+
+impl From<RetentionPolicyRow> for RetentionPolicy {
+    fn from(row: RetentionPolicyRow) -> Self {
+        Self {
+            instance_id: row.instance_id,
+            cache_file_content: row.cache_file_content,
+            comment_retention_days: row.comment_retention_days,
+            closed_mr_retention_hours: row.closed_mr_retention_hours,
+        }
+    }
+}
+
+/// What one enforcement pass removed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionSweep {
+    pub file_versions_removed: u64,
+    /// Blame, commit diffs, READMEs and job logs.
+    pub content_caches_removed: u64,
+    pub comments_removed: u64,
+    pub rendered_markdown_removed: u64,
+}
+
+/// One category of cached data held for an instance.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataCategory {
+    pub category: String,
+    pub row_count: i64,
+    pub size_bytes: Option<i64>,
+    pub oldest_at: Option<i64>,
+}
+
+impl From<DataCategoryRow> for DataCategory {
+    fn from(row: DataCategoryRow) -> Self {
+        Self {
+            category: row.category,
+            row_count: row.row_count,
+            size_bytes: row.size_bytes,
+            oldest_at: row.oldest_at,
+        }
+    }
+}
+
+/// Audit of everything cached for one instance, alongside its policy.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceDataAudit {
+    pub instance_id: i64,
+    pub instance_url: String,
+    pub policy: RetentionPolicy,
+    pub categories: Vec<DataCategory>,
+}
+
+/// Load an instance's policy, falling back to the defaults.
+pub async fn load_policy(pool: &DbPool, instance_id: i64) -> Result<RetentionPolicy, AppError> {
+    Ok(retention::get_policy(pool, instance_id)
+        .await?
+        .map(RetentionPolicy::from)
+        .unwrap_or_else(|| RetentionPolicy::default_for(instance_id)))
+}
+
+/// Validate and persist an instance's policy.
+pub async fn save_policy(pool: &DbPool, policy: &RetentionPolicy) -> Result<(), AppError> {
+    if matches!(policy.comment_retention_days, Some(days) if days < 1) {
+        return Err(AppError::invalid_input(
+            "Comment retention must be at least 1 day",
+        ));
+    }
+    if policy.closed_mr_retention_hours < 0 {
+        return Err(AppError::invalid_input(
+            "Closed MR retention cannot be negative",
+        ));
+    }
+    let row = RetentionPolicyRow {
+        instance_id: policy.instance_id,
+        cache_file_content: policy.cache_file_content,
+        comment_retention_days: policy.comment_retention_days,
+        closed_mr_retention_hours: policy.closed_mr_retention_hours,
+    };
+    retention::upsert_policy(pool, &row).await?;
+    Ok(())
+}
+
+/// Delete cached data the policy no longer allows.
+pub async fn enforce(
+    pool: &DbPool,
+    policy: &RetentionPolicy,
+    now: i64,
+) -> Result<RetentionSweep, AppError> {
+    let mut sweep = RetentionSweep::default();
+
+    if !policy.cache_file_content {
+        sweep.file_versions_removed =
+            retention::delete_file_versions_for_instance(pool, policy.instance_id).await?;
+        if sweep.file_versions_removed > 0 {
+            file_cache::delete_orphaned_blobs(pool).await?;
+        }
+        sweep.content_caches_removed =
+            retention::delete_content_caches_for_instance(pool, policy.instance_id).await?;
+    }
+    if let Some(cutoff) = policy.comment_cutoff(now) {
+        sweep.comments_removed =
+            retention::delete_comments_before(pool, policy.instance_id, cutoff).await?;
+        if sweep.comments_removed > 0 {
+            sweep.rendered_markdown_removed =
+                retention::delete_rendered_markdown_for_instance(pool, policy.instance_id).await?;
+        }
+    }
+
+    Ok(sweep)
+}
+
+/// Report what categories of data are cached for each instance.
+pub async fn audit(pool: &DbPool) -> Result<Vec<InstanceDataAudit>, AppError> {
+    let instances: Vec<(i64, String)> =
+        sqlx::query_as("SELECT id, url FROM gitlab_instances ORDER BY id")
+            .fetch_all(pool)
+            .await?;
+
+    let mut audits = Vec::with_capacity(instances.len());
+    for (instance_id, instance_url) in instances {
+        audits.push(InstanceDataAudit {
+            instance_id,
+            instance_url,
+            policy: load_policy(pool, instance_id).await?,
+            categories: retention::data_inventory(pool, instance_id)
+                .await?
+                .into_iter()
+                .map(DataCategory::from)
+                .collect(),
+        });
+    }
+    Ok(audits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db;
+    use tempfile::{tempdir, TempDir};
+
+    const DAY: i64 = 24 * 60 * 60;
+    const NOW: i64 = 1_000 * DAY;
+
+    /// Instance 1 with MR 1, one cached file in two versions, one row in each
+    /// other content cache and two comments: one from 100 days ago and one
+    /// from yesterday.
+    async fn setup() -> (TempDir, DbPool) {
+        let dir = tempdir().unwrap();
+        let pool = db::initialize(&dir.path().join("t.db")).await.unwrap();
+        sqlx::query(
+            "INSERT INTO gitlab_instances (id, url, token, created_at) VALUES (1, 'https://secret', 't', 0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, project_name, title, author_username,
+              source_branch, target_branch, state, web_url, created_at, updated_at,
+              labels, reviewers, cached_at)
+             VALUES (1, 1, 1, 10, 'g/p', 't', 'alice', 's', 'main', 'opened', 'http://x', 0, 0, '[]', '[]', 0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        file_cache::upsert_file_blob(&pool, "abc", "fn main() {}", 12)
+            .await
+            .unwrap();
+        file_cache::upsert_file_version(&pool, 1, "main.rs", "head", "abc", "1", 10)
+            .await
+            .unwrap();
+        // Recorded without the instance; found through its MR.
+        file_cache::upsert_file_version(&pool, 1, "main.rs", "base", "abc", "", 10)
+            .await
+            .unwrap();
+        for sql in [
+            "INSERT INTO file_blame (instance_id, project_id, sha, path, fetched_at)
+             VALUES (1, 10, 'abc', 'main.rs', 0)",
+            "INSERT INTO commit_diffs (instance_id, project_id, sha, fetched_at)
+             VALUES (1, 10, 'abc', 0)",
+            "INSERT INTO project_readme_cache (instance_id, project_id, ref_name, html)
+             VALUES (1, 10, 'main', '<p>readme</p>')",
+            "INSERT INTO job_trace_cache
+             (instance_id, job_id, project_id, pipeline_id, mr_id, trace, fetched_at)
+             VALUES (1, 5, 10, 4, 1, 'error: failed', 0)",
+            "INSERT INTO markdown_cache (hash, html, instance_id, used_at)
+             VALUES ('h', '<p>old</p>', 1, 0)",
+        ] {
+            sqlx::query(sql).execute(&pool).await.unwrap();
+        }
+        sqlx::query(
+            "INSERT INTO comments (id, mr_id, author_username, body, created_at, updated_at)
+             VALUES (100, 1, 'alice', 'old', ?, ?), (101, 1, 'bob', 'new', ?, ?)",
+        )
+        .bind(NOW - 100 * DAY)
+        .bind(NOW - 100 * DAY)
+        .bind(NOW - DAY)
+        .bind(NOW - DAY)
+        .execute(&pool)
+        .await
+        .unwrap();
+        (dir, pool)
+    }
+
+    #[tokio::test]
+    async fn default_policy_keeps_everything() {
+        let (_dir, pool) = setup().await;
+        let policy = load_policy(&pool, 1).await.unwrap();
+        assert!(policy.cache_file_content);
+        assert_eq!(
+            enforce(&pool, &policy, NOW).await.unwrap(),
+            RetentionSweep::default()
+        );
+    }
+
+    #[tokio::test]
+    async fn strict_policy_purges_file_content_and_old_comments() {
+        let (_dir, pool) = setup().await;
+        let policy = RetentionPolicy {
+            cache_file_content: false,
+            comment_retention_days: Some(90),
+            ..RetentionPolicy::default_for(1)
+        };
+        save_policy(&pool, &policy).await.unwrap();
+        assert_eq!(load_policy(&pool, 1).await.unwrap(), policy);
+
+        let sweep = enforce(&pool, &policy, NOW).await.unwrap();
+        assert_eq!(
+            sweep,
+            RetentionSweep {
+                file_versions_removed: 2,
+                content_caches_removed: 4,
+                comments_removed: 1,
+                rendered_markdown_removed: 1,
+            }
+        );
+
+        let audit = audit(&pool).await.unwrap();
+        let count = |name: &str| {
+            audit[0]
+                .categories
+                .iter()
+                .find(|c| c.category == name)
+                .map(|c| c.row_count)
+                .unwrap()
+        };
+        for category in [
+            "file_content",
+            "file_blame",
+            "commit_diffs",
+            "readmes",
+            "job_traces",
+            "rendered_markdown",
+        ] {
+            assert_eq!(count(category), 0, "{category}");
+        }
+        assert_eq!(count("comments"), 1);
+        assert_eq!(count("merge_requests"), 1);
+        let blobs: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM file_blobs")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(blobs, 0);
+    }
+
+    #[tokio::test]
+    async fn rejects_non_positive_comment_retention() {
+        let (_dir, pool) = setup().await;
+        let policy = RetentionPolicy {
+            comment_retention_days: Some(0),
+            ..RetentionPolicy::default_for(1)
+        };
+        assert!(matches!(
+            save_policy(&pool, &policy).await,
+            Err(AppError::InvalidInput { .. })
+        ));
+    }
+}
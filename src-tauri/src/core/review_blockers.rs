@@ -0,0 +1,99 @@
+//! Reasons to hold off on approving an MR.
+//!
+//! A narrower view than the merge blockers: only what a reviewer should look
+//! at before approving — open threads (and how many of them the reviewer
+//! started), a failing pipeline and merge conflicts. Read from the cache, so
+//! it's cheap enough to check on every approval.
+
+use crate::core::approval_gate::own_open_threads;
+use crate::core::authenticated_username;
+use crate::core::merge_blockers::{get_merge_blockers, MergeBlocker};
+use crate::core::mr_actions::mr_api_ids;
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use serde::Serialize;
+
+/// What stands against approving an MR, from cached data.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewBlockers {
+    pub mr_id: i64,
+    pub unresolved_discussions: usize,
+    /// Unresolved threads started by the authenticated user.
+    pub own_unresolved_threads: usize,
+    pub pipeline_status: Option<String>,
+    pub pipeline_failed: bool,
+    pub has_conflicts: bool,
+}
+
+impl ReviewBlockers {
+    /// Whether anything should make the reviewer think twice.
+    pub fn any(&self) -> bool {
+        self.unresolved_discussions > 0 || self.pipeline_failed || self.has_conflicts
+    }
+}
+
+/// Review blockers of an MR.
+pub async fn get_review_blockers(pool: &DbPool, mr_id: i64) -> Result<ReviewBlockers, AppError> {
+    let merge = get_merge_blockers(pool, mr_id, false).await?;
+    let (instance_id, _, _) = mr_api_ids(pool, mr_id).await?;
+    let own_unresolved_threads = match authenticated_username(pool, instance_id).await? {
+        Some(me) => own_open_threads(pool, mr_id, &me).await?.len(),
+        None => 0,
+    };
+    Ok(ReviewBlockers {
+        mr_id,
+        unresolved_discussions: merge.unresolved_discussion_ids.len(),
+        own_unresolved_threads,
+        pipeline_failed: merge.blockers.contains(&MergeBlocker::PipelineFailed),
+        has_conflicts: merge.blockers.contains(&MergeBlocker::Conflicts),
+        pipeline_status: merge.pipeline_status,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+
+    #[tokio::test]
+    async fn counts_threads_pipeline_and_conflicts() {
+        let (pool, inst) = seed_instance(true).await;
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, title, author_username, source_branch,
+              target_branch, state, web_url, created_at, updated_at, head_pipeline_status)
+             VALUES (1, ?, 7, 100, 'Add cache', 'alice', 'f', 'main', 'opened', 'w', 0, 0, 'success')",
+        )
+        .bind(inst)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let blockers = get_review_blockers(&pool, 1).await.unwrap();
+        assert!(!blockers.any());
+
+        sqlx::query(
+            "INSERT INTO comments
+             (id, mr_id, discussion_id, author_username, body, resolvable, resolved, created_at, updated_at)
+             VALUES (1, 1, 'd1', 'me', 'q', 1, 0, 5, 5), (2, 1, 'd1', 'alice', 'a', 1, 0, 6, 6),
+                    (3, 1, 'd2', 'alice', 'x', 1, 0, 5, 5), (4, 1, 'd3', 'me', 'y', 1, 1, 5, 5)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "UPDATE merge_requests SET head_pipeline_status = 'failed', has_conflicts = 1 WHERE id = 1",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let blockers = get_review_blockers(&pool, 1).await.unwrap();
+        assert_eq!(blockers.unresolved_discussions, 2);
+        assert_eq!(blockers.own_unresolved_threads, 1);
+        assert!(blockers.pipeline_failed);
+        assert!(blockers.has_conflicts);
+        assert!(blockers.any());
+    }
+}
@@ -0,0 +1,275 @@
+//! Review annotations written outside the app, e.g. by an editor plugin or
+//! a CLI patch review tool, turned into inline comments.
+//!
+//! The format is the `file:line: message` convention of compilers and
+//! linters:
+//!
+//! ```text
+//! # comments and blank lines are ignored
+//! src/lib.rs:42: This unwrap panics on empty input.
+//! src/lib.rs:57: Consider a guard clause here,
+//!     continued on indented lines.
+//! ```
+//!
+//! Lines are in the new version of the file and must be shown in the MR's
+//! diff (added or context lines), since GitLab only takes inline comments
+//! there.
+
+use crate::core::comments::resolve_context_lines;
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use std::collections::HashMap;
+
+/// One entry of an annotation file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Annotation {
+    pub file_path: String,
+    pub line: i64,
+    pub body: String,
+}
+
+/// An annotation placed on the MR's diff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlacedAnnotation {
+    pub file_path: String,
+    pub new_line: i64,
+    /// The line is unchanged; the old line number has to be resolved too.
+    pub is_context_line: bool,
+    pub body: String,
+}
+
+/// Parse an annotation file. Errors name the first line that isn't an
+/// annotation, a continuation, a comment or blank.
+pub fn parse(text: &str) -> Result<Vec<Annotation>, AppError> {
+    let mut annotations: Vec<Annotation> = Vec::new();
+    for (index, raw) in text.lines().enumerate() {
+        let line = raw.trim_end();
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with(char::is_whitespace) {
+            if let Some(last) = annotations.last_mut() {
+                last.body.push('\n');
+                last.body.push_str(line.trim_start());
+                continue;
+            }
+        }
+        let annotation = parse_entry(line).ok_or_else(|| {
+            AppError::invalid_input_field(
+                format!(
+                    "Line {} is not a `file:line: comment` annotation",
+                    index + 1
+                ),
+                "path",
+            )
+        })?;
+        annotations.push(annotation);
+    }
+    if annotations.is_empty() {
+        return Err(AppError::invalid_input_field(
+            "The file has no annotations",
+            "path",
+        ));
+    }
+    Ok(annotations)
+}
+
+/// `path:line: body`, also accepting a column (`path:line:col: body`).
+fn parse_entry(line: &str) -> Option<Annotation> {
+    let (path, rest) = line.split_once(':')?;
+    let (number, rest) = rest.split_once(':')?;
+    let line_number: i64 = number.trim().parse().ok().filter(|&n| n > 0)?;
+    let rest = match rest.split_once(':') {
+        Some((column, body)) if column.trim().parse::<u32>().is_ok() => body,
+        _ => rest,
+    };
+    let path = path.trim().trim_start_matches("./");
+    let body = rest.trim();
+    if path.is_empty() || body.is_empty() {
+        return None;
+    }
+    Some(Annotation {
+        file_path: path.to_string(),
+        line: line_number,
+        body: body.to_string(),
+    })
+}
+
+/// Whether `new_line` is an added line of `diff`.
+fn is_added_line(diff: &str, new_line: i64) -> bool {
+    let mut line = 0;
+    for text in diff.lines() {
+        if let Some(header) = text.strip_prefix("@@ ") {
+            line = header
+                .split_whitespace()
+                .nth(1)
+                .and_then(|range| range.trim_start_matches('+').split(',').next())
+                .and_then(|start| start.parse::<i64>().ok())
+                .unwrap_or(0);
+        } else if text.starts_with('+') && !text.starts_with("+++") {
+            if line == new_line {
+                return true;
+            }
+            line += 1;
+        } else if !text.starts_with('-') && !text.starts_with('\\') {
+            line += 1;
+        }
+    }
+    false
+}
+
+/// Place every annotation on the MR's cached diff. Fails without placing
+/// any when some are on files or lines the diff doesn't show, listing them.
+pub async fn place(
+    pool: &DbPool,
+    mr_id: i64,
+    annotations: Vec<Annotation>,
+) -> Result<Vec<PlacedAnnotation>, AppError> {
+    let diffs: HashMap<String, Option<String>> =
+        sqlx::query_as("SELECT new_path, diff_content FROM diff_files WHERE mr_id = ?")
+            .bind(mr_id)
+            .fetch_all(pool)
+            .await?
+            .into_iter()
+            .collect();
+
+    let mut placed = Vec::with_capacity(annotations.len());
+    let mut outside = Vec::new();
+    for annotation in annotations {
+        let diff = diffs
+            .get(&annotation.file_path)
+            .and_then(|d| d.as_deref())
+            .unwrap_or_default();
+        let is_context_line = if is_added_line(diff, annotation.line) {
+            false
+        } else if resolve_context_lines(diff, annotation.line, false).is_some() {
+            true
+        } else {
+            outside.push(format!("{}:{}", annotation.file_path, annotation.line));
+            continue;
+        };
+        placed.push(PlacedAnnotation {
+            file_path: annotation.file_path,
+            new_line: annotation.line,
+            is_context_line,
+            body: annotation.body,
+        });
+    }
+
+    if !outside.is_empty() {
+        return Err(AppError::invalid_input_field(
+            format!(
+                "Not on a line shown in the MR's diff: {}",
+                outside.join(", ")
+            ),
+            "path",
+        ));
+    }
+    Ok(placed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+
+    #[test]
+    fn parse_reads_entries_and_continuations() {
+        let text = "# review of !12\n\
+                    src/lib.rs:42: Panics on empty input.\n\
+                    ./src/main.rs:7:5: Two things:\n\
+                    \x20   first, naming;\n\
+                    \tsecond, the error path.\n\
+                    \n";
+        assert_eq!(
+            parse(text).unwrap(),
+            vec![
+                Annotation {
+                    file_path: "src/lib.rs".into(),
+                    line: 42,
+                    body: "Panics on empty input.".into(),
+                },
+                Annotation {
+                    file_path: "src/main.rs".into(),
+                    line: 7,
+                    body: "Two things:\nfirst, naming;\nsecond, the error path.".into(),
+                },
+            ]
+        );
+        // A colon in the message isn't mistaken for a column.
+        assert_eq!(
+            parse("a.rs:1: note: see docs").unwrap()[0].body,
+            "note: see docs"
+        );
+
+        assert!(parse("").is_err());
+        assert!(parse("src/lib.rs: missing line").is_err());
+        assert!(parse("src/lib.rs:0: zero").is_err());
+        assert!(parse("   indented first line").is_err());
+    }
+
+    #[test]
+    fn is_added_line_counts_new_side() {
+        let diff = "@@ -1,3 +1,4 @@\n a\n-b\n+b2\n+b3\n c\n@@ -20,1 +21,2 @@\n x\n+y";
+        assert!(is_added_line(diff, 2));
+        assert!(is_added_line(diff, 3));
+        assert!(!is_added_line(diff, 1));
+        assert!(!is_added_line(diff, 4));
+        assert!(is_added_line(diff, 22));
+        assert!(!is_added_line(diff, 21));
+    }
+
+    #[tokio::test]
+    async fn place_uses_the_cached_diff() {
+        let (pool, inst) = seed_instance(true).await;
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, project_name, title, author_username,
+              source_branch, target_branch, state, web_url, created_at, updated_at,
+              labels, reviewers, cached_at)
+             VALUES (1, ?, 1, 10, 'g/p', 'MR', 'alice', 's', 'main', 'opened',
+                     'http://x', 0, 0, '[]', '[]', 0)",
+        )
+        .bind(inst)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO diff_files (id, mr_id, old_path, new_path, change_type,
+                                     additions, deletions, file_position, diff_content)
+             VALUES (1, 1, 'src/lib.rs', 'src/lib.rs', 'modified', 1, 1, 0,
+                     '@@ -1,3 +1,3 @@\n a\n-b\n+B\n c')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let annotation = |line: i64| Annotation {
+            file_path: "src/lib.rs".into(),
+            line,
+            body: "note".into(),
+        };
+        let placed = place(&pool, 1, vec![annotation(2), annotation(3)])
+            .await
+            .unwrap();
+        assert!(!placed[0].is_context_line);
+        assert!(placed[1].is_context_line);
+
+        let err = place(
+            &pool,
+            1,
+            vec![
+                annotation(2),
+                annotation(40),
+                Annotation {
+                    file_path: "other.rs".into(),
+                    line: 1,
+                    body: "x".into(),
+                },
+            ],
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("src/lib.rs:40, other.rs:1"));
+    }
+}
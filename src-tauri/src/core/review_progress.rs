@@ -0,0 +1,238 @@
+//! Incremental review: what changed since I last reviewed an MR.
+//!
+//! Marking an MR reviewed stores the head commit of its cached diff. When a
+//! later sync caches a diff with a different head, the MR is flagged
+//! `changed_since_review`, and `changes_since_review` shows just the
+//! difference between the two commits.
+//!
+//! The comparison is taken straight between the commits, so a rebase also
+//! brings in whatever landed on the target branch meanwhile. Only files the
+//! MR itself changes are kept, which drops most of that noise.
+
+use crate::core::create_client;
+use crate::core::mr_commits::{to_files, CommitDiffFile};
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// Result of `changes_since_review`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangesSinceReview {
+    pub reviewed_sha: String,
+    pub head_sha: String,
+    /// Files of the MR that differ between the two commits.
+    pub files: Vec<CommitDiffFile>,
+}
+
+/// Mark the MR reviewed at the head of its cached diff. Returns that SHA.
+pub async fn mark_reviewed(pool: &DbPool, mr_id: i64) -> Result<String, AppError> {
+    let head_sha: String = sqlx::query_scalar("SELECT head_sha FROM diffs WHERE mr_id = ?")
+        .bind(mr_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::invalid_input("The MR's diff hasn't been synced yet"))?;
+    sqlx::query(
+        "UPDATE merge_requests
+         SET last_reviewed_head_sha = ?, changed_since_review = 0
+         WHERE id = ?",
+    )
+    .bind(&head_sha)
+    .bind(mr_id)
+    .execute(pool)
+    .await?;
+    Ok(head_sha)
+}
+
+/// Bring the MR's `changed_since_review` flag in line with its cached diff.
+/// Sync calls this after storing a diff. Returns the new value when it
+/// changed.
+pub async fn refresh_flag(pool: &DbPool, mr_id: i64) -> Result<Option<bool>, AppError> {
+    let flag: Option<bool> = sqlx::query_scalar(
+        "UPDATE merge_requests
+         SET changed_since_review = (
+             last_reviewed_head_sha IS NOT NULL
+             AND last_reviewed_head_sha != (SELECT head_sha FROM diffs WHERE mr_id = merge_requests.id)
+         )
+         WHERE id = ?
+           AND EXISTS (SELECT 1 FROM diffs WHERE mr_id = merge_requests.id)
+           AND changed_since_review != (
+               last_reviewed_head_sha IS NOT NULL
+               AND last_reviewed_head_sha != (SELECT head_sha FROM diffs WHERE mr_id = merge_requests.id)
+           )
+         RETURNING changed_since_review",
+    )
+    .bind(mr_id)
+    .fetch_optional(pool)
+    .await?;
+    Ok(flag)
+}
+
+/// Keep the files whose old or new path the MR changes.
+fn only_mr_files(files: Vec<CommitDiffFile>, mr_paths: &HashSet<String>) -> Vec<CommitDiffFile> {
+    files
+        .into_iter()
+        .filter(|f| mr_paths.contains(&f.new_path) || mr_paths.contains(&f.old_path))
+        .collect()
+}
+
+/// The changes to the MR's files between the commit it was last reviewed at
+/// and its current head.
+pub async fn changes_since_review(
+    pool: &DbPool,
+    mr_id: i64,
+) -> Result<ChangesSinceReview, AppError> {
+    let (instance_id, project_id, reviewed_sha, head_sha): (
+        i64,
+        i64,
+        Option<String>,
+        Option<String>,
+    ) = sqlx::query_as(
+        "SELECT mr.instance_id, mr.project_id, mr.last_reviewed_head_sha, d.head_sha
+         FROM merge_requests mr
+         LEFT JOIN diffs d ON d.mr_id = mr.id
+         WHERE mr.id = ?",
+    )
+    .bind(mr_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::not_found_with_id("MergeRequest", mr_id.to_string()))?;
+    let reviewed_sha = reviewed_sha
+        .ok_or_else(|| AppError::invalid_input("The MR hasn't been marked reviewed"))?;
+    let head_sha =
+        head_sha.ok_or_else(|| AppError::invalid_input("The MR's diff hasn't been synced yet"))?;
+
+    if reviewed_sha == head_sha {
+        return Ok(ChangesSinceReview {
+            reviewed_sha,
+            head_sha,
+            files: Vec::new(),
+        });
+    }
+
+    let paths: Vec<(Option<String>, String)> =
+        sqlx::query_as("SELECT old_path, new_path FROM diff_files WHERE mr_id = ?")
+            .bind(mr_id)
+            .fetch_all(pool)
+            .await?;
+    let mr_paths: HashSet<String> = paths
+        .into_iter()
+        .flat_map(|(old, new)| old.into_iter().chain(Some(new)))
+        .collect();
+
+    let client = create_client(pool, instance_id).await?;
+    let diffs = client.compare(project_id, &reviewed_sha, &head_sha).await?;
+
+    Ok(ChangesSinceReview {
+        files: only_mr_files(to_files(diffs), &mr_paths),
+        reviewed_sha,
+        head_sha,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+
+    async fn seed_mr(pool: &DbPool, inst: i64) {
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, project_name, title, author_username,
+              source_branch, target_branch, state, web_url, created_at, updated_at,
+              labels, reviewers, cached_at)
+             VALUES (1, ?, 1, 10, 'g/p', 'MR', 'alice', 's', 'main', 'opened',
+                     'http://x', 0, 0, '[]', '[]', 0)",
+        )
+        .bind(inst)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    async fn set_head(pool: &DbPool, sha: &str) {
+        sqlx::query(
+            "INSERT INTO diffs (mr_id, content, base_sha, head_sha, start_sha,
+                                file_count, additions, deletions)
+             VALUES (1, '', 'b', ?, 'b', 0, 0, 0)
+             ON CONFLICT (mr_id) DO UPDATE SET head_sha = excluded.head_sha",
+        )
+        .bind(sha)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    async fn flag(pool: &DbPool) -> bool {
+        sqlx::query_scalar("SELECT changed_since_review FROM merge_requests WHERE id = 1")
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn flag_follows_head_after_marking_reviewed() {
+        let (pool, inst) = seed_instance(true).await;
+        seed_mr(&pool, inst).await;
+        assert!(mark_reviewed(&pool, 1).await.is_err());
+
+        // Never reviewed: a new head doesn't flag anything.
+        set_head(&pool, "aaa").await;
+        assert_eq!(refresh_flag(&pool, 1).await.unwrap(), None);
+        assert_eq!(mark_reviewed(&pool, 1).await.unwrap(), "aaa");
+
+        set_head(&pool, "bbb").await;
+        assert_eq!(refresh_flag(&pool, 1).await.unwrap(), Some(true));
+        assert_eq!(refresh_flag(&pool, 1).await.unwrap(), None);
+        assert!(flag(&pool).await);
+
+        // Force-pushed back to what was reviewed.
+        set_head(&pool, "aaa").await;
+        assert_eq!(refresh_flag(&pool, 1).await.unwrap(), Some(false));
+
+        set_head(&pool, "ccc").await;
+        refresh_flag(&pool, 1).await.unwrap();
+        mark_reviewed(&pool, 1).await.unwrap();
+        assert!(!flag(&pool).await);
+    }
+
+    #[tokio::test]
+    async fn nothing_changed_when_head_is_the_reviewed_commit() {
+        let (pool, inst) = seed_instance(true).await;
+        seed_mr(&pool, inst).await;
+        set_head(&pool, "aaa").await;
+        assert!(changes_since_review(&pool, 1).await.is_err());
+
+        mark_reviewed(&pool, 1).await.unwrap();
+        let changes = changes_since_review(&pool, 1).await.unwrap();
+        assert_eq!(changes.head_sha, "aaa");
+        assert!(changes.files.is_empty());
+    }
+
+    #[test]
+    fn only_files_of_the_mr_are_kept() {
+        let file = |old: &str, new: &str| CommitDiffFile {
+            old_path: old.to_string(),
+            new_path: new.to_string(),
+            change_type: "modified".to_string(),
+            additions: 1,
+            deletions: 0,
+            diff: String::new(),
+        };
+        let mr_paths: HashSet<String> = ["src/a.rs", "src/old.rs"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let kept = only_mr_files(
+            vec![
+                file("src/a.rs", "src/a.rs"),
+                file("src/old.rs", "src/new.rs"),
+                file("main_only.rs", "main_only.rs"),
+            ],
+            &mr_paths,
+        );
+        let paths: Vec<_> = kept.iter().map(|f| f.new_path.as_str()).collect();
+        assert_eq!(paths, vec!["src/a.rs", "src/new.rs"]);
+    }
+}
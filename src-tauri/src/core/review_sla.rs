@@ -0,0 +1,482 @@
+//! Review response targets ("respond within one business day").
+//!
+//! The clock of an MR waiting on the user's review starts when the user was
+//! asked (first seen as a reviewer by sync, or re-requested) and only runs
+//! during the configured working hours, in the user's time zone. An MR is on
+//! track until `at_risk_percent` of the target has passed, then at risk, and
+//! breached once the whole target has. Off until the user turns it on.
+//!
+//! Working hours are resolved per day in the time zone passed in (the
+//! system's `Local` zone in the app), so they stay put across DST changes.
+
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone};
+use serde::{Deserialize, Serialize};
+
+const HOUR: i64 = 60 * 60;
+
+/// Review SLA settings, part of the sync config.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReviewSlaConfig {
+    pub enabled: bool,
+    /// Working hours the user has to respond in.
+    pub respond_within_hours: i64,
+    /// Share of the target (in percent) after which an MR is at risk.
+    pub at_risk_percent: i64,
+    /// Start of the working day, as an hour of the local day.
+    pub workday_start_hour: i64,
+    /// End of the working day (exclusive), as an hour of the local day.
+    pub workday_end_hour: i64,
+    /// Working days, ISO numbered (1 = Monday, 7 = Sunday).
+    pub work_days: Vec<u32>,
+}
+
+impl Default for ReviewSlaConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            respond_within_hours: 8,
+            at_risk_percent: 75,
+            workday_start_hour: 9,
+            workday_end_hour: 17,
+            work_days: vec![1, 2, 3, 4, 5],
+        }
+    }
+}
+
+/// Where an awaiting MR stands against the target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlaState {
+    OnTrack,
+    AtRisk,
+    Breached,
+}
+
+/// SLA status of one MR waiting on the user's review.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewSlaStatus {
+    pub mr_id: i64,
+    /// When the clock started (Unix seconds).
+    pub requested_at: i64,
+    /// Working seconds since `requested_at`.
+    pub elapsed_secs: i64,
+    /// When the target is breached (Unix seconds).
+    pub due_at: i64,
+    pub state: SlaState,
+}
+
+impl ReviewSlaConfig {
+    /// Reject settings that leave no working time to count.
+    pub fn validate(&self) -> Result<(), AppError> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.respond_within_hours <= 0 {
+            return Err(AppError::invalid_input_field(
+                "The response target must be at least one hour",
+                "review_sla.respond_within_hours",
+            ));
+        }
+        if !(1..=99).contains(&self.at_risk_percent) {
+            return Err(AppError::invalid_input_field(
+                "The at-risk point must be between 1% and 99% of the target",
+                "review_sla.at_risk_percent",
+            ));
+        }
+        if !(0..24).contains(&self.workday_start_hour)
+            || !(1..=24).contains(&self.workday_end_hour)
+            || self.workday_end_hour <= self.workday_start_hour
+        {
+            return Err(AppError::invalid_input_field(
+                "The working day must end after it starts",
+                "review_sla.workday_end_hour",
+            ));
+        }
+        if self.work_days.is_empty() || self.work_days.iter().any(|d| !(1..=7).contains(d)) {
+            return Err(AppError::invalid_input_field(
+                "Pick at least one working day",
+                "review_sla.work_days",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Whether there is any working time to count. Settings that fail
+    /// `validate` fall back to counting wall-clock time.
+    fn has_working_time(&self) -> bool {
+        self.workday_start_hour < self.workday_end_hour
+            && self.work_days.iter().any(|d| (1..=7).contains(d))
+    }
+
+    /// Working hours of `day` as Unix seconds, if it is a working day.
+    fn window<Tz: TimeZone>(&self, day: NaiveDate, tz: &Tz) -> Option<(i64, i64)> {
+        if !self.work_days.contains(&day.weekday().number_from_monday()) {
+            return None;
+        }
+        Some((
+            local_hour(day, self.workday_start_hour, tz)?,
+            local_hour(day, self.workday_end_hour, tz)?,
+        ))
+    }
+
+    /// Working seconds between `from` and `to`.
+    pub fn working_secs_between<Tz: TimeZone + Copy>(&self, from: i64, to: i64, tz: Tz) -> i64 {
+        if to <= from {
+            return 0;
+        }
+        if !self.has_working_time() {
+            return to - from;
+        }
+        let mut total = 0;
+        let mut day = local_date(from, &tz);
+        let last = local_date(to, &tz);
+        while day <= last {
+            if let Some((open, close)) = self.window(day, &tz) {
+                total += (close.min(to) - open.max(from)).max(0);
+            }
+            day += Duration::days(1);
+        }
+        total
+    }
+
+    /// When `secs` working seconds have passed after `from`.
+    pub fn add_working_secs<Tz: TimeZone + Copy>(&self, from: i64, secs: i64, tz: Tz) -> i64 {
+        if !self.has_working_time() {
+            return from + secs;
+        }
+        let mut remaining = secs;
+        let mut day = local_date(from, &tz);
+        loop {
+            if let Some((open, close)) = self.window(day, &tz) {
+                let start = open.max(from);
+                if close - start >= remaining {
+                    return start + remaining;
+                }
+                remaining -= (close - start).max(0);
+            }
+            day += Duration::days(1);
+        }
+    }
+
+    /// Status of an MR requested at `requested_at`, as of `now`.
+    pub fn status<Tz: TimeZone + Copy>(
+        &self,
+        mr_id: i64,
+        requested_at: i64,
+        now: i64,
+        tz: Tz,
+    ) -> ReviewSlaStatus {
+        let target = self.respond_within_hours * HOUR;
+        let elapsed_secs = self.working_secs_between(requested_at, now, tz);
+        let state = if elapsed_secs >= target {
+            SlaState::Breached
+        } else if elapsed_secs * 100 >= target * self.at_risk_percent {
+            SlaState::AtRisk
+        } else {
+            SlaState::OnTrack
+        };
+        ReviewSlaStatus {
+            mr_id,
+            requested_at,
+            elapsed_secs,
+            due_at: self.add_working_secs(requested_at, target, tz),
+            state,
+        }
+    }
+}
+
+fn local_date<Tz: TimeZone>(ts: i64, tz: &Tz) -> NaiveDate {
+    DateTime::from_timestamp(ts, 0)
+        .unwrap_or_default()
+        .with_timezone(tz)
+        .date_naive()
+}
+
+/// `hour` o'clock on `day` in `tz`. An hour skipped by a DST change starts
+/// when the clocks do.
+fn local_hour<Tz: TimeZone>(day: NaiveDate, hour: i64, tz: &Tz) -> Option<i64> {
+    let at = day.and_hms_opt(0, 0, 0)? + Duration::hours(hour.clamp(0, 24));
+    tz.from_local_datetime(&at)
+        .earliest()
+        .or_else(|| {
+            tz.from_local_datetime(&(at + Duration::hours(1)))
+                .earliest()
+        })
+        .map(|t| t.timestamp())
+}
+
+/// SLA status of every open MR waiting on the user's review: the user is a
+/// pending reviewer who hasn't reviewed yet, and the project isn't muted.
+/// Empty when the SLA is turned off.
+pub async fn statuses<Tz: TimeZone + Copy>(
+    pool: &DbPool,
+    config: &ReviewSlaConfig,
+    now: i64,
+    tz: Tz,
+) -> Result<Vec<ReviewSlaStatus>, AppError> {
+    if !config.enabled {
+        return Ok(Vec::new());
+    }
+    // Without a tracked request time the MR's creation is the best guess.
+    let awaiting: Vec<(i64, i64)> = sqlx::query_as(
+        r#"
+        SELECT mr.id,
+               MAX(COALESCE(rr.requested_at, mr.created_at), COALESCE(rr.rerequested_at, 0))
+        FROM merge_requests mr
+        JOIN gitlab_instances gi ON gi.id = mr.instance_id
+        JOIN mr_reviewers rr
+          ON rr.mr_id = mr.id AND rr.username = gi.authenticated_username
+        WHERE mr.state = 'opened'
+          AND mr.author_username != gi.authenticated_username
+          AND COALESCE(mr.user_has_approved, 0) = 0
+          AND rr.status = 'pending'
+          AND COALESCE(rr.review_state, 'unreviewed') IN ('unreviewed', 'review_started')
+          AND NOT EXISTS (
+              SELECT 1 FROM muted_projects mp
+              WHERE mp.instance_id = mr.instance_id AND mp.project_id = mr.project_id
+                AND (mp.muted_until IS NULL OR mp.muted_until > ?)
+          )
+        ORDER BY mr.id
+        "#,
+    )
+    .bind(now)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(awaiting
+        .into_iter()
+        .map(|(mr_id, requested_at)| config.status(mr_id, requested_at, now, tz))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+    use chrono::{FixedOffset, LocalResult, NaiveDateTime};
+
+    /// Monday 2024-06-03 00:00 UTC.
+    const MONDAY: i64 = 1_717_372_800;
+    const DAY: i64 = 24 * HOUR;
+
+    fn utc() -> FixedOffset {
+        east(0)
+    }
+
+    fn east(hours: i64) -> FixedOffset {
+        FixedOffset::east_opt((hours * HOUR) as i32).unwrap()
+    }
+
+    fn enabled() -> ReviewSlaConfig {
+        ReviewSlaConfig {
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    /// UTC+1 until clocks go forward at 02:00 local on Sunday 2024-06-09,
+    /// UTC+2 after.
+    #[derive(Debug, Clone, Copy)]
+    struct Dst;
+    const SWITCH: i64 = MONDAY + 6 * DAY + HOUR;
+
+    impl TimeZone for Dst {
+        type Offset = FixedOffset;
+
+        fn from_offset(_: &FixedOffset) -> Self {
+            Dst
+        }
+        fn offset_from_local_date(&self, _: &NaiveDate) -> LocalResult<FixedOffset> {
+            unimplemented!()
+        }
+        fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<FixedOffset> {
+            let ts = local.and_utc().timestamp();
+            if ts - HOUR < SWITCH {
+                LocalResult::Single(east(1))
+            } else if ts - 2 * HOUR >= SWITCH {
+                LocalResult::Single(east(2))
+            } else {
+                LocalResult::None
+            }
+        }
+        fn offset_from_utc_date(&self, _: &NaiveDate) -> FixedOffset {
+            unimplemented!()
+        }
+        fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+            east(if utc.and_utc().timestamp() < SWITCH {
+                1
+            } else {
+                2
+            })
+        }
+    }
+
+    #[test]
+    fn counts_only_working_hours() {
+        let config = ReviewSlaConfig::default();
+        // Monday 08:00 to 10:00: one working hour.
+        assert_eq!(
+            config.working_secs_between(MONDAY + 8 * HOUR, MONDAY + 10 * HOUR, utc()),
+            HOUR
+        );
+        // Friday 16:00 to Monday 10:00: an hour on each side of the weekend.
+        let friday = MONDAY + 4 * DAY;
+        assert_eq!(
+            config.working_secs_between(friday + 16 * HOUR, MONDAY + 7 * DAY + 10 * HOUR, utc()),
+            2 * HOUR
+        );
+        // A whole week is five working days.
+        assert_eq!(
+            config.working_secs_between(MONDAY, MONDAY + 7 * DAY, utc()),
+            5 * 8 * HOUR
+        );
+        // Working hours are local: 09:00 in UTC+2 is 07:00 UTC.
+        let plus_two = FixedOffset::east_opt(2 * HOUR as i32).unwrap();
+        assert_eq!(
+            config.working_secs_between(MONDAY + 7 * HOUR, MONDAY + 8 * HOUR, plus_two),
+            HOUR
+        );
+    }
+
+    #[test]
+    fn due_date_skips_nights_and_weekends() {
+        let config = ReviewSlaConfig::default();
+        let friday = MONDAY + 4 * DAY;
+        // Requested Friday 15:00: two hours left that day, six on Monday.
+        assert_eq!(
+            config.add_working_secs(friday + 15 * HOUR, 8 * HOUR, utc()),
+            MONDAY + 7 * DAY + 15 * HOUR
+        );
+        // Requested Saturday: due at the end of Monday.
+        assert_eq!(
+            config.add_working_secs(friday + DAY, 8 * HOUR, utc()),
+            MONDAY + 7 * DAY + 17 * HOUR
+        );
+    }
+
+    #[test]
+    fn classifies_against_the_target() {
+        let config = ReviewSlaConfig::default();
+        let asked = MONDAY + 9 * HOUR;
+        let state = |now: i64| config.status(1, asked, now, utc()).state;
+        assert_eq!(state(asked + 5 * HOUR), SlaState::OnTrack);
+        assert_eq!(state(asked + 6 * HOUR), SlaState::AtRisk);
+        assert_eq!(state(asked + 8 * HOUR), SlaState::Breached);
+        // Asked Monday 13:00, the night doesn't count: four hours in by
+        // Tuesday morning, breached Tuesday 13:00.
+        let afternoon = config.status(1, MONDAY + 13 * HOUR, MONDAY + DAY + 8 * HOUR, utc());
+        assert_eq!(
+            (afternoon.state, afternoon.elapsed_secs),
+            (SlaState::OnTrack, 4 * HOUR)
+        );
+        assert_eq!(afternoon.due_at, MONDAY + DAY + 13 * HOUR);
+    }
+
+    #[test]
+    fn working_hours_follow_dst() {
+        let config = enabled();
+        // Monday 09:00 is 08:00 UTC before the switch and 07:00 UTC after.
+        let before = config.status(1, MONDAY + 8 * HOUR, MONDAY + 8 * HOUR, Dst);
+        assert_eq!(before.due_at, MONDAY + 16 * HOUR);
+        let next_monday = MONDAY + 7 * DAY;
+        let after = config.status(1, next_monday + 7 * HOUR, next_monday + 7 * HOUR, Dst);
+        assert_eq!(after.due_at, next_monday + 15 * HOUR);
+        // Friday 16:00 to Monday 10:00 local, across the switch: an hour
+        // on each side. A fixed UTC+1 would start Monday an hour late.
+        let friday = MONDAY + 4 * DAY;
+        let (from, to) = (friday + 15 * HOUR, next_monday + 8 * HOUR);
+        assert_eq!(config.working_secs_between(from, to, Dst), 2 * HOUR);
+        assert_eq!(config.working_secs_between(from, to, east(1)), HOUR);
+    }
+
+    #[test]
+    fn validates_working_time() {
+        assert!(!ReviewSlaConfig::default().enabled);
+        assert!(ReviewSlaConfig::default().validate().is_ok());
+        assert!(enabled().validate().is_ok());
+        let no_days = ReviewSlaConfig {
+            work_days: vec![],
+            ..enabled()
+        };
+        assert!(no_days.validate().is_err());
+        let backwards = ReviewSlaConfig {
+            workday_start_hour: 17,
+            workday_end_hour: 9,
+            ..enabled()
+        };
+        assert!(backwards.validate().is_err());
+        // Unvalidated settings fall back to wall-clock time.
+        assert_eq!(backwards.working_secs_between(0, DAY, utc()), DAY);
+        assert!(ReviewSlaConfig {
+            enabled: false,
+            ..backwards
+        }
+        .validate()
+        .is_ok());
+    }
+
+    #[tokio::test]
+    async fn statuses_cover_pending_reviews_only() {
+        let (pool, inst) = seed_instance(true).await;
+        for (id, author, approved) in [
+            (1, "alice", 0),
+            (2, "alice", 1),
+            (3, "me", 0),
+            (4, "bob", 0),
+        ] {
+            sqlx::query(
+                "INSERT INTO merge_requests
+                 (id, instance_id, iid, project_id, project_name, title, author_username,
+                  source_branch, target_branch, state, web_url, created_at, updated_at,
+                  labels, reviewers, cached_at, user_has_approved)
+                 VALUES (?, ?, ?, 10, 'g/p', 'MR', ?, 's', 'main', 'opened',
+                         'http://x', ?, 0, '[]', '[\"me\"]', 0, ?)",
+            )
+            .bind(id)
+            .bind(inst)
+            .bind(id)
+            .bind(author)
+            .bind(MONDAY)
+            .bind(approved)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        // MR 4 was re-requested Monday 16:00, restarting its clock.
+        sqlx::query(
+            "INSERT INTO mr_reviewers (mr_id, username, status, review_state, requested_at, rerequested_at)
+             VALUES (1, 'me', 'pending', 'unreviewed', ?, NULL),
+                    (2, 'me', 'approved', 'approved', ?, NULL),
+                    (3, 'me', 'pending', NULL, ?, NULL),
+                    (4, 'me', 'pending', 'unreviewed', ?, ?)",
+        )
+        .bind(MONDAY + 9 * HOUR)
+        .bind(MONDAY + 9 * HOUR)
+        .bind(MONDAY + 9 * HOUR)
+        .bind(MONDAY + 9 * HOUR)
+        .bind(MONDAY + 16 * HOUR)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let config = enabled();
+        let now = MONDAY + 17 * HOUR;
+        let found = statuses(&pool, &config, now, utc()).await.unwrap();
+        let summary: Vec<(i64, SlaState)> = found.iter().map(|s| (s.mr_id, s.state)).collect();
+        assert_eq!(
+            summary,
+            vec![(1, SlaState::Breached), (4, SlaState::OnTrack)]
+        );
+        assert_eq!(found[1].due_at, MONDAY + DAY + 16 * HOUR);
+
+        let off = ReviewSlaConfig {
+            enabled: false,
+            ..config
+        };
+        assert!(statuses(&pool, &off, now, utc()).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,378 @@
+//! Opt-in review stats: streaks, comment volume and response time.
+//!
+//! Nothing is recorded until the user turns tracking on. From then the sync
+//! engine logs review requests as it first sees them and the sync processor
+//! logs the user's comments, replies and approvals once they reach GitLab.
+//! Stats are derived from that log on demand; the log never leaves the
+//! machine except through [`export`].
+
+use crate::db::pool::DbPool;
+use crate::db::review_activity::{self, ReviewEventRow};
+use crate::error::AppError;
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+
+/// Number of weeks covered by [`ReviewStreaks::comments_per_week`].
+const WEEKS_SHOWN: i64 = 8;
+
+/// What happened in a review event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReviewEventKind {
+    /// The user was added as a reviewer (first seen by sync).
+    ReviewRequested,
+    /// The user commented or replied on the MR.
+    Commented,
+    /// The user approved the MR.
+    Approved,
+}
+
+impl ReviewEventKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::ReviewRequested => "review_requested",
+            Self::Commented => "commented",
+            Self::Approved => "approved",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "review_requested" => Some(Self::ReviewRequested),
+            "commented" => Some(Self::Commented),
+            "approved" => Some(Self::Approved),
+            _ => None,
+        }
+    }
+}
+
+/// One logged review event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewEvent {
+    pub instance_id: i64,
+    pub project_id: i64,
+    pub mr_iid: i64,
+    pub kind: ReviewEventKind,
+    pub occurred_at: i64,
+}
+
+/// Comments written in the week starting on `week_start` (a Monday).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeeklyCount {
+    pub week_start: NaiveDate,
+    pub comments: i64,
+}
+
+/// Stats derived from the activity log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewStreaks {
+    pub enabled: bool,
+    /// When tracking was turned on.
+    pub tracking_since: Option<i64>,
+    /// Consecutive days with a review, ending today (or yesterday, so the
+    /// streak survives until the day is over).
+    pub current_streak_days: i64,
+    pub longest_streak_days: i64,
+    /// Days with at least one comment or approval.
+    pub review_days: i64,
+    /// The last eight weeks, oldest first.
+    pub comments_per_week: Vec<WeeklyCount>,
+    /// Median time from a review request to the user's first comment or
+    /// approval on that MR. `None` until a request has been answered.
+    pub median_response_secs: Option<i64>,
+}
+
+/// Everything tracked, for the user to keep or share.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewStatsExport {
+    pub exported_at: i64,
+    pub stats: ReviewStreaks,
+    pub events: Vec<ReviewEvent>,
+}
+
+/// Log an event if tracking is on; a no-op otherwise.
+pub async fn record(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+    mr_iid: i64,
+    kind: ReviewEventKind,
+    occurred_at: i64,
+) -> Result<(), AppError> {
+    if review_activity::enabled_at(pool).await?.is_none() {
+        return Ok(());
+    }
+    review_activity::insert_event(
+        pool,
+        &ReviewEventRow {
+            instance_id,
+            project_id,
+            mr_iid,
+            kind: kind.as_str().to_string(),
+            occurred_at,
+        },
+    )
+    .await?;
+    Ok(())
+}
+
+/// Turn tracking on or off. Turning it off deletes the log.
+pub async fn set_enabled(pool: &DbPool, enabled: bool, now: i64) -> Result<(), AppError> {
+    review_activity::set_enabled(pool, enabled, now).await?;
+    Ok(())
+}
+
+async fn load_events(pool: &DbPool) -> Result<Vec<ReviewEvent>, AppError> {
+    Ok(review_activity::list_events(pool)
+        .await?
+        .into_iter()
+        .filter_map(|row| {
+            Some(ReviewEvent {
+                kind: ReviewEventKind::parse(&row.kind)?,
+                instance_id: row.instance_id,
+                project_id: row.project_id,
+                mr_iid: row.mr_iid,
+                occurred_at: row.occurred_at,
+            })
+        })
+        .collect())
+}
+
+/// Current stats. Days and weeks are bucketed in the `offset` time zone.
+pub async fn streaks(
+    pool: &DbPool,
+    now: i64,
+    offset: FixedOffset,
+) -> Result<ReviewStreaks, AppError> {
+    let tracking_since = review_activity::enabled_at(pool).await?;
+    let events = load_events(pool).await?;
+    Ok(compute(&events, tracking_since, now, offset))
+}
+
+/// Stats plus the raw log.
+pub async fn export(
+    pool: &DbPool,
+    now: i64,
+    offset: FixedOffset,
+) -> Result<ReviewStatsExport, AppError> {
+    let tracking_since = review_activity::enabled_at(pool).await?;
+    let events = load_events(pool).await?;
+    Ok(ReviewStatsExport {
+        exported_at: now,
+        stats: compute(&events, tracking_since, now, offset),
+        events,
+    })
+}
+
+fn local_date(ts: i64, offset: FixedOffset) -> NaiveDate {
+    DateTime::from_timestamp(ts, 0)
+        .unwrap_or_default()
+        .with_timezone(&offset)
+        .date_naive()
+}
+
+fn compute(
+    events: &[ReviewEvent],
+    tracking_since: Option<i64>,
+    now: i64,
+    offset: FixedOffset,
+) -> ReviewStreaks {
+    let today = local_date(now, offset);
+
+    let days: BTreeSet<NaiveDate> = events
+        .iter()
+        .filter(|e| e.kind != ReviewEventKind::ReviewRequested)
+        .map(|e| local_date(e.occurred_at, offset))
+        .collect();
+
+    let mut longest = 0;
+    let mut run = 0;
+    let mut prev: Option<NaiveDate> = None;
+    for &day in &days {
+        run = match prev {
+            Some(p) if p.succ_opt() == Some(day) => run + 1,
+            _ => 1,
+        };
+        longest = longest.max(run);
+        prev = Some(day);
+    }
+
+    let mut current = 0;
+    let mut day = if days.contains(&today) {
+        today
+    } else {
+        today - Duration::days(1)
+    };
+    while days.contains(&day) {
+        current += 1;
+        day -= Duration::days(1);
+    }
+
+    let this_week = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+    let comments_per_week = (0..WEEKS_SHOWN)
+        .rev()
+        .map(|weeks_ago| {
+            let week_start = this_week - Duration::weeks(weeks_ago);
+            let week_end = week_start + Duration::weeks(1);
+            let comments = events
+                .iter()
+                .filter(|e| e.kind == ReviewEventKind::Commented)
+                .map(|e| local_date(e.occurred_at, offset))
+                .filter(|d| *d >= week_start && *d < week_end)
+                .count() as i64;
+            WeeklyCount {
+                week_start,
+                comments,
+            }
+        })
+        .collect();
+
+    // First response after each review request, per MR.
+    let mut requested: HashMap<(i64, i64, i64), i64> = HashMap::new();
+    let mut responses: Vec<i64> = Vec::new();
+    for e in events {
+        let key = (e.instance_id, e.project_id, e.mr_iid);
+        match e.kind {
+            ReviewEventKind::ReviewRequested => {
+                requested.insert(key, e.occurred_at);
+            }
+            _ => {
+                if let Some(at) = requested.remove(&key) {
+                    responses.push((e.occurred_at - at).max(0));
+                }
+            }
+        }
+    }
+    responses.sort_unstable();
+    let median_response_secs = match responses.len() {
+        0 => None,
+        n if n % 2 == 1 => Some(responses[n / 2]),
+        n => Some((responses[n / 2 - 1] + responses[n / 2]) / 2),
+    };
+
+    ReviewStreaks {
+        enabled: tracking_since.is_some(),
+        tracking_since,
+        current_streak_days: current,
+        longest_streak_days: longest,
+        review_days: days.len() as i64,
+        comments_per_week,
+        median_response_secs,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db;
+    use tempfile::tempdir;
+
+    const HOUR: i64 = 60 * 60;
+    const DAY: i64 = 24 * HOUR;
+    /// Wednesday 2024-01-10 12:00 UTC.
+    const NOW: i64 = 1_704_888_000;
+
+    fn event(mr_iid: i64, kind: ReviewEventKind, occurred_at: i64) -> ReviewEvent {
+        ReviewEvent {
+            instance_id: 1,
+            project_id: 10,
+            mr_iid,
+            kind,
+            occurred_at,
+        }
+    }
+
+    #[test]
+    fn computes_streaks_weekly_comments_and_median_response() {
+        use ReviewEventKind::*;
+        let utc = FixedOffset::east_opt(0).unwrap();
+        let events = vec![
+            // A three-day run ending a week ago.
+            event(1, ReviewRequested, NOW - 10 * DAY),
+            event(1, Commented, NOW - 9 * DAY),
+            event(2, Approved, NOW - 8 * DAY),
+            event(2, Commented, NOW - 7 * DAY),
+            // Yesterday and today: current streak of two.
+            event(3, ReviewRequested, NOW - DAY - 4 * HOUR),
+            event(3, Commented, NOW - DAY),
+            event(3, Approved, NOW),
+        ];
+        let stats = compute(&events, Some(0), NOW, utc);
+
+        assert!(stats.enabled);
+        assert_eq!(stats.current_streak_days, 2);
+        assert_eq!(stats.longest_streak_days, 3);
+        assert_eq!(stats.review_days, 5);
+        // Responses took 1 day (MR 1) and 4 hours (MR 3).
+        assert_eq!(stats.median_response_secs, Some((DAY + 4 * HOUR) / 2));
+
+        let weeks = &stats.comments_per_week;
+        assert_eq!(weeks.len(), 8);
+        assert_eq!(
+            weeks[7].week_start,
+            NaiveDate::from_ymd_opt(2024, 1, 8).unwrap()
+        );
+        assert_eq!(weeks[7].comments, 1);
+        assert_eq!(weeks[6].comments, 2);
+    }
+
+    #[test]
+    fn streak_uses_local_days() {
+        let utc = FixedOffset::east_opt(0).unwrap();
+        let tokyo = FixedOffset::east_opt(9 * HOUR as i32).unwrap();
+        // 23:00 UTC on Jan 9 is already Jan 10 in Tokyo, so a day later the
+        // streak has lapsed in UTC but not in Tokyo.
+        let late = NOW - 13 * HOUR;
+        let events = vec![event(1, ReviewEventKind::Commented, late)];
+        assert_eq!(compute(&events, Some(0), NOW, utc).current_streak_days, 1);
+        assert_eq!(
+            compute(&events, Some(0), NOW + DAY, utc).current_streak_days,
+            0
+        );
+        assert_eq!(
+            compute(&events, Some(0), NOW + DAY, tokyo).current_streak_days,
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn records_only_while_enabled() {
+        let dir = tempdir().unwrap();
+        let pool = db::initialize(&dir.path().join("t.db")).await.unwrap();
+        sqlx::query(
+            "INSERT INTO gitlab_instances (id, url, token, created_at) VALUES (1, 'u', 't', 0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        record(&pool, 1, 10, 1, ReviewEventKind::Commented, NOW)
+            .await
+            .unwrap();
+        let utc = FixedOffset::east_opt(0).unwrap();
+        assert!(!streaks(&pool, NOW, utc).await.unwrap().enabled);
+
+        set_enabled(&pool, true, NOW).await.unwrap();
+        record(&pool, 1, 10, 1, ReviewEventKind::ReviewRequested, NOW)
+            .await
+            .unwrap();
+        record(&pool, 1, 10, 1, ReviewEventKind::ReviewRequested, NOW + 5)
+            .await
+            .unwrap();
+        record(&pool, 1, 10, 1, ReviewEventKind::Approved, NOW + HOUR)
+            .await
+            .unwrap();
+        let exported = export(&pool, NOW + HOUR, utc).await.unwrap();
+        assert_eq!(exported.events.len(), 2);
+        assert_eq!(exported.stats.tracking_since, Some(NOW));
+        assert_eq!(exported.stats.median_response_secs, Some(HOUR));
+
+        set_enabled(&pool, false, NOW).await.unwrap();
+        assert!(load_events(&pool).await.unwrap().is_empty());
+    }
+}
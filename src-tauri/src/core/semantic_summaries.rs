@@ -0,0 +1,232 @@
+//! Cache of semantic (symbol-level) summaries of diff files.
+//!
+//! Parsing both sides of a file with tree-sitter is too slow to do for a
+//! whole file list on demand, so summaries are stored per file together with
+//! the head commit of the diff they describe. The idle background job fills
+//! the cache for files whose versions are cached; `summary_for` serves from
+//! it and computes (and stores) anything missing or stale.
+
+use crate::db::file_cache;
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use crate::services::memory_profile::{self, Subsystem};
+use crate::services::semantic_diff::{self, SemanticSummary};
+
+/// A diff file without an up-to-date summary.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct PendingFile {
+    pub mr_id: i64,
+    pub file_path: String,
+}
+
+async fn head_sha(pool: &DbPool, mr_id: i64) -> Result<Option<String>, AppError> {
+    let sha = sqlx::query_scalar("SELECT head_sha FROM diffs WHERE mr_id = ?")
+        .bind(mr_id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(sha)
+}
+
+async fn cached(
+    pool: &DbPool,
+    mr_id: i64,
+    file_path: &str,
+    head_sha: &str,
+) -> Result<Option<SemanticSummary>, AppError> {
+    let json: Option<String> = sqlx::query_scalar(
+        "SELECT summary FROM semantic_summaries
+         WHERE mr_id = ? AND file_path = ? AND head_sha = ?",
+    )
+    .bind(mr_id)
+    .bind(file_path)
+    .bind(head_sha)
+    .fetch_optional(pool)
+    .await?;
+    Ok(json.as_deref().map(serde_json::from_str).transpose()?)
+}
+
+async fn store(
+    pool: &DbPool,
+    mr_id: i64,
+    file_path: &str,
+    head_sha: &str,
+    summary: &SemanticSummary,
+    now: i64,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO semantic_summaries (mr_id, file_path, head_sha, summary, computed_at)
+         VALUES (?, ?, ?, ?, ?)
+         ON CONFLICT (mr_id, file_path) DO UPDATE SET
+             head_sha = excluded.head_sha,
+             summary = excluded.summary,
+             computed_at = excluded.computed_at",
+    )
+    .bind(mr_id)
+    .bind(file_path)
+    .bind(head_sha)
+    .bind(serde_json::to_string(summary)?)
+    .bind(now)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Summary of a file in the MR's cached diff, from the cache when it was
+/// computed at the current head. `None` when neither version of the file is
+/// cached.
+pub async fn summary_for(
+    pool: &DbPool,
+    mr_id: i64,
+    file_path: &str,
+    now: i64,
+) -> Result<Option<SemanticSummary>, AppError> {
+    let head_sha = head_sha(pool, mr_id).await?;
+    if let Some(sha) = &head_sha {
+        if let Some(summary) = cached(pool, mr_id, file_path, sha).await? {
+            return Ok(Some(summary));
+        }
+    }
+
+    let (base, head) = file_cache::get_cached_file_pair(pool, mr_id, file_path).await?;
+    if base.is_none() && head.is_none() {
+        return Ok(None);
+    }
+
+    // Parsing large files is CPU-bound; keep it off the async workers.
+    let path = file_path.to_string();
+    let summary = tokio::task::spawn_blocking(move || {
+        let _mem = memory_profile::enter(Subsystem::DiffParsing);
+        semantic_diff::summarize(&path, base.as_deref(), head.as_deref())
+    })
+    .await
+    .map_err(|e| AppError::internal(format!("Semantic summary failed: {}", e)))?;
+
+    if let Some(sha) = &head_sha {
+        store(pool, mr_id, file_path, sha, &summary, now).await?;
+    }
+    Ok(Some(summary))
+}
+
+/// Up to `limit` diff files with a cached file version but no summary at
+/// their MR's current head, most recently updated MRs first.
+pub async fn pending(pool: &DbPool, limit: i64) -> Result<Vec<PendingFile>, AppError> {
+    let files = sqlx::query_as::<_, PendingFile>(
+        "SELECT f.mr_id, f.new_path AS file_path
+         FROM diff_files f
+         JOIN diffs d ON d.mr_id = f.mr_id
+         JOIN merge_requests m ON m.id = f.mr_id
+         LEFT JOIN semantic_summaries s
+             ON s.mr_id = f.mr_id AND s.file_path = f.new_path AND s.head_sha = d.head_sha
+         WHERE s.mr_id IS NULL
+           AND EXISTS (SELECT 1 FROM file_versions v
+                       WHERE v.mr_id = f.mr_id AND v.file_path = f.new_path)
+         ORDER BY m.updated_at DESC, f.file_position
+         LIMIT ?",
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+
+    async fn seed_diff(pool: &DbPool, inst: i64, head: &str) {
+        sqlx::query(
+            "INSERT OR IGNORE INTO merge_requests
+             (id, instance_id, iid, project_id, project_name, title, author_username,
+              source_branch, target_branch, state, web_url, created_at, updated_at,
+              labels, reviewers, cached_at)
+             VALUES (1, ?, 1, 10, 'g/p', 'MR', 'alice', 's', 'main', 'opened',
+                     'http://x', 0, 0, '[]', '[]', 0)",
+        )
+        .bind(inst)
+        .execute(pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO diffs (mr_id, content, base_sha, head_sha, start_sha,
+                                file_count, additions, deletions)
+             VALUES (1, '', 'b', ?, 'b', 0, 0, 0)
+             ON CONFLICT (mr_id) DO UPDATE SET head_sha = excluded.head_sha",
+        )
+        .bind(head)
+        .execute(pool)
+        .await
+        .unwrap();
+        for (i, path) in ["src/lib.rs", "README.md"].iter().enumerate() {
+            sqlx::query(
+                "INSERT OR IGNORE INTO diff_files (id, mr_id, old_path, new_path, change_type,
+                                                   additions, deletions, file_position)
+                 VALUES (?, 1, ?, ?, 'modified', 1, 0, ?)",
+            )
+            .bind(i as i64 + 1)
+            .bind(path)
+            .bind(path)
+            .bind(i as i64)
+            .execute(pool)
+            .await
+            .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn summaries_are_cached_per_head() {
+        let (pool, inst) = seed_instance(true).await;
+        seed_diff(&pool, inst, "aaa").await;
+        // Only src/lib.rs has a cached version.
+        let content = "fn added() {}\n";
+        file_cache::upsert_file_blob(&pool, "blob", content, content.len() as i64)
+            .await
+            .unwrap();
+        file_cache::upsert_file_version(
+            &pool,
+            1,
+            "src/lib.rs",
+            "head",
+            "blob",
+            &inst.to_string(),
+            10,
+        )
+        .await
+        .unwrap();
+
+        let pending_paths = |files: Vec<PendingFile>| -> Vec<String> {
+            files.into_iter().map(|f| f.file_path).collect()
+        };
+        assert_eq!(
+            pending_paths(pending(&pool, 10).await.unwrap()),
+            vec!["src/lib.rs"]
+        );
+
+        let summary = summary_for(&pool, 1, "src/lib.rs", 100)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(summary.language.as_deref(), Some("rust"));
+        assert_eq!(summary.changes.len(), 1);
+        assert!(pending(&pool, 10).await.unwrap().is_empty());
+        assert!(summary_for(&pool, 1, "README.md", 100)
+            .await
+            .unwrap()
+            .is_none());
+
+        // A new push makes the stored summary stale.
+        seed_diff(&pool, inst, "bbb").await;
+        assert_eq!(
+            pending_paths(pending(&pool, 10).await.unwrap()),
+            vec!["src/lib.rs"]
+        );
+        let cached_summary = cached(&pool, 1, "src/lib.rs", "aaa").await.unwrap();
+        assert_eq!(cached_summary.unwrap().summary, summary.summary);
+        summary_for(&pool, 1, "src/lib.rs", 200).await.unwrap();
+        assert!(cached(&pool, 1, "src/lib.rs", "aaa")
+            .await
+            .unwrap()
+            .is_none());
+        assert!(pending(&pool, 10).await.unwrap().is_empty());
+    }
+}
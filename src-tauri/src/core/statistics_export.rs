@@ -0,0 +1,440 @@
+//! Statistics export for home-made dashboards.
+//!
+//! Review activity (the opt-in `review_activity` log), sync performance
+//! (`sync_metrics`) and pipeline outcomes (MR head pipelines and the latest
+//! pipeline of each watched project) are read from the local tables for a
+//! time range and written to one file.
+//!
+//! JSON keeps each dataset as its own array of typed records. CSV, which has
+//! to be a single table, uses a long format that spreadsheets and BI tools
+//! pivot easily: one row per record with `dataset`, `occurred_at`,
+//! `instance_url`, `project_id`, `mr_iid`, `name`, `value` and `unit`
+//! columns, where `name` is the event kind, sync phase or pipeline status.
+
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Time range of an export, in Unix seconds. `from` is inclusive, `to`
+/// exclusive; an open end covers everything on that side.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsRange {
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+}
+
+impl StatsRange {
+    fn bounds(&self) -> (i64, i64) {
+        (self.from.unwrap_or(i64::MIN), self.to.unwrap_or(i64::MAX))
+    }
+
+    fn contains(&self, ts: i64) -> bool {
+        let (from, to) = self.bounds();
+        from <= ts && ts < to
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+/// One logged review event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewActivityRecord {
+    pub instance_url: String,
+    pub project_id: i64,
+    pub mr_iid: i64,
+    /// `review_requested`, `commented` or `approved`.
+    pub kind: String,
+    pub occurred_at: i64,
+}
+
+/// Timing of one sync phase, for one MR or a whole instance.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncMetricRecord {
+    pub sync_run_id: String,
+    pub phase: String,
+    pub instance_url: Option<String>,
+    pub mr_iid: Option<i64>,
+    pub duration_ms: i64,
+    pub api_calls: i64,
+    pub items_processed: i64,
+    pub recorded_at: i64,
+}
+
+/// Last known status of a pipeline: an MR's head pipeline (`mr_iid` set)
+/// or a watched project's latest one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PipelineOutcomeRecord {
+    pub instance_url: String,
+    pub project_id: i64,
+    pub mr_iid: Option<i64>,
+    pub ref_name: String,
+    pub status: String,
+    /// Only known for project pipelines.
+    pub duration_secs: Option<i64>,
+    /// When the pipeline was created (projects) or the MR last updated.
+    pub occurred_at: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatisticsExport {
+    pub exported_at: i64,
+    pub range: StatsRange,
+    pub review_activity: Vec<ReviewActivityRecord>,
+    pub sync_performance: Vec<SyncMetricRecord>,
+    pub pipeline_outcomes: Vec<PipelineOutcomeRecord>,
+}
+
+/// What an export wrote.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatisticsExportSummary {
+    pub review_events: usize,
+    pub sync_metrics: usize,
+    pub pipelines: usize,
+}
+
+/// Read every dataset for `range`, oldest first.
+pub async fn collect(
+    pool: &DbPool,
+    range: StatsRange,
+    now: i64,
+) -> Result<StatisticsExport, AppError> {
+    let (from, to) = range.bounds();
+
+    let review_activity = sqlx::query_as(
+        "SELECT g.url AS instance_url, r.project_id, r.mr_iid, r.kind, r.occurred_at
+         FROM review_activity r JOIN gitlab_instances g ON g.id = r.instance_id
+         WHERE r.occurred_at >= ? AND r.occurred_at < ?
+         ORDER BY r.occurred_at, r.id",
+    )
+    .bind(from)
+    .bind(to)
+    .fetch_all(pool)
+    .await?;
+
+    let sync_performance = sqlx::query_as(
+        "SELECT m.sync_run_id, m.phase, g.url AS instance_url, m.mr_iid, m.duration_ms,
+                COALESCE(m.api_calls, 0) AS api_calls,
+                COALESCE(m.items_processed, 0) AS items_processed,
+                m.timestamp AS recorded_at
+         FROM sync_metrics m LEFT JOIN gitlab_instances g ON g.id = m.instance_id
+         WHERE m.timestamp >= ? AND m.timestamp < ?
+         ORDER BY m.timestamp, m.id",
+    )
+    .bind(from)
+    .bind(to)
+    .fetch_all(pool)
+    .await?;
+
+    let mr_pipelines: Vec<(String, i64, i64, String, String, i64)> = sqlx::query_as(
+        "SELECT g.url, m.project_id, m.iid, m.source_branch, m.head_pipeline_status, m.updated_at
+         FROM merge_requests m JOIN gitlab_instances g ON g.id = m.instance_id
+         WHERE m.head_pipeline_status IS NOT NULL
+           AND m.updated_at >= ? AND m.updated_at < ?",
+    )
+    .bind(from)
+    .bind(to)
+    .fetch_all(pool)
+    .await?;
+    // Creation times are stored as ISO 8601 text, so the range is applied here.
+    let project_pipelines: Vec<(String, i64, String, String, Option<i64>, String)> =
+        sqlx::query_as(
+            "SELECT g.url, p.project_id, p.ref_name, p.status, p.duration, p.created_at
+             FROM pipeline_status_cache p JOIN gitlab_instances g ON g.id = p.instance_id",
+        )
+        .fetch_all(pool)
+        .await?;
+
+    let mut pipeline_outcomes: Vec<PipelineOutcomeRecord> = mr_pipelines
+        .into_iter()
+        .map(
+            |(instance_url, project_id, iid, ref_name, status, updated_at)| PipelineOutcomeRecord {
+                instance_url,
+                project_id,
+                mr_iid: Some(iid),
+                ref_name,
+                status,
+                duration_secs: None,
+                occurred_at: updated_at,
+            },
+        )
+        .collect();
+    pipeline_outcomes.extend(project_pipelines.into_iter().filter_map(
+        |(instance_url, project_id, ref_name, status, duration, created_at)| {
+            let occurred_at = chrono::DateTime::parse_from_rfc3339(&created_at)
+                .ok()?
+                .timestamp();
+            range
+                .contains(occurred_at)
+                .then_some(PipelineOutcomeRecord {
+                    instance_url,
+                    project_id,
+                    mr_iid: None,
+                    ref_name,
+                    status,
+                    duration_secs: duration,
+                    occurred_at,
+                })
+        },
+    ));
+    pipeline_outcomes.sort_by_key(|p| p.occurred_at);
+
+    Ok(StatisticsExport {
+        exported_at: now,
+        range,
+        review_activity,
+        sync_performance,
+        pipeline_outcomes,
+    })
+}
+
+/// Quote a CSV field when it needs it (RFC 4180).
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn iso(ts: i64) -> String {
+    chrono::DateTime::from_timestamp(ts, 0)
+        .map(|d| d.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+        .unwrap_or_default()
+}
+
+/// Render `export` in the long CSV format described in the module docs.
+pub fn to_csv(export: &StatisticsExport) -> String {
+    let mut out =
+        String::from("dataset,occurred_at,instance_url,project_id,mr_iid,name,value,unit\n");
+    let mut row = |dataset: &str,
+                   at: i64,
+                   instance_url: Option<&str>,
+                   project_id: Option<i64>,
+                   mr_iid: Option<i64>,
+                   name: &str,
+                   value: Option<i64>,
+                   unit: &str| {
+        let opt = |n: Option<i64>| n.map(|n| n.to_string()).unwrap_or_default();
+        let fields = [
+            dataset.to_string(),
+            iso(at),
+            csv_field(instance_url.unwrap_or_default()),
+            opt(project_id),
+            opt(mr_iid),
+            csv_field(name),
+            opt(value),
+            unit.to_string(),
+        ];
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    };
+
+    for r in &export.review_activity {
+        row(
+            "review_activity",
+            r.occurred_at,
+            Some(&r.instance_url),
+            Some(r.project_id),
+            Some(r.mr_iid),
+            &r.kind,
+            Some(1),
+            "count",
+        );
+    }
+    for m in &export.sync_performance {
+        row(
+            "sync_performance",
+            m.recorded_at,
+            m.instance_url.as_deref(),
+            None,
+            m.mr_iid,
+            &m.phase,
+            Some(m.duration_ms),
+            "ms",
+        );
+    }
+    for p in &export.pipeline_outcomes {
+        row(
+            "pipeline_outcomes",
+            p.occurred_at,
+            Some(&p.instance_url),
+            Some(p.project_id),
+            p.mr_iid,
+            &p.status,
+            p.duration_secs,
+            if p.duration_secs.is_some() { "s" } else { "" },
+        );
+    }
+    out
+}
+
+fn export_error(action: &str, path: &Path, e: impl std::fmt::Display) -> AppError {
+    AppError::internal(format!("Failed to {action} {}: {e}", path.display()))
+}
+
+/// Write `contents` to `path`, replacing the file atomically.
+fn write_file(path: &Path, contents: &[u8]) -> Result<(), AppError> {
+    let mut tmp = PathBuf::from(path);
+    tmp.as_mut_os_string().push(".partial");
+
+    let file = File::create(&tmp).map_err(|e| export_error("create", &tmp, e))?;
+    let mut writer = BufWriter::new(file);
+    writer
+        .write_all(contents)
+        .and_then(|_| writer.flush())
+        .map_err(|e| export_error("write", &tmp, e))?;
+    std::fs::rename(&tmp, path).map_err(|e| export_error("write", path, e))
+}
+
+/// Export the statistics for `range` to `path` in `format`.
+pub async fn export(
+    pool: &DbPool,
+    range: StatsRange,
+    format: ExportFormat,
+    path: &Path,
+    now: i64,
+) -> Result<StatisticsExportSummary, AppError> {
+    let export = collect(pool, range, now).await?;
+    let summary = StatisticsExportSummary {
+        review_events: export.review_activity.len(),
+        sync_metrics: export.sync_performance.len(),
+        pipelines: export.pipeline_outcomes.len(),
+    };
+    let contents = match format {
+        ExportFormat::Csv => to_csv(&export).into_bytes(),
+        ExportFormat::Json => serde_json::to_vec_pretty(&export)
+            .map_err(|e| AppError::internal(format!("Failed to encode statistics: {e}")))?,
+    };
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || write_file(&path, &contents))
+        .await
+        .map_err(|e| AppError::internal(format!("Statistics writer panicked: {e}")))??;
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db;
+
+    async fn setup() -> (tempfile::TempDir, DbPool) {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = db::initialize(&dir.path().join("test.db")).await.unwrap();
+        sqlx::query("INSERT INTO gitlab_instances (id, url, name) VALUES (1, 'https://gitlab.com', 'GitLab')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        for (kind, at) in [
+            ("review_requested", 100),
+            ("commented", 200),
+            ("approved", 900),
+        ] {
+            sqlx::query(
+                "INSERT INTO review_activity (instance_id, project_id, mr_iid, kind, occurred_at)
+                 VALUES (1, 7, 5, ?, ?)",
+            )
+            .bind(kind)
+            .bind(at)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        sqlx::query(
+            "INSERT INTO sync_metrics (sync_run_id, phase, instance_id, mr_iid, duration_ms, timestamp)
+             VALUES ('run-1', 'diff', 1, 5, 420, 150)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO merge_requests (id, instance_id, iid, project_id, title, author_username,
+             source_branch, target_branch, state, web_url, created_at, updated_at, head_pipeline_status)
+             VALUES (10, 1, 5, 7, 'MR', 'alice', 'feature', 'main', 'opened',
+                     'https://gitlab.com/g/p/-/merge_requests/5', 0, 300, 'failed')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        (dir, pool)
+    }
+
+    #[tokio::test]
+    async fn test_collect_filters_by_range() {
+        let (_dir, pool) = setup().await;
+        let range = StatsRange {
+            from: Some(150),
+            to: Some(900),
+        };
+        let export = collect(&pool, range, 1_000).await.unwrap();
+
+        let kinds: Vec<_> = export
+            .review_activity
+            .iter()
+            .map(|r| r.kind.as_str())
+            .collect();
+        assert_eq!(kinds, vec!["commented"]);
+        assert_eq!(export.sync_performance.len(), 1);
+        assert_eq!(
+            export.sync_performance[0].instance_url.as_deref(),
+            Some("https://gitlab.com")
+        );
+        assert_eq!(export.pipeline_outcomes.len(), 1);
+        assert_eq!(export.pipeline_outcomes[0].status, "failed");
+
+        let all = collect(&pool, StatsRange::default(), 1_000).await.unwrap();
+        assert_eq!(all.review_activity.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_export_writes_long_format_csv() {
+        let (dir, pool) = setup().await;
+        let path = dir.path().join("stats.csv");
+        let summary = export(
+            &pool,
+            StatsRange {
+                from: Some(200),
+                to: None,
+            },
+            ExportFormat::Csv,
+            &path,
+            1_000,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            summary,
+            StatisticsExportSummary {
+                review_events: 2,
+                sync_metrics: 0,
+                pipelines: 1,
+            }
+        );
+
+        let csv = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "dataset,occurred_at,instance_url,project_id,mr_iid,name,value,unit",
+                "review_activity,1970-01-01T00:03:20Z,https://gitlab.com,7,5,commented,1,count",
+                "review_activity,1970-01-01T00:15:00Z,https://gitlab.com,7,5,approved,1,count",
+                "pipeline_outcomes,1970-01-01T00:05:00Z,https://gitlab.com,7,5,failed,,",
+            ]
+        );
+        assert_eq!(csv_field("feature, v2"), "\"feature, v2\"");
+    }
+}
@@ -0,0 +1,392 @@
+//! Suggested changes in diff notes.
+//!
+//! GitLab parses ```suggestion blocks out of diff notes and gives each one an
+//! id that can be applied, which commits the change to the MR's source
+//! branch. The ids are cached per note at sync time. Applying one also
+//! patches the cached diff of the file, so the change shows before the next
+//! sync brings in the new commit.
+
+use crate::core::comments::build_suggestion_block;
+use crate::core::mr_actions::mr_api_ids;
+use crate::core::{create_client, token_scopes};
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use crate::models::Comment;
+use crate::services::gitlab_client::GitLabSuggestion;
+use serde::Serialize;
+use sqlx::FromRow;
+use std::collections::HashMap;
+
+/// A suggestion in a cached diff note.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentSuggestion {
+    pub id: i64,
+    pub comment_id: i64,
+    /// First and last new-side line the suggestion replaces.
+    pub from_line: i64,
+    pub to_line: i64,
+    pub to_content: String,
+    /// False once applied, or when GitLab refuses it (e.g. outdated).
+    pub appliable: bool,
+    pub applied: bool,
+}
+
+/// `body` followed by a suggestion replacing the commented line with `content`.
+pub fn with_suggestion(body: &str, content: &str) -> String {
+    let block = build_suggestion_block(content.trim_end_matches('\n'), 0, 0);
+    if body.trim().is_empty() {
+        block
+    } else {
+        format!("{}\n\n{}", body.trim_end(), block)
+    }
+}
+
+/// Replace the cached suggestions of a diff note with `suggestions`.
+pub async fn save_for_comment(
+    pool: &DbPool,
+    comment_id: i64,
+    suggestions: &[GitLabSuggestion],
+) -> Result<(), AppError> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM comment_suggestions WHERE comment_id = ?")
+        .bind(comment_id)
+        .execute(&mut *tx)
+        .await?;
+    for s in suggestions {
+        sqlx::query(
+            "INSERT INTO comment_suggestions
+             (id, comment_id, from_line, to_line, to_content, appliable, applied)
+             VALUES (?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(id) DO UPDATE SET
+                 comment_id = excluded.comment_id,
+                 from_line = excluded.from_line,
+                 to_line = excluded.to_line,
+                 to_content = excluded.to_content,
+                 appliable = excluded.appliable,
+                 applied = excluded.applied",
+        )
+        .bind(s.id)
+        .bind(comment_id)
+        .bind(s.from_line)
+        .bind(s.to_line)
+        .bind(&s.to_content)
+        .bind(s.appliable)
+        .bind(s.applied)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Cached suggestions of `comments`, by comment id.
+pub async fn for_comments(
+    pool: &DbPool,
+    comments: &[Comment],
+) -> Result<HashMap<i64, Vec<CommentSuggestion>>, AppError> {
+    let mut mr_ids: Vec<i64> = comments
+        .iter()
+        .filter(|c| c.file_path.is_some())
+        .map(|c| c.mr_id)
+        .collect();
+    mr_ids.sort_unstable();
+    mr_ids.dedup();
+
+    let mut by_comment: HashMap<i64, Vec<CommentSuggestion>> = HashMap::new();
+    for mr_id in mr_ids {
+        let rows = sqlx::query_as::<_, CommentSuggestion>(
+            "SELECT s.id, s.comment_id, s.from_line, s.to_line, s.to_content,
+                    s.appliable, s.applied
+             FROM comment_suggestions s
+             JOIN comments c ON c.id = s.comment_id
+             WHERE c.mr_id = ?
+             ORDER BY s.comment_id, s.from_line, s.id",
+        )
+        .bind(mr_id)
+        .fetch_all(pool)
+        .await?;
+        for row in rows {
+            by_comment.entry(row.comment_id).or_default().push(row);
+        }
+    }
+    Ok(by_comment)
+}
+
+/// Rewrite a file's unified diff as if the new-side lines `from_line..=to_line`
+/// had been replaced by `to_content`. `None` when the lines aren't all in one
+/// hunk of the diff.
+pub fn patch_diff(diff: &str, from_line: i64, to_line: i64, to_content: &str) -> Option<String> {
+    let lines: Vec<&str> = diff.lines().collect();
+    let mut out: Vec<String> = Vec::with_capacity(lines.len() + 4);
+    let mut shift = 0;
+    let mut patched = false;
+    let mut i = 0;
+
+    while i < lines.len() {
+        let Some(header) = HunkHeader::parse(lines[i]) else {
+            out.push(lines[i].to_string());
+            i += 1;
+            continue;
+        };
+        let end = lines[i + 1..]
+            .iter()
+            .position(|l| l.starts_with("@@"))
+            .map_or(lines.len(), |p| i + 1 + p);
+        let body = &lines[i + 1..end];
+
+        let replaced = if patched {
+            None
+        } else {
+            patch_hunk(body, header.new_start, from_line, to_line, to_content)
+        };
+        let body: Vec<String> = match replaced {
+            Some(new_body) => {
+                patched = true;
+                new_body
+            }
+            None => body.iter().map(|l| l.to_string()).collect(),
+        };
+        let (old_count, new_count) = count_sides(&body);
+        out.push(header.render(header.new_start + shift, old_count, new_count));
+        shift += new_count - header.new_count;
+        out.extend(body);
+        i = end;
+    }
+
+    if !patched {
+        return None;
+    }
+    let mut patched_diff = out.join("\n");
+    if diff.ends_with('\n') {
+        patched_diff.push('\n');
+    }
+    Some(patched_diff)
+}
+
+struct HunkHeader<'a> {
+    old_start: i64,
+    new_start: i64,
+    new_count: i64,
+    /// Text after the closing `@@`, usually the enclosing function.
+    section: &'a str,
+}
+
+impl<'a> HunkHeader<'a> {
+    fn parse(line: &'a str) -> Option<Self> {
+        let rest = line.strip_prefix("@@ -")?;
+        let (ranges, section) = rest.split_once(" @@")?;
+        let (old, new) = ranges.split_once(" +")?;
+        let start_count = |range: &str| -> Option<(i64, i64)> {
+            match range.split_once(',') {
+                Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+                None => Some((range.parse().ok()?, 1)),
+            }
+        };
+        let (old_start, _) = start_count(old)?;
+        let (new_start, new_count) = start_count(new)?;
+        Some(Self {
+            old_start,
+            new_start,
+            new_count,
+            section,
+        })
+    }
+
+    fn render(&self, new_start: i64, old_count: i64, new_count: i64) -> String {
+        format!(
+            "@@ -{},{} +{},{} @@{}",
+            self.old_start, old_count, new_start, new_count, self.section
+        )
+    }
+}
+
+fn count_sides(body: &[String]) -> (i64, i64) {
+    body.iter()
+        .fold((0, 0), |(old, new), line| match line.chars().next() {
+            Some('-') => (old + 1, new),
+            Some('+') => (old, new + 1),
+            Some('\\') => (old, new),
+            _ => (old + 1, new + 1),
+        })
+}
+
+fn patch_hunk(
+    body: &[&str],
+    new_start: i64,
+    from_line: i64,
+    to_line: i64,
+    to_content: &str,
+) -> Option<Vec<String>> {
+    let mut out = Vec::with_capacity(body.len());
+    let mut new_line = new_start;
+    let mut replaced = 0;
+
+    for &line in body {
+        match line.chars().next() {
+            Some('-') | Some('\\') => out.push(line.to_string()),
+            tag => {
+                if (from_line..=to_line).contains(&new_line) {
+                    replaced += 1;
+                    // Context lines now go away; added lines are dropped.
+                    if tag != Some('+') {
+                        out.push(format!("-{}", line.get(1..).unwrap_or_default()));
+                    }
+                    if new_line == to_line {
+                        out.extend(to_content.lines().map(|l| format!("+{}", l)));
+                    }
+                } else {
+                    out.push(line.to_string());
+                }
+                new_line += 1;
+            }
+        }
+    }
+
+    (replaced == to_line - from_line + 1).then_some(out)
+}
+
+/// Apply a suggestion on GitLab and patch the cached diff of its file.
+pub async fn apply(
+    pool: &DbPool,
+    mr_id: i64,
+    suggestion_id: i64,
+    commit_message: Option<&str>,
+) -> Result<CommentSuggestion, AppError> {
+    token_scopes::ensure_can_write(pool, mr_id).await?;
+    let file_path: Option<String> = sqlx::query_scalar(
+        "SELECT c.file_path FROM comment_suggestions s
+         JOIN comments c ON c.id = s.comment_id
+         WHERE s.id = ? AND c.mr_id = ?",
+    )
+    .bind(suggestion_id)
+    .bind(mr_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::not_found_with_id("Suggestion", suggestion_id.to_string()))?;
+
+    let (instance_id, _, _) = mr_api_ids(pool, mr_id).await?;
+    let client = create_client(pool, instance_id).await?;
+    let applied = client
+        .apply_suggestion(suggestion_id, commit_message)
+        .await?;
+
+    sqlx::query("UPDATE comment_suggestions SET applied = 1, appliable = 0 WHERE id = ?")
+        .bind(suggestion_id)
+        .execute(pool)
+        .await?;
+    if let Some(path) = file_path {
+        patch_cached_diff(pool, mr_id, &path, &applied).await?;
+    }
+
+    let suggestion = sqlx::query_as::<_, CommentSuggestion>(
+        "SELECT id, comment_id, from_line, to_line, to_content, appliable, applied
+         FROM comment_suggestions WHERE id = ?",
+    )
+    .bind(suggestion_id)
+    .fetch_one(pool)
+    .await?;
+    Ok(suggestion)
+}
+
+async fn patch_cached_diff(
+    pool: &DbPool,
+    mr_id: i64,
+    path: &str,
+    applied: &GitLabSuggestion,
+) -> Result<(), AppError> {
+    let cached: Option<(i64, Option<String>)> =
+        sqlx::query_as("SELECT id, diff_content FROM diff_files WHERE mr_id = ? AND new_path = ?")
+            .bind(mr_id)
+            .bind(path)
+            .fetch_optional(pool)
+            .await?;
+    let Some((file_id, Some(old_diff))) = cached else {
+        return Ok(());
+    };
+    let Some(new_diff) = patch_diff(
+        &old_diff,
+        applied.from_line,
+        applied.to_line,
+        &applied.to_content,
+    ) else {
+        // Not in the cached hunks; the next sync brings the new commit.
+        return Ok(());
+    };
+
+    let old_lines: Vec<String> = old_diff.lines().map(str::to_string).collect();
+    let new_lines: Vec<String> = new_diff.lines().map(str::to_string).collect();
+    let (old_removed, old_added) = changed_lines(&old_lines);
+    let (new_removed, new_added) = changed_lines(&new_lines);
+
+    let mut tx = pool.begin().await?;
+    sqlx::query(
+        "UPDATE diff_files SET diff_content = ?, additions = ?, deletions = ? WHERE id = ?",
+    )
+    .bind(&new_diff)
+    .bind(new_added)
+    .bind(new_removed)
+    .bind(file_id)
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query(
+        "UPDATE diffs SET content = replace(content, ?, ?),
+                          additions = additions + ?, deletions = deletions + ?
+         WHERE mr_id = ?",
+    )
+    .bind(&old_diff)
+    .bind(&new_diff)
+    .bind(new_added - old_added)
+    .bind(new_removed - old_removed)
+    .bind(mr_id)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// `(removed, added)` line counts of a diff.
+fn changed_lines(lines: &[String]) -> (i64, i64) {
+    lines
+        .iter()
+        .filter(|l| !l.starts_with("@@"))
+        .fold((0, 0), |(removed, added), l| match l.chars().next() {
+            Some('-') => (removed + 1, added),
+            Some('+') => (removed, added + 1),
+            _ => (removed, added),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str =
+        "@@ -1,4 +1,5 @@ fn main() {\n a\n-b\n+B\n+C\n d\n e\n@@ -10,2 +11,2 @@\n x\n-y\n+Y\n";
+
+    #[test]
+    fn suggestion_follows_the_comment_body() {
+        assert_eq!(
+            with_suggestion("Rename this ", "let count = 2;\n"),
+            "Rename this\n\n```suggestion:-0+0\nlet count = 2;\n```\n"
+        );
+        assert_eq!(with_suggestion("  ", "x"), "```suggestion:-0+0\nx\n```\n");
+    }
+
+    #[test]
+    fn applying_to_added_and_context_lines_rewrites_the_hunk() {
+        // New-side lines 2-4 are "B", "C" (added) and "d" (context).
+        let patched = patch_diff(DIFF, 2, 4, "Z\n").unwrap();
+        assert_eq!(
+            patched,
+            "@@ -1,4 +1,3 @@ fn main() {\n a\n-b\n-d\n+Z\n e\n@@ -10,2 +9,2 @@\n x\n-y\n+Y\n"
+        );
+    }
+
+    #[test]
+    fn lines_outside_the_cached_hunks_are_not_patched() {
+        assert_eq!(patch_diff(DIFF, 7, 7, "z"), None);
+        // Spans the gap between the hunks.
+        assert_eq!(patch_diff(DIFF, 5, 11, "z"), None);
+    }
+}
@@ -0,0 +1,216 @@
+//! Token scope checks.
+//!
+//! Reading MRs needs `read_api`; comments, approvals and the other queued
+//! actions need `api`. Setup reads the token's scopes from
+//! `/personal_access_tokens/self`, probes the MR list endpoint the sync
+//! relies on, and stores the scopes on the instance. A token without `api`
+//! then gets a clear error when an action is attempted instead of queueing
+//! actions GitLab will refuse. Tokens whose scopes can't be read (project or
+//! group tokens on older GitLab versions) are stored without scopes and
+//! nothing is disabled.
+
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use crate::services::gitlab_client::{GitLabClient, MergeRequestsQuery};
+use serde::Serialize;
+
+/// Scope needed for comments, approvals and other writes.
+pub const WRITE_SCOPE: &str = "api";
+
+/// Scope needed to read merge requests.
+pub const READ_SCOPE: &str = "read_api";
+
+/// Scopes the app uses; anything else is more than it needs.
+const USED_SCOPES: &[&str] = &[WRITE_SCOPE, READ_SCOPE, "read_user", "self_rotate"];
+
+/// Shown when an action needs the `api` scope the token lacks.
+pub const READ_ONLY_MESSAGE: &str =
+    "This instance's token only has read access. Add the api scope to the token to comment, approve or change merge requests.";
+
+/// Outcome of checking a token's scopes.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScopeCheck {
+    /// The token's scopes, or `None` when GitLab didn't report them.
+    pub scopes: Option<Vec<String>>,
+    /// Whether write actions are allowed. True when the scopes are unknown.
+    pub can_write: bool,
+    /// Scopes broader than needed, or missing for write actions.
+    pub warnings: Vec<String>,
+}
+
+/// Check a reported scope list. Fails when the token can't read MRs.
+pub fn assess(scopes: &[String]) -> Result<ScopeCheck, AppError> {
+    let has = |scope: &str| scopes.iter().any(|s| s == scope);
+    let can_write = has(WRITE_SCOPE);
+    if !can_write && !has(READ_SCOPE) {
+        return Err(AppError::invalid_input_field(
+            format!(
+                "The token needs the read_api scope, or api to also comment and approve. It has: {}",
+                if scopes.is_empty() {
+                    "no scopes".to_string()
+                } else {
+                    scopes.join(", ")
+                }
+            ),
+            "token",
+        ));
+    }
+
+    let mut warnings = Vec::new();
+    if !can_write {
+        warnings.push(
+            "The token is read-only (read_api): commenting, approving and other actions are disabled."
+                .to_string(),
+        );
+    }
+    let extra: Vec<&str> = scopes
+        .iter()
+        .map(String::as_str)
+        .filter(|s| !USED_SCOPES.contains(s))
+        .collect();
+    if !extra.is_empty() {
+        warnings.push(format!(
+            "The token has scopes this app doesn't use ({}). A token with only {} is enough.",
+            extra.join(", "),
+            if can_write { WRITE_SCOPE } else { READ_SCOPE }
+        ));
+    }
+
+    Ok(ScopeCheck {
+        scopes: Some(scopes.to_vec()),
+        can_write,
+        warnings,
+    })
+}
+
+/// Check a token against GitLab: its reported scopes, then the MR list
+/// endpoint the sync reads.
+pub async fn verify(client: &GitLabClient) -> Result<ScopeCheck, AppError> {
+    let check = match client.get_token_info().await {
+        Ok(info) => assess(&info.scopes)?,
+        // Not a personal access token, or a GitLab without the endpoint.
+        Err(AppError::GitLabApi { .. }) | Err(AppError::NotFound { .. }) => ScopeCheck {
+            scopes: None,
+            can_write: true,
+            warnings: Vec::new(),
+        },
+        Err(e) => return Err(e),
+    };
+
+    let probe = MergeRequestsQuery {
+        scope: Some("assigned_to_me".to_string()),
+        per_page: Some(1),
+        ..Default::default()
+    };
+    client.list_merge_requests(&probe).await.map_err(|e| {
+        AppError::invalid_input_field(
+            format!("The token can't list merge requests: {}", e),
+            "token",
+        )
+    })?;
+
+    Ok(check)
+}
+
+/// Store the verified scopes on an instance.
+pub async fn store(pool: &DbPool, instance_id: i64, check: &ScopeCheck) -> Result<(), AppError> {
+    let scopes = check
+        .scopes
+        .as_ref()
+        .map(serde_json::to_string)
+        .transpose()?;
+    sqlx::query("UPDATE gitlab_instances SET token_scopes = ? WHERE id = ?")
+        .bind(scopes)
+        .bind(instance_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Whether a stored scope list allows writes. Unknown scopes do.
+pub fn allows_write(stored: Option<&str>) -> bool {
+    stored
+        .and_then(|s| serde_json::from_str::<Vec<String>>(s).ok())
+        .is_none_or(|scopes| scopes.iter().any(|s| s == WRITE_SCOPE))
+}
+
+/// Fail with a readable error when the token of the MR's instance can't
+/// write to GitLab.
+pub async fn ensure_can_write(pool: &DbPool, mr_id: i64) -> Result<(), AppError> {
+    let stored: Option<Option<String>> = sqlx::query_scalar(
+        "SELECT i.token_scopes FROM merge_requests mr
+         JOIN gitlab_instances i ON i.id = mr.instance_id
+         WHERE mr.id = ?",
+    )
+    .bind(mr_id)
+    .fetch_optional(pool)
+    .await?;
+    if allows_write(stored.flatten().as_deref()) {
+        Ok(())
+    } else {
+        Err(AppError::invalid_input(READ_ONLY_MESSAGE))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+
+    fn scopes(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn api_scope_allows_writes_without_warnings() {
+        let check = assess(&scopes(&["api", "read_user"])).unwrap();
+        assert!(check.can_write);
+        assert!(check.warnings.is_empty());
+    }
+
+    #[test]
+    fn warns_about_read_only_and_extra_scopes() {
+        let check = assess(&scopes(&["read_api"])).unwrap();
+        assert!(!check.can_write);
+        assert_eq!(check.warnings.len(), 1);
+
+        let check = assess(&scopes(&["api", "sudo", "write_repository"])).unwrap();
+        assert_eq!(check.warnings.len(), 1);
+        assert!(check.warnings[0].contains("sudo, write_repository"));
+    }
+
+    #[test]
+    fn rejects_tokens_that_cannot_read() {
+        assert!(assess(&scopes(&["read_user"])).is_err());
+        assert!(assess(&[]).is_err());
+    }
+
+    #[tokio::test]
+    async fn blocks_writes_only_for_known_read_only_tokens() {
+        let (pool, inst) = seed_instance(true).await;
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, title, author_username, source_branch,
+              target_branch, state, web_url, created_at, updated_at, cached_at)
+             VALUES (1, ?, 1, 10, 't', 'alice', 's', 'main', 'opened', 'http://x', 0, 0, 0)",
+        )
+        .bind(inst)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // Unknown scopes never block.
+        assert!(ensure_can_write(&pool, 1).await.is_ok());
+
+        let read_only = assess(&scopes(&["read_api"])).unwrap();
+        store(&pool, inst, &read_only).await.unwrap();
+        let err = ensure_can_write(&pool, 1).await.unwrap_err();
+        assert!(err.to_string().contains("read access"));
+
+        store(&pool, inst, &assess(&scopes(&["api"])).unwrap())
+            .await
+            .unwrap();
+        assert!(ensure_can_write(&pool, 1).await.is_ok());
+    }
+}
@@ -0,0 +1,298 @@
+//! Optional machine translation of comment text.
+//!
+//! Off by default: comment bodies only leave the machine once the user has
+//! enabled translation and pointed it at a provider they trust (typically a
+//! self-hosted LibreTranslate). Translations of synced comments are cached
+//! per comment and target language.
+
+use crate::db::pool::DbPool;
+use crate::db::translations::{self, CachedTranslation};
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Per-request timeout for the translation provider.
+const TRANSLATE_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Translation service protocol.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TranslationProvider {
+    /// LibreTranslate-compatible `POST /translate` API.
+    #[default]
+    LibreTranslate,
+}
+
+/// Translation configuration, persisted with the app settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TranslationSettings {
+    /// Whether comments may be sent to the provider at all.
+    pub enabled: bool,
+    pub provider: TranslationProvider,
+    /// Base URL of the provider, e.g. `https://translate.example.com`.
+    pub endpoint: String,
+    /// API key, for providers that require one.
+    pub api_key: Option<String>,
+    /// Language code offered by default, e.g. `en`.
+    pub target_lang: String,
+}
+
+impl Default for TranslationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider: TranslationProvider::default(),
+            endpoint: String::new(),
+            api_key: None,
+            target_lang: "en".to_string(),
+        }
+    }
+}
+
+/// A translated text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Translation {
+    pub text: String,
+    /// Source language reported by the provider, if any.
+    pub detected_lang: Option<String>,
+    /// Whether the result came from the local cache.
+    pub cached: bool,
+}
+
+/// Check settings before they are saved. Disabled settings are accepted as
+/// they are, so a half-filled form can be stored.
+pub fn validate_settings(settings: &TranslationSettings) -> Result<(), AppError> {
+    if !settings.enabled {
+        return Ok(());
+    }
+    let url = reqwest::Url::parse(settings.endpoint.trim())
+        .map_err(|_| AppError::invalid_input_field("Endpoint must be a URL", "endpoint"))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(AppError::invalid_input_field(
+            "Endpoint must use http or https",
+            "endpoint",
+        ));
+    }
+    if settings.target_lang.trim().is_empty() {
+        return Err(AppError::invalid_input_field(
+            "Target language is required",
+            "targetLang",
+        ));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LibreTranslateResponse {
+    translated_text: String,
+    detected_language: Option<LibreTranslateDetected>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LibreTranslateDetected {
+    language: String,
+}
+
+async fn libre_translate(
+    settings: &TranslationSettings,
+    text: &str,
+    target_lang: &str,
+) -> Result<CachedTranslation, AppError> {
+    let client = reqwest::Client::builder()
+        .timeout(TRANSLATE_TIMEOUT)
+        .build()
+        .map_err(|e| AppError::internal(format!("Failed to create HTTP client: {e}")))?;
+    let url = format!(
+        "{}/translate",
+        settings.endpoint.trim().trim_end_matches('/')
+    );
+    let mut body = serde_json::json!({
+        "q": text,
+        "source": "auto",
+        "target": target_lang,
+        "format": "text",
+    });
+    if let Some(key) = settings.api_key.as_deref().filter(|k| !k.is_empty()) {
+        body["api_key"] = key.into();
+    }
+
+    let response = client.post(&url).json(&body).send().await?;
+    let status = response.status();
+    if !status.is_success() {
+        let detail = response.text().await.unwrap_or_default();
+        return Err(AppError::network(format!(
+            "Translation failed (HTTP {status}): {}",
+            detail.trim()
+        )));
+    }
+    let parsed: LibreTranslateResponse = response
+        .json()
+        .await
+        .map_err(|e| AppError::network(format!("Unexpected translation response: {e}")))?;
+    Ok(CachedTranslation {
+        translated_text: parsed.translated_text,
+        detected_lang: parsed.detected_language.map(|d| d.language),
+    })
+}
+
+/// Translate `text` into `target_lang`.
+///
+/// With a `comment_id`, a cached translation of the same text is returned
+/// without contacting the provider, and fresh results are cached.
+pub async fn translate(
+    pool: &DbPool,
+    settings: &TranslationSettings,
+    text: &str,
+    target_lang: &str,
+    comment_id: Option<i64>,
+    now: i64,
+) -> Result<Translation, AppError> {
+    if !settings.enabled {
+        return Err(AppError::invalid_input(
+            "Translation is disabled. Enable it in Settings first.",
+        ));
+    }
+    let target_lang = target_lang.trim();
+    if target_lang.is_empty() {
+        return Err(AppError::invalid_input_field(
+            "Target language is required",
+            "targetLang",
+        ));
+    }
+    if text.trim().is_empty() {
+        return Ok(Translation {
+            text: text.to_string(),
+            detected_lang: None,
+            cached: false,
+        });
+    }
+
+    if let Some(id) = comment_id {
+        if let Some(hit) = translations::get_translation(pool, id, target_lang, text).await? {
+            return Ok(Translation {
+                text: hit.translated_text,
+                detected_lang: hit.detected_lang,
+                cached: true,
+            });
+        }
+    }
+
+    crate::services::offline_mode::ensure_online()?;
+    let result = match settings.provider {
+        TranslationProvider::LibreTranslate => libre_translate(settings, text, target_lang).await?,
+    };
+
+    if let Some(id) = comment_id {
+        translations::save_translation(pool, id, target_lang, text, &result, now).await?;
+    }
+    Ok(Translation {
+        text: result.translated_text,
+        detected_lang: result.detected_lang,
+        cached: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+
+    fn enabled() -> TranslationSettings {
+        TranslationSettings {
+            enabled: true,
+            // Nothing listens here; a cache miss would fail to connect.
+            endpoint: "http://127.0.0.1:9".to_string(),
+            ..TranslationSettings::default()
+        }
+    }
+
+    #[test]
+    fn settings_validation() {
+        assert!(validate_settings(&TranslationSettings::default()).is_ok());
+        assert!(validate_settings(&enabled()).is_ok());
+        let bad = TranslationSettings {
+            endpoint: "ftp://x".to_string(),
+            ..enabled()
+        };
+        assert!(validate_settings(&bad).is_err());
+        let no_lang = TranslationSettings {
+            target_lang: " ".to_string(),
+            ..enabled()
+        };
+        assert!(validate_settings(&no_lang).is_err());
+    }
+
+    #[test]
+    fn parses_libretranslate_response() {
+        let r: LibreTranslateResponse = serde_json::from_str(
+            r#"{"translatedText":"Hello","detectedLanguage":{"confidence":92,"language":"de"}}"#,
+        )
+        .unwrap();
+        assert_eq!(r.translated_text, "Hello");
+        assert_eq!(r.detected_language.unwrap().language, "de");
+    }
+
+    #[tokio::test]
+    async fn disabled_by_default_and_cache_is_per_source_text() {
+        let (pool, inst) = seed_instance(true).await;
+        let err = translate(
+            &pool,
+            &TranslationSettings::default(),
+            "Hallo",
+            "en",
+            None,
+            0,
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("disabled"));
+
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, project_name, title, author_username,
+              source_branch, target_branch, state, web_url, created_at, updated_at,
+              labels, reviewers, cached_at)
+             VALUES (1, ?, 1, 10, 'g/p', 't', 'alice', 's', 'main', 'opened', 'http://x', 0, 0, '[]', '[]', 0)",
+        )
+        .bind(inst)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO comments (id, mr_id, author_username, body, created_at, updated_at)
+             VALUES (100, 1, 'alice', 'Hallo', 0, 0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let cached = CachedTranslation {
+            translated_text: "Hello".to_string(),
+            detected_lang: Some("de".to_string()),
+        };
+        translations::save_translation(&pool, 100, "en", "Hallo", &cached, 1)
+            .await
+            .unwrap();
+
+        let hit = translate(&pool, &enabled(), "Hallo", "en", Some(100), 2)
+            .await
+            .unwrap();
+        assert_eq!(
+            hit,
+            Translation {
+                text: "Hello".to_string(),
+                detected_lang: Some("de".to_string()),
+                cached: true,
+            }
+        );
+
+        // An edited body misses the cache and goes to the (unreachable) provider.
+        assert!(
+            translate(&pool, &enabled(), "Hallo Welt", "en", Some(100), 3)
+                .await
+                .is_err()
+        );
+    }
+}
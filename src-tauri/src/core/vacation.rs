@@ -0,0 +1,196 @@
+//! Vacation mode: answer review requests while the user is away.
+//!
+//! Between `starts_at` and `ends_at` the sync engine looks for review
+//! requests it hasn't seen before and queues one comment per MR: the away
+//! message, and in `Reassign` mode the quick actions that hand the review to
+//! the delegate. GitLab applies the quick actions only when the user may
+//! change reviewers; the message is posted either way.
+
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+
+/// Away message used when none is configured.
+pub const DEFAULT_MESSAGE: &str =
+    "I'm away and can't review this right now. Please reassign the review.";
+
+/// What to do with a new review request during the vacation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VacationAction {
+    /// Post the away message.
+    #[default]
+    Comment,
+    /// Post the away message and move the review to the delegate.
+    Reassign,
+}
+
+/// Vacation mode settings, part of the sync config.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VacationConfig {
+    pub enabled: bool,
+    /// Start of the vacation (Unix seconds).
+    pub starts_at: i64,
+    /// End of the vacation (Unix seconds, exclusive).
+    pub ends_at: i64,
+    pub action: VacationAction,
+    /// Away message; `DEFAULT_MESSAGE` when empty.
+    pub message: String,
+    /// Username that takes over reviews in `Reassign` mode.
+    pub delegate: Option<String>,
+}
+
+impl VacationConfig {
+    /// Whether review requests should be answered at `now`.
+    pub fn is_active(&self, now: i64) -> bool {
+        self.enabled && self.starts_at <= now && now < self.ends_at
+    }
+
+    /// Reject settings the sync engine couldn't act on.
+    pub fn validate(&self) -> Result<(), AppError> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.ends_at <= self.starts_at {
+            return Err(AppError::invalid_input_field(
+                "The vacation must end after it starts",
+                "vacation.ends_at",
+            ));
+        }
+        if self.action == VacationAction::Reassign {
+            match self.delegate.as_deref().map(|d| d.trim_start_matches('@')) {
+                Some(d) if is_username(d) => {}
+                _ => {
+                    return Err(AppError::invalid_input_field(
+                        "Enter the username of the delegate to reassign reviews to",
+                        "vacation.delegate",
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Comment body answering a review request to `me`.
+    pub fn response_body(&self, me: &str) -> String {
+        let message = match self.message.trim() {
+            "" => DEFAULT_MESSAGE,
+            m => m,
+        };
+        let delegate = self
+            .delegate
+            .as_deref()
+            .map(|d| d.trim_start_matches('@'))
+            .filter(|d| is_username(d) && *d != me);
+        match (self.action, delegate) {
+            (VacationAction::Reassign, Some(delegate)) => {
+                format!("{message}\n\n/unassign_reviewer @{me}\n/assign_reviewer @{delegate}")
+            }
+            _ => message.to_string(),
+        }
+    }
+}
+
+fn is_username(s: &str) -> bool {
+    !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+/// Claim the response for an MR's review request. Returns false when this
+/// vacation (everything since `starts_at`) already answered it.
+pub async fn claim_response(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+    mr_iid: i64,
+    starts_at: i64,
+    now: i64,
+) -> Result<bool, AppError> {
+    let result = sqlx::query(
+        "INSERT INTO vacation_responses (instance_id, project_id, mr_iid, responded_at)
+         VALUES (?, ?, ?, ?)
+         ON CONFLICT (instance_id, project_id, mr_iid) DO UPDATE SET responded_at = excluded.responded_at
+         WHERE vacation_responses.responded_at < ?",
+    )
+    .bind(instance_id)
+    .bind(project_id)
+    .bind(mr_iid)
+    .bind(now)
+    .bind(starts_at)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+
+    fn config(action: VacationAction, delegate: Option<&str>) -> VacationConfig {
+        VacationConfig {
+            enabled: true,
+            starts_at: 100,
+            ends_at: 200,
+            action,
+            message: String::new(),
+            delegate: delegate.map(String::from),
+        }
+    }
+
+    #[test]
+    fn active_only_within_the_range() {
+        let c = config(VacationAction::Comment, None);
+        assert!(!c.is_active(99));
+        assert!(c.is_active(100));
+        assert!(!c.is_active(200));
+        assert!(!VacationConfig {
+            enabled: false,
+            ..c
+        }
+        .is_active(150));
+    }
+
+    #[test]
+    fn builds_reassign_quick_actions() {
+        let c = config(VacationAction::Reassign, Some("@bob"));
+        assert_eq!(
+            c.response_body("alice"),
+            format!("{DEFAULT_MESSAGE}\n\n/unassign_reviewer @alice\n/assign_reviewer @bob")
+        );
+        // Never hand the review back to oneself.
+        assert_eq!(c.response_body("bob"), DEFAULT_MESSAGE);
+        assert_eq!(
+            config(VacationAction::Comment, Some("bob")).response_body("alice"),
+            DEFAULT_MESSAGE
+        );
+    }
+
+    #[test]
+    fn validates_range_and_delegate() {
+        assert!(config(VacationAction::Comment, None).validate().is_ok());
+        assert!(config(VacationAction::Reassign, None).validate().is_err());
+        assert!(config(VacationAction::Reassign, Some("a b"))
+            .validate()
+            .is_err());
+        assert!(config(VacationAction::Reassign, Some("@bob"))
+            .validate()
+            .is_ok());
+        let backwards = VacationConfig {
+            ends_at: 50,
+            ..config(VacationAction::Comment, None)
+        };
+        assert!(backwards.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn answers_once_per_vacation() {
+        let (pool, inst) = seed_instance(true).await;
+        assert!(claim_response(&pool, inst, 10, 1, 100, 150).await.unwrap());
+        assert!(!claim_response(&pool, inst, 10, 1, 100, 160).await.unwrap());
+        // A later vacation answers again.
+        assert!(claim_response(&pool, inst, 10, 1, 300, 350).await.unwrap());
+    }
+}
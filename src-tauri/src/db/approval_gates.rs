@@ -0,0 +1,96 @@
+//! Approval gate DB helpers.
+//!
+//! `approval_gates` holds the per-project gate configuration (no row means
+//! the gate is off); `mr_checklist_checks` records which checklist items the
+//! user has ticked on a given MR.
+
+use crate::db::pool::DbPool;
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, FromRow)]
+pub struct ApprovalGateRow {
+    pub instance_id: i64,
+    pub project_id: i64,
+    pub enabled: bool,
+    pub require_files_viewed: bool,
+    pub require_own_threads_resolved: bool,
+    /// JSON array of checklist item labels.
+    pub checklist: String,
+}
+
+/// Load the gate configured for a project, if any.
+pub async fn get_gate(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<Option<ApprovalGateRow>, sqlx::Error> {
+    sqlx::query_as::<_, ApprovalGateRow>(
+        "SELECT instance_id, project_id, enabled, require_files_viewed, \
+         require_own_threads_resolved, checklist \
+         FROM approval_gates WHERE instance_id = ? AND project_id = ?",
+    )
+    .bind(instance_id)
+    .bind(project_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Insert or replace a project's gate configuration.
+pub async fn upsert_gate(pool: &DbPool, gate: &ApprovalGateRow) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO approval_gates \
+         (instance_id, project_id, enabled, require_files_viewed, \
+          require_own_threads_resolved, checklist) \
+         VALUES (?, ?, ?, ?, ?, ?) \
+         ON CONFLICT(instance_id, project_id) DO UPDATE SET \
+         enabled = excluded.enabled, \
+         require_files_viewed = excluded.require_files_viewed, \
+         require_own_threads_resolved = excluded.require_own_threads_resolved, \
+         checklist = excluded.checklist",
+    )
+    .bind(gate.instance_id)
+    .bind(gate.project_id)
+    .bind(gate.enabled)
+    .bind(gate.require_files_viewed)
+    .bind(gate.require_own_threads_resolved)
+    .bind(&gate.checklist)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Checklist items ticked on an MR.
+pub async fn checked_items(pool: &DbPool, mr_id: i64) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar("SELECT item FROM mr_checklist_checks WHERE mr_id = ?")
+        .bind(mr_id)
+        .fetch_all(pool)
+        .await
+}
+
+/// Tick or untick a checklist item on an MR.
+pub async fn set_item_checked(
+    pool: &DbPool,
+    mr_id: i64,
+    item: &str,
+    checked: bool,
+    now: i64,
+) -> Result<(), sqlx::Error> {
+    if checked {
+        sqlx::query(
+            "INSERT INTO mr_checklist_checks (mr_id, item, checked_at) VALUES (?, ?, ?) \
+             ON CONFLICT(mr_id, item) DO NOTHING",
+        )
+        .bind(mr_id)
+        .bind(item)
+        .bind(now)
+        .execute(pool)
+        .await?;
+    } else {
+        sqlx::query("DELETE FROM mr_checklist_checks WHERE mr_id = ? AND item = ?")
+            .bind(mr_id)
+            .bind(item)
+            .execute(pool)
+            .await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,59 @@
+//! MR approval rule DB helpers.
+//!
+//! `mr_approval_rules` holds the rules of an MR as of its last merge-blocker
+//! refresh, in GitLab's order. They're always replaced as a whole.
+
+use crate::db::pool::DbPool;
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, PartialEq, Eq, FromRow)]
+pub struct ApprovalRuleRow {
+    pub rule_id: i64,
+    pub name: String,
+    pub rule_type: String,
+    pub approvals_required: i64,
+    pub approved: bool,
+    /// JSON array of usernames.
+    pub approved_by: String,
+}
+
+pub async fn list_rules(pool: &DbPool, mr_id: i64) -> Result<Vec<ApprovalRuleRow>, sqlx::Error> {
+    sqlx::query_as::<_, ApprovalRuleRow>(
+        "SELECT rule_id, name, rule_type, approvals_required, approved, approved_by
+         FROM mr_approval_rules WHERE mr_id = ? ORDER BY position",
+    )
+    .bind(mr_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Replace the rules of an MR with `rules`, in order.
+pub async fn replace_rules(
+    pool: &DbPool,
+    mr_id: i64,
+    rules: &[ApprovalRuleRow],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM mr_approval_rules WHERE mr_id = ?")
+        .bind(mr_id)
+        .execute(&mut *tx)
+        .await?;
+    for (position, rule) in rules.iter().enumerate() {
+        sqlx::query(
+            "INSERT INTO mr_approval_rules
+             (mr_id, rule_id, name, rule_type, approvals_required, approved, approved_by, position)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(mr_id)
+        .bind(rule.rule_id)
+        .bind(&rule.name)
+        .bind(&rule.rule_type)
+        .bind(rule.approvals_required)
+        .bind(rule.approved)
+        .bind(&rule.approved_by)
+        .bind(position as i64)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await
+}
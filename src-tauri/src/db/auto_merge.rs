@@ -15,6 +15,10 @@ pub struct AutoMergeClaimRow {
     pub last_error: Option<String>,
     pub last_attempt_at: Option<i64>,
     pub attempts: i64,
+    /// JSON merge-when-ready conditions; NULL for a plain auto-merge claim.
+    pub conditions: Option<String>,
+    /// JSON list of conditions that weren't met at the last check.
+    pub unmet_conditions: Option<String>,
 }
 
 /// Joined row with the bits of MR metadata the sync processor needs to make
@@ -27,11 +31,13 @@ pub struct AutoMergeClaimWithMr {
     pub last_error: Option<String>,
     pub last_attempt_at: Option<i64>,
     pub attempts: i64,
+    pub conditions: Option<String>,
     pub instance_id: i64,
     pub project_id: i64,
     pub iid: i64,
     pub state: String,
     pub title: String,
+    pub web_url: String,
 }
 
 /// Insert a claim for this MR. No-op if a claim already exists.
@@ -47,6 +53,27 @@ pub async fn upsert_claim(pool: &DbPool, mr_id: i64, now: i64) -> Result<(), sql
     Ok(())
 }
 
+/// Insert or update a merge-when-ready claim with its conditions.
+///
+/// An existing plain claim is upgraded in place; attempt history is kept.
+pub async fn upsert_conditional_claim(
+    pool: &DbPool,
+    mr_id: i64,
+    now: i64,
+    conditions: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO auto_merge_claims (mr_id, claimed_at, conditions) VALUES (?, ?, ?) \
+         ON CONFLICT(mr_id) DO UPDATE SET conditions = excluded.conditions, unmet_conditions = NULL",
+    )
+    .bind(mr_id)
+    .bind(now)
+    .bind(conditions)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
 /// Delete a claim by MR id.
 pub async fn delete_claim(pool: &DbPool, mr_id: i64) -> Result<(), sqlx::Error> {
     sqlx::query("DELETE FROM auto_merge_claims WHERE mr_id = ?")
@@ -62,7 +89,8 @@ pub async fn get_claim(
     mr_id: i64,
 ) -> Result<Option<AutoMergeClaimRow>, sqlx::Error> {
     sqlx::query_as::<_, AutoMergeClaimRow>(
-        "SELECT mr_id, claimed_at, last_status, last_error, last_attempt_at, attempts \
+        "SELECT mr_id, claimed_at, last_status, last_error, last_attempt_at, attempts, \
+                conditions, unmet_conditions \
          FROM auto_merge_claims WHERE mr_id = ?",
     )
     .bind(mr_id)
@@ -76,7 +104,7 @@ pub async fn list_active_claims_with_mr(
 ) -> Result<Vec<AutoMergeClaimWithMr>, sqlx::Error> {
     sqlx::query_as::<_, AutoMergeClaimWithMr>(
         "SELECT c.mr_id, c.claimed_at, c.last_status, c.last_error, c.last_attempt_at, c.attempts, \
-                mr.instance_id, mr.project_id, mr.iid, mr.state, mr.title \
+                c.conditions, mr.instance_id, mr.project_id, mr.iid, mr.state, mr.title, mr.web_url \
          FROM auto_merge_claims c \
          JOIN merge_requests mr ON mr.id = c.mr_id",
     )
@@ -106,3 +134,18 @@ pub async fn record_attempt(
     .await?;
     Ok(())
 }
+
+/// Store the conditions still blocking a merge-when-ready claim (JSON list),
+/// or clear them when they weren't checked this tick.
+pub async fn record_unmet_conditions(
+    pool: &DbPool,
+    mr_id: i64,
+    unmet: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE auto_merge_claims SET unmet_conditions = ? WHERE mr_id = ?")
+        .bind(unmet)
+        .bind(mr_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,181 @@
+//! Automation rule and run-log DB helpers.
+//!
+//! `automation_rules` holds the user's rules; `automation_runs` records each
+//! execution (real or test) and is trimmed to the most recent runs per rule.
+
+use crate::db::pool::DbPool;
+use sqlx::FromRow;
+
+/// Runs kept per rule; older ones are pruned when a run is recorded.
+const MAX_RUNS_PER_RULE: i64 = 50;
+
+#[derive(Debug, Clone, FromRow)]
+pub struct AutomationRuleRow {
+    pub id: i64,
+    pub name: String,
+    pub trigger: String,
+    pub action_kind: String,
+    pub target: String,
+    pub enabled: bool,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct AutomationRunRow {
+    pub id: i64,
+    pub rule_id: i64,
+    pub trigger: String,
+    pub mr_id: Option<i64>,
+    pub is_test: bool,
+    pub success: bool,
+    pub detail: Option<String>,
+    pub duration_ms: i64,
+    pub ran_at: i64,
+}
+
+const RULE_COLUMNS: &str = "id, name, trigger, action_kind, target, enabled, created_at";
+const RUN_COLUMNS: &str =
+    "id, rule_id, trigger, mr_id, is_test, success, detail, duration_ms, ran_at";
+
+pub async fn list_rules(pool: &DbPool) -> Result<Vec<AutomationRuleRow>, sqlx::Error> {
+    let sql = format!("SELECT {RULE_COLUMNS} FROM automation_rules ORDER BY id");
+    sqlx::query_as::<_, AutomationRuleRow>(&sql)
+        .fetch_all(pool)
+        .await
+}
+
+pub async fn list_enabled_rules(pool: &DbPool) -> Result<Vec<AutomationRuleRow>, sqlx::Error> {
+    let sql = format!("SELECT {RULE_COLUMNS} FROM automation_rules WHERE enabled = 1 ORDER BY id");
+    sqlx::query_as::<_, AutomationRuleRow>(&sql)
+        .fetch_all(pool)
+        .await
+}
+
+pub async fn get_rule(
+    pool: &DbPool,
+    rule_id: i64,
+) -> Result<Option<AutomationRuleRow>, sqlx::Error> {
+    let sql = format!("SELECT {RULE_COLUMNS} FROM automation_rules WHERE id = ?");
+    sqlx::query_as::<_, AutomationRuleRow>(&sql)
+        .bind(rule_id)
+        .fetch_optional(pool)
+        .await
+}
+
+pub async fn insert_rule(
+    pool: &DbPool,
+    name: &str,
+    trigger: &str,
+    action_kind: &str,
+    target: &str,
+    enabled: bool,
+    now: i64,
+) -> Result<i64, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT INTO automation_rules (name, trigger, action_kind, target, enabled, created_at) \
+         VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(name)
+    .bind(trigger)
+    .bind(action_kind)
+    .bind(target)
+    .bind(enabled)
+    .bind(now)
+    .execute(pool)
+    .await?;
+    Ok(result.last_insert_rowid())
+}
+
+/// Update a rule in place. Returns false when it doesn't exist.
+pub async fn update_rule(
+    pool: &DbPool,
+    rule_id: i64,
+    name: &str,
+    trigger: &str,
+    action_kind: &str,
+    target: &str,
+    enabled: bool,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "UPDATE automation_rules \
+         SET name = ?, trigger = ?, action_kind = ?, target = ?, enabled = ? \
+         WHERE id = ?",
+    )
+    .bind(name)
+    .bind(trigger)
+    .bind(action_kind)
+    .bind(target)
+    .bind(enabled)
+    .bind(rule_id)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Delete a rule and its run log.
+pub async fn delete_rule(pool: &DbPool, rule_id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM automation_rules WHERE id = ?")
+        .bind(rule_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Record one run and prune the rule's log to the newest runs.
+#[allow(clippy::too_many_arguments)]
+pub async fn insert_run(
+    pool: &DbPool,
+    rule_id: i64,
+    trigger: &str,
+    mr_id: Option<i64>,
+    is_test: bool,
+    success: bool,
+    detail: Option<&str>,
+    duration_ms: i64,
+    now: i64,
+) -> Result<i64, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT INTO automation_runs \
+         (rule_id, trigger, mr_id, is_test, success, detail, duration_ms, ran_at) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(rule_id)
+    .bind(trigger)
+    .bind(mr_id)
+    .bind(is_test)
+    .bind(success)
+    .bind(detail)
+    .bind(duration_ms)
+    .bind(now)
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        "DELETE FROM automation_runs WHERE rule_id = ? AND id NOT IN ( \
+             SELECT id FROM automation_runs WHERE rule_id = ? ORDER BY id DESC LIMIT ?)",
+    )
+    .bind(rule_id)
+    .bind(rule_id)
+    .bind(MAX_RUNS_PER_RULE)
+    .execute(pool)
+    .await?;
+
+    Ok(result.last_insert_rowid())
+}
+
+/// Most recent runs, newest first, optionally for one rule.
+pub async fn list_runs(
+    pool: &DbPool,
+    rule_id: Option<i64>,
+    limit: i64,
+) -> Result<Vec<AutomationRunRow>, sqlx::Error> {
+    let sql = format!(
+        "SELECT {RUN_COLUMNS} FROM automation_runs \
+         WHERE (?1 IS NULL OR rule_id = ?1) ORDER BY id DESC LIMIT ?2"
+    );
+    sqlx::query_as::<_, AutomationRunRow>(&sql)
+        .bind(rule_id)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+}
@@ -0,0 +1,68 @@
+//! Badge rule DB helpers.
+//!
+//! `mr_badge_rules` holds the user's badge scripts in display order. The
+//! rules are always saved as a whole list, so there is no per-rule update.
+
+use crate::db::pool::DbPool;
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, PartialEq, Eq, FromRow)]
+pub struct BadgeRuleRow {
+    pub id: i64,
+    pub name: String,
+    pub label: String,
+    pub color: Option<String>,
+    pub script: String,
+    pub enabled: bool,
+    pub time_limit_ms: i64,
+}
+
+/// A rule to save; ids are assigned on insert.
+#[derive(Debug, Clone)]
+pub struct NewBadgeRule<'a> {
+    pub name: &'a str,
+    pub label: &'a str,
+    pub color: Option<&'a str>,
+    pub script: &'a str,
+    pub enabled: bool,
+    pub time_limit_ms: i64,
+}
+
+pub async fn list_rules(pool: &DbPool) -> Result<Vec<BadgeRuleRow>, sqlx::Error> {
+    sqlx::query_as::<_, BadgeRuleRow>(
+        "SELECT id, name, label, color, script, enabled, time_limit_ms
+         FROM mr_badge_rules ORDER BY position, id",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Replace every rule with `rules`, in order.
+pub async fn replace_rules(
+    pool: &DbPool,
+    rules: &[NewBadgeRule<'_>],
+    now: i64,
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM mr_badge_rules")
+        .execute(&mut *tx)
+        .await?;
+    for (position, rule) in rules.iter().enumerate() {
+        sqlx::query(
+            "INSERT INTO mr_badge_rules
+             (position, name, label, color, script, enabled, time_limit_ms, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(position as i64)
+        .bind(rule.name)
+        .bind(rule.label)
+        .bind(rule.color)
+        .bind(rule.script)
+        .bind(rule.enabled)
+        .bind(rule.time_limit_ms)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await
+}
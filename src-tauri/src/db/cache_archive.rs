@@ -0,0 +1,306 @@
+//! DB helpers for cache archives.
+//!
+//! Reads the cached MRs, diffs and file content of one instance for export,
+//! and writes an archived copy back under a (possibly different) local
+//! instance id. Nothing here touches `gitlab_instances` beyond its id, so
+//! tokens and cookies never leave the machine.
+
+use crate::db::pool::DbPool;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::collections::HashMap;
+
+/// Portable subset of a `merge_requests` row. Per-user columns (approval
+/// state, assignment, watch flag) are left out; the importing side's sync
+/// fills them in for its own user.
+#[derive(Debug, Clone, PartialEq, FromRow, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedMr {
+    pub id: i64,
+    pub iid: i64,
+    pub project_id: i64,
+    pub title: String,
+    pub description: Option<String>,
+    pub description_preview: Option<String>,
+    pub author_username: String,
+    pub source_branch: String,
+    pub target_branch: String,
+    pub web_url: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub labels: Option<String>,
+    pub reviewers: Option<String>,
+    pub project_name: String,
+    pub head_pipeline_status: Option<String>,
+    pub is_bot_author: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, FromRow, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedDiff {
+    pub mr_id: i64,
+    pub content: String,
+    pub base_sha: String,
+    pub head_sha: String,
+    pub start_sha: String,
+    pub file_count: i64,
+    pub additions: i64,
+    pub deletions: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, FromRow, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedDiffFile {
+    pub mr_id: i64,
+    pub old_path: Option<String>,
+    pub new_path: String,
+    pub change_type: String,
+    pub additions: i64,
+    pub deletions: i64,
+    pub file_position: i64,
+    pub diff_content: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, FromRow, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedFileVersion {
+    pub mr_id: i64,
+    pub file_path: String,
+    pub version_type: String,
+    pub sha: String,
+    pub project_id: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, FromRow, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedBlob {
+    pub sha: String,
+    pub content: String,
+    pub size_bytes: i64,
+}
+
+/// Everything archived for one instance.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedInstanceData {
+    pub merge_requests: Vec<ArchivedMr>,
+    pub diffs: Vec<ArchivedDiff>,
+    pub diff_files: Vec<ArchivedDiffFile>,
+    pub file_versions: Vec<ArchivedFileVersion>,
+    pub blobs: Vec<ArchivedBlob>,
+}
+
+/// Rows written by [`import_instance_data`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportCounts {
+    pub merge_requests: i64,
+    pub diffs: i64,
+    pub files: i64,
+}
+
+/// Read the cached open MRs of an instance together with their diffs and
+/// file content.
+pub async fn read_instance_data(
+    pool: &DbPool,
+    instance_id: i64,
+) -> Result<ArchivedInstanceData, sqlx::Error> {
+    let merge_requests = sqlx::query_as::<_, ArchivedMr>(
+        "SELECT id, iid, project_id, title, description, description_preview, author_username, \
+         source_branch, target_branch, web_url, created_at, updated_at, labels, reviewers, \
+         project_name, head_pipeline_status, is_bot_author \
+         FROM merge_requests WHERE instance_id = ? AND state = 'opened' ORDER BY id",
+    )
+    .bind(instance_id)
+    .fetch_all(pool)
+    .await?;
+
+    let diffs = sqlx::query_as::<_, ArchivedDiff>(
+        "SELECT d.mr_id, d.content, d.base_sha, d.head_sha, d.start_sha, d.file_count, \
+         d.additions, d.deletions \
+         FROM diffs d JOIN merge_requests m ON m.id = d.mr_id \
+         WHERE m.instance_id = ? AND m.state = 'opened' ORDER BY d.mr_id",
+    )
+    .bind(instance_id)
+    .fetch_all(pool)
+    .await?;
+
+    let diff_files = sqlx::query_as::<_, ArchivedDiffFile>(
+        "SELECT f.mr_id, f.old_path, f.new_path, f.change_type, f.additions, f.deletions, \
+         f.file_position, f.diff_content \
+         FROM diff_files f JOIN merge_requests m ON m.id = f.mr_id \
+         WHERE m.instance_id = ? AND m.state = 'opened' ORDER BY f.mr_id, f.file_position",
+    )
+    .bind(instance_id)
+    .fetch_all(pool)
+    .await?;
+
+    let file_versions = sqlx::query_as::<_, ArchivedFileVersion>(
+        "SELECT v.mr_id, v.file_path, v.version_type, v.sha, v.project_id \
+         FROM file_versions v JOIN merge_requests m ON m.id = v.mr_id \
+         WHERE m.instance_id = ? AND m.state = 'opened' ORDER BY v.mr_id, v.file_path",
+    )
+    .bind(instance_id)
+    .fetch_all(pool)
+    .await?;
+
+    let blobs = sqlx::query_as::<_, ArchivedBlob>(
+        "SELECT sha, content, size_bytes FROM file_blobs WHERE sha IN ( \
+             SELECT v.sha FROM file_versions v JOIN merge_requests m ON m.id = v.mr_id \
+             WHERE m.instance_id = ? AND m.state = 'opened') \
+         ORDER BY sha",
+    )
+    .bind(instance_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(ArchivedInstanceData {
+        merge_requests,
+        diffs,
+        diff_files,
+        file_versions,
+        blobs,
+    })
+}
+
+/// Write archived data for `instance_id` in one transaction.
+///
+/// Only fills gaps: MRs already cached are left alone, and a diff (with its
+/// files and file content) is only written for an MR that has none yet.
+/// Imported MRs are stored one second older than exported, so the next sync
+/// sees them as changed and fetches comments and approvals, while the diff
+/// SHAs still match and the file content isn't downloaded again.
+/// `include_file_content` is false when the instance's retention policy
+/// forbids caching file content.
+pub async fn import_instance_data(
+    pool: &DbPool,
+    instance_id: i64,
+    data: &ArchivedInstanceData,
+    include_file_content: bool,
+) -> Result<ImportCounts, sqlx::Error> {
+    let blobs: HashMap<&str, &ArchivedBlob> =
+        data.blobs.iter().map(|b| (b.sha.as_str(), b)).collect();
+    let mut counts = ImportCounts::default();
+    let mut tx = pool.begin().await?;
+
+    for mr in &data.merge_requests {
+        let result = sqlx::query(
+            "INSERT OR IGNORE INTO merge_requests ( \
+                 id, instance_id, iid, project_id, title, description, description_preview, \
+                 author_username, source_branch, target_branch, state, web_url, created_at, \
+                 updated_at, labels, reviewers, project_name, head_pipeline_status, is_bot_author \
+             ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 'opened', ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(mr.id)
+        .bind(instance_id)
+        .bind(mr.iid)
+        .bind(mr.project_id)
+        .bind(&mr.title)
+        .bind(&mr.description)
+        .bind(&mr.description_preview)
+        .bind(&mr.author_username)
+        .bind(&mr.source_branch)
+        .bind(&mr.target_branch)
+        .bind(&mr.web_url)
+        .bind(mr.created_at)
+        .bind(mr.updated_at - 1)
+        .bind(&mr.labels)
+        .bind(&mr.reviewers)
+        .bind(&mr.project_name)
+        .bind(&mr.head_pipeline_status)
+        .bind(mr.is_bot_author)
+        .execute(&mut *tx)
+        .await?;
+        counts.merge_requests += result.rows_affected() as i64;
+    }
+
+    for diff in &data.diffs {
+        // The MR must be cached locally under this instance, whether it was
+        // just imported or synced before.
+        let owned: Option<i64> =
+            sqlx::query_scalar("SELECT id FROM merge_requests WHERE id = ? AND instance_id = ?")
+                .bind(diff.mr_id)
+                .bind(instance_id)
+                .fetch_optional(&mut *tx)
+                .await?;
+        if owned.is_none() {
+            continue;
+        }
+
+        let result = sqlx::query(
+            "INSERT OR IGNORE INTO diffs \
+             (mr_id, content, base_sha, head_sha, start_sha, file_count, additions, deletions) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(diff.mr_id)
+        .bind(&diff.content)
+        .bind(&diff.base_sha)
+        .bind(&diff.head_sha)
+        .bind(&diff.start_sha)
+        .bind(diff.file_count)
+        .bind(diff.additions)
+        .bind(diff.deletions)
+        .execute(&mut *tx)
+        .await?;
+        if result.rows_affected() == 0 {
+            continue;
+        }
+        counts.diffs += 1;
+
+        sqlx::query("DELETE FROM diff_files WHERE mr_id = ?")
+            .bind(diff.mr_id)
+            .execute(&mut *tx)
+            .await?;
+        for f in data.diff_files.iter().filter(|f| f.mr_id == diff.mr_id) {
+            sqlx::query(
+                "INSERT INTO diff_files \
+                 (mr_id, old_path, new_path, change_type, additions, deletions, file_position, diff_content) \
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(f.mr_id)
+            .bind(&f.old_path)
+            .bind(&f.new_path)
+            .bind(&f.change_type)
+            .bind(f.additions)
+            .bind(f.deletions)
+            .bind(f.file_position)
+            .bind(&f.diff_content)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        if !include_file_content {
+            continue;
+        }
+        for v in data.file_versions.iter().filter(|v| v.mr_id == diff.mr_id) {
+            let Some(blob) = blobs.get(v.sha.as_str()) else {
+                continue;
+            };
+            sqlx::query(
+                "INSERT OR IGNORE INTO file_blobs (sha, content, size_bytes) VALUES (?, ?, ?)",
+            )
+            .bind(&blob.sha)
+            .bind(&blob.content)
+            .bind(blob.size_bytes)
+            .execute(&mut *tx)
+            .await?;
+            let result = sqlx::query(
+                "INSERT OR IGNORE INTO file_versions \
+                 (mr_id, file_path, version_type, sha, instance_id, project_id) \
+                 VALUES (?, ?, ?, ?, ?, ?)",
+            )
+            .bind(v.mr_id)
+            .bind(&v.file_path)
+            .bind(&v.version_type)
+            .bind(&v.sha)
+            // file_versions.instance_id is stored as TEXT.
+            .bind(instance_id.to_string())
+            .bind(v.project_id)
+            .execute(&mut *tx)
+            .await?;
+            counts.files += result.rows_affected() as i64;
+        }
+    }
+
+    tx.commit().await?;
+    Ok(counts)
+}
@@ -0,0 +1,85 @@
+//! Change log DB helpers.
+//!
+//! `change_log` is append-only; `seq` is the cursor clients resume from.
+
+use crate::db::pool::DbPool;
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, FromRow)]
+pub struct ChangeLogRow {
+    pub seq: i64,
+    pub entity: String,
+    pub entity_id: i64,
+    pub instance_id: i64,
+    pub op: String,
+    /// JSON object of changed fields.
+    pub fields: String,
+    pub changed_at: i64,
+}
+
+/// Append a change and return its sequence number.
+pub async fn insert(
+    pool: &DbPool,
+    entity: &str,
+    entity_id: i64,
+    instance_id: i64,
+    op: &str,
+    fields: &str,
+    changed_at: i64,
+) -> Result<i64, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT INTO change_log (entity, entity_id, instance_id, op, fields, changed_at)
+         VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(entity)
+    .bind(entity_id)
+    .bind(instance_id)
+    .bind(op)
+    .bind(fields)
+    .bind(changed_at)
+    .execute(pool)
+    .await?;
+    Ok(result.last_insert_rowid())
+}
+
+/// Up to `limit` changes after `cursor`, oldest first.
+pub async fn list_after(
+    pool: &DbPool,
+    cursor: i64,
+    limit: i64,
+) -> Result<Vec<ChangeLogRow>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT seq, entity, entity_id, instance_id, op, fields, changed_at
+         FROM change_log WHERE seq > ? ORDER BY seq LIMIT ?",
+    )
+    .bind(cursor)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+/// Oldest retained sequence number, `None` while the log is empty.
+pub async fn first_seq(pool: &DbPool) -> Result<Option<i64>, sqlx::Error> {
+    sqlx::query_scalar("SELECT MIN(seq) FROM change_log")
+        .fetch_one(pool)
+        .await
+}
+
+/// Highest sequence number ever handed out, including pruned rows.
+pub async fn last_seq(pool: &DbPool) -> Result<i64, sqlx::Error> {
+    let seq: Option<i64> =
+        sqlx::query_scalar("SELECT seq FROM sqlite_sequence WHERE name = 'change_log'")
+            .fetch_optional(pool)
+            .await?;
+    Ok(seq.unwrap_or(0))
+}
+
+/// Keep only the newest `keep` rows. Returns the number removed.
+pub async fn prune(pool: &DbPool, keep: i64) -> Result<u64, sqlx::Error> {
+    let result =
+        sqlx::query("DELETE FROM change_log WHERE seq <= (SELECT MAX(seq) FROM change_log) - ?")
+            .bind(keep)
+            .execute(pool)
+            .await?;
+    Ok(result.rows_affected())
+}
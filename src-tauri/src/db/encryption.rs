@@ -0,0 +1,401 @@
+//! Optional SQLCipher encryption of the database file.
+//!
+//! Whether a file is encrypted is read from the file itself: a plaintext
+//! SQLite database starts with the `SQLite format 3` header, an SQLCipher
+//! one with random bytes. Pools check this on open and apply the key from
+//! the OS keychain, so the app and the `ultra` CLI open either kind of file
+//! without configuration.
+//!
+//! Switching between the two rewrites the file with `sqlcipher_export`, which
+//! needs exclusive access; [`reconcile`] does it at startup before any pool
+//! is opened. A database whose key is missing from the keychain is moved
+//! aside to `<name>.locked` so the app can start with a fresh cache, and is
+//! swapped back in once the key is restored. An existing `.locked` file is
+//! never overwritten; later lockouts get a timestamped name instead.
+
+use super::DbError;
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{ConnectOptions, Connection};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Keychain service name for the database key.
+const KEYCHAIN_SERVICE: &str = "ultra-gitlab";
+
+/// Keychain account name for the database key.
+const KEYCHAIN_ACCOUNT: &str = "database-key";
+
+/// First 16 bytes of every plaintext SQLite database.
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+/// Hex characters in a raw SQLCipher key (256 bits).
+const KEY_HEX_LEN: usize = 64;
+
+/// What [`reconcile`] did to the database file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReconcileOutcome {
+    /// The file already matched the setting.
+    Unchanged,
+    /// The plaintext file was encrypted.
+    Encrypted,
+    /// The encrypted file was decrypted.
+    Decrypted,
+    /// A previously locked-out file was swapped back in.
+    Restored,
+    /// The file couldn't be opened with the stored key and was moved aside.
+    LockedOut,
+}
+
+/// Whether this build links SQLCipher.
+pub fn supported() -> bool {
+    cfg!(feature = "encryption")
+}
+
+/// Whether `db_path` holds an encrypted database. Missing, empty or
+/// unreadable files count as plaintext.
+pub fn is_encrypted(db_path: &Path) -> bool {
+    let mut header = [0u8; 16];
+    match std::fs::File::open(db_path).and_then(|mut f| f.read_exact(&mut header)) {
+        Ok(()) => &header != SQLITE_HEADER,
+        Err(_) => false,
+    }
+}
+
+/// Where a database that couldn't be unlocked is kept.
+pub fn locked_path(db_path: &Path) -> PathBuf {
+    let mut name = db_path.as_os_str().to_owned();
+    name.push(".locked");
+    PathBuf::from(name)
+}
+
+/// Move an unusable database to [`locked_path`], or next to it with the
+/// time appended when an earlier locked-out file is still there.
+fn move_aside(db_path: &Path) -> Result<PathBuf, DbError> {
+    let mut target = locked_path(db_path);
+    if target.exists() {
+        let mut secs = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        target = loop {
+            let mut name = locked_path(db_path).into_os_string();
+            name.push(format!(".{secs}"));
+            let candidate = PathBuf::from(name);
+            if !candidate.exists() {
+                break candidate;
+            }
+            secs += 1;
+        };
+    }
+    std::fs::rename(db_path, &target)
+        .map_err(|e| DbError::Encryption(format!("Failed to move locked database aside: {e}")))?;
+    remove_sidecars(db_path);
+    Ok(target)
+}
+
+/// Normalize a user-supplied key: 64 hex characters, lowercased.
+pub fn normalize_key(key: &str) -> Result<String, DbError> {
+    let key = key.trim().to_ascii_lowercase();
+    if key.len() != KEY_HEX_LEN || !key.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(DbError::Encryption(format!(
+            "Key must be {KEY_HEX_LEN} hexadecimal characters"
+        )));
+    }
+    Ok(key)
+}
+
+fn keychain_entry() -> Result<keyring::Entry, DbError> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT)
+        .map_err(|e| DbError::Encryption(format!("Keychain unavailable: {e}")))
+}
+
+/// The database key stored in the OS keychain, if any.
+pub fn load_key() -> Result<Option<String>, DbError> {
+    match keychain_entry()?.get_password() {
+        Ok(key) => Ok(Some(key)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(DbError::Encryption(format!(
+            "Failed to read database key: {e}"
+        ))),
+    }
+}
+
+/// Store `key` as the database key, replacing any existing one.
+pub fn store_key(key: &str) -> Result<(), DbError> {
+    let key = normalize_key(key)?;
+    keychain_entry()?
+        .set_password(&key)
+        .map_err(|e| DbError::Encryption(format!("Failed to store database key: {e}")))
+}
+
+/// The stored key, generating and storing a new one if there is none.
+fn load_or_create_key() -> Result<String, DbError> {
+    if let Some(key) = load_key()? {
+        return Ok(key);
+    }
+    let bytes: [u8; KEY_HEX_LEN / 2] = rand::random();
+    let key: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    store_key(&key)?;
+    Ok(key)
+}
+
+/// SQLCipher raw-key literal, e.g. `"x'2dd2…'"`.
+fn key_literal(key: &str) -> String {
+    format!("\"x'{key}'\"")
+}
+
+/// Add the keychain key to `options` when `db_path` is encrypted.
+pub fn apply_key(
+    options: SqliteConnectOptions,
+    db_path: &Path,
+) -> Result<SqliteConnectOptions, DbError> {
+    if !is_encrypted(db_path) {
+        return Ok(options);
+    }
+    if !supported() {
+        return Err(DbError::Encryption(
+            "Database is encrypted but this build has no SQLCipher support".to_string(),
+        ));
+    }
+    let key = load_key()?.ok_or_else(|| {
+        DbError::Encryption("Database is encrypted but no key is in the keychain".to_string())
+    })?;
+    Ok(options.pragma("key", key_literal(&key)))
+}
+
+/// Whether `key` opens the encrypted database at `path`.
+pub async fn key_opens(path: &Path, key: &str) -> bool {
+    let options = SqliteConnectOptions::new()
+        .filename(path)
+        .read_only(true)
+        .pragma("key", key_literal(key));
+    let Ok(mut conn) = options.connect().await else {
+        return false;
+    };
+    let ok = sqlx::query("SELECT count(*) FROM sqlite_master")
+        .fetch_one(&mut conn)
+        .await
+        .is_ok();
+    let _ = conn.close().await;
+    ok
+}
+
+fn remove_sidecars(db_path: &Path) {
+    for suffix in ["-wal", "-shm"] {
+        let mut name = db_path.as_os_str().to_owned();
+        name.push(suffix);
+        let _ = std::fs::remove_file(PathBuf::from(name));
+    }
+}
+
+/// Copy `source` (opened with `source_key`, if any) into a new file next to
+/// it encrypted with `target_key` ("" for plaintext), then replace `source`.
+async fn rewrite(source: &Path, source_key: Option<&str>, target_key: &str) -> Result<(), DbError> {
+    let mut tmp = source.as_os_str().to_owned();
+    tmp.push(".rewrite");
+    let tmp = PathBuf::from(tmp);
+    // ATTACH can't create files on a connection opened without
+    // create_if_missing, and an empty file is a valid new database.
+    std::fs::File::create(&tmp)
+        .map_err(|e| DbError::Encryption(format!("Failed to create database file: {e}")))?;
+
+    let mut options = SqliteConnectOptions::new().filename(source);
+    if let Some(key) = source_key {
+        options = options.pragma("key", key_literal(key));
+    }
+    let mut conn = options.connect().await?;
+    let result = async {
+        // Fold the WAL into the main file so the export sees every write.
+        sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)")
+            .execute(&mut conn)
+            .await?;
+        let target = if target_key.is_empty() {
+            String::new()
+        } else {
+            format!("x'{target_key}'")
+        };
+        sqlx::query("ATTACH DATABASE ? AS target KEY ?")
+            .bind(tmp.to_string_lossy().into_owned())
+            .bind(target)
+            .execute(&mut conn)
+            .await?;
+        sqlx::query("SELECT sqlcipher_export('target')")
+            .execute(&mut conn)
+            .await?;
+        sqlx::query("DETACH DATABASE target")
+            .execute(&mut conn)
+            .await?;
+        Ok::<(), sqlx::Error>(())
+    }
+    .await;
+    let _ = conn.close().await;
+    if let Err(e) = result {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(DbError::Encryption(format!(
+            "Failed to rewrite database: {e}"
+        )));
+    }
+
+    remove_sidecars(source);
+    std::fs::rename(&tmp, source)
+        .map_err(|e| DbError::Encryption(format!("Failed to replace database file: {e}")))
+}
+
+/// Encrypt the plaintext database at `db_path` with `key`.
+pub async fn encrypt_file(db_path: &Path, key: &str) -> Result<(), DbError> {
+    rewrite(db_path, None, key).await
+}
+
+/// Decrypt the database at `db_path`, encrypted with `key`, in place.
+pub async fn decrypt_file(db_path: &Path, key: &str) -> Result<(), DbError> {
+    rewrite(db_path, Some(key), "").await
+}
+
+/// Bring the database file in line with the encryption setting. Must run
+/// before any pool is opened on `db_path`.
+pub async fn reconcile(db_path: &Path, want_encrypted: bool) -> Result<ReconcileOutcome, DbError> {
+    let locked = locked_path(db_path);
+    let key = if is_encrypted(db_path) || locked.exists() || want_encrypted {
+        load_key()?
+    } else {
+        None
+    };
+
+    // A locked-out database whose key has since been restored replaces the
+    // fresh cache that was started in its place.
+    let mut restored = false;
+    if locked.exists() {
+        if let Some(key) = key.as_deref() {
+            if key_opens(&locked, key).await {
+                remove_sidecars(db_path);
+                std::fs::rename(&locked, db_path).map_err(|e| {
+                    DbError::Encryption(format!("Failed to restore locked database: {e}"))
+                })?;
+                restored = true;
+            }
+        }
+    }
+
+    if is_encrypted(db_path) {
+        let usable = match key.as_deref() {
+            Some(key) => supported() && key_opens(db_path, key).await,
+            None => false,
+        };
+        if !usable {
+            let moved = move_aside(db_path)?;
+            log::warn!("Database can't be unlocked; moved to {}", moved.display());
+            return Ok(ReconcileOutcome::LockedOut);
+        }
+        if !want_encrypted {
+            decrypt_file(db_path, key.as_deref().unwrap_or_default()).await?;
+            return Ok(ReconcileOutcome::Decrypted);
+        }
+    } else if want_encrypted && supported() && db_path.exists() {
+        let key = match key {
+            Some(key) => key,
+            None => load_or_create_key()?,
+        };
+        encrypt_file(db_path, &key).await?;
+        return Ok(ReconcileOutcome::Encrypted);
+    }
+
+    Ok(if restored {
+        ReconcileOutcome::Restored
+    } else {
+        ReconcileOutcome::Unchanged
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn plaintext_databases_are_detected() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("t.db");
+        assert!(!is_encrypted(&path), "missing file counts as plaintext");
+
+        let pool = crate::db::initialize(&path).await.unwrap();
+        pool.close().await;
+        assert!(!is_encrypted(&path));
+
+        std::fs::write(&path, [0x5au8; 64]).unwrap();
+        assert!(is_encrypted(&path));
+        assert_eq!(locked_path(&path), dir.path().join("t.db.locked"),);
+    }
+
+    #[test]
+    fn keys_are_normalized() {
+        let key = "AB".repeat(32);
+        assert_eq!(
+            normalize_key(&format!(" {key}\n")).unwrap(),
+            "ab".repeat(32)
+        );
+        assert!(normalize_key("abc").is_err());
+        assert!(normalize_key(&"zz".repeat(32)).is_err());
+    }
+
+    #[tokio::test]
+    async fn plaintext_database_is_left_alone_when_encryption_is_off() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("t.db");
+        crate::db::initialize(&path).await.unwrap().close().await;
+        assert_eq!(
+            reconcile(&path, false).await.unwrap(),
+            ReconcileOutcome::Unchanged
+        );
+        assert!(!is_encrypted(&path));
+    }
+
+    #[test]
+    fn moving_aside_keeps_an_earlier_locked_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("t.db");
+        std::fs::write(&path, b"first").unwrap();
+        assert_eq!(move_aside(&path).unwrap(), locked_path(&path));
+        assert!(!path.exists());
+
+        std::fs::write(&path, b"second").unwrap();
+        let second = move_aside(&path).unwrap();
+        assert_ne!(second, locked_path(&path));
+        assert_eq!(std::fs::read(locked_path(&path)).unwrap(), b"first");
+        assert_eq!(std::fs::read(&second).unwrap(), b"second");
+
+        std::fs::write(&path, b"third").unwrap();
+        let third = move_aside(&path).unwrap();
+        assert!(third != second && third != locked_path(&path));
+        assert_eq!(std::fs::read(&second).unwrap(), b"second");
+    }
+
+    #[cfg(feature = "encryption")]
+    #[tokio::test]
+    async fn encrypt_and_decrypt_round_trip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("t.db");
+        let pool = crate::db::initialize(&path).await.unwrap();
+        sqlx::query(
+            "INSERT INTO gitlab_instances (url, name, created_at) VALUES ('https://x', 'x', 0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        pool.close().await;
+
+        let key = "0f".repeat(32);
+        encrypt_file(&path, &key).await.unwrap();
+        assert!(is_encrypted(&path));
+        assert!(key_opens(&path, &key).await);
+        assert!(!key_opens(&path, &"f0".repeat(32)).await);
+
+        decrypt_file(&path, &key).await.unwrap();
+        assert!(!is_encrypted(&path));
+        let pool = crate::db::initialize(&path).await.unwrap();
+        let n: i64 = sqlx::query_scalar("SELECT count(*) FROM gitlab_instances")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(n, 1);
+    }
+}
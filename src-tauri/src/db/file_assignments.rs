@@ -0,0 +1,115 @@
+//! File assignment DB helpers.
+//!
+//! `mr_file_assignments` maps each file of an MR to at most one reviewer.
+//! Recording an older assignment never overwrites a newer one, so comments
+//! can be replayed in any order.
+
+use crate::db::pool::DbPool;
+use serde::Serialize;
+use sqlx::FromRow;
+
+/// The reviewer a file of an MR is assigned to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct FileAssignment {
+    pub file_path: String,
+    pub username: String,
+    pub assigned_by: String,
+    pub assigned_at: i64,
+}
+
+pub async fn list_assignments(
+    pool: &DbPool,
+    mr_id: i64,
+) -> Result<Vec<FileAssignment>, sqlx::Error> {
+    sqlx::query_as::<_, FileAssignment>(
+        "SELECT file_path, username, assigned_by, assigned_at
+         FROM mr_file_assignments WHERE mr_id = ? ORDER BY file_path",
+    )
+    .bind(mr_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Assign `paths` to `username`, unless a file already has a newer
+/// assignment.
+pub async fn record_assignment(
+    pool: &DbPool,
+    mr_id: i64,
+    paths: &[String],
+    username: &str,
+    assigned_by: &str,
+    assigned_at: i64,
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    for path in paths {
+        sqlx::query(
+            "INSERT INTO mr_file_assignments (mr_id, file_path, username, assigned_by, assigned_at)
+             VALUES (?, ?, ?, ?, ?)
+             ON CONFLICT (mr_id, file_path) DO UPDATE SET
+                 username = excluded.username,
+                 assigned_by = excluded.assigned_by,
+                 assigned_at = excluded.assigned_at
+             WHERE excluded.assigned_at >= mr_file_assignments.assigned_at",
+        )
+        .bind(mr_id)
+        .bind(path)
+        .bind(username)
+        .bind(assigned_by)
+        .bind(assigned_at)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db;
+
+    #[tokio::test]
+    async fn test_newest_assignment_wins() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = db::initialize(&dir.path().join("test.db")).await.unwrap();
+        sqlx::query("INSERT INTO gitlab_instances (id, url, name) VALUES (1, 'https://gitlab.com', 'GitLab')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, title, author_username, source_branch,
+              target_branch, state, web_url, created_at, updated_at)
+             VALUES (1, 1, 1, 100, 't', 'alice', 'f', 'main', 'opened', 'w', 0, 0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let paths = |ps: &[&str]| ps.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+        record_assignment(&pool, 1, &paths(&["a.rs", "b.rs"]), "bob", "alice", 20)
+            .await
+            .unwrap();
+        // An older comment replayed later doesn't undo the newer assignment.
+        record_assignment(&pool, 1, &paths(&["b.rs"]), "carol", "alice", 10)
+            .await
+            .unwrap();
+        record_assignment(&pool, 1, &paths(&["a.rs"]), "dave", "alice", 30)
+            .await
+            .unwrap();
+
+        let owners: Vec<_> = list_assignments(&pool, 1)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|a| (a.file_path, a.username))
+            .collect();
+        assert_eq!(
+            owners,
+            vec![
+                ("a.rs".to_string(), "dave".to_string()),
+                ("b.rs".to_string(), "bob".to_string())
+            ]
+        );
+    }
+}
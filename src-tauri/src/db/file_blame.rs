@@ -0,0 +1,66 @@
+//! File blame cache DB helpers.
+//!
+//! Ranges are stored as JSON exactly as `core::file_blame` serializes them.
+
+use crate::db::pool::DbPool;
+
+/// Cached blame ranges (JSON) of a file at a commit.
+pub async fn get_blame(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+    sha: &str,
+    path: &str,
+) -> Result<Option<String>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT ranges FROM file_blame
+         WHERE instance_id = ? AND project_id = ? AND sha = ? AND path = ?",
+    )
+    .bind(instance_id)
+    .bind(project_id)
+    .bind(sha)
+    .bind(path)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Insert or replace the blame of a file at a commit.
+pub async fn save_blame(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+    sha: &str,
+    path: &str,
+    ranges: &str,
+    now: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO file_blame (instance_id, project_id, sha, path, ranges, fetched_at)
+         VALUES (?, ?, ?, ?, ?, ?)
+         ON CONFLICT (instance_id, project_id, sha, path)
+         DO UPDATE SET ranges = excluded.ranges, fetched_at = excluded.fetched_at",
+    )
+    .bind(instance_id)
+    .bind(project_id)
+    .bind(sha)
+    .bind(path)
+    .bind(ranges)
+    .bind(now)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Drop blame fetched before `cutoff` for commits no cached MR diff is at.
+/// Returns the number of rows removed.
+pub async fn prune_blame(pool: &DbPool, cutoff: i64) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        "DELETE FROM file_blame
+         WHERE fetched_at < ?
+           AND NOT EXISTS (SELECT 1 FROM diffs d WHERE d.head_sha = file_blame.sha)",
+    )
+    .bind(cutoff)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
@@ -0,0 +1,91 @@
+//! Cached log tails of failed jobs.
+//!
+//! Filled by the sync engine for the head pipelines of the user's authored
+//! MRs; read first by `get_job_trace` before going to the network.
+
+use crate::db::pool::DbPool;
+
+/// A cached job log tail.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct CachedJobTrace {
+    pub trace: String,
+    /// Bytes dropped from the start of the log to fit the cache limit.
+    pub omitted_bytes: i64,
+}
+
+/// Where a cached trace came from.
+#[derive(Debug, Clone, Copy)]
+pub struct JobTraceSource {
+    pub instance_id: i64,
+    pub project_id: i64,
+    pub pipeline_id: i64,
+    pub mr_id: i64,
+    pub job_id: i64,
+}
+
+/// Cached trace for a job, if any.
+pub async fn get_job_trace(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+    job_id: i64,
+) -> Result<Option<CachedJobTrace>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT trace, omitted_bytes FROM job_trace_cache
+         WHERE instance_id = ? AND project_id = ? AND job_id = ?",
+    )
+    .bind(instance_id)
+    .bind(project_id)
+    .bind(job_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Whether any trace of `pipeline_id` is cached (i.e. it was already prefetched).
+pub async fn has_pipeline_traces(
+    pool: &DbPool,
+    instance_id: i64,
+    pipeline_id: i64,
+) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM job_trace_cache WHERE instance_id = ? AND pipeline_id = ?)",
+    )
+    .bind(instance_id)
+    .bind(pipeline_id)
+    .fetch_one(pool)
+    .await
+}
+
+/// Store (or replace) the trace of a job.
+pub async fn save_job_trace(
+    pool: &DbPool,
+    source: &JobTraceSource,
+    trace: &CachedJobTrace,
+    now: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT OR REPLACE INTO job_trace_cache
+         (instance_id, job_id, project_id, pipeline_id, mr_id, trace, omitted_bytes, fetched_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(source.instance_id)
+    .bind(source.job_id)
+    .bind(source.project_id)
+    .bind(source.pipeline_id)
+    .bind(source.mr_id)
+    .bind(&trace.trace)
+    .bind(trace.omitted_bytes)
+    .bind(now)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Drop traces fetched before `cutoff`. Returns the number of rows removed.
+pub async fn prune_job_traces(pool: &DbPool, cutoff: i64) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM job_trace_cache WHERE fetched_at < ?")
+        .bind(cutoff)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected())
+}
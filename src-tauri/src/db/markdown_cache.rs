@@ -0,0 +1,45 @@
+//! Rendered markdown cache DB helpers.
+//!
+//! Keys are built by `core::markdown`; a row is only ever written once per
+//! key, so a hit just refreshes `used_at`.
+
+use crate::db::pool::DbPool;
+
+/// Cached HTML for `hash`, marking it used at `now`.
+pub async fn get_html(pool: &DbPool, hash: &str, now: i64) -> Result<Option<String>, sqlx::Error> {
+    sqlx::query_scalar("UPDATE markdown_cache SET used_at = ? WHERE hash = ? RETURNING html")
+        .bind(now)
+        .bind(hash)
+        .fetch_optional(pool)
+        .await
+}
+
+/// Store the HTML rendered for `hash` from a text of `instance_id`.
+pub async fn save_html(
+    pool: &DbPool,
+    hash: &str,
+    html: &str,
+    instance_id: Option<i64>,
+    now: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO markdown_cache (hash, html, instance_id, used_at) VALUES (?, ?, ?, ?)
+         ON CONFLICT (hash) DO UPDATE SET used_at = excluded.used_at",
+    )
+    .bind(hash)
+    .bind(html)
+    .bind(instance_id)
+    .bind(now)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Drop HTML not used since `cutoff`. Returns the number of rows removed.
+pub async fn prune_html(pool: &DbPool, cutoff: i64) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM markdown_cache WHERE used_at < ?")
+        .bind(cutoff)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected())
+}
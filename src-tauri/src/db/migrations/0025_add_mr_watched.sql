@@ -0,0 +1,9 @@
+-- Migration: 0025_add_mr_watched.sql
+-- Adds a `watched` flag to merge_requests. Watched MRs are ones the user asked
+-- to follow without being author, assignee or reviewer (e.g. an MR that will
+-- conflict with their own). The sync engine fetches them individually, they
+-- bypass the review list's scope exclusions, and they are never soft-purged.
+
+ALTER TABLE merge_requests ADD COLUMN watched INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_mr_watched ON merge_requests(instance_id, watched);
@@ -0,0 +1,15 @@
+-- Migration: 0026_add_mr_description_preview.sql
+-- Some MR descriptions are huge (generated release notes), and every list
+-- query used to ship the full text over IPC. List queries now read this
+-- truncated preview; the full description is only loaded for the detail view.
+-- Existing rows are backfilled with a plain 500-character cut; the sync engine
+-- rewrites the preview with word-boundary truncation on the next upsert.
+
+ALTER TABLE merge_requests ADD COLUMN description_preview TEXT;
+
+UPDATE merge_requests
+SET description_preview = CASE
+    WHEN length(description) <= 500 THEN description
+    ELSE substr(description, 1, 500) || '…'
+END
+WHERE description IS NOT NULL;
@@ -0,0 +1,24 @@
+-- Migration: 0027_approval_gates.sql
+-- Optional per-project pre-approve gate. When enabled, `approve_mr` refuses
+-- to approve until every configured condition holds and reports the unmet
+-- ones instead. `checklist` is a JSON array of item labels; the per-MR ticks
+-- live in `mr_checklist_checks`.
+
+CREATE TABLE IF NOT EXISTS approval_gates (
+    instance_id INTEGER NOT NULL,
+    project_id INTEGER NOT NULL,
+    enabled INTEGER NOT NULL DEFAULT 0,
+    require_files_viewed INTEGER NOT NULL DEFAULT 1,
+    require_own_threads_resolved INTEGER NOT NULL DEFAULT 1,
+    checklist TEXT NOT NULL DEFAULT '[]',
+    PRIMARY KEY (instance_id, project_id),
+    FOREIGN KEY (instance_id) REFERENCES gitlab_instances(id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS mr_checklist_checks (
+    mr_id INTEGER NOT NULL,
+    item TEXT NOT NULL,
+    checked_at INTEGER NOT NULL,
+    PRIMARY KEY (mr_id, item),
+    FOREIGN KEY (mr_id) REFERENCES merge_requests(id) ON DELETE CASCADE
+);
@@ -0,0 +1,12 @@
+-- Migration: 0028_retention_policies.sql
+-- Per-instance data retention. Without a row an instance gets the defaults:
+-- file content is cached, comments are kept, and merged/closed MRs stay for
+-- 24 hours. `comment_retention_days` NULL means comments never expire.
+
+CREATE TABLE IF NOT EXISTS retention_policies (
+    instance_id INTEGER PRIMARY KEY,
+    cache_file_content INTEGER NOT NULL DEFAULT 1,
+    comment_retention_days INTEGER,
+    closed_mr_retention_hours INTEGER NOT NULL DEFAULT 24,
+    FOREIGN KEY (instance_id) REFERENCES gitlab_instances(id) ON DELETE CASCADE
+);
@@ -0,0 +1,6 @@
+-- Migration: 0029_instance_custom_headers.sql
+-- Extra HTTP headers sent with every API request to an instance, for
+-- self-hosted GitLab behind an SSO proxy. Stored next to the PAT as a JSON
+-- array of {"name", "value"} objects; NULL means no extra headers.
+
+ALTER TABLE gitlab_instances ADD COLUMN custom_headers TEXT;
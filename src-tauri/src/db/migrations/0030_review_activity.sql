@@ -0,0 +1,28 @@
+-- Migration: 0030_review_activity.sql
+-- Opt-in local review stats. `review_stats_opt_in` has a single row while
+-- tracking is on; `review_activity` is an append-only log of the user's own
+-- review events. Events reference MRs by project and iid rather than the
+-- local row id so they outlive purged MRs.
+
+CREATE TABLE IF NOT EXISTS review_stats_opt_in (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    enabled_at INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS review_activity (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    instance_id INTEGER NOT NULL,
+    project_id INTEGER NOT NULL,
+    mr_iid INTEGER NOT NULL,
+    -- 'review_requested', 'commented' or 'approved'
+    kind TEXT NOT NULL,
+    occurred_at INTEGER NOT NULL,
+    FOREIGN KEY (instance_id) REFERENCES gitlab_instances(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_review_activity_time ON review_activity(occurred_at);
+
+-- A review request is recorded once, when first seen.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_review_activity_request
+    ON review_activity(instance_id, project_id, mr_iid)
+    WHERE kind = 'review_requested';
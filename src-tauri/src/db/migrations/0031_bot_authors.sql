@@ -0,0 +1,6 @@
+-- Migration: 0031_bot_authors.sql
+-- Flag MRs opened by bot accounts (Dependabot, Renovate, access tokens) so
+-- they can be filtered out of the review queue. Existing rows are corrected
+-- on their next sync.
+
+ALTER TABLE merge_requests ADD COLUMN is_bot_author INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,5 @@
+-- Migration: 0032_avatar_email.sql
+-- Public email per cached avatar, for the opt-in Gravatar fallback when the
+-- instance-hosted avatar can't be downloaded.
+
+ALTER TABLE user_avatars ADD COLUMN email TEXT;
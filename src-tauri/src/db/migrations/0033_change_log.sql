@@ -0,0 +1,16 @@
+-- Migration: 0033_change_log.sql
+-- Append-only log of cache changes. Every MR upsert, diff/comment refresh
+-- and purge appends a row so clients can patch their state instead of
+-- refetching, and reconnecting clients can catch up from the last `seq`
+-- they saw. Old rows are pruned; a client whose cursor fell off the end
+-- reloads everything.
+
+CREATE TABLE IF NOT EXISTS change_log (
+    seq INTEGER PRIMARY KEY AUTOINCREMENT,
+    entity TEXT NOT NULL,
+    entity_id INTEGER NOT NULL,
+    instance_id INTEGER NOT NULL,
+    op TEXT NOT NULL,
+    fields TEXT NOT NULL DEFAULT '{}',
+    changed_at INTEGER NOT NULL
+);
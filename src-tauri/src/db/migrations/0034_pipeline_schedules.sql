@@ -0,0 +1,23 @@
+-- Migration: 0034_pipeline_schedules.sql
+-- Cached pipeline schedules for dashboard projects, so the schedules tab
+-- renders instantly and stays readable offline. Each fetch replaces a
+-- project's rows wholesale.
+
+CREATE TABLE IF NOT EXISTS pipeline_schedule_cache (
+    instance_id INTEGER NOT NULL,
+    project_id INTEGER NOT NULL,
+    schedule_id INTEGER NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    ref_name TEXT NOT NULL,
+    cron TEXT NOT NULL,
+    cron_timezone TEXT NOT NULL DEFAULT 'UTC',
+    next_run_at TEXT,
+    active INTEGER NOT NULL DEFAULT 1,
+    owner_username TEXT,
+    cached_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (instance_id, schedule_id),
+    FOREIGN KEY (instance_id) REFERENCES gitlab_instances(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_pipeline_schedule_cache_project
+    ON pipeline_schedule_cache(instance_id, project_id);
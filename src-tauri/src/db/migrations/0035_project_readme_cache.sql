@@ -0,0 +1,14 @@
+-- Migration: 0035_project_readme_cache.sql
+-- Caches each project's README, rendered to HTML, per target branch so the
+-- MR detail page can show project context instantly and offline.
+
+CREATE TABLE IF NOT EXISTS project_readme_cache (
+    instance_id INTEGER NOT NULL,
+    project_id INTEGER NOT NULL,
+    ref_name TEXT NOT NULL,
+    file_path TEXT,
+    html TEXT NOT NULL DEFAULT '',
+    fetched_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now')),
+    PRIMARY KEY (instance_id, project_id, ref_name),
+    FOREIGN KEY (instance_id) REFERENCES gitlab_instances(id) ON DELETE CASCADE
+);
@@ -0,0 +1,8 @@
+-- Migration: 0036_merge_when_ready.sql
+-- "Merge when ready" intents are auto-merge claims with local conditions
+-- (pipeline succeeded, approvals met, threads resolved) stored as JSON. A
+-- NULL `conditions` is a plain auto-merge claim. `unmet_conditions` holds the
+-- JSON list of conditions still blocking the merge at the last check.
+
+ALTER TABLE auto_merge_claims ADD COLUMN conditions TEXT;
+ALTER TABLE auto_merge_claims ADD COLUMN unmet_conditions TEXT;
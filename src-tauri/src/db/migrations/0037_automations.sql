@@ -0,0 +1,29 @@
+-- Migration: 0037_automations.sql
+-- User-defined automations: rules that react to MR changes seen during sync
+-- by posting to a webhook or running a shell command, plus a log of each run.
+-- Rules are local to this machine and never synced anywhere.
+
+CREATE TABLE IF NOT EXISTS automation_rules (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    trigger TEXT NOT NULL,
+    action_kind TEXT NOT NULL CHECK (action_kind IN ('webhook', 'command')),
+    target TEXT NOT NULL,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    created_at INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS automation_runs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    rule_id INTEGER NOT NULL,
+    trigger TEXT NOT NULL,
+    mr_id INTEGER,
+    is_test INTEGER NOT NULL DEFAULT 0,
+    success INTEGER NOT NULL,
+    detail TEXT,
+    duration_ms INTEGER NOT NULL,
+    ran_at INTEGER NOT NULL,
+    FOREIGN KEY (rule_id) REFERENCES automation_rules(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_automation_runs_rule ON automation_runs(rule_id, ran_at);
@@ -0,0 +1,38 @@
+-- Migration: 0038_release_tag_cache.sql
+-- Cached releases and tags for pipeline dashboard projects, so "did 2.4.1
+-- ship?" can be answered offline. Like schedules, each fetch replaces a
+-- project's rows wholesale.
+
+CREATE TABLE IF NOT EXISTS release_cache (
+    instance_id INTEGER NOT NULL,
+    project_id INTEGER NOT NULL,
+    tag_name TEXT NOT NULL,
+    name TEXT,
+    description TEXT,
+    created_at TEXT NOT NULL,
+    released_at TEXT,
+    upcoming_release INTEGER NOT NULL DEFAULT 0,
+    author_username TEXT,
+    commit_sha TEXT,
+    web_url TEXT,
+    -- JSON array of {name, url, link_type} asset links.
+    links TEXT NOT NULL DEFAULT '[]',
+    cached_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (instance_id, project_id, tag_name),
+    FOREIGN KEY (instance_id) REFERENCES gitlab_instances(id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS tag_cache (
+    instance_id INTEGER NOT NULL,
+    project_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    message TEXT,
+    commit_sha TEXT,
+    commit_title TEXT,
+    committed_at TEXT,
+    protected INTEGER NOT NULL DEFAULT 0,
+    has_release INTEGER NOT NULL DEFAULT 0,
+    cached_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (instance_id, project_id, name),
+    FOREIGN KEY (instance_id) REFERENCES gitlab_instances(id) ON DELETE CASCADE
+);
@@ -0,0 +1,15 @@
+-- Migration: 0039_comment_translations.sql
+-- Cached machine translations of comments, one per comment and target
+-- language. `source_text` is the body that was translated, so an edited
+-- comment is translated again instead of showing a stale result.
+
+CREATE TABLE IF NOT EXISTS comment_translations (
+    comment_id INTEGER NOT NULL,
+    target_lang TEXT NOT NULL,
+    source_text TEXT NOT NULL,
+    translated_text TEXT NOT NULL,
+    detected_lang TEXT,
+    translated_at INTEGER NOT NULL,
+    PRIMARY KEY (comment_id, target_lang),
+    FOREIGN KEY (comment_id) REFERENCES comments(id) ON DELETE CASCADE
+);
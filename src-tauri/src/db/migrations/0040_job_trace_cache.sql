@@ -0,0 +1,23 @@
+-- Migration: 0040_job_trace_cache.sql
+-- Log tails of failed jobs on the head pipelines of the user's own MRs,
+-- prefetched during sync so a failure can be diagnosed offline. A finished
+-- job's log never changes (a retry creates a new job id), so rows are only
+-- dropped with their MR or when they age out.
+
+CREATE TABLE IF NOT EXISTS job_trace_cache (
+    instance_id INTEGER NOT NULL,
+    job_id INTEGER NOT NULL,
+    project_id INTEGER NOT NULL,
+    pipeline_id INTEGER NOT NULL,
+    mr_id INTEGER NOT NULL,
+    -- Last bytes of the log; `omitted_bytes` counts what was cut from the front.
+    trace TEXT NOT NULL,
+    omitted_bytes INTEGER NOT NULL DEFAULT 0,
+    fetched_at INTEGER NOT NULL,
+    PRIMARY KEY (instance_id, job_id),
+    FOREIGN KEY (instance_id) REFERENCES gitlab_instances(id) ON DELETE CASCADE,
+    FOREIGN KEY (mr_id) REFERENCES merge_requests(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_job_trace_cache_pipeline
+    ON job_trace_cache(instance_id, pipeline_id);
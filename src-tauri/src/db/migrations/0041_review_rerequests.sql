@@ -0,0 +1,7 @@
+-- Migration: 0041_review_rerequests.sql
+-- GitLab review state per reviewer, and when a finished review was
+-- re-requested (the state went back to `unreviewed`). Both are NULL on
+-- instances that don't report review states.
+
+ALTER TABLE mr_reviewers ADD COLUMN review_state TEXT;
+ALTER TABLE mr_reviewers ADD COLUMN rerequested_at INTEGER;
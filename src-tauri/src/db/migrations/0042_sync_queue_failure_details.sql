@@ -0,0 +1,12 @@
+-- Migration: 0042_sync_queue_failure_details.sql
+-- Structured details of the last failed push of a queued action, so the
+-- retry UI can say why it failed and whether retrying can help.
+-- `error_kind` is the error category (`gitlab_api`, `network`,
+-- `authentication`, `other`); the rest stay NULL when not applicable.
+
+ALTER TABLE sync_queue ADD COLUMN error_kind TEXT;
+ALTER TABLE sync_queue ADD COLUMN error_status INTEGER;
+ALTER TABLE sync_queue ADD COLUMN error_message TEXT;
+ALTER TABLE sync_queue ADD COLUMN error_endpoint TEXT;
+ALTER TABLE sync_queue ADD COLUMN first_attempt_at INTEGER;
+ALTER TABLE sync_queue ADD COLUMN last_attempt_at INTEGER;
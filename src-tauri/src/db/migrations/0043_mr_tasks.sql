@@ -0,0 +1,15 @@
+-- Migration: 0043_mr_tasks.sql
+-- Task list items (`- [ ] ...`) parsed from MR descriptions during sync,
+-- plus per-MR counts so lists can show checklist progress cheaply.
+
+CREATE TABLE IF NOT EXISTS mr_tasks (
+    mr_id INTEGER NOT NULL,
+    position INTEGER NOT NULL,
+    text TEXT NOT NULL,
+    checked INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (mr_id, position),
+    FOREIGN KEY (mr_id) REFERENCES merge_requests(id) ON DELETE CASCADE
+);
+
+ALTER TABLE merge_requests ADD COLUMN task_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE merge_requests ADD COLUMN task_completed_count INTEGER NOT NULL DEFAULT 0;
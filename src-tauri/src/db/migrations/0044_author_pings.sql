@@ -0,0 +1,17 @@
+-- Migration: 0044_author_pings.sql
+-- Pings sent to MR authors from the reviewer side, so the same nudge isn't
+-- posted again before the author has reacted.
+
+CREATE TABLE IF NOT EXISTS author_pings (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    mr_id INTEGER NOT NULL,
+    template TEXT NOT NULL,
+    -- JSON array of the discussion IDs linked in the comment
+    discussion_ids TEXT NOT NULL DEFAULT '[]',
+    -- MR head when pinged; a new push counts as a reaction
+    head_sha TEXT,
+    pinged_at INTEGER NOT NULL,
+    FOREIGN KEY (mr_id) REFERENCES merge_requests(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_author_pings_mr ON author_pings(mr_id, template, pinged_at);
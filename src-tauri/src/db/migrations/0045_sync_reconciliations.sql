@@ -0,0 +1,15 @@
+-- Migration: 0045_sync_reconciliations.sql
+-- Deep reconciliation runs. The latest row schedules the next run, since
+-- sync_log is pruned too aggressively to remember a weekly job.
+
+CREATE TABLE IF NOT EXISTS sync_reconciliations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    started_at INTEGER NOT NULL,
+    duration_ms INTEGER NOT NULL,
+    checked INTEGER NOT NULL,
+    drifted INTEGER NOT NULL,
+    state_fixed INTEGER NOT NULL,
+    removed INTEGER NOT NULL,
+    orphans_removed INTEGER NOT NULL,
+    errors INTEGER NOT NULL
+);
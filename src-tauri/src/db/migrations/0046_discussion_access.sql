@@ -0,0 +1,8 @@
+-- Migration: 0046_discussion_access.sql
+-- Whether the current user may still comment on an MR: the MR's
+-- discussion_locked flag, the user's access level on the project (0 when not
+-- a member, NULL when unknown) and the reason GitLab last refused a comment.
+
+ALTER TABLE merge_requests ADD COLUMN discussion_locked INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE merge_requests ADD COLUMN discussion_refused TEXT;
+ALTER TABLE projects ADD COLUMN access_level INTEGER;
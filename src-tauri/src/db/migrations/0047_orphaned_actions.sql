@@ -0,0 +1,20 @@
+-- Migration: 0047_orphaned_actions.sql
+-- Queued actions dropped because their MR left the cache, kept so the user
+-- can see what was never pushed (and copy a lost comment).
+
+CREATE TABLE IF NOT EXISTS orphaned_actions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    action_id INTEGER NOT NULL,
+    mr_id INTEGER NOT NULL,
+    project_id INTEGER,
+    mr_iid INTEGER,
+    mr_title TEXT,
+    action_type TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    status TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    created_at INTEGER NOT NULL,
+    dropped_at INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_orphaned_actions_dropped ON orphaned_actions(dropped_at);
@@ -0,0 +1,11 @@
+-- Migration: 0048_vacation_responses.sql
+-- Review requests answered by vacation mode, so each MR gets one response
+-- per vacation.
+
+CREATE TABLE IF NOT EXISTS vacation_responses (
+    instance_id INTEGER NOT NULL,
+    project_id INTEGER NOT NULL,
+    mr_iid INTEGER NOT NULL,
+    responded_at INTEGER NOT NULL,
+    PRIMARY KEY (instance_id, project_id, mr_iid)
+);
@@ -0,0 +1,6 @@
+-- Migration: 0049_token_scopes.sql
+-- Scopes of the instance's token as verified at setup, as a JSON array.
+-- NULL when they couldn't be read (e.g. tokens saved before this migration);
+-- features are then left enabled and GitLab has the final say.
+
+ALTER TABLE gitlab_instances ADD COLUMN token_scopes TEXT;
@@ -0,0 +1,8 @@
+-- Migration: 0050_pipeline_notify_rules.sql
+-- Per-project notification rule for pinned pipeline projects:
+-- 'failures', 'completion' or 'default_branch'. default_branch caches the
+-- project's default branch for the 'default_branch' rule (NULL until first
+-- looked up).
+
+ALTER TABLE pipeline_projects ADD COLUMN notify_rule TEXT NOT NULL DEFAULT 'completion';
+ALTER TABLE pipeline_projects ADD COLUMN default_branch TEXT;
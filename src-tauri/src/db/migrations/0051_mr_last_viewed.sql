@@ -0,0 +1,5 @@
+-- Migration: 0051_mr_last_viewed.sql
+-- When the user last opened each MR (Unix seconds, NULL = never). Comments
+-- created after it count as unread.
+
+ALTER TABLE merge_requests ADD COLUMN last_viewed_at INTEGER;
@@ -0,0 +1,11 @@
+-- Migration: 0052_muted_projects.sql
+-- Projects whose notifications and badge contributions are muted. They are
+-- still synced. A NULL muted_until mutes until the project is unmuted.
+
+CREATE TABLE IF NOT EXISTS muted_projects (
+    instance_id INTEGER NOT NULL,
+    project_id INTEGER NOT NULL,
+    muted_until INTEGER,
+    muted_at INTEGER NOT NULL,
+    PRIMARY KEY (instance_id, project_id)
+);
@@ -0,0 +1,14 @@
+-- Migration: 0053_file_blame.sql
+-- Blame of a file at a commit, as JSON line ranges. Blame at a SHA never
+-- changes, so rows are shared by every MR whose head is that commit.
+
+CREATE TABLE IF NOT EXISTS file_blame (
+    instance_id INTEGER NOT NULL,
+    project_id INTEGER NOT NULL,
+    sha TEXT NOT NULL,
+    path TEXT NOT NULL,
+    ranges TEXT NOT NULL DEFAULT '[]',
+    fetched_at INTEGER NOT NULL,
+    PRIMARY KEY (instance_id, project_id, sha, path),
+    FOREIGN KEY (instance_id) REFERENCES gitlab_instances(id) ON DELETE CASCADE
+);
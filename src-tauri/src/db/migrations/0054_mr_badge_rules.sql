@@ -0,0 +1,15 @@
+-- Migration: 0054_mr_badge_rules.sql
+-- User-scripted badges for the MR lists. Each rule is a Rhai expression
+-- evaluated against every listed MR; rules apply in `position` order.
+
+CREATE TABLE IF NOT EXISTS mr_badge_rules (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    position INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    label TEXT NOT NULL,
+    color TEXT,
+    script TEXT NOT NULL,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    time_limit_ms INTEGER NOT NULL DEFAULT 10,
+    updated_at INTEGER NOT NULL
+);
@@ -0,0 +1,19 @@
+-- Migration: 0055_sync_queue_batches.sql
+-- Queued actions submitted together (e.g. a review's comments plus its
+-- approval) share a batch id. A batched action is only pushed once every
+-- earlier action of its batch has synced or been discarded.
+
+ALTER TABLE sync_queue ADD COLUMN batch_id TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_sync_queue_batch ON sync_queue(batch_id) WHERE batch_id IS NOT NULL;
+
+-- One row per batch. Synced actions are cleaned out of sync_queue, so the
+-- batch keeps its own size and synced count for reporting its progress.
+CREATE TABLE IF NOT EXISTS sync_batches (
+    id TEXT PRIMARY KEY,
+    mr_id INTEGER NOT NULL,
+    action_count INTEGER NOT NULL,
+    synced_count INTEGER NOT NULL DEFAULT 0,
+    created_at INTEGER NOT NULL,
+    FOREIGN KEY (mr_id) REFERENCES merge_requests(id) ON DELETE CASCADE
+);
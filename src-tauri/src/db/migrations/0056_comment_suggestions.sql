@@ -0,0 +1,17 @@
+-- Migration: 0056_comment_suggestions.sql
+-- Suggestions GitLab parsed out of ```suggestion blocks in diff notes, keyed by
+-- GitLab's suggestion id so they can be applied from the app. Replaced each
+-- time the note is synced.
+
+CREATE TABLE IF NOT EXISTS comment_suggestions (
+    id INTEGER PRIMARY KEY,
+    comment_id INTEGER NOT NULL,
+    from_line INTEGER NOT NULL,
+    to_line INTEGER NOT NULL,
+    to_content TEXT NOT NULL DEFAULT '',
+    appliable INTEGER NOT NULL DEFAULT 0,
+    applied INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (comment_id) REFERENCES comments(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_comment_suggestions_comment ON comment_suggestions(comment_id);
@@ -0,0 +1,21 @@
+-- Migration: 0057_merge_blockers.sql
+-- What GitLab says still blocks an MR: its detailed merge status and conflict
+-- flag from each sync, and the approval rules from the last merge-blocker
+-- refresh (approval rules need a request per MR, so they aren't synced).
+
+ALTER TABLE merge_requests ADD COLUMN detailed_merge_status TEXT;
+ALTER TABLE merge_requests ADD COLUMN has_conflicts INTEGER NOT NULL DEFAULT 0;
+
+CREATE TABLE IF NOT EXISTS mr_approval_rules (
+    mr_id INTEGER NOT NULL,
+    rule_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    rule_type TEXT NOT NULL,
+    approvals_required INTEGER NOT NULL,
+    approved INTEGER NOT NULL DEFAULT 0,
+    -- JSON array of usernames
+    approved_by TEXT NOT NULL DEFAULT '[]',
+    position INTEGER NOT NULL,
+    PRIMARY KEY (mr_id, rule_id),
+    FOREIGN KEY (mr_id) REFERENCES merge_requests(id) ON DELETE CASCADE
+);
@@ -0,0 +1,23 @@
+-- Migration: 0058_review_drafts.sql
+-- Pending reviews: draft comments collected locally for one MR and only
+-- queued for sync, as a single batch, when the review is submitted.
+
+CREATE TABLE IF NOT EXISTS review_drafts (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    mr_id INTEGER NOT NULL UNIQUE,
+    started_at INTEGER NOT NULL,
+    FOREIGN KEY (mr_id) REFERENCES merge_requests(id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS review_draft_comments (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    review_id INTEGER NOT NULL,
+    body TEXT NOT NULL,
+    file_path TEXT,
+    old_line INTEGER,
+    new_line INTEGER,
+    created_at INTEGER NOT NULL,
+    FOREIGN KEY (review_id) REFERENCES review_drafts(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_review_draft_comments_review ON review_draft_comments(review_id);
@@ -0,0 +1,14 @@
+-- Migration: 0059_file_assignments.sql
+-- File-level review assignments within one MR, so a large MR can be split
+-- across reviewers. Rows are read from assignment comments (posted by this
+-- app or synced from GitLab); the newest assignment of a file wins.
+
+CREATE TABLE IF NOT EXISTS mr_file_assignments (
+    mr_id INTEGER NOT NULL,
+    file_path TEXT NOT NULL,
+    username TEXT NOT NULL,
+    assigned_by TEXT NOT NULL,
+    assigned_at INTEGER NOT NULL,
+    PRIMARY KEY (mr_id, file_path),
+    FOREIGN KEY (mr_id) REFERENCES merge_requests(id) ON DELETE CASCADE
+);
@@ -0,0 +1,14 @@
+-- Migration: 0060_sync_configs.sql
+-- Per-instance sync configuration: which MR scopes to fetch and, optionally,
+-- an interval and MR cap overriding the global sync config. A missing row
+-- means every scope at the global settings.
+
+CREATE TABLE IF NOT EXISTS sync_configs (
+    instance_id INTEGER PRIMARY KEY,
+    sync_authored INTEGER NOT NULL DEFAULT 1,
+    sync_reviewing INTEGER NOT NULL DEFAULT 1,
+    sync_assigned INTEGER NOT NULL DEFAULT 1,
+    interval_secs INTEGER,
+    max_mrs_per_sync INTEGER,
+    FOREIGN KEY (instance_id) REFERENCES gitlab_instances(id) ON DELETE CASCADE
+);
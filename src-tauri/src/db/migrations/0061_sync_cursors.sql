@@ -0,0 +1,12 @@
+-- Migration: 0061_sync_cursors.sql
+-- Per-instance incremental sync cursor. `last_synced_at` is when the last
+-- complete MR list fetch started; later syncs only list MRs updated after it.
+-- `last_full_sync_at` is when the full open-MR list was last fetched, which
+-- is still needed now and then to notice MRs that left every scope.
+
+CREATE TABLE IF NOT EXISTS sync_cursors (
+    instance_id INTEGER PRIMARY KEY,
+    last_synced_at INTEGER NOT NULL,
+    last_full_sync_at INTEGER NOT NULL,
+    FOREIGN KEY (instance_id) REFERENCES gitlab_instances(id) ON DELETE CASCADE
+);
@@ -0,0 +1,30 @@
+-- Migration: 0062_mr_commits.sql
+-- Commits of each cached MR, oldest first, refreshed whenever the MR's diff
+-- is. A commit's own diff is fetched when it is first opened and kept as
+-- JSON file diffs; it never changes, so rows are shared by every MR that
+-- contains the commit.
+
+CREATE TABLE IF NOT EXISTS mr_commits (
+    mr_id INTEGER NOT NULL,
+    sha TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    short_sha TEXT NOT NULL,
+    title TEXT NOT NULL,
+    message TEXT NOT NULL,
+    author_name TEXT NOT NULL,
+    author_email TEXT NOT NULL,
+    authored_at INTEGER NOT NULL,
+    web_url TEXT NOT NULL DEFAULT '',
+    PRIMARY KEY (mr_id, sha),
+    FOREIGN KEY (mr_id) REFERENCES merge_requests(id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS commit_diffs (
+    instance_id INTEGER NOT NULL,
+    project_id INTEGER NOT NULL,
+    sha TEXT NOT NULL,
+    files TEXT NOT NULL DEFAULT '[]',
+    fetched_at INTEGER NOT NULL,
+    PRIMARY KEY (instance_id, project_id, sha),
+    FOREIGN KEY (instance_id) REFERENCES gitlab_instances(id) ON DELETE CASCADE
+);
@@ -0,0 +1,7 @@
+-- Migration: 0063_review_progress.sql
+-- The head commit the user last marked an MR reviewed at. Sync sets
+-- `changed_since_review` when it caches a diff whose head is a different
+-- commit; marking the MR reviewed again clears it.
+
+ALTER TABLE merge_requests ADD COLUMN last_reviewed_head_sha TEXT;
+ALTER TABLE merge_requests ADD COLUMN changed_since_review INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,5 @@
+-- Migration: 0064_sync_queue_retry_backoff.sql
+-- When a failed action goes back to pending for an automatic retry, the
+-- earliest time the queue may push it again. NULL means it is due now.
+
+ALTER TABLE sync_queue ADD COLUMN next_retry_at INTEGER;
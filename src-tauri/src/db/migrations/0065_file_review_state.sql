@@ -0,0 +1,15 @@
+-- Migration: 0065_file_review_state.sql
+-- Files the user marked viewed in an MR's diff, at the head commit they were
+-- viewed at, with a hash of the file's diff then. When sync caches a new
+-- diff, rows whose file diff is unchanged move to the new head; the others
+-- are removed so the file shows as unviewed again.
+
+CREATE TABLE IF NOT EXISTS file_review_state (
+    mr_id INTEGER NOT NULL,
+    file_path TEXT NOT NULL,
+    head_sha TEXT NOT NULL,
+    diff_hash TEXT NOT NULL,
+    viewed_at INTEGER NOT NULL,
+    PRIMARY KEY (mr_id, file_path, head_sha),
+    FOREIGN KEY (mr_id) REFERENCES merge_requests(id) ON DELETE CASCADE
+);
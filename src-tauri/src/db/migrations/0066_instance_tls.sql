@@ -0,0 +1,6 @@
+-- Migration: 0066_instance_tls.sql
+-- Per-instance TLS settings: minimum protocol version and an optional client
+-- certificate for mTLS. Stored as a JSON object ({"minVersion",
+-- "clientIdentityPath"}); NULL means the defaults (TLS 1.2+, no certificate).
+
+ALTER TABLE gitlab_instances ADD COLUMN tls_config TEXT;
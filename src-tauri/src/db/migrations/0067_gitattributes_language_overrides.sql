@@ -0,0 +1,5 @@
+-- Migration: 0067_gitattributes_language_overrides.sql
+-- `linguist-language` overrides from a project's .gitattributes, used to pick
+-- the grammar when highlighting diffs. JSON array of {"pattern", "language"}.
+
+ALTER TABLE gitattributes_cache ADD COLUMN language_overrides TEXT NOT NULL DEFAULT '[]';
@@ -0,0 +1,14 @@
+-- Migration: 0068_semantic_summaries.sql
+-- Precomputed symbol-level summaries of diff files, filled in by the idle
+-- background job scheduler. A row is only valid while `head_sha` matches the
+-- MR's cached diff; stale rows are overwritten on the next computation.
+
+CREATE TABLE IF NOT EXISTS semantic_summaries (
+    mr_id INTEGER NOT NULL,
+    file_path TEXT NOT NULL,
+    head_sha TEXT NOT NULL,
+    summary TEXT NOT NULL,
+    computed_at INTEGER NOT NULL,
+    PRIMARY KEY (mr_id, file_path),
+    FOREIGN KEY (mr_id) REFERENCES merge_requests(id) ON DELETE CASCADE
+);
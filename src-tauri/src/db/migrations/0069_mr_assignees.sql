@@ -0,0 +1,5 @@
+-- Migration: 0069_mr_assignees.sql
+-- Usernames of an MR's assignees (JSON array), like `reviewers`. Until now
+-- only `assigned_to_me` was kept; the participant list needs everyone.
+
+ALTER TABLE merge_requests ADD COLUMN assignees TEXT NOT NULL DEFAULT '[]';
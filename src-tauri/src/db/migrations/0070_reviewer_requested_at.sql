@@ -0,0 +1,6 @@
+-- Migration: 0070_reviewer_requested_at.sql
+-- When sync first saw each reviewer on an MR, the start of the review SLA
+-- clock. GitLab doesn't report when a reviewer was added, so the MR's
+-- `updated_at` at that sync stands in. NULL for rows cached before this.
+
+ALTER TABLE mr_reviewers ADD COLUMN requested_at INTEGER;
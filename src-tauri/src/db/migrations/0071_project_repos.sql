@@ -0,0 +1,11 @@
+-- Migration: 0071_project_repos.sql
+-- Local working copy of a project, where MR branches are checked out.
+-- Remembered the first time the user checks out an MR of the project.
+
+CREATE TABLE IF NOT EXISTS project_repos (
+    instance_id INTEGER NOT NULL REFERENCES gitlab_instances(id) ON DELETE CASCADE,
+    project_id INTEGER NOT NULL,
+    repo_path TEXT NOT NULL,
+    updated_at INTEGER NOT NULL,
+    PRIMARY KEY (instance_id, project_id)
+);
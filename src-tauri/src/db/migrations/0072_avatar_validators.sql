@@ -0,0 +1,9 @@
+-- Migration: 0072_avatar_validators.sql
+-- HTTP cache validators of each cached avatar, sent back as If-None-Match /
+-- If-Modified-Since so a refresh of an unchanged image is a 304, plus the
+-- SHA-256 of the stored image so a re-download of the same bytes doesn't
+-- rewrite the blob.
+
+ALTER TABLE user_avatars ADD COLUMN etag TEXT;
+ALTER TABLE user_avatars ADD COLUMN last_modified TEXT;
+ALTER TABLE user_avatars ADD COLUMN content_hash TEXT;
@@ -0,0 +1,20 @@
+-- Migration: 0073_mr_events.sql
+-- Activity timeline of each cached MR: system notes and GitLab's label,
+-- milestone and state resource events, parsed into one row per entry.
+-- `subjects` is a JSON array of the labels, usernames or milestone an entry
+-- is about.
+
+CREATE TABLE IF NOT EXISTS mr_events (
+    mr_id INTEGER NOT NULL,
+    source TEXT NOT NULL,
+    source_id INTEGER NOT NULL,
+    kind TEXT NOT NULL,
+    actor_username TEXT,
+    subjects TEXT NOT NULL DEFAULT '[]',
+    detail TEXT,
+    created_at INTEGER NOT NULL,
+    PRIMARY KEY (mr_id, source, source_id),
+    FOREIGN KEY (mr_id) REFERENCES merge_requests(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_mr_events_created ON mr_events(mr_id, created_at);
@@ -0,0 +1,5 @@
+-- Migration: 0074_manual_gate_notify.sql
+-- Per-project opt-in, for pinned pipeline projects, to notify when a new
+-- manual job (a deploy gate) waits in a pipeline of the default branch.
+
+ALTER TABLE pipeline_projects ADD COLUMN notify_manual_gates INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,12 @@
+-- Migration: 0075_markdown_cache.sql
+-- Sanitized HTML rendered from GitLab-flavored markdown, keyed by a hash of
+-- the source text, the renderer version and the project references resolve
+-- against. Rows unused for a while are pruned.
+
+CREATE TABLE IF NOT EXISTS markdown_cache (
+    hash TEXT PRIMARY KEY,
+    html TEXT NOT NULL,
+    used_at INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_markdown_cache_used ON markdown_cache(used_at);
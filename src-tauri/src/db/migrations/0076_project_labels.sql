@@ -0,0 +1,24 @@
+-- Migration: 0076_project_labels.sql
+-- Labels available in each project (its own and inherited group labels),
+-- refreshed during sync so the label picker works offline. A project's rows
+-- are replaced wholesale on every fetch; project_label_fetches records when
+-- that happened, including for projects that have no labels at all.
+
+CREATE TABLE IF NOT EXISTS project_labels (
+    instance_id INTEGER NOT NULL,
+    project_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    color TEXT NOT NULL,
+    text_color TEXT,
+    description TEXT,
+    PRIMARY KEY (instance_id, project_id, name),
+    FOREIGN KEY (instance_id) REFERENCES gitlab_instances(id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS project_label_fetches (
+    instance_id INTEGER NOT NULL,
+    project_id INTEGER NOT NULL,
+    fetched_at INTEGER NOT NULL,
+    PRIMARY KEY (instance_id, project_id),
+    FOREIGN KEY (instance_id) REFERENCES gitlab_instances(id) ON DELETE CASCADE
+);
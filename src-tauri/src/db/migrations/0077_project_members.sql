@@ -0,0 +1,27 @@
+-- Migration: 0077_project_members.sql
+-- Members of each project (direct and inherited), fetched when the
+-- reviewer/assignee picker first searches a project and kept so it works
+-- offline. GitLab takes user ids when setting reviewers and assignees, so
+-- this is also where usernames are resolved to ids.
+
+CREATE TABLE IF NOT EXISTS project_members (
+    instance_id INTEGER NOT NULL,
+    project_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    username TEXT NOT NULL,
+    name TEXT NOT NULL,
+    avatar_url TEXT,
+    PRIMARY KEY (instance_id, project_id, user_id),
+    FOREIGN KEY (instance_id) REFERENCES gitlab_instances(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_project_members_username
+    ON project_members(instance_id, username);
+
+CREATE TABLE IF NOT EXISTS project_member_fetches (
+    instance_id INTEGER NOT NULL,
+    project_id INTEGER NOT NULL,
+    fetched_at INTEGER NOT NULL,
+    PRIMARY KEY (instance_id, project_id),
+    FOREIGN KEY (instance_id) REFERENCES gitlab_instances(id) ON DELETE CASCADE
+);
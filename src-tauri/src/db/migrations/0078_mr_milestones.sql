@@ -0,0 +1,28 @@
+-- Migration: 0078_mr_milestones.sql
+-- The milestone an MR is scheduled for, copied from the MR on every sync, and
+-- the active milestones of each project (its own and its groups') so MRs can
+-- be moved between milestones offline. Like project_labels, a project's
+-- milestones are replaced wholesale on every fetch.
+
+ALTER TABLE merge_requests ADD COLUMN milestone_id INTEGER;
+ALTER TABLE merge_requests ADD COLUMN milestone_title TEXT;
+-- `YYYY-MM-DD`, as GitLab sends it.
+ALTER TABLE merge_requests ADD COLUMN milestone_due_date TEXT;
+
+CREATE TABLE IF NOT EXISTS project_milestones (
+    instance_id INTEGER NOT NULL,
+    project_id INTEGER NOT NULL,
+    milestone_id INTEGER NOT NULL,
+    title TEXT NOT NULL,
+    due_date TEXT,
+    PRIMARY KEY (instance_id, project_id, milestone_id),
+    FOREIGN KEY (instance_id) REFERENCES gitlab_instances(id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS project_milestone_fetches (
+    instance_id INTEGER NOT NULL,
+    project_id INTEGER NOT NULL,
+    fetched_at INTEGER NOT NULL,
+    PRIMARY KEY (instance_id, project_id),
+    FOREIGN KEY (instance_id) REFERENCES gitlab_instances(id) ON DELETE CASCADE
+);
@@ -0,0 +1,11 @@
+-- Migration: 0079_markdown_cache_instance.sql
+-- Remember which instance rendered markdown came from, so the retention sweep
+-- and the data audit can account for it per instance. Existing rows can't be
+-- attributed and are dropped; they are re-rendered on demand.
+
+DELETE FROM markdown_cache;
+
+-- NULL for text rendered without a project context.
+ALTER TABLE markdown_cache ADD COLUMN instance_id INTEGER;
+
+CREATE INDEX IF NOT EXISTS idx_markdown_cache_instance ON markdown_cache(instance_id);
@@ -5,13 +5,40 @@
 //! - Schema migrations
 //! - Query helpers
 
+pub mod approval_gates;
+pub mod approval_rules;
 pub mod auto_merge;
 pub mod auto_run;
+pub mod automations;
+pub mod badge_rules;
+pub mod cache_archive;
+pub mod change_log;
+pub mod encryption;
+pub mod file_assignments;
+pub mod file_blame;
 pub mod file_cache;
 pub mod issue_notes;
+pub mod job_trace_cache;
+pub mod markdown_cache;
+pub mod mr_commits;
+pub mod mr_events;
+pub mod muted_projects;
 pub mod notification_settings;
 pub mod pipeline_cache;
 pub mod pool;
+pub mod project_labels;
+pub mod project_members;
+pub mod project_milestones;
+pub mod project_readme;
+pub mod project_repos;
+pub mod release_cache;
+pub mod retention;
+pub mod review_activity;
+pub mod review_drafts;
+pub mod sync_configs;
+pub mod sync_cursors;
+pub mod translations;
+pub mod watched;
 
 use std::path::{Path, PathBuf};
 use thiserror::Error;
@@ -25,6 +52,9 @@ pub enum DbError {
     #[error("Migration error: {0}")]
     Migration(String),
 
+    #[error("Encryption error: {0}")]
+    Encryption(String),
+
     #[error("Database not initialized")]
     NotInitialized,
 }
@@ -158,6 +188,226 @@ const MIGRATIONS: &[(&str, &str)] = &[
         "0024_auto_run_claims",
         include_str!("migrations/0024_auto_run_claims.sql"),
     ),
+    (
+        "0025_add_mr_watched",
+        include_str!("migrations/0025_add_mr_watched.sql"),
+    ),
+    (
+        "0026_add_mr_description_preview",
+        include_str!("migrations/0026_add_mr_description_preview.sql"),
+    ),
+    (
+        "0027_approval_gates",
+        include_str!("migrations/0027_approval_gates.sql"),
+    ),
+    (
+        "0028_retention_policies",
+        include_str!("migrations/0028_retention_policies.sql"),
+    ),
+    (
+        "0029_instance_custom_headers",
+        include_str!("migrations/0029_instance_custom_headers.sql"),
+    ),
+    (
+        "0030_review_activity",
+        include_str!("migrations/0030_review_activity.sql"),
+    ),
+    (
+        "0031_bot_authors",
+        include_str!("migrations/0031_bot_authors.sql"),
+    ),
+    (
+        "0032_avatar_email",
+        include_str!("migrations/0032_avatar_email.sql"),
+    ),
+    (
+        "0033_change_log",
+        include_str!("migrations/0033_change_log.sql"),
+    ),
+    (
+        "0034_pipeline_schedules",
+        include_str!("migrations/0034_pipeline_schedules.sql"),
+    ),
+    (
+        "0035_project_readme_cache",
+        include_str!("migrations/0035_project_readme_cache.sql"),
+    ),
+    (
+        "0036_merge_when_ready",
+        include_str!("migrations/0036_merge_when_ready.sql"),
+    ),
+    (
+        "0037_automations",
+        include_str!("migrations/0037_automations.sql"),
+    ),
+    (
+        "0038_release_tag_cache",
+        include_str!("migrations/0038_release_tag_cache.sql"),
+    ),
+    (
+        "0039_comment_translations",
+        include_str!("migrations/0039_comment_translations.sql"),
+    ),
+    (
+        "0040_job_trace_cache",
+        include_str!("migrations/0040_job_trace_cache.sql"),
+    ),
+    (
+        "0041_review_rerequests",
+        include_str!("migrations/0041_review_rerequests.sql"),
+    ),
+    (
+        "0042_sync_queue_failure_details",
+        include_str!("migrations/0042_sync_queue_failure_details.sql"),
+    ),
+    (
+        "0043_mr_tasks",
+        include_str!("migrations/0043_mr_tasks.sql"),
+    ),
+    (
+        "0044_author_pings",
+        include_str!("migrations/0044_author_pings.sql"),
+    ),
+    (
+        "0045_sync_reconciliations",
+        include_str!("migrations/0045_sync_reconciliations.sql"),
+    ),
+    (
+        "0046_discussion_access",
+        include_str!("migrations/0046_discussion_access.sql"),
+    ),
+    (
+        "0047_orphaned_actions",
+        include_str!("migrations/0047_orphaned_actions.sql"),
+    ),
+    (
+        "0048_vacation_responses",
+        include_str!("migrations/0048_vacation_responses.sql"),
+    ),
+    (
+        "0049_token_scopes",
+        include_str!("migrations/0049_token_scopes.sql"),
+    ),
+    (
+        "0050_pipeline_notify_rules",
+        include_str!("migrations/0050_pipeline_notify_rules.sql"),
+    ),
+    (
+        "0051_mr_last_viewed",
+        include_str!("migrations/0051_mr_last_viewed.sql"),
+    ),
+    (
+        "0052_muted_projects",
+        include_str!("migrations/0052_muted_projects.sql"),
+    ),
+    (
+        "0053_file_blame",
+        include_str!("migrations/0053_file_blame.sql"),
+    ),
+    (
+        "0054_mr_badge_rules",
+        include_str!("migrations/0054_mr_badge_rules.sql"),
+    ),
+    (
+        "0055_sync_queue_batches",
+        include_str!("migrations/0055_sync_queue_batches.sql"),
+    ),
+    (
+        "0056_comment_suggestions",
+        include_str!("migrations/0056_comment_suggestions.sql"),
+    ),
+    (
+        "0057_merge_blockers",
+        include_str!("migrations/0057_merge_blockers.sql"),
+    ),
+    (
+        "0058_review_drafts",
+        include_str!("migrations/0058_review_drafts.sql"),
+    ),
+    (
+        "0059_file_assignments",
+        include_str!("migrations/0059_file_assignments.sql"),
+    ),
+    (
+        "0060_sync_configs",
+        include_str!("migrations/0060_sync_configs.sql"),
+    ),
+    (
+        "0061_sync_cursors",
+        include_str!("migrations/0061_sync_cursors.sql"),
+    ),
+    (
+        "0062_mr_commits",
+        include_str!("migrations/0062_mr_commits.sql"),
+    ),
+    (
+        "0063_review_progress",
+        include_str!("migrations/0063_review_progress.sql"),
+    ),
+    (
+        "0064_sync_queue_retry_backoff",
+        include_str!("migrations/0064_sync_queue_retry_backoff.sql"),
+    ),
+    (
+        "0065_file_review_state",
+        include_str!("migrations/0065_file_review_state.sql"),
+    ),
+    (
+        "0066_instance_tls",
+        include_str!("migrations/0066_instance_tls.sql"),
+    ),
+    (
+        "0067_gitattributes_language_overrides",
+        include_str!("migrations/0067_gitattributes_language_overrides.sql"),
+    ),
+    (
+        "0068_semantic_summaries",
+        include_str!("migrations/0068_semantic_summaries.sql"),
+    ),
+    (
+        "0069_mr_assignees",
+        include_str!("migrations/0069_mr_assignees.sql"),
+    ),
+    (
+        "0070_reviewer_requested_at",
+        include_str!("migrations/0070_reviewer_requested_at.sql"),
+    ),
+    (
+        "0071_project_repos",
+        include_str!("migrations/0071_project_repos.sql"),
+    ),
+    (
+        "0072_avatar_validators",
+        include_str!("migrations/0072_avatar_validators.sql"),
+    ),
+    (
+        "0073_mr_events",
+        include_str!("migrations/0073_mr_events.sql"),
+    ),
+    (
+        "0074_manual_gate_notify",
+        include_str!("migrations/0074_manual_gate_notify.sql"),
+    ),
+    (
+        "0075_markdown_cache",
+        include_str!("migrations/0075_markdown_cache.sql"),
+    ),
+    (
+        "0076_project_labels",
+        include_str!("migrations/0076_project_labels.sql"),
+    ),
+    (
+        "0077_project_members",
+        include_str!("migrations/0077_project_members.sql"),
+    ),
+    (
+        "0078_mr_milestones",
+        include_str!("migrations/0078_mr_milestones.sql"),
+    ),
+    (
+        "0079_markdown_cache_instance",
+        include_str!("migrations/0079_markdown_cache_instance.sql"),
+    ),
 ];
 
 /// Run all pending database migrations.
@@ -0,0 +1,124 @@
+//! MR commit list and commit diff cache DB helpers.
+//!
+//! `mr_commits` is replaced as a whole on every sync of an MR's commits.
+//! `commit_diffs` holds file diffs as JSON exactly as `core::mr_commits`
+//! serializes them.
+
+use crate::db::pool::DbPool;
+use serde::Serialize;
+use sqlx::FromRow;
+
+/// A commit of an MR.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct MrCommit {
+    pub sha: String,
+    pub short_sha: String,
+    /// First line of the message.
+    pub title: String,
+    pub message: String,
+    pub author_name: String,
+    pub author_email: String,
+    /// Unix seconds; 0 when GitLab sent an unparseable date.
+    pub authored_at: i64,
+    pub web_url: String,
+}
+
+/// The MR's commits, oldest first.
+pub async fn list_commits(pool: &DbPool, mr_id: i64) -> Result<Vec<MrCommit>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT sha, short_sha, title, message, author_name, author_email, authored_at, web_url
+         FROM mr_commits WHERE mr_id = ? ORDER BY position",
+    )
+    .bind(mr_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Replace the MR's commits with `commits`, given oldest first.
+pub async fn replace_commits(
+    pool: &DbPool,
+    mr_id: i64,
+    commits: &[MrCommit],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM mr_commits WHERE mr_id = ?")
+        .bind(mr_id)
+        .execute(&mut *tx)
+        .await?;
+    for (position, commit) in commits.iter().enumerate() {
+        sqlx::query(
+            "INSERT INTO mr_commits (mr_id, sha, position, short_sha, title, message,
+                                     author_name, author_email, authored_at, web_url)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT (mr_id, sha) DO NOTHING",
+        )
+        .bind(mr_id)
+        .bind(&commit.sha)
+        .bind(position as i64)
+        .bind(&commit.short_sha)
+        .bind(&commit.title)
+        .bind(&commit.message)
+        .bind(&commit.author_name)
+        .bind(&commit.author_email)
+        .bind(commit.authored_at)
+        .bind(&commit.web_url)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await
+}
+
+/// Cached file diffs (JSON) of a commit.
+pub async fn get_commit_diff(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+    sha: &str,
+) -> Result<Option<String>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT files FROM commit_diffs WHERE instance_id = ? AND project_id = ? AND sha = ?",
+    )
+    .bind(instance_id)
+    .bind(project_id)
+    .bind(sha)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Insert or replace the file diffs of a commit.
+pub async fn save_commit_diff(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+    sha: &str,
+    files: &str,
+    now: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO commit_diffs (instance_id, project_id, sha, files, fetched_at)
+         VALUES (?, ?, ?, ?, ?)
+         ON CONFLICT (instance_id, project_id, sha)
+         DO UPDATE SET files = excluded.files, fetched_at = excluded.fetched_at",
+    )
+    .bind(instance_id)
+    .bind(project_id)
+    .bind(sha)
+    .bind(files)
+    .bind(now)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Drop cached diffs of commits no cached MR contains any more. Returns the
+/// number of rows removed.
+pub async fn prune_commit_diffs(pool: &DbPool) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        "DELETE FROM commit_diffs
+         WHERE NOT EXISTS (SELECT 1 FROM mr_commits c WHERE c.sha = commit_diffs.sha)",
+    )
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
@@ -0,0 +1,157 @@
+//! MR activity timeline DB helpers.
+//!
+//! `mr_events` holds one row per timeline entry from two kinds of source:
+//! system notes (`note`) and GitLab's resource event endpoints (`label`,
+//! `milestone`, `state`). The rows of one source are replaced as a whole on
+//! every sync of that source; `core::mr_activity` builds them.
+
+use crate::db::pool::DbPool;
+use serde::{Deserialize, Serialize};
+
+/// What happened, as stored in `mr_events.kind`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    LabelsAdded,
+    LabelsRemoved,
+    MilestoneSet,
+    MilestoneRemoved,
+    Closed,
+    Reopened,
+    Merged,
+    /// Commits pushed; `detail` is the number of commits.
+    Pushed,
+    ForcePushed,
+    ReviewRequested,
+    ReviewRequestRemoved,
+    Assigned,
+    Unassigned,
+    Approved,
+    Unapproved,
+    MarkedDraft,
+    MarkedReady,
+    /// `detail` is the new title.
+    TitleChanged,
+    DescriptionChanged,
+    /// A system note not recognised; `detail` is its text.
+    Other,
+}
+
+impl EventKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EventKind::LabelsAdded => "labels_added",
+            EventKind::LabelsRemoved => "labels_removed",
+            EventKind::MilestoneSet => "milestone_set",
+            EventKind::MilestoneRemoved => "milestone_removed",
+            EventKind::Closed => "closed",
+            EventKind::Reopened => "reopened",
+            EventKind::Merged => "merged",
+            EventKind::Pushed => "pushed",
+            EventKind::ForcePushed => "force_pushed",
+            EventKind::ReviewRequested => "review_requested",
+            EventKind::ReviewRequestRemoved => "review_request_removed",
+            EventKind::Assigned => "assigned",
+            EventKind::Unassigned => "unassigned",
+            EventKind::Approved => "approved",
+            EventKind::Unapproved => "unapproved",
+            EventKind::MarkedDraft => "marked_draft",
+            EventKind::MarkedReady => "marked_ready",
+            EventKind::TitleChanged => "title_changed",
+            EventKind::DescriptionChanged => "description_changed",
+            EventKind::Other => "other",
+        }
+    }
+
+    /// Unknown values (from a newer version) read as `Other`.
+    fn parse(value: &str) -> Self {
+        serde_json::from_value(serde_json::Value::from(value)).unwrap_or(EventKind::Other)
+    }
+}
+
+/// One entry of an MR's activity timeline.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MrEvent {
+    /// `note`, `label`, `milestone` or `state`.
+    pub source: String,
+    /// Id of the note or resource event.
+    pub source_id: i64,
+    pub kind: EventKind,
+    /// `None` when GitLab no longer knows the user.
+    pub actor_username: Option<String>,
+    /// Labels, usernames or milestone the event is about.
+    pub subjects: Vec<String>,
+    pub detail: Option<String>,
+    /// Unix seconds.
+    pub created_at: i64,
+}
+
+type EventRow = (
+    String,
+    i64,
+    String,
+    Option<String>,
+    String,
+    Option<String>,
+    i64,
+);
+
+/// The MR's timeline, oldest first.
+pub async fn list_events(pool: &DbPool, mr_id: i64) -> Result<Vec<MrEvent>, sqlx::Error> {
+    let rows: Vec<EventRow> = sqlx::query_as(
+        "SELECT source, source_id, kind, actor_username, subjects, detail, created_at
+         FROM mr_events WHERE mr_id = ? ORDER BY created_at, source, source_id",
+    )
+    .bind(mr_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(
+            |(source, source_id, kind, actor_username, subjects, detail, created_at)| MrEvent {
+                source,
+                source_id,
+                kind: EventKind::parse(&kind),
+                actor_username,
+                subjects: serde_json::from_str(&subjects).unwrap_or_default(),
+                detail,
+                created_at,
+            },
+        )
+        .collect())
+}
+
+/// Replace the MR's events from `source` with `events`, all from that source.
+pub async fn replace_events(
+    pool: &DbPool,
+    mr_id: i64,
+    source: &str,
+    events: &[MrEvent],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM mr_events WHERE mr_id = ? AND source = ?")
+        .bind(mr_id)
+        .bind(source)
+        .execute(&mut *tx)
+        .await?;
+    for event in events {
+        sqlx::query(
+            "INSERT INTO mr_events (mr_id, source, source_id, kind, actor_username,
+                                    subjects, detail, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT (mr_id, source, source_id) DO NOTHING",
+        )
+        .bind(mr_id)
+        .bind(source)
+        .bind(event.source_id)
+        .bind(event.kind.as_str())
+        .bind(&event.actor_username)
+        .bind(serde_json::to_string(&event.subjects).unwrap_or_else(|_| "[]".into()))
+        .bind(&event.detail)
+        .bind(event.created_at)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await
+}
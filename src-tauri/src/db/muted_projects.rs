@@ -0,0 +1,139 @@
+//! Muted-project DB helpers.
+//!
+//! A row in `muted_projects` silences a project: the sync engine skips its
+//! notifications and the dock badge leaves its MRs and failed actions out.
+//! The project is still synced. Expired mutes are ignored by every query
+//! and cleared by [`prune_expired`].
+
+use crate::db::pool::DbPool;
+use serde::Serialize;
+use sqlx::FromRow;
+use std::collections::HashSet;
+
+/// A muted project, with its name when the project is cached.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct MutedProject {
+    pub instance_id: i64,
+    pub project_id: i64,
+    pub name_with_namespace: Option<String>,
+    /// Unix seconds; None mutes until unmuted.
+    pub muted_until: Option<i64>,
+    pub muted_at: i64,
+}
+
+/// Mute a project until `until` (None: until unmuted). Muting an already
+/// muted project replaces its expiry.
+pub async fn mute(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+    until: Option<i64>,
+    now: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO muted_projects (instance_id, project_id, muted_until, muted_at)
+         VALUES (?, ?, ?, ?)
+         ON CONFLICT (instance_id, project_id)
+         DO UPDATE SET muted_until = excluded.muted_until, muted_at = excluded.muted_at",
+    )
+    .bind(instance_id)
+    .bind(project_id)
+    .bind(until)
+    .bind(now)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Unmute a project. Returns false when it wasn't muted.
+pub async fn unmute(pool: &DbPool, instance_id: i64, project_id: i64) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM muted_projects WHERE instance_id = ? AND project_id = ?")
+        .bind(instance_id)
+        .bind(project_id)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Projects muted at `now`, across all instances.
+pub async fn list_muted(pool: &DbPool, now: i64) -> Result<Vec<MutedProject>, sqlx::Error> {
+    sqlx::query_as::<_, MutedProject>(
+        "SELECT m.instance_id, m.project_id, p.name_with_namespace, m.muted_until, m.muted_at
+         FROM muted_projects m
+         LEFT JOIN projects p ON p.id = m.project_id AND p.instance_id = m.instance_id
+         WHERE m.muted_until IS NULL OR m.muted_until > ?
+         ORDER BY m.muted_at DESC",
+    )
+    .bind(now)
+    .fetch_all(pool)
+    .await
+}
+
+/// Ids of the projects of an instance muted at `now`.
+pub async fn muted_project_ids(
+    pool: &DbPool,
+    instance_id: i64,
+    now: i64,
+) -> Result<HashSet<i64>, sqlx::Error> {
+    let ids: Vec<i64> = sqlx::query_scalar(
+        "SELECT project_id FROM muted_projects
+         WHERE instance_id = ? AND (muted_until IS NULL OR muted_until > ?)",
+    )
+    .bind(instance_id)
+    .bind(now)
+    .fetch_all(pool)
+    .await?;
+    Ok(ids.into_iter().collect())
+}
+
+/// Drop mutes that expired before `now`. Returns the number of rows removed.
+pub async fn prune_expired(pool: &DbPool, now: i64) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM muted_projects WHERE muted_until <= ?")
+        .bind(now)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn mutes_expire_and_can_be_replaced() {
+        let dir = tempdir().unwrap();
+        let pool = db::initialize(&dir.path().join("t.db")).await.unwrap();
+
+        mute(&pool, 1, 10, None, 100).await.unwrap();
+        mute(&pool, 1, 11, Some(500), 100).await.unwrap();
+        mute(&pool, 2, 10, Some(300), 100).await.unwrap();
+
+        assert_eq!(
+            muted_project_ids(&pool, 1, 400).await.unwrap(),
+            HashSet::from([10, 11])
+        );
+        assert_eq!(
+            muted_project_ids(&pool, 1, 500).await.unwrap(),
+            HashSet::from([10])
+        );
+        assert!(muted_project_ids(&pool, 2, 300).await.unwrap().is_empty());
+        assert_eq!(list_muted(&pool, 200).await.unwrap().len(), 3);
+
+        // Muting again replaces the expiry.
+        mute(&pool, 1, 11, None, 150).await.unwrap();
+        assert!(muted_project_ids(&pool, 1, 900)
+            .await
+            .unwrap()
+            .contains(&11));
+
+        assert_eq!(prune_expired(&pool, 300).await.unwrap(), 1);
+        assert!(unmute(&pool, 1, 10).await.unwrap());
+        assert!(!unmute(&pool, 1, 10).await.unwrap());
+        let left = list_muted(&pool, 900).await.unwrap();
+        assert_eq!(left.len(), 1);
+        assert_eq!((left[0].project_id, left[0].muted_until), (11, None));
+    }
+}
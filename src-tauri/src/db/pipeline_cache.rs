@@ -95,3 +95,83 @@ pub async fn get_cached_pipeline_statuses(
         })
         .collect())
 }
+
+/// A row from the `pipeline_schedule_cache` table.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct CachedPipelineSchedule {
+    pub schedule_id: i64,
+    pub project_id: i64,
+    pub description: String,
+    pub ref_name: String,
+    pub cron: String,
+    pub cron_timezone: String,
+    pub next_run_at: Option<String>,
+    pub active: bool,
+    pub owner_username: Option<String>,
+}
+
+/// Replace all cached schedules of a project with `schedules`.
+pub async fn replace_pipeline_schedules(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+    schedules: &[CachedPipelineSchedule],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM pipeline_schedule_cache WHERE instance_id = ? AND project_id = ?")
+        .bind(instance_id)
+        .bind(project_id)
+        .execute(&mut *tx)
+        .await?;
+    for s in schedules {
+        upsert_pipeline_schedule(&mut *tx, instance_id, s).await?;
+    }
+    tx.commit().await
+}
+
+/// Insert or update one cached schedule. Takes a pool or a transaction.
+pub async fn upsert_pipeline_schedule<'e, E>(
+    executor: E,
+    instance_id: i64,
+    s: &CachedPipelineSchedule,
+) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query(
+        "INSERT OR REPLACE INTO pipeline_schedule_cache \
+         (instance_id, project_id, schedule_id, description, ref_name, cron, cron_timezone, next_run_at, active, owner_username, cached_at) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, datetime('now'))",
+    )
+    .bind(instance_id)
+    .bind(s.project_id)
+    .bind(s.schedule_id)
+    .bind(&s.description)
+    .bind(&s.ref_name)
+    .bind(&s.cron)
+    .bind(&s.cron_timezone)
+    .bind(&s.next_run_at)
+    .bind(s.active)
+    .bind(&s.owner_username)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// Load cached schedules for a project, ordered by description.
+pub async fn get_cached_pipeline_schedules(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<Vec<CachedPipelineSchedule>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT schedule_id, project_id, description, ref_name, cron, cron_timezone, next_run_at, active, owner_username \
+         FROM pipeline_schedule_cache \
+         WHERE instance_id = ? AND project_id = ? \
+         ORDER BY description COLLATE NOCASE, schedule_id",
+    )
+    .bind(instance_id)
+    .bind(project_id)
+    .fetch_all(pool)
+    .await
+}
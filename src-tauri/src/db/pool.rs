@@ -1,17 +1,55 @@
-//! SQLite connection pool with WAL mode.
+//! SQLite connection pools with WAL mode.
 //!
-//! Provides a thread-safe connection pool for SQLite with Write-Ahead Logging (WAL)
-//! enabled for concurrent read access during writes.
-
+//! The app uses two pools over the same database file:
+//! - the writer ([`DbPool`]), a single connection that every write goes
+//!   through. SQLite only ever allows one writer, so extra connections just
+//!   queue on the file lock; funnelling them through one connection turns
+//!   that into an in-process queue instead of `SQLITE_BUSY` retries.
+//! - the reader ([`ReadPool`]), several read-only connections for UI queries.
+//!   Under WAL a reader never waits on the writer, so the MR list stays
+//!   responsive while a sync is busy upserting.
+
+use super::encryption;
+use serde::Serialize;
 use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteSynchronous};
 use sqlx::{Pool, Sqlite};
+use std::ops::Deref;
 use std::path::Path;
 use std::str::FromStr;
+use std::time::Duration;
 
 /// Type alias for the SQLite connection pool.
 pub type DbPool = Pool<Sqlite>;
 
-/// Create a new connection pool with WAL mode enabled.
+/// Connections in the writer pool. Must stay 1: see the module docs.
+const WRITER_CONNECTIONS: u32 = 1;
+
+/// Connections in the read pool.
+const READER_CONNECTIONS: u32 = 8;
+
+/// Read-only pool for UI queries. Derefs to [`DbPool`], so it can be passed
+/// anywhere a `&DbPool` is taken for reading.
+#[derive(Clone)]
+pub struct ReadPool(DbPool);
+
+impl Deref for ReadPool {
+    type Target = DbPool;
+
+    fn deref(&self) -> &DbPool {
+        &self.0
+    }
+}
+
+/// Add the database key when the file is encrypted (see [`encryption`]).
+fn with_key(
+    options: SqliteConnectOptions,
+    db_path: &Path,
+) -> Result<SqliteConnectOptions, sqlx::Error> {
+    encryption::apply_key(options, db_path)
+        .map_err(|e| sqlx::Error::Configuration(e.to_string().into()))
+}
+
+/// Create the writer pool with WAL mode enabled.
 ///
 /// WAL mode provides:
 /// - Concurrent reads during writes
@@ -31,20 +69,22 @@ pub async fn create_pool(db_path: &Path) -> Result<DbPool, sqlx::Error> {
         .create_if_missing(true)
         // Enable WAL mode for concurrent access
         .journal_mode(SqliteJournalMode::Wal)
-        // NORMAL synchronous mode balances safety and performance
+        // NORMAL synchronous mode balances safety and performance; under WAL
+        // it only fsyncs at checkpoints, which keeps sync upserts cheap
         .synchronous(SqliteSynchronous::Normal)
         // Enable foreign key constraints
         .foreign_keys(true)
-        // Increase busy timeout to handle concurrent access
-        .busy_timeout(std::time::Duration::from_secs(30));
+        // Only other processes (the CLI) can hold the write lock now, and
+        // they write briefly; 30s covers a checkpoint on a large WAL
+        .busy_timeout(Duration::from_secs(30));
+    let connect_options = with_key(connect_options, db_path)?;
 
     let pool = SqlitePoolOptions::new()
-        // Max connections: SQLite handles concurrency via WAL, so we keep this moderate
-        .max_connections(5)
-        // Min connections: Keep at least one connection warm
+        .max_connections(WRITER_CONNECTIONS)
         .min_connections(1)
-        // Connection timeout
-        .acquire_timeout(std::time::Duration::from_secs(10))
+        // Callers queue for the single connection while a sync is writing,
+        // so allow longer than a plain connect would need
+        .acquire_timeout(Duration::from_secs(30))
         .connect_with(connect_options)
         .await?;
 
@@ -62,6 +102,55 @@ pub async fn create_pool(db_path: &Path) -> Result<DbPool, sqlx::Error> {
     Ok(pool)
 }
 
+/// Create the read-only pool. The database must already exist and be in WAL
+/// mode, i.e. call this after [`create_pool`] and migrations.
+///
+/// `PRAGMA read_uncommitted` is deliberately not set: it only has an effect
+/// on shared-cache connections, and WAL already gives readers a consistent
+/// snapshot without blocking on the writer.
+pub async fn create_read_pool(db_path: &Path) -> Result<ReadPool, sqlx::Error> {
+    let db_url = format!("sqlite:{}", db_path.display());
+
+    let connect_options = SqliteConnectOptions::from_str(&db_url)?
+        .read_only(true)
+        .foreign_keys(true)
+        // Readers only wait while a checkpoint resets the WAL
+        .busy_timeout(Duration::from_secs(5));
+    let connect_options = with_key(connect_options, db_path)?;
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(READER_CONNECTIONS)
+        .min_connections(1)
+        .acquire_timeout(Duration::from_secs(10))
+        .connect_with(connect_options)
+        .await?;
+
+    Ok(ReadPool(pool))
+}
+
+/// Connection usage of one pool, for diagnostics.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolStats {
+    /// Open connections (idle + in use).
+    pub size: u32,
+    pub idle: usize,
+    pub in_use: u32,
+    pub max_connections: u32,
+}
+
+/// Snapshot the connection counts of a pool.
+pub fn pool_stats(pool: &DbPool) -> PoolStats {
+    let size = pool.size();
+    let idle = pool.num_idle();
+    PoolStats {
+        size,
+        idle,
+        in_use: size.saturating_sub(idle as u32),
+        max_connections: pool.options().get_max_connections(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -113,4 +202,34 @@ mod tests {
         let result: (i64,) = sqlx::query_as("SELECT 1").fetch_one(&pool).await.unwrap();
         assert_eq!(result.0, 1);
     }
+
+    #[tokio::test]
+    async fn test_read_pool_sees_writes_and_rejects_its_own() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let writer = create_pool(&db_path).await.unwrap();
+        sqlx::query("CREATE TABLE t (v INTEGER)")
+            .execute(&writer)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO t VALUES (1)")
+            .execute(&writer)
+            .await
+            .unwrap();
+
+        let reader = create_read_pool(&db_path).await.unwrap();
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM t")
+            .fetch_one(&*reader)
+            .await
+            .unwrap();
+        assert_eq!(count, 1);
+        assert!(sqlx::query("INSERT INTO t VALUES (2)")
+            .execute(&*reader)
+            .await
+            .is_err());
+
+        let stats = pool_stats(&writer);
+        assert_eq!(stats.max_connections, WRITER_CONNECTIONS);
+        assert!(stats.size >= 1);
+    }
 }
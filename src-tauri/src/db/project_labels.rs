@@ -0,0 +1,91 @@
+//! Project label cache.
+//!
+//! A project's labels are replaced wholesale on every fetch, so labels
+//! deleted upstream disappear too. `project_label_fetches` remembers when
+//! each project was last fetched.
+
+use crate::db::pool::DbPool;
+
+/// A row from the `project_labels` table.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct ProjectLabelRow {
+    pub name: String,
+    /// Background color, e.g. `#428BCA`.
+    pub color: String,
+    pub text_color: Option<String>,
+    pub description: Option<String>,
+}
+
+/// Replace all cached labels of a project with `labels` and record the fetch.
+pub async fn replace_labels(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+    labels: &[ProjectLabelRow],
+    now: i64,
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM project_labels WHERE instance_id = ? AND project_id = ?")
+        .bind(instance_id)
+        .bind(project_id)
+        .execute(&mut *tx)
+        .await?;
+    for label in labels {
+        sqlx::query(
+            "INSERT OR REPLACE INTO project_labels
+             (instance_id, project_id, name, color, text_color, description)
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(instance_id)
+        .bind(project_id)
+        .bind(&label.name)
+        .bind(&label.color)
+        .bind(&label.text_color)
+        .bind(&label.description)
+        .execute(&mut *tx)
+        .await?;
+    }
+    sqlx::query(
+        "INSERT INTO project_label_fetches (instance_id, project_id, fetched_at)
+         VALUES (?, ?, ?)
+         ON CONFLICT (instance_id, project_id) DO UPDATE SET fetched_at = excluded.fetched_at",
+    )
+    .bind(instance_id)
+    .bind(project_id)
+    .bind(now)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await
+}
+
+/// Cached labels of a project, by name.
+pub async fn list_labels(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<Vec<ProjectLabelRow>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT name, color, text_color, description FROM project_labels
+         WHERE instance_id = ? AND project_id = ?
+         ORDER BY name COLLATE NOCASE",
+    )
+    .bind(instance_id)
+    .bind(project_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// When the project's labels were last fetched, if ever.
+pub async fn fetched_at(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<Option<i64>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT fetched_at FROM project_label_fetches WHERE instance_id = ? AND project_id = ?",
+    )
+    .bind(instance_id)
+    .bind(project_id)
+    .fetch_optional(pool)
+    .await
+}
@@ -0,0 +1,121 @@
+//! Project member cache.
+//!
+//! Like the label cache, a project's members are replaced wholesale on every
+//! fetch and `project_member_fetches` remembers when that happened.
+
+use crate::db::pool::DbPool;
+
+/// A row from the `project_members` table.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct ProjectMemberRow {
+    pub user_id: i64,
+    pub username: String,
+    pub name: String,
+    pub avatar_url: Option<String>,
+}
+
+/// Replace all cached members of a project with `members` and record the
+/// fetch.
+pub async fn replace_members(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+    members: &[ProjectMemberRow],
+    now: i64,
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM project_members WHERE instance_id = ? AND project_id = ?")
+        .bind(instance_id)
+        .bind(project_id)
+        .execute(&mut *tx)
+        .await?;
+    for member in members {
+        sqlx::query(
+            "INSERT OR REPLACE INTO project_members
+             (instance_id, project_id, user_id, username, name, avatar_url)
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(instance_id)
+        .bind(project_id)
+        .bind(member.user_id)
+        .bind(&member.username)
+        .bind(&member.name)
+        .bind(&member.avatar_url)
+        .execute(&mut *tx)
+        .await?;
+    }
+    sqlx::query(
+        "INSERT INTO project_member_fetches (instance_id, project_id, fetched_at)
+         VALUES (?, ?, ?)
+         ON CONFLICT (instance_id, project_id) DO UPDATE SET fetched_at = excluded.fetched_at",
+    )
+    .bind(instance_id)
+    .bind(project_id)
+    .bind(now)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await
+}
+
+/// Cached members of a project whose username or name contains `query`
+/// (case-insensitive), usernames starting with it first.
+pub async fn search_members(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+    query: &str,
+    limit: i64,
+) -> Result<Vec<ProjectMemberRow>, sqlx::Error> {
+    let escaped = query
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    sqlx::query_as(
+        r#"
+        SELECT user_id, username, name, avatar_url FROM project_members
+        WHERE instance_id = ? AND project_id = ?
+          AND (username LIKE '%' || ? || '%' ESCAPE '\'
+               OR name LIKE '%' || ? || '%' ESCAPE '\')
+        ORDER BY username LIKE ? || '%' ESCAPE '\' DESC, username COLLATE NOCASE
+        LIMIT ?
+        "#,
+    )
+    .bind(instance_id)
+    .bind(project_id)
+    .bind(&escaped)
+    .bind(&escaped)
+    .bind(&escaped)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+/// User id of a username on an instance, from any project's member list.
+pub async fn user_id(
+    pool: &DbPool,
+    instance_id: i64,
+    username: &str,
+) -> Result<Option<i64>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT user_id FROM project_members WHERE instance_id = ? AND username = ? LIMIT 1",
+    )
+    .bind(instance_id)
+    .bind(username)
+    .fetch_optional(pool)
+    .await
+}
+
+/// When the project's members were last fetched, if ever.
+pub async fn fetched_at(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<Option<i64>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT fetched_at FROM project_member_fetches WHERE instance_id = ? AND project_id = ?",
+    )
+    .bind(instance_id)
+    .bind(project_id)
+    .fetch_optional(pool)
+    .await
+}
@@ -0,0 +1,108 @@
+//! Project milestone cache.
+//!
+//! Holds the active milestones of each project, replaced wholesale on every
+//! fetch like the label cache.
+
+use crate::db::pool::DbPool;
+
+/// A row from the `project_milestones` table.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct ProjectMilestoneRow {
+    pub milestone_id: i64,
+    pub title: String,
+    /// `YYYY-MM-DD`.
+    pub due_date: Option<String>,
+}
+
+/// Replace all cached milestones of a project with `milestones` and record
+/// the fetch.
+pub async fn replace_milestones(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+    milestones: &[ProjectMilestoneRow],
+    now: i64,
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM project_milestones WHERE instance_id = ? AND project_id = ?")
+        .bind(instance_id)
+        .bind(project_id)
+        .execute(&mut *tx)
+        .await?;
+    for milestone in milestones {
+        sqlx::query(
+            "INSERT OR REPLACE INTO project_milestones
+             (instance_id, project_id, milestone_id, title, due_date)
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(instance_id)
+        .bind(project_id)
+        .bind(milestone.milestone_id)
+        .bind(&milestone.title)
+        .bind(&milestone.due_date)
+        .execute(&mut *tx)
+        .await?;
+    }
+    sqlx::query(
+        "INSERT INTO project_milestone_fetches (instance_id, project_id, fetched_at)
+         VALUES (?, ?, ?)
+         ON CONFLICT (instance_id, project_id) DO UPDATE SET fetched_at = excluded.fetched_at",
+    )
+    .bind(instance_id)
+    .bind(project_id)
+    .bind(now)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await
+}
+
+/// Cached milestones of a project, soonest due first; those without a due
+/// date come last, by title.
+pub async fn list_milestones(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<Vec<ProjectMilestoneRow>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT milestone_id, title, due_date FROM project_milestones
+         WHERE instance_id = ? AND project_id = ?
+         ORDER BY due_date IS NULL, due_date, title COLLATE NOCASE",
+    )
+    .bind(instance_id)
+    .bind(project_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// A cached milestone of a project by id.
+pub async fn get_milestone(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+    milestone_id: i64,
+) -> Result<Option<ProjectMilestoneRow>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT milestone_id, title, due_date FROM project_milestones
+         WHERE instance_id = ? AND project_id = ? AND milestone_id = ?",
+    )
+    .bind(instance_id)
+    .bind(project_id)
+    .bind(milestone_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// When the project's milestones were last fetched, if ever.
+pub async fn fetched_at(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<Option<i64>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT fetched_at FROM project_milestone_fetches WHERE instance_id = ? AND project_id = ?",
+    )
+    .bind(instance_id)
+    .bind(project_id)
+    .fetch_optional(pool)
+    .await
+}
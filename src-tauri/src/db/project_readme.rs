@@ -0,0 +1,62 @@
+//! Project README cache DB helpers.
+
+use crate::db::pool::DbPool;
+
+/// A row from the `project_readme_cache` table.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct CachedReadme {
+    pub ref_name: String,
+    /// Path of the README in the repository; `None` when the project has none.
+    pub file_path: Option<String>,
+    pub html: String,
+    pub fetched_at: i64,
+}
+
+/// Get the cached README for a project at a ref.
+pub async fn get_cached_readme(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+    ref_name: &str,
+) -> Result<Option<CachedReadme>, sqlx::Error> {
+    sqlx::query_as(
+        r#"
+        SELECT ref_name, file_path, html, fetched_at
+        FROM project_readme_cache
+        WHERE instance_id = ? AND project_id = ? AND ref_name = ?
+        "#,
+    )
+    .bind(instance_id)
+    .bind(project_id)
+    .bind(ref_name)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Insert or replace the cached README for a project at a ref.
+pub async fn upsert_readme(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+    readme: &CachedReadme,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO project_readme_cache (instance_id, project_id, ref_name, file_path, html, fetched_at)
+        VALUES (?, ?, ?, ?, ?, ?)
+        ON CONFLICT(instance_id, project_id, ref_name) DO UPDATE SET
+            file_path = excluded.file_path,
+            html = excluded.html,
+            fetched_at = excluded.fetched_at
+        "#,
+    )
+    .bind(instance_id)
+    .bind(project_id)
+    .bind(&readme.ref_name)
+    .bind(&readme.file_path)
+    .bind(&readme.html)
+    .bind(readme.fetched_at)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,54 @@
+//! Local working copies mapped to projects.
+//!
+//! A row in `project_repos` says where the user keeps a clone of a project,
+//! so MR branches can be checked out there (see `services::git`).
+
+use crate::db::pool::DbPool;
+
+/// Path of the working copy of a project, if one is set.
+pub async fn get(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<Option<String>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT repo_path FROM project_repos WHERE instance_id = ? AND project_id = ?",
+    )
+    .bind(instance_id)
+    .bind(project_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Set the working copy of a project, replacing any previous one.
+pub async fn set(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+    repo_path: &str,
+    now: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO project_repos (instance_id, project_id, repo_path, updated_at)
+         VALUES (?, ?, ?, ?)
+         ON CONFLICT (instance_id, project_id)
+         DO UPDATE SET repo_path = excluded.repo_path, updated_at = excluded.updated_at",
+    )
+    .bind(instance_id)
+    .bind(project_id)
+    .bind(repo_path)
+    .bind(now)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Forget the working copy of a project. Returns false when none was set.
+pub async fn remove(pool: &DbPool, instance_id: i64, project_id: i64) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM project_repos WHERE instance_id = ? AND project_id = ?")
+        .bind(instance_id)
+        .bind(project_id)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,147 @@
+//! Release and tag cache for pipeline dashboard projects.
+//!
+//! Both lists are replaced per project on every fetch, so releases or tags
+//! deleted upstream disappear from the cache too.
+
+use crate::db::pool::DbPool;
+
+/// A row from the `release_cache` table. `links` is a JSON array of asset
+/// links.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct CachedRelease {
+    pub project_id: i64,
+    pub tag_name: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub created_at: String,
+    pub released_at: Option<String>,
+    pub upcoming_release: bool,
+    pub author_username: Option<String>,
+    pub commit_sha: Option<String>,
+    pub web_url: Option<String>,
+    pub links: String,
+}
+
+/// A row from the `tag_cache` table.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct CachedTag {
+    pub project_id: i64,
+    pub name: String,
+    pub message: Option<String>,
+    pub commit_sha: Option<String>,
+    pub commit_title: Option<String>,
+    pub committed_at: Option<String>,
+    pub protected: bool,
+    pub has_release: bool,
+}
+
+/// Replace all cached releases of a project with `releases`.
+pub async fn replace_releases(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+    releases: &[CachedRelease],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM release_cache WHERE instance_id = ? AND project_id = ?")
+        .bind(instance_id)
+        .bind(project_id)
+        .execute(&mut *tx)
+        .await?;
+    for r in releases {
+        sqlx::query(
+            "INSERT OR REPLACE INTO release_cache \
+             (instance_id, project_id, tag_name, name, description, created_at, released_at, \
+              upcoming_release, author_username, commit_sha, web_url, links, cached_at) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, datetime('now'))",
+        )
+        .bind(instance_id)
+        .bind(project_id)
+        .bind(&r.tag_name)
+        .bind(&r.name)
+        .bind(&r.description)
+        .bind(&r.created_at)
+        .bind(&r.released_at)
+        .bind(r.upcoming_release)
+        .bind(&r.author_username)
+        .bind(&r.commit_sha)
+        .bind(&r.web_url)
+        .bind(&r.links)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await
+}
+
+/// Load cached releases for a project, newest release first.
+pub async fn get_cached_releases(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<Vec<CachedRelease>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT project_id, tag_name, name, description, created_at, released_at, \
+                upcoming_release, author_username, commit_sha, web_url, links \
+         FROM release_cache \
+         WHERE instance_id = ? AND project_id = ? \
+         ORDER BY COALESCE(released_at, created_at) DESC, tag_name DESC",
+    )
+    .bind(instance_id)
+    .bind(project_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Replace all cached tags of a project with `tags`.
+pub async fn replace_tags(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+    tags: &[CachedTag],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM tag_cache WHERE instance_id = ? AND project_id = ?")
+        .bind(instance_id)
+        .bind(project_id)
+        .execute(&mut *tx)
+        .await?;
+    for t in tags {
+        sqlx::query(
+            "INSERT OR REPLACE INTO tag_cache \
+             (instance_id, project_id, name, message, commit_sha, commit_title, committed_at, \
+              protected, has_release, cached_at) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, datetime('now'))",
+        )
+        .bind(instance_id)
+        .bind(project_id)
+        .bind(&t.name)
+        .bind(&t.message)
+        .bind(&t.commit_sha)
+        .bind(&t.commit_title)
+        .bind(&t.committed_at)
+        .bind(t.protected)
+        .bind(t.has_release)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await
+}
+
+/// Load cached tags for a project, most recent commit first.
+pub async fn get_cached_tags(
+    pool: &DbPool,
+    instance_id: i64,
+    project_id: i64,
+) -> Result<Vec<CachedTag>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT project_id, name, message, commit_sha, commit_title, committed_at, protected, \
+                has_release \
+         FROM tag_cache \
+         WHERE instance_id = ? AND project_id = ? \
+         ORDER BY committed_at IS NULL, committed_at DESC, name DESC",
+    )
+    .bind(instance_id)
+    .bind(project_id)
+    .fetch_all(pool)
+    .await
+}
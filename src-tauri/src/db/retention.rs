@@ -0,0 +1,200 @@
+//! Retention policy DB helpers.
+//!
+//! `retention_policies` holds one optional row per instance; the purge
+//! helpers here delete cached data that a policy no longer allows.
+
+use crate::db::pool::DbPool;
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, FromRow)]
+pub struct RetentionPolicyRow {
+    pub instance_id: i64,
+    pub cache_file_content: bool,
+    pub comment_retention_days: Option<i64>,
+    pub closed_mr_retention_hours: i64,
+}
+
+/// One category of cached data for an instance, as reported by the audit.
+#[derive(Debug, Clone, FromRow)]
+pub struct DataCategoryRow {
+    pub category: String,
+    pub row_count: i64,
+    /// Approximate stored size in bytes, where it is meaningful.
+    pub size_bytes: Option<i64>,
+    /// Unix timestamp of the oldest item, where items carry one.
+    pub oldest_at: Option<i64>,
+}
+
+/// Load the retention policy configured for an instance, if any.
+pub async fn get_policy(
+    pool: &DbPool,
+    instance_id: i64,
+) -> Result<Option<RetentionPolicyRow>, sqlx::Error> {
+    sqlx::query_as::<_, RetentionPolicyRow>(
+        "SELECT instance_id, cache_file_content, comment_retention_days, \
+         closed_mr_retention_hours \
+         FROM retention_policies WHERE instance_id = ?",
+    )
+    .bind(instance_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Insert or replace an instance's retention policy.
+pub async fn upsert_policy(pool: &DbPool, policy: &RetentionPolicyRow) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO retention_policies \
+         (instance_id, cache_file_content, comment_retention_days, closed_mr_retention_hours) \
+         VALUES (?, ?, ?, ?) \
+         ON CONFLICT(instance_id) DO UPDATE SET \
+         cache_file_content = excluded.cache_file_content, \
+         comment_retention_days = excluded.comment_retention_days, \
+         closed_mr_retention_hours = excluded.closed_mr_retention_hours",
+    )
+    .bind(policy.instance_id)
+    .bind(policy.cache_file_content)
+    .bind(policy.comment_retention_days)
+    .bind(policy.closed_mr_retention_hours)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Delete every cached file version for an instance, matched by its own
+/// instance column or by its MR. Blobs that become unreferenced are left for
+/// `file_cache::delete_orphaned_blobs`.
+pub async fn delete_file_versions_for_instance(
+    pool: &DbPool,
+    instance_id: i64,
+) -> Result<u64, sqlx::Error> {
+    // file_versions.instance_id is stored as TEXT.
+    let result = sqlx::query(
+        "DELETE FROM file_versions WHERE instance_id = CAST(?1 AS TEXT) \
+         OR mr_id IN (SELECT id FROM merge_requests WHERE instance_id = ?1)",
+    )
+    .bind(instance_id)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Delete the repository and CI content cached for an instance besides file
+/// versions: blame, commit diffs, READMEs and job logs.
+pub async fn delete_content_caches_for_instance(
+    pool: &DbPool,
+    instance_id: i64,
+) -> Result<u64, sqlx::Error> {
+    let mut removed = 0;
+    for table in [
+        "file_blame",
+        "commit_diffs",
+        "project_readme_cache",
+        "job_trace_cache",
+    ] {
+        removed += sqlx::query(&format!("DELETE FROM {} WHERE instance_id = ?", table))
+            .bind(instance_id)
+            .execute(pool)
+            .await?
+            .rows_affected();
+    }
+    Ok(removed)
+}
+
+/// Delete markdown rendered from an instance's descriptions and comments.
+pub async fn delete_rendered_markdown_for_instance(
+    pool: &DbPool,
+    instance_id: i64,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM markdown_cache WHERE instance_id = ?")
+        .bind(instance_id)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected())
+}
+
+/// Delete synced MR comments and issue notes created before `cutoff`.
+/// Local (not yet synced) comments are never touched.
+pub async fn delete_comments_before(
+    pool: &DbPool,
+    instance_id: i64,
+    cutoff: i64,
+) -> Result<u64, sqlx::Error> {
+    let comments = sqlx::query(
+        "DELETE FROM comments WHERE is_local = 0 AND created_at < ? \
+         AND mr_id IN (SELECT id FROM merge_requests WHERE instance_id = ?)",
+    )
+    .bind(cutoff)
+    .bind(instance_id)
+    .execute(pool)
+    .await?;
+    let notes = sqlx::query("DELETE FROM issue_notes WHERE instance_id = ? AND created_at < ?")
+        .bind(instance_id)
+        .bind(cutoff)
+        .execute(pool)
+        .await?;
+    Ok(comments.rows_affected() + notes.rows_affected())
+}
+
+/// Count the cached data held for an instance, per category.
+pub async fn data_inventory(
+    pool: &DbPool,
+    instance_id: i64,
+) -> Result<Vec<DataCategoryRow>, sqlx::Error> {
+    sqlx::query_as::<_, DataCategoryRow>(
+        r#"
+        SELECT 'merge_requests' AS category, COUNT(*) AS row_count,
+               SUM(LENGTH(CAST(COALESCE(description, '') AS BLOB))) AS size_bytes,
+               MIN(created_at) AS oldest_at
+        FROM merge_requests WHERE instance_id = ?1
+        UNION ALL
+        SELECT 'diffs', COUNT(*), SUM(LENGTH(CAST(COALESCE(df.diff_content, '') AS BLOB))), NULL
+        FROM diff_files df JOIN merge_requests m ON m.id = df.mr_id
+        WHERE m.instance_id = ?1
+        UNION ALL
+        SELECT 'file_content', COUNT(*),
+               (SELECT SUM(size_bytes) FROM file_blobs WHERE sha IN
+                   (SELECT sha FROM file_versions WHERE instance_id = CAST(?1 AS TEXT)
+                    OR mr_id IN (SELECT id FROM merge_requests WHERE instance_id = ?1))),
+               NULL
+        FROM file_versions WHERE instance_id = CAST(?1 AS TEXT)
+            OR mr_id IN (SELECT id FROM merge_requests WHERE instance_id = ?1)
+        UNION ALL
+        SELECT 'file_blame', COUNT(*), SUM(LENGTH(CAST(ranges AS BLOB))), MIN(fetched_at)
+        FROM file_blame WHERE instance_id = ?1
+        UNION ALL
+        SELECT 'commit_diffs', COUNT(*), SUM(LENGTH(CAST(files AS BLOB))), MIN(fetched_at)
+        FROM commit_diffs WHERE instance_id = ?1
+        UNION ALL
+        SELECT 'readmes', COUNT(*), SUM(LENGTH(CAST(html AS BLOB))), MIN(fetched_at)
+        FROM project_readme_cache WHERE instance_id = ?1
+        UNION ALL
+        SELECT 'job_traces', COUNT(*), SUM(LENGTH(CAST(trace AS BLOB))), MIN(fetched_at)
+        FROM job_trace_cache WHERE instance_id = ?1
+        UNION ALL
+        SELECT 'rendered_markdown', COUNT(*), SUM(LENGTH(CAST(html AS BLOB))), MIN(used_at)
+        FROM markdown_cache WHERE instance_id = ?1
+        UNION ALL
+        SELECT 'comments', COUNT(*), SUM(LENGTH(CAST(c.body AS BLOB))), MIN(c.created_at)
+        FROM comments c JOIN merge_requests m ON m.id = c.mr_id
+        WHERE m.instance_id = ?1
+        UNION ALL
+        SELECT 'issues', COUNT(*), SUM(LENGTH(CAST(COALESCE(description, '') AS BLOB))),
+               MIN(created_at)
+        FROM issues WHERE instance_id = ?1
+        UNION ALL
+        SELECT 'issue_notes', COUNT(*), SUM(LENGTH(CAST(body AS BLOB))), MIN(created_at)
+        FROM issue_notes WHERE instance_id = ?1
+        UNION ALL
+        SELECT 'avatars', COUNT(*), SUM(LENGTH(avatar_data)), NULL
+        FROM user_avatars WHERE instance_id = ?1
+        UNION ALL
+        SELECT 'pending_actions', COUNT(*), SUM(LENGTH(CAST(q.payload AS BLOB))),
+               MIN(q.created_at)
+        FROM sync_queue q JOIN merge_requests m ON m.id = q.mr_id
+        WHERE m.instance_id = ?1 AND q.status != 'synced'
+        "#,
+    )
+    .bind(instance_id)
+    .fetch_all(pool)
+    .await
+}
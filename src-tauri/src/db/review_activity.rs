@@ -0,0 +1,70 @@
+//! Review activity DB helpers.
+//!
+//! Backs the opt-in review stats: `review_stats_opt_in` records whether (and
+//! since when) tracking is on, `review_activity` logs the events.
+
+use crate::db::pool::DbPool;
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, FromRow)]
+pub struct ReviewEventRow {
+    pub instance_id: i64,
+    pub project_id: i64,
+    pub mr_iid: i64,
+    pub kind: String,
+    pub occurred_at: i64,
+}
+
+/// When tracking was turned on, or `None` while it is off.
+pub async fn enabled_at(pool: &DbPool) -> Result<Option<i64>, sqlx::Error> {
+    sqlx::query_scalar("SELECT enabled_at FROM review_stats_opt_in WHERE id = 1")
+        .fetch_optional(pool)
+        .await
+}
+
+/// Turn tracking on (keeping the original start time) or off. Turning it off
+/// deletes everything recorded so far.
+pub async fn set_enabled(pool: &DbPool, enabled: bool, now: i64) -> Result<(), sqlx::Error> {
+    if enabled {
+        sqlx::query("INSERT OR IGNORE INTO review_stats_opt_in (id, enabled_at) VALUES (1, ?)")
+            .bind(now)
+            .execute(pool)
+            .await?;
+    } else {
+        let mut tx = pool.begin().await?;
+        sqlx::query("DELETE FROM review_stats_opt_in")
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM review_activity")
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+    }
+    Ok(())
+}
+
+/// Append an event. Repeated review requests for the same MR are ignored.
+pub async fn insert_event(pool: &DbPool, event: &ReviewEventRow) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT OR IGNORE INTO review_activity \
+         (instance_id, project_id, mr_iid, kind, occurred_at) VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(event.instance_id)
+    .bind(event.project_id)
+    .bind(event.mr_iid)
+    .bind(&event.kind)
+    .bind(event.occurred_at)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Every recorded event, oldest first.
+pub async fn list_events(pool: &DbPool) -> Result<Vec<ReviewEventRow>, sqlx::Error> {
+    sqlx::query_as::<_, ReviewEventRow>(
+        "SELECT instance_id, project_id, mr_iid, kind, occurred_at \
+         FROM review_activity ORDER BY occurred_at, id",
+    )
+    .fetch_all(pool)
+    .await
+}
@@ -0,0 +1,197 @@
+//! Pending review DB helpers.
+//!
+//! A pending review (`review_drafts`, at most one per MR) collects draft
+//! comments in `review_draft_comments`. Nothing here touches `comments` or
+//! the sync queue; that happens when the review is submitted.
+
+use crate::db::pool::DbPool;
+use serde::Serialize;
+use sqlx::FromRow;
+
+/// A comment waiting in a pending review; inline when `file_path` is set.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct DraftComment {
+    pub id: i64,
+    pub body: String,
+    pub file_path: Option<String>,
+    pub old_line: Option<i64>,
+    pub new_line: Option<i64>,
+    pub created_at: i64,
+}
+
+/// A pending review with its drafts, oldest first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingReview {
+    pub id: i64,
+    pub mr_id: i64,
+    pub started_at: i64,
+    pub comments: Vec<DraftComment>,
+}
+
+/// A draft to add; lines are already resolved.
+#[derive(Debug, Clone)]
+pub struct NewDraftComment<'a> {
+    pub body: &'a str,
+    pub file_path: Option<&'a str>,
+    pub old_line: Option<i64>,
+    pub new_line: Option<i64>,
+}
+
+/// Start a pending review for an MR, or return the one already started.
+pub async fn start_review(pool: &DbPool, mr_id: i64, now: i64) -> Result<i64, sqlx::Error> {
+    sqlx::query("INSERT INTO review_drafts (mr_id, started_at) VALUES (?, ?) ON CONFLICT (mr_id) DO NOTHING")
+        .bind(mr_id)
+        .bind(now)
+        .execute(pool)
+        .await?;
+    sqlx::query_scalar("SELECT id FROM review_drafts WHERE mr_id = ?")
+        .bind(mr_id)
+        .fetch_one(pool)
+        .await
+}
+
+pub async fn get_review(pool: &DbPool, mr_id: i64) -> Result<Option<PendingReview>, sqlx::Error> {
+    let Some((id, started_at)): Option<(i64, i64)> =
+        sqlx::query_as("SELECT id, started_at FROM review_drafts WHERE mr_id = ?")
+            .bind(mr_id)
+            .fetch_optional(pool)
+            .await?
+    else {
+        return Ok(None);
+    };
+    let comments = sqlx::query_as::<_, DraftComment>(
+        "SELECT id, body, file_path, old_line, new_line, created_at
+         FROM review_draft_comments WHERE review_id = ? ORDER BY id",
+    )
+    .bind(id)
+    .fetch_all(pool)
+    .await?;
+    Ok(Some(PendingReview {
+        id,
+        mr_id,
+        started_at,
+        comments,
+    }))
+}
+
+pub async fn add_comment(
+    pool: &DbPool,
+    review_id: i64,
+    draft: &NewDraftComment<'_>,
+    now: i64,
+) -> Result<DraftComment, sqlx::Error> {
+    sqlx::query_as::<_, DraftComment>(
+        "INSERT INTO review_draft_comments
+         (review_id, body, file_path, old_line, new_line, created_at)
+         VALUES (?, ?, ?, ?, ?, ?)
+         RETURNING id, body, file_path, old_line, new_line, created_at",
+    )
+    .bind(review_id)
+    .bind(draft.body)
+    .bind(draft.file_path)
+    .bind(draft.old_line)
+    .bind(draft.new_line)
+    .bind(now)
+    .fetch_one(pool)
+    .await
+}
+
+/// Delete one draft of an MR's pending review. Returns false when there
+/// was no such draft.
+pub async fn delete_comment(pool: &DbPool, mr_id: i64, draft_id: i64) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "DELETE FROM review_draft_comments WHERE id = ?
+         AND review_id = (SELECT id FROM review_drafts WHERE mr_id = ?)",
+    )
+    .bind(draft_id)
+    .bind(mr_id)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Drop an MR's pending review and its drafts. Takes any executor so
+/// submission can do it in the transaction that queues the review.
+pub async fn delete_review<'e>(
+    executor: impl sqlx::SqliteExecutor<'e>,
+    mr_id: i64,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM review_drafts WHERE mr_id = ?")
+        .bind(mr_id)
+        .execute(executor)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db;
+
+    async fn setup() -> (tempfile::TempDir, DbPool) {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = db::initialize(&dir.path().join("test.db")).await.unwrap();
+        sqlx::query("INSERT INTO gitlab_instances (id, url, name) VALUES (1, 'https://gitlab.com', 'GitLab')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        for id in [1, 2] {
+            sqlx::query(
+                "INSERT INTO merge_requests
+                 (id, instance_id, iid, project_id, title, author_username, source_branch,
+                  target_branch, state, web_url, created_at, updated_at)
+                 VALUES (?, 1, ?, 100, 't', 'alice', 'f', 'main', 'opened', 'w', 0, 0)",
+            )
+            .bind(id)
+            .bind(id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        (dir, pool)
+    }
+
+    fn draft(body: &str) -> NewDraftComment<'_> {
+        NewDraftComment {
+            body,
+            file_path: None,
+            old_line: None,
+            new_line: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_review_collects_drafts_until_deleted() {
+        let (_dir, pool) = setup().await;
+        assert_eq!(get_review(&pool, 1).await.unwrap(), None);
+
+        let review = start_review(&pool, 1, 10).await.unwrap();
+        assert_eq!(start_review(&pool, 1, 20).await.unwrap(), review);
+        let first = add_comment(&pool, review, &draft("one"), 11).await.unwrap();
+        add_comment(&pool, review, &draft("two"), 12).await.unwrap();
+
+        let pending = get_review(&pool, 1).await.unwrap().unwrap();
+        assert_eq!(pending.started_at, 10);
+        let bodies: Vec<_> = pending.comments.iter().map(|c| c.body.as_str()).collect();
+        assert_eq!(bodies, vec!["one", "two"]);
+
+        // A draft only goes away through its own MR.
+        assert!(!delete_comment(&pool, 2, first.id).await.unwrap());
+        assert!(delete_comment(&pool, 1, first.id).await.unwrap());
+        assert_eq!(
+            get_review(&pool, 1).await.unwrap().unwrap().comments.len(),
+            1
+        );
+
+        assert!(delete_review(&pool, 1).await.unwrap());
+        assert!(!delete_review(&pool, 1).await.unwrap());
+        assert_eq!(get_review(&pool, 1).await.unwrap(), None);
+        let left: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM review_draft_comments")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(left, 0);
+    }
+}
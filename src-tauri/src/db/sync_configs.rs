@@ -0,0 +1,138 @@
+//! Per-instance sync configuration DB helpers.
+//!
+//! `sync_configs` holds one optional row per instance. Without a row the
+//! instance syncs every scope at the global interval and MR cap.
+
+use crate::db::pool::DbPool;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Sync configuration of one instance. `None` limits fall back to the
+/// global `SyncConfig`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceSyncConfig {
+    pub instance_id: i64,
+    /// Fetch MRs the user authored.
+    pub sync_authored: bool,
+    /// Fetch MRs the user is asked to review.
+    pub sync_reviewing: bool,
+    /// Fetch MRs assigned to the user.
+    pub sync_assigned: bool,
+    pub interval_secs: Option<i64>,
+    pub max_mrs_per_sync: Option<i64>,
+}
+
+impl InstanceSyncConfig {
+    pub fn default_for(instance_id: i64) -> Self {
+        Self {
+            instance_id,
+            sync_authored: true,
+            sync_reviewing: true,
+            sync_assigned: true,
+            interval_secs: None,
+            max_mrs_per_sync: None,
+        }
+    }
+}
+
+/// Load an instance's sync configuration, falling back to the defaults.
+pub async fn get_config(
+    pool: &DbPool,
+    instance_id: i64,
+) -> Result<InstanceSyncConfig, sqlx::Error> {
+    Ok(sqlx::query_as::<_, InstanceSyncConfig>(
+        "SELECT instance_id, sync_authored, sync_reviewing, sync_assigned, \
+         interval_secs, max_mrs_per_sync \
+         FROM sync_configs WHERE instance_id = ?",
+    )
+    .bind(instance_id)
+    .fetch_optional(pool)
+    .await?
+    .unwrap_or_else(|| InstanceSyncConfig::default_for(instance_id)))
+}
+
+/// Insert or replace an instance's sync configuration.
+pub async fn upsert_config(pool: &DbPool, config: &InstanceSyncConfig) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO sync_configs \
+         (instance_id, sync_authored, sync_reviewing, sync_assigned, interval_secs, max_mrs_per_sync) \
+         VALUES (?, ?, ?, ?, ?, ?) \
+         ON CONFLICT(instance_id) DO UPDATE SET \
+         sync_authored = excluded.sync_authored, \
+         sync_reviewing = excluded.sync_reviewing, \
+         sync_assigned = excluded.sync_assigned, \
+         interval_secs = excluded.interval_secs, \
+         max_mrs_per_sync = excluded.max_mrs_per_sync",
+    )
+    .bind(config.instance_id)
+    .bind(config.sync_authored)
+    .bind(config.sync_reviewing)
+    .bind(config.sync_assigned)
+    .bind(config.interval_secs)
+    .bind(config.max_mrs_per_sync)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Shortest interval any instance overrides the global one with.
+pub async fn min_interval_secs(pool: &DbPool) -> Result<Option<i64>, sqlx::Error> {
+    sqlx::query_scalar("SELECT MIN(interval_secs) FROM sync_configs")
+        .fetch_one(pool)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db;
+
+    #[tokio::test]
+    async fn test_config_defaults_until_saved() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = db::initialize(&dir.path().join("test.db")).await.unwrap();
+        for (id, url) in [(1, "https://gitlab.com"), (2, "https://git.example.com")] {
+            sqlx::query("INSERT INTO gitlab_instances (id, url, name) VALUES (?, ?, 'GitLab')")
+                .bind(id)
+                .bind(url)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(
+            get_config(&pool, 1).await.unwrap(),
+            InstanceSyncConfig::default_for(1)
+        );
+        assert_eq!(min_interval_secs(&pool).await.unwrap(), None);
+
+        let reviewing_only = InstanceSyncConfig {
+            sync_authored: false,
+            sync_assigned: false,
+            interval_secs: Some(900),
+            max_mrs_per_sync: Some(20),
+            ..InstanceSyncConfig::default_for(1)
+        };
+        upsert_config(&pool, &reviewing_only).await.unwrap();
+        upsert_config(
+            &pool,
+            &InstanceSyncConfig {
+                interval_secs: Some(120),
+                ..InstanceSyncConfig::default_for(2)
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(get_config(&pool, 1).await.unwrap(), reviewing_only);
+        assert_eq!(min_interval_secs(&pool).await.unwrap(), Some(120));
+
+        // Removing the instance drops its config.
+        sqlx::query("DELETE FROM gitlab_instances WHERE id = 2")
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert_eq!(min_interval_secs(&pool).await.unwrap(), Some(900));
+    }
+}
@@ -0,0 +1,99 @@
+//! Incremental sync cursor DB helpers.
+//!
+//! `sync_cursors` holds one row per instance once its MR list has been
+//! fetched completely. Deleting the row makes the next sync a full one.
+
+use crate::db::pool::DbPool;
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromRow)]
+pub struct SyncCursor {
+    /// Start of the last complete MR list fetch (full or incremental).
+    pub last_synced_at: i64,
+    /// Start of the last complete full MR list fetch.
+    pub last_full_sync_at: i64,
+}
+
+pub async fn get_cursor(
+    pool: &DbPool,
+    instance_id: i64,
+) -> Result<Option<SyncCursor>, sqlx::Error> {
+    sqlx::query_as::<_, SyncCursor>(
+        "SELECT last_synced_at, last_full_sync_at FROM sync_cursors WHERE instance_id = ?",
+    )
+    .bind(instance_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Record a complete MR list fetch that started at `synced_at`.
+pub async fn advance(
+    pool: &DbPool,
+    instance_id: i64,
+    synced_at: i64,
+    full: bool,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO sync_cursors (instance_id, last_synced_at, last_full_sync_at)
+         VALUES (?, ?, ?)
+         ON CONFLICT (instance_id) DO UPDATE SET
+             last_synced_at = excluded.last_synced_at,
+             last_full_sync_at = CASE WHEN ? THEN excluded.last_full_sync_at
+                                      ELSE sync_cursors.last_full_sync_at END",
+    )
+    .bind(instance_id)
+    .bind(synced_at)
+    .bind(synced_at)
+    .bind(full)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Forget an instance's cursor so its next sync lists every open MR.
+pub async fn reset(pool: &DbPool, instance_id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM sync_cursors WHERE instance_id = ?")
+        .bind(instance_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db;
+
+    #[tokio::test]
+    async fn test_incremental_fetches_keep_full_sync_time() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = db::initialize(&dir.path().join("test.db")).await.unwrap();
+        sqlx::query("INSERT INTO gitlab_instances (id, url, name) VALUES (1, 'https://gitlab.com', 'GitLab')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert_eq!(get_cursor(&pool, 1).await.unwrap(), None);
+
+        advance(&pool, 1, 100, true).await.unwrap();
+        advance(&pool, 1, 400, false).await.unwrap();
+        assert_eq!(
+            get_cursor(&pool, 1).await.unwrap(),
+            Some(SyncCursor {
+                last_synced_at: 400,
+                last_full_sync_at: 100,
+            })
+        );
+
+        advance(&pool, 1, 700, true).await.unwrap();
+        assert_eq!(
+            get_cursor(&pool, 1)
+                .await
+                .unwrap()
+                .map(|c| c.last_full_sync_at),
+            Some(700)
+        );
+
+        reset(&pool, 1).await.unwrap();
+        assert_eq!(get_cursor(&pool, 1).await.unwrap(), None);
+    }
+}
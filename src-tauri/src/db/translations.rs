@@ -0,0 +1,64 @@
+//! Comment translation cache.
+//!
+//! One row per comment and target language. A row only counts as a hit while
+//! its `source_text` still matches the comment body being translated.
+
+use crate::db::pool::DbPool;
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct CachedTranslation {
+    pub translated_text: String,
+    pub detected_lang: Option<String>,
+}
+
+/// Cached translation of `source_text` for a comment, if still current.
+pub async fn get_translation(
+    pool: &DbPool,
+    comment_id: i64,
+    target_lang: &str,
+    source_text: &str,
+) -> Result<Option<CachedTranslation>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT translated_text, detected_lang FROM comment_translations \
+         WHERE comment_id = ? AND target_lang = ? AND source_text = ?",
+    )
+    .bind(comment_id)
+    .bind(target_lang)
+    .bind(source_text)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Store (or replace) the translation of a comment into `target_lang`.
+pub async fn save_translation(
+    pool: &DbPool,
+    comment_id: i64,
+    target_lang: &str,
+    source_text: &str,
+    translation: &CachedTranslation,
+    now: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT OR REPLACE INTO comment_translations \
+         (comment_id, target_lang, source_text, translated_text, detected_lang, translated_at) \
+         VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(comment_id)
+    .bind(target_lang)
+    .bind(source_text)
+    .bind(&translation.translated_text)
+    .bind(&translation.detected_lang)
+    .bind(now)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Drop every cached translation. Returns the number of rows removed.
+pub async fn clear_translations(pool: &DbPool) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM comment_translations")
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected())
+}
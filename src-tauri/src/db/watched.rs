@@ -0,0 +1,49 @@
+//! Watched-MR DB helpers.
+//!
+//! A watched MR is a `merge_requests` row with `watched = 1`. The flag is the
+//! only record of the watch: the sync engine lists watched rows per instance
+//! and fetches each one directly, since none of the authored / reviewing /
+//! assigned scope queries would return them.
+
+use crate::db::pool::DbPool;
+use sqlx::FromRow;
+
+/// The identifiers the sync engine needs to fetch a watched MR from GitLab.
+#[derive(Debug, Clone, FromRow)]
+pub struct WatchedMrRow {
+    pub id: i64,
+    pub project_id: i64,
+    pub iid: i64,
+}
+
+/// Set or clear the watched flag. Returns false when no such MR is cached.
+pub async fn set_watched(pool: &DbPool, mr_id: i64, watched: bool) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("UPDATE merge_requests SET watched = ? WHERE id = ?")
+        .bind(watched)
+        .bind(mr_id)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// List the open watched MRs for an instance.
+pub async fn list_open_watched(
+    pool: &DbPool,
+    instance_id: i64,
+) -> Result<Vec<WatchedMrRow>, sqlx::Error> {
+    sqlx::query_as::<_, WatchedMrRow>(
+        "SELECT id, project_id, iid FROM merge_requests \
+         WHERE instance_id = ? AND watched = 1 AND state = 'opened'",
+    )
+    .bind(instance_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Ids of every watched MR for an instance, regardless of state.
+pub async fn watched_ids(pool: &DbPool, instance_id: i64) -> Result<Vec<i64>, sqlx::Error> {
+    sqlx::query_scalar("SELECT id FROM merge_requests WHERE instance_id = ? AND watched = 1")
+        .bind(instance_id)
+        .fetch_all(pool)
+        .await
+}
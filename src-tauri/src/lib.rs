@@ -10,47 +10,72 @@ pub mod error;
 pub mod models;
 pub mod services;
 
+#[cfg(feature = "memory-profiling")]
+#[global_allocator]
+static GLOBAL_ALLOCATOR: services::memory_profile::TrackingAllocator =
+    services::memory_profile::TrackingAllocator;
+
 use commands::{
     cli_status, download_and_install_cli,
-    add_comment, approve_mr, cancel_pipeline, cancel_pipeline_job, check_merge_status,
+    open_link, register_link_handler, unregister_link_handler,
+    add_comment, upload_comment_attachment, approve_mr, cancel_pipeline, cancel_pipeline_job, check_merge_status, get_merge_blockers,
     claim_auto_merge, clear_test_data, get_auto_merge_claim, process_auto_merge_now,
-    unclaim_auto_merge,
+    unclaim_auto_merge, merge_when_ready, cancel_merge_when_ready,
     claim_auto_run, list_auto_run_claims, unclaim_auto_run,
-    delete_comment, delete_gitlab_instance, discard_failed_action, generate_test_data, get_action_counts,
-    get_approval_status, get_avatar, get_avatars, get_cache_stats, get_cached_file_pair,
-    get_collapse_patterns, get_comments, get_companion_qr_svg, get_companion_settings,
-    get_companion_status, get_diagnostics_report, get_diff_content, get_diff_file,
-    get_diff_file_metadata, get_diff_files, get_diff_hunks, get_diff_refs, get_file_comments,
-    get_file_content, get_file_content_base64, get_gitattributes, get_gitlab_instances,
-    get_cached_pipeline_statuses, get_job_trace, get_memory_stats, get_merge_request_detail, get_merge_requests, get_mr_pipelines, list_system_fonts,
-    get_mr_reviewers, get_notification_settings, get_pipeline_jobs, get_pipeline_statuses,
-    get_project_pipelines, get_settings, get_sync_config, get_sync_settings, get_sync_status,
-    add_issue_note, get_cached_issue_detail, get_token_info,
+    delete_comment, ping_author, get_author_pings, get_my_comment_history, delete_gitlab_instance, discard_failed_action, generate_test_data, get_action_counts, list_failed_actions, get_orphaned_actions,
+    get_approval_gate, get_approval_status, get_review_blockers, get_review_checklist, set_review_checklist_item,
+    update_approval_gate, get_avatar, get_avatars, get_cache_stats, get_cached_file_pair,
+    confirm_pairing, get_collapse_patterns, get_comments, get_latest_comment_cursor, mark_mr_viewed, get_companion_qr_svg, get_companion_settings,
+    get_companion_status, get_companion_url, get_diagnostics_report, get_diff_content, get_diff_file,
+    get_accessible_diff, get_diff_file_metadata, get_diff_files, get_diff_hunks, get_diff_refs,
+    get_file_comments,
+    get_file_blame, get_commit_diff, get_mr_activity, get_mr_language_breakdown, get_mr_commits, mark_mr_reviewed, get_changes_since_review, mark_file_viewed, get_file_review_states, get_file_content, get_file_content_base64, get_gitattributes, get_gitlab_instances, get_project_readme,
+    get_cached_pipeline_statuses, get_job_trace, get_job_trace_range, get_instance_mode, get_memory_breakdown, get_memory_stats, get_pool_stats, get_command_metrics, record_command_timings, get_merge_request_detail, get_merge_requests, get_mr_pipelines, list_system_fonts,
+    get_mr_participants, get_mr_reviewers, request_rereview, search_project_members, set_mr_assignees, set_mr_reviewers, checkout_mr_branch, get_mr_repo_path, open_in_editor, get_mr_tasks, toggle_mr_task, get_project_labels, add_mr_label, remove_mr_label, get_project_milestones, set_mr_milestone, lint_mr_description, get_notification_settings, get_pipeline_graph, get_pipeline_jobs, get_pipeline_statuses,
+    get_changes_since, get_mr_delta, get_project_pipelines, get_settings, get_sync_config, get_sync_settings, get_sync_status,
+    add_issue_note, get_cached_issue_detail, get_instance_quota, get_token_info,
     list_cached_issue_notes, list_cached_issues,
     list_issue_assignee_candidates, list_issue_projects, list_my_merge_requests,
     refresh_issue_detail, set_issue_assignees, set_issue_description, set_issue_state,
-    list_pipeline_projects, merge_mr, play_pipeline_job,
+    list_pipeline_projects, merge_mr, get_pending_manual_jobs, play_jobs, play_pipeline_job,
+    get_cached_pipeline_schedules, list_pipeline_schedules, run_schedule_now,
+    toggle_schedule_active, get_cached_project_releases, get_cached_project_tags,
+    list_project_releases, list_project_tags,
     resolve_mr_by_web_url, fetch_mr_by_web_url,
-    rebase_mr, refresh_avatars, refresh_gitattributes, regenerate_companion_pin, rename_instance,
-    undraft_mr,
-    rename_project, set_companion_pin,
-    remove_pipeline_project, reorder_pinned_pipeline_projects, reply_to_comment, resolve_discussion, resolve_project_by_path, retry_failed_actions,
+    rebase_mr, run_post_merge_tasks, get_semantic_summary, get_side_by_side_diff, get_security_findings, get_quick_switch_index, refresh_avatars, refresh_gitattributes, regenerate_companion_pin, rename_instance,
+    undraft_mr, unwatch_mr, watch_mr, list_quick_actions, preview_quick_actions,
+    rename_project, reject_pairing, set_companion_pin,
+    remove_pipeline_project, reorder_pinned_pipeline_projects, reply_to_comment, resolve_discussion, submit_review_bundle, import_review_annotations, get_review_bundle_status, start_review, get_pending_review, add_draft_comment, delete_draft_comment, discard_review, submit_review, assign_files_to_reviewer, get_file_assignments, apply_suggestion, export_discussion, resolve_project_by_path, retry_failed_actions, run_sync_selftest,
     retry_pipeline_job, revoke_companion_device, search_projects,
     send_native_notification,
-    set_default_instance, setup_gitlab_instance, start_companion_server_cmd, stop_companion_server_cmd,
+    set_default_instance, set_pipeline_manual_gate_notify, set_pipeline_notify_rule, setup_gitlab_instance, import_instances_from_config, start_companion_server_cmd, stop_companion_server_cmd,
     sync_my_issues, sync_project_issues,
-    toggle_issue_star, toggle_pin_pipeline_project, toggle_project_star, trigger_sync, unapprove_mr,
+    toggle_issue_star, toggle_pin_pipeline_project, toggle_project_star, trigger_full_resync, trigger_sync, unapprove_mr,
     update_collapse_patterns,
     update_companion_settings, update_custom_theme_colors, update_diffs_font,
     update_display_font,
-    update_instance_token, update_keyboard_shortcuts, update_mr_list_condensed,
-    update_notification_settings, update_session_cookie, update_settings,
+    update_instance_headers, update_instance_tls, update_instance_token, update_keyboard_shortcuts, update_mr_list_condensed,
+    update_notification_settings, mute_project, unmute_project, list_muted_projects,
+    update_session_cookie, update_settings,
     update_show_draft_mrs, update_show_recently_merged_mrs, update_sync_config,
+    update_allow_api_writes, update_editor_command, set_offline_mode, update_translation_settings, translate_text, render_gfm, update_review_assistant_settings, suggest_comment, summarize_mr, gitlab_api_request,
+    get_database_encryption_status, update_database_encryption, export_database_key,
+    restore_database_key,
+    compare_mrs,
     update_sync_settings, update_theme, update_ui_font, visit_pipeline_project,
+    list_highlight_themes, set_highlight_theme, get_list_view_state, update_list_view_state,
+    audit_cached_data, get_retention_policy, update_retention_policy,
+    export_cache_archive, import_cache_archive,
+    delete_automation_rule, list_automation_rules, list_automation_runs,
+    save_automation_rule, test_automation, list_badge_rules, update_badge_rules,
+    export_review_stats, export_statistics, get_review_sla_status, get_review_streaks,
+    set_review_stats_enabled,
+    get_background_jobs, report_user_activity,
 };
 use services::companion_server;
-use std::sync::Arc;
-use services::sync_engine::{SyncConfig, SyncEngine};
+use services::instance_lock::{self, InstanceLock, InstanceMode, LockOutcome};
+use std::sync::{Arc, Mutex};
+use services::sync_engine::{SyncConfig, SyncEngine, SyncHandle};
 use services::sync_events::TauriEmitter;
 use tauri::{
     Manager, TitleBarStyle, WebviewUrl, WebviewWindowBuilder,
@@ -80,7 +105,29 @@ pub fn run() {
     tauri::async_runtime::set(rt.handle().clone());
     let _rt_guard = rt.enter();
 
-    tauri::Builder::default()
+    let secondary_requested = instance_lock::secondary_requested(std::env::args());
+
+    // `--mock-gitlab` / `--record-gitlab=DIR`: every GitLab client replays
+    // or records fixtures.
+    #[cfg(feature = "gitlab-fixtures")]
+    services::gitlab_fixtures::set_mode(
+        services::gitlab_fixtures::mode_from_args(std::env::args()),
+    );
+
+    let mut builder = tauri::Builder::default();
+    if !secondary_requested {
+        // Must be registered first: a second launch in the same session exits
+        // here and focuses the running window instead.
+        builder = builder.plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.show();
+                let _ = window.unminimize();
+                let _ = window.set_focus();
+            }
+        }));
+    }
+
+    builder
         .plugin(
             tauri_plugin_log::Builder::new()
                 .targets([
@@ -111,6 +158,35 @@ pub fn run() {
             let db_path = db::get_db_path(&app_data_dir);
 
             log::info!("Database path: {}", db_path.display());
+            #[cfg(feature = "gitlab-fixtures")]
+            if let Some(fixtures) = services::gitlab_fixtures::mode() {
+                log::warn!("GitLab fixtures active: {:?}", fixtures);
+            }
+
+            // Only one process may own the database. A process started from
+            // another session (which the single-instance plugin can't see)
+            // falls back to read-only secondary mode.
+            let mut lock = None;
+            let mode = if secondary_requested {
+                InstanceMode::Secondary
+            } else {
+                std::fs::create_dir_all(&app_data_dir)?;
+                match instance_lock::acquire(&app_data_dir)? {
+                    LockOutcome::Acquired(held) => {
+                        lock = Some(held);
+                        InstanceMode::Primary
+                    }
+                    LockOutcome::HeldBy(pid) => {
+                        log::warn!(
+                            "Database is in use by process {}; starting read-only",
+                            pid.map_or_else(|| "?".to_string(), |pid| pid.to_string())
+                        );
+                        InstanceMode::Secondary
+                    }
+                }
+            };
+            app.manage(Mutex::new(lock));
+            app.manage(mode);
 
             // Async initialization via spawn + channel.
             // Cannot use tauri::async_runtime::block_on here because the main thread
@@ -130,23 +206,92 @@ pub fn run() {
                 sync_config.interval_secs, sync_config.max_mrs_per_sync
             );
 
+            // Encryption is applied to the file before any pool opens it
+            let encrypt_database: bool = app_handle
+                .store("settings.json")
+                .ok()
+                .and_then(|store| store.get("encrypt_database"))
+                .and_then(|v| serde_json::from_value(v.clone()).ok())
+                .unwrap_or(false);
+
+            // Restore offline mode before the sync engine's first run
+            let offline_mode: bool = app_handle
+                .store("settings.json")
+                .ok()
+                .and_then(|store| store.get("offline_mode"))
+                .and_then(|v| serde_json::from_value(v.clone()).ok())
+                .unwrap_or(false);
+            services::offline_mode::set_offline(offline_mode);
+
             let (init_tx, init_rx) = std::sync::mpsc::sync_channel(1);
             tauri::async_runtime::spawn(async move {
+                if mode == InstanceMode::Secondary {
+                    // No migrations, writes or syncing: the primary process
+                    // owns the database.
+                    let read_pool = match db::pool::create_read_pool(&db_path).await {
+                        Ok(read_pool) => read_pool,
+                        Err(e) => {
+                            let _ = init_tx.send(Err(format!(
+                                "Secondary mode needs an existing database; start the app normally first ({})",
+                                e
+                            )));
+                            return;
+                        }
+                    };
+                    let pool = (*read_pool).clone();
+                    // Clients built here still need the instances' custom
+                    // headers and TLS settings
+                    if let Err(e) = services::instance_headers::load_all(&pool).await {
+                        log::error!("Failed to load custom instance headers: {}", e);
+                    }
+                    if let Err(e) = services::instance_tls::load_all(&pool).await {
+                        log::error!("Failed to load instance TLS settings: {}", e);
+                    }
+                    let _ = init_tx.send(Ok((pool, read_pool, SyncHandle::detached(sync_config))));
+                    log::info!("[sync] Secondary instance: background sync disabled");
+                    return;
+                }
+
+                match db::encryption::reconcile(&db_path, encrypt_database).await {
+                    Ok(db::encryption::ReconcileOutcome::Unchanged) => {}
+                    Ok(db::encryption::ReconcileOutcome::LockedOut) => log::warn!(
+                        "Database key missing or wrong; moved the encrypted database aside and starting with an empty cache"
+                    ),
+                    Ok(outcome) => log::info!("Database encryption: {:?}", outcome),
+                    Err(e) => log::error!("Failed to apply database encryption setting: {}", e),
+                }
+
                 let pool = db::initialize(&db_path)
                     .await
                     .expect("Failed to initialize database");
+                // Opened after migrations so the file exists and is in WAL mode
+                let read_pool = db::pool::create_read_pool(&db_path)
+                    .await
+                    .expect("Failed to open read pool");
+                // Custom instance headers and TLS settings must be registered
+                // before any client is built
+                if let Err(e) = services::instance_headers::load_all(&pool).await {
+                    log::error!("Failed to load custom instance headers: {}", e);
+                }
+                if let Err(e) = services::instance_tls::load_all(&pool).await {
+                    log::error!("Failed to load instance TLS settings: {}", e);
+                }
 
                 // Start background sync engine (needs active Tokio runtime for tokio::spawn)
                 let sync_handle =
                     SyncEngine::start_background(pool.clone(), sync_config, Arc::new(TauriEmitter(app_handle)));
                 log::info!("[sync] Background sync engine started");
 
-                let _ = init_tx.send((pool, sync_handle));
+                // Idle-time precomputation; paused while syncs run
+                services::background_jobs::start(pool.clone());
+
+                let _ = init_tx.send(Ok((pool, read_pool, sync_handle)));
             });
-            let (pool, sync_handle) = init_rx.recv().expect("Failed to initialize app");
+            let (pool, read_pool, sync_handle) = init_rx.recv().expect("Failed to initialize app")?;
 
             // Store state for use in commands
             app.manage(pool.clone());
+            app.manage(read_pool);
             app.manage(sync_handle.clone());
 
             // Auto-start companion server if enabled in settings
@@ -160,8 +305,9 @@ pub fn run() {
                     .and_then(|v| serde_json::from_value(v.clone()).ok())
                     .unwrap_or_default();
 
-                if companion_settings.enabled {
-                    let port = companion_settings.port;
+                if companion_settings.enabled && mode == InstanceMode::Primary {
+                    let config =
+                        companion_server::CompanionListenConfig::from(&companion_settings);
                     let pool_clone = pool.clone();
                     let sync_clone = sync_handle.clone();
                     let app_handle_clone = app.handle().clone();
@@ -180,7 +326,7 @@ pub fn run() {
                     if let Some(dist_path) = frontend_dist {
                         tauri::async_runtime::spawn(async move {
                             match companion_server::start_companion_server(
-                                port,
+                                config,
                                 dist_path,
                                 pool_clone,
                                 sync_clone,
@@ -188,7 +334,7 @@ pub fn run() {
                             )
                             .await
                             {
-                                Ok(()) => log::info!("[companion] Auto-started on port {}", port),
+                                Ok(()) => log::info!("[companion] Auto-started"),
                                 Err(e) => log::error!("[companion] Auto-start failed: {}", e),
                             }
                         });
@@ -199,8 +345,12 @@ pub fn run() {
             }
 
             // Create window with transparent titlebar
+            let title = match mode {
+                InstanceMode::Primary => "Ultra Gitlab",
+                InstanceMode::Secondary => "Ultra Gitlab (read-only)",
+            };
             let win = WebviewWindowBuilder::new(app, "main", WebviewUrl::default())
-                .title("Ultra Gitlab")
+                .title(title)
                 .inner_size(800.0, 600.0)
                 .hidden_title(true)
                 .title_bar_style(TitleBarStyle::Transparent)
@@ -276,6 +426,7 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             greet,
             setup_gitlab_instance,
+            import_instances_from_config,
             get_gitlab_instances,
             delete_gitlab_instance,
             rename_instance,
@@ -287,31 +438,83 @@ pub fn run() {
             get_diff_file,
             get_diff_file_metadata,
             get_diff_hunks,
+            get_accessible_diff,
             get_diff_refs,
+            get_file_blame,
+            get_mr_commits,
+            get_mr_activity,
+            get_mr_language_breakdown,
+            get_commit_diff,
+            mark_mr_reviewed,
+            get_changes_since_review,
+            mark_file_viewed,
+            get_file_review_states,
             get_file_content,
             get_file_content_base64,
             get_cached_file_pair,
+            get_semantic_summary,
+            get_side_by_side_diff,
+            get_security_findings,
+            get_quick_switch_index,
             get_comments,
+            get_latest_comment_cursor,
+            mark_mr_viewed,
             get_file_comments,
             add_comment,
+            upload_comment_attachment,
             reply_to_comment,
             resolve_discussion,
+            submit_review_bundle,
+            import_review_annotations,
+            start_review,
+            get_pending_review,
+            add_draft_comment,
+            delete_draft_comment,
+            discard_review,
+            submit_review,
+            assign_files_to_reviewer,
+            get_file_assignments,
+            get_review_bundle_status,
+            apply_suggestion,
+            export_discussion,
             delete_comment,
+            list_quick_actions,
+            preview_quick_actions,
+            ping_author,
+            get_author_pings,
+            get_my_comment_history,
             approve_mr,
             unapprove_mr,
             get_approval_status,
+            get_approval_gate,
+            update_approval_gate,
+            get_review_checklist,
+            get_review_blockers,
+            set_review_checklist_item,
             get_action_counts,
+            list_failed_actions,
+            get_orphaned_actions,
             trigger_sync,
+            trigger_full_resync,
             get_sync_status,
+            get_changes_since,
+            get_mr_delta,
+            gitlab_api_request,
             retry_failed_actions,
             discard_failed_action,
             get_sync_config,
             update_sync_config,
+            run_sync_selftest,
             get_settings,
             update_settings,
             update_mr_list_condensed,
             update_show_draft_mrs,
             update_show_recently_merged_mrs,
+            update_allow_api_writes,
+            update_editor_command,
+            set_offline_mode,
+            update_translation_settings,
+            update_review_assistant_settings,
             update_keyboard_shortcuts,
             get_sync_settings,
             update_sync_settings,
@@ -320,14 +523,24 @@ pub fn run() {
             // Gitattributes cache
             get_gitattributes,
             refresh_gitattributes,
+            // Project README context panel
+            get_project_readme,
             // Diagnostics (memory and performance verification)
             get_memory_stats,
+            get_memory_breakdown,
+            get_pool_stats,
+            get_instance_mode,
+            record_command_timings,
+            get_command_metrics,
             get_cache_stats,
             get_diagnostics_report,
             generate_test_data,
             clear_test_data,
             get_token_info,
+            get_instance_quota,
             update_instance_token,
+            update_instance_headers,
+            update_instance_tls,
             set_default_instance,
             // Deep-link
             resolve_mr_by_web_url,
@@ -335,23 +548,55 @@ pub fn run() {
             // Merge
             merge_mr,
             check_merge_status,
+            get_merge_blockers,
             rebase_mr,
+            run_post_merge_tasks,
             undraft_mr,
             get_mr_pipelines,
+            // MR comparison
+            compare_mrs,
             // Auto-merge
             claim_auto_merge,
             unclaim_auto_merge,
             get_auto_merge_claim,
             process_auto_merge_now,
+            merge_when_ready,
+            cancel_merge_when_ready,
             // Auto-run
             claim_auto_run,
             unclaim_auto_run,
             list_auto_run_claims,
+            // Watch list
+            watch_mr,
+            unwatch_mr,
+            // Local working copy
+            get_mr_repo_path,
+            checkout_mr_branch,
+            open_in_editor,
             // Reviewers
             get_mr_reviewers,
+            get_mr_participants,
+            request_rereview,
+            set_mr_reviewers,
+            set_mr_assignees,
+            search_project_members,
+            // Description task lists
+            get_mr_tasks,
+            toggle_mr_task,
+            lint_mr_description,
+            // Labels
+            get_project_labels,
+            add_mr_label,
+            remove_mr_label,
+            // Milestones
+            get_project_milestones,
+            set_mr_milestone,
             // Notifications
             get_notification_settings,
             update_notification_settings,
+            mute_project,
+            unmute_project,
+            list_muted_projects,
             send_native_notification,
             // Issues
             sync_my_issues,
@@ -373,6 +618,8 @@ pub fn run() {
             list_pipeline_projects,
             visit_pipeline_project,
             toggle_pin_pipeline_project,
+            set_pipeline_notify_rule,
+            set_pipeline_manual_gate_notify,
             remove_pipeline_project,
             reorder_pinned_pipeline_projects,
             search_projects,
@@ -380,11 +627,23 @@ pub fn run() {
             get_cached_pipeline_statuses,
             get_project_pipelines,
             get_pipeline_jobs,
+            get_pipeline_graph,
             get_job_trace,
+            get_job_trace_range,
             play_pipeline_job,
+            get_pending_manual_jobs,
+            play_jobs,
             retry_pipeline_job,
             cancel_pipeline_job,
             cancel_pipeline,
+            list_pipeline_schedules,
+            get_cached_pipeline_schedules,
+            run_schedule_now,
+            toggle_schedule_active,
+            list_project_releases,
+            get_cached_project_releases,
+            list_project_tags,
+            get_cached_project_tags,
             resolve_project_by_path,
             // Theme & Font
             list_system_fonts,
@@ -393,14 +652,21 @@ pub fn run() {
             update_display_font,
             update_diffs_font,
             update_custom_theme_colors,
+            list_highlight_themes,
+            set_highlight_theme,
+            get_list_view_state,
+            update_list_view_state,
             // Companion server
             get_companion_settings,
             get_companion_qr_svg,
+            get_companion_url,
             get_companion_status,
             update_companion_settings,
             regenerate_companion_pin,
             set_companion_pin,
             revoke_companion_device,
+            confirm_pairing,
+            reject_pairing,
             start_companion_server_cmd,
             stop_companion_server_cmd,
             // Avatars
@@ -408,9 +674,50 @@ pub fn run() {
             get_avatars,
             update_session_cookie,
             refresh_avatars,
+            // Data retention
+            get_retention_policy,
+            update_retention_policy,
+            audit_cached_data,
+            // Cache archives
+            export_cache_archive,
+            import_cache_archive,
+            // Automations
+            list_automation_rules,
+            save_automation_rule,
+            delete_automation_rule,
+            test_automation,
+            list_automation_runs,
+            // MR list badge rules
+            list_badge_rules,
+            update_badge_rules,
+            // Translation
+            translate_text,
+            // Markdown
+            render_gfm,
+            // Review assistant
+            suggest_comment,
+            summarize_mr,
+            // Database encryption
+            get_database_encryption_status,
+            update_database_encryption,
+            export_database_key,
+            restore_database_key,
+            // Review stats
+            get_review_streaks,
+            set_review_stats_enabled,
+            export_review_stats,
+            get_review_sla_status,
+            export_statistics,
+            // Background jobs
+            get_background_jobs,
+            report_user_activity,
             // CLI installer
             cli_status,
             download_and_install_cli,
+            // OS link handler
+            register_link_handler,
+            unregister_link_handler,
+            open_link,
         ])
         .on_window_event(|window, event| {
             #[cfg(target_os = "macos")]
@@ -433,6 +740,12 @@ pub fn run() {
                     }
                 }
                 tauri::RunEvent::Exit => {
+                    // Release the database lock (removes the lock file).
+                    if let Some(lock) = app_handle.try_state::<Mutex<Option<InstanceLock>>>() {
+                        if let Ok(mut lock) = lock.lock() {
+                            lock.take();
+                        }
+                    }
                     let _ = app_handle.track_event("app_exited", None);
                     app_handle.flush_events_blocking();
                 }
@@ -3,6 +3,71 @@
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+/// Default length, in characters, of the description preview stored for
+/// list views. Overridable via `SyncConfig::description_preview_chars`.
+pub const DEFAULT_DESCRIPTION_PREVIEW_CHARS: usize = 500;
+
+/// Build the list-view preview of an MR description.
+///
+/// Descriptions within `max_chars` are returned unchanged. Longer ones are cut
+/// at the last whitespace inside the limit (unless that would drop more than
+/// half of it) and get a trailing ellipsis.
+pub fn description_preview(description: Option<&str>, max_chars: usize) -> Option<String> {
+    let description = description?;
+    let Some((cut, _)) = description.char_indices().nth(max_chars) else {
+        return Some(description.to_string());
+    };
+
+    let head = &description[..cut];
+    let head = match head.rfind(char::is_whitespace) {
+        Some(idx) if idx >= cut / 2 => &head[..idx],
+        _ => head,
+    };
+    Some(format!("{}…", head.trim_end()))
+}
+
+/// Username patterns that mark an MR author as a bot when GitLab doesn't flag
+/// the account itself. Overridable via `SyncConfig::bot_author_patterns`.
+pub const DEFAULT_BOT_AUTHOR_PATTERNS: &[&str] = &[
+    "dependabot*",
+    "renovate*",
+    "*[bot]",
+    "project_*_bot*",
+    "group_*_bot*",
+];
+
+/// Whether an MR author is a bot: either GitLab says so (`bot` on the user
+/// payload) or the username matches one of `patterns`.
+///
+/// Patterns are case-insensitive and support `*` as a wildcard for any run
+/// of characters; everything else matches literally.
+pub fn is_bot_author(username: &str, flagged_by_api: bool, patterns: &[String]) -> bool {
+    flagged_by_api
+        || patterns
+            .iter()
+            .any(|p| wildcard_match(&p.to_lowercase(), &username.to_lowercase()))
+}
+
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else {
+        // No wildcard at all.
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(idx) => rest = &rest[idx + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
 /// State of a merge request.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -132,6 +197,49 @@ pub struct MergeRequest {
     /// Used to retain merged/closed MRs for 24h before hard-purge.
     #[sqlx(default)]
     pub state_changed_at: Option<i64>,
+
+    /// Whether the user explicitly watches this MR (synced and notified even
+    /// when they are neither author, assignee nor reviewer).
+    #[sqlx(default)]
+    pub watched: bool,
+
+    /// Whether the author is a bot account (Dependabot, Renovate, project
+    /// access tokens, ...). Bot MRs are hidden from lists unless requested.
+    #[sqlx(default)]
+    pub is_bot_author: bool,
+
+    /// When the author re-requested the current user's review (Unix). Set
+    /// until the user reviews again.
+    #[sqlx(default)]
+    pub review_rerequested_at: Option<i64>,
+
+    /// Task list items (`- [ ]`) in the description.
+    #[sqlx(default)]
+    pub task_count: i64,
+
+    /// Checked task list items in the description.
+    #[sqlx(default)]
+    pub task_completed_count: i64,
+
+    /// Whether the discussion is locked (only project members can comment).
+    #[sqlx(default)]
+    pub discussion_locked: bool,
+
+    /// Whether the head moved on since the user last marked the MR reviewed.
+    #[sqlx(default)]
+    pub changed_since_review: bool,
+
+    /// GitLab ID of the MR's milestone.
+    #[sqlx(default)]
+    pub milestone_id: Option<i64>,
+
+    /// Title of the MR's milestone.
+    #[sqlx(default)]
+    pub milestone_title: Option<String>,
+
+    /// Due date of the MR's milestone (`YYYY-MM-DD`).
+    #[sqlx(default)]
+    pub milestone_due_date: Option<String>,
 }
 
 impl MergeRequest {
@@ -183,6 +291,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_description_preview_keeps_short_text() {
+        assert_eq!(
+            description_preview(Some("short"), 10).as_deref(),
+            Some("short")
+        );
+        assert_eq!(description_preview(None, 10), None);
+    }
+
+    #[test]
+    fn test_description_preview_cuts_at_word_boundary() {
+        let preview = description_preview(Some("release notes for version two"), 20).unwrap();
+        assert_eq!(preview, "release notes for…");
+    }
+
+    #[test]
+    fn test_description_preview_respects_char_boundaries() {
+        // Multi-byte characters must not be split mid-codepoint.
+        let preview = description_preview(Some("ééééééééééé"), 4).unwrap();
+        assert_eq!(preview, "éééé…");
+    }
+
+    #[test]
+    fn test_is_bot_author_patterns() {
+        let patterns: Vec<String> = DEFAULT_BOT_AUTHOR_PATTERNS
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        assert!(is_bot_author("Dependabot", false, &patterns));
+        assert!(is_bot_author("renovate-bot", false, &patterns));
+        assert!(is_bot_author("project_42_bot_8f3a", false, &patterns));
+        assert!(is_bot_author("github-actions[bot]", false, &patterns));
+        assert!(!is_bot_author("robert", false, &patterns));
+        assert!(!is_bot_author("project_lead", false, &patterns));
+        assert!(is_bot_author("robert", true, &patterns));
+
+        let exact = vec!["ci".to_string()];
+        assert!(is_bot_author("CI", false, &exact));
+        assert!(!is_bot_author("cicd", false, &exact));
+    }
+
     #[test]
     fn test_state_display() {
         assert_eq!(MergeRequestState::Opened.to_string(), "opened");
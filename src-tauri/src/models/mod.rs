@@ -21,7 +21,10 @@ pub use comment::{Comment, LineType, NewComment};
 pub use diff::{ChangeType, Diff, DiffFile};
 pub use gitlab_instance::{GitLabInstance, NewGitLabInstance};
 pub use issue::{Issue, IssueState, UpsertIssue};
-pub use merge_request::{ApprovalStatus, MergeRequest, MergeRequestState};
+pub use merge_request::{
+    description_preview, ApprovalStatus, MergeRequest, MergeRequestState,
+    DEFAULT_DESCRIPTION_PREVIEW_CHARS,
+};
 pub use mr_reviewer::MrReviewer;
 pub use notification_settings::NotificationSettings;
 pub use pipeline_project::PipelineProject;
@@ -12,4 +12,11 @@ pub struct MrReviewer {
     /// Status: "approved", "pending", or "changes_requested".
     pub status: String,
     pub cached_at: i64,
+    /// GitLab review state (`unreviewed`, `reviewed`, `requested_changes`, ...),
+    /// when the instance reports it.
+    #[sqlx(default)]
+    pub review_state: Option<String>,
+    /// When a finished review was re-requested from this reviewer (Unix).
+    #[sqlx(default)]
+    pub rerequested_at: Option<i64>,
 }
@@ -22,6 +22,14 @@ pub struct PipelineProject {
     /// Custom sort order.
     pub sort_order: Option<i64>,
 
+    /// When a pinned project's pipelines notify: `failures`, `completion`
+    /// or `default_branch`.
+    pub notify_rule: String,
+
+    /// Whether a pinned project notifies about new manual jobs waiting in
+    /// pipelines of its default branch.
+    pub notify_manual_gates: bool,
+
     /// Project name (from projects table).
     pub name: String,
 
@@ -52,7 +60,7 @@ pub async fn list_pipeline_projects(
     sqlx::query_as::<_, PipelineProject>(
         r#"
         SELECT pp.project_id, pp.instance_id, pp.pinned, pp.last_visited_at, pp.sort_order,
-               p.name, p.name_with_namespace, p.path_with_namespace, p.web_url,
+               pp.notify_rule, pp.notify_manual_gates, p.name, p.name_with_namespace, p.path_with_namespace, p.web_url,
                p.starred, p.custom_name
         FROM pipeline_projects pp
         JOIN projects p ON p.id = pp.project_id AND p.instance_id = pp.instance_id
@@ -151,3 +159,63 @@ pub async fn remove_pipeline_project(
 
     Ok(())
 }
+
+/// Set the notification rule for a pipeline project. Returns the number of
+/// rows updated (0 when the project isn't on the dashboard).
+pub async fn set_notify_rule(
+    pool: &sqlx::SqlitePool,
+    project_id: i64,
+    instance_id: i64,
+    notify_rule: &str,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        "UPDATE pipeline_projects SET notify_rule = ? WHERE project_id = ? AND instance_id = ?",
+    )
+    .bind(notify_rule)
+    .bind(project_id)
+    .bind(instance_id)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Turn manual-gate notifications of a pipeline project on or off. Returns
+/// the number of rows updated (0 when the project isn't on the dashboard).
+pub async fn set_notify_manual_gates(
+    pool: &sqlx::SqlitePool,
+    project_id: i64,
+    instance_id: i64,
+    enabled: bool,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        "UPDATE pipeline_projects SET notify_manual_gates = ? WHERE project_id = ? AND instance_id = ?",
+    )
+    .bind(enabled)
+    .bind(project_id)
+    .bind(instance_id)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Cache a project's default branch for the `default_branch` notify rule
+/// and manual-gate notifications.
+pub async fn set_default_branch(
+    pool: &sqlx::SqlitePool,
+    project_id: i64,
+    instance_id: i64,
+    default_branch: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE pipeline_projects SET default_branch = ? WHERE project_id = ? AND instance_id = ?",
+    )
+    .bind(default_branch)
+    .bind(project_id)
+    .bind(instance_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
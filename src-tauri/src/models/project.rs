@@ -139,6 +139,31 @@ pub async fn get_missing_project_ids(
     pool: &sqlx::SqlitePool,
     instance_id: i64,
     project_ids: &[i64],
+) -> Result<Vec<i64>, sqlx::Error> {
+    uncached_project_ids(pool, instance_id, project_ids, "").await
+}
+
+/// Like `get_missing_project_ids`, but also returns cached projects whose
+/// access level for the current user hasn't been fetched yet.
+pub async fn get_project_ids_without_access_level(
+    pool: &sqlx::SqlitePool,
+    instance_id: i64,
+    project_ids: &[i64],
+) -> Result<Vec<i64>, sqlx::Error> {
+    uncached_project_ids(
+        pool,
+        instance_id,
+        project_ids,
+        "AND access_level IS NOT NULL",
+    )
+    .await
+}
+
+async fn uncached_project_ids(
+    pool: &sqlx::SqlitePool,
+    instance_id: i64,
+    project_ids: &[i64],
+    condition: &str,
 ) -> Result<Vec<i64>, sqlx::Error> {
     if project_ids.is_empty() {
         return Ok(Vec::new());
@@ -151,8 +176,8 @@ pub async fn get_missing_project_ids(
         .collect::<Vec<_>>()
         .join(",");
     let query = format!(
-        "SELECT id FROM projects WHERE instance_id = ? AND id IN ({})",
-        placeholders
+        "SELECT id FROM projects WHERE instance_id = ? {} AND id IN ({})",
+        condition, placeholders
     );
 
     let mut q = sqlx::query_as::<_, (i64,)>(&query).bind(instance_id);
@@ -170,6 +195,30 @@ pub async fn get_missing_project_ids(
         .collect())
 }
 
+/// Store the current user's access level on a project (0 when not a member).
+pub async fn set_access_level(
+    pool: &sqlx::SqlitePool,
+    instance_id: i64,
+    project_id: i64,
+    access_level: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE projects SET access_level = ? WHERE instance_id = ? AND id = ?")
+        .bind(access_level)
+        .bind(instance_id)
+        .bind(project_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Drop all stored access levels so the next sync fetches them again.
+pub async fn forget_access_levels(pool: &sqlx::SqlitePool) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE projects SET access_level = NULL")
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -267,6 +316,16 @@ mod tests {
             .await
             .unwrap();
         assert_eq!(missing, vec![20, 30]);
+
+        let unknown = get_project_ids_without_access_level(&pool, 1, &[10, 20])
+            .await
+            .unwrap();
+        assert_eq!(unknown, vec![10, 20]);
+        set_access_level(&pool, 1, 10, 0).await.unwrap();
+        let unknown = get_project_ids_without_access_level(&pool, 1, &[10, 20])
+            .await
+            .unwrap();
+        assert_eq!(unknown, vec![20]);
     }
 
     #[tokio::test]
@@ -13,6 +13,11 @@ pub enum ActionType {
     Resolve,
     Unresolve,
     DeleteComment,
+    ToggleTask,
+    UpdateLabels,
+    SetReviewers,
+    SetAssignees,
+    SetMilestone,
 }
 
 impl From<&str> for ActionType {
@@ -24,6 +29,11 @@ impl From<&str> for ActionType {
             "resolve" => Self::Resolve,
             "unresolve" => Self::Unresolve,
             "deletecomment" => Self::DeleteComment,
+            "toggletask" => Self::ToggleTask,
+            "updatelabels" => Self::UpdateLabels,
+            "setreviewers" => Self::SetReviewers,
+            "setassignees" => Self::SetAssignees,
+            "setmilestone" => Self::SetMilestone,
             _ => Self::Comment, // Default fallback
         }
     }
@@ -38,6 +48,11 @@ impl std::fmt::Display for ActionType {
             Self::Resolve => write!(f, "resolve"),
             Self::Unresolve => write!(f, "unresolve"),
             Self::DeleteComment => write!(f, "deletecomment"),
+            Self::ToggleTask => write!(f, "toggletask"),
+            Self::UpdateLabels => write!(f, "updatelabels"),
+            Self::SetReviewers => write!(f, "setreviewers"),
+            Self::SetAssignees => write!(f, "setassignees"),
+            Self::SetMilestone => write!(f, "setmilestone"),
         }
     }
 }
@@ -88,7 +103,9 @@ pub struct SyncAction {
     /// Target MR ID.
     pub mr_id: i64,
 
-    /// Type of action: `approve`, `comment`, `reply`, `resolve`, `unresolve`.
+    /// Type of action: `approve`, `comment`, `reply`, `resolve`, `unresolve`,
+    /// `deletecomment`, `toggletask`, `updatelabels`, `setreviewers`,
+    /// `setassignees`, `setmilestone`.
     pub action_type: String,
 
     /// JSON payload for GitLab API.
@@ -111,6 +128,11 @@ pub struct SyncAction {
 
     /// When successfully synced (Unix, optional).
     pub synced_at: Option<i64>,
+
+    /// Shared by actions submitted together; they are pushed in order.
+    #[sqlx(default)]
+    #[serde(default)]
+    pub batch_id: Option<String>,
 }
 
 impl SyncAction {
@@ -209,6 +231,10 @@ mod tests {
         assert_eq!(ActionType::from("reply"), ActionType::Reply);
         assert_eq!(ActionType::from("resolve"), ActionType::Resolve);
         assert_eq!(ActionType::from("unresolve"), ActionType::Unresolve);
+        assert_eq!(ActionType::from("updatelabels"), ActionType::UpdateLabels);
+        assert_eq!(ActionType::from("setreviewers"), ActionType::SetReviewers);
+        assert_eq!(ActionType::from("setassignees"), ActionType::SetAssignees);
+        assert_eq!(ActionType::from("setmilestone"), ActionType::SetMilestone);
     }
 
     #[test]
@@ -242,6 +268,7 @@ mod tests {
             last_error: None,
             created_at: 0,
             synced_at: None,
+            batch_id: None,
         };
 
         assert!(action.can_retry());
@@ -267,6 +294,7 @@ mod tests {
             last_error: None,
             created_at: 0,
             synced_at: None,
+            batch_id: None,
         };
 
         assert!(action.is_pending());
@@ -0,0 +1,277 @@
+//! Linear, labeled rendering of a file diff for screen readers.
+//!
+//! A visual diff relies on color, gutters and column position to say what
+//! changed. Here every hunk opens with a sentence ("Change 2 of 3, lines 40
+//! to 44: 3 lines added, 1 line removed.") and every line carries its role
+//! and number in text ("added line 41"), so the frontend can render a plain
+//! list that reads well one item at a time.
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AccessibleLineKind {
+    Added,
+    Removed,
+    Unchanged,
+}
+
+/// One diff line with its spoken label.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessibleLine {
+    pub kind: AccessibleLineKind,
+    /// New-file line number, or old-file line number for removed lines.
+    pub line_number: i64,
+    /// "added line 12", "removed line 9", "unchanged line 13".
+    pub label: String,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessibleHunk {
+    /// Sentence announcing where the change is and how big it is.
+    pub summary: String,
+    pub lines: Vec<AccessibleLine>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessibleDiff {
+    pub file_path: String,
+    /// Sentence describing the whole file change.
+    pub summary: String,
+    pub hunks: Vec<AccessibleHunk>,
+}
+
+fn count(n: usize, singular: &str, plural: &str) -> String {
+    if n == 1 {
+        format!("1 {singular}")
+    } else {
+        format!("{n} {plural}")
+    }
+}
+
+fn line_range(start: i64, len: i64) -> String {
+    if len <= 1 {
+        format!("line {start}")
+    } else {
+        format!("lines {start} to {}", start + len - 1)
+    }
+}
+
+fn changed_counts(added: usize, removed: usize) -> String {
+    match (added, removed) {
+        (0, 0) => "no lines changed".to_string(),
+        (a, 0) => format!("{} added", count(a, "line", "lines")),
+        (0, r) => format!("{} removed", count(r, "line", "lines")),
+        (a, r) => format!(
+            "{} added, {} removed",
+            count(a, "line", "lines"),
+            count(r, "line", "lines")
+        ),
+    }
+}
+
+/// Start and length of each side from `@@ -a,b +c,d @@`.
+fn parse_header(line: &str) -> Option<(i64, i64, i64, i64)> {
+    let mut parts = line.strip_prefix("@@ ")?.split_whitespace();
+    let side = |s: &str| -> Option<(i64, i64)> {
+        match s.split_once(',') {
+            Some((start, len)) => Some((start.parse().ok()?, len.parse().ok()?)),
+            None => Some((s.parse().ok()?, 1)),
+        }
+    };
+    let (old_start, old_len) = side(parts.next()?.strip_prefix('-')?)?;
+    let (new_start, new_len) = side(parts.next()?.strip_prefix('+')?)?;
+    Some((old_start, old_len, new_start, new_len))
+}
+
+struct RawHunk {
+    old_start: i64,
+    old_len: i64,
+    new_start: i64,
+    new_len: i64,
+    lines: Vec<AccessibleLine>,
+}
+
+fn parse_hunks(diff: &str) -> Vec<RawHunk> {
+    let mut hunks: Vec<RawHunk> = Vec::new();
+    let (mut old_line, mut new_line) = (0i64, 0i64);
+    for line in diff.lines() {
+        if line.starts_with("@@") {
+            if let Some((old_start, old_len, new_start, new_len)) = parse_header(line) {
+                old_line = old_start;
+                new_line = new_start;
+                hunks.push(RawHunk {
+                    old_start,
+                    old_len,
+                    new_start,
+                    new_len,
+                    lines: Vec::new(),
+                });
+            }
+            continue;
+        }
+        let Some(hunk) = hunks.last_mut() else {
+            continue;
+        };
+        let (kind, number, content) = if let Some(c) = line.strip_prefix('+') {
+            new_line += 1;
+            (AccessibleLineKind::Added, new_line - 1, c)
+        } else if let Some(c) = line.strip_prefix('-') {
+            old_line += 1;
+            (AccessibleLineKind::Removed, old_line - 1, c)
+        } else if let Some(c) = line.strip_prefix(' ') {
+            old_line += 1;
+            new_line += 1;
+            (AccessibleLineKind::Unchanged, new_line - 1, c)
+        } else {
+            // "\ No newline at end of file" and stray lines.
+            continue;
+        };
+        let verb = match kind {
+            AccessibleLineKind::Added => "added",
+            AccessibleLineKind::Removed => "removed",
+            AccessibleLineKind::Unchanged => "unchanged",
+        };
+        hunk.lines.push(AccessibleLine {
+            kind,
+            line_number: number,
+            label: format!("{verb} line {number}"),
+            content: content.to_string(),
+        });
+    }
+    hunks
+}
+
+fn tally(lines: &[AccessibleLine]) -> (usize, usize) {
+    let added = lines
+        .iter()
+        .filter(|l| l.kind == AccessibleLineKind::Added)
+        .count();
+    let removed = lines
+        .iter()
+        .filter(|l| l.kind == AccessibleLineKind::Removed)
+        .count();
+    (added, removed)
+}
+
+/// Build the accessible form of one file's unified diff. `change_type` is
+/// the `diff_files` value (`added`, `deleted`, `renamed`, `modified`).
+pub fn build(
+    file_path: &str,
+    old_path: Option<&str>,
+    change_type: &str,
+    diff: &str,
+) -> AccessibleDiff {
+    let raw = parse_hunks(diff);
+    let total = raw.len();
+
+    let mut file_added = 0;
+    let mut file_removed = 0;
+    let hunks: Vec<AccessibleHunk> = raw
+        .into_iter()
+        .enumerate()
+        .map(|(i, h)| {
+            let (added, removed) = tally(&h.lines);
+            file_added += added;
+            file_removed += removed;
+            let location = if h.new_len == 0 {
+                format!("old {}", line_range(h.old_start, h.old_len))
+            } else {
+                line_range(h.new_start, h.new_len)
+            };
+            AccessibleHunk {
+                summary: format!(
+                    "Change {} of {}, {}: {}.",
+                    i + 1,
+                    total,
+                    location,
+                    changed_counts(added, removed)
+                ),
+                lines: h.lines,
+            }
+        })
+        .collect();
+
+    let what = match (change_type, old_path) {
+        ("added", _) => "new file".to_string(),
+        ("deleted", _) => "deleted file".to_string(),
+        ("renamed", Some(old)) if old != file_path => format!("renamed from {old}"),
+        _ => "modified".to_string(),
+    };
+    let summary = if total == 0 {
+        format!("{file_path}, {what}, with no text changes.")
+    } else {
+        format!(
+            "{file_path}, {what}: {} in {}.",
+            changed_counts(file_added, file_removed),
+            count(total, "change", "changes")
+        )
+    };
+
+    AccessibleDiff {
+        file_path: file_path.to_string(),
+        summary,
+        hunks,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hunks_are_summarized_and_lines_labeled() {
+        let diff = "@@ -10,4 +10,5 @@ fn main() {\n ctx\n-old\n+new\n+extra\n ctx\n\\ No newline at end of file\n@@ -40,2 +41,0 @@\n-gone\n-also gone\n";
+        let a = build("src/main.rs", Some("src/main.rs"), "modified", diff);
+
+        assert_eq!(
+            a.summary,
+            "src/main.rs, modified: 2 lines added, 3 lines removed in 2 changes."
+        );
+        assert_eq!(a.hunks.len(), 2);
+        assert_eq!(
+            a.hunks[0].summary,
+            "Change 1 of 2, lines 10 to 14: 2 lines added, 1 line removed."
+        );
+        let labels: Vec<&str> = a.hunks[0].lines.iter().map(|l| l.label.as_str()).collect();
+        assert_eq!(
+            labels,
+            vec![
+                "unchanged line 10",
+                "removed line 11",
+                "added line 11",
+                "added line 12",
+                "unchanged line 13",
+            ]
+        );
+        assert_eq!(a.hunks[0].lines[1].content, "old");
+        assert_eq!(
+            a.hunks[1].summary,
+            "Change 2 of 2, old lines 40 to 41: 2 lines removed."
+        );
+        assert_eq!(a.hunks[1].lines[1].label, "removed line 41");
+    }
+
+    #[test]
+    fn file_level_summaries_name_the_change() {
+        let added = build("new.txt", None, "added", "@@ -0,0 +1 @@\n+hello\n");
+        assert_eq!(
+            added.summary,
+            "new.txt, new file: 1 line added in 1 change."
+        );
+        assert_eq!(
+            added.hunks[0].summary,
+            "Change 1 of 1, line 1: 1 line added."
+        );
+
+        let renamed = build("b.rs", Some("a.rs"), "renamed", "");
+        assert_eq!(
+            renamed.summary,
+            "b.rs, renamed from a.rs, with no text changes."
+        );
+    }
+}
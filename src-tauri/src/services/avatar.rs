@@ -1,17 +1,37 @@
 //! Avatar image caching service.
 //!
-//! Downloads and caches GitLab user avatars via session cookie authentication.
-//! Avatars are stored as blobs in SQLite and served as data URIs to the frontend.
+//! Downloads and caches GitLab user avatars. Avatars are stored as blobs in
+//! SQLite and served as data URIs to the frontend.
+//!
+//! Self-hosted instances often return instance-relative avatar URLs or serve
+//! uploads only to signed-in users, so URLs are resolved against the instance
+//! base and fetched with the instance's token (and session cookie, when one
+//! is set). Credentials are only ever sent to the instance itself, so
+//! redirects are followed by hand and a hop to another origin goes out
+//! without them. When the
+//! instance avatar can't be fetched, a Gravatar lookup by email hash can be
+//! used instead if the user allowed it.
 
 use crate::db::pool::DbPool;
 use crate::error::AppError;
 use base64::{engine::general_purpose::STANDARD, Engine};
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-/// TTL for cached avatars (24 hours).
+/// TTL for cached avatars (24 hours). Failed downloads are retried after the
+/// same interval.
 const AVATAR_TTL_SECS: i64 = 24 * 60 * 60;
 
+/// Pixel size requested from Gravatar.
+const GRAVATAR_SIZE: u32 = 96;
+
+/// Avatars downloaded at once during a refresh.
+const DOWNLOAD_CONCURRENCY: usize = 4;
+
+/// Redirects followed per download.
+const MAX_REDIRECTS: usize = 5;
+
 fn now() -> i64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -19,32 +39,227 @@ fn now() -> i64 {
         .unwrap_or(0)
 }
 
-/// Download an avatar image using the GitLab session cookie.
-pub async fn download_avatar(
+/// Credentials for avatars hosted on the instance.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AvatarAuth<'a> {
+    /// Personal access token, sent as `PRIVATE-TOKEN`.
+    pub token: Option<&'a str>,
+    /// `_gitlab_session` cookie, for uploads that only accept a web session.
+    pub cookie: Option<&'a str>,
+}
+
+/// A user whose avatar should be cached.
+#[derive(Debug, Clone, Default)]
+pub struct AvatarSource {
+    pub username: String,
+    pub avatar_url: Option<String>,
+    /// Public email, when the API exposed it; used for the Gravatar fallback.
+    pub email: Option<String>,
+}
+
+/// `scheme://host[:port]` of an absolute URL.
+fn origin(url: &str) -> Option<&str> {
+    let scheme_end = url.find("://")? + 3;
+    let host_end = url[scheme_end..]
+        .find('/')
+        .map_or(url.len(), |i| scheme_end + i);
+    Some(&url[..host_end])
+}
+
+/// Resolve an avatar URL as returned by the API against the instance URL.
+///
+/// Handles absolute URLs, protocol-relative (`//host/...`) URLs, root-relative
+/// paths (`/uploads/...`) and paths relative to the instance base. For
+/// instances under a sub-path (`https://host/gitlab`) root-relative paths
+/// that don't already carry the sub-path get it prepended.
+pub fn resolve_avatar_url(url: &str, instance_url: &str) -> String {
+    let base = instance_url.trim_end_matches('/');
+    if url.starts_with("http://") || url.starts_with("https://") {
+        return url.to_string();
+    }
+    if let Some(rest) = url.strip_prefix("//") {
+        let scheme = base.split("://").next().unwrap_or("https");
+        return format!("{}://{}", scheme, rest);
+    }
+    let Some(origin) = origin(base) else {
+        return format!("{}/{}", base, url.trim_start_matches('/'));
+    };
+    if url.starts_with('/') {
+        let sub_path = &base[origin.len()..];
+        if sub_path.is_empty() || url.starts_with(&format!("{}/", sub_path)) {
+            return format!("{}{}", origin, url);
+        }
+        return format!("{}{}", base, url);
+    }
+    format!("{}/{}", base, url)
+}
+
+/// Whether `url` is served by the instance, i.e. safe to send credentials to.
+fn is_instance_url(url: &str, instance_url: &str) -> bool {
+    match (origin(url), origin(instance_url.trim_end_matches('/'))) {
+        (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+        _ => false,
+    }
+}
+
+/// Gravatar URL for an email address. `d=404` makes unknown addresses fail
+/// instead of returning a generated placeholder.
+pub fn gravatar_url(email: &str) -> String {
+    format!(
+        "https://www.gravatar.com/avatar/{}?s={}&d=404",
+        sha256_hex(email.trim().to_lowercase().as_bytes()),
+        GRAVATAR_SIZE
+    )
+}
+
+/// Lowercase hex SHA-256 of `bytes`.
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// HTTP clients for a batch of downloads, so connections to the instance
+/// are reused. Only the instance client carries its TLS settings. Neither
+/// follows redirects; [`download_avatar`] does, deciding per hop whether
+/// credentials go along.
+pub struct AvatarClients {
+    instance: reqwest::Client,
+    external: reqwest::Client,
+}
+
+impl AvatarClients {
+    pub fn new(instance_url: &str) -> Result<Self, AppError> {
+        let builder = || {
+            reqwest::Client::builder()
+                .redirect(reqwest::redirect::Policy::none())
+                .timeout(std::time::Duration::from_secs(15))
+        };
+        let tls = crate::services::instance_tls::tls_for(instance_url);
+        let build = |b: reqwest::ClientBuilder| {
+            b.build()
+                .map_err(|e| AppError::network(format!("Failed to create HTTP client: {}", e)))
+        };
+        Ok(Self {
+            instance: build(crate::services::instance_tls::apply(builder(), &tls)?)?,
+            external: build(builder())?,
+        })
+    }
+}
+
+/// Cache validators the server sent with an image, sent back as
+/// `If-None-Match` / `If-Modified-Since` on the next download.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Validators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl Validators {
+    fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+}
+
+/// Result of an avatar download.
+#[derive(Debug)]
+pub enum Download {
+    /// The server confirmed the cached image is still current (304).
+    NotModified,
+    Image {
+        data: Vec<u8>,
+        content_type: String,
+        validators: Validators,
+    },
+}
+
+/// Send one GET for an avatar. Credentials and the instance's custom headers
+/// and TLS settings are only used when `url` is on the instance itself.
+async fn send_avatar_request(
+    clients: &AvatarClients,
     url: &str,
-    cookie: &str,
     instance_url: &str,
-) -> Result<(Vec<u8>, String), AppError> {
-    let client = reqwest::Client::builder()
-        .redirect(reqwest::redirect::Policy::limited(5))
-        .timeout(std::time::Duration::from_secs(15))
-        .build()
-        .map_err(|e| AppError::network(format!("Failed to create HTTP client: {}", e)))?;
-
-    // Build the full URL if the avatar_url is relative
-    let full_url = if url.starts_with("http://") || url.starts_with("https://") {
-        url.to_string()
+    auth: AvatarAuth<'_>,
+    cached: &Validators,
+) -> Result<reqwest::Response, AppError> {
+    use reqwest::header::{IF_MODIFIED_SINCE, IF_NONE_MATCH};
+
+    let from_instance = is_instance_url(url, instance_url);
+    let client = if from_instance {
+        &clients.instance
     } else {
-        format!("{}{}", instance_url.trim_end_matches('/'), url)
+        &clients.external
     };
+    let mut request = client.get(url);
+    if from_instance {
+        if let Some(token) = auth.token {
+            request = request.header("PRIVATE-TOKEN", token);
+        }
+        if let Some(cookie) = auth.cookie {
+            request = request.header("Cookie", format!("_gitlab_session={}", cookie));
+        }
+        // Avatars served by the instance itself sit behind the same SSO proxy.
+        for custom in crate::services::instance_headers::headers_for(instance_url) {
+            request = request.header(custom.name.trim(), custom.value);
+        }
+    }
+    if let Some(etag) = &cached.etag {
+        request = request.header(IF_NONE_MATCH, etag);
+    }
+    if let Some(last_modified) = &cached.last_modified {
+        request = request.header(IF_MODIFIED_SINCE, last_modified);
+    }
 
-    let response = client
-        .get(&full_url)
-        .header("Cookie", format!("_gitlab_session={}", cookie))
+    request
         .send()
         .await
-        .map_err(|e| AppError::network(format!("Avatar download failed: {}", e)))?;
+        .map_err(|e| AppError::network(format!("Avatar download failed: {}", e)))
+}
+
+/// Download an avatar image, conditionally when `cached` has validators.
+/// `url` may be instance-relative. Redirects are followed here, so each hop
+/// is checked on its own: one that leaves the instance (e.g. to object
+/// storage) is fetched without credentials.
+pub async fn download_avatar(
+    clients: &AvatarClients,
+    url: &str,
+    instance_url: &str,
+    auth: AvatarAuth<'_>,
+    cached: &Validators,
+) -> Result<Download, AppError> {
+    use reqwest::header::{ETAG, LAST_MODIFIED, LOCATION};
+
+    crate::services::offline_mode::ensure_online()?;
+
+    let mut url = resolve_avatar_url(url, instance_url);
+    let mut redirects = 0;
+    let response = loop {
+        let response = send_avatar_request(clients, &url, instance_url, auth, cached).await?;
+        let location = response
+            .headers()
+            .get(LOCATION)
+            .and_then(|v| v.to_str().ok());
+        let next = match location {
+            Some(location) if response.status().is_redirection() => {
+                response.url().join(location).map_err(|e| {
+                    AppError::network(format!("Avatar redirect to an invalid URL: {}", e))
+                })?
+            }
+            _ => break response,
+        };
+        redirects += 1;
+        if redirects > MAX_REDIRECTS {
+            return Err(AppError::network(
+                "Avatar download redirected too many times",
+            ));
+        }
+        url = next.to_string();
+    };
 
+    if response.status() == reqwest::StatusCode::NOT_MODIFIED && !cached.is_empty() {
+        return Ok(Download::NotModified);
+    }
     if !response.status().is_success() {
         return Err(AppError::network(format!(
             "Avatar download returned status {}",
@@ -52,61 +267,172 @@ pub async fn download_avatar(
         )));
     }
 
-    let content_type = response
-        .headers()
-        .get("content-type")
-        .and_then(|v| v.to_str().ok())
-        .unwrap_or("image/png")
-        .to_string();
+    let header = |name| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    };
+    let content_type = header(reqwest::header::CONTENT_TYPE).unwrap_or_else(|| "image/png".into());
+    let validators = Validators {
+        etag: header(ETAG),
+        last_modified: header(LAST_MODIFIED),
+    };
+
+    // A sign-in page instead of an image means the upload needs a web session.
+    if !content_type.starts_with("image/") {
+        return Err(AppError::network(format!(
+            "Avatar download returned {} instead of an image",
+            content_type
+        )));
+    }
 
     let data = response
         .bytes()
         .await
         .map_err(|e| AppError::network(format!("Failed to read avatar data: {}", e)))?;
 
-    Ok((data.to_vec(), content_type))
+    Ok(Download::Image {
+        data: data.to_vec(),
+        content_type,
+        validators,
+    })
+}
+
+/// Fetch a user's avatar from the instance, falling back to Gravatar when
+/// `allow_gravatar` is set and an email is known. Validators are only used
+/// for, and only returned from, the instance avatar URL they were issued for.
+async fn fetch_avatar(
+    clients: &AvatarClients,
+    cached: &CachedAvatar,
+    instance_url: &str,
+    auth: AvatarAuth<'_>,
+    allow_gravatar: bool,
+) -> Result<Download, AppError> {
+    let primary = match cached.avatar_url.as_deref() {
+        Some(url) => download_avatar(clients, url, instance_url, auth, &cached.validators()).await,
+        None => Err(AppError::not_found("No avatar URL")),
+    };
+    match (primary, cached.email.as_deref()) {
+        (Err(_), Some(email)) if allow_gravatar => {
+            let url = gravatar_url(email);
+            let none = Validators::default();
+            let fallback =
+                download_avatar(clients, &url, instance_url, AvatarAuth::default(), &none);
+            Ok(match fallback.await? {
+                Download::Image {
+                    data, content_type, ..
+                } => Download::Image {
+                    data,
+                    content_type,
+                    validators: none,
+                },
+                not_modified => not_modified,
+            })
+        }
+        (result, _) => result,
+    }
+}
+
+/// Store or update the avatar URL and email during sync (without
+/// downloading). Values the API didn't include keep their cached value. A
+/// new avatar URL drops the validators of the old image and makes it due for
+/// download.
+pub async fn upsert_avatar_source(
+    pool: &DbPool,
+    instance_id: i64,
+    source: &AvatarSource,
+) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        INSERT INTO user_avatars (instance_id, username, avatar_url, email)
+        VALUES (?, ?, ?, ?)
+        ON CONFLICT(instance_id, username) DO UPDATE SET
+            avatar_url = COALESCE(excluded.avatar_url, user_avatars.avatar_url),
+            email = COALESCE(excluded.email, user_avatars.email),
+            etag = CASE WHEN excluded.avatar_url IS NOT NULL
+                         AND excluded.avatar_url IS NOT user_avatars.avatar_url
+                        THEN NULL ELSE user_avatars.etag END,
+            last_modified = CASE WHEN excluded.avatar_url IS NOT NULL
+                                  AND excluded.avatar_url IS NOT user_avatars.avatar_url
+                                 THEN NULL ELSE user_avatars.last_modified END,
+            fetched_at = CASE WHEN excluded.avatar_url IS NOT NULL
+                               AND excluded.avatar_url IS NOT user_avatars.avatar_url
+                              THEN NULL ELSE user_avatars.fetched_at END
+        "#,
+    )
+    .bind(instance_id)
+    .bind(&source.username)
+    .bind(&source.avatar_url)
+    .bind(&source.email)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Bump `fetched_at` without touching the stored image: after a failed
+/// download, so it isn't retried before the TTL runs out, and after a 304.
+async fn mark_attempted(pool: &DbPool, instance_id: i64, username: &str) -> Result<(), AppError> {
+    sqlx::query("UPDATE user_avatars SET fetched_at = ? WHERE instance_id = ? AND username = ?")
+        .bind(now())
+        .bind(instance_id)
+        .bind(username)
+        .execute(pool)
+        .await?;
+
+    Ok(())
 }
 
-/// Store or update the avatar URL during sync (without downloading).
-pub async fn upsert_avatar_url(
+/// Record a download that returned the stored image again: its validators
+/// are kept, the blob isn't rewritten.
+async fn mark_unchanged(
     pool: &DbPool,
     instance_id: i64,
     username: &str,
-    avatar_url: &str,
+    validators: &Validators,
 ) -> Result<(), AppError> {
     sqlx::query(
         r#"
-        INSERT INTO user_avatars (instance_id, username, avatar_url)
-        VALUES (?, ?, ?)
-        ON CONFLICT(instance_id, username) DO UPDATE SET avatar_url = excluded.avatar_url
+        UPDATE user_avatars
+        SET etag = ?, last_modified = ?, fetched_at = ?
+        WHERE instance_id = ? AND username = ?
         "#,
     )
+    .bind(&validators.etag)
+    .bind(&validators.last_modified)
+    .bind(now())
     .bind(instance_id)
     .bind(username)
-    .bind(avatar_url)
     .execute(pool)
     .await?;
 
     Ok(())
 }
 
-/// Store downloaded avatar image data.
+/// Store downloaded avatar image data with its validators and hash.
 pub async fn store_avatar_data(
     pool: &DbPool,
     instance_id: i64,
     username: &str,
     data: &[u8],
     content_type: &str,
+    validators: &Validators,
 ) -> Result<(), AppError> {
     sqlx::query(
         r#"
         UPDATE user_avatars
-        SET avatar_data = ?, content_type = ?, fetched_at = ?
+        SET avatar_data = ?, content_type = ?, content_hash = ?,
+            etag = ?, last_modified = ?, fetched_at = ?
         WHERE instance_id = ? AND username = ?
         "#,
     )
     .bind(data)
     .bind(content_type)
+    .bind(sha256_hex(data))
+    .bind(&validators.etag)
+    .bind(&validators.last_modified)
     .bind(now())
     .bind(instance_id)
     .bind(username)
@@ -168,104 +494,357 @@ pub async fn get_avatar_data_uris_batch(
     Ok(result)
 }
 
-/// Sync avatars for a set of users: upsert URLs and download images if a session cookie is present.
-pub async fn sync_avatars(
+/// A cached avatar row, without the image itself.
+#[derive(Debug, Clone, Default, sqlx::FromRow)]
+struct CachedAvatar {
+    username: String,
+    avatar_url: Option<String>,
+    email: Option<String>,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    content_hash: Option<String>,
+    fetched_at: Option<i64>,
+}
+
+impl CachedAvatar {
+    fn validators(&self) -> Validators {
+        Validators {
+            etag: self.etag.clone(),
+            last_modified: self.last_modified.clone(),
+        }
+    }
+}
+
+/// All cached avatar rows of an instance.
+async fn load_cached(pool: &DbPool, instance_id: i64) -> Result<Vec<CachedAvatar>, AppError> {
+    let rows = sqlx::query_as(
+        r#"
+        SELECT username, avatar_url, email, etag, last_modified, content_hash, fetched_at
+        FROM user_avatars WHERE instance_id = ?
+        "#,
+    )
+    .bind(instance_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// What refreshing one avatar did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Refreshed {
+    /// A new image was stored.
+    Updated,
+    /// The server answered 304, or sent the stored image again.
+    Unchanged,
+    Failed,
+}
+
+/// Download one user's avatar and store it when it changed, recording the
+/// attempt on failure.
+async fn download_and_store(
     pool: &DbPool,
+    clients: &AvatarClients,
     instance_id: i64,
     instance_url: &str,
-    cookie: Option<&str>,
-    users: &[(String, Option<String>)], // (username, avatar_url)
-) -> Result<u32, AppError> {
-    let mut count = 0u32;
-    let cutoff = now() - AVATAR_TTL_SECS;
-
-    for (username, avatar_url) in users {
-        // Upsert the URL if we have one
-        if let Some(url) = avatar_url {
-            if let Err(e) = upsert_avatar_url(pool, instance_id, username, url).await {
-                eprintln!("[avatar] Failed to upsert URL for {}: {}", username, e);
-                continue;
+    auth: AvatarAuth<'_>,
+    allow_gravatar: bool,
+    cached: &CachedAvatar,
+) -> Refreshed {
+    let username = cached.username.as_str();
+    let stored = match fetch_avatar(clients, cached, instance_url, auth, allow_gravatar).await {
+        Ok(Download::NotModified) => mark_attempted(pool, instance_id, username)
+            .await
+            .map(|_| Refreshed::Unchanged),
+        Ok(Download::Image {
+            data,
+            content_type,
+            validators,
+        }) => {
+            if cached.content_hash.as_deref() == Some(sha256_hex(&data).as_str()) {
+                mark_unchanged(pool, instance_id, username, &validators)
+                    .await
+                    .map(|_| Refreshed::Unchanged)
+            } else {
+                store_avatar_data(
+                    pool,
+                    instance_id,
+                    username,
+                    &data,
+                    &content_type,
+                    &validators,
+                )
+                .await
+                .map(|_| Refreshed::Updated)
             }
         }
+        Err(e) => {
+            eprintln!("[avatar] Download failed for {}: {}", username, e);
+            mark_attempted(pool, instance_id, username)
+                .await
+                .map(|_| Refreshed::Failed)
+        }
+    };
+    stored.unwrap_or_else(|e| {
+        eprintln!("[avatar] Failed to store data for {}: {}", username, e);
+        Refreshed::Failed
+    })
+}
 
-        // Download if we have a cookie and the cache is stale
-        if let Some(cookie) = cookie {
-            // Check if we already have a fresh cache
-            let fresh: Option<(i64,)> = sqlx::query_as(
-                "SELECT fetched_at FROM user_avatars WHERE instance_id = ? AND username = ? AND avatar_data IS NOT NULL AND fetched_at > ?",
-            )
-            .bind(instance_id)
-            .bind(username)
-            .bind(cutoff)
-            .fetch_optional(pool)
-            .await
-            .unwrap_or(None);
-
-            if fresh.is_some() {
-                continue; // still fresh
-            }
+/// Refresh `avatars` a few at a time over shared connections. Returns the
+/// number of images that changed.
+async fn refresh_batch(
+    pool: &DbPool,
+    instance_id: i64,
+    instance_url: &str,
+    auth: AvatarAuth<'_>,
+    allow_gravatar: bool,
+    avatars: &[CachedAvatar],
+) -> Result<u32, AppError> {
+    use futures::stream::{self, StreamExt};
 
-            // Get the avatar URL to download
-            let url_row: Option<(String,)> = sqlx::query_as(
-                "SELECT avatar_url FROM user_avatars WHERE instance_id = ? AND username = ? AND avatar_url IS NOT NULL",
+    if avatars.is_empty() {
+        return Ok(0);
+    }
+    let clients = AvatarClients::new(instance_url)?;
+    // Build the futures up front: a borrowing closure inside the stream
+    // makes the future lose `Send` (it's awaited from a boxed sync stage).
+    let downloads: Vec<_> = avatars
+        .iter()
+        .map(|cached| {
+            download_and_store(
+                pool,
+                &clients,
+                instance_id,
+                instance_url,
+                auth,
+                allow_gravatar,
+                cached,
             )
-            .bind(instance_id)
-            .bind(username)
-            .fetch_optional(pool)
-            .await
-            .unwrap_or(None);
-
-            if let Some((url,)) = url_row {
-                match download_avatar(&url, cookie, instance_url).await {
-                    Ok((data, ct)) => {
-                        if let Err(e) =
-                            store_avatar_data(pool, instance_id, username, &data, &ct).await
-                        {
-                            eprintln!("[avatar] Failed to store data for {}: {}", username, e);
-                        } else {
-                            count += 1;
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("[avatar] Download failed for {}: {}", username, e);
-                    }
-                }
+        })
+        .collect();
+    let results: Vec<Refreshed> = stream::iter(downloads)
+        .buffer_unordered(DOWNLOAD_CONCURRENCY)
+        .collect()
+        .await;
+    let updated = results
+        .into_iter()
+        .filter(|refreshed| *refreshed == Refreshed::Updated)
+        .count();
+    Ok(updated as u32)
+}
+
+/// Sync avatars for a set of users: upsert URLs and revalidate images whose
+/// cache is missing or stale. Returns the number of images that changed.
+pub async fn sync_avatars(
+    pool: &DbPool,
+    instance_id: i64,
+    instance_url: &str,
+    auth: AvatarAuth<'_>,
+    allow_gravatar: bool,
+    users: &[AvatarSource],
+) -> Result<u32, AppError> {
+    for user in users {
+        if user.avatar_url.is_some() || user.email.is_some() {
+            if let Err(e) = upsert_avatar_source(pool, instance_id, user).await {
+                eprintln!("[avatar] Failed to upsert URL for {}: {}", user.username, e);
             }
         }
     }
 
-    Ok(count)
+    // Skip users downloaded (or attempted) within the TTL
+    let cutoff = now() - AVATAR_TTL_SECS;
+    let wanted: HashSet<&str> = users.iter().map(|u| u.username.as_str()).collect();
+    let stale: Vec<CachedAvatar> = load_cached(pool, instance_id)
+        .await?
+        .into_iter()
+        .filter(|a| wanted.contains(a.username.as_str()))
+        .filter(|a| a.fetched_at.is_none_or(|t| t <= cutoff))
+        .collect();
+
+    refresh_batch(
+        pool,
+        instance_id,
+        instance_url,
+        auth,
+        allow_gravatar,
+        &stale,
+    )
+    .await
 }
 
-/// Force-refresh all avatars for an instance (ignores TTL).
+/// Revalidate all avatars for an instance (ignores TTL). Unchanged images
+/// cost a 304 each. Returns the number of images that changed.
 pub async fn refresh_all_avatars(
     pool: &DbPool,
     instance_id: i64,
     instance_url: &str,
-    cookie: &str,
+    auth: AvatarAuth<'_>,
+    allow_gravatar: bool,
 ) -> Result<u32, AppError> {
-    let rows: Vec<(String, String)> = sqlx::query_as(
-        "SELECT username, avatar_url FROM user_avatars WHERE instance_id = ? AND avatar_url IS NOT NULL",
+    let avatars = load_cached(pool, instance_id).await?;
+    refresh_batch(
+        pool,
+        instance_id,
+        instance_url,
+        auth,
+        allow_gravatar,
+        &avatars,
     )
-    .bind(instance_id)
-    .fetch_all(pool)
-    .await?;
+    .await
+}
 
-    let mut count = 0u32;
-    for (username, url) in &rows {
-        match download_avatar(url, cookie, instance_url).await {
-            Ok((data, ct)) => {
-                if let Err(e) = store_avatar_data(pool, instance_id, username, &data, &ct).await {
-                    eprintln!("[avatar] Failed to store data for {}: {}", username, e);
-                } else {
-                    count += 1;
-                }
-            }
-            Err(e) => {
-                eprintln!("[avatar] Refresh failed for {}: {}", username, e);
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_relative_avatar_urls() {
+        let base = "https://git.example.com";
+        assert_eq!(
+            resolve_avatar_url("https://cdn.example.com/a.png", base),
+            "https://cdn.example.com/a.png"
+        );
+        assert_eq!(
+            resolve_avatar_url("/uploads/-/system/user/avatar/1/a.png", base),
+            "https://git.example.com/uploads/-/system/user/avatar/1/a.png"
+        );
+        assert_eq!(
+            resolve_avatar_url("//secure.gravatar.com/avatar/x", "http://git.local/"),
+            "http://secure.gravatar.com/avatar/x"
+        );
+
+        let sub = "https://host.example/gitlab/";
+        assert_eq!(
+            resolve_avatar_url("/gitlab/uploads/a.png", sub),
+            "https://host.example/gitlab/uploads/a.png"
+        );
+        assert_eq!(
+            resolve_avatar_url("/uploads/a.png", sub),
+            "https://host.example/gitlab/uploads/a.png"
+        );
+        assert_eq!(
+            resolve_avatar_url("uploads/a.png", sub),
+            "https://host.example/gitlab/uploads/a.png"
+        );
+    }
+
+    #[test]
+    fn credentials_only_go_to_the_instance() {
+        let base = "https://git.example.com/gitlab";
+        assert!(is_instance_url(
+            "https://git.example.com/uploads/a.png",
+            base
+        ));
+        assert!(is_instance_url("https://GIT.example.com/x", base));
+        assert!(!is_instance_url("https://git.example.com.evil.io/x", base));
+        assert!(!is_instance_url("https://www.gravatar.com/avatar/x", base));
+    }
+
+    #[test]
+    fn gravatar_hash_normalizes_email() {
+        assert_eq!(
+            gravatar_url(" Alice@Example.com "),
+            gravatar_url("alice@example.com")
+        );
+        assert!(gravatar_url("a@b.c").starts_with("https://www.gravatar.com/avatar/"));
+    }
+
+    /// Serve one request on `listener` with `response`, returning the
+    /// request head.
+    async fn serve_once(listener: tokio::net::TcpListener, response: String) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut head = Vec::new();
+        let mut buf = [0u8; 1024];
+        while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+            let n = socket.read(&mut buf).await.unwrap();
+            head.extend_from_slice(&buf[..n]);
         }
+        socket.write_all(response.as_bytes()).await.unwrap();
+        String::from_utf8_lossy(&head).to_lowercase()
     }
 
-    Ok(count)
+    #[tokio::test]
+    async fn cross_origin_redirect_drops_credentials() {
+        let instance = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let storage = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let instance_url = format!("http://{}", instance.local_addr().unwrap());
+        let storage_url = format!("http://{}/avatar.png", storage.local_addr().unwrap());
+
+        let instance_req = tokio::spawn(serve_once(
+            instance,
+            format!(
+                "HTTP/1.1 302 Found\r\nLocation: {}\r\nContent-Length: 0\r\n\r\n",
+                storage_url
+            ),
+        ));
+        let storage_req = tokio::spawn(serve_once(
+            storage,
+            "HTTP/1.1 200 OK\r\nContent-Type: image/png\r\nContent-Length: 3\r\n\r\npng"
+                .to_string(),
+        ));
+
+        let clients = AvatarClients::new(&instance_url).unwrap();
+        let auth = AvatarAuth {
+            token: Some("secret-token"),
+            cookie: Some("secret-session"),
+        };
+        let download = download_avatar(
+            &clients,
+            "/uploads/a.png",
+            &instance_url,
+            auth,
+            &Validators::default(),
+        )
+        .await
+        .unwrap();
+        assert!(matches!(download, Download::Image { ref data, .. } if data == b"png"));
+
+        let instance_req = instance_req.await.unwrap();
+        assert!(instance_req.contains("private-token: secret-token"));
+        let storage_req = storage_req.await.unwrap();
+        assert!(!storage_req.contains("secret-token"));
+        assert!(!storage_req.contains("secret-session"));
+    }
+
+    #[tokio::test]
+    async fn new_avatar_url_drops_validators() {
+        let (pool, inst) = crate::core::tests::seed_instance(true).await;
+        let source = |url: &str| AvatarSource {
+            username: "alice".into(),
+            avatar_url: Some(url.into()),
+            email: None,
+        };
+        upsert_avatar_source(&pool, inst, &source("/a.png"))
+            .await
+            .unwrap();
+        let validators = Validators {
+            etag: Some("\"v1\"".into()),
+            last_modified: None,
+        };
+        store_avatar_data(&pool, inst, "alice", b"png", "image/png", &validators)
+            .await
+            .unwrap();
+
+        // Syncing the same URL keeps the validators and the TTL.
+        upsert_avatar_source(&pool, inst, &source("/a.png"))
+            .await
+            .unwrap();
+        let cached = load_cached(&pool, inst).await.unwrap().remove(0);
+        assert_eq!(cached.validators(), validators);
+        assert_eq!(cached.content_hash, Some(sha256_hex(b"png")));
+        assert!(cached.fetched_at.is_some());
+
+        // A new avatar is due now and must not be validated against the old.
+        upsert_avatar_source(&pool, inst, &source("/b.png"))
+            .await
+            .unwrap();
+        let cached = load_cached(&pool, inst).await.unwrap().remove(0);
+        assert_eq!(cached.validators(), Validators::default());
+        assert_eq!(cached.fetched_at, None);
+        assert!(get_avatar_data_uri(&pool, inst, "alice")
+            .await
+            .unwrap()
+            .is_some());
+    }
 }
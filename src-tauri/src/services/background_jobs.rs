@@ -0,0 +1,467 @@
+//! Low-priority jobs that run while the app is idle.
+//!
+//! Precomputed data (semantic summaries now; search indexes and stats
+//! later) is built by jobs behind [`BackgroundJob`]. Jobs are queued by
+//! name, e.g. by the sync engine after it caches new diffs, and the
+//! scheduler runs them one at a time, in small batches, only while no sync
+//! is running and the user hasn't interacted with the app for
+//! [`IDLE_AFTER_SECS`]. User activity or a starting sync cancels the
+//! running batch; the job goes back to the front of the queue and resumes
+//! from wherever its last completed batch left off.
+
+use crate::core::semantic_summaries;
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use futures::future::BoxFuture;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicI64, AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio_util::sync::CancellationToken;
+
+/// How long the user must have been inactive before jobs run.
+pub const IDLE_AFTER_SECS: i64 = 60;
+
+/// How often the scheduler checks whether it can run something.
+const TICK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Result of one batch of a job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JobOutcome {
+    /// Items processed in the batch.
+    pub processed: u64,
+    /// Whether the job has more work; it is requeued behind the other jobs.
+    pub more: bool,
+}
+
+/// A unit of idle-time work. Jobs are stateless: the work left to do is
+/// derived from the database, so a batch can be dropped at any await point
+/// and simply run again later.
+pub trait BackgroundJob: Send + Sync {
+    /// Name used to queue the job and in its status.
+    fn name(&self) -> &'static str;
+
+    /// What the job builds, for the settings page.
+    fn description(&self) -> &'static str;
+
+    /// Do one batch of work.
+    fn run<'a>(&'a self, pool: &'a DbPool, now: i64)
+        -> BoxFuture<'a, Result<JobOutcome, AppError>>;
+}
+
+/// Name of the job that precomputes semantic summaries of diff files.
+pub const SEMANTIC_SUMMARIES: &str = "semantic-summaries";
+
+/// All jobs.
+pub static JOBS: [&dyn BackgroundJob; 1] = [&SemanticSummariesJob];
+
+/// Summarises cached diff files, most recently updated MRs first.
+struct SemanticSummariesJob;
+
+/// Files summarised per batch.
+const SEMANTIC_SUMMARY_BATCH: i64 = 50;
+
+impl BackgroundJob for SemanticSummariesJob {
+    fn name(&self) -> &'static str {
+        SEMANTIC_SUMMARIES
+    }
+
+    fn description(&self) -> &'static str {
+        "Semantic summaries of changed files"
+    }
+
+    fn run<'a>(
+        &'a self,
+        pool: &'a DbPool,
+        now: i64,
+    ) -> BoxFuture<'a, Result<JobOutcome, AppError>> {
+        Box::pin(async move {
+            let files = semantic_summaries::pending(pool, SEMANTIC_SUMMARY_BATCH).await?;
+            let mut processed = 0;
+            for file in &files {
+                if semantic_summaries::summary_for(pool, file.mr_id, &file.file_path, now)
+                    .await?
+                    .is_some()
+                {
+                    processed += 1;
+                }
+            }
+            Ok(JobOutcome {
+                processed,
+                // Nothing stored means the same files would come back.
+                more: processed > 0 && files.len() as i64 == SEMANTIC_SUMMARY_BATCH,
+            })
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum JobState {
+    Idle,
+    Queued,
+    Running,
+}
+
+/// A job's state and the result of its last batch.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackgroundJobStatus {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub state: JobState,
+    /// When the job was last queued.
+    pub queued_at: Option<i64>,
+    /// When the last batch finished, successfully or not.
+    pub last_run_at: Option<i64>,
+    /// Items processed by the last successful batch.
+    pub last_processed: u64,
+    pub last_error: Option<String>,
+    /// Batches cancelled by user activity or a sync.
+    pub cancellations: u64,
+}
+
+struct SchedulerState {
+    queue: VecDeque<&'static str>,
+    statuses: Vec<BackgroundJobStatus>,
+    /// Cancels the running batch.
+    running: Option<CancellationToken>,
+}
+
+impl SchedulerState {
+    fn status_mut(&mut self, name: &str) -> &mut BackgroundJobStatus {
+        self.statuses
+            .iter_mut()
+            .find(|s| s.name == name)
+            .expect("every queued job has a status")
+    }
+
+    /// Queue `name` unless it already is; at the front to resume it first.
+    fn requeue(&mut self, name: &'static str, front: bool) {
+        if self.queue.contains(&name) {
+            return;
+        }
+        if front {
+            self.queue.push_front(name);
+        } else {
+            self.queue.push_back(name);
+        }
+    }
+}
+
+/// The job queue and the idle/sync conditions gating it.
+pub struct Scheduler {
+    jobs: &'static [&'static dyn BackgroundJob],
+    state: Mutex<SchedulerState>,
+    /// Syncs currently running.
+    active_syncs: AtomicUsize,
+    /// Unix time of the last user activity.
+    last_activity: AtomicI64,
+}
+
+/// Keeps background jobs paused while alive; see [`Scheduler::pause_for_sync`].
+pub struct SyncPause<'a>(&'a Scheduler);
+
+impl Drop for SyncPause<'_> {
+    fn drop(&mut self) {
+        self.0.active_syncs.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl Scheduler {
+    pub fn new(jobs: &'static [&'static dyn BackgroundJob]) -> Self {
+        let statuses = jobs
+            .iter()
+            .map(|job| BackgroundJobStatus {
+                name: job.name(),
+                description: job.description(),
+                state: JobState::Idle,
+                queued_at: None,
+                last_run_at: None,
+                last_processed: 0,
+                last_error: None,
+                cancellations: 0,
+            })
+            .collect();
+        Self {
+            jobs,
+            state: Mutex::new(SchedulerState {
+                queue: VecDeque::new(),
+                statuses,
+                running: None,
+            }),
+            active_syncs: AtomicUsize::new(0),
+            last_activity: AtomicI64::new(0),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SchedulerState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Queue a job by name. Queuing a job that is already queued is a no-op.
+    pub fn enqueue(&self, name: &str, now: i64) -> Result<(), AppError> {
+        let job = self
+            .jobs
+            .iter()
+            .find(|job| job.name() == name)
+            .ok_or_else(|| AppError::not_found_with_id("BackgroundJob", name))?;
+        let mut state = self.lock();
+        if state.queue.contains(&job.name()) {
+            return Ok(());
+        }
+        state.queue.push_back(job.name());
+        let status = state.status_mut(job.name());
+        status.queued_at = Some(now);
+        if status.state == JobState::Idle {
+            status.state = JobState::Queued;
+        }
+        Ok(())
+    }
+
+    /// Note user activity, cancelling the running batch.
+    pub fn record_activity(&self, now: i64) {
+        self.last_activity.fetch_max(now, Ordering::SeqCst);
+        if let Some(token) = &self.lock().running {
+            token.cancel();
+        }
+    }
+
+    /// Pause jobs until the returned guard is dropped, cancelling the
+    /// running batch. Held by the sync engine for the length of a sync.
+    pub fn pause_for_sync(&self) -> SyncPause<'_> {
+        self.active_syncs.fetch_add(1, Ordering::SeqCst);
+        if let Some(token) = &self.lock().running {
+            token.cancel();
+        }
+        SyncPause(self)
+    }
+
+    fn is_idle(&self, now: i64) -> bool {
+        self.active_syncs.load(Ordering::SeqCst) == 0
+            && now - self.last_activity.load(Ordering::SeqCst) >= IDLE_AFTER_SECS
+    }
+
+    /// Run one batch of the next queued job if the app is idle. Returns
+    /// whether a batch ran to completion, i.e. whether to try another one.
+    pub async fn run_next(&self, pool: &DbPool, now: i64) -> bool {
+        let (job, token) = {
+            let mut state = self.lock();
+            // Checked under the lock so activity recorded from here on finds
+            // the token to cancel.
+            if !self.is_idle(now) {
+                return false;
+            }
+            let Some(name) = state.queue.pop_front() else {
+                return false;
+            };
+            let Some(job) = self.jobs.iter().find(|job| job.name() == name) else {
+                return false;
+            };
+            let token = CancellationToken::new();
+            state.running = Some(token.clone());
+            state.status_mut(name).state = JobState::Running;
+            (*job, token)
+        };
+
+        let result = tokio::select! {
+            result = job.run(pool, now) => Some(result),
+            _ = token.cancelled() => None,
+        };
+
+        let mut state = self.lock();
+        state.running = None;
+        let name = job.name();
+        let completed = match result {
+            None => {
+                state.requeue(name, true);
+                state.status_mut(name).cancellations += 1;
+                false
+            }
+            Some(Ok(outcome)) => {
+                if outcome.more {
+                    state.requeue(name, false);
+                }
+                let status = state.status_mut(name);
+                status.last_run_at = Some(now);
+                status.last_processed = outcome.processed;
+                status.last_error = None;
+                true
+            }
+            Some(Err(e)) => {
+                log::warn!("[background-jobs] {} failed: {}", name, e);
+                let status = state.status_mut(name);
+                status.last_run_at = Some(now);
+                status.last_error = Some(e.to_string());
+                true
+            }
+        };
+        let queued = state.queue.contains(&name);
+        state.status_mut(name).state = if queued {
+            JobState::Queued
+        } else {
+            JobState::Idle
+        };
+        completed
+    }
+
+    /// Status of every job.
+    pub fn statuses(&self) -> Vec<BackgroundJobStatus> {
+        self.lock().statuses.clone()
+    }
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// The process-wide scheduler running [`JOBS`].
+fn scheduler() -> &'static Scheduler {
+    static SCHEDULER: OnceLock<Scheduler> = OnceLock::new();
+    SCHEDULER.get_or_init(|| Scheduler::new(&JOBS))
+}
+
+/// Queue a job by name; see [`Scheduler::enqueue`].
+pub fn enqueue(name: &str) -> Result<(), AppError> {
+    scheduler().enqueue(name, now())
+}
+
+/// Note user activity; see [`Scheduler::record_activity`].
+pub fn record_activity() {
+    scheduler().record_activity(now());
+}
+
+/// Pause jobs for a sync; see [`Scheduler::pause_for_sync`].
+pub fn pause_for_sync() -> SyncPause<'static> {
+    scheduler().pause_for_sync()
+}
+
+/// Status of every job.
+pub fn statuses() -> Vec<BackgroundJobStatus> {
+    scheduler().statuses()
+}
+
+/// Start the scheduler loop. Every job is queued once to catch up on work
+/// left from the last session, and startup counts as user activity so
+/// nothing runs while the app is being opened.
+pub fn start(pool: DbPool) {
+    let scheduler = scheduler();
+    scheduler.record_activity(now());
+    for job in JOBS.iter() {
+        let _ = scheduler.enqueue(job.name(), now());
+    }
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(TICK_INTERVAL);
+        loop {
+            interval.tick().await;
+            while scheduler.run_next(&pool, now()).await {}
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicU64;
+    use tempfile::tempdir;
+
+    /// Counts down `REMAINING` in batches of two; `SLOW` makes a batch
+    /// wait forever so it can be cancelled.
+    struct CountdownJob;
+
+    static REMAINING: AtomicU64 = AtomicU64::new(0);
+    static SLOW: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+    impl BackgroundJob for CountdownJob {
+        fn name(&self) -> &'static str {
+            "countdown"
+        }
+
+        fn description(&self) -> &'static str {
+            "Counts down"
+        }
+
+        fn run<'a>(
+            &'a self,
+            _pool: &'a DbPool,
+            _now: i64,
+        ) -> BoxFuture<'a, Result<JobOutcome, AppError>> {
+            Box::pin(async move {
+                if SLOW.load(Ordering::SeqCst) {
+                    std::future::pending::<()>().await;
+                }
+                let remaining = REMAINING.load(Ordering::SeqCst);
+                let processed = remaining.min(2);
+                REMAINING.store(remaining - processed, Ordering::SeqCst);
+                Ok(JobOutcome {
+                    processed,
+                    more: remaining > processed,
+                })
+            })
+        }
+    }
+
+    static TEST_JOBS: [&dyn BackgroundJob; 1] = [&CountdownJob];
+
+    fn state_of(scheduler: &Scheduler) -> JobState {
+        scheduler.statuses()[0].state
+    }
+
+    #[tokio::test]
+    async fn jobs_run_only_when_idle_and_resume_after_cancellation() {
+        let dir = tempdir().unwrap();
+        let pool = crate::db::initialize(&dir.path().join("t.db"))
+            .await
+            .unwrap();
+        let scheduler = Scheduler::new(&TEST_JOBS);
+        assert!(scheduler.enqueue("missing", 0).is_err());
+
+        REMAINING.store(3, Ordering::SeqCst);
+        scheduler.enqueue("countdown", 0).unwrap();
+        scheduler.enqueue("countdown", 0).unwrap();
+        assert_eq!(state_of(&scheduler), JobState::Queued);
+
+        // Not idle yet, then blocked by a sync.
+        scheduler.record_activity(100);
+        assert!(!scheduler.run_next(&pool, 100 + IDLE_AFTER_SECS - 1).await);
+        let idle = 100 + IDLE_AFTER_SECS;
+        {
+            let _pause = scheduler.pause_for_sync();
+            assert!(!scheduler.run_next(&pool, idle).await);
+        }
+        assert_eq!(REMAINING.load(Ordering::SeqCst), 3);
+
+        // A batch that is cancelled by activity goes back to the front.
+        SLOW.store(true, Ordering::SeqCst);
+        let run = scheduler.run_next(&pool, idle);
+        let cancel = async {
+            tokio::task::yield_now().await;
+            assert_eq!(state_of(&scheduler), JobState::Running);
+            scheduler.record_activity(idle);
+        };
+        let (completed, ()) = tokio::join!(run, cancel);
+        assert!(!completed);
+        let status = &scheduler.statuses()[0];
+        assert_eq!(status.state, JobState::Queued);
+        assert_eq!(status.cancellations, 1);
+        assert_eq!(REMAINING.load(Ordering::SeqCst), 3);
+
+        // Once idle again it runs batch by batch until done.
+        SLOW.store(false, Ordering::SeqCst);
+        let idle = idle + IDLE_AFTER_SECS;
+        assert!(scheduler.run_next(&pool, idle).await);
+        assert_eq!(state_of(&scheduler), JobState::Queued);
+        assert!(scheduler.run_next(&pool, idle).await);
+        assert!(!scheduler.run_next(&pool, idle).await);
+        let status = &scheduler.statuses()[0];
+        assert_eq!(status.state, JobState::Idle);
+        assert_eq!(status.last_processed, 1);
+        assert_eq!(status.last_run_at, Some(idle));
+        assert_eq!(REMAINING.load(Ordering::SeqCst), 0);
+    }
+}
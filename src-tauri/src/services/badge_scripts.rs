@@ -0,0 +1,631 @@
+//! User-scripted badges for the MR lists.
+//!
+//! A badge rule is a short Rhai script run against each MR when a list is
+//! queried, e.g. `labels.contains("breaking") && lines > 500`. Returning
+//! `true` shows the rule's label; returning a non-empty string shows that
+//! string instead; anything else shows nothing.
+//!
+//! Scripts are sandboxed: modules can't be imported, `eval` is disabled,
+//! `print`/`debug` go nowhere, and operations, string and collection sizes
+//! and nesting are bounded. Each evaluation also has a wall-time limit set
+//! per rule. A rule that errors or runs out of time is skipped for the rest
+//! of that query, and the error is shown next to the rule in settings. All
+//! evaluations for one query share an overall budget too; once it's spent
+//! the remaining evaluations are skipped and the rule that was running gets
+//! the timeout.
+//!
+//! Rules are compiled once and kept in memory. Saving them swaps in the new
+//! set, so edits apply to the next list query without a restart.
+
+use crate::db::badge_rules::{self, BadgeRuleRow, NewBadgeRule};
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use crate::models::MergeRequest;
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Scope, AST};
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+/// Wall-time limit of one evaluation when a rule doesn't set one.
+pub const DEFAULT_TIME_LIMIT_MS: i64 = 10;
+
+/// Largest per-rule time limit. Rules run for every listed MR, so even this
+/// is generous.
+pub const MAX_TIME_LIMIT_MS: i64 = 100;
+
+const MAX_RULES: usize = 50;
+const MAX_SCRIPT_LEN: usize = 4_000;
+const MAX_LABEL_CHARS: usize = 32;
+
+/// Wall time all rules together may take for one list query.
+const QUERY_TIME_LIMIT: Duration = Duration::from_millis(500);
+
+/// Operations per evaluation, a backstop for the time limit.
+const MAX_OPERATIONS: u64 = 500_000;
+
+/// A saved badge rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BadgeRule {
+    pub id: i64,
+    pub name: String,
+    /// Badge text when the script returns `true`.
+    pub label: String,
+    /// `#rgb` or `#rrggbb`; None uses the default badge color.
+    pub color: Option<String>,
+    pub script: String,
+    pub enabled: bool,
+    pub time_limit_ms: i64,
+    /// Last error or timeout while evaluating; cleared when rules are saved.
+    pub last_error: Option<String>,
+}
+
+/// A rule as sent by the settings editor.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BadgeRuleInput {
+    pub name: String,
+    pub label: String,
+    pub color: Option<String>,
+    pub script: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    pub time_limit_ms: Option<i64>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// A badge a rule put on an MR.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomBadge {
+    pub rule_id: i64,
+    pub label: String,
+    pub color: Option<String>,
+}
+
+struct CompiledRule {
+    row: BadgeRuleRow,
+    ast: AST,
+}
+
+struct RuleSet {
+    engine: Engine,
+    rules: Vec<CompiledRule>,
+}
+
+/// An MR as seen by scripts, with a reference for error messages.
+struct Subject {
+    reference: String,
+    scope: Scope<'static>,
+}
+
+thread_local! {
+    /// End of the running evaluation's time limit, checked by the engine's
+    /// progress callback.
+    static DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// Compiled rules; `None` until first loaded.
+fn rule_set() -> &'static RwLock<Option<Arc<RuleSet>>> {
+    static RULES: OnceLock<RwLock<Option<Arc<RuleSet>>>> = OnceLock::new();
+    RULES.get_or_init(|| RwLock::new(None))
+}
+
+/// Last evaluation error per rule id.
+fn rule_errors() -> &'static Mutex<HashMap<i64, String>> {
+    static ERRORS: OnceLock<Mutex<HashMap<i64, String>>> = OnceLock::new();
+    ERRORS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn record_error(rule_id: i64, message: String) {
+    if let Ok(mut errors) = rule_errors().lock() {
+        errors.insert(rule_id, message);
+    }
+}
+
+fn new_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_module_resolver(DummyModuleResolver::new())
+        .disable_symbol("eval")
+        .set_max_operations(MAX_OPERATIONS)
+        .set_max_call_levels(16)
+        .set_max_expr_depths(64, 32)
+        .set_max_string_size(64 * 1024)
+        .set_max_array_size(10_000)
+        .set_max_map_size(10_000);
+    engine.on_print(|_| {});
+    engine.on_debug(|_, _, _| {});
+    engine.on_progress(|_| {
+        DEADLINE.with(|deadline| match deadline.get() {
+            Some(end) if Instant::now() >= end => Some(Dynamic::UNIT),
+            _ => None,
+        })
+    });
+    engine
+}
+
+/// Compile the saved rules. A rule that no longer compiles is left out and
+/// its error recorded.
+fn compile(rows: Vec<BadgeRuleRow>) -> RuleSet {
+    let engine = new_engine();
+    let rules = rows
+        .into_iter()
+        .filter_map(|row| match engine.compile(&row.script) {
+            Ok(ast) => Some(CompiledRule { row, ast }),
+            Err(err) => {
+                record_error(row.id, format!("Script doesn't compile: {}", err));
+                None
+            }
+        })
+        .collect();
+    RuleSet { engine, rules }
+}
+
+async fn current(pool: &DbPool) -> Result<Arc<RuleSet>, AppError> {
+    if let Some(set) = rule_set().read().await.as_ref() {
+        return Ok(set.clone());
+    }
+    let mut guard = rule_set().write().await;
+    if let Some(set) = guard.as_ref() {
+        return Ok(set.clone());
+    }
+    let set = Arc::new(compile(badge_rules::list_rules(pool).await?));
+    *guard = Some(set.clone());
+    Ok(set)
+}
+
+/// The saved rules, with their last evaluation errors.
+pub async fn list_rules(pool: &DbPool) -> Result<Vec<BadgeRule>, AppError> {
+    let rows = badge_rules::list_rules(pool).await?;
+    let errors = rule_errors()
+        .lock()
+        .map(|errors| errors.clone())
+        .unwrap_or_default();
+    Ok(rows
+        .into_iter()
+        .map(|row| BadgeRule {
+            last_error: errors.get(&row.id).cloned(),
+            id: row.id,
+            name: row.name,
+            label: row.label,
+            color: row.color,
+            script: row.script,
+            enabled: row.enabled,
+            time_limit_ms: row.time_limit_ms,
+        })
+        .collect())
+}
+
+fn is_hex_color(color: &str) -> bool {
+    color
+        .strip_prefix('#')
+        .is_some_and(|hex| matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Check rules before saving. Scripts must compile; errors name the rule
+/// by its position so the editor can point at it.
+fn validate(rules: &[BadgeRuleInput]) -> Result<(), AppError> {
+    if rules.len() > MAX_RULES {
+        return Err(AppError::invalid_input(format!(
+            "At most {} badge rules are allowed",
+            MAX_RULES
+        )));
+    }
+    let engine = new_engine();
+    for (i, rule) in rules.iter().enumerate() {
+        let field = |name: &str| format!("rules[{}].{}", i, name);
+        if rule.name.trim().is_empty() {
+            return Err(AppError::invalid_input_field(
+                "Rule name is required",
+                field("name"),
+            ));
+        }
+        let label_len = rule.label.trim().chars().count();
+        if label_len == 0 || label_len > MAX_LABEL_CHARS {
+            return Err(AppError::invalid_input_field(
+                format!("Badge label must be 1 to {} characters", MAX_LABEL_CHARS),
+                field("label"),
+            ));
+        }
+        if rule.color.as_deref().is_some_and(|c| !is_hex_color(c)) {
+            return Err(AppError::invalid_input_field(
+                "Color must be #rgb or #rrggbb",
+                field("color"),
+            ));
+        }
+        let limit = rule.time_limit_ms.unwrap_or(DEFAULT_TIME_LIMIT_MS);
+        if !(1..=MAX_TIME_LIMIT_MS).contains(&limit) {
+            return Err(AppError::invalid_input_field(
+                format!("Time limit must be 1 to {} ms", MAX_TIME_LIMIT_MS),
+                field("timeLimitMs"),
+            ));
+        }
+        if rule.script.len() > MAX_SCRIPT_LEN {
+            return Err(AppError::invalid_input_field(
+                format!("Script is longer than {} bytes", MAX_SCRIPT_LEN),
+                field("script"),
+            ));
+        }
+        if let Err(err) = engine.compile(&rule.script) {
+            return Err(AppError::invalid_input_field(
+                format!("\"{}\" doesn't compile: {}", rule.name.trim(), err),
+                field("script"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Replace the rules and swap them in for the next list query.
+pub async fn update_rules(
+    pool: &DbPool,
+    rules: Vec<BadgeRuleInput>,
+    now: i64,
+) -> Result<Vec<BadgeRule>, AppError> {
+    validate(&rules)?;
+    let new_rules: Vec<NewBadgeRule> = rules
+        .iter()
+        .map(|rule| NewBadgeRule {
+            name: rule.name.trim(),
+            label: rule.label.trim(),
+            color: rule.color.as_deref(),
+            script: &rule.script,
+            enabled: rule.enabled,
+            time_limit_ms: rule.time_limit_ms.unwrap_or(DEFAULT_TIME_LIMIT_MS),
+        })
+        .collect();
+
+    // Hold the write lock so no query loads the old rules mid-save.
+    let mut guard = rule_set().write().await;
+    badge_rules::replace_rules(pool, &new_rules, now).await?;
+    if let Ok(mut errors) = rule_errors().lock() {
+        errors.clear();
+    }
+    *guard = Some(Arc::new(compile(badge_rules::list_rules(pool).await?)));
+    drop(guard);
+
+    list_rules(pool).await
+}
+
+fn is_draft(title: &str) -> bool {
+    let title = title.to_ascii_lowercase();
+    title.starts_with("draft:") || title.starts_with("wip:")
+}
+
+fn to_array(values: Vec<String>) -> Array {
+    values.into_iter().map(Dynamic::from).collect()
+}
+
+/// The variables a script sees for one MR. Diff stats are 0 until the
+/// MR's diff has been cached.
+fn subject(mr: &MergeRequest, diff: Option<(i64, i64, i64)>, now: i64) -> Subject {
+    let (files, additions, deletions) = diff.unwrap_or_default();
+    let mut scope = Scope::new();
+    scope
+        .push_constant("title", mr.title.clone())
+        .push_constant("description", mr.description.clone().unwrap_or_default())
+        .push_constant("author", mr.author_username.clone())
+        .push_constant("project", mr.project_name.clone())
+        .push_constant("source_branch", mr.source_branch.clone())
+        .push_constant("target_branch", mr.target_branch.clone())
+        .push_constant("state", mr.state.clone())
+        .push_constant("draft", is_draft(&mr.title))
+        .push_constant("bot", mr.is_bot_author)
+        .push_constant("watched", mr.watched)
+        .push_constant("labels", to_array(mr.labels_vec()))
+        .push_constant("reviewers", to_array(mr.reviewers_vec()))
+        .push_constant(
+            "approved",
+            mr.approval_status.as_deref() == Some("approved"),
+        )
+        .push_constant("approvals", mr.approvals_count.unwrap_or(0))
+        .push_constant("approvals_required", mr.approvals_required.unwrap_or(0))
+        .push_constant(
+            "pipeline",
+            mr.head_pipeline_status.clone().unwrap_or_default(),
+        )
+        .push_constant("files", files)
+        .push_constant("additions", additions)
+        .push_constant("deletions", deletions)
+        .push_constant("lines", additions + deletions)
+        .push_constant("tasks", mr.task_count)
+        .push_constant("tasks_done", mr.task_completed_count)
+        .push_constant("age_hours", (now - mr.created_at).max(0) / 3600)
+        .push_constant("idle_hours", (now - mr.updated_at).max(0) / 3600);
+    Subject {
+        reference: format!("{}!{}", mr.project_name, mr.iid),
+        scope,
+    }
+}
+
+/// Badge text for a script's result.
+fn badge_label(value: Dynamic, rule_label: &str) -> Option<String> {
+    if let Ok(hit) = value.as_bool() {
+        return hit.then(|| rule_label.to_string());
+    }
+    let text = value.into_string().ok()?;
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.chars().take(MAX_LABEL_CHARS).collect())
+}
+
+fn run(set: &RuleSet, subjects: &[Subject]) -> Vec<Vec<CustomBadge>> {
+    run_within(set, subjects, QUERY_TIME_LIMIT)
+}
+
+/// Run the enabled rules against `subjects`, giving up on whatever is left
+/// once `budget` has passed.
+fn run_within(set: &RuleSet, subjects: &[Subject], budget: Duration) -> Vec<Vec<CustomBadge>> {
+    let query_end = Instant::now() + budget;
+    let mut badges = vec![Vec::new(); subjects.len()];
+    for rule in set.rules.iter().filter(|rule| rule.row.enabled) {
+        let limit = Duration::from_millis(rule.row.time_limit_ms.max(1) as u64);
+        for (subject, out) in subjects.iter().zip(badges.iter_mut()) {
+            let started = Instant::now();
+            let rule_end = started + limit;
+            if started >= query_end {
+                record_budget_timeout(rule.row.id, budget, &subject.reference);
+                return badges;
+            }
+            let mut scope = subject.scope.clone();
+            DEADLINE.with(|deadline| deadline.set(Some(rule_end.min(query_end))));
+            let result = set
+                .engine
+                .eval_ast_with_scope::<Dynamic>(&mut scope, &rule.ast);
+            DEADLINE.with(|deadline| deadline.set(None));
+            match result {
+                Ok(value) => {
+                    if let Some(label) = badge_label(value, &rule.row.label) {
+                        out.push(CustomBadge {
+                            rule_id: rule.row.id,
+                            label,
+                            color: rule.row.color.clone(),
+                        });
+                    }
+                }
+                Err(err) => {
+                    let message = match *err {
+                        EvalAltResult::ErrorTerminated(..) if query_end < rule_end => {
+                            record_budget_timeout(rule.row.id, budget, &subject.reference);
+                            return badges;
+                        }
+                        EvalAltResult::ErrorTerminated(..) => format!(
+                            "Exceeded its {} ms time limit on {}",
+                            rule.row.time_limit_ms, subject.reference
+                        ),
+                        err => format!("{} on {}", err, subject.reference),
+                    };
+                    record_error(rule.row.id, message);
+                    break;
+                }
+            }
+        }
+    }
+    badges
+}
+
+fn record_budget_timeout(rule_id: i64, budget: Duration, reference: &str) {
+    record_error(
+        rule_id,
+        format!(
+            "Badge rules ran past their {} ms total for one list on {}",
+            budget.as_millis(),
+            reference
+        ),
+    );
+}
+
+/// Custom badges for each of `mrs`, in order.
+pub async fn evaluate(
+    pool: &DbPool,
+    mrs: &[MergeRequest],
+    now: i64,
+) -> Result<Vec<Vec<CustomBadge>>, AppError> {
+    let set = current(pool).await?;
+    if mrs.is_empty() || !set.rules.iter().any(|rule| rule.row.enabled) {
+        return Ok(vec![Vec::new(); mrs.len()]);
+    }
+
+    let placeholders = vec!["?"; mrs.len()].join(", ");
+    let sql = format!(
+        "SELECT mr_id, file_count, additions, deletions FROM diffs WHERE mr_id IN ({})",
+        placeholders
+    );
+    let mut query = sqlx::query_as::<_, (i64, i64, i64, i64)>(&sql);
+    for mr in mrs {
+        query = query.bind(mr.id);
+    }
+    let diffs: HashMap<i64, (i64, i64, i64)> = query
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|(mr_id, files, additions, deletions)| (mr_id, (files, additions, deletions)))
+        .collect();
+
+    let subjects: Vec<Subject> = mrs
+        .iter()
+        .map(|mr| subject(mr, diffs.get(&mr.id).copied(), now))
+        .collect();
+    tokio::task::spawn_blocking(move || run(&set, &subjects))
+        .await
+        .map_err(|e| AppError::internal(format!("Badge rules failed: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(id: i64, label: &str, script: &str) -> BadgeRuleRow {
+        BadgeRuleRow {
+            id,
+            name: format!("rule {}", id),
+            label: label.to_string(),
+            color: Some("#c00".to_string()),
+            script: script.to_string(),
+            enabled: true,
+            time_limit_ms: 5,
+        }
+    }
+
+    fn mr(id: i64, title: &str, labels: &str) -> MergeRequest {
+        MergeRequest {
+            id,
+            instance_id: 1,
+            iid: id,
+            project_id: 10,
+            project_name: "g/p".to_string(),
+            title: title.to_string(),
+            description: None,
+            author_username: "alice".to_string(),
+            source_branch: "s".to_string(),
+            target_branch: "main".to_string(),
+            state: "opened".to_string(),
+            web_url: "http://x".to_string(),
+            created_at: 0,
+            updated_at: 7_200,
+            merged_at: None,
+            approval_status: None,
+            approvals_required: None,
+            approvals_count: None,
+            labels: labels.to_string(),
+            reviewers: "[]".to_string(),
+            cached_at: 0,
+            user_has_approved: false,
+            head_pipeline_status: Some("failed".to_string()),
+            state_changed_at: None,
+            watched: false,
+            is_bot_author: false,
+            review_rerequested_at: None,
+            task_count: 0,
+            task_completed_count: 0,
+            discussion_locked: false,
+            changed_since_review: false,
+            milestone_id: None,
+            milestone_title: None,
+            milestone_due_date: None,
+        }
+    }
+
+    fn labels(badges: &[CustomBadge]) -> Vec<&str> {
+        badges.iter().map(|b| b.label.as_str()).collect()
+    }
+
+    #[test]
+    fn rules_badge_matching_mrs() {
+        let set = compile(vec![
+            row(
+                1,
+                "Big break",
+                r#"labels.contains("breaking") && lines > 500"#,
+            ),
+            row(
+                2,
+                "unused",
+                r#"if draft { "WIP " + age_hours + "h" } else { () }"#,
+            ),
+            row(3, "CI", r#"pipeline == "failed""#),
+        ]);
+        let subjects = vec![
+            subject(
+                &mr(1, "Rework API", r#"["breaking"]"#),
+                Some((3, 400, 200)),
+                10_800,
+            ),
+            subject(
+                &mr(2, "Draft: Tidy", r#"["breaking"]"#),
+                Some((1, 1, 1)),
+                10_800,
+            ),
+        ];
+        let badges = run(&set, &subjects);
+        assert_eq!(labels(&badges[0]), vec!["Big break", "CI"]);
+        assert_eq!(labels(&badges[1]), vec!["WIP 3h", "CI"]);
+        assert_eq!(badges[0][0].color.as_deref(), Some("#c00"));
+    }
+
+    #[test]
+    fn runaway_and_failing_rules_are_skipped_and_reported() {
+        let set = compile(vec![
+            row(101, "Loop", "loop { }"),
+            row(102, "Bad", "title.no_such_method()"),
+            row(103, "Ok", "true"),
+            row(104, "Broken", "let = ;"),
+        ]);
+        let subjects = vec![
+            subject(&mr(1, "A", "[]"), None, 0),
+            subject(&mr(2, "B", "[]"), None, 0),
+        ];
+        let started = Instant::now();
+        let badges = run(&set, &subjects);
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(labels(&badges[0]), vec!["Ok"]);
+        assert_eq!(labels(&badges[1]), vec!["Ok"]);
+
+        let errors = rule_errors().lock().unwrap().clone();
+        assert!(errors[&101].starts_with("Exceeded its 5 ms time limit on g/p!1"));
+        assert!(errors[&102].ends_with("on g/p!1"));
+        assert!(!errors.contains_key(&103));
+        assert!(errors[&104].starts_with("Script doesn't compile"));
+    }
+
+    #[test]
+    fn query_budget_skips_the_remaining_evaluations() {
+        let mut slow = row(201, "Slow", "loop { }");
+        slow.time_limit_ms = MAX_TIME_LIMIT_MS;
+        let set = compile(vec![slow, row(202, "Ok", "true")]);
+        let subjects = vec![
+            subject(&mr(1, "A", "[]"), None, 0),
+            subject(&mr(2, "B", "[]"), None, 0),
+        ];
+        let started = Instant::now();
+        let badges = run_within(&set, &subjects, Duration::from_millis(20));
+        assert!(started.elapsed() < Duration::from_millis(MAX_TIME_LIMIT_MS as u64));
+        assert!(badges.iter().all(|b| b.is_empty()));
+
+        let errors = rule_errors().lock().unwrap().clone();
+        assert!(errors[&201].starts_with("Badge rules ran past their 20 ms total"));
+        assert!(errors[&201].ends_with("on g/p!1"));
+        assert!(!errors.contains_key(&202));
+    }
+
+    #[test]
+    fn scripts_are_sandboxed() {
+        let engine = new_engine();
+        assert!(engine.compile(r#"eval("1")"#).is_err());
+
+        let set = compile(vec![
+            row(201, "Import", r#"import "os" as os; true"#),
+            row(202, "Print", r#"print("hi"); "x" * 1"#),
+        ]);
+        let badges = run(&set, &[subject(&mr(1, "A", "[]"), None, 0)]);
+        assert!(badges[0].is_empty());
+
+        let input = |script: &str| BadgeRuleInput {
+            name: "r".to_string(),
+            label: "L".to_string(),
+            color: None,
+            script: script.to_string(),
+            enabled: true,
+            time_limit_ms: None,
+        };
+        assert!(validate(&[input("lines > 1")]).is_ok());
+        assert!(validate(&[input("lines >")]).is_err());
+        assert!(validate(&[BadgeRuleInput {
+            color: Some("red;x".to_string()),
+            ..input("true")
+        }])
+        .is_err());
+        assert!(validate(&[BadgeRuleInput {
+            time_limit_ms: Some(MAX_TIME_LIMIT_MS + 1),
+            ..input("true")
+        }])
+        .is_err());
+    }
+}
@@ -0,0 +1,233 @@
+//! Per-command IPC timings.
+//!
+//! Tauri resolves async commands on a spawned task, out of reach of the
+//! invoke handler, so the round trip is timed where the UI feels it: the
+//! frontend's single invoke chokepoint. It reports samples in small batches
+//! to [`record`], which keeps the most recent [`CAPACITY`] in a ring buffer
+//! and logs a warning for every call slower than [`SLOW_COMMAND_MS`].
+//! [`summarize`] turns the buffer into per-command percentiles.
+//!
+//! Response sizes are only known on the backend, and only for commands that
+//! answer through `ipc_compression::respond`; [`record_response`] tallies
+//! them per command.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, OnceLock};
+
+/// Samples kept across all commands. Older ones are dropped first.
+pub const CAPACITY: usize = 5000;
+
+/// Calls slower than this are logged as warnings.
+pub const SLOW_COMMAND_MS: f64 = 500.0;
+
+/// One timed invoke, as reported by the frontend.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandTiming {
+    pub command: String,
+    /// Round trip from invoke to resolve/reject, in milliseconds.
+    pub duration_ms: f64,
+    /// Size of the serialized arguments in bytes.
+    pub payload_bytes: u64,
+    /// False when the command returned an error.
+    pub ok: bool,
+}
+
+/// Aggregated timings for one command.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandMetric {
+    pub command: String,
+    pub calls: usize,
+    pub errors: usize,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub max_ms: f64,
+    pub avg_payload_bytes: u64,
+    /// Average serialized JSON size of the response, when measured.
+    pub avg_response_bytes: Option<u64>,
+    /// Average bytes actually sent back, after compression.
+    pub avg_sent_bytes: Option<u64>,
+}
+
+/// Running response size totals for one command.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct ResponseSizes {
+    responses: u64,
+    json_bytes: u64,
+    sent_bytes: u64,
+}
+
+fn buffer() -> &'static Mutex<VecDeque<CommandTiming>> {
+    static BUFFER: OnceLock<Mutex<VecDeque<CommandTiming>>> = OnceLock::new();
+    BUFFER.get_or_init(|| Mutex::new(VecDeque::with_capacity(CAPACITY)))
+}
+
+fn response_sizes() -> &'static Mutex<HashMap<String, ResponseSizes>> {
+    static SIZES: OnceLock<Mutex<HashMap<String, ResponseSizes>>> = OnceLock::new();
+    SIZES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn push(buf: &mut VecDeque<CommandTiming>, timing: CommandTiming, capacity: usize) {
+    if buf.len() == capacity {
+        buf.pop_front();
+    }
+    buf.push_back(timing);
+}
+
+/// Add reported samples to the ring buffer.
+pub fn record(timings: Vec<CommandTiming>) {
+    let mut buf = buffer().lock().unwrap_or_else(|e| e.into_inner());
+    for timing in timings {
+        if timing.duration_ms > SLOW_COMMAND_MS {
+            log::warn!(
+                "[ipc] Slow command {}: {:.0} ms ({} byte payload{})",
+                timing.command,
+                timing.duration_ms,
+                timing.payload_bytes,
+                if timing.ok { "" } else { ", failed" }
+            );
+        }
+        push(&mut buf, timing, CAPACITY);
+    }
+}
+
+/// Add one response of `command`: its JSON size and the bytes sent.
+pub fn record_response(command: &str, json_bytes: usize, sent_bytes: usize) {
+    let mut sizes = response_sizes().lock().unwrap_or_else(|e| e.into_inner());
+    let entry = sizes.entry(command.to_string()).or_default();
+    entry.responses += 1;
+    entry.json_bytes += json_bytes as u64;
+    entry.sent_bytes += sent_bytes as u64;
+}
+
+/// Nearest-rank percentile of sorted durations.
+fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn summarize_samples<'a>(samples: impl Iterator<Item = &'a CommandTiming>) -> Vec<CommandMetric> {
+    let mut by_command: HashMap<&str, Vec<&CommandTiming>> = HashMap::new();
+    for sample in samples {
+        by_command.entry(&sample.command).or_default().push(sample);
+    }
+
+    let mut metrics: Vec<CommandMetric> = by_command
+        .into_iter()
+        .map(|(command, samples)| {
+            let mut durations: Vec<f64> = samples.iter().map(|s| s.duration_ms).collect();
+            durations.sort_by(f64::total_cmp);
+            let payload: u64 = samples.iter().map(|s| s.payload_bytes).sum();
+            CommandMetric {
+                command: command.to_string(),
+                calls: samples.len(),
+                errors: samples.iter().filter(|s| !s.ok).count(),
+                p50_ms: percentile(&durations, 50.0),
+                p95_ms: percentile(&durations, 95.0),
+                max_ms: durations.last().copied().unwrap_or(0.0),
+                avg_payload_bytes: payload / samples.len() as u64,
+                avg_response_bytes: None,
+                avg_sent_bytes: None,
+            }
+        })
+        .collect();
+    // Slowest first; ties by name so the order is stable.
+    metrics.sort_by(|a, b| {
+        b.p95_ms
+            .total_cmp(&a.p95_ms)
+            .then_with(|| a.command.cmp(&b.command))
+    });
+    metrics
+}
+
+fn attach_response_sizes(metrics: &mut [CommandMetric], sizes: &HashMap<String, ResponseSizes>) {
+    for metric in metrics {
+        if let Some(size) = sizes.get(&metric.command).filter(|s| s.responses > 0) {
+            metric.avg_response_bytes = Some(size.json_bytes / size.responses);
+            metric.avg_sent_bytes = Some(size.sent_bytes / size.responses);
+        }
+    }
+}
+
+/// Per-command percentiles over the samples currently buffered, with
+/// response sizes where they were recorded.
+pub fn summarize() -> Vec<CommandMetric> {
+    let mut metrics = {
+        let buf = buffer().lock().unwrap_or_else(|e| e.into_inner());
+        summarize_samples(buf.iter())
+    };
+    let sizes = response_sizes().lock().unwrap_or_else(|e| e.into_inner());
+    attach_response_sizes(&mut metrics, &sizes);
+    metrics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(command: &str, duration_ms: f64, ok: bool) -> CommandTiming {
+        CommandTiming {
+            command: command.to_string(),
+            duration_ms,
+            payload_bytes: 10,
+            ok,
+        }
+    }
+
+    #[test]
+    fn summarizes_percentiles_per_command() {
+        let mut samples: Vec<CommandTiming> = (1..=100)
+            .map(|ms| timing("get_merge_requests", ms as f64, ms != 7))
+            .collect();
+        samples.push(timing("get_settings", 2.0, true));
+
+        let metrics = summarize_samples(samples.iter());
+        assert_eq!(metrics.len(), 2);
+        let mrs = &metrics[0];
+        assert_eq!(mrs.command, "get_merge_requests");
+        assert_eq!(mrs.calls, 100);
+        assert_eq!(mrs.errors, 1);
+        assert_eq!(mrs.p50_ms, 50.0);
+        assert_eq!(mrs.p95_ms, 95.0);
+        assert_eq!(mrs.max_ms, 100.0);
+        assert_eq!(mrs.avg_payload_bytes, 10);
+        assert_eq!(metrics[1].p95_ms, 2.0);
+    }
+
+    #[test]
+    fn attaches_average_response_sizes() {
+        let samples = [
+            timing("get_diff_files", 40.0, true),
+            timing("get_settings", 2.0, true),
+        ];
+        let mut metrics = summarize_samples(samples.iter());
+        let sizes = HashMap::from([(
+            "get_diff_files".to_string(),
+            ResponseSizes {
+                responses: 2,
+                json_bytes: 4_000_000,
+                sent_bytes: 600_000,
+            },
+        )]);
+        attach_response_sizes(&mut metrics, &sizes);
+
+        assert_eq!(metrics[0].avg_response_bytes, Some(2_000_000));
+        assert_eq!(metrics[0].avg_sent_bytes, Some(300_000));
+        assert_eq!(metrics[1].avg_response_bytes, None);
+    }
+
+    #[test]
+    fn ring_buffer_drops_oldest() {
+        let mut buf = VecDeque::new();
+        for ms in 0..5 {
+            push(&mut buf, timing("a", ms as f64, true), 3);
+        }
+        let kept: Vec<f64> = buf.iter().map(|t| t.duration_ms).collect();
+        assert_eq!(kept, vec![2.0, 3.0, 4.0]);
+    }
+}
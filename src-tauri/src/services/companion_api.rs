@@ -3,19 +3,34 @@
 //! These routes expose MR read operations over HTTP, mirroring the Tauri commands
 //! so the mobile web frontend can access the same data via fetch() instead of invoke().
 
+use crate::core::change_log::{self, ChangesSince, MrDelta};
+use crate::core::comment_order::{self, CommentSort};
+use crate::core::file_blame::{self, FileBlame};
+use crate::core::file_review::{self, FileReviewState};
+use crate::core::language_stats::{self, LanguageBreakdown};
+use crate::core::markdown::ProjectContext;
+use crate::core::mr_actions::{self, ApproveResult};
+use crate::core::mr_commits::{self, CommitDiff};
+use crate::core::review_progress::{self, ChangesSinceReview};
+use crate::db::mr_commits::MrCommit;
+use crate::db::mr_events::MrEvent;
 use crate::db::pool::DbPool;
 use crate::error::AppError;
 use crate::models::sync_action::ActionType;
 use crate::models::{Comment, Diff, DiffFile, GitLabInstance, MergeRequest, MrReviewer};
+use crate::services::companion_events;
 use crate::services::companion_server::CompanionState;
-use crate::services::sync_queue::{self, ApprovalPayload, EnqueueInput};
-use axum::extract::{Path, Query, State};
+use crate::services::sync_queue::{self, EnqueueInput};
+use axum::extract::{DefaultBodyLimit, Path, Query, State};
 use axum::http::StatusCode;
+use axum::response::sse::{Event, KeepAlive, Sse};
 use axum::response::{IntoResponse, Response};
 use axum::routing::{get, post};
 use axum::{Json, Router};
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 use sqlx::Row;
+use std::convert::Infallible;
 
 // ── Error handling ───────────────────────────────────────────────────────────
 
@@ -67,10 +82,10 @@ impl From<sqlx::Error> for ApiErr {
 use crate::commands::approval::ApprovalStatus;
 use crate::commands::comments::CommentResponse;
 use crate::commands::mr::{
-    DiffFileSummary, DiffHunk, DiffHunksResponse, DiffRefsResponse, DiffSummary,
+    list_items, DiffFileSummary, DiffHunk, DiffHunksResponse, DiffRefsResponse, DiffSummary,
     MergeRequestDetail, MergeRequestListItem,
 };
-use crate::commands::settings::AppSettings;
+use crate::commands::settings::{AppSettings, ListViewState};
 use crate::services::sync_engine::{SyncEngine, SyncLogEntry};
 
 // ── Query parameter types ────────────────────────────────────────────────────
@@ -86,6 +101,14 @@ struct MrListQuery {
 struct HunksQuery {
     start: Option<usize>,
     count: Option<usize>,
+    ignore_whitespace: Option<bool>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CommentsQuery {
+    sort: Option<CommentSort>,
+    context_lines: Option<u32>,
 }
 
 #[derive(Deserialize)]
@@ -115,6 +138,8 @@ struct InstanceResponse {
     has_token: bool,
     created_at: i64,
     authenticated_username: Option<String>,
+    /// Whether the token may comment and approve.
+    can_write: bool,
 }
 
 impl From<GitLabInstance> for InstanceResponse {
@@ -126,6 +151,7 @@ impl From<GitLabInstance> for InstanceResponse {
             has_token: inst.token.is_some(),
             created_at: inst.created_at,
             authenticated_username: inst.authenticated_username,
+            can_write: true,
         }
     }
 }
@@ -140,6 +166,7 @@ pub fn mr_api_routes() -> Router<CompanionState> {
     Router::new()
         .route("/api/instances", get(get_instances))
         .route("/api/merge-requests", get(get_merge_requests))
+        .route("/api/merge-requests/delta", get(get_mr_delta_handler))
         .route("/api/merge-requests/{id}", get(get_merge_request_detail))
         .route("/api/merge-requests/{id}/files", get(get_diff_files))
         .route(
@@ -150,6 +177,28 @@ pub fn mr_api_routes() -> Router<CompanionState> {
             "/api/merge-requests/{mr_id}/files/{file_path}/content",
             get(get_file_content),
         )
+        .route(
+            "/api/merge-requests/{mr_id}/files/{file_path}/blame",
+            get(get_file_blame),
+        )
+        .route("/api/merge-requests/{id}/commits", get(get_mr_commits))
+        .route("/api/merge-requests/{id}/activity", get(get_mr_activity))
+        .route(
+            "/api/merge-requests/{id}/languages",
+            get(get_mr_language_breakdown),
+        )
+        .route(
+            "/api/merge-requests/{mr_id}/commits/{sha}/diff",
+            get(get_commit_diff),
+        )
+        .route(
+            "/api/merge-requests/{id}/changes-since-review",
+            get(get_changes_since_review),
+        )
+        .route(
+            "/api/merge-requests/{id}/file-review-states",
+            get(get_file_review_states),
+        )
         .route("/api/merge-requests/{id}/comments", get(get_comments))
         .route("/api/merge-requests/{id}/reviewers", get(get_reviewers))
         .route("/api/merge-requests/{id}/diff-refs", get(get_diff_refs))
@@ -158,6 +207,10 @@ pub fn mr_api_routes() -> Router<CompanionState> {
             get(get_file_comments),
         )
         .route("/api/file-content", get(get_file_content_direct))
+        .route(
+            "/api/markdown",
+            post(render_markdown).layer(DefaultBodyLimit::max(MAX_MARKDOWN_REQUEST_BYTES)),
+        )
         .route("/api/my-merge-requests", get(get_my_merge_requests))
 }
 
@@ -172,9 +225,23 @@ async fn get_instances(
     )
     .fetch_all(&state.db)
     .await?;
+    let read_only: Vec<i64> =
+        sqlx::query_as::<_, (i64, Option<String>)>("SELECT id, token_scopes FROM gitlab_instances")
+            .fetch_all(&state.db)
+            .await?
+            .into_iter()
+            .filter(|(_, scopes)| !crate::core::token_scopes::allows_write(scopes.as_deref()))
+            .map(|(id, _)| id)
+            .collect();
 
     Ok(Json(
-        instances.into_iter().map(InstanceResponse::from).collect(),
+        instances
+            .into_iter()
+            .map(|inst| InstanceResponse {
+                can_write: !read_only.contains(&inst.id),
+                ..InstanceResponse::from(inst)
+            })
+            .collect(),
     ))
 }
 
@@ -191,7 +258,7 @@ async fn get_merge_requests(
         SELECT
             mr.id, mr.instance_id, mr.iid, mr.project_id,
             COALESCE(p.name_with_namespace, mr.project_name) AS project_name,
-            mr.title, mr.description,
+            mr.title, mr.description_preview AS description,
             mr.author_username, mr.source_branch, mr.target_branch, mr.state,
             mr.web_url, mr.created_at, mr.updated_at, mr.merged_at,
             mr.approval_status, mr.approvals_required, mr.approvals_count,
@@ -262,8 +329,7 @@ async fn get_merge_requests(
         }
     };
 
-    let items = mrs.into_iter().map(MergeRequestListItem::from).collect();
-    Ok(Json(items))
+    Ok(Json(list_items(db, mrs).await?))
 }
 
 /// GET /api/my-merge-requests?instance_id=X — list MRs authored by the authenticated user.
@@ -293,7 +359,7 @@ async fn get_my_merge_requests(
         SELECT
             mr.id, mr.instance_id, mr.iid, mr.project_id,
             COALESCE(p.name_with_namespace, mr.project_name) AS project_name,
-            mr.title, mr.description,
+            mr.title, mr.description_preview AS description,
             mr.author_username, mr.source_branch, mr.target_branch, mr.state,
             mr.web_url, mr.created_at, mr.updated_at, mr.merged_at,
             mr.approval_status, mr.approvals_required, mr.approvals_count,
@@ -310,8 +376,7 @@ async fn get_my_merge_requests(
     .fetch_all(db)
     .await?;
 
-    let items = mrs.into_iter().map(MergeRequestListItem::from).collect();
-    Ok(Json(items))
+    Ok(Json(list_items(db, mrs).await?))
 }
 
 /// GET /api/merge-requests/:id — MR detail with diff summary.
@@ -408,7 +473,7 @@ async fn get_diff_files(
     Ok(Json(diff_files))
 }
 
-/// GET /api/merge-requests/:mr_id/files/:file_path/hunks?start=0&count=10 — paginated diff hunks.
+/// GET /api/merge-requests/:mr_id/files/:file_path/hunks?start=0&count=10&ignore_whitespace=true — paginated diff hunks.
 async fn get_diff_hunks(
     State(state): State<CompanionState>,
     Path((mr_id, file_path)): Path<(i64, String)>,
@@ -433,19 +498,24 @@ async fn get_diff_hunks(
         )))
     })?;
 
-    let diff_content = file.diff_content.unwrap_or_default();
-    let all_hunks = crate::commands::mr::parse_unified_diff_public(&diff_content);
+    let (all_hunks, whitespace_ignored) = crate::commands::mr::file_hunks(
+        &state.db,
+        &file,
+        params.ignore_whitespace.unwrap_or(false),
+    )
+    .await?;
     let total_hunks = all_hunks.len();
 
     let start = params.start.unwrap_or(0);
     let count = params.count.unwrap_or(total_hunks);
     let end = (start + count).min(total_hunks);
-    let hunks: Vec<DiffHunk> = all_hunks
+    let mut hunks: Vec<DiffHunk> = all_hunks
         .into_iter()
         .skip(start)
         .take(end - start)
         .collect();
     let has_more = end < total_hunks;
+    crate::commands::mr::highlight_hunks(&state.db, mr_id, &file.new_path, &mut hunks).await?;
 
     Ok(Json(DiffHunksResponse {
         file_path: file.new_path,
@@ -453,6 +523,7 @@ async fn get_diff_hunks(
         start_index: start,
         total_hunks,
         has_more,
+        whitespace_ignored,
     }))
 }
 
@@ -513,24 +584,171 @@ async fn get_file_content(
     Ok(Json(content))
 }
 
-/// GET /api/merge-requests/:id/comments — comments for an MR.
+/// GET /api/merge-requests/:mr_id/files/:file_path/blame — blame at the MR's head SHA.
+async fn get_file_blame(
+    State(state): State<CompanionState>,
+    Path((mr_id, file_path)): Path<(i64, String)>,
+) -> Result<Json<FileBlame>, ApiErr> {
+    let blame =
+        file_blame::mr_file_blame(&state.db, mr_id, &file_path, chrono::Utc::now().timestamp())
+            .await
+            .map_err(ApiErr::from)?;
+
+    Ok(Json(blame))
+}
+
+/// GET /api/merge-requests/:id/commits — the MR's commits, oldest first.
+async fn get_mr_commits(
+    State(state): State<CompanionState>,
+    Path(mr_id): Path<i64>,
+) -> Result<Json<Vec<MrCommit>>, ApiErr> {
+    let commits = crate::db::mr_commits::list_commits(&state.db, mr_id).await?;
+    Ok(Json(commits))
+}
+
+/// GET /api/merge-requests/:id/activity — the MR's activity timeline, oldest first.
+async fn get_mr_activity(
+    State(state): State<CompanionState>,
+    Path(mr_id): Path<i64>,
+) -> Result<Json<Vec<MrEvent>>, ApiErr> {
+    let events = crate::core::mr_activity::timeline(&state.db, mr_id).await?;
+    Ok(Json(events))
+}
+
+/// GET /api/merge-requests/:id/languages — lines changed per language.
+async fn get_mr_language_breakdown(
+    State(state): State<CompanionState>,
+    Path(mr_id): Path<i64>,
+) -> Result<Json<LanguageBreakdown>, ApiErr> {
+    let breakdown = language_stats::get_language_breakdown(&state.db, mr_id).await?;
+    Ok(Json(breakdown))
+}
+
+/// Longest text POST /api/markdown renders, in bytes.
+const MAX_MARKDOWN_TEXT_BYTES: usize = 256 * 1024;
+
+/// Request body cap for POST /api/markdown: the text limit plus room for JSON
+/// escaping and the project context.
+const MAX_MARKDOWN_REQUEST_BYTES: usize = 2 * MAX_MARKDOWN_TEXT_BYTES;
+
+/// POST /api/markdown — GitLab-flavored markdown rendered to sanitized HTML,
+/// the same as the desktop app shows.
+///
+/// Texts sent here are previews and drafts, so they're rendered without
+/// going through the markdown cache.
+async fn render_markdown(
+    State(state): State<CompanionState>,
+    Json(body): Json<RenderMarkdownRequest>,
+) -> Result<Response, ApiErr> {
+    if body.text.len() > MAX_MARKDOWN_TEXT_BYTES {
+        return Ok((
+            StatusCode::PAYLOAD_TOO_LARGE,
+            Json(ApiError {
+                code: "PAYLOAD_TOO_LARGE".to_string(),
+                message: format!(
+                    "Markdown text is limited to {} KiB",
+                    MAX_MARKDOWN_TEXT_BYTES / 1024
+                ),
+            }),
+        )
+            .into_response());
+    }
+    let html =
+        crate::core::markdown::render_uncached(&state.db, &body.text, body.project_context).await?;
+    Ok(Json(html).into_response())
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RenderMarkdownRequest {
+    text: String,
+    project_context: Option<ProjectContext>,
+}
+
+/// GET /api/merge-requests/:mr_id/commits/:sha/diff — files one commit changed.
+async fn get_commit_diff(
+    State(state): State<CompanionState>,
+    Path((mr_id, sha)): Path<(i64, String)>,
+) -> Result<Json<CommitDiff>, ApiErr> {
+    let diff = mr_commits::commit_diff(&state.db, mr_id, &sha, chrono::Utc::now().timestamp())
+        .await
+        .map_err(ApiErr::from)?;
+
+    Ok(Json(diff))
+}
+
+/// GET /api/merge-requests/:id/changes-since-review — the MR's changes since
+/// it was last marked reviewed.
+async fn get_changes_since_review(
+    State(state): State<CompanionState>,
+    Path(mr_id): Path<i64>,
+) -> Result<Json<ChangesSinceReview>, ApiErr> {
+    let changes = review_progress::changes_since_review(&state.db, mr_id)
+        .await
+        .map_err(ApiErr::from)?;
+    Ok(Json(changes))
+}
+
+/// POST /api/merge-requests/:id/reviewed — mark the MR reviewed at its head.
+async fn mark_mr_reviewed(
+    State(state): State<CompanionState>,
+    Path(mr_id): Path<i64>,
+) -> Result<Json<String>, ApiErr> {
+    let sha = review_progress::mark_reviewed(&state.db, mr_id)
+        .await
+        .map_err(ApiErr::from)?;
+    Ok(Json(sha))
+}
+
+/// GET /api/merge-requests/:id/file-review-states — files marked viewed at
+/// the MR's current head.
+async fn get_file_review_states(
+    State(state): State<CompanionState>,
+    Path(mr_id): Path<i64>,
+) -> Result<Json<Vec<FileReviewState>>, ApiErr> {
+    let states = file_review::states(&state.db, mr_id)
+        .await
+        .map_err(ApiErr::from)?;
+    Ok(Json(states))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MarkFileViewedRequest {
+    file_path: String,
+    viewed: bool,
+}
+
+/// POST /api/merge-requests/:id/viewed-files — mark a file viewed or unviewed.
+async fn mark_file_viewed(
+    State(state): State<CompanionState>,
+    Path(mr_id): Path<i64>,
+    Json(body): Json<MarkFileViewedRequest>,
+) -> Result<Json<()>, ApiErr> {
+    file_review::mark_viewed(
+        &state.db,
+        mr_id,
+        &body.file_path,
+        body.viewed,
+        chrono::Utc::now().timestamp(),
+    )
+    .await
+    .map_err(ApiErr::from)?;
+    Ok(Json(()))
+}
+
+/// GET /api/merge-requests/:id/comments?sort=X&contextLines=N — comments for
+/// an MR, optionally with code around each inline thread.
 async fn get_comments(
     State(state): State<CompanionState>,
     Path(mr_id): Path<i64>,
+    Query(params): Query<CommentsQuery>,
 ) -> Result<Json<Vec<CommentResponse>>, ApiErr> {
-    let comments: Vec<Comment> = sqlx::query_as(
-        r#"
-        SELECT id, mr_id, discussion_id, parent_id, author_username, body,
-               file_path, old_line, new_line, line_type, resolved, resolvable,
-               system, created_at, updated_at, cached_at, is_local
-        FROM comments WHERE mr_id = ? ORDER BY created_at ASC
-        "#,
-    )
-    .bind(mr_id)
-    .fetch_all(&state.db)
-    .await?;
+    let comments = comment_order::list(&state.db, mr_id, params.sort.unwrap_or_default())
+        .await
+        .map_err(ApiErr::from)?;
 
-    let responses = to_comment_responses(&state.db, comments)
+    let responses = to_comment_responses(&state.db, comments, params.context_lines)
         .await
         .map_err(ApiErr::from)?;
 
@@ -544,7 +762,7 @@ async fn get_reviewers(
 ) -> Result<Json<Vec<MrReviewer>>, ApiErr> {
     let reviewers: Vec<MrReviewer> = sqlx::query_as(
         r#"
-        SELECT mr_id, username, status, cached_at
+        SELECT mr_id, username, status, cached_at, review_state, rerequested_at
         FROM mr_reviewers WHERE mr_id = ? ORDER BY username
         "#,
     )
@@ -606,7 +824,7 @@ async fn get_file_comments(
     .fetch_all(&state.db)
     .await?;
 
-    let responses = to_comment_responses(&state.db, comments)
+    let responses = to_comment_responses(&state.db, comments, None)
         .await
         .map_err(ApiErr::from)?;
 
@@ -662,7 +880,13 @@ async fn get_file_content_direct(
 async fn to_comment_responses(
     pool: &DbPool,
     comments: Vec<Comment>,
+    context: Option<u32>,
 ) -> Result<Vec<CommentResponse>, AppError> {
+    let mut by_comment = crate::core::suggestions::for_comments(pool, &comments).await?;
+    let mut snippets = match context {
+        Some(lines) => crate::core::comment_context::for_comments(pool, &comments, lines).await?,
+        None => Default::default(),
+    };
     let mut responses = Vec::with_capacity(comments.len());
     for comment in comments {
         let sync_status = if comment.is_local {
@@ -689,6 +913,8 @@ async fn to_comment_responses(
             updated_at: comment.updated_at,
             is_local: comment.is_local,
             sync_status,
+            suggestions: by_comment.remove(&comment.id).unwrap_or_default(),
+            snippet: snippets.remove(&comment.id),
         });
     }
     Ok(responses)
@@ -726,60 +952,52 @@ pub fn action_api_routes() -> Router<CompanionState> {
             "/api/merge-requests/{id}/approval-status",
             get(get_approval_status_handler),
         )
+        // Review progress
+        .route("/api/merge-requests/{id}/reviewed", post(mark_mr_reviewed))
+        .route(
+            "/api/merge-requests/{id}/viewed-files",
+            post(mark_file_viewed),
+        )
         // Sync
         .route("/api/sync/status", get(get_sync_status_handler))
         .route("/api/sync/trigger", post(trigger_sync_handler))
+        .route("/api/changes", get(get_changes_since_handler))
+        .route("/api/events", get(events_handler))
         // Settings (read-only)
         .route("/api/settings", get(get_settings_handler))
+        // MR list view state, shared with the desktop app
+        .route(
+            "/api/list-view-state",
+            get(get_list_view_state_handler).post(update_list_view_state_handler),
+        )
 }
 
 // ── Approval handlers ────────────────────────────────────────────────────────
 
-/// POST /api/merge-requests/:id/approve — approve an MR.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApproveRequest {
+    /// Approve even if there are review blockers.
+    #[serde(default)]
+    confirm: bool,
+}
+
+/// POST /api/merge-requests/:id/approve — approve an MR, checked against the
+/// approval gate and review blockers like the desktop command.
 async fn approve_mr_handler(
     State(state): State<CompanionState>,
     Path(mr_id): Path<i64>,
-) -> Result<Json<()>, ApiErr> {
-    let (project_id, mr_iid) = get_mr_ids(&state.db, mr_id).await?;
-
-    // Optimistic local update
-    sqlx::query(
-        r#"
-        UPDATE merge_requests
-        SET approvals_count = COALESCE(approvals_count, 0) + 1,
-            approval_status = CASE
-                WHEN COALESCE(approvals_count, 0) + 1 >= COALESCE(approvals_required, 1)
-                THEN 'approved'
-                ELSE 'pending'
-            END,
-            user_has_approved = 1
-        WHERE id = ?
-        "#,
-    )
-    .bind(mr_id)
-    .execute(&state.db)
-    .await?;
-
-    // Queue for sync
-    let payload = serde_json::to_string(&ApprovalPayload { project_id, mr_iid })
-        .map_err(|e| ApiErr(AppError::internal(e.to_string())))?;
-
-    sync_queue::enqueue_action(
-        &state.db,
-        EnqueueInput {
-            mr_id,
-            action_type: ActionType::Approve,
-            payload,
-            local_reference_id: None,
-        },
-    )
-    .await
-    .map_err(ApiErr::from)?;
+    body: Option<Json<ApproveRequest>>,
+) -> Result<Json<ApproveResult>, ApiErr> {
+    let confirm = body.map(|Json(body)| body.confirm).unwrap_or_default();
+    let result = mr_actions::queue_approval(&state.db, mr_id, confirm).await?;
 
     // Fire-and-forget: flush approval actions immediately
-    let _ = state.sync_handle.flush_approvals().await;
+    if result.approved {
+        let _ = state.sync_handle.flush_approvals().await;
+    }
 
-    Ok(Json(()))
+    Ok(Json(result))
 }
 
 /// POST /api/merge-requests/:id/unapprove — remove approval from an MR.
@@ -787,6 +1005,7 @@ async fn unapprove_mr_handler(
     State(state): State<CompanionState>,
     Path(mr_id): Path<i64>,
 ) -> Result<Json<()>, ApiErr> {
+    crate::core::token_scopes::ensure_can_write(&state.db, mr_id).await?;
     let (project_id, mr_iid) = get_mr_ids(&state.db, mr_id).await?;
 
     // Optimistic local update
@@ -893,6 +1112,18 @@ struct SyncStatusResponse {
 }
 
 /// GET /api/sync/status — get sync engine status.
+/// GET /api/events — live `mr-updated`, `sync-progress` and
+/// `action-synced` events as server-sent events.
+async fn events_handler(
+    State(state): State<CompanionState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    Sse::new(companion_events::sse_stream(
+        state.events.subscribe(),
+        state.shutdown.clone(),
+    ))
+    .keep_alive(KeepAlive::default())
+}
+
 async fn get_sync_status_handler(
     State(state): State<CompanionState>,
 ) -> Result<Json<SyncStatusResponse>, ApiErr> {
@@ -946,14 +1177,73 @@ async fn trigger_sync_handler(State(state): State<CompanionState>) -> Result<Jso
     Ok(Json(()))
 }
 
+#[derive(Deserialize)]
+struct ChangesQuery {
+    cursor: Option<i64>,
+}
+
+/// GET /api/changes?cursor=N — cache changes after a cursor, for catching up
+/// after a reconnect. Omit `cursor` to get the current one.
+async fn get_changes_since_handler(
+    State(state): State<CompanionState>,
+    Query(params): Query<ChangesQuery>,
+) -> Result<Json<ChangesSince>, ApiErr> {
+    let changes = change_log::changes_since(&state.db, params.cursor).await?;
+    Ok(Json(changes))
+}
+
+#[derive(Deserialize)]
+struct MrDeltaQuery {
+    since: Option<i64>,
+}
+
+/// GET /api/merge-requests/delta?since=N — created, updated and removed MRs
+/// after a change-log cursor, one entry per MR. Omit `since` to get the
+/// current cursor.
+async fn get_mr_delta_handler(
+    State(state): State<CompanionState>,
+    Query(params): Query<MrDeltaQuery>,
+) -> Result<Json<MrDelta>, ApiErr> {
+    let delta = change_log::mr_delta(&state.db, params.since).await?;
+    Ok(Json(delta))
+}
+
 // ── Settings handler ─────────────────────────────────────────────────────────
 
 /// GET /api/settings — get app settings (read-only).
 async fn get_settings_handler(
     State(state): State<CompanionState>,
 ) -> Result<Json<AppSettings>, ApiErr> {
-    let settings = crate::commands::settings::load_settings(&state.app_handle)
+    let mut settings = crate::commands::settings::load_settings(&state.app_handle)
         .await
         .map_err(ApiErr::from)?;
+    // The translation API key is a third-party credential; phones don't need it.
+    settings.translation.api_key = None;
     Ok(Json(settings))
 }
+
+/// GET /api/list-view-state — get the MR list view state.
+async fn get_list_view_state_handler(
+    State(state): State<CompanionState>,
+) -> Result<Json<ListViewState>, ApiErr> {
+    let settings = crate::commands::settings::load_settings(&state.app_handle)
+        .await
+        .map_err(ApiErr::from)?;
+    Ok(Json(settings.list_view_state))
+}
+
+/// POST /api/list-view-state — replace the MR list view state.
+async fn update_list_view_state_handler(
+    State(state): State<CompanionState>,
+    Json(body): Json<UpdateListViewStateRequest>,
+) -> Result<Json<ListViewState>, ApiErr> {
+    let stored = crate::commands::settings::set_list_view_state(&state.app_handle, body.state)
+        .await
+        .map_err(ApiErr::from)?;
+    Ok(Json(stored))
+}
+
+#[derive(Deserialize)]
+struct UpdateListViewStateRequest {
+    state: ListViewState,
+}
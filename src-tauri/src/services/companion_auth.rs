@@ -4,9 +4,14 @@
 //! limiting, and auth middleware for axum routes. Sessions are stored
 //! alongside authorized device metadata so that revoking a device from
 //! settings immediately invalidates the corresponding session.
+//!
+//! Devices that can't scan the QR code can pair with a one-time code
+//! instead: the companion UI requests pairing, the desktop app shows the
+//! code for confirmation, and the companion UI polls until it is confirmed.
 
 use crate::commands::companion_settings::AuthorizedDevice;
 use chrono::Utc;
+use rand::Rng;
 use std::collections::HashMap;
 use std::net::IpAddr;
 use std::sync::OnceLock;
@@ -22,6 +27,8 @@ pub struct SessionStore {
     sessions: HashMap<String, String>,
     /// Rate limit tracking: IP → list of failed attempt timestamps.
     rate_limits: HashMap<IpAddr, Vec<i64>>,
+    /// Code-based pairing requests: request ID → pairing.
+    pairings: HashMap<String, PendingPairing>,
 }
 
 impl SessionStore {
@@ -29,8 +36,23 @@ impl SessionStore {
         Self {
             sessions: HashMap::new(),
             rate_limits: HashMap::new(),
+            pairings: HashMap::new(),
         }
     }
+
+    /// Drop pairing requests whose code has expired.
+    fn prune_pairings(&mut self, now: i64) {
+        self.pairings.retain(|_, p| p.expires_at > now);
+    }
+}
+
+/// A pairing request waiting for (or having received) desktop confirmation.
+struct PendingPairing {
+    code: String,
+    device_name: String,
+    expires_at: i64,
+    /// Session token, set once the desktop confirms the code.
+    token: Option<String>,
 }
 
 /// Global session store singleton.
@@ -116,6 +138,107 @@ pub async fn verify_pin_and_create_session(
     Ok((token, device))
 }
 
+/// How long a pairing code stays valid, in seconds.
+pub const PAIRING_TTL_SECS: i64 = 120;
+
+/// Start a code-based pairing request.
+///
+/// Returns `(request_id, code, expires_at)`. The code is unique among the
+/// pairing requests currently pending.
+pub async fn start_pairing(device_name: String) -> (String, String, i64) {
+    let mut store = store().write().await;
+    let now = Utc::now().timestamp();
+    store.prune_pairings(now);
+
+    let code = loop {
+        let candidate = format!("{:06}", rand::thread_rng().gen_range(0..1_000_000u32));
+        if !store.pairings.values().any(|p| p.code == candidate) {
+            break candidate;
+        }
+    };
+    let request_id = uuid::Uuid::new_v4().to_string();
+    let expires_at = now + PAIRING_TTL_SECS;
+    store.pairings.insert(
+        request_id.clone(),
+        PendingPairing {
+            code: code.clone(),
+            device_name,
+            expires_at,
+            token: None,
+        },
+    );
+
+    (request_id, code, expires_at)
+}
+
+/// Confirm a pending pairing by its code and create a session for it.
+///
+/// Returns the new `AuthorizedDevice` to persist, or `None` if no unexpired,
+/// unconfirmed request has that code.
+pub async fn confirm_pairing_code(code: &str) -> Option<AuthorizedDevice> {
+    let mut store = store().write().await;
+    let now = Utc::now();
+    store.prune_pairings(now.timestamp());
+
+    let pairing = store
+        .pairings
+        .values_mut()
+        .find(|p| p.code == code && p.token.is_none())?;
+
+    let token = uuid::Uuid::new_v4().to_string();
+    pairing.token = Some(token.clone());
+    let device = AuthorizedDevice {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: pairing.device_name.clone(),
+        token: token.clone(),
+        last_active: now,
+        created_at: now,
+    };
+    store.sessions.insert(token, device.id.clone());
+
+    Some(device)
+}
+
+/// Reject a pending pairing by its code. Returns `true` if one was removed.
+pub async fn reject_pairing_code(code: &str) -> bool {
+    let mut store = store().write().await;
+    let before = store.pairings.len();
+    store
+        .pairings
+        .retain(|_, p| !(p.code == code && p.token.is_none()));
+    store.pairings.len() != before
+}
+
+/// Status of a pairing request as seen by the companion device.
+#[derive(Debug, PartialEq, Eq)]
+pub enum PairingStatus {
+    /// Waiting for the desktop to confirm.
+    Pending,
+    /// Confirmed; carries the new session token.
+    Confirmed(String),
+    /// Rejected, expired, or already collected.
+    Gone,
+}
+
+/// Check a pairing request. A confirmed request is removed once its token
+/// has been handed out, so the token can only be collected once.
+pub async fn poll_pairing(request_id: &str) -> PairingStatus {
+    let mut store = store().write().await;
+    store.prune_pairings(Utc::now().timestamp());
+
+    let confirmed = match store.pairings.get(request_id) {
+        None => return PairingStatus::Gone,
+        Some(p) => p.token.is_some(),
+    };
+    if !confirmed {
+        return PairingStatus::Pending;
+    }
+    match store.pairings.remove(request_id).and_then(|p| p.token) {
+        Some(token) => PairingStatus::Confirmed(token),
+        None => PairingStatus::Gone,
+    }
+}
+
 /// Validate a session token. Returns `true` if the token is active.
 pub async fn validate_token(token: &str) -> bool {
     let store = store().read().await;
@@ -212,6 +335,8 @@ pub async fn auth_middleware(request: Request<Body>, next: Next) -> Response {
 #[derive(Clone)]
 pub struct AuthState {
     pub app_handle: tauri::AppHandle,
+    /// Path the session cookie is scoped to: the configured base path, or `/`.
+    pub cookie_path: String,
 }
 
 /// POST /api/auth/verify-pin request body.
@@ -226,6 +351,16 @@ pub struct VerifyPinResponse {
     pub token: String,
 }
 
+/// `Set-Cookie` value for a new session token, with a 30-day expiry.
+fn session_cookie(token: &str, path: &str) -> String {
+    format!(
+        "companion_token={}; Path={}; Max-Age={}; SameSite=Lax",
+        token,
+        path,
+        30 * 24 * 60 * 60
+    )
+}
+
 /// Handler for POST /api/auth/verify-pin.
 pub async fn verify_pin_handler(
     axum::extract::State(state): axum::extract::State<AuthState>,
@@ -259,13 +394,7 @@ pub async fn verify_pin_handler(
             let _ = crate::commands::settings::save_settings(&state.app_handle, &updated).await;
             *crate::commands::settings::settings_cache().write().await = updated;
 
-            // Set cookie with 30-day expiry
-            let cookie = format!(
-                "companion_token={}; Path=/; Max-Age={}; SameSite=Lax",
-                token,
-                30 * 24 * 60 * 60
-            );
-
+            let cookie = session_cookie(&token, &state.cookie_path);
             let mut response = Json(VerifyPinResponse { token }).into_response();
             response
                 .headers_mut()
@@ -292,6 +421,120 @@ pub async fn verify_pin_handler(
     }
 }
 
+/// Event: companion-pairing-requested
+/// Emitted to the desktop app when a companion device asks to pair by code.
+pub const PAIRING_REQUESTED_EVENT: &str = "companion-pairing-requested";
+
+/// Payload for the pairing-requested event.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PairingRequestedPayload {
+    pub code: String,
+    pub device_name: String,
+    /// Unix timestamp after which the code can no longer be confirmed.
+    pub expires_at: i64,
+}
+
+/// POST /api/auth/pair request body.
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PairRequest {
+    pub device_name: Option<String>,
+}
+
+/// POST /api/auth/pair response.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PairResponse {
+    pub request_id: String,
+    pub code: String,
+    pub expires_at: i64,
+}
+
+/// GET /api/auth/pair/{request_id} response while waiting.
+#[derive(Serialize)]
+pub struct PairPendingResponse {
+    pub status: &'static str,
+}
+
+/// Handler for POST /api/auth/pair.
+///
+/// Creates a pairing request and asks the desktop app to confirm its code.
+/// Every request counts towards the IP's rate limit so a LAN device can't
+/// flood the desktop with confirmation dialogs.
+pub async fn request_pairing_handler(
+    axum::extract::State(state): axum::extract::State<AuthState>,
+    axum::extract::ConnectInfo(addr): axum::extract::ConnectInfo<std::net::SocketAddr>,
+    Json(body): Json<PairRequest>,
+) -> Response {
+    if check_and_record_attempt(addr.ip()).await {
+        let error = AuthError {
+            code: "RATE_LIMITED".to_string(),
+            message: "Too many attempts, try again in 1 minute".to_string(),
+        };
+        return (StatusCode::TOO_MANY_REQUESTS, Json(error)).into_response();
+    }
+
+    let device_name = body
+        .device_name
+        .map(|n| n.trim().chars().take(64).collect::<String>())
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| "Mobile Device".to_string());
+
+    let (request_id, code, expires_at) = start_pairing(device_name.clone()).await;
+
+    use tauri::Emitter;
+    if let Err(e) = state.app_handle.emit(
+        PAIRING_REQUESTED_EVENT,
+        PairingRequestedPayload {
+            code: code.clone(),
+            device_name,
+            expires_at,
+        },
+    ) {
+        log::warn!("[companion] Failed to emit pairing request: {}", e);
+    }
+
+    Json(PairResponse {
+        request_id,
+        code,
+        expires_at,
+    })
+    .into_response()
+}
+
+/// Handler for GET /api/auth/pair/{request_id}.
+///
+/// Returns 202 while the desktop hasn't confirmed, 200 with the session
+/// cookie once it has, and 410 if the request was rejected or expired.
+pub async fn pairing_status_handler(
+    axum::extract::State(state): axum::extract::State<AuthState>,
+    axum::extract::Path(request_id): axum::extract::Path<String>,
+) -> Response {
+    match poll_pairing(&request_id).await {
+        PairingStatus::Pending => (
+            StatusCode::ACCEPTED,
+            Json(PairPendingResponse { status: "pending" }),
+        )
+            .into_response(),
+        PairingStatus::Confirmed(token) => {
+            let cookie = session_cookie(&token, &state.cookie_path);
+            let mut response = Json(VerifyPinResponse { token }).into_response();
+            response
+                .headers_mut()
+                .insert(header::SET_COOKIE, cookie.parse().unwrap());
+            response
+        }
+        PairingStatus::Gone => {
+            let error = AuthError {
+                code: "PAIRING_EXPIRED".to_string(),
+                message: "Pairing request was rejected or has expired".to_string(),
+            };
+            (StatusCode::GONE, Json(error)).into_response()
+        }
+    }
+}
+
 /// Build the auth API router.
 ///
 /// These routes are NOT protected by the auth middleware since they
@@ -306,6 +549,14 @@ pub fn auth_routes(state: AuthState) -> axum::Router {
             "/api/auth/verify-pin",
             axum::routing::post(verify_pin_handler),
         )
+        .route(
+            "/api/auth/pair",
+            axum::routing::post(request_pairing_handler),
+        )
+        .route(
+            "/api/auth/pair/{request_id}",
+            axum::routing::get(pairing_status_handler),
+        )
         .with_state(state)
 }
 
@@ -411,4 +662,45 @@ mod tests {
             assert_eq!(attempts.len(), 1, "stale entries should be pruned");
         }
     }
+
+    #[tokio::test]
+    async fn pairing_code_flow_hands_out_token_once() {
+        let (request_id, code, _) = start_pairing("Tablet".to_string()).await;
+        assert_eq!(code.len(), 6);
+        assert_eq!(poll_pairing(&request_id).await, PairingStatus::Pending);
+
+        let device = confirm_pairing_code(&code)
+            .await
+            .expect("code should confirm");
+        assert_eq!(device.name, "Tablet");
+        assert!(validate_token(&device.token).await);
+        // A code can only be confirmed once.
+        assert!(confirm_pairing_code(&code).await.is_none());
+
+        assert_eq!(
+            poll_pairing(&request_id).await,
+            PairingStatus::Confirmed(device.token.clone())
+        );
+        assert_eq!(poll_pairing(&request_id).await, PairingStatus::Gone);
+        revoke_session_by_token(&device.token).await;
+    }
+
+    #[tokio::test]
+    async fn rejected_or_expired_pairing_is_gone() {
+        let (request_id, code, _) = start_pairing("Tablet".to_string()).await;
+        assert!(reject_pairing_code(&code).await);
+        assert_eq!(poll_pairing(&request_id).await, PairingStatus::Gone);
+        assert!(confirm_pairing_code(&code).await.is_none());
+
+        let (request_id, code, _) = start_pairing("Tablet".to_string()).await;
+        store()
+            .write()
+            .await
+            .pairings
+            .get_mut(&request_id)
+            .unwrap()
+            .expires_at = Utc::now().timestamp() - 1;
+        assert!(confirm_pairing_code(&code).await.is_none());
+        assert_eq!(poll_pairing(&request_id).await, PairingStatus::Gone);
+    }
 }
@@ -0,0 +1,161 @@
+//! Live sync events for companion devices.
+//!
+//! The desktop webview receives sync progress as Tauri events, which never
+//! reach a phone's browser. While the companion server runs, an
+//! [`EventRelay`] listens for [`RELAYED_EVENTS`] on the app handle and fans
+//! them out over a broadcast channel; `GET /api/events` streams them to each
+//! connected device as server-sent events named like the Tauri events, with
+//! the same JSON payloads.
+
+use crate::services::sync_events::{ACTION_SYNCED_EVENT, MR_UPDATED_EVENT, SYNC_PROGRESS_EVENT};
+use axum::response::sse::Event;
+use futures::stream::{self, Stream, StreamExt};
+use std::convert::Infallible;
+use std::sync::Arc;
+use tauri::{AppHandle, EventId, Listener};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio_util::sync::CancellationToken;
+
+/// Tauri events forwarded to companion devices.
+pub const RELAYED_EVENTS: [&str; 3] = [MR_UPDATED_EVENT, SYNC_PROGRESS_EVENT, ACTION_SYNCED_EVENT];
+
+/// Sent instead of the events a slow device missed; it should refetch.
+pub const LAGGED_EVENT: &str = "lagged";
+
+/// Events buffered per device. A full sync emits a few per MR, so this
+/// covers a sync of a couple hundred MRs on a briefly stalled connection.
+const CHANNEL_CAPACITY: usize = 512;
+
+/// A Tauri event as received by the relay.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelayedEvent {
+    pub name: &'static str,
+    /// The event's JSON payload, shared between subscribers.
+    pub payload: Arc<str>,
+}
+
+/// Listens for [`RELAYED_EVENTS`] until dropped with the server handle.
+pub struct EventRelay {
+    sender: broadcast::Sender<RelayedEvent>,
+    app_handle: AppHandle,
+    listeners: Vec<EventId>,
+}
+
+impl EventRelay {
+    pub fn start(app_handle: AppHandle) -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        let listeners = RELAYED_EVENTS
+            .iter()
+            .map(|&name| {
+                let sender = sender.clone();
+                app_handle.listen_any(name, move |event| {
+                    // No subscribers just means no device is connected.
+                    let _ = sender.send(RelayedEvent {
+                        name,
+                        payload: event.payload().into(),
+                    });
+                })
+            })
+            .collect();
+        Self {
+            sender,
+            app_handle,
+            listeners,
+        }
+    }
+
+    /// Sender to hand to route state; devices subscribe through it.
+    pub fn sender(&self) -> broadcast::Sender<RelayedEvent> {
+        self.sender.clone()
+    }
+}
+
+impl Drop for EventRelay {
+    fn drop(&mut self) {
+        for id in self.listeners.drain(..) {
+            self.app_handle.unlisten(id);
+        }
+    }
+}
+
+/// What a device is sent next.
+#[derive(Debug, Clone, PartialEq)]
+enum Message {
+    Event(RelayedEvent),
+    /// This many events were dropped because the device fell behind.
+    Lagged(u64),
+}
+
+async fn next_message(rx: &mut broadcast::Receiver<RelayedEvent>) -> Option<Message> {
+    match rx.recv().await {
+        Ok(event) => Some(Message::Event(event)),
+        Err(RecvError::Lagged(missed)) => Some(Message::Lagged(missed)),
+        Err(RecvError::Closed) => None,
+    }
+}
+
+/// The SSE stream of one device. Ends when the relay goes away or the
+/// server shuts down, so open streams don't hold up graceful shutdown.
+pub fn sse_stream(
+    rx: broadcast::Receiver<RelayedEvent>,
+    shutdown: CancellationToken,
+) -> impl Stream<Item = Result<Event, Infallible>> {
+    stream::unfold(rx, |mut rx| async move {
+        let event = match next_message(&mut rx).await? {
+            Message::Event(event) => Event::default().event(event.name).data(&*event.payload),
+            Message::Lagged(missed) => Event::default()
+                .event(LAGGED_EVENT)
+                .data(format!("{{\"missed\":{}}}", missed)),
+        };
+        Some((Ok(event), rx))
+    })
+    .take_until(shutdown.cancelled_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(name: &'static str, payload: &str) -> RelayedEvent {
+        RelayedEvent {
+            name,
+            payload: payload.into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_slow_subscriber_is_told_it_lagged() {
+        let (sender, mut rx) = broadcast::channel(2);
+        for i in 0..3 {
+            sender
+                .send(event(MR_UPDATED_EVENT, &format!("{{\"mr_id\":{}}}", i)))
+                .unwrap();
+        }
+
+        assert_eq!(next_message(&mut rx).await, Some(Message::Lagged(1)));
+        assert_eq!(
+            next_message(&mut rx).await,
+            Some(Message::Event(event(MR_UPDATED_EVENT, "{\"mr_id\":1}")))
+        );
+        assert_eq!(
+            next_message(&mut rx).await,
+            Some(Message::Event(event(MR_UPDATED_EVENT, "{\"mr_id\":2}")))
+        );
+
+        drop(sender);
+        assert_eq!(next_message(&mut rx).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_stream_ends_on_shutdown() {
+        let (sender, rx) = broadcast::channel(4);
+        let shutdown = CancellationToken::new();
+        let mut stream = Box::pin(sse_stream(rx, shutdown.clone()));
+
+        sender.send(event(SYNC_PROGRESS_EVENT, "{}")).unwrap();
+        assert!(stream.next().await.is_some());
+
+        shutdown.cancel();
+        assert!(stream.next().await.is_none());
+    }
+}
@@ -1,21 +1,28 @@
 //! Companion HTTP server for mobile web access.
 //!
 //! Embeds an axum HTTP server that serves the frontend static files and
-//! exposes REST API endpoints for MR data. The server binds to 0.0.0.0
-//! so mobile devices on the same LAN can connect.
+//! exposes REST API endpoints for MR data. By default the server binds to
+//! 0.0.0.0 so mobile devices on the same LAN can connect. For users who
+//! front it with their own reverse proxy it can instead listen on loopback
+//! or a Unix domain socket, optionally under a base path.
 
+use crate::commands::companion_settings::{CompanionBindMode, CompanionServerSettings};
 use crate::db::pool::DbPool;
 use crate::services::companion_api::{action_api_routes, mr_api_routes};
 use crate::services::companion_auth::{auth_middleware, auth_routes, AuthState};
+use crate::services::companion_events::{EventRelay, RelayedEvent};
 use crate::services::sync_engine::SyncHandle;
 use axum::body::Body;
-use axum::http::{Request, StatusCode, Uri};
-use axum::middleware;
-use axum::response::{Html, IntoResponse, Response};
+use axum::extract::ConnectInfo;
+use axum::http::{HeaderMap, Request, StatusCode, Uri};
+use axum::middleware::{self, Next};
+use axum::response::{Html, IntoResponse, Redirect, Response};
+use axum::routing::get;
 use axum::Router;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::path::PathBuf;
 use std::sync::{Arc, OnceLock};
-use tokio::sync::Mutex;
+use tokio::sync::{broadcast, Mutex};
 use tokio_util::sync::CancellationToken;
 use tower::ServiceExt;
 use tower_http::services::ServeDir;
@@ -26,6 +33,10 @@ pub struct CompanionState {
     pub db: DbPool,
     pub sync_handle: SyncHandle,
     pub app_handle: tauri::AppHandle,
+    /// Sync events relayed to `/api/events` subscribers.
+    pub events: broadcast::Sender<RelayedEvent>,
+    /// Cancelled when the server stops, ending open event streams.
+    pub shutdown: CancellationToken,
 }
 
 /// Handle to control the running companion server.
@@ -34,7 +45,53 @@ pub struct CompanionState {
 /// and the resolved frontend dist path for serving static files.
 pub struct CompanionServerHandle {
     cancel_token: CancellationToken,
-    port: u16,
+    /// Human-readable listen address for logs.
+    address: String,
+    /// Socket file to clean up on shutdown, when bound to a Unix socket.
+    socket_path: Option<PathBuf>,
+    /// Unregisters its Tauri listeners when the handle is dropped.
+    _relay: EventRelay,
+}
+
+/// Where and under which path the companion server listens.
+#[derive(Debug, Clone)]
+pub struct CompanionListenConfig {
+    pub bind_mode: CompanionBindMode,
+    pub port: u16,
+    pub socket_path: Option<PathBuf>,
+    /// `""` or a prefix like `/gitlab`, already normalized by the settings.
+    pub base_path: String,
+}
+
+impl From<&CompanionServerSettings> for CompanionListenConfig {
+    fn from(settings: &CompanionServerSettings) -> Self {
+        Self {
+            bind_mode: settings.bind_mode,
+            port: settings.port,
+            socket_path: settings.socket_path.as_ref().map(PathBuf::from),
+            base_path: settings.base_path.clone(),
+        }
+    }
+}
+
+/// Spawn `axum::serve` for a bound listener, stopping when `cancel` fires.
+macro_rules! spawn_server {
+    ($listener:expr, $make_service:expr, $cancel:expr) => {{
+        let listener = $listener;
+        let make_service = $make_service;
+        let cancel = $cancel;
+        tokio::spawn(async move {
+            let server = axum::serve(listener, make_service).with_graceful_shutdown(async move {
+                cancel.cancelled().await;
+            });
+
+            if let Err(e) = server.await {
+                log::error!("[companion] Server error: {}", e);
+            }
+
+            log::info!("[companion] Server stopped");
+        });
+    }};
 }
 
 /// Global handle to the running companion server (None if stopped).
@@ -43,14 +100,14 @@ fn server_handle() -> &'static Mutex<Option<CompanionServerHandle>> {
     SERVER_HANDLE.get_or_init(|| Mutex::new(None))
 }
 
-/// Start the companion HTTP server on the given port.
+/// Start the companion HTTP server on the configured address.
 ///
 /// Serves frontend static files from `frontend_dist` and shares `db`/`sync_handle`
 /// via axum state for API routes. Auth routes and middleware are wired up automatically.
 ///
-/// Returns an error if the server is already running or the port is unavailable.
+/// Returns an error if the server is already running or the address is unavailable.
 pub async fn start_companion_server(
-    port: u16,
+    config: CompanionListenConfig,
     frontend_dist: PathBuf,
     db: DbPool,
     sync_handle: SyncHandle,
@@ -65,12 +122,22 @@ pub async fn start_companion_server(
     let cancel_token = CancellationToken::new();
     let cancel_clone = cancel_token.clone();
 
+    let relay = EventRelay::start(app_handle.clone());
     let companion_state = CompanionState {
         db,
         sync_handle,
         app_handle: app_handle.clone(),
+        events: relay.sender(),
+        shutdown: cancel_token.clone(),
+    };
+    let auth_state = AuthState {
+        app_handle,
+        cookie_path: if config.base_path.is_empty() {
+            "/".to_string()
+        } else {
+            config.base_path.clone()
+        },
     };
-    let auth_state = AuthState { app_handle };
 
     // Protected API routes (require valid session token).
     let api_routes = mr_api_routes()
@@ -79,9 +146,9 @@ pub async fn start_companion_server(
         .layer(middleware::from_fn(auth_middleware));
 
     // Read index.html once at startup for the SPA fallback.
-    let index_html: Arc<str> = std::fs::read_to_string(frontend_dist.join("index.html"))
-        .map_err(|e| format!("Failed to read index.html: {}", e))?
-        .into();
+    let index_html = std::fs::read_to_string(frontend_dist.join("index.html"))
+        .map_err(|e| format!("Failed to read index.html: {}", e))?;
+    let index_html: Arc<str> = rewrite_index_html(&index_html, &config.base_path).into();
 
     // Build the full router:
     // 1. Auth routes (unprotected) — /api/auth/*
@@ -100,31 +167,73 @@ pub async fn start_companion_server(
             async move { spa_fallback(uri, &dist, &html).await }
         });
 
-    let addr = std::net::SocketAddr::from(([0, 0, 0, 0], port));
-
-    let listener = tokio::net::TcpListener::bind(addr)
-        .await
-        .map_err(|e| format!("Failed to bind to port {}: {}", port, e))?;
+    let mut app = if config.base_path.is_empty() {
+        app
+    } else {
+        // `nest` doesn't match the prefix with a trailing slash.
+        let base = config.base_path.clone();
+        Router::new()
+            .route(
+                &format!("{}/", config.base_path),
+                get(move || async move { Redirect::permanent(&base) }),
+            )
+            .nest(&config.base_path, app)
+    };
 
-    log::info!("[companion] Server starting on http://0.0.0.0:{}", port);
+    // Behind a proxy every connection comes from the proxy itself (or has no
+    // peer address at all on a Unix socket), so take the client address the
+    // proxy forwards for PIN rate limiting.
+    if config.bind_mode.is_proxied() {
+        app = app.layer(middleware::from_fn(forwarded_client_addr));
+    }
 
-    // axum::serve needs ConnectInfo for extracting client IP in handlers
-    let make_service = app.into_make_service_with_connect_info::<std::net::SocketAddr>();
+    let (address, socket_path) = match config.bind_mode {
+        CompanionBindMode::Lan | CompanionBindMode::Loopback => {
+            let ip = if config.bind_mode == CompanionBindMode::Lan {
+                Ipv4Addr::UNSPECIFIED
+            } else {
+                Ipv4Addr::LOCALHOST
+            };
+            let addr = SocketAddr::from((ip, config.port));
+            let listener = tokio::net::TcpListener::bind(addr)
+                .await
+                .map_err(|e| format!("Failed to bind to port {}: {}", config.port, e))?;
 
-    // Spawn the server task with graceful shutdown
-    tokio::spawn(async move {
-        let server = axum::serve(listener, make_service).with_graceful_shutdown(async move {
-            cancel_clone.cancelled().await;
-        });
+            // axum::serve needs ConnectInfo for extracting client IP in handlers
+            let make_service = app.into_make_service_with_connect_info::<SocketAddr>();
+            spawn_server!(listener, make_service, cancel_clone);
+            (format!("http://{}{}", addr, config.base_path), None)
+        }
+        #[cfg(unix)]
+        CompanionBindMode::UnixSocket => {
+            let path = config
+                .socket_path
+                .clone()
+                .ok_or("No socket path configured for the companion server")?;
+            remove_stale_socket(&path)?;
+            let listener = tokio::net::UnixListener::bind(&path)
+                .map_err(|e| format!("Failed to bind to {}: {}", path.display(), e))?;
 
-        if let Err(e) = server.await {
-            log::error!("[companion] Server error: {}", e);
+            spawn_server!(listener, app.into_make_service(), cancel_clone);
+            (
+                format!("unix:{}{}", path.display(), config.base_path),
+                Some(path),
+            )
+        }
+        #[cfg(not(unix))]
+        CompanionBindMode::UnixSocket => {
+            return Err("Unix sockets are not supported on this platform".into());
         }
+    };
 
-        log::info!("[companion] Server stopped");
-    });
+    log::info!("[companion] Server starting on {}", address);
 
-    *handle_guard = Some(CompanionServerHandle { cancel_token, port });
+    *handle_guard = Some(CompanionServerHandle {
+        cancel_token,
+        address,
+        socket_path,
+        _relay: relay,
+    });
     Ok(())
 }
 
@@ -135,8 +244,11 @@ pub async fn stop_companion_server() {
     let mut handle_guard = server_handle().lock().await;
 
     if let Some(handle) = handle_guard.take() {
-        log::info!("[companion] Stopping server on port {}", handle.port);
+        log::info!("[companion] Stopping server on {}", handle.address);
         handle.cancel_token.cancel();
+        if let Some(path) = handle.socket_path {
+            let _ = std::fs::remove_file(path);
+        }
     }
 }
 
@@ -145,6 +257,64 @@ pub async fn is_companion_server_running() -> bool {
     server_handle().lock().await.is_some()
 }
 
+/// Remove a socket file left behind by a server that didn't shut down cleanly.
+///
+/// Refuses to touch anything at `path` that isn't a socket.
+#[cfg(unix)]
+fn remove_stale_socket(path: &std::path::Path) -> Result<(), String> {
+    use std::os::unix::fs::FileTypeExt;
+
+    match std::fs::symlink_metadata(path) {
+        Ok(meta) if meta.file_type().is_socket() => std::fs::remove_file(path)
+            .map_err(|e| format!("Failed to remove stale socket {}: {}", path.display(), e)),
+        Ok(_) => Err(format!(
+            "{} already exists and is not a socket",
+            path.display()
+        )),
+        Err(_) => Ok(()),
+    }
+}
+
+/// Client address reported by the reverse proxy: the last `X-Forwarded-For`
+/// entry (the one the proxy appended), else `X-Real-IP`.
+fn forwarded_client_ip(headers: &HeaderMap) -> Option<IpAddr> {
+    let forwarded_for = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .last();
+    forwarded_for
+        .or_else(|| headers.get("x-real-ip").and_then(|v| v.to_str().ok()))
+        .and_then(|v| v.trim().parse().ok())
+}
+
+/// Middleware for proxied modes that replaces the connection's peer address
+/// with the forwarded client address (loopback when none is forwarded).
+async fn forwarded_client_addr(mut req: Request<Body>, next: Next) -> Response {
+    let ip = forwarded_client_ip(req.headers()).unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST));
+    req.extensions_mut()
+        .insert(ConnectInfo(SocketAddr::new(ip, 0)));
+    next.run(req).await
+}
+
+/// Point the built `index.html` at assets under `base_path` and expose the
+/// base path to the frontend as `window.__COMPANION_BASE_PATH__`, which the
+/// router, API client and lazily loaded chunks prefix their URLs with.
+fn rewrite_index_html(html: &str, base_path: &str) -> String {
+    if base_path.is_empty() {
+        return html.to_string();
+    }
+    let html = html
+        .replace("src=\"/", &format!("src=\"{}/", base_path))
+        .replace("href=\"/", &format!("href=\"{}/", base_path));
+    let script = format!(
+        "<script>window.__COMPANION_BASE_PATH__={};</script>",
+        serde_json::Value::from(base_path)
+    );
+    html.replacen("<head>", &format!("<head>{}", script), 1)
+}
+
 /// SPA-aware fallback handler.
 ///
 /// 1. API paths that didn't match a route get a plain 404
@@ -158,12 +328,48 @@ async fn spa_fallback(uri: Uri, dist: &PathBuf, index_html: &str) -> Response {
         return StatusCode::NOT_FOUND.into_response();
     }
 
+    // index.html is always served from memory since it may have been rebased.
+    if uri.path() == "/index.html" {
+        return Html(index_html.to_owned()).into_response();
+    }
+
     // Try to serve a static file (JS, CSS, images, etc.).
     let req = Request::builder().uri(&uri).body(Body::empty()).unwrap();
+    let serve_dir = ServeDir::new(dist).append_index_html_on_directories(false);
 
-    match ServeDir::new(dist).oneshot(req).await {
+    match serve_dir.oneshot(req).await {
         Ok(res) if res.status() != StatusCode::NOT_FOUND => res.into_response(),
         // No matching static file → serve index.html for client-side routing.
         _ => Html(index_html.to_owned()).into_response(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_html_is_rebased_under_base_path() {
+        let html = r#"<html><head><script type="module" src="/assets/index.js"></script><link rel="stylesheet" href="/assets/index.css"></head></html>"#;
+        assert_eq!(rewrite_index_html(html, ""), html);
+
+        let rebased = rewrite_index_html(html, "/gitlab");
+        assert!(rebased.starts_with(
+            r#"<html><head><script>window.__COMPANION_BASE_PATH__="/gitlab";</script>"#
+        ));
+        assert!(rebased.contains(r#"src="/gitlab/assets/index.js""#));
+        assert!(rebased.contains(r#"href="/gitlab/assets/index.css""#));
+    }
+
+    #[test]
+    fn forwarded_client_ip_uses_the_proxy_appended_entry() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(forwarded_client_ip(&headers), None);
+
+        headers.insert("x-real-ip", "10.0.0.7".parse().unwrap());
+        assert_eq!(forwarded_client_ip(&headers), "10.0.0.7".parse().ok());
+
+        headers.insert("x-forwarded-for", "1.2.3.4, 192.168.1.50".parse().unwrap());
+        assert_eq!(forwarded_client_ip(&headers), "192.168.1.50".parse().ok());
+    }
+}
@@ -0,0 +1,243 @@
+//! Read GitLab instances out of other tools' configuration files.
+//!
+//! Supports the `glab` CLI (`~/.config/glab-cli/config.yml`) and python-gitlab
+//! (`~/.python-gitlab.cfg`). Only the handful of keys we need are read, so
+//! both formats are parsed by hand rather than with a full YAML/INI parser.
+
+use serde::Serialize;
+
+/// Which tool a configuration file belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConfigSource {
+    Glab,
+    PythonGitlab,
+}
+
+/// One instance found in a configuration file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfiguredInstance {
+    /// Instance URL, e.g. `https://gitlab.com`.
+    pub url: String,
+    /// The host key or section name the instance was listed under.
+    pub name: String,
+    /// `None` when the file doesn't hold the token (glab can keep it in the
+    /// system keyring).
+    pub token: Option<String>,
+}
+
+/// Parse a glab or python-gitlab configuration file, detecting which one it
+/// is. Returns `None` when the content is neither.
+pub fn parse_config(content: &str) -> Option<(ConfigSource, Vec<ConfiguredInstance>)> {
+    let is_ini = content
+        .lines()
+        .map(str::trim)
+        .any(|l| l.starts_with('[') && l.ends_with(']'));
+    if is_ini {
+        return Some((ConfigSource::PythonGitlab, parse_python_gitlab(content)));
+    }
+    if content.lines().any(|l| l.trim_end() == "hosts:") {
+        return Some((ConfigSource::Glab, parse_glab(content)));
+    }
+    None
+}
+
+fn unquote(value: &str) -> &str {
+    let value = value.trim();
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+        .unwrap_or(value)
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    let value = unquote(value);
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// python-gitlab: one INI section per instance with `url` and
+/// `private_token`; the `[global]` section holds defaults only.
+fn parse_python_gitlab(content: &str) -> Vec<ConfiguredInstance> {
+    let mut instances: Vec<ConfiguredInstance> = Vec::new();
+    let mut current: Option<(String, Option<String>, Option<String>)> = None;
+
+    let mut finish = |section: Option<(String, Option<String>, Option<String>)>| {
+        if let Some((name, Some(url), token)) = section {
+            instances.push(ConfiguredInstance { url, name, token });
+        }
+    };
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            finish(current.take());
+            if section.trim() != "global" {
+                current = Some((section.trim().to_string(), None, None));
+            }
+            continue;
+        }
+        let (Some((_, url, token)), Some((key, value))) = (current.as_mut(), line.split_once('='))
+        else {
+            continue;
+        };
+        match key.trim() {
+            "url" => *url = non_empty(value),
+            "private_token" => *token = non_empty(value),
+            _ => {}
+        }
+    }
+    finish(current.take());
+    instances
+}
+
+/// glab: a `hosts:` map keyed by hostname, each with `token`, and optionally
+/// `api_host` and `api_protocol` when the API lives elsewhere.
+fn parse_glab(content: &str) -> Vec<ConfiguredInstance> {
+    struct Host {
+        name: String,
+        token: Option<String>,
+        api_host: Option<String>,
+        api_protocol: Option<String>,
+    }
+
+    let mut hosts: Vec<Host> = Vec::new();
+    let mut in_hosts = false;
+    let mut host_indent: Option<usize> = None;
+
+    for raw in content.lines() {
+        let trimmed = raw.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let indent = raw.len() - raw.trim_start().len();
+        if indent == 0 {
+            in_hosts = trimmed == "hosts:";
+            host_indent = None;
+            continue;
+        }
+        if !in_hosts {
+            continue;
+        }
+        let Some((key, value)) = trimmed.split_once(':') else {
+            continue;
+        };
+        let key = unquote(key);
+        if *host_indent.get_or_insert(indent) == indent {
+            hosts.push(Host {
+                name: key.to_string(),
+                token: None,
+                api_host: None,
+                api_protocol: None,
+            });
+            continue;
+        }
+        let Some(host) = hosts.last_mut() else {
+            continue;
+        };
+        match key {
+            "token" => host.token = non_empty(value),
+            "api_host" => host.api_host = non_empty(value),
+            "api_protocol" => host.api_protocol = non_empty(value),
+            _ => {}
+        }
+    }
+
+    hosts
+        .into_iter()
+        .map(|h| {
+            let protocol = h.api_protocol.as_deref().unwrap_or("https");
+            let api_host = h.api_host.as_deref().unwrap_or(&h.name);
+            ConfiguredInstance {
+                url: format!("{}://{}", protocol, api_host),
+                name: h.name,
+                token: h.token,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instance(url: &str, name: &str, token: Option<&str>) -> ConfiguredInstance {
+        ConfiguredInstance {
+            url: url.to_string(),
+            name: name.to_string(),
+            token: token.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn parses_glab_hosts() {
+        let config = r#"
+git_protocol: ssh
+editor:
+hosts:
+    gitlab.com:
+        token: glpat-abc
+        api_protocol: https
+        git_protocol: ssh
+    git.corp.example:
+        token: "glpat-def"
+        api_host: api.corp.example
+        api_protocol: http
+    keyring.example:
+        token:
+check_update: false
+"#;
+        let (source, instances) = parse_config(config).unwrap();
+        assert_eq!(source, ConfigSource::Glab);
+        assert_eq!(
+            instances,
+            vec![
+                instance("https://gitlab.com", "gitlab.com", Some("glpat-abc")),
+                instance(
+                    "http://api.corp.example",
+                    "git.corp.example",
+                    Some("glpat-def")
+                ),
+                instance("https://keyring.example", "keyring.example", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_python_gitlab_sections() {
+        let config = "
+[global]
+default = work
+ssl_verify = true
+
+[work]
+url = https://git.corp.example
+private_token = glpat-xyz
+api_version = 4
+
+; no URL, ignored
+[broken]
+private_token = x
+
+[public]
+url = https://gitlab.com
+";
+        let (source, instances) = parse_config(config).unwrap();
+        assert_eq!(source, ConfigSource::PythonGitlab);
+        assert_eq!(
+            instances,
+            vec![
+                instance("https://git.corp.example", "work", Some("glpat-xyz")),
+                instance("https://gitlab.com", "public", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_format() {
+        assert!(parse_config("{\"hosts\": {}}").is_none());
+    }
+}
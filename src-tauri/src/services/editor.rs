@@ -0,0 +1,184 @@
+//! Opening files of a local working copy in the user's editor.
+//!
+//! The editor is started from a command template such as
+//! `code -g {file}:{line}`. `{file}` becomes the absolute path of the file
+//! and `{line}` the 1-based line. The template is split into arguments the
+//! way a shell would (quotes group words, no other expansion) but isn't run
+//! through one, so a path with spaces or shell characters is always a single
+//! argument.
+
+use crate::error::AppError;
+use std::path::{Component, Path, PathBuf};
+use std::process::Stdio;
+use tokio::process::Command;
+
+/// Split a command template into arguments. Single and double quotes group
+/// words; a backslash escapes the next character outside single quotes.
+fn split_args(template: &str) -> Result<Vec<String>, AppError> {
+    let mut args = Vec::new();
+    let mut current: Option<String> = None;
+    let mut quote: Option<char> = None;
+    let mut chars = template.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('\''), c) => current.get_or_insert_with(String::new).push(c),
+            (_, '\\') => {
+                if let Some(next) = chars.next() {
+                    current.get_or_insert_with(String::new).push(next);
+                }
+            }
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                current.get_or_insert_with(String::new);
+            }
+            (None, c) if c.is_whitespace() => args.extend(current.take()),
+            (_, c) => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if quote.is_some() {
+        return Err(AppError::invalid_input_field(
+            "The editor command has an unterminated quote",
+            "editor_command",
+        ));
+    }
+    args.extend(current);
+    Ok(args)
+}
+
+/// Check an editor command template: a program, and `{file}` somewhere.
+pub fn validate_template(template: &str) -> Result<(), AppError> {
+    let args = split_args(template)?;
+    if args.is_empty() {
+        return Err(AppError::invalid_input_field(
+            "The editor command is empty",
+            "editor_command",
+        ));
+    }
+    if !args.iter().any(|arg| arg.contains("{file}")) {
+        return Err(AppError::invalid_input_field(
+            "The editor command must contain {file}",
+            "editor_command",
+        ));
+    }
+    Ok(())
+}
+
+/// The program and arguments to run for opening `file` at `line`.
+fn command_line(template: &str, file: &Path, line: i64) -> Result<Vec<String>, AppError> {
+    validate_template(template)?;
+    let file = file.display().to_string();
+    let line = line.max(1).to_string();
+    Ok(split_args(template)?
+        .into_iter()
+        .map(|arg| arg.replace("{file}", &file).replace("{line}", &line))
+        .collect())
+}
+
+/// Resolve a repository-relative path, as shown in a diff, to an existing
+/// file in the working copy. Paths leaving the working copy are rejected.
+pub fn resolve_file(repo: &Path, file_path: &str) -> Result<PathBuf, AppError> {
+    let relative = Path::new(file_path);
+    if !relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        return Err(AppError::invalid_input_field(
+            format!("{file_path} is not a path inside the repository"),
+            "file_path",
+        ));
+    }
+    let file = repo.join(relative);
+    if !file.is_file() {
+        return Err(AppError::invalid_input_field(
+            format!(
+                "{file_path} doesn't exist in {}; check out the MR's branch first",
+                repo.display()
+            ),
+            "file_path",
+        ));
+    }
+    Ok(file)
+}
+
+/// Start the editor on `file` at `line`, from the working copy `repo`. The
+/// editor isn't waited for.
+pub fn open(template: &str, repo: &Path, file: &Path, line: i64) -> Result<(), AppError> {
+    if template.trim().is_empty() {
+        return Err(AppError::invalid_input_field(
+            "No editor command is set",
+            "editor_command",
+        ));
+    }
+    let args = command_line(template, file, line)?;
+    let mut child = Command::new(&args[0])
+        .args(&args[1..])
+        .current_dir(repo)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| {
+            AppError::invalid_input_field(
+                format!("Failed to start {}: {e}", args[0]),
+                "editor_command",
+            )
+        })?;
+    // Reap the process once the editor (or its launcher) exits.
+    tokio::spawn(async move {
+        let _ = child.wait().await;
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_command_line_substitutes_after_splitting() {
+        let file = Path::new("/home/me/my repo/src/main.rs");
+        assert_eq!(
+            command_line("code -g {file}:{line}", file, 12).unwrap(),
+            vec!["code", "-g", "/home/me/my repo/src/main.rs:12"]
+        );
+        assert_eq!(
+            command_line(
+                r#""/Applications/My Editor" --line={line} '{file}'"#,
+                file,
+                0
+            )
+            .unwrap(),
+            vec![
+                "/Applications/My Editor",
+                "--line=1",
+                "/home/me/my repo/src/main.rs"
+            ]
+        );
+        assert_eq!(split_args(r#"a\ b "" c"#).unwrap(), vec!["a b", "", "c"]);
+    }
+
+    #[test]
+    fn test_validate_template() {
+        assert!(validate_template("idea --line {line} {file}").is_ok());
+        assert!(validate_template("   ").is_err());
+        assert!(validate_template("code -g").is_err());
+        assert!(validate_template("code '{file}").is_err());
+    }
+
+    #[test]
+    fn test_resolve_file_stays_in_the_repository() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/lib.rs"), "").unwrap();
+
+        assert_eq!(
+            resolve_file(dir.path(), "src/lib.rs").unwrap(),
+            dir.path().join("src/lib.rs")
+        );
+        assert!(resolve_file(dir.path(), "src/missing.rs").is_err());
+        assert!(resolve_file(dir.path(), "../etc/passwd").is_err());
+        assert!(resolve_file(dir.path(), "/etc/passwd").is_err());
+    }
+}
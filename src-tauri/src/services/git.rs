@@ -0,0 +1,350 @@
+//! Local working copies: checking out an MR in the user's clone.
+//!
+//! Runs the `git` executable, so the user's own credentials, hooks and
+//! config apply. The MR's head is fetched from GitLab's
+//! `refs/merge-requests/<iid>/head` ref, which also covers MRs from forks,
+//! and lands on a local branch named like the source branch. An existing
+//! local branch is only fast-forwarded, never reset. The remote fetched from
+//! is the clone's remote for the MR's project, whatever it is called.
+
+use crate::error::AppError;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
+
+/// Time allowed for one git invocation; a fetch of a big repository is the
+/// slow case.
+const GIT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Result of checking out an MR.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Checkout {
+    /// Working copy the MR was checked out in.
+    pub repo_path: String,
+    pub branch: String,
+    pub head_sha: String,
+    /// The local branch didn't exist before.
+    pub created: bool,
+}
+
+/// Run git in `repo`, returning its trimmed stdout. A failing command is
+/// reported with git's own message.
+async fn git(repo: &Path, args: &[&str]) -> Result<String, AppError> {
+    let mut cmd = Command::new("git");
+    cmd.arg("-C")
+        .arg(repo)
+        .args(args)
+        // Fail instead of waiting for credentials nobody can type in.
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::null())
+        .kill_on_drop(true);
+    let output = tokio::time::timeout(GIT_TIMEOUT, cmd.output())
+        .await
+        .map_err(|_| {
+            AppError::internal(format!(
+                "git {} timed out after {}s",
+                args[0],
+                GIT_TIMEOUT.as_secs()
+            ))
+        })?
+        .map_err(|e| AppError::internal(format!("Failed to run git: {e}")))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(AppError::invalid_input(format!(
+            "git {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+/// Resolve `path` (a leading `~/` is expanded) to the top level of the git
+/// working copy it is in.
+pub async fn working_copy(path: &str) -> Result<PathBuf, AppError> {
+    let path = crate::core::expand_home(path.trim())?;
+    if !path.is_dir() {
+        return Err(AppError::invalid_input_field(
+            format!("{} is not a directory", path.display()),
+            "repo_path",
+        ));
+    }
+    let top_level = git(&path, &["rev-parse", "--show-toplevel"])
+        .await
+        .map_err(|_| {
+            AppError::invalid_input_field(
+                format!("{} is not a git working copy", path.display()),
+                "repo_path",
+            )
+        })?;
+    Ok(PathBuf::from(top_level))
+}
+
+/// Project path of a remote URL: `group/app` for
+/// `git@gitlab.example.com:group/app.git`, `https://gitlab.example.com/group/app`
+/// or `ssh://git@gitlab.example.com:2222/group/app.git`.
+fn remote_project_path(url: &str) -> &str {
+    let url = url.trim().trim_end_matches('/');
+    let url = url.strip_suffix(".git").unwrap_or(url);
+    match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/').map_or("", |(_, path)| path),
+        // scp-like `user@host:path`
+        None => url.split_once(':').map_or(url, |(_, path)| path),
+    }
+}
+
+/// Whether `remote_url` points at the project whose web URL is `project_url`.
+/// Only the project paths are compared, as SSH remotes often name the host
+/// differently; an instance served under a path prefix still matches.
+fn is_project_remote(remote_url: &str, project_url: &str) -> bool {
+    let remote = remote_project_path(remote_url).to_lowercase();
+    let project = remote_project_path(project_url).to_lowercase();
+    !remote.is_empty()
+        && (remote == project
+            || project.ends_with(&format!("/{remote}"))
+            || remote.ends_with(&format!("/{project}")))
+}
+
+/// The remote of `repo` for the project at `project_url`.
+async fn project_remote(repo: &Path, project_url: &str) -> Result<String, AppError> {
+    // The configured URLs as written, before any `insteadOf` rewriting.
+    let urls = git(repo, &["config", "--get-regexp", r"^remote\..*\.url$"])
+        .await
+        .unwrap_or_default();
+    urls.lines()
+        .filter_map(|line| line.split_once(' '))
+        .find(|(_, url)| is_project_remote(url, project_url))
+        .and_then(|(key, _)| key.strip_prefix("remote.")?.strip_suffix(".url"))
+        .map(str::to_string)
+        .ok_or_else(|| {
+            AppError::invalid_input_field(
+                format!(
+                    "{} has no remote for {project_url}; add one to check out its MRs",
+                    repo.display()
+                ),
+                "repo_path",
+            )
+        })
+}
+
+/// Fetch MR `iid` of the project at `project_url` (its web URL) into `repo`
+/// and check it out on `source_branch`.
+///
+/// Refuses when `repo` has no remote for the project, when tracked files
+/// have uncommitted changes, and when an existing local branch has commits
+/// the MR doesn't.
+pub async fn checkout_mr(
+    repo: &Path,
+    project_url: &str,
+    iid: i64,
+    source_branch: &str,
+) -> Result<Checkout, AppError> {
+    // Also rejects names git would read as options.
+    let branch = git(repo, &["check-ref-format", "--branch", source_branch])
+        .await
+        .map_err(|_| {
+            AppError::invalid_input(format!("{source_branch} is not a valid branch name"))
+        })?;
+
+    let changes = git(repo, &["status", "--porcelain", "--untracked-files=no"]).await?;
+    if !changes.is_empty() {
+        return Err(AppError::invalid_input(format!(
+            "{} has uncommitted changes; commit or stash them first",
+            repo.display()
+        )));
+    }
+
+    let remote = project_remote(repo, project_url).await?;
+    crate::services::offline_mode::ensure_online()?;
+    let mr_ref = format!("refs/merge-requests/{iid}/head");
+    git(repo, &["fetch", "--no-tags", &remote, &mr_ref]).await?;
+
+    let local_ref = format!("refs/heads/{branch}");
+    let exists = git(repo, &["rev-parse", "--verify", "--quiet", &local_ref])
+        .await
+        .is_ok();
+    if exists {
+        git(repo, &["checkout", &branch, "--"]).await?;
+        git(repo, &["merge", "--ff-only", "FETCH_HEAD"])
+            .await
+            .map_err(|_| {
+                AppError::invalid_input(format!(
+                    "Local branch {branch} has commits that aren't in the MR; \
+                     it was checked out but not updated"
+                ))
+            })?;
+    } else {
+        git(repo, &["checkout", "-b", &branch, "FETCH_HEAD", "--"]).await?;
+        // Track the source branch when the remote has it; MRs from forks
+        // have no such branch on this remote.
+        let upstream = format!("{remote}/{branch}");
+        let _ = git(repo, &["branch", "--set-upstream-to", &upstream]).await;
+    }
+
+    Ok(Checkout {
+        repo_path: repo.display().to_string(),
+        head_sha: git(repo, &["rev-parse", "HEAD"]).await?,
+        branch,
+        created: !exists,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    /// Web URL of the project the test clones stand in for.
+    const PROJECT_URL: &str = "https://gitlab.example.com/group/app";
+
+    fn run(dir: &Path, args: &[&str]) -> String {
+        let output = std::process::Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    /// A repository under `dir` with a `main` branch and MR 7 from `feature`.
+    fn origin_with_mr(dir: &Path) -> PathBuf {
+        let origin = dir.join("origin");
+        std::fs::create_dir(&origin).unwrap();
+        run(&origin, &["init", "-q", "-b", "main"]);
+        std::fs::write(origin.join("a.txt"), "a\n").unwrap();
+        run(&origin, &["add", "."]);
+        run(&origin, &["commit", "-qm", "init"]);
+        run(&origin, &["checkout", "-qb", "feature"]);
+        run(&origin, &["commit", "-q", "--allow-empty", "-m", "one"]);
+        run(
+            &origin,
+            &["update-ref", "refs/merge-requests/7/head", "feature"],
+        );
+        run(&origin, &["checkout", "-q", "main"]);
+        origin
+    }
+
+    /// Clone `origin` into `dir/clone` with an `upstream` remote that has the
+    /// project's SSH URL; git rewrites it to `origin` when fetching.
+    fn clone_project(dir: &Path, origin: &Path) -> PathBuf {
+        let clone = dir.join("clone");
+        let origin = origin.to_str().unwrap();
+        run(dir, &["clone", "-q", origin, clone.to_str().unwrap()]);
+        run(&clone, &["remote", "rename", "origin", "upstream"]);
+        let ssh_url = "git@gitlab.example.com:group/app.git";
+        run(&clone, &["remote", "set-url", "upstream", ssh_url]);
+        run(
+            &clone,
+            &["config", &format!("url.{origin}.insteadOf"), ssh_url],
+        );
+        clone
+    }
+
+    #[test]
+    fn test_is_project_remote() {
+        for url in [
+            "git@gitlab.example.com:group/app.git",
+            "https://gitlab.example.com/group/app.git",
+            "ssh://git@ssh.gitlab.example.com:2222/Group/App/",
+        ] {
+            assert!(is_project_remote(url, PROJECT_URL), "{url}");
+        }
+        assert!(is_project_remote(
+            "git@gitlab.example.com:group/app.git",
+            "https://example.com/gitlab/group/app"
+        ));
+        assert!(!is_project_remote(
+            "git@gitlab.example.com:me/app.git",
+            PROJECT_URL
+        ));
+        assert!(!is_project_remote(
+            "git@gitlab.example.com:group/app-docs.git",
+            PROJECT_URL
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_checkout_mr_creates_then_fast_forwards() {
+        let dir = tempdir().unwrap();
+        let origin = origin_with_mr(dir.path());
+        let clone = clone_project(dir.path(), &origin);
+        // A path inside the clone resolves to its top level.
+        std::fs::create_dir(clone.join("docs")).unwrap();
+        let repo = working_copy(clone.join("docs").to_str().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(repo, clone.canonicalize().unwrap());
+
+        let first = checkout_mr(&repo, PROJECT_URL, 7, "feature").await.unwrap();
+        assert!(first.created);
+        assert_eq!(first.branch, "feature");
+        assert_eq!(first.head_sha, run(&origin, &["rev-parse", "feature"]));
+
+        // A new push to the MR fast-forwards the local branch.
+        run(&origin, &["checkout", "-q", "feature"]);
+        run(&origin, &["commit", "-q", "--allow-empty", "-m", "two"]);
+        run(
+            &origin,
+            &["update-ref", "refs/merge-requests/7/head", "feature"],
+        );
+        run(&clone, &["checkout", "-q", "main"]);
+        let second = checkout_mr(&repo, PROJECT_URL, 7, "feature").await.unwrap();
+        assert!(!second.created);
+        assert_eq!(second.head_sha, run(&origin, &["rev-parse", "feature"]));
+        assert_eq!(run(&clone, &["branch", "--show-current"]), "feature");
+
+        // Uncommitted changes to tracked files stop the checkout.
+        std::fs::write(clone.join("a.txt"), "changed\n").unwrap();
+        let err = checkout_mr(&repo, PROJECT_URL, 7, "feature")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("uncommitted changes"));
+    }
+
+    #[tokio::test]
+    async fn test_checkout_mr_refuses_a_diverged_local_branch() {
+        let dir = tempdir().unwrap();
+        let origin = origin_with_mr(dir.path());
+        let clone = clone_project(dir.path(), &origin);
+        run(&clone, &["checkout", "-qb", "feature"]);
+        run(&clone, &["commit", "-q", "--allow-empty", "-m", "local"]);
+        let local_head = run(&clone, &["rev-parse", "HEAD"]);
+        run(&clone, &["checkout", "-q", "main"]);
+
+        let err = checkout_mr(&clone, PROJECT_URL, 7, "feature")
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("has commits that aren't in the MR"));
+        assert_eq!(run(&clone, &["rev-parse", "feature"]), local_head);
+    }
+
+    #[tokio::test]
+    async fn test_checkout_mr_needs_a_remote_for_the_project() {
+        let dir = tempdir().unwrap();
+        let origin = origin_with_mr(dir.path());
+        let clone = clone_project(dir.path(), &origin);
+
+        let err = checkout_mr(&clone, "https://gitlab.example.com/other/app", 7, "feature")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("has no remote for"));
+        assert_eq!(run(&clone, &["branch", "--show-current"]), "main");
+    }
+
+    #[tokio::test]
+    async fn test_working_copy_rejects_non_repositories() {
+        let dir = tempdir().unwrap();
+        assert!(working_copy(dir.path().to_str().unwrap()).await.is_err());
+        assert!(working_copy(dir.path().join("missing").to_str().unwrap())
+            .await
+            .is_err());
+    }
+}
@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 /// Parses `.gitattributes` file content and extracts glob patterns
 /// marked with `linguist-generated` (or `linguist-generated=true`).
 ///
@@ -40,10 +42,117 @@ pub fn parse_gitattributes(content: &str) -> Vec<String> {
         .collect()
 }
 
+/// A `linguist-language` override: files matching `pattern` are highlighted
+/// as `language` (a Linguist language name such as `TypeScript`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LanguageOverride {
+    pub pattern: String,
+    pub language: String,
+}
+
+/// Parses `.gitattributes` file content and extracts `linguist-language`
+/// overrides, in file order.
+pub fn parse_language_overrides(content: &str) -> Vec<LanguageOverride> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+            let mut parts = line.split_whitespace();
+            let pattern = parts.next()?;
+            let language = parts.find_map(|attr| attr.strip_prefix("linguist-language="))?;
+            Some(LanguageOverride {
+                pattern: pattern.to_string(),
+                language: language.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Whether a `.gitattributes` pattern matches a repository-relative path.
+///
+/// As in git, a pattern without a `/` matches the file name at any depth and
+/// one with a `/` matches from the repository root. `*` and `?` don't match
+/// `/`; `**` matches across directories.
+pub fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.strip_prefix('/').unwrap_or(pattern);
+    if pattern.contains('/') {
+        glob_match(pattern.as_bytes(), path.as_bytes())
+    } else {
+        let name = path.rsplit('/').next().unwrap_or(path);
+        glob_match(pattern.as_bytes(), name.as_bytes())
+    }
+}
+
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern {
+        [] => text.is_empty(),
+        [b'*', b'*', b'/', rest @ ..] => {
+            // Zero or more leading directories.
+            glob_match(rest, text)
+                || text
+                    .iter()
+                    .enumerate()
+                    .any(|(i, c)| *c == b'/' && glob_match(rest, &text[i + 1..]))
+        }
+        [b'*', b'*', rest @ ..] => (0..=text.len()).any(|i| glob_match(rest, &text[i..])),
+        [b'*', rest @ ..] => {
+            let segment = text.iter().position(|c| *c == b'/').unwrap_or(text.len());
+            (0..=segment).any(|i| glob_match(rest, &text[i..]))
+        }
+        [b'?', rest @ ..] => {
+            matches!(text.first(), Some(c) if *c != b'/') && glob_match(rest, &text[1..])
+        }
+        [c, rest @ ..] => text.first() == Some(c) && glob_match(rest, &text[1..]),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_language_overrides() {
+        let content = "\
+# Highlighting
+*.inc linguist-language=Rust
+legacy/*.js text linguist-language=TypeScript
+*.lock linguist-generated
+";
+        let result = parse_language_overrides(content);
+        assert_eq!(
+            result,
+            vec![
+                LanguageOverride {
+                    pattern: "*.inc".to_string(),
+                    language: "Rust".to_string(),
+                },
+                LanguageOverride {
+                    pattern: "legacy/*.js".to_string(),
+                    language: "TypeScript".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pattern_matching() {
+        assert!(pattern_matches("*.inc", "a.inc"));
+        assert!(pattern_matches("*.inc", "src/deep/a.inc"));
+        assert!(!pattern_matches("*.inc", "a.inc.rs"));
+        assert!(pattern_matches("legacy/*.js", "legacy/a.js"));
+        assert!(pattern_matches("/legacy/*.js", "legacy/a.js"));
+        assert!(!pattern_matches("legacy/*.js", "legacy/sub/a.js"));
+        assert!(!pattern_matches("legacy/*.js", "src/legacy/a.js"));
+        assert!(pattern_matches("vendor/**/*.js", "vendor/a.js"));
+        assert!(pattern_matches("vendor/**/*.js", "vendor/x/y/a.js"));
+        assert!(pattern_matches("**/gen/*", "src/gen/a.rs"));
+        assert!(pattern_matches("a?.rs", "ab.rs"));
+        assert!(!pattern_matches("a?.rs", "a.rs"));
+    }
+
     #[test]
     fn test_typical_lock_pattern() {
         let content = "*.lock linguist-generated";
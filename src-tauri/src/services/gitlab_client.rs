@@ -3,6 +3,11 @@
 //! Provides HTTP client for GitLab API v4 with authentication and pagination.
 
 use crate::error::AppError;
+#[cfg(feature = "gitlab-fixtures")]
+use crate::services::gitlab_fixtures::{self, FixtureMode};
+use crate::services::instance_headers::{self, CustomHeader};
+use crate::services::instance_tls::{self, InstanceTls};
+use crate::services::rate_limits;
 use reqwest::{header, Client, Response, StatusCode};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
@@ -39,6 +44,9 @@ pub struct GitLabClient {
     config: GitLabClientConfig,
     /// Shared API call counter for metrics instrumentation.
     api_call_count: Arc<AtomicU64>,
+    /// Record or replay responses instead of only talking to GitLab.
+    #[cfg(feature = "gitlab-fixtures")]
+    fixtures: Option<FixtureMode>,
 }
 
 /// Pagination information from GitLab API response headers.
@@ -146,11 +154,32 @@ pub struct GitLabMergeRequest {
     pub updated_at: String,
     pub merged_at: Option<String>,
     pub author: GitLabUser,
+    /// Who merged it; only set on merged MRs.
+    #[serde(default)]
+    pub merged_by: Option<GitLabUser>,
     pub labels: Vec<String>,
     pub reviewers: Option<Vec<GitLabUser>>,
     pub assignees: Option<Vec<GitLabUser>>,
     pub detailed_merge_status: Option<String>,
     pub head_pipeline: Option<GitLabHeadPipeline>,
+    /// Only project members can comment while set. GitLab sends `null`
+    /// for MRs that were never locked.
+    #[serde(default)]
+    pub discussion_locked: Option<bool>,
+    #[serde(default)]
+    pub has_conflicts: bool,
+    #[serde(default)]
+    pub milestone: Option<GitLabMilestone>,
+}
+
+/// A project or group milestone, as embedded in MRs and listed by
+/// `/projects/:id/milestones`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitLabMilestone {
+    pub id: i64,
+    pub title: String,
+    /// `YYYY-MM-DD`.
+    pub due_date: Option<String>,
 }
 
 /// GitLab user from API.
@@ -160,6 +189,14 @@ pub struct GitLabUser {
     pub username: String,
     pub name: String,
     pub avatar_url: Option<String>,
+    /// Bot and service accounts (project/group access tokens, bot users).
+    /// Only sent by newer GitLab versions and not on every endpoint.
+    #[serde(default)]
+    pub bot: bool,
+    /// Only present where the API exposes it (e.g. `/users/:id`, `/user`)
+    /// and the user chose to make it public.
+    #[serde(default)]
+    pub public_email: Option<String>,
 }
 
 /// GitLab diff from API (version endpoint).
@@ -199,6 +236,39 @@ pub struct GitLabFileDiff {
     pub diff: String,
 }
 
+/// Commit of a merge request (GET /projects/:id/merge_requests/:iid/commits).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabCommit {
+    pub id: String,
+    pub short_id: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub message: String,
+    pub author_name: String,
+    #[serde(default)]
+    pub author_email: String,
+    /// ISO 8601 timestamp.
+    pub authored_date: String,
+    #[serde(default)]
+    pub web_url: String,
+}
+
+/// Comparison of two commits (GET /projects/:id/repository/compare).
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitLabCompare {
+    #[serde(default)]
+    pub diffs: Vec<GitLabFileDiff>,
+}
+
+/// A file uploaded to a project, for linking from Markdown.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitLabUpload {
+    pub url: String,
+    /// Ready-made Markdown link, e.g. `[build.log](/uploads/<hash>/build.log)`.
+    pub markdown: String,
+}
+
 /// GitLab note/comment from API.
 #[derive(Debug, Clone, Deserialize)]
 pub struct GitLabNote {
@@ -231,6 +301,81 @@ pub struct GitLabDiscussionNote {
     pub resolvable: bool,
     pub resolved: Option<bool>,
     pub position: Option<GitLabNotePosition>,
+    /// Parsed ```suggestion blocks; only sent for diff notes, and not by
+    /// every GitLab version.
+    #[serde(default)]
+    pub suggestions: Vec<GitLabSuggestion>,
+}
+
+/// A label added to or removed from an MR, from `resource_label_events`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitLabLabelEvent {
+    pub id: i64,
+    /// `None` when the user was deleted.
+    pub user: Option<GitLabUser>,
+    pub created_at: String,
+    /// `None` when the label was deleted since.
+    pub label: Option<GitLabEventLabel>,
+    /// `add` or `remove`.
+    pub action: String,
+}
+
+/// The label of a label event.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitLabEventLabel {
+    pub name: String,
+}
+
+/// A milestone set or removed on an MR, from `resource_milestone_events`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitLabMilestoneEvent {
+    pub id: i64,
+    pub user: Option<GitLabUser>,
+    pub created_at: String,
+    pub milestone: Option<GitLabEventMilestone>,
+    /// `add` or `remove`.
+    pub action: String,
+}
+
+/// The milestone of a milestone event.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitLabEventMilestone {
+    pub title: String,
+}
+
+/// A label that can be applied in a project.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitLabLabel {
+    pub id: i64,
+    pub name: String,
+    pub color: String,
+    pub text_color: Option<String>,
+    pub description: Option<String>,
+}
+
+/// An MR closed, reopened or merged, from `resource_state_events`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitLabStateEvent {
+    pub id: i64,
+    pub user: Option<GitLabUser>,
+    pub created_at: String,
+    /// `closed`, `reopened`, `merged` or `locked`.
+    pub state: String,
+}
+
+/// A change suggested in a diff note.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitLabSuggestion {
+    pub id: i64,
+    /// First and last new-side line the suggestion replaces.
+    pub from_line: i64,
+    pub to_line: i64,
+    #[serde(default)]
+    pub appliable: bool,
+    #[serde(default)]
+    pub applied: bool,
+    #[serde(default)]
+    pub to_content: String,
 }
 
 /// Response from the MR approvals endpoint.
@@ -242,12 +387,42 @@ pub struct MergeRequestApprovals {
     pub approved_by: Vec<ApprovedBy>,
 }
 
+/// Approval rules of an MR (GET .../approval_state). Premium only.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitLabApprovalState {
+    #[serde(default)]
+    pub rules: Vec<GitLabApprovalRule>,
+}
+
+/// One approval rule with its progress.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitLabApprovalRule {
+    pub id: i64,
+    pub name: String,
+    /// `"regular"`, `"code_owner"`, `"any_approver"`, `"report_approver"`.
+    pub rule_type: String,
+    pub approvals_required: i64,
+    pub approved: bool,
+    #[serde(default)]
+    pub approved_by: Vec<GitLabUser>,
+}
+
 /// User who approved an MR.
 #[derive(Debug, Clone, Deserialize)]
 pub struct ApprovedBy {
     pub user: GitLabUser,
 }
 
+/// Reviewer entry from `GET /projects/:id/merge_requests/:iid/reviewers`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitLabMrReviewer {
+    pub user: GitLabUser,
+    /// Review state: `unreviewed`, `reviewed`, `requested_changes`,
+    /// `approved`, `unapproved` or `review_started`. Missing before GitLab 16.
+    #[serde(default)]
+    pub state: Option<String>,
+}
+
 /// Per-MR approval + pipeline state fetched in bulk via GraphQL.
 ///
 /// Replaces the per-MR REST calls to `/approvals` and the MR detail endpoint
@@ -279,6 +454,71 @@ pub struct GitLabProject {
     pub web_url: String,
     pub created_at: Option<String>,
     pub updated_at: Option<String>,
+    /// Absent for projects without a repository.
+    #[serde(default)]
+    pub default_branch: Option<String>,
+    /// The token owner's membership. Only sent on single-project requests.
+    #[serde(default)]
+    pub permissions: Option<GitLabProjectPermissions>,
+}
+
+impl GitLabProject {
+    /// The user's effective access level (0 when not a member), or `None`
+    /// when the response carried no permissions.
+    pub fn access_level(&self) -> Option<i64> {
+        let permissions = self.permissions.as_ref()?;
+        Some(
+            [&permissions.project_access, &permissions.group_access]
+                .into_iter()
+                .flatten()
+                .map(|a| a.access_level)
+                .max()
+                .unwrap_or(0),
+        )
+    }
+}
+
+/// `permissions` object of a single-project response.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitLabProjectPermissions {
+    pub project_access: Option<GitLabAccess>,
+    pub group_access: Option<GitLabAccess>,
+}
+
+/// A membership access level (10 Guest, 20 Reporter, 30 Developer, ...).
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitLabAccess {
+    pub access_level: i64,
+}
+
+/// GitLab namespace from API (GET /namespaces/:id).
+///
+/// The minute limits and storage statistics are only sent to admins, and
+/// only by instances with CI minute quotas (Premium and gitlab.com).
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitLabNamespace {
+    pub id: i64,
+    pub full_path: String,
+    /// `"user"` or `"group"`.
+    pub kind: String,
+    #[serde(default)]
+    pub plan: Option<String>,
+    /// Monthly shared-runner minutes; 0 means unlimited, absent means hidden
+    /// or the instance default.
+    #[serde(default)]
+    pub shared_runners_minutes_limit: Option<i64>,
+    /// Purchased minutes on top of the monthly limit.
+    #[serde(default)]
+    pub extra_shared_runners_minutes_limit: Option<i64>,
+    #[serde(default)]
+    pub root_storage_statistics: Option<GitLabStorageStatistics>,
+}
+
+/// Storage statistics of a root namespace.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitLabStorageStatistics {
+    /// Total bytes used across the namespace's projects.
+    pub storage_size: i64,
 }
 
 /// GitLab pipeline from API (GET /projects/:id/pipelines).
@@ -343,6 +583,146 @@ pub struct GitLabDownstreamPipeline {
     pub web_url: String,
 }
 
+/// Stage order and job needs of a pipeline, from GraphQL. The REST jobs
+/// API reports neither.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GitLabPipelineDag {
+    /// Stage names in pipeline order.
+    pub stages: Vec<String>,
+    pub jobs: Vec<GitLabDagJob>,
+}
+
+/// Scheduling information for one job.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GitLabDagJob {
+    pub id: i64,
+    pub name: String,
+    pub stage: String,
+    /// True when the job declares `needs:`; otherwise it waits for the
+    /// whole previous stage.
+    pub uses_needs: bool,
+    /// Names of the jobs it needs.
+    pub needs: Vec<String>,
+}
+
+/// GitLab pipeline schedule from API (GET /projects/:id/pipeline_schedules).
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitLabPipelineSchedule {
+    pub id: i64,
+    #[serde(default)]
+    pub description: String,
+    /// Short (`main`) or fully qualified (`refs/heads/main`) ref.
+    #[serde(rename = "ref")]
+    pub ref_name: String,
+    pub cron: String,
+    #[serde(default = "default_cron_timezone")]
+    pub cron_timezone: String,
+    pub next_run_at: Option<String>,
+    pub active: bool,
+    pub owner: Option<GitLabUser>,
+}
+
+fn default_cron_timezone() -> String {
+    "UTC".to_string()
+}
+
+/// GitLab release from API (GET /projects/:id/releases).
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitLabRelease {
+    pub tag_name: String,
+    pub name: Option<String>,
+    /// Release notes (Markdown).
+    pub description: Option<String>,
+    pub created_at: String,
+    pub released_at: Option<String>,
+    #[serde(default)]
+    pub upcoming_release: bool,
+    pub author: Option<GitLabUser>,
+    pub commit: Option<GitLabRefCommit>,
+    #[serde(default)]
+    pub assets: GitLabReleaseAssets,
+    #[serde(rename = "_links")]
+    pub links: Option<GitLabReleaseWebLinks>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GitLabReleaseAssets {
+    #[serde(default)]
+    pub links: Vec<GitLabReleaseLink>,
+}
+
+/// Asset link attached to a release (package, binary, runbook, ...).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GitLabReleaseLink {
+    pub name: String,
+    pub url: String,
+    pub link_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabReleaseWebLinks {
+    #[serde(rename = "self")]
+    pub self_url: Option<String>,
+}
+
+/// Commit a release or tag points at.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabRefCommit {
+    pub id: String,
+    #[serde(default)]
+    pub title: String,
+    pub committed_date: Option<String>,
+}
+
+/// GitLab tag from API (GET /projects/:id/repository/tags).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabTag {
+    pub name: String,
+    /// Annotation message; `None` for lightweight tags.
+    pub message: Option<String>,
+    pub commit: Option<GitLabRefCommit>,
+    pub release: Option<GitLabTagRelease>,
+    #[serde(default)]
+    pub protected: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabTagRelease {
+    pub tag_name: String,
+    pub description: Option<String>,
+}
+
+/// Commit of a blame range.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabBlameCommit {
+    pub id: String,
+    #[serde(default)]
+    pub message: String,
+    pub author_name: String,
+    #[serde(default)]
+    pub author_email: String,
+    /// ISO 8601 timestamp.
+    pub authored_date: String,
+}
+
+/// Consecutive lines last changed by one commit
+/// (GET /projects/:id/repository/files/:path/blame).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabBlameRange {
+    pub commit: GitLabBlameCommit,
+    pub lines: Vec<String>,
+}
+
+/// Entry in a repository tree listing (GET /projects/:id/repository/tree).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabTreeEntry {
+    pub name: String,
+    pub path: String,
+    /// `blob`, `tree` or `commit` (submodule).
+    #[serde(rename = "type")]
+    pub entry_type: String,
+}
+
 /// Runner info attached to a job.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitLabJobRunner {
@@ -350,6 +730,61 @@ pub struct GitLabJobRunner {
     pub description: Option<String>,
 }
 
+/// Which bytes of a job log to fetch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceRange {
+    /// The last `n` bytes.
+    Tail(u64),
+    /// `length` bytes starting at `offset`.
+    Span { offset: u64, length: u64 },
+}
+
+impl TraceRange {
+    fn header_value(self) -> String {
+        match self {
+            TraceRange::Tail(n) => format!("bytes=-{}", n),
+            TraceRange::Span { offset, length } => {
+                format!("bytes={}-{}", offset, offset + length.max(1) - 1)
+            }
+        }
+    }
+
+    /// Cut this range out of a whole log, for servers that ignore `Range`.
+    fn slice(self, log: &[u8]) -> JobTraceBytes {
+        let total = log.len() as u64;
+        let (start, end) = match self {
+            TraceRange::Tail(n) => (total.saturating_sub(n), total),
+            TraceRange::Span { offset, length } => {
+                let start = offset.min(total);
+                (start, start.saturating_add(length).min(total))
+            }
+        };
+        JobTraceBytes {
+            bytes: log[start as usize..end as usize].to_vec(),
+            offset: start,
+            total,
+        }
+    }
+}
+
+/// A byte range of a job log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobTraceBytes {
+    pub bytes: Vec<u8>,
+    /// Where `bytes` start in the whole log.
+    pub offset: u64,
+    /// Size of the whole log.
+    pub total: u64,
+}
+
+/// Parse a `Content-Range: bytes <start>-<end>/<total>` header into the
+/// start offset and the total size (None when the server sent `*`).
+fn parse_content_range(value: &str) -> Option<(u64, Option<u64>)> {
+    let (range, total) = value.strip_prefix("bytes ")?.split_once('/')?;
+    let (start, _) = range.split_once('-')?;
+    Some((start.parse().ok()?, total.parse().ok()))
+}
+
 /// GitLab issue from API.
 #[derive(Debug, Clone, Deserialize)]
 pub struct GitLabIssue {
@@ -450,6 +885,28 @@ const MAX_429_RETRIES: u32 = 3;
 impl GitLabClient {
     /// Create a new GitLab client.
     pub fn new(config: GitLabClientConfig) -> Result<Self, AppError> {
+        let custom_headers = instance_headers::headers_for(&config.base_url);
+        Self::with_custom_headers(config, &custom_headers)
+    }
+
+    /// Create a client that sends `custom_headers` on every request instead
+    /// of the ones registered for the instance. Used to validate headers
+    /// before they are saved.
+    pub fn with_custom_headers(
+        config: GitLabClientConfig,
+        custom_headers: &[CustomHeader],
+    ) -> Result<Self, AppError> {
+        let tls = instance_tls::tls_for(&config.base_url);
+        Self::with_connection_settings(config, custom_headers, &tls)
+    }
+
+    /// Create a client with explicit custom headers and TLS settings, used to
+    /// validate both before they are saved.
+    pub fn with_connection_settings(
+        config: GitLabClientConfig,
+        custom_headers: &[CustomHeader],
+        tls: &InstanceTls,
+    ) -> Result<Self, AppError> {
         let mut headers = header::HeaderMap::new();
 
         // Add the private token header for authentication
@@ -457,10 +914,24 @@ impl GitLabClient {
             .map_err(|_| AppError::authentication("Invalid token format"))?;
         headers.insert("PRIVATE-TOKEN", token_value);
 
+        // Extra headers required by e.g. an SSO proxy in front of the instance
+        for custom in custom_headers {
+            let name =
+                header::HeaderName::from_bytes(custom.name.trim().as_bytes()).map_err(|_| {
+                    AppError::invalid_input(format!("Invalid header name: {}", custom.name))
+                })?;
+            let mut value = header::HeaderValue::from_str(&custom.value).map_err(|_| {
+                AppError::invalid_input(format!("Invalid value for header {}", custom.name))
+            })?;
+            value.set_sensitive(true);
+            headers.insert(name, value);
+        }
+
         // Build the HTTP client
-        let client = Client::builder()
+        let builder = Client::builder()
             .default_headers(headers)
-            .timeout(std::time::Duration::from_secs(config.timeout_secs))
+            .timeout(std::time::Duration::from_secs(config.timeout_secs));
+        let client = instance_tls::apply(builder, tls)?
             .build()
             .map_err(|e| AppError::internal(format!("Failed to build HTTP client: {}", e)))?;
 
@@ -468,9 +939,18 @@ impl GitLabClient {
             client,
             config,
             api_call_count: Arc::new(AtomicU64::new(0)),
+            #[cfg(feature = "gitlab-fixtures")]
+            fixtures: gitlab_fixtures::mode(),
         })
     }
 
+    /// Use `mode` for this client instead of the process-wide fixture mode.
+    #[cfg(feature = "gitlab-fixtures")]
+    pub fn with_fixtures(mut self, mode: Option<FixtureMode>) -> Self {
+        self.fixtures = mode;
+        self
+    }
+
     /// Get the base URL for API requests.
     fn api_url(&self, path: &str) -> String {
         format!(
@@ -488,6 +968,8 @@ impl GitLabClient {
         &self,
         request_builder: reqwest::RequestBuilder,
     ) -> Result<Response, AppError> {
+        crate::services::offline_mode::ensure_online()?;
+
         let request = request_builder
             .build()
             .map_err(|e| AppError::internal(format!("Failed to build request: {}", e)))?;
@@ -497,9 +979,10 @@ impl GitLabClient {
                 AppError::internal("Request cannot be cloned for retry")
             })?;
 
-            let response = self.client.execute(req).await?;
+            let response = self.execute(req).await?;
 
             self.api_call_count.fetch_add(1, Ordering::Relaxed);
+            rate_limits::observe(&self.config.base_url, response.headers());
 
             if response.status() == StatusCode::TOO_MANY_REQUESTS && attempt < MAX_429_RETRIES {
                 let delay_secs = response
@@ -526,6 +1009,17 @@ impl GitLabClient {
         Err(AppError::internal("Rate limit retries exhausted"))
     }
 
+    /// Send one request, through the fixtures when a fixture mode is set.
+    async fn execute(&self, request: reqwest::Request) -> Result<Response, AppError> {
+        #[cfg(feature = "gitlab-fixtures")]
+        if let Some(fixtures) = &self.fixtures {
+            return fixtures
+                .execute(&self.client, &self.config.base_url, request)
+                .await;
+        }
+        Ok(self.client.execute(request).await?)
+    }
+
     /// Return the total number of API calls made by this client (and its clones).
     pub fn call_count(&self) -> u64 {
         self.api_call_count.load(Ordering::Relaxed)
@@ -725,6 +1219,25 @@ impl GitLabClient {
         Ok(pipelines.into_iter().next())
     }
 
+    /// Get the latest pipeline that ran on a given branch or tag.
+    pub async fn get_latest_pipeline_for_ref(
+        &self,
+        project_id: i64,
+        ref_name: &str,
+    ) -> Result<Option<GitLabPipeline>, AppError> {
+        let endpoint = format!("/projects/{}/pipelines", project_id);
+        let url = self.api_url(&endpoint);
+        let response = self
+            .send_with_retry(
+                self.client
+                    .get(&url)
+                    .query(&[("ref", ref_name), ("per_page", "1")]),
+            )
+            .await?;
+        let pipelines: Vec<GitLabPipeline> = self.handle_response(response, &endpoint).await?;
+        Ok(pipelines.into_iter().next())
+    }
+
     /// Get a single pipeline by id.
     pub async fn get_pipeline(
         &self,
@@ -779,6 +1292,26 @@ impl GitLabClient {
         self.get_all_pages(&endpoint, None::<&()>).await
     }
 
+    /// Get the project's most recent manual jobs (newest first), across
+    /// pipelines, up to `limit`.
+    pub async fn list_manual_jobs(
+        &self,
+        project_id: i64,
+        limit: u32,
+    ) -> Result<Vec<GitLabJob>, AppError> {
+        let endpoint = format!("/projects/{}/jobs", project_id);
+        let url = self.api_url(&endpoint);
+        let per_page = limit.to_string();
+        let response = self
+            .send_with_retry(
+                self.client
+                    .get(&url)
+                    .query(&[("scope[]", "manual"), ("per_page", per_page.as_str())]),
+            )
+            .await?;
+        self.handle_response(response, &endpoint).await
+    }
+
     /// Get bridge (downstream/child pipeline trigger) jobs for a pipeline.
     pub async fn get_pipeline_bridges(
         &self,
@@ -793,6 +1326,73 @@ impl GitLabClient {
         }
     }
 
+    /// Maximum job pages fetched for a pipeline graph (100 jobs each).
+    const DAG_MAX_PAGES: usize = 20;
+
+    /// Get stage order and job needs of a pipeline via GraphQL.
+    pub async fn get_pipeline_dag(
+        &self,
+        project_path: &str,
+        pipeline_id: i64,
+    ) -> Result<GitLabPipelineDag, AppError> {
+        let path_literal =
+            serde_json::to_string(project_path).unwrap_or_else(|_| format!("\"{}\"", project_path));
+        let mut dag = GitLabPipelineDag::default();
+        let mut after: Option<String> = None;
+
+        for _ in 0..Self::DAG_MAX_PAGES {
+            let after_arg = after
+                .as_deref()
+                .map(|c| format!(", after: {}", serde_json::Value::from(c)))
+                .unwrap_or_default();
+            let query = format!(
+                "query {{ project(fullPath: {path_literal}) {{ \
+                 pipeline(id: \"gid://gitlab/Ci::Pipeline/{pipeline_id}\") {{ \
+                 stages {{ nodes {{ name }} }} \
+                 jobs(retried: false, first: 100{after_arg}) {{ \
+                 pageInfo {{ hasNextPage endCursor }} \
+                 nodes {{ id name schedulingType stage {{ name }} needs {{ nodes {{ name }} }} }} \
+                 }} }} }} }}"
+            );
+            let data = self.graphql(&query).await?;
+            let pipeline = data
+                .get("project")
+                .and_then(|p| p.get("pipeline"))
+                .filter(|p| !p.is_null())
+                .ok_or_else(|| AppError::not_found_with_id("Pipeline", pipeline_id.to_string()))?;
+
+            if dag.stages.is_empty() {
+                dag.stages = pipeline
+                    .pointer("/stages/nodes")
+                    .and_then(|n| n.as_array())
+                    .map(|nodes| {
+                        nodes
+                            .iter()
+                            .filter_map(|s| Some(s.get("name")?.as_str()?.to_string()))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+            }
+            if let Some(nodes) = pipeline.pointer("/jobs/nodes").and_then(|n| n.as_array()) {
+                dag.jobs.extend(nodes.iter().filter_map(parse_dag_job_node));
+            }
+
+            let page_info = pipeline.pointer("/jobs/pageInfo");
+            let has_next = page_info
+                .and_then(|p| p.get("hasNextPage"))
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            after = page_info
+                .and_then(|p| p.get("endCursor"))
+                .and_then(|v| v.as_str())
+                .map(str::to_string);
+            if !has_next || after.is_none() {
+                break;
+            }
+        }
+        Ok(dag)
+    }
+
     /// Play (trigger) a manual job.
     pub async fn play_job(&self, project_id: i64, job_id: i64) -> Result<GitLabJob, AppError> {
         let endpoint = format!("/projects/{}/jobs/{}/play", project_id, job_id);
@@ -825,6 +1425,142 @@ impl GitLabClient {
         self.handle_response(response, &endpoint).await
     }
 
+    /// List a project's pipeline schedules.
+    pub async fn get_pipeline_schedules(
+        &self,
+        project_id: i64,
+    ) -> Result<Vec<GitLabPipelineSchedule>, AppError> {
+        let endpoint = format!("/projects/{}/pipeline_schedules", project_id);
+        self.get_all_pages(&endpoint, None::<&()>).await
+    }
+
+    /// Run a pipeline schedule now. GitLab creates the pipeline asynchronously.
+    pub async fn play_pipeline_schedule(
+        &self,
+        project_id: i64,
+        schedule_id: i64,
+    ) -> Result<(), AppError> {
+        let endpoint = format!(
+            "/projects/{}/pipeline_schedules/{}/play",
+            project_id, schedule_id
+        );
+        let url = self.api_url(&endpoint);
+        let response = self.send_with_retry(self.client.post(&url)).await?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(self.error_from_response(response, &endpoint).await)
+        }
+    }
+
+    /// Activate or deactivate a pipeline schedule.
+    pub async fn set_pipeline_schedule_active(
+        &self,
+        project_id: i64,
+        schedule_id: i64,
+        active: bool,
+    ) -> Result<GitLabPipelineSchedule, AppError> {
+        let endpoint = format!(
+            "/projects/{}/pipeline_schedules/{}",
+            project_id, schedule_id
+        );
+        let url = self.api_url(&endpoint);
+        let response = self
+            .send_with_retry(
+                self.client
+                    .put(&url)
+                    .json(&serde_json::json!({ "active": active })),
+            )
+            .await?;
+        self.handle_response(response, &endpoint).await
+    }
+
+    /// List a project's most recent releases, newest first.
+    pub async fn get_project_releases(
+        &self,
+        project_id: i64,
+        limit: u32,
+    ) -> Result<Vec<GitLabRelease>, AppError> {
+        let endpoint = format!("/projects/{}/releases", project_id);
+        let url = self.api_url(&endpoint);
+        let response = self
+            .send_with_retry(self.client.get(&url).query(&[
+                ("per_page", limit.to_string().as_str()),
+                ("order_by", "released_at"),
+                ("sort", "desc"),
+            ]))
+            .await?;
+        self.handle_response(response, &endpoint).await
+    }
+
+    /// List a project's most recently updated tags.
+    pub async fn get_project_tags(
+        &self,
+        project_id: i64,
+        limit: u32,
+    ) -> Result<Vec<GitLabTag>, AppError> {
+        let endpoint = format!("/projects/{}/repository/tags", project_id);
+        let url = self.api_url(&endpoint);
+        let response = self
+            .send_with_retry(self.client.get(&url).query(&[
+                ("per_page", limit.to_string().as_str()),
+                ("order_by", "updated"),
+                ("sort", "desc"),
+            ]))
+            .await?;
+        self.handle_response(response, &endpoint).await
+    }
+
+    /// Create a new pipeline for a branch or tag.
+    pub async fn create_pipeline(
+        &self,
+        project_id: i64,
+        ref_name: &str,
+    ) -> Result<GitLabPipeline, AppError> {
+        let endpoint = format!("/projects/{}/pipeline", project_id);
+        let url = self.api_url(&endpoint);
+        let response = self
+            .send_with_retry(self.client.post(&url).query(&[("ref", ref_name)]))
+            .await?;
+        self.handle_response(response, &endpoint).await
+    }
+
+    /// Check whether a branch exists in a project's repository.
+    pub async fn branch_exists(&self, project_id: i64, branch: &str) -> Result<bool, AppError> {
+        let endpoint = format!(
+            "/projects/{}/repository/branches/{}",
+            project_id,
+            urlencoding::encode(branch)
+        );
+        let url = self.api_url(&endpoint);
+        let response = self.send_with_retry(self.client.get(&url)).await?;
+
+        if response.status().is_success() {
+            Ok(true)
+        } else if response.status() == StatusCode::NOT_FOUND {
+            Ok(false)
+        } else {
+            Err(self.error_from_response(response, &endpoint).await)
+        }
+    }
+
+    /// Delete a branch from a project's repository.
+    pub async fn delete_branch(&self, project_id: i64, branch: &str) -> Result<(), AppError> {
+        let endpoint = format!(
+            "/projects/{}/repository/branches/{}",
+            project_id,
+            urlencoding::encode(branch)
+        );
+        let url = self.api_url(&endpoint);
+        let response = self.send_with_retry(self.client.delete(&url)).await?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(self.error_from_response(response, &endpoint).await)
+        }
+    }
+
     /// List merge requests.
     pub async fn list_merge_requests(
         &self,
@@ -901,6 +1637,26 @@ impl GitLabClient {
         self.handle_response(response, &endpoint).await
     }
 
+    /// Apply a suggestion, committing it to the MR's source branch.
+    ///
+    /// GitLab API: PUT /suggestions/:id/apply
+    pub async fn apply_suggestion(
+        &self,
+        suggestion_id: i64,
+        commit_message: Option<&str>,
+    ) -> Result<GitLabSuggestion, AppError> {
+        let endpoint = format!("/suggestions/{}/apply", suggestion_id);
+        let url = self.api_url(&endpoint);
+        let body = match commit_message {
+            Some(message) => serde_json::json!({ "commit_message": message }),
+            None => serde_json::json!({}),
+        };
+        let response = self
+            .send_with_retry(self.client.put(&url).json(&body))
+            .await?;
+        self.handle_response(response, &endpoint).await
+    }
+
     /// Update an issue's assignees and/or state.
     pub async fn update_issue(
         &self,
@@ -930,76 +1686,297 @@ impl GitLabClient {
         self.get_all_pages(&endpoint, Some(&query)).await
     }
 
-    /// Get a single merge request by project and IID.
-    pub async fn get_merge_request(
+    /// Get a single merge request by project and IID.
+    pub async fn get_merge_request(
+        &self,
+        project_id: i64,
+        mr_iid: i64,
+    ) -> Result<GitLabMergeRequest, AppError> {
+        let endpoint = format!("/projects/{}/merge_requests/{}", project_id, mr_iid);
+        let url = self.api_url(&endpoint);
+        let response = self.send_with_retry(self.client.get(&url)).await?;
+        self.handle_response(response, &endpoint).await
+    }
+
+    /// Get an MR's approval rules and which of them are satisfied.
+    ///
+    /// Returns `None` on instances without approval rules (404 or 403 on
+    /// the free tier).
+    pub async fn get_mr_approval_state(
+        &self,
+        project_id: i64,
+        mr_iid: i64,
+    ) -> Result<Option<GitLabApprovalState>, AppError> {
+        let endpoint = format!(
+            "/projects/{}/merge_requests/{}/approval_state",
+            project_id, mr_iid
+        );
+        let url = self.api_url(&endpoint);
+        let response = self.send_with_retry(self.client.get(&url)).await?;
+        if matches!(
+            response.status(),
+            StatusCode::NOT_FOUND | StatusCode::FORBIDDEN
+        ) {
+            return Ok(None);
+        }
+        self.handle_response(response, &endpoint).await.map(Some)
+    }
+
+    /// Get a single merge request by project path (URL-encoded) and IID.
+    pub async fn get_merge_request_by_path(
+        &self,
+        project_path: &str,
+        mr_iid: i64,
+    ) -> Result<GitLabMergeRequest, AppError> {
+        let encoded = urlencoding::encode(project_path);
+        let endpoint = format!("/projects/{}/merge_requests/{}", encoded, mr_iid);
+        let url = self.api_url(&endpoint);
+        let response = self.send_with_retry(self.client.get(&url)).await?;
+        self.handle_response(response, &endpoint).await
+    }
+
+    /// List the issues a merge request closes (`Closes #12` references).
+    pub async fn get_mr_closes_issues(
+        &self,
+        project_id: i64,
+        mr_iid: i64,
+    ) -> Result<Vec<GitLabIssue>, AppError> {
+        let endpoint = format!(
+            "/projects/{}/merge_requests/{}/closes_issues",
+            project_id, mr_iid
+        );
+        self.get_all_pages(&endpoint, None::<&()>).await
+    }
+
+    /// Get the latest diff version for a merge request.
+    pub async fn get_merge_request_diff(
+        &self,
+        project_id: i64,
+        mr_iid: i64,
+    ) -> Result<GitLabDiffVersion, AppError> {
+        // First, get the list of versions
+        let versions_endpoint = format!(
+            "/projects/{}/merge_requests/{}/versions",
+            project_id, mr_iid
+        );
+        let url = self.api_url(&versions_endpoint);
+        let response = self.send_with_retry(self.client.get(&url)).await?;
+
+        let versions: Vec<serde_json::Value> =
+            self.handle_response(response, &versions_endpoint).await?;
+
+        // Get the latest version (first in list)
+        let version_id = versions
+            .first()
+            .and_then(|v| v.get("id"))
+            .and_then(|id| id.as_i64())
+            .ok_or_else(|| AppError::not_found("No diff versions found"))?;
+
+        // Fetch the full version with diffs
+        let version_endpoint = format!(
+            "/projects/{}/merge_requests/{}/versions/{}",
+            project_id, mr_iid, version_id
+        );
+        let url = self.api_url(&version_endpoint);
+        let response = self.send_with_retry(self.client.get(&url)).await?;
+        self.handle_response(response, &version_endpoint).await
+    }
+
+    /// List the commits of a merge request, newest first.
+    pub async fn get_mr_commits(
+        &self,
+        project_id: i64,
+        mr_iid: i64,
+    ) -> Result<Vec<GitLabCommit>, AppError> {
+        let endpoint = format!("/projects/{}/merge_requests/{}/commits", project_id, mr_iid);
+        self.get_all_pages(&endpoint, None::<&()>).await
+    }
+
+    /// Get the changes a commit made, compared with its first parent.
+    pub async fn get_commit_diff(
+        &self,
+        project_id: i64,
+        sha: &str,
+    ) -> Result<Vec<GitLabFileDiff>, AppError> {
+        let endpoint = format!("/projects/{}/repository/commits/{}/diff", project_id, sha);
+        self.get_all_pages(&endpoint, None::<&()>).await
+    }
+
+    /// File diffs between two commits, taken straight from `from` to `to`
+    /// rather than from their merge base.
+    pub async fn compare(
+        &self,
+        project_id: i64,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<GitLabFileDiff>, AppError> {
+        let endpoint = format!("/projects/{}/repository/compare", project_id);
+        let url = self.api_url(&endpoint);
+        let response = self
+            .send_with_retry(self.client.get(&url).query(&[
+                ("from", from),
+                ("to", to),
+                ("straight", "true"),
+            ]))
+            .await?;
+        let compare: GitLabCompare = self.handle_response(response, &endpoint).await?;
+        Ok(compare.diffs)
+    }
+
+    /// List discussions on a merge request.
+    pub async fn list_discussions(
+        &self,
+        project_id: i64,
+        mr_iid: i64,
+    ) -> Result<Vec<GitLabDiscussion>, AppError> {
+        let endpoint = format!(
+            "/projects/{}/merge_requests/{}/discussions",
+            project_id, mr_iid
+        );
+        self.get_all_pages(&endpoint, None::<&()>).await
+    }
+
+    /// List label changes of a merge request, oldest first.
+    pub async fn list_label_events(
+        &self,
+        project_id: i64,
+        mr_iid: i64,
+    ) -> Result<Vec<GitLabLabelEvent>, AppError> {
+        let endpoint = format!(
+            "/projects/{}/merge_requests/{}/resource_label_events",
+            project_id, mr_iid
+        );
+        self.get_all_pages(&endpoint, None::<&()>).await
+    }
+
+    /// List milestone changes of a merge request, oldest first.
+    pub async fn list_milestone_events(
+        &self,
+        project_id: i64,
+        mr_iid: i64,
+    ) -> Result<Vec<GitLabMilestoneEvent>, AppError> {
+        let endpoint = format!(
+            "/projects/{}/merge_requests/{}/resource_milestone_events",
+            project_id, mr_iid
+        );
+        self.get_all_pages(&endpoint, None::<&()>).await
+    }
+
+    /// List state changes (closed, reopened, merged) of a merge request,
+    /// oldest first.
+    pub async fn list_state_events(
+        &self,
+        project_id: i64,
+        mr_iid: i64,
+    ) -> Result<Vec<GitLabStateEvent>, AppError> {
+        let endpoint = format!(
+            "/projects/{}/merge_requests/{}/resource_state_events",
+            project_id, mr_iid
+        );
+        self.get_all_pages(&endpoint, None::<&()>).await
+    }
+
+    /// List the labels usable in a project, including its groups' labels.
+    pub async fn list_project_labels(&self, project_id: i64) -> Result<Vec<GitLabLabel>, AppError> {
+        let endpoint = format!("/projects/{}/labels", project_id);
+        self.get_all_pages(&endpoint, Some(&[("include_ancestor_groups", "true")]))
+            .await
+    }
+
+    /// List the active milestones of a project, including its groups'.
+    pub async fn list_project_milestones(
+        &self,
+        project_id: i64,
+    ) -> Result<Vec<GitLabMilestone>, AppError> {
+        let endpoint = format!("/projects/{}/milestones", project_id);
+        self.get_all_pages(
+            &endpoint,
+            Some(&[("state", "active"), ("include_ancestors", "true")]),
+        )
+        .await
+    }
+
+    /// Set the milestone of a merge request; `None` removes it.
+    pub async fn update_merge_request_milestone(
+        &self,
+        project_id: i64,
+        mr_iid: i64,
+        milestone_id: Option<i64>,
+    ) -> Result<GitLabMergeRequest, AppError> {
+        let endpoint = format!("/projects/{}/merge_requests/{}", project_id, mr_iid);
+        let url = self.api_url(&endpoint);
+        // GitLab removes the milestone when given `0`.
+        let response = self
+            .send_with_retry(self.client.put(&url).json(&serde_json::json!({
+                "milestone_id": milestone_id.unwrap_or(0),
+            })))
+            .await?;
+        self.handle_response(response, &endpoint).await
+    }
+
+    /// Add and remove labels on a merge request. Labels not mentioned are
+    /// left alone, so concurrent edits of other labels aren't lost.
+    pub async fn update_merge_request_labels(
         &self,
         project_id: i64,
         mr_iid: i64,
+        add: &[String],
+        remove: &[String],
     ) -> Result<GitLabMergeRequest, AppError> {
         let endpoint = format!("/projects/{}/merge_requests/{}", project_id, mr_iid);
         let url = self.api_url(&endpoint);
-        let response = self.send_with_retry(self.client.get(&url)).await?;
+        let response = self
+            .send_with_retry(self.client.put(&url).json(&serde_json::json!({
+                "add_labels": add.join(","),
+                "remove_labels": remove.join(","),
+            })))
+            .await?;
         self.handle_response(response, &endpoint).await
     }
 
-    /// Get a single merge request by project path (URL-encoded) and IID.
-    pub async fn get_merge_request_by_path(
+    /// Replace the reviewers of a merge request. An empty list removes all.
+    pub async fn update_merge_request_reviewers(
         &self,
-        project_path: &str,
+        project_id: i64,
         mr_iid: i64,
+        user_ids: &[i64],
     ) -> Result<GitLabMergeRequest, AppError> {
-        let encoded = urlencoding::encode(project_path);
-        let endpoint = format!("/projects/{}/merge_requests/{}", encoded, mr_iid);
-        let url = self.api_url(&endpoint);
-        let response = self.send_with_retry(self.client.get(&url)).await?;
-        self.handle_response(response, &endpoint).await
+        self.update_merge_request_users(project_id, mr_iid, "reviewer_ids", user_ids)
+            .await
     }
 
-    /// Get the latest diff version for a merge request.
-    pub async fn get_merge_request_diff(
+    /// Replace the assignees of a merge request. An empty list removes all.
+    pub async fn update_merge_request_assignees(
         &self,
         project_id: i64,
         mr_iid: i64,
-    ) -> Result<GitLabDiffVersion, AppError> {
-        // First, get the list of versions
-        let versions_endpoint = format!(
-            "/projects/{}/merge_requests/{}/versions",
-            project_id, mr_iid
-        );
-        let url = self.api_url(&versions_endpoint);
-        let response = self.send_with_retry(self.client.get(&url)).await?;
-
-        let versions: Vec<serde_json::Value> =
-            self.handle_response(response, &versions_endpoint).await?;
-
-        // Get the latest version (first in list)
-        let version_id = versions
-            .first()
-            .and_then(|v| v.get("id"))
-            .and_then(|id| id.as_i64())
-            .ok_or_else(|| AppError::not_found("No diff versions found"))?;
-
-        // Fetch the full version with diffs
-        let version_endpoint = format!(
-            "/projects/{}/merge_requests/{}/versions/{}",
-            project_id, mr_iid, version_id
-        );
-        let url = self.api_url(&version_endpoint);
-        let response = self.send_with_retry(self.client.get(&url)).await?;
-        self.handle_response(response, &version_endpoint).await
+        user_ids: &[i64],
+    ) -> Result<GitLabMergeRequest, AppError> {
+        self.update_merge_request_users(project_id, mr_iid, "assignee_ids", user_ids)
+            .await
     }
 
-    /// List discussions on a merge request.
-    pub async fn list_discussions(
+    async fn update_merge_request_users(
         &self,
         project_id: i64,
         mr_iid: i64,
-    ) -> Result<Vec<GitLabDiscussion>, AppError> {
-        let endpoint = format!(
-            "/projects/{}/merge_requests/{}/discussions",
-            project_id, mr_iid
-        );
-        self.get_all_pages(&endpoint, None::<&()>).await
+        field: &str,
+        user_ids: &[i64],
+    ) -> Result<GitLabMergeRequest, AppError> {
+        let endpoint = format!("/projects/{}/merge_requests/{}", project_id, mr_iid);
+        let url = self.api_url(&endpoint);
+        // GitLab clears the list when given `0`.
+        let ids: Vec<i64> = if user_ids.is_empty() {
+            vec![0]
+        } else {
+            user_ids.to_vec()
+        };
+        let mut body = serde_json::Map::new();
+        body.insert(field.to_string(), ids.into());
+        let response = self
+            .send_with_retry(self.client.put(&url).json(&body))
+            .await?;
+        self.handle_response(response, &endpoint).await
     }
 
     /// Read an error response body and return an appropriate AppError.
@@ -1159,13 +2136,36 @@ impl GitLabClient {
         }
     }
 
+    /// Replace the description of a merge request.
+    pub async fn update_merge_request_description(
+        &self,
+        project_id: i64,
+        mr_iid: i64,
+        description: &str,
+    ) -> Result<GitLabMergeRequest, AppError> {
+        let endpoint = format!("/projects/{}/merge_requests/{}", project_id, mr_iid);
+        let url = self.api_url(&endpoint);
+        let response = self
+            .send_with_retry(
+                self.client
+                    .put(&url)
+                    .json(&serde_json::json!({ "description": description })),
+            )
+            .await?;
+        self.handle_response(response, &endpoint).await
+    }
+
     /// Add a general comment to a merge request.
+    ///
+    /// The body is posted verbatim so GitLab executes any quick actions in it.
+    /// A body made only of quick actions creates no note: GitLab answers
+    /// 202 with `{commands_changes, summary}`, which is returned as `None`.
     pub async fn add_comment(
         &self,
         project_id: i64,
         mr_iid: i64,
         body: &str,
-    ) -> Result<GitLabNote, AppError> {
+    ) -> Result<Option<GitLabNote>, AppError> {
         let endpoint = format!("/projects/{}/merge_requests/{}/notes", project_id, mr_iid);
         let url = self.api_url(&endpoint);
 
@@ -1177,7 +2177,13 @@ impl GitLabClient {
             )
             .await?;
 
-        self.handle_response(response, &endpoint).await
+        let value: serde_json::Value = self.handle_response(response, &endpoint).await?;
+        if value.get("id").is_none() {
+            return Ok(None);
+        }
+        serde_json::from_value(value)
+            .map(Some)
+            .map_err(|e| AppError::internal(format!("Failed to parse response: {}", e)))
     }
 
     /// Add an inline comment to a merge request at a specific line.
@@ -1242,6 +2248,43 @@ impl GitLabClient {
         self.handle_response(response, &endpoint).await
     }
 
+    /// Upload a file to a project so comments can link to it.
+    ///
+    /// The multipart body is built by hand: reqwest's streaming multipart forms
+    /// can't be cloned, which `send_with_retry` needs for 429 retries.
+    pub async fn upload_project_file(
+        &self,
+        project_id: i64,
+        filename: &str,
+        content: &[u8],
+    ) -> Result<GitLabUpload, AppError> {
+        let endpoint = format!("/projects/{}/uploads", project_id);
+        let url = self.api_url(&endpoint);
+
+        let boundary = format!("ultra-gitlab-{}", uuid::Uuid::new_v4().simple());
+        let filename = filename.replace(['"', '\r', '\n'], "_");
+        let mut body = format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{filename}\"\r\nContent-Type: application/octet-stream\r\n\r\n"
+        )
+        .into_bytes();
+        body.extend_from_slice(content);
+        body.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+
+        let response = self
+            .send_with_retry(
+                self.client
+                    .post(&url)
+                    .header(
+                        reqwest::header::CONTENT_TYPE,
+                        format!("multipart/form-data; boundary={boundary}"),
+                    )
+                    .body(body),
+            )
+            .await?;
+
+        self.handle_response(response, &endpoint).await
+    }
+
     /// Reply to a discussion.
     pub async fn reply_to_discussion(
         &self,
@@ -1282,6 +2325,53 @@ impl GitLabClient {
         self.handle_response(response, &endpoint).await
     }
 
+    /// Get the reviewers of a merge request with their review state.
+    pub async fn get_mr_reviewer_states(
+        &self,
+        project_id: i64,
+        mr_iid: i64,
+    ) -> Result<Vec<GitLabMrReviewer>, AppError> {
+        let endpoint = format!(
+            "/projects/{}/merge_requests/{}/reviewers",
+            project_id, mr_iid
+        );
+        let url = self.api_url(&endpoint);
+        let response = self.send_with_retry(self.client.get(&url)).await?;
+        self.handle_response(response, &endpoint).await
+    }
+
+    /// Ask a reviewer to review a merge request again (GitLab 16+).
+    ///
+    /// There is no REST endpoint for this; it uses the
+    /// `mergeRequestReviewerRereview` GraphQL mutation.
+    pub async fn rerequest_review(
+        &self,
+        project_path: &str,
+        mr_iid: i64,
+        user_id: i64,
+    ) -> Result<(), AppError> {
+        let path_literal =
+            serde_json::to_string(project_path).unwrap_or_else(|_| format!("\"{}\"", project_path));
+        let query = format!(
+            "mutation {{ mergeRequestReviewerRereview(input: {{ projectPath: {path_literal}, \
+             iid: \"{mr_iid}\", userId: \"gid://gitlab/User/{user_id}\" }}) {{ errors }} }}"
+        );
+        let data = self.graphql(&query).await?;
+        let errors = data
+            .get("mergeRequestReviewerRereview")
+            .and_then(|m| m.get("errors"))
+            .and_then(|e| e.as_array())
+            .cloned()
+            .unwrap_or_default();
+        if !errors.is_empty() {
+            return Err(AppError::internal(format!(
+                "Re-requesting review failed: {}",
+                serde_json::Value::Array(errors)
+            )));
+        }
+        Ok(())
+    }
+
     /// Execute a GraphQL query against the instance's `/api/graphql` endpoint.
     ///
     /// Returns the `data` payload. GraphQL-level errors are mapped to `AppError`.
@@ -1483,6 +2573,185 @@ impl GitLabClient {
             .map_err(|e| AppError::internal(format!("Failed to read file content: {}", e)))
     }
 
+    /// Blame a file at a ref.
+    ///
+    /// Returns an empty list when the file doesn't exist at that ref (404).
+    pub async fn get_file_blame(
+        &self,
+        project_id: i64,
+        file_path: &str,
+        ref_name: &str,
+    ) -> Result<Vec<GitLabBlameRange>, AppError> {
+        let endpoint = format!(
+            "/projects/{}/repository/files/{}/blame",
+            project_id,
+            urlencoding::encode(file_path)
+        );
+        let url = self.api_url(&endpoint);
+        let response = self
+            .send_with_retry(self.client.get(&url).query(&[("ref", ref_name)]))
+            .await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(Vec::new());
+        }
+        self.handle_response(response, &endpoint).await
+    }
+
+    /// Get a namespace by id or full path.
+    pub async fn get_namespace(&self, id_or_path: &str) -> Result<GitLabNamespace, AppError> {
+        let endpoint = format!("/namespaces/{}", urlencoding::encode(id_or_path));
+        let url = self.api_url(&endpoint);
+        let response = self.send_with_retry(self.client.get(&url)).await?;
+        self.handle_response(response, &endpoint).await
+    }
+
+    /// Shared-runner minutes a namespace used in the month starting
+    /// `month_start` (`YYYY-MM-01`). `None` when GitLab has no usage for that
+    /// month; an error on instances without CI minute tracking.
+    pub async fn get_ci_minutes_used(
+        &self,
+        namespace_id: i64,
+        month_start: &str,
+    ) -> Result<Option<i64>, AppError> {
+        let query = format!(
+            "query {{ ciMinutesUsage(namespaceId: \"gid://gitlab/Namespace/{namespace_id}\", \
+             date: \"{month_start}\") {{ nodes {{ monthIso8601 minutes }} }} }}"
+        );
+        let data = self.graphql(&query).await?;
+        let nodes = data
+            .get("ciMinutesUsage")
+            .and_then(|u| u.get("nodes"))
+            .and_then(|n| n.as_array())
+            .cloned()
+            .unwrap_or_default();
+        Ok(nodes
+            .iter()
+            .find(|n| n.get("monthIso8601").and_then(|m| m.as_str()) == Some(month_start))
+            .and_then(|n| n.get("minutes"))
+            .and_then(|m| m.as_i64()))
+    }
+
+    /// Storage used by a root namespace, from GraphQL, which unlike REST
+    /// also answers group owners. `None` when the statistics are hidden.
+    pub async fn get_namespace_storage_size(
+        &self,
+        full_path: &str,
+    ) -> Result<Option<i64>, AppError> {
+        let path_literal =
+            serde_json::to_string(full_path).unwrap_or_else(|_| format!("\"{}\"", full_path));
+        let query = format!(
+            "query {{ namespace(fullPath: {path_literal}) {{ rootStorageStatistics {{ storageSize }} }} }}"
+        );
+        let data = self.graphql(&query).await?;
+        Ok(data
+            .get("namespace")
+            .and_then(|n| n.get("rootStorageStatistics"))
+            .and_then(|s| s.get("storageSize"))
+            .and_then(|s| s.as_f64())
+            .map(|s| s as i64))
+    }
+
+    /// List the top-level entries of a repository at a ref.
+    ///
+    /// Returns an empty list when the ref or repository doesn't exist (404).
+    pub async fn get_repository_root(
+        &self,
+        project_id: i64,
+        ref_name: &str,
+    ) -> Result<Vec<GitLabTreeEntry>, AppError> {
+        self.get_repository_tree(project_id, "", ref_name).await
+    }
+
+    /// List the entries of a repository directory at a ref (`""` for the root).
+    ///
+    /// Returns an empty list when the ref, directory or repository doesn't
+    /// exist (404).
+    pub async fn get_repository_tree(
+        &self,
+        project_id: i64,
+        path: &str,
+        ref_name: &str,
+    ) -> Result<Vec<GitLabTreeEntry>, AppError> {
+        let endpoint = format!("/projects/{}/repository/tree", project_id);
+        let url = self.api_url(&endpoint);
+        let mut query = vec![("ref", ref_name), ("per_page", "100")];
+        if !path.is_empty() {
+            query.push(("path", path));
+        }
+        let response = self
+            .send_with_retry(self.client.get(&url).query(&query))
+            .await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(Vec::new());
+        }
+        self.handle_response(response, &endpoint).await
+    }
+
+    /// Render GitLab Flavored Markdown to HTML.
+    ///
+    /// Calls `POST /markdown`. When `project_path` is given, relative links,
+    /// images and references resolve against that project.
+    pub async fn render_markdown(
+        &self,
+        text: &str,
+        project_path: Option<&str>,
+    ) -> Result<String, AppError> {
+        #[derive(Deserialize)]
+        struct Rendered {
+            html: String,
+        }
+
+        let endpoint = "/markdown";
+        let url = self.api_url(endpoint);
+        let mut body = serde_json::json!({ "text": text, "gfm": true });
+        if let Some(path) = project_path {
+            body["project"] = serde_json::Value::from(path);
+        }
+        let response = self
+            .send_with_retry(self.client.post(&url).json(&body))
+            .await?;
+        let rendered: Rendered = self.handle_response(response, endpoint).await?;
+        Ok(rendered.html)
+    }
+
+    /// Send an arbitrary request to `endpoint` (relative to `/api/v4`).
+    ///
+    /// Backs the raw API passthrough; callers validate the method and path.
+    /// Returns the parsed JSON body, a JSON string for non-JSON bodies, or
+    /// `null` for empty ones (e.g. `204 No Content`).
+    pub async fn raw_request(
+        &self,
+        method: reqwest::Method,
+        endpoint: &str,
+        body: Option<&serde_json::Value>,
+    ) -> Result<serde_json::Value, AppError> {
+        let url = self.api_url(endpoint);
+        let mut request = self.client.request(method, &url);
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+        let response = self.send_with_retry(request).await?;
+
+        let status = response.status();
+        if status == StatusCode::UNAUTHORIZED {
+            return Err(AppError::authentication_expired(
+                "GitLab token expired or revoked. Please re-authenticate.",
+            ));
+        }
+        if !status.is_success() {
+            return Err(self.error_from_response(response, endpoint).await);
+        }
+
+        let text = response
+            .text()
+            .await
+            .map_err(|e| AppError::internal(format!("Failed to read response: {}", e)))?;
+        if text.trim().is_empty() {
+            return Ok(serde_json::Value::Null);
+        }
+        Ok(serde_json::from_str(&text).unwrap_or(serde_json::Value::String(text)))
+    }
+
     /// Get the raw log (trace) output for a job.
     ///
     /// Calls `GET /projects/:id/jobs/:job_id/trace` and returns the plain-text log.
@@ -1515,6 +2784,80 @@ impl GitLabClient {
             .map_err(|e| AppError::internal(format!("Failed to read job trace: {}", e)))
     }
 
+    /// Get a byte range of a job's log.
+    ///
+    /// Sends a `Range` request to `GET /projects/:id/jobs/:job_id/trace`. When
+    /// GitLab serves the whole log instead (traces stored outside object
+    /// storage, or running jobs), the range is cut out locally. A missing
+    /// trace is an empty log.
+    pub async fn get_job_trace_range(
+        &self,
+        project_id: i64,
+        job_id: i64,
+        range: TraceRange,
+    ) -> Result<JobTraceBytes, AppError> {
+        let endpoint = format!("/projects/{}/jobs/{}/trace", project_id, job_id);
+        let url = self.api_url(&endpoint);
+        let request = self
+            .client
+            .get(&url)
+            .header(header::RANGE, range.header_value());
+        let response = self.send_with_retry(request).await?;
+
+        let status = response.status();
+        let content_range = response
+            .headers()
+            .get(header::CONTENT_RANGE)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        match status {
+            StatusCode::NOT_FOUND => return Ok(range.slice(&[])),
+            StatusCode::UNAUTHORIZED => {
+                return Err(AppError::authentication_expired(
+                    "GitLab token expired or revoked. Please re-authenticate.",
+                ))
+            }
+            // Asked past the end, e.g. an empty log: `Content-Range: bytes */<total>`.
+            StatusCode::RANGE_NOT_SATISFIABLE => {
+                let total = content_range
+                    .as_deref()
+                    .and_then(|v| v.strip_prefix("bytes */"))
+                    .and_then(|t| t.parse().ok())
+                    .unwrap_or(0);
+                return Ok(JobTraceBytes {
+                    bytes: Vec::new(),
+                    offset: total,
+                    total,
+                });
+            }
+            s if !s.is_success() => {
+                return Err(AppError::gitlab_api_full(
+                    "Failed to fetch job trace",
+                    s.as_u16(),
+                    &endpoint,
+                ))
+            }
+            _ => {}
+        }
+
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| AppError::internal(format!("Failed to read job trace: {}", e)))?;
+        if status != StatusCode::PARTIAL_CONTENT {
+            return Ok(range.slice(&bytes));
+        }
+        let (offset, total) = content_range
+            .as_deref()
+            .and_then(parse_content_range)
+            .ok_or_else(|| AppError::internal("Job trace range response without Content-Range"))?;
+        Ok(JobTraceBytes {
+            offset,
+            total: total.unwrap_or(offset + bytes.len() as u64),
+            bytes: bytes.to_vec(),
+        })
+    }
+
     /// Get raw file content as bytes at a specific SHA.
     ///
     /// This fetches binary file content from the repository at a specific commit.
@@ -1607,6 +2950,8 @@ fn parse_mr_state_node(node: &serde_json::Value) -> Option<(i64, BatchedMrState)
                                 .get("avatarUrl")
                                 .and_then(|a| a.as_str())
                                 .map(String::from),
+                            bot: false,
+                            public_email: None,
                         },
                     })
                 })
@@ -1653,6 +2998,26 @@ fn parse_graphql_user_id(gid: &str) -> Option<i64> {
     gid.rsplit('/').next()?.parse().ok()
 }
 
+/// Parse one `CiJob` node of the pipeline DAG query.
+fn parse_dag_job_node(node: &serde_json::Value) -> Option<GitLabDagJob> {
+    Some(GitLabDagJob {
+        id: parse_graphql_user_id(node.get("id")?.as_str()?)?,
+        name: node.get("name")?.as_str()?.to_string(),
+        stage: node.pointer("/stage/name")?.as_str()?.to_string(),
+        uses_needs: node.get("schedulingType").and_then(|s| s.as_str()) == Some("dag"),
+        needs: node
+            .pointer("/needs/nodes")
+            .and_then(|n| n.as_array())
+            .map(|nodes| {
+                nodes
+                    .iter()
+                    .filter_map(|n| Some(n.get("name")?.as_str()?.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default(),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1690,6 +3055,41 @@ mod tests {
         assert_eq!(message, "Access denied");
     }
 
+    #[test]
+    fn test_trace_range_header_and_local_slice() {
+        assert_eq!(TraceRange::Tail(1024).header_value(), "bytes=-1024");
+        let span = TraceRange::Span {
+            offset: 2,
+            length: 3,
+        };
+        assert_eq!(span.header_value(), "bytes=2-4");
+
+        let log = b"abcdefgh";
+        let tail = TraceRange::Tail(3).slice(log);
+        assert_eq!(
+            (tail.bytes.as_slice(), tail.offset, tail.total),
+            (&b"fgh"[..], 5, 8)
+        );
+        assert_eq!(TraceRange::Tail(64).slice(log).offset, 0);
+        assert_eq!(span.slice(log).bytes, b"cde");
+        let past_end = TraceRange::Span {
+            offset: 20,
+            length: 3,
+        }
+        .slice(log);
+        assert_eq!((past_end.bytes.len(), past_end.offset), (0, 8));
+    }
+
+    #[test]
+    fn test_parse_content_range() {
+        assert_eq!(
+            parse_content_range("bytes 100-199/5000"),
+            Some((100, Some(5000)))
+        );
+        assert_eq!(parse_content_range("bytes 100-199/*"), Some((100, None)));
+        assert_eq!(parse_content_range("items 1-2/3"), None);
+    }
+
     #[test]
     fn test_merge_requests_query_serialization() {
         let query = MergeRequestsQuery {
@@ -1775,6 +3175,27 @@ mod tests {
         assert!(job.downstream_pipeline.is_none());
     }
 
+    #[test]
+    fn test_project_access_level_from_permissions() {
+        let base = r#""id": 1, "name": "p", "name_with_namespace": "g / p",
+            "path_with_namespace": "g/p", "web_url": "https://gitlab.example.com/g/p""#;
+        let project: GitLabProject = serde_json::from_str(&format!(
+            r#"{{{base}, "permissions": {{"project_access": {{"access_level": 20}},
+                "group_access": {{"access_level": 30}}}}}}"#
+        ))
+        .unwrap();
+        assert_eq!(project.access_level(), Some(30));
+
+        let project: GitLabProject = serde_json::from_str(&format!(
+            r#"{{{base}, "permissions": {{"project_access": null, "group_access": null}}}}"#
+        ))
+        .unwrap();
+        assert_eq!(project.access_level(), Some(0));
+
+        let project: GitLabProject = serde_json::from_str(&format!("{{{base}}}")).unwrap();
+        assert_eq!(project.access_level(), None);
+    }
+
     #[test]
     fn test_build_mr_states_query_aliases_projects() {
         let groups = vec![
@@ -1833,4 +3254,73 @@ mod tests {
         assert!(state.approvals.approved_by.is_empty());
         assert!(state.head_pipeline_status.is_none());
     }
+
+    #[test]
+    fn test_parse_dag_job_node() {
+        let node = serde_json::json!({
+            "id": "gid://gitlab/Ci::Build/501",
+            "name": "deploy",
+            "schedulingType": "dag",
+            "stage": { "name": "deploy" },
+            "needs": { "nodes": [{ "name": "build" }, { "name": "test" }] }
+        });
+        let job = parse_dag_job_node(&node).unwrap();
+        assert_eq!(job.id, 501);
+        assert!(job.uses_needs);
+        assert_eq!(job.needs, vec!["build", "test"]);
+
+        let bridge = serde_json::json!({
+            "id": "gid://gitlab/Ci::Bridge/502",
+            "name": "child",
+            "schedulingType": "stage",
+            "stage": { "name": "deploy" },
+            "needs": null
+        });
+        let job = parse_dag_job_node(&bridge).unwrap();
+        assert_eq!(job.id, 502);
+        assert!(!job.uses_needs);
+        assert!(job.needs.is_empty());
+    }
+
+    /// Answer one request with `status` and a JSON `body`; returns the base URL.
+    async fn serve_json_once(status: &'static str, body: &'static str) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut head = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = socket.read(&mut buf).await.unwrap();
+                head.extend_from_slice(&buf[..n]);
+            }
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn test_add_comment_with_only_quick_actions_returns_none() {
+        let url = serve_json_once(
+            "202 Accepted",
+            r#"{"commands_changes":{"approve":true},"summary":["Approved the current merge request."]}"#,
+        )
+        .await;
+        let client = GitLabClient::new(GitLabClientConfig {
+            base_url: url,
+            token: "t".to_string(),
+            timeout_secs: 5,
+        })
+        .unwrap();
+
+        let note = client.add_comment(10, 1, "/approve").await.unwrap();
+        assert!(note.is_none());
+    }
 }
@@ -0,0 +1,388 @@
+//! Record/replay of GitLab API traffic.
+//!
+//! Built with the `gitlab-fixtures` feature. In record mode every request a
+//! [`GitLabClient`](super::gitlab_client::GitLabClient) sends still goes to
+//! GitLab, and the response is written to a fixture file. In replay mode
+//! nothing leaves the machine: responses are read back from the fixtures,
+//! and a request without a fixture gets a 404.
+//!
+//! Fixtures are keyed by method, the path and query relative to the
+//! instance URL, and the request body, so fixtures recorded against one
+//! instance replay against any other. Request headers, including the
+//! token, are never written.
+//!
+//! The mode is process-wide so every client picks it up, wherever it is
+//! built. The app sets it from the command line:
+//!
+//! - `--mock-gitlab[=DIR]` replays from `DIR` (default: the fixtures the
+//!   integration tests use);
+//! - `--record-gitlab=DIR` records into `DIR`.
+//!
+//! Tests set it on a single client with
+//! [`GitLabClient::with_fixtures`](super::gitlab_client::GitLabClient::with_fixtures).
+
+use crate::error::AppError;
+use base64::Engine;
+use reqwest::{Client, Request, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+/// Command-line flag that replays GitLab responses from fixtures.
+pub const MOCK_FLAG: &str = "--mock-gitlab";
+
+/// Command-line flag that records GitLab responses to fixtures.
+pub const RECORD_FLAG: &str = "--record-gitlab";
+
+/// Response headers kept in fixtures. The client reads nothing else.
+const RECORDED_HEADERS: &[&str] = &[
+    "content-range",
+    "content-type",
+    "link",
+    "retry-after",
+    "x-next-page",
+    "x-page",
+    "x-per-page",
+    "x-total",
+    "x-total-pages",
+];
+
+/// Longest readable path prefix in a fixture file name.
+const MAX_SLUG_LEN: usize = 80;
+
+/// What clients do with fixtures.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FixtureMode {
+    /// Send requests and write each response to the directory.
+    Record(PathBuf),
+    /// Answer requests from the directory without sending them.
+    Replay(PathBuf),
+}
+
+static MODE: RwLock<Option<FixtureMode>> = RwLock::new(None);
+
+/// Fixtures the integration tests replay; the default for `--mock-gitlab`.
+pub fn default_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/gitlab")
+}
+
+/// The process-wide mode, picked up by clients when they are built.
+pub fn mode() -> Option<FixtureMode> {
+    MODE.read().map(|m| m.clone()).unwrap_or(None)
+}
+
+/// Set the process-wide mode. Clients built earlier keep theirs.
+pub fn set_mode(mode: Option<FixtureMode>) {
+    if let Ok(mut current) = MODE.write() {
+        *current = mode;
+    }
+}
+
+/// The mode the command line asks for, if any.
+pub fn mode_from_args<I: IntoIterator<Item = String>>(args: I) -> Option<FixtureMode> {
+    args.into_iter().find_map(|arg| {
+        if arg == MOCK_FLAG {
+            Some(FixtureMode::Replay(default_dir()))
+        } else if let Some(dir) = arg
+            .strip_prefix(MOCK_FLAG)
+            .and_then(|r| r.strip_prefix('='))
+        {
+            Some(FixtureMode::Replay(PathBuf::from(dir)))
+        } else {
+            let dir = arg.strip_prefix(RECORD_FLAG)?.strip_prefix('=')?;
+            Some(FixtureMode::Record(PathBuf::from(dir)))
+        }
+    })
+}
+
+/// One recorded response.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fixture {
+    pub method: String,
+    /// Path and query relative to the instance URL, e.g. `/api/v4/user`.
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<String>,
+    pub status: u16,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// UTF-8 response body. Binary bodies go in `body_base64` instead.
+    #[serde(default)]
+    pub body: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_base64: Option<String>,
+}
+
+impl Fixture {
+    fn body_bytes(&self) -> Result<Vec<u8>, AppError> {
+        match &self.body_base64 {
+            Some(encoded) => base64::engine::general_purpose::STANDARD
+                .decode(encoded)
+                .map_err(|e| AppError::internal(format!("Invalid fixture body: {}", e))),
+            None => Ok(self.body.clone().into_bytes()),
+        }
+    }
+}
+
+/// Path and query of `request` relative to `base_url`, which may itself
+/// have a path (GitLab served from a subdirectory).
+fn relative_path(base_url: &str, request: &Request) -> String {
+    let url = request.url();
+    let base_path = reqwest::Url::parse(base_url)
+        .map(|b| b.path().trim_end_matches('/').to_string())
+        .unwrap_or_default();
+    let path = url.path();
+    let path = path.strip_prefix(base_path.as_str()).unwrap_or(path);
+    match url.query() {
+        Some(query) => format!("{}?{}", path, query),
+        None => path.to_string(),
+    }
+}
+
+/// File name of the fixture for a request: a readable slug of the path plus
+/// a hash of everything that identifies the request.
+pub fn fixture_file_name(method: &str, path: &str, body: Option<&[u8]>) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_bytes());
+    hasher.update(b" ");
+    hasher.update(path.as_bytes());
+    if let Some(body) = body {
+        hasher.update(b"\n");
+        hasher.update(body);
+    }
+    let hash = hasher.finalize();
+
+    let mut slug: String = path
+        .split('?')
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches('/')
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    slug.truncate(MAX_SLUG_LEN);
+    let hex: String = hash[..4].iter().map(|b| format!("{:02x}", b)).collect();
+    format!("{}_{}_{}.json", method.to_ascii_lowercase(), slug, hex)
+}
+
+fn build_response(
+    status: u16,
+    headers: &BTreeMap<String, String>,
+    body: Vec<u8>,
+) -> Result<Response, AppError> {
+    let mut builder = http::Response::builder().status(status);
+    for (name, value) in headers {
+        builder = builder.header(name.as_str(), value.as_str());
+    }
+    let response = builder
+        .body(body)
+        .map_err(|e| AppError::internal(format!("Invalid fixture response: {}", e)))?;
+    Ok(Response::from(response))
+}
+
+impl FixtureMode {
+    /// Answer `request`: from the fixtures when replaying, from GitLab when
+    /// recording.
+    pub(crate) async fn execute(
+        &self,
+        client: &Client,
+        base_url: &str,
+        request: Request,
+    ) -> Result<Response, AppError> {
+        let method = request.method().as_str().to_string();
+        let path = relative_path(base_url, &request);
+        let request_body = request
+            .body()
+            .and_then(|b| b.as_bytes())
+            .map(<[u8]>::to_vec);
+        let file_name = fixture_file_name(&method, &path, request_body.as_deref());
+
+        match self {
+            FixtureMode::Replay(dir) => {
+                let file = dir.join(&file_name);
+                let contents = match std::fs::read_to_string(&file) {
+                    Ok(contents) => contents,
+                    Err(_) => {
+                        log::warn!(
+                            "[fixtures] no fixture for {} {} ({})",
+                            method,
+                            path,
+                            file_name
+                        );
+                        let body = br#"{"message":"404 Not Found"}"#.to_vec();
+                        let headers = BTreeMap::from([(
+                            "content-type".to_string(),
+                            "application/json".to_string(),
+                        )]);
+                        return build_response(StatusCode::NOT_FOUND.as_u16(), &headers, body);
+                    }
+                };
+                let fixture: Fixture = serde_json::from_str(&contents).map_err(|e| {
+                    AppError::internal(format!("Invalid fixture {}: {}", file.display(), e))
+                })?;
+                build_response(fixture.status, &fixture.headers, fixture.body_bytes()?)
+            }
+            FixtureMode::Record(dir) => {
+                let response = client.execute(request).await?;
+                let status = response.status().as_u16();
+                let headers: BTreeMap<String, String> = RECORDED_HEADERS
+                    .iter()
+                    .filter_map(|name| {
+                        let value = response.headers().get(*name)?.to_str().ok()?;
+                        Some((name.to_string(), value.to_string()))
+                    })
+                    .collect();
+                let bytes = response.bytes().await?.to_vec();
+
+                let (body, body_base64) = match String::from_utf8(bytes.clone()) {
+                    Ok(text) => (text, None),
+                    Err(_) => (
+                        String::new(),
+                        Some(base64::engine::general_purpose::STANDARD.encode(&bytes)),
+                    ),
+                };
+                let fixture = Fixture {
+                    method,
+                    path,
+                    request_body: request_body.map(|b| String::from_utf8_lossy(&b).into_owned()),
+                    status,
+                    headers: headers.clone(),
+                    body,
+                    body_base64,
+                };
+                let json = serde_json::to_string_pretty(&fixture)?;
+                std::fs::create_dir_all(dir)
+                    .and_then(|_| std::fs::write(dir.join(&file_name), json))
+                    .map_err(|e| {
+                        AppError::internal(format!("Failed to write fixture {}: {}", file_name, e))
+                    })?;
+
+                build_response(status, &headers, bytes)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: reqwest::Method, url: &str, body: Option<&str>) -> Request {
+        let mut builder = Client::new().request(method, url);
+        if let Some(body) = body {
+            builder = builder.body(body.to_string());
+        }
+        builder.build().unwrap()
+    }
+
+    #[test]
+    fn parses_mode_flags() {
+        let args = |a: &[&str]| a.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(mode_from_args(args(&["app", "--secondary"])), None);
+        assert_eq!(
+            mode_from_args(args(&["app", "--mock-gitlab"])),
+            Some(FixtureMode::Replay(default_dir()))
+        );
+        assert_eq!(
+            mode_from_args(args(&["app", "--mock-gitlab=/tmp/fx"])),
+            Some(FixtureMode::Replay(PathBuf::from("/tmp/fx")))
+        );
+        assert_eq!(
+            mode_from_args(args(&["app", "--record-gitlab=/tmp/fx"])),
+            Some(FixtureMode::Record(PathBuf::from("/tmp/fx")))
+        );
+        // Recording needs a directory.
+        assert_eq!(mode_from_args(args(&["app", "--record-gitlab"])), None);
+    }
+
+    #[test]
+    fn keys_are_relative_to_the_instance() {
+        let on_subpath = request(
+            reqwest::Method::GET,
+            "https://example.com/gitlab/api/v4/user?x=1",
+            None,
+        );
+        assert_eq!(
+            relative_path("https://example.com/gitlab/", &on_subpath),
+            "/api/v4/user?x=1"
+        );
+        let on_root = request(
+            reqwest::Method::GET,
+            "https://gitlab.com/api/v4/user?x=1",
+            None,
+        );
+        assert_eq!(
+            relative_path("https://gitlab.com", &on_root),
+            "/api/v4/user?x=1"
+        );
+    }
+
+    #[test]
+    fn file_names_separate_queries_and_bodies() {
+        let name = fixture_file_name("GET", "/api/v4/projects/1/merge_requests?page=1", None);
+        assert!(name.starts_with("get_api_v4_projects_1_merge_requests_"));
+        assert!(name.ends_with(".json"));
+        assert_ne!(
+            name,
+            fixture_file_name("GET", "/api/v4/projects/1/merge_requests?page=2", None)
+        );
+        assert_ne!(
+            fixture_file_name("POST", "/api/graphql", Some(b"{\"a\":1}")),
+            fixture_file_name("POST", "/api/graphql", Some(b"{\"a\":2}"))
+        );
+    }
+
+    #[tokio::test]
+    async fn replays_fixtures_and_404s_the_rest() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = "/api/v4/projects/1/merge_requests?page=2";
+        let fixture = Fixture {
+            method: "GET".to_string(),
+            path: path.to_string(),
+            request_body: None,
+            status: 200,
+            headers: BTreeMap::from([("x-total-pages".to_string(), "2".to_string())]),
+            body: "[]".to_string(),
+            body_base64: None,
+        };
+        std::fs::write(
+            dir.path().join(fixture_file_name("GET", path, None)),
+            serde_json::to_string(&fixture).unwrap(),
+        )
+        .unwrap();
+
+        let mode = FixtureMode::Replay(dir.path().to_path_buf());
+        let client = Client::new();
+        let hit = mode
+            .execute(
+                &client,
+                "https://gitlab.example.com",
+                request(
+                    reqwest::Method::GET,
+                    &format!("https://gitlab.example.com{}", path),
+                    None,
+                ),
+            )
+            .await
+            .unwrap();
+        assert_eq!(hit.status(), StatusCode::OK);
+        assert_eq!(hit.headers()["x-total-pages"], "2");
+        assert_eq!(hit.text().await.unwrap(), "[]");
+
+        let miss = mode
+            .execute(
+                &client,
+                "https://gitlab.example.com",
+                request(
+                    reqwest::Method::GET,
+                    "https://gitlab.example.com/api/v4/user",
+                    None,
+                ),
+            )
+            .await
+            .unwrap();
+        assert_eq!(miss.status(), StatusCode::NOT_FOUND);
+    }
+}
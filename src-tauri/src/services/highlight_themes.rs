@@ -0,0 +1,168 @@
+//! Color themes for syntax-highlighted diff tokens.
+//!
+//! Diff tokens carry `hl-*` classes (see `src/styles/syntax.css`) that are
+//! colored from the app theme's `--syntax-*` variables by default, so they
+//! follow built-in and custom app themes alike. The user can instead pick one
+//! of the TextMate themes bundled with syntect; for those, each class is
+//! mapped to a representative scope and the theme's style for that scope is
+//! turned into a CSS rule here, so every view gets the same colors.
+
+use serde::Serialize;
+use std::fmt::Write;
+use std::sync::OnceLock;
+use syntect::highlighting::{Color, FontStyle, Highlighter, Theme, ThemeSet};
+use syntect::parsing::Scope;
+
+/// Theme ID meaning "use the app theme's syntax colors".
+pub const APP_THEME_ID: &str = "app";
+
+/// Highlight class (without the `hl-` prefix) and the scope whose style it
+/// takes. The diff highlighter emits exactly these, plus `embedded`.
+pub(crate) const CLASS_SCOPES: &[(&str, &str)] = &[
+    ("comment", "comment"),
+    ("string", "string"),
+    ("string-special", "string.regexp"),
+    ("escape", "constant.character.escape"),
+    ("number", "constant.numeric"),
+    ("constant", "constant"),
+    ("constant-builtin", "constant.language"),
+    ("keyword", "keyword"),
+    ("function", "entity.name.function"),
+    ("function-builtin", "support.function"),
+    ("function-macro", "entity.name.function.macro"),
+    ("type", "entity.name.type"),
+    ("type-builtin", "storage.type"),
+    ("variable", "variable"),
+    ("variable-builtin", "variable.language"),
+    ("variable-parameter", "variable.parameter"),
+    ("property", "variable.other.member"),
+    ("attribute", "entity.other.attribute-name"),
+    ("operator", "keyword.operator"),
+    ("punctuation", "punctuation"),
+    ("punctuation-bracket", "punctuation.section"),
+    ("punctuation-delimiter", "punctuation.separator"),
+    (
+        "punctuation-special",
+        "punctuation.definition.template-expression",
+    ),
+    ("tag", "entity.name.tag"),
+    ("label", "entity.name.label"),
+    ("constructor", "entity.name.function.constructor"),
+];
+
+/// A selectable highlight theme.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HighlightTheme {
+    pub id: String,
+    pub name: String,
+    /// Whether the theme is meant for a dark background. `None` for the app theme.
+    pub dark: Option<bool>,
+    /// Stylesheet overriding the `hl-*` colors. Empty for the app theme.
+    pub css: String,
+}
+
+fn theme_set() -> &'static ThemeSet {
+    static THEMES: OnceLock<ThemeSet> = OnceLock::new();
+    THEMES.get_or_init(ThemeSet::load_defaults)
+}
+
+/// Whether `id` names a known highlight theme.
+pub fn is_known(id: &str) -> bool {
+    id == APP_THEME_ID || theme_set().themes.contains_key(id)
+}
+
+/// All selectable themes, the app theme first.
+pub fn list_themes() -> Vec<HighlightTheme> {
+    let app = HighlightTheme {
+        id: APP_THEME_ID.to_string(),
+        name: "Match app theme".to_string(),
+        dark: None,
+        css: String::new(),
+    };
+    let bundled = theme_set().themes.iter().map(|(id, theme)| HighlightTheme {
+        id: id.clone(),
+        name: theme.name.clone().unwrap_or_else(|| id.clone()),
+        dark: theme.settings.background.map(is_dark),
+        css: theme_css(theme),
+    });
+    std::iter::once(app).chain(bundled).collect()
+}
+
+/// Stylesheet for the theme with the given ID, empty for the app theme or an
+/// unknown ID.
+pub fn css_for(id: &str) -> String {
+    theme_set()
+        .themes
+        .get(id)
+        .map(theme_css)
+        .unwrap_or_default()
+}
+
+fn theme_css(theme: &Theme) -> String {
+    let highlighter = Highlighter::new(theme);
+    let mut css = String::new();
+    for (class, scope) in CLASS_SCOPES {
+        let Ok(scope) = Scope::new(scope) else {
+            continue;
+        };
+        let style = highlighter.style_for_stack(&[scope]);
+        let italic = style.font_style.contains(FontStyle::ITALIC);
+        let bold = style.font_style.contains(FontStyle::BOLD);
+        // `:root` outranks the plain class rules in syntax.css.
+        let _ = writeln!(
+            css,
+            ":root .hl-{class} {{ color: {}; font-style: {}; font-weight: {}; }}",
+            hex(style.foreground),
+            if italic { "italic" } else { "normal" },
+            if bold { "600" } else { "normal" },
+        );
+    }
+    css
+}
+
+fn hex(c: Color) -> String {
+    if c.a == 0xff {
+        format!("#{:02x}{:02x}{:02x}", c.r, c.g, c.b)
+    } else {
+        format!("#{:02x}{:02x}{:02x}{:02x}", c.r, c.g, c.b, c.a)
+    }
+}
+
+fn is_dark(c: Color) -> bool {
+    // Rec. 601 luma, good enough to tell dark from light backgrounds.
+    let luma = 0.299 * c.r as f32 + 0.587 * c.g as f32 + 0.114 * c.b as f32;
+    luma < 128.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn app_theme_comes_first_without_css() {
+        let themes = list_themes();
+        assert_eq!(themes[0].id, APP_THEME_ID);
+        assert!(themes[0].css.is_empty());
+        assert!(themes.len() > 1);
+        assert!(is_known(APP_THEME_ID));
+        assert!(!is_known("no-such-theme"));
+    }
+
+    #[test]
+    fn bundled_themes_cover_every_class() {
+        let css = css_for("InspiredGitHub");
+        for (class, _) in CLASS_SCOPES {
+            assert!(css.contains(&format!(".hl-{class} ")), "missing {class}");
+        }
+        assert_eq!(css_for(APP_THEME_ID), "");
+    }
+
+    #[test]
+    fn tells_dark_from_light() {
+        let themes = list_themes();
+        let dark = |id: &str| themes.iter().find(|t| t.id == id).unwrap().dark;
+        assert_eq!(dark("base16-ocean.dark"), Some(true));
+        assert_eq!(dark("InspiredGitHub"), Some(false));
+    }
+}
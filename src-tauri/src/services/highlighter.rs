@@ -0,0 +1,282 @@
+//! Tree-sitter syntax highlighting for diff lines.
+//!
+//! Sources are parsed with the languages and grammars of semantic diff
+//! summaries and run through each grammar's own `highlights.scm` query.
+//! Captures are turned into the `hl-*` token types that `src/styles/syntax.css`
+//! and the highlight themes style (`function.builtin` becomes
+//! `function-builtin`), falling back to the parent type for ones without a
+//! class (`comment.documentation` becomes `comment`).
+
+use serde::Serialize;
+use std::sync::OnceLock;
+use streaming_iterator::StreamingIterator;
+use tree_sitter::{Parser, Query, QueryCursor};
+
+use crate::services::gitattributes::{self, LanguageOverride};
+use crate::services::highlight_themes::CLASS_SCOPES;
+use crate::services::semantic_diff::Lang;
+
+/// Sources larger than this are left unhighlighted.
+pub const MAX_HIGHLIGHT_BYTES: usize = 512 * 1024;
+
+/// A highlighted byte range within one line.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HighlightSpan {
+    /// Token type, the `hl-*` class without its prefix (e.g. `"keyword"`).
+    pub token_type: &'static str,
+    /// Byte offset of the span's start within the line.
+    pub start: usize,
+    /// Byte offset just past the span's end.
+    pub end: usize,
+}
+
+/// The highlights query. TypeScript's only covers what it adds to
+/// JavaScript, so the JavaScript one is appended; earlier patterns win.
+fn highlights_query(lang: Lang) -> String {
+    match lang {
+        Lang::Rust => tree_sitter_rust::HIGHLIGHTS_QUERY.to_string(),
+        Lang::TypeScript => format!(
+            "{}\n{}",
+            tree_sitter_typescript::HIGHLIGHTS_QUERY,
+            tree_sitter_javascript::HIGHLIGHT_QUERY
+        ),
+        Lang::Tsx => format!(
+            "{}\n{}\n{}",
+            tree_sitter_typescript::HIGHLIGHTS_QUERY,
+            tree_sitter_javascript::JSX_HIGHLIGHT_QUERY,
+            tree_sitter_javascript::HIGHLIGHT_QUERY
+        ),
+        Lang::JavaScript => format!(
+            "{}\n{}",
+            tree_sitter_javascript::JSX_HIGHLIGHT_QUERY,
+            tree_sitter_javascript::HIGHLIGHT_QUERY
+        ),
+        Lang::Python => tree_sitter_python::HIGHLIGHTS_QUERY.to_string(),
+        Lang::Go => tree_sitter_go::HIGHLIGHTS_QUERY.to_string(),
+    }
+}
+
+/// The compiled highlights query, built once per language. `None` if the
+/// query doesn't compile.
+fn query(lang: Lang) -> Option<&'static HighlightQuery> {
+    static QUERIES: OnceLock<Vec<Option<HighlightQuery>>> = OnceLock::new();
+    let queries = QUERIES.get_or_init(|| {
+        Lang::ALL
+            .iter()
+            .map(|lang| {
+                let query = Query::new(&lang.grammar(), &highlights_query(*lang))
+                    .map_err(|e| log::warn!("[highlighter] {:?} query: {}", lang, e))
+                    .ok()?;
+                let types = query
+                    .capture_names()
+                    .iter()
+                    .map(|name| token_type(name))
+                    .collect();
+                Some(HighlightQuery { query, types })
+            })
+            .collect()
+    });
+    let index = Lang::ALL.iter().position(|l| *l == lang)?;
+    queries[index].as_ref()
+}
+
+/// A compiled highlights query.
+struct HighlightQuery {
+    query: Query,
+    /// Token type of each capture, by capture index.
+    types: Vec<Option<&'static str>>,
+}
+
+/// Token type for a capture name: the name with dots as hyphens, or its
+/// nearest parent with a highlight class. `embedded` only sets a background,
+/// so it isn't themed but is still a token type.
+fn token_type(capture: &str) -> Option<&'static str> {
+    let mut name = capture.replace('.', "-");
+    loop {
+        if name == "embedded" {
+            return Some("embedded");
+        }
+        if let Some((known, _)) = CLASS_SCOPES.iter().find(|(class, _)| *class == name) {
+            return Some(known);
+        }
+        let (parent, _) = name.rsplit_once('-')?;
+        name = parent.to_string();
+    }
+}
+
+/// Language for a file, honoring `linguist-language` overrides from the
+/// project's `.gitattributes` before falling back to the extension. The last
+/// matching override wins, as in git.
+pub fn language_for(path: &str, overrides: &[LanguageOverride]) -> Option<Lang> {
+    match overrides
+        .iter()
+        .rev()
+        .find(|o| gitattributes::pattern_matches(&o.pattern, path))
+    {
+        Some(o) => Lang::from_linguist_name(&o.language),
+        None => Lang::from_path(path),
+    }
+}
+
+/// Highlights source code one file at a time, reusing its parser.
+pub struct SyntaxHighlighter {
+    parser: Parser,
+}
+
+impl Default for SyntaxHighlighter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SyntaxHighlighter {
+    pub fn new() -> Self {
+        Self {
+            parser: Parser::new(),
+        }
+    }
+
+    /// Highlight `source`, returning the spans of each of its lines (split
+    /// as by `str::lines`). `None` when the language's query isn't available
+    /// or the source is too large to highlight.
+    pub fn highlight(&mut self, lang: Lang, source: &str) -> Option<Vec<Vec<HighlightSpan>>> {
+        if source.len() > MAX_HIGHLIGHT_BYTES {
+            return None;
+        }
+        let HighlightQuery { query, types } = query(lang)?;
+        self.parser.set_language(&lang.grammar()).ok()?;
+        let tree = self.parser.parse(source, None)?;
+
+        // Paint each byte with its token type. Wider captures are painted
+        // first so nested ones (an escape in a string) show through; for the
+        // same node, the earliest pattern in the query is painted last.
+        let mut captures = Vec::new();
+        let mut cursor = QueryCursor::new();
+        let mut matches = cursor.captures(query, tree.root_node(), source.as_bytes());
+        while let Some((m, index)) = matches.next() {
+            let capture = m.captures[*index];
+            if let Some(token) = types[capture.index as usize] {
+                captures.push((capture.node.byte_range(), m.pattern_index, token));
+            }
+        }
+        captures.sort_by(|(a, ap, _), (b, bp, _)| {
+            (b.end - b.start).cmp(&(a.end - a.start)).then(bp.cmp(ap))
+        });
+        let mut painted: Vec<Option<&'static str>> = vec![None; source.len()];
+        for (range, _, token) in captures {
+            painted[range].fill(Some(token));
+        }
+
+        let mut lines = Vec::new();
+        let mut offset = 0;
+        for line in source.lines() {
+            lines.push(spans(&painted[offset..offset + line.len()]));
+            offset += line.len();
+            // Skip the line break, `\r\n` or `\n`.
+            if source[offset..].starts_with("\r\n") {
+                offset += 2;
+            } else if offset < source.len() {
+                offset += 1;
+            }
+        }
+        Some(lines)
+    }
+}
+
+/// Collapse per-byte token types into spans.
+fn spans(painted: &[Option<&'static str>]) -> Vec<HighlightSpan> {
+    let mut spans: Vec<HighlightSpan> = Vec::new();
+    for (i, token) in painted.iter().enumerate() {
+        let Some(token) = token else { continue };
+        match spans.last_mut() {
+            Some(last) if last.end == i && last.token_type == *token => last.end = i + 1,
+            _ => spans.push(HighlightSpan {
+                token_type: token,
+                start: i,
+                end: i + 1,
+            }),
+        }
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens<'a>(line: &'a str, spans: &[HighlightSpan]) -> Vec<(&'static str, &'a str)> {
+        spans
+            .iter()
+            .map(|s| (s.token_type, &line[s.start..s.end]))
+            .collect()
+    }
+
+    #[test]
+    fn test_every_language_query_compiles() {
+        for lang in Lang::ALL {
+            assert!(query(lang).is_some(), "{:?}", lang);
+        }
+    }
+
+    #[test]
+    fn test_rust_tokens_per_line() {
+        let source = "// add\r\nfn add(a: u32) -> u32 {\n    a + 1\n}";
+        let lines = SyntaxHighlighter::new()
+            .highlight(Lang::Rust, source)
+            .unwrap();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(tokens("// add", &lines[0]), vec![("comment", "// add")]);
+
+        let line = "fn add(a: u32) -> u32 {";
+        let found = tokens(line, &lines[1]);
+        assert!(found.contains(&("keyword", "fn")));
+        assert!(found.contains(&("function", "add")));
+        assert!(found.contains(&("variable-parameter", "a")));
+        assert!(found.contains(&("type-builtin", "u32")));
+        assert_eq!(
+            tokens("    a + 1", &lines[2]),
+            vec![("constant-builtin", "1")]
+        );
+    }
+
+    #[test]
+    fn test_nested_captures_show_through() {
+        let source = "x = \"a\\nb\"";
+        let lines = SyntaxHighlighter::new()
+            .highlight(Lang::Python, source)
+            .unwrap();
+        let found = tokens(source, &lines[0]);
+        assert!(found.contains(&("escape", "\\n")));
+        assert!(found.contains(&("string", "\"a")));
+    }
+
+    #[test]
+    fn test_token_type_falls_back_to_parent() {
+        assert_eq!(token_type("function.builtin"), Some("function-builtin"));
+        assert_eq!(token_type("function.method"), Some("function"));
+        assert_eq!(token_type("comment.documentation"), Some("comment"));
+        assert_eq!(token_type("local.scope"), None);
+    }
+
+    #[test]
+    fn test_gitattributes_override_language() {
+        let overrides = vec![
+            LanguageOverride {
+                pattern: "*.inc".to_string(),
+                language: "Rust".to_string(),
+            },
+            LanguageOverride {
+                pattern: "legacy/*.js".to_string(),
+                language: "TypeScript".to_string(),
+            },
+        ];
+        assert_eq!(language_for("src/a.inc", &overrides), Some(Lang::Rust));
+        assert_eq!(
+            language_for("legacy/a.js", &overrides),
+            Some(Lang::TypeScript)
+        );
+        assert_eq!(language_for("src/a.js", &overrides), Some(Lang::JavaScript));
+        assert_eq!(language_for("README.md", &overrides), None);
+    }
+}
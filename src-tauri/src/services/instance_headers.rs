@@ -0,0 +1,184 @@
+//! Per-instance custom HTTP headers.
+//!
+//! Some self-hosted instances sit behind an SSO proxy that wants its own
+//! header (e.g. `X-Auth-Token`) on every request in addition to the PAT.
+//! Headers are stored in `gitlab_instances.custom_headers`, next to the
+//! token, and mirrored in a process-wide map keyed by instance URL so that
+//! `GitLabClient::new` can inject them without every caller loading them.
+
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use reqwest::header::{HeaderName, HeaderValue};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+/// One extra header sent with every request to an instance.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomHeader {
+    pub name: String,
+    pub value: String,
+}
+
+/// Headers the client manages itself and that must not be overridden.
+const RESERVED_HEADERS: &[&str] = &[
+    "private-token",
+    "host",
+    "content-length",
+    "content-type",
+    "transfer-encoding",
+    "connection",
+];
+
+/// Instance URL → custom headers.
+fn registry() -> &'static RwLock<HashMap<String, Vec<CustomHeader>>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<String, Vec<CustomHeader>>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+fn registry_key(url: &str) -> String {
+    url.trim_end_matches('/').to_string()
+}
+
+/// Check that every header is a valid, non-reserved HTTP header and that
+/// no name appears twice.
+pub fn validate(headers: &[CustomHeader]) -> Result<(), AppError> {
+    let invalid = |message: String| AppError::invalid_input_field(message, "customHeaders");
+    let mut seen = Vec::with_capacity(headers.len());
+    for h in headers {
+        let name = HeaderName::from_bytes(h.name.trim().as_bytes())
+            .map_err(|_| invalid(format!("Invalid header name: {}", h.name)))?;
+        if RESERVED_HEADERS.contains(&name.as_str()) {
+            return Err(invalid(format!(
+                "Header {} is set by the app and cannot be overridden",
+                h.name
+            )));
+        }
+        if h.value.is_empty() || HeaderValue::from_str(&h.value).is_err() {
+            return Err(invalid(format!("Invalid value for header {}", h.name)));
+        }
+        if seen.contains(&name) {
+            return Err(invalid(format!(
+                "Header {} is listed more than once",
+                h.name
+            )));
+        }
+        seen.push(name);
+    }
+    Ok(())
+}
+
+/// Custom headers registered for an instance URL.
+pub fn headers_for(url: &str) -> Vec<CustomHeader> {
+    registry()
+        .read()
+        .map(|r| r.get(&registry_key(url)).cloned().unwrap_or_default())
+        .unwrap_or_default()
+}
+
+fn register(url: &str, headers: Vec<CustomHeader>) {
+    if let Ok(mut r) = registry().write() {
+        if headers.is_empty() {
+            r.remove(&registry_key(url));
+        } else {
+            r.insert(registry_key(url), headers);
+        }
+    }
+}
+
+/// Forget the headers for an instance URL (e.g. after deleting it).
+pub fn unregister(url: &str) {
+    register(url, Vec::new());
+}
+
+/// Load every instance's headers from the database into the registry.
+/// Called once at startup, before any client is built.
+pub async fn load_all(pool: &DbPool) -> Result<(), AppError> {
+    let rows: Vec<(String, Option<String>)> =
+        sqlx::query_as("SELECT url, custom_headers FROM gitlab_instances")
+            .fetch_all(pool)
+            .await?;
+    for (url, json) in rows {
+        let headers: Vec<CustomHeader> = json
+            .as_deref()
+            .map(serde_json::from_str)
+            .transpose()?
+            .unwrap_or_default();
+        register(&url, headers);
+    }
+    Ok(())
+}
+
+/// Persist an instance's headers and make them active for new clients.
+pub async fn save(
+    pool: &DbPool,
+    instance_id: i64,
+    headers: &[CustomHeader],
+) -> Result<(), AppError> {
+    validate(headers)?;
+    let json = if headers.is_empty() {
+        None
+    } else {
+        Some(serde_json::to_string(headers)?)
+    };
+    let url: String = sqlx::query_scalar(
+        "UPDATE gitlab_instances SET custom_headers = ? WHERE id = ? RETURNING url",
+    )
+    .bind(json)
+    .bind(instance_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::not_found_with_id("GitLabInstance", instance_id.to_string()))?;
+    register(&url, headers.to_vec());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db;
+    use tempfile::tempdir;
+
+    fn header(name: &str, value: &str) -> CustomHeader {
+        CustomHeader {
+            name: name.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_and_reserved_headers() {
+        assert!(validate(&[header("X-Auth-Token", "abc")]).is_ok());
+        assert!(validate(&[header("Bad Header", "abc")]).is_err());
+        assert!(validate(&[header("X-Auth-Token", "line\nbreak")]).is_err());
+        assert!(validate(&[header("X-Auth-Token", "")]).is_err());
+        assert!(validate(&[header("Private-Token", "abc")]).is_err());
+        assert!(validate(&[header("X-A", "1"), header("x-a", "2")]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_save_and_load_roundtrip() {
+        let dir = tempdir().unwrap();
+        let pool = db::initialize(&dir.path().join("t.db")).await.unwrap();
+        sqlx::query(
+            "INSERT INTO gitlab_instances (id, url, token, created_at) VALUES (1, 'https://sso.example', 't', 0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let headers = vec![header("X-Auth-Token", "secret")];
+        save(&pool, 1, &headers).await.unwrap();
+        assert_eq!(headers_for("https://sso.example/"), headers);
+
+        unregister("https://sso.example");
+        assert!(headers_for("https://sso.example").is_empty());
+        load_all(&pool).await.unwrap();
+        assert_eq!(headers_for("https://sso.example"), headers);
+
+        save(&pool, 1, &[]).await.unwrap();
+        assert!(headers_for("https://sso.example").is_empty());
+        assert!(save(&pool, 2, &headers).await.is_err());
+    }
+}
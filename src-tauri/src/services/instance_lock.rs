@@ -0,0 +1,142 @@
+//! Guard against two app processes driving the same database.
+//!
+//! The single-instance plugin covers the common case — launching the app
+//! again in the same session focuses the running window. It can't see a
+//! process started from another user session or by another build, so the
+//! primary process also holds an OS advisory lock on a file next to the
+//! database. A process that finds the lock held falls back to secondary
+//! mode: read-only database access, no sync engine and no companion server.
+//!
+//! The OS releases the lock when its holder exits, however it exits, so a
+//! crashed primary never leaves a stale lock behind. The file itself stays;
+//! it only carries the holder's PID for the log.
+
+use serde::Serialize;
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::{Read, Seek, Write};
+use std::path::Path;
+
+/// Command-line flag for deliberately running a read-only second viewer.
+pub const SECONDARY_FLAG: &str = "--secondary";
+
+const LOCK_FILE_NAME: &str = "ultra-gitlab.lock";
+
+/// How this process uses the database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum InstanceMode {
+    /// Owns the database: runs migrations, syncs and writes.
+    Primary,
+    /// Read-only viewer alongside a primary process.
+    Secondary,
+}
+
+/// Lock held by the primary process; released when dropped.
+#[derive(Debug)]
+pub struct InstanceLock {
+    _file: File,
+}
+
+#[derive(Debug)]
+pub enum LockOutcome {
+    Acquired(InstanceLock),
+    /// Another process holds the lock. Its PID, when it could be read.
+    HeldBy(Option<u32>),
+}
+
+/// Whether the command line asks for secondary mode.
+pub fn secondary_requested<I: IntoIterator<Item = String>>(args: I) -> bool {
+    args.into_iter().any(|arg| arg == SECONDARY_FLAG)
+}
+
+/// Take the lock in `dir` without waiting.
+pub fn acquire(dir: &Path) -> std::io::Result<LockOutcome> {
+    let path = dir.join(LOCK_FILE_NAME);
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(&path)?;
+    match file.try_lock() {
+        Ok(()) => {
+            file.set_len(0)?;
+            file.rewind()?;
+            write!(file, "{}", std::process::id())?;
+            file.flush()?;
+            Ok(LockOutcome::Acquired(InstanceLock { _file: file }))
+        }
+        Err(TryLockError::WouldBlock) => {
+            let mut holder = String::new();
+            let pid = file
+                .read_to_string(&mut holder)
+                .ok()
+                .and_then(|_| holder.trim().parse::<u32>().ok());
+            Ok(LockOutcome::HeldBy(pid))
+        }
+        Err(TryLockError::Error(e)) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lock_is_exclusive_until_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = match acquire(dir.path()).unwrap() {
+            LockOutcome::Acquired(lock) => lock,
+            LockOutcome::HeldBy(pid) => panic!("unexpectedly held by {pid:?}"),
+        };
+        match acquire(dir.path()).unwrap() {
+            LockOutcome::HeldBy(pid) => assert_eq!(pid, Some(std::process::id())),
+            LockOutcome::Acquired(_) => panic!("lock acquired twice"),
+        }
+        drop(first);
+        assert!(matches!(
+            acquire(dir.path()).unwrap(),
+            LockOutcome::Acquired(_)
+        ));
+    }
+
+    #[test]
+    fn stale_and_garbled_locks_are_replaced() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(LOCK_FILE_NAME);
+        for contents in ["4294967294", "not a pid"] {
+            std::fs::write(&path, contents).unwrap();
+            let LockOutcome::Acquired(lock) = acquire(dir.path()).unwrap() else {
+                panic!("stale lock {contents:?} was not replaced");
+            };
+            assert_eq!(
+                std::fs::read_to_string(&path).unwrap(),
+                std::process::id().to_string()
+            );
+            drop(lock);
+        }
+    }
+
+    #[test]
+    fn concurrent_starts_get_one_primary() {
+        let dir = tempfile::tempdir().unwrap();
+        let outcomes: Vec<LockOutcome> = std::thread::scope(|scope| {
+            let starts: Vec<_> = (0..8)
+                .map(|_| scope.spawn(|| acquire(dir.path()).unwrap()))
+                .collect();
+            starts.into_iter().map(|t| t.join().unwrap()).collect()
+        });
+        let primaries = outcomes
+            .iter()
+            .filter(|o| matches!(o, LockOutcome::Acquired(_)))
+            .count();
+        assert_eq!(primaries, 1);
+    }
+
+    #[test]
+    fn secondary_flag_is_detected() {
+        let args = |a: &[&str]| a.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert!(secondary_requested(args(&["ultra-gitlab", "--secondary"])));
+        assert!(!secondary_requested(args(&["ultra-gitlab"])));
+    }
+}
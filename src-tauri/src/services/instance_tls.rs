@@ -0,0 +1,266 @@
+//! Per-instance TLS settings.
+//!
+//! Some self-hosted instances are locked down to TLS 1.3, or require a
+//! client certificate (mTLS) on every connection. The settings are stored in
+//! `gitlab_instances.tls_config` and mirrored in a process-wide map keyed by
+//! instance URL, like the custom headers, so every client built for the
+//! instance picks them up.
+//!
+//! There is no cipher suite list: rustls only offers modern AEAD suites, and
+//! pinning the protocol version is what such policies come down to.
+
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use reqwest::tls::{Identity, Version};
+use reqwest::ClientBuilder;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+/// Oldest TLS version the client will negotiate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TlsMinVersion {
+    #[default]
+    #[serde(rename = "1.2")]
+    Tls12,
+    #[serde(rename = "1.3")]
+    Tls13,
+}
+
+impl TlsMinVersion {
+    fn version(self) -> Version {
+        match self {
+            Self::Tls12 => Version::TLS_1_2,
+            Self::Tls13 => Version::TLS_1_3,
+        }
+    }
+}
+
+/// TLS settings for connections to an instance.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceTls {
+    #[serde(default)]
+    pub min_version: TlsMinVersion,
+
+    /// PEM file holding the client certificate followed by its private key,
+    /// presented to instances that require mTLS.
+    #[serde(default)]
+    pub client_identity_path: Option<String>,
+}
+
+impl InstanceTls {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn identity_path(&self) -> Option<&str> {
+        self.client_identity_path
+            .as_deref()
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+    }
+}
+
+/// Instance URL → TLS settings.
+fn registry() -> &'static RwLock<HashMap<String, InstanceTls>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<String, InstanceTls>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+fn registry_key(url: &str) -> String {
+    url.trim_end_matches('/').to_string()
+}
+
+fn load_identity(path: &str) -> Result<Identity, AppError> {
+    let invalid = |message: String| AppError::invalid_input_field(message, "tls");
+    let pem = std::fs::read(path)
+        .map_err(|e| invalid(format!("Cannot read client certificate {}: {}", path, e)))?;
+    Identity::from_pem(&pem).map_err(|_| {
+        invalid(format!(
+            "{} must contain a PEM certificate and its private key",
+            path
+        ))
+    })
+}
+
+/// Check that the client certificate, if any, can be loaded.
+pub fn validate(tls: &InstanceTls) -> Result<(), AppError> {
+    if let Some(path) = tls.identity_path() {
+        load_identity(path)?;
+    }
+    Ok(())
+}
+
+/// Apply `tls` to a client being built for the instance.
+pub fn apply(builder: ClientBuilder, tls: &InstanceTls) -> Result<ClientBuilder, AppError> {
+    let mut builder = builder.min_tls_version(tls.min_version.version());
+    if let Some(path) = tls.identity_path() {
+        builder = builder.identity(load_identity(path)?);
+    }
+    Ok(builder)
+}
+
+/// TLS settings registered for an instance URL.
+pub fn tls_for(url: &str) -> InstanceTls {
+    registry()
+        .read()
+        .map(|r| r.get(&registry_key(url)).cloned().unwrap_or_default())
+        .unwrap_or_default()
+}
+
+fn register(url: &str, tls: InstanceTls) {
+    if let Ok(mut r) = registry().write() {
+        if tls.is_default() {
+            r.remove(&registry_key(url));
+        } else {
+            r.insert(registry_key(url), tls);
+        }
+    }
+}
+
+/// Forget the TLS settings for an instance URL (e.g. after deleting it).
+pub fn unregister(url: &str) {
+    register(url, InstanceTls::default());
+}
+
+/// Load every instance's TLS settings from the database into the registry.
+/// Called once at startup, before any client is built.
+pub async fn load_all(pool: &DbPool) -> Result<(), AppError> {
+    let rows: Vec<(String, Option<String>)> =
+        sqlx::query_as("SELECT url, tls_config FROM gitlab_instances")
+            .fetch_all(pool)
+            .await?;
+    for (url, json) in rows {
+        let tls: InstanceTls = json
+            .as_deref()
+            .map(serde_json::from_str)
+            .transpose()?
+            .unwrap_or_default();
+        register(&url, tls);
+    }
+    Ok(())
+}
+
+/// Persist an instance's TLS settings and make them active for new clients.
+pub async fn save(pool: &DbPool, instance_id: i64, tls: &InstanceTls) -> Result<(), AppError> {
+    validate(tls)?;
+    let json = if tls.is_default() {
+        None
+    } else {
+        Some(serde_json::to_string(tls)?)
+    };
+    let url: String =
+        sqlx::query_scalar("UPDATE gitlab_instances SET tls_config = ? WHERE id = ? RETURNING url")
+            .bind(json)
+            .bind(instance_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| {
+                AppError::not_found_with_id("GitLabInstance", instance_id.to_string())
+            })?;
+    register(&url, tls.clone());
+    Ok(())
+}
+
+/// Find out which TLS version connections to `url` with `tls` end up on,
+/// e.g. `"TLS 1.3"`. rustls always negotiates the newest version both sides
+/// support, so this tries a TLS 1.3-only handshake first and falls back to
+/// TLS 1.2. `None` for plain-HTTP URLs or when neither handshake succeeds.
+pub async fn negotiated_protocol(url: &str, tls: &InstanceTls) -> Option<&'static str> {
+    if !url.starts_with("https://") {
+        return None;
+    }
+    let candidates: &[(Version, &'static str)] = match tls.min_version {
+        TlsMinVersion::Tls13 => &[(Version::TLS_1_3, "TLS 1.3")],
+        TlsMinVersion::Tls12 => &[(Version::TLS_1_3, "TLS 1.3"), (Version::TLS_1_2, "TLS 1.2")],
+    };
+    for &(version, label) in candidates {
+        let builder = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .timeout(std::time::Duration::from_secs(10));
+        let Ok(builder) = apply(builder, tls) else {
+            return None;
+        };
+        let Ok(client) = builder
+            .min_tls_version(version)
+            .max_tls_version(version)
+            .build()
+        else {
+            continue;
+        };
+        // Any HTTP response, even an error status, means the handshake worked.
+        if client.head(url).send().await.is_ok() {
+            return Some(label);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_validate_rejects_unusable_client_certificates() {
+        let dir = tempdir().unwrap();
+        let bogus = dir.path().join("client.pem");
+        std::fs::write(&bogus, "not a certificate").unwrap();
+
+        assert!(validate(&InstanceTls::default()).is_ok());
+        let with_path = |path: &str| InstanceTls {
+            min_version: TlsMinVersion::Tls13,
+            client_identity_path: Some(path.to_string()),
+        };
+        // A blank path means no client certificate.
+        assert!(validate(&with_path("  ")).is_ok());
+        assert!(validate(&with_path(bogus.to_str().unwrap())).is_err());
+        assert!(validate(&with_path(dir.path().join("missing.pem").to_str().unwrap())).is_err());
+    }
+
+    #[test]
+    fn test_min_version_serializes_as_version_number() {
+        let tls: InstanceTls = serde_json::from_str(r#"{"minVersion":"1.3"}"#).unwrap();
+        assert_eq!(tls.min_version, TlsMinVersion::Tls13);
+        assert_eq!(tls.client_identity_path, None);
+        assert!(serde_json::from_str::<InstanceTls>(r#"{"minVersion":"1.1"}"#).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_save_and_load_roundtrip() {
+        let dir = tempdir().unwrap();
+        let pool = db::initialize(&dir.path().join("t.db")).await.unwrap();
+        sqlx::query(
+            "INSERT INTO gitlab_instances (id, url, token, created_at) VALUES (1, 'https://tls.example', 't', 0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let tls = InstanceTls {
+            min_version: TlsMinVersion::Tls13,
+            client_identity_path: None,
+        };
+        save(&pool, 1, &tls).await.unwrap();
+        assert_eq!(tls_for("https://tls.example/"), tls);
+
+        unregister("https://tls.example");
+        assert!(tls_for("https://tls.example").is_default());
+        load_all(&pool).await.unwrap();
+        assert_eq!(tls_for("https://tls.example"), tls);
+
+        save(&pool, 1, &InstanceTls::default()).await.unwrap();
+        assert!(tls_for("https://tls.example").is_default());
+        assert!(save(&pool, 2, &tls).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_plain_http_has_no_negotiated_protocol() {
+        assert_eq!(
+            negotiated_protocol("http://localhost:1", &InstanceTls::default()).await,
+            None
+        );
+    }
+}
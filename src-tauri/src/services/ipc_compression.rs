@@ -0,0 +1,108 @@
+//! Compression of large command responses.
+//!
+//! Tauri serializes command results to JSON and the webview parses them on
+//! the main thread, so multi-megabyte responses (diff files with content,
+//! comments of busy MRs) stall the UI. Commands that can return such
+//! payloads answer through [`respond`]: below [`COMPRESS_THRESHOLD_BYTES`]
+//! the JSON goes out as usual, above it a gzip stream is sent as raw bytes,
+//! which the frontend's invoke wrapper inflates with `DecompressionStream`.
+//!
+//! Every response is recorded in [`command_metrics`] with its JSON and
+//! on-the-wire size, so the effect shows up next to the command's latency.
+
+use crate::error::AppError;
+use crate::services::command_metrics;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::Serialize;
+use std::io::Write;
+use tauri::ipc::Response;
+
+/// JSON payloads at least this large are compressed.
+pub const COMPRESS_THRESHOLD_BYTES: usize = 64 * 1024;
+
+/// A serialized response, ready to send.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Encoded {
+    Json(String),
+    /// Gzip of the JSON.
+    Gzip(Vec<u8>),
+}
+
+impl Encoded {
+    /// Bytes that cross the IPC boundary.
+    pub fn sent_bytes(&self) -> usize {
+        match self {
+            Encoded::Json(json) => json.len(),
+            Encoded::Gzip(bytes) => bytes.len(),
+        }
+    }
+}
+
+fn gzip(data: &[u8]) -> std::io::Result<Vec<u8>> {
+    // The fast level gets most of the gain on JSON; the point is latency.
+    let mut encoder = GzEncoder::new(Vec::with_capacity(data.len() / 4), Compression::fast());
+    encoder.write_all(data)?;
+    encoder.finish()
+}
+
+/// Compress `json` when it is over the threshold and compression pays off.
+pub fn encode(json: String) -> Encoded {
+    if json.len() < COMPRESS_THRESHOLD_BYTES {
+        return Encoded::Json(json);
+    }
+    match gzip(json.as_bytes()) {
+        Ok(bytes) if bytes.len() < json.len() => Encoded::Gzip(bytes),
+        Ok(_) => Encoded::Json(json),
+        Err(e) => {
+            log::warn!("[ipc] Failed to compress response: {}", e);
+            Encoded::Json(json)
+        }
+    }
+}
+
+/// Serialize `value` as the response of `command`, compressing it when it
+/// is large.
+pub fn respond<T: Serialize>(command: &str, value: &T) -> Result<Response, AppError> {
+    let json = serde_json::to_string(value)
+        .map_err(|e| AppError::internal(format!("Failed to serialize response: {}", e)))?;
+    let json_bytes = json.len();
+    let encoded = encode(json);
+    command_metrics::record_response(command, json_bytes, encoded.sent_bytes());
+    Ok(match encoded {
+        Encoded::Json(json) => Response::new(json),
+        Encoded::Gzip(bytes) => Response::new(bytes),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+
+    #[test]
+    fn test_small_payloads_stay_json() {
+        let json = r#"{"id":1}"#.to_string();
+        assert_eq!(encode(json.clone()), Encoded::Json(json));
+    }
+
+    #[test]
+    fn test_large_payloads_round_trip_through_gzip() {
+        let rows: Vec<_> = (0..5000)
+            .map(|i| serde_json::json!({ "id": i, "body": "looks good to me" }))
+            .collect();
+        let json = serde_json::to_string(&rows).unwrap();
+        assert!(json.len() >= COMPRESS_THRESHOLD_BYTES);
+
+        let Encoded::Gzip(bytes) = encode(json.clone()) else {
+            panic!("expected a compressed payload");
+        };
+        assert!(bytes.len() < json.len() / 4);
+        let mut inflated = String::new();
+        GzDecoder::new(bytes.as_slice())
+            .read_to_string(&mut inflated)
+            .unwrap();
+        assert_eq!(inflated, json);
+    }
+}
@@ -0,0 +1,213 @@
+//! Routing of GitLab links the OS hands to the app.
+//!
+//! When registered as the http/https handler, every link the user clicks
+//! anywhere lands here. Only merge request URLs on hosts the user opted into
+//! open in the app; everything else goes straight on to the browser they
+//! picked as fallback, so registering never hijacks ordinary browsing.
+
+use crate::error::AppError;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+/// Schemes claimed while registered.
+pub const SCHEMES: [&str; 2] = ["https", "http"];
+
+/// Link handler configuration, persisted with the app settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LinkHandlerSettings {
+    /// Whether the app is registered as the OS link handler.
+    pub enabled: bool,
+    /// Hosts whose MR links open in the app, e.g. `gitlab.example.com`.
+    pub hosts: Vec<String>,
+    /// Browser that receives every other link (a program name or path).
+    pub fallback_browser: String,
+}
+
+/// Where a link should go.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkRoute {
+    /// Open the MR in the app; carries the URL without query or fragment.
+    App(String),
+    Browser,
+}
+
+/// Host of `input`, which may be a bare host or a URL. Lowercased, with a
+/// non-default port kept.
+pub fn normalize_host(input: &str) -> Option<String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return None;
+    }
+    let url = if input.contains("://") {
+        Url::parse(input).ok()?
+    } else {
+        Url::parse(&format!("https://{}", input)).ok()?
+    };
+    let host = url.host_str()?.to_ascii_lowercase();
+    Some(match url.port() {
+        Some(port) => format!("{}:{}", host, port),
+        None => host,
+    })
+}
+
+/// Check and normalize settings before they're saved: hosts are reduced to
+/// their normalized form, and enabling needs a host and a fallback browser.
+pub fn validate_settings(settings: &mut LinkHandlerSettings) -> Result<(), AppError> {
+    let mut hosts = Vec::with_capacity(settings.hosts.len());
+    for raw in &settings.hosts {
+        let host = normalize_host(raw).ok_or_else(|| {
+            AppError::invalid_input_field(format!("Invalid host: {}", raw), "hosts")
+        })?;
+        if !hosts.contains(&host) {
+            hosts.push(host);
+        }
+    }
+    settings.hosts = hosts;
+    settings.fallback_browser = settings.fallback_browser.trim().to_string();
+
+    if settings.enabled {
+        if settings.hosts.is_empty() {
+            return Err(AppError::invalid_input_field(
+                "Pick at least one host to open in the app",
+                "hosts",
+            ));
+        }
+        // The OS sends every link to the app once it is registered, so
+        // opening the "default browser" would hand the link straight back.
+        if settings.fallback_browser.is_empty() {
+            return Err(AppError::invalid_input_field(
+                "A fallback browser is required for links the app doesn't handle",
+                "fallbackBrowser",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Decide where `link` goes. Only `/-/merge_requests/<iid>` URLs (and their
+/// tabs, such as `/diffs`) on an opted-in host open in the app, and only
+/// while the handler is enabled.
+pub fn route(link: &str, settings: &LinkHandlerSettings) -> LinkRoute {
+    if !settings.enabled {
+        return LinkRoute::Browser;
+    }
+    let Ok(mut url) = Url::parse(link.trim()) else {
+        return LinkRoute::Browser;
+    };
+    if !SCHEMES.contains(&url.scheme()) {
+        return LinkRoute::Browser;
+    }
+    let Some(host) = normalize_host(url.as_str()) else {
+        return LinkRoute::Browser;
+    };
+    if !settings.hosts.contains(&host) {
+        return LinkRoute::Browser;
+    }
+
+    let Some((project, rest)) = url.path().split_once("/-/merge_requests/") else {
+        return LinkRoute::Browser;
+    };
+    let iid = rest.split('/').next().unwrap_or_default();
+    if project.trim_matches('/').is_empty()
+        || iid.is_empty()
+        || !iid.bytes().all(|b| b.is_ascii_digit())
+    {
+        return LinkRoute::Browser;
+    }
+
+    let path = format!("{}/-/merge_requests/{}", project, iid);
+    url.set_path(&path);
+    url.set_query(None);
+    url.set_fragment(None);
+    LinkRoute::App(url.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(hosts: &[&str]) -> LinkHandlerSettings {
+        LinkHandlerSettings {
+            enabled: true,
+            hosts: hosts.iter().map(|h| h.to_string()).collect(),
+            fallback_browser: "firefox".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_normalize_host() {
+        assert_eq!(
+            normalize_host("GitLab.Example.com"),
+            Some("gitlab.example.com".into())
+        );
+        assert_eq!(
+            normalize_host("https://gitlab.example.com/group"),
+            Some("gitlab.example.com".into())
+        );
+        assert_eq!(
+            normalize_host("git.local:8443"),
+            Some("git.local:8443".into())
+        );
+        assert_eq!(
+            normalize_host("https://gitlab.com:443"),
+            Some("gitlab.com".into())
+        );
+        assert_eq!(normalize_host("  "), None);
+    }
+
+    #[test]
+    fn test_validate_settings() {
+        let mut s = settings(&["https://GitLab.example.com/", "gitlab.example.com"]);
+        validate_settings(&mut s).unwrap();
+        assert_eq!(s.hosts, vec!["gitlab.example.com"]);
+
+        let mut no_hosts = settings(&[]);
+        assert!(validate_settings(&mut no_hosts).is_err());
+        let mut no_fallback = settings(&["gitlab.example.com"]);
+        no_fallback.fallback_browser = " ".into();
+        assert!(validate_settings(&mut no_fallback).is_err());
+
+        // Hosts can be edited while unregistered.
+        let mut disabled = settings(&[]);
+        disabled.enabled = false;
+        assert!(validate_settings(&mut disabled).is_ok());
+    }
+
+    #[test]
+    fn test_route_only_opens_mr_links_on_opted_in_hosts() {
+        let s = settings(&["gitlab.example.com"]);
+        assert_eq!(
+            route("https://gitlab.example.com/group/sub/app/-/merge_requests/42/diffs?commit_id=a#note_1", &s),
+            LinkRoute::App("https://gitlab.example.com/group/sub/app/-/merge_requests/42".into())
+        );
+        assert_eq!(
+            route("https://GITLAB.example.com/g/p/-/merge_requests/7", &s),
+            LinkRoute::App("https://gitlab.example.com/g/p/-/merge_requests/7".into())
+        );
+
+        for link in [
+            "https://gitlab.com/g/p/-/merge_requests/42",
+            "https://gitlab.example.com/g/p/-/issues/42",
+            "https://gitlab.example.com/g/p/-/merge_requests/new",
+            "https://gitlab.example.com/-/merge_requests/42",
+            "https://gitlab.example.com/g/p/-/merge_requests",
+            "ftp://gitlab.example.com/g/p/-/merge_requests/42",
+            "not a url",
+        ] {
+            assert_eq!(route(link, &s), LinkRoute::Browser, "{}", link);
+        }
+
+        let disabled = LinkHandlerSettings {
+            enabled: false,
+            ..s
+        };
+        assert_eq!(
+            route(
+                "https://gitlab.example.com/g/p/-/merge_requests/7",
+                &disabled
+            ),
+            LinkRoute::Browser
+        );
+    }
+}
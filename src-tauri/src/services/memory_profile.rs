@@ -0,0 +1,247 @@
+//! Per-subsystem heap attribution for diagnostics.
+//!
+//! Code that may hold large buffers marks itself with a [`Subsystem`] — either
+//! a scoped [`enter`] guard for synchronous work or [`track`] for futures (the
+//! tag is re-applied on every poll, so it follows the task across worker
+//! threads). When the app is built with the `memory-profiling` feature,
+//! [`TrackingAllocator`] is installed as the global allocator and charges
+//! every allocation to the subsystem active at the time, stamping the tag into
+//! a small header so the matching free is charged back to the same bucket.
+//!
+//! Without the feature the tags are still set (a thread-local write) but
+//! nothing is counted, and [`snapshot`] reports tracking as disabled. The
+//! header costs a few bytes per allocation, which is why it is opt-in.
+
+use serde::Serialize;
+use std::cell::Cell;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::task::{Context, Poll};
+
+/// Component an allocation is charged to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[repr(u8)]
+pub enum Subsystem {
+    /// Anything not running inside a tagged scope (Tauri, UI IPC, runtime).
+    Untracked = 0,
+    /// Sync engine buffers: MR lists, GraphQL batches, discussions.
+    SyncEngine = 1,
+    /// Unified-diff parsing into hunks for the diff viewer.
+    DiffParsing = 2,
+    /// Full file contents fetched to pre-populate the file cache.
+    FileCache = 3,
+}
+
+impl Subsystem {
+    pub const ALL: [Subsystem; SUBSYSTEM_COUNT] = [
+        Subsystem::Untracked,
+        Subsystem::SyncEngine,
+        Subsystem::DiffParsing,
+        Subsystem::FileCache,
+    ];
+}
+
+const SUBSYSTEM_COUNT: usize = 4;
+
+thread_local! {
+    static CURRENT: Cell<u8> = const { Cell::new(Subsystem::Untracked as u8) };
+}
+
+static LIVE_BYTES: [AtomicI64; SUBSYSTEM_COUNT] = [const { AtomicI64::new(0) }; SUBSYSTEM_COUNT];
+static PEAK_BYTES: [AtomicI64; SUBSYSTEM_COUNT] = [const { AtomicI64::new(0) }; SUBSYSTEM_COUNT];
+static ALLOCATIONS: [AtomicU64; SUBSYSTEM_COUNT] = [const { AtomicU64::new(0) }; SUBSYSTEM_COUNT];
+
+/// Tag of the current thread, or `Untracked` during thread teardown.
+#[cfg_attr(not(any(feature = "memory-profiling", test)), allow(dead_code))]
+fn current_tag() -> u8 {
+    CURRENT
+        .try_with(|c| c.get())
+        .unwrap_or(Subsystem::Untracked as u8)
+}
+
+fn set_tag(tag: u8) -> u8 {
+    CURRENT
+        .try_with(|c| c.replace(tag))
+        .unwrap_or(Subsystem::Untracked as u8)
+}
+
+/// Restores the previous subsystem tag when dropped.
+pub struct SubsystemGuard {
+    previous: u8,
+}
+
+impl Drop for SubsystemGuard {
+    fn drop(&mut self) {
+        set_tag(self.previous);
+    }
+}
+
+/// Charge allocations on this thread to `subsystem` until the guard drops.
+///
+/// Only for synchronous code — holding the guard across an `.await` would
+/// leak the tag into whatever else the worker thread polls. Use [`track`].
+pub fn enter(subsystem: Subsystem) -> SubsystemGuard {
+    SubsystemGuard {
+        previous: set_tag(subsystem as u8),
+    }
+}
+
+/// Future wrapper returned by [`track`].
+pub struct Tracked<F> {
+    subsystem: Subsystem,
+    inner: F,
+}
+
+impl<F: Future> Future for Tracked<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        // SAFETY: `inner` is structurally pinned — it is never moved out of
+        // `self` and `Tracked` has no Drop impl that could move it.
+        let this = unsafe { self.get_unchecked_mut() };
+        let _guard = enter(this.subsystem);
+        unsafe { Pin::new_unchecked(&mut this.inner) }.poll(cx)
+    }
+}
+
+/// Charge every allocation made while polling `future` to `subsystem`.
+pub fn track<F: Future>(subsystem: Subsystem, future: F) -> Tracked<F> {
+    Tracked {
+        subsystem,
+        inner: future,
+    }
+}
+
+#[cfg_attr(not(feature = "memory-profiling"), allow(dead_code))]
+fn record_alloc(tag: u8, size: usize) {
+    let idx = (tag as usize).min(SUBSYSTEM_COUNT - 1);
+    let live = LIVE_BYTES[idx].fetch_add(size as i64, Ordering::Relaxed) + size as i64;
+    PEAK_BYTES[idx].fetch_max(live, Ordering::Relaxed);
+    ALLOCATIONS[idx].fetch_add(1, Ordering::Relaxed);
+}
+
+#[cfg_attr(not(feature = "memory-profiling"), allow(dead_code))]
+fn record_free(tag: u8, size: usize) {
+    let idx = (tag as usize).min(SUBSYSTEM_COUNT - 1);
+    LIVE_BYTES[idx].fetch_sub(size as i64, Ordering::Relaxed);
+}
+
+/// Heap usage attributed to one subsystem.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubsystemUsage {
+    pub subsystem: Subsystem,
+    /// Bytes currently allocated and not yet freed.
+    pub live_bytes: i64,
+    /// Highest `live_bytes` seen since startup.
+    pub peak_bytes: i64,
+    /// Number of allocations since startup.
+    pub allocations: u64,
+}
+
+/// Whether allocations are actually being counted in this build.
+pub const fn tracking_enabled() -> bool {
+    cfg!(feature = "memory-profiling")
+}
+
+/// Current counters for every subsystem (all zero when tracking is disabled).
+pub fn snapshot() -> Vec<SubsystemUsage> {
+    Subsystem::ALL
+        .iter()
+        .enumerate()
+        .map(|(idx, subsystem)| SubsystemUsage {
+            subsystem: *subsystem,
+            live_bytes: LIVE_BYTES[idx].load(Ordering::Relaxed),
+            peak_bytes: PEAK_BYTES[idx].load(Ordering::Relaxed),
+            allocations: ALLOCATIONS[idx].load(Ordering::Relaxed),
+        })
+        .collect()
+}
+
+#[cfg(feature = "memory-profiling")]
+pub use allocator::TrackingAllocator;
+
+#[cfg(feature = "memory-profiling")]
+mod allocator {
+    use super::{current_tag, record_alloc, record_free};
+    use std::alloc::{GlobalAlloc, Layout, System};
+
+    /// Global allocator that charges each allocation to the active subsystem.
+    ///
+    /// Every block gets a header of `max(align, 8)` bytes in front of it; the
+    /// byte right before the returned pointer holds the subsystem tag. Keeping
+    /// the header a multiple of the alignment preserves the caller's alignment.
+    /// `realloc` and `alloc_zeroed` use the trait defaults, which go through
+    /// `alloc`/`dealloc` below.
+    pub struct TrackingAllocator;
+
+    fn header_len(align: usize) -> usize {
+        align.max(8)
+    }
+
+    fn outer_layout(layout: Layout) -> Option<Layout> {
+        let header = header_len(layout.align());
+        Layout::from_size_align(layout.size().checked_add(header)?, header).ok()
+    }
+
+    unsafe impl GlobalAlloc for TrackingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let Some(outer) = outer_layout(layout) else {
+                return std::ptr::null_mut();
+            };
+            let base = System.alloc(outer);
+            if base.is_null() {
+                return base;
+            }
+            let tag = current_tag();
+            let ptr = base.add(header_len(layout.align()));
+            ptr.sub(1).write(tag);
+            record_alloc(tag, layout.size());
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            let header = header_len(layout.align());
+            let tag = ptr.sub(1).read();
+            record_free(tag, layout.size());
+            // `alloc` only succeeded if this layout was valid.
+            let outer = Layout::from_size_align_unchecked(layout.size() + header, header);
+            System.dealloc(ptr.sub(header), outer);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enter_restores_previous_tag() {
+        assert_eq!(current_tag(), Subsystem::Untracked as u8);
+        {
+            let _outer = enter(Subsystem::SyncEngine);
+            {
+                let _inner = enter(Subsystem::DiffParsing);
+                assert_eq!(current_tag(), Subsystem::DiffParsing as u8);
+            }
+            assert_eq!(current_tag(), Subsystem::SyncEngine as u8);
+        }
+        assert_eq!(current_tag(), Subsystem::Untracked as u8);
+    }
+
+    #[tokio::test]
+    async fn test_track_tags_only_while_polling() {
+        let seen = track(Subsystem::FileCache, async { current_tag() }).await;
+        assert_eq!(seen, Subsystem::FileCache as u8);
+        assert_eq!(current_tag(), Subsystem::Untracked as u8);
+    }
+
+    #[test]
+    fn test_snapshot_lists_every_subsystem() {
+        let usage = snapshot();
+        assert_eq!(usage.len(), SUBSYSTEM_COUNT);
+        assert_eq!(usage[0].subsystem, Subsystem::Untracked);
+    }
+}
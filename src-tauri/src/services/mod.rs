@@ -5,17 +5,46 @@
 //!
 //! Services are designed to be testable and independent of Tauri-specific code.
 
+pub mod accessible_diff;
 pub mod auto_run;
 pub mod avatar;
+pub mod background_jobs;
+pub mod badge_scripts;
+pub mod command_metrics;
 pub mod companion_api;
 pub mod companion_auth;
+pub mod companion_events;
 pub mod companion_server;
+pub mod config_import;
+pub mod editor;
+pub mod git;
 pub mod gitattributes;
 pub mod gitlab_client;
+#[cfg(feature = "gitlab-fixtures")]
+pub mod gitlab_fixtures;
+pub mod highlight_themes;
+pub mod highlighter;
+pub mod instance_headers;
+pub mod instance_lock;
+pub mod instance_tls;
+pub mod ipc_compression;
+pub mod link_handler;
+pub mod memory_profile;
+pub mod myers;
+pub mod offline_mode;
+pub mod quick_switch;
+pub mod rate_limits;
+pub mod reconciliation;
+pub mod secret_scan;
+pub mod semantic_diff;
+pub mod side_by_side;
 pub mod sync_engine;
 pub mod sync_events;
 pub mod sync_processor;
 pub mod sync_queue;
+pub mod sync_selftest;
+pub mod whitespace_diff;
+pub mod word_diff;
 
 pub use gitlab_client::GitLabClient;
 pub use sync_engine::{SyncConfig, SyncEngine, SyncLogEntry, SyncResult, SyncStatus};
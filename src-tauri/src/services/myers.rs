@@ -0,0 +1,128 @@
+//! Myers' O(ND) difference algorithm ("An O(ND) Difference Algorithm and
+//! Its Variations", 1986), shared by the word and line diffs.
+//!
+//! Only the frontier of each edit distance is kept for the walk back, so
+//! memory grows with the square of the number of edits rather than with the
+//! input size times the edits.
+
+/// Which elements of `a` and `b` are not part of a shortest edit script's
+/// common subsequence, or `None` when the script needs more than
+/// `max_edits` insertions and deletions.
+pub fn diff<T: PartialEq>(a: &[T], b: &[T], max_edits: usize) -> Option<(Vec<bool>, Vec<bool>)> {
+    let mut removed = vec![false; a.len()];
+    let mut added = vec![false; b.len()];
+
+    // Common ends can't be part of an edit; skipping them keeps the search
+    // to the changed middle.
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let (a_mid, b_mid) = (&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+
+    let (n, m) = (a_mid.len() as isize, b_mid.len() as isize);
+    let max = (n + m).min(isize::try_from(max_edits).unwrap_or(isize::MAX));
+    let offset = max as usize;
+    // `v[k + max]` is the furthest x reached on diagonal k so far.
+    let mut v = vec![0isize; 2 * offset + 2];
+    // `trace[d]` is `v` for diagonals -d..=d before the search at distance d.
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+    let mut found = false;
+
+    'search: for d in 0..=max {
+        trace.push(v[offset - d as usize..=offset + d as usize].to_vec());
+        for k in (-d..=d).step_by(2) {
+            let idx = (k + max) as usize;
+            let mut x = if k == -d || (k != d && v[idx - 1] < v[idx + 1]) {
+                v[idx + 1]
+            } else {
+                v[idx - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a_mid[x as usize] == b_mid[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[idx] = x;
+            if x >= n && y >= m {
+                found = true;
+                break 'search;
+            }
+        }
+    }
+    if !found {
+        return None;
+    }
+
+    // Walk the trace back from the end, marking every non-diagonal step.
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().skip(1).rev() {
+        let d = d as isize;
+        let at = |k: isize| v[(k + d) as usize];
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && at(k - 1) < at(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = at(prev_k);
+        let prev_y = prev_x - prev_k;
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+        }
+        if x == prev_x {
+            added[prefix + (y - 1) as usize] = true;
+        } else {
+            removed[prefix + (x - 1) as usize] = true;
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+    Some((removed, added))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edits(removed: &[bool], added: &[bool]) -> usize {
+        removed.iter().chain(added).filter(|&&c| c).count()
+    }
+
+    #[test]
+    fn test_diff_finds_a_shortest_script() {
+        let (removed, added) = diff(
+            &['a', 'b', 'c', 'a', 'b', 'b', 'a'],
+            &['c', 'b', 'a', 'b', 'a', 'c'],
+            usize::MAX,
+        )
+        .unwrap();
+        // Edit distance 5 for this classic example.
+        assert_eq!(edits(&removed, &added), 5);
+    }
+
+    #[test]
+    fn test_diff_keeps_common_ends() {
+        let (removed, added) = diff(&[1, 2, 3, 4, 5], &[1, 2, 9, 4, 5], usize::MAX).unwrap();
+        assert_eq!(removed, vec![false, false, true, false, false]);
+        assert_eq!(added, vec![false, false, true, false, false]);
+
+        let (removed, added) = diff(&[1, 2], &[1, 2, 3], usize::MAX).unwrap();
+        assert_eq!((removed, added), (vec![false; 2], vec![false, false, true]));
+        let (removed, added) = diff::<u8>(&[], &[], 0).unwrap();
+        assert!(removed.is_empty() && added.is_empty());
+    }
+
+    #[test]
+    fn test_diff_gives_up_past_max_edits() {
+        let a: Vec<u32> = (0..100).collect();
+        let b: Vec<u32> = (100..200).collect();
+        assert!(diff(&a, &b, 50).is_none());
+        let (removed, added) = diff(&a, &b, 200).unwrap();
+        assert_eq!(edits(&removed, &added), 200);
+    }
+}
@@ -0,0 +1,50 @@
+//! Explicit offline mode.
+//!
+//! On a flight or a captive network every background request ends in a
+//! timeout and an error. While offline mode is on, nothing leaves the
+//! machine: the GitLab client, avatar downloads, translation and automation
+//! webhooks fail fast with a network error, and the sync engine skips its
+//! runs. Cached reads are unaffected and new actions still land in the sync
+//! queue, where they wait, without spending retries, until the mode is
+//! turned off.
+//!
+//! The flag is persisted in the settings store and restored at startup.
+
+use crate::error::AppError;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Whether offline mode is on.
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
+
+/// Turn offline mode on or off. Returns the previous state.
+pub fn set_offline(offline: bool) -> bool {
+    OFFLINE.swap(offline, Ordering::Relaxed)
+}
+
+/// Fail with a network error while offline mode is on.
+pub fn ensure_online() -> Result<(), AppError> {
+    if is_offline() {
+        Err(AppError::network("Offline mode is on"))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blocks_requests_while_offline() {
+        let previous = set_offline(true);
+        let err = ensure_online().unwrap_err();
+        assert!(matches!(err, AppError::Network { .. }));
+        set_offline(false);
+        assert!(ensure_online().is_ok());
+        set_offline(previous);
+    }
+}
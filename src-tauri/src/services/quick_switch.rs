@@ -0,0 +1,195 @@
+//! In-memory index of open MRs for the quick switcher.
+//!
+//! The switcher (Cmd+K) filters on every keystroke, so instead of running
+//! the list queries it reads a compact, pre-sorted copy of every open MR
+//! across all instances. The index is built from the database on first use
+//! and afterwards kept current by the sync engine, which refreshes single
+//! entries as MRs are upserted and drops them when they are merged, closed
+//! or purged. Anything that deletes MRs behind the engine's back calls
+//! [`invalidate`] and the next read rebuilds it.
+
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use serde::Serialize;
+use sqlx::FromRow;
+use std::sync::OnceLock;
+use tokio::sync::RwLock;
+
+/// Half-life of the recency score: an MR untouched for a week scores 0.5.
+const SCORE_HALF_LIFE_SECS: f64 = 7.0 * 24.0 * 60.0 * 60.0;
+
+#[derive(Debug, Clone, FromRow)]
+struct IndexRow {
+    id: i64,
+    instance_id: i64,
+    iid: i64,
+    title: String,
+    project_name: String,
+    updated_at: i64,
+}
+
+/// One switcher entry, ranked most recently updated first.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickSwitchEntry {
+    pub mr_id: i64,
+    pub instance_id: i64,
+    pub iid: i64,
+    pub title: String,
+    pub project: String,
+    /// 1.0 for an MR updated just now, halving every week since.
+    pub score: f64,
+}
+
+/// Entries sorted by `updated_at` descending; `None` until first built.
+fn index() -> &'static RwLock<Option<Vec<IndexRow>>> {
+    static INDEX: OnceLock<RwLock<Option<Vec<IndexRow>>>> = OnceLock::new();
+    INDEX.get_or_init(|| RwLock::new(None))
+}
+
+const SELECT_ROWS: &str = "SELECT id, instance_id, iid, title, project_name, updated_at \
+     FROM merge_requests WHERE state = 'opened'";
+
+fn recency_score(updated_at: i64, now: i64) -> f64 {
+    let age = (now - updated_at).max(0) as f64;
+    0.5f64.powf(age / SCORE_HALF_LIFE_SECS)
+}
+
+fn insert_sorted(rows: &mut Vec<IndexRow>, row: IndexRow) {
+    let pos = rows.partition_point(|r| r.updated_at > row.updated_at);
+    rows.insert(pos, row);
+}
+
+/// Ranked switcher entries, building the index first if needed.
+pub async fn entries(pool: &DbPool, now: i64) -> Result<Vec<QuickSwitchEntry>, AppError> {
+    {
+        let guard = index().read().await;
+        if let Some(rows) = guard.as_ref() {
+            return Ok(to_entries(rows, now));
+        }
+    }
+
+    // Hold the write lock while reading the table so a concurrent refresh
+    // either lands in the table before we read it or waits for the build.
+    let mut guard = index().write().await;
+    if guard.is_none() {
+        let rows: Vec<IndexRow> =
+            sqlx::query_as(&format!("{} ORDER BY updated_at DESC", SELECT_ROWS))
+                .fetch_all(pool)
+                .await?;
+        *guard = Some(rows);
+    }
+    Ok(to_entries(guard.as_deref().unwrap_or_default(), now))
+}
+
+fn to_entries(rows: &[IndexRow], now: i64) -> Vec<QuickSwitchEntry> {
+    rows.iter()
+        .map(|r| QuickSwitchEntry {
+            mr_id: r.id,
+            instance_id: r.instance_id,
+            iid: r.iid,
+            title: r.title.clone(),
+            project: r.project_name.clone(),
+            score: recency_score(r.updated_at, now),
+        })
+        .collect()
+}
+
+/// Re-read one MR into the index, or drop it if it is no longer open.
+/// A no-op until the index has been built.
+pub async fn refresh(pool: &DbPool, mr_id: i64) -> Result<(), AppError> {
+    let mut guard = index().write().await;
+    let Some(rows) = guard.as_mut() else {
+        return Ok(());
+    };
+    let row: Option<IndexRow> = sqlx::query_as(&format!("{} AND id = ?", SELECT_ROWS))
+        .bind(mr_id)
+        .fetch_optional(pool)
+        .await?;
+    rows.retain(|r| r.id != mr_id);
+    if let Some(row) = row {
+        insert_sorted(rows, row);
+    }
+    Ok(())
+}
+
+/// Drop MRs that were deleted from the cache.
+pub async fn remove(mr_ids: &[i64]) {
+    if let Some(rows) = index().write().await.as_mut() {
+        rows.retain(|r| !mr_ids.contains(&r.id));
+    }
+}
+
+/// Discard the index; the next read rebuilds it from the database.
+pub async fn invalidate() {
+    *index().write().await = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db;
+    use tempfile::tempdir;
+
+    const WEEK: i64 = 7 * 24 * 60 * 60;
+
+    async fn insert_mr(pool: &DbPool, id: i64, title: &str, state: &str, updated_at: i64) {
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, project_name, title, author_username,
+              source_branch, target_branch, state, web_url, created_at, updated_at,
+              labels, reviewers, cached_at)
+             VALUES (?, 1, ?, 10, 'g/p', ?, 'alice', 's', 'main', ?, 'http://x', 0, ?, '[]', '[]', 0)
+             ON CONFLICT(id) DO UPDATE SET title = excluded.title, state = excluded.state,
+                updated_at = excluded.updated_at",
+        )
+        .bind(id)
+        .bind(id)
+        .bind(title)
+        .bind(state)
+        .bind(updated_at)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    #[test]
+    fn score_halves_every_week() {
+        assert_eq!(recency_score(100, 100), 1.0);
+        assert!((recency_score(0, WEEK) - 0.5).abs() < 1e-9);
+        assert!((recency_score(0, 2 * WEEK) - 0.25).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn index_is_built_then_maintained_incrementally() {
+        let dir = tempdir().unwrap();
+        let pool = db::initialize(&dir.path().join("t.db")).await.unwrap();
+        sqlx::query(
+            "INSERT INTO gitlab_instances (id, url, token, created_at) VALUES (1, 'u', 't', 0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        insert_mr(&pool, 1, "older", "opened", WEEK).await;
+        insert_mr(&pool, 2, "newer", "opened", 2 * WEEK).await;
+        insert_mr(&pool, 3, "merged", "merged", 3 * WEEK).await;
+
+        invalidate().await;
+        let ids = |e: Vec<QuickSwitchEntry>| e.into_iter().map(|e| e.mr_id).collect::<Vec<_>>();
+        let built = entries(&pool, 2 * WEEK).await.unwrap();
+        assert_eq!(built[0].score, 1.0);
+        assert_eq!(ids(built), vec![2, 1]);
+
+        // Touching MR 1 moves it to the front; merging MR 2 drops it.
+        insert_mr(&pool, 1, "older, renamed", "opened", 4 * WEEK).await;
+        insert_mr(&pool, 2, "newer", "merged", 4 * WEEK).await;
+        refresh(&pool, 1).await.unwrap();
+        refresh(&pool, 2).await.unwrap();
+        let after = entries(&pool, 4 * WEEK).await.unwrap();
+        assert_eq!(after[0].title, "older, renamed");
+        assert_eq!(ids(after), vec![1]);
+
+        remove(&[1]).await;
+        assert!(entries(&pool, 4 * WEEK).await.unwrap().is_empty());
+    }
+}
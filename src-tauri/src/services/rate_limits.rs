@@ -0,0 +1,126 @@
+//! API rate-limit budget observed from response headers.
+//!
+//! GitLab sends `RateLimit-*` headers on API responses whenever throttling is
+//! enabled (always on gitlab.com). The client records the latest values per
+//! instance URL, so the quota view can show how much budget is left without
+//! spending a request to find out.
+
+use reqwest::header::HeaderMap;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+/// The rate-limit budget as of the last response that carried it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitSnapshot {
+    /// Requests allowed per window.
+    pub limit: i64,
+    pub remaining: i64,
+    /// Unix seconds when the window resets, when sent.
+    pub reset_at: Option<i64>,
+    /// Unix seconds when the headers were seen.
+    pub observed_at: i64,
+}
+
+/// Instance URL → latest snapshot.
+fn registry() -> &'static RwLock<HashMap<String, RateLimitSnapshot>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<String, RateLimitSnapshot>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+fn registry_key(url: &str) -> String {
+    url.trim_end_matches('/').to_string()
+}
+
+fn header_i64(headers: &HeaderMap, name: &str) -> Option<i64> {
+    headers.get(name)?.to_str().ok()?.trim().parse().ok()
+}
+
+/// Read the budget from a response's headers. `None` when the instance
+/// doesn't send `RateLimit-Limit` and `RateLimit-Remaining`.
+pub fn parse(headers: &HeaderMap, now: i64) -> Option<RateLimitSnapshot> {
+    let limit = header_i64(headers, "ratelimit-limit")?;
+    let remaining = header_i64(headers, "ratelimit-remaining")?;
+    Some(RateLimitSnapshot {
+        limit,
+        remaining: remaining.clamp(0, limit.max(0)),
+        reset_at: header_i64(headers, "ratelimit-reset"),
+        observed_at: now,
+    })
+}
+
+/// Record the budget from a response for an instance URL, if it carried one.
+pub fn observe(url: &str, headers: &HeaderMap) {
+    let Some(snapshot) = parse(headers, chrono::Utc::now().timestamp()) else {
+        return;
+    };
+    if let Ok(mut r) = registry().write() {
+        r.insert(registry_key(url), snapshot);
+    }
+}
+
+/// Latest budget seen for an instance URL this session.
+pub fn latest(url: &str) -> Option<RateLimitSnapshot> {
+    registry().read().ok()?.get(&registry_key(url)).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        map
+    }
+
+    #[test]
+    fn test_parse_reads_budget_headers() {
+        let h = headers(&[
+            ("RateLimit-Limit", "2000"),
+            ("RateLimit-Remaining", "1987"),
+            ("RateLimit-Reset", "1700000060"),
+            ("RateLimit-Observed", "13"),
+        ]);
+        assert_eq!(
+            parse(&h, 1_700_000_000),
+            Some(RateLimitSnapshot {
+                limit: 2000,
+                remaining: 1987,
+                reset_at: Some(1_700_000_060),
+                observed_at: 1_700_000_000,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_needs_limit_and_remaining() {
+        assert_eq!(parse(&headers(&[("RateLimit-Limit", "2000")]), 0), None);
+        assert_eq!(parse(&headers(&[("RateLimit-Remaining", "x")]), 0), None);
+        assert_eq!(parse(&HeaderMap::new(), 0), None);
+
+        let over = parse(
+            &headers(&[("RateLimit-Limit", "10"), ("RateLimit-Remaining", "-3")]),
+            0,
+        )
+        .unwrap();
+        assert_eq!((over.remaining, over.reset_at), (0, None));
+    }
+
+    #[test]
+    fn test_observe_keys_by_instance_url() {
+        let url = "https://rate-limits.test/";
+        observe(
+            url,
+            &headers(&[("RateLimit-Limit", "600"), ("RateLimit-Remaining", "599")]),
+        );
+        observe(url, &HeaderMap::new());
+        let seen = latest("https://rate-limits.test").unwrap();
+        assert_eq!((seen.limit, seen.remaining), (600, 599));
+        assert_eq!(latest("https://other.test"), None);
+    }
+}
@@ -0,0 +1,258 @@
+//! Deep reconciliation of the MR cache.
+//!
+//! Regular syncs are incremental: an MR whose `updated_at` didn't move keeps
+//! its cached diff and comments, and MRs missing from an incomplete fetch are
+//! left alone. GitLab doesn't bump `updated_at` for every change, so over
+//! weeks the cache can drift. A reconciliation run forces a full re-fetch,
+//! re-checks every opened MR the forced pass didn't refresh, and removes
+//! rows nothing refers to anymore. It runs on `reconcile_interval_secs` or
+//! on demand, and its summary goes to `sync_log`.
+
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use sqlx::FromRow;
+use std::collections::HashMap;
+
+/// Default time between reconciliation runs (one week).
+pub const DEFAULT_RECONCILE_INTERVAL_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// What the cache knows about an MR, compared before and after a forced sync.
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct MrFingerprint {
+    pub id: i64,
+    pub updated_at: i64,
+    pub state: String,
+    pub title: String,
+    pub labels: Option<String>,
+    pub reviewers: Option<String>,
+    pub head_sha: Option<String>,
+    pub file_count: i64,
+    pub comment_count: i64,
+}
+
+/// Outcome of a reconciliation run.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DriftSummary {
+    /// MRs cached when the run started.
+    pub checked: i64,
+    /// MRs whose cached data was stale although `updated_at` hadn't changed.
+    pub drifted: i64,
+    /// Opened MRs the forced sync didn't return that turned out merged or closed.
+    pub state_fixed: i64,
+    /// MRs GitLab no longer knows about (deleted, or access lost).
+    pub removed: i64,
+    /// Cached file versions left without an MR.
+    pub orphans_removed: i64,
+    /// Errors from the forced sync and the re-checks.
+    pub errors: i64,
+}
+
+impl DriftSummary {
+    /// One-line summary for the sync log.
+    pub fn message(&self) -> String {
+        format!(
+            "Checked {} MRs: {} drifted, {} closed upstream, {} gone, {} orphaned file versions removed, {} errors",
+            self.checked,
+            self.drifted,
+            self.state_fixed,
+            self.removed,
+            self.orphans_removed,
+            self.errors
+        )
+    }
+}
+
+/// Fingerprints of all cached MRs.
+pub async fn snapshot(pool: &DbPool) -> Result<HashMap<i64, MrFingerprint>, AppError> {
+    let rows: Vec<MrFingerprint> = sqlx::query_as(
+        "SELECT mr.id, mr.updated_at, mr.state, mr.title, mr.labels, mr.reviewers, d.head_sha,
+                (SELECT COUNT(*) FROM diff_files f WHERE f.mr_id = mr.id) AS file_count,
+                (SELECT COUNT(*) FROM comments c WHERE c.mr_id = mr.id AND c.id > 0) AS comment_count
+         FROM merge_requests mr
+         LEFT JOIN diffs d ON d.mr_id = mr.id",
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.into_iter().map(|r| (r.id, r)).collect())
+}
+
+/// MRs that changed between two snapshots although their `updated_at` didn't,
+/// i.e. changes an incremental sync would have skipped.
+pub fn drifted(
+    before: &HashMap<i64, MrFingerprint>,
+    after: &HashMap<i64, MrFingerprint>,
+) -> Vec<i64> {
+    let mut ids: Vec<i64> = before
+        .iter()
+        .filter_map(|(id, old)| {
+            let new = after.get(id)?;
+            (new.updated_at == old.updated_at && new != old).then_some(*id)
+        })
+        .collect();
+    ids.sort_unstable();
+    ids
+}
+
+/// Opened MRs not refreshed since `since`, as (id, instance_id, project_id, iid).
+pub async fn stale_open_mrs(
+    pool: &DbPool,
+    since: i64,
+) -> Result<Vec<(i64, i64, i64, i64)>, AppError> {
+    Ok(sqlx::query_as(
+        "SELECT id, instance_id, project_id, iid FROM merge_requests
+         WHERE state = 'opened' AND cached_at < ?
+         ORDER BY instance_id, id",
+    )
+    .bind(since)
+    .fetch_all(pool)
+    .await?)
+}
+
+/// Delete cached file versions whose MR is gone, then unreferenced blobs.
+/// Returns the number of file versions removed.
+pub async fn remove_orphans(pool: &DbPool) -> Result<i64, AppError> {
+    let removed =
+        sqlx::query("DELETE FROM file_versions WHERE mr_id NOT IN (SELECT id FROM merge_requests)")
+            .execute(pool)
+            .await?
+            .rows_affected() as i64;
+    crate::db::file_cache::delete_orphaned_blobs(pool).await?;
+    Ok(removed)
+}
+
+/// When the last reconciliation started.
+pub async fn last_run_at(pool: &DbPool) -> Result<Option<i64>, AppError> {
+    Ok(
+        sqlx::query_scalar("SELECT MAX(started_at) FROM sync_reconciliations")
+            .fetch_one(pool)
+            .await?,
+    )
+}
+
+/// Whether a reconciliation is due. An interval of 0 disables the schedule.
+pub fn is_due(last_run_at: Option<i64>, now: i64, interval_secs: u64) -> bool {
+    if interval_secs == 0 {
+        return false;
+    }
+    last_run_at.is_none_or(|last| now - last >= interval_secs as i64)
+}
+
+/// Store a finished run.
+pub async fn record_run(
+    pool: &DbPool,
+    started_at: i64,
+    duration_ms: i64,
+    summary: &DriftSummary,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO sync_reconciliations
+         (started_at, duration_ms, checked, drifted, state_fixed, removed, orphans_removed, errors)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(started_at)
+    .bind(duration_ms)
+    .bind(summary.checked)
+    .bind(summary.drifted)
+    .bind(summary.state_fixed)
+    .bind(summary.removed)
+    .bind(summary.orphans_removed)
+    .bind(summary.errors)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tests::seed_instance;
+
+    async fn insert_mr(pool: &DbPool, inst: i64, id: i64, state: &str, cached_at: i64) {
+        sqlx::query(
+            "INSERT INTO merge_requests
+             (id, instance_id, iid, project_id, project_name, title, author_username,
+              source_branch, target_branch, state, web_url, created_at, updated_at,
+              labels, reviewers, cached_at)
+             VALUES (?, ?, ?, 10, 'g/p', 't', 'alice', 's', 'main', ?, 'http://x', 0, 50,
+                     '[]', '[]', ?)",
+        )
+        .bind(id)
+        .bind(inst)
+        .bind(id)
+        .bind(state)
+        .bind(cached_at)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    #[test]
+    fn schedules_by_interval() {
+        assert!(is_due(None, 1_000, 600));
+        assert!(!is_due(Some(500), 1_000, 600));
+        assert!(is_due(Some(400), 1_000, 600));
+        assert!(!is_due(None, 1_000, 0));
+    }
+
+    #[tokio::test]
+    async fn detects_drift_behind_unchanged_updated_at() {
+        let (pool, inst) = seed_instance(true).await;
+        insert_mr(&pool, inst, 1, "opened", 0).await;
+        insert_mr(&pool, inst, 2, "opened", 0).await;
+        insert_mr(&pool, inst, 3, "opened", 0).await;
+        let before = snapshot(&pool).await.unwrap();
+        assert_eq!(before[&1].comment_count, 0);
+
+        // 1: a comment the incremental sync missed. 2: a real update.
+        sqlx::query(
+            "INSERT INTO comments (id, mr_id, author_username, body, system, created_at, updated_at)
+             VALUES (7, 1, 'bob', 'late', 0, 0, 0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("UPDATE merge_requests SET updated_at = 60, title = 'u' WHERE id = 2")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let after = snapshot(&pool).await.unwrap();
+        assert_eq!(drifted(&before, &after), vec![1]);
+    }
+
+    #[tokio::test]
+    async fn finds_stale_mrs_and_removes_orphans() {
+        let (pool, inst) = seed_instance(true).await;
+        insert_mr(&pool, inst, 1, "opened", 100).await;
+        insert_mr(&pool, inst, 2, "opened", 200).await;
+        insert_mr(&pool, inst, 3, "merged", 100).await;
+        let stale = stale_open_mrs(&pool, 200).await.unwrap();
+        assert_eq!(stale, vec![(1, inst, 10, 1)]);
+
+        sqlx::query(
+            "INSERT INTO file_blobs (sha, content, size_bytes) VALUES ('a', 'x', 1), ('b', 'y', 1)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO file_versions (mr_id, file_path, version_type, sha, instance_id, project_id)
+             VALUES (1, 'f', 'head', 'a', '1', 10), (99, 'f', 'head', 'b', '1', 10)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        assert_eq!(remove_orphans(&pool).await.unwrap(), 1);
+        let blobs: Vec<String> = sqlx::query_scalar("SELECT sha FROM file_blobs")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(blobs, vec!["a".to_string()]);
+
+        assert_eq!(last_run_at(&pool).await.unwrap(), None);
+        record_run(&pool, 300, 5, &DriftSummary::default())
+            .await
+            .unwrap();
+        assert_eq!(last_run_at(&pool).await.unwrap(), Some(300));
+    }
+}
@@ -0,0 +1,669 @@
+//! Language-aware summaries of what a file change does to its symbols.
+//!
+//! Both sides of a file are parsed with tree-sitter and their top-level
+//! definitions (plus the members of classes, impls, traits and modules) are
+//! matched by qualified name. The result reads like "modifies
+//! `SyncEngine::run_sync`, adds `flush_actions`" instead of a line count.
+//! Bodies are compared with whitespace collapsed, so a reformat alone doesn't
+//! count as a modification.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tree_sitter::{Language, Node, Parser};
+
+/// How many names each verb lists in the one-line summary before "+N more".
+const SUMMARY_NAMES_PER_VERB: usize = 3;
+
+/// A language with a bundled tree-sitter grammar, shared with the diff
+/// syntax highlighter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    Rust,
+    TypeScript,
+    Tsx,
+    JavaScript,
+    Python,
+    Go,
+}
+
+impl Lang {
+    pub const ALL: [Self; 6] = [
+        Lang::Rust,
+        Lang::TypeScript,
+        Lang::Tsx,
+        Lang::JavaScript,
+        Lang::Python,
+        Lang::Go,
+    ];
+
+    /// Language for a file path, by extension.
+    pub fn from_path(path: &str) -> Option<Self> {
+        let ext = path.rsplit_once('.')?.1.to_ascii_lowercase();
+        match ext.as_str() {
+            "rs" => Some(Lang::Rust),
+            "ts" | "mts" | "cts" => Some(Lang::TypeScript),
+            "tsx" => Some(Lang::Tsx),
+            "js" | "jsx" | "mjs" | "cjs" => Some(Lang::JavaScript),
+            "py" | "pyi" => Some(Lang::Python),
+            "go" => Some(Lang::Go),
+            _ => None,
+        }
+    }
+
+    /// Language for a Linguist language name, as used by
+    /// `linguist-language=` in `.gitattributes`.
+    pub fn from_linguist_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "rust" => Some(Lang::Rust),
+            "typescript" => Some(Lang::TypeScript),
+            "tsx" => Some(Lang::Tsx),
+            "javascript" | "jsx" => Some(Lang::JavaScript),
+            "python" => Some(Lang::Python),
+            "go" => Some(Lang::Go),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Lang::Rust => "rust",
+            Lang::TypeScript | Lang::Tsx => "typescript",
+            Lang::JavaScript => "javascript",
+            Lang::Python => "python",
+            Lang::Go => "go",
+        }
+    }
+
+    pub fn grammar(self) -> Language {
+        match self {
+            Lang::Rust => tree_sitter_rust::LANGUAGE.into(),
+            Lang::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            Lang::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
+            Lang::JavaScript => tree_sitter_javascript::LANGUAGE.into(),
+            Lang::Python => tree_sitter_python::LANGUAGE.into(),
+            Lang::Go => tree_sitter_go::LANGUAGE.into(),
+        }
+    }
+
+    fn separator(self) -> &'static str {
+        match self {
+            Lang::Rust => "::",
+            _ => ".",
+        }
+    }
+
+    /// Nodes that only wrap definitions (`export`, `const x = ...`,
+    /// decorators) and should be looked through.
+    fn is_wrapper(self, kind: &str) -> bool {
+        matches!(
+            (self, kind),
+            (
+                Lang::TypeScript | Lang::Tsx | Lang::JavaScript,
+                "export_statement"
+                    | "lexical_declaration"
+                    | "variable_declaration"
+                    | "ambient_declaration"
+            ) | (Lang::Python, "decorated_definition")
+                | (Lang::Go, "type_declaration")
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SymbolKind {
+    Function,
+    Method,
+    Class,
+    Struct,
+    Enum,
+    Trait,
+    Interface,
+    Type,
+    Module,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SymbolChangeKind {
+    Added,
+    Removed,
+    /// Parameters, return type, generics or visibility changed.
+    SignatureChanged,
+    /// Same signature, different body.
+    Modified,
+}
+
+/// One symbol that differs between base and head.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolChange {
+    /// Qualified name, e.g. `SyncEngine::run_sync` or `Widget.render`.
+    pub name: String,
+    pub kind: SymbolKind,
+    pub change: SymbolChangeKind,
+    pub old_signature: Option<String>,
+    pub new_signature: Option<String>,
+}
+
+/// Symbol-level summary of a file's change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SemanticSummary {
+    /// Detected language, or `None` when the file type isn't supported (in
+    /// which case `changes` is empty).
+    pub language: Option<String>,
+    pub changes: Vec<SymbolChange>,
+    /// One-line description for the file list; empty when no symbol changed.
+    pub summary: String,
+}
+
+#[derive(Debug, Clone)]
+struct Symbol {
+    name: String,
+    kind: SymbolKind,
+    signature: String,
+    body: String,
+}
+
+/// A definition node recognised by [`classify`].
+struct Definition<'a> {
+    kind: SymbolKind,
+    name: String,
+    /// Node whose named children are this definition's members, if it is a
+    /// container (class, impl, trait, module).
+    members: Option<Node<'a>>,
+    /// Start of the body; the signature is everything before it.
+    body_start: Option<usize>,
+    /// Whether to report the definition itself (false for `impl` blocks).
+    reported: bool,
+}
+
+fn text<'a>(node: Node<'_>, source: &'a str) -> &'a str {
+    &source[node.byte_range()]
+}
+
+fn collapse_whitespace(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn field_text(node: Node<'_>, field: &str, source: &str) -> Option<String> {
+    node.child_by_field_name(field)
+        .map(|n| text(n, source).to_string())
+}
+
+/// Recognise a definition node. `in_container` is true for members of a
+/// class/impl/trait, so functions there are reported as methods.
+fn classify<'a>(
+    lang: Lang,
+    node: Node<'a>,
+    source: &str,
+    in_container: bool,
+) -> Option<Definition<'a>> {
+    let body = node.child_by_field_name("body");
+    let def = |kind, name: String, container: bool| Definition {
+        kind,
+        name,
+        members: if container { body } else { None },
+        body_start: body.map(|b| b.start_byte()),
+        reported: true,
+    };
+    let function_kind = if in_container {
+        SymbolKind::Method
+    } else {
+        SymbolKind::Function
+    };
+
+    match (lang, node.kind()) {
+        (Lang::Rust, "function_item" | "function_signature_item") => {
+            Some(def(function_kind, field_text(node, "name", source)?, false))
+        }
+        (Lang::Rust, "struct_item" | "union_item") => Some(def(
+            SymbolKind::Struct,
+            field_text(node, "name", source)?,
+            false,
+        )),
+        (Lang::Rust, "enum_item") => Some(def(
+            SymbolKind::Enum,
+            field_text(node, "name", source)?,
+            false,
+        )),
+        (Lang::Rust, "type_item") => Some(def(
+            SymbolKind::Type,
+            field_text(node, "name", source)?,
+            false,
+        )),
+        (Lang::Rust, "trait_item") => Some(def(
+            SymbolKind::Trait,
+            field_text(node, "name", source)?,
+            true,
+        )),
+        (Lang::Rust, "mod_item") if body.is_some() => Some(def(
+            SymbolKind::Module,
+            field_text(node, "name", source)?,
+            true,
+        )),
+        (Lang::Rust, "impl_item") => {
+            // Members are named after the implementing type; strip generics so
+            // `impl<T> Foo<T>` and `impl Foo<u8>` both qualify as `Foo::...`.
+            let ty = field_text(node, "type", source)?;
+            let name = ty.split('<').next().unwrap_or(&ty).trim().to_string();
+            Some(Definition {
+                reported: false,
+                ..def(SymbolKind::Struct, name, true)
+            })
+        }
+
+        (
+            Lang::TypeScript | Lang::Tsx | Lang::JavaScript,
+            "function_declaration" | "generator_function_declaration",
+        ) => Some(def(
+            SymbolKind::Function,
+            field_text(node, "name", source)?,
+            false,
+        )),
+        (
+            Lang::TypeScript | Lang::Tsx | Lang::JavaScript,
+            "class_declaration" | "abstract_class_declaration",
+        ) => Some(def(
+            SymbolKind::Class,
+            field_text(node, "name", source)?,
+            true,
+        )),
+        (
+            Lang::TypeScript | Lang::Tsx | Lang::JavaScript,
+            "method_definition" | "method_signature" | "abstract_method_signature",
+        ) => Some(def(
+            SymbolKind::Method,
+            field_text(node, "name", source)?,
+            false,
+        )),
+        (Lang::TypeScript | Lang::Tsx, "interface_declaration") => Some(def(
+            SymbolKind::Interface,
+            field_text(node, "name", source)?,
+            false,
+        )),
+        (Lang::TypeScript | Lang::Tsx, "type_alias_declaration") => Some(def(
+            SymbolKind::Type,
+            field_text(node, "name", source)?,
+            false,
+        )),
+        (Lang::TypeScript | Lang::Tsx, "enum_declaration") => Some(def(
+            SymbolKind::Enum,
+            field_text(node, "name", source)?,
+            false,
+        )),
+        // `const foo = () => {}` and class fields holding arrow functions.
+        (
+            Lang::TypeScript | Lang::Tsx | Lang::JavaScript,
+            "variable_declarator" | "public_field_definition" | "field_definition",
+        ) => {
+            let name_node = node
+                .child_by_field_name("name")
+                .or_else(|| node.child_by_field_name("property"))?;
+            let value = node.child_by_field_name("value")?;
+            if !matches!(
+                value.kind(),
+                "arrow_function" | "function_expression" | "function"
+            ) {
+                return None;
+            }
+            Some(Definition {
+                kind: function_kind,
+                name: text(name_node, source).to_string(),
+                members: None,
+                body_start: value.child_by_field_name("body").map(|b| b.start_byte()),
+                reported: true,
+            })
+        }
+
+        (Lang::Python, "function_definition") => {
+            Some(def(function_kind, field_text(node, "name", source)?, false))
+        }
+        (Lang::Python, "class_definition") => Some(def(
+            SymbolKind::Class,
+            field_text(node, "name", source)?,
+            true,
+        )),
+
+        (Lang::Go, "function_declaration") => Some(def(
+            SymbolKind::Function,
+            field_text(node, "name", source)?,
+            false,
+        )),
+        (Lang::Go, "method_declaration") => {
+            // `func (s *Server) Start()` -> `Server.Start`
+            let receiver = node
+                .child_by_field_name("receiver")?
+                .named_child(0)
+                .and_then(|param| param.child_by_field_name("type"))
+                .map(|ty| text(ty, source))?;
+            let receiver = receiver.trim_start_matches('*');
+            let receiver = receiver.split('[').next().unwrap_or(receiver);
+            let name = format!("{}.{}", receiver, field_text(node, "name", source)?);
+            Some(def(SymbolKind::Method, name, false))
+        }
+        (Lang::Go, "type_spec" | "type_alias") => {
+            let ty = node.child_by_field_name("type");
+            let kind = match ty.map(|t| t.kind()) {
+                Some("struct_type") => SymbolKind::Struct,
+                Some("interface_type") => SymbolKind::Interface,
+                _ => SymbolKind::Type,
+            };
+            Some(Definition {
+                kind,
+                name: field_text(node, "name", source)?,
+                members: None,
+                body_start: ty.map(|t| t.start_byte()),
+                reported: true,
+            })
+        }
+
+        _ => None,
+    }
+}
+
+/// Whether `node` is (or only wraps) a definition [`collect_symbols`] reports.
+fn is_definition(lang: Lang, node: Node<'_>, source: &str) -> bool {
+    if classify(lang, node, source, true).is_some() {
+        return true;
+    }
+    let mut cursor = node.walk();
+    lang.is_wrapper(node.kind())
+        && node
+            .named_children(&mut cursor)
+            .any(|child| is_definition(lang, child, source))
+}
+
+/// Signature plus every member that isn't itself a reported definition
+/// (fields, class attributes, associated consts).
+fn container_body(lang: Lang, members: Node<'_>, source: &str, signature: &str) -> String {
+    let mut parts = vec![signature.to_string()];
+    let mut cursor = members.walk();
+    for member in members.named_children(&mut cursor) {
+        if !is_definition(lang, member, source) {
+            parts.push(collapse_whitespace(text(member, source)));
+        }
+    }
+    parts.join(" ")
+}
+
+fn collect_symbols(
+    lang: Lang,
+    parent: Node<'_>,
+    source: &str,
+    prefix: &str,
+    in_container: bool,
+    out: &mut Vec<Symbol>,
+) {
+    let mut cursor = parent.walk();
+    for child in parent.named_children(&mut cursor) {
+        let Some(def) = classify(lang, child, source, in_container) else {
+            if lang.is_wrapper(child.kind()) {
+                collect_symbols(lang, child, source, prefix, in_container, out);
+            }
+            continue;
+        };
+
+        let name = if prefix.is_empty() {
+            def.name
+        } else {
+            format!("{}{}{}", prefix, lang.separator(), def.name)
+        };
+        if def.reported {
+            let signature_end = def.body_start.unwrap_or(child.end_byte());
+            let signature = collapse_whitespace(&source[child.start_byte()..signature_end]);
+            // A container's own body excludes its members, which are compared
+            // individually; otherwise every method edit would also flag the class.
+            let body = match def.members {
+                Some(members) => container_body(lang, members, source, &signature),
+                None => collapse_whitespace(text(child, source)),
+            };
+            out.push(Symbol {
+                name: name.clone(),
+                kind: def.kind,
+                signature,
+                body,
+            });
+        }
+        if let Some(members) = def.members {
+            collect_symbols(lang, members, source, &name, true, out);
+        }
+    }
+}
+
+fn parse_symbols(lang: Lang, source: Option<&str>) -> Vec<Symbol> {
+    let Some(source) = source else {
+        return Vec::new();
+    };
+    let mut parser = Parser::new();
+    if parser.set_language(&lang.grammar()).is_err() {
+        return Vec::new();
+    }
+    let Some(tree) = parser.parse(source, None) else {
+        return Vec::new();
+    };
+    let mut symbols = Vec::new();
+    collect_symbols(lang, tree.root_node(), source, "", false, &mut symbols);
+    symbols
+}
+
+/// Pair base and head symbols by qualified name. Repeated names (overloads,
+/// several `impl` blocks) are paired in source order.
+fn diff_symbols(base: Vec<Symbol>, head: Vec<Symbol>) -> Vec<SymbolChange> {
+    let mut base_by_name: HashMap<String, Vec<Symbol>> = HashMap::new();
+    let mut base_order = Vec::new();
+    for symbol in base {
+        if !base_by_name.contains_key(&symbol.name) {
+            base_order.push(symbol.name.clone());
+        }
+        base_by_name
+            .entry(symbol.name.clone())
+            .or_default()
+            .push(symbol);
+    }
+    for matches in base_by_name.values_mut() {
+        matches.reverse();
+    }
+
+    let mut changes = Vec::new();
+    for new in head {
+        let old = base_by_name.get_mut(&new.name).and_then(|m| m.pop());
+        let change = match &old {
+            None => SymbolChangeKind::Added,
+            Some(old) if old.signature != new.signature => SymbolChangeKind::SignatureChanged,
+            Some(old) if old.body != new.body => SymbolChangeKind::Modified,
+            Some(_) => continue,
+        };
+        changes.push(SymbolChange {
+            name: new.name,
+            kind: new.kind,
+            change,
+            old_signature: old.map(|o| o.signature),
+            new_signature: Some(new.signature),
+        });
+    }
+
+    for name in base_order {
+        let Some(remaining) = base_by_name.remove(&name) else {
+            continue;
+        };
+        for old in remaining.into_iter().rev() {
+            changes.push(SymbolChange {
+                name: old.name,
+                kind: old.kind,
+                change: SymbolChangeKind::Removed,
+                old_signature: Some(old.signature),
+                new_signature: None,
+            });
+        }
+    }
+    changes
+}
+
+fn describe(changes: &[SymbolChange]) -> String {
+    let verbs = [
+        (
+            "modifies",
+            &[
+                SymbolChangeKind::SignatureChanged,
+                SymbolChangeKind::Modified,
+            ][..],
+        ),
+        ("adds", &[SymbolChangeKind::Added][..]),
+        ("removes", &[SymbolChangeKind::Removed][..]),
+    ];
+    let mut parts = Vec::new();
+    for (verb, kinds) in verbs {
+        let names: Vec<String> = changes
+            .iter()
+            .filter(|c| kinds.contains(&c.change))
+            .map(|c| format!("`{}`", c.name))
+            .collect();
+        if names.is_empty() {
+            continue;
+        }
+        let mut part = format!(
+            "{} {}",
+            verb,
+            names
+                .iter()
+                .take(SUMMARY_NAMES_PER_VERB)
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        if names.len() > SUMMARY_NAMES_PER_VERB {
+            part.push_str(&format!(" +{} more", names.len() - SUMMARY_NAMES_PER_VERB));
+        }
+        parts.push(part);
+    }
+    parts.join(", ")
+}
+
+/// Summarise the symbol-level changes between two versions of a file.
+///
+/// `None` for a side means the file doesn't exist there (added or deleted
+/// file), so every symbol on the other side is added or removed.
+pub fn summarize(file_path: &str, base: Option<&str>, head: Option<&str>) -> SemanticSummary {
+    let Some(lang) = Lang::from_path(file_path) else {
+        return SemanticSummary {
+            language: None,
+            changes: Vec::new(),
+            summary: String::new(),
+        };
+    };
+
+    let changes = diff_symbols(parse_symbols(lang, base), parse_symbols(lang, head));
+    SemanticSummary {
+        language: Some(lang.name().to_string()),
+        summary: describe(&changes),
+        changes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changes_of(summary: &SemanticSummary) -> Vec<(&str, SymbolChangeKind)> {
+        summary
+            .changes
+            .iter()
+            .map(|c| (c.name.as_str(), c.change))
+            .collect()
+    }
+
+    #[test]
+    fn test_rust_impl_methods_and_signatures() {
+        let base = r#"
+struct SyncEngine { n: u32 }
+impl SyncEngine {
+    pub fn run_sync(&self) { self.fetch(); }
+    fn fetch(&self) {}
+    fn legacy(&self) {}
+}
+fn helper(a: u32) -> u32 { a }
+"#;
+        let head = r#"
+struct SyncEngine { n: u32 }
+impl SyncEngine {
+    pub fn run_sync(&self) { self.fetch(); self.flush_actions(); }
+    fn fetch(&self) {}
+    fn flush_actions(&self) {}
+}
+fn helper(a: u64) -> u64 { a }
+"#;
+        let summary = summarize("src/sync.rs", Some(base), Some(head));
+        assert_eq!(summary.language.as_deref(), Some("rust"));
+        assert_eq!(
+            changes_of(&summary),
+            vec![
+                ("SyncEngine::run_sync", SymbolChangeKind::Modified),
+                ("SyncEngine::flush_actions", SymbolChangeKind::Added),
+                ("helper", SymbolChangeKind::SignatureChanged),
+                ("SyncEngine::legacy", SymbolChangeKind::Removed),
+            ]
+        );
+        assert_eq!(summary.changes[1].kind, SymbolKind::Method);
+        assert_eq!(
+            summary.summary,
+            "modifies `SyncEngine::run_sync`, `helper`, adds `SyncEngine::flush_actions`, removes `SyncEngine::legacy`"
+        );
+    }
+
+    #[test]
+    fn test_reformat_is_not_a_change() {
+        let base = "fn a() { let x = 1; }\n";
+        let head = "fn a() {\n    let x = 1;\n}\n";
+        let summary = summarize("lib.rs", Some(base), Some(head));
+        assert!(summary.changes.is_empty());
+        assert_eq!(summary.summary, "");
+    }
+
+    #[test]
+    fn test_typescript_classes_and_arrow_functions() {
+        let base = "export class Widget {\n  render() { return 1; }\n}\n";
+        let head = "export class Widget {\n  render() { return 2; }\n}\nexport const useThing = (id: number) => id;\n";
+        let summary = summarize("src/widget.tsx", Some(base), Some(head));
+        assert_eq!(
+            changes_of(&summary),
+            vec![
+                ("Widget.render", SymbolChangeKind::Modified),
+                ("useThing", SymbolChangeKind::Added),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_python_new_file() {
+        let head = "class Repo:\n    def load(self):\n        pass\n\ndef main():\n    pass\n";
+        let summary = summarize("tool.py", None, Some(head));
+        assert_eq!(
+            changes_of(&summary),
+            vec![
+                ("Repo", SymbolChangeKind::Added),
+                ("Repo.load", SymbolChangeKind::Added),
+                ("main", SymbolChangeKind::Added),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_go_method_receiver() {
+        let base = "package x\nfunc (s *Server) Start() {}\n";
+        let head = "package x\nfunc (s *Server) Start(port int) {}\n";
+        let summary = summarize("server.go", Some(base), Some(head));
+        assert_eq!(
+            changes_of(&summary),
+            vec![("Server.Start", SymbolChangeKind::SignatureChanged)]
+        );
+    }
+
+    #[test]
+    fn test_unsupported_language() {
+        let summary = summarize("README.md", Some("a"), Some("b"));
+        assert_eq!(summary.language, None);
+        assert!(summary.changes.is_empty());
+    }
+}
@@ -0,0 +1,204 @@
+//! Side-by-side alignment of two whole file versions.
+//!
+//! The lines of both versions are diffed, then laid out as rows: unchanged
+//! lines sit next to each other, a run of removed lines sits next to the
+//! added lines that follow it, and where one side of a change is longer the
+//! other side gets placeholder rows. Paired lines carry their word-level
+//! changes, and both sides are syntax highlighted from the whole file.
+
+use super::highlighter::{HighlightSpan, SyntaxHighlighter};
+use super::myers;
+use super::semantic_diff::Lang;
+use super::word_diff::{self, ChangeRange};
+use serde::Serialize;
+
+/// Line edits beyond which the versions are shown as entirely replaced
+/// rather than diffed.
+const MAX_EDITS: usize = 20_000;
+
+/// What a row shows on one side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SideLineKind {
+    Context,
+    Removed,
+    Added,
+    /// Filler opposite a line that only exists on the other side.
+    Placeholder,
+}
+
+/// One side of a row.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SideLine {
+    pub kind: SideLineKind,
+    /// 1-based line number in this side's file; `None` for placeholders.
+    pub number: Option<i64>,
+    pub content: String,
+    pub highlights: Vec<HighlightSpan>,
+    /// Byte ranges that differ from the line on the other side of the row.
+    pub word_changes: Vec<ChangeRange>,
+}
+
+impl SideLine {
+    fn placeholder() -> Self {
+        SideLine {
+            kind: SideLineKind::Placeholder,
+            number: None,
+            content: String::new(),
+            highlights: Vec::new(),
+            word_changes: Vec::new(),
+        }
+    }
+}
+
+/// Rows of a side-by-side diff; `left[i]` and `right[i]` form row `i`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SideBySideDiff {
+    pub left: Vec<SideLine>,
+    pub right: Vec<SideLine>,
+}
+
+/// Align `base` (left) and `head` (right), highlighting both as `lang`.
+pub fn align(base: &str, head: &str, lang: Option<Lang>) -> SideBySideDiff {
+    let old: Vec<&str> = base.lines().collect();
+    let new: Vec<&str> = head.lines().collect();
+    let (removed, added) = myers::diff(&old, &new, MAX_EDITS)
+        .unwrap_or_else(|| (vec![true; old.len()], vec![true; new.len()]));
+
+    let (old_spans, new_spans) = match lang {
+        Some(lang) => {
+            let mut highlighter = SyntaxHighlighter::new();
+            (
+                highlighter.highlight(lang, base).unwrap_or_default(),
+                highlighter.highlight(lang, head).unwrap_or_default(),
+            )
+        }
+        None => (Vec::new(), Vec::new()),
+    };
+    let line = |lines: &[&str], spans: &[Vec<HighlightSpan>], i: usize, kind| SideLine {
+        kind,
+        number: Some(i as i64 + 1),
+        content: lines[i].to_string(),
+        highlights: spans.get(i).cloned().unwrap_or_default(),
+        word_changes: Vec::new(),
+    };
+
+    let mut diff = SideBySideDiff::default();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        let removed_start = i;
+        while i < old.len() && removed[i] {
+            i += 1;
+        }
+        let added_start = j;
+        while j < new.len() && added[j] {
+            j += 1;
+        }
+
+        let rows = (i - removed_start).max(j - added_start);
+        for row in 0..rows {
+            let (o, n) = (removed_start + row, added_start + row);
+            let mut left = if o < i {
+                line(&old, &old_spans, o, SideLineKind::Removed)
+            } else {
+                SideLine::placeholder()
+            };
+            let mut right = if n < j {
+                line(&new, &new_spans, n, SideLineKind::Added)
+            } else {
+                SideLine::placeholder()
+            };
+            if o < i && n < j {
+                if let Some(changes) = word_diff::diff_words(&left.content, &right.content) {
+                    left.word_changes = changes.old;
+                    right.word_changes = changes.new;
+                }
+            }
+            diff.left.push(left);
+            diff.right.push(right);
+        }
+
+        if i < old.len() && j < new.len() {
+            diff.left
+                .push(line(&old, &old_spans, i, SideLineKind::Context));
+            diff.right
+                .push(line(&new, &new_spans, j, SideLineKind::Context));
+            i += 1;
+            j += 1;
+        }
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(diff: &SideBySideDiff) -> Vec<(Option<i64>, &str, Option<i64>, &str)> {
+        diff.left
+            .iter()
+            .zip(&diff.right)
+            .map(|(l, r)| (l.number, l.content.as_str(), r.number, r.content.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_align_pairs_changes_and_fills_placeholders() {
+        let base = "a\nb\nc\nd\n";
+        let head = "a\nB\nx\nc\n";
+        let diff = align(base, head, None);
+        assert_eq!(
+            rows(&diff),
+            vec![
+                (Some(1), "a", Some(1), "a"),
+                (Some(2), "b", Some(2), "B"),
+                (None, "", Some(3), "x"),
+                (Some(3), "c", Some(4), "c"),
+                (Some(4), "d", None, ""),
+            ]
+        );
+        let kinds: Vec<(SideLineKind, SideLineKind)> = diff
+            .left
+            .iter()
+            .zip(&diff.right)
+            .map(|(l, r)| (l.kind, r.kind))
+            .collect();
+        use SideLineKind::*;
+        assert_eq!(
+            kinds,
+            vec![
+                (Context, Context),
+                (Removed, Added),
+                (Placeholder, Added),
+                (Context, Context),
+                (Removed, Placeholder),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_align_marks_words_and_highlights() {
+        let base = "let total = 1;\n";
+        let head = "let total = 2;\n";
+        let diff = align(base, head, Some(Lang::Rust));
+        let changed = &diff.right[0].word_changes;
+        assert_eq!(&head[changed[0].start..changed[0].end], "2");
+        assert!(diff.left[0]
+            .highlights
+            .iter()
+            .any(|s| s.token_type == "keyword"));
+    }
+
+    #[test]
+    fn test_align_added_file() {
+        let diff = align("", "x\ny\n", None);
+        assert_eq!(diff.left.len(), 2);
+        assert!(diff
+            .left
+            .iter()
+            .all(|l| l.kind == SideLineKind::Placeholder));
+        assert!(diff.right.iter().all(|l| l.kind == SideLineKind::Added));
+    }
+}
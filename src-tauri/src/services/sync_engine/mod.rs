@@ -3,35 +3,77 @@
 //! This module provides the core sync functionality:
 //! - Scheduled background sync at configurable intervals
 //! - MR fetching with scope filters (author/reviewer)
-//! - Diff and comment fetching during sync
+//! - Diff and comment fetching during sync, split into per-MR stages (`stages`)
 //! - Sync queue processing (pushing local actions to GitLab)
 //! - Sync logging for status display
 //! - MR purge on merge/close per FR-005a
 
+mod stages;
+
+use self::stages::{MrSync, SyncStage};
+use crate::core::badge::{self, BadgeSources};
+use crate::core::change_log::{self, ChangeEntity, ChangeOp};
+use crate::core::comment_anchors;
+use crate::core::discussion_access;
+use crate::core::file_blame::BLAME_TTL_SECS;
+use crate::core::manual_jobs::{self, MANUAL_JOBS_PER_PROJECT};
+use crate::core::markdown::MARKDOWN_TTL_SECS;
+use crate::core::merge_when_ready::{self, MergeConditions};
+use crate::core::mr_actions;
+use crate::core::mr_labels;
+use crate::core::mr_milestones;
+use crate::core::mr_tasks;
+use crate::core::pipeline_notify::{self, NotifyRule, SeenPipeline};
+use crate::core::pipelines;
+use crate::core::rereview::{self, ReviewerState};
+use crate::core::retention;
+use crate::core::review_sla::{self, ReviewSlaConfig, SlaState};
+use crate::core::suggestions;
+use crate::core::token_scopes;
+use crate::core::vacation::{self, VacationConfig};
 use crate::db::auto_merge;
 use crate::db::auto_run;
+use crate::db::file_blame;
+use crate::db::job_trace_cache;
+use crate::db::markdown_cache;
+use crate::db::mr_commits;
+use crate::db::muted_projects;
 use crate::db::pool::DbPool;
+use crate::db::sync_configs::{self, InstanceSyncConfig};
+use crate::db::sync_cursors::{self, SyncCursor};
 use crate::error::AppError;
+use crate::models::merge_request::{
+    description_preview, is_bot_author, DEFAULT_BOT_AUTHOR_PATTERNS,
+    DEFAULT_DESCRIPTION_PREVIEW_CHARS,
+};
+use crate::models::pipeline_project;
 use crate::models::project::{self, Project};
-use crate::models::sync_action::ActionType;
+use crate::models::sync_action::{ActionType, SyncAction};
+use crate::services::background_jobs;
 use crate::services::gitlab_client::{
     BatchedMrState, GitLabClient, GitLabClientConfig, GitLabDiffVersion, GitLabDiscussion,
-    GitLabMergeRequest, MergeRequestsQuery,
+    GitLabJob, GitLabMergeRequest, GitLabMrReviewer, MergeRequestsQuery,
 };
+use crate::services::memory_profile::{self, Subsystem};
+use crate::services::offline_mode;
+use crate::services::quick_switch;
+use crate::services::reconciliation::{self, DriftSummary};
 use crate::services::sync_events::{
     ActionSyncedPayload, AuthExpiredPayload, AutoMergeUpdatedPayload, AutoRunNotificationPayload,
-    AutoRunUpdatedPayload, EventEmitter, IssuesUpdatedPayload, MrReadyPayload, MrUpdateType,
-    MrUpdatedPayload, PipelineStatusChangedPayload, SyncPhase, SyncProgressPayload,
+    AutoRunUpdatedPayload, EventEmitter, IssuesUpdatedPayload, ManualGatePayload,
+    MergedWhenReadyPayload, MrReadyPayload, MrUpdateType, MrUpdatedPayload,
+    PipelineStatusChangedPayload, ReviewSlaPayload, SyncPhase, SyncProgressPayload,
     ACTION_SYNCED_EVENT, AUTH_EXPIRED_EVENT, AUTO_MERGE_UPDATED_EVENT, AUTO_RUN_NOTIFICATION_EVENT,
-    AUTO_RUN_UPDATED_EVENT, ISSUES_UPDATED_EVENT, MR_READY_EVENT, MR_UPDATED_EVENT,
-    PIPELINE_STATUS_CHANGED_EVENT, SYNC_PROGRESS_EVENT,
+    AUTO_RUN_UPDATED_EVENT, DB_CHANGES_EVENT, ISSUES_UPDATED_EVENT, MANUAL_GATE_EVENT,
+    MERGED_WHEN_READY_EVENT, MR_READY_EVENT, MR_UPDATED_EVENT, PIPELINE_STATUS_CHANGED_EVENT,
+    REVIEW_SLA_EVENT, SYNC_PROGRESS_EVENT,
 };
 use crate::services::sync_processor;
-use crate::services::sync_queue;
+use crate::services::sync_queue::{self, CommentPayload, EnqueueInput, RetryPolicy};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
-use std::sync::Arc;
 use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::sync::{mpsc, RwLock};
 use tokio::time;
@@ -44,6 +86,11 @@ pub const DEFAULT_SYNC_INTERVAL_SECS: u64 = 300;
 /// every MR sync tick.
 pub const DEFAULT_ISSUE_SYNC_INTERVAL_SECS: u64 = 1800;
 
+/// Default interval for polling pinned pipeline projects (1 minute).
+/// Pipelines finish on their own schedule; waiting for the next MR sync
+/// would delay a failure notification by up to 5 minutes.
+pub const DEFAULT_PIPELINE_POLL_INTERVAL_SECS: u64 = 60;
+
 /// Maximum number of log entries to keep.
 const MAX_LOG_ENTRIES: i64 = 50;
 
@@ -56,6 +103,37 @@ const CACHE_SIZE_LIMIT_BYTES: i64 = 500 * 1024 * 1024;
 /// Maximum number of MRs to sync concurrently within an instance.
 const MAX_CONCURRENT_MRS: usize = 4;
 
+/// Authored MRs with a failed head pipeline whose job logs are prefetched per sync.
+const MAX_TRACE_PREFETCH_MRS: i64 = 10;
+
+/// How long prefetched job logs are kept.
+const JOB_TRACE_TTL_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// Shortest sync interval an instance can be configured with.
+const MIN_INSTANCE_INTERVAL_SECS: i64 = 60;
+
+/// Most MRs an instance can be configured to sync per run.
+const MAX_INSTANCE_MRS_PER_SYNC: i64 = 1000;
+
+/// Most push attempts per queued action that can be configured.
+const MAX_ACTION_ATTEMPTS: i64 = 20;
+
+/// How early a scheduled tick may catch an instance whose own interval is
+/// almost over. Ticks run at the shortest interval of all instances, so
+/// without slack an instance last synced a moment after the previous tick
+/// would wait a whole extra tick.
+const INSTANCE_INTERVAL_SLACK: Duration = Duration::from_secs(5);
+
+/// How often an instance's full open-MR list is fetched. Syncs in between
+/// only list MRs updated since the cursor, which can't tell that an MR left
+/// every scope (merged, closed, or the user approved it).
+const FULL_LIST_REFRESH_SECS: i64 = 30 * 60;
+
+/// How far before the cursor incremental list fetches start, so a clock
+/// difference between this machine and the GitLab server can't skip an
+/// update. Re-listed MRs cost nothing more: unchanged ones skip their stages.
+const CURSOR_OVERLAP_SECS: i64 = 10 * 60;
+
 /// Get the current Unix timestamp.
 fn now() -> i64 {
     SystemTime::now()
@@ -77,20 +155,225 @@ pub struct SyncConfig {
     /// slowly than MRs so this is typically a multiple of `interval_secs`.
     #[serde(default = "default_issue_interval_secs")]
     pub issue_interval_secs: u64,
+
+    /// Length (in characters) of the description preview served to MR
+    /// lists. The full description is only returned by the detail view.
+    #[serde(default = "default_description_preview_chars")]
+    pub description_preview_chars: usize,
+
+    /// Author username patterns (`*` wildcards) treated as bots in addition
+    /// to accounts GitLab itself flags as bots.
+    #[serde(default = "default_bot_author_patterns")]
+    pub bot_author_patterns: Vec<String>,
+
+    /// Automatically approve bot MRs the user is asked to review once their
+    /// head pipeline has succeeded. Off unless explicitly enabled.
+    #[serde(default)]
+    pub auto_approve_bot_mrs: bool,
+
+    /// Fall back to Gravatar (by hashed public email) for avatars the
+    /// instance doesn't serve. Off by default since it contacts a third party.
+    #[serde(default)]
+    pub gravatar_fallback: bool,
+
+    /// What counts toward the dock/taskbar badge set after each sync.
+    #[serde(default)]
+    pub badge: BadgeSources,
+
+    /// Time between deep reconciliation runs, in seconds. 0 disables the
+    /// schedule; `trigger_full_resync` still works.
+    #[serde(default = "default_reconcile_interval_secs")]
+    pub reconcile_interval_secs: u64,
+
+    /// How often pinned pipeline projects are polled for notifications, in
+    /// seconds. 0 disables the poll.
+    #[serde(default = "default_pipeline_poll_interval_secs")]
+    pub pipeline_poll_interval_secs: u64,
+
+    /// Sync stages to skip, per instance id (`approvals`, `diff`, `comments`,
+    /// `avatars`). The metadata stage always runs.
+    #[serde(default)]
+    pub disabled_stages: HashMap<i64, Vec<String>>,
+
+    /// Answer new review requests while the user is away. Off by default.
+    #[serde(default)]
+    pub vacation: VacationConfig,
+
+    /// Push attempts for a queued action before it is left failed. Failures
+    /// retrying can help with are retried automatically with backoff.
+    #[serde(default = "default_action_max_attempts")]
+    pub action_max_attempts: i64,
+
+    /// Response target for review requests, in working hours.
+    #[serde(default)]
+    pub review_sla: ReviewSlaConfig,
 }
 
 fn default_issue_interval_secs() -> u64 {
     DEFAULT_ISSUE_SYNC_INTERVAL_SECS
 }
 
+fn default_description_preview_chars() -> usize {
+    DEFAULT_DESCRIPTION_PREVIEW_CHARS
+}
+
+fn default_reconcile_interval_secs() -> u64 {
+    reconciliation::DEFAULT_RECONCILE_INTERVAL_SECS
+}
+
+fn default_pipeline_poll_interval_secs() -> u64 {
+    DEFAULT_PIPELINE_POLL_INTERVAL_SECS
+}
+
+fn default_action_max_attempts() -> i64 {
+    SyncAction::MAX_RETRIES
+}
+
+fn default_bot_author_patterns() -> Vec<String> {
+    DEFAULT_BOT_AUTHOR_PATTERNS
+        .iter()
+        .map(|p| p.to_string())
+        .collect()
+}
+
 impl Default for SyncConfig {
     fn default() -> Self {
         Self {
             interval_secs: DEFAULT_SYNC_INTERVAL_SECS,
             max_mrs_per_sync: 100,
             issue_interval_secs: DEFAULT_ISSUE_SYNC_INTERVAL_SECS,
+            description_preview_chars: DEFAULT_DESCRIPTION_PREVIEW_CHARS,
+            bot_author_patterns: default_bot_author_patterns(),
+            auto_approve_bot_mrs: false,
+            gravatar_fallback: false,
+            badge: BadgeSources::default(),
+            reconcile_interval_secs: default_reconcile_interval_secs(),
+            pipeline_poll_interval_secs: DEFAULT_PIPELINE_POLL_INTERVAL_SECS,
+            disabled_stages: HashMap::new(),
+            vacation: VacationConfig::default(),
+            action_max_attempts: default_action_max_attempts(),
+            review_sla: ReviewSlaConfig::default(),
+        }
+    }
+}
+
+impl SyncConfig {
+    /// Reject `disabled_stages` entries that don't name a stage that can be
+    /// turned off.
+    pub fn validate_disabled_stages(&self) -> Result<(), AppError> {
+        for name in self.disabled_stages.values().flatten() {
+            if !stages::toggleable_names().any(|n| n == name) {
+                return Err(AppError::invalid_input_field(
+                    format!(
+                        "Unknown sync stage '{}'. Stages that can be disabled: {}",
+                        name,
+                        stages::toggleable_names().collect::<Vec<_>>().join(", ")
+                    ),
+                    "disabled_stages",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Reject an `action_max_attempts` outside 1..=`MAX_ACTION_ATTEMPTS`.
+    pub fn validate_action_max_attempts(&self) -> Result<(), AppError> {
+        if !(1..=MAX_ACTION_ATTEMPTS).contains(&self.action_max_attempts) {
+            return Err(AppError::invalid_input_field(
+                format!(
+                    "Attempts per action must be between 1 and {}",
+                    MAX_ACTION_ATTEMPTS
+                ),
+                "action_max_attempts",
+            ));
+        }
+        Ok(())
+    }
+
+    /// How the queue retries failed actions.
+    pub fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: self.action_max_attempts,
         }
     }
+
+    /// Interval between scheduled syncs of an instance.
+    pub fn interval_for(&self, instance: &InstanceSyncConfig) -> Duration {
+        Duration::from_secs(
+            instance
+                .interval_secs
+                .map_or(self.interval_secs, |secs| secs as u64),
+        )
+    }
+
+    /// Most MRs synced per run for an instance.
+    pub fn max_mrs_for(&self, instance: &InstanceSyncConfig) -> usize {
+        instance
+            .max_mrs_per_sync
+            .map_or(self.max_mrs_per_sync, |max| max as usize)
+    }
+}
+
+/// Reject an instance sync config that would sync nothing or whose limits
+/// are out of range.
+pub fn validate_instance_config(config: &InstanceSyncConfig) -> Result<(), AppError> {
+    if !(config.sync_authored || config.sync_reviewing || config.sync_assigned) {
+        return Err(AppError::invalid_input(
+            "Pick at least one kind of MR to sync",
+        ));
+    }
+    if matches!(config.interval_secs, Some(secs) if secs < MIN_INSTANCE_INTERVAL_SECS) {
+        return Err(AppError::invalid_input_field(
+            format!(
+                "Sync interval must be at least {} seconds",
+                MIN_INSTANCE_INTERVAL_SECS
+            ),
+            "intervalSecs",
+        ));
+    }
+    if matches!(config.max_mrs_per_sync, Some(max) if !(1..=MAX_INSTANCE_MRS_PER_SYNC).contains(&max))
+    {
+        return Err(AppError::invalid_input_field(
+            format!(
+                "Max MRs per sync must be between 1 and {}",
+                MAX_INSTANCE_MRS_PER_SYNC
+            ),
+            "maxMrsPerSync",
+        ));
+    }
+    Ok(())
+}
+
+/// The `updated_after` bound for an instance's MR list fetch, or `None` when
+/// the full list is due.
+fn incremental_since(cursor: Option<SyncCursor>, now: i64, force: bool) -> Option<i64> {
+    let cursor = cursor.filter(|_| !force)?;
+    (now - cursor.last_full_sync_at < FULL_LIST_REFRESH_SECS)
+        .then(|| cursor.last_synced_at - CURSOR_OVERLAP_SECS)
+}
+
+/// Whether the batched approval/pipeline state of a cached MR differs from
+/// what was stored at its last sync. Neither change bumps the MR's
+/// `updated_at`, so an incremental list fetch doesn't return such MRs.
+fn batched_state_changed(
+    approvals_count: Option<i64>,
+    approvals_required: Option<i64>,
+    pipeline_status: Option<&str>,
+    state: &BatchedMrState,
+) -> bool {
+    let approvals = &state.approvals;
+    approvals_count != Some(approvals.approvals_required - approvals.approvals_left)
+        || approvals_required != Some(approvals.approvals_required)
+        || state
+            .head_pipeline_status
+            .as_deref()
+            .is_some_and(|status| pipeline_status != Some(status))
+}
+
+/// Whether a scheduled tick should sync an instance last synced `since` ago
+/// (`None` when it hasn't synced this session).
+fn instance_sync_due(since: Option<Duration>, interval: Duration) -> bool {
+    since.is_none_or(|since| since + INSTANCE_INTERVAL_SLACK >= interval)
 }
 
 /// Status of the sync engine.
@@ -171,6 +454,9 @@ pub enum SyncCommand {
     /// "MR unchanged" optimisation so diffs/files are always re-fetched.
     TriggerSync { force: bool },
 
+    /// Run a deep reconciliation now (forced sync plus drift checks).
+    Reconcile,
+
     /// Flush pending actions of the given types immediately.
     FlushActions(Vec<ActionType>),
 
@@ -183,6 +469,9 @@ pub enum SyncCommand {
     /// Update the sync configuration.
     UpdateConfig(SyncConfig),
 
+    /// Recount the dock/taskbar badge, e.g. after a project was muted.
+    RefreshBadge,
+
     /// Stop the sync engine.
     Stop,
 }
@@ -201,6 +490,16 @@ pub struct SyncHandle {
 }
 
 impl SyncHandle {
+    /// A handle with no engine behind it, for a read-only secondary process.
+    /// Every command fails with "Sync engine not running".
+    pub fn detached(config: SyncConfig) -> Self {
+        let (command_tx, _) = mpsc::channel(1);
+        Self {
+            command_tx,
+            config: Arc::new(RwLock::new(config)),
+        }
+    }
+
     /// Trigger an immediate sync.
     /// When `force` is true the "MR unchanged" optimisation is bypassed
     /// so diffs and files are always re-fetched.
@@ -216,6 +515,15 @@ impl SyncHandle {
         self.trigger_sync_force(false).await
     }
 
+    /// Trigger a deep reconciliation: a forced sync that also re-checks
+    /// MRs the sync didn't return and removes orphaned cache rows.
+    pub async fn trigger_full_resync(&self) -> Result<(), AppError> {
+        self.command_tx
+            .send(SyncCommand::Reconcile)
+            .await
+            .map_err(|_| AppError::internal("Sync engine not running"))
+    }
+
     /// Flush pending actions of the given types immediately.
     pub async fn flush_actions(&self, types: Vec<ActionType>) -> Result<(), AppError> {
         self.command_tx
@@ -247,7 +555,8 @@ impl SyncHandle {
             .map_err(|_| AppError::internal("Sync engine not running"))
     }
 
-    /// Flush pending comment-related actions (comment, reply, resolve, unresolve) immediately.
+    /// Flush pending comment-related actions (comment, reply, resolve, unresolve),
+    /// task toggles and label changes immediately.
     pub async fn flush_comments(&self) -> Result<(), AppError> {
         self.flush_actions(vec![
             ActionType::Comment,
@@ -255,10 +564,23 @@ impl SyncHandle {
             ActionType::Resolve,
             ActionType::Unresolve,
             ActionType::DeleteComment,
+            ActionType::ToggleTask,
+            ActionType::UpdateLabels,
+            ActionType::SetReviewers,
+            ActionType::SetAssignees,
+            ActionType::SetMilestone,
         ])
         .await
     }
 
+    /// Recount the dock/taskbar badge without syncing.
+    pub async fn refresh_badge(&self) -> Result<(), AppError> {
+        self.command_tx
+            .send(SyncCommand::RefreshBadge)
+            .await
+            .map_err(|_| AppError::internal("Sync engine not running"))
+    }
+
     /// Update the sync configuration.
     pub async fn update_config(&self, config: SyncConfig) -> Result<(), AppError> {
         self.command_tx
@@ -273,6 +595,9 @@ impl SyncHandle {
     }
 }
 
+/// Manual job ids waiting per (instance_id, project_id).
+type SeenManualGates = HashMap<(i64, i64), HashSet<i64>>;
+
 /// Background sync engine.
 ///
 /// Manages periodic synchronization with GitLab, including:
@@ -297,14 +622,27 @@ pub struct SyncEngine {
     /// MR IDs already notified as ready-to-merge this session (avoids duplicate notifications).
     notified_mr_ready: Arc<RwLock<HashSet<i64>>>,
 
-    /// Previous pipeline statuses for pinned projects, keyed by (instance_id, project_id).
-    /// Used to detect status transitions and emit notifications.
-    previous_pipeline_statuses: Arc<RwLock<HashMap<(i64, i64), String>>>,
+    /// Review SLA state of each MR awaiting my review at the last check,
+    /// keyed by local MR id. Used to notify once per threshold crossed.
+    review_sla_states: Arc<RwLock<HashMap<i64, SlaState>>>,
+
+    /// Latest pipeline seen for each pinned project, keyed by (instance_id, project_id).
+    /// Used to notify once per finished pipeline.
+    previous_pipeline_statuses: Arc<RwLock<HashMap<(i64, i64), SeenPipeline>>>,
+
+    /// Manual jobs waiting on the default branch of each pinned project with
+    /// manual-gate notifications on at the last poll, keyed by
+    /// (instance_id, project_id). Used to notify once per new gate.
+    seen_manual_gates: Arc<RwLock<SeenManualGates>>,
 
     /// Last time the issue cache was refreshed, keyed by instance id.
     /// Empty key means "never synced this session" so the next run will fetch.
     last_issue_sync: Arc<RwLock<HashMap<i64, Instant>>>,
 
+    /// Last time each instance's MRs were synced, keyed by instance id. Lets
+    /// scheduled runs honour per-instance intervals.
+    last_instance_sync: Arc<RwLock<HashMap<i64, Instant>>>,
+
     /// Authenticated user per instance, keyed by instance id and the token it
     /// was validated with. Avoids a validate_token API call on every sync tick;
     /// invalidated automatically when the stored token changes (re-auth).
@@ -329,8 +667,11 @@ impl SyncEngine {
             status: Arc::new(RwLock::new(SyncStatus::default())),
             emitter,
             notified_mr_ready: Arc::new(RwLock::new(HashSet::new())),
+            review_sla_states: Arc::new(RwLock::new(HashMap::new())),
             previous_pipeline_statuses: Arc::new(RwLock::new(HashMap::new())),
+            seen_manual_gates: Arc::new(RwLock::new(HashMap::new())),
             last_issue_sync: Arc::new(RwLock::new(HashMap::new())),
+            last_instance_sync: Arc::new(RwLock::new(HashMap::new())),
             cached_instance_users: Arc::new(RwLock::new(HashMap::new())),
         }
     }
@@ -342,10 +683,49 @@ impl SyncEngine {
 
     /// Emit an event with a serializable payload. Errors are silently logged.
     fn emit_event<T: Serialize>(&self, event: &str, payload: &T) {
-        self.emitter.emit_json(
-            event,
-            serde_json::to_value(payload).unwrap_or_default(),
-        );
+        self.emitter
+            .emit_json(event, serde_json::to_value(payload).unwrap_or_default());
+    }
+
+    /// Append a change to the change log and emit it to the frontend.
+    /// Failures are only logged: a missed change is picked up by the next
+    /// list refetch.
+    async fn record_change(
+        &self,
+        entity: ChangeEntity,
+        entity_id: i64,
+        instance_id: i64,
+        op: ChangeOp,
+        fields: serde_json::Map<String, serde_json::Value>,
+    ) {
+        match change_log::record(
+            &self.pool,
+            entity,
+            entity_id,
+            instance_id,
+            op,
+            fields,
+            now(),
+        )
+        .await
+        {
+            Ok(event) => self.emit_event(DB_CHANGES_EVENT, &event),
+            Err(e) => log::warn!(
+                "Failed to record change for {:?} {}: {}",
+                entity,
+                entity_id,
+                e
+            ),
+        }
+    }
+
+    /// Recount the dock/taskbar badge from the cache and apply it.
+    async fn update_badge(&self) {
+        let sources = self.config.read().await.badge;
+        match badge::badge_count(&self.pool, sources, now()).await {
+            Ok(count) => self.emitter.set_badge_count(count),
+            Err(e) => log::warn!("Failed to compute badge count: {}", e),
+        }
     }
 
     /// Emit a sync-progress event to the frontend.
@@ -399,8 +779,11 @@ impl SyncEngine {
                 status: Arc::new(RwLock::new(SyncStatus::default())),
                 emitter,
                 notified_mr_ready: Arc::new(RwLock::new(HashSet::new())),
+                review_sla_states: Arc::new(RwLock::new(HashMap::new())),
                 previous_pipeline_statuses: Arc::new(RwLock::new(HashMap::new())),
+                seen_manual_gates: Arc::new(RwLock::new(HashMap::new())),
                 last_issue_sync: Arc::new(RwLock::new(HashMap::new())),
+                last_instance_sync: Arc::new(RwLock::new(HashMap::new())),
                 cached_instance_users: Arc::new(RwLock::new(HashMap::new())),
             };
 
@@ -408,6 +791,15 @@ impl SyncEngine {
             if let Err(e) = sync_queue::recover_stale_syncing_actions(&engine.pool).await {
                 eprintln!("[sync] Failed to recover stale syncing actions: {}", e);
             }
+            // Re-home or drop actions whose MR row disappeared
+            match sync_queue::resolve_orphaned_actions(&engine.pool).await {
+                Ok((0, 0)) => {}
+                Ok((rehomed, dropped)) => eprintln!(
+                    "[sync] Orphaned actions: {} re-homed, {} dropped",
+                    rehomed, dropped
+                ),
+                Err(e) => eprintln!("[sync] Failed to resolve orphaned actions: {}", e),
+            }
 
             // Run initial sync immediately
             eprintln!("[sync] Running initial background sync...");
@@ -427,7 +819,7 @@ impl SyncEngine {
             }
 
             let interval_secs = { engine.config.read().await.interval_secs };
-            let mut interval = time::interval(Duration::from_secs(interval_secs));
+            let mut interval = time::interval(engine.tick_interval(interval_secs).await);
             // Consume the first (immediate) tick since we just ran sync
             interval.tick().await;
 
@@ -437,12 +829,34 @@ impl SyncEngine {
             let mut auto_run_interval = time::interval(Duration::from_secs(30));
             auto_run_interval.tick().await;
 
+            // Pinned pipeline projects are polled on their own timer. A zero
+            // interval disables the poll; tokio's interval needs a non-zero
+            // period, so the timer keeps ticking and the tick is ignored.
+            let pipeline_poll_secs = { engine.config.read().await.pipeline_poll_interval_secs };
+            let mut pipeline_interval =
+                time::interval(Duration::from_secs(pipeline_poll_secs.max(1)));
+
             loop {
                 tokio::select! {
                     _ = interval.tick() => {
-                        eprintln!("[sync] Running periodic background sync...");
-                        if let Err(e) = engine.run_sync().await {
-                            eprintln!("[sync] Periodic sync error: {}", e);
+                        if engine.reconciliation_due().await {
+                            eprintln!("[sync] Running scheduled reconciliation...");
+                            if let Err(e) = engine.run_reconciliation().await {
+                                eprintln!("[sync] Reconciliation error: {}", e);
+                            }
+                        } else {
+                            eprintln!("[sync] Running periodic background sync...");
+                            if let Err(e) = engine.run_scheduled_sync().await {
+                                eprintln!("[sync] Periodic sync error: {}", e);
+                            }
+                        }
+                    }
+                    _ = pipeline_interval.tick() => {
+                        if engine.config.read().await.pipeline_poll_interval_secs > 0
+                            && !offline_mode::is_offline()
+                        {
+                            engine.check_pinned_pipeline_statuses().await;
+                            engine.check_manual_gates().await;
                         }
                     }
                     _ = auto_run_interval.tick() => {
@@ -458,6 +872,12 @@ impl SyncEngine {
                                     eprintln!("[sync] Manual sync error: {}", e);
                                 }
                             }
+                            SyncCommand::Reconcile => {
+                                eprintln!("[sync] Full reconciliation triggered");
+                                if let Err(e) = engine.run_reconciliation().await {
+                                    eprintln!("[sync] Reconciliation error: {}", e);
+                                }
+                            }
                             SyncCommand::FlushActions(action_types) => {
                                 eprintln!("[sync] Flushing {:?} actions immediately", action_types);
                                 if let Err(e) = engine.flush_actions_by_types(&action_types).await {
@@ -474,8 +894,21 @@ impl SyncEngine {
                             }
                         SyncCommand::UpdateConfig(new_config) => {
                                 eprintln!("[sync] Config updated, interval={}s", new_config.interval_secs);
-                                interval = time::interval(Duration::from_secs(new_config.interval_secs));
+                                interval =
+                                    time::interval(engine.tick_interval(new_config.interval_secs).await);
+                                if new_config.pipeline_poll_interval_secs
+                                    != engine.config.read().await.pipeline_poll_interval_secs
+                                {
+                                    pipeline_interval = time::interval(Duration::from_secs(
+                                        new_config.pipeline_poll_interval_secs.max(1),
+                                    ));
+                                }
                                 *engine.config.write().await = new_config;
+                                // The badge sources may have changed.
+                                engine.update_badge().await;
+                            }
+                            SyncCommand::RefreshBadge => {
+                                engine.update_badge().await;
                             }
                             SyncCommand::Stop => {
                                 eprintln!("[sync] Sync engine stopping");
@@ -506,6 +939,43 @@ impl SyncEngine {
     }
 
     pub async fn run_sync_with_force(&self, force: bool) -> Result<SyncResult, AppError> {
+        self.run_sync_inner(force, false).await
+    }
+
+    /// Run a sync from the periodic timer: instances whose own interval
+    /// hasn't passed yet are skipped.
+    async fn run_scheduled_sync(&self) -> Result<SyncResult, AppError> {
+        self.run_sync_inner(false, true).await
+    }
+
+    /// Period of the background timer: the global interval, or a shorter
+    /// interval some instance is configured with.
+    async fn tick_interval(&self, global_secs: u64) -> Duration {
+        let shortest = sync_configs::min_interval_secs(&self.pool)
+            .await
+            .unwrap_or_else(|e| {
+                log::warn!("Failed to load per-instance sync intervals: {}", e);
+                None
+            });
+        Duration::from_secs(shortest.map_or(global_secs, |secs| global_secs.min(secs as u64)))
+    }
+
+    async fn run_sync_inner(&self, force: bool, scheduled: bool) -> Result<SyncResult, AppError> {
+        if offline_mode::is_offline() {
+            log::info!("[sync] Skipping sync: offline mode is on");
+            return Ok(SyncResult {
+                mr_count: 0,
+                purged_count: 0,
+                actions_pushed: 0,
+                errors: Vec::new(),
+                duration_ms: 0,
+                api_calls: 0,
+            });
+        }
+
+        // Idle-time jobs would compete for the database; hold them off.
+        let _paused = background_jobs::pause_for_sync();
+
         let start = Instant::now();
         let sync_run_id = uuid::Uuid::new_v4().to_string();
 
@@ -545,9 +1015,14 @@ impl SyncEngine {
         // The sync-queue action push deliberately happens once afterwards, not
         // per instance (see below).
         let run_id: &str = &sync_run_id;
-        let instance_results = futures::future::join_all(instances.iter().map(
-            |instance| async move { (instance, self.sync_instance(instance, run_id, force).await) },
-        ))
+        let instance_results = futures::future::join_all(instances.iter().map(|instance| {
+            memory_profile::track(Subsystem::SyncEngine, async move {
+                (
+                    instance,
+                    self.sync_instance(instance, run_id, force, scheduled).await,
+                )
+            })
+        }))
         .await;
 
         for (instance, instance_result) in instance_results {
@@ -590,7 +1065,8 @@ impl SyncEngine {
         // ALL pending actions with its own client (wrong instance for some),
         // and with instances now syncing concurrently that would also race.
         self.emit_progress(SyncPhase::PushingActions, "Processing sync queue");
-        match sync_queue::get_pending_actions(&self.pool).await {
+        // Actions backing off after a failure wait for their next_retry_at.
+        match sync_queue::get_due_actions(&self.pool, now()).await {
             Ok(pending) if !pending.is_empty() => {
                 let (pushed, push_errors, push_api_calls) =
                     self.process_actions_resolving_instances(&pending).await;
@@ -606,9 +1082,6 @@ impl SyncEngine {
             }
         }
 
-        // Check pinned pipeline statuses and emit notifications on transitions
-        self.check_pinned_pipeline_statuses().await;
-
         // Process any auto-merge claims the user has set on their own MRs.
         // Runs after MR sync so we have the freshest data to check against.
         self.process_auto_merge_claims().await;
@@ -620,6 +1093,23 @@ impl SyncEngine {
         if let Err(e) = sync_queue::cleanup_synced(&self.pool).await {
             log::warn!("Failed to cleanup synced actions: {}", e);
         }
+        if let Err(e) = change_log::prune(&self.pool).await {
+            log::warn!("Failed to prune change log: {}", e);
+        }
+        if let Err(e) = muted_projects::prune_expired(&self.pool, now()).await {
+            log::warn!("Failed to prune expired project mutes: {}", e);
+        }
+        if let Err(e) = file_blame::prune_blame(&self.pool, now() - BLAME_TTL_SECS).await {
+            log::warn!("Failed to prune file blame cache: {}", e);
+        }
+        if let Err(e) = markdown_cache::prune_html(&self.pool, now() - MARKDOWN_TTL_SECS).await {
+            log::warn!("Failed to prune rendered markdown cache: {}", e);
+        }
+        if let Err(e) = mr_commits::prune_commit_diffs(&self.pool).await {
+            log::warn!("Failed to prune commit diff cache: {}", e);
+        }
+        // Summarise newly cached diffs once the app goes idle.
+        let _ = background_jobs::enqueue(background_jobs::SEMANTIC_SUMMARIES);
 
         // Calculate duration
         result.duration_ms = start.elapsed().as_millis() as i64;
@@ -657,6 +1147,9 @@ impl SyncEngine {
             }
         }
 
+        self.update_badge().await;
+        self.check_review_slas().await;
+
         // Emit complete or failed event
         if result.errors.is_empty() {
             self.emit_event(
@@ -703,15 +1196,18 @@ impl SyncEngine {
         .await?;
 
         // Record total sync run metric
-        if let Err(e) = self.record_metric(
-            &sync_run_id,
-            "total",
-            None,
-            None,
-            result.duration_ms,
-            total_api_calls,
-            result.mr_count as u64,
-        ).await {
+        if let Err(e) = self
+            .record_metric(
+                &sync_run_id,
+                "total",
+                None,
+                None,
+                result.duration_ms,
+                total_api_calls,
+                result.mr_count as u64,
+            )
+            .await
+        {
             log::warn!("Failed to record total sync metric: {}", e);
         }
 
@@ -730,15 +1226,225 @@ impl SyncEngine {
         Ok(result)
     }
 
-    /// Sync a single GitLab instance.
+    /// Whether the scheduled deep reconciliation is due.
+    async fn reconciliation_due(&self) -> bool {
+        let interval_secs = { self.config.read().await.reconcile_interval_secs };
+        match reconciliation::last_run_at(&self.pool).await {
+            Ok(last) => reconciliation::is_due(last, now(), interval_secs),
+            Err(e) => {
+                log::warn!("Failed to read last reconciliation time: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Run a deep reconciliation.
+    ///
+    /// 1. Snapshots the cache and runs a forced sync, so every MR in scope is
+    ///    re-fetched even when its `updated_at` is unchanged, along with the
+    ///    user's access level on each project
+    /// 2. Counts MRs whose data changed anyway (drift)
+    /// 3. Re-checks opened MRs the sync didn't return, one by one
+    /// 4. Removes cached file versions left without an MR
+    ///
+    /// The summary is written to sync_log as a `reconcile` entry.
+    pub async fn run_reconciliation(&self) -> Result<DriftSummary, AppError> {
+        if offline_mode::is_offline() {
+            log::info!("[sync] Skipping reconciliation: offline mode is on");
+            return Ok(DriftSummary::default());
+        }
+        let _paused = background_jobs::pause_for_sync();
+
+        let started_at = now();
+        let start = Instant::now();
+        let before = reconciliation::snapshot(&self.pool).await?;
+        project::forget_access_levels(&self.pool).await?;
+        let sync = self.run_sync_with_force(true).await?;
+        let after = reconciliation::snapshot(&self.pool).await?;
+
+        let drifted = reconciliation::drifted(&before, &after);
+        if !drifted.is_empty() {
+            log::info!("[sync] Reconciliation found drift in MRs {:?}", drifted);
+        }
+        let mut summary = DriftSummary {
+            checked: before.len() as i64,
+            drifted: drifted.len() as i64,
+            errors: sync.errors.len() as i64,
+            ..Default::default()
+        };
+
+        self.revalidate_stale_mrs(started_at, &mut summary).await?;
+
+        match reconciliation::remove_orphans(&self.pool).await {
+            Ok(removed) => summary.orphans_removed = removed,
+            Err(e) => {
+                log::warn!("Failed to remove orphaned file versions: {}", e);
+                summary.errors += 1;
+            }
+        }
+
+        let duration_ms = start.elapsed().as_millis() as i64;
+        reconciliation::record_run(&self.pool, started_at, duration_ms, &summary).await?;
+        self.log_sync_operation(
+            "reconcile",
+            if summary.errors == 0 {
+                "success"
+            } else {
+                "error"
+            },
+            None,
+            Some(summary.message()),
+            Some(duration_ms),
+        )
+        .await?;
+        log::info!("[sync] Reconciliation done: {}", summary.message());
+
+        Ok(summary)
+    }
+
+    /// Fetch every opened MR not refreshed since `since` on its own: MRs that
+    /// were merged or closed get their state fixed, MRs GitLab no longer
+    /// returns are deleted. An instance is given up on at its first
+    /// connection or auth error.
+    async fn revalidate_stale_mrs(
+        &self,
+        since: i64,
+        summary: &mut DriftSummary,
+    ) -> Result<(), AppError> {
+        let stale = reconciliation::stale_open_mrs(&self.pool, since).await?;
+        let mut by_instance: HashMap<i64, Vec<(i64, i64, i64)>> = HashMap::new();
+        for (id, instance_id, project_id, iid) in stale {
+            by_instance
+                .entry(instance_id)
+                .or_default()
+                .push((id, project_id, iid));
+        }
+
+        for (instance_id, mrs) in by_instance {
+            let client = match crate::core::create_client(&self.pool, instance_id).await {
+                Ok(client) => client,
+                Err(e) => {
+                    log::warn!(
+                        "[sync] Can't re-check MRs of instance {}: {}",
+                        instance_id,
+                        e
+                    );
+                    summary.errors += 1;
+                    continue;
+                }
+            };
+
+            let mut gone: Vec<(i64, i64)> = Vec::new();
+            for (mr_id, project_id, iid) in mrs {
+                match client.get_merge_request(project_id, iid).await {
+                    Ok(mr) if mr.state == "merged" || mr.state == "closed" => {
+                        let now_ts = now();
+                        sqlx::query(
+                            "UPDATE merge_requests SET state = ?, state_changed_at = ?, merged_at = COALESCE(merged_at, ?) WHERE id = ?",
+                        )
+                        .bind(&mr.state)
+                        .bind(now_ts)
+                        .bind(mr.merged_at.as_deref().map(parse_iso_timestamp))
+                        .bind(mr_id)
+                        .execute(&self.pool)
+                        .await?;
+                        let mut fields = serde_json::Map::new();
+                        fields.insert("state".to_string(), mr.state.clone().into());
+                        self.record_change(
+                            ChangeEntity::MergeRequest,
+                            mr_id,
+                            instance_id,
+                            ChangeOp::Upsert,
+                            fields,
+                        )
+                        .await;
+                        self.emit_mr_updated(mr_id, instance_id, iid, MrUpdateType::Updated);
+                        summary.state_fixed += 1;
+                    }
+                    Ok(_) => {}
+                    Err(AppError::GitLabApi {
+                        status_code: Some(404),
+                        ..
+                    }) => gone.push((mr_id, iid)),
+                    Err(e) => {
+                        log::warn!("[sync] Re-check of MR !{} failed: {}", iid, e);
+                        summary.errors += 1;
+                        if matches!(
+                            e,
+                            AppError::Network { .. }
+                                | AppError::Authentication { .. }
+                                | AppError::AuthenticationExpired { .. }
+                        ) {
+                            break;
+                        }
+                    }
+                }
+            }
+
+            let gone_ids: Vec<i64> = gone.iter().map(|(id, _)| *id).collect();
+            sync_queue::drop_actions_for_mrs(
+                &self.pool,
+                &gone_ids,
+                "The merge request was deleted on GitLab or is no longer accessible",
+            )
+            .await?;
+            for (mr_id, iid) in &gone {
+                crate::db::file_cache::delete_file_versions_for_mr(&self.pool, *mr_id).await?;
+                sqlx::query("DELETE FROM merge_requests WHERE id = ?")
+                    .bind(mr_id)
+                    .execute(&self.pool)
+                    .await?;
+                self.record_change(
+                    ChangeEntity::MergeRequest,
+                    *mr_id,
+                    instance_id,
+                    ChangeOp::Delete,
+                    Default::default(),
+                )
+                .await;
+                self.emit_mr_updated(*mr_id, instance_id, *iid, MrUpdateType::Purged);
+            }
+            quick_switch::remove(&gone_ids).await;
+            summary.removed += gone.len() as i64;
+        }
+        Ok(())
+    }
+
+    /// Sync a single GitLab instance. A `scheduled` run skips the instance
+    /// until its own interval has passed.
     async fn sync_instance(
         &self,
         instance: &GitLabInstanceRow,
         sync_run_id: &str,
         force: bool,
+        scheduled: bool,
     ) -> Result<SyncResult, AppError> {
         let instance_start = Instant::now();
         let config = self.config.read().await;
+        let instance_config = sync_configs::get_config(&self.pool, instance.id).await?;
+        if scheduled {
+            let since = self
+                .last_instance_sync
+                .read()
+                .await
+                .get(&instance.id)
+                .map(Instant::elapsed);
+            if !instance_sync_due(since, config.interval_for(&instance_config)) {
+                eprintln!("[sync] Skipping {}: not due yet", instance.url);
+                return Ok(SyncResult {
+                    mr_count: 0,
+                    purged_count: 0,
+                    actions_pushed: 0,
+                    errors: Vec::new(),
+                    duration_ms: 0,
+                    api_calls: 0,
+                });
+            }
+        }
+        self.last_instance_sync
+            .write()
+            .await
+            .insert(instance.id, instance_start);
         eprintln!(
             "[sync] sync_instance: url={}, has_token={}",
             instance.url,
@@ -853,12 +1559,20 @@ impl SyncEngine {
             groups.into_iter().collect()
         };
 
+        // Between full list refreshes only MRs updated since the cursor are
+        // listed; unchanged ones keep their cached rows.
+        let list_started_at = now();
+        let cursor = sync_cursors::get_cursor(&self.pool, instance.id).await?;
+        let updated_after = incremental_since(cursor, list_started_at, force);
+
         // Fetch MR lists and the speculative state batch concurrently —
         // reuse username from validate_token above
         let (fetch_result, batch_result) = tokio::join!(
             self.fetch_mrs_for_instance(
                 &client,
-                &config,
+                &instance_config,
+                config.max_mrs_for(&instance_config),
+                updated_after,
                 current_username.as_deref().unwrap_or("unknown")
             ),
             async {
@@ -870,8 +1584,33 @@ impl SyncEngine {
             }
         );
 
-        let (mrs, fetch_complete) = match fetch_result {
-            Ok(fetched) => (fetched.mrs, fetched.complete),
+        let stages: Arc<[&'static dyn SyncStage]> = stages::enabled(
+            config
+                .disabled_stages
+                .get(&instance.id)
+                .map(Vec::as_slice)
+                .unwrap_or_default(),
+        )
+        .into();
+
+        let (mut mrs, fetch_complete) = match fetch_result {
+            Ok(fetched) => {
+                if fetched.complete {
+                    if let Err(e) = sync_cursors::advance(
+                        &self.pool,
+                        instance.id,
+                        list_started_at,
+                        updated_after.is_none(),
+                    )
+                    .await
+                    {
+                        log::warn!("Failed to save sync cursor for {}: {}", instance.url, e);
+                    }
+                }
+                // An incremental list never has every open MR, so missing
+                // ones can't be inferred merged from it.
+                (fetched.mrs, fetched.complete && updated_after.is_none())
+            }
             Err(e) => {
                 // If auth expired, propagate the error with instance info
                 if e.is_authentication_expired() {
@@ -890,10 +1629,6 @@ impl SyncEngine {
         // Drop the config read guard before mutable borrows
         drop(config);
 
-        // Snapshot pre-sync ready state for authored MRs (for transition detection)
-        let mr_ids: Vec<i64> = mrs.iter().map(|mr| mr.id).collect();
-        let pre_sync_ready = self.get_ready_states(&mr_ids).await;
-
         // Collect the speculative batch result. On error every MR falls back
         // to per-MR REST inside sync_mr.
         let mut mr_states: HashMap<(String, i64), BatchedMrState> = match batch_result {
@@ -907,6 +1642,25 @@ impl SyncEngine {
             }
         };
 
+        // An incremental list misses approvals and pipelines that changed
+        // without touching the MR; the batch shows which ones did.
+        if updated_after.is_some() {
+            let changed = self
+                .fetch_mrs_with_changed_state(instance.id, &client, &mrs, &mr_states, &mut result)
+                .await;
+            mrs.extend(changed);
+        }
+
+        // Watched MRs fall outside every scope query, so fetch them one by one.
+        let watched_mrs = self
+            .fetch_watched_mrs(instance.id, &client, &mrs, &mut result)
+            .await;
+        mrs.extend(watched_mrs);
+
+        // Snapshot pre-sync ready state for authored MRs (for transition detection)
+        let mr_ids: Vec<i64> = mrs.iter().map(|mr| mr.id).collect();
+        let pre_sync_ready = self.get_ready_states(&mr_ids).await;
+
         // Top-up batch for MRs the speculation didn't cover (new since the
         // last sync, or first run with an empty cache). Usually empty.
         {
@@ -933,6 +1687,18 @@ impl SyncEngine {
 
         // Process MRs concurrently with bounded parallelism
         let instance_id = instance.id;
+        // On an instance's first sync every MR is new; only treat new MRs as
+        // automation events once something was cached before.
+        let automate_new_mrs: bool =
+            sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM merge_requests WHERE instance_id = ?)")
+                .bind(instance_id)
+                .fetch_one(&self.pool)
+                .await
+                .unwrap_or(false);
+        let retention_policy = Arc::new(retention::load_policy(&self.pool, instance_id).await?);
+        let muted_projects = muted_projects::muted_project_ids(&self.pool, instance_id, now())
+            .await
+            .unwrap_or_default();
         let mut synced_local_mr_ids: Vec<i64> = Vec::new();
         let semaphore = Arc::new(tokio::sync::Semaphore::new(MAX_CONCURRENT_MRS));
         let mut join_set = tokio::task::JoinSet::new();
@@ -944,15 +1710,29 @@ impl SyncEngine {
             let client = client.clone();
             let sync_run_id = sync_run_id.to_string();
             let state = mr_states.remove(&(extract_project_path(&mr.web_url), mr.iid));
+            let retention_policy = retention_policy.clone();
+            let stages = stages.clone();
             let mr = mr.clone();
-            join_set.spawn(async move {
+            let muted = muted_projects.contains(&mr.project_id);
+            // Spawned tasks don't inherit the caller's memory tag — re-apply it.
+            join_set.spawn(memory_profile::track(Subsystem::SyncEngine, async move {
                 let mr_iid = mr.iid;
-                let res = engine
-                    .sync_mr(instance_id, &client, &mr, current_user_id, &sync_run_id, force, state)
-                    .await;
+                let mut ctx = MrSync::new(
+                    instance_id,
+                    &client,
+                    &mr,
+                    current_user_id,
+                    &sync_run_id,
+                    force,
+                    state,
+                    &retention_policy,
+                    automate_new_mrs,
+                );
+                ctx.muted = muted;
+                let res = engine.sync_mr(ctx, &stages).await;
                 drop(permit);
                 (mr_iid, res)
-            });
+            }));
         }
         // Unwrap Arc for post-loop operations (all tasks have their own clones now)
         let mrs = Arc::try_unwrap(mrs_shared).unwrap_or_else(|arc| (*arc).clone());
@@ -991,8 +1771,14 @@ impl SyncEngine {
         }
 
         // Detect MR ready-to-merge transitions and emit notifications
-        self.check_mr_ready_transitions(&mr_ids, &pre_sync_ready, &mrs, current_username.as_deref())
-            .await;
+        self.check_mr_ready_transitions(
+            instance.id,
+            &mr_ids,
+            &pre_sync_ready,
+            &mrs,
+            current_username.as_deref(),
+        )
+        .await;
 
         // Fetch and cache project titles for any new project IDs
         self.cache_project_titles(instance.id, &client, &mrs).await;
@@ -1001,8 +1787,13 @@ impl SyncEngine {
         self.refresh_gitattributes_for_projects(instance.id, &mrs)
             .await;
 
-        // Sync user avatars (non-fatal)
-        self.sync_user_avatars(instance, &mrs).await;
+        // Refresh the label and milestone pickers' caches the same way
+        self.refresh_pickers_for_projects(instance.id, &client, &mrs)
+            .await;
+
+        // Instance-level stages (avatars); non-fatal
+        self.run_instance_stages(&stages, instance, &mrs, sync_run_id)
+            .await;
 
         // Purge merged/closed MRs.
         //
@@ -1034,26 +1825,53 @@ impl SyncEngine {
             );
         }
 
-        // NOTE: pending sync-queue actions are pushed once per sync run in
-        // run_sync() (not per instance) — instances sync concurrently and the
-        // queue has no per-instance claim, so pushing here would double-process.
-
-        // Refresh cached issues on a slower cadence than MRs.
-        self.maybe_sync_issues(instance, &mut result).await;
+        // Remove data cached before the retention policy was tightened.
+        match retention::enforce(&self.pool, &retention_policy, now()).await {
+            Ok(sweep) if sweep != retention::RetentionSweep::default() => log::info!(
+                "[sync] Retention for instance {}: removed {} file versions, {} other cached content, {} comments, {} rendered markdown",
+                instance.id,
+                sweep.file_versions_removed,
+                sweep.content_caches_removed,
+                sweep.comments_removed,
+                sweep.rendered_markdown_removed
+            ),
+            Ok(_) => {}
+            Err(e) => log::warn!(
+                "[sync] Retention sweep failed for instance {}: {}",
+                instance.id,
+                e
+            ),
+        }
+
+        // Cache logs of failed jobs on my MRs so they can be read offline.
+        if let Some(username) = current_username.as_deref() {
+            self.prefetch_failed_job_traces(instance.id, &client, username)
+                .await;
+        }
+
+        // NOTE: pending sync-queue actions are pushed once per sync run in
+        // run_sync() (not per instance) — instances sync concurrently and the
+        // queue has no per-instance claim, so pushing here would double-process.
+
+        // Refresh cached issues on a slower cadence than MRs.
+        self.maybe_sync_issues(instance, &mut result).await;
 
         // Capture API call count from this instance's client
         result.api_calls = client.call_count();
 
         // Record instance-level metrics
-        if let Err(e) = self.record_metric(
-            sync_run_id,
-            "instance",
-            Some(instance.id),
-            None,
-            instance_start.elapsed().as_millis() as i64,
-            result.api_calls,
-            result.mr_count as u64,
-        ).await {
+        if let Err(e) = self
+            .record_metric(
+                sync_run_id,
+                "instance",
+                Some(instance.id),
+                None,
+                instance_start.elapsed().as_millis() as i64,
+                result.api_calls,
+                result.mr_count as u64,
+            )
+            .await
+        {
             log::warn!("Failed to record instance metric: {}", e);
         }
 
@@ -1102,11 +1920,17 @@ impl SyncEngine {
         }
     }
 
-    /// Fetch MRs for an instance based on scope configuration.
+    /// Fetch MRs for an instance based on its scope configuration, only
+    /// those updated after `updated_after` (a Unix timestamp) when set.
+    ///
+    /// A scope that is switched off is not fetched at all. MRs only it
+    /// returned then drop out of the cache like any MR that leaves scope.
     async fn fetch_mrs_for_instance(
         &self,
         client: &GitLabClient,
-        config: &SyncConfig,
+        scopes: &InstanceSyncConfig,
+        max_mrs: usize,
+        updated_after: Option<i64>,
         username: &str,
     ) -> Result<FetchedMrs, AppError> {
         let mut all_mrs: Vec<GitLabMergeRequest> = Vec::new();
@@ -1133,9 +1957,13 @@ impl SyncEngine {
         //   but assigned to the user — not picked up by the other scopes.
         //   Drafts are intentionally included; the "My MRs" view applies its
         //   own draft toggle.
+        let updated_after = updated_after
+            .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+            .map(|dt| dt.to_rfc3339_opts(chrono::SecondsFormat::Secs, true));
         let authored_query = MergeRequestsQuery {
             state: Some("opened".to_string()),
             scope: Some("created_by_me".to_string()),
+            updated_after: updated_after.clone(),
             per_page: Some(100),
             ..Default::default()
         };
@@ -1146,12 +1974,14 @@ impl SyncEngine {
             draft: Some("no".to_string()), // Exclude draft/WIP MRs
             not_author_username: Some(username.to_string()), // Exclude own MRs
             not_approved_by_usernames: Some(username.to_string()), // Exclude already approved
+            updated_after: updated_after.clone(),
             per_page: Some(100),
             ..Default::default()
         };
         let assigned_query = MergeRequestsQuery {
             state: Some("opened".to_string()),
             scope: Some("assigned_to_me".to_string()),
+            updated_after,
             per_page: Some(100),
             ..Default::default()
         };
@@ -1161,11 +1991,22 @@ impl SyncEngine {
             username
         );
 
+        let fetch_scope = |enabled: bool, query: &MergeRequestsQuery| {
+            let query = query.clone();
+            async move {
+                if enabled {
+                    Some(client.list_merge_requests(&query).await)
+                } else {
+                    None
+                }
+            }
+        };
+
         // All three are independent — fetch them concurrently.
         let (authored, reviewing, assigned) = tokio::join!(
-            client.list_merge_requests(&authored_query),
-            client.list_merge_requests(&reviewing_query),
-            client.list_merge_requests(&assigned_query),
+            fetch_scope(scopes.sync_authored, &authored_query),
+            fetch_scope(scopes.sync_reviewing, &reviewing_query),
+            fetch_scope(scopes.sync_assigned, &assigned_query),
         );
 
         for (scope, result) in [
@@ -1174,7 +2015,8 @@ impl SyncEngine {
             ("assigned", assigned),
         ] {
             match result {
-                Ok(response) => {
+                None => eprintln!("[sync] Skipping {} MRs (disabled for this instance)", scope),
+                Some(Ok(response)) => {
                     eprintln!(
                         "[sync] Received {} {} MRs from GitLab",
                         response.data.len(),
@@ -1183,8 +2025,8 @@ impl SyncEngine {
                     merge_unique(&mut all_mrs, response.data);
                 }
                 // Auth failures must propagate so the caller can prompt re-auth.
-                Err(e) if e.is_authentication_expired() => return Err(e),
-                Err(e) => {
+                Some(Err(e)) if e.is_authentication_expired() => return Err(e),
+                Some(Err(e)) => {
                     complete = false;
                     eprintln!("[sync] {} MR fetch failed (continuing): {}", scope, e);
                 }
@@ -1193,390 +2035,136 @@ impl SyncEngine {
 
         // Limit to max MRs per sync. A truncated fetch is incomplete: we can no
         // longer tell a dropped MR apart from a merged one, so disable soft-purge.
-        if all_mrs.len() > config.max_mrs_per_sync {
-            all_mrs.truncate(config.max_mrs_per_sync);
+        if all_mrs.len() > max_mrs {
+            all_mrs.truncate(max_mrs);
             complete = false;
             eprintln!(
                 "[sync] MR fetch truncated to {} — soft-purge disabled this cycle",
-                config.max_mrs_per_sync
+                max_mrs
             );
         }
 
-        Ok(FetchedMrs { mrs: all_mrs, complete })
+        Ok(FetchedMrs {
+            mrs: all_mrs,
+            complete,
+        })
     }
 
-    /// Sync a single MR (metadata, diff, comments, approval status).
+    /// Fetch cached open MRs missing from an incremental list whose batched
+    /// approval or pipeline state no longer matches the cache.
     ///
-    /// `prefetched_state` carries approval + pipeline data already fetched in
-    /// bulk via GraphQL; when None the data is fetched per-MR over REST.
-    #[allow(clippy::too_many_arguments)]
-    async fn sync_mr(
+    /// Failures are recorded in `result` and skipped; the MR is then picked
+    /// up by the next full list fetch.
+    async fn fetch_mrs_with_changed_state(
         &self,
         instance_id: i64,
         client: &GitLabClient,
-        mr: &GitLabMergeRequest,
-        current_user_id: Option<i64>,
-        sync_run_id: &str,
-        force: bool,
-        prefetched_state: Option<BatchedMrState>,
-    ) -> Result<i64, AppError> {
-        let start = Instant::now();
-
-        // Check if MR already exists and fetch cached updated_at for skip logic
-        let existing: Option<(i64, Option<i64>)> = sqlx::query_as(
-            "SELECT id, updated_at FROM merge_requests WHERE instance_id = ? AND project_id = ? AND iid = ?",
+        fetched: &[GitLabMergeRequest],
+        states: &HashMap<(String, i64), BatchedMrState>,
+        result: &mut SyncResult,
+    ) -> Vec<GitLabMergeRequest> {
+        type CachedState = (i64, i64, String, Option<i64>, Option<i64>, Option<String>);
+        let cached: Vec<CachedState> = match sqlx::query_as(
+            "SELECT project_id, iid, project_name, approvals_count, approvals_required,
+                    head_pipeline_status
+             FROM merge_requests
+             WHERE instance_id = ? AND state = 'opened' AND watched = 0
+               AND project_name IS NOT NULL AND project_name != ''",
         )
         .bind(instance_id)
-        .bind(mr.project_id)
-        .bind(mr.iid)
-        .fetch_optional(&self.pool)
-        .await?;
-        let is_new = existing.is_none();
-        let cached_updated_at = existing.and_then(|(_, ts)| ts);
-
-        // Upsert MR metadata and get the canonical DB row id
-        // (may differ from mr.id if the row already existed with a different PK)
-        let local_mr_id = self.upsert_mr(instance_id, mr, current_user_id).await.map_err(|e| {
-            eprintln!("[sync] MR !{}: upsert_mr failed: {}", mr.iid, e);
-            e
-        })?;
-
-        // Approval state must be refreshed every sync — GitLab doesn't update
-        // updated_at on approve/unapprove, so the skip-unchanged optimization
-        // below can't cover approvals safely. The same goes for head pipeline
-        // status, which the list endpoint no longer includes (GitLab 14.5+)
-        // and which the "MR ready to merge" notification depends on.
-        //
-        // Normally both arrive via the instance-wide batched GraphQL query
-        // (prefetched_state). The REST path below is the fallback when the
-        // batch failed or didn't cover this MR.
-        let (approvals_result, pipeline_status) = match prefetched_state {
-            Some(state) => {
-                let pipeline_status = if mr.head_pipeline.is_none() {
-                    state.head_pipeline_status
-                } else {
-                    None
-                };
-                (Ok(state.approvals), pipeline_status)
-            }
-            None => {
-                let needs_pipeline_fetch = mr.head_pipeline.is_none();
-                tokio::join!(
-                    client.get_mr_approvals(mr.project_id, mr.iid),
-                    async {
-                        if needs_pipeline_fetch {
-                            match client.get_merge_request(mr.project_id, mr.iid).await {
-                                Ok(detail) => detail.head_pipeline.map(|p| p.status),
-                                Err(e) => {
-                                    log::warn!("Failed to fetch MR detail for pipeline status (MR !{}): {}", mr.iid, e);
-                                    None
-                                }
-                            }
-                        } else {
-                            None
-                        }
-                    }
-                )
+        .fetch_all(&self.pool)
+        .await
+        {
+            Ok(rows) => rows,
+            Err(e) => {
+                log::warn!(
+                    "Failed to load cached MR states for instance {}: {}",
+                    instance_id,
+                    e
+                );
+                return Vec::new();
             }
         };
 
-        // Update head_pipeline_status from individual MR fetch if list didn't include it
-        if let Some(status) = &pipeline_status {
-            if let Err(e) = sqlx::query(
-                "UPDATE merge_requests SET head_pipeline_status = ? WHERE id = ?",
-            )
-            .bind(status)
-            .bind(local_mr_id)
-            .execute(&self.pool)
-            .await
+        let mut out = Vec::new();
+        for (project_id, iid, path, approvals_count, approvals_required, pipeline) in cached {
+            if fetched
+                .iter()
+                .any(|m| m.project_id == project_id && m.iid == iid)
             {
-                log::warn!("Failed to update head_pipeline_status for MR !{}: {}", mr.iid, e);
-            }
-        }
-
-        match approvals_result {
-            Ok(approvals) => {
-                // Use pre-fetched current_user_id (hoisted to sync_instance) to avoid
-                // per-MR validate_token() calls and transient-failure false negatives
-                let remote_user_has_approved = current_user_id.map_or(false, |uid| {
-                    approvals.approved_by.iter().any(|a| a.user.id == uid)
-                });
-
-                let approval_status = if approvals.approved {
-                    "approved"
-                } else {
-                    "pending"
-                };
-                let approvals_count = approvals.approvals_required - approvals.approvals_left;
-
-                // Guard: check for pending/in-flight approval actions in sync_queue.
-                // If present, preserve the optimistic DB value to prevent stale API data
-                // from overwriting a local approve/unapprove the user just performed.
-                // Note: unapprove actions also use action_type = 'approve' (distinguished by payload)
-                let has_pending_approval_action: i64 = sqlx::query_scalar(
-                    "SELECT COUNT(*) FROM sync_queue WHERE mr_id = ? AND action_type = 'approve' AND status IN ('pending', 'syncing')"
-                )
-                .bind(local_mr_id)
-                .fetch_one(&self.pool)
-                .await
-                .unwrap_or(0);
-
-                let user_has_approved = if has_pending_approval_action > 0 && !remote_user_has_approved {
-                    // Pending local action exists and remote doesn't reflect it yet —
-                    // read and preserve the current (optimistic) DB value
-                    let current: bool = sqlx::query_scalar(
-                        "SELECT user_has_approved FROM merge_requests WHERE id = ?"
-                    )
-                    .bind(local_mr_id)
-                    .fetch_optional(&self.pool)
-                    .await
-                    .unwrap_or(None)
-                    .unwrap_or(remote_user_has_approved);
-
-                    log::info!(
-                        "[sync] MR !{}: preserving local approval state (pending action in queue, remote={})",
-                        mr.iid, remote_user_has_approved
-                    );
-                    current
-                } else {
-                    remote_user_has_approved
-                };
-
-                sqlx::query(
-                    "UPDATE merge_requests SET
-                        approval_status = ?,
-                        approvals_count = ?,
-                        approvals_required = ?,
-                        user_has_approved = ?
-                     WHERE id = ?",
-                )
-                .bind(approval_status)
-                .bind(approvals_count)
-                .bind(approvals.approvals_required)
-                .bind(user_has_approved)
-                .bind(local_mr_id)
-                .execute(&self.pool)
-                .await?;
-
-                // Upsert per-reviewer status
-                self.upsert_reviewers(local_mr_id, mr, &approvals).await;
+                continue;
             }
-            Err(e) => {
-                // Non-critical - log and continue
-                log::warn!("Failed to fetch approvals for MR {}: {}", mr.iid, e);
+            let Some(state) = states.get(&(path, iid)) else {
+                continue;
+            };
+            if !batched_state_changed(
+                approvals_count,
+                approvals_required,
+                pipeline.as_deref(),
+                state,
+            ) {
+                continue;
             }
-        }
-
-        // Emit event AFTER approval fields are written (when available) so the
-        // frontend sees correct state. Always emit even if approvals fetch failed
-        // so the frontend knows about new/updated MRs.
-        self.emit_mr_updated(
-            local_mr_id,
-            instance_id,
-            mr.iid,
-            if is_new {
-                MrUpdateType::Created
-            } else {
-                MrUpdateType::Updated
-            },
-        );
-
-        // Skip expensive API calls (diff, comments) if MR hasn't changed.
-        // Approvals are always fetched above since GitLab doesn't update
-        // updated_at on approve/unapprove.
-        let fresh_updated_at = parse_iso_timestamp(&mr.updated_at);
-        let mut mr_unchanged = !force && !is_new && cached_updated_at == Some(fresh_updated_at);
-
-        // Even if the MR looks unchanged, re-fetch if it has no cached files.
-        // This handles MRs that were synced before file caching completed.
-        if mr_unchanged {
-            let cached_file_count: (i64,) = sqlx::query_as(
-                "SELECT COUNT(*) FROM file_versions WHERE mr_id = ?"
-            )
-            .bind(local_mr_id)
-            .fetch_one(&self.pool)
-            .await
-            .unwrap_or((0,));
-
-            if cached_file_count.0 == 0 {
-                log::info!(
-                    "[sync] MR !{}: unchanged but has 0 cached files, re-fetching",
-                    mr.iid
-                );
-                mr_unchanged = false;
+            match client.get_merge_request(project_id, iid).await {
+                Ok(mr) => out.push(mr),
+                Err(e) => result.errors.push(format!("MR !{}: {}", iid, e)),
             }
         }
-
-        if mr_unchanged {
-            log::info!(
-                "[sync] MR !{}: unchanged (updated_at={}), skipping diff/comments",
-                mr.iid, mr.updated_at
+        if !out.is_empty() {
+            eprintln!(
+                "[sync] Fetched {} MRs with changed approvals or pipelines",
+                out.len()
             );
-
-            let mr_duration_ms = start.elapsed().as_millis() as i64;
-            self.log_sync_operation(
-                "sync_mr",
-                "skipped",
-                Some(local_mr_id),
-                None,
-                Some(mr_duration_ms),
-            )
-            .await?;
-
-            if let Err(e) = self.record_metric(
-                sync_run_id, "mr", Some(instance_id), Some(mr.iid),
-                mr_duration_ms, 0, 0,
-            ).await {
-                log::warn!("Failed to record MR metric: {}", e);
-            }
-
-            return Ok(local_mr_id);
         }
+        out
+    }
 
-        // Emit progress events before concurrent fetch
-        self.emit_progress(
-            SyncPhase::FetchingDiff,
-            format!("Fetching diff for MR !{}", mr.iid),
-        );
-        self.emit_progress(
-            SyncPhase::FetchingComments,
-            format!("Fetching comments for MR !{}", mr.iid),
-        );
-
-        // Fetch diff and comments concurrently
-        let diff_start = Instant::now();
-        let (diff_result, comments_result) = tokio::join!(
-            client.get_merge_request_diff(mr.project_id, mr.iid),
-            client.list_discussions(mr.project_id, mr.iid)
-        );
-
-        // Process diff result first (cache_file_contents depends on it)
-        match diff_result {
-            Ok(diff) => {
-                // Get previously cached SHAs before upserting (for skip-unchanged logic)
-                let prev_shas =
-                    crate::db::file_cache::get_cached_diff_shas(&self.pool, local_mr_id)
-                        .await
-                        .unwrap_or(None);
-
-                self.upsert_diff(local_mr_id, &diff).await.map_err(|e| {
-                    eprintln!("[sync] MR !{}: upsert_diff failed: {}", mr.iid, e);
-                    e
-                })?;
-
-                // Emit diff_updated event
-                self.emit_mr_updated(local_mr_id, instance_id, mr.iid, MrUpdateType::DiffUpdated);
-
-                // Record diff phase metric
-                if let Err(e) = self.record_metric(
-                    sync_run_id, "diff", Some(instance_id), Some(mr.iid),
-                    diff_start.elapsed().as_millis() as i64, 0,
-                    diff.diffs.len() as u64,
-                ).await {
-                    log::warn!("Failed to record diff metric: {}", e);
-                }
-
-                // Pre-cache full file content for instant viewing
-                let file_cache_start = Instant::now();
-                self.cache_file_contents(
-                    local_mr_id,
-                    mr.project_id,
-                    instance_id,
-                    client,
-                    &diff,
-                    prev_shas.as_ref(),
-                )
-                .await;
-
-                // Record file_cache phase metric
-                if let Err(e) = self.record_metric(
-                    sync_run_id, "file_cache", Some(instance_id), Some(mr.iid),
-                    file_cache_start.elapsed().as_millis() as i64, 0,
-                    diff.diffs.len() as u64,
-                ).await {
-                    log::warn!("Failed to record file_cache metric: {}", e);
-                }
-            }
+    /// Fetch open watched MRs that the scope queries did not already return.
+    ///
+    /// Failures are recorded in `result` and skipped; watched MRs are exempt
+    /// from soft-purge, so a failed fetch just leaves the cached row as-is.
+    async fn fetch_watched_mrs(
+        &self,
+        instance_id: i64,
+        client: &GitLabClient,
+        fetched: &[GitLabMergeRequest],
+        result: &mut SyncResult,
+    ) -> Vec<GitLabMergeRequest> {
+        let watched = match crate::db::watched::list_open_watched(&self.pool, instance_id).await {
+            Ok(rows) => rows,
             Err(e) => {
-                self.log_sync_operation(
-                    "fetch_diff",
-                    "error",
-                    Some(local_mr_id),
-                    Some(e.to_string()),
-                    None,
-                )
-                .await?;
-            }
-        }
-
-        // Process comments result (fetch happened concurrently with diff via join!)
-        let comments_start = diff_start; // Both started at the same time
-        match comments_result {
-            Ok(discussions) => {
-                self.upsert_discussions(local_mr_id, &discussions)
-                    .await
-                    .map_err(|e| {
-                        eprintln!("[sync] MR !{}: upsert_discussions failed: {}", mr.iid, e);
-                        e
-                    })?;
-
-                // Emit comments_updated event
-                self.emit_mr_updated(
-                    local_mr_id,
+                log::warn!(
+                    "Failed to list watched MRs for instance {}: {}",
                     instance_id,
-                    mr.iid,
-                    MrUpdateType::CommentsUpdated,
+                    e
                 );
+                return Vec::new();
+            }
+        };
 
-                // Record comments phase metric
-                if let Err(e) = self.record_metric(
-                    sync_run_id, "comments", Some(instance_id), Some(mr.iid),
-                    comments_start.elapsed().as_millis() as i64, 0,
-                    discussions.len() as u64,
-                ).await {
-                    log::warn!("Failed to record comments metric: {}", e);
-                }
+        let mut out = Vec::new();
+        for row in watched {
+            if fetched
+                .iter()
+                .any(|m| m.project_id == row.project_id && m.iid == row.iid)
+            {
+                continue;
             }
-            Err(e) => {
-                self.log_sync_operation(
-                    "fetch_comments",
-                    "error",
-                    Some(local_mr_id),
-                    Some(e.to_string()),
-                    None,
-                )
-                .await?;
+            match client.get_merge_request(row.project_id, row.iid).await {
+                Ok(mr) => out.push(mr),
+                Err(e) => result
+                    .errors
+                    .push(format!("Watched MR !{}: {}", row.iid, e)),
             }
         }
-
-        // Log successful sync
-        let mr_duration_ms = start.elapsed().as_millis() as i64;
-        self.log_sync_operation(
-            "sync_mr",
-            "success",
-            Some(local_mr_id),
-            None,
-            Some(mr_duration_ms),
-        )
-        .await?;
-
-        // Record MR-level metric
-        if let Err(e) = self.record_metric(
-            sync_run_id,
-            "mr",
-            Some(instance_id),
-            Some(mr.iid),
-            mr_duration_ms,
-            0,
-            0,
-        ).await {
-            log::warn!("Failed to record MR metric: {}", e);
+        if !out.is_empty() {
+            eprintln!("[sync] Fetched {} watched MRs", out.len());
         }
-
-        Ok(local_mr_id)
+        out
     }
 
-    /// Fetch and cache project titles for any project IDs not already in the projects table.
+    /// Fetch and cache project titles and the user's access level for any
+    /// project IDs not already in the projects table.
     async fn cache_project_titles(
         &self,
         instance_id: i64,
@@ -1588,15 +2176,21 @@ impl SyncEngine {
         project_ids.sort_unstable();
         project_ids.dedup();
 
-        // Find which ones are missing from cache
-        let missing =
-            match project::get_missing_project_ids(&self.pool, instance_id, &project_ids).await {
-                Ok(ids) => ids,
-                Err(e) => {
-                    log::warn!("Failed to check cached project IDs: {}", e);
-                    return;
-                }
-            };
+        // Find which ones are missing from cache, or cached before access
+        // levels were stored
+        let missing = match project::get_project_ids_without_access_level(
+            &self.pool,
+            instance_id,
+            &project_ids,
+        )
+        .await
+        {
+            Ok(ids) => ids,
+            Err(e) => {
+                log::warn!("Failed to check cached project IDs: {}", e);
+                return;
+            }
+        };
 
         if missing.is_empty() {
             return;
@@ -1607,6 +2201,7 @@ impl SyncEngine {
         for project_id in missing {
             match client.get_project(project_id).await {
                 Ok(gitlab_project) => {
+                    let access_level = gitlab_project.access_level();
                     let project = Project {
                         id: gitlab_project.id,
                         instance_id,
@@ -1621,6 +2216,17 @@ impl SyncEngine {
                     };
                     if let Err(e) = project::upsert_project(&self.pool, &project).await {
                         log::warn!("Failed to cache project {}: {}", project_id, e);
+                    } else if let Some(level) = access_level {
+                        if let Err(e) =
+                            project::set_access_level(&self.pool, instance_id, project_id, level)
+                                .await
+                        {
+                            log::warn!(
+                                "Failed to store access level for project {}: {}",
+                                project_id,
+                                e
+                            );
+                        }
                     }
                 }
                 Err(e) => {
@@ -1668,50 +2274,304 @@ impl SyncEngine {
                     );
                 }
             }
-        }
-    }
+        }
+    }
+
+    /// Refresh the cached labels and milestones of the projects with MRs in
+    /// the current sync whose caches are stale or were never fetched.
+    async fn refresh_pickers_for_projects(
+        &self,
+        instance_id: i64,
+        client: &GitLabClient,
+        mrs: &[GitLabMergeRequest],
+    ) {
+        let mut project_ids: Vec<i64> = mrs.iter().map(|mr| mr.project_id).collect();
+        project_ids.sort_unstable();
+        project_ids.dedup();
+
+        let now = now();
+        for project_id in project_ids {
+            if let Err(e) =
+                mr_labels::refresh_labels_if_stale(&self.pool, client, instance_id, project_id, now)
+                    .await
+            {
+                log::warn!("Failed to refresh labels for project {}: {}", project_id, e);
+            }
+            if let Err(e) = mr_milestones::refresh_milestones_if_stale(
+                &self.pool,
+                client,
+                instance_id,
+                project_id,
+                now,
+            )
+            .await
+            {
+                log::warn!(
+                    "Failed to refresh milestones for project {}: {}",
+                    project_id,
+                    e
+                );
+            }
+        }
+    }
+
+    /// Prefetch the log tails of failed jobs on the head pipelines of the
+    /// user's open MRs. Best-effort: failures are logged and never fail the sync.
+    async fn prefetch_failed_job_traces(
+        &self,
+        instance_id: i64,
+        client: &GitLabClient,
+        username: &str,
+    ) {
+        let now = now();
+        if let Err(e) =
+            job_trace_cache::prune_job_traces(&self.pool, now - JOB_TRACE_TTL_SECS).await
+        {
+            log::warn!("[sync] Failed to prune job trace cache: {}", e);
+        }
+
+        let failed: Vec<(i64, i64, i64)> = match sqlx::query_as(
+            "SELECT id, project_id, iid FROM merge_requests
+             WHERE instance_id = ? AND state = 'opened' AND author_username = ?
+               AND head_pipeline_status = 'failed'
+             ORDER BY updated_at DESC LIMIT ?",
+        )
+        .bind(instance_id)
+        .bind(username)
+        .bind(MAX_TRACE_PREFETCH_MRS)
+        .fetch_all(&self.pool)
+        .await
+        {
+            Ok(rows) => rows,
+            Err(e) => {
+                log::warn!("[sync] Failed to list MRs for trace prefetch: {}", e);
+                return;
+            }
+        };
+
+        for (mr_id, project_id, iid) in failed {
+            match pipelines::prefetch_failed_traces(
+                &self.pool,
+                client,
+                instance_id,
+                mr_id,
+                project_id,
+                iid,
+                now,
+            )
+            .await
+            {
+                Ok(0) => {}
+                Ok(n) => log::info!("[sync] Cached {} failed job trace(s) for MR !{}", n, iid),
+                Err(e) => log::warn!("[sync] Job trace prefetch failed for MR !{}: {}", iid, e),
+            }
+        }
+    }
+
+    /// Sync user avatars for MR authors and reviewers. Returns the number
+    /// of avatars whose image changed.
+    async fn sync_user_avatars(
+        &self,
+        instance: &GitLabInstanceRow,
+        mrs: &[GitLabMergeRequest],
+    ) -> u32 {
+        use crate::services::avatar::{self, AvatarAuth, AvatarSource};
+        use std::collections::HashMap;
+
+        // Collect unique users from authors, reviewers and assignees
+        let mut users: HashMap<String, AvatarSource> = HashMap::new();
+        for mr in mrs {
+            let people = std::iter::once(&mr.author)
+                .chain(mr.reviewers.iter().flatten())
+                .chain(mr.assignees.iter().flatten());
+            for user in people {
+                users
+                    .entry(user.username.clone())
+                    .or_insert_with(|| AvatarSource {
+                        username: user.username.clone(),
+                        avatar_url: user.avatar_url.clone(),
+                        email: user.public_email.clone(),
+                    });
+            }
+        }
+
+        let user_list: Vec<AvatarSource> = users.into_values().collect();
+        let allow_gravatar = self.config.read().await.gravatar_fallback;
+        let auth = AvatarAuth {
+            token: instance.token.as_deref(),
+            cookie: instance.session_cookie.as_deref(),
+        };
+
+        match avatar::sync_avatars(
+            &self.pool,
+            instance.id,
+            &instance.url,
+            auth,
+            allow_gravatar,
+            &user_list,
+        )
+        .await
+        {
+            Ok(count) => {
+                if count > 0 {
+                    eprintln!(
+                        "[sync] Updated {} avatar(s) for instance {}",
+                        count, instance.id
+                    );
+                }
+                count
+            }
+            Err(e) => {
+                eprintln!("[sync] Avatar sync error (non-fatal): {}", e);
+                0
+            }
+        }
+    }
+
+    /// Queue an approval for a bot-authored MR with a green head pipeline
+    /// when `auto_approve_bot_mrs` is enabled. The caller has already checked
+    /// that the user is a reviewer who hasn't approved yet. MRs held back by
+    /// the approval gate or review blockers are left alone.
+    async fn maybe_auto_approve_bot_mr(
+        &self,
+        local_mr_id: i64,
+        mr: &GitLabMergeRequest,
+        pipeline_status: Option<&str>,
+    ) {
+        let eligible = {
+            let config = self.config.read().await;
+            config.auto_approve_bot_mrs
+                && mr.state == "opened"
+                && pipeline_status == Some("success")
+                && is_bot_author(
+                    &mr.author.username,
+                    mr.author.bot,
+                    &config.bot_author_patterns,
+                )
+        };
+        if !eligible {
+            return;
+        }
+
+        let result: Result<(), AppError> = async {
+            if token_scopes::ensure_can_write(&self.pool, local_mr_id)
+                .await
+                .is_err()
+            {
+                log::debug!("[sync] MR !{}: read-only token, not auto-approving", mr.iid);
+                return Ok(());
+            }
+            let result = mr_actions::queue_approval(&self.pool, local_mr_id, false).await?;
+            if !result.approved {
+                log::debug!(
+                    "[sync] MR !{}: approval held back, not auto-approving",
+                    mr.iid
+                );
+                return Ok(());
+            }
+            log::info!(
+                "[sync] MR !{}: queued auto-approval of bot MR with green pipeline",
+                mr.iid
+            );
+            Ok(())
+        }
+        .await;
+
+        if let Err(e) = result {
+            log::warn!("Failed to auto-approve bot MR !{}: {}", mr.iid, e);
+        }
+    }
+
+    /// During vacation mode, queue the away comment on a review request the
+    /// user didn't have before this sync. Runs before `upsert_reviewers`, so
+    /// `mr_reviewers` still holds the previous reviewer list. An MR seen for
+    /// the first time only counts if it changed during the vacation.
+    async fn maybe_answer_vacation(
+        &self,
+        instance_id: i64,
+        local_mr_id: i64,
+        mr: &GitLabMergeRequest,
+        current_user_id: Option<i64>,
+        is_new: bool,
+    ) {
+        let config = self.config.read().await.vacation.clone();
+        let now = now();
+        if !config.is_active(now) || mr.state != "opened" {
+            return;
+        }
+        let Some(me) = mr
+            .reviewers
+            .iter()
+            .flatten()
+            .find(|u| Some(u.id) == current_user_id)
+            .map(|u| u.username.clone())
+        else {
+            return;
+        };
+
+        let result: Result<(), AppError> = async {
+            // Leave the request unclaimed so it's answered once the token can write.
+            if token_scopes::ensure_can_write(&self.pool, local_mr_id)
+                .await
+                .is_err()
+            {
+                log::debug!(
+                    "[sync] MR !{}: read-only token, no vacation response",
+                    mr.iid
+                );
+                return Ok(());
+            }
+            let newly_requested = if is_new {
+                parse_iso_timestamp(&mr.updated_at) >= config.starts_at
+            } else {
+                let listed: Option<i64> = sqlx::query_scalar(
+                    "SELECT 1 FROM mr_reviewers WHERE mr_id = ? AND username = ?",
+                )
+                .bind(local_mr_id)
+                .bind(&me)
+                .fetch_optional(&self.pool)
+                .await?;
+                listed.is_none()
+            };
+            if !newly_requested
+                || !vacation::claim_response(
+                    &self.pool,
+                    instance_id,
+                    mr.project_id,
+                    mr.iid,
+                    config.starts_at,
+                    now,
+                )
+                .await?
+            {
+                return Ok(());
+            }
 
-    /// Sync user avatars for MR authors and reviewers.
-    async fn sync_user_avatars(&self, instance: &GitLabInstanceRow, mrs: &[GitLabMergeRequest]) {
-        use std::collections::HashMap;
-
-        // Collect unique (username, avatar_url) pairs from authors and reviewers
-        let mut users: HashMap<String, Option<String>> = HashMap::new();
-        for mr in mrs {
-            users
-                .entry(mr.author.username.clone())
-                .or_insert_with(|| mr.author.avatar_url.clone());
-            if let Some(reviewers) = &mr.reviewers {
-                for r in reviewers {
-                    users
-                        .entry(r.username.clone())
-                        .or_insert_with(|| r.avatar_url.clone());
-                }
-            }
+            let payload = serde_json::to_string(&CommentPayload {
+                project_id: mr.project_id,
+                mr_iid: mr.iid,
+                body: config.response_body(&me),
+                file_path: None,
+                old_line: None,
+                new_line: None,
+            })?;
+            sync_queue::enqueue_action(
+                &self.pool,
+                EnqueueInput {
+                    mr_id: local_mr_id,
+                    action_type: ActionType::Comment,
+                    payload,
+                    local_reference_id: None,
+                },
+            )
+            .await?;
+            log::info!("[sync] MR !{}: queued vacation response", mr.iid);
+            Ok(())
         }
+        .await;
 
-        let user_list: Vec<(String, Option<String>)> = users.into_iter().collect();
-
-        match crate::services::avatar::sync_avatars(
-            &self.pool,
-            instance.id,
-            &instance.url,
-            instance.session_cookie.as_deref(),
-            &user_list,
-        )
-        .await
-        {
-            Ok(count) => {
-                if count > 0 {
-                    eprintln!(
-                        "[sync] Downloaded {} avatar(s) for instance {}",
-                        count, instance.id
-                    );
-                }
-            }
-            Err(e) => {
-                eprintln!("[sync] Avatar sync error (non-fatal): {}", e);
-            }
+        if let Err(e) = result {
+            log::warn!("Failed to answer review request on MR !{}: {}", mr.iid, e);
         }
     }
 
@@ -1735,8 +2595,28 @@ impl SyncEngine {
                     .unwrap_or_else(|_| "[]".to_string())
             })
             .unwrap_or_else(|| "[]".to_string());
+        let assignees_json = serde_json::to_string(
+            &mr.assignees
+                .iter()
+                .flatten()
+                .map(|u| &u.username)
+                .collect::<Vec<_>>(),
+        )
+        .unwrap_or_else(|_| "[]".to_string());
         let project_name = extract_project_path(&mr.web_url);
         let head_pipeline_status = mr.head_pipeline.as_ref().map(|p| p.status.clone());
+        let (preview_chars, bot_author) = {
+            let config = self.config.read().await;
+            (
+                config.description_preview_chars,
+                is_bot_author(
+                    &mr.author.username,
+                    mr.author.bot,
+                    &config.bot_author_patterns,
+                ),
+            )
+        };
+        let preview = description_preview(mr.description.as_deref(), preview_chars);
 
         // Flag MRs assigned to the authenticated user (matched by user id).
         // Falls back to false when the user id is unknown (token validation failed).
@@ -1760,19 +2640,90 @@ impl SyncEngine {
                 id, instance_id, iid, project_id, title, description,
                 author_username, source_branch, target_branch, state, web_url,
                 created_at, updated_at, merged_at, labels, reviewers, cached_at,
-                project_name, head_pipeline_status, state_changed_at, assigned_to_me
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                project_name, head_pipeline_status, state_changed_at, assigned_to_me,
+                description_preview, is_bot_author, discussion_locked,
+                detailed_merge_status, has_conflicts, assignees, milestone_id,
+                milestone_title, milestone_due_date
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             ON CONFLICT(instance_id, project_id, iid) DO UPDATE SET
+                -- A refused comment is worth another try once the MR changed.
+                discussion_refused = CASE
+                    WHEN excluded.updated_at != merge_requests.updated_at
+                      OR excluded.discussion_locked != merge_requests.discussion_locked
+                        THEN NULL
+                    ELSE merge_requests.discussion_refused
+                END,
+                discussion_locked = excluded.discussion_locked,
                 title = excluded.title,
                 description = excluded.description,
+                description_preview = excluded.description_preview,
                 state = excluded.state,
                 updated_at = excluded.updated_at,
                 merged_at = excluded.merged_at,
-                labels = excluded.labels,
-                reviewers = excluded.reviewers,
+                -- Keep label changes that haven't been pushed yet.
+                labels = CASE
+                    WHEN EXISTS (
+                        SELECT 1 FROM sync_queue
+                        WHERE mr_id = merge_requests.id AND action_type = 'updatelabels'
+                          AND status IN ('pending', 'syncing')
+                    ) THEN merge_requests.labels
+                    ELSE excluded.labels
+                END,
+                -- Same for reviewers and assignees.
+                reviewers = CASE
+                    WHEN EXISTS (
+                        SELECT 1 FROM sync_queue
+                        WHERE mr_id = merge_requests.id AND action_type = 'setreviewers'
+                          AND status IN ('pending', 'syncing')
+                    ) THEN merge_requests.reviewers
+                    ELSE excluded.reviewers
+                END,
+                assignees = CASE
+                    WHEN EXISTS (
+                        SELECT 1 FROM sync_queue
+                        WHERE mr_id = merge_requests.id AND action_type = 'setassignees'
+                          AND status IN ('pending', 'syncing')
+                    ) THEN merge_requests.assignees
+                    ELSE excluded.assignees
+                END,
                 cached_at = excluded.cached_at,
                 project_name = excluded.project_name,
-                assigned_to_me = excluded.assigned_to_me,
+                assigned_to_me = CASE
+                    WHEN EXISTS (
+                        SELECT 1 FROM sync_queue
+                        WHERE mr_id = merge_requests.id AND action_type = 'setassignees'
+                          AND status IN ('pending', 'syncing')
+                    ) THEN merge_requests.assigned_to_me
+                    ELSE excluded.assigned_to_me
+                END,
+                -- And the milestone.
+                milestone_id = CASE
+                    WHEN EXISTS (
+                        SELECT 1 FROM sync_queue
+                        WHERE mr_id = merge_requests.id AND action_type = 'setmilestone'
+                          AND status IN ('pending', 'syncing')
+                    ) THEN merge_requests.milestone_id
+                    ELSE excluded.milestone_id
+                END,
+                milestone_title = CASE
+                    WHEN EXISTS (
+                        SELECT 1 FROM sync_queue
+                        WHERE mr_id = merge_requests.id AND action_type = 'setmilestone'
+                          AND status IN ('pending', 'syncing')
+                    ) THEN merge_requests.milestone_title
+                    ELSE excluded.milestone_title
+                END,
+                milestone_due_date = CASE
+                    WHEN EXISTS (
+                        SELECT 1 FROM sync_queue
+                        WHERE mr_id = merge_requests.id AND action_type = 'setmilestone'
+                          AND status IN ('pending', 'syncing')
+                    ) THEN merge_requests.milestone_due_date
+                    ELSE excluded.milestone_due_date
+                END,
+                is_bot_author = excluded.is_bot_author,
+                detailed_merge_status = COALESCE(excluded.detailed_merge_status, merge_requests.detailed_merge_status),
+                has_conflicts = excluded.has_conflicts,
                 head_pipeline_status = COALESCE(excluded.head_pipeline_status, merge_requests.head_pipeline_status),
                 state_changed_at = CASE
                     WHEN excluded.state != 'opened' AND merge_requests.state = 'opened'
@@ -1804,6 +2755,15 @@ impl SyncEngine {
         .bind(&head_pipeline_status)
         .bind(state_changed_at)
         .bind(assigned_to_me)
+        .bind(&preview)
+        .bind(bot_author)
+        .bind(mr.discussion_locked.unwrap_or(false))
+        .bind(&mr.detailed_merge_status)
+        .bind(mr.has_conflicts)
+        .bind(&assignees_json)
+        .bind(mr.milestone.as_ref().map(|m| m.id))
+        .bind(mr.milestone.as_ref().map(|m| m.title.as_str()))
+        .bind(mr.milestone.as_ref().and_then(|m| m.due_date.as_deref()))
         .execute(&self.pool)
         .await?;
 
@@ -1817,6 +2777,14 @@ impl SyncEngine {
         .fetch_one(&self.pool)
         .await?;
 
+        mr_tasks::store_tasks(&self.pool, db_id, mr.description.as_deref()).await?;
+
+        match discussion_access::stop_denied_actions(&self.pool, db_id).await {
+            Ok(0) => {}
+            Ok(n) => log::info!("Stopped {} queued discussion action(s) on MR {}", n, db_id),
+            Err(e) => log::warn!("Failed to check discussion access for MR {}: {}", db_id, e),
+        }
+
         Ok(db_id)
     }
 
@@ -1930,7 +2898,8 @@ impl SyncEngine {
     ///
     /// If `prev_shas` matches the current diff SHAs, all file fetching is
     /// skipped entirely. If SHAs changed, only files without an existing
-    /// cached version are fetched.
+    /// cached version are fetched, and inline comments are moved from the
+    /// previous versions to the new ones.
     async fn cache_file_contents(
         &self,
         mr_id: i64,
@@ -1942,6 +2911,9 @@ impl SyncEngine {
     ) {
         use sha2::{Digest, Sha256};
 
+        // Content of commented files before the SHAs changed.
+        let mut previous_versions = comment_anchors::PreviousVersions::new();
+
         // If base_sha and head_sha are unchanged, skip all file fetching
         if let Some((prev_base, prev_head)) = prev_shas {
             if prev_base == &diff.base_commit_sha && prev_head == &diff.head_commit_sha {
@@ -1957,6 +2929,12 @@ impl SyncEngine {
                 "SHAs changed for MR {}, purging cached file versions",
                 mr_id
             );
+            previous_versions = comment_anchors::snapshot(&self.pool, mr_id)
+                .await
+                .unwrap_or_else(|e| {
+                    log::warn!("Failed to keep file versions of MR {}: {}", mr_id, e);
+                    Default::default()
+                });
             if let Err(e) =
                 crate::db::file_cache::delete_file_versions_for_mr(&self.pool, mr_id).await
             {
@@ -2026,10 +3004,7 @@ impl SyncEngine {
                         return;
                     }
 
-                    match client
-                        .get_file_content(project_id, &path, &ref_sha)
-                        .await
-                    {
+                    match client.get_file_content(project_id, &path, &ref_sha).await {
                         Ok(content) => {
                             let mut hasher = Sha256::new();
                             hasher.update(content.as_bytes());
@@ -2041,10 +3016,7 @@ impl SyncEngine {
                             )
                             .await
                             {
-                                log::warn!(
-                                    "Failed to cache {} blob for {}: {}",
-                                    version, path, e
-                                );
+                                log::warn!("Failed to cache {} blob for {}: {}", version, path, e);
                             }
                             if let Err(e) = crate::db::file_cache::upsert_file_version(
                                 &pool,
@@ -2059,15 +3031,14 @@ impl SyncEngine {
                             {
                                 log::warn!(
                                     "Failed to cache {} version for {}: {}",
-                                    version, path, e
+                                    version,
+                                    path,
+                                    e
                                 );
                             }
                         }
                         Err(e) => {
-                            log::warn!(
-                                "Failed to fetch {} content for {}: {}",
-                                version, path, e
-                            );
+                            log::warn!("Failed to fetch {} content for {}: {}", version, path, e);
                         }
                     }
                 }
@@ -2082,6 +3053,14 @@ impl SyncEngine {
                 mr_id
             );
         }
+
+        if !previous_versions.is_empty() {
+            match comment_anchors::reanchor(&self.pool, mr_id, &previous_versions).await {
+                Ok(0) => {}
+                Ok(moved) => log::debug!("Moved {} inline comments of MR {}", moved, mr_id),
+                Err(e) => log::warn!("Failed to move comments of MR {}: {}", mr_id, e),
+            }
+        }
     }
 
     /// Upsert discussions (comments) into the database.
@@ -2089,9 +3068,16 @@ impl SyncEngine {
         &self,
         mr_id: i64,
         discussions: &[GitLabDiscussion],
+        created_after: Option<i64>,
     ) -> Result<(), AppError> {
         for discussion in discussions {
             for note in &discussion.notes {
+                let created_at = parse_iso_timestamp(&note.created_at);
+                // Notes past the instance's comment retention are not cached.
+                if created_after.is_some_and(|cutoff| created_at < cutoff) {
+                    continue;
+                }
+
                 let file_path = note
                     .position
                     .as_ref()
@@ -2099,7 +3085,6 @@ impl SyncEngine {
                 let old_line = note.position.as_ref().and_then(|p| p.old_line);
                 let new_line = note.position.as_ref().and_then(|p| p.new_line);
 
-                let created_at = parse_iso_timestamp(&note.created_at);
                 let updated_at = parse_iso_timestamp(&note.updated_at);
 
                 sqlx::query(
@@ -2132,6 +3117,10 @@ impl SyncEngine {
                 .bind(now())
                 .execute(&self.pool)
                 .await?;
+
+                if note.position.is_some() {
+                    suggestions::save_for_comment(&self.pool, note.id, &note.suggestions).await?;
+                }
             }
         }
 
@@ -2158,13 +3147,45 @@ impl SyncEngine {
     /// Upsert per-reviewer approval statuses for a merge request.
     ///
     /// Combines the MR's assigned reviewers list with the approvals endpoint data
-    /// to determine each reviewer's status: approved, pending, or (future) changes_requested.
+    /// to determine each reviewer's status: approved, changes_requested, or pending.
+    /// `review_states` (from the reviewers endpoint) is only fetched when the MR
+    /// changed; without it the cached review state is kept.
+    ///
+    /// Returns the reviewers whose review was re-requested since the last sync.
+    /// Left alone while a reviewer change of this MR waits in the queue.
     async fn upsert_reviewers(
         &self,
         mr_id: i64,
         mr: &GitLabMergeRequest,
         approvals: &crate::services::gitlab_client::MergeRequestApprovals,
-    ) {
+        review_states: Option<&[GitLabMrReviewer]>,
+    ) -> Vec<String> {
+        let pending_change: Option<i64> = sqlx::query_scalar(
+            "SELECT 1 FROM sync_queue
+             WHERE mr_id = ? AND action_type = 'setreviewers' AND status IN ('pending', 'syncing')
+             LIMIT 1",
+        )
+        .bind(mr_id)
+        .fetch_optional(&self.pool)
+        .await
+        .unwrap_or_default();
+        if pending_change.is_some() {
+            return Vec::new();
+        }
+
+        let previous: HashMap<String, ReviewerState> = sqlx::query_as::<_, (String, Option<String>, Option<i64>, Option<i64>)>(
+            "SELECT username, review_state, rerequested_at, requested_at FROM mr_reviewers WHERE mr_id = ?",
+        )
+        .bind(mr_id)
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(username, review_state, rerequested_at, requested_at)| {
+            (username, ReviewerState { review_state, rerequested_at, requested_at })
+        })
+        .collect();
+
         // Delete existing reviewers for this MR (full replace per sync cycle)
         if let Err(e) = sqlx::query("DELETE FROM mr_reviewers WHERE mr_id = ?")
             .bind(mr_id)
@@ -2172,7 +3193,7 @@ impl SyncEngine {
             .await
         {
             log::warn!("Failed to delete old reviewers for MR {}: {}", mr_id, e);
-            return;
+            return Vec::new();
         }
 
         // Build a set of approved usernames for quick lookup
@@ -2182,25 +3203,55 @@ impl SyncEngine {
             .map(|a| a.user.username.as_str())
             .collect();
 
+        let now = now();
+        let mut rerequested = Vec::new();
+        // GitLab doesn't say when a reviewer was added; adding one bumps the
+        // MR's `updated_at`, which is the closest we get.
+        let updated_at = parse_iso_timestamp(&mr.updated_at);
+        let first_seen_at = if updated_at > 0 {
+            updated_at.min(now)
+        } else {
+            now
+        };
+
         // Use the MR's reviewers list as the source of truth for who is assigned
         let reviewers = mr.reviewers.as_deref().unwrap_or(&[]);
         for reviewer in reviewers {
+            let prev = previous.get(&reviewer.username);
+            let fresh_state = review_states.and_then(|states| {
+                states
+                    .iter()
+                    .find(|s| s.user.username == reviewer.username)
+                    .and_then(|s| s.state.clone())
+            });
+            let rerequested_at = rereview::next_rerequested_at(prev, fresh_state.as_deref(), now);
+            if rerequested_at.is_some() && prev.and_then(|p| p.rerequested_at).is_none() {
+                rerequested.push(reviewer.username.clone());
+            }
+            let review_state = fresh_state.or_else(|| prev.and_then(|p| p.review_state.clone()));
+            let requested_at = prev.and_then(|p| p.requested_at).unwrap_or(first_seen_at);
+
             let status = if approved_usernames.contains(reviewer.username.as_str()) {
                 "approved"
+            } else if review_state.as_deref() == Some("requested_changes") {
+                "changes_requested"
             } else {
                 "pending"
             };
 
             if let Err(e) = sqlx::query(
                 r#"
-                INSERT INTO mr_reviewers (mr_id, username, status, cached_at)
-                VALUES (?, ?, ?, ?)
+                INSERT INTO mr_reviewers (mr_id, username, status, cached_at, review_state, rerequested_at, requested_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?)
                 "#,
             )
             .bind(mr_id)
             .bind(&reviewer.username)
             .bind(status)
-            .bind(now())
+            .bind(now)
+            .bind(&review_state)
+            .bind(rerequested_at)
+            .bind(requested_at)
             .execute(&self.pool)
             .await
             {
@@ -2227,7 +3278,7 @@ impl SyncEngine {
                 )
                 .bind(mr_id)
                 .bind(&approved.user.username)
-                .bind(now())
+                .bind(now)
                 .execute(&self.pool)
                 .await
                 {
@@ -2240,6 +3291,8 @@ impl SyncEngine {
                 }
             }
         }
+
+        rerequested
     }
 
     /// Purge merged/closed MRs that are no longer open on GitLab.
@@ -2250,7 +3303,8 @@ impl SyncEngine {
     ///   → update state to 'merged' and set `state_changed_at` so the frontend
     ///   can show a graceful banner.
     /// Pass 2 (hard-purge): MRs in 'merged'/'closed' state whose
-    ///   `state_changed_at` is older than 24 hours → actually delete from DB.
+    ///   `state_changed_at` is older than the instance's closed-MR retention
+    ///   (24 hours by default) → actually delete from DB.
     ///
     /// Both candidate sets are collected up-front (before the UPDATE) to ensure
     /// soft-purged MRs are not immediately hard-deleted in the same cycle.
@@ -2274,7 +3328,7 @@ impl SyncEngine {
             Vec::new()
         } else if open_mr_ids.is_empty() {
             sqlx::query_as(
-                "SELECT id, iid FROM merge_requests WHERE instance_id = ? AND state = 'opened'",
+                "SELECT id, iid FROM merge_requests WHERE instance_id = ? AND state = 'opened' AND watched = 0",
             )
             .bind(instance_id)
             .fetch_all(&self.pool)
@@ -2283,7 +3337,7 @@ impl SyncEngine {
             let placeholders: Vec<String> =
                 (0..open_mr_ids.len()).map(|_| "?".to_string()).collect();
             let query = format!(
-                "SELECT id, iid FROM merge_requests WHERE instance_id = ? AND state = 'opened' AND id NOT IN ({})",
+                "SELECT id, iid FROM merge_requests WHERE instance_id = ? AND state = 'opened' AND watched = 0 AND id NOT IN ({})",
                 placeholders.join(", ")
             );
             let mut q = sqlx::query_as(&query).bind(instance_id);
@@ -2293,9 +3347,13 @@ impl SyncEngine {
             q.fetch_all(&self.pool).await?
         };
 
-        // Hard-purge candidates: merged/closed MRs whose state_changed_at is >24h ago.
+        // Hard-purge candidates: merged/closed MRs whose state_changed_at is older
+        // than the instance's closed-MR retention (24h by default).
         // MRs without state_changed_at (legacy rows) are also eligible.
-        let cutoff = now() - 24 * 60 * 60; // 24 hours ago
+        let retention_hours = retention::load_policy(&self.pool, instance_id)
+            .await?
+            .closed_mr_retention_hours;
+        let cutoff = now() - retention_hours * 60 * 60;
         let hard_purge_rows: Vec<(i64, i64)> = if open_mr_ids.is_empty() {
             sqlx::query_as(
                 "SELECT id, iid FROM merge_requests WHERE instance_id = ? AND state != 'opened' AND (state_changed_at IS NULL OR state_changed_at <= ?)",
@@ -2321,8 +3379,7 @@ impl SyncEngine {
         // --- Pass 1: Soft-purge — mark opened MRs as merged ---
         if !soft_purge_rows.is_empty() {
             let soft_ids: Vec<i64> = soft_purge_rows.iter().map(|(id, _)| *id).collect();
-            let placeholders: Vec<String> =
-                (0..soft_ids.len()).map(|_| "?".to_string()).collect();
+            let placeholders: Vec<String> = (0..soft_ids.len()).map(|_| "?".to_string()).collect();
             // Backfill merged_at when missing so the "recently merged" window on
             // the My MRs page can surface auto-merged work. GitLab's real merge
             // timestamp isn't known here, but "noticed merged at now" is the
@@ -2340,8 +3397,19 @@ impl SyncEngine {
 
             // Emit updated events so the frontend refetches and sees merged state
             for (mr_id, iid) in &soft_purge_rows {
+                let mut fields = serde_json::Map::new();
+                fields.insert("state".to_string(), "merged".into());
+                self.record_change(
+                    ChangeEntity::MergeRequest,
+                    *mr_id,
+                    instance_id,
+                    ChangeOp::Upsert,
+                    fields,
+                )
+                .await;
                 self.emit_mr_updated(*mr_id, instance_id, *iid, MrUpdateType::Updated);
             }
+            quick_switch::remove(&soft_ids).await;
 
             log::info!(
                 "[sync] Soft-purged {} MRs (marked as merged) for instance {}",
@@ -2366,8 +3434,20 @@ impl SyncEngine {
             0i64
         } else {
             let hard_ids: Vec<i64> = hard_purge_rows.iter().map(|(id, _)| *id).collect();
-            let placeholders: Vec<String> =
-                (0..hard_ids.len()).map(|_| "?".to_string()).collect();
+            // Their queue rows would cascade away unseen; record what was
+            // still waiting to be pushed first.
+            match sync_queue::drop_actions_for_mrs(
+                &self.pool,
+                &hard_ids,
+                "The merge request was merged or closed and removed from the cache before the action was pushed",
+            )
+            .await
+            {
+                Ok(0) => {}
+                Ok(n) => log::info!("[sync] Dropped {} queued action(s) of purged MRs", n),
+                Err(e) => log::warn!("Failed to record actions of purged MRs: {}", e),
+            }
+            let placeholders: Vec<String> = (0..hard_ids.len()).map(|_| "?".to_string()).collect();
             let query = format!(
                 "DELETE FROM merge_requests WHERE id IN ({})",
                 placeholders.join(", ")
@@ -2381,7 +3461,17 @@ impl SyncEngine {
         };
 
         // Emit purged events for each hard-deleted MR
+        let hard_ids: Vec<i64> = hard_purge_rows.iter().map(|(id, _)| *id).collect();
+        quick_switch::remove(&hard_ids).await;
         for (mr_id, iid) in &hard_purge_rows {
+            self.record_change(
+                ChangeEntity::MergeRequest,
+                *mr_id,
+                instance_id,
+                ChangeOp::Delete,
+                Default::default(),
+            )
+            .await;
             self.emit_mr_updated(*mr_id, instance_id, *iid, MrUpdateType::Purged);
         }
 
@@ -2417,6 +3507,11 @@ impl SyncEngine {
     /// queued action types are left untouched. If no matching actions are
     /// pending, this is a no-op.
     async fn flush_actions_by_types(&self, action_types: &[ActionType]) -> Result<(), AppError> {
+        // Leave the actions queued rather than failing them one by one.
+        if offline_mode::is_offline() {
+            return Ok(());
+        }
+
         // Collect pending actions for all requested types
         let mut actions = Vec::new();
         for action_type in action_types {
@@ -2430,7 +3525,7 @@ impl SyncEngine {
         }
 
         // Sort by created_at so actions are processed in order
-        actions.sort_by_key(|a| a.created_at);
+        actions.sort_by_key(|a| (a.created_at, a.id));
 
         eprintln!(
             "[sync] Flushing {} action(s) for {:?}",
@@ -2456,7 +3551,7 @@ impl SyncEngine {
     /// Returns (success_count, errors, api_calls).
     async fn process_actions_resolving_instances(
         &self,
-        actions: &[crate::models::sync_action::SyncAction],
+        actions: &[SyncAction],
     ) -> (i64, Vec<String>, u64) {
         let mut success_count = 0i64;
         let mut errors: Vec<String> = Vec::new();
@@ -2470,25 +3565,45 @@ impl SyncEngine {
         };
 
         let mut clients: HashMap<i64, GitLabClient> = HashMap::new();
+        let policy = self.config.read().await.retry_policy();
 
         for action in actions {
-            // Find the instance for this action's MR
-            let instance_id: Option<i64> = match sqlx::query_scalar(
-                "SELECT instance_id FROM merge_requests WHERE id = ?",
-            )
-            .bind(action.mr_id)
-            .fetch_optional(&self.pool)
-            .await
-            {
-                Ok(id) => id,
+            // Keep batches in order: hold an action back until the earlier
+            // actions of its batch went through. It stays pending.
+            match sync_queue::waiting_on_batch(&self.pool, action).await {
+                Ok(false) => {}
+                Ok(true) => {
+                    eprintln!(
+                        "[sync] Action {} waits for earlier actions of its batch",
+                        action.id
+                    );
+                    continue;
+                }
                 Err(e) => {
                     errors.push(format!(
-                        "Action {}: failed to resolve instance: {}",
+                        "Action {}: failed to check its batch: {}",
                         action.id, e
                     ));
                     continue;
                 }
-            };
+            }
+
+            // Find the instance for this action's MR
+            let instance_id: Option<i64> =
+                match sqlx::query_scalar("SELECT instance_id FROM merge_requests WHERE id = ?")
+                    .bind(action.mr_id)
+                    .fetch_optional(&self.pool)
+                    .await
+                {
+                    Ok(id) => id,
+                    Err(e) => {
+                        errors.push(format!(
+                            "Action {}: failed to resolve instance: {}",
+                            action.id, e
+                        ));
+                        continue;
+                    }
+                };
 
             let Some(instance_id) = instance_id else {
                 eprintln!(
@@ -2535,7 +3650,7 @@ impl SyncEngine {
             }
             let client = clients.get(&instance_id).expect("client inserted above");
 
-            let result = sync_processor::process_action(client, &self.pool, action).await;
+            let result = sync_processor::process_action(client, &self.pool, action, &policy).await;
 
             // Emit action-synced event
             self.emit_event(
@@ -2564,37 +3679,237 @@ impl SyncEngine {
                 );
             }
         }
-
-        let api_calls = clients.values().map(|c| c.call_count()).sum();
-        (success_count, errors, api_calls)
+
+        let api_calls = clients.values().map(|c| c.call_count()).sum();
+        (success_count, errors, api_calls)
+    }
+
+    /// Poll pinned pipeline projects and notify about finished pipelines.
+    ///
+    /// Queries all pinned pipeline projects across all instances and fetches
+    /// each one's latest pipeline (on the default branch for projects using
+    /// the `default_branch` rule). Pipelines that finished since the last poll
+    /// and match the project's notify rule emit `notification:pipeline-changed`,
+    /// with the names of the failed jobs for failed pipelines. The first poll
+    /// per project establishes a baseline without emitting, and muted
+    /// projects never emit.
+    async fn check_pinned_pipeline_statuses(&self) {
+        // Check notification settings first
+        let settings =
+            match crate::db::notification_settings::get_notification_settings(&self.pool).await {
+                Ok(s) => s,
+                Err(e) => {
+                    log::warn!(
+                        "Failed to read notification settings for pipeline check: {}",
+                        e
+                    );
+                    return;
+                }
+            };
+
+        if !settings.pipeline_status_pinned {
+            return;
+        }
+
+        // Get all instances
+        let instances = match self.get_gitlab_instances().await {
+            Ok(i) => i,
+            Err(e) => {
+                log::warn!("Failed to get instances for pipeline check: {}", e);
+                return;
+            }
+        };
+
+        for instance in &instances {
+            let token = match &instance.token {
+                Some(t) => t.clone(),
+                None => continue,
+            };
+
+            let client = match GitLabClient::new(GitLabClientConfig {
+                base_url: instance.url.clone(),
+                token,
+                timeout_secs: 30,
+            }) {
+                Ok(c) => c,
+                Err(_) => continue,
+            };
+
+            // Query pinned pipeline projects for this instance
+            let pinned_projects: Vec<PinnedPipelineProject> = sqlx::query_as(
+                r#"
+                SELECT pp.project_id, p.name_with_namespace, p.web_url,
+                       pp.notify_rule, pp.default_branch
+                FROM pipeline_projects pp
+                JOIN projects p ON p.id = pp.project_id AND p.instance_id = pp.instance_id
+                WHERE pp.instance_id = ? AND pp.pinned = 1
+                "#,
+            )
+            .bind(instance.id)
+            .fetch_all(&self.pool)
+            .await
+            .unwrap_or_default();
+
+            if pinned_projects.is_empty() {
+                continue;
+            }
+            // Muted projects are still polled so their baseline stays current.
+            let muted = muted_projects::muted_project_ids(&self.pool, instance.id, now())
+                .await
+                .unwrap_or_default();
+
+            eprintln!(
+                "[sync] Checking pipeline statuses for {} pinned project(s) on {}",
+                pinned_projects.len(),
+                instance.url
+            );
+
+            // Fetch latest pipeline for each pinned project
+            let futures = pinned_projects.iter().map(|project| {
+                let client = client.clone();
+                let pool = self.pool.clone();
+                let instance_id = instance.id;
+                let project_id = project.project_id;
+                let rule = NotifyRule::from_stored(&project.notify_rule);
+                let cached_branch = project.default_branch.clone();
+                async move {
+                    if rule != NotifyRule::DefaultBranch {
+                        let result = client.get_latest_pipeline(project_id).await;
+                        return (project_id, rule, None, result);
+                    }
+                    let default_branch = match cached_branch {
+                        Some(branch) => Some(branch),
+                        None => match client.get_project(project_id).await {
+                            Ok(p) => {
+                                if let Some(branch) = &p.default_branch {
+                                    let _ = pipeline_project::set_default_branch(
+                                        &pool,
+                                        project_id,
+                                        instance_id,
+                                        branch,
+                                    )
+                                    .await;
+                                }
+                                p.default_branch
+                            }
+                            Err(e) => return (project_id, rule, None, Err(e)),
+                        },
+                    };
+                    let result = match &default_branch {
+                        Some(branch) => {
+                            client.get_latest_pipeline_for_ref(project_id, branch).await
+                        }
+                        None => Ok(None),
+                    };
+                    (project_id, rule, default_branch, result)
+                }
+            });
+
+            let results = futures::future::join_all(futures).await;
+
+            for (project_id, rule, default_branch, result) in results {
+                let project_name = pinned_projects
+                    .iter()
+                    .find(|p| p.project_id == project_id)
+                    .map(|p| p.name_with_namespace.as_str())
+                    .unwrap_or("unknown");
+
+                let pipeline = match result {
+                    Ok(Some(p)) => p,
+                    Ok(None) => {
+                        eprintln!(
+                            "[sync] No pipelines found for project {} ({})",
+                            project_name, project_id
+                        );
+                        continue;
+                    }
+                    Err(e) => {
+                        eprintln!(
+                            "[sync] Failed to fetch pipeline for project {} ({}): {}",
+                            project_name, project_id, e
+                        );
+                        continue;
+                    }
+                };
+
+                let key = (instance.id, project_id);
+                let previous = self
+                    .previous_pipeline_statuses
+                    .read()
+                    .await
+                    .get(&key)
+                    .cloned();
+
+                if !muted.contains(&project_id)
+                    && pipeline_notify::should_notify(
+                        rule,
+                        previous.as_ref(),
+                        &pipeline,
+                        default_branch.as_deref(),
+                    )
+                {
+                    eprintln!(
+                        "[sync] Pipeline {} finished for {}: {} (ref: {})",
+                        pipeline.id, project_name, pipeline.status, pipeline.ref_name
+                    );
+
+                    let failed_jobs = if pipeline.status == "failed" {
+                        match client.get_pipeline_jobs(project_id, pipeline.id).await {
+                            Ok(jobs) => pipeline_notify::failed_job_names(&jobs),
+                            Err(e) => {
+                                log::warn!(
+                                    "Failed to fetch jobs of pipeline {}: {}",
+                                    pipeline.id,
+                                    e
+                                );
+                                Vec::new()
+                            }
+                        }
+                    } else {
+                        Vec::new()
+                    };
+
+                    self.emit_event(
+                        PIPELINE_STATUS_CHANGED_EVENT,
+                        &PipelineStatusChangedPayload {
+                            project_name: project_name.to_string(),
+                            old_status: previous.map(|p| p.status).unwrap_or_default(),
+                            new_status: pipeline.status.clone(),
+                            ref_name: pipeline.ref_name.clone(),
+                            web_url: pipeline.web_url.clone(),
+                            instance_id: instance.id,
+                            project_id,
+                            pipeline_id: pipeline.id,
+                            failed_jobs,
+                        },
+                    );
+                } else if previous.is_none() {
+                    eprintln!(
+                        "[sync] Pipeline baseline set for {}: {} (ref: {})",
+                        project_name, pipeline.status, pipeline.ref_name
+                    );
+                }
+
+                // Store the latest pipeline (first fetch establishes baseline)
+                self.previous_pipeline_statuses
+                    .write()
+                    .await
+                    .insert(key, SeenPipeline::from(&pipeline));
+            }
+        }
     }
 
-    /// Check pinned pipeline project statuses and emit notification events on transitions.
+    /// Poll pinned projects with manual-gate notifications on and notify
+    /// about manual jobs newly waiting on their default branch.
     ///
-    /// Queries all pinned pipeline projects across all instances, fetches their
-    /// latest pipeline status from GitLab, compares with the previously stored
-    /// status, and emits `notification:pipeline-changed` for any transitions.
-    /// The first fetch per project establishes a baseline without emitting.
-    async fn check_pinned_pipeline_statuses(&self) {
-        // Check notification settings first
-        let settings =
-            match crate::db::notification_settings::get_notification_settings(&self.pool).await {
-                Ok(s) => s,
-                Err(e) => {
-                    log::warn!("Failed to read notification settings for pipeline check: {}", e);
-                    return;
-                }
-            };
-
-        if !settings.pipeline_status_pinned {
-            return;
-        }
-
-        // Get all instances
+    /// Emits `notification:manual-gate` once per new job. As with pipeline
+    /// statuses, the first poll per project only sets the baseline and muted
+    /// projects are polled without emitting.
+    async fn check_manual_gates(&self) {
         let instances = match self.get_gitlab_instances().await {
             Ok(i) => i,
             Err(e) => {
-                log::warn!("Failed to get instances for pipeline check: {}", e);
+                log::warn!("Failed to get instances for manual gate check: {}", e);
                 return;
             }
         };
@@ -2605,22 +3920,12 @@ impl SyncEngine {
                 None => continue,
             };
 
-            let client = match GitLabClient::new(GitLabClientConfig {
-                base_url: instance.url.clone(),
-                token,
-                timeout_secs: 30,
-            }) {
-                Ok(c) => c,
-                Err(_) => continue,
-            };
-
-            // Query pinned pipeline projects for this instance
-            let pinned_projects: Vec<PinnedPipelineProject> = sqlx::query_as(
+            let projects: Vec<ManualGateProject> = sqlx::query_as(
                 r#"
-                SELECT pp.project_id, p.name_with_namespace, p.web_url
+                SELECT pp.project_id, p.name_with_namespace, pp.default_branch
                 FROM pipeline_projects pp
                 JOIN projects p ON p.id = pp.project_id AND p.instance_id = pp.instance_id
-                WHERE pp.instance_id = ? AND pp.pinned = 1
+                WHERE pp.instance_id = ? AND pp.pinned = 1 AND pp.notify_manual_gates = 1
                 "#,
             )
             .bind(instance.id)
@@ -2628,92 +3933,109 @@ impl SyncEngine {
             .await
             .unwrap_or_default();
 
-            if pinned_projects.is_empty() {
+            if projects.is_empty() {
                 continue;
             }
 
-            eprintln!(
-                "[sync] Checking pipeline statuses for {} pinned project(s) on {}",
-                pinned_projects.len(),
-                instance.url
-            );
-
-            // Fetch latest pipeline for each pinned project
-            let futures = pinned_projects.iter().map(|project| {
-                let client = client.clone();
-                let project_id = project.project_id;
-                async move { (project_id, client.get_latest_pipeline(project_id).await) }
-            });
+            let client = match GitLabClient::new(GitLabClientConfig {
+                base_url: instance.url.clone(),
+                token,
+                timeout_secs: 30,
+            }) {
+                Ok(c) => c,
+                Err(_) => continue,
+            };
+            let muted = muted_projects::muted_project_ids(&self.pool, instance.id, now())
+                .await
+                .unwrap_or_default();
 
+            let futures = projects
+                .iter()
+                .map(|project| self.waiting_manual_jobs(&client, instance.id, project));
             let results = futures::future::join_all(futures).await;
 
-            for (project_id, result) in results {
-                let project_name = pinned_projects
-                    .iter()
-                    .find(|p| p.project_id == project_id)
-                    .map(|p| p.name_with_namespace.as_str())
-                    .unwrap_or("unknown");
-
-                let pipeline = match result {
-                    Ok(Some(p)) => p,
-                    Ok(None) => {
-                        eprintln!(
-                            "[sync] No pipelines found for project {} ({})",
-                            project_name, project_id
-                        );
-                        continue;
-                    }
+            for (project, result) in projects.iter().zip(results) {
+                let (default_branch, waiting) = match result {
+                    Ok(Some(found)) => found,
+                    Ok(None) => continue,
                     Err(e) => {
                         eprintln!(
-                            "[sync] Failed to fetch pipeline for project {} ({}): {}",
-                            project_name, project_id, e
+                            "[sync] Failed to fetch manual jobs for project {} ({}): {}",
+                            project.name_with_namespace, project.project_id, e
                         );
                         continue;
                     }
                 };
 
-                let key = (instance.id, project_id);
-                let mut prev_statuses = self.previous_pipeline_statuses.write().await;
+                let key = (instance.id, project.project_id);
+                let seen = self.seen_manual_gates.read().await.get(&key).cloned();
 
-                if let Some(old_status) = prev_statuses.get(&key) {
-                    if *old_status != pipeline.status {
+                if !muted.contains(&project.project_id) {
+                    for job in manual_jobs::new_gates(&waiting, &default_branch, seen.as_ref()) {
                         eprintln!(
-                            "[sync] Pipeline status changed for {}: {} → {}",
-                            project_name, old_status, pipeline.status
+                            "[sync] Manual job {} waiting in {} (ref: {})",
+                            job.name, project.name_with_namespace, default_branch
                         );
-
                         self.emit_event(
-                            PIPELINE_STATUS_CHANGED_EVENT,
-                            &PipelineStatusChangedPayload {
-                                project_name: project_name.to_string(),
-                                old_status: old_status.clone(),
-                                new_status: pipeline.status.clone(),
-                                ref_name: pipeline.ref_name.clone(),
-                                web_url: pipeline.web_url.clone(),
+                            MANUAL_GATE_EVENT,
+                            &ManualGatePayload {
                                 instance_id: instance.id,
-                                project_id,
-                                pipeline_id: pipeline.id,
+                                project_id: project.project_id,
+                                project_name: project.name_with_namespace.clone(),
+                                pipeline_id: job.pipeline.as_ref().map_or(0, |p| p.id),
+                                job_id: job.id,
+                                job_name: job.name.clone(),
+                                ref_name: default_branch.clone(),
+                                web_url: job.web_url.clone(),
                             },
                         );
-                    } else {
-                        eprintln!(
-                            "[sync] Pipeline status unchanged for {}: {} (ref: {})",
-                            project_name, pipeline.status, pipeline.ref_name
-                        );
                     }
-                } else {
-                    eprintln!(
-                        "[sync] Pipeline baseline set for {}: {} (ref: {})",
-                        project_name, pipeline.status, pipeline.ref_name
-                    );
                 }
 
-                // Store current status (first fetch establishes baseline)
-                prev_statuses.insert(key, pipeline.status);
+                let waiting_ids = waiting
+                    .iter()
+                    .filter(|job| job.ref_name.as_deref() == Some(default_branch.as_str()))
+                    .map(|job| job.id)
+                    .collect();
+                self.seen_manual_gates
+                    .write()
+                    .await
+                    .insert(key, waiting_ids);
             }
         }
     }
 
+    /// Default branch of `project` and the manual jobs waiting in it, or
+    /// `None` when the project has no default branch (empty repository).
+    async fn waiting_manual_jobs(
+        &self,
+        client: &GitLabClient,
+        instance_id: i64,
+        project: &ManualGateProject,
+    ) -> Result<Option<(String, Vec<GitLabJob>)>, AppError> {
+        let default_branch = match &project.default_branch {
+            Some(branch) => branch.clone(),
+            None => {
+                let fetched = client.get_project(project.project_id).await?;
+                let Some(branch) = fetched.default_branch else {
+                    return Ok(None);
+                };
+                let _ = pipeline_project::set_default_branch(
+                    &self.pool,
+                    project.project_id,
+                    instance_id,
+                    &branch,
+                )
+                .await;
+                branch
+            }
+        };
+        let jobs = client
+            .list_manual_jobs(project.project_id, MANUAL_JOBS_PER_PROJECT)
+            .await?;
+        Ok(Some((default_branch, manual_jobs::waiting_jobs(jobs))))
+    }
+
     /// Check if an MR is ready to merge based on its DB state.
     ///
     /// Ready condition: approval_status = 'approved' AND approvals_count >= approvals_required
@@ -2759,13 +4081,62 @@ impl SyncEngine {
         result
     }
 
+    /// Notify about MRs awaiting my review that became at risk or breached
+    /// their review SLA since the previous check. MRs not seen by a previous
+    /// check only record their state, so starting the app doesn't replay
+    /// every overdue review.
+    async fn check_review_slas(&self) {
+        let config = self.config.read().await.review_sla.clone();
+        let statuses = match review_sla::statuses(&self.pool, &config, now(), chrono::Local).await {
+            Ok(statuses) => statuses,
+            Err(e) => {
+                log::warn!("Failed to compute review SLA status: {}", e);
+                return;
+            }
+        };
+
+        let mut states = self.review_sla_states.write().await;
+        let previous = std::mem::take(&mut *states);
+        for status in statuses {
+            states.insert(status.mr_id, status.state);
+            if previous
+                .get(&status.mr_id)
+                .is_none_or(|&prev| prev >= status.state)
+            {
+                continue;
+            }
+            let row: Option<(String, String)> =
+                sqlx::query_as("SELECT title, web_url FROM merge_requests WHERE id = ?")
+                    .bind(status.mr_id)
+                    .fetch_optional(&self.pool)
+                    .await
+                    .unwrap_or_default();
+            let Some((title, web_url)) = row else {
+                continue;
+            };
+            self.emit_event(
+                REVIEW_SLA_EVENT,
+                &ReviewSlaPayload {
+                    mr_id: status.mr_id,
+                    title,
+                    project_name: extract_project_path(&web_url),
+                    web_url,
+                    state: status.state,
+                    due_at: status.due_at,
+                },
+            );
+        }
+    }
+
     /// Check for MR ready-to-merge transitions and emit notification events.
     ///
     /// Compares pre-sync state with post-sync state. Only emits for MRs where
-    /// the current user is an assignee and the MR transitioned from not-ready
-    /// to ready, and only if notification settings have mr_ready_to_merge enabled.
+    /// the current user is an assignee (or which the user watches) and the MR
+    /// transitioned from not-ready to ready, and only if notification settings
+    /// have mr_ready_to_merge enabled. MRs of muted projects are skipped.
     async fn check_mr_ready_transitions(
         &self,
+        instance_id: i64,
         mr_ids: &[i64],
         pre_sync_ready: &std::collections::HashMap<i64, bool>,
         mrs: &[GitLabMergeRequest],
@@ -2796,16 +4167,28 @@ impl SyncEngine {
 
         // Get post-sync ready states
         let post_sync_ready = self.get_ready_states(mr_ids).await;
+        let watched_ids: HashSet<i64> = crate::db::watched::watched_ids(&self.pool, instance_id)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        let muted = muted_projects::muted_project_ids(&self.pool, instance_id, now())
+            .await
+            .unwrap_or_default();
 
         for mr in mrs {
             let mr_id = mr.id;
+            if muted.contains(&mr.project_id) {
+                continue;
+            }
 
-            // Only notify for MRs where the current user is an assignee
+            // Only notify for MRs where the current user is an assignee, or
+            // that the user explicitly watches
             let is_assignee = mr
                 .assignees
                 .as_ref()
                 .is_some_and(|a| a.iter().any(|u| u.username == username));
-            if !is_assignee {
+            if !is_assignee && !watched_ids.contains(&mr_id) {
                 continue;
             }
 
@@ -2848,9 +4231,15 @@ impl SyncEngine {
     /// - `conflict` → drop the claim (cannot recover automatically)
     /// - anything else (ci_must_pass, checking, not_approved, ...) → wait
     ///
+    /// Merge-when-ready claims also wait while `mergeable` until their own
+    /// conditions hold, and emit a notification once merged.
+    ///
     /// MR state changes are reflected in the local DB so the frontend can see
     /// the merge land via the existing `mr-updated` event flow.
     pub async fn process_auto_merge_claims(&self) {
+        if offline_mode::is_offline() {
+            return;
+        }
         let claims = match auto_merge::list_active_claims_with_mr(&self.pool).await {
             Ok(c) => c,
             Err(e) => {
@@ -2915,7 +4304,9 @@ impl SyncEngine {
                 if let Err(e) = auto_merge::delete_claim(&self.pool, claim.mr_id).await {
                     log::warn!(
                         "[auto-merge] Failed to drop claim for !{} (state={}): {}",
-                        claim.iid, claim.state, e
+                        claim.iid,
+                        claim.state,
+                        e
                     );
                 }
                 let _ = self
@@ -2945,7 +4336,8 @@ impl SyncEngine {
             let Some(client) = clients.get(&claim.instance_id) else {
                 log::warn!(
                     "[auto-merge] No client for instance {} (mr !{})",
-                    claim.instance_id, claim.iid
+                    claim.instance_id,
+                    claim.iid
                 );
                 let _ = self
                     .log_sync_operation(
@@ -2972,16 +4364,61 @@ impl SyncEngine {
         claim: &auto_merge::AutoMergeClaimWithMr,
         client: &GitLabClient,
     ) {
-        // Fetch the freshest detailed_merge_status from GitLab.
-        let status = match client.get_merge_request(claim.project_id, claim.iid).await {
-            Ok(gl) => gl
-                .detailed_merge_status
-                .unwrap_or_else(|| "unknown".into()),
+        let conditions = MergeConditions::from_column(claim.conditions.as_deref());
+
+        // Fetch the freshest detailed_merge_status from GitLab. For
+        // merge-when-ready claims, the local conditions are checked too once
+        // GitLab itself has nothing left to block on.
+        let checked = async {
+            let gl = client
+                .get_merge_request(claim.project_id, claim.iid)
+                .await?;
+            let status = gl.detailed_merge_status.unwrap_or_else(|| "unknown".into());
+            let unmet = match &conditions {
+                Some(conditions) if status == "mergeable" => Some(
+                    merge_when_ready::check_conditions(
+                        client,
+                        claim.project_id,
+                        claim.iid,
+                        conditions,
+                        gl.head_pipeline.map(|p| p.status),
+                    )
+                    .await?,
+                ),
+                _ => None,
+            };
+            Ok::<_, AppError>((status, unmet))
+        }
+        .await;
+        let status = match checked {
+            Ok((status, unmet)) => {
+                if conditions.is_some() {
+                    let unmet_json = unmet.as_ref().and_then(|u| serde_json::to_string(u).ok());
+                    let _ = auto_merge::record_unmet_conditions(
+                        &self.pool,
+                        claim.mr_id,
+                        unmet_json.as_deref(),
+                    )
+                    .await;
+                }
+                match unmet {
+                    Some(unmet) if !unmet.is_empty() => {
+                        let labels: Vec<&str> = unmet.iter().map(|c| c.label()).collect();
+                        log::info!(
+                            "[auto-merge] !{} mergeable but waiting for {}",
+                            claim.iid,
+                            labels.join(", ")
+                        );
+                        merge_when_ready::CONDITIONS_UNMET_STATUS.to_string()
+                    }
+                    _ => status,
+                }
+            }
             Err(e) => {
                 let msg = e.to_string();
-                let _ = auto_merge::record_attempt(
-                    &self.pool, claim.mr_id, now(), None, Some(&msg),
-                ).await;
+                let _ =
+                    auto_merge::record_attempt(&self.pool, claim.mr_id, now(), None, Some(&msg))
+                        .await;
                 let _ = self
                     .log_sync_operation(
                         "auto_merge",
@@ -2993,7 +4430,8 @@ impl SyncEngine {
                     .await;
                 log::warn!(
                     "[auto-merge] check_merge_status failed for !{}: {}",
-                    claim.iid, msg
+                    claim.iid,
+                    msg
                 );
                 self.emit_event(
                     AUTO_MERGE_UPDATED_EVENT,
@@ -3010,9 +4448,8 @@ impl SyncEngine {
 
         // Record the observed status before deciding what to do — useful even
         // when we then drop the claim, so the UI can show why.
-        let _ = auto_merge::record_attempt(
-            &self.pool, claim.mr_id, now(), Some(&status), None,
-        ).await;
+        let _ =
+            auto_merge::record_attempt(&self.pool, claim.mr_id, now(), Some(&status), None).await;
 
         match status.as_str() {
             "mergeable" => {
@@ -3025,9 +4462,15 @@ impl SyncEngine {
                         None,
                     )
                     .await;
-                eprintln!("[auto-merge] !{} is mergeable, calling merge API", claim.iid);
+                eprintln!(
+                    "[auto-merge] !{} is mergeable, calling merge API",
+                    claim.iid
+                );
                 log::info!("[auto-merge] !{} is mergeable, merging", claim.iid);
-                match client.merge_merge_request(claim.project_id, claim.iid).await {
+                match client
+                    .merge_merge_request(claim.project_id, claim.iid)
+                    .await
+                {
                     Ok(()) => {
                         // Reflect merged state in the local DB.
                         let now_ts = now();
@@ -3048,7 +4491,8 @@ impl SyncEngine {
                         if let Err(e) = auto_merge::delete_claim(&self.pool, claim.mr_id).await {
                             log::warn!(
                                 "[auto-merge] Failed to drop claim after merge for !{}: {}",
-                                claim.iid, e
+                                claim.iid,
+                                e
                             );
                         }
                         let _ = self
@@ -3060,6 +4504,17 @@ impl SyncEngine {
                                 None,
                             )
                             .await;
+                        let mut fields = serde_json::Map::new();
+                        fields.insert("state".to_string(), "merged".into());
+                        fields.insert("mergedAt".to_string(), now_ts.into());
+                        self.record_change(
+                            ChangeEntity::MergeRequest,
+                            claim.mr_id,
+                            claim.instance_id,
+                            ChangeOp::Upsert,
+                            fields,
+                        )
+                        .await;
                         self.emit_mr_updated(
                             claim.mr_id,
                             claim.instance_id,
@@ -3075,6 +4530,17 @@ impl SyncEngine {
                                 last_error: None,
                             },
                         );
+                        if conditions.is_some() {
+                            self.emit_event(
+                                MERGED_WHEN_READY_EVENT,
+                                &MergedWhenReadyPayload {
+                                    mr_id: claim.mr_id,
+                                    title: claim.title.clone(),
+                                    project_name: extract_project_path(&claim.web_url),
+                                    web_url: claim.web_url.clone(),
+                                },
+                            );
+                        }
                     }
                     Err(e) => {
                         let msg = e.to_string();
@@ -3089,13 +4555,23 @@ impl SyncEngine {
                                 "[auto-merge] !{} merge failed (FF required) — triggering rebase",
                                 claim.iid
                             );
-                            self.trigger_rebase_for_claim(claim, client, &status, "merge rejected — fast-forward required")
-                                .await;
+                            self.trigger_rebase_for_claim(
+                                claim,
+                                client,
+                                &status,
+                                "merge rejected — fast-forward required",
+                            )
+                            .await;
                             return;
                         }
                         let _ = auto_merge::record_attempt(
-                            &self.pool, claim.mr_id, now(), Some(&status), Some(&msg),
-                        ).await;
+                            &self.pool,
+                            claim.mr_id,
+                            now(),
+                            Some(&status),
+                            Some(&msg),
+                        )
+                        .await;
                         let _ = self
                             .log_sync_operation(
                                 "auto_merge",
@@ -3135,18 +4611,13 @@ impl SyncEngine {
                         None,
                     )
                     .await;
-                eprintln!(
-                    "[auto-merge] !{} has conflicts, dropping claim",
-                    claim.iid
-                );
-                log::info!(
-                    "[auto-merge] !{} has conflicts, dropping claim",
-                    claim.iid
-                );
+                eprintln!("[auto-merge] !{} has conflicts, dropping claim", claim.iid);
+                log::info!("[auto-merge] !{} has conflicts, dropping claim", claim.iid);
                 if let Err(e) = auto_merge::delete_claim(&self.pool, claim.mr_id).await {
                     log::warn!(
                         "[auto-merge] Failed to drop claim after conflict for !{}: {}",
-                        claim.iid, e
+                        claim.iid,
+                        e
                     );
                 }
                 self.emit_event(
@@ -3206,21 +4677,12 @@ impl SyncEngine {
                 "auto_merge",
                 "info",
                 Some(claim.mr_id),
-                Some(format!(
-                    "!{}: triggering rebase ({})",
-                    claim.iid, reason
-                )),
+                Some(format!("!{}: triggering rebase ({})", claim.iid, reason)),
                 None,
             )
             .await;
-        eprintln!(
-            "[auto-merge] !{} triggering rebase ({})",
-            claim.iid, reason
-        );
-        log::info!(
-            "[auto-merge] !{} triggering rebase ({})",
-            claim.iid, reason
-        );
+        eprintln!("[auto-merge] !{} triggering rebase ({})", claim.iid, reason);
+        log::info!("[auto-merge] !{} triggering rebase ({})", claim.iid, reason);
         if let Err(e) = client
             .rebase_merge_request(claim.project_id, claim.iid)
             .await
@@ -3281,6 +4743,9 @@ impl SyncEngine {
     /// Process all auto-run claims: play armed manual jobs whose pipeline
     /// finished successfully, drop claims whose pipeline failed.
     pub async fn process_auto_run_claims(&self) {
+        if offline_mode::is_offline() {
+            return;
+        }
         let claims = match auto_run::list_active_claims(&self.pool).await {
             Ok(c) => c,
             Err(e) => {
@@ -3291,7 +4756,10 @@ impl SyncEngine {
         if claims.is_empty() {
             return;
         }
-        eprintln!("[auto-run] Processor running — {} active claim(s)", claims.len());
+        eprintln!(
+            "[auto-run] Processor running — {} active claim(s)",
+            claims.len()
+        );
 
         // Build one GitLab client per instance and reuse across claims.
         let instances = match self.get_gitlab_instances().await {
@@ -3317,7 +4785,8 @@ impl SyncEngine {
             let Some(client) = clients.get(&claim.instance_id) else {
                 log::warn!(
                     "[auto-run] No client for instance {} (job {})",
-                    claim.instance_id, claim.job_id
+                    claim.instance_id,
+                    claim.job_id
                 );
                 continue;
             };
@@ -3331,7 +4800,10 @@ impl SyncEngine {
         claim: &auto_run::AutoRunClaimRow,
         client: &GitLabClient,
     ) {
-        let pipeline = match client.get_pipeline(claim.project_id, claim.pipeline_id).await {
+        let pipeline = match client
+            .get_pipeline(claim.project_id, claim.pipeline_id)
+            .await
+        {
             Ok(p) => p,
             Err(e) => {
                 self.record_auto_run_error(claim, &e.to_string()).await;
@@ -3346,7 +4818,10 @@ impl SyncEngine {
         // A job played manually mid-pipeline is still disarmed once the
         // pipeline settles and the fetched status shows it left `manual`.
         let job_status = if matches!(pipeline.status.as_str(), "success" | "manual") {
-            let found = match client.get_pipeline_jobs(claim.project_id, claim.pipeline_id).await {
+            let found = match client
+                .get_pipeline_jobs(claim.project_id, claim.pipeline_id)
+                .await
+            {
                 Ok(jobs) => match jobs.into_iter().find(|j| j.id == claim.job_id) {
                     Some(j) => Some(j.status),
                     // Bridges live on a separate endpoint, so fall back to it
@@ -3378,9 +4853,14 @@ impl SyncEngine {
                 );
                 let status_changed = claim.last_status.as_deref() != Some(pipeline.status.as_str());
                 let _ = auto_run::record_status(
-                    &self.pool, claim.instance_id, claim.project_id, claim.job_id,
-                    now(), &pipeline.status,
-                ).await;
+                    &self.pool,
+                    claim.instance_id,
+                    claim.project_id,
+                    claim.job_id,
+                    now(),
+                    &pipeline.status,
+                )
+                .await;
                 if status_changed {
                     self.emit_auto_run_updated(claim, false, false, Some(pipeline.status), None);
                 }
@@ -3411,10 +4891,15 @@ impl SyncEngine {
                         // the error path. Acceptable — local deletes are
                         // effectively reliable.
                         let _ = auto_run::delete_claim(
-                            &self.pool, claim.instance_id, claim.project_id, claim.job_id,
-                        ).await;
+                            &self.pool,
+                            claim.instance_id,
+                            claim.project_id,
+                            claim.job_id,
+                        )
+                        .await;
                         self.emit_auto_run_updated(claim, true, true, Some(pipeline.status), None);
-                        self.emit_auto_run_notification(claim, true, Some(job.web_url)).await;
+                        self.emit_auto_run_notification(claim, true, Some(job.web_url))
+                            .await;
                     }
                     Err(e) => self.record_auto_run_error(claim, &e.to_string()).await,
                 }
@@ -3437,10 +4922,15 @@ impl SyncEngine {
                     )
                     .await;
                 let _ = auto_run::delete_claim(
-                    &self.pool, claim.instance_id, claim.project_id, claim.job_id,
-                ).await;
+                    &self.pool,
+                    claim.instance_id,
+                    claim.project_id,
+                    claim.job_id,
+                )
+                .await;
                 self.emit_auto_run_updated(claim, true, false, Some(pipeline.status.clone()), None);
-                self.emit_auto_run_notification(claim, false, Some(pipeline.web_url)).await;
+                self.emit_auto_run_notification(claim, false, Some(pipeline.web_url))
+                    .await;
             }
             crate::services::auto_run::AutoRunDecision::DisarmJobGone => {
                 eprintln!(
@@ -3448,8 +4938,12 @@ impl SyncEngine {
                     claim.job_name, claim.job_id, job_status
                 );
                 let _ = auto_run::delete_claim(
-                    &self.pool, claim.instance_id, claim.project_id, claim.job_id,
-                ).await;
+                    &self.pool,
+                    claim.instance_id,
+                    claim.project_id,
+                    claim.job_id,
+                )
+                .await;
                 self.emit_auto_run_updated(claim, true, false, Some(pipeline.status), None);
             }
         }
@@ -3458,16 +4952,30 @@ impl SyncEngine {
     /// Record an API error for a claim; disarm + notify after 10 consecutive
     /// failures so a dead pipeline doesn't poll forever.
     async fn record_auto_run_error(&self, claim: &auto_run::AutoRunClaimRow, msg: &str) {
-        log::warn!("[auto-run] job {} ({}): {}", claim.job_id, claim.job_name, msg);
+        log::warn!(
+            "[auto-run] job {} ({}): {}",
+            claim.job_id,
+            claim.job_name,
+            msg
+        );
         let attempts = auto_run::record_error(
-            &self.pool, claim.instance_id, claim.project_id, claim.job_id, now(), msg,
+            &self.pool,
+            claim.instance_id,
+            claim.project_id,
+            claim.job_id,
+            now(),
+            msg,
         )
         .await
         .unwrap_or(0);
         if attempts >= 10 {
             let _ = auto_run::delete_claim(
-                &self.pool, claim.instance_id, claim.project_id, claim.job_id,
-            ).await;
+                &self.pool,
+                claim.instance_id,
+                claim.project_id,
+                claim.job_id,
+            )
+            .await;
             self.emit_auto_run_updated(claim, true, false, None, Some(msg.to_string()));
             self.emit_auto_run_notification(claim, false, None).await;
         } else {
@@ -3661,6 +5169,16 @@ struct PinnedPipelineProject {
     name_with_namespace: String,
     #[allow(dead_code)]
     web_url: String,
+    notify_rule: String,
+    default_branch: Option<String>,
+}
+
+/// Pinned pipeline project with manual-gate notifications on.
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct ManualGateProject {
+    project_id: i64,
+    name_with_namespace: String,
+    default_branch: Option<String>,
 }
 
 /// Database row for GitLab instance.
@@ -3735,6 +5253,134 @@ mod tests {
         assert_eq!(config.max_mrs_per_sync, 100);
     }
 
+    #[test]
+    fn test_disabled_stages_validation() {
+        let mut config = SyncConfig::default();
+        let json = serde_json::json!({ "1": ["diff"] });
+        config.disabled_stages = serde_json::from_value(json).unwrap();
+        assert_eq!(config.disabled_stages[&1], vec!["diff"]);
+        assert!(config.validate_disabled_stages().is_ok());
+
+        config
+            .disabled_stages
+            .insert(2, vec!["metadata".to_string()]);
+        assert!(config.validate_disabled_stages().is_err());
+
+        config
+            .disabled_stages
+            .insert(2, vec!["pipelines".to_string()]);
+        assert!(config.validate_disabled_stages().is_err());
+    }
+
+    #[test]
+    fn test_instance_config_overrides_global() {
+        let global = SyncConfig::default();
+        let mut instance = InstanceSyncConfig::default_for(1);
+        assert!(validate_instance_config(&instance).is_ok());
+        assert_eq!(
+            global.interval_for(&instance),
+            Duration::from_secs(DEFAULT_SYNC_INTERVAL_SECS)
+        );
+        assert_eq!(global.max_mrs_for(&instance), 100);
+
+        instance.interval_secs = Some(900);
+        instance.max_mrs_per_sync = Some(20);
+        assert_eq!(global.interval_for(&instance), Duration::from_secs(900));
+        assert_eq!(global.max_mrs_for(&instance), 20);
+
+        for invalid in [
+            InstanceSyncConfig {
+                interval_secs: Some(10),
+                ..instance.clone()
+            },
+            InstanceSyncConfig {
+                max_mrs_per_sync: Some(0),
+                ..instance.clone()
+            },
+            InstanceSyncConfig {
+                sync_authored: false,
+                sync_reviewing: false,
+                sync_assigned: false,
+                ..instance.clone()
+            },
+        ] {
+            assert!(validate_instance_config(&invalid).is_err(), "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_instance_sync_due() {
+        let interval = Duration::from_secs(600);
+        assert!(instance_sync_due(None, interval));
+        assert!(!instance_sync_due(Some(Duration::from_secs(300)), interval));
+        // A tick landing just before the interval is over still syncs.
+        assert!(instance_sync_due(Some(Duration::from_secs(598)), interval));
+        assert!(instance_sync_due(Some(Duration::from_secs(900)), interval));
+    }
+
+    #[test]
+    fn test_incremental_since() {
+        let cursor = SyncCursor {
+            last_synced_at: 10_000,
+            last_full_sync_at: 9_000,
+        };
+        assert_eq!(incremental_since(None, 10_300, false), None);
+        assert_eq!(
+            incremental_since(Some(cursor), 10_300, false),
+            Some(10_000 - CURSOR_OVERLAP_SECS)
+        );
+        assert_eq!(incremental_since(Some(cursor), 10_300, true), None);
+        // A full refresh is due again.
+        assert_eq!(
+            incremental_since(Some(cursor), 9_000 + FULL_LIST_REFRESH_SECS, false),
+            None
+        );
+    }
+
+    #[test]
+    fn test_batched_state_changed() {
+        let state = BatchedMrState {
+            approvals: crate::services::gitlab_client::MergeRequestApprovals {
+                approved: false,
+                approvals_required: 2,
+                approvals_left: 1,
+                approved_by: Vec::new(),
+            },
+            head_pipeline_status: Some("success".to_string()),
+        };
+        assert!(!batched_state_changed(
+            Some(1),
+            Some(2),
+            Some("success"),
+            &state
+        ));
+        assert!(batched_state_changed(
+            Some(0),
+            Some(2),
+            Some("success"),
+            &state
+        ));
+        assert!(batched_state_changed(
+            Some(1),
+            Some(2),
+            Some("running"),
+            &state
+        ));
+        assert!(batched_state_changed(None, None, None, &state));
+
+        // No pipeline in the batch says nothing about the cached one.
+        let no_pipeline = BatchedMrState {
+            head_pipeline_status: None,
+            ..state
+        };
+        assert!(!batched_state_changed(
+            Some(1),
+            Some(2),
+            Some("failed"),
+            &no_pipeline
+        ));
+    }
+
     #[test]
     fn test_parse_iso_timestamp() {
         let ts = parse_iso_timestamp("2024-01-15T10:30:00Z");
@@ -3815,10 +5461,15 @@ mod tests {
     #[tokio::test]
     async fn test_incomplete_fetch_does_not_soft_purge() {
         let dir = tempfile::tempdir().unwrap();
-        let pool = crate::db::initialize(&dir.path().join("test.db")).await.unwrap();
+        let pool = crate::db::initialize(&dir.path().join("test.db"))
+            .await
+            .unwrap();
         let (instance_id, mr_id) = seed_opened_mr(&pool).await;
 
-        let engine = SyncEngine::new(pool.clone(), Arc::new(crate::services::sync_events::NoopEmitter));
+        let engine = SyncEngine::new(
+            pool.clone(),
+            Arc::new(crate::services::sync_events::NoopEmitter),
+        );
 
         // MR not in the synced set, but fetch was incomplete → must stay opened.
         engine
@@ -3834,10 +5485,15 @@ mod tests {
     #[tokio::test]
     async fn test_complete_fetch_soft_purges_missing_mr() {
         let dir = tempfile::tempdir().unwrap();
-        let pool = crate::db::initialize(&dir.path().join("test.db")).await.unwrap();
+        let pool = crate::db::initialize(&dir.path().join("test.db"))
+            .await
+            .unwrap();
         let (instance_id, mr_id) = seed_opened_mr(&pool).await;
 
-        let engine = SyncEngine::new(pool.clone(), Arc::new(crate::services::sync_events::NoopEmitter));
+        let engine = SyncEngine::new(
+            pool.clone(),
+            Arc::new(crate::services::sync_events::NoopEmitter),
+        );
 
         // MR absent from a complete fetch → genuinely gone → soft-purged.
         engine
@@ -3847,4 +5503,30 @@ mod tests {
 
         assert_eq!(mr_state(&pool, mr_id).await, "merged");
     }
+
+    // Watched MRs are fetched individually rather than through the scope
+    // queries, so their absence from the synced set says nothing about state.
+    #[tokio::test]
+    async fn test_soft_purge_skips_watched_mr() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = crate::db::initialize(&dir.path().join("test.db"))
+            .await
+            .unwrap();
+        let (instance_id, mr_id) = seed_opened_mr(&pool).await;
+        crate::db::watched::set_watched(&pool, mr_id, true)
+            .await
+            .unwrap();
+
+        let engine = SyncEngine::new(
+            pool.clone(),
+            Arc::new(crate::services::sync_events::NoopEmitter),
+        );
+
+        engine
+            .purge_closed_mrs(instance_id, &[], true)
+            .await
+            .unwrap();
+
+        assert_eq!(mr_state(&pool, mr_id).await, "opened");
+    }
 }
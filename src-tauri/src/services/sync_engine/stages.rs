@@ -0,0 +1,1243 @@
+//! Sync stages.
+//!
+//! Syncing one MR is a fixed sequence of stages, each behind [`SyncStage`]:
+//! metadata (the MR row itself), approvals, diff, commits, comments and
+//! activity.
+//! `sync_mr` runs the stages that don't depend on the MR having changed,
+//! publishes the MR change, then runs the rest only when the MR's
+//! `updated_at` moved.
+//! Avatars are an instance-level stage that runs once after all MRs.
+//!
+//! Every stage is timed into `sync_metrics` under its name, and all stages
+//! except metadata can be switched off per instance with
+//! `SyncConfig::disabled_stages`.
+
+use super::{now, parse_iso_timestamp, GitLabInstanceRow, SyncEngine};
+use crate::core::automations;
+use crate::core::change_log::{self, ChangeEntity, ChangeOp};
+use crate::core::file_review;
+use crate::core::mr_activity;
+use crate::core::mr_commits;
+use crate::core::retention::RetentionPolicy;
+use crate::core::review_progress;
+use crate::core::review_stats::{self, ReviewEventKind};
+use crate::error::AppError;
+use crate::services::gitlab_client::{BatchedMrState, GitLabClient, GitLabMergeRequest};
+use crate::services::memory_profile::{self, Subsystem};
+use crate::services::quick_switch;
+use crate::services::sync_events::{
+    MrUpdateType, ReviewRerequestedPayload, SyncPhase, REVIEW_REREQUESTED_EVENT,
+};
+use futures::future::BoxFuture;
+use serde_json::{Map, Value};
+use std::time::Instant;
+
+/// One step of a sync. Stages are stateless; everything they read or
+/// produce for later stages lives in [`MrSync`].
+pub(super) trait SyncStage: Send + Sync {
+    /// Name used for metrics and for `SyncConfig::disabled_stages`.
+    fn name(&self) -> &'static str;
+
+    /// Whether the stage only runs when the MR changed since the last sync.
+    fn needs_changes(&self) -> bool {
+        false
+    }
+
+    /// Per-MR work. Returns the number of items processed, for metrics.
+    fn sync_mr<'a, 'b: 'a>(
+        &'a self,
+        _engine: &'a SyncEngine,
+        _ctx: &'a mut MrSync<'b>,
+    ) -> Option<BoxFuture<'a, Result<u64, AppError>>> {
+        None
+    }
+
+    /// Instance-level work after every MR synced. Returns the number of
+    /// items processed, for metrics.
+    fn sync_instance<'a>(
+        &'a self,
+        _engine: &'a SyncEngine,
+        _instance: &'a GitLabInstanceRow,
+        _mrs: &'a [GitLabMergeRequest],
+    ) -> Option<BoxFuture<'a, Result<u64, AppError>>> {
+        None
+    }
+}
+
+/// All stages, in the order they run.
+pub(super) static STAGES: [&dyn SyncStage; 7] = [
+    &MetadataStage,
+    &ApprovalStage,
+    &DiffStage,
+    &CommitsStage,
+    &CommentsStage,
+    &ActivityStage,
+    &AvatarStage,
+];
+
+/// The stage every other stage depends on; it can't be disabled.
+pub(super) const METADATA: &str = "metadata";
+
+/// Stage names that can be disabled.
+pub(super) fn toggleable_names() -> impl Iterator<Item = &'static str> {
+    STAGES.iter().map(|s| s.name()).filter(|n| *n != METADATA)
+}
+
+/// The stages enabled for an instance, in order.
+pub(super) fn enabled(disabled: &[String]) -> Vec<&'static dyn SyncStage> {
+    STAGES
+        .iter()
+        .copied()
+        .filter(|s| s.name() == METADATA || !disabled.iter().any(|d| d == s.name()))
+        .collect()
+}
+
+/// State of one MR's sync, threaded through the stages.
+pub(super) struct MrSync<'a> {
+    pub instance_id: i64,
+    pub client: &'a GitLabClient,
+    pub mr: &'a GitLabMergeRequest,
+    pub current_user_id: Option<i64>,
+    pub sync_run_id: &'a str,
+    pub force: bool,
+    /// Approval + pipeline data already fetched in bulk via GraphQL; when
+    /// None the approval stage fetches per-MR over REST.
+    pub prefetched_state: Option<BatchedMrState>,
+    /// Decides whether file content and old notes are cached.
+    pub retention_policy: &'a RetentionPolicy,
+    /// Lets a newly cached MR fire automations.
+    pub automate_new_mrs: bool,
+    /// The MR's project is muted: sync it, but don't notify about it.
+    pub muted: bool,
+
+    // Set by the metadata stage.
+    /// Canonical DB row id (may differ from `mr.id` for pre-existing rows).
+    pub local_mr_id: i64,
+    pub is_new: bool,
+    pub cached_updated_at: Option<i64>,
+    pub snapshot_before: Option<Map<String, Value>>,
+    /// The current user is a reviewer of someone else's MR.
+    pub review_requested: bool,
+}
+
+impl<'a> MrSync<'a> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        instance_id: i64,
+        client: &'a GitLabClient,
+        mr: &'a GitLabMergeRequest,
+        current_user_id: Option<i64>,
+        sync_run_id: &'a str,
+        force: bool,
+        prefetched_state: Option<BatchedMrState>,
+        retention_policy: &'a RetentionPolicy,
+        automate_new_mrs: bool,
+    ) -> Self {
+        Self {
+            instance_id,
+            client,
+            mr,
+            current_user_id,
+            sync_run_id,
+            force,
+            prefetched_state,
+            retention_policy,
+            automate_new_mrs,
+            muted: false,
+            local_mr_id: 0,
+            is_new: true,
+            cached_updated_at: None,
+            snapshot_before: None,
+            review_requested: false,
+        }
+    }
+
+    /// Whether the MR changed since it was last cached (or a full sync was
+    /// forced).
+    pub fn mr_changed(&self) -> bool {
+        self.force
+            || self.is_new
+            || self.cached_updated_at != Some(parse_iso_timestamp(&self.mr.updated_at))
+    }
+}
+
+/// Upserts the MR row, keeps the quick switch index current and records
+/// review requests.
+pub(super) struct MetadataStage;
+
+impl SyncStage for MetadataStage {
+    fn name(&self) -> &'static str {
+        METADATA
+    }
+
+    fn sync_mr<'a, 'b: 'a>(
+        &'a self,
+        engine: &'a SyncEngine,
+        ctx: &'a mut MrSync<'b>,
+    ) -> Option<BoxFuture<'a, Result<u64, AppError>>> {
+        Some(Box::pin(async move {
+            let mr = ctx.mr;
+
+            // Check if MR already exists and fetch cached updated_at for skip logic
+            let existing: Option<(i64, Option<i64>)> = sqlx::query_as(
+                "SELECT id, updated_at FROM merge_requests WHERE instance_id = ? AND project_id = ? AND iid = ?",
+            )
+            .bind(ctx.instance_id)
+            .bind(mr.project_id)
+            .bind(mr.iid)
+            .fetch_optional(&engine.pool)
+            .await?;
+            ctx.is_new = existing.is_none();
+            ctx.cached_updated_at = existing.and_then(|(_, ts)| ts);
+            ctx.snapshot_before = match existing {
+                Some((id, _)) => change_log::mr_snapshot(&engine.pool, id)
+                    .await
+                    .unwrap_or_default(),
+                None => None,
+            };
+
+            ctx.local_mr_id = engine
+                .upsert_mr(ctx.instance_id, mr, ctx.current_user_id)
+                .await
+                .map_err(|e| {
+                    eprintln!("[sync] MR !{}: upsert_mr failed: {}", mr.iid, e);
+                    e
+                })?;
+
+            if let Err(e) = quick_switch::refresh(&engine.pool, ctx.local_mr_id).await {
+                log::warn!(
+                    "Failed to update quick switch index for MR {}: {}",
+                    mr.iid,
+                    e
+                );
+            }
+
+            ctx.review_requested = ctx.current_user_id.is_some_and(|uid| {
+                mr.author.id != uid
+                    && mr
+                        .reviewers
+                        .as_deref()
+                        .is_some_and(|r| r.iter().any(|u| u.id == uid))
+            });
+            if ctx.review_requested {
+                if let Err(e) = review_stats::record(
+                    &engine.pool,
+                    ctx.instance_id,
+                    mr.project_id,
+                    mr.iid,
+                    ReviewEventKind::ReviewRequested,
+                    now(),
+                )
+                .await
+                {
+                    log::warn!("Failed to record review request for MR {}: {}", mr.iid, e);
+                }
+                engine
+                    .maybe_answer_vacation(
+                        ctx.instance_id,
+                        ctx.local_mr_id,
+                        mr,
+                        ctx.current_user_id,
+                        ctx.is_new,
+                    )
+                    .await;
+            }
+
+            Ok(1)
+        }))
+    }
+}
+
+/// Refreshes approval state, head pipeline status and reviewer states.
+///
+/// Runs on every sync: GitLab doesn't bump `updated_at` on approve or
+/// unapprove, and the head pipeline status the "ready to merge" notification
+/// depends on isn't in the MR list response (GitLab 14.5+).
+pub(super) struct ApprovalStage;
+
+impl SyncStage for ApprovalStage {
+    fn name(&self) -> &'static str {
+        "approvals"
+    }
+
+    fn sync_mr<'a, 'b: 'a>(
+        &'a self,
+        engine: &'a SyncEngine,
+        ctx: &'a mut MrSync<'b>,
+    ) -> Option<BoxFuture<'a, Result<u64, AppError>>> {
+        Some(Box::pin(async move {
+            let mr = ctx.mr;
+            let client = ctx.client;
+            let local_mr_id = ctx.local_mr_id;
+
+            // Normally both arrive via the instance-wide batched GraphQL query
+            // (prefetched_state). The REST path below is the fallback when the
+            // batch failed or didn't cover this MR.
+            let (approvals_result, pipeline_status) = match ctx.prefetched_state.take() {
+                Some(state) => {
+                    let pipeline_status = if mr.head_pipeline.is_none() {
+                        state.head_pipeline_status
+                    } else {
+                        None
+                    };
+                    (Ok(state.approvals), pipeline_status)
+                }
+                None => {
+                    let needs_pipeline_fetch = mr.head_pipeline.is_none();
+                    tokio::join!(client.get_mr_approvals(mr.project_id, mr.iid), async {
+                        if needs_pipeline_fetch {
+                            match client.get_merge_request(mr.project_id, mr.iid).await {
+                                Ok(detail) => detail.head_pipeline.map(|p| p.status),
+                                Err(e) => {
+                                    log::warn!(
+                                        "Failed to fetch MR detail for pipeline status (MR !{}): {}",
+                                        mr.iid,
+                                        e
+                                    );
+                                    None
+                                }
+                            }
+                        } else {
+                            None
+                        }
+                    })
+                }
+            };
+
+            // Update head_pipeline_status from individual MR fetch if list didn't include it
+            if let Some(status) = &pipeline_status {
+                if let Err(e) =
+                    sqlx::query("UPDATE merge_requests SET head_pipeline_status = ? WHERE id = ?")
+                        .bind(status)
+                        .bind(local_mr_id)
+                        .execute(&engine.pool)
+                        .await
+                {
+                    log::warn!(
+                        "Failed to update head_pipeline_status for MR !{}: {}",
+                        mr.iid,
+                        e
+                    );
+                }
+            }
+
+            let approvals = match approvals_result {
+                Ok(approvals) => approvals,
+                Err(e) => {
+                    // Non-critical - log and continue
+                    log::warn!("Failed to fetch approvals for MR {}: {}", mr.iid, e);
+                    return Ok(0);
+                }
+            };
+
+            // Use pre-fetched current_user_id (hoisted to sync_instance) to avoid
+            // per-MR validate_token() calls and transient-failure false negatives
+            let remote_user_has_approved = ctx
+                .current_user_id
+                .is_some_and(|uid| approvals.approved_by.iter().any(|a| a.user.id == uid));
+
+            let approval_status = if approvals.approved {
+                "approved"
+            } else {
+                "pending"
+            };
+            let approvals_count = approvals.approvals_required - approvals.approvals_left;
+
+            // Guard: check for pending/in-flight approval actions in sync_queue.
+            // If present, preserve the optimistic DB value to prevent stale API data
+            // from overwriting a local approve/unapprove the user just performed.
+            // Note: unapprove actions also use action_type = 'approve' (distinguished by payload)
+            let has_pending_approval_action: i64 = sqlx::query_scalar(
+                "SELECT COUNT(*) FROM sync_queue WHERE mr_id = ? AND action_type = 'approve' AND status IN ('pending', 'syncing')"
+            )
+            .bind(local_mr_id)
+            .fetch_one(&engine.pool)
+            .await
+            .unwrap_or(0);
+
+            let user_has_approved = if has_pending_approval_action > 0 && !remote_user_has_approved
+            {
+                // Pending local action exists and remote doesn't reflect it yet —
+                // read and preserve the current (optimistic) DB value
+                let current: bool =
+                    sqlx::query_scalar("SELECT user_has_approved FROM merge_requests WHERE id = ?")
+                        .bind(local_mr_id)
+                        .fetch_optional(&engine.pool)
+                        .await
+                        .unwrap_or(None)
+                        .unwrap_or(remote_user_has_approved);
+
+                log::info!(
+                    "[sync] MR !{}: preserving local approval state (pending action in queue, remote={})",
+                    mr.iid, remote_user_has_approved
+                );
+                current
+            } else {
+                remote_user_has_approved
+            };
+
+            sqlx::query(
+                "UPDATE merge_requests SET
+                    approval_status = ?,
+                    approvals_count = ?,
+                    approvals_required = ?,
+                    user_has_approved = ?
+                 WHERE id = ?",
+            )
+            .bind(approval_status)
+            .bind(approvals_count)
+            .bind(approvals.approvals_required)
+            .bind(user_has_approved)
+            .bind(local_mr_id)
+            .execute(&engine.pool)
+            .await?;
+
+            // Review states (for re-requested reviews) only change along
+            // with the MR, so skip the extra call when it is unchanged.
+            let review_states =
+                if ctx.mr_changed() && mr.reviewers.as_deref().is_some_and(|r| !r.is_empty()) {
+                    match client.get_mr_reviewer_states(mr.project_id, mr.iid).await {
+                        Ok(states) => Some(states),
+                        Err(e) => {
+                            log::warn!("Failed to fetch reviewer states for MR !{}: {}", mr.iid, e);
+                            None
+                        }
+                    }
+                } else {
+                    None
+                };
+
+            // Upsert per-reviewer status
+            let rerequested = engine
+                .upsert_reviewers(local_mr_id, mr, &approvals, review_states.as_deref())
+                .await;
+            let me_rerequested = ctx.review_requested
+                && !ctx.muted
+                && mr.reviewers.as_deref().is_some_and(|r| {
+                    r.iter().any(|u| {
+                        Some(u.id) == ctx.current_user_id && rerequested.contains(&u.username)
+                    })
+                });
+            if me_rerequested {
+                engine.emit_event(
+                    REVIEW_REREQUESTED_EVENT,
+                    &ReviewRerequestedPayload {
+                        mr_id: local_mr_id,
+                        title: mr.title.clone(),
+                        project_name: super::extract_project_path(&mr.web_url),
+                        author_username: mr.author.username.clone(),
+                        web_url: mr.web_url.clone(),
+                    },
+                );
+            }
+
+            if ctx.review_requested && !user_has_approved {
+                let status = mr
+                    .head_pipeline
+                    .as_ref()
+                    .map(|p| p.status.as_str())
+                    .or(pipeline_status.as_deref());
+                engine
+                    .maybe_auto_approve_bot_mr(local_mr_id, mr, status)
+                    .await;
+            }
+
+            Ok(approvals.approved_by.len() as u64)
+        }))
+    }
+}
+
+/// Fetches the MR diff and pre-caches full file contents.
+pub(super) struct DiffStage;
+
+impl DiffStage {
+    pub const NAME: &'static str = "diff";
+}
+
+impl SyncStage for DiffStage {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn needs_changes(&self) -> bool {
+        true
+    }
+
+    fn sync_mr<'a, 'b: 'a>(
+        &'a self,
+        engine: &'a SyncEngine,
+        ctx: &'a mut MrSync<'b>,
+    ) -> Option<BoxFuture<'a, Result<u64, AppError>>> {
+        Some(Box::pin(async move {
+            let mr = ctx.mr;
+            let local_mr_id = ctx.local_mr_id;
+            engine.emit_progress(
+                SyncPhase::FetchingDiff,
+                format!("Fetching diff for MR !{}", mr.iid),
+            );
+
+            let diff = match ctx
+                .client
+                .get_merge_request_diff(mr.project_id, mr.iid)
+                .await
+            {
+                Ok(diff) => diff,
+                Err(e) => {
+                    engine
+                        .log_sync_operation(
+                            "fetch_diff",
+                            "error",
+                            Some(local_mr_id),
+                            Some(e.to_string()),
+                            None,
+                        )
+                        .await?;
+                    return Ok(0);
+                }
+            };
+
+            // Get previously cached SHAs before upserting (for skip-unchanged logic)
+            let prev_shas = crate::db::file_cache::get_cached_diff_shas(&engine.pool, local_mr_id)
+                .await
+                .unwrap_or(None);
+
+            engine.upsert_diff(local_mr_id, &diff).await.map_err(|e| {
+                eprintln!("[sync] MR !{}: upsert_diff failed: {}", mr.iid, e);
+                e
+            })?;
+
+            // Files marked viewed stay viewed unless this diff changed them.
+            if let Err(e) = file_review::carry_forward(&engine.pool, local_mr_id).await {
+                log::warn!(
+                    "Failed to carry viewed files of MR !{} forward: {}",
+                    mr.iid,
+                    e
+                );
+            }
+
+            engine
+                .record_change(
+                    ChangeEntity::Diff,
+                    local_mr_id,
+                    ctx.instance_id,
+                    ChangeOp::Upsert,
+                    Default::default(),
+                )
+                .await;
+            engine.emit_mr_updated(
+                local_mr_id,
+                ctx.instance_id,
+                mr.iid,
+                MrUpdateType::DiffUpdated,
+            );
+
+            // Flag the MR when its head moved past the commit last reviewed.
+            // The MR change was already published, so log the flag on its own.
+            match review_progress::refresh_flag(&engine.pool, local_mr_id).await {
+                Ok(Some(changed)) => {
+                    let mut fields = Map::new();
+                    fields.insert("changedSinceReview".to_string(), changed.into());
+                    engine
+                        .record_change(
+                            ChangeEntity::MergeRequest,
+                            local_mr_id,
+                            ctx.instance_id,
+                            ChangeOp::Upsert,
+                            fields,
+                        )
+                        .await;
+                }
+                Ok(None) => {}
+                Err(e) => log::warn!("Failed to refresh review flag of MR !{}: {}", mr.iid, e),
+            }
+
+            // Pre-cache full file content for instant viewing, unless the
+            // instance's retention policy forbids storing file content.
+            if ctx.retention_policy.cache_file_content {
+                let file_cache_start = Instant::now();
+                memory_profile::track(
+                    Subsystem::FileCache,
+                    engine.cache_file_contents(
+                        local_mr_id,
+                        mr.project_id,
+                        ctx.instance_id,
+                        ctx.client,
+                        &diff,
+                        prev_shas.as_ref(),
+                    ),
+                )
+                .await;
+
+                if let Err(e) = engine
+                    .record_metric(
+                        ctx.sync_run_id,
+                        "file_cache",
+                        Some(ctx.instance_id),
+                        Some(mr.iid),
+                        file_cache_start.elapsed().as_millis() as i64,
+                        0,
+                        diff.diffs.len() as u64,
+                    )
+                    .await
+                {
+                    log::warn!("Failed to record file_cache metric: {}", e);
+                }
+            }
+
+            Ok(diff.diffs.len() as u64)
+        }))
+    }
+}
+
+/// Replaces the MR's cached commit list. Commit diffs are fetched when a
+/// commit is opened, not here.
+pub(super) struct CommitsStage;
+
+impl SyncStage for CommitsStage {
+    fn name(&self) -> &'static str {
+        "commits"
+    }
+
+    fn needs_changes(&self) -> bool {
+        true
+    }
+
+    fn sync_mr<'a, 'b: 'a>(
+        &'a self,
+        engine: &'a SyncEngine,
+        ctx: &'a mut MrSync<'b>,
+    ) -> Option<BoxFuture<'a, Result<u64, AppError>>> {
+        Some(Box::pin(async move {
+            let mr = ctx.mr;
+            let local_mr_id = ctx.local_mr_id;
+
+            let commits = match ctx.client.get_mr_commits(mr.project_id, mr.iid).await {
+                Ok(commits) => mr_commits::to_commits(commits),
+                Err(e) => {
+                    engine
+                        .log_sync_operation(
+                            "fetch_commits",
+                            "error",
+                            Some(local_mr_id),
+                            Some(e.to_string()),
+                            None,
+                        )
+                        .await?;
+                    return Ok(0);
+                }
+            };
+
+            crate::db::mr_commits::replace_commits(&engine.pool, local_mr_id, &commits).await?;
+
+            // The commit list is part of the diff as far as clients go.
+            engine
+                .record_change(
+                    ChangeEntity::Diff,
+                    local_mr_id,
+                    ctx.instance_id,
+                    ChangeOp::Upsert,
+                    Default::default(),
+                )
+                .await;
+            engine.emit_mr_updated(
+                local_mr_id,
+                ctx.instance_id,
+                mr.iid,
+                MrUpdateType::DiffUpdated,
+            );
+
+            Ok(commits.len() as u64)
+        }))
+    }
+}
+
+/// Fetches and caches the MR's discussions.
+pub(super) struct CommentsStage;
+
+impl SyncStage for CommentsStage {
+    fn name(&self) -> &'static str {
+        "comments"
+    }
+
+    fn needs_changes(&self) -> bool {
+        true
+    }
+
+    fn sync_mr<'a, 'b: 'a>(
+        &'a self,
+        engine: &'a SyncEngine,
+        ctx: &'a mut MrSync<'b>,
+    ) -> Option<BoxFuture<'a, Result<u64, AppError>>> {
+        Some(Box::pin(async move {
+            let mr = ctx.mr;
+            let local_mr_id = ctx.local_mr_id;
+            engine.emit_progress(
+                SyncPhase::FetchingComments,
+                format!("Fetching comments for MR !{}", mr.iid),
+            );
+
+            let discussions = match ctx.client.list_discussions(mr.project_id, mr.iid).await {
+                Ok(discussions) => discussions,
+                Err(e) => {
+                    engine
+                        .log_sync_operation(
+                            "fetch_comments",
+                            "error",
+                            Some(local_mr_id),
+                            Some(e.to_string()),
+                            None,
+                        )
+                        .await?;
+                    return Ok(0);
+                }
+            };
+
+            engine
+                .upsert_discussions(
+                    local_mr_id,
+                    &discussions,
+                    ctx.retention_policy.comment_cutoff(now()),
+                )
+                .await
+                .map_err(|e| {
+                    eprintln!("[sync] MR !{}: upsert_discussions failed: {}", mr.iid, e);
+                    e
+                })?;
+
+            engine
+                .record_change(
+                    ChangeEntity::Comments,
+                    local_mr_id,
+                    ctx.instance_id,
+                    ChangeOp::Upsert,
+                    Default::default(),
+                )
+                .await;
+            engine.emit_mr_updated(
+                local_mr_id,
+                ctx.instance_id,
+                mr.iid,
+                MrUpdateType::CommentsUpdated,
+            );
+
+            Ok(discussions.len() as u64)
+        }))
+    }
+}
+
+/// Rebuilds the MR's activity timeline from the system notes the comments
+/// stage cached and from GitLab's label, milestone and state events. A
+/// source whose events can't be fetched keeps its previous rows.
+pub(super) struct ActivityStage;
+
+impl SyncStage for ActivityStage {
+    fn name(&self) -> &'static str {
+        "activity"
+    }
+
+    fn needs_changes(&self) -> bool {
+        true
+    }
+
+    fn sync_mr<'a, 'b: 'a>(
+        &'a self,
+        engine: &'a SyncEngine,
+        ctx: &'a mut MrSync<'b>,
+    ) -> Option<BoxFuture<'a, Result<u64, AppError>>> {
+        Some(Box::pin(async move {
+            let mr = ctx.mr;
+            let local_mr_id = ctx.local_mr_id;
+
+            let (labels, milestones, states) = futures::join!(
+                ctx.client.list_label_events(mr.project_id, mr.iid),
+                ctx.client.list_milestone_events(mr.project_id, mr.iid),
+                ctx.client.list_state_events(mr.project_id, mr.iid),
+            );
+            let fetched = [
+                (
+                    mr_activity::LABEL,
+                    labels.map(|e| mr_activity::from_label_events(&e)),
+                ),
+                (
+                    mr_activity::MILESTONE,
+                    milestones.map(|e| mr_activity::from_milestone_events(&e)),
+                ),
+                (
+                    mr_activity::STATE,
+                    states.map(|e| mr_activity::from_state_events(&e)),
+                ),
+            ];
+
+            let mut sources = vec![(
+                mr_activity::NOTE,
+                mr_activity::note_events(&engine.pool, local_mr_id).await?,
+            )];
+            for (source, events) in fetched {
+                match events {
+                    Ok(events) => sources.push((source, events)),
+                    Err(e) => {
+                        engine
+                            .log_sync_operation(
+                                "fetch_activity",
+                                "error",
+                                Some(local_mr_id),
+                                Some(format!("{source} events: {e}")),
+                                None,
+                            )
+                            .await?;
+                    }
+                }
+            }
+
+            // Like comments, events older than the comment retention aren't kept.
+            let cutoff = ctx.retention_policy.comment_cutoff(now());
+            let mut count = 0;
+            for (source, mut events) in sources {
+                events.retain(|e| cutoff.is_none_or(|cutoff| e.created_at >= cutoff));
+                crate::db::mr_events::replace_events(&engine.pool, local_mr_id, source, &events)
+                    .await?;
+                count += events.len() as u64;
+            }
+
+            // The timeline is part of the discussions as far as clients go.
+            engine
+                .record_change(
+                    ChangeEntity::Comments,
+                    local_mr_id,
+                    ctx.instance_id,
+                    ChangeOp::Upsert,
+                    Default::default(),
+                )
+                .await;
+            engine.emit_mr_updated(
+                local_mr_id,
+                ctx.instance_id,
+                mr.iid,
+                MrUpdateType::CommentsUpdated,
+            );
+
+            Ok(count)
+        }))
+    }
+}
+
+/// Downloads avatars of MR authors and reviewers once per instance sync.
+pub(super) struct AvatarStage;
+
+impl SyncStage for AvatarStage {
+    fn name(&self) -> &'static str {
+        "avatars"
+    }
+
+    fn sync_instance<'a>(
+        &'a self,
+        engine: &'a SyncEngine,
+        instance: &'a GitLabInstanceRow,
+        mrs: &'a [GitLabMergeRequest],
+    ) -> Option<BoxFuture<'a, Result<u64, AppError>>> {
+        Some(Box::pin(async move {
+            Ok(engine.sync_user_avatars(instance, mrs).await as u64)
+        }))
+    }
+}
+
+impl SyncEngine {
+    /// Run one stage's per-MR hook and record its timing. Stages without a
+    /// per-MR hook are skipped.
+    async fn run_mr_stage(
+        &self,
+        stage: &dyn SyncStage,
+        ctx: &mut MrSync<'_>,
+    ) -> Result<(), AppError> {
+        let start = Instant::now();
+        let Some(work) = stage.sync_mr(self, ctx) else {
+            return Ok(());
+        };
+        let items = work.await?;
+        if let Err(e) = self
+            .record_metric(
+                ctx.sync_run_id,
+                stage.name(),
+                Some(ctx.instance_id),
+                Some(ctx.mr.iid),
+                start.elapsed().as_millis() as i64,
+                0,
+                items,
+            )
+            .await
+        {
+            log::warn!("Failed to record {} metric: {}", stage.name(), e);
+        }
+        Ok(())
+    }
+
+    /// Sync a single MR through `stages`.
+    ///
+    /// Stages that run on every sync go first; the MR change is then
+    /// published (change log, automations, `mr-updated`), and the stages that
+    /// need changes run only if the MR changed since it was last cached.
+    /// Returns the MR's local id.
+    pub(super) async fn sync_mr(
+        &self,
+        mut ctx: MrSync<'_>,
+        stages: &[&dyn SyncStage],
+    ) -> Result<i64, AppError> {
+        let start = Instant::now();
+        let mr = ctx.mr;
+
+        for stage in stages.iter().filter(|s| !s.needs_changes()) {
+            self.run_mr_stage(*stage, &mut ctx).await?;
+        }
+        if ctx.local_mr_id == 0 {
+            return Err(AppError::internal(format!(
+                "MR !{} synced without the {} stage",
+                mr.iid, METADATA
+            )));
+        }
+        let local_mr_id = ctx.local_mr_id;
+
+        // Emit events AFTER approval fields are written (when available) so the
+        // frontend sees correct state. Always emit even if approvals fetch failed
+        // so the frontend knows about new/updated MRs.
+        self.publish_mr_change(&ctx).await;
+
+        // Skip expensive API calls (diff, comments) if MR hasn't changed.
+        let mut mr_unchanged = !ctx.mr_changed();
+
+        // Even if the MR looks unchanged, re-fetch if it has no cached files.
+        // This handles MRs that were synced before file caching completed.
+        // Files come from the diff stage; without it there are none to expect.
+        if mr_unchanged && stages.iter().any(|s| s.name() == DiffStage::NAME) {
+            let cached_file_count: (i64,) =
+                sqlx::query_as("SELECT COUNT(*) FROM file_versions WHERE mr_id = ?")
+                    .bind(local_mr_id)
+                    .fetch_one(&self.pool)
+                    .await
+                    .unwrap_or((0,));
+
+            if cached_file_count.0 == 0 {
+                log::info!(
+                    "[sync] MR !{}: unchanged but has 0 cached files, re-fetching",
+                    mr.iid
+                );
+                mr_unchanged = false;
+            }
+        }
+
+        if mr_unchanged {
+            log::info!(
+                "[sync] MR !{}: unchanged (updated_at={}), skipping diff/comments",
+                mr.iid,
+                mr.updated_at
+            );
+        } else {
+            for stage in stages.iter().filter(|s| s.needs_changes()) {
+                self.run_mr_stage(*stage, &mut ctx).await?;
+            }
+        }
+
+        let mr_duration_ms = start.elapsed().as_millis() as i64;
+        self.log_sync_operation(
+            "sync_mr",
+            if mr_unchanged { "skipped" } else { "success" },
+            Some(local_mr_id),
+            None,
+            Some(mr_duration_ms),
+        )
+        .await?;
+
+        if let Err(e) = self
+            .record_metric(
+                ctx.sync_run_id,
+                "mr",
+                Some(ctx.instance_id),
+                Some(mr.iid),
+                mr_duration_ms,
+                0,
+                0,
+            )
+            .await
+        {
+            log::warn!("Failed to record MR metric: {}", e);
+        }
+
+        Ok(local_mr_id)
+    }
+
+    /// Record the MR's changed fields in the change log, run automations on
+    /// them and emit `mr-updated`.
+    async fn publish_mr_change(&self, ctx: &MrSync<'_>) {
+        let mr = ctx.mr;
+        match change_log::mr_snapshot(&self.pool, ctx.local_mr_id).await {
+            Ok(Some(after)) => {
+                let fields = change_log::changed_fields(ctx.snapshot_before.as_ref(), &after);
+                if !fields.is_empty() {
+                    self.record_change(
+                        ChangeEntity::MergeRequest,
+                        ctx.local_mr_id,
+                        ctx.instance_id,
+                        ChangeOp::Upsert,
+                        fields,
+                    )
+                    .await;
+                    if let Err(e) = automations::on_mr_changed(
+                        &self.pool,
+                        ctx.instance_id,
+                        ctx.snapshot_before.as_ref(),
+                        &after,
+                        ctx.automate_new_mrs,
+                    )
+                    .await
+                    {
+                        log::warn!("Failed to evaluate automations for MR !{}: {}", mr.iid, e);
+                    }
+                }
+            }
+            Ok(None) => {}
+            Err(e) => log::warn!("Failed to snapshot MR !{} for change log: {}", mr.iid, e),
+        }
+        self.emit_mr_updated(
+            ctx.local_mr_id,
+            ctx.instance_id,
+            mr.iid,
+            if ctx.is_new {
+                MrUpdateType::Created
+            } else {
+                MrUpdateType::Updated
+            },
+        );
+    }
+
+    /// Run the instance-level hooks of `stages` after all MRs synced.
+    /// Failures are logged; they never fail the instance sync.
+    pub(super) async fn run_instance_stages(
+        &self,
+        stages: &[&dyn SyncStage],
+        instance: &GitLabInstanceRow,
+        mrs: &[GitLabMergeRequest],
+        sync_run_id: &str,
+    ) {
+        for stage in stages {
+            let start = Instant::now();
+            let Some(work) = stage.sync_instance(self, instance, mrs) else {
+                continue;
+            };
+            let items = match work.await {
+                Ok(items) => items,
+                Err(e) => {
+                    log::warn!(
+                        "[sync] {} stage failed for instance {}: {}",
+                        stage.name(),
+                        instance.id,
+                        e
+                    );
+                    continue;
+                }
+            };
+            if let Err(e) = self
+                .record_metric(
+                    sync_run_id,
+                    stage.name(),
+                    Some(instance.id),
+                    None,
+                    start.elapsed().as_millis() as i64,
+                    0,
+                    items,
+                )
+                .await
+            {
+                log::warn!("Failed to record {} metric: {}", stage.name(), e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::retention;
+    use crate::services::gitlab_client::GitLabClientConfig;
+    use crate::services::sync_events::NoopEmitter;
+    use std::sync::{Arc, Mutex};
+
+    /// Stage that counts the MRs it ran for.
+    struct Recorder {
+        name: &'static str,
+        needs_changes: bool,
+        runs: Mutex<usize>,
+    }
+
+    impl Recorder {
+        fn new(name: &'static str, needs_changes: bool) -> Self {
+            Self {
+                name,
+                needs_changes,
+                runs: Mutex::new(0),
+            }
+        }
+
+        fn runs(&self) -> usize {
+            *self.runs.lock().unwrap()
+        }
+    }
+
+    impl SyncStage for Recorder {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn needs_changes(&self) -> bool {
+            self.needs_changes
+        }
+
+        fn sync_mr<'a, 'b: 'a>(
+            &'a self,
+            _engine: &'a SyncEngine,
+            ctx: &'a mut MrSync<'b>,
+        ) -> Option<BoxFuture<'a, Result<u64, AppError>>> {
+            Some(Box::pin(async move {
+                assert_ne!(ctx.local_mr_id, 0, "ran before the metadata stage");
+                *self.runs.lock().unwrap() += 1;
+                Ok(2)
+            }))
+        }
+    }
+
+    fn merge_request(updated_at: &str) -> GitLabMergeRequest {
+        serde_json::from_value(serde_json::json!({
+            "id": 501,
+            "iid": 7,
+            "project_id": 1000,
+            "title": "Add stages",
+            "description": null,
+            "state": "opened",
+            "web_url": "https://gitlab.com/group/proj/-/merge_requests/7",
+            "source_branch": "feature",
+            "target_branch": "main",
+            "created_at": "2026-01-01T00:00:00Z",
+            "updated_at": updated_at,
+            "author": { "id": 1, "username": "alice", "name": "Alice", "avatar_url": null },
+            "labels": [],
+        }))
+        .unwrap()
+    }
+
+    /// Engine over a fresh database with one instance. The client never
+    /// makes a request: the test stages don't call GitLab.
+    async fn setup() -> (tempfile::TempDir, SyncEngine, GitLabClient, i64) {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = crate::db::initialize(&dir.path().join("test.db"))
+            .await
+            .unwrap();
+        let (instance_id,): (i64,) = sqlx::query_as(
+            "INSERT INTO gitlab_instances (url, name) VALUES ('https://gitlab.com', 'GitLab') RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let client = GitLabClient::new(GitLabClientConfig {
+            base_url: "https://gitlab.com".to_string(),
+            token: "test".to_string(),
+            timeout_secs: 1,
+        })
+        .unwrap();
+        let engine = SyncEngine::new(pool, Arc::new(NoopEmitter));
+        (dir, engine, client, instance_id)
+    }
+
+    async fn sync(
+        engine: &SyncEngine,
+        client: &GitLabClient,
+        instance_id: i64,
+        mr: &GitLabMergeRequest,
+        stages: &[&dyn SyncStage],
+    ) -> Result<i64, AppError> {
+        let policy = retention::load_policy(&engine.pool, instance_id)
+            .await
+            .unwrap();
+        let ctx = MrSync::new(
+            instance_id,
+            client,
+            mr,
+            None,
+            "run",
+            false,
+            None,
+            &policy,
+            false,
+        );
+        engine.sync_mr(ctx, stages).await
+    }
+
+    #[test]
+    fn metadata_cannot_be_disabled() {
+        let disabled = vec!["diff".to_string(), METADATA.to_string()];
+        let names: Vec<&str> = enabled(&disabled).iter().map(|s| s.name()).collect();
+        assert_eq!(
+            names,
+            vec![
+                METADATA,
+                "approvals",
+                "commits",
+                "comments",
+                "activity",
+                "avatars"
+            ]
+        );
+        assert!(!toggleable_names().any(|n| n == METADATA));
+    }
+
+    #[tokio::test]
+    async fn unchanged_mr_skips_stages_that_need_changes() {
+        let (_dir, engine, client, instance_id) = setup().await;
+        let always = Recorder::new("always", false);
+        let on_change = Recorder::new("on_change", true);
+        let stages: [&dyn SyncStage; 3] = [&MetadataStage, &always, &on_change];
+
+        let mr = merge_request("2026-01-02T00:00:00Z");
+        let local_id = sync(&engine, &client, instance_id, &mr, &stages)
+            .await
+            .unwrap();
+        assert!(local_id > 0);
+        assert_eq!((always.runs(), on_change.runs()), (1, 1));
+
+        // Same updated_at, and no diff stage expecting cached files.
+        sync(&engine, &client, instance_id, &mr, &stages)
+            .await
+            .unwrap();
+        assert_eq!((always.runs(), on_change.runs()), (2, 1));
+
+        let mr = merge_request("2026-01-03T00:00:00Z");
+        sync(&engine, &client, instance_id, &mr, &stages)
+            .await
+            .unwrap();
+        assert_eq!((always.runs(), on_change.runs()), (3, 2));
+    }
+
+    #[tokio::test]
+    async fn records_a_metric_per_stage() {
+        let (_dir, engine, client, instance_id) = setup().await;
+        let on_change = Recorder::new("on_change", true);
+        let stages: [&dyn SyncStage; 2] = [&MetadataStage, &on_change];
+        let mr = merge_request("2026-01-02T00:00:00Z");
+        sync(&engine, &client, instance_id, &mr, &stages)
+            .await
+            .unwrap();
+
+        let phases: Vec<(String, i64)> = sqlx::query_as(
+            "SELECT phase, items_processed FROM sync_metrics WHERE mr_iid = 7 ORDER BY id",
+        )
+        .fetch_all(&engine.pool)
+        .await
+        .unwrap();
+        let expected = [(METADATA, 1), ("on_change", 2), ("mr", 0)];
+        assert_eq!(phases, expected.map(|(p, n)| (p.to_string(), n)).to_vec());
+    }
+
+    #[tokio::test]
+    async fn fails_without_the_metadata_stage() {
+        let (_dir, engine, client, instance_id) = setup().await;
+        let mr = merge_request("2026-01-02T00:00:00Z");
+        assert!(sync(&engine, &client, instance_id, &mr, &[]).await.is_err());
+    }
+}
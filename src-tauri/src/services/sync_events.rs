@@ -3,6 +3,7 @@
 //! These events are emitted during sync operations to allow the frontend
 //! to reactively update its state.
 
+use crate::core::review_sla::SlaState;
 use serde::Serialize;
 
 /// Trait for emitting events to the frontend (or nowhere, for benchmarks).
@@ -12,6 +13,9 @@ use serde::Serialize;
 pub trait EventEmitter: Send + Sync {
     /// Emit an event with a JSON-serialized payload.
     fn emit_json(&self, event: &str, payload: serde_json::Value);
+
+    /// Show `count` on the app's dock/taskbar icon; `None` clears it.
+    fn set_badge_count(&self, _count: Option<i64>) {}
 }
 
 /// Wraps a `tauri::AppHandle` to implement `EventEmitter`.
@@ -24,6 +28,41 @@ impl EventEmitter for TauriEmitter {
             log::warn!("Failed to emit {} event: {}", event, e);
         }
     }
+
+    fn set_badge_count(&self, count: Option<i64>) {
+        use tauri::Manager;
+        let Some(window) = self.0.get_webview_window("main") else {
+            return;
+        };
+        // Windows has no numeric badge; show an overlay dot instead.
+        #[cfg(target_os = "windows")]
+        let result = window.set_overlay_icon(count.map(|_| badge_overlay_icon()));
+        #[cfg(not(target_os = "windows"))]
+        let result = window.set_badge_count(count);
+        if let Err(e) = result {
+            log::warn!("Failed to set badge count: {}", e);
+        }
+    }
+}
+
+/// A 16x16 red dot for the Windows taskbar overlay.
+#[cfg(target_os = "windows")]
+fn badge_overlay_icon() -> tauri::image::Image<'static> {
+    const SIZE: u32 = 16;
+    let center = (SIZE as f32 - 1.0) / 2.0;
+    let mut rgba = Vec::with_capacity((SIZE * SIZE * 4) as usize);
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            let (dx, dy) = (x as f32 - center, y as f32 - center);
+            let inside = dx * dx + dy * dy <= center * center;
+            rgba.extend_from_slice(if inside {
+                &[220, 38, 38, 255]
+            } else {
+                &[0, 0, 0, 0]
+            });
+        }
+    }
+    tauri::image::Image::new_owned(rgba, SIZE, SIZE)
 }
 
 /// No-op emitter for benchmarks and tests.
@@ -41,6 +80,12 @@ pub const SYNC_PROGRESS_EVENT: &str = "sync-progress";
 /// Emitted when an MR is created, updated, or deleted in the local cache.
 pub const MR_UPDATED_EVENT: &str = "mr-updated";
 
+/// Event: db-changes
+/// Emitted for every entry appended to the change log (payload is a
+/// `core::change_log::ChangeEvent`), so the frontend can patch cached rows
+/// instead of refetching whole lists.
+pub const DB_CHANGES_EVENT: &str = "db-changes";
+
 /// Event: action-synced
 /// Emitted when a local action is successfully synced to GitLab.
 pub const ACTION_SYNCED_EVENT: &str = "action-synced";
@@ -79,6 +124,53 @@ pub struct AutoMergeUpdatedPayload {
     pub last_error: Option<String>,
 }
 
+/// Event: notification:merged-when-ready
+/// Emitted when the sync engine merges an MR queued with "merge when ready".
+pub const MERGED_WHEN_READY_EVENT: &str = "notification:merged-when-ready";
+
+/// Payload for notification:merged-when-ready events.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergedWhenReadyPayload {
+    pub mr_id: i64,
+    pub title: String,
+    pub project_name: String,
+    pub web_url: String,
+}
+
+/// Event: notification:review-rerequested
+/// Emitted when the author of an MR I review asks me to review it again.
+pub const REVIEW_REREQUESTED_EVENT: &str = "notification:review-rerequested";
+
+/// Payload for notification:review-rerequested events.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewRerequestedPayload {
+    pub mr_id: i64,
+    pub title: String,
+    pub project_name: String,
+    pub author_username: String,
+    pub web_url: String,
+}
+
+/// Event: notification:review-sla
+/// Emitted when an MR awaiting my review becomes at risk of missing, or
+/// misses, the review response target.
+pub const REVIEW_SLA_EVENT: &str = "notification:review-sla";
+
+/// Payload for notification:review-sla events.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewSlaPayload {
+    pub mr_id: i64,
+    pub title: String,
+    pub project_name: String,
+    pub web_url: String,
+    pub state: SlaState,
+    /// When the target is (or was) missed, Unix seconds.
+    pub due_at: i64,
+}
+
 /// Event: auto-run-updated
 /// Emitted when the sync engine processes an auto-run claim — status
 /// observed, job played, or claim removed. The frontend invalidates its
@@ -104,6 +196,27 @@ pub struct AutoRunUpdatedPayload {
     pub last_error: Option<String>,
 }
 
+/// Event: notification:manual-gate
+/// Emitted when a new manual job waits in a pipeline of the default branch
+/// of a pinned project with manual-gate notifications on.
+pub const MANUAL_GATE_EVENT: &str = "notification:manual-gate";
+
+/// Payload for notification:manual-gate events.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManualGatePayload {
+    pub instance_id: i64,
+    pub project_id: i64,
+    /// Project name with namespace.
+    pub project_name: String,
+    pub pipeline_id: i64,
+    pub job_id: i64,
+    pub job_name: String,
+    pub ref_name: String,
+    /// URL of the job in GitLab web UI.
+    pub web_url: String,
+}
+
 /// Event: notification:auto-run
 /// Emitted when an armed job is played (played=true) or the arm is dropped
 /// because the pipeline failed / errors exhausted retries (played=false).
@@ -289,6 +402,9 @@ pub struct PipelineStatusChangedPayload {
 
     /// GitLab pipeline ID for in-app navigation.
     pub pipeline_id: i64,
+
+    /// Names of the jobs that failed the pipeline (empty unless it failed).
+    pub failed_jobs: Vec<String>,
 }
 
 /// Payload for auth-expired events.
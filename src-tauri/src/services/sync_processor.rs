@@ -7,13 +7,23 @@
 //!
 //! When an MR is merged, closed, or deleted on GitLab while local actions are pending,
 //! the sync processor detects these conflicts and discards the stale actions instead
-//! of retrying them indefinitely.
-
+//! of retrying them indefinitely. A 403 on a comment, reply or resolve means
+//! GitLab won't take discussion actions on that MR from this user; all of the
+//! MR's queued ones are then marked failed for good and new ones are refused
+//! up front until the MR changes.
+
+use crate::core::discussion_access;
+use crate::core::mr_actions::mr_api_ids;
+use crate::core::mr_tasks;
+use crate::core::review_stats::{self, ReviewEventKind};
 use crate::db::pool::DbPool;
 use crate::error::AppError;
 use crate::models::sync_action::{ActionType, SyncAction};
 use crate::services::gitlab_client::GitLabClient;
-use crate::services::sync_queue::{self, DeleteCommentPayload, ReplyPayload, ResolvePayload};
+use crate::services::sync_queue::{
+    self, DeleteCommentPayload, ReplyPayload, ResolvePayload, RetryPolicy, SetMilestonePayload,
+    SetMrUsersPayload, ToggleTaskPayload, UpdateLabelsPayload,
+};
 use serde::Deserialize;
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -122,6 +132,34 @@ fn check_stale_mr_error(error: &AppError) -> Option<DiscardReason> {
     }
 }
 
+/// Check if an error means GitLab won't accept discussion actions on the MR
+/// from this user (locked discussion, no access). Returns the reason to show.
+///
+/// Only comments, replies and resolves qualify: a 403 on those is about the
+/// discussion, while for approvals it can be a passing rule.
+fn check_discussion_refused(action_type: ActionType, error: &AppError) -> Option<&'static str> {
+    if !matches!(
+        action_type,
+        ActionType::Comment | ActionType::Reply | ActionType::Resolve | ActionType::Unresolve
+    ) {
+        return None;
+    }
+    match error {
+        AppError::GitLabApi {
+            status_code: Some(403),
+            message,
+            ..
+        } => {
+            if message.to_lowercase().contains("locked") {
+                Some(discussion_access::LOCKED_MESSAGE)
+            } else {
+                Some(discussion_access::REFUSED_MESSAGE)
+            }
+        }
+        _ => None,
+    }
+}
+
 /// Get the current Unix timestamp.
 fn now() -> i64 {
     SystemTime::now()
@@ -130,6 +168,27 @@ fn now() -> i64 {
         .unwrap_or(0)
 }
 
+/// Log a synced comment, reply or approval for the opt-in review stats.
+async fn record_review_activity(pool: &DbPool, action: &SyncAction) {
+    let kind = match action.action_type_enum() {
+        ActionType::Approve => ReviewEventKind::Approved,
+        ActionType::Comment | ActionType::Reply => ReviewEventKind::Commented,
+        _ => return,
+    };
+    let result = async {
+        let (instance_id, project_id, iid) = mr_api_ids(pool, action.mr_id).await?;
+        review_stats::record(pool, instance_id, project_id, iid, kind, action.created_at).await
+    }
+    .await;
+    if let Err(e) = result {
+        log::warn!(
+            "Failed to record review activity for action {}: {}",
+            action.id,
+            e
+        );
+    }
+}
+
 /// Process a single sync action.
 ///
 /// # Arguments
@@ -150,6 +209,7 @@ pub async fn process_action(
     client: &GitLabClient,
     pool: &DbPool,
     action: &SyncAction,
+    policy: &RetryPolicy,
 ) -> ProcessResult {
     let start = now();
 
@@ -172,6 +232,11 @@ pub async fn process_action(
         ActionType::Resolve => process_resolve(client, action, true).await,
         ActionType::Unresolve => process_resolve(client, action, false).await,
         ActionType::DeleteComment => process_delete_comment(client, action).await,
+        ActionType::ToggleTask => process_toggle_task(client, action).await,
+        ActionType::UpdateLabels => process_update_labels(client, action).await,
+        ActionType::SetReviewers => process_set_mr_users(client, action, true).await,
+        ActionType::SetAssignees => process_set_mr_users(client, action, false).await,
+        ActionType::SetMilestone => process_set_milestone(client, action).await,
     };
 
     let duration_ms = now() - start;
@@ -191,6 +256,7 @@ pub async fn process_action(
                     discarded: false,
                 };
             }
+            record_review_activity(pool, action).await;
             ProcessResult {
                 action: action.clone(),
                 success: true,
@@ -235,9 +301,38 @@ pub async fn process_action(
                 };
             }
 
-            // Regular failure - mark as failed for retry
+            // GitLab won't take discussion actions on this MR from us: stop
+            // this one and the MR's other queued ones instead of retrying.
+            if let Some(reason) = check_discussion_refused(action.action_type_enum(), &e) {
+                log::info!(
+                    "GitLab refused action {} (type: {}) on MR {}: {}",
+                    action.id,
+                    action.action_type,
+                    action.mr_id,
+                    e
+                );
+                let error =
+                    match discussion_access::record_refusal(pool, action.mr_id, reason).await {
+                        Ok(_) => reason.to_string(),
+                        Err(mark_err) => format!(
+                            "Action refused ({}) and failed to update status: {}",
+                            reason, mark_err
+                        ),
+                    };
+                return ProcessResult {
+                    action: action.clone(),
+                    success: false,
+                    error: Some(error),
+                    duration_ms,
+                    discarded: false,
+                };
+            }
+
+            // Regular failure - schedule a retry if the policy allows one
             let error_msg = e.to_string();
-            if let Err(mark_err) = sync_queue::mark_failed(pool, action.id, &error_msg).await {
+            if let Err(mark_err) =
+                sync_queue::mark_failed_with_policy(pool, action.id, &e, policy).await
+            {
                 return ProcessResult {
                     action: action.clone(),
                     success: false,
@@ -318,7 +413,8 @@ async fn process_comment(client: &GitLabClient, action: &SyncAction) -> Result<(
             )
             .await?;
     } else {
-        // General comment
+        // General comment. The body goes out untouched so GitLab runs any
+        // quick actions (/approve, /label, ...) it contains.
         client
             .add_comment(payload.project_id, payload.mr_iid, &payload.body)
             .await?;
@@ -373,6 +469,82 @@ async fn process_delete_comment(
         .await
 }
 
+/// Process a task toggle by editing the current description on GitLab.
+///
+/// The description is re-read first so edits made since the toggle was
+/// queued are kept. A task that was removed meanwhile is skipped.
+async fn process_toggle_task(client: &GitLabClient, action: &SyncAction) -> Result<(), AppError> {
+    let payload: ToggleTaskPayload = serde_json::from_str(&action.payload)?;
+
+    let mr = client
+        .get_merge_request(payload.project_id, payload.mr_iid)
+        .await?;
+    let description = mr.description.unwrap_or_default();
+    let index = usize::try_from(payload.index).unwrap_or(usize::MAX);
+    let Some(position) = mr_tasks::locate_task(&description, index, &payload.text) else {
+        log::info!(
+            "Task {:?} is no longer in the description of MR !{}; skipping toggle",
+            payload.text,
+            payload.mr_iid
+        );
+        return Ok(());
+    };
+    let Some(updated) = mr_tasks::set_task_checked(&description, position, payload.checked) else {
+        return Ok(());
+    };
+    if updated == description {
+        return Ok(());
+    }
+
+    client
+        .update_merge_request_description(payload.project_id, payload.mr_iid, &updated)
+        .await?;
+    Ok(())
+}
+
+/// Process a label change.
+async fn process_update_labels(client: &GitLabClient, action: &SyncAction) -> Result<(), AppError> {
+    let payload: UpdateLabelsPayload = serde_json::from_str(&action.payload)?;
+
+    client
+        .update_merge_request_labels(
+            payload.project_id,
+            payload.mr_iid,
+            &payload.add,
+            &payload.remove,
+        )
+        .await?;
+    Ok(())
+}
+
+async fn process_set_mr_users(
+    client: &GitLabClient,
+    action: &SyncAction,
+    reviewers: bool,
+) -> Result<(), AppError> {
+    let payload: SetMrUsersPayload = serde_json::from_str(&action.payload)?;
+
+    if reviewers {
+        client
+            .update_merge_request_reviewers(payload.project_id, payload.mr_iid, &payload.user_ids)
+            .await?;
+    } else {
+        client
+            .update_merge_request_assignees(payload.project_id, payload.mr_iid, &payload.user_ids)
+            .await?;
+    }
+    Ok(())
+}
+
+async fn process_set_milestone(client: &GitLabClient, action: &SyncAction) -> Result<(), AppError> {
+    let payload: SetMilestonePayload = serde_json::from_str(&action.payload)?;
+
+    client
+        .update_merge_request_milestone(payload.project_id, payload.mr_iid, payload.milestone_id)
+        .await?;
+    Ok(())
+}
+
 /// Process all pending actions from the queue.
 ///
 /// # Arguments
@@ -385,11 +557,11 @@ pub async fn process_pending_actions(
     client: &GitLabClient,
     pool: &DbPool,
 ) -> Result<Vec<ProcessResult>, AppError> {
-    let pending = sync_queue::get_pending_actions(pool).await?;
+    let pending = sync_queue::get_due_actions(pool, now()).await?;
     let mut results = Vec::with_capacity(pending.len());
 
     for action in &pending {
-        let result = process_action(client, pool, action).await;
+        let result = process_action(client, pool, action, &RetryPolicy::default()).await;
         results.push(result);
     }
 
@@ -416,7 +588,7 @@ pub async fn retry_failed_actions(
         sync_queue::retry_action(pool, action.id).await?;
 
         // Then process
-        let result = process_action(client, pool, action).await;
+        let result = process_action(client, pool, action, &RetryPolicy::default()).await;
         results.push(result);
     }
 
@@ -601,6 +773,30 @@ mod tests {
         assert!(matches!(result.unwrap(), DiscardReason::MrNotFound));
     }
 
+    #[test]
+    fn test_check_discussion_refused() {
+        let locked = AppError::gitlab_api_full(
+            "403 Forbidden - Discussion is locked",
+            403,
+            "/api/v4/projects/1/merge_requests/1/notes",
+        );
+        assert_eq!(
+            check_discussion_refused(ActionType::Comment, &locked),
+            Some(discussion_access::LOCKED_MESSAGE)
+        );
+
+        let denied = AppError::gitlab_api_full("403 Forbidden", 403, "/api/v4/merge_requests/1");
+        assert_eq!(
+            check_discussion_refused(ActionType::Reply, &denied),
+            Some(discussion_access::REFUSED_MESSAGE)
+        );
+        // Approvals can be refused for reasons that pass.
+        assert_eq!(check_discussion_refused(ActionType::Approve, &denied), None);
+
+        let server = AppError::gitlab_api_full("Internal server error", 500, "/api/v4/x");
+        assert_eq!(check_discussion_refused(ActionType::Comment, &server), None);
+    }
+
     #[test]
     fn test_discard_reason_messages() {
         assert!(!DiscardReason::MrNotFound.message().is_empty());
@@ -7,7 +7,7 @@ use crate::db::pool::DbPool;
 use crate::error::AppError;
 use crate::models::sync_action::{ActionType, SyncAction};
 use serde::{Deserialize, Serialize};
-use sqlx::Row;
+use sqlx::{Row, SqliteConnection};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Payload for an approval action.
@@ -57,6 +57,49 @@ pub struct ResolvePayload {
     pub discussion_id: String,
 }
 
+/// Payload for checking or unchecking a description task.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToggleTaskPayload {
+    pub project_id: i64,
+    pub mr_iid: i64,
+    /// Task index when the toggle was queued.
+    pub index: i64,
+    /// Task text, to find the task again if the description changed.
+    pub text: String,
+    pub checked: bool,
+}
+
+/// Payload for adding and removing MR labels.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateLabelsPayload {
+    pub project_id: i64,
+    pub mr_iid: i64,
+    #[serde(default)]
+    pub add: Vec<String>,
+    #[serde(default)]
+    pub remove: Vec<String>,
+}
+
+/// Payload for replacing an MR's reviewers or assignees.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetMrUsersPayload {
+    pub project_id: i64,
+    pub mr_iid: i64,
+    pub user_ids: Vec<i64>,
+    /// The same users by name, for display in the queue.
+    #[serde(default)]
+    pub usernames: Vec<String>,
+}
+
+/// Payload for setting or removing an MR's milestone.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetMilestonePayload {
+    pub project_id: i64,
+    pub mr_iid: i64,
+    /// `None` removes the milestone.
+    pub milestone_id: Option<i64>,
+}
+
 /// Input for enqueuing a new action.
 #[derive(Debug, Clone)]
 pub struct EnqueueInput {
@@ -66,6 +109,223 @@ pub struct EnqueueInput {
     pub local_reference_id: Option<i64>,
 }
 
+/// Structured details of why pushing an action failed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailureDetails {
+    /// Error category: `gitlab_api`, `network`, `authentication` or `other`.
+    pub kind: &'static str,
+    pub http_status: Option<u16>,
+    /// GitLab's own message, or the error text when there is none.
+    pub message: String,
+    pub endpoint: Option<String>,
+}
+
+impl FailureDetails {
+    /// Extract the details carried by an error.
+    pub fn from_error(err: &AppError) -> Self {
+        match err {
+            AppError::GitLabApi {
+                message,
+                status_code,
+                endpoint,
+            } => Self {
+                kind: "gitlab_api",
+                http_status: *status_code,
+                message: message.clone(),
+                endpoint: endpoint.clone(),
+            },
+            AppError::Network { message } => Self::kind("network", message),
+            AppError::Authentication { message }
+            | AppError::AuthenticationExpired { message, .. } => {
+                Self::kind("authentication", message)
+            }
+            other => Self::kind("other", &other.to_string()),
+        }
+    }
+
+    fn kind(kind: &'static str, message: &str) -> Self {
+        Self {
+            kind,
+            http_status: None,
+            message: message.to_string(),
+            endpoint: None,
+        }
+    }
+}
+
+/// Whether retrying a failed action can be expected to help.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetryAdvice {
+    /// A retry will likely go through.
+    Retry,
+    /// GitLab is throttling or erroring; wait a bit first.
+    RetryLater,
+    /// The token was rejected; re-authenticate before retrying.
+    Reauthenticate,
+    /// GitLab rejected the action itself; retrying won't change that.
+    Discard,
+}
+
+impl RetryAdvice {
+    /// True when a plain retry can succeed without user intervention.
+    pub fn is_retryable(self) -> bool {
+        matches!(self, Self::Retry | Self::RetryLater)
+    }
+}
+
+/// Wait before the first automatic retry; doubled after every further failure.
+const RETRY_BASE_DELAY_SECS: i64 = 30;
+
+/// First wait when GitLab is throttling or erroring.
+const RETRY_LATER_BASE_DELAY_SECS: i64 = 2 * 60;
+
+/// Longest wait between automatic retries.
+const RETRY_MAX_DELAY_SECS: i64 = 60 * 60;
+
+/// How failed actions are retried automatically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Push attempts, the first included, before an action is left failed
+    /// for the user to retry or discard.
+    pub max_attempts: i64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: SyncAction::MAX_RETRIES,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Seconds to wait after an action's `failures`-th failed attempt.
+    pub fn delay_secs(&self, failures: i64, advice: RetryAdvice) -> i64 {
+        let base = match advice {
+            RetryAdvice::RetryLater => RETRY_LATER_BASE_DELAY_SECS,
+            _ => RETRY_BASE_DELAY_SECS,
+        };
+        let doublings = (failures - 1).clamp(0, 16) as u32;
+        (base << doublings).min(RETRY_MAX_DELAY_SECS)
+    }
+}
+
+/// Classify a recorded failure and explain it in one sentence.
+pub fn retry_advice(kind: Option<&str>, http_status: Option<u16>) -> (RetryAdvice, &'static str) {
+    match (kind, http_status) {
+        (_, Some(401)) | (Some("authentication"), _) => (
+            RetryAdvice::Reauthenticate,
+            "GitLab rejected the token. Re-authenticate this instance, then retry.",
+        ),
+        (_, Some(429)) => (
+            RetryAdvice::RetryLater,
+            "GitLab is rate limiting requests. Retry in a few minutes.",
+        ),
+        (_, Some(500..=599)) => (
+            RetryAdvice::RetryLater,
+            "GitLab returned a server error. Retrying later usually works.",
+        ),
+        (_, Some(403)) => (
+            RetryAdvice::Discard,
+            "GitLab refused the action for your account. Retrying won't help unless your permissions change.",
+        ),
+        (_, Some(404 | 405 | 410)) => (
+            RetryAdvice::Discard,
+            "The merge request or discussion no longer accepts this action.",
+        ),
+        (_, Some(400 | 422)) => (
+            RetryAdvice::Discard,
+            "GitLab rejected the request content. Retrying will fail the same way.",
+        ),
+        (_, Some(409)) => (
+            RetryAdvice::Retry,
+            "GitLab reported a conflicting change. Retrying after the next sync usually works.",
+        ),
+        (Some("network"), _) => (
+            RetryAdvice::Retry,
+            "GitLab could not be reached. Retry once you are back online.",
+        ),
+        _ => (
+            RetryAdvice::Retry,
+            "No details were recorded for this failure. Retrying may help.",
+        ),
+    }
+}
+
+/// A failed action with everything needed to explain it.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FailedAction {
+    pub id: i64,
+    pub mr_id: i64,
+    /// Title of the target MR, if it is still cached.
+    pub mr_title: Option<String>,
+    pub action_type: String,
+    /// `failed`, or `pending` while waiting for an automatic retry.
+    pub status: String,
+    /// When the automatic retry is due; `None` once retries stopped.
+    pub next_retry_at: Option<i64>,
+    pub retry_count: i64,
+    pub last_error: Option<String>,
+    pub error_kind: Option<String>,
+    pub http_status: Option<u16>,
+    /// Message returned by GitLab, without our own prefix.
+    pub gitlab_message: Option<String>,
+    /// API path that failed.
+    pub endpoint: Option<String>,
+    pub created_at: i64,
+    pub first_attempt_at: Option<i64>,
+    pub last_attempt_at: Option<i64>,
+    pub advice: RetryAdvice,
+    pub hint: String,
+}
+
+#[derive(sqlx::FromRow)]
+struct FailedActionRow {
+    id: i64,
+    mr_id: i64,
+    mr_title: Option<String>,
+    action_type: String,
+    status: String,
+    next_retry_at: Option<i64>,
+    retry_count: i64,
+    last_error: Option<String>,
+    error_kind: Option<String>,
+    error_status: Option<i64>,
+    error_message: Option<String>,
+    error_endpoint: Option<String>,
+    created_at: i64,
+    first_attempt_at: Option<i64>,
+    last_attempt_at: Option<i64>,
+}
+
+impl From<FailedActionRow> for FailedAction {
+    fn from(row: FailedActionRow) -> Self {
+        let http_status = row.error_status.and_then(|s| u16::try_from(s).ok());
+        let (advice, hint) = retry_advice(row.error_kind.as_deref(), http_status);
+        Self {
+            id: row.id,
+            mr_id: row.mr_id,
+            mr_title: row.mr_title,
+            action_type: row.action_type,
+            status: row.status,
+            next_retry_at: row.next_retry_at,
+            retry_count: row.retry_count,
+            last_error: row.last_error,
+            error_kind: row.error_kind,
+            http_status,
+            gitlab_message: row.error_message,
+            endpoint: row.error_endpoint,
+            created_at: row.created_at,
+            first_attempt_at: row.first_attempt_at,
+            last_attempt_at: row.last_attempt_at,
+            advice,
+            hint: hint.to_string(),
+        }
+    }
+}
+
 /// Get the current Unix timestamp.
 fn now() -> i64 {
     SystemTime::now()
@@ -112,6 +372,183 @@ pub async fn enqueue_action(pool: &DbPool, input: EnqueueInput) -> Result<SyncAc
         last_error: result.get("last_error"),
         created_at: result.get("created_at"),
         synced_at: result.get("synced_at"),
+        batch_id: None,
+    })
+}
+
+/// Batches whose actions are all settled are kept this long for status
+/// queries, then pruned by [`cleanup_synced`].
+const SETTLED_BATCH_TTL_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// Start a batch of `action_count` actions on an MR. Returns its id.
+pub async fn create_batch(
+    conn: &mut SqliteConnection,
+    mr_id: i64,
+    action_count: i64,
+) -> Result<String, AppError> {
+    let batch_id = uuid::Uuid::new_v4().to_string();
+    sqlx::query(
+        "INSERT INTO sync_batches (id, mr_id, action_count, synced_count, created_at)
+         VALUES (?, ?, ?, 0, ?)",
+    )
+    .bind(&batch_id)
+    .bind(mr_id)
+    .bind(action_count)
+    .bind(now())
+    .execute(conn)
+    .await?;
+    Ok(batch_id)
+}
+
+/// Queue an action as the next step of a batch, on the caller's connection
+/// so a whole batch can be queued in one transaction. Returns the action id.
+pub async fn enqueue_batch_action(
+    conn: &mut SqliteConnection,
+    batch_id: &str,
+    input: EnqueueInput,
+) -> Result<i64, AppError> {
+    let id = sqlx::query_scalar(
+        "INSERT INTO sync_queue
+         (mr_id, action_type, payload, local_reference_id, status, retry_count, created_at, batch_id)
+         VALUES (?, ?, ?, ?, 'pending', 0, ?, ?)
+         RETURNING id",
+    )
+    .bind(input.mr_id)
+    .bind(input.action_type.to_string())
+    .bind(&input.payload)
+    .bind(input.local_reference_id)
+    .bind(now())
+    .bind(batch_id)
+    .fetch_one(conn)
+    .await?;
+    Ok(id)
+}
+
+/// Whether an earlier action of `action`'s batch hasn't synced yet, so
+/// pushing `action` now would reorder the batch.
+pub async fn waiting_on_batch(pool: &DbPool, action: &SyncAction) -> Result<bool, AppError> {
+    let Some(batch_id) = &action.batch_id else {
+        return Ok(false);
+    };
+    let waiting = sqlx::query_scalar(
+        "SELECT EXISTS (
+             SELECT 1 FROM sync_queue
+             WHERE batch_id = ? AND id < ? AND status NOT IN ('synced', 'discarded')
+         )",
+    )
+    .bind(batch_id)
+    .bind(action.id)
+    .fetch_one(pool)
+    .await?;
+    Ok(waiting)
+}
+
+/// Overall progress of a batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchState {
+    /// Actions are still queued or being retried, and none has given up.
+    Pending,
+    /// Every action synced.
+    Synced,
+    /// Some actions synced and at least one failed or was discarded.
+    Partial,
+    /// No action synced and at least one failed or was discarded.
+    Failed,
+}
+
+/// An action of a batch that hasn't been cleaned up yet.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchStep {
+    pub action_id: i64,
+    pub action_type: String,
+    /// Queue status, or `blocked` for a pending action held back by an
+    /// earlier action of the batch that failed.
+    pub status: String,
+    pub local_reference_id: Option<i64>,
+    pub retry_count: i64,
+    pub error: Option<String>,
+}
+
+/// Progress of a batch of actions.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchStatus {
+    pub batch_id: String,
+    pub mr_id: i64,
+    pub state: BatchState,
+    pub total: i64,
+    pub synced: i64,
+    /// Actions still in the queue, in order. Synced actions are cleaned up
+    /// after each sync and drop out of this list.
+    pub steps: Vec<BatchStep>,
+}
+
+fn batch_state(synced: i64, steps: &[BatchStep]) -> BatchState {
+    let gave_up = steps
+        .iter()
+        .any(|s| s.status == "failed" || s.status == "discarded");
+    let open = steps
+        .iter()
+        .any(|s| matches!(s.status.as_str(), "pending" | "syncing" | "blocked"));
+    match (gave_up, synced > 0) {
+        (false, _) if open => BatchState::Pending,
+        (false, _) => BatchState::Synced,
+        (true, true) => BatchState::Partial,
+        (true, false) => BatchState::Failed,
+    }
+}
+
+/// Progress of a batch.
+pub async fn get_batch_status(pool: &DbPool, batch_id: &str) -> Result<BatchStatus, AppError> {
+    let (mr_id, total, synced): (i64, i64, i64) =
+        sqlx::query_as("SELECT mr_id, action_count, synced_count FROM sync_batches WHERE id = ?")
+            .bind(batch_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::not_found_with_id("SyncBatch", batch_id))?;
+
+    let rows = sqlx::query_as::<_, SyncAction>(
+        r#"
+        SELECT id, mr_id, action_type, payload, local_reference_id, status, retry_count, last_error, created_at, synced_at, batch_id
+        FROM sync_queue
+        WHERE batch_id = ? AND status != 'synced'
+        ORDER BY id ASC
+        "#,
+    )
+    .bind(batch_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut blocked = false;
+    let steps: Vec<BatchStep> = rows
+        .into_iter()
+        .map(|action| {
+            let status = if blocked && action.status == "pending" {
+                "blocked".to_string()
+            } else {
+                action.status.clone()
+            };
+            blocked |= action.status == "failed";
+            BatchStep {
+                action_id: action.id,
+                action_type: action.action_type,
+                status,
+                local_reference_id: action.local_reference_id,
+                retry_count: action.retry_count,
+                error: action.last_error,
+            }
+        })
+        .collect();
+
+    Ok(BatchStatus {
+        batch_id: batch_id.to_string(),
+        mr_id,
+        state: batch_state(synced, &steps),
+        total,
+        synced,
+        steps,
     })
 }
 
@@ -125,10 +562,10 @@ pub async fn enqueue_action(pool: &DbPool, input: EnqueueInput) -> Result<SyncAc
 pub async fn get_pending_actions(pool: &DbPool) -> Result<Vec<SyncAction>, AppError> {
     let actions = sqlx::query_as::<_, SyncAction>(
         r#"
-        SELECT id, mr_id, action_type, payload, local_reference_id, status, retry_count, last_error, created_at, synced_at
+        SELECT id, mr_id, action_type, payload, local_reference_id, status, retry_count, last_error, created_at, synced_at, batch_id
         FROM sync_queue
         WHERE status = 'pending'
-        ORDER BY created_at ASC
+        ORDER BY created_at ASC, id ASC
         "#,
     )
     .fetch_all(pool)
@@ -137,6 +574,26 @@ pub async fn get_pending_actions(pool: &DbPool) -> Result<Vec<SyncAction>, AppEr
     Ok(actions)
 }
 
+/// Get the pending actions whose automatic retry, if any, is due at `now`.
+///
+/// This is what the queue pushes; `get_pending_actions` also lists actions
+/// still backing off.
+pub async fn get_due_actions(pool: &DbPool, now: i64) -> Result<Vec<SyncAction>, AppError> {
+    let actions = sqlx::query_as::<_, SyncAction>(
+        r#"
+        SELECT id, mr_id, action_type, payload, local_reference_id, status, retry_count, last_error, created_at, synced_at, batch_id
+        FROM sync_queue
+        WHERE status = 'pending' AND (next_retry_at IS NULL OR next_retry_at <= ?)
+        ORDER BY created_at ASC, id ASC
+        "#,
+    )
+    .bind(now)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(actions)
+}
+
 /// Get pending actions filtered by action type.
 ///
 /// # Arguments
@@ -144,20 +601,23 @@ pub async fn get_pending_actions(pool: &DbPool) -> Result<Vec<SyncAction>, AppEr
 /// * `action_type` - Action type to filter by (e.g., `ActionType::Approve`)
 ///
 /// # Returns
-/// List of pending actions matching the given type, ordered by creation time
+/// List of pending actions matching the given type, ordered by creation time.
+/// Actions waiting for an automatic retry are left out until it is due.
 pub async fn get_pending_actions_by_type(
     pool: &DbPool,
     action_type: ActionType,
 ) -> Result<Vec<SyncAction>, AppError> {
     let actions = sqlx::query_as::<_, SyncAction>(
         r#"
-        SELECT id, mr_id, action_type, payload, local_reference_id, status, retry_count, last_error, created_at, synced_at
+        SELECT id, mr_id, action_type, payload, local_reference_id, status, retry_count, last_error, created_at, synced_at, batch_id
         FROM sync_queue
         WHERE status = 'pending' AND action_type = ?
-        ORDER BY created_at ASC
+          AND (next_retry_at IS NULL OR next_retry_at <= ?)
+        ORDER BY created_at ASC, id ASC
         "#,
     )
     .bind(action_type.to_string())
+    .bind(now())
     .fetch_all(pool)
     .await?;
 
@@ -233,24 +693,74 @@ pub async fn mark_syncing(pool: &DbPool, action_id: i64) -> Result<(), AppError>
 pub async fn mark_synced(pool: &DbPool, action_id: i64) -> Result<(), AppError> {
     let synced_at = now();
 
-    sqlx::query("UPDATE sync_queue SET status = 'synced', synced_at = ? WHERE id = ?")
-        .bind(synced_at)
+    let result = sqlx::query(
+        "UPDATE sync_queue SET status = 'synced', synced_at = ? WHERE id = ? AND status != 'synced'",
+    )
+    .bind(synced_at)
+    .bind(action_id)
+    .execute(pool)
+    .await?;
+
+    // Count it on its batch, which outlives the synced row.
+    if result.rows_affected() > 0 {
+        sqlx::query(
+            "UPDATE sync_batches SET synced_count = synced_count + 1
+             WHERE id = (SELECT batch_id FROM sync_queue WHERE id = ?)",
+        )
         .bind(action_id)
         .execute(pool)
         .await?;
+    }
 
     Ok(())
 }
 
 /// Mark action as failed with error message.
 ///
-/// Increments retry count and marks as permanently failed if max retries reached.
+/// Increments retry count and schedules an automatic retry under the default
+/// `RetryPolicy`, or marks it permanently failed once attempts run out.
 ///
 /// # Arguments
 /// * `pool` - Database connection pool
 /// * `action_id` - Action ID to update
 /// * `error` - Error message
 pub async fn mark_failed(pool: &DbPool, action_id: i64, error: &str) -> Result<(), AppError> {
+    let details = FailureDetails::kind("other", error);
+    record_failure(pool, action_id, error, &details, &RetryPolicy::default()).await
+}
+
+/// Mark action as failed, keeping the HTTP status, GitLab message and
+/// endpoint carried by `err` for the retry UI.
+pub async fn mark_failed_with_error(
+    pool: &DbPool,
+    action_id: i64,
+    err: &AppError,
+) -> Result<(), AppError> {
+    mark_failed_with_policy(pool, action_id, err, &RetryPolicy::default()).await
+}
+
+/// Like `mark_failed_with_error`, with the user's retry policy.
+///
+/// Errors retrying can't fix (rejected content, missing permission, an
+/// expired token) fail the action at once; others are retried after a
+/// capped exponential backoff until `policy.max_attempts` is reached.
+pub async fn mark_failed_with_policy(
+    pool: &DbPool,
+    action_id: i64,
+    err: &AppError,
+    policy: &RetryPolicy,
+) -> Result<(), AppError> {
+    let details = FailureDetails::from_error(err);
+    record_failure(pool, action_id, &err.to_string(), &details, policy).await
+}
+
+async fn record_failure(
+    pool: &DbPool,
+    action_id: i64,
+    error: &str,
+    details: &FailureDetails,
+    policy: &RetryPolicy,
+) -> Result<(), AppError> {
     // First get current retry count
     let row = sqlx::query("SELECT retry_count FROM sync_queue WHERE id = ?")
         .bind(action_id)
@@ -267,21 +777,38 @@ pub async fn mark_failed(pool: &DbPool, action_id: i64, error: &str) -> Result<(
     let retry_count: i64 = row.get("retry_count");
     let new_retry_count = retry_count + 1;
 
-    // If max retries reached, mark as permanently failed
-    // Otherwise, reset to pending for retry
-    let new_status = if new_retry_count >= SyncAction::MAX_RETRIES {
-        "failed"
-    } else {
+    // Back to pending with a retry time, unless retrying can't help or the
+    // attempts are used up: then it waits for the user.
+    let attempted_at = now();
+    let (advice, _) = retry_advice(Some(details.kind), details.http_status);
+    let next_retry_at = (advice.is_retryable() && new_retry_count < policy.max_attempts)
+        .then(|| attempted_at + policy.delay_secs(new_retry_count, advice));
+    let new_status = if next_retry_at.is_some() {
         "pending"
+    } else {
+        "failed"
     };
 
-    sqlx::query("UPDATE sync_queue SET status = ?, retry_count = ?, last_error = ? WHERE id = ?")
-        .bind(new_status)
-        .bind(new_retry_count)
-        .bind(error)
-        .bind(action_id)
-        .execute(pool)
-        .await?;
+    sqlx::query(
+        "UPDATE sync_queue
+         SET status = ?, retry_count = ?, last_error = ?, next_retry_at = ?,
+             error_kind = ?, error_status = ?, error_message = ?, error_endpoint = ?,
+             first_attempt_at = COALESCE(first_attempt_at, ?), last_attempt_at = ?
+         WHERE id = ?",
+    )
+    .bind(new_status)
+    .bind(new_retry_count)
+    .bind(error)
+    .bind(next_retry_at)
+    .bind(details.kind)
+    .bind(details.http_status.map(i64::from))
+    .bind(&details.message)
+    .bind(&details.endpoint)
+    .bind(attempted_at)
+    .bind(attempted_at)
+    .bind(action_id)
+    .execute(pool)
+    .await?;
 
     Ok(())
 }
@@ -293,7 +820,8 @@ pub async fn mark_failed(pool: &DbPool, action_id: i64, error: &str) -> Result<(
 /// * `action_id` - Action ID to retry
 pub async fn retry_action(pool: &DbPool, action_id: i64) -> Result<(), AppError> {
     let result = sqlx::query(
-        "UPDATE sync_queue SET status = 'pending', last_error = NULL WHERE id = ? AND status = 'failed'",
+        "UPDATE sync_queue SET status = 'pending', last_error = NULL, next_retry_at = NULL
+         WHERE id = ? AND status = 'failed'",
     )
     .bind(action_id)
     .execute(pool)
@@ -309,7 +837,24 @@ pub async fn retry_action(pool: &DbPool, action_id: i64) -> Result<(), AppError>
     Ok(())
 }
 
-/// Delete an action from the queue.
+/// Remove the optimistic local comment of a comment or reply action that will
+/// never sync, so it doesn't stay in the thread as an orphan.
+async fn remove_local_comment(pool: &DbPool, action_id: i64) -> Result<(), AppError> {
+    sqlx::query(
+        "DELETE FROM comments
+         WHERE is_local = 1 AND id = (
+             SELECT local_reference_id FROM sync_queue
+             WHERE id = ? AND action_type IN ('comment', 'reply')
+         )",
+    )
+    .bind(action_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Delete an action from the queue, along with its local comment if any.
 ///
 /// Typically used for discarding failed actions.
 ///
@@ -317,6 +862,7 @@ pub async fn retry_action(pool: &DbPool, action_id: i64) -> Result<(), AppError>
 /// * `pool` - Database connection pool
 /// * `action_id` - Action ID to delete
 pub async fn delete_action(pool: &DbPool, action_id: i64) -> Result<(), AppError> {
+    remove_local_comment(pool, action_id).await?;
     let result = sqlx::query("DELETE FROM sync_queue WHERE id = ?")
         .bind(action_id)
         .execute(pool)
@@ -335,13 +881,15 @@ pub async fn delete_action(pool: &DbPool, action_id: i64) -> Result<(), AppError
 /// Mark action as discarded because the MR is no longer actionable.
 ///
 /// This is used when the MR has been merged, closed, or deleted on GitLab
-/// while local actions were pending.
+/// while local actions were pending. A discarded comment's local row is
+/// removed.
 ///
 /// # Arguments
 /// * `pool` - Database connection pool
 /// * `action_id` - Action ID to update
 /// * `reason` - Reason for discarding (e.g., "MR was merged", "MR not found")
 pub async fn mark_discarded(pool: &DbPool, action_id: i64, reason: &str) -> Result<(), AppError> {
+    remove_local_comment(pool, action_id).await?;
     sqlx::query("UPDATE sync_queue SET status = 'discarded', last_error = ? WHERE id = ?")
         .bind(reason)
         .bind(action_id)
@@ -351,6 +899,38 @@ pub async fn mark_discarded(pool: &DbPool, action_id: i64, reason: &str) -> Resu
     Ok(())
 }
 
+/// Stop the queued comments, replies and resolves of an MR because GitLab
+/// won't accept them (locked discussion, missing permission).
+///
+/// They end up failed with no retries left and a 403 attached, so the retry
+/// UI offers to discard them instead of the queue pushing them again. Returns
+/// the number of actions marked.
+pub async fn fail_discussion_actions(
+    pool: &DbPool,
+    mr_id: i64,
+    reason: &str,
+) -> Result<u64, AppError> {
+    let attempted_at = now();
+    let result = sqlx::query(
+        "UPDATE sync_queue
+         SET status = 'failed', retry_count = MAX(retry_count, ?), last_error = ?,
+             error_kind = 'gitlab_api', error_status = 403, error_message = ?,
+             first_attempt_at = COALESCE(first_attempt_at, ?), last_attempt_at = ?
+         WHERE mr_id = ? AND status IN ('pending', 'syncing')
+           AND action_type IN ('comment', 'reply', 'resolve', 'unresolve')",
+    )
+    .bind(SyncAction::MAX_RETRIES)
+    .bind(reason)
+    .bind(reason)
+    .bind(attempted_at)
+    .bind(attempted_at)
+    .bind(mr_id)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
 /// Get counts of actions by status.
 ///
 /// # Arguments
@@ -373,6 +953,27 @@ pub async fn get_action_counts(pool: &DbPool) -> Result<(i64, i64), AppError> {
     Ok((row.get("pending"), row.get("failed")))
 }
 
+/// Get failed actions with their failure details, oldest first: permanently
+/// failed ones and those waiting for an automatic retry.
+pub async fn get_failed_actions(pool: &DbPool) -> Result<Vec<FailedAction>, AppError> {
+    let rows = sqlx::query_as::<_, FailedActionRow>(
+        r#"
+        SELECT sq.id, sq.mr_id, mr.title AS mr_title, sq.action_type, sq.status,
+               sq.next_retry_at, sq.retry_count,
+               sq.last_error, sq.error_kind, sq.error_status, sq.error_message,
+               sq.error_endpoint, sq.created_at, sq.first_attempt_at, sq.last_attempt_at
+        FROM sync_queue sq
+        LEFT JOIN merge_requests mr ON mr.id = sq.mr_id
+        WHERE sq.status = 'failed' OR (sq.status = 'pending' AND sq.next_retry_at IS NOT NULL)
+        ORDER BY sq.created_at ASC
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(FailedAction::from).collect())
+}
+
 /// Recover actions stuck in 'syncing' state after a crash.
 ///
 /// On app startup, any actions left in 'syncing' state were mid-flight when
@@ -406,9 +1007,165 @@ pub async fn cleanup_synced(pool: &DbPool) -> Result<u64, AppError> {
         .execute(pool)
         .await?;
 
+    sqlx::query(
+        "DELETE FROM sync_batches
+         WHERE created_at < ?
+           AND NOT EXISTS (
+               SELECT 1 FROM sync_queue q
+               WHERE q.batch_id = sync_batches.id AND q.status IN ('pending', 'syncing', 'failed')
+           )",
+    )
+    .bind(now() - SETTLED_BATCH_TTL_SECS)
+    .execute(pool)
+    .await?;
+
     Ok(result.rows_affected())
 }
 
+/// Orphaned actions kept for the user to look at; older ones are pruned.
+const ORPHANED_ACTIONS_KEPT: i64 = 200;
+
+/// A queued action that was dropped because its MR left the cache.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanedAction {
+    pub id: i64,
+    /// ID the action had in the queue.
+    pub action_id: i64,
+    pub mr_id: i64,
+    pub project_id: Option<i64>,
+    pub mr_iid: Option<i64>,
+    /// Title of the MR when the action was dropped, if it was still cached.
+    pub mr_title: Option<String>,
+    pub action_type: String,
+    /// Comment or reply text, so it isn't lost with the action.
+    pub body: Option<String>,
+    /// Queue status at the time (`pending`, `syncing` or `failed`).
+    pub status: String,
+    pub reason: String,
+    pub created_at: i64,
+    pub dropped_at: i64,
+}
+
+/// Move the unfinished actions matching `condition` (a `WHERE` clause on
+/// `sq`) to `orphaned_actions`, then delete them from the queue.
+async fn drop_actions_where(
+    pool: &DbPool,
+    condition: &str,
+    mr_ids: &[i64],
+    reason: &str,
+) -> Result<u64, AppError> {
+    let mut tx = pool.begin().await?;
+
+    let insert = format!(
+        "INSERT INTO orphaned_actions
+         (action_id, mr_id, project_id, mr_iid, mr_title, action_type, payload, status,
+          reason, created_at, dropped_at)
+         SELECT sq.id, sq.mr_id, json_extract(sq.payload, '$.project_id'),
+                json_extract(sq.payload, '$.mr_iid'), mr.title, sq.action_type, sq.payload,
+                sq.status, ?, sq.created_at, ?
+         FROM sync_queue sq
+         LEFT JOIN merge_requests mr ON mr.id = sq.mr_id
+         WHERE sq.status IN ('pending', 'syncing', 'failed') AND {condition}"
+    );
+    let mut q = sqlx::query(&insert).bind(reason).bind(now());
+    for id in mr_ids {
+        q = q.bind(*id);
+    }
+    let dropped = q.execute(&mut *tx).await?.rows_affected();
+
+    let delete = format!("DELETE FROM sync_queue AS sq WHERE {condition}");
+    let mut q = sqlx::query(&delete);
+    for id in mr_ids {
+        q = q.bind(*id);
+    }
+    q.execute(&mut *tx).await?;
+
+    sqlx::query(
+        "DELETE FROM orphaned_actions WHERE id NOT IN
+         (SELECT id FROM orphaned_actions ORDER BY dropped_at DESC, id DESC LIMIT ?)",
+    )
+    .bind(ORPHANED_ACTIONS_KEPT)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(dropped)
+}
+
+/// Cancel the queued actions of MRs that are about to be deleted from the
+/// cache, recording each unfinished one with `reason`. Call before deleting
+/// the MR rows, which would otherwise take the actions with them silently.
+///
+/// # Returns
+/// Number of unfinished actions dropped
+pub async fn drop_actions_for_mrs(
+    pool: &DbPool,
+    mr_ids: &[i64],
+    reason: &str,
+) -> Result<u64, AppError> {
+    if mr_ids.is_empty() {
+        return Ok(0);
+    }
+    let placeholders = vec!["?"; mr_ids.len()].join(", ");
+    drop_actions_where(
+        pool,
+        &format!("sq.mr_id IN ({placeholders})"),
+        mr_ids,
+        reason,
+    )
+    .await
+}
+
+/// Deal with unfinished actions whose MR row no longer exists.
+///
+/// Every payload carries the MR's project and iid. When exactly one cached
+/// MR matches them (the MR was cached again under a new row), the action is
+/// re-homed to it. The rest are dropped and recorded as orphaned.
+///
+/// # Returns
+/// Tuple of (rehomed_count, dropped_count)
+pub async fn resolve_orphaned_actions(pool: &DbPool) -> Result<(u64, u64), AppError> {
+    let rehomed = sqlx::query(
+        "UPDATE sync_queue
+         SET mr_id = (
+             SELECT mr.id FROM merge_requests mr
+             WHERE mr.project_id = json_extract(sync_queue.payload, '$.project_id')
+               AND mr.iid = json_extract(sync_queue.payload, '$.mr_iid')
+         )
+         WHERE status IN ('pending', 'syncing', 'failed')
+           AND mr_id NOT IN (SELECT id FROM merge_requests)
+           AND (SELECT COUNT(*) FROM merge_requests mr
+                WHERE mr.project_id = json_extract(sync_queue.payload, '$.project_id')
+                  AND mr.iid = json_extract(sync_queue.payload, '$.mr_iid')) = 1",
+    )
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    let dropped = drop_actions_where(
+        pool,
+        "sq.mr_id NOT IN (SELECT id FROM merge_requests)",
+        &[],
+        "The merge request is no longer cached",
+    )
+    .await?;
+
+    Ok((rehomed, dropped))
+}
+
+/// Get recently dropped actions, newest first.
+pub async fn get_orphaned_actions(pool: &DbPool) -> Result<Vec<OrphanedAction>, AppError> {
+    Ok(sqlx::query_as(
+        "SELECT id, action_id, mr_id, project_id, mr_iid, mr_title, action_type,
+                json_extract(payload, '$.body') AS body, status, reason, created_at, dropped_at
+         FROM orphaned_actions
+         ORDER BY dropped_at DESC, id DESC",
+    )
+    .fetch_all(pool)
+    .await?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -623,6 +1380,327 @@ mod tests {
         assert_eq!(failed, 1);
     }
 
+    #[tokio::test]
+    async fn test_fail_discussion_actions_stops_retries() {
+        let pool = setup_test_db().await;
+
+        for action_type in [ActionType::Comment, ActionType::Reply, ActionType::Approve] {
+            enqueue_action(
+                &pool,
+                EnqueueInput {
+                    mr_id: 1,
+                    action_type,
+                    payload: "{}".to_string(),
+                    local_reference_id: None,
+                },
+            )
+            .await
+            .unwrap();
+        }
+
+        let marked = fail_discussion_actions(&pool, 1, "Discussion is locked")
+            .await
+            .unwrap();
+        assert_eq!(marked, 2);
+
+        let pending = get_pending_actions(&pool).await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].action_type, "approve");
+        assert!(get_retryable_actions(&pool).await.unwrap().is_empty());
+
+        let failed = get_failed_actions(&pool).await.unwrap();
+        assert_eq!(failed.len(), 2);
+        assert!(failed.iter().all(|f| f.advice == RetryAdvice::Discard
+            && f.gitlab_message.as_deref() == Some("Discussion is locked")));
+    }
+
+    #[tokio::test]
+    async fn test_dropping_comment_actions_removes_local_comments() {
+        let pool = setup_test_db().await;
+
+        let mut actions = Vec::new();
+        for (local_id, action_type) in [(-1, ActionType::Comment), (-2, ActionType::Reply)] {
+            sqlx::query(
+                "INSERT INTO comments (id, mr_id, author_username, body, resolved, resolvable,
+                                       system, created_at, updated_at, cached_at, is_local)
+                 VALUES (?, 1, 'user', 'Looks off', 0, 1, 0, 0, 0, 0, 1)",
+            )
+            .bind(local_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+            let action = enqueue_action(
+                &pool,
+                EnqueueInput {
+                    mr_id: 1,
+                    action_type,
+                    payload: "{}".to_string(),
+                    local_reference_id: Some(local_id),
+                },
+            )
+            .await
+            .unwrap();
+            actions.push(action);
+        }
+
+        delete_action(&pool, actions[0].id).await.unwrap();
+        mark_discarded(&pool, actions[1].id, "MR was merged")
+            .await
+            .unwrap();
+
+        let remaining: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM comments")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(remaining, 0);
+    }
+
+    #[tokio::test]
+    async fn test_drop_actions_for_mrs_records_unfinished() {
+        let pool = setup_test_db().await;
+
+        let comment = enqueue_action(
+            &pool,
+            EnqueueInput {
+                mr_id: 1,
+                action_type: ActionType::Comment,
+                payload: r#"{"project_id":1,"mr_iid":1,"body":"Looks off"}"#.to_string(),
+                local_reference_id: None,
+            },
+        )
+        .await
+        .unwrap();
+        let approve = enqueue_action(
+            &pool,
+            EnqueueInput {
+                mr_id: 1,
+                action_type: ActionType::Approve,
+                payload: r#"{"project_id":1,"mr_iid":1}"#.to_string(),
+                local_reference_id: None,
+            },
+        )
+        .await
+        .unwrap();
+        mark_synced(&pool, approve.id).await.unwrap();
+
+        let dropped = drop_actions_for_mrs(&pool, &[1], "MR was purged")
+            .await
+            .unwrap();
+        assert_eq!(dropped, 1);
+        assert!(get_actions_for_mr(&pool, 1).await.unwrap().is_empty());
+
+        let orphaned = get_orphaned_actions(&pool).await.unwrap();
+        assert_eq!(orphaned.len(), 1);
+        assert_eq!(orphaned[0].action_id, comment.id);
+        assert_eq!(orphaned[0].mr_title.as_deref(), Some("Test MR"));
+        assert_eq!(orphaned[0].mr_iid, Some(1));
+        assert_eq!(orphaned[0].body.as_deref(), Some("Looks off"));
+        assert_eq!(orphaned[0].status, "pending");
+        assert_eq!(orphaned[0].reason, "MR was purged");
+    }
+
+    #[tokio::test]
+    async fn test_resolve_orphaned_actions_rehomes_or_drops() {
+        let pool = setup_test_db().await;
+
+        // Orphans can only exist when foreign keys were off at some point.
+        let mut conn = pool.acquire().await.unwrap();
+        sqlx::query("PRAGMA foreign_keys = OFF")
+            .execute(&mut *conn)
+            .await
+            .unwrap();
+        sqlx::query(
+            r#"INSERT INTO sync_queue (mr_id, action_type, payload, status, created_at) VALUES
+               (99, 'comment', '{"project_id":1,"mr_iid":1,"body":"a"}', 'pending', 0),
+               (98, 'approve', '{"project_id":5,"mr_iid":5}', 'failed', 0)"#,
+        )
+        .execute(&mut *conn)
+        .await
+        .unwrap();
+        sqlx::query("PRAGMA foreign_keys = ON")
+            .execute(&mut *conn)
+            .await
+            .unwrap();
+        drop(conn);
+
+        assert_eq!(resolve_orphaned_actions(&pool).await.unwrap(), (1, 1));
+
+        let rehomed = get_actions_for_mr(&pool, 1).await.unwrap();
+        assert_eq!(rehomed.len(), 1);
+        assert_eq!(rehomed[0].action_type, "comment");
+
+        let orphaned = get_orphaned_actions(&pool).await.unwrap();
+        assert_eq!(orphaned.len(), 1);
+        assert_eq!(orphaned[0].mr_id, 98);
+        assert_eq!(orphaned[0].mr_title, None);
+        assert_eq!(orphaned[0].status, "failed");
+    }
+
+    #[tokio::test]
+    async fn test_failed_actions_keep_failure_details() {
+        let pool = setup_test_db().await;
+
+        let action = enqueue_action(
+            &pool,
+            EnqueueInput {
+                mr_id: 1,
+                action_type: ActionType::Comment,
+                payload: "{}".to_string(),
+                local_reference_id: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        mark_failed_with_error(&pool, action.id, &AppError::network("timed out"))
+            .await
+            .unwrap();
+        let err = AppError::gitlab_api_full(
+            "Note can't be blank",
+            422,
+            "/projects/1/merge_requests/1/notes",
+        );
+        for _ in 1..SyncAction::MAX_RETRIES {
+            mark_failed_with_error(&pool, action.id, &err)
+                .await
+                .unwrap();
+        }
+
+        let failed = get_failed_actions(&pool).await.unwrap();
+        assert_eq!(failed.len(), 1);
+        let failed = &failed[0];
+        assert_eq!(failed.mr_title.as_deref(), Some("Test MR"));
+        assert_eq!(failed.error_kind.as_deref(), Some("gitlab_api"));
+        assert_eq!(failed.http_status, Some(422));
+        assert_eq!(
+            failed.gitlab_message.as_deref(),
+            Some("Note can't be blank")
+        );
+        assert_eq!(
+            failed.endpoint.as_deref(),
+            Some("/projects/1/merge_requests/1/notes")
+        );
+        assert!(failed.first_attempt_at.is_some());
+        assert!(failed.last_attempt_at >= failed.first_attempt_at);
+        assert_eq!(failed.advice, RetryAdvice::Discard);
+    }
+
+    #[test]
+    fn test_retry_advice() {
+        let advice = |kind, status| retry_advice(kind, status).0;
+        assert_eq!(advice(Some("network"), None), RetryAdvice::Retry);
+        assert_eq!(
+            advice(Some("gitlab_api"), Some(401)),
+            RetryAdvice::Reauthenticate
+        );
+        assert_eq!(
+            advice(Some("authentication"), None),
+            RetryAdvice::Reauthenticate
+        );
+        assert_eq!(
+            advice(Some("gitlab_api"), Some(429)),
+            RetryAdvice::RetryLater
+        );
+        assert_eq!(
+            advice(Some("gitlab_api"), Some(502)),
+            RetryAdvice::RetryLater
+        );
+        assert_eq!(advice(Some("gitlab_api"), Some(403)), RetryAdvice::Discard);
+        assert_eq!(advice(Some("gitlab_api"), Some(422)), RetryAdvice::Discard);
+        // Rows failed before details were recorded.
+        assert_eq!(advice(None, None), RetryAdvice::Retry);
+        assert!(!RetryAdvice::Reauthenticate.is_retryable());
+    }
+
+    #[test]
+    fn test_retry_delay_doubles_up_to_cap() {
+        let policy = RetryPolicy::default();
+        assert_eq!(
+            policy.delay_secs(1, RetryAdvice::Retry),
+            RETRY_BASE_DELAY_SECS
+        );
+        assert_eq!(
+            policy.delay_secs(2, RetryAdvice::Retry),
+            2 * RETRY_BASE_DELAY_SECS
+        );
+        assert_eq!(
+            policy.delay_secs(1, RetryAdvice::RetryLater),
+            RETRY_LATER_BASE_DELAY_SECS
+        );
+        assert_eq!(
+            policy.delay_secs(40, RetryAdvice::Retry),
+            RETRY_MAX_DELAY_SECS
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_action_backs_off_until_due() {
+        let pool = setup_test_db().await;
+
+        let action = enqueue_action(
+            &pool,
+            EnqueueInput {
+                mr_id: 1,
+                action_type: ActionType::Comment,
+                payload: "{}".to_string(),
+                local_reference_id: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let policy = RetryPolicy { max_attempts: 2 };
+        mark_failed_with_policy(&pool, action.id, &AppError::network("timed out"), &policy)
+            .await
+            .unwrap();
+
+        let failed = get_failed_actions(&pool).await.unwrap();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].status, "pending");
+        let retry_at = failed[0].next_retry_at.expect("retry scheduled");
+        assert!(get_due_actions(&pool, retry_at - 1)
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(get_due_actions(&pool, retry_at).await.unwrap().len(), 1);
+
+        // The second attempt is the last one the policy allows.
+        mark_failed_with_policy(&pool, action.id, &AppError::network("timed out"), &policy)
+            .await
+            .unwrap();
+        let failed = get_failed_actions(&pool).await.unwrap();
+        assert_eq!(failed[0].status, "failed");
+        assert_eq!(failed[0].next_retry_at, None);
+        assert!(get_due_actions(&pool, i64::MAX).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_validation_error_is_not_retried() {
+        let pool = setup_test_db().await;
+
+        let action = enqueue_action(
+            &pool,
+            EnqueueInput {
+                mr_id: 1,
+                action_type: ActionType::Comment,
+                payload: "{}".to_string(),
+                local_reference_id: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let err = AppError::gitlab_api_full("Note can't be blank", 422, "/notes");
+        mark_failed_with_error(&pool, action.id, &err)
+            .await
+            .unwrap();
+
+        let failed = get_failed_actions(&pool).await.unwrap();
+        assert_eq!(failed[0].status, "failed");
+        assert_eq!(failed[0].next_retry_at, None);
+        assert!(get_due_actions(&pool, i64::MAX).await.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn test_delete_action() {
         let pool = setup_test_db().await;
@@ -900,4 +1978,61 @@ mod tests {
             .unwrap();
         assert_eq!(row.get::<String, _>("status"), "failed");
     }
+
+    #[tokio::test]
+    async fn test_batch_is_pushed_in_order_and_reports_progress() {
+        let pool = setup_test_db().await;
+        let mut conn = pool.acquire().await.unwrap();
+        let batch_id = create_batch(&mut conn, 1, 3).await.unwrap();
+        let mut ids = Vec::new();
+        for action_type in [
+            ActionType::Comment,
+            ActionType::Resolve,
+            ActionType::Approve,
+        ] {
+            let input = EnqueueInput {
+                mr_id: 1,
+                action_type,
+                payload: "{}".to_string(),
+                local_reference_id: None,
+            };
+            ids.push(
+                enqueue_batch_action(&mut conn, &batch_id, input)
+                    .await
+                    .unwrap(),
+            );
+        }
+        drop(conn);
+
+        let actions = get_pending_actions(&pool).await.unwrap();
+        assert_eq!(actions.len(), 3);
+        assert!(!waiting_on_batch(&pool, &actions[0]).await.unwrap());
+        assert!(waiting_on_batch(&pool, &actions[1]).await.unwrap());
+
+        mark_synced(&pool, ids[0]).await.unwrap();
+        // Marking twice doesn't count twice.
+        mark_synced(&pool, ids[0]).await.unwrap();
+        assert!(!waiting_on_batch(&pool, &actions[1]).await.unwrap());
+        assert!(waiting_on_batch(&pool, &actions[2]).await.unwrap());
+        let status = get_batch_status(&pool, &batch_id).await.unwrap();
+        assert_eq!(
+            (status.state, status.total, status.synced),
+            (BatchState::Pending, 3, 1)
+        );
+
+        for _ in 0..SyncAction::MAX_RETRIES {
+            mark_failed(&pool, ids[1], "Error").await.unwrap();
+        }
+        assert!(waiting_on_batch(&pool, &actions[2]).await.unwrap());
+        let status = get_batch_status(&pool, &batch_id).await.unwrap();
+        assert_eq!(status.state, BatchState::Partial);
+        let steps: Vec<_> = status.steps.iter().map(|s| s.status.as_str()).collect();
+        assert_eq!(steps, vec!["failed", "blocked"]);
+
+        // Synced rows are cleaned up, but the batch keeps its count.
+        cleanup_synced(&pool).await.unwrap();
+        let status = get_batch_status(&pool, &batch_id).await.unwrap();
+        assert_eq!(status.synced, 1);
+        assert_eq!(status.steps.len(), 2);
+    }
 }
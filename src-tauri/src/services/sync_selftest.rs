@@ -0,0 +1,338 @@
+//! Scripted health check of the sync path for one instance.
+//!
+//! "Sync doesn't work" can mean a revoked token, a proxy eating requests,
+//! a GitLab too slow to list MRs or a broken local queue. The self-test
+//! walks the same path a sync takes, one step at a time, and reports which
+//! step failed and how long each took, so a bug report can carry the
+//! result instead of a description of the symptoms.
+//!
+//! Nothing is written: the queue step enqueues and flushes a throwaway
+//! action inside a transaction that is rolled back.
+
+use crate::db::pool::DbPool;
+use crate::error::AppError;
+use crate::models::sync_action::ActionType;
+use crate::services::gitlab_client::{
+    GitLabClient, GitLabClientConfig, GitLabMergeRequest, MergeRequestsQuery,
+};
+use crate::services::sync_queue::FailureDetails;
+use serde::Serialize;
+use std::future::Future;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StepStatus {
+    Passed,
+    Failed,
+    /// Not run because a step it depends on failed or found nothing.
+    Skipped,
+}
+
+/// Outcome of one self-test step.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfTestStep {
+    /// Step identifier: `validate_token`, `list_mrs`, `fetch_diff` or `queue`.
+    pub name: &'static str,
+    pub status: StepStatus,
+    pub duration_ms: u64,
+    /// What the step found, or why it failed or was skipped.
+    pub detail: String,
+    /// Error category of a failure, as recorded for failed sync actions.
+    pub error_kind: Option<&'static str>,
+    pub http_status: Option<u16>,
+    pub endpoint: Option<String>,
+}
+
+impl SelfTestStep {
+    fn skipped(name: &'static str, reason: &str) -> Self {
+        Self {
+            name,
+            status: StepStatus::Skipped,
+            duration_ms: 0,
+            detail: reason.to_string(),
+            error_kind: None,
+            http_status: None,
+            endpoint: None,
+        }
+    }
+}
+
+/// Result of a self-test run.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfTestReport {
+    pub instance_id: i64,
+    pub instance_url: String,
+    /// True when no step failed.
+    pub passed: bool,
+    pub steps: Vec<SelfTestStep>,
+    pub started_at: i64,
+    pub total_ms: u64,
+}
+
+/// Time `step` and turn its outcome into a report entry. `Ok` carries the
+/// value later steps need and the detail to show.
+async fn timed<T, F>(name: &'static str, step: F) -> (SelfTestStep, Option<T>)
+where
+    F: Future<Output = Result<(T, String), AppError>>,
+{
+    let start = Instant::now();
+    let outcome = step.await;
+    let duration_ms = start.elapsed().as_millis() as u64;
+    match outcome {
+        Ok((value, detail)) => (
+            SelfTestStep {
+                name,
+                status: StepStatus::Passed,
+                duration_ms,
+                detail,
+                error_kind: None,
+                http_status: None,
+                endpoint: None,
+            },
+            Some(value),
+        ),
+        Err(e) => {
+            let details = FailureDetails::from_error(&e);
+            (
+                SelfTestStep {
+                    name,
+                    status: StepStatus::Failed,
+                    duration_ms,
+                    detail: details.message,
+                    error_kind: Some(details.kind),
+                    http_status: details.http_status,
+                    endpoint: details.endpoint,
+                },
+                None,
+            )
+        }
+    }
+}
+
+/// Enqueue a throwaway action for `mr_id`, check the flush would pick it
+/// up and mark it synced, then roll everything back.
+pub async fn dry_run_queue(pool: &DbPool, mr_id: i64) -> Result<(), AppError> {
+    let mut tx = pool.begin().await?;
+    let action_id: i64 = sqlx::query_scalar(
+        "INSERT INTO sync_queue (mr_id, action_type, payload, status, retry_count, created_at)
+         VALUES (?, ?, '{}', 'pending', 0, ?)
+         RETURNING id",
+    )
+    .bind(mr_id)
+    .bind(ActionType::Comment.to_string())
+    .bind(now())
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let pending: Vec<i64> = sqlx::query_scalar(
+        "SELECT id FROM sync_queue WHERE status = 'pending' ORDER BY created_at, id",
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    if !pending.contains(&action_id) {
+        return Err(AppError::sync("Queued action is not visible to the flush"));
+    }
+
+    let flushed = sqlx::query(
+        "UPDATE sync_queue SET status = 'synced', synced_at = ? WHERE id = ? AND status = 'pending'",
+    )
+    .bind(now())
+    .bind(action_id)
+    .execute(&mut *tx)
+    .await?;
+    if flushed.rows_affected() != 1 {
+        return Err(AppError::sync_with_action(
+            "Queued action could not be marked synced",
+            action_id,
+        ));
+    }
+
+    tx.rollback().await?;
+    Ok(())
+}
+
+/// Run the self-test against an instance. Step failures go into the
+/// report; only a missing instance is an error.
+pub async fn run(pool: &DbPool, instance_id: i64) -> Result<SelfTestReport, AppError> {
+    let (url, token): (String, Option<String>) =
+        sqlx::query_as("SELECT url, token FROM gitlab_instances WHERE id = ?")
+            .bind(instance_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| {
+                AppError::not_found_with_id("GitLabInstance", instance_id.to_string())
+            })?;
+
+    let started_at = now();
+    let start = Instant::now();
+    let mut steps = Vec::with_capacity(4);
+
+    let (step, client) = timed("validate_token", async {
+        let token = token
+            .ok_or_else(|| AppError::authentication("No token configured for GitLab instance"))?;
+        let client = GitLabClient::new(GitLabClientConfig {
+            base_url: url.clone(),
+            token,
+            timeout_secs: 30,
+        })?;
+        let user = client.validate_token().await?;
+        Ok((client, format!("Signed in as @{}", user.username)))
+    })
+    .await;
+    steps.push(step);
+
+    let mr: Option<GitLabMergeRequest> = match &client {
+        Some(client) => {
+            let (step, mr) = timed("list_mrs", async {
+                let query = MergeRequestsQuery {
+                    scope: Some("all".to_string()),
+                    per_page: Some(1),
+                    ..Default::default()
+                };
+                let mr = client
+                    .list_merge_requests(&query)
+                    .await?
+                    .data
+                    .into_iter()
+                    .next();
+                let detail = match &mr {
+                    Some(mr) => format!("Listed !{} in project {}", mr.iid, mr.project_id),
+                    None => "No merge requests visible to this token".to_string(),
+                };
+                Ok((mr, detail))
+            })
+            .await;
+            steps.push(step);
+            mr.flatten()
+        }
+        None => {
+            steps.push(SelfTestStep::skipped("list_mrs", "Token validation failed"));
+            None
+        }
+    };
+
+    match (&client, &mr) {
+        (Some(client), Some(mr)) => {
+            let (step, _) = timed("fetch_diff", async {
+                let version = client.get_merge_request_diff(mr.project_id, mr.iid).await?;
+                Ok((
+                    (),
+                    format!(
+                        "Fetched {} changed files of !{}",
+                        version.diffs.len(),
+                        mr.iid
+                    ),
+                ))
+            })
+            .await;
+            steps.push(step);
+        }
+        (Some(_), None) => steps.push(SelfTestStep::skipped(
+            "fetch_diff",
+            "No merge request to fetch",
+        )),
+        (None, _) => steps.push(SelfTestStep::skipped(
+            "fetch_diff",
+            "Token validation failed",
+        )),
+    }
+
+    let cached_mr: Option<i64> =
+        sqlx::query_scalar("SELECT id FROM merge_requests WHERE instance_id = ? LIMIT 1")
+            .bind(instance_id)
+            .fetch_optional(pool)
+            .await?;
+    match cached_mr {
+        Some(mr_id) => {
+            let (step, _) = timed("queue", async {
+                dry_run_queue(pool, mr_id).await?;
+                Ok((
+                    (),
+                    "Enqueued and flushed a test action (rolled back)".to_string(),
+                ))
+            })
+            .await;
+            steps.push(step);
+        }
+        None => steps.push(SelfTestStep::skipped(
+            "queue",
+            "No cached merge request to queue an action for; sync once first",
+        )),
+    }
+
+    Ok(SelfTestReport {
+        instance_id,
+        instance_url: url,
+        passed: steps.iter().all(|s| s.status != StepStatus::Failed),
+        steps,
+        started_at,
+        total_ms: start.elapsed().as_millis() as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db;
+
+    async fn setup() -> (tempfile::TempDir, DbPool) {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = db::initialize(&dir.path().join("test.db")).await.unwrap();
+        sqlx::query("INSERT INTO gitlab_instances (id, url, name) VALUES (1, 'https://gitlab.com', 'GitLab')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        (dir, pool)
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_queue_leaves_queue_untouched() {
+        let (_dir, pool) = setup().await;
+        sqlx::query(
+            "INSERT INTO merge_requests (id, instance_id, iid, project_id, title, author_username,
+             source_branch, target_branch, state, web_url, created_at, updated_at)
+             VALUES (10, 1, 5, 100, 'MR', 'alice', 'feature', 'main', 'opened', 'https://gitlab.com/g/p/-/merge_requests/5', 0, 0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        dry_run_queue(&pool, 10).await.unwrap();
+        let queued: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM sync_queue")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(queued, 0);
+    }
+
+    #[tokio::test]
+    async fn test_run_without_token_fails_first_step_and_skips_network() {
+        let (_dir, pool) = setup().await;
+        let report = run(&pool, 1).await.unwrap();
+
+        assert!(!report.passed);
+        let statuses: Vec<_> = report.steps.iter().map(|s| (s.name, s.status)).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("validate_token", StepStatus::Failed),
+                ("list_mrs", StepStatus::Skipped),
+                ("fetch_diff", StepStatus::Skipped),
+                ("queue", StepStatus::Skipped),
+            ]
+        );
+        assert_eq!(report.steps[0].error_kind, Some("authentication"));
+
+        assert!(run(&pool, 99).await.is_err());
+    }
+}
@@ -0,0 +1,141 @@
+//! Line diff of two file versions that ignores whitespace, like `git diff -w`.
+//!
+//! GitLab's diffs count a re-indented line as removed and added again, which
+//! buries the real changes of a re-indent MR. Here lines are compared with
+//! all whitespace stripped; lines that differ only in whitespace become
+//! context, shown with their new text. The result is unified diff text so it
+//! goes through the same hunk parser as GitLab's diffs.
+
+use super::myers;
+
+/// Unchanged lines shown around each change, as in GitLab's diffs.
+const CONTEXT_LINES: usize = 3;
+
+/// Edit scripts longer than this aren't computed; the caller keeps the
+/// regular diff.
+const MAX_EDITS: usize = 20_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    /// Old and new line indexes of lines equal apart from whitespace.
+    Equal(usize, usize),
+    Remove(usize),
+    Add(usize),
+}
+
+fn without_whitespace(line: &str) -> String {
+    line.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+/// Unified diff (hunks only, no file headers) from `old` to `new` that
+/// leaves out whitespace-only changes. Empty when nothing else changed;
+/// `None` when the files differ too much to diff here.
+pub fn unified_diff(old: &str, new: &str) -> Option<String> {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let old_keys: Vec<String> = old_lines.iter().map(|l| without_whitespace(l)).collect();
+    let new_keys: Vec<String> = new_lines.iter().map(|l| without_whitespace(l)).collect();
+    let (removed, added) = myers::diff(&old_keys, &new_keys, MAX_EDITS)?;
+
+    let mut ops = Vec::with_capacity(old_lines.len().max(new_lines.len()));
+    let (mut i, mut j) = (0, 0);
+    loop {
+        if i < old_lines.len() && removed[i] {
+            ops.push(Op::Remove(i));
+            i += 1;
+        } else if j < new_lines.len() && added[j] {
+            ops.push(Op::Add(j));
+            j += 1;
+        } else if i < old_lines.len() && j < new_lines.len() {
+            ops.push(Op::Equal(i, j));
+            i += 1;
+            j += 1;
+        } else {
+            break;
+        }
+    }
+
+    // Op ranges to show: every change plus its context, merged when they
+    // touch or overlap.
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for (index, op) in ops.iter().enumerate() {
+        if matches!(op, Op::Equal(..)) {
+            continue;
+        }
+        let start = index.saturating_sub(CONTEXT_LINES);
+        let end = (index + 1 + CONTEXT_LINES).min(ops.len());
+        match ranges.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => ranges.push((start, end)),
+        }
+    }
+
+    let mut out = String::new();
+    for (start, end) in ranges {
+        let hunk = &ops[start..end];
+        // Old and new line indexes at the start of the hunk.
+        let (old_at, new_at) = ops[..start].iter().fold((0, 0), |(o, n), op| match op {
+            Op::Equal(..) => (o + 1, n + 1),
+            Op::Remove(_) => (o + 1, n),
+            Op::Add(_) => (o, n + 1),
+        });
+        let old_count = hunk.iter().filter(|op| !matches!(op, Op::Add(_))).count();
+        let new_count = hunk
+            .iter()
+            .filter(|op| !matches!(op, Op::Remove(_)))
+            .count();
+        // An empty side is numbered by the line before it, as in `diff -u`.
+        let number = |at: usize, count: usize| if count == 0 { at } else { at + 1 };
+        out.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            number(old_at, old_count),
+            old_count,
+            number(new_at, new_count),
+            new_count
+        ));
+        for op in hunk {
+            let (prefix, text) = match *op {
+                Op::Equal(_, j) => (' ', new_lines[j]),
+                Op::Remove(i) => ('-', old_lines[i]),
+                Op::Add(j) => ('+', new_lines[j]),
+            };
+            out.push(prefix);
+            out.push_str(text);
+            out.push('\n');
+        }
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unified_diff_ignores_reindented_lines() {
+        let old = "fn main() {\nlet a = 1;\nlet b = 2;\n}\n";
+        let new = "fn main() {\n    let a = 1;\n    let b  = 2;\n}\n";
+        assert_eq!(unified_diff(old, new).unwrap(), "");
+    }
+
+    #[test]
+    fn test_unified_diff_keeps_real_changes_with_context() {
+        let old: String = (1..=10).map(|n| format!("line {}\n", n)).collect();
+        let new = old
+            .replace("line 2\n", "  line 2\n")
+            .replace("line 5\n", "line five\n");
+        let diff = unified_diff(&old, &new).unwrap();
+        assert_eq!(
+            diff,
+            "@@ -2,7 +2,7 @@\n   line 2\n line 3\n line 4\n-line 5\n+line five\n line 6\n line 7\n line 8\n"
+        );
+    }
+
+    #[test]
+    fn test_unified_diff_numbers_pure_additions_and_deletions() {
+        let diff = unified_diff("", "a\nb\n").unwrap();
+        assert_eq!(diff, "@@ -0,0 +1,2 @@\n+a\n+b\n");
+        let diff = unified_diff("a\nb\nc\nd\ne\nf\ng\nh\ni\n", "a\nb\nc\nd\nf\ng\nh\ni\n").unwrap();
+        assert_eq!(diff, "@@ -2,7 +2,6 @@\n b\n c\n d\n-e\n f\n g\n h\n");
+    }
+}
@@ -0,0 +1,178 @@
+//! Word-level diff between a removed line and the line that replaced it.
+//!
+//! Both lines are split into words (runs of letters, digits and `_`), runs
+//! of whitespace and single punctuation characters, and the token sequences
+//! are diffed with Myers' algorithm. The tokens that don't survive become
+//! byte ranges the diff view can highlight on top of the whole-line colour.
+//! Lines that have too little in common get no ranges: marking nearly every
+//! token is noisier than marking none.
+
+use super::myers;
+use serde::Serialize;
+
+/// Lines with more tokens than this aren't diffed (minified code, data).
+const MAX_TOKENS: usize = 500;
+
+/// Share of the longer line's non-whitespace bytes that must be unchanged
+/// for the ranges to be worth showing.
+const MIN_SHARED_RATIO: f64 = 0.4;
+
+/// A changed byte range within a line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeRange {
+    /// Byte offset of the first changed byte.
+    pub start: usize,
+    /// Byte offset just past the last changed byte.
+    pub end: usize,
+}
+
+/// Changed ranges on each side of a line pair.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LineChanges {
+    pub old: Vec<ChangeRange>,
+    pub new: Vec<ChangeRange>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenKind {
+    Word,
+    Space,
+    Other,
+}
+
+fn kind_of(c: char) -> TokenKind {
+    if c.is_alphanumeric() || c == '_' {
+        TokenKind::Word
+    } else if c.is_whitespace() {
+        TokenKind::Space
+    } else {
+        TokenKind::Other
+    }
+}
+
+/// Split `line` into `(start, end)` byte ranges of words, whitespace runs
+/// and single other characters.
+fn tokenize(line: &str) -> Vec<(usize, usize)> {
+    let mut tokens: Vec<(usize, usize)> = Vec::new();
+    let mut current: Option<(usize, TokenKind)> = None;
+    for (i, c) in line.char_indices() {
+        let kind = kind_of(c);
+        match current {
+            Some((_, k)) if k == kind && kind != TokenKind::Other => {}
+            Some((start, _)) => {
+                tokens.push((start, i));
+                current = Some((i, kind));
+            }
+            None => current = Some((i, kind)),
+        }
+    }
+    if let Some((start, _)) = current {
+        tokens.push((start, line.len()));
+    }
+    tokens
+}
+
+/// Turn per-token change flags into merged byte ranges. Whitespace between
+/// two changed tokens is included so "a b" → "c d" is one range, not two.
+fn ranges(line: &str, tokens: &[(usize, usize)], changed: &[bool]) -> Vec<ChangeRange> {
+    let mut out: Vec<ChangeRange> = Vec::new();
+    for (i, &(start, end)) in tokens.iter().enumerate() {
+        let bridged = !changed[i]
+            && i > 0
+            && i + 1 < tokens.len()
+            && changed[i - 1]
+            && changed[i + 1]
+            && line[start..end].trim().is_empty();
+        if !changed[i] && !bridged {
+            continue;
+        }
+        match out.last_mut() {
+            Some(last) if last.end == start => last.end = end,
+            _ => out.push(ChangeRange { start, end }),
+        }
+    }
+    out
+}
+
+fn non_space_len(line: &str, tokens: &[(usize, usize)], keep: impl Fn(usize) -> bool) -> usize {
+    tokens
+        .iter()
+        .enumerate()
+        .filter(|&(i, &(start, end))| keep(i) && !line[start..end].trim().is_empty())
+        .map(|(_, &(start, end))| end - start)
+        .sum()
+}
+
+/// Word-level changes between `old` and `new`, or `None` when the lines are
+/// too long or too different for the ranges to help.
+pub fn diff_words(old: &str, new: &str) -> Option<LineChanges> {
+    let old_tokens = tokenize(old);
+    let new_tokens = tokenize(new);
+    if old_tokens.len() > MAX_TOKENS || new_tokens.len() > MAX_TOKENS {
+        return None;
+    }
+
+    let old_words: Vec<&str> = old_tokens.iter().map(|&(s, e)| &old[s..e]).collect();
+    let new_words: Vec<&str> = new_tokens.iter().map(|&(s, e)| &new[s..e]).collect();
+    let (removed, added) = myers::diff(&old_words, &new_words, usize::MAX)?;
+
+    let longest =
+        non_space_len(old, &old_tokens, |_| true).max(non_space_len(new, &new_tokens, |_| true));
+    let shared = non_space_len(new, &new_tokens, |i| !added[i]);
+    if longest > 0 && (shared as f64) < longest as f64 * MIN_SHARED_RATIO {
+        return None;
+    }
+
+    Some(LineChanges {
+        old: ranges(old, &old_tokens, &removed),
+        new: ranges(new, &new_tokens, &added),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changed<'a>(line: &'a str, ranges: &[ChangeRange]) -> Vec<&'a str> {
+        ranges.iter().map(|r| &line[r.start..r.end]).collect()
+    }
+
+    #[test]
+    fn test_tokenize_splits_words_spaces_and_punctuation() {
+        let line = "let x_1 = foo(a, b);";
+        let tokens: Vec<&str> = tokenize(line).iter().map(|&(s, e)| &line[s..e]).collect();
+        assert_eq!(
+            tokens,
+            vec!["let", " ", "x_1", " ", "=", " ", "foo", "(", "a", ",", " ", "b", ")", ";"]
+        );
+        assert!(tokenize("").is_empty());
+    }
+
+    #[test]
+    fn test_diff_words_marks_only_changed_tokens() {
+        let old = "    let total = compute(items, 10);";
+        let new = "    let total = compute_all(items, 20, true);";
+        let changes = diff_words(old, new).unwrap();
+        assert_eq!(changed(old, &changes.old), vec!["compute", "10"]);
+        assert_eq!(changed(new, &changes.new), vec!["compute_all", "20, true"]);
+    }
+
+    #[test]
+    fn test_diff_words_handles_multibyte_text() {
+        let old = "msg = \"héllo wörld\"";
+        let new = "msg = \"héllo wereld\"";
+        let changes = diff_words(old, new).unwrap();
+        assert_eq!(changed(old, &changes.old), vec!["wörld"]);
+        assert_eq!(changed(new, &changes.new), vec!["wereld"]);
+    }
+
+    #[test]
+    fn test_diff_words_skips_unrelated_lines() {
+        assert_eq!(diff_words("return a + b;", "fn main() {"), None);
+        // Identical apart from indentation: only the whitespace differs.
+        let changes = diff_words("  x();", "    x();").unwrap();
+        assert_eq!(changed("    x();", &changes.new), vec!["    "]);
+        assert_eq!(diff_words("", ""), Some(LineChanges::default()));
+    }
+}
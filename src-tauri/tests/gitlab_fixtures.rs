@@ -0,0 +1,188 @@
+//! GitLab client record/replay.
+//!
+//! Runs with `cargo test --features gitlab-fixtures`. Recording goes through
+//! a one-shot local HTTP server standing in for GitLab; replay must then
+//! answer the same request with the server gone. A full sync is replayed
+//! from the committed fixtures into a fresh database.
+
+#![cfg(feature = "gitlab-fixtures")]
+
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tempfile::tempdir;
+use ultra_gitlab_lib::db;
+use ultra_gitlab_lib::services::gitlab_client::{GitLabClient, GitLabClientConfig};
+use ultra_gitlab_lib::services::gitlab_fixtures::{self, FixtureMode};
+use ultra_gitlab_lib::services::sync_events::NoopEmitter;
+use ultra_gitlab_lib::services::SyncEngine;
+
+fn client(base_url: &str, mode: FixtureMode) -> GitLabClient {
+    GitLabClient::new(GitLabClientConfig {
+        base_url: base_url.to_string(),
+        token: "glpat-test".to_string(),
+        timeout_secs: 5,
+    })
+    .unwrap()
+    .with_fixtures(Some(mode))
+}
+
+/// Serve one HTTP response with `body` on a local port; returns the base URL.
+fn serve_once(body: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0u8; 1024];
+        while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+            let n = stream.read(&mut buf).unwrap();
+            if n == 0 {
+                break;
+            }
+            request.extend_from_slice(&buf[..n]);
+        }
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\nx-total-pages: 1\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        )
+        .unwrap();
+    });
+    format!("http://{}", addr)
+}
+
+#[tokio::test]
+async fn replays_committed_fixtures() {
+    let client = client(
+        "https://gitlab.example.com",
+        FixtureMode::Replay(gitlab_fixtures::default_dir()),
+    );
+
+    let user = client.validate_token().await.unwrap();
+    assert_eq!(user.id, 42);
+    assert_eq!(user.username, "fixture-user");
+
+    // No fixture: answered like GitLab would, without a request.
+    assert!(client.get_project(1).await.is_err());
+}
+
+#[tokio::test]
+async fn records_then_replays_without_the_server() {
+    let dir = tempdir().unwrap();
+    let fixtures: PathBuf = dir.path().join("fixtures");
+    let base_url = serve_once(r#"{"id":7,"username":"rec","name":"Recorded","avatar_url":null}"#);
+
+    let recorder = client(&base_url, FixtureMode::Record(fixtures.clone()));
+    let recorded = recorder.validate_token().await.unwrap();
+    assert_eq!(recorded.username, "rec");
+
+    let files: Vec<_> = std::fs::read_dir(&fixtures).unwrap().collect();
+    assert_eq!(files.len(), 1);
+    let contents = std::fs::read_to_string(files[0].as_ref().unwrap().path()).unwrap();
+    assert!(contents.contains("\"path\": \"/api/v4/user\""));
+    assert!(
+        !contents.contains("glpat-test"),
+        "token must not be recorded"
+    );
+
+    // The server only answered once; this must come from the fixture.
+    let replayer = client(&base_url, FixtureMode::Replay(fixtures));
+    let replayed = replayer.validate_token().await.unwrap();
+    assert_eq!(replayed.id, 7);
+}
+
+#[tokio::test]
+async fn sync_replays_into_the_cache() {
+    let dir = tempdir().unwrap();
+    let pool = db::initialize(&dir.path().join("sync.db")).await.unwrap();
+    sqlx::query(
+        "INSERT INTO gitlab_instances (url, name, token, created_at)
+         VALUES ('https://gitlab.example.com', 'Fixtures', 'glpat-test', 0)",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    // The engine builds its own clients, so replay is switched on globally.
+    gitlab_fixtures::set_mode(Some(FixtureMode::Replay(gitlab_fixtures::default_dir())));
+    let result = SyncEngine::new(pool.clone(), Arc::new(NoopEmitter))
+        .run_sync()
+        .await
+        .unwrap();
+    assert_eq!(result.mr_count, 1);
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+
+    let mr: (i64, i64, String, String, i64, i64, Option<String>, String) = sqlx::query_as(
+        "SELECT id, iid, title, author_username, approvals_required, approvals_count,
+                head_pipeline_status, reviewers
+         FROM merge_requests",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        mr,
+        (
+            5001,
+            7,
+            "Add response cache".into(),
+            "alice".into(),
+            2,
+            1,
+            Some("success".into()),
+            r#"["fixture-user"]"#.into()
+        )
+    );
+
+    let project: String =
+        sqlx::query_scalar("SELECT name_with_namespace FROM projects WHERE id = 100")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(project, "Acme / api");
+
+    let head_sha: String = sqlx::query_scalar("SELECT head_sha FROM diffs WHERE mr_id = 5001")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(head_sha, "c".repeat(40));
+    let files: Vec<(String, String, i64)> = sqlx::query_as(
+        "SELECT new_path, change_type, additions FROM diff_files
+         WHERE mr_id = 5001 ORDER BY new_path",
+    )
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        files,
+        vec![
+            ("src/cache.rs".into(), "added".into(), 2),
+            ("src/http.rs".into(), "modified".into(), 1),
+        ]
+    );
+
+    let comments: Vec<(i64, String, Option<String>, Option<i64>)> = sqlx::query_as(
+        "SELECT id, discussion_id, file_path, new_line FROM comments
+         WHERE mr_id = 5001 ORDER BY id",
+    )
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        comments,
+        vec![
+            (901, "d1".into(), None, None),
+            (902, "d2".into(), Some("src/http.rs".into()), Some(2)),
+            (903, "d2".into(), None, None),
+        ]
+    );
+
+    let issue: (i64, String) = sqlx::query_as("SELECT iid, title FROM issues")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(issue, (12, "Cache invalidation on deploy".into()));
+}
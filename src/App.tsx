@@ -4,9 +4,9 @@
 
 import { useState, useEffect, useCallback, useMemo } from 'react';
 import { MotionConfig } from 'motion/react';
-import { useQueries } from '@tanstack/react-query';
+import { useQueries, useQuery } from '@tanstack/react-query';
 import { BrowserRouter, Routes, Route, Navigate, useNavigate, useLocation } from 'react-router-dom';
-import { isTauri, tauriListen } from './services/transport';
+import { companionBasePath, isTauri, tauriListen } from './services/transport';
 import { trackEvent, trackShortcut } from './services/analytics';
 import Settings from './pages/Settings';
 import MRListPage from './pages/MRListPage';
@@ -24,17 +24,19 @@ import { AppSidebar } from './components/AppSidebar';
 import { CommandPalette, type Command } from './components/CommandPalette';
 import { KeyboardHelp } from './components/KeyboardHelp';
 import { ReAuthPrompt } from './components/ReAuthPrompt';
+import { CompanionPairingDialog } from './components/CompanionPairingDialog';
 import useUpdateChecker from './hooks/useUpdateChecker';
 import { useHasApprovedMRsQuery } from './hooks/queries/useHasApprovedMRsQuery';
 import useNotifications from './hooks/useNotifications';
 import { useCompanionStatusQuery } from './hooks/queries/useCompanionStatusQuery';
 import useCompanionAuth from './hooks/useCompanionAuth';
 import useDeepLink from './hooks/useDeepLink';
+import useUserActivityReporter from './hooks/useUserActivityReporter';
 import { CommandId, CommandCategory, commandDefinitions } from './commands/registry';
 import { manualSync } from './services/storage';
 import { useInstancesQuery } from './hooks/queries/useInstancesQuery';
 import { queryKeys } from './lib/queryKeys';
-import { listPipelineProjects, visitPipelineProject } from './services/tauri';
+import { getInstanceMode, listPipelineProjects, visitPipelineProject } from './services/tauri';
 import { WorkerPoolContextProvider } from '@pierre/diffs/react';
 import WorkerUrl from '@pierre/diffs/worker/worker.js?worker&url';
 import { ThemeProvider } from './components/ThemeProvider';
@@ -72,8 +74,15 @@ function AppContent() {
   const { getKey } = useShortcuts();
   useNotifications();
   useDeepLink();
+  useUserActivityReporter();
   const companionStatusQuery = useCompanionStatusQuery();
   const instancesQuery = useInstancesQuery();
+  const instanceModeQuery = useQuery({
+    queryKey: queryKeys.instanceMode(),
+    queryFn: getInstanceMode,
+    enabled: isTauri,
+    staleTime: Infinity,
+  });
 
   // Track screen views for main overview screens
   useEffect(() => {
@@ -292,6 +301,14 @@ function AppContent() {
     <div className="app">
       {isTauri && <div className="titlebar-drag-region" data-tauri-drag-region />}
       <AppSidebar updateAvailable={updateChecker.available} hasApprovedMRs={hasApprovedMRs} companionEnabled={companionStatusQuery.data?.enabled ?? false} companionDeviceCount={companionStatusQuery.data?.connectedDevices ?? 0} />
+      {instanceModeQuery.data === 'secondary' && (
+        <div
+          className="read-only-indicator"
+          title="Another Ultra Gitlab process owns the database. This window can browse the cache but doesn't sync or save changes."
+        >
+          Read-only viewer
+        </div>
+      )}
       <div className="app-content">
         <Routes>
           {/* Redirect root to MR list */}
@@ -352,6 +369,8 @@ function AppContent() {
         />
       )}
 
+      {isTauri && <CompanionPairingDialog />}
+
       <ToastContainer />
     </div>
   );
@@ -381,7 +400,7 @@ function App() {
         }}
       >
         <ToastProvider>
-          <BrowserRouter>
+          <BrowserRouter basename={companionBasePath || undefined}>
             <HotkeysProvider>
               <ShortcutsProvider>
                 <AppContent />
@@ -0,0 +1,37 @@
+/**
+ * Screen-reader-friendly diff view.
+ *
+ * Renders the backend's linearized diff as a heading per change followed by
+ * a plain list, where each line announces its role and number before its
+ * content. No color or column position is needed to follow the change.
+ */
+
+import type { AccessibleDiff } from "../../types";
+import "./AccessibleDiffView.css";
+
+interface AccessibleDiffViewProps {
+  diff: AccessibleDiff;
+}
+
+export function AccessibleDiffView({ diff }: AccessibleDiffViewProps) {
+  return (
+    <article className="accessible-diff" aria-label={`Changes in ${diff.filePath}`}>
+      <p className="accessible-diff-summary">{diff.summary}</p>
+      {diff.hunks.map((hunk, i) => (
+        <section key={i} className="accessible-diff-hunk" aria-labelledby={`accessible-hunk-${i}`}>
+          <h3 id={`accessible-hunk-${i}`} className="accessible-diff-hunk-summary" tabIndex={-1}>
+            {hunk.summary}
+          </h3>
+          <ol className="accessible-diff-lines">
+            {hunk.lines.map((line, j) => (
+              <li key={j} className={`accessible-diff-line ${line.kind}`}>
+                <span className="accessible-diff-label">{line.label}:</span>{" "}
+                <code>{line.content || "(blank)"}</code>
+              </li>
+            ))}
+          </ol>
+        </section>
+      ))}
+    </article>
+  );
+}
@@ -0,0 +1 @@
+export { AccessibleDiffView } from './AccessibleDiffView';
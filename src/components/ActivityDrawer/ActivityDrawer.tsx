@@ -16,6 +16,8 @@ interface ActivityDrawerProps {
   onToggleSystemEvents: () => void;
   heightVh: number;
   onHeightChange: (vh: number) => void;
+  /** Extra controls shown before the "Show activity" toggle. */
+  headerActions?: React.ReactNode;
   children?: React.ReactNode;
   footer?: React.ReactNode;
 }
@@ -23,7 +25,7 @@ interface ActivityDrawerProps {
 const MIN_HEIGHT_VH = 20;
 const MAX_HEIGHT_VH = 80;
 
-export default function ActivityDrawer({ isOpen, onToggle, showSystemEvents, onToggleSystemEvents, heightVh, onHeightChange, children, footer }: ActivityDrawerProps) {
+export default function ActivityDrawer({ isOpen, onToggle, showSystemEvents, onToggleSystemEvents, heightVh, onHeightChange, headerActions, children, footer }: ActivityDrawerProps) {
   const drawerRef = useRef<HTMLDivElement>(null);
   const isDraggingRef = useRef(false);
 
@@ -82,6 +84,7 @@ export default function ActivityDrawer({ isOpen, onToggle, showSystemEvents, onT
       <div className="activity-drawer__header">
         <span className="activity-drawer__title">Activity</span>
         <div className="activity-drawer__header-actions">
+          {headerActions}
           <label className="activity-drawer__toggle-label" data-testid="activity-show-events-toggle">
             <input
               type="checkbox"
@@ -6,22 +6,29 @@
  */
 
 import { useState, useRef, useEffect, useMemo, useCallback } from 'react';
-import type { Comment, SyncStatus } from '../../types';
+import type { Comment, CommentSnippet, CommentSort, CommentSuggestion, SyncStatus } from '../../types';
 import Markdown from '../Markdown';
 import { TrashIcon, PendingIcon, WarningIcon } from '../icons';
 import { formatRelativeTime } from '../../utils/formatRelativeTime';
+import { useSettingsQuery } from '../../hooks/queries/useSettingsQuery';
+import { translateText } from '../../services/tauri';
 import './ActivityFeed.css';
 
 interface ActivityFeedProps {
   threads: Comment[][];
   systemEvents: Comment[];
   showSystemEvents: boolean;
+  /** Order the threads arrive in; decides where system events go. */
+  sort: CommentSort;
+  /** Marked with a "New" divider; the jump-to-unread target. */
+  firstUnreadId?: number | null;
   loading: boolean;
   error?: string | null;
   currentUser?: string | null;
   onReply?: (discussionId: string, parentId: number, body: string) => Promise<void>;
   onResolve?: (discussionId: string, resolved: boolean) => Promise<void>;
   onDelete?: (commentId: number) => Promise<void>;
+  onApplySuggestion?: (suggestionId: number) => Promise<void>;
 }
 
 function SyncBadge({ status }: { status: SyncStatus | null }) {
@@ -40,15 +47,100 @@ function SyncBadge({ status }: { status: SyncStatus | null }) {
 
 interface CommentEntryProps {
   comment: Comment;
+  isFirstUnread?: boolean;
   currentUser?: string | null;
   onDelete?: (commentId: number) => Promise<void>;
+  onApplySuggestion?: (suggestionId: number) => Promise<void>;
 }
 
-function CommentEntry({ comment, currentUser, onDelete }: CommentEntryProps) {
+function SuggestionActions({
+  suggestions,
+  onApply,
+}: {
+  suggestions: CommentSuggestion[];
+  onApply?: (suggestionId: number) => Promise<void>;
+}) {
+  const [applying, setApplying] = useState<number | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const handleApply = async (suggestionId: number) => {
+    if (!onApply) return;
+    setApplying(suggestionId);
+    setError(null);
+    try {
+      await onApply(suggestionId);
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setApplying(null);
+    }
+  };
+
+  return (
+    <div className="activity-comment__suggestions">
+      {suggestions.map((s) => {
+        const lines = s.fromLine === s.toLine ? `line ${s.fromLine}` : `lines ${s.fromLine}-${s.toLine}`;
+        if (s.applied) {
+          return (
+            <span key={s.id} className="activity-comment__suggestion-applied">
+              Suggestion for {lines} applied
+            </span>
+          );
+        }
+        if (!s.appliable || !onApply) return null;
+        return (
+          <button
+            key={s.id}
+            className="activity-thread__resolve-btn"
+            onClick={() => handleApply(s.id)}
+            disabled={applying !== null}
+            data-testid="activity-apply-suggestion-btn"
+          >
+            {applying === s.id ? 'Applying…' : `Apply suggestion (${lines})`}
+          </button>
+        );
+      })}
+      {error && <span className="activity-comment__translate-error">{error}</span>}
+    </div>
+  );
+}
+
+function CommentEntry({ comment, isFirstUnread, currentUser, onDelete, onApplySuggestion }: CommentEntryProps) {
   const isOwn = currentUser && comment.authorUsername === currentUser;
 
   const [confirmingDelete, setConfirmingDelete] = useState(false);
 
+  const { data: settings } = useSettingsQuery();
+  const translation = settings?.translation;
+  const [translated, setTranslated] = useState<{ text: string; from: string | null } | null>(null);
+  const [showTranslated, setShowTranslated] = useState(false);
+  const [translating, setTranslating] = useState(false);
+  const [translateError, setTranslateError] = useState<string | null>(null);
+
+  const handleTranslate = useCallback(async () => {
+    if (!translation?.enabled) return;
+    if (translated) {
+      setShowTranslated((v) => !v);
+      return;
+    }
+    setTranslating(true);
+    setTranslateError(null);
+    try {
+      // Unsynced comments get a new ID once posted, so don't cache them.
+      const result = await translateText(
+        comment.body,
+        translation.targetLang,
+        comment.isLocal ? undefined : comment.id,
+      );
+      setTranslated({ text: result.text, from: result.detectedLang });
+      setShowTranslated(true);
+    } catch (err) {
+      setTranslateError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setTranslating(false);
+    }
+  }, [translation, translated, comment.body, comment.id, comment.isLocal]);
+
   const handleDelete = useCallback(() => {
     if (!onDelete) return;
     if (confirmingDelete) {
@@ -60,7 +152,8 @@ function CommentEntry({ comment, currentUser, onDelete }: CommentEntryProps) {
   }, [onDelete, comment.id, confirmingDelete]);
 
   return (
-    <div className="activity-comment" data-testid="activity-comment">
+    <div className="activity-comment" data-testid="activity-comment" data-comment-id={comment.id}>
+      {isFirstUnread && <div className="activity-unread-divider">New</div>}
       <div className="activity-comment__meta">
         <span className="activity-comment__author">{comment.authorUsername}</span>
         <span className="activity-comment__time">{formatRelativeTime(comment.createdAt)}</span>
@@ -76,8 +169,31 @@ function CommentEntry({ comment, currentUser, onDelete }: CommentEntryProps) {
             {confirmingDelete ? 'Delete?' : <TrashIcon />}
           </button>
         )}
+        {translation?.enabled && (
+          <button
+            className="activity-comment__translate"
+            onClick={handleTranslate}
+            disabled={translating}
+            aria-pressed={showTranslated}
+            data-testid="activity-translate-btn"
+          >
+            {translating ? 'Translating…' : showTranslated ? 'Show original' : 'Translate'}
+          </button>
+        )}
       </div>
-      <Markdown className="activity-comment__body" content={comment.body} />
+      <Markdown
+        className="activity-comment__body"
+        content={showTranslated && translated ? translated.text : comment.body}
+      />
+      {showTranslated && translated && (
+        <span className="activity-comment__translated-note">
+          Translated{translated.from ? ` from ${translated.from}` : ''}
+        </span>
+      )}
+      {translateError && <span className="activity-comment__translate-error">{translateError}</span>}
+      {comment.suggestions && comment.suggestions.length > 0 && (
+        <SuggestionActions suggestions={comment.suggestions} onApply={onApplySuggestion} />
+      )}
     </div>
   );
 }
@@ -152,18 +268,40 @@ function ReplyInput({ onSubmit, onCancel }: { onSubmit: (body: string) => Promis
   );
 }
 
+/** Cached code around an inline thread's line, the commented line marked. */
+function ThreadSnippet({ snippet }: { snippet: CommentSnippet }) {
+  return (
+    <pre className="activity-thread__snippet" data-testid="activity-thread-snippet">
+      {snippet.lines.map((text, i) => {
+        const line = snippet.startLine + i;
+        return (
+          <div
+            key={line}
+            className={`activity-thread__snippet-line ${line === snippet.line ? 'activity-thread__snippet-line--target' : ''}`}
+          >
+            <span className="activity-thread__snippet-number">{line}</span>
+            {text}
+          </div>
+        );
+      })}
+    </pre>
+  );
+}
+
 interface ThreadCardProps {
   thread: Comment[];
   isReplying: boolean;
+  firstUnreadId?: number | null;
   currentUser?: string | null;
   onStartReply: () => void;
   onCancelReply: () => void;
   onSubmitReply?: (discussionId: string, parentId: number, body: string) => Promise<void>;
   onResolve?: (discussionId: string, resolved: boolean) => Promise<void>;
   onDelete?: (commentId: number) => Promise<void>;
+  onApplySuggestion?: (suggestionId: number) => Promise<void>;
 }
 
-function ThreadCard({ thread, isReplying, currentUser, onStartReply, onCancelReply, onSubmitReply, onResolve, onDelete }: ThreadCardProps) {
+function ThreadCard({ thread, isReplying, firstUnreadId, currentUser, onStartReply, onCancelReply, onSubmitReply, onResolve, onDelete, onApplySuggestion }: ThreadCardProps) {
   const root = thread[0];
   const replies = thread.slice(1);
   const isResolved = root.resolved;
@@ -200,11 +338,12 @@ function ThreadCard({ thread, isReplying, currentUser, onStartReply, onCancelRep
           )}
         </div>
       )}
-      <CommentEntry comment={root} currentUser={currentUser} onDelete={onDelete} />
+      {isInline && root.snippet && <ThreadSnippet snippet={root.snippet} />}
+      <CommentEntry comment={root} isFirstUnread={root.id === firstUnreadId} currentUser={currentUser} onDelete={onDelete} onApplySuggestion={onApplySuggestion} />
       {replies.length > 0 && (
         <div className="activity-thread__replies" data-testid="activity-thread-replies">
           {replies.map((reply) => (
-            <CommentEntry key={reply.id} comment={reply} currentUser={currentUser} onDelete={onDelete} />
+            <CommentEntry key={reply.id} comment={reply} isFirstUnread={reply.id === firstUnreadId} currentUser={currentUser} onDelete={onDelete} onApplySuggestion={onApplySuggestion} />
           ))}
         </div>
       )}
@@ -259,31 +398,33 @@ function SystemEventEntry({ event }: { event: Comment }) {
   );
 }
 
-export default function ActivityFeed({ threads, systemEvents, showSystemEvents, loading, error, currentUser, onReply, onResolve, onDelete }: ActivityFeedProps) {
+export default function ActivityFeed({ threads, systemEvents, showSystemEvents, sort, firstUnreadId, loading, error, currentUser, onReply, onResolve, onDelete, onApplySuggestion }: ActivityFeedProps) {
   const [replyingToThreadRootId, setReplyingToThreadRootId] = useState<number | null>(null);
 
   const feedItems = useMemo((): FeedItem[] => {
     const items: FeedItem[] = threads.map((thread) => ({
       kind: 'thread' as const,
       thread,
-      timestamp: thread[0]?.createdAt ?? 0,
+      timestamp: sort === 'newest'
+        ? Math.max(...thread.map((c) => c.createdAt))
+        : thread[0]?.createdAt ?? 0,
     }));
+    if (!showSystemEvents) return items;
 
-    if (showSystemEvents) {
-      for (const event of systemEvents) {
-        items.push({ kind: 'event' as const, event, timestamp: event.createdAt });
-      }
+    const events: FeedItem[] = systemEvents.map((event) => ({
+      kind: 'event' as const,
+      event,
+      timestamp: event.createdAt,
+    }));
+    // Threads arrive sorted by the backend. Chronological sorts interleave
+    // the events (a stable sort keeps the thread order); the others list
+    // events after the threads.
+    if (sort === 'oldest' || sort === 'newest') {
+      const direction = sort === 'newest' ? -1 : 1;
+      return [...items, ...events].sort((a, b) => direction * (a.timestamp - b.timestamp));
     }
-
-    // Sort: unresolved threads first, then chronologically
-    return items.sort((a, b) => {
-      const aIsUnresolvedThread = a.kind === 'thread' && !a.thread.some(c => c.resolved);
-      const bIsUnresolvedThread = b.kind === 'thread' && !b.thread.some(c => c.resolved);
-      if (aIsUnresolvedThread && !bIsUnresolvedThread) return -1;
-      if (!aIsUnresolvedThread && bIsUnresolvedThread) return 1;
-      return a.timestamp - b.timestamp;
-    });
-  }, [threads, systemEvents, showSystemEvents]);
+    return [...items, ...events];
+  }, [threads, systemEvents, showSystemEvents, sort]);
 
   if (loading) {
     return (
@@ -318,12 +459,14 @@ export default function ActivityFeed({ threads, systemEvents, showSystemEvents,
             key={`thread-${item.thread[0].id}`}
             thread={item.thread}
             isReplying={replyingToThreadRootId === item.thread[0].id}
+            firstUnreadId={firstUnreadId}
             currentUser={currentUser}
             onStartReply={() => setReplyingToThreadRootId(item.thread[0].id)}
             onCancelReply={() => setReplyingToThreadRootId(null)}
             onSubmitReply={onReply}
             onResolve={onResolve}
             onDelete={onDelete}
+            onApplySuggestion={onApplySuggestion}
           />
         ) : (
           <SystemEventEntry key={`event-${item.event.id}`} event={item.event} />
@@ -0,0 +1,130 @@
+/**
+ * Activity timeline rendered inside the ActivityDrawer.
+ *
+ * Lists what happened to an MR apart from comments — pushes, label,
+ * milestone, reviewer and state changes — oldest first, as parsed by the
+ * backend from system notes and GitLab's resource events.
+ */
+
+import type { MrEvent } from '../../types';
+import { useMrActivityQuery } from '../../hooks/queries/useMrActivityQuery';
+import { formatRelativeTime } from '../../utils/formatRelativeTime';
+import './ActivityTimeline.css';
+
+function list(items: string[], prefix = ''): string {
+  const names = items.map((item) => `${prefix}${item}`);
+  if (names.length <= 1) return names.join('');
+  return `${names.slice(0, -1).join(', ')} and ${names[names.length - 1]}`;
+}
+
+function plural(count: number, word: string): string {
+  return `${count} ${word}${count === 1 ? '' : 's'}`;
+}
+
+function stripHtml(text: string): string {
+  return text.replace(/<[^>]*>/g, '').trim();
+}
+
+/** The sentence following the actor's name. */
+function describe(event: MrEvent): string {
+  const { subjects } = event;
+  switch (event.kind) {
+    case 'labels_added':
+      return `added ${subjects.length === 1 ? 'label' : 'labels'} ${list(subjects)}`;
+    case 'labels_removed':
+      return `removed ${subjects.length === 1 ? 'label' : 'labels'} ${list(subjects)}`;
+    case 'milestone_set':
+      return `set milestone ${list(subjects)}`;
+    case 'milestone_removed':
+      return subjects.length > 0 ? `removed milestone ${list(subjects)}` : 'removed the milestone';
+    case 'closed':
+      return 'closed the merge request';
+    case 'reopened':
+      return 'reopened the merge request';
+    case 'merged':
+      return 'merged the merge request';
+    case 'pushed': {
+      const count = Number(event.detail);
+      return Number.isFinite(count) && count > 0 ? `pushed ${plural(count, 'commit')}` : 'pushed commits';
+    }
+    case 'force_pushed':
+      return 'force-pushed the source branch';
+    case 'review_requested':
+      return `requested review from ${list(subjects, '@')}`;
+    case 'review_request_removed':
+      return `removed review request for ${list(subjects, '@')}`;
+    case 'assigned':
+      return `assigned ${list(subjects, '@')}`;
+    case 'unassigned':
+      return `unassigned ${list(subjects, '@')}`;
+    case 'approved':
+      return 'approved';
+    case 'unapproved':
+      return 'revoked approval';
+    case 'marked_draft':
+      return 'marked as draft';
+    case 'marked_ready':
+      return 'marked as ready';
+    case 'title_changed':
+      return event.detail ? `changed the title to “${event.detail}”` : 'changed the title';
+    case 'description_changed':
+      return 'changed the description';
+    case 'other':
+      return stripHtml(event.detail ?? '');
+  }
+}
+
+export default function ActivityTimeline({ mrId }: { mrId: number }) {
+  const { data: events, isLoading, error } = useMrActivityQuery(mrId);
+
+  if (isLoading) {
+    return (
+      <div className="activity-feed__loading" data-testid="activity-timeline-loading">
+        <div className="activity-feed__spinner" />
+        Loading activity...
+      </div>
+    );
+  }
+
+  if (error) {
+    return (
+      <div className="activity-feed__error" data-testid="activity-timeline-error">
+        {error instanceof Error ? error.message : String(error)}
+      </div>
+    );
+  }
+
+  if (!events || events.length === 0) {
+    return (
+      <div className="activity-feed__empty" data-testid="activity-timeline-empty">
+        No activity yet
+      </div>
+    );
+  }
+
+  return (
+    <ol className="activity-timeline" data-testid="activity-timeline">
+      {events.map((event) => (
+        <li
+          key={`${event.source}-${event.sourceId}`}
+          className={`activity-timeline__entry activity-timeline__entry--${event.kind}`}
+        >
+          <span className="activity-timeline__dot" />
+          <span className="activity-timeline__text">
+            {event.actorUsername && (
+              <span className="activity-timeline__actor">{event.actorUsername} </span>
+            )}
+            {describe(event)}
+          </span>
+          <time
+            className="activity-timeline__time"
+            dateTime={new Date(event.createdAt * 1000).toISOString()}
+            title={new Date(event.createdAt * 1000).toLocaleString()}
+          >
+            {formatRelativeTime(event.createdAt)}
+          </time>
+        </li>
+      ))}
+    </ol>
+  );
+}
@@ -4,30 +4,51 @@
  * Fixed at the bottom of the drawer. Supports Cmd+Enter to submit.
  */
 
-import { useState, useRef, useCallback } from 'react';
+import { useState, useRef, useCallback, useEffect } from 'react';
 import './CommentInput.css';
 
 interface CommentInputProps {
   onSubmit: (body: string) => Promise<void>;
+  /** When set, a second button posts the comment and then approves the MR. */
+  onSubmitAndApprove?: (body: string) => Promise<void>;
+  /**
+   * Start a reply to this user: prefixes `@username ` and focuses the input.
+   * `at` distinguishes repeated requests for the same user.
+   */
+  mention?: { username: string; at: number } | null;
 }
 
-export default function CommentInput({ onSubmit }: CommentInputProps) {
+export default function CommentInput({ onSubmit, onSubmitAndApprove, mention }: CommentInputProps) {
   const [value, setValue] = useState('');
   const [submitting, setSubmitting] = useState(false);
   const textareaRef = useRef<HTMLTextAreaElement>(null);
 
-  const handleSubmit = useCallback(async () => {
-    const body = value.trim();
-    if (!body || submitting) return;
-    setSubmitting(true);
-    try {
-      await onSubmit(body);
-      setValue('');
-    } finally {
-      setSubmitting(false);
-      textareaRef.current?.focus();
-    }
-  }, [value, submitting, onSubmit]);
+  useEffect(() => {
+    if (!mention) return;
+    const prefix = `@${mention.username} `;
+    setValue((current) => (current.startsWith(prefix) ? current : prefix + current));
+    textareaRef.current?.focus();
+  }, [mention]);
+
+  const submitWith = useCallback(
+    async (handler: (body: string) => Promise<void>) => {
+      const body = value.trim();
+      if (!body || submitting) return;
+      setSubmitting(true);
+      try {
+        await handler(body);
+        setValue('');
+      } catch {
+        // The handler reports the error; keep the text so it can be resent.
+      } finally {
+        setSubmitting(false);
+        textareaRef.current?.focus();
+      }
+    },
+    [value, submitting],
+  );
+
+  const handleSubmit = useCallback(() => submitWith(onSubmit), [submitWith, onSubmit]);
 
   const handleKeyDown = useCallback(
     (e: React.KeyboardEvent<HTMLTextAreaElement>) => {
@@ -61,6 +82,17 @@ export default function CommentInput({ onSubmit }: CommentInputProps) {
       >
         Send
       </button>
+      {onSubmitAndApprove && (
+        <button
+          className="comment-input__send"
+          onClick={() => submitWith(onSubmitAndApprove)}
+          disabled={!value.trim() || submitting}
+          title="Send, then approve once the comment is posted"
+          data-testid="activity-comment-send-approve"
+        >
+          Send &amp; approve
+        </button>
+      )}
     </div>
   );
 }
@@ -1,3 +1,4 @@
 export { default as ActivityDrawer } from './ActivityDrawer';
 export { default as ActivityFeed } from './ActivityFeed';
+export { default as ActivityTimeline } from './ActivityTimeline';
 export { default as CommentInput } from './CommentInput';
@@ -2,11 +2,15 @@
  * Approval button component for MR approval/unapproval.
  *
  * Provides optimistic updates - the UI updates immediately
- * while the action is queued for sync to GitLab.
+ * while the action is queued for sync to GitLab. When the backend holds
+ * the approval back because of review blockers, they're listed with an
+ * "Approve anyway" confirmation.
  */
 
 import { useState, useCallback, useEffect, forwardRef, useImperativeHandle } from 'react';
 import { useApproveMRMutation } from '../../hooks/queries/useApproveMRMutation';
+import { describeBlockers, describeUnmet } from '../../hooks/useActivityData';
+import type { ReviewBlockers } from '../../types';
 import './ApprovalButton.css';
 
 /** Methods exposed via ref */
@@ -43,6 +47,8 @@ const ApprovalButton = forwardRef<ApprovalButtonRef, ApprovalButtonProps>(functi
   const [isApproved, setIsApproved] = useState(hasApproved);
   const [count, setCount] = useState(approvalsCount);
   const [error, setError] = useState<string | null>(null);
+  /** Blockers of the last approval attempt, awaiting confirmation. */
+  const [blockers, setBlockers] = useState<ReviewBlockers | null>(null);
 
   // Sync local state when props change (e.g., from TQ background refetch)
   useEffect(() => { setIsApproved(hasApproved); }, [hasApproved]);
@@ -51,10 +57,13 @@ const ApprovalButton = forwardRef<ApprovalButtonRef, ApprovalButtonProps>(functi
   const { approve, unapprove } = useApproveMRMutation(mrId);
   const isSubmitting = approve.isPending || unapprove.isPending;
 
-  // Handle approve/unapprove
+  // Handle approve/unapprove. With blockers awaiting confirmation, approving
+  // again confirms.
   const handleClick = useCallback((trigger: 'button' | 'keyboard' = 'button') => {
     if (isSubmitting) return;
     setError(null);
+    const confirm = blockers !== null;
+    setBlockers(null);
 
     // Optimistic update
     const newApproved = !isApproved;
@@ -63,17 +72,30 @@ const ApprovalButton = forwardRef<ApprovalButtonRef, ApprovalButtonProps>(functi
     setCount(newCount);
     onApprovalChange?.(newApproved, newCount, trigger);
 
-    const mutation = newApproved ? approve : unapprove;
-    mutation.mutate(undefined, {
-      onError: (err) => {
-        // Rollback on error
-        setIsApproved(isApproved);
-        setCount(count);
-        onApprovalChange?.(isApproved, count, trigger);
-        setError(err instanceof Error ? err.message : 'Failed to update approval');
+    const rollback = () => {
+      setIsApproved(isApproved);
+      setCount(count);
+      onApprovalChange?.(isApproved, count, trigger);
+    };
+    const onError = (err: unknown) => {
+      rollback();
+      setError(err instanceof Error ? err.message : 'Failed to update approval');
+    };
+
+    if (!newApproved) {
+      unapprove.mutate(undefined, { onError });
+      return;
+    }
+    approve.mutate(confirm, {
+      onSuccess: (result) => {
+        if (result.approved) return;
+        rollback();
+        if (result.blockers) setBlockers(result.blockers);
+        else setError(describeUnmet(result.unmet));
       },
+      onError,
     });
-  }, [isApproved, count, isSubmitting, approve, unapprove, onApprovalChange]);
+  }, [isApproved, count, isSubmitting, blockers, approve, unapprove, onApprovalChange]);
 
   // Expose toggle method via ref
   useImperativeHandle(ref, () => ({
@@ -107,6 +129,17 @@ const ApprovalButton = forwardRef<ApprovalButtonRef, ApprovalButtonProps>(functi
         <span className="approval-changes">Changes requested</span>
       )}
       {error && <div className="approval-error">{error}</div>}
+      {blockers && (
+        <div className="approval-blockers" role="alert">
+          <span className="approval-blockers-text">{describeBlockers(blockers)}</span>
+          <button type="button" className="approval-blockers-confirm" onClick={() => handleClick('button')}>
+            Approve anyway
+          </button>
+          <button type="button" className="approval-blockers-cancel" onClick={() => setBlockers(null)}>
+            Cancel
+          </button>
+        </div>
+      )}
     </div>
   );
 });
@@ -2,10 +2,16 @@
  * Comment overlay component for MR detail page.
  *
  * Extracted to isolate re-renders during comment typing from the diff viewer.
+ * Large pastes are held back with an offer to upload them as an attachment.
+ * Comments can also be added to a pending review instead of posted at once.
  */
 
 import { useState, useCallback, useRef, forwardRef, useImperativeHandle, useEffect } from 'react';
+import { useQueryClient } from '@tanstack/react-query';
 import { useAddInlineCommentMutation } from '../hooks/queries/useAddInlineCommentMutation';
+import { useSettingsQuery } from '../hooks/queries/useSettingsQuery';
+import { addDraftComment, suggestComment, uploadCommentAttachment } from '../services/tauri';
+import { queryKeys } from '../lib/queryKeys';
 import type { LineComment } from './PierreDiffViewer/PierreDiffViewer';
 import { buildGitLabSuggestionBlock } from '../utils/gitlabSuggestions';
 
@@ -42,6 +48,9 @@ interface CommentState {
   text: string;
 }
 
+/** Pastes longer than this (in characters) offer an attachment upload instead. */
+const LARGE_PASTE_CHARS = 64 * 1024;
+
 const EMPTY_STATE: CommentState = {
   visible: false,
   position: null,
@@ -59,17 +68,68 @@ export const CommentOverlay = forwardRef<CommentOverlayRef, CommentOverlayProps>
     const submitRef = useRef<() => void>(() => {});
 
     const { mutate: addInlineComment, isPending: submitting } = useAddInlineCommentMutation(mrId);
+    const { data: settings } = useSettingsQuery();
+    const assistEnabled = settings?.reviewAssistant?.enabled ?? false;
+    const [assisting, setAssisting] = useState(false);
+    const [error, setError] = useState<string | null>(null);
+    const [largePaste, setLargePaste] = useState<string | null>(null);
+    const [uploading, setUploading] = useState(false);
+    const [drafting, setDrafting] = useState(false);
+    const queryClient = useQueryClient();
 
     const close = useCallback(() => {
       visibleRef.current = false;
       setState(EMPTY_STATE);
+      setError(null);
+      setLargePaste(null);
     }, []);
 
-    const submit = useCallback(() => {
-      const { text, position } = stateRef.current;
-      if (!text.trim() || !selectedFile || !position) return;
+    const appendText = useCallback((addition: string) => {
+      setState((prev) => ({
+        ...prev,
+        text: prev.text ? `${prev.text.trimEnd()}\n\n${addition}` : addition,
+      }));
+    }, []);
+
+    const uploadPaste = useCallback(async () => {
+      if (!largePaste) return;
+      setUploading(true);
+      setError(null);
+      try {
+        const attachment = await uploadCommentAttachment(mrId, 'paste.txt', largePaste);
+        appendText(attachment.markdown);
+        setLargePaste(null);
+      } catch (err) {
+        setError(err instanceof Error ? err.message : String(err));
+      } finally {
+        setUploading(false);
+      }
+    }, [mrId, largePaste, appendText]);
+
+    const assist = useCallback(async () => {
+      const { position, selection } = stateRef.current;
+      if (!selectedFile || !position) return;
+      setAssisting(true);
+      setError(null);
+      try {
+        const reply = await suggestComment(
+          mrId,
+          selectedFile,
+          selection?.startLine ?? position.line,
+          selection?.endLine ?? position.line,
+        );
+        appendText(reply.text);
+      } catch (err) {
+        setError(err instanceof Error ? err.message : String(err));
+      } finally {
+        setAssisting(false);
+      }
+    }, [mrId, selectedFile, appendText]);
 
-      const request = {
+    const buildRequest = useCallback(() => {
+      const { text, position } = stateRef.current;
+      if (!text.trim() || !selectedFile || !position) return null;
+      return {
         mrId,
         body: text.trim(),
         filePath: selectedFile,
@@ -78,6 +138,28 @@ export const CommentOverlay = forwardRef<CommentOverlayRef, CommentOverlayProps>
           : { newLine: position.line }),
         ...(position.isContext && { isContextLine: true }),
       };
+    }, [mrId, selectedFile]);
+
+    const addToReview = useCallback(async () => {
+      const request = buildRequest();
+      if (!request) return;
+      setDrafting(true);
+      setError(null);
+      try {
+        await addDraftComment(request);
+        queryClient.invalidateQueries({ queryKey: queryKeys.pendingReview(mrId) });
+        close();
+      } catch (err) {
+        setError(err instanceof Error ? err.message : String(err));
+      } finally {
+        setDrafting(false);
+      }
+    }, [mrId, buildRequest, queryClient, close]);
+
+    const submit = useCallback(() => {
+      const { text, position } = stateRef.current;
+      const request = buildRequest();
+      if (!request || !position) return;
 
       addInlineComment(request, {
         onSuccess: (response) => {
@@ -93,9 +175,10 @@ export const CommentOverlay = forwardRef<CommentOverlayRef, CommentOverlayProps>
         },
         onError: (err) => {
           console.error('Failed to add comment:', err);
+          setError(err instanceof Error ? err.message : String(err));
         },
       });
-    }, [selectedFile, mrId, addInlineComment, onCommentAdded, close]);
+    }, [buildRequest, addInlineComment, onCommentAdded, close]);
 
     submitRef.current = submit;
 
@@ -154,6 +237,16 @@ export const CommentOverlay = forwardRef<CommentOverlayRef, CommentOverlayProps>
               >
                 Suggest
               </button>
+              {assistEnabled && !state.position.isOriginal && (
+                <button
+                  className="comment-suggest-btn"
+                  title="Draft a comment on these lines with the review assistant"
+                  onClick={assist}
+                  disabled={assisting || submitting}
+                >
+                  {assisting ? 'Drafting...' : 'Assist'}
+                </button>
+              )}
               <button
                 className="comment-input-close"
                 onClick={close}
@@ -179,6 +272,13 @@ export const CommentOverlay = forwardRef<CommentOverlayRef, CommentOverlayProps>
                   .replace(/[\u201C\u201D\u201F]/g, '"');
                 setState((prev) => ({ ...prev, text: normalized }));
               }}
+              onPaste={(e) => {
+                const pasted = e.clipboardData.getData('text');
+                if (pasted.length > LARGE_PASTE_CHARS) {
+                  e.preventDefault();
+                  setLargePaste(pasted);
+                }
+              }}
               onKeyDown={(e) => {
                 if ((e.metaKey || e.ctrlKey) && e.key === 'Enter') {
                   e.preventDefault();
@@ -190,14 +290,46 @@ export const CommentOverlay = forwardRef<CommentOverlayRef, CommentOverlayProps>
               rows={8}
             />
           </div>
+          {largePaste !== null && (
+            <div className="comment-large-paste">
+              <span>
+                Pasted text is {Math.round(largePaste.length / 1024)} KB. Upload it as an attachment?
+              </span>
+              <button className="comment-suggest-btn" onClick={uploadPaste} disabled={uploading}>
+                {uploading ? 'Uploading...' : 'Upload'}
+              </button>
+              <button
+                className="comment-suggest-btn"
+                onClick={() => {
+                  setState((prev) => ({ ...prev, text: prev.text + largePaste }));
+                  setLargePaste(null);
+                }}
+                disabled={uploading}
+              >
+                Paste inline
+              </button>
+              <button className="comment-suggest-btn" onClick={() => setLargePaste(null)} disabled={uploading}>
+                Discard
+              </button>
+            </div>
+          )}
+          {error && <div className="comment-input-error">{error}</div>}
           <div className="comment-input-actions">
             <span className="comment-input-hint">
               <kbd>⌘</kbd>+<kbd>Enter</kbd> to submit · <kbd>Esc</kbd> to cancel · <kbd>s</kbd> suggest
             </span>
+            <button
+              className="comment-suggest-btn"
+              onClick={addToReview}
+              disabled={!state.text.trim() || submitting || drafting}
+              title="Keep this comment as a draft until the review is submitted"
+            >
+              {drafting ? 'Adding...' : 'Add to review'}
+            </button>
             <button
               className="comment-input-submit"
               onClick={submit}
-              disabled={!state.text.trim() || submitting}
+              disabled={!state.text.trim() || submitting || drafting}
             >
               {submitting ? 'Submitting...' : 'Add Comment'}
             </button>
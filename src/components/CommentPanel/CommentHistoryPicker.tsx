@@ -0,0 +1,78 @@
+/**
+ * Search my past comments across all cached MRs and pick one to reuse.
+ */
+
+import { useEffect, useState } from 'react';
+import { useQuery } from '@tanstack/react-query';
+import { queryKeys } from '../../lib/queryKeys';
+import { getMyCommentHistory } from '../../services/tauri';
+import { formatRelativeTime } from '../../utils/formatRelativeTime';
+
+const RANGES = [
+  { value: 30, label: 'Last 30 days' },
+  { value: 90, label: 'Last 90 days' },
+  { value: 0, label: 'All time' },
+];
+
+const SEARCH_DEBOUNCE_MS = 250;
+
+interface CommentHistoryPickerProps {
+  /** Called with the body of the chosen comment */
+  onPick: (body: string) => void;
+}
+
+export default function CommentHistoryPicker({ onPick }: CommentHistoryPickerProps) {
+  const [input, setInput] = useState('');
+  const [search, setSearch] = useState('');
+  const [days, setDays] = useState(90);
+
+  useEffect(() => {
+    const timer = setTimeout(() => setSearch(input.trim()), SEARCH_DEBOUNCE_MS);
+    return () => clearTimeout(timer);
+  }, [input]);
+
+  const { data: comments = [], isFetching } = useQuery({
+    queryKey: queryKeys.myCommentHistory(days, search),
+    queryFn: () =>
+      getMyCommentHistory(
+        days > 0 ? { since: Math.floor(Date.now() / 1000) - days * 86400 } : {},
+        search,
+      ),
+  });
+
+  return (
+    <div className="comment-history">
+      <div className="comment-history-filters">
+        <input
+          className="comment-history-search"
+          placeholder="Search my comments..."
+          value={input}
+          onChange={(e) => setInput(e.target.value)}
+          autoFocus
+        />
+        <select value={days} onChange={(e) => setDays(Number(e.target.value))}>
+          {RANGES.map((r) => (
+            <option key={r.value} value={r.value}>
+              {r.label}
+            </option>
+          ))}
+        </select>
+      </div>
+      <ul className="comment-history-list">
+        {comments.map((c) => (
+          <li key={c.id}>
+            <button type="button" className="comment-history-item" onClick={() => onPick(c.body)}>
+              <span className="comment-history-meta">
+                {c.projectName} !{c.mrIid} · {formatRelativeTime(c.createdAt)}
+              </span>
+              <span className="comment-history-body">{c.body}</span>
+            </button>
+          </li>
+        ))}
+        {!isFetching && comments.length === 0 && (
+          <li className="comment-history-empty">No matching comments</li>
+        )}
+      </ul>
+    </div>
+  );
+}
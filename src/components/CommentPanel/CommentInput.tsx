@@ -3,6 +3,7 @@
  */
 
 import { useState, useRef, useEffect } from 'react';
+import CommentHistoryPicker from './CommentHistoryPicker';
 import './CommentInput.css';
 
 interface CommentInputProps {
@@ -35,6 +36,7 @@ export default function CommentInput({
   isSubmitting = false,
 }: CommentInputProps) {
   const [body, setBody] = useState('');
+  const [showHistory, setShowHistory] = useState(false);
   const textareaRef = useRef<HTMLTextAreaElement>(null);
 
   useEffect(() => {
@@ -64,6 +66,12 @@ export default function CommentInput({
     }
   };
 
+  const handlePickFromHistory = (picked: string) => {
+    setBody((current) => (current.trim() ? `${current}\n\n${picked}` : picked));
+    setShowHistory(false);
+    textareaRef.current?.focus();
+  };
+
   const isEmpty = body.trim().length === 0;
 
   return (
@@ -78,11 +86,21 @@ export default function CommentInput({
         disabled={isSubmitting}
         rows={3}
       />
+      {showHistory && <CommentHistoryPicker onPick={handlePickFromHistory} />}
       <div className="comment-input-actions">
         <span className="comment-input-hint">
           <kbd>Cmd</kbd>+<kbd>Enter</kbd> to submit
         </span>
         <div className="comment-input-buttons">
+          <button
+            type="button"
+            className="btn btn-secondary"
+            onClick={() => setShowHistory((v) => !v)}
+            disabled={isSubmitting}
+            title="Reuse one of your past comments"
+          >
+            {showHistory ? 'Hide history' : 'My comments'}
+          </button>
           {onCancel && (
             <button
               type="button"
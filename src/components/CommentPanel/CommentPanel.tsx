@@ -5,8 +5,9 @@
  */
 
 import { useReducer, useEffect, useCallback, useState } from 'react';
-import type { Comment } from '../../types';
+import type { AddCommentResult, Comment } from '../../types';
 import { invoke, getGitLabInstances } from '../../services/tauri';
+import { describeHold } from '../../hooks/useActivityData';
 import Markdown from '../Markdown';
 import CommentThread from './CommentThread';
 import CommentInput from './CommentInput';
@@ -127,9 +128,10 @@ export default function CommentPanel({
   const handleAddComment = async (body: string) => {
     try {
       dispatch({ type: 'SUBMIT_START' });
-      await invoke('add_comment', {
+      const result = await invoke<AddCommentResult>('add_comment', {
         input: { mrId, body },
       });
+      if (!result.comment) throw new Error(describeHold(result));
       await fetchComments();
     } catch (e) {
       dispatch({ type: 'SET_ERROR', error: e instanceof Error ? e.message : 'Failed to add comment' });
@@ -0,0 +1,115 @@
+/**
+ * CompanionPairingDialog component.
+ *
+ * Listens for code-based pairing requests from the companion server and asks
+ * the user to confirm that the code matches the one shown on the device.
+ */
+
+import { useEffect, useState } from 'react';
+import { tauriListen } from '../../services/transport';
+import { confirmCompanionPairing, rejectCompanionPairing } from '../../services/tauri';
+import type { CompanionPairingRequest } from '../../types';
+import '../ReAuthPrompt/ReAuthPrompt.css';
+
+/**
+ * Modal shown while a companion device is waiting for pairing confirmation.
+ */
+export default function CompanionPairingDialog() {
+  const [request, setRequest] = useState<CompanionPairingRequest | null>(null);
+  const [error, setError] = useState('');
+  const [busy, setBusy] = useState(false);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    tauriListen<CompanionPairingRequest>('companion-pairing-requested', (event) => {
+      setRequest(event.payload);
+      setError('');
+    }).then((fn) => {
+      unlisten = fn;
+    });
+
+    return () => {
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, []);
+
+  // Close the dialog once the code can no longer be confirmed.
+  useEffect(() => {
+    if (!request) return;
+    const remainingMs = request.expiresAt * 1000 - Date.now();
+    const timer = setTimeout(() => setRequest(null), Math.max(remainingMs, 0));
+    return () => clearTimeout(timer);
+  }, [request]);
+
+  if (!request) return null;
+
+  const handleConfirm = async () => {
+    setBusy(true);
+    try {
+      await confirmCompanionPairing(request.code);
+      setRequest(null);
+    } catch {
+      setError('This pairing request has expired. Start pairing again on the device.');
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const handleReject = async () => {
+    setBusy(true);
+    // The request may already have expired; either way the dialog closes.
+    await rejectCompanionPairing(request.code).catch(() => {});
+    setBusy(false);
+    setRequest(null);
+  };
+
+  return (
+    <div
+      className="reauth-prompt-overlay"
+      role="dialog"
+      aria-modal="true"
+      aria-labelledby="pairing-title"
+    >
+      <div className="reauth-prompt-modal">
+        <h2 id="pairing-title" className="reauth-prompt-title">
+          Pair Companion Device
+        </h2>
+
+        <p className="reauth-prompt-message">
+          <strong>{request.deviceName}</strong> wants to connect. Only confirm if
+          the device shows this code:
+        </p>
+
+        <div className="reauth-prompt-instance">
+          <span className="reauth-prompt-instance-url">{request.code}</span>
+        </div>
+
+        {error && (
+          <p className="reauth-prompt-hint" role="alert">
+            {error}
+          </p>
+        )}
+
+        <div className="reauth-prompt-actions">
+          <button
+            className="reauth-prompt-button primary"
+            onClick={handleConfirm}
+            disabled={busy}
+          >
+            Confirm
+          </button>
+          <button
+            className="reauth-prompt-button secondary"
+            onClick={handleReject}
+            disabled={busy}
+          >
+            Reject
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1 @@
+export { default as CompanionPairingDialog } from './CompanionPairingDialog';
@@ -19,6 +19,8 @@ interface FileNavigationProps {
   focusIndex?: number;
   /** Set of file paths that have been marked as viewed */
   viewedPaths?: Set<string>;
+  /** Callback to mark a file viewed or unviewed from the list */
+  onToggleViewed?: (filePath: string, viewed: boolean) => void;
   /** Set of file paths classified as generated */
   generatedPaths?: Set<string>;
   /** Whether generated files are currently hidden */
@@ -69,6 +71,7 @@ export default function FileNavigation({
   onSelect,
   focusIndex,
   viewedPaths,
+  onToggleViewed,
   generatedPaths,
   hideGenerated,
   onToggleHideGenerated,
@@ -220,7 +223,23 @@ export default function FileNavigation({
                   <span className="stat-del">-{file.deletions}</span>
                 )}
               </div>
-              {isViewed && <span className="file-viewed-indicator">✓</span>}
+              {onToggleViewed ? (
+                <button
+                  type="button"
+                  className={`file-viewed-toggle ${isViewed ? 'checked' : ''}`}
+                  onClick={(e) => {
+                    e.stopPropagation();
+                    onToggleViewed(file.newPath, !isViewed);
+                  }}
+                  aria-label={isViewed ? 'Mark as not viewed' : 'Mark as viewed'}
+                  aria-pressed={isViewed}
+                  title={isViewed ? 'Viewed' : 'Mark as viewed'}
+                >
+                  ✓
+                </button>
+              ) : (
+                isViewed && <span className="file-viewed-indicator">✓</span>
+              )}
             </div>
           );
         })}
@@ -0,0 +1,150 @@
+/**
+ * Import GitLab instances from a glab or python-gitlab config file.
+ *
+ * Scans the file first, lets the user tick which instances to import, and
+ * only then sends tokens for validation.
+ */
+
+import { useState } from 'react';
+import { importInstancesFromConfig, type ConfigImportEntry } from '../../services/tauri';
+
+interface ConfigImportProps {
+  /** Called after at least one instance was imported */
+  onImported: () => void;
+}
+
+const STATUS_LABELS: Record<ConfigImportEntry['status'], string> = {
+  pending: '',
+  missingToken: 'No token in file',
+  skipped: 'Skipped',
+  imported: 'Imported',
+  failed: 'Failed',
+};
+
+export default function ConfigImport({ onImported }: ConfigImportProps) {
+  const [path, setPath] = useState('~/.config/glab-cli/config.yml');
+  const [entries, setEntries] = useState<ConfigImportEntry[] | null>(null);
+  const [selected, setSelected] = useState<Set<string>>(new Set());
+  const [loading, setLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  async function handleScan() {
+    setLoading(true);
+    setError(null);
+    try {
+      const found = await importInstancesFromConfig(path.trim());
+      setEntries(found);
+      setSelected(
+        new Set(found.filter((e) => e.status === 'pending' && !e.alreadyConfigured).map((e) => e.url))
+      );
+    } catch (err) {
+      setEntries(null);
+      setError(err instanceof Error ? err.message : 'Failed to read config file');
+    } finally {
+      setLoading(false);
+    }
+  }
+
+  async function handleImport() {
+    setLoading(true);
+    setError(null);
+    try {
+      const results = await importInstancesFromConfig(path.trim(), [...selected]);
+      setEntries(results);
+      setSelected(new Set());
+      if (results.some((e) => e.status === 'imported')) {
+        onImported();
+      }
+    } catch (err) {
+      setError(err instanceof Error ? err.message : 'Import failed');
+    } finally {
+      setLoading(false);
+    }
+  }
+
+  function toggle(url: string) {
+    setSelected((prev) => {
+      const next = new Set(prev);
+      if (next.has(url)) {
+        next.delete(url);
+      } else {
+        next.add(url);
+      }
+      return next;
+    });
+  }
+
+  return (
+    <div className="config-import">
+      <div className="form-group">
+        <label htmlFor="config-import-path">Import from glab or python-gitlab</label>
+        <div className="config-import-row">
+          <input
+            id="config-import-path"
+            type="text"
+            value={path}
+            onChange={(e) => setPath(e.target.value)}
+            disabled={loading}
+          />
+          <button
+            type="button"
+            className="cancel-button"
+            onClick={handleScan}
+            disabled={loading || !path.trim()}
+          >
+            Scan
+          </button>
+        </div>
+        <span className="form-help">
+          e.g. <code>~/.config/glab-cli/config.yml</code> or <code>~/.python-gitlab.cfg</code>
+        </span>
+      </div>
+
+      {error && <div className="form-error">{error}</div>}
+
+      {entries && entries.length === 0 && (
+        <div className="form-help">No instances found in this file.</div>
+      )}
+
+      {entries && entries.length > 0 && (
+        <>
+          <ul className="config-import-list">
+            {entries.map((entry) => (
+              <li key={entry.url}>
+                <label>
+                  <input
+                    type="checkbox"
+                    checked={selected.has(entry.url)}
+                    onChange={() => toggle(entry.url)}
+                    disabled={loading || entry.status !== 'pending'}
+                  />
+                  <span>{entry.url}</span>
+                  {entry.alreadyConfigured && entry.status === 'pending' && (
+                    <span className="form-help">already set up, token will be replaced</span>
+                  )}
+                  {entry.status !== 'pending' && (
+                    <span className={`config-import-status ${entry.status}`}>
+                      {entry.status === 'imported' && entry.username
+                        ? `Imported as ${entry.username}`
+                        : entry.error ?? STATUS_LABELS[entry.status]}
+                    </span>
+                  )}
+                </label>
+              </li>
+            ))}
+          </ul>
+          <div className="form-actions">
+            <button
+              type="button"
+              className="submit-button"
+              onClick={handleImport}
+              disabled={loading || selected.size === 0}
+            >
+              {loading ? 'Validating...' : `Import ${selected.size} selected`}
+            </button>
+          </div>
+        </>
+      )}
+    </div>
+  );
+}
@@ -6,6 +6,7 @@
 
 import { useReducer } from 'react';
 import { addGitLabInstance } from '../../services/gitlab';
+import ConfigImport from './ConfigImport';
 import './InstanceSetup.css';
 
 interface InstanceSetupProps {
@@ -20,15 +21,22 @@ interface SetupState {
   token: string;
   name: string;
   sessionCookie: string;
+  headerName: string;
+  headerValue: string;
+  tlsMinVersion: '1.2' | '1.3';
+  clientIdentityPath: string;
   loading: boolean;
   error: string | null;
   success: string | null;
+  /** Token scope warnings from a successful setup. */
+  warnings: string[];
 }
 
 type SetupAction =
-  | { type: 'SET_FIELD'; field: 'url' | 'token' | 'name' | 'sessionCookie'; value: string }
+  | { type: 'SET_FIELD'; field: 'url' | 'token' | 'name' | 'sessionCookie' | 'headerName' | 'headerValue' | 'clientIdentityPath'; value: string }
+  | { type: 'SET_TLS_MIN_VERSION'; value: '1.2' | '1.3' }
   | { type: 'SUBMIT_START' }
-  | { type: 'SUBMIT_SUCCESS'; message: string }
+  | { type: 'SUBMIT_SUCCESS'; message: string; warnings: string[] }
   | { type: 'SUBMIT_ERROR'; error: string }
   | { type: 'SUBMIT_END' };
 
@@ -36,10 +44,12 @@ function setupReducer(state: SetupState, action: SetupAction): SetupState {
   switch (action.type) {
     case 'SET_FIELD':
       return { ...state, [action.field]: action.value };
+    case 'SET_TLS_MIN_VERSION':
+      return { ...state, tlsMinVersion: action.value };
     case 'SUBMIT_START':
       return { ...state, loading: true, error: null };
     case 'SUBMIT_SUCCESS':
-      return { ...state, success: action.message };
+      return { ...state, success: action.message, warnings: action.warnings };
     case 'SUBMIT_ERROR':
       return { ...state, error: action.error };
     case 'SUBMIT_END':
@@ -56,12 +66,20 @@ export default function InstanceSetup({ onComplete, onCancel }: InstanceSetupPro
     token: '',
     name: '',
     sessionCookie: '',
+    headerName: '',
+    headerValue: '',
+    tlsMinVersion: '1.2',
+    clientIdentityPath: '',
     loading: false,
     error: null,
     success: null,
+    warnings: [],
   });
 
-  const { url, token, name, sessionCookie, loading, error, success } = state;
+  const {
+    url, token, name, sessionCookie, headerName, headerValue, tlsMinVersion, clientIdentityPath,
+    loading, error, success, warnings,
+  } = state;
 
   async function handleSubmit(e: React.FormEvent) {
     e.preventDefault();
@@ -74,6 +92,10 @@ export default function InstanceSetup({ onComplete, onCancel }: InstanceSetupPro
       dispatch({ type: 'SUBMIT_ERROR', error: 'Personal Access Token is required' });
       return;
     }
+    if (headerName.trim() && !headerValue) {
+      dispatch({ type: 'SUBMIT_ERROR', error: 'Custom header value is required' });
+      return;
+    }
 
     try {
       dispatch({ type: 'SUBMIT_START' });
@@ -83,14 +105,28 @@ export default function InstanceSetup({ onComplete, onCancel }: InstanceSetupPro
         token: token.trim(),
         name: name.trim() || undefined,
         sessionCookie: sessionCookie.trim() || undefined,
+        customHeaders: headerName.trim()
+          ? [{ name: headerName.trim(), value: headerValue }]
+          : undefined,
+        tls: tlsMinVersion !== '1.2' || clientIdentityPath.trim()
+          ? { minVersion: tlsMinVersion, clientIdentityPath: clientIdentityPath.trim() || null }
+          : undefined,
       });
 
-      dispatch({ type: 'SUBMIT_SUCCESS', message: `Successfully connected as ${result.username}` });
+      const scopeWarnings = result.scopeCheck.warnings;
+      const protocol = result.tlsProtocol ? ` over ${result.tlsProtocol}` : '';
+      dispatch({
+        type: 'SUBMIT_SUCCESS',
+        message: `Successfully connected as ${result.username}${protocol}`,
+        warnings: scopeWarnings,
+      });
 
-      // Brief delay to show success message
-      setTimeout(() => {
-        onComplete();
-      }, 1000);
+      // Brief delay to show success message; scope warnings wait for the user
+      if (scopeWarnings.length === 0) {
+        setTimeout(() => {
+          onComplete();
+        }, 1000);
+      }
     } catch (err) {
       dispatch({ type: 'SUBMIT_ERROR', error: err instanceof Error ? err.message : 'Failed to add instance' });
     } finally {
@@ -130,8 +166,9 @@ export default function InstanceSetup({ onComplete, onCancel }: InstanceSetupPro
             disabled={loading}
           />
           <span className="form-help">
-            Create a token with <code>read_api</code> and <code>api</code> scopes
-            in GitLab → Settings → Access Tokens
+            Create a token in GitLab → Settings → Access Tokens with the{' '}
+            <code>api</code> scope, or only <code>read_api</code> for a read-only
+            setup without commenting and approving. Other scopes aren't needed.
           </span>
         </div>
 
@@ -167,8 +204,77 @@ export default function InstanceSetup({ onComplete, onCancel }: InstanceSetupPro
           </span>
         </div>
 
+        <div className="form-group">
+          <label htmlFor="instance-header-name">Custom Header (optional)</label>
+          <input
+            id="instance-header-name"
+            type="text"
+            placeholder="X-Auth-Token"
+            value={headerName}
+            onChange={(e) => dispatch({ type: 'SET_FIELD', field: 'headerName', value: e.target.value })}
+            disabled={loading}
+          />
+          <input
+            id="instance-header-value"
+            type="password"
+            placeholder="header value"
+            aria-label="Custom header value"
+            value={headerValue}
+            onChange={(e) => dispatch({ type: 'SET_FIELD', field: 'headerValue', value: e.target.value })}
+            disabled={loading}
+          />
+          <span className="form-help">
+            Sent with every request, for instances behind an SSO proxy that
+            requires its own header in addition to the token.
+          </span>
+        </div>
+
+        <div className="form-group">
+          <label htmlFor="instance-tls-min-version">Minimum TLS Version</label>
+          <select
+            id="instance-tls-min-version"
+            value={tlsMinVersion}
+            onChange={(e) => dispatch({ type: 'SET_TLS_MIN_VERSION', value: e.target.value as '1.2' | '1.3' })}
+            disabled={loading}
+          >
+            <option value="1.2">TLS 1.2</option>
+            <option value="1.3">TLS 1.3 only</option>
+          </select>
+          <span className="form-help">
+            Refuse older protocol versions, for instances that require TLS 1.3.
+          </span>
+        </div>
+
+        <div className="form-group">
+          <label htmlFor="instance-client-identity">Client Certificate (optional)</label>
+          <input
+            id="instance-client-identity"
+            type="text"
+            placeholder="/path/to/client.pem"
+            value={clientIdentityPath}
+            onChange={(e) => dispatch({ type: 'SET_FIELD', field: 'clientIdentityPath', value: e.target.value })}
+            disabled={loading}
+          />
+          <span className="form-help">
+            A PEM file with the certificate followed by its private key, for
+            instances that require mutual TLS.
+          </span>
+        </div>
+
         {error && <div className="form-error">{error}</div>}
         {success && <div className="form-success">{success}</div>}
+        {warnings.length > 0 && (
+          <div className="form-warning">
+            <ul>
+              {warnings.map((w) => (
+                <li key={w}>{w}</li>
+              ))}
+            </ul>
+            <button type="button" className="submit-button" onClick={onComplete}>
+              Continue
+            </button>
+          </div>
+        )}
 
         <div className="form-actions">
           <button
@@ -188,6 +294,8 @@ export default function InstanceSetup({ onComplete, onCancel }: InstanceSetupPro
           </button>
         </div>
       </form>
+
+      <ConfigImport onImported={onComplete} />
     </div>
   );
 }
@@ -4,16 +4,40 @@
  * Displays a list of merge requests with filtering and selection.
  */
 
-import { useState, useEffect, useCallback, useRef, useMemo } from 'react';
+import { Fragment, useState, useEffect, useCallback, useRef, useMemo } from 'react';
 import { useQueryClient } from '@tanstack/react-query';
 import { useMRListQuery } from '../../hooks/queries/useMRListQuery';
-import type { MergeRequest } from '../../types';
+import { useReviewSlaQuery } from '../../hooks/queries/useReviewSlaQuery';
+import { useSettingsQuery } from '../../hooks/queries/useSettingsQuery';
+import { queryKeys } from '../../lib/queryKeys';
+import { setOfflineMode } from '../../services/tauri';
+import type { MergeRequest, ReviewSlaStatus } from '../../types';
 import MRListItem from './MRListItem';
 import './MRList.css';
 
 const SYNCING_INDICATOR_DELAY_MS = 350;
 const UPDATED_INDICATOR_DURATION_MS = 2000;
 
+/** A project with MRs in the list, for quick filters and group headers. */
+interface ListProject {
+  id: number;
+  name: string;
+  count: number;
+}
+
+/**
+ * Projects in display order: those in `order` first, the rest by name.
+ */
+function orderProjects(projects: ListProject[], order: number[]): ListProject[] {
+  const rank = new Map(order.map((id, i) => [id, i]));
+  return [...projects].sort((a, b) => {
+    const ra = rank.get(a.id) ?? Infinity;
+    const rb = rank.get(b.id) ?? Infinity;
+    if (ra !== rb) return ra - rb;
+    return a.name.localeCompare(b.name);
+  });
+}
+
 /**
  * Format a timestamp as relative time string.
  */
@@ -48,8 +72,22 @@ interface MRListProps {
   onToggleApproved?: () => void;
   /** Render rows in the compact single-line layout */
   condensed?: boolean;
+  /** Project IDs the list is limited to; empty shows all */
+  projectFilter?: number[];
+  /** Callback when the project quick filter changes */
+  onProjectFilterChange?: (projectIds: number[]) => void;
+  /** Group MRs under project headers */
+  groupByProject?: boolean;
+  /** Callback to toggle project grouping */
+  onGroupByProjectChange?: (group: boolean) => void;
+  /** Order of project groups; unlisted projects follow by name */
+  projectOrder?: number[];
+  /** Callback when a project group is moved */
+  onProjectOrderChange?: (projectIds: number[]) => void;
 }
 
+const NO_PROJECTS: number[] = [];
+
 /**
  * Merge request list component with filtering.
  */
@@ -65,9 +103,22 @@ export default function MRList({
   showApproved = false,
   onToggleApproved,
   condensed = false,
+  projectFilter = NO_PROJECTS,
+  onProjectFilterChange,
+  groupByProject = false,
+  onGroupByProjectChange,
+  projectOrder = NO_PROJECTS,
+  onProjectOrderChange,
 }: MRListProps) {
-  const query = useMRListQuery(instanceId);
+  const [showBots, setShowBots] = useState(false);
+  const query = useMRListQuery(instanceId, showBots);
   const queryClient = useQueryClient();
+  const offline = useSettingsQuery().data?.offlineMode ?? false;
+  const slaQuery = useReviewSlaQuery();
+  const slaByMr = useMemo(
+    () => new Map<number, ReviewSlaStatus>((slaQuery.data ?? []).map((s) => [s.mrId, s])),
+    [slaQuery.data]
+  );
 
   // UI-only state
   const [syncStatus, setSyncStatus] = useState<'idle' | 'syncing' | 'success' | 'error'>('idle');
@@ -77,13 +128,65 @@ export default function MRList({
   const idleTimerRef = useRef<ReturnType<typeof setTimeout> | null>(null);
 
   // Apply showApproved filter to query data
-  const mrs = useMemo(() => {
+  const unapprovedMrs = useMemo(() => {
     const data = query.data ?? [];
-    return showApproved ? data : data.filter(mr => !mr.userHasApproved);
+    // A re-requested review needs another look even if I approved before.
+    return showApproved ? data : data.filter(mr => !mr.userHasApproved || mr.reviewRerequestedAt != null);
   }, [query.data, showApproved]);
 
+  // Projects with MRs, in group order
+  const projects = useMemo(() => {
+    const byId = new Map<number, ListProject>();
+    for (const mr of unapprovedMrs) {
+      const project = byId.get(mr.projectId);
+      if (project) project.count += 1;
+      else byId.set(mr.projectId, { id: mr.projectId, name: mr.projectName || `Project ${mr.projectId}`, count: 1 });
+    }
+    return orderProjects([...byId.values()], projectOrder);
+  }, [unapprovedMrs, projectOrder]);
+
+  // Only filter on projects that still have MRs, so a stale filter can't
+  // hide the whole list.
+  const activeFilter = useMemo(
+    () => projectFilter.filter(id => projects.some(p => p.id === id)),
+    [projectFilter, projects]
+  );
+
+  // Apply the project quick filter and grouping. Grouping sorts here, so the
+  // parent's keyboard indices match the rendered order.
+  const mrs = useMemo(() => {
+    const visible = activeFilter.length > 0
+      ? unapprovedMrs.filter(mr => activeFilter.includes(mr.projectId))
+      : unapprovedMrs;
+    if (!groupByProject) return visible;
+    const rank = new Map(projects.map((p, i) => [p.id, i]));
+    return [...visible].sort((a, b) => (rank.get(a.projectId) ?? 0) - (rank.get(b.projectId) ?? 0));
+  }, [unapprovedMrs, activeFilter, groupByProject, projects]);
+
   const totalFetched = query.data?.length ?? 0;
-  const approvedCount = totalFetched - mrs.length;
+  const approvedCount = totalFetched - unapprovedMrs.length;
+
+  const toggleProjectFilter = useCallback(
+    (projectId: number) => {
+      const next = activeFilter.includes(projectId)
+        ? activeFilter.filter(id => id !== projectId)
+        : [...activeFilter, projectId];
+      onProjectFilterChange?.(next);
+    },
+    [activeFilter, onProjectFilterChange]
+  );
+
+  const moveProjectGroup = useCallback(
+    (projectId: number, delta: -1 | 1) => {
+      const order = projects.map(p => p.id);
+      const from = order.indexOf(projectId);
+      const to = from + delta;
+      if (from < 0 || to < 0 || to >= order.length) return;
+      [order[from], order[to]] = [order[to], order[from]];
+      onProjectOrderChange?.(order);
+    },
+    [projects, onProjectOrderChange]
+  );
 
   // Filter MRs by search query
   const filteredMrs = useMemo(() => {
@@ -229,8 +332,37 @@ export default function MRList({
     );
   }
 
+  const groupCounts = new Map(projects.map(p => [p.id, 0]));
+  for (const mr of filteredMrs) {
+    groupCounts.set(mr.projectId, (groupCounts.get(mr.projectId) ?? 0) + 1);
+  }
+  const groupProjects = projects.filter(p => (groupCounts.get(p.id) ?? 0) > 0);
+
   return (
     <div className="mr-list">
+      {projects.length > 1 && (
+        <div className="mr-list-project-filters" role="toolbar" aria-label="Filter by project">
+          {projects.map(project => {
+            const active = activeFilter.includes(project.id);
+            return (
+              <button
+                key={project.id}
+                className={`mr-list-project-chip${active ? ' mr-list-project-chip--active' : ''}`}
+                onClick={() => toggleProjectFilter(project.id)}
+                aria-pressed={active}
+              >
+                {project.name}
+                <span className="mr-list-project-chip-count">{project.count}</span>
+              </button>
+            );
+          })}
+          {activeFilter.length > 0 && (
+            <button className="mr-list-project-chip-clear" onClick={() => onProjectFilterChange?.([])}>
+              Clear
+            </button>
+          )}
+        </div>
+      )}
       <div className={`mr-list-content${condensed ? ' mr-list-content--condensed' : ''}`}>
         {mrs.length === 0 ? (
           <div className="mr-list-empty">
@@ -246,21 +378,49 @@ export default function MRList({
             <p>No merge requests match your search</p>
           </div>
         ) : (
-          filteredMrs.map((mr, index) => (
-            <MRListItem
-              key={mr.id}
-              ref={(el) => {
-                if (el) itemRefs.current.set(index, el);
-                else itemRefs.current.delete(index);
-              }}
-              mr={mr}
-              selected={mr.id === selectedMrId || index === focusIndex}
-              isNew={newMrIds.has(mr.id)}
-              onClick={() => handleSelect(mr, index)}
-              highlightQuery={filterQuery}
-              condensed={condensed}
-            />
-          ))
+          filteredMrs.map((mr, index) => {
+            const groupIndex = groupProjects.findIndex(p => p.id === mr.projectId);
+            const startsGroup = groupByProject && filteredMrs[index - 1]?.projectId !== mr.projectId;
+            return (
+              <Fragment key={mr.id}>
+                {startsGroup && (
+                  <div className="mr-list-group-header">
+                    <span className="mr-list-group-name">{groupProjects[groupIndex]?.name ?? mr.projectName}</span>
+                    <span className="mr-list-group-count">{groupCounts.get(mr.projectId)}</span>
+                    <button
+                      className="mr-list-group-move"
+                      onClick={() => moveProjectGroup(mr.projectId, -1)}
+                      disabled={groupIndex <= 0}
+                      aria-label="Move project up"
+                    >
+                      ↑
+                    </button>
+                    <button
+                      className="mr-list-group-move"
+                      onClick={() => moveProjectGroup(mr.projectId, 1)}
+                      disabled={groupIndex < 0 || groupIndex >= groupProjects.length - 1}
+                      aria-label="Move project down"
+                    >
+                      ↓
+                    </button>
+                  </div>
+                )}
+                <MRListItem
+                  ref={(el) => {
+                    if (el) itemRefs.current.set(index, el);
+                    else itemRefs.current.delete(index);
+                  }}
+                  mr={mr}
+                  selected={mr.id === selectedMrId || index === focusIndex}
+                  isNew={newMrIds.has(mr.id)}
+                  onClick={() => handleSelect(mr, index)}
+                  highlightQuery={filterQuery}
+                  condensed={condensed}
+                  sla={slaByMr.get(mr.id)}
+                />
+              </Fragment>
+            );
+          })
         )}
         {!showApproved && approvedCount > 0 && mrs.length > 0 && (
           <button
@@ -278,29 +438,58 @@ export default function MRList({
 
       <div className="mr-list-footer">
         <span className="mr-count">{mrs.length} merge requests</span>
-        <span className={`mr-sync-status mr-sync-status--${syncStatus}`}>
-          {syncStatus === 'syncing' && (
-            <>
-              <span className="sync-spinner" />
-              Syncing...
-            </>
-          )}
-          {syncStatus === 'success' && (
-            <>
-              <span className="sync-check">✓</span>
-              Updated
-            </>
-          )}
-          {syncStatus === 'idle' && lastSyncedAt && (
-            <>Synced {formatSyncTime(lastSyncedAt)}</>
-          )}
-          {syncStatus === 'error' && (
-            <>
-              <span className="sync-error">!</span>
-              Sync failed
-            </>
-          )}
-        </span>
+        {onGroupByProjectChange && (
+          <button
+            className="mr-list-bots-toggle"
+            onClick={() => onGroupByProjectChange(!groupByProject)}
+          >
+            {groupByProject ? 'Ungroup' : 'Group by project'}
+          </button>
+        )}
+        <button
+          className="mr-list-bots-toggle"
+          onClick={() => setShowBots((v) => !v)}
+          title="Dependabot, Renovate and other bot accounts"
+        >
+          {showBots ? 'Hide bot MRs' : 'Show bot MRs'}
+        </button>
+        {offline ? (
+          <button
+            className="mr-sync-status mr-sync-status--offline"
+            onClick={async () => {
+              await setOfflineMode(false);
+              queryClient.invalidateQueries({ queryKey: queryKeys.settings() });
+            }}
+            title="Offline mode is on: nothing is synced and actions stay queued. Click to go back online."
+          >
+            <span className="sync-offline" />
+            Offline
+          </button>
+        ) : (
+          <span className={`mr-sync-status mr-sync-status--${syncStatus}`}>
+            {syncStatus === 'syncing' && (
+              <>
+                <span className="sync-spinner" />
+                Syncing...
+              </>
+            )}
+            {syncStatus === 'success' && (
+              <>
+                <span className="sync-check">✓</span>
+                Updated
+              </>
+            )}
+            {syncStatus === 'idle' && lastSyncedAt && (
+              <>Synced {formatSyncTime(lastSyncedAt)}</>
+            )}
+            {syncStatus === 'error' && (
+              <>
+                <span className="sync-error">!</span>
+                Sync failed
+              </>
+            )}
+          </span>
+        )}
       </div>
     </div>
   );
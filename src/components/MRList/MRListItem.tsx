@@ -5,7 +5,7 @@
  */
 
 import { forwardRef } from 'react';
-import type { MergeRequest, ApprovalStatus } from '../../types';
+import type { MergeRequest, ApprovalStatus, ReviewSlaStatus, SlaState } from '../../types';
 import UserAvatar from '../UserAvatar/UserAvatar';
 import HighlightText from '../HighlightText/HighlightText';
 import './MRListItem.css';
@@ -23,6 +23,8 @@ interface MRListItemProps {
   highlightQuery?: string;
   /** Render the compact single-line layout */
   condensed?: boolean;
+  /** Review SLA status, when the MR is awaiting my review */
+  sla?: ReviewSlaStatus;
 }
 
 /**
@@ -79,6 +81,22 @@ function approvalTitle(mr: MergeRequest): string {
   }
 }
 
+const SLA_LABELS: Record<SlaState, string> = {
+  on_track: 'On track',
+  at_risk: 'Due soon',
+  breached: 'Overdue',
+};
+
+/** Tooltip for the SLA badge: when the response is (or was) due. */
+function slaTitle(sla: ReviewSlaStatus): string {
+  const due = new Date(sla.dueAt * 1000).toLocaleString(undefined, {
+    weekday: 'short',
+    hour: '2-digit',
+    minute: '2-digit',
+  });
+  return sla.state === 'breached' ? `Review was due ${due}` : `Review due ${due}`;
+}
+
 function condensedApprovalClass(mr: MergeRequest): string {
   if (mr.userHasApproved) return 'approval-user-approved';
   return getApprovalClass(mr.approvalStatus);
@@ -88,12 +106,13 @@ function condensedApprovalClass(mr: MergeRequest): string {
  * Single merge request list item.
  */
 const MRListItem = forwardRef<HTMLDivElement, MRListItemProps>(
-  function MRListItem({ mr, selected, isNew, onClick, highlightQuery, condensed }, ref) {
+  function MRListItem({ mr, selected, isNew, onClick, highlightQuery, condensed, sla }, ref) {
     const classNames = ['mr-list-item'];
     if (condensed) classNames.push('mr-list-item--condensed');
     if (selected) classNames.push('selected');
     if (isNew) classNames.push('is-new');
-    if (mr.userHasApproved) classNames.push('user-approved');
+    if (mr.userHasApproved && mr.reviewRerequestedAt == null) classNames.push('user-approved');
+    if (mr.reviewRerequestedAt != null) classNames.push('review-rerequested');
 
     const projectLabel = mr.projectName?.replace(/^Customers\s*\/\s*/, '') ?? '';
 
@@ -128,6 +147,21 @@ const MRListItem = forwardRef<HTMLDivElement, MRListItemProps>(
               className="mr-condensed-avatar"
             />
             <span className="mr-iid">!{mr.iid}</span>
+            {mr.reviewRerequestedAt != null && (
+              <span className="mr-condensed-rerequested" title="Review re-requested" aria-label="Review re-requested">
+                ↻
+              </span>
+            )}
+            {sla && sla.state !== 'on_track' && (
+              <span className={`mr-condensed-sla sla-${sla.state}`} title={slaTitle(sla)} aria-label={SLA_LABELS[sla.state]}>
+                ⏱
+              </span>
+            )}
+            {mr.changedSinceReview && (
+              <span className="mr-condensed-new-commits" title="New commits since your review" aria-label="New commits since your review">
+                ●
+              </span>
+            )}
             <span className="mr-condensed-title">
               {highlightQuery ? <HighlightText text={mr.title} query={highlightQuery} /> : mr.title}
             </span>
@@ -170,9 +204,43 @@ const MRListItem = forwardRef<HTMLDivElement, MRListItemProps>(
           </div>
 
           <div className="mr-item-footer">
+            {sla && (
+              <span className={`mr-approval mr-sla sla-${sla.state}`} title={slaTitle(sla)}>
+                ⏱ {SLA_LABELS[sla.state]}
+              </span>
+            )}
+            {mr.reviewRerequestedAt != null && (
+              <span
+                className="mr-approval approval-rerequested"
+                title={`Review re-requested ${formatRelativeTime(mr.reviewRerequestedAt)}`}
+              >
+                ↻ Re-review
+              </span>
+            )}
+            {mr.changedSinceReview && (
+              <span className="mr-approval approval-new-commits" title="New commits since your review">
+                ● New commits
+              </span>
+            )}
             {mr.userHasApproved && (
               <span className="mr-approval approval-user-approved">✓ You approved</span>
             )}
+            {mr.taskCount > 0 && (
+              <span
+                className="mr-tasks"
+                title={`${mr.taskCompletedCount} of ${mr.taskCount} tasks done`}
+              >
+                ☑ {mr.taskCompletedCount}/{mr.taskCount}
+              </span>
+            )}
+            {mr.milestoneTitle && (
+              <span
+                className="mr-milestone"
+                title={mr.milestoneDueDate ? `Milestone, due ${mr.milestoneDueDate}` : 'Milestone'}
+              >
+                ◷ {mr.milestoneTitle}
+              </span>
+            )}
             {mr.approvalStatus && !mr.userHasApproved && (
               <span className={`mr-approval ${getApprovalClass(mr.approvalStatus)}`}>
                 {mr.approvalStatus === 'approved' && '✓ Approved'}
@@ -180,6 +248,15 @@ const MRListItem = forwardRef<HTMLDivElement, MRListItemProps>(
                 {mr.approvalStatus === 'changes_requested' && '✕ Changes'}
               </span>
             )}
+            {mr.customBadges?.map((badge) => (
+              <span
+                key={badge.ruleId}
+                className="mr-custom-badge"
+                style={badge.color ? { background: badge.color } : undefined}
+              >
+                {badge.label}
+              </span>
+            ))}
             {mr.labels.length > 0 && (
               <span className="mr-labels">
                 {mr.labels.slice(0, 3).map((label) => (
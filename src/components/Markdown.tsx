@@ -1,10 +1,8 @@
-import ReactMarkdown from 'react-markdown';
-import remarkGfm from 'remark-gfm';
-import rehypeRaw from 'rehype-raw';
-import rehypeSanitize from 'rehype-sanitize';
-import { Link, useNavigate } from 'react-router-dom';
-import { useMemo, type MouseEvent } from 'react';
+import { createContext, useContext, type MouseEvent } from 'react';
+import { useNavigate } from 'react-router-dom';
+import { useRenderedMarkdownQuery } from '../hooks/queries/useRenderedMarkdownQuery';
 import { resolveProjectByPath } from '../services';
+import type { MarkdownProjectContext } from '../types';
 import './Markdown.css';
 
 export interface IssueRef {
@@ -26,8 +24,16 @@ interface Props {
   content: string;
   className?: string;
   issueLinkContext?: IssueLinkContext;
+  /** Project references resolve against; defaults to the provider's. */
+  projectContext?: MarkdownProjectContext;
 }
 
+/**
+ * Project that markdown below it belongs to, for pages where every body
+ * comes from one project (an MR's description and comments).
+ */
+export const MarkdownProjectScope = createContext<MarkdownProjectContext | undefined>(undefined);
+
 function escapeRegex(s: string): string {
   return s.replace(/[.*+?^${}()|[\]\\]/g, '\\$&');
 }
@@ -66,49 +72,19 @@ function normalizePath(p: string): string {
 }
 
 /**
- * Insert a blank line after `</summary>` when the author forgot one.
- *
- * Per CommonMark, a raw-HTML block runs until the next blank line, so
- * `<details>` without a blank line after `</summary>` swallows whatever
- * follows (typically a code fence) as literal HTML text — GitLab's own
- * renderer has the same problem. Normalizing here lets the content inside
- * `<details>` parse as real markdown. Lines inside code fences are left
- * untouched.
+ * GitLab-flavored markdown, rendered and sanitized by the backend so the
+ * desktop app and the companion show the same HTML. The source shows as
+ * plain text until the HTML arrives.
  */
-export function normalizeDetailsBlocks(content: string): string {
-  if (!content.includes('</summary>')) return content;
-  const lines = content.split('\n');
-  const out: string[] = [];
-  let inFence = false;
-  let fenceMarker = '';
-  for (let i = 0; i < lines.length; i++) {
-    const line = lines[i];
-    out.push(line);
-    const fenceMatch = line.match(/^ {0,3}(`{3,}|~{3,})/);
-    if (fenceMatch) {
-      if (!inFence) {
-        inFence = true;
-        fenceMarker = fenceMatch[1][0];
-      } else if (fenceMatch[1][0] === fenceMarker) {
-        inFence = false;
-      }
-      continue;
-    }
-    if (
-      !inFence &&
-      /<\/summary>\s*$/i.test(line) &&
-      i + 1 < lines.length &&
-      lines[i + 1].trim() !== ''
-    ) {
-      out.push('');
-    }
-  }
-  return out.join('\n');
-}
-
-export default function Markdown({ content, className, issueLinkContext }: Props) {
+export default function Markdown({ content, className, issueLinkContext, projectContext }: Props) {
   const navigate = useNavigate();
-  const normalized = useMemo(() => normalizeDetailsBlocks(content), [content]);
+  const providedContext = useContext(MarkdownProjectScope);
+  const context =
+    projectContext ??
+    (issueLinkContext
+      ? { instanceId: issueLinkContext.instanceId, projectId: issueLinkContext.projectId }
+      : providedContext);
+  const { data: html } = useRenderedMarkdownQuery(content, context);
 
   const openIssue = async (
     ctx: IssueLinkContext,
@@ -138,69 +114,30 @@ export default function Markdown({ content, className, issueLinkContext }: Props
     }
   };
 
-  const handleIssueLinkClick = (
-    e: MouseEvent<HTMLAnchorElement>,
-    ctx: IssueLinkContext,
-    match: IssueLinkMatch,
-    href: string,
-  ) => {
+  // Issue links of the context's instance open in the app; every other
+  // link keeps the target the sanitizer gave it.
+  const handleClick = (e: MouseEvent<HTMLDivElement>) => {
+    if (!issueLinkContext) return;
     if (e.metaKey || e.ctrlKey || e.shiftKey || e.altKey || e.button !== 0) return;
+    const anchor = (e.target as HTMLElement).closest('a');
+    const href = anchor?.getAttribute('href') ?? undefined;
+    const match = matchInstanceIssue(href, issueLinkContext.instanceOrigin);
+    if (!href || !match) return;
     e.preventDefault();
-    void openIssue(ctx, match, href);
+    void openIssue(issueLinkContext, match, href);
   };
 
+  const classes = `md-body${className ? ` ${className}` : ''}`;
+  if (html === undefined) {
+    return <div className={`${classes} md-body--pending`}>{content}</div>;
+  }
   return (
-    <div className={`md-body${className ? ` ${className}` : ''}`}>
-      <ReactMarkdown
-        remarkPlugins={[remarkGfm]}
-        // GitLab Flavored Markdown allows inline HTML (<details>, <summary>, …).
-        // rehype-raw parses it; rehype-sanitize strips anything unsafe since
-        // comment bodies are untrusted remote content.
-        rehypePlugins={[rehypeRaw, rehypeSanitize]}
-        components={{
-          a: ({ href, children, ...rest }) => {
-            if (issueLinkContext && href) {
-              const match = matchInstanceIssue(href, issueLinkContext.instanceOrigin);
-              if (match) {
-                const sameProject =
-                  normalizePath(match.projectPath) ===
-                  normalizePath(issueLinkContext.projectPath);
-                // Same-project with no onOpenIssue callback: plain Link keeps
-                // native prefetch/accessibility semantics.
-                if (sameProject && !issueLinkContext.onOpenIssue) {
-                  return (
-                    <Link
-                      to={`/issues/${issueLinkContext.instanceId}/${issueLinkContext.projectId}/${match.iid}`}
-                    >
-                      {children}
-                    </Link>
-                  );
-                }
-                return (
-                  <a
-                    href={href}
-                    target="_blank"
-                    rel="noopener noreferrer"
-                    onClick={(e) =>
-                      handleIssueLinkClick(e, issueLinkContext, match, href)
-                    }
-                    {...rest}
-                  >
-                    {children}
-                  </a>
-                );
-              }
-            }
-            return (
-              <a href={href} target="_blank" rel="noopener noreferrer" {...rest}>
-                {children}
-              </a>
-            );
-          },
-        }}
-      >
-        {normalized}
-      </ReactMarkdown>
-    </div>
+    <div
+      className={classes}
+      onClick={handleClick}
+      // Sanitized by the backend with an allow-list; bodies are untrusted
+      // remote content and never reach the DOM unsanitized.
+      dangerouslySetInnerHTML={{ __html: html }}
+    />
   );
 }
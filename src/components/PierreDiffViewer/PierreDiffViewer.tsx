@@ -6,6 +6,7 @@ import { TrashIcon } from '../icons';
 import Markdown from '../Markdown';
 import UserAvatar from '../UserAvatar/UserAvatar';
 import { useCopyToast } from '../../hooks/useCopyToast';
+import type { BlameRange } from '../../types';
 import '../ActivityDrawer/ActivityFeed.css';
 import './PierreDiffViewer.css';
 
@@ -62,6 +63,10 @@ export interface PierreDiffViewerProps {
   onReply?: (discussionId: string, parentId: number, body: string) => Promise<void>;
   /** Called when the user resolves/unresolves a discussion thread */
   onResolve?: (discussionId: string, resolved: boolean) => Promise<void>;
+  /** Blame of the new version; shown in the header for the selected line */
+  blame?: BlameRange[];
+  /** Open the file in the external editor, at the selected new-side line if any */
+  onOpenInEditor?: (line?: number) => void;
 }
 
 /** Map LineComment[] to Pierre DiffLineAnnotation<LineComment>[]. */
@@ -329,33 +334,77 @@ export function PierreDiffViewer({
   onDeleteComment,
   onReply,
   onResolve,
+  blame,
+  onOpenInEditor,
 }: PierreDiffViewerProps) {
   const [selectedLines, setSelectedLines] = useState<SelectedLineRange | null>(null);
   const [copied, copyToClipboard] = useCopyToast(1200);
 
+  const selectedBlame = useMemo(() => {
+    if (!blame || !selectedLines || selectedLines.side === 'deletions') return null;
+    const line = Math.min(selectedLines.start, selectedLines.end);
+    const range = blame.find((r) => line >= r.startLine && line < r.startLine + r.lineCount);
+    return range ? { line, range } : null;
+  }, [blame, selectedLines]);
+
+  const selectedNewLine =
+    selectedLines && selectedLines.side !== 'deletions'
+      ? Math.min(selectedLines.start, selectedLines.end)
+      : undefined;
+
   const renderHeaderMetadata = useCallback(
     (fileDiff: FileDiffMetadata) => (
-      <button
-        className={`diff-header-copy-btn ${copied ? 'diff-header-copy-btn--copied' : ''}`}
-        onClick={(e) => {
-          e.stopPropagation();
-          copyToClipboard(fileDiff.name);
-        }}
-        title={copied ? 'Copied!' : 'Copy file path'}
-      >
-        {copied ? (
-          <svg width="14" height="14" viewBox="0 0 16 16" fill="none" stroke="currentColor" strokeWidth="2" strokeLinecap="round" strokeLinejoin="round">
-            <polyline points="3.5 8.5 6.5 11.5 12.5 4.5" />
-          </svg>
-        ) : (
-          <svg width="14" height="14" viewBox="0 0 16 16" fill="none" stroke="currentColor" strokeWidth="1.5" strokeLinecap="round" strokeLinejoin="round">
-            <rect x="5.5" y="5.5" width="7" height="8" rx="1" />
-            <path d="M10.5 5.5V3.5a1 1 0 0 0-1-1h-6a1 1 0 0 0-1 1v6a1 1 0 0 0 1 1h2" />
-          </svg>
+      <>
+        {selectedBlame && (
+          <span
+            className="diff-header-blame"
+            title={`${selectedBlame.range.commitSha}\n${selectedBlame.range.authorName} <${selectedBlame.range.authorEmail}>\n${selectedBlame.range.summary}`}
+          >
+            <span className="diff-header-blame__line">L{selectedBlame.line}</span>
+            <span className="diff-header-blame__author">{selectedBlame.range.authorName}</span>
+            <span className="diff-header-blame__time">{formatDate(selectedBlame.range.authoredAt)}</span>
+            <code className="diff-header-blame__sha">{selectedBlame.range.commitSha.slice(0, 8)}</code>
+            <span className="diff-header-blame__summary">{selectedBlame.range.summary}</span>
+          </span>
+        )}
+        <button
+          className={`diff-header-copy-btn ${copied ? 'diff-header-copy-btn--copied' : ''}`}
+          onClick={(e) => {
+            e.stopPropagation();
+            copyToClipboard(fileDiff.name);
+          }}
+          title={copied ? 'Copied!' : 'Copy file path'}
+        >
+          {copied ? (
+            <svg width="14" height="14" viewBox="0 0 16 16" fill="none" stroke="currentColor" strokeWidth="2" strokeLinecap="round" strokeLinejoin="round">
+              <polyline points="3.5 8.5 6.5 11.5 12.5 4.5" />
+            </svg>
+          ) : (
+            <svg width="14" height="14" viewBox="0 0 16 16" fill="none" stroke="currentColor" strokeWidth="1.5" strokeLinecap="round" strokeLinejoin="round">
+              <rect x="5.5" y="5.5" width="7" height="8" rx="1" />
+              <path d="M10.5 5.5V3.5a1 1 0 0 0-1-1h-6a1 1 0 0 0-1 1v6a1 1 0 0 0 1 1h2" />
+            </svg>
+          )}
+        </button>
+        {onOpenInEditor && (
+          <button
+            className="diff-header-copy-btn"
+            onClick={(e) => {
+              e.stopPropagation();
+              onOpenInEditor(selectedNewLine);
+            }}
+            title={selectedNewLine ? `Open in editor at line ${selectedNewLine}` : 'Open in editor'}
+          >
+            <svg width="14" height="14" viewBox="0 0 16 16" fill="none" stroke="currentColor" strokeWidth="1.5" strokeLinecap="round" strokeLinejoin="round">
+              <path d="M9.5 2.5h4v4" />
+              <path d="M13.5 2.5 7.5 8.5" />
+              <path d="M11.5 9.5v3a1 1 0 0 1-1 1h-7a1 1 0 0 1-1-1v-7a1 1 0 0 1 1-1h3" />
+            </svg>
+          </button>
         )}
-      </button>
+      </>
     ),
-    [copied, copyToClipboard],
+    [copied, copyToClipboard, selectedBlame, onOpenInEditor, selectedNewLine],
   );
 
   const handleLineSelected = useCallback(
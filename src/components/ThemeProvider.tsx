@@ -6,14 +6,16 @@
  * to document.documentElement.style.
  *
  * Loads the persisted theme ID from the Rust settings store on startup.
+ * A non-"app" highlight theme injects a stylesheet from the backend that
+ * overrides the hl-* token colors; "app" leaves them on the --syntax-* vars.
  */
 
-import { createContext, useEffect, useReducer, useCallback, type ReactNode } from 'react';
+import { createContext, useEffect, useReducer, useCallback, useState, type ReactNode } from 'react';
 import type { ThemeDefinition } from '../themes/types';
 import { kanagawaWave } from '../themes/kanagawa-wave';
 import { kanagawaLight } from '../themes/kanagawa-light';
 import { loved } from '../themes/loved';
-import { invoke, updateTheme as persistTheme, updateUiFont as persistUiFont, updateDisplayFont as persistDisplayFont, updateDiffsFont as persistDiffsFont, updateCustomThemeColors as persistCustomColors, type CustomThemeColors } from '../services/tauri';
+import { invoke, updateTheme as persistTheme, updateUiFont as persistUiFont, updateDisplayFont as persistDisplayFont, updateDiffsFont as persistDiffsFont, updateCustomThemeColors as persistCustomColors, listHighlightThemes, setHighlightTheme as persistHighlightTheme, type CustomThemeColors, type HighlightTheme } from '../services/tauri';
 import { deriveTheme } from '../themes/deriveTheme';
 import type { Theme } from '../types';
 
@@ -58,6 +60,12 @@ export interface ThemeContextValue {
   saveCustomTheme: (colors: CustomThemeColors) => void;
   /** Delete the saved custom theme and revert to default preset. */
   deleteCustomTheme: () => void;
+  /** Current syntax highlight theme ID ("app" follows the active theme). */
+  highlightTheme: string;
+  /** Available syntax highlight themes. */
+  highlightThemes: HighlightTheme[];
+  /** Switch syntax highlight theme and persist the choice. */
+  setHighlightTheme: (id: string) => void;
 }
 
 export const ThemeContext = createContext<ThemeContextValue | null>(null);
@@ -266,11 +274,17 @@ export function ThemeProvider({ children }: ThemeProviderProps) {
   });
 
   const { theme, uiFont, displayFont, diffsFont, customColors } = state;
+  const [highlightTheme, setHighlightThemeId] = useState('app');
+  const [highlightThemes, setHighlightThemes] = useState<HighlightTheme[]>([]);
+  const [highlightCss, setHighlightCss] = useState('');
 
   // Load persisted theme, font, and custom colors on mount
   useEffect(() => {
-    invoke<{ theme?: string; uiFont?: string; displayFont?: string; diffsFont?: string; customThemeColors?: CustomThemeColors | null }>('get_settings')
-      .then((settings) => {
+    Promise.all([
+      invoke<{ theme?: string; uiFont?: string; displayFont?: string; diffsFont?: string; customThemeColors?: CustomThemeColors | null; highlightTheme?: string }>('get_settings'),
+      listHighlightThemes().catch(() => [] as HighlightTheme[]),
+    ])
+      .then(([settings, themes]) => {
         const id = settings.theme || 'kanagawa-wave';
         const savedColors = settings.customThemeColors ?? null;
         const font = settings.uiFont || 'Noto Sans JP';
@@ -287,6 +301,11 @@ export function ThemeProvider({ children }: ThemeProviderProps) {
 
         dispatch({ type: 'INIT', theme: resolvedTheme, uiFont: font, displayFont: dFont, diffsFont: diffFont, customColors: savedColors });
 
+        const hlId = settings.highlightTheme || 'app';
+        setHighlightThemes(themes);
+        setHighlightThemeId(hlId);
+        setHighlightCss(themes.find(t => t.id === hlId)?.css ?? '');
+
         // Eager-load Google fonts for presets (system fonts need no loading)
         const fontDef = UI_FONTS.find(f => f.id === font);
         if (fontDef?.googleFont) loadGoogleFont(fontDef.googleFont);
@@ -354,6 +373,13 @@ export function ThemeProvider({ children }: ThemeProviderProps) {
     persistCustomColors(null).catch(console.error);
   }, []);
 
+  const setHighlightTheme = useCallback((id: string) => {
+    setHighlightThemeId(id);
+    persistHighlightTheme(id)
+      .then(setHighlightCss)
+      .catch(console.error);
+  }, []);
+
   // Apply CSS variables whenever theme changes
   useEffect(() => {
     const vars = themeToCssVars(theme);
@@ -379,8 +405,19 @@ export function ThemeProvider({ children }: ThemeProviderProps) {
     document.documentElement.style.setProperty('--diffs-font-family', resolveFontFamily(diffsFont));
   }, [diffsFont]);
 
+  // Inject the highlight theme stylesheet (empty for "app")
+  useEffect(() => {
+    let style = document.getElementById('highlight-theme') as HTMLStyleElement | null;
+    if (!style) {
+      style = document.createElement('style');
+      style.id = 'highlight-theme';
+      document.head.appendChild(style);
+    }
+    style.textContent = highlightCss;
+  }, [highlightCss]);
+
   return (
-    <ThemeContext value={{ theme, setTheme, setThemeById, uiFont, setUiFont, displayFont, setDisplayFont, diffsFont, setDiffsFont, customColors, previewCustomTheme, saveCustomTheme, deleteCustomTheme }}>
+    <ThemeContext value={{ theme, setTheme, setThemeById, uiFont, setUiFont, displayFont, setDisplayFont, diffsFont, setDiffsFont, customColors, previewCustomTheme, saveCustomTheme, deleteCustomTheme, highlightTheme, highlightThemes, setHighlightTheme }}>
       {children}
     </ThemeContext>
   );
@@ -16,12 +16,22 @@ interface UserAvatarProps {
   username: string;
   size?: number;
   className?: string;
+  /** Avatar the caller already has (e.g. from a batch query); skips the lookup. */
+  dataUri?: string | null;
 }
 
-export default function UserAvatar({ instanceId, username, size = 20, className = '' }: UserAvatarProps) {
+export default function UserAvatar({
+  instanceId,
+  username,
+  size = 20,
+  className = '',
+  dataUri: knownDataUri,
+}: UserAvatarProps) {
   const cacheKey = `${instanceId}:${username}`;
+  if (knownDataUri) avatarCache.set(cacheKey, knownDataUri);
   const cached = avatarCache.get(cacheKey);
-  const [dataUri, setDataUri] = useState<string | undefined>(cached);
+  const [fetchedDataUri, setDataUri] = useState<string | undefined>(cached);
+  const dataUri = knownDataUri || fetchedDataUri;
 
   useEffect(() => {
     // If we already have a definitive answer (loaded or known-empty), skip IPC
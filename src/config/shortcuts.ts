@@ -199,6 +199,13 @@ export const defaultShortcuts: ShortcutDefinition[] = [
     category: 'diff',
     context: 'diff-viewer',
   },
+  {
+    id: 'toggle-accessible-diff',
+    description: 'Toggle screen reader diff view',
+    defaultKey: 'Shift+X',
+    category: 'diff',
+    context: 'diff-viewer',
+  },
   {
     id: 'next-change',
     description: 'Next change in file',
@@ -0,0 +1,11 @@
+import { useQuery } from '@tanstack/react-query';
+import { queryKeys } from '../../lib/queryKeys';
+import { getAccessibleDiff } from '../../services/tauri';
+
+export function useAccessibleDiffQuery(mrId: number, filePath: string | null, enabled = true) {
+  return useQuery({
+    queryKey: queryKeys.mrAccessibleDiff(mrId, filePath ?? ''),
+    queryFn: () => getAccessibleDiff(mrId, filePath!),
+    enabled: enabled && mrId > 0 && !!filePath,
+  });
+}
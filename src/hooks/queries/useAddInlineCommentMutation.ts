@@ -1,13 +1,19 @@
 import { useMutation, useQueryClient } from '@tanstack/react-query';
 import { queryKeys } from '../../lib/queryKeys';
 import { addComment } from '../../services/tauri';
+import { describeHold } from '../useActivityData';
 import type { AddCommentRequest } from '../../types';
 
 export function useAddInlineCommentMutation(mrId: number) {
   const queryClient = useQueryClient();
 
   return useMutation({
-    mutationFn: (request: AddCommentRequest) => addComment(request),
+    mutationFn: async (request: AddCommentRequest) => {
+      const result = await addComment(request);
+      // An `/approve` in the body was held back; nothing was posted.
+      if (!result.comment) throw new Error(describeHold(result));
+      return result.comment;
+    },
     onSuccess: (_data, variables) => {
       if (variables.filePath) {
         queryClient.invalidateQueries({
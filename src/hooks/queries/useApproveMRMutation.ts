@@ -8,13 +8,15 @@ export function useApproveMRMutation(mrId: number) {
   const invalidate = () => {
     queryClient.invalidateQueries({ queryKey: queryKeys.mr(mrId) });
     queryClient.invalidateQueries({ queryKey: queryKeys.mrReviewers(mrId) });
+    queryClient.invalidateQueries({ queryKey: queryKeys.mrParticipants(mrId) });
     // Invalidate all MR list queries so approval badges stay consistent
     queryClient.invalidateQueries({ queryKey: ['mrList'] });
     queryClient.invalidateQueries({ queryKey: ['myMRList'] });
   };
 
   const approve = useMutation({
-    mutationFn: () => approveMR(mrId),
+    /** `confirm` approves even when review blockers exist. */
+    mutationFn: (confirm: boolean = false) => approveMR(mrId, confirm),
     onSuccess: invalidate,
   });
 
@@ -1,11 +1,24 @@
 import { useQuery } from '@tanstack/react-query';
 import { queryKeys } from '../../lib/queryKeys';
 import { getComments } from '../../services/tauri';
+import type { CommentSort } from '../../types';
 
-export function useCommentsQuery(mrId: number) {
+/**
+ * Comments of an MR. With a `sort` (and `contextLines` for code snippets)
+ * the result is cached under its own key below `queryKeys.mrComments(mrId)`,
+ * so invalidating the MR's comments refetches every variant.
+ */
+export function useCommentsQuery(mrId: number, sort?: CommentSort, contextLines?: number) {
   return useQuery({
-    queryKey: queryKeys.mrComments(mrId),
-    queryFn: () => getComments(mrId),
+    queryKey: commentsQueryKey(mrId, sort, contextLines),
+    queryFn: () => getComments(mrId, sort, contextLines),
     enabled: !!mrId,
   });
 }
+
+export function commentsQueryKey(mrId: number, sort?: CommentSort, contextLines?: number) {
+  if (!sort) return queryKeys.mrComments(mrId);
+  return contextLines
+    ? [...queryKeys.mrComments(mrId), sort, contextLines]
+    : [...queryKeys.mrComments(mrId), sort];
+}
@@ -0,0 +1,14 @@
+import { useQuery } from '@tanstack/react-query';
+import { queryKeys } from '../../lib/queryKeys';
+import { lintMrDescription } from '../../services/tauri';
+
+/** Lint of an MR description; refetched when the description changes. */
+export function useDescriptionLintQuery(mrId: number, description: string | null) {
+  return useQuery({
+    queryKey: [...queryKeys.descriptionLint(mrId), description ?? ''],
+    queryFn: () => lintMrDescription(mrId),
+    enabled: mrId > 0,
+    staleTime: 5 * 60_000,
+    retry: false,
+  });
+}
@@ -0,0 +1,12 @@
+import { useQuery } from '@tanstack/react-query';
+import { queryKeys } from '../../lib/queryKeys';
+import { getFileAssignments } from '../../services/tauri';
+
+/** Which reviewer each file of an MR is assigned to. */
+export function useFileAssignmentsQuery(mrId: number) {
+  return useQuery({
+    queryKey: queryKeys.fileAssignments(mrId),
+    queryFn: () => getFileAssignments(mrId),
+    enabled: mrId > 0,
+  });
+}
@@ -0,0 +1,13 @@
+import { useQuery } from '@tanstack/react-query';
+import { queryKeys } from '../../lib/queryKeys';
+import { getFileBlame } from '../../services/tauri';
+
+/** Blame of a file at the MR's head SHA; it can't change, so it never goes stale. */
+export function useFileBlameQuery(mrId: number, filePath: string | null, headSha: string, enabled = true) {
+  return useQuery({
+    queryKey: queryKeys.fileBlame(mrId, filePath ?? '', headSha),
+    queryFn: () => getFileBlame(mrId, filePath!),
+    enabled: enabled && mrId > 0 && !!filePath && !!headSha,
+    staleTime: Infinity,
+  });
+}
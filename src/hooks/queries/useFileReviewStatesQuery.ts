@@ -0,0 +1,40 @@
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query';
+import { queryKeys } from '../../lib/queryKeys';
+import { getFileReviewStates, markFileViewed } from '../../services/tauri';
+import type { FileReviewState } from '../../types';
+
+/** Files of the MR marked viewed at its current head. */
+export function useFileReviewStatesQuery(mrId: number) {
+  return useQuery({
+    queryKey: queryKeys.fileReviewStates(mrId),
+    queryFn: () => getFileReviewStates(mrId),
+    enabled: mrId > 0,
+  });
+}
+
+/**
+ * Mark a file viewed or unviewed. The cache is updated right away so the
+ * file list doesn't wait for the round trip.
+ */
+export function useMarkFileViewedMutation(mrId: number) {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ filePath, viewed }: { filePath: string; viewed: boolean }) =>
+      markFileViewed(mrId, filePath, viewed),
+    onMutate: ({ filePath, viewed }) => {
+      queryClient.setQueryData<FileReviewState[]>(
+        queryKeys.fileReviewStates(mrId),
+        (states = []) => {
+          const rest = states.filter((s) => s.filePath !== filePath);
+          if (!viewed) return rest;
+          const viewedAt = Math.floor(Date.now() / 1000);
+          return [...rest, { filePath, headSha: states[0]?.headSha ?? '', viewedAt }];
+        },
+      );
+    },
+    onSettled: () => {
+      queryClient.invalidateQueries({ queryKey: queryKeys.fileReviewStates(mrId) });
+    },
+  });
+}
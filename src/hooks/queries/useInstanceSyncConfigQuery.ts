@@ -0,0 +1,11 @@
+import { useQuery } from '@tanstack/react-query';
+import { queryKeys } from '../../lib/queryKeys';
+import { getSyncConfig } from '../../services/tauri';
+
+/** Which MRs an instance syncs, and its own interval and MR cap. */
+export function useInstanceSyncConfigQuery(instanceId: number) {
+  return useQuery({
+    queryKey: queryKeys.instanceSyncConfig(instanceId),
+    queryFn: () => getSyncConfig(instanceId),
+  });
+}
@@ -0,0 +1,10 @@
+import { useQuery } from '@tanstack/react-query';
+import { queryKeys } from '../../lib/queryKeys';
+import { getListViewState } from '../../services/tauri';
+
+export function useListViewStateQuery() {
+  return useQuery({
+    queryKey: queryKeys.listViewState(),
+    queryFn: getListViewState,
+  });
+}
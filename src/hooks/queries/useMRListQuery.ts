@@ -2,10 +2,10 @@ import { useQuery } from '@tanstack/react-query';
 import { queryKeys } from '../../lib/queryKeys';
 import { listMergeRequests } from '../../services/gitlab';
 
-export function useMRListQuery(instanceId: number | undefined) {
+export function useMRListQuery(instanceId: number | undefined, includeBots = false) {
   return useQuery({
-    queryKey: queryKeys.mrList(String(instanceId ?? '')),
-    queryFn: () => listMergeRequests(instanceId!),
+    queryKey: [...queryKeys.mrList(String(instanceId ?? '')), { includeBots }],
+    queryFn: () => listMergeRequests(instanceId!, { includeBots }),
     enabled: !!instanceId,
   });
 }
@@ -0,0 +1,11 @@
+import { useQuery } from '@tanstack/react-query';
+import { queryKeys } from '../../lib/queryKeys';
+import { getMrParticipants } from '../../services/tauri';
+
+export function useMRParticipantsQuery(mrId: number) {
+  return useQuery({
+    queryKey: queryKeys.mrParticipants(mrId),
+    queryFn: () => getMrParticipants(mrId),
+    enabled: mrId > 0,
+  });
+}
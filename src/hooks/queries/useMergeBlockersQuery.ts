@@ -0,0 +1,12 @@
+import { useQuery } from '@tanstack/react-query';
+import { queryKeys } from '../../lib/queryKeys';
+import { getMergeBlockers } from '../../services/tauri';
+
+/** Cached merge blockers of an MR; refreshing from GitLab is up to the caller. */
+export function useMergeBlockersQuery(mrId: number, enabled: boolean) {
+  return useQuery({
+    queryKey: queryKeys.mergeBlockers(mrId),
+    queryFn: () => getMergeBlockers(mrId),
+    enabled: enabled && mrId > 0,
+  });
+}
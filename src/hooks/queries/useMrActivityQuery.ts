@@ -0,0 +1,12 @@
+import { useQuery } from '@tanstack/react-query';
+import { queryKeys } from '../../lib/queryKeys';
+import { getMrActivity } from '../../services/tauri';
+
+/** The MR's activity timeline, oldest first; refreshed when a sync changes its discussions. */
+export function useMrActivityQuery(mrId: number, enabled = true) {
+  return useQuery({
+    queryKey: queryKeys.mrActivity(mrId),
+    queryFn: () => getMrActivity(mrId),
+    enabled: enabled && mrId > 0,
+  });
+}
@@ -0,0 +1,22 @@
+import { useQuery } from '@tanstack/react-query';
+import { queryKeys } from '../../lib/queryKeys';
+import { getCommitDiff, getMrCommits } from '../../services/tauri';
+
+/** The MR's commits, oldest first; refreshed when a sync changes its diff. */
+export function useMrCommitsQuery(mrId: number, enabled = true) {
+  return useQuery({
+    queryKey: queryKeys.mrCommits(mrId),
+    queryFn: () => getMrCommits(mrId),
+    enabled: enabled && mrId > 0,
+  });
+}
+
+/** Files one commit changed; a commit can't change, so it never goes stale. */
+export function useCommitDiffQuery(mrId: number, sha: string | null) {
+  return useQuery({
+    queryKey: queryKeys.commitDiff(mrId, sha ?? ''),
+    queryFn: () => getCommitDiff(mrId, sha!),
+    enabled: mrId > 0 && !!sha,
+    staleTime: Infinity,
+  });
+}
@@ -0,0 +1,12 @@
+import { useQuery } from '@tanstack/react-query';
+import { queryKeys } from '../../lib/queryKeys';
+import { compareMrs } from '../../services/tauri';
+
+export function useMrComparisonQuery(mrIdA: number, mrIdB: number | null) {
+  return useQuery({
+    queryKey: queryKeys.mrComparison(mrIdA, mrIdB ?? 0),
+    queryFn: () => compareMrs(mrIdA, mrIdB!),
+    enabled: mrIdA > 0 && !!mrIdB,
+    retry: false,
+  });
+}
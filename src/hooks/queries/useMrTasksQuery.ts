@@ -0,0 +1,11 @@
+import { useQuery } from '@tanstack/react-query';
+import { queryKeys } from '../../lib/queryKeys';
+import { getMrTasks } from '../../services/tauri';
+
+export function useMrTasksQuery(mrId: number) {
+  return useQuery({
+    queryKey: queryKeys.mrTasks(mrId),
+    queryFn: () => getMrTasks(mrId),
+    enabled: mrId > 0,
+  });
+}
@@ -0,0 +1,11 @@
+import { useQuery } from '@tanstack/react-query';
+import { queryKeys } from '../../lib/queryKeys';
+import { listMutedProjects } from '../../services/tauri';
+
+export function useMutedProjectsQuery() {
+  return useQuery({
+    queryKey: queryKeys.mutedProjects(),
+    queryFn: listMutedProjects,
+    staleTime: 60_000,
+  });
+}
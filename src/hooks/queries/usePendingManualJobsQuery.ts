@@ -0,0 +1,12 @@
+import { useQuery } from '@tanstack/react-query';
+import { queryKeys } from '../../lib/queryKeys';
+import { getPendingManualJobs } from '../../services/tauri';
+
+export function usePendingManualJobsQuery() {
+  return useQuery({
+    queryKey: queryKeys.pendingManualJobs(),
+    queryFn: getPendingManualJobs,
+    refetchInterval: 120_000,
+    refetchIntervalInBackground: false,
+  });
+}
@@ -0,0 +1,12 @@
+import { useQuery } from '@tanstack/react-query';
+import { queryKeys } from '../../lib/queryKeys';
+import { getPendingReview } from '../../services/tauri';
+
+/** The MR's pending review; `null` when no review has been started. */
+export function usePendingReviewQuery(mrId: number) {
+  return useQuery({
+    queryKey: queryKeys.pendingReview(mrId),
+    queryFn: () => getPendingReview(mrId),
+    enabled: mrId > 0,
+  });
+}
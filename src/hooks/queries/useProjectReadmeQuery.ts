@@ -0,0 +1,17 @@
+import { useQuery } from '@tanstack/react-query';
+import { queryKeys } from '../../lib/queryKeys';
+import { getProjectReadme } from '../../services/tauri';
+
+export function useProjectReadmeQuery(
+  instanceId: number,
+  projectId: number,
+  refName: string,
+  enabled = true,
+) {
+  return useQuery({
+    queryKey: queryKeys.projectReadme(instanceId, projectId, refName),
+    queryFn: () => getProjectReadme(instanceId, projectId, refName),
+    staleTime: 10 * 60_000,
+    enabled: enabled && !!instanceId && !!projectId && !!refName,
+  });
+}
@@ -0,0 +1,13 @@
+import { useQuery } from '@tanstack/react-query';
+import { queryKeys } from '../../lib/queryKeys';
+import { renderGfm } from '../../services/tauri';
+import type { MarkdownProjectContext } from '../../types';
+
+export function useRenderedMarkdownQuery(text: string, projectContext?: MarkdownProjectContext) {
+  return useQuery({
+    queryKey: queryKeys.renderedMarkdown(text, projectContext?.instanceId, projectContext?.projectId),
+    queryFn: () => renderGfm(text, projectContext),
+    // The backend caches by content, so a result never goes stale.
+    staleTime: Infinity,
+  });
+}
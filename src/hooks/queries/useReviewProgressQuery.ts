@@ -0,0 +1,27 @@
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query';
+import { queryKeys } from '../../lib/queryKeys';
+import { getChangesSinceReview, markMrReviewed } from '../../services/tauri';
+
+/** Changes to the MR's files since it was last marked reviewed. */
+export function useChangesSinceReviewQuery(mrId: number, enabled = true) {
+  return useQuery({
+    queryKey: queryKeys.changesSinceReview(mrId),
+    queryFn: () => getChangesSinceReview(mrId),
+    enabled: enabled && mrId > 0,
+  });
+}
+
+/** Mark the MR reviewed at its current head, clearing its "new commits" flag. */
+export function useMarkReviewedMutation(mrId: number) {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: () => markMrReviewed(mrId),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: queryKeys.mr(mrId) });
+      queryClient.invalidateQueries({ queryKey: ['mrList'] });
+      queryClient.invalidateQueries({ queryKey: ['myMRList'] });
+      queryClient.invalidateQueries({ queryKey: queryKeys.changesSinceReview(mrId) });
+    },
+  });
+}
@@ -0,0 +1,18 @@
+import { useQuery } from '@tanstack/react-query';
+import { queryKeys } from '../../lib/queryKeys';
+import { isTauri } from '../../services/transport';
+import { getReviewSlaStatus } from '../../services/tauri';
+
+/**
+ * Review SLA status of the MRs awaiting my review. Refetched every minute
+ * since MRs cross thresholds as working time passes, not only on sync.
+ */
+export function useReviewSlaQuery() {
+  return useQuery({
+    queryKey: queryKeys.reviewSlaStatus(),
+    queryFn: () => getReviewSlaStatus(),
+    enabled: isTauri,
+    refetchInterval: 60_000,
+    refetchIntervalInBackground: false,
+  });
+}
@@ -0,0 +1,11 @@
+import { useQuery } from '@tanstack/react-query';
+import { queryKeys } from '../../lib/queryKeys';
+import { getSecurityFindings } from '../../services/tauri';
+
+export function useSecurityFindingsQuery(mrId: number, enabled = true) {
+  return useQuery({
+    queryKey: queryKeys.mrSecurityFindings(mrId),
+    queryFn: () => getSecurityFindings(mrId),
+    enabled: enabled && mrId > 0,
+  });
+}
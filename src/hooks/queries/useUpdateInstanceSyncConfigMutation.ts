@@ -0,0 +1,16 @@
+import { useMutation, useQueryClient } from '@tanstack/react-query';
+import { queryKeys } from '../../lib/queryKeys';
+import { updateSyncConfig } from '../../services/tauri';
+import type { InstanceSyncConfig } from '../../types';
+
+/** Save an instance's sync configuration. */
+export function useUpdateInstanceSyncConfigMutation() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (config: InstanceSyncConfig) => updateSyncConfig(config.instanceId, config),
+    onSuccess: (stored) => {
+      queryClient.setQueryData(queryKeys.instanceSyncConfig(stored.instanceId), stored);
+    },
+  });
+}
@@ -0,0 +1,26 @@
+import { useMutation, useQueryClient } from '@tanstack/react-query';
+import { queryKeys } from '../../lib/queryKeys';
+import { updateListViewState } from '../../services/tauri';
+import type { ListViewState } from '../../types';
+
+/**
+ * Persist the MR list view state. The cache is updated right away so filter
+ * toggles don't wait for the round trip.
+ */
+export function useUpdateListViewStateMutation() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (state: ListViewState) => updateListViewState(state),
+    onMutate: (state) => {
+      queryClient.setQueryData(queryKeys.listViewState(), state);
+    },
+    onSuccess: (stored) => {
+      queryClient.setQueryData(queryKeys.listViewState(), stored);
+      queryClient.invalidateQueries({ queryKey: queryKeys.settings() });
+    },
+    onError: () => {
+      queryClient.invalidateQueries({ queryKey: queryKeys.listViewState() });
+    },
+  });
+}
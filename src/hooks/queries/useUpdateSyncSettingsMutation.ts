@@ -1,11 +1,22 @@
 import { useMutation, useQueryClient } from '@tanstack/react-query';
 import { queryKeys } from '../../lib/queryKeys';
 import { invoke } from '../../services/tauri';
+import type { ReviewSlaConfig, VacationConfig } from '../../types';
 
 interface SyncConfig {
   interval_secs: number;
   max_mrs_per_sync: number;
   issue_interval_secs: number;
+  description_preview_chars?: number;
+  bot_author_patterns?: string[];
+  auto_approve_bot_mrs?: boolean;
+  gravatar_fallback?: boolean;
+  badge?: { awaiting_review: boolean; failed_actions: boolean };
+  reconcile_interval_secs?: number;
+  pipeline_poll_interval_secs?: number;
+  disabled_stages?: Record<string, string[]>;
+  vacation?: VacationConfig;
+  review_sla?: ReviewSlaConfig;
 }
 
 export function useUpdateSyncSettingsMutation() {
@@ -6,12 +6,13 @@ import {
   setDiscussionResolved,
   deleteComment as gitlabDeleteComment,
 } from '../services/gitlab';
+import { applySuggestion as tauriApplySuggestion, submitReviewBundle } from '../services/tauri';
 import { tauriListen } from '../services/transport';
-import { useCommentsQuery } from './queries/useCommentsQuery';
+import { queryKeys } from '../lib/queryKeys';
+import { commentsQueryKey, useCommentsQuery } from './queries/useCommentsQuery';
 import { useCurrentUserQuery } from './queries/useCurrentUserQuery';
 import { useMRDetailQuery } from './queries/useMRDetailQuery';
-import { queryKeys } from '../lib/queryKeys';
-import type { Comment } from '../types';
+import type { AddCommentResult, Comment, CommentSort, ReviewBlockers, UnmetCondition } from '../types';
 
 interface ActionSyncedPayload {
   action_id: number;
@@ -22,6 +23,9 @@ interface ActionSyncedPayload {
   local_reference_id: number | null;
 }
 
+/** Lines of code shown either side of an inline thread's line. */
+const SNIPPET_CONTEXT_LINES = 2;
+
 // Module-level monotonically decrementing counter for optimistic comment IDs.
 // Guarantees uniqueness even when multiple comments are created in the same millisecond.
 let _nextOptimisticId = -1;
@@ -34,15 +38,65 @@ export interface ActivityData {
   currentUser: string | null;
   loading: boolean;
   error: string | null;
+  /**
+   * Post a general comment. Throws when an `/approve` in it is held back by
+   * the approval gate or (unless the user confirms) review blockers.
+   */
   addComment: (body: string) => Promise<void>;
+  /**
+   * Post a general comment and approve the MR after it, as one ordered batch.
+   * Review blockers are confirmed with the user first. Throws when the
+   * approval gate (or a declined confirmation) blocks the approval; nothing
+   * is posted then.
+   */
+  submitReview: (body: string) => Promise<void>;
   replyToComment: (discussionId: string, parentId: number, body: string) => Promise<void>;
   resolveDiscussion: (discussionId: string, resolved: boolean) => Promise<void>;
   deleteComment: (commentId: number) => Promise<void>;
+  applySuggestion: (suggestionId: number) => Promise<void>;
+}
+
+export function describeUnmet(unmet: UnmetCondition[]): string {
+  const reasons = unmet.map((condition) => {
+    switch (condition.type) {
+      case 'unviewedFiles':
+        return `${condition.files.length} file(s) not viewed`;
+      case 'unresolvedOwnThreads':
+        return `${condition.discussionIds.length} of your thread(s) unresolved`;
+      case 'checklistIncomplete':
+        return `${condition.items.length} checklist item(s) open`;
+    }
+  });
+  return `Approval gate not met: ${reasons.join(', ')}`;
+}
+
+export function describeBlockers(blockers: ReviewBlockers): string {
+  const reasons: string[] = [];
+  if (blockers.unresolvedDiscussions > 0) {
+    const own = blockers.ownUnresolvedThreads > 0 ? ` (${blockers.ownUnresolvedThreads} yours)` : '';
+    reasons.push(`${blockers.unresolvedDiscussions} unresolved thread(s)${own}`);
+  }
+  if (blockers.pipelineFailed) reasons.push(`pipeline ${blockers.pipelineStatus ?? 'failed'}`);
+  if (blockers.hasConflicts) reasons.push('merge conflicts');
+  return reasons.join(', ');
 }
 
-export function useActivityData(mrId: number): ActivityData {
+/** Why an approval was held back: review blockers, or the unmet gate. */
+export function describeHold(hold: { unmet: UnmetCondition[]; blockers: ReviewBlockers | null }): string {
+  return hold.blockers ? `Review blockers: ${describeBlockers(hold.blockers)}` : describeUnmet(hold.unmet);
+}
+
+/**
+ * Comment threads of an MR in `sort` order (computed by the backend), with
+ * optimistic comment actions.
+ */
+export function useActivityData(mrId: number, sort: CommentSort): ActivityData {
   const queryClient = useQueryClient();
-  const commentsQuery = useCommentsQuery(mrId);
+  const commentsQuery = useCommentsQuery(mrId, sort, SNIPPET_CONTEXT_LINES);
+  const commentsKey = useMemo(
+    () => commentsQueryKey(mrId, sort, SNIPPET_CONTEXT_LINES),
+    [mrId, sort],
+  );
   const mrQuery = useMRDetailQuery(mrId);
   const currentUserQuery = useCurrentUserQuery(mrQuery.data?.instanceId ?? 0);
 
@@ -58,7 +112,7 @@ export function useActivityData(mrId: number): ActivityData {
       if (action_type !== 'comment' && action_type !== 'reply') return;
 
       if (!success && local_reference_id !== null) {
-        queryClient.setQueryData<Comment[]>(queryKeys.mrComments(mrId), (prev) => {
+        queryClient.setQueryData<Comment[]>(commentsKey, (prev) => {
           if (!prev) return prev;
           return prev.map(c =>
             c.id === local_reference_id ? { ...c, syncStatus: 'failed' as const } : c,
@@ -68,7 +122,7 @@ export function useActivityData(mrId: number): ActivityData {
     }).then(fn => { unlisten = fn; });
 
     return () => { unlisten?.(); };
-  }, [mrId, queryClient]);
+  }, [mrId, queryClient, commentsKey]);
 
   const threads = useMemo(() => {
     const threadMap = new Map<string, Comment[]>();
@@ -78,12 +132,8 @@ export function useActivityData(mrId: number): ActivityData {
       if (!threadMap.has(key)) threadMap.set(key, []);
       threadMap.get(key)!.push(c);
     }
-    return Array.from(threadMap.values()).sort((a, b) => {
-      const aResolved = a.some(c => c.resolved);
-      const bResolved = b.some(c => c.resolved);
-      if (aResolved !== bResolved) return aResolved ? 1 : -1;
-      return (a[0]?.createdAt ?? 0) - (b[0]?.createdAt ?? 0);
-    });
+    // Threads keep the order of their first note in the sorted list.
+    return Array.from(threadMap.values());
   }, [comments]);
 
   const systemEvents = useMemo(
@@ -116,23 +166,61 @@ export function useActivityData(mrId: number): ActivityData {
         isLocal: true,
         syncStatus: 'pending',
       };
-      queryClient.setQueryData<Comment[]>(queryKeys.mrComments(mrId), (prev) => [
+      queryClient.setQueryData<Comment[]>(commentsKey, (prev) => [
         ...(prev ?? []),
         optimistic,
       ]);
 
+      let result: AddCommentResult;
       try {
-        const created = await addGeneralComment(mrId, body);
-        queryClient.setQueryData<Comment[]>(queryKeys.mrComments(mrId), (prev) =>
-          (prev ?? []).map(c => (c.id === optimistic.id ? created : c)),
-        );
+        result = await addGeneralComment(mrId, body);
+        if (result.blockers && confirm(`${describeBlockers(result.blockers)}. Approve anyway?`)) {
+          result = await addGeneralComment(mrId, body, true);
+        }
       } catch {
-        queryClient.setQueryData<Comment[]>(queryKeys.mrComments(mrId), (prev) =>
+        queryClient.setQueryData<Comment[]>(commentsKey, (prev) =>
           (prev ?? []).filter(c => c.id !== optimistic.id),
         );
+        return;
       }
+      const created = result.comment;
+      if (!created) {
+        // An `/approve` in the body was held back; nothing was posted.
+        queryClient.setQueryData<Comment[]>(commentsKey, (prev) =>
+          (prev ?? []).filter(c => c.id !== optimistic.id),
+        );
+        throw new Error(describeHold(result));
+      }
+      queryClient.setQueryData<Comment[]>(commentsKey, (prev) =>
+        (prev ?? []).map(c => (c.id === optimistic.id ? created : c)),
+      );
     },
-    [mrId, currentUser, queryClient],
+    [mrId, currentUser, queryClient, commentsKey],
+  );
+
+  const submitReview = useCallback(
+    async (body: string) => {
+      const request = { mrId, comments: [{ body }], approve: true };
+      let result = await submitReviewBundle(request);
+      if (result.blockers) {
+        const reasons = describeBlockers(result.blockers);
+        if (!confirm(`${reasons}. Approve anyway?`)) {
+          throw new Error(`Review blockers: ${reasons}`);
+        }
+        result = await submitReviewBundle({ ...request, confirm: true });
+      }
+      if (!result.submitted) {
+        throw new Error(describeUnmet(result.unmet));
+      }
+      queryClient.setQueryData<Comment[]>(commentsKey, (prev) => [
+        ...(prev ?? []),
+        ...result.comments,
+      ]);
+      queryClient.invalidateQueries({ queryKey: queryKeys.mr(mrId) });
+      queryClient.invalidateQueries({ queryKey: ['mrList'] });
+      queryClient.invalidateQueries({ queryKey: ['myMRList'] });
+    },
+    [mrId, queryClient, commentsKey],
   );
 
   const replyToComment = useCallback(
@@ -154,48 +242,48 @@ export function useActivityData(mrId: number): ActivityData {
         isLocal: true,
         syncStatus: 'pending',
       };
-      queryClient.setQueryData<Comment[]>(queryKeys.mrComments(mrId), (prev) => [
+      queryClient.setQueryData<Comment[]>(commentsKey, (prev) => [
         ...(prev ?? []),
         optimistic,
       ]);
 
       try {
         const created = await replyToDiscussion(mrId, discussionId, parentId, body);
-        queryClient.setQueryData<Comment[]>(queryKeys.mrComments(mrId), (prev) =>
+        queryClient.setQueryData<Comment[]>(commentsKey, (prev) =>
           (prev ?? []).map(c => (c.id === optimistic.id ? created : c)),
         );
       } catch {
-        queryClient.setQueryData<Comment[]>(queryKeys.mrComments(mrId), (prev) =>
+        queryClient.setQueryData<Comment[]>(commentsKey, (prev) =>
           (prev ?? []).filter(c => c.id !== optimistic.id),
         );
       }
     },
-    [mrId, currentUser, queryClient],
+    [mrId, currentUser, queryClient, commentsKey],
   );
 
   const resolveDiscussion = useCallback(
     async (discussionId: string, resolved: boolean) => {
-      queryClient.setQueryData<Comment[]>(queryKeys.mrComments(mrId), (prev) =>
+      queryClient.setQueryData<Comment[]>(commentsKey, (prev) =>
         (prev ?? []).map(c => (c.discussionId === discussionId ? { ...c, resolved } : c)),
       );
 
       try {
         await setDiscussionResolved(mrId, discussionId, resolved);
       } catch {
-        queryClient.setQueryData<Comment[]>(queryKeys.mrComments(mrId), (prev) =>
+        queryClient.setQueryData<Comment[]>(commentsKey, (prev) =>
           (prev ?? []).map(c =>
             c.discussionId === discussionId ? { ...c, resolved: !resolved } : c,
           ),
         );
       }
     },
-    [mrId, queryClient],
+    [mrId, queryClient, commentsKey],
   );
 
   const deleteComment = useCallback(
     async (commentId: number) => {
       let removedComment: Comment | undefined;
-      queryClient.setQueryData<Comment[]>(queryKeys.mrComments(mrId), (prev) => {
+      queryClient.setQueryData<Comment[]>(commentsKey, (prev) => {
         if (!prev) return prev;
         removedComment = prev.find(c => c.id === commentId);
         return prev.filter(c => c.id !== commentId);
@@ -206,13 +294,22 @@ export function useActivityData(mrId: number): ActivityData {
       } catch {
         if (removedComment !== undefined) {
           const toRestore = removedComment;
-          queryClient.setQueryData<Comment[]>(queryKeys.mrComments(mrId), (prev) => [
+          queryClient.setQueryData<Comment[]>(commentsKey, (prev) => [
             ...(prev ?? []),
             toRestore,
           ]);
         }
       }
     },
+    [mrId, queryClient, commentsKey],
+  );
+
+  const applySuggestion = useCallback(
+    async (suggestionId: number) => {
+      await tauriApplySuggestion(mrId, suggestionId);
+      queryClient.invalidateQueries({ queryKey: queryKeys.mrComments(mrId) });
+      queryClient.invalidateQueries({ queryKey: queryKeys.mrFiles(mrId) });
+    },
     [mrId, queryClient],
   );
 
@@ -226,8 +323,10 @@ export function useActivityData(mrId: number): ActivityData {
       ? (commentsQuery.error instanceof Error ? commentsQuery.error.message : 'Failed to load comments')
       : null,
     addComment,
+    submitReview,
     replyToComment,
     resolveDiscussion,
     deleteComment,
+    applySuggestion,
   };
 }
@@ -1,10 +1,13 @@
 import { useCallback } from 'react';
 import { useQuery, useMutation, useQueryClient } from '@tanstack/react-query';
 import {
+  cancelMergeWhenReady,
   claimAutoMerge,
   getAutoMergeClaim,
+  mergeWhenReady,
   unclaimAutoMerge,
   type AutoMergeClaim,
+  type MergeConditions,
 } from '../services/tauri';
 
 const autoMergeClaimKey = (mrId: number) => ['autoMergeClaim', mrId] as const;
@@ -15,12 +18,17 @@ export interface UseAutoMergeResult {
   isLoading: boolean;
   setClaimed: (next: boolean) => void;
   toggle: () => void;
+  /** Queue a merge-when-ready claim, replacing any existing claim's conditions. */
+  mergeWhenReady: (conditions: MergeConditions) => void;
+  /** Error from the last merge-when-ready request, if it was rejected. */
+  mergeWhenReadyError: Error | null;
   /** True while a claim/unclaim mutation is in flight. */
   isMutating: boolean;
 }
 
 /**
- * Hook for reading and toggling the local auto-merge claim for an MR.
+ * Hook for reading and toggling the local auto-merge claim for an MR,
+ * including "merge when ready" claims that carry their own conditions.
  *
  * Claims persist in SQLite and are processed by the background sync engine
  * — this hook just exposes the row to the UI and provides toggle mutations.
@@ -45,29 +53,37 @@ export function useAutoMerge(mrId: number): UseAutoMergeResult {
     },
   });
 
+  const whenReadyMutation = useMutation({
+    mutationFn: (conditions: MergeConditions) => mergeWhenReady(mrId, conditions),
+    onSuccess: (claim) => {
+      queryClient.setQueryData(autoMergeClaimKey(mrId), claim);
+    },
+  });
+
   const unclaimMutation = useMutation({
-    mutationFn: () => unclaimAutoMerge(mrId),
+    mutationFn: (claim: AutoMergeClaim | null) =>
+      claim?.conditions ? cancelMergeWhenReady(mrId) : unclaimAutoMerge(mrId),
     onSuccess: () => {
       queryClient.setQueryData(autoMergeClaimKey(mrId), null);
     },
   });
 
+  const claim = query.data ?? null;
+
   const setClaimed = useCallback(
     (next: boolean) => {
       if (next) {
         claimMutation.mutate();
       } else {
-        unclaimMutation.mutate();
+        unclaimMutation.mutate(claim);
       }
     },
-    [claimMutation, unclaimMutation],
+    [claim, claimMutation, unclaimMutation],
   );
 
-  const claim = query.data ?? null;
-
   const toggle = useCallback(() => {
     if (claim) {
-      unclaimMutation.mutate();
+      unclaimMutation.mutate(claim);
     } else {
       claimMutation.mutate();
     }
@@ -79,6 +95,8 @@ export function useAutoMerge(mrId: number): UseAutoMergeResult {
     isLoading: query.isLoading,
     setClaimed,
     toggle,
-    isMutating: claimMutation.isPending || unclaimMutation.isPending,
+    mergeWhenReady: whenReadyMutation.mutate,
+    mergeWhenReadyError: whenReadyMutation.error,
+    isMutating: claimMutation.isPending || whenReadyMutation.isPending || unclaimMutation.isPending,
   };
 }
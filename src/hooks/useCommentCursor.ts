@@ -0,0 +1,32 @@
+/**
+ * Unread comments of an MR for "jump to first unread".
+ *
+ * Reads the cursor once per MR, then records the visit, so the cursor stays
+ * relative to the previous visit for as long as the MR is open.
+ */
+
+import { useEffect, useState } from 'react';
+import { getLatestCommentCursor, markMrViewed } from '../services/tauri';
+import type { CommentCursor } from '../types';
+
+export function useCommentCursor(mrId: number): CommentCursor | null {
+  const [cursor, setCursor] = useState<CommentCursor | null>(null);
+
+  useEffect(() => {
+    if (!mrId) return;
+    let cancelled = false;
+    setCursor(null);
+    getLatestCommentCursor(mrId)
+      .then((next) => {
+        if (!cancelled) setCursor(next);
+        return markMrViewed(mrId);
+      })
+      // Not available in browser mode; the feed works without it.
+      .catch(() => {});
+    return () => {
+      cancelled = true;
+    };
+  }, [mrId]);
+
+  return cursor;
+}
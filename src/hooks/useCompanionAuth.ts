@@ -7,6 +7,7 @@
  */
 
 import { useState, useEffect } from 'react';
+import { apiUrl } from '../services/transport';
 
 interface CompanionAuthState {
   /** null while checking, true/false once resolved */
@@ -28,7 +29,7 @@ export default function useCompanionAuth(skip = false): CompanionAuthState {
 
     async function checkSession() {
       try {
-        const res = await fetch('/api/instances', { credentials: 'include' });
+        const res = await fetch(apiUrl('/api/instances'), { credentials: 'include' });
         if (!cancelled) {
           setState({ isAuthenticated: res.ok, isChecking: false });
         }
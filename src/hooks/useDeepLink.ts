@@ -4,6 +4,7 @@ import { getCurrentWindow } from '@tauri-apps/api/window';
 import { useToast } from '../components/Toast';
 import { parseDeepLinkUrl } from '../utils/deepLinkParser';
 import { isTauri, resolveMrByWebUrl, listInstances, resolveProjectByPath, getProjectPipelines } from '../services';
+import { openLink } from '../services/tauri';
 
 // Module-level flag so the cold-start URL is only processed once,
 // even if the hook effect re-runs (React strict mode, HMR, etc.)
@@ -14,7 +15,19 @@ let coldStartHandled = false;
 const handledUrls = new Set<string>();
 
 /**
- * Hook that listens for ultra-gitlab:// deep-link URLs and navigates accordingly.
+ * Plain http(s) links arrive when the app is registered as the OS link
+ * handler. The backend keeps MR links on opted-in hosts and forwards the
+ * rest to the fallback browser; kept links become ultra-gitlab:// URLs.
+ */
+async function toDeepLinkUrl(url: string): Promise<string | null> {
+  if (!/^https?:/i.test(url)) return url;
+  const mrUrl = await openLink(url);
+  return mrUrl ? `ultra-gitlab://open?url=${encodeURIComponent(mrUrl)}` : null;
+}
+
+/**
+ * Hook that listens for ultra-gitlab:// deep-link URLs (and GitLab links claimed
+ * through the OS link handler) and navigates accordingly.
  *
  * - If MR is found and opened: navigates to /mrs/:localId
  * - If MR is found but merged/closed: shows toast, navigates to /mrs
@@ -36,7 +49,9 @@ export default function useDeepLink() {
       processingRef.current = true;
 
       try {
-        const data = parseDeepLinkUrl(url);
+        const deepLinkUrl = await toDeepLinkUrl(url);
+        if (!deepLinkUrl) return;
+        const data = parseDeepLinkUrl(deepLinkUrl);
         if (!data) return;
 
         // Check if the instance is configured
@@ -131,13 +146,17 @@ export default function useDeepLink() {
       // Listen for subsequent deep-link URLs (warm start)
       const unlistenDeepLink = await deepLink.onOpenUrl(async (urls) => {
         if (!cancelled && urls.length > 0) {
+          // Links forwarded to the browser shouldn't pull the window forward
+          const deepLinkUrl = await toDeepLinkUrl(urls[0]);
+          handledUrls.add(urls[0]);
+          if (!deepLinkUrl) return;
+
           // Surface the window if it was hidden (macOS hide-on-close)
           const appWindow = getCurrentWindow();
           await appWindow.show();
           await appWindow.setFocus();
 
-          await handleDeepLinkUrl(urls[0]);
-          handledUrls.add(urls[0]);
+          await handleDeepLinkUrl(deepLinkUrl);
         }
       });
 
@@ -0,0 +1,100 @@
+/**
+ * A job's log, loaded from the end.
+ *
+ * The tail comes from useJobTraceQuery (polled while the job runs). Earlier
+ * output is fetched a window at a time by `loadEarlier`, e.g. when the log
+ * viewer is scrolled to the top, so huge traces never load in full.
+ *
+ * Earlier output stays attached while it ends where the tail starts. When a
+ * running job writes more than a window between polls the tail moves past
+ * it, and the earlier output is dropped rather than shown with a gap.
+ */
+
+import { useCallback, useEffect, useRef, useState } from 'react';
+import { getJobTraceRange } from '../services/tauri';
+import { useJobTraceQuery } from './queries/useJobTraceQuery';
+import type { PipelineJobStatus } from '../types';
+
+/** Bytes fetched per `loadEarlier` call. */
+const EARLIER_WINDOW_BYTES = 256 * 1024;
+
+interface EarlierOutput {
+  content: string;
+  offset: number;
+  /** Where the tail started when this was loaded. */
+  end: number;
+}
+
+export interface JobTraceData {
+  /** Loaded part of the log. */
+  content: string;
+  /** Bytes of the log before `content` that aren't loaded. */
+  offset: number;
+  totalBytes: number;
+  isLoading: boolean;
+  error: unknown;
+  loadingEarlier: boolean;
+  earlierError: string | null;
+  loadEarlier: () => void;
+}
+
+export function useJobTrace(
+  instanceId: number,
+  projectId: number,
+  jobId: number,
+  status: PipelineJobStatus
+): JobTraceData {
+  const traceQuery = useJobTraceQuery(instanceId, projectId, jobId, status);
+  const tail = traceQuery.data;
+  const [earlier, setEarlier] = useState<EarlierOutput | null>(null);
+  const [loadingEarlier, setLoadingEarlier] = useState(false);
+  const [earlierError, setEarlierError] = useState<string | null>(null);
+  const loadingRef = useRef(false);
+  const jobKey = `${instanceId}:${projectId}:${jobId}`;
+  const jobKeyRef = useRef(jobKey);
+
+  useEffect(() => {
+    jobKeyRef.current = jobKey;
+    setEarlier(null);
+    setEarlierError(null);
+  }, [jobKey]);
+
+  const attached = tail && earlier && earlier.end === tail.offset ? earlier : null;
+  const offset = attached ? attached.offset : tail?.offset ?? 0;
+
+  const loadEarlier = useCallback(() => {
+    if (!tail || offset === 0 || loadingRef.current) return;
+    const start = Math.max(0, offset - EARLIER_WINDOW_BYTES);
+    const requestedFor = jobKey;
+    loadingRef.current = true;
+    setLoadingEarlier(true);
+    setEarlierError(null);
+    getJobTraceRange(instanceId, projectId, jobId, start, offset - start)
+      .then((chunk) => {
+        if (jobKeyRef.current !== requestedFor) return;
+        setEarlier({
+          content: chunk.content + (attached?.content ?? ''),
+          offset: chunk.offset,
+          end: attached?.end ?? tail.offset,
+        });
+      })
+      .catch(() => {
+        if (jobKeyRef.current === requestedFor) setEarlierError('Failed to load earlier output');
+      })
+      .finally(() => {
+        loadingRef.current = false;
+        setLoadingEarlier(false);
+      });
+  }, [tail, offset, attached, jobKey, instanceId, projectId, jobId]);
+
+  return {
+    content: (attached?.content ?? '') + (tail?.content ?? ''),
+    offset,
+    totalBytes: tail?.totalBytes ?? 0,
+    isLoading: traceQuery.isLoading,
+    error: traceQuery.error,
+    loadingEarlier,
+    earlierError,
+    loadEarlier,
+  };
+}
@@ -14,6 +14,30 @@ interface MrReadyPayload {
   mrId: number;
 }
 
+interface MergedWhenReadyPayload {
+  mrId: number;
+  title: string;
+  projectName: string;
+  webUrl: string;
+}
+
+interface ReviewRerequestedPayload {
+  mrId: number;
+  title: string;
+  projectName: string;
+  authorUsername: string;
+  webUrl: string;
+}
+
+interface ReviewSlaPayload {
+  mrId: number;
+  title: string;
+  projectName: string;
+  webUrl: string;
+  state: 'at_risk' | 'breached';
+  dueAt: number;
+}
+
 interface PipelineChangedPayload {
   projectName: string;
   oldStatus: string;
@@ -23,6 +47,8 @@ interface PipelineChangedPayload {
   instanceId: number;
   projectId: number;
   pipelineId: number;
+  /** Jobs that failed the pipeline; empty unless it failed. */
+  failedJobs: string[];
 }
 
 interface AutoRunPayload {
@@ -36,6 +62,17 @@ interface AutoRunPayload {
   pipelineId: number;
 }
 
+interface ManualGatePayload {
+  instanceId: number;
+  projectId: number;
+  projectName: string;
+  pipelineId: number;
+  jobId: number;
+  jobName: string;
+  refName: string;
+  webUrl: string;
+}
+
 function pipelineToastType(status: string): 'pipeline-success' | 'pipeline-failed' | 'pipeline-running' {
   if (status === 'success') return 'pipeline-success';
   if (status === 'failed') return 'pipeline-failed';
@@ -89,8 +126,11 @@ export default function useNotifications() {
         const settings = await getNotificationSettings();
         if (!settings.pipelineStatusPinned) return;
 
-        const { projectName, newStatus, refName, webUrl, instanceId, projectId, pipelineId } = event.payload;
+        const { projectName, newStatus, refName, webUrl, instanceId, projectId, pipelineId, failedJobs } = event.payload;
         const statusTitle = `Pipeline ${capitalize(newStatus)}`;
+        const body = failedJobs.length > 0
+          ? `${projectName} (${refName}): ${failedJobs.join(', ')} failed`
+          : `${projectName} (${refName})`;
         const params = new URLSearchParams({
           instance: String(instanceId),
           project: projectName,
@@ -102,7 +142,7 @@ export default function useNotifications() {
         addToastRef.current({
           type: pipelineToastType(newStatus),
           title: statusTitle,
-          body: `${projectName} (${refName})`,
+          body,
           url: webUrl,
           route,
         });
@@ -110,7 +150,7 @@ export default function useNotifications() {
         if (isTauri && settings.nativeNotificationsEnabled) {
           sendNativeNotification(
             statusTitle,
-            `${projectName} (${refName})`,
+            body,
             route
           ).catch(console.error);
         }
@@ -156,11 +196,132 @@ export default function useNotifications() {
       }
     });
 
+    const manualGatePromise = tauriListen<ManualGatePayload>('notification:manual-gate', async (event) => {
+      if (cancelled) return;
+      try {
+        const { instanceId, projectId, projectName, pipelineId, jobName, refName, webUrl } = event.payload;
+        const title = 'Manual Job Waiting';
+        const body = `${jobName} (${refName}) in ${projectName}`;
+        const params = new URLSearchParams({
+          instance: String(instanceId),
+          project: projectName,
+          ref: refName,
+          url: webUrl,
+        });
+        const route = `/pipelines/${projectId}/${pipelineId}?${params.toString()}`;
+
+        addToastRef.current({
+          type: 'pipeline-running',
+          title,
+          body,
+          url: webUrl,
+          route,
+        });
+
+        // Opted into per project, so only the native toggle applies.
+        const settings = await getNotificationSettings();
+        if (isTauri && settings.nativeNotificationsEnabled) {
+          sendNativeNotification(title, body, route).catch(console.error);
+        }
+      } catch (err) {
+        console.error('Failed to handle manual gate notification:', err);
+      }
+    });
+
+    const mergedWhenReadyPromise = tauriListen<MergedWhenReadyPayload>('notification:merged-when-ready', async (event) => {
+      if (cancelled) return;
+      try {
+        const { mrId, title, projectName, webUrl } = event.payload;
+        const route = `/my-mrs/${mrId}`;
+        const body = `${title} in ${projectName}`;
+
+        addToastRef.current({
+          type: 'mr-ready',
+          title: 'MR Merged',
+          body,
+          url: webUrl,
+          route,
+        });
+
+        // Like auto-run, the user queued this merge themselves, so the toast
+        // isn't gated on a notification setting.
+        const settings = await getNotificationSettings();
+        if (isTauri && settings.nativeNotificationsEnabled) {
+          sendNativeNotification('MR Merged', body, route).catch(console.error);
+        }
+      } catch (err) {
+        console.error('Failed to handle merged-when-ready notification:', err);
+      }
+    });
+
+    const rerequestedPromise = tauriListen<ReviewRerequestedPayload>('notification:review-rerequested', async (event) => {
+      if (cancelled) return;
+      try {
+        const { mrId, title, projectName, authorUsername, webUrl } = event.payload;
+        const route = `/mrs/${mrId}`;
+        const toastTitle = `${authorUsername} re-requested your review`;
+        const body = `${title} in ${projectName}`;
+
+        addToastRef.current({
+          type: 'info',
+          title: toastTitle,
+          body,
+          url: webUrl,
+          route,
+        });
+
+        // A direct request from the author, so the toast is always shown;
+        // native notifications follow the global toggle.
+        const settings = await getNotificationSettings();
+        if (isTauri && settings.nativeNotificationsEnabled) {
+          sendNativeNotification(toastTitle, body, route).catch(console.error);
+        }
+      } catch (err) {
+        console.error('Failed to handle review re-request notification:', err);
+      }
+    });
+
+    const slaPromise = tauriListen<ReviewSlaPayload>('notification:review-sla', async (event) => {
+      if (cancelled) return;
+      try {
+        const { mrId, title, projectName, webUrl, state, dueAt } = event.payload;
+        const route = `/mrs/${mrId}`;
+        const due = new Date(dueAt * 1000).toLocaleString(undefined, {
+          weekday: 'short',
+          hour: '2-digit',
+          minute: '2-digit',
+        });
+        const toastTitle = state === 'breached' ? 'Review overdue' : `Review due ${due}`;
+        const body = `${title} in ${projectName}`;
+
+        addToastRef.current({
+          type: 'info',
+          title: toastTitle,
+          body,
+          url: webUrl,
+          route,
+        });
+
+        // Thresholds are set in the Review SLA settings, so the toast is
+        // always shown; native notifications follow the global toggle.
+        const settings = await getNotificationSettings();
+        if (isTauri && settings.nativeNotificationsEnabled) {
+          sendNativeNotification(toastTitle, body, route).catch(console.error);
+        }
+      } catch (err) {
+        console.error('Failed to handle review SLA notification:', err);
+      }
+    });
+
     return () => {
       cancelled = true;
       mrReadyPromise.then((unlisten) => unlisten());
       pipelinePromise.then((unlisten) => unlisten());
       autoRunPromise.then((unlisten) => unlisten());
+      manualGatePromise.then((unlisten) => unlisten());
+      mergedWhenReadyPromise.then((unlisten) => unlisten());
+      rerequestedPromise.then((unlisten) => unlisten());
+      slaPromise.then((unlisten) => unlisten());
     };
   }, []);
 }
@@ -0,0 +1,35 @@
+import { useEffect } from 'react';
+import { isTauri } from '../services/transport';
+import { reportUserActivity } from '../services/tauri';
+
+/** At most one report per this interval; the backend waits a minute anyway. */
+const THROTTLE_MS = 5000;
+
+const ACTIVITY_EVENTS = ['keydown', 'pointerdown', 'wheel'] as const;
+
+/**
+ * Reports keyboard, pointer and scroll activity to the backend so idle-time
+ * background jobs pause while the user is working.
+ */
+export default function useUserActivityReporter() {
+  useEffect(() => {
+    if (!isTauri) return;
+
+    let lastReport = 0;
+    const onActivity = () => {
+      const now = Date.now();
+      if (now - lastReport < THROTTLE_MS) return;
+      lastReport = now;
+      reportUserActivity().catch(() => {});
+    };
+
+    for (const event of ACTIVITY_EVENTS) {
+      window.addEventListener(event, onActivity, { passive: true, capture: true });
+    }
+    return () => {
+      for (const event of ACTIVITY_EVENTS) {
+        window.removeEventListener(event, onActivity, { capture: true });
+      }
+    };
+  }, []);
+}
@@ -15,7 +15,36 @@ export const queryKeys = {
   mrComments: (mrId: number) => ["mrComments", mrId] as const,
   mrFileComments: (mrId: number, filePath: string) =>
     ["mrFileComments", mrId, filePath] as const,
+  mrSecurityFindings: (mrId: number) => ["mrSecurityFindings", mrId] as const,
+  mrAccessibleDiff: (mrId: number, filePath: string) =>
+    ["mrAccessibleDiff", mrId, filePath] as const,
+  fileBlame: (mrId: number, filePath: string, headSha: string) =>
+    ["fileBlame", mrId, filePath, headSha] as const,
+  mrCommits: (mrId: number) => ["mrCommits", mrId] as const,
+  mrActivity: (mrId: number) => ["mrActivity", mrId] as const,
+  mrLanguages: (mrId: number) => ["mrLanguages", mrId] as const,
+  renderedMarkdown: (text: string, instanceId?: number, projectId?: number) =>
+    ["renderedMarkdown", instanceId ?? null, projectId ?? null, text] as const,
+  commitDiff: (mrId: number, sha: string) => ["commitDiff", mrId, sha] as const,
+  changesSinceReview: (mrId: number) => ["changesSinceReview", mrId] as const,
+  fileReviewStates: (mrId: number) => ["fileReviewStates", mrId] as const,
   mrReviewers: (mrId: number) => ["mrReviewers", mrId] as const,
+  mrParticipants: (mrId: number) => ["mrParticipants", mrId] as const,
+  reviewSlaStatus: () => ["reviewSlaStatus"] as const,
+  mrTasks: (mrId: number) => ["mrTasks", mrId] as const,
+  projectLabels: (instanceId: number, projectId: number) =>
+    ["projectLabels", instanceId, projectId] as const,
+  projectMilestones: (instanceId: number, projectId: number) =>
+    ["projectMilestones", instanceId, projectId] as const,
+  projectMembers: (instanceId: number, projectId: number, query: string) =>
+    ["projectMembers", instanceId, projectId, query] as const,
+  mergeBlockers: (mrId: number) => ["mergeBlockers", mrId] as const,
+  pendingReview: (mrId: number) => ["pendingReview", mrId] as const,
+  fileAssignments: (mrId: number) => ["fileAssignments", mrId] as const,
+  descriptionLint: (mrId: number) => ["descriptionLint", mrId] as const,
+  mrAuthorPings: (mrId: number) => ["mrAuthorPings", mrId] as const,
+  myCommentHistory: (days: number, search: string) =>
+    ["myCommentHistory", days, search] as const,
   fileContent: (
     instanceId: string,
     projectId: number,
@@ -30,6 +59,8 @@ export const queryKeys = {
   ) => ["fileContentBase64", instanceId, projectId, filePath, sha] as const,
   gitattributes: (instanceId: string, projectId: number) =>
     ["gitattributes", instanceId, projectId] as const,
+  projectReadme: (instanceId: number, projectId: number, refName: string) =>
+    ["projectReadme", instanceId, projectId, refName] as const,
   issues: (
     instanceId: string,
     scope: "all" | "assigned" | "starred",
@@ -44,15 +75,31 @@ export const queryKeys = {
     ["issueAssigneeCandidates", instanceId, projectId] as const,
   pipelineProjects: (instanceId: string) =>
     ["pipelineProjects", instanceId] as const,
+  mutedProjects: () => ["mutedProjects"] as const,
+  pendingManualJobs: () => ["pendingManualJobs"] as const,
   pipelineStatuses: (instanceId: string, projectIds: number[]) =>
     ["pipelineStatuses", instanceId, projectIds] as const,
   pipelineJobs: (instanceId: string, projectId: number, pipelineId: number) =>
     ["pipelineJobs", instanceId, projectId, pipelineId] as const,
+  pipelineGraph: (instanceId: string, projectId: number, pipelineId: number) =>
+    ["pipelineGraph", instanceId, projectId, pipelineId] as const,
   pipelineHistory: (instanceId: string, projectId: number) =>
     ["pipelineHistory", instanceId, projectId] as const,
+  pipelineSchedules: (instanceId: string, projectId: number) =>
+    ["pipelineSchedules", instanceId, projectId] as const,
+  projectReleases: (instanceId: string, projectId: number) =>
+    ["projectReleases", instanceId, projectId] as const,
+  projectTags: (instanceId: string, projectId: number) =>
+    ["projectTags", instanceId, projectId] as const,
   jobTrace: (instanceId: string, projectId: number, jobId: number) =>
     ["jobTrace", instanceId, projectId, jobId] as const,
+  instanceMode: () => ["instanceMode"] as const,
+  mrComparison: (mrIdA: number, mrIdB: number) =>
+    ["mrComparison", mrIdA, mrIdB] as const,
   companionStatus: () => ["companionStatus"] as const,
   companionSettings: () => ["companionSettings"] as const,
   syncSettings: () => ["syncSettings"] as const,
+  instanceSyncConfig: (instanceId: number) =>
+    ["instanceSyncConfig", instanceId] as const,
+  listViewState: () => ["listViewState"] as const,
 };
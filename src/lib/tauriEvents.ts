@@ -1,14 +1,21 @@
 import { queryClient } from './queryClient';
-import { tauriListen } from '../services/transport';
+import { isTauri, tauriListen } from '../services/transport';
+import { getChangesSince } from '../services/tauri';
+import type { ChangeEvent, MergeRequest } from '../types';
 
 let initialized = false;
 
-interface MrUpdatedPayload {
-  mr_id: number;
-  update_type: string;
-  instance_id: number;
-  iid: number;
-}
+/** How often the companion web UI polls for changes (it gets no events). */
+const CHANGE_POLL_INTERVAL_MS = 15_000;
+
+/** MR fields that decide whether an MR shows up in a list at all. */
+const LIST_MEMBERSHIP_FIELDS: (keyof MergeRequest)[] = [
+  'state',
+  'isBotAuthor',
+  'watched',
+  'reviewers',
+  'title',
+];
 
 interface ActionSyncedPayload {
   action_id: number;
@@ -43,38 +50,145 @@ interface AutoRunUpdatedPayload {
   lastError: string | null;
 }
 
+/**
+ * Patch cached MR rows in every list that holds them. Returns false when
+ * some list didn't have the MR, so it needs a refetch instead.
+ */
+function patchListedMr(mrId: number, fields: Partial<MergeRequest>): boolean {
+  let found = true;
+  for (const key of ['mrList', 'myMRList']) {
+    for (const [queryKey, data] of queryClient.getQueriesData<MergeRequest[]>({ queryKey: [key] })) {
+      if (!data) continue;
+      if (!data.some((mr) => mr.id === mrId)) {
+        found = false;
+        continue;
+      }
+      queryClient.setQueryData<MergeRequest[]>(queryKey, (list) =>
+        list?.map((mr) => (mr.id === mrId ? { ...mr, ...fields } : mr)),
+      );
+    }
+  }
+  return found;
+}
+
+function applyChange(change: ChangeEvent, invalidateLists: () => void) {
+  const mrId = change.entityId;
+  switch (change.entity) {
+    case 'merge_request': {
+      if (change.op === 'delete') {
+        for (const key of ['mrList', 'myMRList']) {
+          queryClient.setQueriesData<MergeRequest[]>({ queryKey: [key] }, (list) =>
+            list?.filter((mr) => mr.id !== mrId),
+          );
+        }
+        queryClient.removeQueries({ queryKey: ['mr', mrId] });
+        return;
+      }
+      const fields = change.fields;
+      const membershipChanged = LIST_MEMBERSHIP_FIELDS.some((f) => f in fields);
+      if (!patchListedMr(mrId, fields) || membershipChanged) {
+        invalidateLists();
+      }
+      // List rows carry a description preview; the detail view needs the full text.
+      if ('description' in fields) {
+        queryClient.invalidateQueries({ queryKey: ['mr', mrId] });
+      } else {
+        queryClient.setQueryData<MergeRequest>(['mr', mrId], (mr) =>
+          mr ? { ...mr, ...fields } : mr,
+        );
+      }
+      return;
+    }
+    case 'diff':
+      queryClient.invalidateQueries({ queryKey: ['mrFiles', mrId] });
+      queryClient.invalidateQueries({ queryKey: ['mrDiffRefs', mrId] });
+      queryClient.invalidateQueries({ queryKey: ['mrSecurityFindings', mrId] });
+      queryClient.invalidateQueries({ queryKey: ['mrCommits', mrId] });
+      queryClient.invalidateQueries({ queryKey: ['mrLanguages', mrId] });
+      queryClient.invalidateQueries({ queryKey: ['changesSinceReview', mrId] });
+      queryClient.invalidateQueries({ queryKey: ['fileReviewStates', mrId] });
+      return;
+    case 'comments':
+      queryClient.invalidateQueries({ queryKey: ['mrComments', mrId] });
+      queryClient.invalidateQueries({ queryKey: ['mrFileComments', mrId] });
+      queryClient.invalidateQueries({ queryKey: ['mrActivity', mrId] });
+      return;
+  }
+}
+
 export async function setupTauriEventListeners(): Promise<() => void> {
   if (initialized) return () => {};
   initialized = true;
 
-  const debounceTimers = new Map<number, ReturnType<typeof setTimeout>>();
+  // Cursor into the backend change log; null until the first fetch.
+  let cursor: number | null = null;
+  let catchingUp = false;
+  let catchUpAgain = false;
+  let listTimer: ReturnType<typeof setTimeout> | null = null;
 
-  const unlistenMrUpdated = await tauriListen<MrUpdatedPayload>(
-    'mr-updated',
-    (event) => {
-      const { mr_id, update_type } = event.payload;
+  // Several MRs usually change in one sync; refetch the lists once.
+  const invalidateLists = () => {
+    if (listTimer) clearTimeout(listTimer);
+    listTimer = setTimeout(() => {
+      listTimer = null;
+      queryClient.invalidateQueries({ queryKey: ['mrList'] });
+      queryClient.invalidateQueries({ queryKey: ['myMRList'] });
+    }, 500);
+  };
 
-      if (debounceTimers.has(mr_id)) {
-        clearTimeout(debounceTimers.get(mr_id)!);
+  const catchUp = async () => {
+    if (catchingUp) {
+      catchUpAgain = true;
+      return;
+    }
+    catchingUp = true;
+    try {
+      let more = true;
+      while (more || catchUpAgain) {
+        catchUpAgain = false;
+        const page = await getChangesSince(cursor);
+        if (page.reset && cursor !== null) {
+          queryClient.invalidateQueries();
+        }
+        page.changes.forEach((change) => applyChange(change, invalidateLists));
+        cursor = page.cursor;
+        more = page.hasMore;
       }
+    } catch (err) {
+      console.warn('[changes] Failed to catch up:', err);
+    } finally {
+      catchingUp = false;
+    }
+  };
 
-      debounceTimers.set(
-        mr_id,
-        setTimeout(() => {
-          debounceTimers.delete(mr_id);
-          queryClient.invalidateQueries({ queryKey: ['mr', mr_id] });
-          queryClient.invalidateQueries({ queryKey: ['mrFiles', mr_id] });
-          queryClient.invalidateQueries({ queryKey: ['mrDiffRefs', mr_id] });
-          queryClient.invalidateQueries({ queryKey: ['mrList'] });
-          queryClient.invalidateQueries({ queryKey: ['myMRList'] });
-          if (update_type === 'comments_updated') {
-            queryClient.invalidateQueries({ queryKey: ['mrComments', mr_id] });
-            queryClient.invalidateQueries({ queryKey: ['mrFileComments', mr_id] });
-          }
-        }, 500),
-      );
-    },
-  );
+  await catchUp();
+
+  const unlistenDbChanges = await tauriListen<ChangeEvent>('db-changes', (event) => {
+    const change = event.payload;
+    if (cursor !== null && change.seq > cursor + 1) {
+      // Missed events (e.g. while the webview was reloading): fetch the gap.
+      void catchUp();
+      return;
+    }
+    applyChange(change, invalidateLists);
+    cursor = Math.max(cursor ?? 0, change.seq);
+  });
+
+  // The companion web UI gets no db-changes events. It catches up when the
+  // server relays an MR update (or says it dropped some), and still polls
+  // in case the event stream is cut by a proxy or a sleeping phone.
+  const onVisible = () => {
+    if (document.visibilityState === 'visible') void catchUp();
+  };
+  let pollTimer: ReturnType<typeof setInterval> | null = null;
+  const companionUnlisteners: (() => void)[] = [];
+  if (!isTauri) {
+    pollTimer = setInterval(() => void catchUp(), CHANGE_POLL_INTERVAL_MS);
+    document.addEventListener('visibilitychange', onVisible);
+    for (const event of ['mr-updated', 'lagged']) {
+      companionUnlisteners.push(await tauriListen(event, () => void catchUp()));
+    }
+  }
 
   const unlistenActionSynced = await tauriListen<ActionSyncedPayload>(
     'action-synced',
@@ -128,15 +242,15 @@ export async function setupTauriEventListeners(): Promise<() => void> {
   );
 
   return () => {
-    unlistenMrUpdated();
+    unlistenDbChanges();
     unlistenActionSynced();
     unlistenIssuesUpdated();
     unlistenAutoMergeUpdated();
     unlistenAutoRunUpdated();
-    for (const timer of debounceTimers.values()) {
-      clearTimeout(timer);
-    }
-    debounceTimers.clear();
+    if (listTimer) clearTimeout(listTimer);
+    if (pollTimer) clearInterval(pollTimer);
+    companionUnlisteners.forEach((unlisten) => unlisten());
+    document.removeEventListener('visibilitychange', onVisible);
     initialized = false;
   };
 }
@@ -3,14 +3,29 @@
  *
  * Shown when accessing the app via the companion HTTP server in a browser.
  * Accepts a 6-digit PIN, verifies against the server, and redirects to /mrs on success.
- * Supports auto-auth via ?pin= query parameter (QR code flow).
+ * Supports auto-auth via ?pin= query parameter (QR code flow), and pairing
+ * with a one-time code confirmed in the desktop app for devices that can't
+ * scan the QR code.
  */
 
 import { useState, useEffect, useRef, useCallback } from 'react';
 import { useNavigate, useSearchParams } from 'react-router-dom';
 import useCompanionAuth from '../hooks/useCompanionAuth';
+import { apiUrl } from '../services/transport';
 import './AuthPage.css';
 
+/** How often to check whether the desktop confirmed a pairing code. */
+const PAIRING_POLL_MS = 2000;
+
+/** Short device label for the desktop confirmation dialog. */
+function deviceLabel(): string {
+  const ua = navigator.userAgent;
+  if (/iPad/.test(ua)) return 'iPad';
+  if (/iPhone/.test(ua)) return 'iPhone';
+  if (/Android/.test(ua)) return /Mobile/.test(ua) ? 'Android Phone' : 'Android Tablet';
+  return 'Browser';
+}
+
 export default function AuthPage() {
   const navigate = useNavigate();
   const [searchParams] = useSearchParams();
@@ -21,6 +36,7 @@ export default function AuthPage() {
   const checkingSession = companionAuth.isChecking;
   const inputRef = useRef<HTMLInputElement>(null);
   const autoAuthAttempted = useRef(false);
+  const [pairing, setPairing] = useState<{ requestId: string; code: string } | null>(null);
 
   // If already authenticated, redirect to MR list
   useEffect(() => {
@@ -40,7 +56,7 @@ export default function AuthPage() {
     setError('');
 
     try {
-      const res = await fetch('/api/auth/verify-pin', {
+      const res = await fetch(apiUrl('/api/auth/verify-pin'), {
         method: 'POST',
         headers: { 'Content-Type': 'application/json' },
         body: JSON.stringify({ pin: pinValue }),
@@ -71,6 +87,53 @@ export default function AuthPage() {
     }
   }, [navigate]);
 
+  // Ask the desktop app to confirm a one-time pairing code
+  const startPairing = useCallback(async () => {
+    setError('');
+    try {
+      const res = await fetch(apiUrl('/api/auth/pair'), {
+        method: 'POST',
+        headers: { 'Content-Type': 'application/json' },
+        body: JSON.stringify({ deviceName: deviceLabel() }),
+        credentials: 'include',
+      });
+      if (res.status === 429) {
+        setError('Too many attempts. Try again in 1 minute.');
+        return;
+      }
+      if (!res.ok) {
+        setError('Could not start pairing');
+        return;
+      }
+      const body = await res.json();
+      setPairing({ requestId: body.requestId, code: body.code });
+    } catch {
+      setError('Could not reach the server. Make sure your desktop app is running.');
+    }
+  }, []);
+
+  // Poll until the desktop confirms, rejects, or the code expires
+  useEffect(() => {
+    if (!pairing) return;
+    const timer = setInterval(async () => {
+      try {
+        const res = await fetch(apiUrl(`/api/auth/pair/${encodeURIComponent(pairing.requestId)}`), {
+          credentials: 'include',
+        });
+        if (res.status === 202) return;
+        setPairing(null);
+        if (res.ok) {
+          navigate('/mrs', { replace: true });
+        } else {
+          setError('Pairing was rejected or expired. Please try again.');
+        }
+      } catch {
+        // Transient network error; keep polling.
+      }
+    }, PAIRING_POLL_MS);
+    return () => clearInterval(timer);
+  }, [pairing, navigate]);
+
   // Auto-auth flow: if ?pin= query param is present, auto-submit
   useEffect(() => {
     if (checkingSession || autoAuthAttempted.current) return;
@@ -152,6 +215,16 @@ export default function AuthPage() {
         <p className="auth-hint">
           Open Settings → Companion Server in the desktop app to find the PIN.
         </p>
+
+        {pairing ? (
+          <p className="auth-hint" role="status">
+            Confirm code <strong>{pairing.code}</strong> in the desktop app…
+          </p>
+        ) : (
+          <button type="button" className="auth-pair-link" onClick={startPairing}>
+            Pair with a code instead
+          </button>
+        )}
       </div>
     </div>
   );
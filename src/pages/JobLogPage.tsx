@@ -7,6 +7,9 @@
  * For running/pending/created jobs, TanStack Query polls the trace endpoint
  * every 3s and job status every 10s (via usePipelineJobsQuery). Polling stops
  * automatically when the job completes.
+ *
+ * Only the end of the log is loaded at first; earlier output loads a window
+ * at a time when the log is scrolled to the top.
  */
 
 import { useState, useEffect, useCallback, useMemo, useRef } from 'react';
@@ -20,9 +23,9 @@ import { parseLog, formatSectionName } from '../utils/logLineParser';
 import type { LogLine, LogSection } from '../utils/logLineParser';
 import type { AnsiSegment } from '../utils/ansiParser';
 import type { PipelineJobStatus } from '../types';
-import { useJobTraceQuery } from '../hooks/queries/useJobTraceQuery';
 import { usePipelineJobsQuery } from '../hooks/queries/usePipelineJobsQuery';
 import { useCopyToast } from '../hooks/useCopyToast';
+import { useJobTrace } from '../hooks/useJobTrace';
 import './JobLogPage.css';
 
 /** Statuses that indicate a job is still active and should be polled. */
@@ -41,6 +44,17 @@ function formatDuration(seconds: number): string {
   return rm > 0 ? `${h}h ${rm}m` : `${h}h`;
 }
 
+/** Scroll distance from the top of the log that loads earlier output. */
+const LOAD_EARLIER_THRESHOLD_PX = 200;
+
+/**
+ * Format a byte count as KB or MB.
+ */
+function formatSize(bytes: number): string {
+  if (bytes < 1024 * 1024) return `${Math.max(1, Math.round(bytes / 1024))} KB`;
+  return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
+}
+
 /**
  * Human-readable status label.
  */
@@ -175,9 +189,10 @@ export default function JobLogPage() {
   // Measured heights for wrapped log lines (default matches .log-line min-height)
   const rowHeight = useDynamicRowHeight({ defaultRowHeight: 20 });
 
-  // TQ-backed trace query — polls every 3s when job is active, stops when complete
-  const traceQuery = useJobTraceQuery(instanceId, pid, jid, currentStatus);
-  const trace = traceQuery.data ?? '';
+  // End of the log from a TQ-backed query (polls every 3s when job is active,
+  // stops when complete); earlier output loads on demand
+  const jobTrace = useJobTrace(instanceId, pid, jid, currentStatus);
+  const trace = jobTrace.content;
 
   // TQ-backed jobs query — polls every 10s when any job is active; used to detect completion
   const jobsQuery = usePipelineJobsQuery(instanceId, pid, plid);
@@ -248,7 +263,23 @@ export default function JobLogPage() {
     requestAnimationFrame(() => { isAutoScrollingRef.current = false; });
   }, [followMode, rows.length, listApi]);
 
-  // Detect manual scroll to toggle follow mode
+  // Keep the view in place when earlier output is prepended
+  const prependAnchorRef = useRef({ offset: jobTrace.offset, rowCount: 0 });
+  useEffect(() => {
+    const anchor = prependAnchorRef.current;
+    if (listApi && jobTrace.offset < anchor.offset && rows.length > anchor.rowCount) {
+      isAutoScrollingRef.current = true;
+      listApi.scrollToRow({ index: rows.length - anchor.rowCount, align: 'start' });
+      requestAnimationFrame(() => { isAutoScrollingRef.current = false; });
+    }
+    prependAnchorRef.current = { offset: jobTrace.offset, rowCount: rows.length };
+  }, [jobTrace.offset, rows.length, listApi]);
+
+  // Scrolling to the top loads earlier output; after a failure only the button retries
+  const autoLoadEarlierRef = useRef<(() => void) | null>(null);
+  autoLoadEarlierRef.current = jobTrace.earlierError ? null : jobTrace.loadEarlier;
+
+  // Detect manual scroll to toggle follow mode and load earlier output
   useEffect(() => {
     const el = listApi?.element;
     if (!el) return;
@@ -258,6 +289,7 @@ export default function JobLogPage() {
       const { scrollTop, scrollHeight, clientHeight } = el!;
       const atBottom = scrollHeight - scrollTop - clientHeight < 30;
       setFollowMode(atBottom);
+      if (scrollTop < LOAD_EARLIER_THRESHOLD_PX) autoLoadEarlierRef.current?.();
     }
 
     el.addEventListener('scroll', handleScroll, { passive: true });
@@ -289,8 +321,8 @@ export default function JobLogPage() {
     return () => document.removeEventListener('keydown', handleKeyDown);
   }, [navigate, backUrl, jobWebUrl, copyToClipboard]);
 
-  const loading = traceQuery.isLoading;
-  const error = traceQuery.error ? 'Failed to load job trace' : null;
+  const loading = jobTrace.isLoading;
+  const error = jobTrace.error ? 'Failed to load job trace' : null;
 
   return (
     <div className="job-log-page">
@@ -344,20 +376,45 @@ export default function JobLogPage() {
         ) : trace.length === 0 ? (
           <div className="job-log-empty">No log output for this job.</div>
         ) : (
-          <div className={`job-log-trace${parsedLog.timestamped ? ' job-log-trace--timestamped' : ''}`}>
-            <List
-              listRef={setListApi}
-              rowComponent={VirtualLogRow}
-              rowCount={rows.length}
-              rowHeight={rowHeight}
-              rowProps={{
-                rows,
-                showTimestamp: parsedLog.timestamped,
-                onToggleSection: toggleSection,
-              }}
-              overscanCount={20}
-            />
-          </div>
+          <>
+            {jobTrace.offset > 0 && (
+              <div className="job-log-earlier">
+                {jobTrace.loadingEarlier ? (
+                  <>
+                    <span className="job-log-spinner" />
+                    Loading earlier output...
+                  </>
+                ) : (
+                  <>
+                    <span>
+                      {formatSize(jobTrace.offset)} of earlier output not loaded
+                      {' '}(log is {formatSize(jobTrace.totalBytes)})
+                    </span>
+                    <button type="button" className="job-log-earlier-btn" onClick={jobTrace.loadEarlier}>
+                      Load earlier
+                    </button>
+                    {jobTrace.earlierError && (
+                      <span className="job-log-earlier-error">{jobTrace.earlierError}</span>
+                    )}
+                  </>
+                )}
+              </div>
+            )}
+            <div className={`job-log-trace${parsedLog.timestamped ? ' job-log-trace--timestamped' : ''}`}>
+              <List
+                listRef={setListApi}
+                rowComponent={VirtualLogRow}
+                rowCount={rows.length}
+                rowHeight={rowHeight}
+                rowProps={{
+                  rows,
+                  showTimestamp: parsedLog.timestamped,
+                  onToggleSection: toggleSection,
+                }}
+                overscanCount={20}
+              />
+            </div>
+          </>
         )}
       </main>
 
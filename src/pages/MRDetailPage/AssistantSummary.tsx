@@ -0,0 +1,61 @@
+import { useState } from 'react';
+import Markdown from '../../components/Markdown';
+import { summarizeMr } from '../../services/tauri';
+import type { AssistantReply } from '../../types';
+
+interface AssistantSummaryProps {
+  mrId: number;
+}
+
+/**
+ * On-demand MR summary from the review assistant. Nothing is sent until the
+ * reviewer asks; the result is kept for the lifetime of the page.
+ */
+export default function AssistantSummary({ mrId }: AssistantSummaryProps) {
+  const [reply, setReply] = useState<AssistantReply | null>(null);
+  const [loading, setLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const [expanded, setExpanded] = useState(true);
+
+  async function load() {
+    setLoading(true);
+    setError(null);
+    try {
+      setReply(await summarizeMr(mrId));
+      setExpanded(true);
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setLoading(false);
+    }
+  }
+
+  const masked = reply && reply.redactions > 0 ? ` (${reply.redactions} masked before sending)` : '';
+
+  return (
+    <div className="mr-assistant-summary">
+      <div className="mr-security-banner-summary">
+        <span>{reply ? `Assistant summary${masked}` : 'Review assistant'}</span>
+        {reply ? (
+          <button
+            className="mr-state-banner-btn"
+            onClick={() => setExpanded((e) => !e)}
+            aria-expanded={expanded}
+          >
+            {expanded ? 'Hide' : 'Show'}
+          </button>
+        ) : (
+          <button className="mr-state-banner-btn" onClick={load} disabled={loading}>
+            {loading ? 'Summarizing...' : 'Summarize'}
+          </button>
+        )}
+      </div>
+      {error && <div className="mr-assistant-summary-error">{error}</div>}
+      {reply && expanded && (
+        <div className="mr-assistant-summary-body">
+          <Markdown content={reply.text} />
+        </div>
+      )}
+    </div>
+  );
+}
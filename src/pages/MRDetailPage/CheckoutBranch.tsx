@@ -0,0 +1,90 @@
+import { useState } from 'react';
+import { useToast } from '../../components/Toast';
+import { checkoutMrBranch, getMrRepoPath } from '../../services/tauri';
+
+interface CheckoutBranchProps {
+  mrId: number;
+}
+
+/**
+ * Check the MR out in the project's local clone. The first time asks for
+ * the clone's path, which is remembered for the project.
+ */
+export default function CheckoutBranch({ mrId }: CheckoutBranchProps) {
+  const { addToast } = useToast();
+  const [open, setOpen] = useState(false);
+  const [path, setPath] = useState('');
+  const [busy, setBusy] = useState(false);
+
+  async function checkout(repoPath?: string) {
+    setBusy(true);
+    try {
+      const result = await checkoutMrBranch(mrId, repoPath);
+      addToast({
+        type: 'info',
+        title: `Checked out ${result.branch}`,
+        body: `${result.headSha.slice(0, 8)} in ${result.repoPath}`,
+      });
+      setOpen(false);
+    } catch (err) {
+      addToast({
+        type: 'info',
+        title: 'Could not check out the MR',
+        body: err instanceof Error ? err.message : String(err),
+      });
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  async function handleClick() {
+    const saved = await getMrRepoPath(mrId).catch(() => null);
+    if (saved) {
+      await checkout();
+    } else {
+      setOpen(true);
+    }
+  }
+
+  function handleSubmit(e: React.FormEvent) {
+    e.preventDefault();
+    if (path.trim()) checkout(path.trim());
+  }
+
+  if (!open) {
+    return (
+      <button
+        className="mr-readme-toggle"
+        onClick={handleClick}
+        onContextMenu={(e) => {
+          e.preventDefault();
+          setOpen(true);
+        }}
+        disabled={busy}
+        title="Fetch the MR and switch to its branch in your local clone (right-click to change the clone)"
+      >
+        {busy ? 'Checking out…' : 'Check out'}
+      </button>
+    );
+  }
+
+  return (
+    <form className="mr-checkout-branch" onSubmit={handleSubmit}>
+      <input
+        type="text"
+        value={path}
+        onChange={(e) => setPath(e.target.value)}
+        placeholder="~/code/project"
+        aria-label="Local clone path"
+        disabled={busy}
+        autoFocus
+      />
+      <button type="submit" className="mr-readme-toggle" disabled={busy || !path.trim()}>
+        {busy ? 'Checking out…' : 'Check out'}
+      </button>
+      <button type="button" className="mr-readme-toggle" onClick={() => setOpen(false)} disabled={busy}>
+        Cancel
+      </button>
+    </form>
+  );
+}
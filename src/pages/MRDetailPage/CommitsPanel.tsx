@@ -0,0 +1,166 @@
+import { useEffect, useState } from 'react';
+import { useCommitDiffQuery, useMrCommitsQuery } from '../../hooks/queries/useMrCommitsQuery';
+import {
+  useChangesSinceReviewQuery,
+  useMarkReviewedMutation,
+} from '../../hooks/queries/useReviewProgressQuery';
+import type { CommitDiffFile } from '../../types';
+
+interface CommitsPanelProps {
+  mrId: number;
+  /** The head moved on since the MR was last marked reviewed. */
+  changedSinceReview: boolean;
+  onClose: () => void;
+}
+
+function lineClass(line: string): string {
+  if (line.startsWith('@@')) return 'mr-commit-line hunk';
+  if (line.startsWith('+')) return 'mr-commit-line added';
+  if (line.startsWith('-')) return 'mr-commit-line removed';
+  return 'mr-commit-line';
+}
+
+function CommitFile({ file }: { file: CommitDiffFile }) {
+  const path = file.changeType === 'renamed' ? `${file.oldPath} → ${file.newPath}` : file.newPath;
+  return (
+    <details className="mr-commit-file" open>
+      <summary>
+        <span className="mr-commit-file-path">{path}</span>
+        <span className="mr-commit-file-stats">
+          <span className="added">+{file.additions}</span>{' '}
+          <span className="removed">-{file.deletions}</span>
+        </span>
+      </summary>
+      <pre className="mr-commit-diff">
+        {file.diff.split('\n').map((line, i) => (
+          <div key={i} className={lineClass(line)}>
+            {line || ' '}
+          </div>
+        ))}
+      </pre>
+    </details>
+  );
+}
+
+/**
+ * Side panel listing the MR's commits, oldest first. Selecting one shows
+ * only the changes that commit made, for reviewing commit by commit. Once
+ * the MR was marked reviewed, new pushes can be reviewed as one diff.
+ */
+export default function CommitsPanel({ mrId, changedSinceReview, onClose }: CommitsPanelProps) {
+  const { data: commits, isLoading, error } = useMrCommitsQuery(mrId);
+  const [selectedSha, setSelectedSha] = useState<string | null>(null);
+  const [sinceReview, setSinceReview] = useState(false);
+  const diff = useCommitDiffQuery(mrId, selectedSha);
+  const changes = useChangesSinceReviewQuery(mrId, sinceReview);
+  const markReviewed = useMarkReviewedMutation(mrId);
+
+  useEffect(() => {
+    const onKeyDown = (e: KeyboardEvent) => {
+      if (e.key === 'Escape') onClose();
+    };
+    window.addEventListener('keydown', onKeyDown);
+    return () => window.removeEventListener('keydown', onKeyDown);
+  }, [onClose]);
+
+  const selected = commits?.find((c) => c.sha === selectedSha);
+  const showList = !selected && !sinceReview;
+
+  return (
+    <aside className="mr-commits-panel" aria-label="Commits">
+      <div className="mr-readme-header">
+        <span className="mr-readme-title">
+          {showList ? (
+            'Commits'
+          ) : (
+            <button
+              className="mr-commits-back"
+              onClick={() => {
+                setSelectedSha(null);
+                setSinceReview(false);
+              }}
+            >
+              ← Commits
+            </button>
+          )}
+          {commits && showList && <span className="mr-readme-ref">{commits.length}</span>}
+          {selected && <span className="mr-readme-ref">{selected.shortSha}</span>}
+        </span>
+        <button className="mr-readme-close" onClick={onClose} title="Close (Esc)">
+          ×
+        </button>
+      </div>
+      <div className="mr-readme-body">
+        {isLoading && <p className="loading">Loading commits...</p>}
+        {error && <p className="error-message">Failed to load commits</p>}
+        {commits && commits.length === 0 && (
+          <p className="mr-readme-empty">No commits synced yet.</p>
+        )}
+        {showList && (
+          <div className="mr-commit-review">
+            {changedSinceReview && (
+              <button className="mr-commit-item" onClick={() => setSinceReview(true)}>
+                <span className="mr-commit-title">Changes since your review</span>
+                <span className="mr-commit-meta">New commits were pushed after you marked it reviewed</span>
+              </button>
+            )}
+            <button
+              className="mr-readme-toggle"
+              onClick={() => markReviewed.mutate()}
+              disabled={markReviewed.isPending}
+              title="Remember the current head, to see only what changes after it"
+            >
+              {markReviewed.isPending ? 'Marking...' : 'Mark reviewed'}
+            </button>
+            {markReviewed.error && (
+              <span className="error-message">{String(markReviewed.error)}</span>
+            )}
+          </div>
+        )}
+        {commits && showList && (
+          <ol className="mr-commit-list">
+            {commits.map((commit) => (
+              <li key={commit.sha}>
+                <button className="mr-commit-item" onClick={() => setSelectedSha(commit.sha)}>
+                  <span className="mr-commit-title">{commit.title}</span>
+                  <span className="mr-commit-meta">
+                    <code>{commit.shortSha}</code> {commit.authorName} ·{' '}
+                    {new Date(commit.authoredAt * 1000).toLocaleString()}
+                  </span>
+                </button>
+              </li>
+            ))}
+          </ol>
+        )}
+        {selected && (
+          <>
+            <pre className="mr-commit-message">{selected.message.trim()}</pre>
+            {diff.isLoading && <p className="loading">Loading commit diff...</p>}
+            {diff.error && <p className="error-message">Failed to load commit diff</p>}
+            {diff.data?.files.map((file) => (
+              <CommitFile key={`${file.oldPath}:${file.newPath}`} file={file} />
+            ))}
+          </>
+        )}
+        {sinceReview && !selected && (
+          <>
+            {changes.isLoading && <p className="loading">Loading changes...</p>}
+            {changes.error && <p className="error-message">Failed to load changes since your review</p>}
+            {changes.data && (
+              <p className="mr-commit-meta">
+                <code>{changes.data.reviewedSha.slice(0, 8)}</code> →{' '}
+                <code>{changes.data.headSha.slice(0, 8)}</code>
+              </p>
+            )}
+            {changes.data?.files.length === 0 && (
+              <p className="mr-readme-empty">None of the MR's files changed since your review.</p>
+            )}
+            {changes.data?.files.map((file) => (
+              <CommitFile key={`${file.oldPath}:${file.newPath}`} file={file} />
+            ))}
+          </>
+        )}
+      </div>
+    </aside>
+  );
+}
@@ -0,0 +1,97 @@
+import { useState } from 'react';
+import { useQueryClient } from '@tanstack/react-query';
+import { queryKeys } from '../../lib/queryKeys';
+import { useMRReviewersQuery } from '../../hooks/queries/useMRReviewersQuery';
+import { assignFilesToReviewer } from '../../services/tauri';
+import { useToast } from '../../components/Toast';
+import type { FileAssignment } from '../../types';
+
+interface FileAssignmentBarProps {
+  mrId: number;
+  selectedFile: string | null;
+  assignments: FileAssignment[];
+  /** Number of files assigned to the current user. */
+  myFileCount: number;
+  onlyMine: boolean;
+  onToggleOnlyMine: () => void;
+  /** Hide the assign controls (read-only token, closed MR). */
+  readOnly: boolean;
+}
+
+/**
+ * Splits a large MR across reviewers: shows who the selected file is
+ * assigned to, assigns it to one of the MR's reviewers, and narrows the file
+ * list to the current user's slice.
+ */
+export default function FileAssignmentBar({
+  mrId,
+  selectedFile,
+  assignments,
+  myFileCount,
+  onlyMine,
+  onToggleOnlyMine,
+  readOnly,
+}: FileAssignmentBarProps) {
+  const queryClient = useQueryClient();
+  const { addToast } = useToast();
+  const { data: reviewers = [] } = useMRReviewersQuery(mrId);
+  const [assignee, setAssignee] = useState('');
+  const [assigning, setAssigning] = useState(false);
+
+  const owner = assignments.find((a) => a.filePath === selectedFile)?.username ?? null;
+  if (assignments.length === 0 && (readOnly || reviewers.length === 0)) return null;
+
+  const assign = async () => {
+    if (!selectedFile || !assignee) return;
+    setAssigning(true);
+    try {
+      const updated = await assignFilesToReviewer(mrId, [selectedFile], assignee);
+      queryClient.setQueryData(queryKeys.fileAssignments(mrId), updated);
+      queryClient.invalidateQueries({ queryKey: queryKeys.mrComments(mrId) });
+    } catch (err) {
+      addToast({
+        type: 'info',
+        title: 'Could not assign file',
+        body: err instanceof Error ? err.message : String(err),
+      });
+    } finally {
+      setAssigning(false);
+    }
+  };
+
+  return (
+    <div className="file-assignment-bar">
+      {myFileCount > 0 && (
+        <label className="file-assignment-mine">
+          <input type="checkbox" checked={onlyMine} onChange={onToggleOnlyMine} />
+          Only my files ({myFileCount})
+        </label>
+      )}
+      {selectedFile && (
+        <div className="file-assignment-owner">
+          <span>{owner ? `Assigned to @${owner}` : 'Unassigned'}</span>
+          {!readOnly && reviewers.length > 0 && (
+            <>
+              <select
+                aria-label="Reviewer for this file"
+                value={assignee}
+                onChange={(e) => setAssignee(e.target.value)}
+                disabled={assigning}
+              >
+                <option value="">Reviewer…</option>
+                {reviewers.map((r) => (
+                  <option key={r.username} value={r.username}>
+                    @{r.username}
+                  </option>
+                ))}
+              </select>
+              <button onClick={assign} disabled={!assignee || assigning}>
+                {assigning ? 'Assigning...' : 'Assign'}
+              </button>
+            </>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
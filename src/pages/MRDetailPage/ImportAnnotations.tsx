@@ -0,0 +1,75 @@
+import { useState } from 'react';
+import { useQueryClient } from '@tanstack/react-query';
+import { useToast } from '../../components/Toast';
+import { queryKeys } from '../../lib/queryKeys';
+import { importReviewAnnotations } from '../../services/tauri';
+
+interface ImportAnnotationsProps {
+  mrId: number;
+}
+
+/**
+ * Queue inline comments from a `file:line: comment` annotation file, for
+ * reviews written in an editor or with a CLI patch review tool.
+ */
+export default function ImportAnnotations({ mrId }: ImportAnnotationsProps) {
+  const queryClient = useQueryClient();
+  const { addToast } = useToast();
+  const [open, setOpen] = useState(false);
+  const [path, setPath] = useState('');
+  const [busy, setBusy] = useState(false);
+
+  async function handleImport(e: React.FormEvent) {
+    e.preventDefault();
+    if (!path.trim()) return;
+    setBusy(true);
+    try {
+      const result = await importReviewAnnotations(mrId, path.trim());
+      const count = result.comments.length;
+      addToast({ type: 'info', title: `Queued ${count} comment${count === 1 ? '' : 's'}` });
+      queryClient.invalidateQueries({ queryKey: queryKeys.mrComments(mrId) });
+      setOpen(false);
+      setPath('');
+    } catch (err) {
+      addToast({
+        type: 'info',
+        title: 'Could not import the review',
+        body: err instanceof Error ? err.message : String(err),
+      });
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  if (!open) {
+    return (
+      <button
+        className="mr-readme-toggle"
+        onClick={() => setOpen(true)}
+        title="Post comments from a file:line: comment annotation file"
+      >
+        Import review
+      </button>
+    );
+  }
+
+  return (
+    <form className="mr-import-annotations" onSubmit={handleImport}>
+      <input
+        type="text"
+        value={path}
+        onChange={(e) => setPath(e.target.value)}
+        placeholder="~/review.txt"
+        aria-label="Annotation file path"
+        disabled={busy}
+        autoFocus
+      />
+      <button type="submit" className="mr-readme-toggle" disabled={busy || !path.trim()}>
+        {busy ? 'Importing…' : 'Import'}
+      </button>
+      <button type="button" className="mr-readme-toggle" onClick={() => setOpen(false)} disabled={busy}>
+        Cancel
+      </button>
+    </form>
+  );
+}
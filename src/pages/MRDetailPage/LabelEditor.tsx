@@ -0,0 +1,98 @@
+import { useState } from 'react';
+import { useQuery, useQueryClient } from '@tanstack/react-query';
+import { useToast } from '../../components/Toast';
+import { queryKeys } from '../../lib/queryKeys';
+import { addMrLabel, getProjectLabels, removeMrLabel } from '../../services/tauri';
+import { isTauri } from '../../services/transport';
+import type { MergeRequest } from '../../types';
+
+interface LabelEditorProps {
+  mr: MergeRequest;
+  /** Show the add/remove controls; labels are read-only when false. */
+  editable: boolean;
+}
+
+/**
+ * The MR's labels in their project colors, with a picker fed by the
+ * project's cached labels. Changes apply locally and sync in the background.
+ */
+export default function LabelEditor({ mr, editable }: LabelEditorProps) {
+  const queryClient = useQueryClient();
+  const { addToast } = useToast();
+  const [busy, setBusy] = useState(false);
+  const { data: projectLabels = [] } = useQuery({
+    queryKey: queryKeys.projectLabels(mr.instanceId, mr.projectId),
+    queryFn: () => getProjectLabels(mr.instanceId, mr.projectId),
+    enabled: isTauri,
+  });
+
+  const colors = new Map(projectLabels.map((label) => [label.name, label]));
+  const available = projectLabels.filter((label) => !mr.labels.includes(label.name));
+
+  async function update(label: string, add: boolean) {
+    setBusy(true);
+    try {
+      const labels = add ? await addMrLabel(mr.id, label) : await removeMrLabel(mr.id, label);
+      queryClient.setQueryData<MergeRequest>(queryKeys.mr(mr.id), (prev) => prev && { ...prev, labels });
+      queryClient.invalidateQueries({ queryKey: ['mrList'] });
+      queryClient.invalidateQueries({ queryKey: ['myMRList'] });
+    } catch (err) {
+      addToast({
+        type: 'info',
+        title: add ? 'Could not add label' : 'Could not remove label',
+        body: err instanceof Error ? err.message : String(err),
+      });
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  if (mr.labels.length === 0 && !editable) return null;
+
+  return (
+    <span className="mr-detail-labels">
+      {mr.labels.map((name) => {
+        const label = colors.get(name);
+        return (
+          <span
+            key={name}
+            className="mr-detail-label"
+            title={label?.description ?? undefined}
+            style={label ? { background: label.color, color: label.textColor ?? undefined } : undefined}
+          >
+            {name}
+            {editable && (
+              <button
+                type="button"
+                className="mr-detail-label-remove"
+                onClick={() => update(name, false)}
+                disabled={busy}
+                title={`Remove ${name}`}
+              >
+                ×
+              </button>
+            )}
+          </span>
+        );
+      })}
+      {editable && available.length > 0 && (
+        <select
+          className="mr-detail-label-add"
+          value=""
+          disabled={busy}
+          onChange={(e) => update(e.target.value, true)}
+          title="Add a label"
+        >
+          <option value="" disabled>
+            + Label
+          </option>
+          {available.map((label) => (
+            <option key={label.name} value={label.name}>
+              {label.name}
+            </option>
+          ))}
+        </select>
+      )}
+    </span>
+  );
+}
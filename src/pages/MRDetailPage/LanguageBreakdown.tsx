@@ -0,0 +1,37 @@
+import { useQuery } from '@tanstack/react-query';
+import { queryKeys } from '../../lib/queryKeys';
+import { getMrLanguageBreakdown } from '../../services/tauri';
+
+/** Languages named in the header; the rest are listed in the tooltip. */
+const MAX_SHOWN = 3;
+
+function percent(lines: number, total: number): number {
+  return Math.round((lines / total) * 100);
+}
+
+/**
+ * "70% Rust, 20% SQL, 10% YAML" — what the MR changes, by lines per
+ * language, to tell at a glance who should review it.
+ */
+export default function LanguageBreakdown({ mrId }: { mrId: number }) {
+  const { data } = useQuery({
+    queryKey: queryKeys.mrLanguages(mrId),
+    queryFn: () => getMrLanguageBreakdown(mrId),
+    enabled: mrId > 0,
+  });
+  if (!data || data.totalLines === 0) return null;
+
+  const { languages, totalLines } = data;
+  const shown = languages
+    .slice(0, MAX_SHOWN)
+    .filter((l) => percent(l.lines, totalLines) > 0)
+    .map((l) => `${percent(l.lines, totalLines)}% ${l.language}`);
+  const title = languages.map((l) => `${l.language}: ${l.lines} lines`).join('\n');
+
+  return (
+    <span className="mr-languages" title={title} data-testid="mr-languages">
+      {shown.join(', ')}
+      {languages.length > shown.length && ', …'}
+    </span>
+  );
+}
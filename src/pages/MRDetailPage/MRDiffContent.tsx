@@ -1,7 +1,11 @@
+import { useCallback, useState } from 'react';
 import { PierreDiffViewer } from '../../components/PierreDiffViewer';
 import type { LineComment, DiffLineClickInfo } from '../../components/PierreDiffViewer/PierreDiffViewer';
 import type { SelectedLineRange } from '../../components/PierreDiffViewer';
 import { ImageDiffViewer } from '../../components/ImageDiffViewer';
+import { AccessibleDiffView } from '../../components/AccessibleDiffView';
+import { useAccessibleDiffQuery } from '../../hooks/queries/useAccessibleDiffQuery';
+import { useFileBlameQuery } from '../../hooks/queries/useFileBlameQuery';
 import { isImageFile, getImageMimeType } from '../../utils/languageDetection';
 import type { DiffRefs, DiffFileSummary } from '../../types';
 
@@ -15,6 +19,9 @@ interface MRDiffContentProps {
   fileContentLoading: boolean;
   fileContentError: string | null;
   viewMode: 'unified' | 'split';
+  /** Render the linearized screen-reader diff instead of the visual viewer. */
+  accessibleDiff: boolean;
+  mrId: number;
   mrIid: number;
   instanceId?: number;
   comments?: LineComment[];
@@ -25,6 +32,8 @@ interface MRDiffContentProps {
   onDeleteComment?: (commentId: number) => void;
   onReply?: (discussionId: string, parentId: number, body: string) => Promise<void>;
   onResolve?: (discussionId: string, resolved: boolean) => Promise<void>;
+  /** Open a file of the project's local clone in the external editor. */
+  onOpenInEditor?: (filePath: string, line?: number) => void;
   bottomPadding?: number;
 }
 
@@ -38,6 +47,8 @@ export default function MRDiffContent({
   fileContentLoading,
   fileContentError,
   viewMode,
+  accessibleDiff,
+  mrId,
   mrIid,
   instanceId,
   comments,
@@ -48,8 +59,28 @@ export default function MRDiffContent({
   onDeleteComment,
   onReply,
   onResolve,
+  onOpenInEditor,
   bottomPadding,
 }: MRDiffContentProps) {
+  const showAccessible = accessibleDiff && !!selectedFile && !isImageFile(selectedFile);
+  const accessible = useAccessibleDiffQuery(mrId, selectedFile, showAccessible);
+
+  // Blame is fetched once a line of the new version is selected in the file.
+  const [blameFile, setBlameFile] = useState<string | null>(null);
+  const blame = useFileBlameQuery(
+    mrId,
+    selectedFile,
+    diffRefs?.headSha ?? '',
+    !!selectedFile && blameFile === selectedFile,
+  );
+  const handleLineSelected = useCallback(
+    (range: SelectedLineRange | null) => {
+      if (range && range.side !== 'deletions') setBlameFile(selectedFile);
+      onLineSelected?.(range);
+    },
+    [onLineSelected, selectedFile],
+  );
+
   if (!selectedFile) {
     if (files.length > 0 && reviewableFiles.length === 0) {
       return (
@@ -107,7 +138,17 @@ export default function MRDiffContent({
         />
       )}
 
-      {!isImageFile(selectedFile) && !fileContentLoading && !fileContentError && diffRefs && (
+      {showAccessible && accessible.data && <AccessibleDiffView diff={accessible.data} />}
+
+      {showAccessible && accessible.error && (
+        <div className="file-loading-overlay">
+          <div className="file-error">
+            <p>Accessible diff not available for this file.</p>
+          </div>
+        </div>
+      )}
+
+      {!showAccessible && !isImageFile(selectedFile) && !fileContentLoading && !fileContentError && diffRefs && (
         <PierreDiffViewer
           oldContent={fileContent.original}
           newContent={fileContent.modified}
@@ -118,11 +159,13 @@ export default function MRDiffContent({
           comments={comments}
           instanceId={instanceId}
           onLineClick={onLineClick}
-          onLineSelected={onLineSelected}
+          onLineSelected={handleLineSelected}
           currentUser={currentUser}
           onDeleteComment={onDeleteComment}
           onReply={onReply}
           onResolve={onResolve}
+          blame={blame.data?.ranges}
+          onOpenInEditor={onOpenInEditor && ((line) => onOpenInEditor(selectedFile, line))}
         />
       )}
     </main>
@@ -1,3 +1,4 @@
+import type { ReactNode } from 'react';
 import { FileNavigation } from '../../components/FileNavigation';
 import type { DiffFileSummary } from '../../types';
 
@@ -11,8 +12,11 @@ interface MRFilePanelProps {
   mobileSidebarOpen: boolean;
   isSmallScreen: boolean;
   onSelect: (path: string) => void;
+  onToggleViewed: (path: string, viewed: boolean) => void;
   onToggleHideGenerated: () => void;
   onCloseMobileSidebar: () => void;
+  /** Shown above the file list. */
+  header?: ReactNode;
 }
 
 export default function MRFilePanel({
@@ -25,8 +29,10 @@ export default function MRFilePanel({
   mobileSidebarOpen,
   isSmallScreen,
   onSelect,
+  onToggleViewed,
   onToggleHideGenerated,
   onCloseMobileSidebar,
+  header,
 }: MRFilePanelProps) {
   return (
     <>
@@ -34,12 +40,14 @@ export default function MRFilePanel({
         <div className="mobile-sidebar-backdrop" onClick={onCloseMobileSidebar} onKeyDown={(e) => { if (e.key === 'Enter' || e.key === ' ') onCloseMobileSidebar(); }} role="button" tabIndex={0} aria-label="Close sidebar" />
       )}
       <aside className={`mr-detail-sidebar${mobileSidebarOpen ? ' mobile-open' : ''}`}>
+        {header}
         <FileNavigation
           files={files}
           selectedPath={selectedPath ?? undefined}
           onSelect={onSelect}
           focusIndex={focusIndex}
           viewedPaths={viewedPaths}
+          onToggleViewed={onToggleViewed}
           generatedPaths={generatedPaths}
           hideGenerated={hideGenerated}
           onToggleHideGenerated={onToggleHideGenerated}
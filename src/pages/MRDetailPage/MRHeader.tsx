@@ -1,8 +1,17 @@
-import type { RefObject } from 'react';
+import { useState, type RefObject } from 'react';
 import { ApprovalButton, type ApprovalButtonRef } from '../../components/Approval';
 import BackButton from '../../components/BackButton';
 import UserAvatar from '../../components/UserAvatar/UserAvatar';
+import { useMRParticipantsQuery } from '../../hooks/queries/useMRParticipantsQuery';
 import type { MergeRequest } from '../../types';
+import CheckoutBranch from './CheckoutBranch';
+import ImportAnnotations from './ImportAnnotations';
+import LabelEditor from './LabelEditor';
+import LanguageBreakdown from './LanguageBreakdown';
+import MilestonePicker from './MilestonePicker';
+import MRPeoplePicker from './MRPeoplePicker';
+import ParticipantList from './ParticipantList';
+import PingAuthorMenu from './PingAuthorMenu';
 
 interface MRHeaderProps {
   mr: MergeRequest;
@@ -16,6 +25,22 @@ interface MRHeaderProps {
   onUnapproved?: (trigger: 'button' | 'keyboard') => void;
   /** Hide approval button for merged/closed MRs */
   hideApproval?: boolean;
+  /** Toggle the project README panel; the button is hidden when omitted. */
+  onToggleReadme?: () => void;
+  /** Toggle the commit-by-commit panel; the button is hidden when omitted. */
+  onToggleCommits?: () => void;
+  /** Start a comment mentioning a participant; participants aren't clickable when omitted. */
+  onMentionParticipant?: (username: string) => void;
+  /** Show the review annotation import (needs local file access). */
+  canImportAnnotations?: boolean;
+  /** Show the local checkout button (needs a local clone and git). */
+  canCheckout?: boolean;
+  /** Show the label add/remove controls. */
+  canEditLabels?: boolean;
+  /** Show the milestone select. */
+  canEditMilestone?: boolean;
+  /** Show the reviewer and assignee pickers. */
+  canEditPeople?: boolean;
 }
 
 export default function MRHeader({
@@ -29,7 +54,19 @@ export default function MRHeader({
   onApproved,
   onUnapproved,
   hideApproval,
+  onToggleReadme,
+  onToggleCommits,
+  onMentionParticipant,
+  canImportAnnotations,
+  canCheckout,
+  canEditLabels = false,
+  canEditMilestone = false,
+  canEditPeople = false,
 }: MRHeaderProps) {
+  const [pickerRole, setPickerRole] = useState<'reviewer' | 'assignee' | null>(null);
+  const { data: participants = [] } = useMRParticipantsQuery(mrId);
+  const assignees = participants.filter((p) => p.roles.includes('assignee')).map((p) => p.username);
+
   return (
     <header className="mr-detail-header">
       <div className="mr-header-top">
@@ -55,6 +92,34 @@ export default function MRHeader({
           {updateAvailable && (
             <span className="mr-update-tag">Update available</span>
           )}
+          {mr.changedSinceReview && (
+            <span className="mr-update-tag" title="New commits since you marked this MR reviewed">
+              New since review
+            </span>
+          )}
+          {onToggleReadme && (
+            <button
+              className="mr-readme-toggle"
+              onClick={onToggleReadme}
+              title="Show the project README"
+            >
+              README
+            </button>
+          )}
+          {onToggleCommits && (
+            <button
+              className="mr-readme-toggle"
+              onClick={onToggleCommits}
+              title="Review the MR commit by commit"
+            >
+              Commits
+            </button>
+          )}
+          {canImportAnnotations && <ImportAnnotations mrId={mrId} />}
+          {canCheckout && <CheckoutBranch mrId={mrId} />}
+          {!hideApproval && (
+            <PingAuthorMenu mrId={mrId} authorUsername={mr.authorUsername} />
+          )}
           {!hideApproval && (
             <ApprovalButton
               ref={approvalButtonRef}
@@ -81,8 +146,30 @@ export default function MRHeader({
           <span className="mr-branches">
             {mr.sourceBranch} → {mr.targetBranch}
           </span>
+          <LanguageBreakdown mrId={mrId} />
+          <LabelEditor mr={mr} editable={canEditLabels} />
+          <MilestonePicker mr={mr} editable={canEditMilestone} />
+          <ParticipantList mrId={mrId} instanceId={mr.instanceId} onMention={onMentionParticipant} />
+          {canEditPeople && (
+            <>
+              <button className="mr-people-edit" onClick={() => setPickerRole('reviewer')} title="Change reviewers">
+                Reviewers
+              </button>
+              <button className="mr-people-edit" onClick={() => setPickerRole('assignee')} title="Change assignees">
+                Assignees
+              </button>
+            </>
+          )}
         </div>
       </div>
+      {pickerRole && (
+        <MRPeoplePicker
+          mr={mr}
+          role={pickerRole}
+          currentUsernames={pickerRole === 'reviewer' ? mr.reviewers : assignees}
+          onClose={() => setPickerRole(null)}
+        />
+      )}
     </header>
   );
 }
@@ -0,0 +1,132 @@
+import { useEffect, useRef, useState } from 'react';
+import { keepPreviousData, useQuery, useQueryClient } from '@tanstack/react-query';
+import UserAvatar from '../../components/UserAvatar/UserAvatar';
+import { useToast } from '../../components/Toast';
+import { queryKeys } from '../../lib/queryKeys';
+import { searchProjectMembers, setMrAssignees, setMrReviewers } from '../../services/tauri';
+import type { MergeRequest } from '../../types';
+
+interface MRPeoplePickerProps {
+  mr: MergeRequest;
+  role: 'reviewer' | 'assignee';
+  currentUsernames: string[];
+  onClose: () => void;
+}
+
+/**
+ * Dialog for replacing an MR's reviewers or assignees, searching the
+ * project's (cached) members. The change applies locally and syncs in the
+ * background.
+ */
+export default function MRPeoplePicker({ mr, role, currentUsernames, onClose }: MRPeoplePickerProps) {
+  const queryClient = useQueryClient();
+  const { addToast } = useToast();
+  const [selected, setSelected] = useState<string[]>(currentUsernames);
+  const [query, setQuery] = useState('');
+  const [busy, setBusy] = useState(false);
+  const inputRef = useRef<HTMLInputElement>(null);
+  const title = role === 'reviewer' ? 'Reviewers' : 'Assignees';
+
+  useEffect(() => {
+    inputRef.current?.focus();
+  }, []);
+
+  const { data: members = [], isLoading } = useQuery({
+    queryKey: queryKeys.projectMembers(mr.instanceId, mr.projectId, query.trim()),
+    queryFn: () => searchProjectMembers(mr.instanceId, mr.projectId, query.trim()),
+    placeholderData: keepPreviousData,
+  });
+
+  const toggle = (username: string) => {
+    setSelected((prev) =>
+      prev.includes(username) ? prev.filter((u) => u !== username) : [...prev, username],
+    );
+  };
+
+  async function apply() {
+    setBusy(true);
+    try {
+      await (role === 'reviewer' ? setMrReviewers(mr.id, selected) : setMrAssignees(mr.id, selected));
+      queryClient.invalidateQueries({ queryKey: queryKeys.mr(mr.id) });
+      queryClient.invalidateQueries({ queryKey: queryKeys.mrParticipants(mr.id) });
+      queryClient.invalidateQueries({ queryKey: queryKeys.mrReviewers(mr.id) });
+      queryClient.invalidateQueries({ queryKey: ['mrList'] });
+      queryClient.invalidateQueries({ queryKey: ['myMRList'] });
+      onClose();
+    } catch (err) {
+      addToast({
+        type: 'info',
+        title: `Could not update ${title.toLowerCase()}`,
+        body: err instanceof Error ? err.message : String(err),
+      });
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  return (
+    <div className="mr-people-overlay" onClick={onClose}>
+      <div
+        className="mr-people-dialog"
+        role="dialog"
+        aria-modal="true"
+        aria-label={`Change ${title.toLowerCase()}`}
+        onClick={(e) => e.stopPropagation()}
+      >
+        <h3>{title}</h3>
+        {selected.length > 0 && (
+          <div className="mr-people-selected">
+            {selected.map((username) => (
+              <button
+                key={username}
+                type="button"
+                className="mr-detail-label"
+                onClick={() => toggle(username)}
+                title={`Remove ${username}`}
+              >
+                @{username} ×
+              </button>
+            ))}
+          </div>
+        )}
+        <input
+          ref={inputRef}
+          className="mr-people-filter"
+          type="text"
+          placeholder="Search members…"
+          value={query}
+          onChange={(e) => setQuery(e.target.value)}
+          onKeyDown={(e) => {
+            if (e.key === 'Escape') onClose();
+          }}
+        />
+        <div className="mr-people-list">
+          {isLoading && <div className="mr-people-empty">Loading members…</div>}
+          {!isLoading && members.length === 0 && (
+            <div className="mr-people-empty">No members found</div>
+          )}
+          {members.map((m) => (
+            <label key={m.id} className="mr-people-row">
+              <input
+                type="checkbox"
+                checked={selected.includes(m.username)}
+                onChange={() => toggle(m.username)}
+              />
+              <UserAvatar instanceId={mr.instanceId} username={m.username} size={20} />
+              <span className="mr-people-name">{m.name}</span>
+              <span className="mr-people-username">@{m.username}</span>
+            </label>
+          ))}
+        </div>
+        <div className="mr-people-actions">
+          <button type="button" className="secondary-button" onClick={onClose} disabled={busy}>
+            Cancel
+          </button>
+          <button type="button" className="primary-button" onClick={apply} disabled={busy}>
+            {busy ? 'Saving…' : 'Apply'}
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,88 @@
+import { useState } from 'react';
+import { useQuery, useQueryClient } from '@tanstack/react-query';
+import { useToast } from '../../components/Toast';
+import { queryKeys } from '../../lib/queryKeys';
+import { getProjectMilestones, setMrMilestone } from '../../services/tauri';
+import type { MergeRequest } from '../../types';
+
+interface MilestonePickerProps {
+  mr: MergeRequest;
+  /** Show a select over the project's milestones; read-only when false. */
+  editable: boolean;
+}
+
+/**
+ * The MR's milestone, with a select fed by the project's cached milestones.
+ * Changes apply locally and sync in the background.
+ */
+export default function MilestonePicker({ mr, editable }: MilestonePickerProps) {
+  const queryClient = useQueryClient();
+  const { addToast } = useToast();
+  const [busy, setBusy] = useState(false);
+  const { data: milestones = [] } = useQuery({
+    queryKey: queryKeys.projectMilestones(mr.instanceId, mr.projectId),
+    queryFn: () => getProjectMilestones(mr.instanceId, mr.projectId),
+    enabled: editable,
+  });
+
+  async function update(value: string) {
+    setBusy(true);
+    try {
+      const milestone = await setMrMilestone(mr.id, value ? Number(value) : null);
+      queryClient.setQueryData<MergeRequest>(
+        queryKeys.mr(mr.id),
+        (prev) =>
+          prev && {
+            ...prev,
+            milestoneId: milestone?.id ?? null,
+            milestoneTitle: milestone?.title ?? null,
+            milestoneDueDate: milestone?.dueDate ?? null,
+          },
+      );
+      queryClient.invalidateQueries({ queryKey: ['mrList'] });
+      queryClient.invalidateQueries({ queryKey: ['myMRList'] });
+    } catch (err) {
+      addToast({
+        type: 'info',
+        title: 'Could not change milestone',
+        body: err instanceof Error ? err.message : String(err),
+      });
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  const title = mr.milestoneDueDate ? `Milestone, due ${mr.milestoneDueDate}` : 'Milestone';
+
+  if (!editable || (milestones.length === 0 && mr.milestoneId == null)) {
+    return mr.milestoneTitle ? (
+      <span className="mr-detail-milestone" title={title}>
+        ◷ {mr.milestoneTitle}
+      </span>
+    ) : null;
+  }
+
+  // Keep the current milestone selectable even once it's closed upstream.
+  const options =
+    mr.milestoneId != null && !milestones.some((m) => m.id === mr.milestoneId)
+      ? [{ id: mr.milestoneId, title: mr.milestoneTitle ?? `#${mr.milestoneId}`, dueDate: mr.milestoneDueDate }, ...milestones]
+      : milestones;
+
+  return (
+    <select
+      className="mr-detail-milestone-select"
+      value={mr.milestoneId ?? ''}
+      disabled={busy}
+      onChange={(e) => update(e.target.value)}
+      title={title}
+    >
+      <option value="">◷ No milestone</option>
+      {options.map((m) => (
+        <option key={m.id} value={m.id}>
+          ◷ {m.title}
+          {m.dueDate ? ` (${m.dueDate})` : ''}
+        </option>
+      ))}
+    </select>
+  );
+}
@@ -0,0 +1,54 @@
+import UserAvatar from '../../components/UserAvatar/UserAvatar';
+import { useMRParticipantsQuery } from '../../hooks/queries/useMRParticipantsQuery';
+import type { Participant, ParticipantRole } from '../../types';
+import { formatRelativeTime } from '../../utils/formatRelativeTime';
+
+const ROLE_LABELS: Record<ParticipantRole, string> = {
+  author: 'author',
+  assignee: 'assignee',
+  reviewer: 'reviewer',
+  approver: 'approved',
+  commenter: 'commenter',
+};
+
+function describe(p: Participant): string {
+  const parts = [`@${p.username}`, p.roles.map((r) => ROLE_LABELS[r]).join(', ')];
+  if (p.commentCount > 0) {
+    const comments = `${p.commentCount} comment${p.commentCount === 1 ? '' : 's'}`;
+    parts.push(p.lastCommentAt ? `${comments}, last ${formatRelativeTime(p.lastCommentAt)}` : comments);
+  }
+  return parts.join(' · ');
+}
+
+interface ParticipantListProps {
+  mrId: number;
+  instanceId: number;
+  /** Start a comment addressed to the user; the list is read-only when omitted. */
+  onMention?: (username: string) => void;
+}
+
+/**
+ * Avatar strip of everyone involved in the MR. Clicking a person starts a
+ * reply that mentions them.
+ */
+export default function ParticipantList({ mrId, instanceId, onMention }: ParticipantListProps) {
+  const { data: participants = [] } = useMRParticipantsQuery(mrId);
+  if (participants.length === 0) return null;
+
+  return (
+    <div className="mr-participants" data-testid="mr-participants">
+      {participants.map((p) => (
+        <button
+          key={p.username}
+          className={`mr-participant${p.roles.includes('approver') ? ' approved' : ''}`}
+          onClick={onMention ? () => onMention(p.username) : undefined}
+          disabled={!onMention}
+          title={onMention ? `${describe(p)}\nClick to reply` : describe(p)}
+        >
+          <UserAvatar instanceId={instanceId} username={p.username} size={20} dataUri={p.avatar} />
+          {p.commentCount > 0 && <span className="mr-participant-count">{p.commentCount}</span>}
+        </button>
+      ))}
+    </div>
+  );
+}
@@ -0,0 +1,166 @@
+import { useState } from 'react';
+import { useQueryClient } from '@tanstack/react-query';
+import { queryKeys } from '../../lib/queryKeys';
+import { usePendingReviewQuery } from '../../hooks/queries/usePendingReviewQuery';
+import { describeBlockers, describeUnmet } from '../../hooks/useActivityData';
+import { deleteDraftComment, discardReview, submitReview } from '../../services/tauri';
+import { useToast } from '../../components/Toast';
+
+interface PendingReviewBannerProps {
+  mrId: number;
+  /** Offer to approve along with the review. */
+  canApprove: boolean;
+  onSelectFile: (filePath: string) => void;
+}
+
+/**
+ * Shows the drafts of a pending review and submits them, with an optional
+ * summary and approval, as one batch. Hidden while no review is pending.
+ */
+export default function PendingReviewBanner({
+  mrId,
+  canApprove,
+  onSelectFile,
+}: PendingReviewBannerProps) {
+  const queryClient = useQueryClient();
+  const { addToast } = useToast();
+  const { data: review } = usePendingReviewQuery(mrId);
+  const [expanded, setExpanded] = useState(false);
+  const [summary, setSummary] = useState('');
+  const [approve, setApprove] = useState(false);
+  const [busy, setBusy] = useState(false);
+
+  if (!review) return null;
+
+  const refresh = () => {
+    queryClient.invalidateQueries({ queryKey: queryKeys.pendingReview(mrId) });
+  };
+
+  const submit = async () => {
+    setBusy(true);
+    try {
+      const request = {
+        mrId,
+        summary: summary.trim() || undefined,
+        approve: canApprove && approve,
+      };
+      let result = await submitReview(request);
+      if (result.blockers) {
+        if (!confirm(`${describeBlockers(result.blockers)}. Approve anyway?`)) return;
+        result = await submitReview({ ...request, confirm: true });
+      }
+      if (!result.submitted) {
+        addToast({ type: 'info', title: 'Review not submitted', body: describeUnmet(result.unmet) });
+        return;
+      }
+      setSummary('');
+      setApprove(false);
+      setExpanded(false);
+      queryClient.invalidateQueries({ queryKey: queryKeys.mrComments(mrId) });
+      queryClient.invalidateQueries({ queryKey: queryKeys.mr(mrId) });
+      refresh();
+    } catch (err) {
+      addToast({
+        type: 'info',
+        title: 'Could not submit review',
+        body: err instanceof Error ? err.message : String(err),
+      });
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const discard = async () => {
+    setBusy(true);
+    try {
+      await discardReview(mrId);
+      refresh();
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const count = review.comments.length;
+
+  return (
+    <div className="mr-pending-review">
+      <div className="mr-security-banner-summary">
+        <span>
+          Pending review: {count} draft comment{count === 1 ? '' : 's'}
+        </span>
+        <div className="mr-state-banner-actions">
+          <button
+            className="mr-state-banner-btn"
+            onClick={() => setExpanded((e) => !e)}
+            aria-expanded={expanded}
+          >
+            {expanded ? 'Hide' : 'Finish review'}
+          </button>
+          <button className="mr-state-banner-btn" onClick={discard} disabled={busy}>
+            Discard
+          </button>
+        </div>
+      </div>
+      {expanded && (
+        <div className="mr-pending-review-body">
+          <ul className="mr-pending-review-drafts">
+            {review.comments.map((draft) => (
+              <li key={draft.id} className="mr-pending-review-draft">
+                {draft.filePath ? (
+                  <button
+                    className="mr-security-location"
+                    onClick={() => onSelectFile(draft.filePath!)}
+                    title="Open file"
+                  >
+                    {draft.filePath}:{draft.newLine ?? draft.oldLine}
+                  </button>
+                ) : (
+                  <span>General</span>
+                )}
+                <span className="mr-pending-review-text">{draft.body}</span>
+                <button
+                  className="mr-state-banner-btn"
+                  onClick={async () => {
+                    await deleteDraftComment(mrId, draft.id);
+                    refresh();
+                  }}
+                  disabled={busy}
+                >
+                  Remove
+                </button>
+              </li>
+            ))}
+          </ul>
+          <textarea
+            className="comment-textarea"
+            value={summary}
+            onChange={(e) => setSummary(e.target.value)}
+            placeholder="Review summary (optional)"
+            rows={3}
+            disabled={busy}
+          />
+          <div className="mr-pending-review-actions">
+            {canApprove && (
+              <label>
+                <input
+                  type="checkbox"
+                  checked={approve}
+                  onChange={(e) => setApprove(e.target.checked)}
+                  disabled={busy}
+                />{' '}
+                Approve
+              </label>
+            )}
+            <button
+              className="comment-input-submit"
+              onClick={submit}
+              disabled={busy || (count === 0 && !summary.trim() && !approve)}
+            >
+              {busy ? 'Submitting...' : 'Submit review'}
+            </button>
+          </div>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,70 @@
+import { useState } from 'react';
+import { useQuery, useQueryClient } from '@tanstack/react-query';
+import { useToast } from '../../components/Toast';
+import { queryKeys } from '../../lib/queryKeys';
+import { getAuthorPings, pingAuthor } from '../../services/tauri';
+import type { PingTemplate } from '../../types';
+import { formatRelativeTime } from '../../utils/formatRelativeTime';
+
+const TEMPLATES: { value: PingTemplate; label: string }[] = [
+  { value: 'awaiting_reply', label: 'Waiting on your reply' },
+  { value: 'open_threads', label: 'Unresolved threads' },
+  { value: 'rebase', label: 'Please rebase' },
+];
+
+interface PingAuthorMenuProps {
+  mrId: number;
+  authorUsername: string;
+}
+
+/**
+ * Nudge the MR author with a templated comment that links the relevant
+ * unresolved threads. The backend refuses repeats until the author reacts.
+ */
+export default function PingAuthorMenu({ mrId, authorUsername }: PingAuthorMenuProps) {
+  const queryClient = useQueryClient();
+  const { addToast } = useToast();
+  const [busy, setBusy] = useState(false);
+  const { data: pings = [] } = useQuery({
+    queryKey: queryKeys.mrAuthorPings(mrId),
+    queryFn: () => getAuthorPings(mrId),
+  });
+
+  async function handlePing(template: PingTemplate) {
+    setBusy(true);
+    try {
+      await pingAuthor(mrId, template);
+      addToast({ type: 'info', title: `Pinged @${authorUsername}` });
+      queryClient.invalidateQueries({ queryKey: queryKeys.mrAuthorPings(mrId) });
+      queryClient.invalidateQueries({ queryKey: queryKeys.mrComments(mrId) });
+    } catch (err) {
+      addToast({
+        type: 'info',
+        title: 'Could not ping the author',
+        body: err instanceof Error ? err.message : String(err),
+      });
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  const lastPing = pings[0];
+  return (
+    <select
+      className="mr-readme-toggle mr-ping-author"
+      value=""
+      disabled={busy}
+      onChange={(e) => handlePing(e.target.value as PingTemplate)}
+      title={lastPing ? `Last pinged ${formatRelativeTime(lastPing.pingedAt)}` : `Ping @${authorUsername}`}
+    >
+      <option value="" disabled>
+        Ping author
+      </option>
+      {TEMPLATES.map((t) => (
+        <option key={t.value} value={t.value}>
+          {t.label}
+        </option>
+      ))}
+    </select>
+  );
+}
@@ -0,0 +1,69 @@
+import { useEffect, useMemo, type MouseEvent } from 'react';
+import { useProjectReadmeQuery } from '../../hooks/queries/useProjectReadmeQuery';
+import { openExternalUrl } from '../../services/transport';
+import { sanitizeHtml } from '../../utils/sanitizeHtml';
+import type { MergeRequest } from '../../types';
+import '../../components/Markdown.css';
+
+interface ProjectReadmePanelProps {
+  mr: MergeRequest;
+  onClose: () => void;
+}
+
+/**
+ * Side panel with the project's README at the MR's target branch, for
+ * reviewers who are new to the project.
+ */
+export default function ProjectReadmePanel({ mr, onClose }: ProjectReadmePanelProps) {
+  const { data: readme, isLoading, error } = useProjectReadmeQuery(
+    mr.instanceId,
+    mr.projectId,
+    mr.targetBranch,
+  );
+  const html = useMemo(() => (readme?.html ? sanitizeHtml(readme.html) : ''), [readme?.html]);
+
+  useEffect(() => {
+    const onKeyDown = (e: KeyboardEvent) => {
+      if (e.key === 'Escape') onClose();
+    };
+    window.addEventListener('keydown', onKeyDown);
+    return () => window.removeEventListener('keydown', onKeyDown);
+  }, [onClose]);
+
+  // Links open in the system browser instead of navigating the app away.
+  function handleClick(e: MouseEvent<HTMLDivElement>) {
+    const link = (e.target as HTMLElement).closest('a');
+    const href = link?.getAttribute('href');
+    if (!link || !href || href.startsWith('#')) return;
+    e.preventDefault();
+    openExternalUrl(link.href);
+  }
+
+  return (
+    <aside className="mr-readme-panel" aria-label="Project README">
+      <div className="mr-readme-header">
+        <span className="mr-readme-title">
+          {readme?.filePath ?? 'README'}
+          <span className="mr-readme-ref">{mr.targetBranch}</span>
+        </span>
+        <button className="mr-readme-close" onClick={onClose} title="Close (Esc)">
+          ×
+        </button>
+      </div>
+      <div className="mr-readme-body">
+        {isLoading && <p className="loading">Loading README...</p>}
+        {error && <p className="error-message">Failed to load README</p>}
+        {readme && !readme.filePath && (
+          <p className="mr-readme-empty">This project has no README on {mr.targetBranch}.</p>
+        )}
+        {html && (
+          <div
+            className="mr-readme-content md-body"
+            onClick={handleClick}
+            dangerouslySetInnerHTML={{ __html: html }}
+          />
+        )}
+      </div>
+    </aside>
+  );
+}
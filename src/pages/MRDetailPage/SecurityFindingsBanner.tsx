@@ -0,0 +1,67 @@
+import { useState } from 'react';
+import type { SecurityFinding } from '../../types';
+
+interface SecurityFindingsBannerProps {
+  findings: SecurityFinding[];
+  onSelectFile: (filePath: string) => void;
+}
+
+const SEVERITY_ORDER: Record<SecurityFinding['severity'], number> = {
+  high: 0,
+  medium: 1,
+  low: 2,
+};
+
+/**
+ * Warns about added lines that look like leaked credentials. Collapsed by
+ * default; expanding lists each finding with a link to its file.
+ */
+export default function SecurityFindingsBanner({ findings, onSelectFile }: SecurityFindingsBannerProps) {
+  const [expanded, setExpanded] = useState(false);
+  if (findings.length === 0) return null;
+
+  const sorted = [...findings].sort(
+    (a, b) => SEVERITY_ORDER[a.severity] - SEVERITY_ORDER[b.severity],
+  );
+  const worst = sorted[0].severity;
+  const highCount = findings.filter((f) => f.severity === 'high').length;
+  const label =
+    highCount > 0
+      ? `${highCount} likely secret${highCount === 1 ? '' : 's'} added in this diff`
+      : `${findings.length} possible secret${findings.length === 1 ? '' : 's'} added in this diff`;
+
+  return (
+    <div className={`mr-security-banner mr-security-banner--${worst}`}>
+      <div className="mr-security-banner-summary">
+        <span>{label}</span>
+        <button
+          className="mr-state-banner-btn"
+          onClick={() => setExpanded((e) => !e)}
+          aria-expanded={expanded}
+        >
+          {expanded ? 'Hide' : 'Review'}
+        </button>
+      </div>
+      {expanded && (
+        <ul className="mr-security-findings">
+          {sorted.map((f) => (
+            <li key={`${f.filePath}:${f.line}`} className="mr-security-finding">
+              <span className={`mr-security-severity mr-security-severity--${f.severity}`}>
+                {f.severity}
+              </span>
+              <button
+                className="mr-security-location"
+                onClick={() => onSelectFile(f.filePath)}
+                title="Open file"
+              >
+                {f.filePath}:{f.line}
+              </button>
+              <span className="mr-security-message">{f.message}</span>
+              <code className="mr-security-excerpt">{f.excerpt}</code>
+            </li>
+          ))}
+        </ul>
+      )}
+    </div>
+  );
+}
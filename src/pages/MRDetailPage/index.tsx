@@ -8,10 +8,12 @@ import { useState, useEffect, useCallback, useRef, useMemo } from 'react';
 import { useParams, useNavigate } from 'react-router-dom';
 import type { ApprovalButtonRef } from '../../components/Approval';
 import { CommentOverlay, type CommentOverlayRef } from '../../components/CommentOverlay';
-import { ActivityDrawer, ActivityFeed, CommentInput } from '../../components/ActivityDrawer';
+import { ActivityDrawer, ActivityFeed, ActivityTimeline, CommentInput } from '../../components/ActivityDrawer';
 import { useActivityData } from '../../hooks/useActivityData';
+import { useCommentCursor } from '../../hooks/useCommentCursor';
 import type { DiffLineClickInfo } from '../../components/PierreDiffViewer';
 import type { SelectedLineRange } from '../../components/PierreDiffViewer';
+import type { CommentSort, DiffFileSummary } from '../../types';
 import { useFileContent } from '../../hooks/useFileContent';
 import { useCopyToast } from '../../hooks/useCopyToast';
 import { useSmallScreen } from '../../hooks/useSmallScreen';
@@ -23,10 +25,26 @@ import MRHeader from './MRHeader';
 import MRDiffContent from './MRDiffContent';
 import MRFilePanel from './MRFilePanel';
 import MRFooter from './MRFooter';
+import SecurityFindingsBanner from './SecurityFindingsBanner';
+import PendingReviewBanner from './PendingReviewBanner';
+import FileAssignmentBar from './FileAssignmentBar';
+import AssistantSummary from './AssistantSummary';
+import ProjectReadmePanel from './ProjectReadmePanel';
+import CommitsPanel from './CommitsPanel';
+import { MarkdownProjectScope } from '../../components/Markdown';
 import { deleteComment } from '../../services/gitlab';
-import { openExternalUrl } from '../../services/transport';
+import { openInEditor } from '../../services/tauri';
+import { isTauri, openExternalUrl } from '../../services/transport';
+import { useToast } from '../../components/Toast';
 import { useCurrentUserQuery } from '../../hooks/queries/useCurrentUserQuery';
 import { useSettingsQuery } from '../../hooks/queries/useSettingsQuery';
+import { useInstancesQuery } from '../../hooks/queries/useInstancesQuery';
+import { useSecurityFindingsQuery } from '../../hooks/queries/useSecurityFindingsQuery';
+import { useFileAssignmentsQuery } from '../../hooks/queries/useFileAssignmentsQuery';
+import {
+  useFileReviewStatesQuery,
+  useMarkFileViewedMutation,
+} from '../../hooks/queries/useFileReviewStatesQuery';
 import { trackMRApproved, trackMRUnapproved, trackCommentPosted, trackReplyPosted } from '../../services/analytics';
 import { computeNextFileIndex } from '../../utils/fileNavigation';
 import '../MRDetailPage.css';
@@ -35,6 +53,13 @@ interface MRDetailPageProps {
   updateAvailable?: boolean;
 }
 
+const COMMENT_SORTS: { value: CommentSort; label: string }[] = [
+  { value: 'unresolved_first', label: 'Unresolved first' },
+  { value: 'oldest', label: 'Oldest first' },
+  { value: 'newest', label: 'Newest first' },
+  { value: 'by_file', label: 'By file' },
+];
+
 export default function MRDetailPage({ updateAvailable }: MRDetailPageProps) {
   const { id } = useParams<{ id: string }>();
   const navigate = useNavigate();
@@ -47,12 +72,29 @@ export default function MRDetailPage({ updateAvailable }: MRDetailPageProps) {
   const previousFileRef = useRef<string | null>(null);
 
   const [activityOpen, setActivityOpen] = useState(false);
+  const [mention, setMention] = useState<{ username: string; at: number } | null>(null);
   const [showSystemEvents, setShowSystemEvents] = useState(false);
+  const [activityView, setActivityView] = useState<'comments' | 'timeline'>('comments');
+  const [commentSort, setCommentSort] = useState<CommentSort>('unresolved_first');
   const [activityHeightVh, setActivityHeightVh] = useState(40);
-  const { threads: activityThreads, systemEvents: activitySystemEvents, unresolvedCount, currentUser: activityCurrentUser, loading: activityLoading, error: activityError, addComment: activityAddComment, replyToComment: activityReplyToComment, resolveDiscussion: activityResolveDiscussion, deleteComment: activityDeleteComment } = useActivityData(mrId);
+  const [readmeOpen, setReadmeOpen] = useState(false);
+  const [commitsOpen, setCommitsOpen] = useState(false);
+  const { addToast } = useToast();
+  const closeReadme = useCallback(() => setReadmeOpen(false), []);
+  const closeCommits = useCallback(() => setCommitsOpen(false), []);
+  const { threads: activityThreads, systemEvents: activitySystemEvents, unresolvedCount, currentUser: activityCurrentUser, loading: activityLoading, error: activityError, addComment: activityAddComment, submitReview: activitySubmitReview, replyToComment: activityReplyToComment, resolveDiscussion: activityResolveDiscussion, deleteComment: activityDeleteComment, applySuggestion: activityApplySuggestion } = useActivityData(mrId, commentSort);
+  const commentCursor = useCommentCursor(mrId);
   const [showCopyToast, copyToClipboard] = useCopyToast();
   const isSmallScreen = useSmallScreen();
   const [view, dispatch] = useViewReducer();
+  const { data: securityFindings = [] } = useSecurityFindingsQuery(mrId);
+  const { data: instances = [] } = useInstancesQuery();
+  const { data: fileReviewStates } = useFileReviewStatesQuery(mrId);
+  const { mutate: markFileViewed } = useMarkFileViewedMutation(mrId);
+  const viewedPaths = useMemo(
+    () => new Set((fileReviewStates ?? []).map((s) => s.filePath)),
+    [fileReviewStates]
+  );
 
   const effectiveViewMode = isSmallScreen ? 'unified' : view.viewMode;
 
@@ -84,6 +126,23 @@ export default function MRDetailPage({ updateAvailable }: MRDetailPageProps) {
   const currentUserQuery = useCurrentUserQuery(mr?.instanceId ?? 0);
   const currentUser = currentUserQuery.data ?? null;
 
+  // File-level assignments: optionally narrow the list to my slice
+  const { data: fileAssignments = [] } = useFileAssignmentsQuery(mrId);
+  const [onlyMyFiles, setOnlyMyFiles] = useState(false);
+  const myPaths = useMemo(
+    () => new Set(fileAssignments.filter((a) => a.username === currentUser).map((a) => a.filePath)),
+    [fileAssignments, currentUser]
+  );
+  const inMySlice = useCallback(
+    (f: DiffFileSummary) => myPaths.has(f.newPath) || (f.oldPath !== null && myPaths.has(f.oldPath)),
+    [myPaths]
+  );
+  const sliceActive = onlyMyFiles && myPaths.size > 0;
+  const panelFiles = useMemo(
+    () => (sliceActive ? files.filter(inMySlice) : files),
+    [sliceActive, files, inMySlice]
+  );
+
   const handleDeleteComment = useCallback((commentId: number) => {
     const toRestore = fileComments.find((c) => c.id === commentId);
     removeComment(commentId);
@@ -129,7 +188,10 @@ export default function MRDetailPage({ updateAvailable }: MRDetailPageProps) {
     previousFileRef.current = filePath;
   }, [files, dispatch]);
 
-  const navigableFiles = view.hideGenerated ? reviewableFiles : files;
+  const navigableFiles = useMemo(() => {
+    const base = view.hideGenerated ? reviewableFiles : files;
+    return sliceActive ? base.filter(inMySlice) : base;
+  }, [view.hideGenerated, reviewableFiles, files, sliceActive, inMySlice]);
 
   const navigateFile = useCallback(
     (direction: number) => {
@@ -143,12 +205,17 @@ export default function MRDetailPage({ updateAvailable }: MRDetailPageProps) {
 
   const markViewedAndNext = useCallback(() => {
     if (!view.selectedFile) return;
-    dispatch({ type: 'MARK_VIEWED', path: view.selectedFile });
+    markFileViewed({ filePath: view.selectedFile, viewed: true });
     const currentIdx = navigableFiles.findIndex((f) => f.newPath === view.selectedFile);
     if (currentIdx < navigableFiles.length - 1) {
       navigateFile(1);
     }
-  }, [view.selectedFile, dispatch, navigateFile, navigableFiles]);
+  }, [view.selectedFile, markFileViewed, navigateFile, navigableFiles]);
+
+  const handleToggleViewed = useCallback(
+    (filePath: string, viewed: boolean) => markFileViewed({ filePath, viewed }),
+    [markFileViewed],
+  );
 
   const handleToggleViewMode = useCallback(() => {
     dispatch({
@@ -169,6 +236,35 @@ export default function MRDetailPage({ updateAvailable }: MRDetailPageProps) {
     lineSelectionRef.current = range;
   }, []);
 
+  const instanceId = mr?.instanceId;
+  const projectId = mr?.projectId;
+  const handleOpenInEditor = useCallback(
+    (filePath: string, line?: number) => {
+      if (instanceId == null || projectId == null) return;
+      openInEditor(instanceId, projectId, filePath, line).catch((err) =>
+        addToast({
+          type: 'info',
+          title: 'Could not open the editor',
+          body: err instanceof Error ? err.message : String(err),
+        })
+      );
+    },
+    [instanceId, projectId, addToast],
+  );
+
+  // Open the drawer and scroll to the first comment since the last visit,
+  // or to the newest one when everything was read.
+  const jumpToComment = useCallback(() => {
+    const target = commentCursor?.firstUnreadId ?? commentCursor?.latestId;
+    if (target == null) return;
+    setActivityOpen(true);
+    requestAnimationFrame(() => {
+      document
+        .querySelector(`[data-comment-id="${target}"]`)
+        ?.scrollIntoView({ block: 'center', behavior: 'smooth' });
+    });
+  }, [commentCursor]);
+
   // Cmd+D toggles activity drawer (skip when focus is in text input/textarea)
   useEffect(() => {
     const handler = (e: KeyboardEvent) => {
@@ -194,6 +290,7 @@ export default function MRDetailPage({ updateAvailable }: MRDetailPageProps) {
     onNavigateFile: navigateFile,
     fileJumpCount: settings?.fileJumpCount,
     onToggleViewMode: handleToggleViewMode,
+    onToggleAccessibleDiff: () => dispatch({ type: 'TOGGLE_ACCESSIBLE_DIFF' }),
     onMarkViewedAndNext: markViewedAndNext,
     onToggleHideGenerated: () => dispatch({ type: 'TOGGLE_HIDE_GENERATED' }),
     onCopyLink: copyToClipboard,
@@ -220,115 +317,261 @@ export default function MRDetailPage({ updateAvailable }: MRDetailPageProps) {
   }
 
   const isMergedOrClosed = mr.state === 'merged' || mr.state === 'closed';
+  // Unknown token scopes leave everything enabled.
+  const readOnlyToken = instances.find((i) => i.id === mr.instanceId)?.canWrite === false;
 
   return (
-    <div className="mr-detail-page">
-      {isMergedOrClosed && (
-        <div className={`mr-state-banner ${mr.state}`}>
-          <span>
-            This merge request has been {mr.state === 'closed' ? 'closed' : 'merged'}
-          </span>
-          <div className="mr-state-banner-actions">
-            {mr.webUrl && (
-              <button className="mr-state-banner-btn" onClick={() => openExternalUrl(mr.webUrl)}>
-                Open in GitLab
+    <MarkdownProjectScope.Provider value={{ instanceId: mr.instanceId, projectId: mr.projectId }}>
+      <div className="mr-detail-page">
+        {isMergedOrClosed && (
+          <div className={`mr-state-banner ${mr.state}`}>
+            <span>
+              This merge request has been {mr.state === 'closed' ? 'closed' : 'merged'}
+            </span>
+            <div className="mr-state-banner-actions">
+              {mr.webUrl && (
+                <button className="mr-state-banner-btn" onClick={() => openExternalUrl(mr.webUrl)}>
+                  Open in GitLab
+                </button>
+              )}
+              <button className="mr-state-banner-btn" onClick={() => navigate('/mrs')}>
+                Back to list
               </button>
-            )}
-            <button className="mr-state-banner-btn" onClick={() => navigate('/mrs')}>
-              Back to list
-            </button>
+            </div>
           </div>
-        </div>
-      )}
-
-      <MRHeader
-        mr={mr}
-        mrId={mrId}
-        updateAvailable={updateAvailable}
-        isSmallScreen={isSmallScreen}
-        fileCount={files.length}
-        approvalButtonRef={approvalButtonRef}
-        onToggleMobileSidebar={() => dispatch({ type: 'TOGGLE_MOBILE_SIDEBAR' })}
-        onApproved={(trigger) => {
-          trackMRApproved(mrId, Math.round((Date.now() - mrEnteredAtRef.current) / 1000), trigger);
-          navigate('/mrs');
-        }}
-        onUnapproved={(trigger) => trackMRUnapproved(mrId, trigger)}
-        hideApproval={isMergedOrClosed}
-      />
-
-      <div className="mr-detail-content">
-        <MRFilePanel
-          files={files}
-          selectedPath={view.selectedFile}
-          focusIndex={view.fileFocusIndex}
-          viewedPaths={view.viewedPaths}
-          generatedPaths={generatedPaths}
-          hideGenerated={view.hideGenerated}
-          mobileSidebarOpen={view.mobileSidebarOpen}
+        )}
+
+        {!isMergedOrClosed && readOnlyToken && (
+          <div className="mr-state-banner locked">
+            <span>This instance's token is read-only (read_api). Add the api scope to comment and approve.</span>
+          </div>
+        )}
+
+        {!isMergedOrClosed && mr.discussionLocked && (
+          <div className="mr-state-banner locked">
+            <span>Discussion is locked. Only project members can comment.</span>
+          </div>
+        )}
+
+        <MRHeader
+          mr={mr}
+          mrId={mrId}
+          updateAvailable={updateAvailable}
           isSmallScreen={isSmallScreen}
-          onSelect={handleFileSelect}
-          onToggleHideGenerated={() => dispatch({ type: 'TOGGLE_HIDE_GENERATED' })}
-          onCloseMobileSidebar={() => dispatch({ type: 'CLOSE_MOBILE_SIDEBAR' })}
+          fileCount={files.length}
+          approvalButtonRef={approvalButtonRef}
+          onToggleMobileSidebar={() => dispatch({ type: 'TOGGLE_MOBILE_SIDEBAR' })}
+          onApproved={(trigger) => {
+            trackMRApproved(mrId, Math.round((Date.now() - mrEnteredAtRef.current) / 1000), trigger);
+            navigate('/mrs');
+          }}
+          onUnapproved={(trigger) => trackMRUnapproved(mrId, trigger)}
+          hideApproval={isMergedOrClosed || readOnlyToken}
+          onToggleReadme={isTauri ? () => { setCommitsOpen(false); setReadmeOpen((open) => !open); } : undefined}
+          onToggleCommits={() => { setReadmeOpen(false); setCommitsOpen((open) => !open); }}
+          onMentionParticipant={
+            readOnlyToken
+              ? undefined
+              : (username) => { setActivityOpen(true); setMention({ username, at: Date.now() }); }
+          }
+          canImportAnnotations={isTauri && !readOnlyToken}
+          canCheckout={isTauri}
+          canEditLabels={isTauri && !readOnlyToken && !isMergedOrClosed}
+          canEditMilestone={isTauri && !readOnlyToken && !isMergedOrClosed}
+          canEditPeople={isTauri && !readOnlyToken && !isMergedOrClosed}
         />
 
-        <MRDiffContent
+        {!isMergedOrClosed && (
+          <SecurityFindingsBanner findings={securityFindings} onSelectFile={handleFileSelect} />
+        )}
+
+        {!isMergedOrClosed && !readOnlyToken && (
+          <PendingReviewBanner
+            mrId={mrId}
+            canApprove={!mr.userHasApproved}
+            onSelectFile={handleFileSelect}
+          />
+        )}
+
+        {settings?.reviewAssistant?.enabled && <AssistantSummary key={mrId} mrId={mrId} />}
+
+        <div className="mr-detail-content">
+          <MRFilePanel
+            files={panelFiles}
+            selectedPath={view.selectedFile}
+            focusIndex={view.fileFocusIndex}
+            viewedPaths={viewedPaths}
+            generatedPaths={generatedPaths}
+            hideGenerated={view.hideGenerated}
+            mobileSidebarOpen={view.mobileSidebarOpen}
+            isSmallScreen={isSmallScreen}
+            onSelect={handleFileSelect}
+            onToggleViewed={handleToggleViewed}
+            onToggleHideGenerated={() => dispatch({ type: 'TOGGLE_HIDE_GENERATED' })}
+            onCloseMobileSidebar={() => dispatch({ type: 'CLOSE_MOBILE_SIDEBAR' })}
+            header={
+              <FileAssignmentBar
+                mrId={mrId}
+                selectedFile={view.selectedFile}
+                assignments={fileAssignments}
+                myFileCount={myPaths.size}
+                onlyMine={sliceActive}
+                onToggleOnlyMine={() => setOnlyMyFiles((on) => !on)}
+                readOnly={isMergedOrClosed || readOnlyToken}
+              />
+            }
+          />
+
+          <MRDiffContent
+            selectedFile={view.selectedFile}
+            files={files}
+            reviewableFiles={reviewableFiles}
+            diffRefs={diffRefs}
+            fileContent={fileContent}
+            imageContent={imageContent}
+            fileContentLoading={fileContentLoading}
+            fileContentError={fileContentError}
+            viewMode={effectiveViewMode}
+            accessibleDiff={view.accessibleDiff}
+            mrId={mrId}
+            mrIid={mr.iid}
+            instanceId={mr.instanceId}
+            comments={fileComments}
+            onLineClick={handleLineClick}
+            onLineSelected={handleLineSelected}
+            onRetry={() => view.selectedFile && handleFileSelect(view.selectedFile)}
+            currentUser={currentUser ?? undefined}
+            onDeleteComment={handleDeleteComment}
+            onReply={async (discussionId, parentId, body) => { await activityReplyToComment(discussionId, parentId, body); trackReplyPosted(mrId); }}
+            onResolve={activityResolveDiscussion}
+            onOpenInEditor={isTauri ? handleOpenInEditor : undefined}
+            bottomPadding={activityOpen ? activityHeightVh : undefined}
+          />
+          {readmeOpen && <ProjectReadmePanel mr={mr} onClose={closeReadme} />}
+          {commitsOpen && (
+            <CommitsPanel
+              mrId={mrId}
+              changedSinceReview={mr.changedSinceReview}
+              onClose={closeCommits}
+            />
+          )}
+        </div>
+
+        <CommentOverlay
+          ref={commentOverlayRef}
+          mrId={mrId}
           selectedFile={view.selectedFile}
-          files={files}
-          reviewableFiles={reviewableFiles}
-          diffRefs={diffRefs}
-          fileContent={fileContent}
-          imageContent={imageContent}
-          fileContentLoading={fileContentLoading}
-          fileContentError={fileContentError}
-          viewMode={effectiveViewMode}
-          mrIid={mr.iid}
-          instanceId={mr.instanceId}
-          comments={fileComments}
-          onLineClick={handleLineClick}
-          onLineSelected={handleLineSelected}
-          onRetry={() => view.selectedFile && handleFileSelect(view.selectedFile)}
-          currentUser={currentUser ?? undefined}
-          onDeleteComment={handleDeleteComment}
-          onReply={async (discussionId, parentId, body) => { await activityReplyToComment(discussionId, parentId, body); trackReplyPosted(mrId); }}
-          onResolve={activityResolveDiscussion}
-          bottomPadding={activityOpen ? activityHeightVh : undefined}
         />
-      </div>
 
-      <CommentOverlay
-        ref={commentOverlayRef}
-        mrId={mrId}
-        selectedFile={view.selectedFile}
-      />
-
-      <ActivityDrawer
-        isOpen={activityOpen}
-        onToggle={() => setActivityOpen((o) => !o)}
-        showSystemEvents={showSystemEvents}
-        onToggleSystemEvents={() => setShowSystemEvents((s) => !s)}
-        heightVh={activityHeightVh}
-        onHeightChange={setActivityHeightVh}
-        footer={<CommentInput onSubmit={async (body) => { await activityAddComment(body); trackCommentPosted(mrId); }} />}
-      >
-        <ActivityFeed
-          threads={activityThreads}
-          systemEvents={activitySystemEvents}
+        <ActivityDrawer
+          isOpen={activityOpen}
+          onToggle={() => setActivityOpen((o) => !o)}
           showSystemEvents={showSystemEvents}
-          loading={activityLoading}
-          error={activityError}
-          currentUser={activityCurrentUser}
-          onReply={async (discussionId, parentId, body) => { await activityReplyToComment(discussionId, parentId, body); trackReplyPosted(mrId); }}
-          onResolve={activityResolveDiscussion}
-          onDelete={activityDeleteComment}
-        />
-      </ActivityDrawer>
-
-      {showCopyToast && (
-        <div className="copy-toast">Link copied</div>
-      )}
-
-      <MRFooter unresolvedCount={unresolvedCount} onToggleActivity={() => setActivityOpen((o) => !o)} />
-    </div>
+          onToggleSystemEvents={() => setShowSystemEvents((s) => !s)}
+          heightVh={activityHeightVh}
+          onHeightChange={setActivityHeightVh}
+          headerActions={
+            <>
+              <div className="activity-drawer__tabs" role="tablist" aria-label="Activity view">
+                {(['comments', 'timeline'] as const).map((view) => (
+                  <button
+                    key={view}
+                    type="button"
+                    role="tab"
+                    aria-selected={activityView === view}
+                    className={`activity-drawer__tab${activityView === view ? ' activity-drawer__tab--active' : ''}`}
+                    onClick={() => setActivityView(view)}
+                  >
+                    {view === 'comments' ? 'Comments' : 'Timeline'}
+                  </button>
+                ))}
+              </div>
+              {activityView === 'comments' && commentCursor?.latestId != null && (
+                <button type="button" className="activity-drawer__jump" onClick={jumpToComment}>
+                  {commentCursor.unreadCount > 0
+                    ? `Jump to first unread (${commentCursor.unreadCount})`
+                    : 'Jump to newest'}
+                </button>
+              )}
+              {activityView === 'comments' && (
+                <select
+                  className="activity-drawer__sort"
+                  value={commentSort}
+                  onChange={(e) => setCommentSort(e.target.value as CommentSort)}
+                  aria-label="Sort comments"
+                >
+                  {COMMENT_SORTS.map((option) => (
+                    <option key={option.value} value={option.value}>
+                      {option.label}
+                    </option>
+                  ))}
+                </select>
+              )}
+            </>
+          }
+          footer={
+            <CommentInput
+              mention={mention}
+              onSubmit={async (body) => {
+                try {
+                  await activityAddComment(body);
+                } catch (err) {
+                  addToast({
+                    type: 'info',
+                    title: 'Could not send and approve',
+                    body: err instanceof Error ? err.message : String(err),
+                  });
+                  throw err;
+                }
+                trackCommentPosted(mrId);
+              }}
+              onSubmitAndApprove={
+                isMergedOrClosed || readOnlyToken || mr.userHasApproved
+                  ? undefined
+                  : async (body) => {
+                      try {
+                        await activitySubmitReview(body);
+                      } catch (err) {
+                        addToast({
+                          type: 'info',
+                          title: 'Could not send and approve',
+                          body: err instanceof Error ? err.message : String(err),
+                        });
+                        throw err;
+                      }
+                      trackCommentPosted(mrId);
+                      trackMRApproved(mrId, Math.round((Date.now() - mrEnteredAtRef.current) / 1000), 'button');
+                    }
+              }
+            />
+          }
+        >
+          {activityView === 'timeline' ? (
+            <ActivityTimeline mrId={mrId} />
+          ) : (
+            <ActivityFeed
+              threads={activityThreads}
+              systemEvents={activitySystemEvents}
+              showSystemEvents={showSystemEvents}
+              sort={commentSort}
+              firstUnreadId={commentCursor?.firstUnreadId}
+              loading={activityLoading}
+              error={activityError}
+              currentUser={activityCurrentUser}
+              onReply={async (discussionId, parentId, body) => { await activityReplyToComment(discussionId, parentId, body); trackReplyPosted(mrId); }}
+              onResolve={activityResolveDiscussion}
+              onDelete={activityDeleteComment}
+              onApplySuggestion={readOnlyToken || isMergedOrClosed ? undefined : activityApplySuggestion}
+            />
+          )}
+        </ActivityDrawer>
+
+        {showCopyToast && (
+          <div className="copy-toast">Link copied</div>
+        )}
+
+        <MRFooter unresolvedCount={unresolvedCount} onToggleActivity={() => setActivityOpen((o) => !o)} />
+      </div>
+    </MarkdownProjectScope.Provider>
   );
 }
@@ -20,6 +20,7 @@ interface UseMRKeyboardOptions {
   onNavigateFile: (direction: number) => void;
   fileJumpCount?: number;
   onToggleViewMode: () => void;
+  onToggleAccessibleDiff: () => void;
   onMarkViewedAndNext: () => void;
   onToggleHideGenerated: () => void;
   onCopyLink: (url: string) => void;
@@ -37,6 +38,7 @@ export function useMRKeyboard({
   onNavigateFile,
   fileJumpCount = DEFAULT_FILE_JUMP_COUNT,
   onToggleViewMode,
+  onToggleAccessibleDiff,
   onMarkViewedAndNext,
   onToggleHideGenerated,
   onCopyLink,
@@ -95,6 +97,11 @@ export function useMRKeyboard({
     onToggleViewMode();
   }, { enabled: !isSmallScreen });
 
+  useHotkey(parseHotkey(getKey('toggle-accessible-diff') ?? 'Shift+X'), () => {
+    trackShortcut('Shift+X', 'toggle_accessible_diff', 'mr_detail');
+    onToggleAccessibleDiff();
+  });
+
   // --- Review actions ---
   useHotkey(parseHotkey(getKey('approve') ?? 'a'), () => {
     trackShortcut('a', 'toggle_approval', 'mr_detail');
@@ -6,8 +6,9 @@ export interface ViewState {
   viewMode: 'unified' | 'split';
   collapseState: 'collapsed' | 'expanded' | 'partial';
   mobileSidebarOpen: boolean;
-  viewedPaths: Set<string>;
   hideGenerated: boolean;
+  /** Show the linearized screen-reader diff instead of the visual viewer. */
+  accessibleDiff: boolean;
 }
 
 type ViewAction =
@@ -16,8 +17,8 @@ type ViewAction =
   | { type: 'SET_COLLAPSE'; state: 'collapsed' | 'expanded' | 'partial' }
   | { type: 'TOGGLE_MOBILE_SIDEBAR' }
   | { type: 'CLOSE_MOBILE_SIDEBAR' }
-  | { type: 'MARK_VIEWED'; path: string }
-  | { type: 'TOGGLE_HIDE_GENERATED' };
+  | { type: 'TOGGLE_HIDE_GENERATED' }
+  | { type: 'TOGGLE_ACCESSIBLE_DIFF' };
 
 export const initialViewState: ViewState = {
   selectedFile: null,
@@ -25,8 +26,8 @@ export const initialViewState: ViewState = {
   viewMode: 'unified',
   collapseState: 'collapsed',
   mobileSidebarOpen: false,
-  viewedPaths: new Set(),
   hideGenerated: true,
+  accessibleDiff: false,
 };
 
 function viewReducer(state: ViewState, action: ViewAction): ViewState {
@@ -47,10 +48,10 @@ function viewReducer(state: ViewState, action: ViewAction): ViewState {
       return { ...state, mobileSidebarOpen: !state.mobileSidebarOpen };
     case 'CLOSE_MOBILE_SIDEBAR':
       return { ...state, mobileSidebarOpen: false };
-    case 'MARK_VIEWED':
-      return { ...state, viewedPaths: new Set(state.viewedPaths).add(action.path) };
     case 'TOGGLE_HIDE_GENERATED':
       return { ...state, hideGenerated: !state.hideGenerated };
+    case 'TOGGLE_ACCESSIBLE_DIFF':
+      return { ...state, accessibleDiff: !state.accessibleDiff };
   }
 }
 
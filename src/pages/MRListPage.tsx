@@ -8,13 +8,15 @@ import { useState, useEffect, useCallback, useRef, useMemo } from 'react';
 import { useNavigate, useLocation } from 'react-router-dom';
 import { useQueryClient } from '@tanstack/react-query';
 import { MRList } from '../components/MRList';
-import type { MergeRequest } from '../types';
+import type { InstanceListView, ListViewState, MergeRequest } from '../types';
 import { useKeyboardNav } from '../hooks/useKeyboardNav';
 import { useListSearch } from '../hooks/useListSearch';
 import { useCondensedModeAnnouncement } from '../hooks/useCondensedModeAnnouncement';
 import SearchBar from '../components/SearchBar/SearchBar';
 import { useInstancesQuery } from '../hooks/queries/useInstancesQuery';
 import { useSettingsQuery } from '../hooks/queries/useSettingsQuery';
+import { useListViewStateQuery } from '../hooks/queries/useListViewStateQuery';
+import { useUpdateListViewStateMutation } from '../hooks/queries/useUpdateListViewStateMutation';
 import { InstanceSwitcher } from '../components/InstanceSwitcher';
 import { queryKeys } from '../lib/queryKeys';
 import { ShortcutBar } from '../components/ShortcutBar';
@@ -29,6 +31,14 @@ const defaultShortcuts: ShortcutDef[] = [
   { key: '?', label: 'help' },
 ];
 
+const DEFAULT_LIST_VIEW_STATE: ListViewState = {
+  lastInstanceId: null,
+  groupByProject: false,
+  instances: {},
+};
+
+const EMPTY_INSTANCE_VIEW: InstanceListView = { projectFilter: [], projectOrder: [] };
+
 const searchShortcuts: ShortcutDef[] = [
   { key: '↑/↓', label: 'navigate' },
   { key: 'Enter', label: 'open' },
@@ -48,6 +58,9 @@ export default function MRListPage() {
   const settingsQuery = useSettingsQuery();
   const condensed = settingsQuery.data?.mrListCondensed ?? false;
   useCondensedModeAnnouncement();
+  const viewStateQuery = useListViewStateQuery();
+  const viewState = viewStateQuery.data ?? DEFAULT_LIST_VIEW_STATE;
+  const updateViewState = useUpdateListViewStateMutation();
   const [selectedInstanceId, setSelectedInstanceId] = useState<number | null>(null);
   const [mrs, setMrs] = useState<MergeRequest[]>([]);
   const [showApproved, setShowApproved] = useState(false);
@@ -86,12 +99,41 @@ export default function MRListPage() {
     setFilteredCounts(counts);
   }, []);
 
-  // Auto-select first instance when instances load
+  // Select the last used instance (or the first one) once instances and the
+  // stored view state have loaded
+  const lastInstanceId = viewState.lastInstanceId;
   useEffect(() => {
-    if (instances.length > 0 && !selectedInstanceId) {
-      setSelectedInstanceId(instances[0].id);
-    }
-  }, [instances, selectedInstanceId]);
+    if (instances.length === 0 || selectedInstanceId || viewStateQuery.isLoading) return;
+    const last = instances.find((i) => i.id === lastInstanceId);
+    setSelectedInstanceId(last?.id ?? instances[0].id);
+  }, [instances, selectedInstanceId, lastInstanceId, viewStateQuery.isLoading]);
+
+  const handleSelectInstance = useCallback(
+    (id: number) => {
+      setSelectedInstanceId(id);
+      if (id !== viewState.lastInstanceId) {
+        updateViewState.mutate({ ...viewState, lastInstanceId: id });
+      }
+    },
+    [viewState, updateViewState]
+  );
+
+  const instanceView =
+    (selectedInstanceId != null ? viewState.instances[String(selectedInstanceId)] : undefined) ??
+    EMPTY_INSTANCE_VIEW;
+
+  const updateInstanceView = useCallback(
+    (patch: Partial<InstanceListView>) => {
+      if (selectedInstanceId == null) return;
+      const key = String(selectedInstanceId);
+      const current = viewState.instances[key] ?? EMPTY_INSTANCE_VIEW;
+      updateViewState.mutate({
+        ...viewState,
+        instances: { ...viewState.instances, [key]: { ...current, ...patch } },
+      });
+    },
+    [selectedInstanceId, viewState, updateViewState]
+  );
 
   // Sync MRs from MRList component (for keyboard navigation)
   const handleMRsLoaded = useCallback((loadedMrs: MergeRequest[]) => {
@@ -194,7 +236,7 @@ export default function MRListPage() {
             <InstanceSwitcher
               instances={instances}
               selectedId={selectedInstanceId}
-              onSelect={setSelectedInstanceId}
+              onSelect={handleSelectInstance}
             />
             <div className="approved-toggle-wrapper">
               <button
@@ -241,6 +283,14 @@ export default function MRListPage() {
             showApproved={showApproved}
             onToggleApproved={() => setShowApproved(v => !v)}
             condensed={condensed}
+            projectFilter={instanceView.projectFilter}
+            onProjectFilterChange={(projectFilter) => updateInstanceView({ projectFilter })}
+            groupByProject={viewState.groupByProject}
+            onGroupByProjectChange={(groupByProject) =>
+              updateViewState.mutate({ ...viewState, groupByProject })
+            }
+            projectOrder={instanceView.projectOrder}
+            onProjectOrderChange={(projectOrder) => updateInstanceView({ projectOrder })}
           />
         ) : null}
       </main>
@@ -20,6 +20,7 @@ export function CommentsTab({ threads, currentUser, onDelete, onReply, onResolve
         threads={threads}
         systemEvents={[]}
         showSystemEvents={false}
+        sort="unresolved_first"
         loading={false}
         currentUser={currentUser}
         onReply={onReply}
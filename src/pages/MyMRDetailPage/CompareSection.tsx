@@ -0,0 +1,99 @@
+/**
+ * Compare section — pick another open MR of the same project and see which
+ * files both change and where their hunks collide, before either merges.
+ */
+
+import { useMemo, useState } from 'react';
+import { useMRListQuery } from '../../hooks/queries/useMRListQuery';
+import { useMyMRListQuery } from '../../hooks/queries/useMyMRListQuery';
+import { useMrComparisonQuery } from '../../hooks/queries/useMrComparisonQuery';
+import type { LineRange, MergeRequest } from '../../types';
+
+interface CompareSectionProps {
+  mr: MergeRequest;
+}
+
+function formatRange(range: LineRange): string {
+  return range.start === range.end ? `L${range.start}` : `L${range.start}–${range.end}`;
+}
+
+export function CompareSection({ mr }: CompareSectionProps) {
+  const [otherId, setOtherId] = useState<number | null>(null);
+  const { data: reviewMrs = [] } = useMRListQuery(mr.instanceId);
+  const { data: myMrs = [] } = useMyMRListQuery(mr.instanceId);
+  const { data: comparison, isFetching, error } = useMrComparisonQuery(mr.id, otherId);
+
+  const candidates = useMemo(() => {
+    const seen = new Set<number>();
+    return [...myMrs, ...reviewMrs].filter((other) => {
+      if (seen.has(other.id)) return false;
+      seen.add(other.id);
+      return other.id !== mr.id && other.projectId === mr.projectId && other.state === 'opened';
+    });
+  }, [myMrs, reviewMrs, mr.id, mr.projectId]);
+
+  if (candidates.length === 0) return null;
+
+  const conflictCount = comparison?.overlappingFiles.filter((f) => f.conflicts.length > 0).length ?? 0;
+
+  return (
+    <section className="my-mr-overview-section">
+      <h3>Compare with another MR</h3>
+      <select
+        className="my-mr-compare-select"
+        aria-label="MR to compare with"
+        value={otherId ?? ''}
+        onChange={(e) => setOtherId(e.target.value ? Number(e.target.value) : null)}
+      >
+        <option value="">Select an open MR in {mr.projectName}…</option>
+        {candidates.map((other) => (
+          <option key={other.id} value={other.id}>
+            !{other.iid} {other.title}
+          </option>
+        ))}
+      </select>
+
+      {isFetching && <p className="my-mr-merge-status-text">Comparing…</p>}
+      {error && <p className="my-mr-merge-error">{error.message}</p>}
+      {comparison && !isFetching && (
+        <div className="my-mr-compare-result">
+          {comparison.overlappingFiles.length === 0 ? (
+            <p className="my-mr-merge-status-text">No files in common — these MRs won't conflict.</p>
+          ) : (
+            <p className="my-mr-merge-status-text">
+              {comparison.overlappingFiles.length} shared file{comparison.overlappingFiles.length === 1 ? '' : 's'}
+              {conflictCount > 0
+                ? `, ${conflictCount} with colliding changes`
+                : ', no colliding changes'}
+              {comparison.mrA.targetBranch !== comparison.mrB.targetBranch &&
+                ` (targets differ: ${comparison.mrA.targetBranch} / ${comparison.mrB.targetBranch})`}
+            </p>
+          )}
+          {!comparison.sameBase && comparison.overlappingFiles.length > 0 && (
+            <p className="my-mr-compare-detail">
+              The MRs branch off different commits, so line numbers are approximate.
+            </p>
+          )}
+          <ul className="my-mr-compare-files">
+            {comparison.overlappingFiles.map((file) => (
+              <li key={file.path} className={file.conflicts.length > 0 ? 'conflict' : ''}>
+                <code>{file.path}</code>
+                {!file.diffAvailable ? (
+                  <span className="my-mr-compare-detail">diff not cached</span>
+                ) : file.conflicts.length > 0 ? (
+                  <span className="my-mr-compare-detail">
+                    {file.conflicts
+                      .map((c) => `${formatRange(c.rangeA)} ↔ ${formatRange(c.rangeB)}`)
+                      .join(', ')}
+                  </span>
+                ) : (
+                  <span className="my-mr-compare-detail">different lines</span>
+                )}
+              </li>
+            ))}
+          </ul>
+        </div>
+      )}
+    </section>
+  );
+}
@@ -2,18 +2,30 @@
  * Merge section — merge status display, merge/rebase actions.
  */
 
-import { useCallback, useEffect, useRef } from 'react';
+import { useCallback, useEffect, useRef, useState } from 'react';
 import { useQueryClient } from '@tanstack/react-query';
-import { mergeMR, checkMergeStatus, rebaseMR, undraftMR } from '../../services/tauri';
+import { mergeMR, checkMergeStatus, getMergeBlockers, rebaseMR, undraftMR } from '../../services/tauri';
 import { useToast } from '../../components/Toast/ToastContext';
 import { queryKeys } from '../../lib/queryKeys';
 import { pendingMerges } from '../../lib/pendingMerges';
 import { useAutoMerge } from '../../hooks/useAutoMerge';
-import type { MergeRequest } from '../../types';
+import { useMergeBlockersQuery } from '../../hooks/queries/useMergeBlockersQuery';
+import type { MergeCondition, MergeConditions } from '../../services/tauri';
+import type { MergeBlocker, MergeBlockers, MergeRequest } from '../../types';
 import type { MergeState, MergeAction } from './mergeReducer';
 
-function autoMergeStatusLabel(status: string | null): string {
+const MERGE_CONDITION_LABELS: Record<MergeCondition, string> = {
+  pipelineSucceeded: 'Pipeline succeeded',
+  approvalsMet: 'Approvals met',
+  threadsResolved: 'All threads resolved',
+};
+
+function autoMergeStatusLabel(status: string | null, unmet: MergeCondition[] = []): string {
   switch (status) {
+    case 'conditions_unmet':
+      return unmet.length > 0
+        ? `Waiting for: ${unmet.map((c) => MERGE_CONDITION_LABELS[c].toLowerCase()).join(', ')}`
+        : 'Waiting for merge conditions';
     case null:
       return 'Waiting for first status check...';
     case 'mergeable':
@@ -40,6 +52,32 @@ function autoMergeStatusLabel(status: string | null): string {
   }
 }
 
+function blockerLabel(blocker: MergeBlocker, data: MergeBlockers): string {
+  switch (blocker) {
+    case 'draft':
+      return 'Draft';
+    case 'conflicts':
+      return 'Merge conflicts';
+    case 'needsRebase':
+      return 'Source branch needs rebase';
+    case 'unresolvedDiscussions': {
+      const count = data.unresolvedDiscussionIds.length;
+      return `${count} unresolved thread${count === 1 ? '' : 's'}`;
+    }
+    case 'pipelineFailed':
+    case 'pipelineRunning':
+      return `Pipeline ${data.pipelineStatus?.replace(/_/g, ' ')}`;
+    case 'pipelineMissing':
+      return 'No pipeline, but one must pass';
+    case 'approvalsMissing': {
+      const missing = data.approvals.rules.filter((r) => !r.approved).map((r) => r.name);
+      return missing.length > 0
+        ? `Approval missing: ${missing.join(', ')}`
+        : `Approvals ${data.approvals.given}/${data.approvals.required}`;
+    }
+  }
+}
+
 export interface MergeActions {
   merge: (() => void) | null;
   rebase: (() => void) | null;
@@ -66,7 +104,20 @@ export function MergeSection({ mr, mergeState, mergeDispatch, mrId, setMr, actio
   const mrIid = mr.iid;
   const isDraft = mrTitle.startsWith('Draft:') || mrTitle.startsWith('WIP:');
 
-  const { claim: autoMergeClaim, isClaimed: autoMergeOn, toggle: toggleAutoMerge } = useAutoMerge(mrId);
+  const {
+    claim: autoMergeClaim,
+    isClaimed: autoMergeOn,
+    toggle: toggleAutoMerge,
+    mergeWhenReady,
+    mergeWhenReadyError,
+  } = useAutoMerge(mrId);
+  const { data: blockers } = useMergeBlockersQuery(mrId, mr.state === 'opened');
+  const [refreshingBlockers, setRefreshingBlockers] = useState(false);
+  const [whenReadyConditions, setWhenReadyConditions] = useState<MergeConditions>({
+    pipelineSucceeded: true,
+    approvalsMet: true,
+    threadsResolved: true,
+  });
 
   useEffect(() => {
     return () => {
@@ -180,6 +231,21 @@ export function MergeSection({ mr, mergeState, mergeDispatch, mrId, setMr, actio
     }
   }, [mrId, setMr, mergeDispatch, fetchMergeStatus, queryClient, instanceId, mrIid, mrTitle, addToast]);
 
+  const handleRefreshBlockers = useCallback(async () => {
+    setRefreshingBlockers(true);
+    try {
+      queryClient.setQueryData(queryKeys.mergeBlockers(mrId), await getMergeBlockers(mrId, true));
+    } catch (err) {
+      addToast({
+        type: 'info',
+        title: `Failed to check !${mrIid} on GitLab`,
+        body: err instanceof Error ? err.message : String(err),
+      });
+    } finally {
+      setRefreshingBlockers(false);
+    }
+  }, [mrId, mrIid, queryClient, addToast]);
+
   // Treat an unresolved merge status (still loading or not yet fetched) as
   // optimistically mergeable when the MR is approved, so the user does not
   // wait on GitLab before clicking Merge. A failed merge surfaces via toast.
@@ -214,8 +280,9 @@ export function MergeSection({ mr, mergeState, mergeDispatch, mrId, setMr, actio
   if (mr.state !== 'opened') return null;
 
   const autoMergeLabel = autoMergeClaim
-    ? autoMergeStatusLabel(autoMergeClaim.lastStatus)
+    ? autoMergeStatusLabel(autoMergeClaim.lastStatus, autoMergeClaim.unmetConditions)
     : null;
+  const whenReadyConditionsPicked = Object.values(whenReadyConditions).some(Boolean);
 
   return (
     <section className="my-mr-merge-section">
@@ -225,15 +292,24 @@ export function MergeSection({ mr, mergeState, mergeDispatch, mrId, setMr, actio
           <div className="my-mr-auto-merge-status">
             <span className="my-mr-auto-merge-dot" />
             <div className="my-mr-auto-merge-text">
-              <strong>Auto-merge enabled</strong>
+              <strong>{autoMergeClaim?.conditions ? 'Merge when ready' : 'Auto-merge enabled'}</strong>
               <span className="my-mr-auto-merge-detail">{autoMergeLabel}</span>
+              {autoMergeClaim?.conditions && (
+                <span className="my-mr-auto-merge-detail">
+                  Requires:{' '}
+                  {(Object.keys(MERGE_CONDITION_LABELS) as MergeCondition[])
+                    .filter((c) => autoMergeClaim.conditions?.[c])
+                    .map((c) => MERGE_CONDITION_LABELS[c].toLowerCase())
+                    .join(', ')}
+                </span>
+              )}
               {autoMergeClaim?.lastError && (
                 <span className="my-mr-auto-merge-error">{autoMergeClaim.lastError}</span>
               )}
             </div>
           </div>
           <button className="my-mr-merge-cancel" onClick={toggleAutoMerge}>
-            Cancel auto-merge
+            {autoMergeClaim?.conditions ? 'Cancel merge when ready' : 'Cancel auto-merge'}
           </button>
         </div>
       ) : isDraft || mergeStatus === 'draft_status' ? (
@@ -301,6 +377,22 @@ export function MergeSection({ mr, mergeState, mergeDispatch, mrId, setMr, actio
       ) : mergeStatusLoading ? (
         <p className="my-mr-merge-status-text">Checking merge status...</p>
       ) : null}
+      {!autoMergeOn && blockers && blockers.blockers.length > 0 && (
+        <div className="my-mr-merge-blockers">
+          <ul>
+            {blockers.blockers.map((blocker) => (
+              <li key={blocker}>{blockerLabel(blocker, blockers)}</li>
+            ))}
+          </ul>
+          <button
+            className="my-mr-merge-cancel"
+            onClick={handleRefreshBlockers}
+            disabled={refreshingBlockers}
+          >
+            {refreshingBlockers ? 'Checking...' : 'Check on GitLab'}
+          </button>
+        </div>
+      )}
       {mergeError && (
         <p className="my-mr-merge-error">{mergeError}</p>
       )}
@@ -313,6 +405,36 @@ export function MergeSection({ mr, mergeState, mergeDispatch, mrId, setMr, actio
           </span>
         </label>
       )}
+      {!autoMergeOn && (
+        <details className="my-mr-merge-when-ready">
+          <summary>Merge when ready…</summary>
+          <p className="my-mr-auto-merge-hint">
+            Like auto-merge, but also waits for these, even if the project doesn't require them.
+          </p>
+          {(Object.keys(MERGE_CONDITION_LABELS) as MergeCondition[]).map((condition) => (
+            <label key={condition} className="my-mr-merge-when-ready-condition">
+              <input
+                type="checkbox"
+                checked={whenReadyConditions[condition]}
+                onChange={(e) =>
+                  setWhenReadyConditions((prev) => ({ ...prev, [condition]: e.target.checked }))
+                }
+              />
+              <span>{MERGE_CONDITION_LABELS[condition]}</span>
+            </label>
+          ))}
+          <button
+            className="my-mr-action-btn rebase"
+            onClick={() => mergeWhenReady(whenReadyConditions)}
+            disabled={!whenReadyConditionsPicked}
+          >
+            Queue merge
+          </button>
+          {mergeWhenReadyError && (
+            <p className="my-mr-merge-error">{mergeWhenReadyError.message}</p>
+          )}
+        </details>
+      )}
     </section>
   );
 }
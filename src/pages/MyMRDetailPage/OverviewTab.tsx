@@ -2,13 +2,20 @@
  * Overview tab for MyMRDetailPage — details, description, approvals, merge.
  */
 
-import { useMemo } from 'react';
+import { useMemo, useState } from 'react';
+import { useQueryClient } from '@tanstack/react-query';
 import { formatRelativeTime, reviewerStatusClass, reviewerStatusLabel } from './utils';
 import { MergeSection } from './MergeSection';
 import { PipelinesSection } from './PipelinesSection';
+import { CompareSection } from './CompareSection';
 import type { MergeActions } from './MergeSection';
 import UserAvatar from '../../components/UserAvatar/UserAvatar';
 import Markdown, { type IssueLinkContext } from '../../components/Markdown';
+import { useToast } from '../../components/Toast/ToastContext';
+import { queryKeys } from '../../lib/queryKeys';
+import { useMrTasksQuery } from '../../hooks/queries/useMrTasksQuery';
+import { useDescriptionLintQuery } from '../../hooks/queries/useDescriptionLintQuery';
+import { requestRereview, toggleMrTask } from '../../services/tauri';
 import type { MergeRequest, MrReviewer } from '../../types';
 import type { MergeState, MergeAction } from './mergeReducer';
 
@@ -36,6 +43,48 @@ export function OverviewTab({
   onMerged,
 }: OverviewTabProps) {
   const requiredCount = mr.approvalsRequired ?? 0;
+  const queryClient = useQueryClient();
+  const { addToast } = useToast();
+  const [rerequesting, setRerequesting] = useState(false);
+  const { data: tasks } = useMrTasksQuery(mrId);
+  const { data: lint } = useDescriptionLintQuery(mr.state === 'opened' ? mrId : 0, mr.description);
+  const lintProblems = lint?.sections.filter((s) => s.status !== 'filled') ?? [];
+
+  // Without GitLab 16 review states, any non-pending reviewer counts as done.
+  const canRerequest =
+    mr.state === 'opened' &&
+    reviewers.some((r) => (r.reviewState ? r.reviewState !== 'unreviewed' : r.status !== 'pending'));
+
+  async function handleRerequest() {
+    setRerequesting(true);
+    try {
+      const asked = await requestRereview(mrId);
+      queryClient.invalidateQueries({ queryKey: queryKeys.mrReviewers(mrId) });
+      addToast({ type: 'info', title: 'Review re-requested', body: asked.join(', ') });
+    } catch (err) {
+      addToast({
+        type: 'info',
+        title: 'Could not re-request review',
+        body: err instanceof Error ? err.message : String(err),
+      });
+    } finally {
+      setRerequesting(false);
+    }
+  }
+
+  async function handleToggleTask(index: number, checked: boolean) {
+    try {
+      const next = await toggleMrTask(mrId, index, checked);
+      queryClient.setQueryData(queryKeys.mrTasks(mrId), next);
+      setMr((prev) => prev && { ...prev, taskCount: next.total, taskCompletedCount: next.completed });
+    } catch (err) {
+      addToast({
+        type: 'info',
+        title: 'Could not update task',
+        body: err instanceof Error ? err.message : String(err),
+      });
+    }
+  }
 
   const issueLinkContext = useMemo<IssueLinkContext | undefined>(() => {
     if (!mr.webUrl) return undefined;
@@ -88,6 +137,32 @@ export function OverviewTab({
         </dl>
       </section>
 
+      {tasks && tasks.total > 0 && (
+        <section className="my-mr-overview-section">
+          <h3>
+            Tasks
+            <span className="my-mr-approval-summary">
+              {tasks.completed} of {tasks.total} done
+            </span>
+          </h3>
+          <ul className="my-mr-task-list">
+            {tasks.items.map((task) => (
+              <li key={task.position}>
+                <label className="my-mr-task">
+                  <input
+                    type="checkbox"
+                    checked={task.checked}
+                    disabled={mr.state !== 'opened'}
+                    onChange={(e) => handleToggleTask(task.position, e.target.checked)}
+                  />
+                  <span>{task.text}</span>
+                </label>
+              </li>
+            ))}
+          </ul>
+        </section>
+      )}
+
       {mr.description && (
         <section className="my-mr-overview-section">
           <h3>Description</h3>
@@ -99,6 +174,27 @@ export function OverviewTab({
         </section>
       )}
 
+      {lint?.templatePath && lintProblems.length > 0 && (
+        <section className="my-mr-overview-section">
+          <h3>
+            Description check
+            <span className="my-mr-approval-summary" title={lint.templatePath}>
+              {lintProblems.length} of {lint.sections.length} template sections to fill
+            </span>
+          </h3>
+          <ul className="my-mr-lint-list">
+            {lintProblems.map((section) => (
+              <li key={section.heading} className={`my-mr-lint-item ${section.status}`}>
+                <span className="my-mr-lint-heading">{section.heading}</span>
+                <span className="my-mr-lint-status">
+                  {section.status === 'missing' ? 'missing' : 'still the template text'}
+                </span>
+              </li>
+            ))}
+          </ul>
+        </section>
+      )}
+
       <section className="my-mr-overview-section">
         <h3>
           Approvals
@@ -107,6 +203,17 @@ export function OverviewTab({
               {approvedCount} of {requiredCount} required
             </span>
           )}
+          {canRerequest && (
+            <button
+              type="button"
+              className="my-mr-rereview-button"
+              onClick={handleRerequest}
+              disabled={rerequesting}
+              title="Ask reviewers who already reviewed to look again"
+            >
+              {rerequesting ? 'Requesting…' : 'Re-request review'}
+            </button>
+          )}
         </h3>
         {reviewers.length === 0 ? (
           <p className="my-mr-no-reviewers">No reviewers assigned</p>
@@ -118,7 +225,10 @@ export function OverviewTab({
                   <UserAvatar instanceId={mr.instanceId} username={reviewer.username} size={24} />
                 </div>
                 <span className="my-mr-reviewer-name">{reviewer.username}</span>
-                <span className="my-mr-reviewer-dot" title={reviewerStatusLabel(reviewer.status)} />
+                <span
+                  className="my-mr-reviewer-dot"
+                  title={reviewer.rerequestedAt != null ? 'Review re-requested' : reviewerStatusLabel(reviewer.status)}
+                />
               </div>
             ))}
           </div>
@@ -127,6 +237,8 @@ export function OverviewTab({
 
       <PipelinesSection mrId={mrId} instanceId={mr.instanceId} projectName={mr.projectName} />
 
+      {mr.state === 'opened' && <CompareSection mr={mr} />}
+
       <MergeSection
         mr={mr}
         mergeState={mergeState}
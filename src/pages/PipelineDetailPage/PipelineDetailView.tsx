@@ -6,6 +6,9 @@ import type { PipelineJob, PipelineStatus } from '../../types';
 import PipelineHeader from './PipelineHeader';
 import JobsTab from './JobsTab';
 import HistoryTab from './HistoryTab';
+import SchedulesTab from './SchedulesTab';
+import ReleasesTab from './ReleasesTab';
+import TagsTab from './TagsTab';
 import { usePipelineData } from './usePipelineData';
 import { useAutoRun } from '../../hooks/useAutoRun';
 import { groupJobsByStage } from './utils';
@@ -15,7 +18,7 @@ import {
   trackShortcut,
 } from '../../services/analytics';
 
-type TabId = 'jobs' | 'history';
+type TabId = 'jobs' | 'history' | 'schedules' | 'releases' | 'tags';
 
 export interface PipelineDetailViewProps {
   instanceId: number;
@@ -64,8 +67,23 @@ export default function PipelineDetailView({
     pipelines,
     historyLoading,
     historyLoaded,
+    schedules,
+    schedulesLoading,
+    schedulesError,
+    scheduleActionLoading,
+    releases,
+    releasesLoading,
+    releasesError,
+    tags,
+    tagsLoading,
+    tagsError,
     refresh,
     loadHistory,
+    loadSchedules,
+    loadReleases,
+    loadTags,
+    handleRunSchedule,
+    handleToggleSchedule,
     handlePlayJob,
     handleRetryJob,
     handleCancelJob,
@@ -90,8 +108,14 @@ export default function PipelineDetailView({
   useEffect(() => {
     if (activeTab === 'history') {
       loadHistory();
+    } else if (activeTab === 'schedules') {
+      loadSchedules();
+    } else if (activeTab === 'releases') {
+      loadReleases();
+    } else if (activeTab === 'tags') {
+      loadTags();
     }
-  }, [activeTab, loadHistory]);
+  }, [activeTab, loadHistory, loadSchedules, loadReleases, loadTags]);
 
   useEffect(() => {
     if (!isActive) return;
@@ -114,6 +138,18 @@ export default function PipelineDetailView({
         e.preventDefault();
         trackShortcut('2', 'switch_tab_history', 'pipeline_detail');
         setActiveTab('history');
+      } else if (e.key === '3') {
+        e.preventDefault();
+        trackShortcut('3', 'switch_tab_schedules', 'pipeline_detail');
+        setActiveTab('schedules');
+      } else if (e.key === '4') {
+        e.preventDefault();
+        trackShortcut('4', 'switch_tab_releases', 'pipeline_detail');
+        setActiveTab('releases');
+      } else if (e.key === '5') {
+        e.preventDefault();
+        trackShortcut('5', 'switch_tab_tags', 'pipeline_detail');
+        setActiveTab('tags');
       } else if ((e.key === 'o' || e.key === 'O') && pipelineWebUrl) {
         e.preventDefault();
         trackShortcut('o', 'open_in_browser', 'pipeline_detail');
@@ -155,6 +191,9 @@ export default function PipelineDetailView({
         tabs={[
           { id: 'jobs', label: 'Jobs' },
           { id: 'history', label: 'History' },
+          { id: 'schedules', label: 'Schedules' },
+          { id: 'releases', label: 'Releases' },
+          { id: 'tags', label: 'Tags' },
         ]}
         activeTab={activeTab}
         onTabChange={(tab) => {
@@ -191,6 +230,29 @@ export default function PipelineDetailView({
         />
       )}
 
+      {activeTab === 'schedules' && (
+        <SchedulesTab
+          schedules={schedules}
+          schedulesLoading={schedulesLoading}
+          schedulesError={schedulesError}
+          onRunSchedule={handleRunSchedule}
+          onToggleSchedule={handleToggleSchedule}
+          scheduleActionLoading={scheduleActionLoading}
+        />
+      )}
+
+      {activeTab === 'releases' && (
+        <ReleasesTab
+          releases={releases}
+          releasesLoading={releasesLoading}
+          releasesError={releasesError}
+        />
+      )}
+
+      {activeTab === 'tags' && (
+        <TagsTab tags={tags} tagsLoading={tagsLoading} tagsError={tagsError} />
+      )}
+
       {showCopyToast && (
         <div className="copy-toast">Link copied</div>
       )}
@@ -0,0 +1,93 @@
+import type { ProjectRelease } from '../../types';
+import Markdown from '../../components/Markdown';
+import { openExternalUrl } from '../../services/transport';
+import { formatRelativeTime } from './utils';
+
+interface ReleasesTabProps {
+  releases: ProjectRelease[];
+  releasesLoading: boolean;
+  releasesError: boolean;
+}
+
+export default function ReleasesTab({ releases, releasesLoading, releasesError }: ReleasesTabProps) {
+  if (releasesLoading) {
+    return (
+      <main className="pipeline-detail-content">
+        <div className="pipeline-detail-loading">Loading releases...</div>
+      </main>
+    );
+  }
+
+  if (releasesError) {
+    return (
+      <main className="pipeline-detail-content">
+        <div className="pipeline-detail-error">Failed to load releases</div>
+      </main>
+    );
+  }
+
+  if (releases.length === 0) {
+    return (
+      <main className="pipeline-detail-content">
+        <div className="pipeline-detail-empty">This project has no releases.</div>
+      </main>
+    );
+  }
+
+  return (
+    <main className="pipeline-detail-content">
+      <div className="pipeline-history-list" aria-label="Releases">
+        {releases.map((r) => {
+          const date = r.releasedAt ?? r.createdAt;
+          return (
+            <div key={r.tagName} className="pipeline-history-row pipeline-release-row">
+              <div className="pipeline-history-info">
+                <span className="pipeline-history-id">
+                  {r.name || r.tagName}
+                  {r.upcomingRelease && <span className="pipeline-history-current-badge">upcoming</span>}
+                </span>
+                <div className="pipeline-job-meta">
+                  <span className="pipeline-detail-ref">{r.tagName}</span>
+                  {r.commitSha && <code className="pipeline-history-sha">{r.commitSha.slice(0, 8)}</code>}
+                  <span className="pipeline-job-time" title={new Date(date).toLocaleString()}>
+                    {formatRelativeTime(date)}
+                  </span>
+                  {r.authorUsername && <span className="pipeline-job-time">by {r.authorUsername}</span>}
+                </div>
+                {r.links.length > 0 && (
+                  <div className="pipeline-release-links">
+                    {r.links.map((link) => (
+                      <button
+                        key={link.url}
+                        className="pipeline-release-link"
+                        onClick={() => openExternalUrl(link.url)}
+                        title={link.url}
+                      >
+                        {link.name}
+                      </button>
+                    ))}
+                  </div>
+                )}
+                {r.description && (
+                  <details className="pipeline-release-notes">
+                    <summary>Changelog</summary>
+                    <Markdown content={r.description} />
+                  </details>
+                )}
+              </div>
+              {r.webUrl && (
+                <button
+                  className="pipeline-job-action-btn"
+                  onClick={() => openExternalUrl(r.webUrl!)}
+                  title="Open release in browser"
+                >
+                  <span>Open</span>
+                </button>
+              )}
+            </div>
+          );
+        })}
+      </div>
+    </main>
+  );
+}
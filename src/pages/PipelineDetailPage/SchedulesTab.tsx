@@ -0,0 +1,99 @@
+import type { PipelineSchedule } from '../../types';
+import { PlayIcon } from './icons';
+import { formatTimeUntil } from './utils';
+
+interface SchedulesTabProps {
+  schedules: PipelineSchedule[];
+  schedulesLoading: boolean;
+  schedulesError: boolean;
+  onRunSchedule: (schedule: PipelineSchedule) => void;
+  onToggleSchedule: (schedule: PipelineSchedule) => void;
+  scheduleActionLoading: Set<number>;
+}
+
+export default function SchedulesTab({
+  schedules,
+  schedulesLoading,
+  schedulesError,
+  onRunSchedule,
+  onToggleSchedule,
+  scheduleActionLoading,
+}: SchedulesTabProps) {
+  if (schedulesLoading) {
+    return (
+      <main className="pipeline-detail-content">
+        <div className="pipeline-detail-loading">Loading pipeline schedules...</div>
+      </main>
+    );
+  }
+
+  if (schedulesError) {
+    return (
+      <main className="pipeline-detail-content">
+        <div className="pipeline-detail-error">Failed to load pipeline schedules</div>
+      </main>
+    );
+  }
+
+  if (schedules.length === 0) {
+    return (
+      <main className="pipeline-detail-content">
+        <div className="pipeline-detail-empty">This project has no pipeline schedules.</div>
+      </main>
+    );
+  }
+
+  return (
+    <main className="pipeline-detail-content">
+      <div className="pipeline-history-list" aria-label="Pipeline schedules">
+        {schedules.map((s) => {
+          const busy = scheduleActionLoading.has(s.id);
+          return (
+            <div
+              key={s.id}
+              className={`pipeline-history-row pipeline-schedule-row ${s.active ? '' : 'pipeline-schedule-row--inactive'}`}
+            >
+              <div className="pipeline-history-info">
+                <span className="pipeline-history-id">
+                  {s.description || `Schedule #${s.id}`}
+                  {!s.active && <span className="pipeline-history-current-badge">inactive</span>}
+                </span>
+                <div className="pipeline-job-meta">
+                  <span className="pipeline-detail-ref">{s.refName}</span>
+                  <code className="pipeline-schedule-cron" title={s.cronTimezone}>
+                    {s.cron}
+                  </code>
+                  {s.active && s.nextRunAt && (
+                    <span className="pipeline-job-time" title={new Date(s.nextRunAt).toLocaleString()}>
+                      next run {formatTimeUntil(s.nextRunAt)}
+                    </span>
+                  )}
+                  {s.ownerUsername && (
+                    <span className="pipeline-job-time">by {s.ownerUsername}</span>
+                  )}
+                </div>
+              </div>
+              <button
+                className="pipeline-job-action-btn pipeline-job-action-btn--play"
+                onClick={() => onRunSchedule(s)}
+                disabled={busy}
+                title="Run this schedule now"
+              >
+                {busy ? <span className="pipeline-job-spinner" /> : <PlayIcon />}
+                <span>Run now</span>
+              </button>
+              <button
+                className="pipeline-job-action-btn"
+                onClick={() => onToggleSchedule(s)}
+                disabled={busy}
+                title={s.active ? 'Stop running this schedule' : 'Resume this schedule'}
+              >
+                <span>{s.active ? 'Deactivate' : 'Activate'}</span>
+              </button>
+            </div>
+          );
+        })}
+      </div>
+    </main>
+  );
+}
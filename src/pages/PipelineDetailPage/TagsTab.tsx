@@ -0,0 +1,62 @@
+import type { ProjectTag } from '../../types';
+import { formatRelativeTime } from './utils';
+
+interface TagsTabProps {
+  tags: ProjectTag[];
+  tagsLoading: boolean;
+  tagsError: boolean;
+}
+
+export default function TagsTab({ tags, tagsLoading, tagsError }: TagsTabProps) {
+  if (tagsLoading) {
+    return (
+      <main className="pipeline-detail-content">
+        <div className="pipeline-detail-loading">Loading tags...</div>
+      </main>
+    );
+  }
+
+  if (tagsError) {
+    return (
+      <main className="pipeline-detail-content">
+        <div className="pipeline-detail-error">Failed to load tags</div>
+      </main>
+    );
+  }
+
+  if (tags.length === 0) {
+    return (
+      <main className="pipeline-detail-content">
+        <div className="pipeline-detail-empty">This project has no tags.</div>
+      </main>
+    );
+  }
+
+  return (
+    <main className="pipeline-detail-content">
+      <div className="pipeline-history-list" aria-label="Tags">
+        {tags.map((t) => (
+          <div key={t.name} className="pipeline-history-row pipeline-release-row">
+            <div className="pipeline-history-info">
+              <span className="pipeline-history-id">
+                {t.name}
+                {t.hasRelease && <span className="pipeline-history-current-badge">release</span>}
+                {t.protected && <span className="pipeline-history-current-badge">protected</span>}
+              </span>
+              <div className="pipeline-job-meta">
+                {t.commitSha && <code className="pipeline-history-sha">{t.commitSha.slice(0, 8)}</code>}
+                {t.commitTitle && <span className="pipeline-job-time">{t.commitTitle}</span>}
+                {t.committedAt && (
+                  <span className="pipeline-job-time" title={new Date(t.committedAt).toLocaleString()}>
+                    {formatRelativeTime(t.committedAt)}
+                  </span>
+                )}
+              </div>
+              {t.message && <p className="pipeline-tag-message">{t.message}</p>}
+            </div>
+          </div>
+        ))}
+      </div>
+    </main>
+  );
+}
@@ -7,8 +7,16 @@ import {
   retryPipelineJob,
   cancelPipelineJob,
   cancelPipeline,
+  listPipelineSchedules,
+  getCachedPipelineSchedules,
+  runScheduleNow,
+  toggleScheduleActive,
+  listProjectReleases,
+  getCachedProjectReleases,
+  listProjectTags,
+  getCachedProjectTags,
 } from '../../services/tauri';
-import type { PipelineJob } from '../../types';
+import type { PipelineJob, PipelineSchedule } from '../../types';
 import { usePipelineJobsQuery } from '../../hooks/queries/usePipelineJobsQuery';
 import { queryClient } from '../../lib/queryClient';
 import { queryKeys } from '../../lib/queryKeys';
@@ -52,6 +60,66 @@ export function usePipelineData({
     setHistoryEnabled(true);
   }, []);
 
+  // Schedules are lazy too; the cached copy shows while the live list loads
+  const [schedulesEnabled, setSchedulesEnabled] = useState(false);
+  const [scheduleActionLoading, setScheduleActionLoading] = useState<Set<number>>(new Set());
+  const cachedSchedulesQuery = useQuery({
+    queryKey: [...queryKeys.pipelineSchedules(String(instanceId), projectId), 'cached'],
+    queryFn: () => getCachedPipelineSchedules(instanceId, projectId),
+    enabled: schedulesEnabled && !!instanceId && !!projectId,
+    staleTime: Infinity,
+  });
+  const schedulesQuery = useQuery({
+    queryKey: queryKeys.pipelineSchedules(String(instanceId), projectId),
+    queryFn: () => listPipelineSchedules(instanceId, projectId),
+    enabled: schedulesEnabled && !!instanceId && !!projectId,
+    placeholderData: cachedSchedulesQuery.data,
+    staleTime: 30_000,
+  });
+
+  const loadSchedules = useCallback(() => {
+    setSchedulesEnabled(true);
+  }, []);
+
+  // Releases and tags follow the same lazy, cache-first pattern
+  const [releasesEnabled, setReleasesEnabled] = useState(false);
+  const cachedReleasesQuery = useQuery({
+    queryKey: [...queryKeys.projectReleases(String(instanceId), projectId), 'cached'],
+    queryFn: () => getCachedProjectReleases(instanceId, projectId),
+    enabled: releasesEnabled && !!instanceId && !!projectId,
+    staleTime: Infinity,
+  });
+  const releasesQuery = useQuery({
+    queryKey: queryKeys.projectReleases(String(instanceId), projectId),
+    queryFn: () => listProjectReleases(instanceId, projectId),
+    enabled: releasesEnabled && !!instanceId && !!projectId,
+    placeholderData: cachedReleasesQuery.data,
+    staleTime: 60_000,
+  });
+
+  const [tagsEnabled, setTagsEnabled] = useState(false);
+  const cachedTagsQuery = useQuery({
+    queryKey: [...queryKeys.projectTags(String(instanceId), projectId), 'cached'],
+    queryFn: () => getCachedProjectTags(instanceId, projectId),
+    enabled: tagsEnabled && !!instanceId && !!projectId,
+    staleTime: Infinity,
+  });
+  const tagsQuery = useQuery({
+    queryKey: queryKeys.projectTags(String(instanceId), projectId),
+    queryFn: () => listProjectTags(instanceId, projectId),
+    enabled: tagsEnabled && !!instanceId && !!projectId,
+    placeholderData: cachedTagsQuery.data,
+    staleTime: 60_000,
+  });
+
+  const loadReleases = useCallback(() => {
+    setReleasesEnabled(true);
+  }, []);
+
+  const loadTags = useCallback(() => {
+    setTagsEnabled(true);
+  }, []);
+
   const refresh = useCallback(() => {
     queryClient.invalidateQueries({
       queryKey: queryKeys.pipelineJobs(String(instanceId), projectId, pipelineId),
@@ -158,6 +226,55 @@ export function usePipelineData({
     [instanceId, projectId, pipelineId]
   );
 
+  const withScheduleAction = useCallback(
+    async (scheduleId: number, label: string, action: () => Promise<unknown>) => {
+      setScheduleActionLoading((prev) => new Set(prev).add(scheduleId));
+      try {
+        await action();
+      } catch (err) {
+        console.error(`Failed to ${label}:`, err);
+      } finally {
+        setScheduleActionLoading((prev) => {
+          const next = new Set(prev);
+          next.delete(scheduleId);
+          return next;
+        });
+      }
+    },
+    []
+  );
+
+  const handleRunSchedule = useCallback(
+    (schedule: PipelineSchedule) =>
+      withScheduleAction(schedule.id, 'run schedule', async () => {
+        await runScheduleNow(instanceId, projectId, schedule.id);
+        // The pipeline is created asynchronously; refresh history once it exists
+        setTimeout(() => {
+          queryClient.invalidateQueries({
+            queryKey: queryKeys.pipelineHistory(String(instanceId), projectId),
+          });
+        }, 2_000);
+      }),
+    [instanceId, projectId, withScheduleAction]
+  );
+
+  const handleToggleSchedule = useCallback(
+    (schedule: PipelineSchedule) =>
+      withScheduleAction(schedule.id, 'toggle schedule', async () => {
+        const updated = await toggleScheduleActive(
+          instanceId,
+          projectId,
+          schedule.id,
+          !schedule.active,
+        );
+        queryClient.setQueryData<PipelineSchedule[]>(
+          queryKeys.pipelineSchedules(String(instanceId), projectId),
+          (prev) => prev?.map((s) => (s.id === updated.id ? updated : s)),
+        );
+      }),
+    [instanceId, projectId, withScheduleAction]
+  );
+
   const handleNavigateToJob = useCallback(
     (
       job: PipelineJob,
@@ -196,8 +313,23 @@ export function usePipelineData({
     pipelines: historyQuery.data ?? [],
     historyLoading: historyQuery.isLoading && historyEnabled,
     historyLoaded: historyEnabled && (historyQuery.isSuccess || historyQuery.isError),
+    schedules: schedulesQuery.data ?? [],
+    schedulesLoading: schedulesQuery.isLoading && schedulesEnabled,
+    schedulesError: schedulesQuery.isError && !schedulesQuery.data,
+    scheduleActionLoading,
+    releases: releasesQuery.data ?? [],
+    releasesLoading: releasesQuery.isLoading && releasesEnabled,
+    releasesError: releasesQuery.isError && !releasesQuery.data,
+    tags: tagsQuery.data ?? [],
+    tagsLoading: tagsQuery.isLoading && tagsEnabled,
+    tagsError: tagsQuery.isError && !tagsQuery.data,
     refresh,
     loadHistory,
+    loadSchedules,
+    loadReleases,
+    loadTags,
+    handleRunSchedule,
+    handleToggleSchedule,
     handlePlayJob,
     handleRetryJob,
     handleCancelJob,
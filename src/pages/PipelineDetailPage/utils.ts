@@ -19,6 +19,15 @@ export function formatRelativeTime(isoString: string): string {
   return new Date(isoString).toLocaleDateString();
 }
 
+/** "in 5m", "in 3h", "in 2d" for a future timestamp. */
+export function formatTimeUntil(isoString: string): string {
+  const diff = Math.floor((new Date(isoString).getTime() - Date.now()) / 1000);
+  if (diff < 60) return 'now';
+  if (diff < 3600) return `in ${Math.floor(diff / 60)}m`;
+  if (diff < 86400) return `in ${Math.floor(diff / 3600)}h`;
+  return `in ${Math.floor(diff / 86400)}d`;
+}
+
 export function jobStatusLabel(status: PipelineJobStatus): string {
   switch (status) {
     case 'success': return 'passed';
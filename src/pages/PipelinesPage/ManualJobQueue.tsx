@@ -0,0 +1,133 @@
+/**
+ * Manual-job approval queue.
+ *
+ * Manual jobs waiting in the dashboard projects of every instance, with
+ * checkboxes to play several at once. Hidden while nothing is waiting.
+ */
+
+import { useMemo, useState } from 'react';
+import { usePendingManualJobsQuery } from '../../hooks/queries/usePendingManualJobsQuery';
+import { queryClient } from '../../lib/queryClient';
+import { queryKeys } from '../../lib/queryKeys';
+import { playJobs } from '../../services/tauri';
+import { openExternalUrl } from '../../services/transport';
+import type { JobRef, PendingManualJob } from '../../types';
+import { formatRelativeTime } from './utils';
+import { BranchIcon, ExternalLinkIcon } from './icons';
+
+function jobKey(job: JobRef): string {
+  return `${job.instanceId}:${job.projectId}:${job.jobId}`;
+}
+
+function toRef(job: PendingManualJob): JobRef {
+  return { instanceId: job.instanceId, projectId: job.projectId, jobId: job.jobId };
+}
+
+export default function ManualJobQueue() {
+  const { data: jobs = [] } = usePendingManualJobsQuery();
+  const [selected, setSelected] = useState<Set<string>>(new Set());
+  const [playing, setPlaying] = useState(false);
+  /** Errors of the last play, keyed by `jobKey`. */
+  const [errors, setErrors] = useState<Map<string, string>>(new Map());
+
+  const selectedJobs = useMemo(
+    () => jobs.filter((job) => selected.has(jobKey(job))),
+    [jobs, selected]
+  );
+
+  if (jobs.length === 0) return null;
+
+  const toggle = (job: PendingManualJob) => {
+    setSelected((previous) => {
+      const next = new Set(previous);
+      const key = jobKey(job);
+      if (next.has(key)) next.delete(key);
+      else next.add(key);
+      return next;
+    });
+  };
+
+  const toggleAll = () => {
+    setSelected(
+      selectedJobs.length === jobs.length ? new Set() : new Set(jobs.map(jobKey))
+    );
+  };
+
+  async function handlePlay() {
+    setPlaying(true);
+    try {
+      const results = await playJobs(selectedJobs.map(toRef));
+      const failed = new Map<string, string>();
+      for (const result of results) {
+        if (result.error) failed.set(jobKey(result), result.error);
+      }
+      setErrors(failed);
+      setSelected(new Set(failed.keys()));
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      setErrors(new Map(selectedJobs.map((job) => [jobKey(job), message])));
+    } finally {
+      setPlaying(false);
+      queryClient.invalidateQueries({ queryKey: queryKeys.pendingManualJobs() });
+      queryClient.invalidateQueries({ queryKey: ['pipelineStatuses'] });
+    }
+  }
+
+  return (
+    <section className="pipelines-section manual-job-queue">
+      <div className="manual-job-queue-header">
+        <h2 className="pipelines-section-title">Waiting for approval ({jobs.length})</h2>
+        <div className="manual-job-queue-actions">
+          <button className="manual-job-queue-select" onClick={toggleAll} disabled={playing}>
+            {selectedJobs.length === jobs.length ? 'Select none' : 'Select all'}
+          </button>
+          <button
+            className="primary-button"
+            onClick={handlePlay}
+            disabled={playing || selectedJobs.length === 0}
+          >
+            {playing ? 'Playing…' : `Play selected (${selectedJobs.length})`}
+          </button>
+        </div>
+      </div>
+      <ul className="manual-job-queue-list">
+        {jobs.map((job) => {
+          const key = jobKey(job);
+          const error = errors.get(key);
+          return (
+            <li key={key} className="manual-job-queue-item">
+              <label className="manual-job-queue-job">
+                <input
+                  type="checkbox"
+                  checked={selected.has(key)}
+                  onChange={() => toggle(job)}
+                  disabled={playing}
+                />
+                <span className="manual-job-queue-name">{job.jobName}</span>
+                <span className="manual-job-queue-stage">{job.stage}</span>
+                <span className="manual-job-queue-project" title={job.projectName}>
+                  {job.projectName}
+                </span>
+                {job.refName && (
+                  <span className="pipeline-card-ref">
+                    <BranchIcon />
+                    {job.refName}
+                  </span>
+                )}
+                <span className="pipeline-card-time">{formatRelativeTime(job.createdAt)}</span>
+              </label>
+              <button
+                className="pipeline-card-action-btn"
+                onClick={() => openExternalUrl(job.webUrl)}
+                title="Open in browser"
+              >
+                <ExternalLinkIcon />
+              </button>
+              {error && <p className="manual-job-queue-error">{error}</p>}
+            </li>
+          );
+        })}
+      </ul>
+    </section>
+  );
+}
@@ -15,25 +15,33 @@ import {
 } from '@dnd-kit/sortable';
 import { CSS } from '@dnd-kit/utilities';
 import ProjectCard from './ProjectCard';
-import type { PipelineProject, PipelineStatus } from '../../types';
+import type { MutedProject, PipelineNotifyRule, PipelineProject, PipelineStatus } from '../../types';
 
 interface PinnedGridProps {
   projects: PipelineProject[];
   statuses: Map<number, PipelineStatus>;
+  mutedProjects: Map<number, MutedProject>;
   statusesLoading: boolean;
   onTogglePin: (projectId: number) => void;
   onRemove: (projectId: number) => void;
   onOpenDetail: (project: PipelineProject, status: PipelineStatus) => void;
   onReorder: (orderedIds: number[]) => void;
+  onNotifyRuleChange: (projectId: number, rule: PipelineNotifyRule) => void;
+  onManualGatesChange: (projectId: number, enabled: boolean) => void;
+  onMuteChange: (projectId: number, until: number | null | undefined) => void;
 }
 
 interface SortableCardProps {
   project: PipelineProject;
   status?: PipelineStatus;
+  muted?: MutedProject;
   statusLoading: boolean;
   onTogglePin: (projectId: number) => void;
   onRemove: (projectId: number) => void;
   onOpenDetail: (project: PipelineProject, status: PipelineStatus) => void;
+  onNotifyRuleChange: (projectId: number, rule: PipelineNotifyRule) => void;
+  onManualGatesChange: (projectId: number, enabled: boolean) => void;
+  onMuteChange: (projectId: number, until: number | null | undefined) => void;
 }
 
 function SortableCard({ project, ...rest }: SortableCardProps) {
@@ -63,11 +71,15 @@ function SortableCard({ project, ...rest }: SortableCardProps) {
 export default function PinnedGrid({
   projects,
   statuses,
+  mutedProjects,
   statusesLoading,
   onTogglePin,
   onRemove,
   onOpenDetail,
   onReorder,
+  onNotifyRuleChange,
+  onManualGatesChange,
+  onMuteChange,
 }: PinnedGridProps) {
   const sensors = useSensors(
     useSensor(PointerSensor, { activationConstraint: { distance: 5 } }),
@@ -105,10 +117,14 @@ export default function PinnedGrid({
               key={project.projectId}
               project={project}
               status={statuses.get(project.projectId)}
+              muted={mutedProjects.get(project.projectId)}
               statusLoading={statusesLoading}
               onTogglePin={onTogglePin}
               onRemove={onRemove}
               onOpenDetail={onOpenDetail}
+              onNotifyRuleChange={onNotifyRuleChange}
+              onManualGatesChange={onManualGatesChange}
+              onMuteChange={onMuteChange}
             />
           ))}
         </div>
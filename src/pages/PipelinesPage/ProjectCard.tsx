@@ -1,5 +1,5 @@
 import { openExternalUrl } from '../../services/transport';
-import type { PipelineProject, PipelineStatus } from '../../types';
+import type { MutedProject, PipelineNotifyRule, PipelineProject, PipelineStatus } from '../../types';
 import { formatRelativeTime, statusLabel, formatDuration } from './utils';
 import { PinIcon, RemoveIcon, ExternalLinkIcon, BranchIcon } from './icons';
 
@@ -10,13 +10,43 @@ interface ProjectCardProps {
   onTogglePin: (projectId: number) => void;
   onRemove: (projectId: number) => void;
   onOpenDetail: (project: PipelineProject, status: PipelineStatus) => void;
+  /** Shown for pinned projects, which are the ones polled for notifications. */
+  onNotifyRuleChange?: (projectId: number, rule: PipelineNotifyRule) => void;
+  /** Shown for pinned projects, like `onNotifyRuleChange`. */
+  onManualGatesChange?: (projectId: number, enabled: boolean) => void;
+  /** Set when the project's notifications are muted. */
+  muted?: MutedProject;
+  /** `until`: Unix seconds, null to mute until unmuted, undefined to unmute. */
+  onMuteChange?: (projectId: number, until: number | null | undefined) => void;
 }
 
-export default function ProjectCard({ project, status, statusLoading, onTogglePin, onRemove, onOpenDetail }: ProjectCardProps) {
+const NOTIFY_RULES: { value: PipelineNotifyRule; label: string }[] = [
+  { value: 'completion', label: 'Notify on any completion' },
+  { value: 'failures', label: 'Notify on failure only' },
+  { value: 'default_branch', label: 'Notify on default branch only' },
+];
+
+/** Mute durations in seconds; `forever` mutes until unmuted. */
+const MUTE_OPTIONS: { value: string; label: string }[] = [
+  { value: '3600', label: 'Mute for 1 hour' },
+  { value: '86400', label: 'Mute for 1 day' },
+  { value: '604800', label: 'Mute for 1 week' },
+  { value: 'forever', label: 'Mute until unmuted' },
+];
+
+function mutedLabel(muted: MutedProject): string {
+  if (muted.mutedUntil === null) return 'Muted';
+  return `Muted until ${new Date(muted.mutedUntil * 1000).toLocaleString([], {
+    dateStyle: 'short',
+    timeStyle: 'short',
+  })}`;
+}
+
+export default function ProjectCard({ project, status, statusLoading, onTogglePin, onRemove, onOpenDetail, onNotifyRuleChange, onManualGatesChange, muted, onMuteChange }: ProjectCardProps) {
   const statusName = status?.status;
 
   const handleCardClick = (e: React.MouseEvent) => {
-    if ((e.target as HTMLElement).closest('.pipeline-card-actions')) return;
+    if ((e.target as HTMLElement).closest('.pipeline-card-actions, .pipeline-card-notify, .pipeline-card-gates, .pipeline-card-mute')) return;
     if (status) {
       onOpenDetail(project, status);
     }
@@ -24,7 +54,7 @@ export default function ProjectCard({ project, status, statusLoading, onTogglePi
 
   return (
     <div
-      className={`pipeline-card ${statusName ? `pipeline-card--${statusName}` : ''} ${status ? 'pipeline-card--clickable' : ''}`}
+      className={`pipeline-card ${statusName ? `pipeline-card--${statusName}` : ''} ${status ? 'pipeline-card--clickable' : ''} ${muted ? 'pipeline-card--muted' : ''}`}
       onClick={handleCardClick}
     >
       <div className="pipeline-card-header">
@@ -98,6 +128,55 @@ export default function ProjectCard({ project, status, statusLoading, onTogglePi
             visited {formatRelativeTime(project.lastVisitedAt)}
           </span>
         )}
+        {project.pinned && onNotifyRuleChange && (
+          <select
+            className="pipeline-card-notify"
+            value={project.notifyRule}
+            onChange={(e) => onNotifyRuleChange(project.projectId, e.target.value as PipelineNotifyRule)}
+            onPointerDown={(e) => e.stopPropagation()}
+            title="Pipeline notifications"
+          >
+            {NOTIFY_RULES.map((rule) => (
+              <option key={rule.value} value={rule.value}>{rule.label}</option>
+            ))}
+          </select>
+        )}
+        {project.pinned && onManualGatesChange && (
+          <label
+            className="pipeline-card-gates"
+            onPointerDown={(e) => e.stopPropagation()}
+            title="Notify when a manual job starts waiting on the default branch"
+          >
+            <input
+              type="checkbox"
+              checked={project.notifyManualGates}
+              onChange={(e) => onManualGatesChange(project.projectId, e.target.checked)}
+            />
+            Manual jobs
+          </label>
+        )}
+        {onMuteChange && (
+          <select
+            className="pipeline-card-mute"
+            value={muted ? 'muted' : 'on'}
+            onChange={(e) => {
+              const { value } = e.target;
+              if (value === 'on') onMuteChange(project.projectId, undefined);
+              else if (value === 'forever') onMuteChange(project.projectId, null);
+              else if (value !== 'muted') {
+                onMuteChange(project.projectId, Math.floor(Date.now() / 1000) + Number(value));
+              }
+            }}
+            onPointerDown={(e) => e.stopPropagation()}
+            title="Mute notifications and badge count from this project (it keeps syncing)"
+          >
+            <option value="on">Not muted</option>
+            {muted && <option value="muted">{mutedLabel(muted)}</option>}
+            {MUTE_OPTIONS.map((option) => (
+              <option key={option.value} value={option.value}>{option.label}</option>
+            ))}
+          </select>
+        )}
       </div>
     </div>
   );
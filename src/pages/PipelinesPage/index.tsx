@@ -10,6 +10,7 @@ import usePipelinesData from './usePipelinesData';
 import ProjectSearch from './ProjectSearch';
 import ProjectCard from './ProjectCard';
 import PinnedGrid from './PinnedGrid';
+import ManualJobQueue from './ManualJobQueue';
 import { InstanceSwitcher } from '../../components/InstanceSwitcher';
 import { formatRelativeTime } from './utils';
 import '../PipelinesPage.css';
@@ -22,6 +23,7 @@ export default function PipelinesPage() {
     selectedInstanceId,
     projects,
     statuses,
+    mutedProjects,
     loading,
     statusesLoading,
     lastFetched,
@@ -29,6 +31,9 @@ export default function PipelinesPage() {
     handleTogglePin,
     handleRemoveProject,
     handleReorderPinned,
+    handleNotifyRuleChange,
+    handleManualGatesChange,
+    handleMuteChange,
     handleOpenDetail,
     handleSelectInstance,
   } = usePipelinesData();
@@ -82,6 +87,7 @@ export default function PipelinesPage() {
       />
 
       <main className="pipelines-content">
+        <ManualJobQueue />
         {loading ? (
           <div className="pipelines-loading">Loading pipeline projects...</div>
         ) : projects.length === 0 ? (
@@ -99,11 +105,15 @@ export default function PipelinesPage() {
                 <PinnedGrid
                   projects={pinnedProjects}
                   statuses={statuses}
+                  mutedProjects={mutedProjects}
                   statusesLoading={statusesLoading}
                   onTogglePin={handleTogglePin}
                   onRemove={handleRemoveProject}
                   onOpenDetail={handleOpenDetail}
                   onReorder={handleReorderPinned}
+                  onNotifyRuleChange={handleNotifyRuleChange}
+                  onManualGatesChange={handleManualGatesChange}
+                  onMuteChange={handleMuteChange}
                 />
               </section>
             )}
@@ -122,6 +132,8 @@ export default function PipelinesPage() {
                       onTogglePin={handleTogglePin}
                       onRemove={handleRemoveProject}
                       onOpenDetail={handleOpenDetail}
+                      muted={mutedProjects.get(project.projectId)}
+                      onMuteChange={handleMuteChange}
                     />
                   ))}
                 </div>
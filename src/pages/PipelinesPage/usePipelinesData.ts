@@ -5,11 +5,16 @@ import {
   togglePinPipelineProject,
   removePipelineProject,
   reorderPinnedPipelineProjects,
+  setPipelineNotifyRule,
+  setPipelineManualGateNotify,
+  muteProject,
+  unmuteProject,
 } from '../../services/tauri';
-import type { PipelineProject, PipelineStatus, ProjectSearchResult } from '../../types';
+import type { MutedProject, PipelineNotifyRule, PipelineProject, PipelineStatus, ProjectSearchResult } from '../../types';
 import { useInstancesQuery } from '../../hooks/queries/useInstancesQuery';
 import { usePipelineProjectsQuery } from '../../hooks/queries/usePipelineProjectsQuery';
 import { usePipelineStatusesQuery } from '../../hooks/queries/usePipelineStatusesQuery';
+import { useMutedProjectsQuery } from '../../hooks/queries/useMutedProjectsQuery';
 import { queryClient } from '../../lib/queryClient';
 import { queryKeys } from '../../lib/queryKeys';
 
@@ -40,6 +45,17 @@ export default function usePipelinesData() {
     [statusList]
   );
 
+  const mutedQuery = useMutedProjectsQuery();
+  const mutedProjects = useMemo(
+    () =>
+      new Map<number, MutedProject>(
+        (mutedQuery.data ?? [])
+          .filter((m) => m.instanceId === selectedInstanceId)
+          .map((m) => [m.projectId, m])
+      ),
+    [mutedQuery.data, selectedInstanceId]
+  );
+
   const lastFetched = statusesQuery.dataUpdatedAt
     ? new Date(statusesQuery.dataUpdatedAt)
     : null;
@@ -116,6 +132,67 @@ export default function usePipelinesData() {
     [selectedInstanceId]
   );
 
+  const handleNotifyRuleChange = useCallback(
+    async (projectId: number, rule: PipelineNotifyRule) => {
+      if (!selectedInstanceId) return;
+      const key = queryKeys.pipelineProjects(String(selectedInstanceId));
+      const previous = queryClient.getQueryData<PipelineProject[]>(key);
+      if (previous) {
+        queryClient.setQueryData<PipelineProject[]>(
+          key,
+          previous.map((p) => (p.projectId === projectId ? { ...p, notifyRule: rule } : p))
+        );
+      }
+      try {
+        await setPipelineNotifyRule(selectedInstanceId, projectId, rule);
+      } catch (error) {
+        console.error('Failed to set notification rule:', error);
+        if (previous) queryClient.setQueryData(key, previous);
+      }
+    },
+    [selectedInstanceId]
+  );
+
+  const handleManualGatesChange = useCallback(
+    async (projectId: number, enabled: boolean) => {
+      if (!selectedInstanceId) return;
+      const key = queryKeys.pipelineProjects(String(selectedInstanceId));
+      const previous = queryClient.getQueryData<PipelineProject[]>(key);
+      if (previous) {
+        queryClient.setQueryData<PipelineProject[]>(
+          key,
+          previous.map((p) => (p.projectId === projectId ? { ...p, notifyManualGates: enabled } : p))
+        );
+      }
+      try {
+        await setPipelineManualGateNotify(selectedInstanceId, projectId, enabled);
+      } catch (error) {
+        console.error('Failed to set manual job notifications:', error);
+        if (previous) queryClient.setQueryData(key, previous);
+      }
+    },
+    [selectedInstanceId]
+  );
+
+  /** `until`: Unix seconds, null to mute until unmuted, undefined to unmute. */
+  const handleMuteChange = useCallback(
+    async (projectId: number, until: number | null | undefined) => {
+      if (!selectedInstanceId) return;
+      try {
+        if (until === undefined) {
+          await unmuteProject(selectedInstanceId, projectId);
+        } else {
+          await muteProject(selectedInstanceId, projectId, until);
+        }
+      } catch (error) {
+        console.error('Failed to change project mute:', error);
+      } finally {
+        queryClient.invalidateQueries({ queryKey: queryKeys.mutedProjects() });
+      }
+    },
+    [selectedInstanceId]
+  );
+
   const handleOpenDetail = useCallback(
     (project: PipelineProject, status: PipelineStatus) => {
       const params = new URLSearchParams({
@@ -139,6 +216,7 @@ export default function usePipelinesData() {
     selectedInstanceId,
     projects,
     statuses,
+    mutedProjects,
     loading: projectsQuery.isLoading,
     statusesLoading: statusesQuery.isFetching,
     lastFetched,
@@ -146,6 +224,9 @@ export default function usePipelinesData() {
     handleTogglePin,
     handleRemoveProject,
     handleReorderPinned,
+    handleNotifyRuleChange,
+    handleManualGatesChange,
+    handleMuteChange,
     handleOpenDetail,
     handleSelectInstance,
   };
@@ -0,0 +1,100 @@
+import { useCallback, useEffect, useState } from 'react';
+import { discardFailedAction, listFailedActions, retryFailedActions } from '../../services/tauri';
+import type { FailedAction } from '../../types';
+
+/** How often the listing refreshes while open, so retry times stay current. */
+const REFRESH_MS = 15_000;
+
+function formatRetryTime(secs: number): string {
+  return new Date(secs * 1000).toLocaleTimeString('en-US', { hour: 'numeric', minute: '2-digit' });
+}
+
+function describe(action: FailedAction): string {
+  const attempts = `${action.retryCount} ${action.retryCount === 1 ? 'attempt' : 'attempts'}`;
+  if (action.status === 'pending' && action.nextRetryAt) {
+    return `Will retry at ${formatRetryTime(action.nextRetryAt)} · ${attempts}`;
+  }
+  return `Gave up after ${attempts}`;
+}
+
+/**
+ * Queued comments, approvals and other actions that failed to push: those
+ * waiting for their automatic retry and those left for the user.
+ */
+export default function ActionQueueSection() {
+  const [actions, setActions] = useState<FailedAction[] | null>(null);
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const load = useCallback(async () => {
+    try {
+      setActions(await listFailedActions());
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    }
+  }, []);
+
+  useEffect(() => {
+    load();
+    const timer = setInterval(load, REFRESH_MS);
+    return () => clearInterval(timer);
+  }, [load]);
+
+  async function run(action: () => Promise<unknown>) {
+    setBusy(true);
+    setError(null);
+    try {
+      await action();
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setBusy(false);
+      load();
+    }
+  }
+
+  if (!actions) {
+    return <p className="loading">Loading queue...</p>;
+  }
+
+  const retryable = actions.some((a) => a.status === 'failed' && a.advice !== 'discard');
+
+  return (
+    <div className="sync-settings-form">
+      <p className="shortcut-hint">
+        Network and server errors are retried automatically, waiting longer after each
+        failure. Rejected actions, like a comment GitLab refuses, aren't retried.
+      </p>
+      {actions.length === 0 ? (
+        <p className="companion-devices-empty">No failed actions</p>
+      ) : (
+        <ul className="companion-device-list">
+          {actions.map((action) => (
+            <li key={action.id} className="companion-device-item">
+              <div className="companion-device-info">
+                <span className="companion-device-name">
+                  {action.actionType} · {action.mrTitle ?? `MR ${action.mrId}`}
+                </span>
+                <span className="companion-device-meta">{describe(action)}</span>
+                <span className="companion-device-meta action-queue-hint">{action.hint}</span>
+              </div>
+              <button
+                className="companion-device-revoke"
+                onClick={() => run(() => discardFailedAction(action.id))}
+                disabled={busy}
+              >
+                Discard
+              </button>
+            </li>
+          ))}
+        </ul>
+      )}
+      {retryable && (
+        <button className="add-button" onClick={() => run(retryFailedActions)} disabled={busy}>
+          {busy ? 'Retrying...' : 'Retry now'}
+        </button>
+      )}
+      {error && <div className="error-message">{error}</div>}
+    </div>
+  );
+}
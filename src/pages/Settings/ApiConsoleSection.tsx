@@ -0,0 +1,142 @@
+import { useState } from 'react';
+import { useQueryClient } from '@tanstack/react-query';
+import { useInstancesQuery } from '../../hooks/queries/useInstancesQuery';
+import { useSettingsQuery } from '../../hooks/queries/useSettingsQuery';
+import { queryKeys } from '../../lib/queryKeys';
+import { gitlabApiRequest, updateAllowApiWrites, type ApiRequestMethod } from '../../services/tauri';
+
+const METHODS: ApiRequestMethod[] = ['GET', 'POST', 'PUT', 'PATCH', 'DELETE'];
+
+/**
+ * Raw GitLab API console: runs a request with the instance's stored token
+ * and shows the JSON response. Writes stay disabled unless opted into.
+ */
+export default function ApiConsoleSection() {
+  const queryClient = useQueryClient();
+  const { data: instances = [] } = useInstancesQuery();
+  const { data: settings } = useSettingsQuery();
+  const allowWrites = settings?.allowApiWrites ?? false;
+
+  const [instanceId, setInstanceId] = useState<number | null>(null);
+  const [method, setMethod] = useState<ApiRequestMethod>('GET');
+  const [path, setPath] = useState('/user');
+  const [body, setBody] = useState('');
+  const [sending, setSending] = useState(false);
+  const [result, setResult] = useState<{ ok: boolean; text: string } | null>(null);
+
+  const selectedInstanceId = instanceId ?? instances[0]?.id ?? null;
+  const isWrite = method !== 'GET';
+
+  async function handleToggleWrites(allow: boolean) {
+    try {
+      await updateAllowApiWrites(allow);
+      queryClient.invalidateQueries({ queryKey: queryKeys.settings() });
+    } catch (err) {
+      console.error('Failed to update API write setting:', err);
+    }
+  }
+
+  async function handleSend() {
+    if (selectedInstanceId === null) return;
+    let parsedBody: unknown;
+    if (isWrite && body.trim()) {
+      try {
+        parsedBody = JSON.parse(body);
+      } catch {
+        setResult({ ok: false, text: 'Body is not valid JSON' });
+        return;
+      }
+    }
+    setSending(true);
+    try {
+      const response = await gitlabApiRequest(selectedInstanceId, method, path, parsedBody);
+      setResult({ ok: true, text: JSON.stringify(response, null, 2) });
+    } catch (err) {
+      setResult({ ok: false, text: err instanceof Error ? err.message : String(err) });
+    } finally {
+      setSending(false);
+    }
+  }
+
+  if (instances.length === 0) {
+    return <p className="shortcut-hint">Add a GitLab instance to use the API console.</p>;
+  }
+
+  return (
+    <div className="sync-settings-form">
+      <div className="setting-row">
+        <label htmlFor="api-console-instance">Instance</label>
+        <select
+          id="api-console-instance"
+          value={selectedInstanceId ?? ''}
+          onChange={(e) => setInstanceId(Number(e.target.value))}
+        >
+          {instances.map((inst) => (
+            <option key={inst.id} value={inst.id}>
+              {inst.name ?? inst.url}
+            </option>
+          ))}
+        </select>
+      </div>
+
+      <div className="api-console-request">
+        <select
+          aria-label="HTTP method"
+          value={method}
+          onChange={(e) => setMethod(e.target.value as ApiRequestMethod)}
+        >
+          {METHODS.map((m) => (
+            <option key={m} value={m} disabled={m !== 'GET' && !allowWrites}>
+              {m}
+            </option>
+          ))}
+        </select>
+        <input
+          className="companion-text-input api-console-path"
+          aria-label="API path"
+          value={path}
+          onChange={(e) => setPath(e.target.value)}
+          onKeyDown={(e) => {
+            if (e.key === 'Enter') handleSend();
+          }}
+          placeholder="/projects/:id/merge_requests"
+          spellCheck={false}
+        />
+        <button className="add-button" onClick={handleSend} disabled={sending || !path.trim()}>
+          {sending ? 'Sending...' : 'Send'}
+        </button>
+      </div>
+
+      {isWrite && (
+        <textarea
+          className="companion-text-input api-console-body"
+          aria-label="JSON body"
+          value={body}
+          onChange={(e) => setBody(e.target.value)}
+          placeholder='{"key": "value"}'
+          spellCheck={false}
+        />
+      )}
+
+      {result && (
+        <pre className={`api-console-result ${result.ok ? '' : 'error'}`}>{result.text}</pre>
+      )}
+
+      <div className="checkbox-group">
+        <label className="checkbox-label">
+          <input
+            type="checkbox"
+            checked={allowWrites}
+            onChange={(e) => handleToggleWrites(e.target.checked)}
+          />
+          <span>
+            Allow write requests
+            <span className="checkbox-description">
+              Enables POST, PUT, PATCH and DELETE. These change data on GitLab with your token.
+            </span>
+          </span>
+        </label>
+      </div>
+    </div>
+  );
+}
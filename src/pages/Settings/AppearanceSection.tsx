@@ -111,7 +111,7 @@ interface AppearanceSectionProps {
  * Appearance section — theme selector with visual swatches + font combobox + custom theme editor.
  */
 export default function AppearanceSection({ highlightCondensed = false }: AppearanceSectionProps) {
-  const { theme, setThemeById, uiFont, setUiFont, displayFont, setDisplayFont, diffsFont, setDiffsFont, customColors, previewCustomTheme, saveCustomTheme, deleteCustomTheme } = useTheme();
+  const { theme, setThemeById, uiFont, setUiFont, displayFont, setDisplayFont, diffsFont, setDiffsFont, customColors, previewCustomTheme, saveCustomTheme, deleteCustomTheme, highlightTheme, highlightThemes, setHighlightTheme } = useTheme();
   const settingsQuery = useSettingsQuery();
   const queryClient = useQueryClient();
   const condensed = settingsQuery.data?.mrListCondensed ?? false;
@@ -384,6 +384,23 @@ export default function AppearanceSection({ highlightCondensed = false }: Appear
         error={systemFontsError}
       />
 
+      {highlightThemes.length > 0 && (
+        <div className="setting-row">
+          <label htmlFor="highlight-theme-select">Syntax Colors</label>
+          <select
+            id="highlight-theme-select"
+            value={highlightTheme}
+            onChange={(e) => setHighlightTheme(e.target.value)}
+          >
+            {highlightThemes.map((t) => (
+              <option key={t.id} value={t.id}>
+                {t.name}{t.dark === null ? '' : t.dark ? ' (dark)' : ' (light)'}
+              </option>
+            ))}
+          </select>
+        </div>
+      )}
+
       <div
         ref={condensedRowRef}
         className={`condensed-toggle-row${pulseCondensed ? ' condensed-toggle-row--pulse' : ''}`}
@@ -0,0 +1,217 @@
+import { useState, useEffect } from 'react';
+import {
+  listAutomationRules,
+  saveAutomationRule,
+  deleteAutomationRule,
+  testAutomation,
+  listAutomationRuns,
+} from '../../services/tauri';
+import type { AutomationRule, AutomationRun, AutomationTrigger, AutomationAction } from '../../types';
+import { formatRelativeTime } from '../../utils/formatRelativeTime';
+
+const TRIGGERS: { value: AutomationTrigger; label: string }[] = [
+  { value: 'my_mr_approved', label: 'My MR is approved' },
+  { value: 'my_mr_pipeline_failed', label: 'My MR pipeline fails' },
+  { value: 'my_mr_merged', label: 'My MR is merged' },
+  { value: 'review_requested', label: 'My review is requested' },
+];
+
+function triggerLabel(trigger: AutomationTrigger): string {
+  return TRIGGERS.find((t) => t.value === trigger)?.label ?? trigger;
+}
+
+function errorText(err: unknown): string {
+  return err instanceof Error ? err.message : String(err);
+}
+
+/**
+ * User-defined automations: post to a webhook or run a shell command when a
+ * synced MR changes. Evaluated locally after each sync.
+ */
+export default function AutomationsSection() {
+  const [rules, setRules] = useState<AutomationRule[] | null>(null);
+  const [runs, setRuns] = useState<AutomationRun[]>([]);
+  const [name, setName] = useState('');
+  const [trigger, setTrigger] = useState<AutomationTrigger>('my_mr_approved');
+  const [kind, setKind] = useState<AutomationAction['kind']>('webhook');
+  const [target, setTarget] = useState('');
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  function refreshRuns() {
+    listAutomationRuns().then(setRuns).catch(() => {});
+  }
+
+  useEffect(() => {
+    listAutomationRules().then(setRules).catch(() => setRules([]));
+    refreshRuns();
+  }, []);
+
+  async function handleAdd() {
+    setBusy(true);
+    setError(null);
+    try {
+      const rule = await saveAutomationRule({
+        id: null,
+        name: name.trim(),
+        trigger,
+        action: { kind, target: target.trim() },
+        enabled: true,
+      });
+      setRules((prev) => [...(prev ?? []), rule]);
+      setName('');
+      setTarget('');
+    } catch (err) {
+      setError(errorText(err));
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  async function handleToggle(rule: AutomationRule, enabled: boolean) {
+    try {
+      const saved = await saveAutomationRule({
+        id: rule.id,
+        name: rule.name,
+        trigger: rule.trigger,
+        action: rule.action,
+        enabled,
+      });
+      setRules((prev) => (prev ?? []).map((r) => (r.id === saved.id ? saved : r)));
+    } catch (err) {
+      setError(errorText(err));
+    }
+  }
+
+  async function handleDelete(ruleId: number) {
+    try {
+      await deleteAutomationRule(ruleId);
+      setRules((prev) => (prev ?? []).filter((r) => r.id !== ruleId));
+      refreshRuns();
+    } catch (err) {
+      setError(errorText(err));
+    }
+  }
+
+  async function handleTest(ruleId: number) {
+    setError(null);
+    try {
+      await testAutomation(ruleId);
+    } catch (err) {
+      setError(errorText(err));
+    } finally {
+      refreshRuns();
+    }
+  }
+
+  if (!rules) {
+    return <p className="loading">Loading settings...</p>;
+  }
+
+  const ruleName = (ruleId: number) => rules.find((r) => r.id === ruleId)?.name ?? `Rule ${ruleId}`;
+
+  return (
+    <div className="sync-settings-form">
+      <p className="shortcut-hint">
+        Rules run on this machine after each sync. Webhooks receive a JSON body with a
+        Slack-compatible <code>text</code> field; commands get the MR in <code>ULTRA_*</code>{' '}
+        environment variables.
+      </p>
+
+      {rules.length > 0 && (
+        <ul className="automation-rules">
+          {rules.map((rule) => (
+            <li key={rule.id} className="automation-rule">
+              <label className="checkbox-label">
+                <input
+                  type="checkbox"
+                  checked={rule.enabled}
+                  onChange={(e) => handleToggle(rule, e.target.checked)}
+                />
+                <span>
+                  {rule.name}
+                  <span className="checkbox-description">
+                    {triggerLabel(rule.trigger)} → {rule.action.kind === 'webhook' ? 'POST' : 'run'}{' '}
+                    <code>{rule.action.target}</code>
+                  </span>
+                </span>
+              </label>
+              <button className="add-button" onClick={() => handleTest(rule.id)}>
+                Test
+              </button>
+              <button className="add-button" onClick={() => handleDelete(rule.id)}>
+                Delete
+              </button>
+            </li>
+          ))}
+        </ul>
+      )}
+
+      <div className="setting-row">
+        <label htmlFor="automation-name">New rule</label>
+        <div className="automation-form">
+          <input
+            id="automation-name"
+            className="companion-text-input"
+            value={name}
+            onChange={(e) => setName(e.target.value)}
+            placeholder="Name"
+          />
+          <select
+            aria-label="Trigger"
+            value={trigger}
+            onChange={(e) => setTrigger(e.target.value as AutomationTrigger)}
+          >
+            {TRIGGERS.map((t) => (
+              <option key={t.value} value={t.value}>
+                {t.label}
+              </option>
+            ))}
+          </select>
+          <select
+            aria-label="Action"
+            value={kind}
+            onChange={(e) => setKind(e.target.value as AutomationAction['kind'])}
+          >
+            <option value="webhook">POST to webhook</option>
+            <option value="command">Run shell command</option>
+          </select>
+          <input
+            className="companion-text-input automation-target"
+            aria-label={kind === 'webhook' ? 'Webhook URL' : 'Command'}
+            value={target}
+            onChange={(e) => setTarget(e.target.value)}
+            placeholder={kind === 'webhook' ? 'https://hooks.slack.com/services/...' : 'say "$ULTRA_SUMMARY"'}
+            spellCheck={false}
+          />
+          <button
+            className="add-button"
+            onClick={handleAdd}
+            disabled={busy || !name.trim() || !target.trim()}
+          >
+            Add
+          </button>
+        </div>
+      </div>
+
+      {error && <p className="cache-archive-result error">{error}</p>}
+
+      {runs.length > 0 && (
+        <div className="setting-row">
+          <label>Recent runs</label>
+          <ul className="automation-runs">
+            {runs.map((run) => (
+              <li key={run.id} className={run.success ? '' : 'error'}>
+                <span>
+                  {run.success ? '✓' : '✗'} {ruleName(run.ruleId)}
+                  {run.isTest && ' (test)'} · {formatRelativeTime(run.ranAt)} · {run.durationMs}ms
+                </span>
+                {run.detail && <pre>{run.detail}</pre>}
+              </li>
+            ))}
+          </ul>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,77 @@
+import { useCallback, useEffect, useState } from 'react';
+import { getBackgroundJobs } from '../../services/tauri';
+import type { BackgroundJobStatus } from '../../types';
+
+/** How often the listing refreshes while open. */
+const REFRESH_MS = 5_000;
+
+function formatTime(secs: number): string {
+  return new Date(secs * 1000).toLocaleTimeString('en-US', { hour: 'numeric', minute: '2-digit' });
+}
+
+function describe(job: BackgroundJobStatus): string {
+  const state =
+    job.state === 'running' ? 'Running' : job.state === 'queued' ? 'Waiting for idle' : 'Up to date';
+  const parts = [state];
+  if (job.lastRunAt) {
+    parts.push(`last batch ${formatTime(job.lastRunAt)}: ${job.lastProcessed} processed`);
+  }
+  if (job.cancellations > 0) {
+    parts.push(`${job.cancellations} ${job.cancellations === 1 ? 'pause' : 'pauses'}`);
+  }
+  return parts.join(' · ');
+}
+
+/**
+ * Precomputation that runs while the app sits idle, with what each job is
+ * doing and how its last batch went.
+ */
+export default function BackgroundJobsSection() {
+  const [jobs, setJobs] = useState<BackgroundJobStatus[] | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const load = useCallback(async () => {
+    try {
+      setJobs(await getBackgroundJobs());
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    }
+  }, []);
+
+  useEffect(() => {
+    load();
+    const timer = setInterval(load, REFRESH_MS);
+    return () => clearInterval(timer);
+  }, [load]);
+
+  if (!jobs) {
+    return <p className="loading">Loading jobs...</p>;
+  }
+
+  return (
+    <div className="sync-settings-form">
+      <p className="shortcut-hint">
+        These run after a minute without keyboard or mouse activity and never during a
+        sync. Using the app pauses them; they pick up where they left off.
+      </p>
+      {jobs.length === 0 ? (
+        <p className="companion-devices-empty">No background jobs</p>
+      ) : (
+        <ul className="companion-device-list">
+          {jobs.map((job) => (
+            <li key={job.name} className="companion-device-item">
+              <div className="companion-device-info">
+                <span className="companion-device-name">{job.description}</span>
+                <span className="companion-device-meta">{describe(job)}</span>
+                {job.lastError && (
+                  <span className="companion-device-meta action-queue-hint">{job.lastError}</span>
+                )}
+              </div>
+            </li>
+          ))}
+        </ul>
+      )}
+      {error && <div className="error-message">{error}</div>}
+    </div>
+  );
+}
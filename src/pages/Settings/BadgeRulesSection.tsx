@@ -0,0 +1,203 @@
+import { useState, useEffect } from 'react';
+import { useQueryClient } from '@tanstack/react-query';
+import { listBadgeRules, updateBadgeRules } from '../../services/tauri';
+import type { BadgeRule, BadgeRuleInput } from '../../types';
+
+const DEFAULT_TIME_LIMIT_MS = 10;
+
+/** Variables scripts can read, shown as a reference under the editor. */
+const SCRIPT_VARIABLES =
+  'title, description, author, project, source_branch, target_branch, state, draft, bot, ' +
+  'watched, labels, reviewers, approved, approvals, approvals_required, pipeline, files, ' +
+  'additions, deletions, lines, tasks, tasks_done, age_hours, idle_hours';
+
+interface DraftRule extends BadgeRuleInput {
+  /** Saved id, for showing the rule's last error; null for new rules. */
+  id: number | null;
+  lastError: string | null;
+}
+
+function toDraft(rule: BadgeRule): DraftRule {
+  return {
+    id: rule.id,
+    name: rule.name,
+    label: rule.label,
+    color: rule.color,
+    script: rule.script,
+    enabled: rule.enabled,
+    timeLimitMs: rule.timeLimitMs,
+    lastError: rule.lastError,
+  };
+}
+
+function errorText(err: unknown): string {
+  return err instanceof Error ? err.message : String(err);
+}
+
+/**
+ * Custom MR list badges: each rule is a small Rhai script run against every
+ * listed MR. Saved rules apply from the next list refresh.
+ */
+export default function BadgeRulesSection() {
+  const queryClient = useQueryClient();
+  const [rules, setRules] = useState<DraftRule[] | null>(null);
+  const [dirty, setDirty] = useState(false);
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    listBadgeRules()
+      .then((saved) => setRules(saved.map(toDraft)))
+      .catch(() => setRules([]));
+  }, []);
+
+  function update(index: number, patch: Partial<DraftRule>) {
+    setRules((prev) => (prev ?? []).map((r, i) => (i === index ? { ...r, ...patch } : r)));
+    setDirty(true);
+  }
+
+  function handleAdd() {
+    setRules((prev) => [
+      ...(prev ?? []),
+      {
+        id: null,
+        name: '',
+        label: '',
+        color: null,
+        script: '',
+        enabled: true,
+        timeLimitMs: DEFAULT_TIME_LIMIT_MS,
+        lastError: null,
+      },
+    ]);
+    setDirty(true);
+  }
+
+  function handleRemove(index: number) {
+    setRules((prev) => (prev ?? []).filter((_, i) => i !== index));
+    setDirty(true);
+  }
+
+  async function handleSave() {
+    if (!rules) return;
+    setBusy(true);
+    setError(null);
+    try {
+      const saved = await updateBadgeRules(
+        rules.map(({ name, label, color, script, enabled, timeLimitMs }) => ({
+          name,
+          label,
+          color: color?.trim() ? color.trim() : null,
+          script,
+          enabled,
+          timeLimitMs,
+        })),
+      );
+      setRules(saved.map(toDraft));
+      setDirty(false);
+      queryClient.invalidateQueries({ queryKey: ['mrList'] });
+      queryClient.invalidateQueries({ queryKey: ['myMRList'] });
+    } catch (err) {
+      setError(errorText(err));
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  if (!rules) {
+    return <p className="loading">Loading settings...</p>;
+  }
+
+  return (
+    <div className="sync-settings-form">
+      <p className="shortcut-hint">
+        Each rule is a <a href="https://rhai.rs/book/" target="_blank" rel="noreferrer">Rhai</a>{' '}
+        script run against every MR in the lists, e.g.{' '}
+        <code>labels.contains("breaking") &amp;&amp; lines &gt; 500</code>. Return{' '}
+        <code>true</code> to show the badge label, or a string to show that text instead.
+        Line counts are 0 until an MR's diff has been loaded.
+      </p>
+
+      {rules.length > 0 && (
+        <ul className="badge-rules">
+          {rules.map((rule, index) => (
+            <li key={rule.id ?? `new-${index}`} className="badge-rule">
+              <div className="automation-form">
+                <input
+                  type="checkbox"
+                  aria-label="Enabled"
+                  checked={rule.enabled}
+                  onChange={(e) => update(index, { enabled: e.target.checked })}
+                />
+                <input
+                  className="companion-text-input"
+                  aria-label="Rule name"
+                  value={rule.name}
+                  onChange={(e) => update(index, { name: e.target.value })}
+                  placeholder="Name"
+                />
+                <input
+                  className="companion-text-input badge-rule-label"
+                  aria-label="Badge label"
+                  value={rule.label}
+                  onChange={(e) => update(index, { label: e.target.value })}
+                  placeholder="Badge label"
+                  maxLength={32}
+                />
+                <input
+                  className="companion-text-input badge-rule-color"
+                  aria-label="Badge color"
+                  value={rule.color ?? ''}
+                  onChange={(e) => update(index, { color: e.target.value })}
+                  placeholder="#rrggbb"
+                  spellCheck={false}
+                />
+                <label className="badge-rule-limit">
+                  <input
+                    type="number"
+                    min={1}
+                    max={100}
+                    aria-label="Time limit (ms)"
+                    value={rule.timeLimitMs ?? DEFAULT_TIME_LIMIT_MS}
+                    onChange={(e) => update(index, { timeLimitMs: Number(e.target.value) || null })}
+                  />
+                  ms
+                </label>
+                <button className="add-button" onClick={() => handleRemove(index)}>
+                  Remove
+                </button>
+              </div>
+              <textarea
+                className="badge-rule-script"
+                aria-label="Script"
+                value={rule.script}
+                onChange={(e) => update(index, { script: e.target.value })}
+                placeholder='labels.contains("breaking") && lines > 500'
+                rows={3}
+                spellCheck={false}
+              />
+              {rule.lastError && !dirty && (
+                <p className="badge-rule-error">{rule.lastError}</p>
+              )}
+            </li>
+          ))}
+        </ul>
+      )}
+
+      <div className="automation-form">
+        <button className="add-button" onClick={handleAdd} disabled={busy}>
+          Add rule
+        </button>
+        <button className="add-button" onClick={handleSave} disabled={busy || !dirty}>
+          {busy ? 'Saving...' : 'Save rules'}
+        </button>
+      </div>
+
+      <p className="shortcut-hint">
+        Variables: <code>{SCRIPT_VARIABLES}</code>
+      </p>
+
+      {error && <p className="cache-archive-result error">{error}</p>}
+    </div>
+  );
+}
@@ -0,0 +1,69 @@
+import { useState } from 'react';
+import { useQueryClient } from '@tanstack/react-query';
+import { exportCacheArchive, importCacheArchive } from '../../services/tauri';
+import type { CacheArchiveSummary } from '../../types';
+
+function describe(summary: CacheArchiveSummary, verb: string): string {
+  const text = `${verb} ${summary.mergeRequests} MRs, ${summary.diffs} diffs and ${summary.files} cached files`;
+  if (summary.skippedInstances.length === 0) return text;
+  return `${text}. Skipped instances not set up here: ${summary.skippedInstances.join(', ')}`;
+}
+
+/**
+ * Export the local cache to a file, or warm-start this machine's cache from
+ * one exported elsewhere. Archives never contain tokens.
+ */
+export default function CacheArchiveSection() {
+  const queryClient = useQueryClient();
+  const [path, setPath] = useState('');
+  const [busy, setBusy] = useState<'export' | 'import' | null>(null);
+  const [result, setResult] = useState<{ ok: boolean; text: string } | null>(null);
+
+  async function run(action: 'export' | 'import') {
+    setBusy(action);
+    setResult(null);
+    try {
+      if (action === 'export') {
+        const summary = await exportCacheArchive(path.trim());
+        setResult({ ok: true, text: describe(summary, 'Exported') });
+      } else {
+        const summary = await importCacheArchive(path.trim());
+        setResult({ ok: true, text: describe(summary, 'Imported') });
+        queryClient.invalidateQueries();
+      }
+    } catch (err) {
+      setResult({ ok: false, text: err instanceof Error ? err.message : String(err) });
+    } finally {
+      setBusy(null);
+    }
+  }
+
+  return (
+    <div className="sync-settings-form">
+      <p className="shortcut-hint">
+        Copy cached MRs, diffs and file content to another machine so its first sync
+        doesn't have to download everything. Only instances configured on both sides are
+        imported; tokens are never included.
+      </p>
+      <div className="api-console-request">
+        <input
+          className="companion-text-input api-console-path"
+          aria-label="Archive path"
+          value={path}
+          onChange={(e) => setPath(e.target.value)}
+          placeholder="/Users/me/ultra-gitlab-cache.json.gz"
+          spellCheck={false}
+        />
+        <button className="add-button" onClick={() => run('export')} disabled={busy !== null || !path.trim()}>
+          {busy === 'export' ? 'Exporting...' : 'Export'}
+        </button>
+        <button className="add-button" onClick={() => run('import')} disabled={busy !== null || !path.trim()}>
+          {busy === 'import' ? 'Importing...' : 'Import'}
+        </button>
+      </div>
+      {result && (
+        <p className={`cache-archive-result ${result.ok ? '' : 'error'}`}>{result.text}</p>
+      )}
+    </div>
+  );
+}
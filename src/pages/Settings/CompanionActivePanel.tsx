@@ -5,6 +5,8 @@ import CompanionDeviceList from './CompanionDeviceList';
 interface CompanionActivePanelProps {
   settings: CompanionServerSettings;
   qrSvg: string | null;
+  /** URL phones open, or null when it can't be determined (proxied without a public URL). */
+  url: string | null;
   saving: boolean;
   onRegeneratePin: () => void;
   onSetPin: (pin: string) => void;
@@ -14,7 +16,7 @@ interface CompanionActivePanelProps {
 /**
  * Content shown when the companion server is enabled: QR code, PIN, and device list.
  */
-export default function CompanionActivePanel({ settings, qrSvg, saving, onRegeneratePin, onSetPin, onRevokeDevice }: CompanionActivePanelProps) {
+export default function CompanionActivePanel({ settings, qrSvg, url, saving, onRegeneratePin, onSetPin, onRevokeDevice }: CompanionActivePanelProps) {
   const [editing, setEditing] = useState(false);
   const [pinInput, setPinInput] = useState('');
   const [pinError, setPinError] = useState<string | null>(null);
@@ -55,10 +57,15 @@ export default function CompanionActivePanel({ settings, qrSvg, saving, onRegene
               alt="QR code to connect your phone"
             />
             <p className="companion-qr-hint">Scan with your phone camera</p>
+            {url && <p className="companion-url-hint"><code>{url}</code></p>}
           </div>
+        ) : settings.bindMode !== 'lan' && !url ? (
+          <p className="companion-url-hint">
+            Set a public URL to show a pairing QR code for your reverse proxy.
+          </p>
         ) : (
           <p className="companion-url-hint">
-            Open on your phone: <code>http://{'<your-ip>'}:{settings.port}</code>
+            Open on your phone: <code>{url ?? `http://<your-ip>:${settings.port}${settings.basePath}`}</code>
           </p>
         )}
       </div>
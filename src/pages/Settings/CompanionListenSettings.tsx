@@ -0,0 +1,179 @@
+import { useState, useEffect } from 'react';
+import type { CompanionBindMode, CompanionServerSettings } from '../../types';
+
+interface CompanionListenSettingsProps {
+  settings: CompanionServerSettings;
+  saving: boolean;
+  /** Persist a change; resolves to an error message if it was rejected. */
+  onSave: (patch: Partial<CompanionServerSettings>) => Promise<string | null>;
+}
+
+const BIND_MODE_LABELS: Record<CompanionBindMode, string> = {
+  lan: 'Local network',
+  loopback: 'Loopback only (reverse proxy)',
+  unixSocket: 'Unix socket (reverse proxy)',
+};
+
+/**
+ * Listen address settings: LAN, loopback or Unix socket, plus the base path
+ * and public URL used when the server sits behind a reverse proxy.
+ */
+export default function CompanionListenSettings({ settings, saving, onSave }: CompanionListenSettingsProps) {
+  const [portInput, setPortInput] = useState(String(settings.port));
+  const [socketPathInput, setSocketPathInput] = useState(settings.socketPath ?? '');
+  const [basePathInput, setBasePathInput] = useState(settings.basePath);
+  const [publicUrlInput, setPublicUrlInput] = useState(settings.publicUrl ?? '');
+  const [error, setError] = useState<{ field: string; message: string } | null>(null);
+
+  // Pick up normalized values saved by the backend
+  useEffect(() => {
+    setPortInput(String(settings.port));
+    setSocketPathInput(settings.socketPath ?? '');
+    setBasePathInput(settings.basePath);
+    setPublicUrlInput(settings.publicUrl ?? '');
+  }, [settings.port, settings.socketPath, settings.basePath, settings.publicUrl]);
+
+  const proxied = settings.bindMode !== 'lan';
+
+  async function save(field: string, patch: Partial<CompanionServerSettings>) {
+    const message = await onSave(patch);
+    setError(message ? { field, message } : null);
+  }
+
+  function handlePortBlur() {
+    const port = parseInt(portInput, 10);
+    if (isNaN(port) || port < 1024 || port > 65535) {
+      setError({ field: 'port', message: 'Port must be between 1024 and 65535' });
+      return;
+    }
+    if (port === settings.port) return;
+    save('port', { port });
+  }
+
+  function handleBindModeChange(bindMode: CompanionBindMode) {
+    // Switching to a Unix socket needs a path first; save it together with the path.
+    if (bindMode === 'unixSocket' && !socketPathInput.trim()) {
+      setError({ field: 'socketPath', message: 'Enter a socket path, then select Unix socket again' });
+      return;
+    }
+    save('bindMode', { bindMode, socketPath: socketPathInput.trim() || null });
+  }
+
+  function commitOnEnter(e: React.KeyboardEvent<HTMLInputElement>) {
+    if (e.key === 'Enter') (e.target as HTMLInputElement).blur();
+  }
+
+  function fieldError(field: string) {
+    return error?.field === field ? <span className="companion-port-error">{error.message}</span> : null;
+  }
+
+  return (
+    <>
+      <div className="setting-row">
+        <label htmlFor="companion-bind-mode">Listen on</label>
+        <select
+          id="companion-bind-mode"
+          value={settings.bindMode}
+          onChange={(e) => handleBindModeChange(e.target.value as CompanionBindMode)}
+          disabled={saving}
+        >
+          {(Object.keys(BIND_MODE_LABELS) as CompanionBindMode[]).map((mode) => (
+            <option key={mode} value={mode}>{BIND_MODE_LABELS[mode]}</option>
+          ))}
+        </select>
+        {fieldError('bindMode')}
+      </div>
+
+      {settings.bindMode !== 'unixSocket' && (
+        <div className="setting-row">
+          <label htmlFor="companion-port">Port</label>
+          <div className="companion-port-row">
+            <input
+              id="companion-port"
+              type="number"
+              className="companion-port-input"
+              value={portInput}
+              onChange={(e) => { setPortInput(e.target.value); setError(null); }}
+              onBlur={handlePortBlur}
+              onKeyDown={commitOnEnter}
+              min={1024}
+              max={65535}
+              disabled={saving}
+            />
+            {fieldError('port')}
+          </div>
+        </div>
+      )}
+
+      {(settings.bindMode === 'unixSocket' || error?.field === 'socketPath') && (
+        <div className="setting-row">
+          <label htmlFor="companion-socket-path">Socket path</label>
+          <div className="companion-port-row">
+            <input
+              id="companion-socket-path"
+              type="text"
+              className="companion-text-input"
+              placeholder="/run/ultra-gitlab/companion.sock"
+              value={socketPathInput}
+              onChange={(e) => setSocketPathInput(e.target.value)}
+              onBlur={() => {
+                if (settings.bindMode === 'unixSocket' && socketPathInput.trim() !== (settings.socketPath ?? '')) {
+                  save('socketPath', { socketPath: socketPathInput.trim() || null });
+                }
+              }}
+              onKeyDown={commitOnEnter}
+              disabled={saving}
+            />
+            {fieldError('socketPath')}
+          </div>
+        </div>
+      )}
+
+      {proxied && (
+        <>
+          <div className="setting-row">
+            <label htmlFor="companion-base-path">Base path</label>
+            <div className="companion-port-row">
+              <input
+                id="companion-base-path"
+                type="text"
+                className="companion-text-input"
+                placeholder="/ (served from the root)"
+                value={basePathInput}
+                onChange={(e) => setBasePathInput(e.target.value)}
+                onBlur={() => {
+                  if (basePathInput !== settings.basePath) save('basePath', { basePath: basePathInput });
+                }}
+                onKeyDown={commitOnEnter}
+                disabled={saving}
+              />
+              {fieldError('basePath')}
+            </div>
+          </div>
+
+          <div className="setting-row">
+            <label htmlFor="companion-public-url">Public URL</label>
+            <div className="companion-port-row">
+              <input
+                id="companion-public-url"
+                type="url"
+                className="companion-text-input"
+                placeholder="https://review.example.com/gitlab"
+                value={publicUrlInput}
+                onChange={(e) => setPublicUrlInput(e.target.value)}
+                onBlur={() => {
+                  if (publicUrlInput.trim() !== (settings.publicUrl ?? '')) {
+                    save('publicUrl', { publicUrl: publicUrlInput.trim() || null });
+                  }
+                }}
+                onKeyDown={commitOnEnter}
+                disabled={saving}
+              />
+              {fieldError('publicUrl')}
+            </div>
+          </div>
+        </>
+      )}
+    </>
+  );
+}
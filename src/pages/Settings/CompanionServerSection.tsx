@@ -1,11 +1,12 @@
 import { useState, useEffect, useCallback } from 'react';
 import { useQueryClient } from '@tanstack/react-query';
-import { getCompanionQrSvg, updateCompanionSettings, regenerateCompanionPin, setCompanionPin, revokeCompanionDevice, startCompanionServer, stopCompanionServer } from '../../services/tauri';
+import { getCompanionQrSvg, getCompanionUrl, updateCompanionSettings, regenerateCompanionPin, setCompanionPin, revokeCompanionDevice, startCompanionServer, stopCompanionServer } from '../../services/tauri';
 import type { CompanionServerSettings } from '../../types';
 import { useToast } from '../../components/Toast';
 import { useCompanionSettingsQuery } from '../../hooks/queries/useCompanionSettingsQuery';
 import { queryKeys } from '../../lib/queryKeys';
 import CompanionActivePanel from './CompanionActivePanel';
+import CompanionListenSettings from './CompanionListenSettings';
 
 /**
  * Companion server settings section.
@@ -17,28 +18,25 @@ export default function CompanionServerSection() {
   const settingsQuery = useCompanionSettingsQuery();
 
   const [saving, setSaving] = useState(false);
-  const [portInput, setPortInput] = useState('');
-  const [portError, setPortError] = useState<string | null>(null);
   const [qrSvg, setQrSvg] = useState<string | null>(null);
+  const [companionUrl, setCompanionUrl] = useState<string | null>(null);
 
   const settings = settingsQuery.data ?? null;
 
   const refreshQrSvg = useCallback((s: CompanionServerSettings) => {
-    if (s.enabled && s.port) {
+    if (s.enabled) {
       getCompanionQrSvg().then(setQrSvg).catch(() => setQrSvg(null));
+      getCompanionUrl().then(setCompanionUrl).catch(() => setCompanionUrl(null));
     } else {
       setQrSvg(null);
+      setCompanionUrl(null);
     }
   }, []);
 
-  // Sync port input and QR when query data changes
+  // Refresh the QR code and URL when query data changes
   useEffect(() => {
     if (!settings) return;
     refreshQrSvg(settings);
-    setPortInput((prev) => {
-      const currentPort = String(settings.port);
-      return prev === '' || prev === currentPort ? currentPort : prev;
-    });
   }, [settings, refreshQrSvg]);
 
   const invalidateSettings = useCallback(() => {
@@ -53,7 +51,8 @@ export default function CompanionServerSection() {
       await updateCompanionSettings(updated);
       if (enabled) {
         await startCompanionServer();
-        addToast({ type: 'info', title: 'Companion Server', body: `Server started on port ${settings.port}` });
+        const where = settings.bindMode === 'unixSocket' ? settings.socketPath : `port ${settings.port}`;
+        addToast({ type: 'info', title: 'Companion Server', body: `Server started on ${where}` });
       } else {
         await stopCompanionServer();
         addToast({ type: 'info', title: 'Companion Server', body: 'Server stopped' });
@@ -68,28 +67,21 @@ export default function CompanionServerSection() {
     }
   }
 
-  async function handlePortBlur() {
-    if (!settings) return;
-    const port = parseInt(portInput, 10);
-    if (isNaN(port) || port < 1024 || port > 65535) {
-      setPortError('Port must be between 1024 and 65535');
-      return;
-    }
-    setPortError(null);
-    if (port === settings.port) return;
-
-    const updated = { ...settings, port };
+  /** Save a listen setting and restart a running server so it takes effect. */
+  async function handleListenChange(patch: Partial<CompanionServerSettings>): Promise<string | null> {
+    if (!settings) return null;
     try {
       setSaving(true);
-      await updateCompanionSettings(updated);
+      await updateCompanionSettings({ ...settings, ...patch });
       invalidateSettings();
       if (settings.enabled) {
         await stopCompanionServer();
         await startCompanionServer();
       }
+      return null;
     } catch (err) {
-      console.error('Failed to update port:', err);
-      setPortInput(String(settings.port));
+      console.error('Failed to update companion listen settings:', err);
+      return err instanceof Error ? err.message : 'Failed to save setting';
     } finally {
       setSaving(false);
     }
@@ -174,29 +166,13 @@ export default function CompanionServerSection() {
           </label>
         </div>
 
-        <div className="setting-row">
-          <label htmlFor="companion-port">Port</label>
-          <div className="companion-port-row">
-            <input
-              id="companion-port"
-              type="number"
-              className="companion-port-input"
-              value={portInput}
-              onChange={(e) => { setPortInput(e.target.value); setPortError(null); }}
-              onBlur={handlePortBlur}
-              onKeyDown={(e) => { if (e.key === 'Enter') (e.target as HTMLInputElement).blur(); }}
-              min={1024}
-              max={65535}
-              disabled={saving}
-            />
-            {portError && <span className="companion-port-error">{portError}</span>}
-          </div>
-        </div>
+        <CompanionListenSettings settings={settings} saving={saving} onSave={handleListenChange} />
 
         {settings.enabled && (
           <CompanionActivePanel
             settings={settings}
             qrSvg={qrSvg}
+            url={companionUrl}
             saving={saving}
             onRegeneratePin={handleRegeneratePin}
             onSetPin={handleSetPin}
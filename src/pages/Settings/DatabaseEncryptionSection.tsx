@@ -0,0 +1,152 @@
+import { useState, useEffect } from 'react';
+import {
+  getDatabaseEncryptionStatus,
+  updateDatabaseEncryption,
+  exportDatabaseKey,
+  restoreDatabaseKey,
+} from '../../services/tauri';
+import type { DatabaseEncryptionStatus } from '../../types';
+import { useToast } from '../../components/Toast';
+
+function errorText(err: unknown): string {
+  return err instanceof Error ? err.message : String(err);
+}
+
+/**
+ * Encrypt the local cache at rest with SQLCipher. The key lives in the OS
+ * keychain; exporting it is the only way back in if the keychain entry is
+ * lost. Changes apply on restart, before the database is opened.
+ */
+export default function DatabaseEncryptionSection() {
+  const { addToast } = useToast();
+  const [status, setStatus] = useState<DatabaseEncryptionStatus | null>(null);
+  const [restoreKey, setRestoreKey] = useState('');
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    getDatabaseEncryptionStatus().then(setStatus).catch((err) => setError(errorText(err)));
+  }, []);
+
+  async function run(action: () => Promise<DatabaseEncryptionStatus>) {
+    setBusy(true);
+    setError(null);
+    try {
+      setStatus(await action());
+    } catch (err) {
+      setError(errorText(err));
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  async function handleExportKey() {
+    try {
+      const key = await exportDatabaseKey();
+      await navigator.clipboard.writeText(key);
+      addToast({
+        type: 'info',
+        title: 'Database key copied',
+        body: 'Store it in a password manager. Without it the encrypted cache cannot be opened.',
+      });
+    } catch (err) {
+      setError(errorText(err));
+    }
+  }
+
+  async function handleRestart() {
+    const { relaunch } = await import('@tauri-apps/plugin-process');
+    await relaunch();
+  }
+
+  if (!status) {
+    return error ? <p className="cache-archive-result error">{error}</p> : <p className="loading">Loading settings...</p>;
+  }
+
+  const pendingRestart = status.supported && status.enabled !== status.encrypted;
+
+  return (
+    <div className="sync-settings-form">
+      <div className="checkbox-group">
+        <label className="checkbox-label">
+          <input
+            type="checkbox"
+            checked={status.enabled}
+            disabled={busy || (!status.supported && !status.enabled)}
+            onChange={(e) => run(() => updateDatabaseEncryption(e.target.checked))}
+          />
+          <span>
+            Encrypt the local database
+            <span className="checkbox-description">
+              {status.supported
+                ? 'Uses SQLCipher with a key kept in the OS keychain. Applied the next time the app starts.'
+                : 'Not available in this build.'}
+            </span>
+          </span>
+        </label>
+      </div>
+
+      <p className="shortcut-hint">
+        Database is currently {status.encrypted ? 'encrypted' : 'not encrypted'}.
+        {pendingRestart && (
+          <>
+            {' '}
+            <button className="add-button" onClick={handleRestart}>
+              Restart to {status.enabled ? 'encrypt' : 'decrypt'}
+            </button>
+          </>
+        )}
+      </p>
+
+      {status.keyStored && (
+        <div className="setting-row">
+          <label>Recovery key</label>
+          <button className="add-button" onClick={handleExportKey} disabled={busy}>
+            Copy key
+          </button>
+        </div>
+      )}
+
+      {(status.lockedDatabase || (status.encrypted && !status.keyStored)) && (
+        <p className="cache-archive-result error">
+          The encrypted database could not be opened with the keychain key
+          {status.lockedDatabase ? ' and was set aside; the app is using a fresh cache' : ''}. Restore
+          the exported key below and restart to get it back.
+        </p>
+      )}
+
+      {(status.lockedDatabase || status.encrypted || status.enabled) && (
+        <div className="setting-row">
+          <label htmlFor="restore-database-key">Restore key</label>
+          <div className="automation-form">
+            <input
+              id="restore-database-key"
+              type="password"
+              className="companion-text-input"
+              placeholder="64 hex characters"
+              value={restoreKey}
+              onChange={(e) => setRestoreKey(e.target.value)}
+              spellCheck={false}
+              disabled={busy}
+            />
+            <button
+              className="add-button"
+              disabled={busy || !restoreKey.trim()}
+              onClick={() =>
+                run(async () => {
+                  const next = await restoreDatabaseKey(restoreKey);
+                  setRestoreKey('');
+                  return next;
+                })
+              }
+            >
+              Restore
+            </button>
+          </div>
+        </div>
+      )}
+
+      {error && <p className="cache-archive-result error">{error}</p>}
+    </div>
+  );
+}
@@ -0,0 +1,64 @@
+import { useState, useEffect } from 'react';
+import { useQueryClient } from '@tanstack/react-query';
+import { useSettingsQuery } from '../../hooks/queries/useSettingsQuery';
+import { queryKeys } from '../../lib/queryKeys';
+import { updateEditorCommand } from '../../services/tauri';
+
+/**
+ * External editor command. Files of a project's local clone open with it
+ * from the diff header, at the selected line.
+ */
+export default function EditorSection() {
+  const queryClient = useQueryClient();
+  const { data: settings } = useSettingsQuery();
+  const saved = settings?.editorCommand ?? '';
+
+  const [command, setCommand] = useState(saved);
+  const [saving, setSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => setCommand(saved), [saved]);
+
+  async function handleSave() {
+    setSaving(true);
+    setError(null);
+    try {
+      await updateEditorCommand(command);
+      queryClient.invalidateQueries({ queryKey: queryKeys.settings() });
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setSaving(false);
+    }
+  }
+
+  return (
+    <div className="sync-settings-form">
+      <div className="setting-row">
+        <label htmlFor="editor-command">Editor command</label>
+        <input
+          id="editor-command"
+          type="text"
+          className="companion-text-input"
+          placeholder="code -g {file}:{line}"
+          value={command}
+          onChange={(e) => setCommand(e.target.value)}
+          spellCheck={false}
+          disabled={saving}
+        />
+      </div>
+      <p className="shortcut-hint">
+        {'{file}'} is replaced by the file's path in the project's local clone and {'{line}'} by the
+        selected line. Set the clone with "Check out" on a merge request.
+      </p>
+
+      {command.trim() !== saved && (
+        <button className="add-button" onClick={handleSave} disabled={saving}>
+          Save
+        </button>
+      )}
+
+      {error && <p className="cache-archive-result error">{error}</p>}
+    </div>
+  );
+}
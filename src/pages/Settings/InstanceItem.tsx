@@ -63,13 +63,13 @@ export default function InstanceItem({ inst, tokenInfo, onDelete, onTokenUpdated
     try {
       setSaving(true);
       setError(null);
-      const username = await updateInstanceToken(inst.id, tokenInput.trim());
-      setSuccess(`Token updated (${username})`);
+      const { username, scopeCheck } = await updateInstanceToken(inst.id, tokenInput.trim());
+      setSuccess([`Token updated (${username})`, ...scopeCheck.warnings].join(' '));
       setTokenInput('');
       setTimeout(() => {
         cancelEdit();
         onTokenUpdated();
-      }, 1500);
+      }, scopeCheck.warnings.length > 0 ? 6000 : 1500);
     } catch (err) {
       setError(err instanceof Error ? err.message : 'Invalid token');
     } finally {
@@ -130,7 +130,7 @@ export default function InstanceItem({ inst, tokenInfo, onDelete, onTokenUpdated
       setCookieError(null);
       const count = await refreshAvatars(inst.id);
       clearAvatarCache();
-      setCookieSuccess(`Downloaded ${count} avatar${count === 1 ? '' : 's'}`);
+      setCookieSuccess(`Updated ${count} avatar${count === 1 ? '' : 's'}`);
       setTimeout(() => setCookieSuccess(null), 3000);
     } catch (err) {
       setCookieError(err instanceof Error ? err.message : 'Refresh failed');
@@ -140,6 +140,7 @@ export default function InstanceItem({ inst, tokenInfo, onDelete, onTokenUpdated
   }
 
   const hasCookie = !!inst.sessionCookie;
+  const canRefreshAvatars = inst.hasToken || hasCookie;
 
   return (
     <li className={`instance-item${inst.isDefault ? ' instance-item--default' : ''}`}>
@@ -180,6 +181,14 @@ export default function InstanceItem({ inst, tokenInfo, onDelete, onTokenUpdated
         {!inst.hasToken && (
           <span className="instance-status-pill instance-status-pill--warn">Token missing</span>
         )}
+        {inst.hasToken && !inst.canWrite && (
+          <span
+            className="instance-status-pill instance-status-pill--warn"
+            title="The token has read_api but not api: commenting and approving are disabled."
+          >
+            Read-only token
+          </span>
+        )}
         {tokenInfo && tokenInfo !== 'error' && (() => {
           const { text, daysLeft } = formatExpiration(tokenInfo);
           return (
@@ -289,7 +298,7 @@ export default function InstanceItem({ inst, tokenInfo, onDelete, onTokenUpdated
             <button className="edit-token-button" onClick={startCookieEdit}>
               {hasCookie ? 'Update Cookie' : 'Set Session Cookie'}
             </button>
-            {hasCookie && (
+            {canRefreshAvatars && (
               <button
                 className="edit-token-button"
                 onClick={handleRefreshAvatars}
@@ -1,8 +1,8 @@
 import { useState } from 'react';
 import { formatRelativeTime } from '../../services/storage';
-import { renameInstance, updateInstanceToken, updateSessionCookie, refreshAvatars } from '../../services/tauri';
+import { renameInstance, updateInstanceToken, updateSessionCookie, refreshAvatars, getInstanceQuota } from '../../services/tauri';
 import { clearAvatarCache } from '../../components/UserAvatar/UserAvatar';
-import type { TokenInfo } from '../../types';
+import type { InstanceQuota, NamespaceQuota, TokenInfo } from '../../types';
 import type { GitLabInstanceWithStatus } from '../../services/gitlab';
 import './InstanceItem.variant-terminal.css';
 
@@ -25,6 +25,27 @@ function formatExpiration(info: TokenInfo): { text: string; daysLeft: number | n
   return { text: `expires ${formatted} (${daysLeft}d)`, daysLeft };
 }
 
+function formatStorage(bytes: number): string {
+  if (bytes < 1024 ** 3) return `${Math.max(1, Math.round(bytes / 1024 ** 2))} MB`;
+  return `${(bytes / 1024 ** 3).toFixed(1)} GB`;
+}
+
+function formatMinutes(ns: NamespaceQuota): string | null {
+  if (ns.ciMinutesUnlimited) return 'ci-minutes unlimited';
+  if (ns.ciMinutesRemaining !== null && ns.ciMinutesLimit !== null) {
+    return `ci-minutes ${ns.ciMinutesRemaining}/${ns.ciMinutesLimit} left`;
+  }
+  if (ns.ciMinutesUsed !== null) return `ci-minutes ${ns.ciMinutesUsed} used this month`;
+  return null;
+}
+
+function formatRateLimit(quota: InstanceQuota): string {
+  const rl = quota.rateLimit;
+  if (!rl) return 'api no rate-limit headers seen';
+  const resetIn = rl.resetAt !== null ? Math.max(0, rl.resetAt - Math.floor(Date.now() / 1000)) : null;
+  return `api ${rl.remaining}/${rl.limit} requests left${resetIn !== null ? `, resets in ${resetIn}s` : ''}`;
+}
+
 export default function InstanceItem({ inst, tokenInfo, onDelete, onTokenUpdated, onSetDefault }: InstanceItemProps) {
   const [editing, setEditing] = useState(false);
   const [tokenInput, setTokenInput] = useState('');
@@ -39,6 +60,8 @@ export default function InstanceItem({ inst, tokenInfo, onDelete, onTokenUpdated
   const [refreshing, setRefreshing] = useState(false);
   const [renaming, setRenaming] = useState(false);
   const [nameInput, setNameInput] = useState('');
+  const [quota, setQuota] = useState<InstanceQuota | null>(null);
+  const [quotaLoading, setQuotaLoading] = useState(false);
   function startRename() { setRenaming(true); setNameInput(inst.name || ''); }
   function cancelRename() { setRenaming(false); setNameInput(''); }
 
@@ -55,10 +78,10 @@ export default function InstanceItem({ inst, tokenInfo, onDelete, onTokenUpdated
     if (!tokenInput.trim()) return;
     try {
       setSaving(true); setError(null);
-      const username = await updateInstanceToken(inst.id, tokenInput.trim());
-      setSuccess(`token updated → ${username}`);
+      const { username, scopeCheck } = await updateInstanceToken(inst.id, tokenInput.trim());
+      setSuccess([`token updated → ${username}`, ...scopeCheck.warnings].join(' '));
       setTokenInput('');
-      setTimeout(() => { cancelEdit(); onTokenUpdated(); }, 1500);
+      setTimeout(() => { cancelEdit(); onTokenUpdated(); }, scopeCheck.warnings.length > 0 ? 6000 : 1500);
     } catch (err) { setError(err instanceof Error ? err.message : 'invalid token'); }
     finally { setSaving(false); }
   }
@@ -94,13 +117,23 @@ export default function InstanceItem({ inst, tokenInfo, onDelete, onTokenUpdated
       setRefreshing(true); setCookieError(null);
       const count = await refreshAvatars(inst.id);
       clearAvatarCache();
-      setCookieSuccess(`fetched ${count} avatar${count === 1 ? '' : 's'}`);
+      setCookieSuccess(`updated ${count} avatar${count === 1 ? '' : 's'}`);
       setTimeout(() => setCookieSuccess(null), 3000);
     } catch (err) { setCookieError(err instanceof Error ? err.message : 'refresh failed'); }
     finally { setRefreshing(false); }
   }
 
+  async function handleQuota() {
+    if (quota) { setQuota(null); return; }
+    try {
+      setQuotaLoading(true); setCookieError(null);
+      setQuota(await getInstanceQuota(inst.id));
+    } catch (err) { setCookieError(err instanceof Error ? err.message : 'quota failed'); }
+    finally { setQuotaLoading(false); }
+  }
+
   const hasCookie = !!inst.sessionCookie;
+  const canRefreshAvatars = inst.hasToken || hasCookie;
   const tokenExp = tokenInfo && tokenInfo !== 'error' ? formatExpiration(tokenInfo) : null;
 
   return (
@@ -165,6 +198,9 @@ export default function InstanceItem({ inst, tokenInfo, onDelete, onTokenUpdated
           {!inst.hasToken && (
             <span className="term-card__tag term-card__tag--warn">TOKEN_MISSING</span>
           )}
+          {inst.hasToken && !inst.canWrite && (
+            <span className="term-card__tag term-card__tag--warn">READ_ONLY</span>
+          )}
           {inst.isDefault && (
             <span className="term-card__tag term-card__tag--active">DEFAULT</span>
           )}
@@ -252,14 +288,46 @@ export default function InstanceItem({ inst, tokenInfo, onDelete, onTokenUpdated
             <button className="term-card__cmd" onClick={startCookieEdit}>
               $ {hasCookie ? 'update-cookie' : 'set-cookie'}
             </button>
-            {hasCookie && (
+            {canRefreshAvatars && (
               <button className="term-card__cmd" onClick={handleRefreshAvatars} disabled={refreshing}>
                 $ {refreshing ? 'refreshing...' : 'refresh-avatars'}
               </button>
             )}
+            {inst.hasToken && (
+              <button className="term-card__cmd" onClick={handleQuota} disabled={quotaLoading}>
+                $ {quotaLoading ? 'loading...' : 'quota'}
+              </button>
+            )}
           </div>
         )}
 
+        {!editing && !editingCookie && quota && (
+          <div className="term-card__quota">
+            <div className="term-card__line">
+              <span className="term-card__prompt">{'>'}</span>
+              <span className="term-card__dim">{formatRateLimit(quota)}</span>
+            </div>
+            {quota.namespaces.map((ns) => {
+              const figures = [
+                ns.plan,
+                formatMinutes(ns),
+                ns.storageBytes !== null ? `storage ${formatStorage(ns.storageBytes)}` : null,
+              ].filter(Boolean);
+              return (
+                <div key={ns.fullPath} className="term-card__line">
+                  <span className="term-card__prompt">{'>'}</span>
+                  <span className="term-card__url">{ns.fullPath}</span>
+                  <span className="term-card__dim">
+                    {figures.length > 0 ? figures.join(' · ') : 'quota hidden'}
+                  </span>
+                </div>
+              );
+            })}
+            {quota.namespaces.length === 0 && (
+              <div className="term-card__hint">// no synced projects to read quotas for</div>
+            )}
+          </div>
+        )}
         {!editing && !editingCookie && cookieError && (
           <div className="term-card__output term-card__output--error">ERR: {cookieError}</div>
         )}
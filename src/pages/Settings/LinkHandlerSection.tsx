@@ -0,0 +1,112 @@
+import { useState, useEffect } from 'react';
+import { useQueryClient } from '@tanstack/react-query';
+import { useInstancesQuery } from '../../hooks/queries/useInstancesQuery';
+import { useSettingsQuery } from '../../hooks/queries/useSettingsQuery';
+import { queryKeys } from '../../lib/queryKeys';
+import { registerLinkHandler, unregisterLinkHandler } from '../../services/tauri';
+
+function hostOf(url: string): string | null {
+  try {
+    return new URL(url).host.toLowerCase();
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Opt-in OS link handler: MR links on the chosen instance hosts open in the
+ * app, every other link goes on to the fallback browser.
+ */
+export default function LinkHandlerSection() {
+  const queryClient = useQueryClient();
+  const { data: instances = [] } = useInstancesQuery();
+  const { data: settings } = useSettingsQuery();
+  const saved = settings?.linkHandler;
+
+  const [hosts, setHosts] = useState<string[]>(saved?.hosts ?? []);
+  const [fallbackBrowser, setFallbackBrowser] = useState(saved?.fallbackBrowser ?? '');
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    if (!saved) return;
+    setHosts(saved.hosts);
+    setFallbackBrowser(saved.fallbackBrowser);
+  }, [saved]);
+
+  const instanceHosts = [...new Set(instances.map((inst) => hostOf(inst.url)).filter((h): h is string => !!h))];
+  const enabled = saved?.enabled ?? false;
+
+  async function run(action: () => Promise<unknown>) {
+    setBusy(true);
+    setError(null);
+    try {
+      await action();
+      queryClient.invalidateQueries({ queryKey: queryKeys.settings() });
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  if (instanceHosts.length === 0) {
+    return <p className="shortcut-hint">Add a GitLab instance to open its links in the app.</p>;
+  }
+
+  return (
+    <div className="sync-settings-form">
+      <p className="shortcut-hint">
+        Registers the app as the system handler for web links (Windows and Linux). Merge request
+        links on the hosts below open here; all other links are passed to the fallback browser.
+      </p>
+
+      <div className="checkbox-group">
+        {instanceHosts.map((host) => (
+          <label key={host} className="checkbox-label">
+            <input
+              type="checkbox"
+              checked={hosts.includes(host)}
+              disabled={busy}
+              onChange={(e) =>
+                setHosts((prev) => (e.target.checked ? [...prev, host] : prev.filter((h) => h !== host)))
+              }
+            />
+            <span>{host}</span>
+          </label>
+        ))}
+      </div>
+
+      <div className="setting-row">
+        <label htmlFor="link-handler-browser">Fallback browser</label>
+        <input
+          id="link-handler-browser"
+          type="text"
+          className="companion-text-input"
+          placeholder="firefox"
+          value={fallbackBrowser}
+          onChange={(e) => setFallbackBrowser(e.target.value)}
+          spellCheck={false}
+          disabled={busy}
+        />
+      </div>
+
+      <div className="setting-row">
+        <button
+          className="add-button"
+          onClick={() => run(() => registerLinkHandler(hosts, fallbackBrowser))}
+          disabled={busy || hosts.length === 0 || !fallbackBrowser.trim()}
+        >
+          {enabled ? 'Update' : 'Open links in app'}
+        </button>
+        {enabled && (
+          <button className="add-button" onClick={() => run(unregisterLinkHandler)} disabled={busy}>
+            Stop handling links
+          </button>
+        )}
+      </div>
+
+      {error && <p className="cache-archive-result error">{error}</p>}
+    </div>
+  );
+}
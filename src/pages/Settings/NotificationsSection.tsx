@@ -1,7 +1,10 @@
 import { useState, useEffect } from 'react';
-import { getNotificationSettings, updateNotificationSettings, sendNativeNotification } from '../../services/tauri';
-import type { NotificationSettings } from '../../types';
+import { useQueryClient } from '@tanstack/react-query';
+import { getNotificationSettings, updateNotificationSettings, sendNativeNotification, unmuteProject } from '../../services/tauri';
+import type { MutedProject, NotificationSettings } from '../../types';
 import { useToast } from '../../components/Toast';
+import { useMutedProjectsQuery } from '../../hooks/queries/useMutedProjectsQuery';
+import { queryKeys } from '../../lib/queryKeys';
 
 /**
  * Notification settings section.
@@ -11,6 +14,9 @@ export default function NotificationsSection() {
   const [notifSettings, setNotifSettings] = useState<NotificationSettings | null>(null);
   const [loading, setLoading] = useState(true);
   const [saving, setSaving] = useState(false);
+  const queryClient = useQueryClient();
+  const mutedQuery = useMutedProjectsQuery();
+  const mutedProjects = mutedQuery.data ?? [];
 
   useEffect(() => {
     loadNotificationSettings();
@@ -43,6 +49,15 @@ export default function NotificationsSection() {
     }
   }
 
+  async function handleUnmute(muted: MutedProject) {
+    try {
+      await unmuteProject(muted.instanceId, muted.projectId);
+    } catch (err) {
+      console.error('Failed to unmute project:', err);
+    }
+    queryClient.invalidateQueries({ queryKey: queryKeys.mutedProjects() });
+  }
+
   function handleTestNotification() {
     addToast({
       type: 'mr-ready',
@@ -100,6 +115,35 @@ export default function NotificationsSection() {
             </label>
           </div>
 
+          {mutedProjects.length > 0 && (
+            <div className="muted-projects">
+              <p className="muted-projects-title">Muted projects</p>
+              <div className="collapse-patterns-list">
+                {mutedProjects.map((muted) => (
+                  <div key={`${muted.instanceId}:${muted.projectId}`} className="collapse-pattern-row">
+                    <span className="muted-project-name">
+                      {muted.nameWithNamespace ?? `Project ${muted.projectId}`}
+                    </span>
+                    <span className="muted-project-until">
+                      {muted.mutedUntil === null
+                        ? 'until unmuted'
+                        : `until ${new Date(muted.mutedUntil * 1000).toLocaleString()}`}
+                    </span>
+                    <button
+                      type="button"
+                      className="collapse-pattern-remove"
+                      onClick={() => handleUnmute(muted)}
+                      title="Unmute"
+                      aria-label={`Unmute ${muted.nameWithNamespace ?? muted.projectId}`}
+                    >
+                      ×
+                    </button>
+                  </div>
+                ))}
+              </div>
+            </div>
+          )}
+
           {saving && (
             <p className="saving-indicator">Saving...</p>
           )}
@@ -0,0 +1,174 @@
+import { useState, useEffect } from 'react';
+import { useQueryClient } from '@tanstack/react-query';
+import { useSettingsQuery } from '../../hooks/queries/useSettingsQuery';
+import { queryKeys } from '../../lib/queryKeys';
+import { updateReviewAssistantSettings } from '../../services/tauri';
+import type { AssistantProvider, AssistantSettings } from '../../types';
+
+const DEFAULT_ASSISTANT: AssistantSettings = {
+  enabled: false,
+  provider: 'openAiCompatible',
+  endpoint: '',
+  apiKey: null,
+  model: '',
+  redactTerms: [],
+};
+
+const ENDPOINT_PLACEHOLDERS: Record<AssistantProvider, string> = {
+  openAiCompatible: 'https://api.example.com/v1',
+  ollama: 'http://localhost:11434',
+};
+
+/**
+ * Review assistant provider. Off until enabled; the comment composer then
+ * offers "Suggest" on a selected line range and the MR page a summary, both
+ * built from cached diffs with secrets and the terms below masked.
+ */
+export default function ReviewAssistantSection() {
+  const queryClient = useQueryClient();
+  const { data: settings } = useSettingsQuery();
+  const saved = settings?.reviewAssistant ?? DEFAULT_ASSISTANT;
+
+  const [draft, setDraft] = useState<AssistantSettings>(saved);
+  const [termsText, setTermsText] = useState(saved.redactTerms.join('\n'));
+  const [saving, setSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    if (settings?.reviewAssistant) {
+      setDraft(settings.reviewAssistant);
+      setTermsText(settings.reviewAssistant.redactTerms.join('\n'));
+    }
+  }, [settings?.reviewAssistant]);
+
+  async function save(next: AssistantSettings) {
+    setSaving(true);
+    setError(null);
+    try {
+      await updateReviewAssistantSettings(next);
+      queryClient.invalidateQueries({ queryKey: queryKeys.settings() });
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setSaving(false);
+    }
+  }
+
+  const terms = termsText
+    .split('\n')
+    .map((t) => t.trim())
+    .filter(Boolean);
+  const dirty =
+    draft.provider !== saved.provider ||
+    draft.endpoint !== saved.endpoint ||
+    (draft.apiKey ?? '') !== (saved.apiKey ?? '') ||
+    draft.model !== saved.model ||
+    terms.join('\n') !== saved.redactTerms.join('\n');
+
+  return (
+    <div className="sync-settings-form">
+      <div className="checkbox-group">
+        <label className="checkbox-label">
+          <input
+            type="checkbox"
+            checked={draft.enabled}
+            disabled={saving}
+            onChange={(e) => {
+              const next = { ...draft, redactTerms: terms, enabled: e.target.checked };
+              setDraft(next);
+              save(next);
+            }}
+          />
+          <span>
+            Enable review assistant
+            <span className="checkbox-description">
+              When you ask for a suggestion or summary, the MR title, description and diff are
+              sent to the endpoint below. Detected secrets and the terms listed here are masked
+              first.
+            </span>
+          </span>
+        </label>
+      </div>
+
+      <div className="setting-row">
+        <label htmlFor="assistant-provider">Provider</label>
+        <select
+          id="assistant-provider"
+          value={draft.provider}
+          onChange={(e) => setDraft({ ...draft, provider: e.target.value as AssistantProvider })}
+          disabled={saving}
+        >
+          <option value="openAiCompatible">OpenAI-compatible API</option>
+          <option value="ollama">Ollama</option>
+        </select>
+      </div>
+
+      <div className="setting-row">
+        <label htmlFor="assistant-endpoint">URL</label>
+        <input
+          id="assistant-endpoint"
+          type="url"
+          className="companion-text-input"
+          placeholder={ENDPOINT_PLACEHOLDERS[draft.provider]}
+          value={draft.endpoint}
+          onChange={(e) => setDraft({ ...draft, endpoint: e.target.value })}
+          spellCheck={false}
+          disabled={saving}
+        />
+      </div>
+
+      <div className="setting-row">
+        <label htmlFor="assistant-model">Model</label>
+        <input
+          id="assistant-model"
+          type="text"
+          className="companion-text-input"
+          placeholder="llama3.1"
+          value={draft.model}
+          onChange={(e) => setDraft({ ...draft, model: e.target.value.trim() })}
+          spellCheck={false}
+          disabled={saving}
+        />
+      </div>
+
+      <div className="setting-row">
+        <label htmlFor="assistant-api-key">API key</label>
+        <input
+          id="assistant-api-key"
+          type="password"
+          className="companion-text-input"
+          placeholder="Optional"
+          value={draft.apiKey ?? ''}
+          onChange={(e) => setDraft({ ...draft, apiKey: e.target.value || null })}
+          disabled={saving}
+        />
+      </div>
+
+      <div className="setting-row">
+        <label htmlFor="assistant-redact">Always mask (one per line)</label>
+        <textarea
+          id="assistant-redact"
+          className="companion-text-input"
+          rows={3}
+          placeholder="internal.example.com"
+          value={termsText}
+          onChange={(e) => setTermsText(e.target.value)}
+          spellCheck={false}
+          disabled={saving}
+        />
+      </div>
+
+      {dirty && (
+        <button
+          className="add-button"
+          onClick={() => save({ ...draft, redactTerms: terms })}
+          disabled={saving}
+        >
+          Save
+        </button>
+      )}
+
+      {error && <p className="cache-archive-result error">{error}</p>}
+    </div>
+  );
+}
@@ -0,0 +1,170 @@
+import { useState, useEffect } from 'react';
+import { useQueryClient } from '@tanstack/react-query';
+import { useSyncSettingsQuery } from '../../hooks/queries/useSyncSettingsQuery';
+import { useUpdateSyncSettingsMutation } from '../../hooks/queries/useUpdateSyncSettingsMutation';
+import { queryKeys } from '../../lib/queryKeys';
+import type { ReviewSlaConfig } from '../../types';
+
+const DEFAULT_SLA: ReviewSlaConfig = {
+  enabled: false,
+  respond_within_hours: 8,
+  at_risk_percent: 75,
+  workday_start_hour: 9,
+  workday_end_hour: 17,
+  work_days: [1, 2, 3, 4, 5],
+};
+
+const WEEKDAYS = [
+  { value: 1, label: 'Mon' },
+  { value: 2, label: 'Tue' },
+  { value: 3, label: 'Wed' },
+  { value: 4, label: 'Thu' },
+  { value: 5, label: 'Fri' },
+  { value: 6, label: 'Sat' },
+  { value: 7, label: 'Sun' },
+];
+
+const HOURS = Array.from({ length: 25 }, (_, h) => h);
+
+/**
+ * Review SLA. MRs awaiting my review are on track, at risk or overdue by
+ * the working hours passed since I was asked; crossing into at risk or
+ * overdue sends a notification.
+ */
+export default function ReviewSlaSection() {
+  const queryClient = useQueryClient();
+  const { data: syncSettings } = useSyncSettingsQuery();
+  const updateMutation = useUpdateSyncSettingsMutation();
+  const saved = syncSettings?.review_sla ?? DEFAULT_SLA;
+
+  const [draft, setDraft] = useState<ReviewSlaConfig>(saved);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    if (syncSettings?.review_sla) setDraft(syncSettings.review_sla);
+  }, [syncSettings?.review_sla]);
+
+  function save(next: ReviewSlaConfig) {
+    if (!syncSettings) return;
+    setError(null);
+    updateMutation.mutate(
+      { ...syncSettings, review_sla: next },
+      {
+        onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.reviewSlaStatus() }),
+        onError: (err) => setError(err instanceof Error ? err.message : String(err)),
+      }
+    );
+  }
+
+  function toggleDay(day: number) {
+    const work_days = draft.work_days.includes(day)
+      ? draft.work_days.filter((d) => d !== day)
+      : [...draft.work_days, day].sort((a, b) => a - b);
+    setDraft({ ...draft, work_days });
+  }
+
+  const saving = updateMutation.isPending;
+  const dirty = JSON.stringify(draft) !== JSON.stringify(saved);
+
+  return (
+    <div className="sync-settings-form">
+      <div className="checkbox-group">
+        <label className="checkbox-label">
+          <input
+            type="checkbox"
+            checked={draft.enabled}
+            disabled={saving || !syncSettings}
+            onChange={(e) => {
+              const next = { ...draft, enabled: e.target.checked };
+              setDraft(next);
+              save(next);
+            }}
+          />
+          <span>
+            Review response target
+            <span className="checkbox-description">
+              Mark MRs waiting on your review as due soon or overdue, counting only working hours.
+            </span>
+          </span>
+        </label>
+      </div>
+
+      <div className="setting-row">
+        <label htmlFor="sla-hours">Respond within</label>
+        <input
+          id="sla-hours"
+          type="number"
+          min={1}
+          className="companion-text-input"
+          value={draft.respond_within_hours}
+          onChange={(e) => setDraft({ ...draft, respond_within_hours: Number(e.target.value) })}
+          disabled={saving || !draft.enabled}
+        />
+        <span>working hours</span>
+      </div>
+
+      <div className="setting-row">
+        <label htmlFor="sla-at-risk">Due soon after</label>
+        <input
+          id="sla-at-risk"
+          type="number"
+          min={1}
+          max={99}
+          className="companion-text-input"
+          value={draft.at_risk_percent}
+          onChange={(e) => setDraft({ ...draft, at_risk_percent: Number(e.target.value) })}
+          disabled={saving || !draft.enabled}
+        />
+        <span>% of the target</span>
+      </div>
+
+      <div className="setting-row">
+        <label htmlFor="sla-start">Working hours</label>
+        <select
+          id="sla-start"
+          value={draft.workday_start_hour}
+          onChange={(e) => setDraft({ ...draft, workday_start_hour: Number(e.target.value) })}
+          disabled={saving || !draft.enabled}
+        >
+          {HOURS.slice(0, 24).map((h) => (
+            <option key={h} value={h}>{`${h}:00`}</option>
+          ))}
+        </select>
+        <label htmlFor="sla-end">to</label>
+        <select
+          id="sla-end"
+          value={draft.workday_end_hour}
+          onChange={(e) => setDraft({ ...draft, workday_end_hour: Number(e.target.value) })}
+          disabled={saving || !draft.enabled}
+        >
+          {HOURS.slice(1).map((h) => (
+            <option key={h} value={h}>{`${h}:00`}</option>
+          ))}
+        </select>
+      </div>
+
+      <div className="setting-row">
+        <span>Working days</span>
+        {WEEKDAYS.map(({ value, label }) => (
+          <label key={value} className="checkbox-label">
+            <input
+              type="checkbox"
+              checked={draft.work_days.includes(value)}
+              onChange={() => toggleDay(value)}
+              disabled={saving || !draft.enabled}
+            />
+            <span>{label}</span>
+          </label>
+        ))}
+      </div>
+
+      {dirty && (
+        <button className="add-button" onClick={() => save(draft)} disabled={saving}>
+          Save
+        </button>
+      )}
+
+      {error && <p className="cache-archive-result error">{error}</p>}
+    </div>
+  );
+}
@@ -0,0 +1,96 @@
+import { useState, useEffect } from 'react';
+import { getReviewStreaks, setReviewStatsEnabled, exportReviewStats } from '../../services/tauri';
+import type { ReviewStreaks } from '../../types';
+import { useToast } from '../../components/Toast';
+
+function formatDuration(secs: number): string {
+  if (secs < 3600) return `${Math.max(1, Math.round(secs / 60))}m`;
+  if (secs < 86400) return `${Math.round(secs / 3600)}h`;
+  return `${(secs / 86400).toFixed(1)}d`;
+}
+
+/**
+ * Opt-in review stats — streaks, comments per week and response time,
+ * tracked locally only while enabled.
+ */
+export default function ReviewStatsSection() {
+  const { addToast } = useToast();
+  const [stats, setStats] = useState<ReviewStreaks | null>(null);
+  const [saving, setSaving] = useState(false);
+
+  useEffect(() => {
+    getReviewStreaks().then(setStats).catch(() => {});
+  }, []);
+
+  async function handleToggle(enabled: boolean) {
+    try {
+      setSaving(true);
+      setStats(await setReviewStatsEnabled(enabled));
+    } catch (err) {
+      console.error('Failed to update review stats:', err);
+    } finally {
+      setSaving(false);
+    }
+  }
+
+  async function handleExport() {
+    try {
+      const data = await exportReviewStats();
+      await navigator.clipboard.writeText(JSON.stringify(data, null, 2));
+      addToast({ type: 'info', title: 'Review stats copied', body: `${data.events.length} events as JSON` });
+    } catch (err) {
+      addToast({
+        type: 'info',
+        title: 'Export failed',
+        body: err instanceof Error ? err.message : 'Failed to export review stats',
+      });
+    }
+  }
+
+  if (!stats) {
+    return <p className="loading">Loading settings...</p>;
+  }
+
+  return (
+    <div className="sync-settings-form">
+      <div className="checkbox-group">
+        <label className="checkbox-label">
+          <input
+            type="checkbox"
+            checked={stats.enabled}
+            onChange={(e) => handleToggle(e.target.checked)}
+            disabled={saving}
+          />
+          <span>
+            Track my review activity
+            <span className="checkbox-description">
+              Stored on this machine only. Turning this off deletes what was recorded.
+            </span>
+          </span>
+        </label>
+      </div>
+
+      {stats.enabled && (
+        <>
+          <dl className="review-stats">
+            <dt>Current streak</dt>
+            <dd>{stats.currentStreakDays} {stats.currentStreakDays === 1 ? 'day' : 'days'}</dd>
+            <dt>Longest streak</dt>
+            <dd>{stats.longestStreakDays} {stats.longestStreakDays === 1 ? 'day' : 'days'}</dd>
+            <dt>Review days</dt>
+            <dd>{stats.reviewDays}</dd>
+            <dt>Median response</dt>
+            <dd>{stats.medianResponseSecs === null ? '—' : formatDuration(stats.medianResponseSecs)}</dd>
+            <dt>Comments per week</dt>
+            <dd>{stats.commentsPerWeek.map((w) => w.comments).join(' · ')}</dd>
+          </dl>
+          <div style={{ marginTop: 12 }}>
+            <button className="add-button" onClick={handleExport}>
+              Copy as JSON
+            </button>
+          </div>
+        </>
+      )}
+    </div>
+  );
+}
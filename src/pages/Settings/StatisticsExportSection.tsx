@@ -0,0 +1,85 @@
+import { useState } from 'react';
+import { exportStatistics } from '../../services/tauri';
+import type { StatisticsExportFormat } from '../../types';
+
+/** Export ranges offered, in days; 0 exports everything recorded. */
+const RANGES = [
+  { days: 7, label: 'Last 7 days' },
+  { days: 30, label: 'Last 30 days' },
+  { days: 90, label: 'Last 90 days' },
+  { days: 0, label: 'Everything' },
+];
+
+/**
+ * Export review activity, sync performance and pipeline outcomes from the
+ * local database for use in a team's own dashboards.
+ */
+export default function StatisticsExportSection() {
+  const [days, setDays] = useState(30);
+  const [format, setFormat] = useState<StatisticsExportFormat>('csv');
+  const [path, setPath] = useState('');
+  const [busy, setBusy] = useState(false);
+  const [result, setResult] = useState<{ ok: boolean; text: string } | null>(null);
+
+  async function handleExport() {
+    setBusy(true);
+    setResult(null);
+    try {
+      const from = days > 0 ? Math.floor(Date.now() / 1000) - days * 86400 : null;
+      const summary = await exportStatistics({ from, to: null }, format, path.trim());
+      setResult({
+        ok: true,
+        text: `Exported ${summary.reviewEvents} review events, ${summary.syncMetrics} sync timings and ${summary.pipelines} pipeline outcomes`,
+      });
+    } catch (err) {
+      setResult({ ok: false, text: err instanceof Error ? err.message : String(err) });
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  return (
+    <div className="sync-settings-form">
+      <p className="shortcut-hint">
+        Write review activity, sync timings and pipeline outcomes to a file for your own
+        dashboards. CSV uses one row per record with a <code>dataset</code> column; JSON
+        keeps each dataset separate. Review activity is only there if review stats are on.
+      </p>
+      <div className="api-console-request">
+        <select
+          aria-label="Range"
+          value={days}
+          onChange={(e) => setDays(Number(e.target.value))}
+        >
+          {RANGES.map((r) => (
+            <option key={r.days} value={r.days}>
+              {r.label}
+            </option>
+          ))}
+        </select>
+        <select
+          aria-label="Format"
+          value={format}
+          onChange={(e) => setFormat(e.target.value as StatisticsExportFormat)}
+        >
+          <option value="csv">CSV</option>
+          <option value="json">JSON</option>
+        </select>
+        <input
+          className="companion-text-input api-console-path"
+          aria-label="Export path"
+          value={path}
+          onChange={(e) => setPath(e.target.value)}
+          placeholder={`/Users/me/ultra-gitlab-stats.${format}`}
+          spellCheck={false}
+        />
+        <button className="add-button" onClick={handleExport} disabled={busy || !path.trim()}>
+          {busy ? 'Exporting...' : 'Export'}
+        </button>
+      </div>
+      {result && (
+        <p className={`cache-archive-result ${result.ok ? '' : 'error'}`}>{result.text}</p>
+      )}
+    </div>
+  );
+}
@@ -1,12 +1,31 @@
 import { useState } from 'react';
+import { useQueryClient } from '@tanstack/react-query';
+import { useInstancesQuery } from '../../hooks/queries/useInstancesQuery';
+import { useSettingsQuery } from '../../hooks/queries/useSettingsQuery';
+import { useInstanceSyncConfigQuery } from '../../hooks/queries/useInstanceSyncConfigQuery';
 import { useSyncSettingsQuery } from '../../hooks/queries/useSyncSettingsQuery';
+import { useUpdateInstanceSyncConfigMutation } from '../../hooks/queries/useUpdateInstanceSyncConfigMutation';
 import { useUpdateSyncSettingsMutation } from '../../hooks/queries/useUpdateSyncSettingsMutation';
+import { queryKeys } from '../../lib/queryKeys';
+import { runSyncSelftest, setOfflineMode, triggerFullResync } from '../../services/tauri';
+import type { InstanceSyncConfig, ReviewSlaConfig, SyncSelfTestReport, VacationConfig } from '../../types';
 
 /** Sync configuration */
 interface SyncConfig {
   interval_secs: number;
   max_mrs_per_sync: number;
   issue_interval_secs: number;
+  description_preview_chars?: number;
+  bot_author_patterns?: string[];
+  auto_approve_bot_mrs?: boolean;
+  gravatar_fallback?: boolean;
+  badge?: { awaiting_review: boolean; failed_actions: boolean };
+  reconcile_interval_secs?: number;
+  pipeline_poll_interval_secs?: number;
+  disabled_stages?: Record<string, string[]>;
+  vacation?: VacationConfig;
+  review_sla?: ReviewSlaConfig;
+  action_max_attempts?: number;
 }
 
 /** Predefined sync interval options */
@@ -28,15 +47,236 @@ const ISSUE_SYNC_INTERVALS = [
   { value: 7200, label: '2 hours' },
 ];
 
+/** Deep reconciliation schedule options (0 = only on demand). */
+const RECONCILE_INTERVALS = [
+  { value: 86400, label: 'Daily' },
+  { value: 604800, label: 'Weekly' },
+  { value: 2592000, label: 'Monthly' },
+  { value: 0, label: 'Only when requested' },
+];
+
+/** How often pinned pipeline projects are checked for notifications */
+const PIPELINE_POLL_INTERVALS = [
+  { value: 30, label: '30 seconds' },
+  { value: 60, label: '1 minute' },
+  { value: 300, label: '5 minutes' },
+  { value: 0, label: 'Off' },
+];
+
+/** Push attempts per queued action before it's left failed */
+const ACTION_ATTEMPTS = [
+  { value: 1, label: 'Once (no automatic retry)' },
+  { value: 3, label: '3 attempts' },
+  { value: 5, label: '5 attempts' },
+  { value: 10, label: '10 attempts' },
+  { value: 20, label: '20 attempts' },
+];
+
+/** Sync stages that can be switched off per instance (metadata always runs) */
+const SYNC_STAGES = [
+  { name: 'approvals', label: 'Approvals', description: 'Approval state, reviewers and head pipeline.' },
+  { name: 'diff', label: 'Diffs', description: 'Changed files and cached file content.' },
+  { name: 'commits', label: 'Commits', description: 'Commit list for reviewing commit by commit.' },
+  { name: 'comments', label: 'Comments', description: 'Discussions and review threads.' },
+  { name: 'activity', label: 'Activity', description: 'Timeline of pushes, label, reviewer and state changes.' },
+  { name: 'avatars', label: 'Avatars', description: 'Author and reviewer pictures.' },
+];
+
+/** MR scopes each instance can sync */
+const SYNC_SCOPES: {
+  key: 'syncAuthored' | 'syncReviewing' | 'syncAssigned';
+  label: string;
+  description: string;
+}[] = [
+  { key: 'syncAuthored', label: 'My MRs', description: 'Merge requests you opened.' },
+  {
+    key: 'syncReviewing',
+    label: 'Review requests',
+    description: "Others' MRs you are a reviewer on and haven't approved.",
+  },
+  { key: 'syncAssigned', label: 'Assigned to me', description: 'MRs assigned to you, e.g. by bots.' },
+];
+
+/**
+ * Per-instance scopes, interval and MR cap. Empty limits follow the global
+ * settings above.
+ */
+function InstanceSyncFields({
+  instanceId,
+  label,
+  globalIntervalSecs,
+  globalMaxMrs,
+}: {
+  instanceId: number;
+  label: string;
+  globalIntervalSecs: number;
+  globalMaxMrs: number;
+}) {
+  const [error, setError] = useState<string | null>(null);
+  const { data: config } = useInstanceSyncConfigQuery(instanceId);
+  const updateMutation = useUpdateInstanceSyncConfigMutation();
+  const saving = updateMutation.isPending;
+
+  if (!config) return null;
+
+  function save(next: InstanceSyncConfig) {
+    setError(null);
+    updateMutation.mutate(next, {
+      onError: (err) => {
+        console.error('Failed to save instance sync config:', err);
+        setError(err instanceof Error ? err.message : 'Failed to save settings');
+      },
+    });
+  }
+
+  const globalInterval = SYNC_INTERVALS.find((o) => o.value === globalIntervalSecs);
+
+  return (
+    <>
+      <div className="setting-row">
+        <label>Synced MRs{label}</label>
+      </div>
+      <div className="checkbox-group">
+        {SYNC_SCOPES.map((scope) => (
+          <label key={scope.key} className="checkbox-label">
+            <input
+              type="checkbox"
+              checked={config[scope.key]}
+              onChange={(e) => save({ ...config, [scope.key]: e.target.checked })}
+              disabled={saving}
+            />
+            <span>
+              {scope.label}
+              <span className="checkbox-description">{scope.description}</span>
+            </span>
+          </label>
+        ))}
+      </div>
+      <div className="setting-row">
+        <label htmlFor={`instance-interval-${instanceId}`}>Sync Interval{label}</label>
+        <select
+          id={`instance-interval-${instanceId}`}
+          value={config.intervalSecs ?? ''}
+          onChange={(e) =>
+            save({
+              ...config,
+              intervalSecs: e.target.value === '' ? null : parseInt(e.target.value, 10),
+            })
+          }
+          disabled={saving}
+        >
+          <option value="">
+            Same as above ({globalInterval?.label ?? `${globalIntervalSecs} seconds`})
+          </option>
+          {SYNC_INTERVALS.map((option) => (
+            <option key={option.value} value={option.value}>
+              {option.label}
+            </option>
+          ))}
+        </select>
+      </div>
+      <div className="setting-row">
+        <label htmlFor={`instance-max-mrs-${instanceId}`}>Max MRs per Sync{label}</label>
+        <input
+          id={`instance-max-mrs-${instanceId}`}
+          type="number"
+          min={1}
+          max={1000}
+          placeholder={String(globalMaxMrs)}
+          defaultValue={config.maxMrsPerSync ?? ''}
+          onBlur={(e) => {
+            const value = e.target.value === '' ? null : parseInt(e.target.value, 10);
+            if (value !== config.maxMrsPerSync) save({ ...config, maxMrsPerSync: value });
+          }}
+          disabled={saving}
+        />
+      </div>
+      {error && <div className="error-message">{error}</div>}
+    </>
+  );
+}
+
+const SELFTEST_STEP_LABELS: Record<SyncSelfTestReport['steps'][number]['name'], string> = {
+  validate_token: 'Validate token',
+  list_mrs: 'List merge requests',
+  fetch_diff: 'Fetch a diff',
+  queue: 'Queue an action (dry run)',
+};
+
+/**
+ * Runs the sync self-test for one instance and shows each step, with a
+ * button to copy the report for a bug report.
+ */
+function SyncSelfTest({ instanceId, label }: { instanceId: number; label: string }) {
+  const [report, setReport] = useState<SyncSelfTestReport | null>(null);
+  const [running, setRunning] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  async function handleRun() {
+    setRunning(true);
+    setError(null);
+    try {
+      setReport(await runSyncSelftest(instanceId));
+    } catch (err) {
+      console.error('Sync self-test failed to run:', err);
+      setError(err instanceof Error ? err.message : 'Failed to run the self-test');
+    } finally {
+      setRunning(false);
+    }
+  }
+
+  return (
+    <>
+      <div className="setting-row">
+        <label>Sync self-test{label}</label>
+        <button type="button" onClick={handleRun} disabled={running}>
+          {running ? 'Running...' : 'Run self-test'}
+        </button>
+        {report && (
+          <button
+            type="button"
+            onClick={() => navigator.clipboard.writeText(JSON.stringify(report, null, 2))}
+          >
+            Copy report
+          </button>
+        )}
+      </div>
+      {report && (
+        <dl className="review-stats selftest-steps">
+          {report.steps.map((step) => (
+            <div key={step.name} className={`selftest-step selftest-step--${step.status}`}>
+              <dt>
+                {SELFTEST_STEP_LABELS[step.name]}
+                {step.status !== 'skipped' && ` (${step.durationMs} ms)`}
+              </dt>
+              <dd>
+                {step.status === 'failed' && step.httpStatus !== null && `HTTP ${step.httpStatus}: `}
+                {step.detail}
+              </dd>
+            </div>
+          ))}
+        </dl>
+      )}
+      {error && <div className="error-message">{error}</div>}
+    </>
+  );
+}
+
 /**
  * Sync settings section — interval and scope configuration.
  */
 export default function SyncSettingsSection() {
   const [error, setError] = useState<string | null>(null);
+  const [resyncStarted, setResyncStarted] = useState(false);
+  const queryClient = useQueryClient();
+  const { data: settings } = useSettingsQuery();
+  const { data: instances = [] } = useInstancesQuery();
   const syncQuery = useSyncSettingsQuery();
   const updateMutation = useUpdateSyncSettingsMutation();
+  const offline = settings?.offlineMode ?? false;
 
   const syncSettings = syncQuery.data ?? null;
+  const badge = syncSettings?.badge ?? { awaiting_review: true, failed_actions: true };
   const loading = syncQuery.isLoading;
   const saving = updateMutation.isPending;
 
@@ -49,6 +289,39 @@ export default function SyncSettingsSection() {
     });
   }
 
+  async function handleOfflineChange(next: boolean) {
+    try {
+      await setOfflineMode(next);
+      queryClient.invalidateQueries({ queryKey: queryKeys.settings() });
+    } catch (err) {
+      console.error('Failed to update offline mode:', err);
+      setError(err instanceof Error ? err.message : 'Failed to update offline mode');
+    }
+  }
+
+  async function handleFullResync() {
+    try {
+      await triggerFullResync();
+      setResyncStarted(true);
+    } catch (err) {
+      console.error('Failed to start full resync:', err);
+      setError(err instanceof Error ? err.message : 'Failed to start full resync');
+    }
+  }
+
+  function handleStageToggle(instanceId: number, stage: string, enabled: boolean) {
+    if (!syncSettings) return;
+    const disabledStages = { ...syncSettings.disabled_stages };
+    const current = disabledStages[instanceId] ?? [];
+    const next = enabled ? current.filter((s) => s !== stage) : [...current, stage];
+    if (next.length > 0) {
+      disabledStages[instanceId] = next;
+    } else {
+      delete disabledStages[instanceId];
+    }
+    saveSyncSettings({ ...syncSettings, disabled_stages: disabledStages });
+  }
+
   function handleIntervalChange(e: React.ChangeEvent<HTMLSelectElement>) {
     if (!syncSettings) return;
     const newSettings = { ...syncSettings, interval_secs: parseInt(e.target.value, 10) };
@@ -70,6 +343,23 @@ export default function SyncSettingsSection() {
         <p className="loading">Loading settings...</p>
       ) : syncSettings ? (
         <div className="sync-settings-form">
+          <div className="checkbox-group">
+            <label className="checkbox-label">
+              <input
+                type="checkbox"
+                checked={offline}
+                onChange={(e) => handleOfflineChange(e.target.checked)}
+              />
+              <span>
+                Offline mode
+                <span className="checkbox-description">
+                  Stops syncing and all other network requests. Cached MRs stay available and
+                  comments or approvals are queued until you go back online.
+                </span>
+              </span>
+            </label>
+          </div>
+
           <div className="setting-row">
             <label htmlFor="sync-interval">Sync Interval</label>
             <select
@@ -102,6 +392,192 @@ export default function SyncSettingsSection() {
             </select>
           </div>
 
+          <div className="setting-row">
+            <label htmlFor="reconcile-interval">Full Reconciliation</label>
+            <select
+              id="reconcile-interval"
+              value={syncSettings.reconcile_interval_secs ?? 604800}
+              onChange={(e) =>
+                saveSyncSettings({
+                  ...syncSettings,
+                  reconcile_interval_secs: parseInt(e.target.value, 10),
+                })
+              }
+              disabled={saving}
+            >
+              {RECONCILE_INTERVALS.map((option) => (
+                <option key={option.value} value={option.value}>
+                  {option.label}
+                </option>
+              ))}
+            </select>
+            <button type="button" onClick={handleFullResync} disabled={offline || resyncStarted}>
+              {resyncStarted ? 'Resync started' : 'Resync now'}
+            </button>
+          </div>
+
+          <div className="setting-row">
+            <label htmlFor="pipeline-poll-interval">Pinned Pipeline Checks</label>
+            <select
+              id="pipeline-poll-interval"
+              value={syncSettings.pipeline_poll_interval_secs ?? 60}
+              onChange={(e) =>
+                saveSyncSettings({
+                  ...syncSettings,
+                  pipeline_poll_interval_secs: parseInt(e.target.value, 10),
+                })
+              }
+              disabled={saving}
+            >
+              {PIPELINE_POLL_INTERVALS.map((option) => (
+                <option key={option.value} value={option.value}>
+                  {option.label}
+                </option>
+              ))}
+            </select>
+          </div>
+
+          <div className="setting-row">
+            <label htmlFor="action-max-attempts">Attempts per Action</label>
+            <select
+              id="action-max-attempts"
+              value={syncSettings.action_max_attempts ?? 5}
+              onChange={(e) =>
+                saveSyncSettings({
+                  ...syncSettings,
+                  action_max_attempts: parseInt(e.target.value, 10),
+                })
+              }
+              disabled={saving}
+            >
+              {ACTION_ATTEMPTS.map((option) => (
+                <option key={option.value} value={option.value}>
+                  {option.label}
+                </option>
+              ))}
+            </select>
+          </div>
+
+          <div className="checkbox-group">
+            <label className="checkbox-label">
+              <input
+                type="checkbox"
+                checked={syncSettings.auto_approve_bot_mrs ?? false}
+                onChange={(e) =>
+                  saveSyncSettings({ ...syncSettings, auto_approve_bot_mrs: e.target.checked })
+                }
+                disabled={saving}
+              />
+              <span>
+                Auto-approve bot MRs with a green pipeline
+                <span className="checkbox-description">
+                  Only MRs from bot accounts (Dependabot, Renovate, access tokens) where you are a
+                  reviewer.
+                </span>
+              </span>
+            </label>
+          </div>
+
+          <div className="checkbox-group">
+            <label className="checkbox-label">
+              <input
+                type="checkbox"
+                checked={syncSettings.gravatar_fallback ?? false}
+                onChange={(e) =>
+                  saveSyncSettings({ ...syncSettings, gravatar_fallback: e.target.checked })
+                }
+                disabled={saving}
+              />
+              <span>
+                Use Gravatar when an avatar can't be loaded
+                <span className="checkbox-description">
+                  Sends a hash of the user's public email to gravatar.com.
+                </span>
+              </span>
+            </label>
+          </div>
+
+          <div className="setting-row">
+            <label>App icon badge counts</label>
+          </div>
+          <div className="checkbox-group">
+            <label className="checkbox-label">
+              <input
+                type="checkbox"
+                checked={badge.awaiting_review}
+                onChange={(e) =>
+                  saveSyncSettings({
+                    ...syncSettings,
+                    badge: { ...badge, awaiting_review: e.target.checked },
+                  })
+                }
+                disabled={saving}
+              />
+              <span>
+                MRs awaiting my review
+                <span className="checkbox-description">
+                  Open MRs from others that you haven't approved yet.
+                </span>
+              </span>
+            </label>
+            <label className="checkbox-label">
+              <input
+                type="checkbox"
+                checked={badge.failed_actions}
+                onChange={(e) =>
+                  saveSyncSettings({
+                    ...syncSettings,
+                    badge: { ...badge, failed_actions: e.target.checked },
+                  })
+                }
+                disabled={saving}
+              />
+              <span>
+                Failed actions
+                <span className="checkbox-description">
+                  Comments, approvals and other actions that couldn't be pushed to GitLab.
+                </span>
+              </span>
+            </label>
+          </div>
+
+          {instances.map((inst) => {
+            const disabled = syncSettings.disabled_stages?.[inst.id] ?? [];
+            return (
+              <div key={inst.id}>
+                <InstanceSyncFields
+                  instanceId={inst.id}
+                  label={instances.length > 1 ? ` — ${inst.name || inst.url}` : ''}
+                  globalIntervalSecs={syncSettings.interval_secs}
+                  globalMaxMrs={syncSettings.max_mrs_per_sync}
+                />
+                <SyncSelfTest
+                  instanceId={inst.id}
+                  label={instances.length > 1 ? ` — ${inst.name || inst.url}` : ''}
+                />
+                <div className="setting-row">
+                  <label>Sync stages{instances.length > 1 ? ` — ${inst.name || inst.url}` : ''}</label>
+                </div>
+                <div className="checkbox-group">
+                  {SYNC_STAGES.map((stage) => (
+                    <label key={stage.name} className="checkbox-label">
+                      <input
+                        type="checkbox"
+                        checked={!disabled.includes(stage.name)}
+                        onChange={(e) => handleStageToggle(inst.id, stage.name, e.target.checked)}
+                        disabled={saving}
+                      />
+                      <span>
+                        {stage.label}
+                        <span className="checkbox-description">{stage.description}</span>
+                      </span>
+                    </label>
+                  ))}
+                </div>
+              </div>
+            );
+          })}
+
           {saving && (
             <p className="saving-indicator">Saving...</p>
           )}
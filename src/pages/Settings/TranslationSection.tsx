@@ -0,0 +1,126 @@
+import { useState, useEffect } from 'react';
+import { useQueryClient } from '@tanstack/react-query';
+import { useSettingsQuery } from '../../hooks/queries/useSettingsQuery';
+import { queryKeys } from '../../lib/queryKeys';
+import { updateTranslationSettings } from '../../services/tauri';
+import type { TranslationSettings } from '../../types';
+
+const DEFAULT_TRANSLATION: TranslationSettings = {
+  enabled: false,
+  provider: 'libreTranslate',
+  endpoint: '',
+  apiKey: null,
+  targetLang: 'en',
+};
+
+/**
+ * Comment translation provider. Nothing is sent anywhere until enabled;
+ * comments then get a "Translate" action that posts their text to the
+ * configured LibreTranslate-compatible endpoint.
+ */
+export default function TranslationSection() {
+  const queryClient = useQueryClient();
+  const { data: settings } = useSettingsQuery();
+  const saved = settings?.translation ?? DEFAULT_TRANSLATION;
+
+  const [draft, setDraft] = useState<TranslationSettings>(saved);
+  const [saving, setSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    if (settings?.translation) setDraft(settings.translation);
+  }, [settings?.translation]);
+
+  async function save(next: TranslationSettings) {
+    setSaving(true);
+    setError(null);
+    try {
+      await updateTranslationSettings(next);
+      queryClient.invalidateQueries({ queryKey: queryKeys.settings() });
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setSaving(false);
+    }
+  }
+
+  const dirty =
+    draft.endpoint !== saved.endpoint ||
+    (draft.apiKey ?? '') !== (saved.apiKey ?? '') ||
+    draft.targetLang !== saved.targetLang;
+
+  return (
+    <div className="sync-settings-form">
+      <div className="checkbox-group">
+        <label className="checkbox-label">
+          <input
+            type="checkbox"
+            checked={draft.enabled}
+            disabled={saving}
+            onChange={(e) => {
+              const next = { ...draft, enabled: e.target.checked };
+              setDraft(next);
+              save(next);
+            }}
+          />
+          <span>
+            Enable comment translation
+            <span className="checkbox-description">
+              Comment text is sent to the endpoint below when you click Translate. Turning this off
+              also clears cached translations.
+            </span>
+          </span>
+        </label>
+      </div>
+
+      <div className="setting-row">
+        <label htmlFor="translation-endpoint">LibreTranslate URL</label>
+        <input
+          id="translation-endpoint"
+          type="url"
+          className="companion-text-input"
+          placeholder="https://translate.example.com"
+          value={draft.endpoint}
+          onChange={(e) => setDraft({ ...draft, endpoint: e.target.value })}
+          spellCheck={false}
+          disabled={saving}
+        />
+      </div>
+
+      <div className="setting-row">
+        <label htmlFor="translation-api-key">API key</label>
+        <input
+          id="translation-api-key"
+          type="password"
+          className="companion-text-input"
+          placeholder="Optional"
+          value={draft.apiKey ?? ''}
+          onChange={(e) => setDraft({ ...draft, apiKey: e.target.value || null })}
+          disabled={saving}
+        />
+      </div>
+
+      <div className="setting-row">
+        <label htmlFor="translation-target">Translate into</label>
+        <input
+          id="translation-target"
+          type="text"
+          className="companion-text-input"
+          placeholder="en"
+          value={draft.targetLang}
+          onChange={(e) => setDraft({ ...draft, targetLang: e.target.value.trim() })}
+          spellCheck={false}
+          disabled={saving}
+        />
+      </div>
+
+      {dirty && (
+        <button className="add-button" onClick={() => save(draft)} disabled={saving}>
+          Save
+        </button>
+      )}
+
+      {error && <p className="cache-archive-result error">{error}</p>}
+    </div>
+  );
+}
@@ -0,0 +1,162 @@
+import { useState, useEffect } from 'react';
+import { useSyncSettingsQuery } from '../../hooks/queries/useSyncSettingsQuery';
+import { useUpdateSyncSettingsMutation } from '../../hooks/queries/useUpdateSyncSettingsMutation';
+import type { VacationAction, VacationConfig } from '../../types';
+
+const DEFAULT_VACATION: VacationConfig = {
+  enabled: false,
+  starts_at: 0,
+  ends_at: 0,
+  action: 'comment',
+  message: '',
+  delegate: null,
+};
+
+const DAY_SECS = 24 * 60 * 60;
+
+/** Unix seconds to a local `YYYY-MM-DD` for a date input. */
+function toDateInput(secs: number): string {
+  if (!secs) return '';
+  const d = new Date(secs * 1000);
+  const pad = (n: number) => String(n).padStart(2, '0');
+  return `${d.getFullYear()}-${pad(d.getMonth() + 1)}-${pad(d.getDate())}`;
+}
+
+/** Local `YYYY-MM-DD` to Unix seconds at local midnight. */
+function fromDateInput(value: string): number {
+  if (!value) return 0;
+  const [y, m, d] = value.split('-').map(Number);
+  return Math.floor(new Date(y, m - 1, d).getTime() / 1000);
+}
+
+/**
+ * Vacation mode. Between the two dates (both inclusive) the sync engine
+ * answers new review requests with an away comment, or hands them to a
+ * delegate with reviewer quick actions.
+ */
+export default function VacationSection() {
+  const { data: syncSettings } = useSyncSettingsQuery();
+  const updateMutation = useUpdateSyncSettingsMutation();
+  const saved = syncSettings?.vacation ?? DEFAULT_VACATION;
+
+  const [draft, setDraft] = useState<VacationConfig>(saved);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    if (syncSettings?.vacation) setDraft(syncSettings.vacation);
+  }, [syncSettings?.vacation]);
+
+  function save(next: VacationConfig) {
+    if (!syncSettings) return;
+    setError(null);
+    updateMutation.mutate(
+      { ...syncSettings, vacation: next },
+      { onError: (err) => setError(err instanceof Error ? err.message : String(err)) }
+    );
+  }
+
+  const saving = updateMutation.isPending;
+  const dirty = JSON.stringify(draft) !== JSON.stringify(saved);
+  // The end date input is inclusive; ends_at is the following midnight.
+  const endDate = draft.ends_at ? toDateInput(draft.ends_at - DAY_SECS) : '';
+
+  return (
+    <div className="sync-settings-form">
+      <div className="checkbox-group">
+        <label className="checkbox-label">
+          <input
+            type="checkbox"
+            checked={draft.enabled}
+            disabled={saving || !syncSettings}
+            onChange={(e) => {
+              const next = { ...draft, enabled: e.target.checked };
+              setDraft(next);
+              if (!next.enabled || (next.starts_at && next.ends_at)) save(next);
+            }}
+          />
+          <span>
+            Vacation mode
+            <span className="checkbox-description">
+              Review requests you receive between these dates get an away comment, posted from
+              your account on the next sync.
+            </span>
+          </span>
+        </label>
+      </div>
+
+      <div className="setting-row">
+        <label htmlFor="vacation-start">From</label>
+        <input
+          id="vacation-start"
+          type="date"
+          value={toDateInput(draft.starts_at)}
+          onChange={(e) => setDraft({ ...draft, starts_at: fromDateInput(e.target.value) })}
+          disabled={saving}
+        />
+        <label htmlFor="vacation-end">to</label>
+        <input
+          id="vacation-end"
+          type="date"
+          value={endDate}
+          onChange={(e) =>
+            setDraft({
+              ...draft,
+              ends_at: e.target.value ? fromDateInput(e.target.value) + DAY_SECS : 0,
+            })
+          }
+          disabled={saving}
+        />
+      </div>
+
+      <div className="setting-row">
+        <label htmlFor="vacation-action">On a new review request</label>
+        <select
+          id="vacation-action"
+          value={draft.action}
+          onChange={(e) => setDraft({ ...draft, action: e.target.value as VacationAction })}
+          disabled={saving}
+        >
+          <option value="comment">Post the away message</option>
+          <option value="reassign">Post the message and reassign to a delegate</option>
+        </select>
+      </div>
+
+      {draft.action === 'reassign' && (
+        <div className="setting-row">
+          <label htmlFor="vacation-delegate">Delegate</label>
+          <input
+            id="vacation-delegate"
+            type="text"
+            className="companion-text-input"
+            placeholder="username"
+            value={draft.delegate ?? ''}
+            onChange={(e) => setDraft({ ...draft, delegate: e.target.value.trim() || null })}
+            spellCheck={false}
+            disabled={saving}
+          />
+        </div>
+      )}
+
+      <div className="setting-row">
+        <label htmlFor="vacation-message">Away message</label>
+        <textarea
+          id="vacation-message"
+          className="companion-text-input"
+          rows={2}
+          placeholder="I'm away and can't review this right now. Please reassign the review."
+          value={draft.message}
+          onChange={(e) => setDraft({ ...draft, message: e.target.value })}
+          disabled={saving}
+        />
+      </div>
+
+      {dirty && (
+        <button className="add-button" onClick={() => save(draft)} disabled={saving}>
+          Save
+        </button>
+      )}
+
+      {error && <p className="cache-archive-result error">{error}</p>}
+    </div>
+  );
+}
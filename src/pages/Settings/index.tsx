@@ -17,12 +17,27 @@ import CollapsibleSection from './CollapsibleSection';
 import UpdatesSection from './UpdatesSection';
 import InstancesSection from './InstancesSection';
 import SyncSettingsSection from './SyncSettingsSection';
+import ActionQueueSection from './ActionQueueSection';
+import BackgroundJobsSection from './BackgroundJobsSection';
 import CompanionServerSection from './CompanionServerSection';
 import CliSection from './CliSection';
 import AppearanceSection from './AppearanceSection';
 import NotificationsSection from './NotificationsSection';
+import ReviewStatsSection from './ReviewStatsSection';
 import CollapsePatternsEditor from './CollapsePatternsEditor';
 import NavigationSection from './NavigationSection';
+import EditorSection from './EditorSection';
+import ApiConsoleSection from './ApiConsoleSection';
+import CacheArchiveSection from './CacheArchiveSection';
+import StatisticsExportSection from './StatisticsExportSection';
+import DatabaseEncryptionSection from './DatabaseEncryptionSection';
+import AutomationsSection from './AutomationsSection';
+import BadgeRulesSection from './BadgeRulesSection';
+import TranslationSection from './TranslationSection';
+import LinkHandlerSection from './LinkHandlerSection';
+import ReviewAssistantSection from './ReviewAssistantSection';
+import VacationSection from './VacationSection';
+import ReviewSlaSection from './ReviewSlaSection';
 import ShortcutEditor from './ShortcutEditor';
 import '../Settings.css';
 
@@ -86,6 +101,18 @@ export default function Settings({ updateChecker }: SettingsProps) {
           <SyncSettingsSection />
         </CollapsibleSection>
 
+        {isTauri && (
+          <CollapsibleSection title="Action Queue">
+            <ActionQueueSection />
+          </CollapsibleSection>
+        )}
+
+        {isTauri && (
+          <CollapsibleSection title="Background Jobs">
+            <BackgroundJobsSection />
+          </CollapsibleSection>
+        )}
+
         {isTauri && (
           <CollapsibleSection title={<>Companion Server <span className="beta-badge">Beta</span></>}>
             <CompanionServerSection />
@@ -108,6 +135,24 @@ export default function Settings({ updateChecker }: SettingsProps) {
           </CollapsibleSection>
         )}
 
+        {isTauri && (
+          <CollapsibleSection title="Vacation">
+            <VacationSection />
+          </CollapsibleSection>
+        )}
+
+        {isTauri && (
+          <CollapsibleSection title="Review SLA">
+            <ReviewSlaSection />
+          </CollapsibleSection>
+        )}
+
+        {isTauri && (
+          <CollapsibleSection title="Review Stats">
+            <ReviewStatsSection />
+          </CollapsibleSection>
+        )}
+
         {isTauri && (
           <CollapsibleSection title="Generated File Patterns">
             <CollapsePatternsEditor />
@@ -120,11 +165,71 @@ export default function Settings({ updateChecker }: SettingsProps) {
           </CollapsibleSection>
         )}
 
+        {isTauri && (
+          <CollapsibleSection title="External Editor">
+            <EditorSection />
+          </CollapsibleSection>
+        )}
+
         {isTauri && (
           <CollapsibleSection title="Keyboard Shortcuts">
             <ShortcutEditor />
           </CollapsibleSection>
         )}
+
+        {isTauri && (
+          <CollapsibleSection title="Open GitLab Links">
+            <LinkHandlerSection />
+          </CollapsibleSection>
+        )}
+
+        {isTauri && (
+          <CollapsibleSection title="Translation">
+            <TranslationSection />
+          </CollapsibleSection>
+        )}
+
+        {isTauri && (
+          <CollapsibleSection title="Review Assistant">
+            <ReviewAssistantSection />
+          </CollapsibleSection>
+        )}
+
+        {isTauri && (
+          <CollapsibleSection title="Automations">
+            <AutomationsSection />
+          </CollapsibleSection>
+        )}
+
+        {isTauri && (
+          <CollapsibleSection title="List Badges">
+            <BadgeRulesSection />
+          </CollapsibleSection>
+        )}
+
+        {isTauri && (
+          <CollapsibleSection title="Cache Archive">
+            <CacheArchiveSection />
+          </CollapsibleSection>
+        )}
+
+        {isTauri && (
+          <CollapsibleSection title="Statistics Export">
+            <StatisticsExportSection />
+          </CollapsibleSection>
+        )}
+
+        {isTauri && (
+          <CollapsibleSection title="Database Encryption">
+            <DatabaseEncryptionSection />
+          </CollapsibleSection>
+        )}
+
+        {isTauri && (
+          <CollapsibleSection title="API Console">
+            <ApiConsoleSection />
+          </CollapsibleSection>
+        )}
       </main>
     </div>
   );
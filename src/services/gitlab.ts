@@ -34,6 +34,7 @@ import {
   type GitLabInstanceWithStatus,
 } from './tauri';
 import type {
+  ApproveResult,
   GitLabInstanceSetup,
   MergeRequest,
   MRFilter,
@@ -45,6 +46,7 @@ import type {
   CachedFilePair,
   Comment,
   AddCommentRequest,
+  AddCommentResult,
   ReplyToCommentRequest,
   ResolveDiscussionRequest,
   DeleteCommentRequest,
@@ -145,13 +147,15 @@ export async function getMergeRequestFiles(mrId: number): Promise<DiffFile[]> {
  *
  * @param mrId - The merge request ID
  * @param filePath - The file path
+ * @param ignoreWhitespace - Leave out whitespace-only changes when possible
  * @returns The diff content with highlighted tokens
  */
 export async function getFileDiff(
   mrId: number,
-  filePath: string
+  filePath: string,
+  ignoreWhitespace = false
 ): Promise<DiffFileContent> {
-  return getDiffFileContent(mrId, filePath);
+  return getDiffFileContent(mrId, filePath, ignoreWhitespace);
 }
 
 /**
@@ -175,15 +179,17 @@ export async function getFileDiffMetadata(
  * @param filePath - The file path
  * @param start - Starting hunk index
  * @param count - Number of hunks to fetch
+ * @param ignoreWhitespace - Leave out whitespace-only changes when possible
  * @returns The requested hunks with pagination info
  */
 export async function getFileDiffHunks(
   mrId: number,
   filePath: string,
   start: number,
-  count: number
+  count: number,
+  ignoreWhitespace = false
 ): Promise<DiffHunksResponse> {
-  return getDiffHunks(mrId, filePath, start, count);
+  return getDiffHunks(mrId, filePath, start, count, ignoreWhitespace);
 }
 
 /**
@@ -303,13 +309,15 @@ export async function listComments(mrId: number): Promise<Comment[]> {
  *
  * @param mrId - The merge request ID
  * @param body - The comment text
- * @returns The created comment
+ * @param confirm - Post an `/approve` in the body despite review blockers
+ * @returns The created comment, or what held its `/approve` back
  */
 export async function addGeneralComment(
   mrId: number,
-  body: string
-): Promise<Comment> {
-  const request: AddCommentRequest = { mrId, body };
+  body: string,
+  confirm = false
+): Promise<AddCommentResult> {
+  const request: AddCommentRequest = { mrId, body, confirm };
   return addComment(request);
 }
 
@@ -321,7 +329,7 @@ export async function addGeneralComment(
  * @param filePath - The file path
  * @param line - The line number (newLine for additions, oldLine for deletions)
  * @param isOldLine - Whether this is a line in the old version
- * @returns The created comment
+ * @returns The created comment, or what held its `/approve` back
  */
 export async function addInlineComment(
   mrId: number,
@@ -329,7 +337,7 @@ export async function addInlineComment(
   filePath: string,
   line: number,
   isOldLine = false
-): Promise<Comment> {
+): Promise<AddCommentResult> {
   const request: AddCommentRequest = {
     mrId,
     body,
@@ -397,7 +405,7 @@ export async function deleteComment(
  *
  * @param mrId - The merge request ID
  */
-export async function approve(mrId: number): Promise<void> {
+export async function approve(mrId: number): Promise<ApproveResult> {
   return approveMR(mrId);
 }
 
@@ -23,6 +23,7 @@ export {
   getMergeRequest,
   listMyMergeRequests,
   getMrReviewers,
+  getMrParticipants,
   getDiffFiles,
   getDiffFileContent,
   getDiffFileMetadata,
@@ -66,6 +67,7 @@ export {
   updateNotificationSettings,
   sendNativeNotification,
   getJobTrace,
+  getJobTraceRange,
   updateTheme,
   updateUiFont,
   updateDiffsFont,
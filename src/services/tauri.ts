@@ -7,35 +7,113 @@
 
 import { transportInvoke } from './transport';
 import type {
+  ChangesSince,
+  MrDelta,
+  MrComparison,
+  SecurityFinding,
+  ProjectReadme,
+  ApprovalGate,
+  CustomHeader,
+  InstanceTls,
+  ApproveResult,
+  ReviewBlockers,
+  ChecklistItem,
   GitLabInstance,
   GitLabInstanceSetup,
   TokenInfo,
+  InstanceQuota,
   MergeRequest,
   MRFilter,
   MrReviewer,
+  Participant,
+  MrTaskList,
+  ProjectLabel,
+  ProjectMilestone,
+  ProjectMember,
+  MergeBlockers,
+  DescriptionLint,
   DiffFile,
   DiffFileContent,
   DiffFileMetadata,
   DiffHunksResponse,
+  SideBySideDiff,
   DiffRefs,
+  FileBlame,
+  MrCommit,
+  MrEvent,
+  LanguageBreakdown,
+  CommitDiff,
+  ChangesSinceReview,
+  FileReviewState,
   CachedFilePair,
   Comment,
+  CommentCursor,
+  CommentSort,
   AddCommentRequest,
+  AddCommentResult,
+  CommentSuggestion,
+  ReviewBundleRequest,
+  ReviewBundleResult,
+  ReviewBatchStatus,
+  DraftComment,
+  PendingReview,
+  SubmitReviewRequest,
+  FileAssignment,
   ReplyToCommentRequest,
   ResolveDiscussionRequest,
+  DiscussionExportFormat,
   DeleteCommentRequest,
+  PostMergeTask,
+  PostMergeTaskResult,
+  QuickActionPreview,
+  QuickActionSpec,
+  PingTemplate,
+  AuthorPing,
+  CommentRange,
+  MyComment,
+  SemanticSummary,
+  TranslationSettings,
+  AssistantSettings,
+  AssistantReply,
+  DatabaseEncryptionStatus,
+  Translation,
+  MarkdownProjectContext,
+  AccessibleDiff,
+  QuickSwitchEntry,
   SyncStatusResponse,
+  InstanceSyncConfig,
+  SyncSelfTestReport,
+  ActionCounts,
+  FailedAction,
+  RetryActionsResponse,
+  OrphanedAction,
+  BackgroundJobStatus,
   Settings,
   SettingsUpdate,
   MemoryStats,
+  DbPoolStats,
+  InstanceMode,
+  MemoryBreakdown,
   CacheStats,
+  CommandMetric,
   DiagnosticsReport,
   TestDataResult,
+  PipelineNotifyRule,
+  PendingManualJob,
+  JobRef,
+  PlayJobResult,
+  MutedProject,
   PipelineProject,
   PipelineStatus,
+  PipelineSchedule,
+  ProjectRelease,
+  ProjectTag,
   ProjectSearchResult,
   PipelineJob,
+  JobTrace,
+  PipelineGraph,
   NotificationSettings,
+  AuthorizedDevice,
   CompanionServerSettings,
   CompanionStatus,
   ResolvedMr,
@@ -46,6 +124,24 @@ import type {
   IssueAssigneeCandidate,
   CliInstallResult,
   CliStatus,
+  LinkHandlerSettings,
+  InstanceDataAudit,
+  RetentionPolicy,
+  CacheArchiveSummary,
+  StatsRange,
+  StatisticsExportFormat,
+  StatisticsExportSummary,
+  AutomationRule,
+  AutomationRuleInput,
+  BadgeRule,
+  BadgeRuleInput,
+  AutomationRun,
+  RetentionSweep,
+  ReviewStatsExport,
+  ReviewStreaks,
+  ReviewSlaStatus,
+  BranchCheckout,
+  ListViewState,
 } from '../types';
 
 // ============================================================================
@@ -91,13 +187,39 @@ export async function invoke<T>(cmd: string, args?: Record<string, unknown>): Pr
 // Authentication Commands
 // ============================================================================
 
+/** A token's verified scopes and what they mean for the app. */
+export interface ScopeCheck {
+  /** Null when GitLab didn't report the scopes (nothing is disabled then). */
+  scopes: string[] | null;
+  /** Whether comments, approvals and other changes are allowed. */
+  canWrite: boolean;
+  /** Scopes broader than needed, or missing for write actions. */
+  warnings: string[];
+}
+
 export interface SetupInstanceResponse {
   instance: GitLabInstance;
   username: string;
+  scopeCheck: ScopeCheck;
+  /** TLS version the connection was negotiated on (e.g. "TLS 1.3"); null for plain HTTP. */
+  tlsProtocol: string | null;
+}
+
+export interface UpdateTokenResponse {
+  username: string;
+  scopeCheck: ScopeCheck;
 }
 
 export interface GitLabInstanceWithStatus extends GitLabInstance {
   hasToken: boolean;
+  /** Names of the custom headers sent to this instance (values stay in the backend). */
+  customHeaderNames: string[];
+  /** Minimum TLS version and client certificate used for this instance. */
+  tls: InstanceTls;
+  /** Scopes verified when the token was saved; null when unknown. */
+  tokenScopes?: string[] | null;
+  /** Whether the token may comment, approve and make other changes. */
+  canWrite: boolean;
 }
 
 /**
@@ -109,6 +231,35 @@ export async function setupGitLabInstance(
   return invoke<SetupInstanceResponse>('setup_gitlab_instance', { input });
 }
 
+export type ConfigImportStatus = 'pending' | 'missingToken' | 'skipped' | 'imported' | 'failed';
+
+/** An instance found in a glab or python-gitlab config file. */
+export interface ConfigImportEntry {
+  url: string;
+  name: string;
+  source: 'glab' | 'pythonGitlab';
+  alreadyConfigured: boolean;
+  status: ConfigImportStatus;
+  username?: string;
+  error?: string;
+}
+
+/**
+ * Import instances from a glab or python-gitlab config file.
+ *
+ * Without `confirmedUrls` this only lists what the file contains; call it
+ * again with the URLs the user confirmed to validate and save them.
+ */
+export async function importInstancesFromConfig(
+  path: string,
+  confirmedUrls?: string[]
+): Promise<ConfigImportEntry[]> {
+  return invoke<ConfigImportEntry[]>('import_instances_from_config', {
+    path,
+    confirmedUrls: confirmedUrls ?? null,
+  });
+}
+
 /**
  * Get all configured GitLab instances.
  */
@@ -130,6 +281,13 @@ export async function getTokenInfo(instanceId: number): Promise<TokenInfo> {
   return invoke<TokenInfo>('get_token_info', { instanceId });
 }
 
+/**
+ * Get CI minutes, storage and API rate-limit budget for a GitLab instance.
+ */
+export async function getInstanceQuota(instanceId: number): Promise<InstanceQuota> {
+  return invoke<InstanceQuota>('get_instance_quota', { instanceId });
+}
+
 /**
  * Set an instance as the default (auto-selected on pages).
  */
@@ -146,10 +304,31 @@ export async function renameInstance(instanceId: number, name: string): Promise<
 
 /**
  * Update the personal access token for a GitLab instance.
- * Validates the token and returns the authenticated username.
+ * Validates the token and its scopes; returns the authenticated username
+ * and any scope warnings.
+ */
+export async function updateInstanceToken(
+  instanceId: number,
+  token: string
+): Promise<UpdateTokenResponse> {
+  return invoke<UpdateTokenResponse>('update_instance_token', { instanceId, token });
+}
+
+/**
+ * Replace the custom headers sent to a GitLab instance (empty list removes them).
+ * The headers are validated against GitLab before being saved.
  */
-export async function updateInstanceToken(instanceId: number, token: string): Promise<string> {
-  return invoke<string>('update_instance_token', { instanceId, token });
+export async function updateInstanceHeaders(instanceId: number, headers: CustomHeader[]): Promise<void> {
+  return invoke<void>('update_instance_headers', { instanceId, headers });
+}
+
+/**
+ * Replace the TLS settings for a GitLab instance.
+ * The settings are validated against GitLab before being saved; returns the
+ * TLS version the connection was negotiated on.
+ */
+export async function updateInstanceTls(instanceId: number, tls: InstanceTls): Promise<string | null> {
+  return invoke<string | null>('update_instance_tls', { instanceId, tls });
 }
 
 // ============================================================================
@@ -182,13 +361,15 @@ export async function getDiffFiles(mrId: number): Promise<DiffFile[]> {
 }
 
 /**
- * Get diff content for a specific file with syntax highlighting.
+ * Get diff content for a specific file with syntax highlighting,
+ * optionally without whitespace-only changes.
  */
 export async function getDiffFileContent(
   mrId: number,
-  filePath: string
+  filePath: string,
+  ignoreWhitespace = false
 ): Promise<DiffFileContent> {
-  return invoke<DiffFileContent>('get_diff_file', { mrId, filePath });
+  return invoke<DiffFileContent>('get_diff_file', { mrId, filePath, ignoreWhitespace });
 }
 
 /**
@@ -203,14 +384,23 @@ export async function getDiffFileMetadata(
 
 /**
  * Get a range of diff hunks for progressive loading.
+ * With `ignoreWhitespace`, hunks are recomputed without whitespace-only
+ * changes when both file versions are cached (see `whitespaceIgnored`).
  */
 export async function getDiffHunks(
   mrId: number,
   filePath: string,
   start: number,
-  count: number
+  count: number,
+  ignoreWhitespace = false
 ): Promise<DiffHunksResponse> {
-  return invoke<DiffHunksResponse>('get_diff_hunks', { mrId, filePath, start, count });
+  return invoke<DiffHunksResponse>('get_diff_hunks', {
+    mrId,
+    filePath,
+    start,
+    count,
+    ignoreWhitespace,
+  });
 }
 
 /**
@@ -239,6 +429,76 @@ export async function getFileContentBase64(
   return invoke<string>('get_file_content_base64', { instanceId, projectId, filePath, sha });
 }
 
+/**
+ * Get the blame of a file at an MR's head SHA, cached per commit.
+ */
+export async function getFileBlame(mrId: number, filePath: string): Promise<FileBlame> {
+  return invoke<FileBlame>('get_file_blame', { mrId, filePath });
+}
+
+/**
+ * Get the commits of an MR, oldest first, as of the last sync.
+ */
+export async function getMrCommits(mrId: number): Promise<MrCommit[]> {
+  return invoke<MrCommit[]>('get_mr_commits', { mrId });
+}
+
+/**
+ * Get the activity timeline of an MR (pushes, label, reviewer and state
+ * changes), oldest first, as of the last sync.
+ */
+export async function getMrActivity(mrId: number): Promise<MrEvent[]> {
+  return invoke<MrEvent[]>('get_mr_activity', { mrId });
+}
+
+/**
+ * Get the lines changed per language in an MR, from file extensions and the
+ * project's `.gitattributes` overrides.
+ */
+export async function getMrLanguageBreakdown(mrId: number): Promise<LanguageBreakdown> {
+  return invoke<LanguageBreakdown>('get_mr_language_breakdown', { mrId });
+}
+
+/**
+ * Get the files one commit of an MR changed; fetched once, then cached.
+ */
+export async function getCommitDiff(mrId: number, sha: string): Promise<CommitDiff> {
+  return invoke<CommitDiff>('get_commit_diff', { mrId, sha });
+}
+
+/**
+ * Mark an MR reviewed at its current head. Returns the head SHA.
+ */
+export async function markMrReviewed(mrId: number): Promise<string> {
+  return invoke<string>('mark_mr_reviewed', { mrId });
+}
+
+/**
+ * Get the changes to an MR's files since it was last marked reviewed.
+ */
+export async function getChangesSinceReview(mrId: number): Promise<ChangesSinceReview> {
+  return invoke<ChangesSinceReview>('get_changes_since_review', { mrId });
+}
+
+/**
+ * Mark a file of an MR's diff viewed, or unmark it. Sync unmarks it again
+ * when a new push changes the file.
+ */
+export async function markFileViewed(
+  mrId: number,
+  filePath: string,
+  viewed: boolean,
+): Promise<void> {
+  return invoke<void>('mark_file_viewed', { mrId, filePath, viewed });
+}
+
+/**
+ * Get the files of an MR marked viewed at its current head.
+ */
+export async function getFileReviewStates(mrId: number): Promise<FileReviewState[]> {
+  return invoke<FileReviewState[]>('get_file_review_states', { mrId });
+}
+
 /**
  * Get diff refs (SHA values) for a merge request.
  * Used to fetch original and modified file content for Monaco diff viewer.
@@ -263,6 +523,21 @@ export async function fetchMrByWebUrl(webUrl: string): Promise<ResolvedMr> {
   return invoke<ResolvedMr>('fetch_mr_by_web_url', { webUrl });
 }
 
+/**
+ * Watch an MR by local ID or web URL. Watched MRs are synced and notified
+ * even when the user is not the author, an assignee or a reviewer.
+ */
+export async function watchMr(target: { mrId?: number; webUrl?: string }): Promise<ResolvedMr> {
+  return invoke<ResolvedMr>('watch_mr', { mrId: target.mrId, webUrl: target.webUrl });
+}
+
+/**
+ * Stop watching an MR.
+ */
+export async function unwatchMr(mrId: number): Promise<void> {
+  return invoke<void>('unwatch_mr', { mrId });
+}
+
 /**
  * Get merge requests authored by the current user.
  * When `includeRecentlyMerged` is true, the response also contains MRs merged
@@ -287,6 +562,110 @@ export async function getMrReviewers(mrId: number): Promise<MrReviewer[]> {
   return invoke<MrReviewer[]>('get_mr_reviewers', { mrId });
 }
 
+/**
+ * Get everyone involved in a merge request (author, assignees, reviewers,
+ * approvers and commenters) from cached data.
+ */
+export async function getMrParticipants(mrId: number): Promise<Participant[]> {
+  return invoke<Participant[]>('get_mr_participants', { mrId });
+}
+
+/**
+ * Ask reviewers who already reviewed one of my MRs to review it again.
+ * Returns the usernames that were asked. Needs GitLab 16+.
+ */
+export async function requestRereview(mrId: number): Promise<string[]> {
+  return invoke<string[]>('request_rereview', { mrId });
+}
+
+/**
+ * Get the task list items of an MR description with progress counts.
+ */
+export async function getMrTasks(mrId: number): Promise<MrTaskList> {
+  return invoke<MrTaskList>('get_mr_tasks', { mrId });
+}
+
+/**
+ * Check or uncheck a description task. Applied locally right away and
+ * pushed to GitLab in the background.
+ */
+export async function toggleMrTask(mrId: number, index: number, checked: boolean): Promise<MrTaskList> {
+  return invoke<MrTaskList>('toggle_mr_task', { mrId, index, checked });
+}
+
+/**
+ * Get the cached labels of a project. Sync keeps them fresh for every
+ * project with open MRs.
+ */
+export async function getProjectLabels(instanceId: number, projectId: number): Promise<ProjectLabel[]> {
+  return invoke<ProjectLabel[]>('get_project_labels', { instanceId, projectId });
+}
+
+/**
+ * Add a label to an MR. Applied locally right away and pushed to GitLab in
+ * the background; returns the MR's labels.
+ */
+export async function addMrLabel(mrId: number, label: string): Promise<string[]> {
+  return invoke<string[]>('add_mr_label', { mrId, label });
+}
+
+/**
+ * Remove a label from an MR, like `addMrLabel`.
+ */
+export async function removeMrLabel(mrId: number, label: string): Promise<string[]> {
+  return invoke<string[]>('remove_mr_label', { mrId, label });
+}
+
+/**
+ * Get the cached active milestones of a project, soonest due first. Sync
+ * keeps them fresh for every project with open MRs.
+ */
+export async function getProjectMilestones(instanceId: number, projectId: number): Promise<ProjectMilestone[]> {
+  return invoke<ProjectMilestone[]>('get_project_milestones', { instanceId, projectId });
+}
+
+/**
+ * Put an MR in a milestone, or take it out with `null`. Applied locally right
+ * away and pushed to GitLab in the background; returns the MR's milestone.
+ */
+export async function setMrMilestone(mrId: number, milestoneId: number | null): Promise<ProjectMilestone | null> {
+  return invoke<ProjectMilestone | null>('set_mr_milestone', { mrId, milestoneId });
+}
+
+/**
+ * Search a project's members by username or name. The member list is
+ * cached, so this works offline once the project was searched.
+ */
+export async function searchProjectMembers(
+  instanceId: number,
+  projectId: number,
+  query: string,
+): Promise<ProjectMember[]> {
+  return invoke<ProjectMember[]>('search_project_members', { instanceId, projectId, query });
+}
+
+/**
+ * Replace an MR's reviewers. Applied locally right away and pushed to
+ * GitLab in the background; returns the reviewers.
+ */
+export async function setMrReviewers(mrId: number, usernames: string[]): Promise<string[]> {
+  return invoke<string[]>('set_mr_reviewers', { mrId, usernames });
+}
+
+/**
+ * Replace an MR's assignees, like `setMrReviewers`.
+ */
+export async function setMrAssignees(mrId: number, usernames: string[]): Promise<string[]> {
+  return invoke<string[]>('set_mr_assignees', { mrId, usernames });
+}
+
+/**
+ * Check the description of one of my MRs against the project's MR template.
+ */
+export async function lintMrDescription(mrId: number): Promise<DescriptionLint> {
+  return invoke<DescriptionLint>('lint_mr_description', { mrId });
+}
+
 /**
  * Get cached file content pair (base + head) from local cache.
  * Returns null values for cache misses, signaling fallback to network fetch.
@@ -298,6 +677,54 @@ export async function getCachedFilePair(
   return invoke<CachedFilePair>('get_cached_file_pair', { mrId, filePath });
 }
 
+/**
+ * Get a file's diff as aligned left/right rows built from the cached base
+ * and head versions, with placeholder rows opposite added/removed lines.
+ * Fails when the file versions aren't cached yet.
+ */
+export async function getSideBySideDiff(
+  mrId: number,
+  filePath: string
+): Promise<SideBySideDiff> {
+  return invoke<SideBySideDiff>('get_side_by_side_diff', { mrId, filePath });
+}
+
+/**
+ * Scan an MR's cached diff for added lines that look like leaked secrets.
+ */
+export async function getSecurityFindings(mrId: number): Promise<SecurityFinding[]> {
+  return invoke<SecurityFinding[]>('get_security_findings', { mrId });
+}
+
+/**
+ * Get a file's diff as labeled, linear hunks for the screen-reader view.
+ */
+export async function getAccessibleDiff(
+  mrId: number,
+  filePath: string
+): Promise<AccessibleDiff> {
+  return invoke<AccessibleDiff>('get_accessible_diff', { mrId, filePath });
+}
+
+/**
+ * Get a symbol-level summary (changed functions, classes, types) of a file in
+ * an MR, computed from the cached base and head versions.
+ */
+export async function getSemanticSummary(
+  mrId: number,
+  filePath: string
+): Promise<SemanticSummary> {
+  return invoke<SemanticSummary>('get_semantic_summary', { mrId, filePath });
+}
+
+/**
+ * Get every open MR across all instances, pre-ranked for the quick switcher.
+ * Served from an in-memory index, so it is cheap to call on each open.
+ */
+export async function getQuickSwitchIndex(): Promise<QuickSwitchEntry[]> {
+  return invoke<QuickSwitchEntry[]>('get_quick_switch_index');
+}
+
 /**
  * Merge a merge request via the GitLab API.
  */
@@ -313,6 +740,14 @@ export async function checkMergeStatus(mrId: number): Promise<string> {
   return invoke<string>('check_merge_status', { mrId });
 }
 
+/**
+ * Everything blocking an MR's merge, from the cache, or from GitLab when
+ * `refresh` is set (which also fetches per-rule approvals).
+ */
+export async function getMergeBlockers(mrId: number, refresh = false): Promise<MergeBlockers> {
+  return invoke<MergeBlockers>('get_merge_blockers', { mrId, refresh });
+}
+
 /**
  * Rebase a merge request's source branch via GitLab.
  */
@@ -328,6 +763,16 @@ export async function undraftMR(mrId: number): Promise<string> {
   return invoke<string>('undraft_mr', { mrId });
 }
 
+/**
+ * Run optional cleanup tasks for a merged MR. Returns one result per task.
+ */
+export async function runPostMergeTasks(
+  mrId: number,
+  tasks: PostMergeTask[]
+): Promise<PostMergeTaskResult[]> {
+  return invoke<PostMergeTaskResult[]>('run_post_merge_tasks', { mrId, tasks });
+}
+
 /**
  * Get pipelines associated with a merge request, newest first.
  */
@@ -335,6 +780,26 @@ export async function getMRPipelines(mrId: number): Promise<PipelineStatus[]> {
   return invoke<PipelineStatus[]>('get_mr_pipelines', { mrId });
 }
 
+/**
+ * Local conditions a "merge when ready" claim waits for, on top of GitLab's
+ * own mergeability checks.
+ */
+export interface MergeConditions {
+  pipelineSucceeded: boolean;
+  approvalsMet: boolean;
+  threadsResolved: boolean;
+}
+
+export type MergeCondition = keyof MergeConditions;
+
+/**
+ * Compare two open MRs of the same project: files both change and the hunks
+ * that collide on the target branch. Uses the cached diffs.
+ */
+export async function compareMrs(mrIdA: number, mrIdB: number): Promise<MrComparison> {
+  return invoke<MrComparison>('compare_mrs', { mrIdA, mrIdB });
+}
+
 /**
  * Auto-merge claim payload from the backend.
  */
@@ -345,6 +810,10 @@ export interface AutoMergeClaim {
   lastError: string | null;
   lastAttemptAt: number | null;
   attempts: number;
+  /** Set for merge-when-ready claims; null for plain auto-merge. */
+  conditions: MergeConditions | null;
+  /** Conditions that weren't met at the last check. */
+  unmetConditions: MergeCondition[];
 }
 
 /**
@@ -361,6 +830,24 @@ export async function unclaimAutoMerge(mrId: number): Promise<void> {
   return invoke<void>('unclaim_auto_merge', { mrId });
 }
 
+/**
+ * Queue an MR to merge once the given conditions hold. The sync engine
+ * checks them every cycle and merges (with a notification) when all pass.
+ */
+export async function mergeWhenReady(
+  mrId: number,
+  conditions: MergeConditions
+): Promise<AutoMergeClaim> {
+  return invoke<AutoMergeClaim>('merge_when_ready', { mrId, conditions });
+}
+
+/**
+ * Cancel a pending merge-when-ready intent for an MR.
+ */
+export async function cancelMergeWhenReady(mrId: number): Promise<void> {
+  return invoke<void>('cancel_merge_when_ready', { mrId });
+}
+
 /**
  * Read the current auto-merge claim for an MR, if any.
  */
@@ -436,10 +923,30 @@ export async function listAutoRunClaims(
 // ============================================================================
 
 /**
- * Get comments for a merge request.
+ * Get comments for a merge request, oldest first unless `sort` says otherwise.
+ * With `contextLines`, inline threads carry that many lines of cached code
+ * either side of the commented line.
  */
-export async function getComments(mrId: number): Promise<Comment[]> {
-  return invoke<Comment[]>('get_comments', { mrId });
+export async function getComments(
+  mrId: number,
+  sort?: CommentSort,
+  contextLines?: number,
+): Promise<Comment[]> {
+  return invoke<Comment[]>('get_comments', { mrId, sort, contextLines });
+}
+
+/**
+ * Where the unread comments of an MR start, relative to its last visit.
+ */
+export async function getLatestCommentCursor(mrId: number): Promise<CommentCursor> {
+  return invoke<CommentCursor>('get_latest_comment_cursor', { mrId });
+}
+
+/**
+ * Record that the MR was opened now; later comments count as unread.
+ */
+export async function markMrViewed(mrId: number): Promise<void> {
+  return invoke<void>('mark_mr_viewed', { mrId });
 }
 
 /**
@@ -450,10 +957,43 @@ export async function getFileComments(mrId: number, filePath: string): Promise<C
 }
 
 /**
- * Add a new comment to a merge request.
+ * Add a new comment to a merge request. An `/approve` in the body is checked
+ * like `approveMR`; `comment` is null when that held it back.
+ */
+export async function addComment(request: AddCommentRequest): Promise<AddCommentResult> {
+  return invoke<AddCommentResult>('add_comment', { input: request });
+}
+
+/**
+ * Apply a suggestion from a diff note. GitLab commits it to the source
+ * branch; the cached diff is patched and a sync is started.
  */
-export async function addComment(request: AddCommentRequest): Promise<Comment> {
-  return invoke<Comment>('add_comment', { input: request });
+export async function applySuggestion(
+  mrId: number,
+  suggestionId: number,
+  commitMessage?: string
+): Promise<CommentSuggestion> {
+  return invoke<CommentSuggestion>('apply_suggestion', { mrId, suggestionId, commitMessage });
+}
+
+/** A pasted text uploaded to the MR's project. */
+export interface CommentAttachment {
+  /** Markdown link to insert into the comment. */
+  markdown: string;
+  url: string;
+  sizeBytes: number;
+}
+
+/**
+ * Upload a large pasted text to the MR's project and get a Markdown link
+ * to post instead of the text itself.
+ */
+export async function uploadCommentAttachment(
+  mrId: number,
+  filename: string,
+  content: string
+): Promise<CommentAttachment> {
+  return invoke<CommentAttachment>('upload_comment_attachment', { mrId, filename, content });
 }
 
 /**
@@ -470,6 +1010,140 @@ export async function resolveDiscussion(request: ResolveDiscussionRequest): Prom
   return invoke<void>('resolve_discussion', { input: request });
 }
 
+/**
+ * Submit a review as one batch: comments, then resolved threads, then the
+ * approval. GitLab receives them in that order; nothing is queued if the
+ * approval gate blocks the approval.
+ */
+export async function submitReviewBundle(request: ReviewBundleRequest): Promise<ReviewBundleResult> {
+  return invoke<ReviewBundleResult>('submit_review_bundle', { input: request });
+}
+
+/**
+ * Sync progress of a submitted review bundle.
+ */
+export async function getReviewBundleStatus(batchId: string): Promise<ReviewBatchStatus> {
+  return invoke<ReviewBatchStatus>('get_review_bundle_status', { batchId });
+}
+
+/**
+ * Start a pending review, or return the one already started.
+ */
+export async function startReview(mrId: number): Promise<PendingReview> {
+  return invoke<PendingReview>('start_review', { mrId });
+}
+
+/**
+ * The MR's pending review, or null when none has been started.
+ */
+export async function getPendingReview(mrId: number): Promise<PendingReview | null> {
+  return invoke<PendingReview | null>('get_pending_review', { mrId });
+}
+
+/**
+ * Add a draft comment to the MR's pending review, starting one if needed.
+ * The draft stays local until the review is submitted.
+ */
+export async function addDraftComment(request: AddCommentRequest): Promise<DraftComment> {
+  return invoke<DraftComment>('add_draft_comment', { input: request });
+}
+
+/**
+ * Remove a draft from the MR's pending review.
+ */
+export async function deleteDraftComment(mrId: number, draftId: number): Promise<void> {
+  return invoke<void>('delete_draft_comment', { mrId, draftId });
+}
+
+/**
+ * Throw away the MR's pending review and its drafts.
+ */
+export async function discardReview(mrId: number): Promise<void> {
+  return invoke<void>('discard_review', { mrId });
+}
+
+/**
+ * Submit the pending review: drafts, summary note and approval are pushed
+ * as one batch. The drafts are kept if the approval gate blocks it.
+ */
+export async function submitReview(request: SubmitReviewRequest): Promise<ReviewBundleResult> {
+  return invoke<ReviewBundleResult>('submit_review', { input: request });
+}
+
+/**
+ * Import `file:line: comment` annotations from a file written by an editor
+ * or CLI review tool, and queue them as inline comments in one batch.
+ * Fails without queueing anything when an annotation isn't on the diff.
+ */
+export async function importReviewAnnotations(
+  mrId: number,
+  path: string
+): Promise<ReviewBundleResult> {
+  return invoke<ReviewBundleResult>('import_review_annotations', { mrId, path });
+}
+
+// ============================================================================
+// Local Working Copy Commands
+// ============================================================================
+
+/**
+ * Path of the local clone remembered for the MR's project, if any.
+ */
+export async function getMrRepoPath(mrId: number): Promise<string | null> {
+  return invoke<string | null>('get_mr_repo_path', { mrId });
+}
+
+/**
+ * Fetch the MR into the project's local clone and switch to its branch.
+ * A given `repoPath` is remembered for the project.
+ */
+export async function checkoutMrBranch(mrId: number, repoPath?: string): Promise<BranchCheckout> {
+  return invoke<BranchCheckout>('checkout_mr_branch', { mrId, repoPath: repoPath ?? null });
+}
+
+/**
+ * Open a file of the project's local clone in the configured editor, at
+ * `line` when given.
+ */
+export async function openInEditor(
+  instanceId: number,
+  projectId: number,
+  filePath: string,
+  line?: number
+): Promise<void> {
+  return invoke<void>('open_in_editor', { instanceId, projectId, filePath, line: line ?? null });
+}
+
+/**
+ * Assign files of an MR to a reviewer. Posts a comment asking them to
+ * review the files; returns all assignments of the MR.
+ */
+export async function assignFilesToReviewer(
+  mrId: number,
+  paths: string[],
+  username: string
+): Promise<FileAssignment[]> {
+  return invoke<FileAssignment[]>('assign_files_to_reviewer', { mrId, paths, username });
+}
+
+/**
+ * Which reviewer each file of an MR is assigned to.
+ */
+export async function getFileAssignments(mrId: number): Promise<FileAssignment[]> {
+  return invoke<FileAssignment[]>('get_file_assignments', { mrId });
+}
+
+/**
+ * Export a discussion thread as a markdown or plain-text transcript with
+ * authors, timestamps and links back to each note on GitLab.
+ */
+export async function exportDiscussion(
+  discussionId: string,
+  format: DiscussionExportFormat
+): Promise<string> {
+  return invoke<string>('export_discussion', { discussionId, format });
+}
+
 /**
  * Delete a comment from a merge request.
  */
@@ -477,15 +1151,68 @@ export async function deleteComment(request: DeleteCommentRequest): Promise<void
   return invoke<void>('delete_comment', { input: request });
 }
 
+/**
+ * List the GitLab quick actions available in MR comments.
+ */
+export async function listQuickActions(): Promise<QuickActionSpec[]> {
+  return invoke<QuickActionSpec[]>('list_quick_actions');
+}
+
+/**
+ * Preview which quick actions a comment body will trigger on GitLab.
+ */
+export async function previewQuickActions(body: string): Promise<QuickActionPreview> {
+  return invoke<QuickActionPreview>('preview_quick_actions', { body });
+}
+
+/**
+ * Post a comment mentioning the MR author, with links to the unresolved
+ * threads the template is about. Fails if the same ping was already sent
+ * and the author hasn't reacted since.
+ */
+export async function pingAuthor(mrId: number, template: PingTemplate): Promise<Comment> {
+  return invoke<Comment>('ping_author', { mrId, template });
+}
+
+/**
+ * Search my own comments across all cached MRs. Every word of `search` must
+ * appear in the body. Returns at most 200, newest first.
+ */
+export async function getMyCommentHistory(
+  range: CommentRange,
+  search?: string,
+): Promise<MyComment[]> {
+  return invoke<MyComment[]>('get_my_comment_history', { range, search: search || null });
+}
+
+/**
+ * Pings already sent to the MR author, newest first.
+ */
+export async function getAuthorPings(mrId: number): Promise<AuthorPing[]> {
+  return invoke<AuthorPing[]>('get_author_pings', { mrId });
+}
+
 // ============================================================================
 // Approval Commands
 // ============================================================================
 
 /**
  * Approve a merge request.
+ * If the project's approval gate has unmet conditions (judged on the files
+ * marked viewed), nothing is approved and the result lists them. The same
+ * goes for review blockers (open threads, a failing pipeline, conflicts)
+ * unless `confirm` is set.
  */
-export async function approveMR(mrId: number): Promise<void> {
-  return invoke<void>('approve_mr', { mrId });
+export async function approveMR(mrId: number, confirm?: boolean): Promise<ApproveResult> {
+  return invoke<ApproveResult>('approve_mr', { mrId, confirm });
+}
+
+/**
+ * Get what stands against approving an MR: unresolved threads, a failing
+ * pipeline and merge conflicts.
+ */
+export async function getReviewBlockers(mrId: number): Promise<ReviewBlockers> {
+  return invoke<ReviewBlockers>('get_review_blockers', { mrId });
 }
 
 /**
@@ -495,6 +1222,38 @@ export async function unapproveMR(mrId: number): Promise<void> {
   return invoke<void>('unapprove_mr', { mrId });
 }
 
+/**
+ * Get a project's approval gate configuration.
+ */
+export async function getApprovalGate(instanceId: number, projectId: number): Promise<ApprovalGate> {
+  return invoke<ApprovalGate>('get_approval_gate', { instanceId, projectId });
+}
+
+/**
+ * Create or update a project's approval gate.
+ */
+export async function updateApprovalGate(gate: ApprovalGate): Promise<void> {
+  return invoke<void>('update_approval_gate', { gate });
+}
+
+/**
+ * Get the reviewer checklist for an MR with its ticked state.
+ */
+export async function getReviewChecklist(mrId: number): Promise<ChecklistItem[]> {
+  return invoke<ChecklistItem[]>('get_review_checklist', { mrId });
+}
+
+/**
+ * Tick or untick a reviewer checklist item on an MR.
+ */
+export async function setReviewChecklistItem(
+  mrId: number,
+  item: string,
+  checked: boolean
+): Promise<void> {
+  return invoke<void>('set_review_checklist_item', { mrId, item, checked });
+}
+
 // ============================================================================
 // Sync Commands
 // ============================================================================
@@ -506,6 +1265,14 @@ export async function triggerSync(force = false): Promise<void> {
   return invoke<void>('trigger_sync', { force });
 }
 
+/**
+ * Start a deep reconciliation: a forced sync that also re-checks MRs the
+ * fetch didn't return and cleans up orphaned cache rows.
+ */
+export async function triggerFullResync(): Promise<void> {
+  return invoke<void>('trigger_full_resync');
+}
+
 /**
  * Get current sync status.
  */
@@ -513,6 +1280,81 @@ export async function getSyncStatus(): Promise<SyncStatusResponse> {
   return invoke<SyncStatusResponse>('get_sync_status');
 }
 
+/**
+ * Get an instance's sync configuration (every scope at the global settings
+ * when none is saved).
+ */
+export async function getSyncConfig(instanceId: number): Promise<InstanceSyncConfig> {
+  return invoke<InstanceSyncConfig>('get_sync_config', { instanceId });
+}
+
+/**
+ * Save an instance's sync configuration. Returns the saved configuration.
+ */
+export async function updateSyncConfig(
+  instanceId: number,
+  config: InstanceSyncConfig
+): Promise<InstanceSyncConfig> {
+  return invoke<InstanceSyncConfig>('update_sync_config', { instanceId, config });
+}
+
+/**
+ * Run the sync self-test against an instance. Failing steps are part of
+ * the report, not a rejected promise.
+ */
+export async function runSyncSelftest(instanceId: number): Promise<SyncSelfTestReport> {
+  return invoke<SyncSelfTestReport>('run_sync_selftest', { instanceId });
+}
+
+/**
+ * Get cache changes recorded after `cursor`.
+ *
+ * Pass `null` to get the current cursor without any changes (e.g. right
+ * before a full load).
+ */
+export async function getChangesSince(cursor: number | null): Promise<ChangesSince> {
+  return invoke<ChangesSince>('get_changes_since', { cursor });
+}
+
+/**
+ * Get the MRs created, updated or removed after `since`, folded into one
+ * entry per MR. Pass `null` to get the current cursor.
+ */
+export async function getMrDelta(since: number | null): Promise<MrDelta> {
+  return invoke<MrDelta>('get_mr_delta', { since });
+}
+
+/**
+ * Get pending and failed action counts, with failed ones split by whether
+ * retrying can help.
+ */
+export async function getActionCounts(): Promise<ActionCounts> {
+  return invoke<ActionCounts>('get_action_counts');
+}
+
+/**
+ * List failed actions with why they failed, including those waiting for an
+ * automatic retry.
+ */
+export async function listFailedActions(): Promise<FailedAction[]> {
+  return invoke<FailedAction[]>('list_failed_actions');
+}
+
+/**
+ * List queued actions dropped because their MR was purged or deleted before
+ * they were pushed, newest first.
+ */
+export async function getOrphanedActions(): Promise<OrphanedAction[]> {
+  return invoke<OrphanedAction[]>('get_orphaned_actions');
+}
+
+/**
+ * Retry every failed action a retry can help with, now.
+ */
+export async function retryFailedActions(): Promise<RetryActionsResponse> {
+  return invoke<RetryActionsResponse>('retry_failed_actions');
+}
+
 /**
  * Retry a failed sync action.
  */
@@ -527,6 +1369,25 @@ export async function discardFailedAction(actionId: number): Promise<void> {
   return invoke<void>('discard_failed_action', { actionId });
 }
 
+// ============================================================================
+// Background Job Commands
+// ============================================================================
+
+/**
+ * Get the state of every idle-time background job.
+ */
+export async function getBackgroundJobs(): Promise<BackgroundJobStatus[]> {
+  return invoke<BackgroundJobStatus[]>('get_background_jobs');
+}
+
+/**
+ * Tell the backend the user is active, pausing background jobs until the
+ * app has been idle for a while.
+ */
+export async function reportUserActivity(): Promise<void> {
+  return invoke<void>('report_user_activity');
+}
+
 // ============================================================================
 // Gitattributes Commands
 // ============================================================================
@@ -553,54 +1414,204 @@ export async function refreshGitattributes(
   return invoke<string[]>('refresh_gitattributes', { instanceId, projectId });
 }
 
-// ============================================================================
-// Settings Commands
-// ============================================================================
-
 /**
- * Get current settings.
+ * Get a project's README at a ref (usually the MR's target branch), rendered
+ * to HTML. Served from cache and refreshed in the background once a day old.
+ */
+export async function getProjectReadme(
+  instanceId: number,
+  projectId: number,
+  refName: string
+): Promise<ProjectReadme> {
+  return invoke<ProjectReadme>('get_project_readme', { instanceId, projectId, refName });
+}
+
+// ============================================================================
+// Settings Commands
+// ============================================================================
+
+/**
+ * Get current settings.
+ */
+export async function getSettings(): Promise<Settings> {
+  return invoke<Settings>('get_settings');
+}
+
+/**
+ * Update settings.
+ */
+export async function updateSettings(update: SettingsUpdate): Promise<Settings> {
+  return invoke<Settings>('update_settings', { update });
+}
+
+// ============================================================================
+// Keyboard Shortcuts Commands
+// ============================================================================
+
+/**
+ * Update custom keyboard shortcut bindings.
+ */
+export async function updateKeyboardShortcuts(shortcuts: Record<string, string>): Promise<void> {
+  return invoke<void>('update_keyboard_shortcuts', { shortcuts });
+}
+
+/**
+ * Persist the MR list condensed view toggle.
+ */
+export async function updateMrListCondensed(condensed: boolean): Promise<void> {
+  return invoke<void>('update_mr_list_condensed', { condensed });
+}
+
+/**
+ * Persist the "show recently merged MRs" toggle used on the My MRs page.
+ */
+export async function updateShowRecentlyMergedMrs(show: boolean): Promise<void> {
+  return invoke<void>('update_show_recently_merged_mrs', { show });
+}
+
+/**
+ * Persist the "show draft MRs" toggle used on the My MRs page.
+ */
+export async function updateShowDraftMrs(show: boolean): Promise<void> {
+  return invoke<void>('update_show_draft_mrs', { show });
+}
+
+/**
+ * Allow or forbid write requests through the raw API console.
+ */
+export async function updateAllowApiWrites(allow: boolean): Promise<void> {
+  return invoke<void>('update_allow_api_writes', { allow });
+}
+
+/**
+ * Set the external editor command template (`{file}`, `{line}`); an empty
+ * string clears it.
+ */
+export async function updateEditorCommand(command: string): Promise<void> {
+  return invoke<void>('update_editor_command', { command });
+}
+
+/**
+ * Turn offline mode on or off. While on, sync and all other network requests
+ * are skipped; cached data stays readable and new actions stay queued.
+ */
+export async function setOfflineMode(offline: boolean): Promise<void> {
+  return invoke<void>('set_offline_mode', { offline });
+}
+
+/**
+ * Get the database encryption setting and the state of the database file.
+ */
+export async function getDatabaseEncryptionStatus(): Promise<DatabaseEncryptionStatus> {
+  return invoke<DatabaseEncryptionStatus>('get_database_encryption_status');
+}
+
+/**
+ * Turn database encryption on or off. Applied when the app next starts.
+ */
+export async function updateDatabaseEncryption(enabled: boolean): Promise<DatabaseEncryptionStatus> {
+  return invoke<DatabaseEncryptionStatus>('update_database_encryption', { enabled });
+}
+
+/**
+ * Read the database key from the keychain so the user can store it safely.
+ */
+export async function exportDatabaseKey(): Promise<string> {
+  return invoke<string>('export_database_key');
+}
+
+/**
+ * Put a previously exported database key back into the keychain.
+ */
+export async function restoreDatabaseKey(key: string): Promise<DatabaseEncryptionStatus> {
+  return invoke<DatabaseEncryptionStatus>('restore_database_key', { key });
+}
+
+/**
+ * Save comment translation settings. Disabling also clears cached translations.
+ */
+export async function updateTranslationSettings(translation: TranslationSettings): Promise<void> {
+  return invoke<void>('update_translation_settings', { translation });
+}
+
+/**
+ * Translate text with the configured provider. Passing a comment ID caches
+ * the result for that comment.
  */
-export async function getSettings(): Promise<Settings> {
-  return invoke<Settings>('get_settings');
+export async function translateText(
+  text: string,
+  targetLang: string,
+  commentId?: number
+): Promise<Translation> {
+  return invoke<Translation>('translate_text', { text, targetLang, commentId });
 }
 
+// ============================================================================
+// Markdown
+// ============================================================================
+
 /**
- * Update settings.
+ * Render GitLab-flavored markdown to sanitized HTML. References like `#12`
+ * link against the project in `projectContext`; without it only bare URLs
+ * are linked.
  */
-export async function updateSettings(update: SettingsUpdate): Promise<Settings> {
-  return invoke<Settings>('update_settings', { update });
+export async function renderGfm(
+  text: string,
+  projectContext?: MarkdownProjectContext
+): Promise<string> {
+  return invoke<string>('render_gfm', { text, projectContext });
 }
 
 // ============================================================================
-// Keyboard Shortcuts Commands
+// Review Assistant
 // ============================================================================
 
 /**
- * Update custom keyboard shortcut bindings.
+ * Save review assistant settings.
  */
-export async function updateKeyboardShortcuts(shortcuts: Record<string, string>): Promise<void> {
-  return invoke<void>('update_keyboard_shortcuts', { shortcuts });
+export async function updateReviewAssistantSettings(reviewAssistant: AssistantSettings): Promise<void> {
+  return invoke<void>('update_review_assistant_settings', { reviewAssistant });
 }
 
 /**
- * Persist the MR list condensed view toggle.
+ * Draft a review comment for new-file lines `startLine..=endLine` of a file
+ * in the MR diff. Secrets and configured terms are masked before sending.
  */
-export async function updateMrListCondensed(condensed: boolean): Promise<void> {
-  return invoke<void>('update_mr_list_condensed', { condensed });
+export async function suggestComment(
+  mrId: number,
+  filePath: string,
+  startLine: number,
+  endLine: number
+): Promise<AssistantReply> {
+  return invoke<AssistantReply>('suggest_comment', { mrId, filePath, startLine, endLine });
 }
 
 /**
- * Persist the "show recently merged MRs" toggle used on the My MRs page.
+ * Summarize an MR from its cached description and diffs.
  */
-export async function updateShowRecentlyMergedMrs(show: boolean): Promise<void> {
-  return invoke<void>('update_show_recently_merged_mrs', { show });
+export async function summarizeMr(mrId: number): Promise<AssistantReply> {
+  return invoke<AssistantReply>('summarize_mr', { mrId });
 }
 
+// ============================================================================
+// Raw API Passthrough
+// ============================================================================
+
+export type ApiRequestMethod = 'GET' | 'POST' | 'PUT' | 'PATCH' | 'DELETE';
+
 /**
- * Persist the "show draft MRs" toggle used on the My MRs page.
+ * Run a GitLab REST API call with the instance's stored credentials.
+ *
+ * `path` is relative to `/api/v4`. Only GET is allowed unless API writes are
+ * enabled in settings. Resolves to the response JSON (null when empty).
  */
-export async function updateShowDraftMrs(show: boolean): Promise<void> {
-  return invoke<void>('update_show_draft_mrs', { show });
+export async function gitlabApiRequest(
+  instanceId: number,
+  method: ApiRequestMethod,
+  path: string,
+  body?: unknown
+): Promise<unknown> {
+  return invoke<unknown>('gitlab_api_request', { instanceId, method, path, body: body ?? null });
 }
 
 // ============================================================================
@@ -635,6 +1646,44 @@ export async function updateDiffsFont(font: string): Promise<void> {
   return invoke<void>('update_diffs_font', { font });
 }
 
+/** A syntax highlight theme for diff tokens. */
+export interface HighlightTheme {
+  id: string;
+  name: string;
+  /** Whether the theme suits a dark background; null for "app". */
+  dark: boolean | null;
+  /** Stylesheet overriding the hl-* token colors; empty for "app". */
+  css: string;
+}
+
+/**
+ * List the syntax highlight themes, "app" (follow the app theme) first.
+ */
+export async function listHighlightThemes(): Promise<HighlightTheme[]> {
+  return invoke<HighlightTheme[]>('list_highlight_themes');
+}
+
+/**
+ * Persist the syntax highlight theme and return its stylesheet.
+ */
+export async function setHighlightTheme(themeId: string): Promise<string> {
+  return invoke<string>('set_highlight_theme', { themeId });
+}
+
+/**
+ * Get the MR list view state (last instance, project filters, grouping).
+ */
+export async function getListViewState(): Promise<ListViewState> {
+  return invoke<ListViewState>('get_list_view_state');
+}
+
+/**
+ * Replace the MR list view state and return what was stored.
+ */
+export async function updateListViewState(state: ListViewState): Promise<ListViewState> {
+  return invoke<ListViewState>('update_list_view_state', { state });
+}
+
 /** Custom theme color inputs. */
 export interface CustomThemeColors {
   bg: string;
@@ -679,6 +1728,28 @@ export async function getMemoryStats(): Promise<MemoryStats> {
   return invoke<MemoryStats>('get_memory_stats');
 }
 
+/**
+ * Get heap usage attributed per backend subsystem.
+ */
+export async function getMemoryBreakdown(): Promise<MemoryBreakdown> {
+  return invoke<MemoryBreakdown>('get_memory_breakdown');
+}
+
+/**
+ * Get connection usage of the writer and read-only database pools.
+ */
+export async function getPoolStats(): Promise<DbPoolStats> {
+  return invoke<DbPoolStats>('get_pool_stats');
+}
+
+/**
+ * Whether this process owns the database, or is a read-only secondary viewer
+ * (started with `--secondary`, or while another process held the database).
+ */
+export async function getInstanceMode(): Promise<InstanceMode> {
+  return invoke<InstanceMode>('get_instance_mode');
+}
+
 /**
  * Get database cache statistics.
  */
@@ -686,6 +1757,13 @@ export async function getCacheStats(): Promise<CacheStats> {
   return invoke<CacheStats>('get_cache_stats');
 }
 
+/**
+ * Per-command IPC timings (P50/P95/max), slowest first.
+ */
+export async function getCommandMetrics(): Promise<CommandMetric[]> {
+  return invoke<CommandMetric[]>('get_command_metrics');
+}
+
 /**
  * Get a full diagnostics report.
  */
@@ -921,6 +1999,69 @@ export async function togglePinPipelineProject(instanceId: number, projectId: nu
   return invoke<void>('toggle_pin_pipeline_project', { instanceId, projectId });
 }
 
+/**
+ * Set when a pinned pipeline project's pipelines notify.
+ */
+export async function setPipelineNotifyRule(
+  instanceId: number,
+  projectId: number,
+  rule: PipelineNotifyRule
+): Promise<void> {
+  return invoke<void>('set_pipeline_notify_rule', { instanceId, projectId, rule });
+}
+
+/**
+ * Turn notifications about new manual jobs on a pinned project's default
+ * branch on or off.
+ */
+export async function setPipelineManualGateNotify(
+  instanceId: number,
+  projectId: number,
+  enabled: boolean
+): Promise<void> {
+  return invoke<void>('set_pipeline_manual_gate_notify', { instanceId, projectId, enabled });
+}
+
+/**
+ * Manual jobs waiting in the dashboard projects of every instance.
+ */
+export async function getPendingManualJobs(): Promise<PendingManualJob[]> {
+  return invoke<PendingManualJob[]>('get_pending_manual_jobs');
+}
+
+/**
+ * Play several manual jobs at once. One result per job, in order.
+ */
+export async function playJobs(jobRefs: JobRef[]): Promise<PlayJobResult[]> {
+  return invoke<PlayJobResult[]>('play_jobs', { jobRefs });
+}
+
+/**
+ * Mute a project's notifications and badge contributions until `until`
+ * (Unix seconds), or until unmuted when `until` is null. It keeps syncing.
+ */
+export async function muteProject(
+  instanceId: number,
+  projectId: number,
+  until: number | null
+): Promise<void> {
+  return invoke<void>('mute_project', { instanceId, projectId, until });
+}
+
+/**
+ * Unmute a project.
+ */
+export async function unmuteProject(instanceId: number, projectId: number): Promise<void> {
+  return invoke<void>('unmute_project', { instanceId, projectId });
+}
+
+/**
+ * List the currently muted projects across all instances.
+ */
+export async function listMutedProjects(): Promise<MutedProject[]> {
+  return invoke<MutedProject[]>('list_muted_projects');
+}
+
 /**
  * Remove a pipeline project from the dashboard.
  */
@@ -981,6 +2122,13 @@ export async function getPipelineJobs(instanceId: number, projectId: number, pip
   return invoke<PipelineJob[]>('get_pipeline_jobs', { instanceId, projectId, pipelineId });
 }
 
+/**
+ * Get a pipeline's jobs as a graph of stages and dependency edges.
+ */
+export async function getPipelineGraph(instanceId: number, projectId: number, pipelineId: number): Promise<PipelineGraph> {
+  return invoke<PipelineGraph>('get_pipeline_graph', { instanceId, projectId, pipelineId });
+}
+
 /**
  * Play (trigger) a manual job.
  */
@@ -1010,10 +2158,86 @@ export async function cancelPipeline(instanceId: number, projectId: number, pipe
 }
 
 /**
- * Get the raw log trace for a pipeline job.
+ * Fetch a project's pipeline schedules (refreshes the local cache).
+ */
+export async function listPipelineSchedules(instanceId: number, projectId: number): Promise<PipelineSchedule[]> {
+  return invoke<PipelineSchedule[]>('list_pipeline_schedules', { instanceId, projectId });
+}
+
+/**
+ * Get cached pipeline schedules from local DB for instant display.
+ */
+export async function getCachedPipelineSchedules(instanceId: number, projectId: number): Promise<PipelineSchedule[]> {
+  return invoke<PipelineSchedule[]>('get_cached_pipeline_schedules', { instanceId, projectId });
+}
+
+/**
+ * Run a pipeline schedule now.
+ */
+export async function runScheduleNow(instanceId: number, projectId: number, scheduleId: number): Promise<void> {
+  return invoke<void>('run_schedule_now', { instanceId, projectId, scheduleId });
+}
+
+/**
+ * Activate or deactivate a pipeline schedule.
+ */
+export async function toggleScheduleActive(
+  instanceId: number,
+  projectId: number,
+  scheduleId: number,
+  active: boolean,
+): Promise<PipelineSchedule> {
+  return invoke<PipelineSchedule>('toggle_schedule_active', { instanceId, projectId, scheduleId, active });
+}
+
+/**
+ * Fetch a project's recent releases with changelogs and asset links
+ * (refreshes the local cache).
+ */
+export async function listProjectReleases(instanceId: number, projectId: number): Promise<ProjectRelease[]> {
+  return invoke<ProjectRelease[]>('list_project_releases', { instanceId, projectId });
+}
+
+/**
+ * Get cached releases from local DB for instant display.
+ */
+export async function getCachedProjectReleases(instanceId: number, projectId: number): Promise<ProjectRelease[]> {
+  return invoke<ProjectRelease[]>('get_cached_project_releases', { instanceId, projectId });
+}
+
+/**
+ * Fetch a project's recent tags (refreshes the local cache).
+ */
+export async function listProjectTags(instanceId: number, projectId: number): Promise<ProjectTag[]> {
+  return invoke<ProjectTag[]>('list_project_tags', { instanceId, projectId });
+}
+
+/**
+ * Get cached tags from local DB for instant display.
+ */
+export async function getCachedProjectTags(instanceId: number, projectId: number): Promise<ProjectTag[]> {
+  return invoke<ProjectTag[]>('get_cached_project_tags', { instanceId, projectId });
+}
+
+/**
+ * Get the end of the log trace for a pipeline job (the last few hundred KB).
+ */
+export async function getJobTrace(instanceId: number, projectId: number, jobId: number): Promise<JobTrace> {
+  return invoke<JobTrace>('get_job_trace', { instanceId, projectId, jobId });
+}
+
+/**
+ * Get `length` bytes of a job's log trace from `offset`, to load output
+ * earlier than `getJobTrace` returned. The chunk starts at a line boundary.
  */
-export async function getJobTrace(instanceId: number, projectId: number, jobId: number): Promise<string> {
-  return invoke<string>('get_job_trace', { instanceId, projectId, jobId });
+export async function getJobTraceRange(
+  instanceId: number,
+  projectId: number,
+  jobId: number,
+  offset: number,
+  length: number,
+): Promise<JobTrace> {
+  return invoke<JobTrace>('get_job_trace_range', { instanceId, projectId, jobId, offset, length });
 }
 
 // ============================================================================
@@ -1097,6 +2321,14 @@ export async function getCompanionQrSvg(): Promise<string> {
   return invoke<string>('get_companion_qr_svg');
 }
 
+/**
+ * Get the URL phones open to reach the companion UI: the configured public
+ * URL, or the LAN address. Fails for proxied setups without a public URL.
+ */
+export async function getCompanionUrl(): Promise<string> {
+  return invoke<string>('get_companion_url');
+}
+
 /**
  * Revoke an authorized companion device.
  */
@@ -1125,6 +2357,21 @@ export async function getCompanionStatus(): Promise<CompanionStatus> {
   return invoke<CompanionStatus>('get_companion_status');
 }
 
+/**
+ * Confirm a companion pairing code shown in the pairing dialog.
+ * Returns the newly authorized device.
+ */
+export async function confirmCompanionPairing(code: string): Promise<AuthorizedDevice> {
+  return invoke<AuthorizedDevice>('confirm_pairing', { code });
+}
+
+/**
+ * Reject a companion pairing request.
+ */
+export async function rejectCompanionPairing(code: string): Promise<void> {
+  return invoke<void>('reject_pairing', { code });
+}
+
 // ============================================================================
 // Avatar Commands
 // ============================================================================
@@ -1151,12 +2398,152 @@ export async function updateSessionCookie(instanceId: number, sessionCookie: str
 }
 
 /**
- * Manually refresh all avatars for an instance. Returns count downloaded.
+ * Manually revalidate all avatars for an instance, or for every instance when
+ * none is given. Returns how many images changed.
  */
-export async function refreshAvatars(instanceId: number): Promise<number> {
+export async function refreshAvatars(instanceId?: number): Promise<number> {
   return invoke<number>('refresh_avatars', { instanceId });
 }
 
+// ============================================================================
+// Data Retention Commands
+// ============================================================================
+
+/**
+ * Get an instance's retention policy (defaults when none is configured).
+ */
+export async function getRetentionPolicy(instanceId: number): Promise<RetentionPolicy> {
+  return invoke<RetentionPolicy>('get_retention_policy', { instanceId });
+}
+
+/**
+ * Save an instance's retention policy and apply it to the existing cache.
+ * Returns what was removed.
+ */
+export async function updateRetentionPolicy(policy: RetentionPolicy): Promise<RetentionSweep> {
+  return invoke<RetentionSweep>('update_retention_policy', { policy });
+}
+
+/**
+ * List the categories of cached data held for each instance.
+ */
+export async function auditCachedData(): Promise<InstanceDataAudit[]> {
+  return invoke<InstanceDataAudit[]>('audit_cached_data');
+}
+
+/**
+ * Write the cached MRs, diffs and file content of all instances to an
+ * archive at `path` (absolute). Credentials are not included.
+ */
+export async function exportCacheArchive(path: string): Promise<CacheArchiveSummary> {
+  return invoke<CacheArchiveSummary>('export_cache_archive', { path });
+}
+
+/**
+ * Warm-start the local cache from an archive written by `exportCacheArchive`.
+ * Only instances already configured here are imported.
+ */
+export async function importCacheArchive(path: string): Promise<CacheArchiveSummary> {
+  return invoke<CacheArchiveSummary>('import_cache_archive', { path });
+}
+
+/**
+ * Write review activity, sync performance and pipeline outcomes recorded in
+ * `range` to `path` (absolute) as CSV or JSON.
+ */
+export async function exportStatistics(
+  range: StatsRange,
+  format: StatisticsExportFormat,
+  path: string,
+): Promise<StatisticsExportSummary> {
+  return invoke<StatisticsExportSummary>('export_statistics', { range, format, path });
+}
+
+// ============================================================================
+// Automation Commands
+// ============================================================================
+
+/**
+ * List the user's automation rules.
+ */
+export async function listAutomationRules(): Promise<AutomationRule[]> {
+  return invoke<AutomationRule[]>('list_automation_rules');
+}
+
+/**
+ * Create an automation rule, or update it when `rule.id` is set.
+ */
+export async function saveAutomationRule(rule: AutomationRuleInput): Promise<AutomationRule> {
+  return invoke<AutomationRule>('save_automation_rule', { rule });
+}
+
+/**
+ * Delete an automation rule and its run log.
+ */
+export async function deleteAutomationRule(ruleId: number): Promise<void> {
+  return invoke<void>('delete_automation_rule', { ruleId });
+}
+
+/**
+ * Run a rule once against a sample MR. The run is logged and returned.
+ */
+export async function testAutomation(ruleId: number): Promise<AutomationRun> {
+  return invoke<AutomationRun>('test_automation', { ruleId });
+}
+
+/**
+ * Recent automation runs, newest first, optionally for one rule.
+ */
+export async function listAutomationRuns(ruleId?: number): Promise<AutomationRun[]> {
+  return invoke<AutomationRun[]>('list_automation_runs', { ruleId: ruleId ?? null });
+}
+
+/**
+ * List the MR list badge rules, with the last error each one hit.
+ */
+export async function listBadgeRules(): Promise<BadgeRule[]> {
+  return invoke<BadgeRule[]>('list_badge_rules');
+}
+
+/**
+ * Replace the badge rules. Fails without saving when a script doesn't compile.
+ */
+export async function updateBadgeRules(rules: BadgeRuleInput[]): Promise<BadgeRule[]> {
+  return invoke<BadgeRule[]>('update_badge_rules', { rules });
+}
+
+// ============================================================================
+// Review Stats Commands
+// ============================================================================
+
+/**
+ * Get the opt-in review stats (streaks, weekly comments, response time).
+ */
+export async function getReviewStreaks(): Promise<ReviewStreaks> {
+  return invoke<ReviewStreaks>('get_review_streaks');
+}
+
+/**
+ * Opt in to or out of review stats. Opting out deletes the recorded activity.
+ */
+export async function setReviewStatsEnabled(enabled: boolean): Promise<ReviewStreaks> {
+  return invoke<ReviewStreaks>('set_review_stats_enabled', { enabled });
+}
+
+/**
+ * Export the review stats together with the raw activity log.
+ */
+export async function exportReviewStats(): Promise<ReviewStatsExport> {
+  return invoke<ReviewStatsExport>('export_review_stats');
+}
+
+/**
+ * Review SLA status of every MR awaiting my review. Empty when the SLA is off.
+ */
+export async function getReviewSlaStatus(): Promise<ReviewSlaStatus[]> {
+  return invoke<ReviewSlaStatus[]>('get_review_sla_status');
+}
+
 // ============================================================================
 // CLI Commands
 // ============================================================================
@@ -1175,3 +2562,33 @@ export async function downloadAndInstallCli(): Promise<CliInstallResult> {
 export async function cliStatus(): Promise<CliStatus> {
   return invoke<CliStatus>('cli_status');
 }
+
+// ============================================================================
+// Link Handler Commands
+// ============================================================================
+
+/**
+ * Register the app as the OS handler for links. MR links on `hosts` open in
+ * the app; everything else goes to `fallbackBrowser`. Windows and Linux only.
+ */
+export async function registerLinkHandler(
+  hosts: string[],
+  fallbackBrowser: string
+): Promise<LinkHandlerSettings> {
+  return invoke<LinkHandlerSettings>('register_link_handler', { hosts, fallbackBrowser });
+}
+
+/**
+ * Hand links back to the browser; the chosen hosts are kept.
+ */
+export async function unregisterLinkHandler(): Promise<void> {
+  return invoke<void>('unregister_link_handler');
+}
+
+/**
+ * Route a link the OS handed to the app. Returns the MR URL to open, or null
+ * after forwarding the link to the browser.
+ */
+export async function openLink(url: string): Promise<string | null> {
+  return invoke<string | null>('open_link', { url });
+}
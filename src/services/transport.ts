@@ -17,6 +17,28 @@
  */
 export const isTauri: boolean = typeof window !== 'undefined' && '__TAURI_INTERNALS__' in window;
 
+declare global {
+  interface Window {
+    /** Injected by the companion server when it is served under a base path. */
+    __COMPANION_BASE_PATH__?: string;
+  }
+}
+
+/**
+ * Path prefix the companion UI is served under behind a reverse proxy
+ * (e.g. `/gitlab`), or an empty string when served from the root.
+ */
+export const companionBasePath: string =
+  (typeof window !== 'undefined' && window.__COMPANION_BASE_PATH__) || '';
+
+/**
+ * Resolve an absolute companion server path (e.g. `/api/instances`)
+ * against the base path.
+ */
+export function apiUrl(path: string): string {
+  return `${companionBasePath}${path}`;
+}
+
 // ============================================================================
 // HTTP Transport — command-to-REST mapping
 // ============================================================================
@@ -90,6 +112,7 @@ const ROUTE_MAP: Record<string, RouteMapping> = {
       const p: Record<string, unknown> = {};
       if (args?.start != null) p.start = args.start;
       if (args?.count != null) p.count = args.count;
+      if (args?.ignoreWhitespace) p.ignore_whitespace = true;
       return p;
     },
   },
@@ -108,6 +131,7 @@ const ROUTE_MAP: Record<string, RouteMapping> = {
   get_comments: {
     method: 'GET',
     path: (args) => `/api/merge-requests/${args?.mrId}/comments`,
+    params: (args) => ({ sort: args?.sort, contextLines: args?.contextLines }),
   },
 
   get_mr_reviewers: {
@@ -120,16 +144,71 @@ const ROUTE_MAP: Record<string, RouteMapping> = {
     path: (args) => `/api/merge-requests/${args?.mrId}/diff-refs`,
   },
 
+  get_file_blame: {
+    method: 'GET',
+    path: (args) =>
+      `/api/merge-requests/${args?.mrId}/files/${encodeURIComponent(String(args?.filePath))}/blame`,
+  },
+
+  get_mr_commits: {
+    method: 'GET',
+    path: (args) => `/api/merge-requests/${args?.mrId}/commits`,
+  },
+
+  get_mr_activity: {
+    method: 'GET',
+    path: (args) => `/api/merge-requests/${args?.mrId}/activity`,
+  },
+
+  get_mr_language_breakdown: {
+    method: 'GET',
+    path: (args) => `/api/merge-requests/${args?.mrId}/languages`,
+  },
+
+  get_commit_diff: {
+    method: 'GET',
+    path: (args) =>
+      `/api/merge-requests/${args?.mrId}/commits/${encodeURIComponent(String(args?.sha))}/diff`,
+  },
+
+  get_changes_since_review: {
+    method: 'GET',
+    path: (args) => `/api/merge-requests/${args?.mrId}/changes-since-review`,
+  },
+
+  mark_mr_reviewed: {
+    method: 'POST',
+    path: (args) => `/api/merge-requests/${args?.mrId}/reviewed`,
+  },
+
+  get_file_review_states: {
+    method: 'GET',
+    path: (args) => `/api/merge-requests/${args?.mrId}/file-review-states`,
+  },
+
+  mark_file_viewed: {
+    method: 'POST',
+    path: (args) => `/api/merge-requests/${args?.mrId}/viewed-files`,
+    params: (args) => ({ filePath: args?.filePath, viewed: args?.viewed }),
+  },
+
   get_file_comments: {
     method: 'GET',
     path: (args) => `/api/merge-requests/${args?.mrId}/file-comments`,
     params: (args) => ({ filePath: args?.filePath }),
   },
 
+  render_gfm: {
+    method: 'POST',
+    path: () => '/api/markdown',
+    params: (args) => ({ text: args?.text, projectContext: args?.projectContext }),
+  },
+
   // ── Approvals ──────────────────────────────────────────────────────────
   approve_mr: {
     method: 'POST',
     path: (args) => `/api/merge-requests/${args?.mrId}/approve`,
+    params: (args) => ({ confirm: args?.confirm }),
   },
 
   unapprove_mr: {
@@ -153,11 +232,43 @@ const ROUTE_MAP: Record<string, RouteMapping> = {
     path: () => '/api/sync/trigger',
   },
 
+  get_changes_since: {
+    method: 'GET',
+    path: () => '/api/changes',
+    params: (args) => {
+      const p: Record<string, unknown> = {};
+      if (args?.cursor != null) p.cursor = args.cursor;
+      return p;
+    },
+  },
+
+  get_mr_delta: {
+    method: 'GET',
+    path: () => '/api/merge-requests/delta',
+    params: (args) => {
+      const p: Record<string, unknown> = {};
+      if (args?.since != null) p.since = args.since;
+      return p;
+    },
+  },
+
   // ── Settings (read-only) ───────────────────────────────────────────────
   get_settings: {
     method: 'GET',
     path: () => '/api/settings',
   },
+
+  // ── MR list view state ─────────────────────────────────────────────────
+  get_list_view_state: {
+    method: 'GET',
+    path: () => '/api/list-view-state',
+  },
+
+  update_list_view_state: {
+    method: 'POST',
+    path: () => '/api/list-view-state',
+    params: (args) => ({ state: args?.state }),
+  },
 };
 
 // ============================================================================
@@ -174,7 +285,7 @@ async function httpInvoke<T>(cmd: string, args?: Record<string, unknown>): Promi
     throw new Error(`Command "${cmd}" is not available in browser mode`);
   }
 
-  const urlPath = route.path(args);
+  const urlPath = apiUrl(route.path(args));
   const extraParams = route.params?.(args);
 
   let url: string;
@@ -226,15 +337,37 @@ function toQueryString(params: Record<string, unknown>): string {
 
 type UnlistenFn = () => void;
 
+/** Events the companion server relays over `/api/events`. */
+const COMPANION_EVENTS = new Set(['mr-updated', 'sync-progress', 'action-synced', 'lagged']);
+
+let companionEventSource: EventSource | null = null;
+let companionListenerCount = 0;
+
 /**
- * Listen for a Tauri event. Returns a no-op unlisten function in browser mode
- * (Tauri events never fire there).
+ * Listen for a Tauri event. In browser mode the events the companion server
+ * relays arrive over one shared SSE connection; for all others this returns
+ * a no-op unlisten function (they never fire there).
  */
 export async function tauriListen<T>(
   event: string,
   handler: (event: { payload: T }) => void,
 ): Promise<UnlistenFn> {
-  if (!isTauri) return () => {};
+  if (!isTauri) {
+    if (!COMPANION_EVENTS.has(event) || typeof EventSource === 'undefined') return () => {};
+    companionEventSource ??= new EventSource(apiUrl('/api/events'));
+    companionListenerCount += 1;
+    const source = companionEventSource;
+    const listener = (e: MessageEvent<string>) => handler({ payload: JSON.parse(e.data) as T });
+    source.addEventListener(event, listener);
+    return () => {
+      source.removeEventListener(event, listener);
+      companionListenerCount -= 1;
+      if (companionListenerCount === 0 && companionEventSource === source) {
+        source.close();
+        companionEventSource = null;
+      }
+    };
+  }
   const { listen } = await import('@tauri-apps/api/event');
   return listen<T>(event, handler);
 }
@@ -256,6 +389,76 @@ export async function openExternalUrl(url: string): Promise<void> {
   }
 }
 
+// ============================================================================
+// Command timing
+// ============================================================================
+
+interface CommandTiming {
+  command: string;
+  durationMs: number;
+  payloadBytes: number;
+  ok: boolean;
+}
+
+const RECORD_TIMINGS_COMMAND = 'record_command_timings';
+const TIMING_FLUSH_INTERVAL_MS = 5000;
+const TIMING_FLUSH_BATCH = 100;
+
+let pendingTimings: CommandTiming[] = [];
+let timingFlushTimer: ReturnType<typeof setTimeout> | null = null;
+
+/** Send buffered timings to the backend ring buffer (see `get_command_metrics`). */
+async function flushTimings(): Promise<void> {
+  if (timingFlushTimer) {
+    clearTimeout(timingFlushTimer);
+    timingFlushTimer = null;
+  }
+  if (pendingTimings.length === 0) return;
+  const timings = pendingTimings;
+  pendingTimings = [];
+  const { invoke: tauriInvoke } = await import('@tauri-apps/api/core');
+  // Metrics are best-effort; never surface a failure here.
+  await tauriInvoke(RECORD_TIMINGS_COMMAND, { timings }).catch(() => {});
+}
+
+function queueTiming(timing: CommandTiming): void {
+  pendingTimings.push(timing);
+  if (pendingTimings.length >= TIMING_FLUSH_BATCH) {
+    void flushTimings();
+  } else if (!timingFlushTimer) {
+    timingFlushTimer = setTimeout(() => void flushTimings(), TIMING_FLUSH_INTERVAL_MS);
+  }
+}
+
+function payloadBytes(args?: Record<string, unknown>): number {
+  if (!args) return 0;
+  try {
+    return new TextEncoder().encode(JSON.stringify(args)).length;
+  } catch {
+    return 0;
+  }
+}
+
+// ============================================================================
+// Compressed responses
+// ============================================================================
+
+/** First two bytes of every gzip stream. */
+const GZIP_MAGIC = [0x1f, 0x8b];
+
+/**
+ * Large responses of some commands arrive as gzipped JSON in raw bytes
+ * instead of JSON (see `ipc_compression` in the backend). Inflate and parse
+ * those; anything else is returned unchanged.
+ */
+async function decodeResponse<T>(result: unknown): Promise<T> {
+  if (!(result instanceof ArrayBuffer)) return result as T;
+  const head = new Uint8Array(result, 0, Math.min(2, result.byteLength));
+  if (head[0] !== GZIP_MAGIC[0] || head[1] !== GZIP_MAGIC[1]) return result as T;
+  const stream = new Blob([result]).stream().pipeThrough(new DecompressionStream('gzip'));
+  return JSON.parse(await new Response(stream).text()) as T;
+}
+
 // ============================================================================
 // Unified invoke — the public API
 // ============================================================================
@@ -263,11 +466,27 @@ export async function openExternalUrl(url: string): Promise<void> {
 /**
  * Invoke a backend command. Routes to Tauri IPC when in a webview,
  * or to HTTP fetch when in a browser.
+ *
+ * Every IPC call is timed and reported to the backend in batches; the time
+ * includes inflating a compressed response.
  */
 export async function transportInvoke<T>(cmd: string, args?: Record<string, unknown>): Promise<T> {
   if (isTauri) {
     const { invoke: tauriInvoke } = await import('@tauri-apps/api/core');
-    return tauriInvoke<T>(cmd, args);
+    const start = performance.now();
+    let ok = false;
+    try {
+      const result = await decodeResponse<T>(await tauriInvoke<unknown>(cmd, args));
+      ok = true;
+      return result;
+    } finally {
+      queueTiming({
+        command: cmd,
+        durationMs: performance.now() - start,
+        payloadBytes: payloadBytes(args),
+        ok,
+      });
+    }
   }
   return httpInvoke<T>(cmd, args);
 }
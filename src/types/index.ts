@@ -17,11 +17,29 @@ export interface GitLabInstance {
   isDefault: boolean;
 }
 
+/** Extra HTTP header sent with every request to an instance (e.g. an SSO proxy token). */
+export interface CustomHeader {
+  name: string;
+  value: string;
+}
+
+/** TLS settings for connections to an instance. */
+export interface InstanceTls {
+  /** Oldest TLS version negotiated with the instance. */
+  minVersion: '1.2' | '1.3';
+  /** PEM file with the client certificate and its private key, for mTLS. */
+  clientIdentityPath?: string | null;
+}
+
 export interface GitLabInstanceSetup {
   url: string;
   token: string;
   name?: string;
   sessionCookie?: string;
+  /** Omit to keep the headers already configured for this URL. */
+  customHeaders?: CustomHeader[];
+  /** Omit to keep the TLS settings already configured for this URL. */
+  tls?: InstanceTls;
 }
 
 export interface GitLabInstanceResponse {
@@ -38,6 +56,34 @@ export interface TokenInfo {
   active: boolean;
 }
 
+/** API rate-limit budget seen in the last response's `RateLimit-*` headers. */
+export interface RateLimitSnapshot {
+  limit: number;
+  remaining: number;
+  /** Unix seconds. */
+  resetAt: number | null;
+  /** Unix seconds. */
+  observedAt: number;
+}
+
+/** CI minutes and storage of a root namespace; null when GitLab hides it. */
+export interface NamespaceQuota {
+  fullPath: string;
+  kind: string;
+  plan: string | null;
+  ciMinutesLimit: number | null;
+  ciMinutesUnlimited: boolean;
+  ciMinutesUsed: number | null;
+  ciMinutesRemaining: number | null;
+  storageBytes: number | null;
+}
+
+export interface InstanceQuota {
+  instanceId: number;
+  rateLimit: RateLimitSnapshot | null;
+  namespaces: NamespaceQuota[];
+}
+
 // ============================================================================
 // Merge Request
 // ============================================================================
@@ -69,12 +115,194 @@ export interface MergeRequest {
   cachedAt: number;
   userHasApproved: boolean;
   headPipelineStatus: string | null;
+  watched: boolean;
+  /** Author is a bot account (Dependabot, Renovate, access tokens) */
+  isBotAuthor: boolean;
+  /** When the author asked me to review again (Unix); null once I review */
+  reviewRerequestedAt: number | null;
+  /** Task list items (`- [ ]`) in the description */
+  taskCount: number;
+  /** Checked task list items in the description */
+  taskCompletedCount: number;
+  /** Only project members can comment */
+  discussionLocked: boolean;
+  /** The head moved on since the user last marked the MR reviewed */
+  changedSinceReview: boolean;
+  /** GitLab ID of the MR's milestone */
+  milestoneId: number | null;
+  milestoneTitle: string | null;
+  /** Milestone due date (`YYYY-MM-DD`) */
+  milestoneDueDate: string | null;
+  /** Badges from the user's badge rules (MR list queries only) */
+  customBadges?: CustomBadge[];
+}
+
+/** One checkbox of an MR description task list. */
+export interface MrTask {
+  position: number;
+  text: string;
+  checked: boolean;
+}
+
+export interface MrTaskList {
+  total: number;
+  completed: number;
+  items: MrTask[];
+}
+
+/** A label that can be applied in a project. */
+export interface ProjectLabel {
+  name: string;
+  /** Background color, e.g. `#428BCA`. */
+  color: string;
+  textColor: string | null;
+  description: string | null;
+}
+
+/** An active project or group milestone an MR can be put in. */
+export interface ProjectMilestone {
+  id: number;
+  title: string;
+  /** `YYYY-MM-DD` */
+  dueDate: string | null;
+}
+
+/** A project member who can be picked as reviewer or assignee. */
+export interface ProjectMember {
+  id: number;
+  username: string;
+  name: string;
+  avatarUrl: string | null;
+}
+
+/** One reason an MR can't be merged yet. */
+export type MergeBlocker =
+  | 'draft'
+  | 'conflicts'
+  | 'needsRebase'
+  | 'unresolvedDiscussions'
+  | 'pipelineFailed'
+  | 'pipelineRunning'
+  | 'pipelineMissing'
+  | 'approvalsMissing';
+
+export interface ApprovalRuleStatus {
+  name: string;
+  ruleType: string;
+  approvalsRequired: number;
+  approved: boolean;
+  approvedBy: string[];
+}
+
+/** Result of `get_merge_blockers`. */
+export interface MergeBlockers {
+  mrId: number;
+  /** Empty when nothing known blocks the merge. */
+  blockers: MergeBlocker[];
+  draft: boolean;
+  hasConflicts: boolean;
+  pipelineStatus: string | null;
+  /** Oldest first. */
+  unresolvedDiscussionIds: string[];
+  approvals: {
+    required: number;
+    given: number;
+    /** null until approvals have been synced. */
+    approved: boolean | null;
+    /** Empty until refreshed, or when the instance has no approval rules. */
+    rules: ApprovalRuleStatus[];
+  };
+  detailedMergeStatus: string | null;
+  /** Read from GitLab rather than the cache. */
+  refreshed: boolean;
+}
+
+/** State of an MR template section in a description. */
+export type DescriptionSectionStatus = 'filled' | 'missing' | 'empty';
+
+export interface DescriptionSectionCheck {
+  /** Heading as written in the template. */
+  heading: string;
+  status: DescriptionSectionStatus;
+}
+
+/** Result of `lint_mr_description`. */
+export interface DescriptionLint {
+  /** Template checked against; null when the project has no MR templates. */
+  templatePath: string | null;
+  sections: DescriptionSectionCheck[];
 }
 
 export interface MRFilter {
   state?: MRState | 'all';
   scope?: 'authored' | 'reviewing' | 'all';
   search?: string;
+  /** Include bot-authored MRs, which are hidden by default */
+  includeBots?: boolean;
+}
+
+/** An added diff line that likely introduces a credential. */
+export interface SecurityFinding {
+  filePath: string;
+  /** Line number in the new version of the file. */
+  line: number;
+  severity: 'low' | 'medium' | 'high';
+  /** Check identifier, e.g. `aws-access-key-id`. */
+  rule: string;
+  message: string;
+  /** The line with the secret value masked. */
+  excerpt: string;
+}
+
+/** A project's README at a ref, rendered to HTML by GitLab. */
+export interface ProjectReadme {
+  refName: string;
+  /** Path of the README in the repository, or null when the project has none. */
+  filePath: string | null;
+  html: string;
+  /** Unix timestamp (seconds) of when the README was fetched. */
+  fetchedAt: number;
+}
+
+/** Inclusive range of target-branch line numbers. */
+export interface LineRange {
+  start: number;
+  end: number;
+}
+
+/** Hunks of two MRs that change the same (or adjacent) lines. */
+export interface ConflictingHunks {
+  rangeA: LineRange;
+  rangeB: LineRange;
+}
+
+/** A file changed by both compared MRs. */
+export interface OverlappingFile {
+  /** Path on the target branch (the old path for renames). */
+  path: string;
+  changeTypeA: string;
+  changeTypeB: string;
+  /** False when a diff wasn't cached, so hunks couldn't be compared. */
+  diffAvailable: boolean;
+  conflicts: ConflictingHunks[];
+}
+
+export interface ComparedMr {
+  id: number;
+  iid: number;
+  title: string;
+  sourceBranch: string;
+  targetBranch: string;
+  baseSha: string;
+}
+
+/** Result of comparing two open MRs of the same project. */
+export interface MrComparison {
+  mrA: ComparedMr;
+  mrB: ComparedMr;
+  /** Both diffs share a base commit, so line numbers line up exactly. */
+  sameBase: boolean;
+  overlappingFiles: OverlappingFile[];
 }
 
 export interface MRDetailResponse {
@@ -92,6 +320,38 @@ export interface MrReviewer {
   username: string;
   status: 'approved' | 'pending' | 'changes_requested';
   cachedAt: number;
+  /** GitLab review state (`unreviewed`, `reviewed`, ...), GitLab 16+ only */
+  reviewState: string | null;
+  /** When a finished review was re-requested from this reviewer (Unix) */
+  rerequestedAt: number | null;
+}
+
+// ============================================================================
+// MR Participants
+// ============================================================================
+
+export type ParticipantRole = 'author' | 'assignee' | 'reviewer' | 'approver' | 'commenter';
+
+/** Someone involved in an MR, built from cached data. */
+export interface Participant {
+  username: string;
+  /** All roles of this person, most prominent first */
+  roles: ParticipantRole[];
+  /** Comments and replies written (system notes excluded) */
+  commentCount: number;
+  /** When the last of those comments was written (Unix) */
+  lastCommentAt: number | null;
+  /** Cached avatar as a data URI */
+  avatar: string | null;
+}
+
+/** An MR checked out in a local clone. */
+export interface BranchCheckout {
+  repoPath: string;
+  branch: string;
+  headSha: string;
+  /** The local branch was created by this checkout. */
+  created: boolean;
 }
 
 // ============================================================================
@@ -140,11 +400,133 @@ export interface DiffRefs {
   startSha: string;
 }
 
+/** Consecutive lines last changed by one commit. */
+export interface BlameRange {
+  /** First line of the range, from 1. */
+  startLine: number;
+  lineCount: number;
+  commitSha: string;
+  authorName: string;
+  authorEmail: string;
+  /** Unix seconds. */
+  authoredAt: number;
+  /** First line of the commit message. */
+  summary: string;
+}
+
+/** Blame of a file at an MR's head SHA. */
+export interface FileBlame {
+  sha: string;
+  filePath: string;
+  ranges: BlameRange[];
+}
+
+/** A commit of an MR, as of the last sync. */
+export interface MrCommit {
+  sha: string;
+  shortSha: string;
+  /** First line of the message. */
+  title: string;
+  message: string;
+  authorName: string;
+  authorEmail: string;
+  /** Unix seconds. */
+  authoredAt: number;
+  webUrl: string;
+}
+
+/** What an activity timeline entry records. */
+export type MrEventKind =
+  | 'labels_added'
+  | 'labels_removed'
+  | 'milestone_set'
+  | 'milestone_removed'
+  | 'closed'
+  | 'reopened'
+  | 'merged'
+  | 'pushed'
+  | 'force_pushed'
+  | 'review_requested'
+  | 'review_request_removed'
+  | 'assigned'
+  | 'unassigned'
+  | 'approved'
+  | 'unapproved'
+  | 'marked_draft'
+  | 'marked_ready'
+  | 'title_changed'
+  | 'description_changed'
+  | 'other';
+
+/** One entry of an MR's activity timeline, from a system note or resource event. */
+export interface MrEvent {
+  source: 'note' | 'label' | 'milestone' | 'state';
+  sourceId: number;
+  kind: MrEventKind;
+  /** Null when GitLab no longer knows the user. */
+  actorUsername: string | null;
+  /** Labels, usernames or milestone the event is about. */
+  subjects: string[];
+  /** Commit count for pushes, new title for title changes, text of other notes. */
+  detail: string | null;
+  /** Unix seconds. */
+  createdAt: number;
+}
+
+/** Lines changed in one language of an MR. */
+export interface LanguageLines {
+  /** Linguist name, or `Other`. */
+  language: string;
+  /** Added plus removed lines. */
+  lines: number;
+}
+
+/** Lines changed per language in an MR, most changed first. */
+export interface LanguageBreakdown {
+  mrId: number;
+  languages: LanguageLines[];
+  /** Generated files aren't counted. */
+  totalLines: number;
+}
+
+/** One file changed by a commit. */
+export interface CommitDiffFile {
+  oldPath: string;
+  newPath: string;
+  changeType: 'added' | 'modified' | 'deleted' | 'renamed';
+  additions: number;
+  deletions: number;
+  /** Unified diff of the file. */
+  diff: string;
+}
+
+/** The changes one commit of an MR made, compared with its parent. */
+export interface CommitDiff {
+  commit: MrCommit;
+  files: CommitDiffFile[];
+}
+
+/** What changed in an MR's files since the user last marked it reviewed. */
+export interface ChangesSinceReview {
+  reviewedSha: string;
+  headSha: string;
+  files: CommitDiffFile[];
+}
+
+/** A file marked viewed at the MR's current head. */
+export interface FileReviewState {
+  filePath: string;
+  headSha: string;
+  viewedAt: number;
+}
+
 export interface DiffFileContent {
   filePath: string;
   oldContent: string | null;
   newContent: string | null;
   diffHunks: DiffHunk[];
+  /** Whether whitespace-only changes were left out of the hunks */
+  whitespaceIgnored: boolean;
 }
 
 export interface DiffHunk {
@@ -155,11 +537,52 @@ export interface DiffHunk {
   lines: DiffLine[];
 }
 
+/** A syntax-highlighted byte range of a diff line. */
+export interface HighlightSpan {
+  /** The `hl-*` class without its prefix, e.g. "keyword". */
+  tokenType: string;
+  /** Byte offsets into the line's UTF-8 content. */
+  start: number;
+  end: number;
+}
+
+/** A changed byte range of a diff line (UTF-8 offsets, like `HighlightSpan`). */
+export interface ChangeRange {
+  start: number;
+  end: number;
+}
+
 export interface DiffLine {
   type: LineType;
   content: string;
   oldLineNumber: number | null;
   newLineNumber: number | null;
+  /** Syntax highlighting; empty when the file's language isn't supported. */
+  highlights: HighlightSpan[];
+  /**
+   * Changed words relative to the paired removed/added line; empty for
+   * context lines and changes without a similar counterpart.
+   */
+  wordChanges: ChangeRange[];
+}
+
+// Side-by-side diff built from the cached file versions
+export type SideLineKind = 'context' | 'removed' | 'added' | 'placeholder';
+
+export interface SideLine {
+  kind: SideLineKind;
+  /** 1-based line number in this side's file; null for placeholders */
+  number: number | null;
+  content: string;
+  highlights: HighlightSpan[];
+  /** Changed words relative to the line on the other side of the row */
+  wordChanges: ChangeRange[];
+}
+
+/** `left[i]` and `right[i]` form row `i`; both arrays are equally long. */
+export interface SideBySideDiff {
+  left: SideLine[];
+  right: SideLine[];
 }
 
 // Progressive diff loading types
@@ -178,6 +601,8 @@ export interface DiffHunksResponse {
   startIndex: number;
   totalHunks: number;
   hasMore: boolean;
+  /** Whether whitespace-only changes were left out of the hunks */
+  whitespaceIgnored: boolean;
 }
 
 // ============================================================================
@@ -202,6 +627,45 @@ export interface Comment {
   updatedAt: number;
   isLocal: boolean;
   syncStatus: SyncStatus | null;
+  /** Suggestions GitLab found in a synced diff note. */
+  suggestions?: CommentSuggestion[];
+  /** Cached code around the commented line, when requested with `contextLines`. */
+  snippet?: CommentSnippet | null;
+}
+
+/** Lines of code around an inline comment, cut from the cached file. */
+export interface CommentSnippet {
+  /** `new` when cut from the head version, `old` from the base version. */
+  side: 'new' | 'old';
+  /** Line number of the first entry in `lines`. */
+  startLine: number;
+  /** Line number of the commented line. */
+  line: number;
+  lines: string[];
+}
+
+/** A ```suggestion block in a diff note that can be applied from the app. */
+export interface CommentSuggestion {
+  id: number;
+  commentId: number;
+  /** First and last new-side line the suggestion replaces. */
+  fromLine: number;
+  toLine: number;
+  toContent: string;
+  appliable: boolean;
+  applied: boolean;
+}
+
+/** Order of the comments returned by `get_comments`. */
+export type CommentSort = 'oldest' | 'newest' | 'unresolved_first' | 'by_file';
+
+/** Where the unread comments of an MR start (from `get_latest_comment_cursor`). */
+export interface CommentCursor {
+  /** When the MR was last opened (Unix seconds); null if never. */
+  lastViewedAt: number | null;
+  firstUnreadId: number | null;
+  unreadCount: number;
+  latestId: number | null;
 }
 
 export interface AddCommentRequest {
@@ -212,6 +676,21 @@ export interface AddCommentRequest {
   newLine?: number;
   /** When true, this is an unchanged (context) line — backend resolves both line numbers from the diff. */
   isContextLine?: boolean;
+  /** Replacement for the commented line, posted as a suggestion after the body. Needs `newLine`. */
+  suggestionContent?: string;
+  /** Post an `/approve` in the body even if there are review blockers. */
+  confirm?: boolean;
+}
+
+export interface AddCommentResult {
+  /**
+   * Null when the approval gate or review blockers held back an `/approve`
+   * in the body; nothing was queued.
+   */
+  comment: Comment | null;
+  unmet: UnmetCondition[];
+  /** Set when review blockers held the approval; post again with `confirm`. */
+  blockers: ReviewBlockers | null;
 }
 
 export interface ReplyToCommentRequest {
@@ -227,11 +706,155 @@ export interface ResolveDiscussionRequest {
   resolved: boolean;
 }
 
+/** A comment in a review bundle; inline when `filePath` is set. */
+export type ReviewBundleComment = Omit<AddCommentRequest, 'mrId' | 'confirm'>;
+
+/** Comments, resolved threads and an approval, pushed to GitLab in that order. */
+export interface ReviewBundleRequest {
+  mrId: number;
+  comments?: ReviewBundleComment[];
+  resolveDiscussions?: string[];
+  approve?: boolean;
+  /** Approve even if there are review blockers. */
+  confirm?: boolean;
+}
+
+export interface ReviewBundleResult {
+  /**
+   * False when the approval gate or review blockers held the approval back;
+   * nothing was queued.
+   */
+  submitted: boolean;
+  /** Null when nothing needed queueing (e.g. the MR was already approved). */
+  batchId: string | null;
+  comments: Comment[];
+  unmet: UnmetCondition[];
+  /** Set when review blockers held the approval; resubmit with `confirm`. */
+  blockers: ReviewBlockers | null;
+}
+
+/** The reviewer one file of an MR is assigned to. */
+export interface FileAssignment {
+  filePath: string;
+  username: string;
+  assignedBy: string;
+  /** Unix seconds. */
+  assignedAt: number;
+}
+
+/** A comment held in a pending review until it is submitted. */
+export interface DraftComment {
+  id: number;
+  body: string;
+  filePath: string | null;
+  oldLine: number | null;
+  newLine: number | null;
+  createdAt: number;
+}
+
+/** Draft comments collected locally; nothing reaches GitLab before submit. */
+export interface PendingReview {
+  id: number;
+  mrId: number;
+  startedAt: number;
+  comments: DraftComment[];
+}
+
+export interface SubmitReviewRequest {
+  mrId: number;
+  /** Overall note, posted after the drafts. */
+  summary?: string;
+  resolveDiscussions?: string[];
+  approve?: boolean;
+  /** Approve even if there are review blockers. */
+  confirm?: boolean;
+}
+
+export type ReviewBatchState = 'pending' | 'synced' | 'partial' | 'failed';
+
+/** An action of a review bundle still in the sync queue. */
+export interface ReviewBatchStep {
+  actionId: number;
+  actionType: string;
+  /** Queue status, or `blocked` when held back by an earlier failed step. */
+  status: string;
+  localReferenceId: number | null;
+  retryCount: number;
+  error: string | null;
+}
+
+export interface ReviewBatchStatus {
+  batchId: string;
+  mrId: number;
+  state: ReviewBatchState;
+  total: number;
+  synced: number;
+  /** Steps not synced yet, in push order. */
+  steps: ReviewBatchStep[];
+}
+
+/** Transcript format for exporting a discussion thread. */
+export type DiscussionExportFormat = 'markdown' | 'text';
+
 export interface DeleteCommentRequest {
   mrId: number;
   commentId: number;
 }
 
+/** A GitLab quick action (`/approve`, `/label ~bug`, ...) for composer autocomplete. */
+export interface QuickActionSpec {
+  name: string;
+  aliases: string[];
+  /** Argument hint, empty when the command takes none. */
+  params: string;
+  description: string;
+}
+
+export interface ParsedQuickAction {
+  name: string;
+  args: string;
+  /** Human-readable summary of what GitLab will do. */
+  effect: string;
+}
+
+export interface QuickActionPreview {
+  actions: ParsedQuickAction[];
+  /** The body is only quick actions; GitLab will not create a visible note. */
+  commandsOnly: boolean;
+}
+
+/** Creation time bounds (Unix seconds) for comment history. */
+export interface CommentRange {
+  since?: number;
+  until?: number;
+}
+
+/** One of my own comments, from any cached MR. */
+export interface MyComment {
+  id: number;
+  mrId: number;
+  instanceId: number;
+  mrIid: number;
+  mrTitle: string;
+  projectName: string;
+  /** Link to the note on GitLab; null while it is still queued. */
+  webUrl: string | null;
+  body: string;
+  filePath: string | null;
+  createdAt: number;
+}
+
+/** What a ping asks the MR author to do. */
+export type PingTemplate = 'awaiting_reply' | 'open_threads' | 'rebase';
+
+/** A ping already sent to the MR author. */
+export interface AuthorPing {
+  template: PingTemplate;
+  /** Number of unresolved threads the ping linked. */
+  threadCount: number;
+  pingedAt: number;
+}
+
 // ============================================================================
 // Sync Action
 // ============================================================================
@@ -249,6 +872,85 @@ export interface SyncAction {
   createdAt: number;
 }
 
+/** Whether retrying a failed action can be expected to help. */
+export type RetryAdvice = 'retry' | 'retry_later' | 'reauthenticate' | 'discard';
+
+/**
+ * A failed action with the details of its last failure: either waiting for
+ * its automatic retry (`pending`) or left for the user (`failed`).
+ */
+export interface FailedAction {
+  id: number;
+  mrId: number;
+  mrTitle: string | null;
+  actionType: ActionType;
+  status: 'pending' | 'failed';
+  /** When the automatic retry is due (Unix seconds); null once it is left failed. */
+  nextRetryAt: number | null;
+  retryCount: number;
+  lastError: string | null;
+  /** `gitlab_api`, `network`, `authentication` or `other`; null for older failures. */
+  errorKind: string | null;
+  httpStatus: number | null;
+  gitlabMessage: string | null;
+  endpoint: string | null;
+  createdAt: number;
+  firstAttemptAt: number | null;
+  lastAttemptAt: number | null;
+  advice: RetryAdvice;
+  /** One-sentence explanation of the failure and what to do. */
+  hint: string;
+}
+
+/** A queued action dropped because its MR left the cache before it was pushed. */
+export interface OrphanedAction {
+  id: number;
+  actionId: number;
+  mrId: number;
+  projectId: number | null;
+  mrIid: number | null;
+  mrTitle: string | null;
+  actionType: ActionType;
+  /** Comment or reply text, if any. */
+  body: string | null;
+  /** Queue status when it was dropped. */
+  status: 'pending' | 'syncing' | 'failed';
+  reason: string;
+  createdAt: number;
+  droppedAt: number;
+}
+
+/** An idle-time background job, with the result of its last batch. */
+export interface BackgroundJobStatus {
+  name: string;
+  description: string;
+  state: 'idle' | 'queued' | 'running';
+  /** When the job was last queued (Unix seconds). */
+  queuedAt: number | null;
+  /** When the last batch finished (Unix seconds). */
+  lastRunAt: number | null;
+  lastProcessed: number;
+  lastError: string | null;
+  /** Batches cancelled by user activity or a sync. */
+  cancellations: number;
+}
+
+export interface ActionCounts {
+  pending: number;
+  failed: number;
+  /** Failed actions a plain retry can be expected to fix. */
+  retryable: number;
+  /** Failed actions that need re-authentication or should be discarded. */
+  needsAttention: number;
+}
+
+/** Outcome of retrying the failed actions. */
+export interface RetryActionsResponse {
+  retried_count: number;
+  success_count: number;
+  failed_count: number;
+}
+
 export interface SyncLogEntry {
   id: number;
   operation: string;
@@ -268,6 +970,39 @@ export interface SyncStatusResponse {
   recentLogs: SyncLogEntry[];
   cacheSizeBytes: number;
   cacheSizeWarning: boolean;
+  offline: boolean;
+}
+
+/** Which MRs an instance syncs, and how often. Null limits use the global sync settings. */
+export interface InstanceSyncConfig {
+  instanceId: number;
+  syncAuthored: boolean;
+  syncReviewing: boolean;
+  syncAssigned: boolean;
+  intervalSecs: number | null;
+  maxMrsPerSync: number | null;
+}
+
+/** One step of the sync self-test. */
+export interface SyncSelfTestStep {
+  name: 'validate_token' | 'list_mrs' | 'fetch_diff' | 'queue';
+  status: 'passed' | 'failed' | 'skipped';
+  durationMs: number;
+  /** What the step found, or why it failed or was skipped. */
+  detail: string;
+  errorKind: 'gitlab_api' | 'network' | 'authentication' | 'other' | null;
+  httpStatus: number | null;
+  endpoint: string | null;
+}
+
+/** Result of `run_sync_selftest`, meant to be attached to bug reports. */
+export interface SyncSelfTestReport {
+  instanceId: number;
+  instanceUrl: string;
+  passed: boolean;
+  steps: SyncSelfTestStep[];
+  startedAt: number;
+  totalMs: number;
 }
 
 // ============================================================================
@@ -279,10 +1014,98 @@ export interface ApprovalResponse {
   localStatus: 'approved' | 'pending';
 }
 
+/** A pre-approve gate condition that still needs attention. */
+export type UnmetCondition =
+  | { type: 'unviewedFiles'; files: string[] }
+  | { type: 'unresolvedOwnThreads'; discussionIds: string[] }
+  | { type: 'checklistIncomplete'; items: string[] };
+
+/** What stands against approving an MR, from cached data. */
+export interface ReviewBlockers {
+  mrId: number;
+  unresolvedDiscussions: number;
+  /** Unresolved threads started by the current user. */
+  ownUnresolvedThreads: number;
+  pipelineStatus: string | null;
+  pipelineFailed: boolean;
+  hasConflicts: boolean;
+}
+
+export interface ApproveResult {
+  /** False when the approval gate or review blockers held the approval back. */
+  approved: boolean;
+  unmet: UnmetCondition[];
+  /** Set when review blockers held the approval back; confirm to approve anyway. */
+  blockers: ReviewBlockers | null;
+}
+
+/** Per-project pre-approve gate configuration. */
+export interface ApprovalGate {
+  instanceId: number;
+  projectId: number;
+  enabled: boolean;
+  requireFilesViewed: boolean;
+  requireOwnThreadsResolved: boolean;
+  checklist: string[];
+}
+
+export interface ChecklistItem {
+  label: string;
+  checked: boolean;
+}
+
 // ============================================================================
 // Settings
 // ============================================================================
 
+/** What vacation mode does with a new review request. */
+export type VacationAction = 'comment' | 'reassign';
+
+/** Vacation mode, part of the sync config (snake_case like the rest of it). */
+export interface VacationConfig {
+  enabled: boolean;
+  /** Unix seconds. */
+  starts_at: number;
+  /** Unix seconds, exclusive. */
+  ends_at: number;
+  action: VacationAction;
+  /** Away message; a default is used when empty. */
+  message: string;
+  /** Username that takes over reviews in reassign mode. */
+  delegate: string | null;
+}
+
+/** Response target for review requests, part of the sync config. */
+export interface ReviewSlaConfig {
+  enabled: boolean;
+  /** Working hours to respond in. */
+  respond_within_hours: number;
+  /** Share of the target (percent) after which an MR is at risk. */
+  at_risk_percent: number;
+  /** Local hour the working day starts. */
+  workday_start_hour: number;
+  /** Local hour the working day ends (exclusive). */
+  workday_end_hour: number;
+  /** ISO weekdays, 1 = Monday. */
+  work_days: number[];
+}
+
+/** MR list view state for one instance. */
+export interface InstanceListView {
+  /** Projects the list is limited to; empty shows all. */
+  projectFilter: number[];
+  /** Order of project groups; unlisted projects follow by name. */
+  projectOrder: number[];
+}
+
+/** MR list view state, shared by the desktop app and the companion. */
+export interface ListViewState {
+  lastInstanceId: number | null;
+  groupByProject: boolean;
+  /** Keyed by instance ID. */
+  instances: Record<string, InstanceListView>;
+}
+
 export type Theme = 'kanagawa-wave' | 'kanagawa-light' | 'loved' | 'custom';
 export type DiffViewMode = 'unified' | 'split';
 
@@ -291,6 +1114,8 @@ export interface Settings {
   theme: Theme;
   uiFont: string;
   displayFont: string;
+  /** Syntax highlight theme for diff tokens; "app" follows the active theme. */
+  highlightTheme: string;
   keyboardShortcuts: Record<string, string>;
   diffViewMode: DiffViewMode;
   collapsePatterns: string[];
@@ -298,6 +1123,90 @@ export interface Settings {
   mrListCondensed: boolean;
   showRecentlyMergedMrs: boolean;
   showDraftMrs: boolean;
+  /** Whether the raw API console may send write (non-GET) requests. */
+  allowApiWrites: boolean;
+  /** Comment translation provider; disabled by default. */
+  translation: TranslationSettings;
+  /** Review assistant model provider; disabled by default. */
+  reviewAssistant: AssistantSettings;
+  /** Whether the database should be encrypted; applied on the next start. */
+  encryptDatabase: boolean;
+  /** Whether offline mode is on: no network requests until turned off. */
+  offlineMode: boolean;
+  /** MR list instance, project filters and grouping. */
+  listViewState: ListViewState;
+  /** OS link handler registration; off by default. */
+  linkHandler: LinkHandlerSettings;
+  /** Command opening a file in an external editor, e.g. `code -g {file}:{line}`; empty when unset. */
+  editorCommand: string;
+}
+
+/** Which GitLab links the app claims from the OS. */
+export interface LinkHandlerSettings {
+  /** Whether the app is registered as the http/https handler. */
+  enabled: boolean;
+  /** Hosts whose MR links open in the app. */
+  hosts: string[];
+  /** Program that receives every other link. */
+  fallbackBrowser: string;
+}
+
+export interface DatabaseEncryptionStatus {
+  /** Whether this build includes SQLCipher. */
+  supported: boolean;
+  /** The setting, which takes effect on the next start. */
+  enabled: boolean;
+  /** Whether the database file is encrypted right now. */
+  encrypted: boolean;
+  /** Whether the key is in the OS keychain. */
+  keyStored: boolean;
+  /** An encrypted database was moved aside because its key was missing. */
+  lockedDatabase: boolean;
+}
+
+export type TranslationProvider = 'libreTranslate';
+
+export interface TranslationSettings {
+  enabled: boolean;
+  provider: TranslationProvider;
+  /** Provider base URL, e.g. `https://translate.example.com`. */
+  endpoint: string;
+  apiKey: string | null;
+  /** Default target language code, e.g. `en`. */
+  targetLang: string;
+}
+
+export interface Translation {
+  text: string;
+  /** Source language reported by the provider. */
+  detectedLang: string | null;
+  /** True when served from the local cache. */
+  cached: boolean;
+}
+
+/** The project markdown references (`#12`, `!3`) resolve against. */
+export interface MarkdownProjectContext {
+  instanceId: number;
+  projectId: number;
+}
+
+export type AssistantProvider = 'openAiCompatible' | 'ollama';
+
+export interface AssistantSettings {
+  enabled: boolean;
+  provider: AssistantProvider;
+  /** OpenAI-compatible base URL including `/v1`, or the Ollama server URL. */
+  endpoint: string;
+  apiKey: string | null;
+  model: string;
+  /** Terms masked before anything is sent, e.g. customer names. */
+  redactTerms: string[];
+}
+
+export interface AssistantReply {
+  text: string;
+  /** Values masked in the prompt before it was sent. */
+  redactions: number;
 }
 
 export type SettingsUpdate = Partial<Settings>;
@@ -353,6 +1262,23 @@ export interface MemoryStats {
   targetBytes: number;
 }
 
+export type MemorySubsystem = 'untracked' | 'syncEngine' | 'diffParsing' | 'fileCache';
+
+export interface SubsystemUsage {
+  subsystem: MemorySubsystem;
+  liveBytes: number;
+  peakBytes: number;
+  allocations: number;
+}
+
+export interface MemoryBreakdown {
+  processMemoryBytes: number;
+  /** False unless the backend was built with the `memory-profiling` feature. */
+  trackingEnabled: boolean;
+  subsystems: SubsystemUsage[];
+  trackedHeapBytes: number;
+}
+
 export interface CacheStats {
   mrCount: number;
   diffFileCount: number;
@@ -361,9 +1287,40 @@ export interface CacheStats {
   dbSizeMb: number;
 }
 
+export interface PoolStats {
+  size: number;
+  idle: number;
+  inUse: number;
+  maxConnections: number;
+}
+
+/** Whether this process owns the database or is a read-only second viewer. */
+export type InstanceMode = 'primary' | 'secondary';
+
+export interface DbPoolStats {
+  writer: PoolStats;
+  reader: PoolStats;
+}
+
+/** IPC latency for one command over the most recent invokes. */
+export interface CommandMetric {
+  command: string;
+  calls: number;
+  errors: number;
+  p50Ms: number;
+  p95Ms: number;
+  maxMs: number;
+  avgPayloadBytes: number;
+  /** Average JSON size of the response; null when the command doesn't measure it. */
+  avgResponseBytes: number | null;
+  /** Average bytes actually sent back, after compression. */
+  avgSentBytes: number | null;
+}
+
 export interface DiagnosticsReport {
   memory: MemoryStats;
   cache: CacheStats;
+  pools: DbPoolStats;
   timestamp: number;
 }
 
@@ -383,6 +1340,68 @@ export interface CachedFilePair {
   headContent: string | null;
 }
 
+export type SymbolKind =
+  | 'function'
+  | 'method'
+  | 'class'
+  | 'struct'
+  | 'enum'
+  | 'trait'
+  | 'interface'
+  | 'type'
+  | 'module';
+
+export interface SymbolChange {
+  /** Qualified name, e.g. `SyncEngine::run_sync` or `Widget.render`. */
+  name: string;
+  kind: SymbolKind;
+  change: 'added' | 'removed' | 'signatureChanged' | 'modified';
+  oldSignature: string | null;
+  newSignature: string | null;
+}
+
+export interface SemanticSummary {
+  /** Null when the file's language isn't supported. */
+  language: string | null;
+  changes: SymbolChange[];
+  /** One-line description, e.g. "modifies `run_sync`, adds `flush_actions`". */
+  summary: string;
+}
+
+/** One line of an accessible diff; removed lines carry their old line number. */
+export interface AccessibleLine {
+  kind: 'added' | 'removed' | 'unchanged';
+  lineNumber: number;
+  /** Spoken prefix, e.g. "added line 12". */
+  label: string;
+  content: string;
+}
+
+export interface AccessibleHunk {
+  /** e.g. "Change 1 of 3, lines 10 to 14: 2 lines added, 1 line removed." */
+  summary: string;
+  lines: AccessibleLine[];
+}
+
+/** Linearized, labeled diff of one file for screen readers. */
+export interface AccessibleDiff {
+  filePath: string;
+  summary: string;
+  hunks: AccessibleHunk[];
+}
+
+/** One open MR in the quick switcher index, most recently updated first. */
+export interface QuickSwitchEntry {
+  mrId: number;
+  instanceId: number;
+  iid: number;
+  title: string;
+  /** Project path, e.g. `group/project`. */
+  project: string;
+  /** 1 for an MR updated just now, halving every week since. */
+  score: number;
+}
+
 // ============================================================================
 // Issues
 // ============================================================================
@@ -474,12 +1493,29 @@ export interface IssueAssigneeCandidate {
 // Pipeline Dashboard
 // ============================================================================
 
+/** A project whose notifications and badge contributions are muted. */
+export interface MutedProject {
+  instanceId: number;
+  projectId: number;
+  /** Null when the project isn't cached. */
+  nameWithNamespace: string | null;
+  /** Unix seconds; null mutes until unmuted. */
+  mutedUntil: number | null;
+  mutedAt: number;
+}
+
+/** When a pinned pipeline project's pipelines notify. */
+export type PipelineNotifyRule = 'failures' | 'completion' | 'default_branch';
+
 export interface PipelineProject {
   projectId: number;
   instanceId: number;
   pinned: boolean;
   lastVisitedAt: string | null;
   sortOrder: number | null;
+  notifyRule: PipelineNotifyRule;
+  /** Notify when a manual job starts waiting on the default branch. */
+  notifyManualGates: boolean;
   name: string;
   nameWithNamespace: string;
   pathWithNamespace: string;
@@ -500,6 +1536,81 @@ export interface PipelineStatus {
   duration: number | null;
 }
 
+/** A manual job waiting to be played, from the approval queue. */
+export interface PendingManualJob {
+  instanceId: number;
+  projectId: number;
+  projectName: string;
+  pipelineId: number;
+  refName: string | null;
+  jobId: number;
+  jobName: string;
+  stage: string;
+  webUrl: string;
+  createdAt: string;
+}
+
+/** A job to play from the approval queue. */
+export interface JobRef {
+  instanceId: number;
+  projectId: number;
+  jobId: number;
+}
+
+/** Outcome of playing one job; `error` is null when it started. */
+export interface PlayJobResult extends JobRef {
+  error: string | null;
+}
+
+export interface PipelineSchedule {
+  id: number;
+  projectId: number;
+  description: string;
+  refName: string;
+  cron: string;
+  cronTimezone: string;
+  /** ISO timestamp of the next scheduled run; null when inactive. */
+  nextRunAt: string | null;
+  active: boolean;
+  ownerUsername: string | null;
+}
+
+/** Asset link attached to a release. */
+export interface ReleaseLink {
+  name: string;
+  url: string;
+  /** `package`, `image`, `runbook` or `other`. */
+  linkType: string | null;
+}
+
+export interface ProjectRelease {
+  projectId: number;
+  tagName: string;
+  name: string | null;
+  /** Release notes / changelog (Markdown). */
+  description: string | null;
+  createdAt: string;
+  releasedAt: string | null;
+  upcomingRelease: boolean;
+  authorUsername: string | null;
+  commitSha: string | null;
+  webUrl: string | null;
+  links: ReleaseLink[];
+}
+
+export interface ProjectTag {
+  projectId: number;
+  name: string;
+  /** Annotation message; null for lightweight tags. */
+  message: string | null;
+  commitSha: string | null;
+  commitTitle: string | null;
+  committedAt: string | null;
+  protected: boolean;
+  /** Whether a release was created for this tag. */
+  hasRelease: boolean;
+}
+
 export interface ProjectSearchResult {
   id: number;
   name: string;
@@ -540,6 +1651,14 @@ export interface PipelineJob {
   downstreamPipeline: DownstreamPipeline | null;
 }
 
+/** Part of a job log. Earlier output starts before `offset`. */
+export interface JobTrace {
+  content: string;
+  /** Byte offset of `content` in the whole log; 0 when nothing is before it. */
+  offset: number;
+  totalBytes: number;
+}
+
 export interface DownstreamPipeline {
   id: number;
   /** Null on GitLab versions that omit project_id; drill-down is disabled then. */
@@ -549,6 +1668,29 @@ export interface DownstreamPipeline {
   webUrl: string;
 }
 
+/** A stage column of a pipeline graph. */
+export interface GraphStage {
+  name: string;
+  /** Aggregate status of the stage's jobs. */
+  status: PipelineJobStatus;
+  jobIds: number[];
+}
+
+/** `needs`: `to` lists `from` in its `needs:`; `stage`: `to` waits for the previous stage. */
+export interface GraphEdge {
+  from: number;
+  to: number;
+  kind: 'needs' | 'stage';
+}
+
+export interface PipelineGraph {
+  stages: GraphStage[];
+  jobs: PipelineJob[];
+  edges: GraphEdge[];
+  /** False when `needs:` couldn't be loaded and edges follow stages only. */
+  needsKnown: boolean;
+}
+
 // ============================================================================
 // Notification Settings
 // ============================================================================
@@ -586,11 +1728,21 @@ export interface AuthorizedDevice {
   createdAt: string;
 }
 
+/** Where the companion server listens. */
+export type CompanionBindMode = 'lan' | 'loopback' | 'unixSocket';
+
 export interface CompanionServerSettings {
   enabled: boolean;
   port: number;
   pin: string;
   authorizedDevices: AuthorizedDevice[];
+  bindMode: CompanionBindMode;
+  /** Socket file path when `bindMode` is `unixSocket`. */
+  socketPath: string | null;
+  /** Path prefix when served behind a reverse proxy, e.g. `/gitlab`; empty for the root. */
+  basePath: string;
+  /** Externally reachable URL used in the pairing QR code. */
+  publicUrl: string | null;
 }
 
 export interface CompanionStatus {
@@ -598,6 +1750,15 @@ export interface CompanionStatus {
   connectedDevices: number;
 }
 
+/** Payload of the `companion-pairing-requested` event. */
+export interface CompanionPairingRequest {
+  /** 6-digit code also shown on the companion device. */
+  code: string;
+  deviceName: string;
+  /** Unix timestamp (seconds) after which the code can't be confirmed. */
+  expiresAt: number;
+}
+
 // ============================================================================
 // Deep Link
 // ============================================================================
@@ -607,6 +1768,267 @@ export interface ResolvedMr {
   state: MRState;
 }
 
+// ============================================================================
+// Post-merge cleanup
+// ============================================================================
+
+export type PostMergeTask = 'delete_source_branch' | 'close_linked_issues' | 'trigger_pipeline';
+
+export interface PostMergeTaskResult {
+  task: PostMergeTask;
+  status: 'done' | 'skipped' | 'partial' | 'failed';
+  message: string;
+}
+
+// ============================================================================
+// Data Retention
+// ============================================================================
+
+/** Per-instance limits on what the local cache keeps. */
+export interface RetentionPolicy {
+  instanceId: number;
+  /** Cache full file content for instant file viewing. */
+  cacheFileContent: boolean;
+  /** Drop MR comments and issue notes older than this many days (null = keep). */
+  commentRetentionDays: number | null;
+  /** How long merged/closed MRs stay cached. */
+  closedMrRetentionHours: number;
+}
+
+/** What applying a retention policy removed. */
+export interface RetentionSweep {
+  fileVersionsRemoved: number;
+  /** Blame, commit diffs, READMEs and job logs. */
+  contentCachesRemoved: number;
+  commentsRemoved: number;
+  renderedMarkdownRemoved: number;
+}
+
+export interface DataCategory {
+  category:
+    | 'merge_requests'
+    | 'diffs'
+    | 'file_content'
+    | 'file_blame'
+    | 'commit_diffs'
+    | 'readmes'
+    | 'job_traces'
+    | 'rendered_markdown'
+    | 'comments'
+    | 'issues'
+    | 'issue_notes'
+    | 'avatars'
+    | 'pending_actions';
+  rowCount: number;
+  sizeBytes: number | null;
+  /** Unix timestamp of the oldest item, when items carry one. */
+  oldestAt: number | null;
+}
+
+/** Cached data held for one instance, alongside its retention policy. */
+export interface InstanceDataAudit {
+  instanceId: number;
+  instanceUrl: string;
+  policy: RetentionPolicy;
+  categories: DataCategory[];
+}
+
+/** Local event an automation rule reacts to. */
+export type AutomationTrigger =
+  | 'my_mr_approved'
+  | 'my_mr_pipeline_failed'
+  | 'my_mr_merged'
+  | 'review_requested';
+
+/** What a rule does: POST to a webhook URL or run a shell command. */
+export type AutomationAction =
+  | { kind: 'webhook'; target: string }
+  | { kind: 'command'; target: string };
+
+export interface AutomationRule {
+  id: number;
+  name: string;
+  trigger: AutomationTrigger;
+  action: AutomationAction;
+  enabled: boolean;
+  createdAt: number;
+}
+
+/** A rule as saved from the settings form; `id` is set when editing. */
+export interface AutomationRuleInput {
+  id: number | null;
+  name: string;
+  trigger: AutomationTrigger;
+  action: AutomationAction;
+  enabled: boolean;
+}
+
+/** One logged execution of a rule. */
+export interface AutomationRun {
+  id: number;
+  ruleId: number;
+  trigger: AutomationTrigger;
+  mrId: number | null;
+  isTest: boolean;
+  success: boolean;
+  /** Response status or command output (truncated). */
+  detail: string | null;
+  durationMs: number;
+  ranAt: number;
+}
+
+/** A user-scripted badge rule for the MR lists. */
+export interface BadgeRule {
+  id: number;
+  name: string;
+  /** Badge text when the script returns `true`. */
+  label: string;
+  /** `#rgb` or `#rrggbb`; null uses the default badge color. */
+  color: string | null;
+  /** Rhai expression evaluated per MR. */
+  script: string;
+  enabled: boolean;
+  timeLimitMs: number;
+  /** Last error or timeout while evaluating; cleared on save. */
+  lastError: string | null;
+}
+
+/** A badge rule as saved from settings; the whole list is saved at once. */
+export interface BadgeRuleInput {
+  name: string;
+  label: string;
+  color: string | null;
+  script: string;
+  enabled: boolean;
+  timeLimitMs: number | null;
+}
+
+/** A badge a rule put on an MR. */
+export interface CustomBadge {
+  ruleId: number;
+  label: string;
+  color: string | null;
+}
+
+/** What a cache archive export or import covered. */
+export interface CacheArchiveSummary {
+  instances: number;
+  mergeRequests: number;
+  diffs: number;
+  files: number;
+  /** Archived instance URLs with no matching instance here (import only). */
+  skippedInstances: string[];
+}
+
+/** Time range of a statistics export, in Unix seconds; open ends are unbounded. */
+export interface StatsRange {
+  from: number | null;
+  to: number | null;
+}
+
+export type StatisticsExportFormat = 'csv' | 'json';
+
+/** Record counts written by `exportStatistics`. */
+export interface StatisticsExportSummary {
+  reviewEvents: number;
+  syncMetrics: number;
+  pipelines: number;
+}
+
+// ============================================================================
+// Review Stats
+// ============================================================================
+
+export interface ReviewEvent {
+  instanceId: number;
+  projectId: number;
+  mrIid: number;
+  kind: 'reviewRequested' | 'commented' | 'approved';
+  occurredAt: number;
+}
+
+export interface WeeklyCount {
+  /** Monday of the week, `YYYY-MM-DD`. */
+  weekStart: string;
+  comments: number;
+}
+
+/** Opt-in review stats, computed from the local activity log. */
+export interface ReviewStreaks {
+  enabled: boolean;
+  trackingSince: number | null;
+  currentStreakDays: number;
+  longestStreakDays: number;
+  /** Days with at least one comment or approval. */
+  reviewDays: number;
+  /** The last eight weeks, oldest first. */
+  commentsPerWeek: WeeklyCount[];
+  /** Median seconds from review request to first comment or approval. */
+  medianResponseSecs: number | null;
+}
+
+export interface ReviewStatsExport {
+  exportedAt: number;
+  stats: ReviewStreaks;
+  events: ReviewEvent[];
+}
+
+/** Where an MR awaiting my review stands against the review SLA. */
+export type SlaState = 'on_track' | 'at_risk' | 'breached';
+
+export interface ReviewSlaStatus {
+  mrId: number;
+  /** When the clock started: first seen as reviewer, or re-requested. */
+  requestedAt: number;
+  /** Working seconds since `requestedAt`. */
+  elapsedSecs: number;
+  dueAt: number;
+  state: SlaState;
+}
+
+// ============================================================================
+// Change Log
+// ============================================================================
+
+/** One change to the local cache, as recorded by the sync engine. */
+export interface ChangeEvent {
+  seq: number;
+  entity: 'merge_request' | 'diff' | 'comments';
+  /** MR id for all entities. */
+  entityId: number;
+  instanceId: number;
+  op: 'upsert' | 'delete';
+  /** Changed MR fields with their new values (empty for diff/comments). */
+  fields: Partial<MergeRequest>;
+  changedAt: number;
+}
+
+export interface ChangesSince {
+  changes: ChangeEvent[];
+  cursor: number;
+  hasMore: boolean;
+  /** The cursor was too old to catch up from; reload everything. */
+  reset: boolean;
+}
+
+/** An MR created or changed since a cursor. */
+export interface MrFieldChanges {
+  id: number;
+  instanceId: number;
+  /** The whole row for created MRs, only the changed fields otherwise. */
+  fields: Partial<MergeRequest>;
+}
+
+/** Net MR changes since a cursor, one entry per MR. */
+export interface MrDelta {
+  created: MrFieldChanges[];
+  updated: MrFieldChanges[];
+  removed: number[];
+  cursor: number;
+  /** The cursor was too old to catch up from; reload everything. */
+  reset: boolean;
+}
+
 // ============================================================================
 // Error Types
 // ============================================================================
@@ -0,0 +1,78 @@
+/**
+ * Allowlist-based sanitizer for HTML rendered by GitLab (e.g. project READMEs).
+ *
+ * DOMParser yields an inert document: scripts don't run and resources don't
+ * load until nodes are attached, so disallowed elements and attributes can be
+ * stripped before the markup reaches the page.
+ */
+
+const ALLOWED_TAGS = new Set([
+  'a', 'abbr', 'b', 'blockquote', 'br', 'code', 'dd', 'del', 'details', 'div', 'dl', 'dt',
+  'em', 'h1', 'h2', 'h3', 'h4', 'h5', 'h6', 'hr', 'i', 'img', 'input', 'ins', 'kbd', 'li',
+  'ol', 'p', 'pre', 'q', 's', 'samp', 'span', 'strong', 'sub', 'summary', 'sup', 'table',
+  'tbody', 'td', 'tfoot', 'th', 'thead', 'tr', 'ul',
+]);
+
+/** Elements removed together with everything inside them. */
+const DROPPED_TAGS = new Set([
+  'script', 'style', 'iframe', 'object', 'embed', 'form', 'button', 'textarea', 'select',
+  'template', 'noscript', 'svg', 'math', 'link', 'meta', 'base',
+]);
+
+const ALLOWED_ATTRS = new Set([
+  'href', 'src', 'alt', 'title', 'width', 'height', 'align', 'colspan', 'rowspan', 'start',
+  'type', 'checked', 'disabled', 'open', 'lang', 'dir', 'class', 'id',
+]);
+
+/** http(s), mailto, in-page anchors and relative paths; no `javascript:` and friends. */
+const SAFE_URL = /^(https?:|mailto:|#|[^:]*$)/i;
+
+function cleanElement(el: Element): void {
+  // GitLab lazy-loads images: the real URL is in data-src, src is a placeholder.
+  if (el.tagName === 'IMG' && el.hasAttribute('data-src')) {
+    el.setAttribute('src', el.getAttribute('data-src') ?? '');
+  }
+
+  for (const attr of Array.from(el.attributes)) {
+    const name = attr.name.toLowerCase();
+    const value = attr.value.trim();
+    const allowed =
+      ALLOWED_ATTRS.has(name) &&
+      ((name !== 'href' && name !== 'src') || SAFE_URL.test(value)) &&
+      // Heading anchors only; arbitrary ids could clobber globals.
+      (name !== 'id' || value.startsWith('user-content-'));
+    if (!allowed) el.removeAttribute(attr.name);
+  }
+
+  if (el.tagName === 'A') {
+    el.setAttribute('rel', 'noopener noreferrer');
+  }
+  // Task list checkboxes are the only inputs, and they're display-only.
+  if (el.tagName === 'INPUT') {
+    el.setAttribute('disabled', '');
+  }
+}
+
+function cleanChildren(parent: Element): void {
+  for (const el of Array.from(parent.children)) {
+    const tag = el.tagName.toLowerCase();
+    if (DROPPED_TAGS.has(tag) || (tag === 'input' && el.getAttribute('type') !== 'checkbox')) {
+      el.remove();
+      continue;
+    }
+    cleanChildren(el);
+    if (ALLOWED_TAGS.has(tag)) {
+      cleanElement(el);
+    } else {
+      // Unknown wrapper: keep its (already cleaned) content.
+      el.replaceWith(...Array.from(el.childNodes));
+    }
+  }
+}
+
+/** Strip everything but basic formatting, links and images from `html`. */
+export function sanitizeHtml(html: string): string {
+  const doc = new DOMParser().parseFromString(html, 'text/html');
+  cleanChildren(doc.body);
+  return doc.body.innerHTML;
+}
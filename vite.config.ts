@@ -16,6 +16,24 @@ export default defineConfig(async () => ({
     exclude: ["node_modules", "e2e", "src-tauri"],
   },
 
+  // The companion server may be proxied under a base path that is only known
+  // at runtime. It rewrites index.html and injects window.__COMPANION_BASE_PATH__,
+  // so JS-referenced assets and lazy chunks resolve against that global and CSS
+  // url()s stay relative to the stylesheet.
+  experimental: {
+    renderBuiltUrl(filename: string, { hostType }: { hostType: "js" | "css" | "html" }) {
+      if (hostType === "js") {
+        return {
+          runtime: `(globalThis.__COMPANION_BASE_PATH__ || "") + ${JSON.stringify("/" + filename)}`,
+        };
+      }
+      if (hostType === "css") {
+        return { relative: true };
+      }
+      return undefined;
+    },
+  },
+
   // Use ES module format for workers so @pierre/diffs worker (which uses dynamic imports) can be bundled
   worker: {
     format: "es",